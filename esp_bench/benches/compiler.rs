@@ -0,0 +1,55 @@
+//! Compiler benchmarks: lexing + parsing alone, and the full pipeline
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use esp_bench::synthetic::SyntheticPolicy;
+use esp_bench::{compile_file, write_policy};
+
+const SIZES: [usize; 2] = [100, 1000];
+
+fn policy(criteria: usize) -> SyntheticPolicy {
+    SyntheticPolicy::new()
+        .with_criteria(criteria)
+        .with_objects(criteria.min(500))
+        .with_states(10)
+        .with_fields_per_state(3)
+}
+
+fn lex_and_parse(c: &mut Criterion) {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let mut group = c.benchmark_group("compiler/lex_parse");
+    for size in SIZES {
+        let path = write_policy(dir.path(), &format!("lex_{}", size), &policy(size).build())
+            .expect("write policy");
+        let file = esp_compiler::file_processor::process_file(&path.display().to_string())
+            .expect("read policy");
+        group.bench_with_input(BenchmarkId::from_parameter(size), &file, |b, file| {
+            b.iter(|| {
+                let tokens =
+                    esp_compiler::lexical::tokenize_file_result(file.clone()).expect("lex");
+                esp_compiler::syntax::parse_esp_file(tokens).expect("parse")
+            })
+        });
+    }
+    group.finish();
+}
+
+fn full_pipeline(c: &mut Criterion) {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let mut group = c.benchmark_group("compiler/pipeline");
+    group.sample_size(20);
+    for size in SIZES {
+        let path = write_policy(
+            dir.path(),
+            &format!("pipeline_{}", size),
+            &policy(size).build(),
+        )
+        .expect("write policy");
+        group.bench_with_input(BenchmarkId::from_parameter(size), &path, |b, path| {
+            b.iter(|| compile_file(path).expect("compile"))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, lex_and_parse, full_pipeline);
+criterion_main!(benches);
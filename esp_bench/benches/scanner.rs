@@ -0,0 +1,124 @@
+//! Scanner benchmarks: AST ingestion, resolution and execution against the
+//! in-memory mock registry
+
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use esp_bench::mock::mock_registry;
+use esp_bench::synthetic::SyntheticPolicy;
+use esp_bench::{compile_file, execute, prepare, resolve, write_policy};
+use esp_compiler::grammar::ast::{AstDocument, EspFile};
+use esp_scanner_base::resolution::parse_ast_document;
+use esp_scanner_base::resolution::{HostScanContext, PolicyCache};
+use esp_scanner_base::results::ScanProvenance;
+use std::sync::Arc;
+
+fn compiled(policy: &SyntheticPolicy, name: &str) -> EspFile {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let path = write_policy(dir.path(), name, &policy.build()).expect("write policy");
+    compile_file(&path).expect("compile")
+}
+
+fn ast_json_ingestion(c: &mut Criterion) {
+    let ast = compiled(
+        &SyntheticPolicy::new()
+            .with_criteria(1000)
+            .with_objects(500)
+            .with_states(10)
+            .with_fields_per_state(3),
+        "ingest",
+    );
+    let json = AstDocument::new(ast).to_json().expect("serialize AST");
+    c.bench_function("scanner/ast_json_ingestion/1000", |b| {
+        b.iter(|| parse_ast_document(&json).expect("decode AST"))
+    });
+}
+
+fn wide_graph_resolution(c: &mut Criterion) {
+    // Every object depends on its own RUN variable, all derived from one root
+    let ast = compiled(
+        &SyntheticPolicy::new()
+            .with_criteria(1000)
+            .with_objects(1000)
+            .with_states(10),
+        "wide",
+    );
+    let mut group = c.benchmark_group("scanner/resolve_wide_graph");
+    group.sample_size(20);
+    group.bench_function("1000", |b| b.iter(|| resolve(&ast).expect("resolve")));
+    group.finish();
+
+    // The same policy prepared once; each scan only finalizes a copy
+    let prepared = prepare(&ast).expect("prepare");
+    let host = HostScanContext::new();
+    c.bench_function("scanner/finalize_prepared/1000", |b| {
+        b.iter(|| prepared.finalize_for_host(&host))
+    });
+}
+
+fn execution(c: &mut Criterion) {
+    let ast = compiled(
+        &SyntheticPolicy::new()
+            .with_criteria(1000)
+            .with_objects(100)
+            .with_states(10)
+            .with_fields_per_state(3),
+        "execute",
+    );
+    let context = resolve(&ast).expect("resolve");
+    let registry = Arc::new(mock_registry().expect("mock registry"));
+    let mut group = c.benchmark_group("scanner/execute_mock");
+    group.sample_size(20);
+    group.bench_function("1000", |b| {
+        b.iter_batched(
+            || context.clone(),
+            |context| execute(context, registry.clone()).expect("execute"),
+            BatchSize::LargeInput,
+        )
+    });
+    group.finish();
+}
+
+fn policy_cache(c: &mut Criterion) {
+    // A cache hit replaces compiling and resolving with reading one entry
+    let dir = tempfile::tempdir().expect("tempdir");
+    let source = SyntheticPolicy::new()
+        .with_criteria(1000)
+        .with_objects(500)
+        .with_states(10)
+        .with_fields_per_state(3)
+        .build();
+    let path = write_policy(dir.path(), "cached", &source).expect("write policy");
+    let ast = compile_file(&path).expect("compile");
+    let cache = PolicyCache::new(dir.path().join("cache"), "bench");
+    cache
+        .store(
+            source.as_bytes(),
+            &[],
+            &ScanProvenance::for_policy(source.as_bytes(), &ast),
+            &resolve(&ast).expect("resolve"),
+        )
+        .expect("store cache entry");
+
+    let mut group = c.benchmark_group("scanner/policy_cache");
+    group.sample_size(20);
+    group.bench_function("compile_resolve/1000", |b| {
+        b.iter(|| resolve(&compile_file(&path).expect("compile")).expect("resolve"))
+    });
+    group.bench_function("cache_hit/1000", |b| {
+        b.iter(|| {
+            cache
+                .load(source.as_bytes())
+                .expect("load cache entry")
+                .expect("cache hit")
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    ast_json_ingestion,
+    wide_graph_resolution,
+    execution,
+    policy_cache
+);
+criterion_main!(benches);
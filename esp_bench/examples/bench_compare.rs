@@ -0,0 +1,216 @@
+//! Compare the latest `cargo bench` run against a saved baseline
+//!
+//! ```text
+//! cargo bench -p esp_bench
+//! cargo run --release -p esp_bench --example bench_compare
+//! cargo run --release -p esp_bench --example bench_compare -- --save
+//! ```
+//!
+//! Reads criterion's `new/estimates.json` for every benchmark under
+//! `--criterion-dir` and compares mean times with `--baseline`. Exits 1 when
+//! any benchmark is slower than its baseline by more than `--threshold`
+//! percent. `--save` rewrites the baseline from the latest run instead.
+//!
+//! Baselines are machine-specific: regenerate on the machine you compare on.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+const DEFAULT_BASELINE: &str = "esp_bench/baseline.json";
+const DEFAULT_CRITERION_DIR: &str = "target/criterion";
+const DEFAULT_THRESHOLD_PERCENT: f64 = 20.0;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Baseline {
+    /// Allowed slowdown before a benchmark counts as a regression
+    threshold_percent: f64,
+    /// Mean time per iteration in nanoseconds, by benchmark id
+    benchmarks: BTreeMap<String, f64>,
+}
+
+struct Options {
+    baseline: PathBuf,
+    criterion_dir: PathBuf,
+    threshold: Option<f64>,
+    save: bool,
+}
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(e) => {
+            eprintln!("bench_compare: {}", e);
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn run() -> Result<bool, String> {
+    let options = parse_args(std::env::args().skip(1))?;
+    let latest = read_criterion_results(&options.criterion_dir)?;
+    if latest.is_empty() {
+        return Err(format!(
+            "no benchmark results under {}; run `cargo bench -p esp_bench` first",
+            options.criterion_dir.display()
+        ));
+    }
+
+    if options.save {
+        let baseline = Baseline {
+            threshold_percent: options.threshold.unwrap_or(DEFAULT_THRESHOLD_PERCENT),
+            benchmarks: latest
+                .into_iter()
+                .map(|(id, mean)| (id, mean.round()))
+                .collect(),
+        };
+        let json = serde_json::to_string_pretty(&baseline).map_err(|e| e.to_string())?;
+        std::fs::write(&options.baseline, json + "\n")
+            .map_err(|e| format!("cannot write {}: {}", options.baseline.display(), e))?;
+        println!(
+            "Saved {} benchmarks to {}",
+            baseline.benchmarks.len(),
+            options.baseline.display()
+        );
+        return Ok(true);
+    }
+
+    let baseline: Baseline = std::fs::read_to_string(&options.baseline)
+        .map_err(|e| format!("cannot read {}: {}", options.baseline.display(), e))
+        .and_then(|json| serde_json::from_str(&json).map_err(|e| e.to_string()))?;
+    let threshold = options.threshold.unwrap_or(baseline.threshold_percent);
+
+    let mut regressions = 0;
+    println!(
+        "{:<45} {:>12} {:>12} {:>9}",
+        "benchmark", "baseline", "latest", "change"
+    );
+    for (id, &before) in &baseline.benchmarks {
+        let Some(&after) = latest.get(id) else {
+            println!(
+                "{:<45} {:>12} {:>12} {:>9}",
+                id,
+                format_ns(before),
+                "-",
+                "missing"
+            );
+            continue;
+        };
+        let change = (after - before) / before * 100.0;
+        let regressed = change > threshold;
+        if regressed {
+            regressions += 1;
+        }
+        println!(
+            "{:<45} {:>12} {:>12} {:>+8.1}%{}",
+            id,
+            format_ns(before),
+            format_ns(after),
+            change,
+            if regressed { "  REGRESSION" } else { "" }
+        );
+    }
+    for id in latest
+        .keys()
+        .filter(|id| !baseline.benchmarks.contains_key(*id))
+    {
+        println!(
+            "{:<45} {:>12} {:>12} {:>9}",
+            id,
+            "-",
+            format_ns(latest[id]),
+            "new"
+        );
+    }
+
+    if regressions > 0 {
+        println!(
+            "\n{} benchmark(s) regressed by more than {}%",
+            regressions, threshold
+        );
+        return Ok(false);
+    }
+    println!("\nNo regressions beyond {}%", threshold);
+    Ok(true)
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut options = Options {
+        baseline: PathBuf::from(DEFAULT_BASELINE),
+        criterion_dir: PathBuf::from(DEFAULT_CRITERION_DIR),
+        threshold: None,
+        save: false,
+    };
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| args.next().ok_or_else(|| format!("{} needs a value", name));
+        match arg.as_str() {
+            "--baseline" => options.baseline = PathBuf::from(value("--baseline")?),
+            "--criterion-dir" => options.criterion_dir = PathBuf::from(value("--criterion-dir")?),
+            "--threshold" => {
+                let raw = value("--threshold")?;
+                let percent = raw
+                    .parse::<f64>()
+                    .map_err(|_| format!("invalid --threshold '{}'", raw))?;
+                options.threshold = Some(percent);
+            }
+            "--save" => options.save = true,
+            other => return Err(format!("unknown argument '{}'", other)),
+        }
+    }
+    Ok(options)
+}
+
+/// Mean time of every benchmark, keyed by criterion's full id
+fn read_criterion_results(dir: &Path) -> Result<BTreeMap<String, f64>, String> {
+    let mut results = BTreeMap::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(current) = pending.pop() {
+        let Ok(entries) = std::fs::read_dir(&current) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if !path.is_dir() {
+                continue;
+            }
+            if path.file_name().is_some_and(|name| name == "new") {
+                if let Some((id, mean)) = read_estimate(&path)? {
+                    results.insert(id, mean);
+                }
+            } else if path.file_name().is_some_and(|name| name != "report") {
+                pending.push(path);
+            }
+        }
+    }
+    Ok(results)
+}
+
+fn read_estimate(new_dir: &Path) -> Result<Option<(String, f64)>, String> {
+    let read_json = |name: &str| -> Result<Option<serde_json::Value>, String> {
+        match std::fs::read_to_string(new_dir.join(name)) {
+            Ok(json) => serde_json::from_str(&json)
+                .map(Some)
+                .map_err(|e| format!("{}: {}", new_dir.join(name).display(), e)),
+            Err(_) => Ok(None),
+        }
+    };
+    let (Some(benchmark), Some(estimates)) =
+        (read_json("benchmark.json")?, read_json("estimates.json")?)
+    else {
+        return Ok(None);
+    };
+    let id = benchmark["full_id"].as_str().map(str::to_string);
+    let mean = estimates["mean"]["point_estimate"].as_f64();
+    Ok(id.zip(mean))
+}
+
+fn format_ns(ns: f64) -> String {
+    match ns {
+        n if n >= 1e9 => format!("{:.2} s", n / 1e9),
+        n if n >= 1e6 => format!("{:.2} ms", n / 1e6),
+        n if n >= 1e3 => format!("{:.2} µs", n / 1e3),
+        n => format!("{:.0} ns", n),
+    }
+}
@@ -0,0 +1,116 @@
+//! # ESP Bench
+//!
+//! Shared support for benchmarks, fuzzers and memory tests:
+//!
+//! - [`synthetic`] builds valid policies of any size
+//! - [`mock`] provides in-memory strategies so scans never touch the host
+//!
+//! The criterion benchmarks live in `benches/`; `bench-compare` checks a run
+//! against the committed `baseline.json`.
+
+pub mod mock;
+pub mod synthetic;
+
+use esp_compiler::grammar::ast::EspFile;
+use esp_scanner_base::execution::ExecutionEngine;
+use esp_scanner_base::resolution::engine::ResolutionEngine;
+use esp_scanner_base::resolution::{resolution_context_from_ast, ResolvedPolicy};
+use esp_scanner_base::results::ScanResult;
+use esp_scanner_base::strategies::CtnStrategyRegistry;
+use esp_scanner_base::types::execution_context::ExecutionContext;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Write ESP source to `dir/name.esp`
+pub fn write_policy(dir: &Path, name: &str, source: &str) -> std::io::Result<PathBuf> {
+    let path = dir.join(format!("{}.esp", name));
+    std::fs::write(&path, source)?;
+    Ok(path)
+}
+
+/// Run the full compiler pipeline on a policy file
+pub fn compile_file(path: &Path) -> Result<EspFile, String> {
+    esp_compiler::pipeline::process_file(&path.display().to_string())
+        .map(|result| result.ast)
+        .map_err(|e| e.to_string())
+}
+
+/// Resolve an AST into an execution context
+pub fn resolve(ast: &EspFile) -> Result<ExecutionContext, String> {
+    let mut context = resolution_context_from_ast(ast);
+    ResolutionEngine::new()
+        .resolve_context(&mut context)
+        .map_err(|e| e.to_string())
+}
+
+/// Resolve an AST once for repeated scans
+pub fn prepare(ast: &EspFile) -> Result<ResolvedPolicy, String> {
+    ResolvedPolicy::from_ast(ast).map_err(|e| e.to_string())
+}
+
+/// Execute a resolved context against a registry
+pub fn execute(
+    context: ExecutionContext,
+    registry: Arc<CtnStrategyRegistry>,
+) -> Result<ScanResult, String> {
+    ExecutionEngine::new(context, registry)
+        .execute()
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::synthetic::SyntheticPolicy;
+
+    #[test]
+    fn test_synthetic_policy_scans_clean_against_mock_registry() {
+        let dir = tempfile::tempdir().unwrap();
+        let policy = SyntheticPolicy::new()
+            .with_criteria(25)
+            .with_objects(5)
+            .with_states(3)
+            .with_fields_per_state(2);
+        let path = write_policy(dir.path(), "synthetic", &policy.build()).unwrap();
+
+        let ast = compile_file(&path).unwrap();
+        let context = resolve(&ast).unwrap();
+        let registry = Arc::new(mock::mock_registry().unwrap());
+        let result = execute(context, registry).unwrap();
+
+        assert_eq!(result.results.check.total_criteria, 25);
+        assert_eq!(result.results.check.passed_criteria, 25);
+    }
+
+    #[test]
+    fn test_prepared_policy_scans_like_single_pass_resolution() {
+        use esp_scanner_base::execution::ScanClock;
+        use esp_scanner_base::resolution::HostScanContext;
+
+        let dir = tempfile::tempdir().unwrap();
+        let policy = SyntheticPolicy::new().with_criteria(40).with_objects(8);
+        let path = write_policy(dir.path(), "prepared", &policy.build()).unwrap();
+        let ast = compile_file(&path).unwrap();
+        let registry = Arc::new(mock::mock_registry().unwrap());
+        let clock = ScanClock::parse_fixed("2026-03-01T12:00:00Z").unwrap();
+
+        let single_pass = execute(resolve(&ast).unwrap().with_clock(clock), registry.clone());
+        let prepared = prepare(&ast).unwrap();
+        let host = HostScanContext::new().with_clock(clock);
+        let first = execute(prepared.finalize_for_host(&host), registry.clone());
+        let second = execute(prepared.finalize_for_host(&host), registry);
+
+        let summary = |result: Result<ScanResult, String>| {
+            let result = result.unwrap();
+            serde_json::json!({
+                "scan_id": result.scan_id,
+                "check": result.results.check,
+                "findings": result.results.findings,
+                "plan": result.plan,
+            })
+        };
+        let expected = summary(single_pass);
+        assert_eq!(summary(first), expected);
+        assert_eq!(summary(second), expected);
+    }
+}
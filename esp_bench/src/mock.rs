@@ -0,0 +1,215 @@
+//! # In-Memory Strategies
+//!
+//! A collector and executor for the `mock_file` CTN type that never touch the
+//! host. Every object collects the same string field, so scan cost measures
+//! the engine rather than the system being scanned.
+
+use esp_scanner_base::execution::{
+    evaluate_existence_check, evaluate_item_check, evaluate_state_operator, BehaviorHints,
+    ComparisonExt,
+};
+use esp_scanner_base::strategies::{
+    CollectedData, CollectionError, ComplianceStatus, CtnContract, CtnDataCollector,
+    CtnExecutionError, CtnExecutionResult, CtnExecutor, CtnStrategyRegistry, FieldOutcome,
+    FieldValidationResult, ObjectFieldSpec, StateFieldSpec, StateValidationResult, StrategyError,
+};
+use esp_scanner_base::types::common::{DataType, Operation, ResolvedValue};
+use esp_scanner_base::types::execution_context::{ExecutableCriterion, ExecutableObject};
+use std::collections::HashMap;
+
+/// CTN type served by the mock strategies
+pub const MOCK_CTN_TYPE: &str = "mock_file";
+
+/// State field every mock object collects
+pub const MOCK_FIELD: &str = "value";
+
+/// Value of [`MOCK_FIELD`] on every collected object
+pub const MOCK_VALUE: &str = "compliant";
+
+/// Registry with only the mock strategies registered
+pub fn mock_registry() -> Result<CtnStrategyRegistry, StrategyError> {
+    let mut registry = CtnStrategyRegistry::new();
+    registry.register_ctn_strategy(Box::new(MockCollector), Box::new(MockExecutor))?;
+    Ok(registry)
+}
+
+/// Contract for `mock_file`: a `path` object field and a `value` state field
+pub fn mock_contract() -> CtnContract {
+    let mut contract = CtnContract::new(MOCK_CTN_TYPE.to_string());
+    contract
+        .object_requirements
+        .add_required_field(ObjectFieldSpec {
+            name: "path".to_string(),
+            data_type: DataType::String,
+            description: "Synthetic object path".to_string(),
+            example_values: vec!["/synthetic/object_0".to_string()],
+            validation_notes: None,
+        });
+    contract
+        .state_requirements
+        .add_optional_field(StateFieldSpec {
+            name: MOCK_FIELD.to_string(),
+            data_type: DataType::String,
+            allowed_operations: vec![
+                Operation::Equals,
+                Operation::NotEqual,
+                Operation::Contains,
+                Operation::NotContains,
+            ],
+            description: "Collected value".to_string(),
+            example_values: vec![MOCK_VALUE.to_string()],
+            validation_notes: None,
+        });
+    contract
+        .field_mappings
+        .collection_mappings
+        .object_to_collection
+        .insert("path".to_string(), "path".to_string());
+    contract
+        .field_mappings
+        .collection_mappings
+        .required_data_fields = vec![MOCK_FIELD.to_string()];
+    contract
+        .field_mappings
+        .validation_mappings
+        .state_to_data
+        .insert(MOCK_FIELD.to_string(), MOCK_FIELD.to_string());
+    contract.collection_strategy.collector_type = "mock".to_string();
+    contract
+}
+
+/// Collector returning [`MOCK_VALUE`] for every object
+pub struct MockCollector;
+
+impl CtnDataCollector for MockCollector {
+    fn collect_for_ctn_with_hints(
+        &self,
+        object: &ExecutableObject,
+        contract: &CtnContract,
+        _hints: &BehaviorHints,
+    ) -> Result<CollectedData, CollectionError> {
+        let mut data = CollectedData::new(
+            object.identifier.clone(),
+            contract.ctn_type.clone(),
+            self.collector_id().to_string(),
+        );
+        data.add_field(
+            MOCK_FIELD.to_string(),
+            ResolvedValue::String(MOCK_VALUE.to_string()),
+        );
+        Ok(data)
+    }
+
+    fn supported_ctn_types(&self) -> Vec<String> {
+        vec![MOCK_CTN_TYPE.to_string()]
+    }
+
+    fn validate_ctn_compatibility(&self, _contract: &CtnContract) -> Result<(), CollectionError> {
+        Ok(())
+    }
+
+    fn collector_id(&self) -> &str {
+        "mock-collector"
+    }
+}
+
+/// Executor comparing each state field against the collected [`MOCK_FIELD`]
+pub struct MockExecutor;
+
+impl CtnExecutor for MockExecutor {
+    fn execute_with_contract(
+        &self,
+        criterion: &ExecutableCriterion,
+        collected_data: &HashMap<String, CollectedData>,
+        _contract: &CtnContract,
+    ) -> Result<CtnExecutionResult, CtnExecutionError> {
+        let test_spec = &criterion.test;
+        let objects_expected = criterion.expected_object_count();
+        if !evaluate_existence_check(
+            test_spec.existence_check,
+            collected_data.len(),
+            objects_expected,
+        ) {
+            return Ok(CtnExecutionResult::fail(
+                criterion.criterion_type.clone(),
+                format!(
+                    "Existence check failed: expected {} objects, found {}",
+                    objects_expected,
+                    collected_data.len()
+                ),
+            ));
+        }
+
+        let mut state_results = Vec::new();
+        for (object_id, data) in collected_data {
+            let actual = data
+                .get_field(MOCK_FIELD)
+                .cloned()
+                .unwrap_or(ResolvedValue::String(String::new()));
+            let field_results: Vec<FieldValidationResult> = criterion
+                .states
+                .iter()
+                .flat_map(|state| &state.fields)
+                .map(|field| {
+                    if field.name != MOCK_FIELD {
+                        return FieldValidationResult::not_present(
+                            field.name.clone(),
+                            field.value.clone(),
+                            field.operation,
+                            String::new(),
+                        );
+                    }
+                    let passed = actual
+                        .compare_with(&field.value, field.operation)
+                        .unwrap_or(false);
+                    FieldValidationResult {
+                        field_name: field.name.clone(),
+                        expected_value: field.value.clone(),
+                        actual_value: actual.clone(),
+                        operation: field.operation,
+                        passed,
+                        message: String::new(),
+                        outcome: FieldOutcome::from_passed(passed),
+                    }
+                })
+                .collect();
+            let passed: Vec<bool> = field_results.iter().map(|r| r.passed).collect();
+            state_results.push(StateValidationResult {
+                object_id: object_id.clone(),
+                state_results: field_results,
+                combined_result: evaluate_state_operator(test_spec.state_operator, &passed),
+                state_operator: test_spec.state_operator,
+                message: String::new(),
+            });
+        }
+
+        let passing = state_results.iter().filter(|r| r.combined_result).count();
+        let status = if evaluate_item_check(test_spec.item_check, passing, state_results.len()) {
+            ComplianceStatus::Pass
+        } else {
+            ComplianceStatus::Fail
+        };
+        let message = format!("{} of {} objects compliant", passing, state_results.len());
+        let result = match status {
+            ComplianceStatus::Pass => CtnExecutionResult::pass(MOCK_CTN_TYPE.to_string(), message),
+            _ => CtnExecutionResult::fail(MOCK_CTN_TYPE.to_string(), message),
+        };
+        Ok(result.with_state_results(state_results))
+    }
+
+    fn get_ctn_contract(&self) -> CtnContract {
+        mock_contract()
+    }
+
+    fn validate_collected_data(
+        &self,
+        _collected_data: &HashMap<String, CollectedData>,
+        _contract: &CtnContract,
+    ) -> Result<(), CtnExecutionError> {
+        Ok(())
+    }
+
+    fn ctn_type(&self) -> &str {
+        MOCK_CTN_TYPE
+    }
+}
@@ -0,0 +1,169 @@
+//! # Synthetic Policies
+//!
+//! Generates valid ESP source of a chosen size for benchmarks, fuzz seeds and
+//! memory tests. Every criterion uses the [`MOCK_CTN_TYPE`](crate::mock::MOCK_CTN_TYPE)
+//! type so the result runs against [`mock_registry`](crate::mock::mock_registry)
+//! without touching the host.
+//!
+//! Shape of a generated policy:
+//!
+//! - one root `VAR` and one `RUN CONCAT` per object deriving its path from it,
+//!   so resolution walks a wide, one-level-deep dependency graph
+//! - `objects` global objects, each reading its path from its `RUN` variable
+//! - `states` global states with `fields_per_state` fields each
+//! - `criteria` CTNs in a single `CRI AND`, cycling through objects and states
+
+use crate::mock::{MOCK_CTN_TYPE, MOCK_FIELD, MOCK_VALUE};
+use std::fmt::Write;
+
+/// Builder for a synthetic policy
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyntheticPolicy {
+    criteria: usize,
+    objects: usize,
+    states: usize,
+    fields_per_state: usize,
+}
+
+impl Default for SyntheticPolicy {
+    fn default() -> Self {
+        Self {
+            criteria: 10,
+            objects: 10,
+            states: 1,
+            fields_per_state: 1,
+        }
+    }
+}
+
+impl SyntheticPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of CTNs (at least 1)
+    pub fn with_criteria(mut self, criteria: usize) -> Self {
+        self.criteria = criteria.max(1);
+        self
+    }
+
+    /// Number of global objects, each with its own computed path (at least 1)
+    pub fn with_objects(mut self, objects: usize) -> Self {
+        self.objects = objects.max(1);
+        self
+    }
+
+    /// Number of global states (at least 1)
+    pub fn with_states(mut self, states: usize) -> Self {
+        self.states = states.max(1);
+        self
+    }
+
+    /// Fields in each state (at least 1)
+    pub fn with_fields_per_state(mut self, fields: usize) -> Self {
+        self.fields_per_state = fields.max(1);
+        self
+    }
+
+    pub fn criteria(&self) -> usize {
+        self.criteria
+    }
+
+    /// ESP source for the policy
+    pub fn build(&self) -> String {
+        let mut out = String::new();
+        self.write_meta(&mut out);
+        out.push_str("DEF\n");
+        out.push_str("    VAR root string `/synthetic`\n\n");
+
+        for i in 0..self.objects {
+            let _ = writeln!(out, "    RUN path_{i} CONCAT");
+            out.push_str("        VAR root\n");
+            let _ = writeln!(out, "        literal `/object_{i}`");
+            out.push_str("    RUN_END\n");
+        }
+        out.push('\n');
+
+        for i in 0..self.states {
+            let _ = writeln!(out, "    STATE state_{i}");
+            for f in 0..self.fields_per_state {
+                // The first field always matches the mock; the rest are
+                // cheap substring checks that also pass
+                if f == 0 {
+                    let _ = writeln!(out, "        {MOCK_FIELD} string = `{MOCK_VALUE}`");
+                } else {
+                    let _ = writeln!(
+                        out,
+                        "        {MOCK_FIELD} string contains `{}`",
+                        &MOCK_VALUE[..1]
+                    );
+                }
+            }
+            out.push_str("    STATE_END\n");
+        }
+        out.push('\n');
+
+        for i in 0..self.objects {
+            let _ = writeln!(out, "    OBJECT object_{i}");
+            let _ = writeln!(out, "        path VAR path_{i}");
+            out.push_str("    OBJECT_END\n");
+        }
+        out.push('\n');
+
+        out.push_str("    CRI AND\n");
+        for i in 0..self.criteria {
+            let _ = writeln!(out, "        CTN {MOCK_CTN_TYPE}");
+            out.push_str("            TEST all all\n");
+            let _ = writeln!(out, "            STATE_REF state_{}", i % self.states);
+            let _ = writeln!(out, "            OBJECT_REF object_{}", i % self.objects);
+            out.push_str("        CTN_END\n");
+        }
+        out.push_str("    CRI_END\n");
+        out.push_str("DEF_END\n");
+        out
+    }
+
+    fn write_meta(&self, out: &mut String) {
+        let _ = write!(
+            out,
+            "META
+    version `1.0.0`
+    esp_version `1.0`
+    author `esp_bench`
+    date `2026-01-01`
+    severity `low`
+    platform `linux`
+    description `Synthetic policy with {} criteria`
+    control_framework `BENCH`
+    control `SYN-1`
+    esp_scan_id `synthetic-{}`
+    criticality `low`
+    tags `synthetic`
+META_END
+
+",
+            self.criteria, self.criteria
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_sizes() {
+        let source = SyntheticPolicy::new()
+            .with_criteria(5)
+            .with_objects(2)
+            .with_states(3)
+            .with_fields_per_state(2)
+            .build();
+        assert_eq!(source.matches("CTN_END").count(), 5);
+        assert_eq!(source.matches("OBJECT_END").count(), 2);
+        assert_eq!(source.matches("RUN_END").count(), 2);
+        assert_eq!(source.matches("STATE_END").count(), 3);
+        assert!(source.contains("OBJECT_REF object_1\n"));
+        assert!(source.ends_with("DEF_END\n"));
+    }
+}
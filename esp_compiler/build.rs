@@ -84,6 +84,7 @@ struct StructuralLimits {
     max_variables_per_definition: usize,
     max_states_per_definition: usize,
     max_objects_per_definition: usize,
+    max_metadata_value_length: usize,
 }
 
 #[derive(serde::Deserialize)]
@@ -277,6 +278,7 @@ pub mod compile_time {{
         pub const MAX_VARIABLES_PER_DEFINITION: usize = {};
         pub const MAX_STATES_PER_DEFINITION: usize = {};
         pub const MAX_OBJECTS_PER_DEFINITION: usize = {};
+        pub const MAX_METADATA_VALUE_LENGTH: usize = {};
     }}
 
     pub mod batch_processing {{
@@ -352,6 +354,7 @@ pub mod compile_time {{
         config.structural.max_variables_per_definition,
         config.structural.max_states_per_definition,
         config.structural.max_objects_per_definition,
+        config.structural.max_metadata_value_length,
         // Batch Processing
         config.batch_processing.max_worker_threads,
         config.batch_processing.max_files_per_batch,
@@ -2,12 +2,16 @@
 //!
 //! Provides directory-based batch processing with sequential and parallel execution modes.
 //! Integrates with the global logging system and error collector for cargo-style output.
+//!
+//! Results never pass through shared state: each worker thread accumulates its
+//! own [`BatchResults`] and the batch merges them in file order once the
+//! workers have joined. The logging collectors only observe processing, so
+//! the counts reported here do not depend on what was logged.
 
 use crate::logging::{self, codes};
 use crate::pipeline::{self, PipelineError, PipelineResult};
 use std::fs;
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
 use std::thread;
 use std::time::{Duration, Instant};
 
@@ -69,10 +73,11 @@ impl BatchResults {
     }
 
     pub fn success_rate(&self) -> f64 {
-        if self.files_processed == 0 {
+        let attempted = self.success_count() + self.failure_count();
+        if attempted == 0 {
             0.0
         } else {
-            self.successful_files.len() as f64 / self.files_processed as f64
+            self.successful_files.len() as f64 / attempted as f64
         }
     }
 
@@ -86,6 +91,16 @@ impl BatchResults {
         self.files_processed += 1;
     }
 
+    /// Record files that were discovered but rejected before processing
+    ///
+    /// They count as failures but not as processed files.
+    fn add_invalid(&mut self, invalid_files: Vec<(PathBuf, String)>) {
+        for (file_path, reason) in invalid_files {
+            self.failed_files
+                .push((file_path, PipelineError::Pipeline { message: reason }));
+        }
+    }
+
     pub fn merge(&mut self, other: BatchResults) {
         self.successful_files.extend(other.successful_files);
         self.failed_files.extend(other.failed_files);
@@ -368,7 +383,7 @@ pub fn process_directory_sequential(
 
         // Process with file context for automatic error collection
         let should_continue = logging::with_file_context(file_path.clone(), file_id, || {
-            match pipeline::process_file(file_path.to_str().unwrap()) {
+            match process_single_file(file_path) {
                 Ok(pipeline_result) => {
                     results.add_success(file_path.clone(), pipeline_result);
 
@@ -405,6 +420,7 @@ pub fn process_directory_sequential(
         }
     }
 
+    results.add_invalid(invalid_files);
     results.processing_duration = start_time.elapsed();
 
     crate::log_success!(
@@ -449,6 +465,7 @@ pub fn process_directory_parallel(
     results.files_discovered = discovered_files.len();
 
     if valid_files.is_empty() {
+        results.add_invalid(invalid_files);
         results.processing_duration = start_time.elapsed();
         return Ok(results);
     }
@@ -463,8 +480,8 @@ pub fn process_directory_parallel(
     );
 
     // Process files in chunks
-    for chunk in valid_files.chunks(chunk_size) {
-        let chunk_results = process_chunk_parallel(chunk, config)?;
+    for (chunk_index, chunk) in valid_files.chunks(chunk_size).enumerate() {
+        let chunk_results = process_chunk_parallel(chunk, chunk_index * chunk_size, config)?;
         results.merge(chunk_results);
 
         // Check for fail-fast mode
@@ -474,6 +491,7 @@ pub fn process_directory_parallel(
         }
     }
 
+    results.add_invalid(invalid_files);
     results.processing_duration = start_time.elapsed();
 
     crate::log_success!(
@@ -490,65 +508,62 @@ pub fn process_directory_parallel(
 }
 
 /// Process a chunk of files in parallel
+///
+/// Each worker owns a contiguous slice of the chunk and returns its own
+/// results; merging them in thread order keeps the output in file order.
 fn process_chunk_parallel(
     files: &[PathBuf],
+    first_file_id: usize,
     config: &BatchConfig,
 ) -> Result<BatchResults, BatchError> {
-    let results = Arc::new(std::sync::Mutex::new(BatchResults::new()));
-
-    // Create thread handles
     let mut handles = Vec::new();
     let files_per_thread = (files.len() + config.max_threads - 1) / config.max_threads;
 
-    for thread_id in 0..config.max_threads {
-        let start_idx = thread_id * files_per_thread;
-        let end_idx = ((thread_id + 1) * files_per_thread).min(files.len());
-
-        if start_idx >= files.len() {
-            break;
-        }
-
-        let thread_files: Vec<PathBuf> = files[start_idx..end_idx].to_vec();
-        let results_clone = Arc::clone(&results);
+    for thread_files in files.chunks(files_per_thread.max(1)) {
+        let start_idx = first_file_id + handles.len() * files_per_thread;
+        let thread_files: Vec<PathBuf> = thread_files.to_vec();
 
         let handle = thread::spawn(move || {
-            for (local_file_id, file_path) in thread_files.iter().enumerate() {
+            let mut worker_results = BatchResults::new();
+            for (local_file_id, file_path) in thread_files.into_iter().enumerate() {
                 let global_file_id = start_idx + local_file_id;
 
-                logging::with_file_context(file_path.clone(), global_file_id, || {
-                    match pipeline::process_file(file_path.to_str().unwrap()) {
-                        Ok(pipeline_result) => {
-                            let mut results_guard = results_clone.lock().unwrap();
-                            results_guard.add_success(file_path.clone(), pipeline_result);
-                        }
-                        Err(pipeline_error) => {
-                            let mut results_guard = results_clone.lock().unwrap();
-                            results_guard.add_failure(file_path.clone(), pipeline_error);
-                        }
-                    }
+                let outcome = logging::with_file_context(file_path.clone(), global_file_id, || {
+                    process_single_file(&file_path)
                 });
+                match outcome {
+                    Ok(pipeline_result) => worker_results.add_success(file_path, pipeline_result),
+                    Err(pipeline_error) => worker_results.add_failure(file_path, pipeline_error),
+                }
             }
+            worker_results
         });
 
         handles.push(handle);
     }
 
-    // Wait for all threads to complete
+    // Join every worker before reporting a panic so none is left detached
+    let mut chunk_results = BatchResults::new();
+    let mut panicked = false;
     for handle in handles {
-        handle.join().map_err(|_| BatchError::ThreadError {
+        match handle.join() {
+            Ok(worker_results) => chunk_results.merge(worker_results),
+            Err(_) => panicked = true,
+        }
+    }
+
+    if panicked {
+        return Err(BatchError::ThreadError {
             message: "Thread panicked during processing".to_string(),
-        })?;
+        });
     }
 
-    // Extract results
-    let final_results = Arc::try_unwrap(results)
-        .map_err(|_| BatchError::ThreadError {
-            message: "Failed to extract results from thread pool".to_string(),
-        })?
-        .into_inner()
-        .unwrap();
+    Ok(chunk_results)
+}
 
-    Ok(final_results)
+/// Run the pipeline on one file; non-UTF-8 paths are passed lossily
+fn process_single_file(file_path: &Path) -> Result<PipelineResult, PipelineError> {
+    pipeline::process_file(&file_path.to_string_lossy())
 }
 
 /// Calculate optimal chunk size for parallel processing
@@ -688,6 +703,74 @@ mod tests {
         assert_eq!(results.failure_count(), 0);
     }
 
+    fn tiny_policy(n: usize) -> String {
+        format!(
+            "META\n    version `1.0.0`\n    esp_version `1.0`\n    author `test`\n    date `2026-01-01`\n    severity `low`\n    platform `linux`\n    description `stress {n}`\n    control_framework `TEST`\n    control `STRESS-{n}`\n    esp_scan_id `stress_{n}`\n    criticality `low`\n    tags `test`\nMETA_END\n\nDEF\n    OBJECT target\n        path `/tmp/{n}`\n    OBJECT_END\n\n    STATE present\n        exists boolean = true\n    STATE_END\n\n    CRI AND\n        CTN file_metadata\n            TEST all all\n            STATE_REF present\n            OBJECT_REF target\n        CTN_END\n    CRI_END\nDEF_END\n"
+        )
+    }
+
+    #[test]
+    fn test_parallel_results_account_for_every_file() {
+        let temp_dir = tempdir().unwrap();
+        for n in 0..500 {
+            // Every seventh file is broken so both result lists fill up
+            let source = if n % 7 == 0 {
+                "DEF\n    CRI AND\nDEF_END\n".to_string()
+            } else {
+                tiny_policy(n)
+            };
+            fs::write(temp_dir.path().join(format!("policy_{:03}.esp", n)), source).unwrap();
+        }
+
+        let config = BatchConfig {
+            max_threads: 16,
+            progress_reporting: false,
+            ..BatchConfig::default()
+        };
+        let results = process_directory_with_config(temp_dir.path(), &config).unwrap();
+
+        assert_eq!(results.files_discovered, 500);
+        assert_eq!(results.files_processed, 500);
+        assert_eq!(
+            results.success_count() + results.failure_count(),
+            results.files_discovered
+        );
+        assert_eq!(results.failure_count(), 72);
+
+        let mut seen: Vec<&PathBuf> = results
+            .successful_files
+            .iter()
+            .map(|(path, _)| path)
+            .chain(results.failed_files.iter().map(|(path, _)| path))
+            .collect();
+        seen.sort();
+        seen.dedup();
+        assert_eq!(seen.len(), 500);
+
+        // Worker results are merged in file order
+        let successes: Vec<&PathBuf> = results.successful_files.iter().map(|(p, _)| p).collect();
+        assert!(successes.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_invalid_files_are_reported_as_failures() {
+        let temp_dir = tempdir().unwrap();
+        fs::write(temp_dir.path().join("ok.esp"), tiny_policy(1)).unwrap();
+        let dangling = temp_dir.path().join("dangling.esp");
+        std::os::unix::fs::symlink(temp_dir.path().join("missing"), &dangling).unwrap();
+
+        let files = vec![temp_dir.path().join("ok.esp"), dangling.clone()];
+        let (_, invalid) = validate_files(&files);
+        let mut results = BatchResults::new();
+        results.add_invalid(invalid);
+
+        assert_eq!(results.files_processed, 0);
+        assert_eq!(results.failure_count(), 1);
+        assert_eq!(results.failed_files[0].0, dangling);
+        assert_eq!(results.success_rate(), 0.0);
+    }
+
     #[test]
     fn test_chunk_size_calculation() {
         assert_eq!(calculate_chunk_size(&vec![PathBuf::new(); 100], 4), 25);
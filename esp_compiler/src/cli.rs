@@ -0,0 +1,242 @@
+//! # Compiler Command Line
+//!
+//! clap definitions for the `esp_compiler` binary, plus shell completion and
+//! man page generation from those same definitions.
+
+use clap::builder::{PossibleValuesParser, TypedValueParser};
+use clap::{Args, CommandFactory, Parser, Subcommand, ValueHint};
+use clap_complete::Shell;
+use esp_compiler::batch::BatchConfig;
+use esp_compiler::terminal::ColorChoice;
+use std::io::Write;
+use std::path::PathBuf;
+
+/// Upper bound on worker threads for batch processing
+const MAX_THREADS: usize = 32;
+
+/// Exit codes and examples appended to `--help` and the man page
+pub const AFTER_LONG_HELP: &str = "\
+EXIT CODES:
+    0    All files compiled successfully
+    1    At least one file failed to compile, the input was invalid, or
+         --deny-incompatible found constructs the target scanner lacks, or
+         --fmt --check found files that are not formatted
+
+EXAMPLES:
+    esp_compiler example.esp
+    esp_compiler /path/to/esp-files/
+    esp_compiler configs/ --threads 4
+    esp_compiler tests/ --sequential --fail-fast
+    esp_compiler example.esp --emit-ast example.ast.json
+    esp_compiler policies/ --target-capabilities scanner-caps.json --deny-incompatible
+    esp_compiler large-dir/ --max-files 100
+    esp_compiler --fmt policies/
+    esp_compiler --fmt --check policies/
+    esp_compiler completions zsh > _esp_compiler";
+
+/// Complete ESP compilation pipeline with batch processing
+#[derive(Debug, Parser)]
+#[command(
+    name = "esp_compiler",
+    version,
+    after_long_help = AFTER_LONG_HELP,
+    args_conflicts_with_subcommands = true,
+    subcommand_negates_reqs = true
+)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// ESP file or directory of ESP files to compile
+    #[arg(value_name = "PATH", value_hint = ValueHint::AnyPath, required_unless_present = "man")]
+    pub input: Option<PathBuf>,
+
+    #[command(flatten)]
+    pub batch: BatchArgs,
+
+    /// Write the compiled AST as versioned JSON to FILE (single file only)
+    #[arg(long, value_name = "FILE", value_hint = ValueHint::FilePath)]
+    pub emit_ast: Option<PathBuf>,
+
+    /// Report constructs the scanner described by this capability manifest
+    /// (`scanner capabilities --json`) cannot execute, as warnings
+    #[arg(long, value_name = "FILE", value_hint = ValueHint::FilePath)]
+    pub target_capabilities: Option<PathBuf>,
+
+    /// Treat constructs missing from --target-capabilities as errors
+    #[arg(long, requires = "target_capabilities")]
+    pub deny_incompatible: bool,
+
+    /// Rewrite PATH in canonical layout instead of compiling it; a directory
+    /// formats every ESP file found the way batch compilation would
+    #[arg(long, conflicts_with_all = ["emit_ast", "target_capabilities"])]
+    pub fmt: bool,
+
+    /// With --fmt, write nothing and fail if any file is not formatted
+    #[arg(long, requires = "fmt")]
+    pub check: bool,
+
+    /// Color diagnostics and statuses: auto (terminal without NO_COLOR),
+    /// always or never
+    #[arg(
+        long,
+        global = true,
+        value_name = "WHEN",
+        default_value = "auto",
+        value_parser = PossibleValuesParser::new(ColorChoice::VALUES)
+            .map(|s| s.parse::<ColorChoice>().unwrap())
+    )]
+    pub color: ColorChoice,
+
+    /// Print the man page (roff) to stdout and exit
+    #[arg(long)]
+    pub man: bool,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Print a shell completion script to stdout
+    Completions {
+        /// Shell to generate completions for
+        #[arg(value_enum)]
+        shell: Shell,
+    },
+}
+
+/// Directory (batch) processing options
+#[derive(Debug, Clone, Default, Args)]
+pub struct BatchArgs {
+    /// Force sequential processing (no parallelism)
+    #[arg(long, conflicts_with_all = ["parallel", "threads"])]
+    pub sequential: bool,
+
+    /// Force parallel processing (default)
+    #[arg(long)]
+    pub parallel: bool,
+
+    /// Maximum number of worker threads (default: auto)
+    #[arg(long, value_name = "N")]
+    pub threads: Option<usize>,
+
+    /// Don't search subdirectories
+    #[arg(long)]
+    pub no_recursive: bool,
+
+    /// Limit the number of files processed
+    #[arg(long, value_name = "N")]
+    pub max_files: Option<usize>,
+
+    /// Stop on first error
+    #[arg(long)]
+    pub fail_fast: bool,
+
+    /// Suppress progress reporting
+    #[arg(long)]
+    pub quiet: bool,
+}
+
+impl BatchArgs {
+    /// Lower command-line options onto the batch configuration
+    pub fn to_batch_config(&self) -> BatchConfig {
+        let mut config = BatchConfig::default();
+
+        if self.sequential {
+            config.max_threads = 1;
+        }
+        if let Some(threads) = self.threads {
+            config.max_threads = threads.clamp(1, MAX_THREADS);
+        }
+        if self.no_recursive {
+            config.recursive = false;
+        }
+        config.max_files = self.max_files;
+        config.fail_fast = self.fail_fast;
+        if self.quiet {
+            config.progress_reporting = false;
+        }
+
+        config
+    }
+}
+
+/// Write a completion script for `shell`
+pub fn write_completions(shell: Shell, out: &mut dyn Write) {
+    let mut command = Cli::command();
+    let name = command.get_name().to_string();
+    clap_complete::generate(shell, &mut command, name, out);
+}
+
+/// Write the roff man page
+pub fn write_man_page(out: &mut dyn Write) -> std::io::Result<()> {
+    clap_mangen::Man::new(Cli::command()).render(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cli_definition_is_valid() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn test_completions_for_every_shell() {
+        for shell in [Shell::Bash, Shell::Zsh, Shell::Fish, Shell::PowerShell] {
+            let mut out = Vec::new();
+            write_completions(shell, &mut out);
+            let script = String::from_utf8(out).unwrap();
+            assert!(script.contains("max-files"), "{} completions", shell);
+        }
+    }
+
+    #[test]
+    fn test_man_page_lists_every_flag() {
+        let mut out = Vec::new();
+        write_man_page(&mut out).unwrap();
+        let man = String::from_utf8(out).unwrap();
+
+        for arg in Cli::command().get_arguments() {
+            if let Some(long) = arg.get_long() {
+                // roff escapes hyphens in option names
+                let escaped = format!("\\-\\-{}", long.replace('-', "\\-"));
+                assert!(man.contains(&escaped), "man page missing --{}", long);
+            }
+        }
+        assert!(man.contains("EXIT CODES"));
+    }
+
+    #[test]
+    fn test_deny_incompatible_requires_manifest() {
+        assert!(Cli::try_parse_from(["esp_compiler", "a.esp", "--deny-incompatible"]).is_err());
+        let cli = Cli::try_parse_from([
+            "esp_compiler",
+            "a.esp",
+            "--target-capabilities",
+            "caps.json",
+            "--deny-incompatible",
+        ])
+        .unwrap();
+        assert!(cli.deny_incompatible);
+    }
+
+    #[test]
+    fn test_fmt_flags() {
+        let cli = Cli::try_parse_from(["esp_compiler", "--fmt", "--check", "dir/"]).unwrap();
+        assert!(cli.fmt && cli.check);
+        assert!(Cli::try_parse_from(["esp_compiler", "a.esp", "--check"]).is_err());
+        assert!(
+            Cli::try_parse_from(["esp_compiler", "a.esp", "--fmt", "--emit-ast", "a.json"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_color_flag() {
+        let cli = Cli::try_parse_from(["esp_compiler", "a.esp"]).unwrap();
+        assert_eq!(cli.color, ColorChoice::Auto);
+        let cli = Cli::try_parse_from(["esp_compiler", "a.esp", "--color", "never"]).unwrap();
+        assert_eq!(cli.color, ColorChoice::Never);
+        assert!(Cli::try_parse_from(["esp_compiler", "a.esp", "--color", "rainbow"]).is_err());
+    }
+}
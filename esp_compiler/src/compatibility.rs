@@ -0,0 +1,372 @@
+//! # Policy Compatibility
+//!
+//! Policies are often authored against a newer scanner than the one deployed
+//! on hosts. A scanner describes what it can execute in a
+//! [`CapabilityManifest`] (`scanner capabilities --json`); [`check`] lists
+//! every construct of a compiled policy that the manifest does not cover, with
+//! the AST version that introduced it when known.
+
+use crate::grammar::ast::nodes::{
+    CriteriaContent, CriteriaNode, DataType, EspFile, ObjectDefinition, ObjectElement, Operation,
+    RecordCheck, RecordContent, RuntimeOperationType, StateDefinition,
+};
+use crate::grammar::ast::AST_VERSION;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fmt;
+use std::path::Path;
+
+/// Version of the manifest layout itself
+pub const MANIFEST_VERSION: u32 = 1;
+
+/// Language constructs added after AST 1.0, with the version that added them
+const INTRODUCED_IN: &[(ConstructKind, &str, &str)] = &[
+    (ConstructKind::Operation, "equals_any", "1.1"),
+    (ConstructKind::Operation, "not_equals_any", "1.1"),
+    (ConstructKind::Operation, "length_equals", "1.2"),
+    (ConstructKind::Operation, "length_greater_than", "1.2"),
+    (ConstructKind::Operation, "occurrence_count", "1.2"),
+    (ConstructKind::Operation, "version_equals", "1.5"),
+    (ConstructKind::Operation, "version_not_equal", "1.5"),
+    (ConstructKind::Operation, "version_greater_than", "1.5"),
+    (ConstructKind::Operation, "version_less_than", "1.5"),
+    (ConstructKind::Operation, "version_greater_or_equal", "1.5"),
+    (ConstructKind::Operation, "version_less_or_equal", "1.5"),
+    (ConstructKind::Operation, "in_subnet", "1.6"),
+    (ConstructKind::Operation, "not_in_subnet", "1.6"),
+    (ConstructKind::DataType, "ip_address", "1.6"),
+    (ConstructKind::Operation, "glob_match", "1.7"),
+    (ConstructKind::Operation, "not_glob_match", "1.7"),
+    (ConstructKind::Operation, "permissions_at_most", "1.8"),
+    (ConstructKind::Operation, "permissions_at_least", "1.8"),
+    (ConstructKind::DataType, "octal", "1.8"),
+    (ConstructKind::DataType, "datetime", "1.9"),
+    (ConstructKind::RuntimeOperation, "REPLACE", "1.11"),
+    (ConstructKind::RuntimeOperation, "LENGTH", "1.12"),
+    (ConstructKind::RuntimeOperation, "UPPERCASE", "1.13"),
+    (ConstructKind::RuntimeOperation, "LOWERCASE", "1.13"),
+    (ConstructKind::RuntimeOperation, "TRIM", "1.13"),
+];
+
+/// What a scanner build can execute
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CapabilityManifest {
+    pub manifest_version: u32,
+    #[serde(default)]
+    pub scanner_version: String,
+    /// AST version the scanner decodes
+    #[serde(default)]
+    pub ast_version: String,
+    /// Version of the scan result JSON the scanner writes
+    #[serde(default)]
+    pub result_schema_version: String,
+    /// Registered criterion types; empty means not checked
+    #[serde(default)]
+    pub criterion_types: BTreeSet<String>,
+    #[serde(default)]
+    pub operations: BTreeSet<String>,
+    #[serde(default)]
+    pub runtime_operations: BTreeSet<String>,
+    #[serde(default)]
+    pub data_types: BTreeSet<String>,
+}
+
+impl CapabilityManifest {
+    /// Every language construct this compiler knows; no criterion types
+    pub fn language() -> Self {
+        Self {
+            manifest_version: MANIFEST_VERSION,
+            scanner_version: String::new(),
+            ast_version: AST_VERSION.to_string(),
+            result_schema_version: String::new(),
+            criterion_types: BTreeSet::new(),
+            operations: Operation::ALL.iter().map(|o| o.as_str().into()).collect(),
+            runtime_operations: RuntimeOperationType::ALL
+                .iter()
+                .map(|o| o.as_str().into())
+                .collect(),
+            data_types: DataType::ALL.iter().map(|t| t.as_str().into()).collect(),
+        }
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let manifest: Self = serde_json::from_str(json)
+            .map_err(|e| format!("invalid capability manifest: {}", e))?;
+        if manifest.manifest_version > MANIFEST_VERSION {
+            return Err(format!(
+                "capability manifest version {} is newer than supported version {}",
+                manifest.manifest_version, MANIFEST_VERSION
+            ));
+        }
+        Ok(manifest)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let json = std::fs::read_to_string(path)
+            .map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+        Self::from_json(&json)
+    }
+
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string_pretty(self).map_err(|e| e.to_string())
+    }
+
+    fn supports(&self, kind: ConstructKind, name: &str) -> bool {
+        match kind {
+            ConstructKind::CriterionType => {
+                self.criterion_types.is_empty() || self.criterion_types.contains(name)
+            }
+            ConstructKind::Operation => self.operations.contains(name),
+            ConstructKind::RuntimeOperation => self.runtime_operations.contains(name),
+            ConstructKind::DataType => self.data_types.contains(name),
+        }
+    }
+}
+
+/// Category of a policy construct
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ConstructKind {
+    CriterionType,
+    Operation,
+    RuntimeOperation,
+    DataType,
+}
+
+impl ConstructKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::CriterionType => "criterion type",
+            Self::Operation => "operation",
+            Self::RuntimeOperation => "runtime operation",
+            Self::DataType => "data type",
+        }
+    }
+}
+
+/// A construct the target scanner cannot execute
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Incompatibility {
+    pub kind: ConstructKind,
+    pub name: String,
+    /// AST version that introduced the construct, when known
+    pub introduced_in: Option<&'static str>,
+}
+
+impl fmt::Display for Incompatibility {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} '{}' is not supported by the target scanner",
+            self.kind.as_str(),
+            self.name
+        )?;
+        if let Some(version) = self.introduced_in {
+            write!(f, " (introduced in AST {})", version)?;
+        }
+        Ok(())
+    }
+}
+
+/// Constructs used by `ast` that `target` does not list, in a stable order
+pub fn check(ast: &EspFile, target: &CapabilityManifest) -> Vec<Incompatibility> {
+    policy_constructs(ast)
+        .into_iter()
+        .filter(|(kind, name)| !target.supports(*kind, name))
+        .map(|(kind, name)| Incompatibility {
+            introduced_in: INTRODUCED_IN
+                .iter()
+                .find(|(k, n, _)| *k == kind && *n == name)
+                .map(|(_, _, version)| *version),
+            kind,
+            name,
+        })
+        .collect()
+}
+
+/// Every checked construct a policy uses
+fn policy_constructs(ast: &EspFile) -> BTreeSet<(ConstructKind, String)> {
+    let mut found = BTreeSet::new();
+    let definition = &ast.definition;
+    for variable in &definition.variables {
+        found.insert(data_type(variable.data_type));
+    }
+    for operation in &definition.runtime_operations {
+        found.insert((
+            ConstructKind::RuntimeOperation,
+            operation.operation_type.as_str().to_string(),
+        ));
+    }
+    for state in &definition.states {
+        state_constructs(state, &mut found);
+    }
+    for object in &definition.objects {
+        object_constructs(object, &mut found);
+    }
+    for criteria in &definition.criteria {
+        criteria_constructs(criteria, &mut found);
+    }
+    found
+}
+
+fn data_type(data_type: DataType) -> (ConstructKind, String) {
+    (ConstructKind::DataType, data_type.as_str().to_string())
+}
+
+fn operation(operation: Operation) -> (ConstructKind, String) {
+    (ConstructKind::Operation, operation.as_str().to_string())
+}
+
+fn state_constructs(state: &StateDefinition, found: &mut BTreeSet<(ConstructKind, String)>) {
+    for field in &state.fields {
+        found.insert(data_type(field.data_type));
+        found.insert(operation(field.operation));
+    }
+    for check in &state.record_checks {
+        record_constructs(check, found);
+    }
+}
+
+fn record_constructs(check: &RecordCheck, found: &mut BTreeSet<(ConstructKind, String)>) {
+    if let Some(t) = check.data_type {
+        found.insert(data_type(t));
+    }
+    match &check.content {
+        RecordContent::Direct { operation: op, .. } => {
+            found.insert(operation(*op));
+        }
+        RecordContent::Nested { fields } => {
+            for field in fields {
+                found.insert(data_type(field.data_type));
+                found.insert(operation(field.operation));
+            }
+        }
+    }
+}
+
+fn object_constructs(object: &ObjectDefinition, found: &mut BTreeSet<(ConstructKind, String)>) {
+    for element in &object.elements {
+        match element {
+            ObjectElement::Parameter { data_type: t, .. }
+            | ObjectElement::Select { data_type: t, .. } => {
+                found.insert(data_type(*t));
+            }
+            ObjectElement::RecordCheck(check) => record_constructs(check, found),
+            _ => {}
+        }
+    }
+}
+
+fn criteria_constructs(criteria: &CriteriaNode, found: &mut BTreeSet<(ConstructKind, String)>) {
+    for content in &criteria.content {
+        match content {
+            CriteriaContent::Criteria(nested) => criteria_constructs(nested, found),
+            CriteriaContent::Criterion(ctn) => {
+                found.insert((ConstructKind::CriterionType, ctn.criterion_type.clone()));
+                for state in &ctn.local_states {
+                    state_constructs(state, found);
+                }
+                if let Some(object) = &ctn.local_object {
+                    object_constructs(object, found);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OLDER_SCANNER: &str = r#"{
+        "manifest_version": 1,
+        "scanner_version": "0.1.0",
+        "ast_version": "1.1",
+        "criterion_types": ["file_content", "file_metadata"],
+        "operations": ["=", "!=", "contains", "equals_any", "not_equals_any"],
+        "runtime_operations": ["CONCAT"],
+        "data_types": ["string", "int", "boolean"]
+    }"#;
+
+    const POLICY: &str = "META
+    version `1.0.0`
+    esp_version `1.0`
+    author `test`
+    date `2026-01-01`
+    severity `low`
+    platform `linux`
+    description `compatibility`
+    control_framework `TEST`
+    control `CMP-1`
+    esp_scan_id `compat`
+    criticality `low`
+    tags `test`
+META_END
+
+DEF
+    OBJECT banner
+        path `/etc/issue`
+    OBJECT_END
+
+    STATE short_banner
+        file_content string length_equals 12
+    STATE_END
+
+    CRI AND
+        CTN file_content
+            TEST all all
+            STATE_REF short_banner
+            OBJECT_REF banner
+        CTN_END
+    CRI_END
+DEF_END
+";
+
+    fn compile(source: &str) -> EspFile {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("policy.esp");
+        std::fs::write(&path, source).unwrap();
+        crate::pipeline::process_file(&path.display().to_string())
+            .unwrap()
+            .ast
+    }
+
+    #[test]
+    fn test_new_operation_against_older_manifest() {
+        let ast = compile(POLICY);
+        let older = CapabilityManifest::from_json(OLDER_SCANNER).unwrap();
+
+        let found = check(&ast, &older);
+        assert_eq!(
+            found,
+            vec![Incompatibility {
+                kind: ConstructKind::Operation,
+                name: "length_equals".to_string(),
+                introduced_in: Some("1.2"),
+            }]
+        );
+        assert_eq!(
+            found[0].to_string(),
+            "operation 'length_equals' is not supported by the target scanner (introduced in AST 1.2)"
+        );
+
+        assert!(check(&ast, &CapabilityManifest::language()).is_empty());
+
+        let mut no_file_content = older.clone();
+        no_file_content
+            .operations
+            .insert("length_equals".to_string());
+        no_file_content.criterion_types.remove("file_content");
+        let found = check(&ast, &no_file_content);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].kind, ConstructKind::CriterionType);
+        assert_eq!(found[0].introduced_in, None);
+    }
+
+    #[test]
+    fn test_newer_manifest_version_is_rejected() {
+        let json = OLDER_SCANNER.replace("\"manifest_version\": 1", "\"manifest_version\": 9");
+        assert!(CapabilityManifest::from_json(&json)
+            .unwrap_err()
+            .contains("newer than supported"));
+    }
+}
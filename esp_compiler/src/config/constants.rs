@@ -205,6 +205,11 @@ pub mod compile_time {
         /// SECURITY: Prevents DoS via object explosion
         /// SSDF: PW.8.1 (DoS Protection)
         pub const MAX_OBJECTS_PER_DEFINITION: usize = 200;
+
+        /// Maximum length of a single MET field value
+        /// SECURITY: Prevents oversized metadata propagating into results
+        /// SSDF: PW.8.1 (DoS Protection)
+        pub const MAX_METADATA_VALUE_LENGTH: usize = 1_024;
     }
 
     pub mod batch_processing {
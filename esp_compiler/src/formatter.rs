@@ -0,0 +1,515 @@
+//! # ESP Formatter
+//!
+//! Rewrites an ESP file in canonical layout: one statement per line, blocks
+//! indented four spaces per level, single spaces between tokens (none inside
+//! field paths, value lists or `at_least(N)`), symbolic operator aliases in
+//! their canonical spelling and at most one blank line between statements.
+//! Comments stay where they are; string, number and identifier text is copied
+//! verbatim.
+//!
+//! Statements keep their order. CTN order is part of a policy's meaning and
+//! the order of declarations is how authors group them, so no ordering rule
+//! is applied.
+//!
+//! Formatting never changes what a file compiles to. The input must parse,
+//! and the output is re-parsed and compared with it, ignoring spans, before
+//! it is returned; output that differs, drops a comment or would change again
+//! on a second pass is refused rather than written.
+
+use crate::file_processor::{FileMetadata, FileProcessingResult};
+use crate::grammar::ast::nodes::EspFile;
+use crate::grammar::keywords::Keyword;
+use crate::lexical::{self, LexerError};
+use crate::syntax::{self, SyntaxError};
+use crate::tokens::{SpannedToken, Token, TokenStream};
+use std::borrow::Cow;
+use std::path::PathBuf;
+
+/// Indentation for each level of block nesting
+pub const INDENT: &str = "    ";
+
+/// Why a file could not be formatted
+#[derive(Debug, thiserror::Error)]
+pub enum FormatError {
+    #[error("lexical error: {0}")]
+    Lexical(#[from] LexerError),
+
+    #[error("{} syntax error(s); fix them before formatting", .0.len())]
+    Syntax(Vec<SyntaxError>),
+
+    /// The formatter's own safety checks failed; this is a formatter bug
+    #[error("refusing to format: the output {0}")]
+    Unsafe(&'static str),
+}
+
+/// Format ESP source, verifying the result compiles to the same AST
+pub fn format_source(source: &str) -> Result<String, FormatError> {
+    let tokens = tokenize(source)?;
+    let ast = parse(tokens.clone()).map_err(FormatError::Syntax)?;
+    let formatted = layout(tokens.all_tokens(), source);
+
+    let reformatted_tokens =
+        tokenize(&formatted).map_err(|_| FormatError::Unsafe("does not tokenize"))?;
+    let reformatted_ast =
+        parse(reformatted_tokens.clone()).map_err(|_| FormatError::Unsafe("does not parse"))?;
+    if without_spans(&ast) != without_spans(&reformatted_ast) {
+        return Err(FormatError::Unsafe("compiles to a different policy"));
+    }
+    if comments(tokens.all_tokens(), source)
+        != comments(reformatted_tokens.all_tokens(), &formatted)
+    {
+        return Err(FormatError::Unsafe("changes the comments"));
+    }
+    if layout(reformatted_tokens.all_tokens(), &formatted) != formatted {
+        return Err(FormatError::Unsafe("changes when formatted again"));
+    }
+
+    Ok(formatted)
+}
+
+fn tokenize(source: &str) -> Result<TokenStream, LexerError> {
+    lexical::tokenize_file_result(FileProcessingResult {
+        source: source.to_string(),
+        metadata: FileMetadata {
+            path: PathBuf::new(),
+            size: source.len() as u64,
+            extension: Some("esp".to_string()),
+            line_count: source.lines().count(),
+            is_esp_file: true,
+            modified: None,
+        },
+        processing_duration: std::time::Duration::ZERO,
+    })
+}
+
+/// Files without criteria are parsed as imported declaration files
+fn parse(tokens: TokenStream) -> Result<EspFile, Vec<SyntaxError>> {
+    let has_criteria = tokens.all_tokens().iter().any(|spanned| {
+        matches!(
+            spanned.value,
+            Token::Keyword(Keyword::Cri) | Token::Keyword(Keyword::CriRoot)
+        )
+    });
+    if has_criteria {
+        syntax::parse_esp_file(tokens)
+    } else {
+        syntax::parse_import_file(tokens)
+    }
+}
+
+/// The AST as JSON with every span removed, so layout changes compare equal
+fn without_spans(ast: &EspFile) -> serde_json::Value {
+    fn strip(value: &mut serde_json::Value) {
+        match value {
+            serde_json::Value::Object(fields) => {
+                fields.remove("span");
+                fields.values_mut().for_each(strip);
+            }
+            serde_json::Value::Array(items) => items.iter_mut().for_each(strip),
+            _ => {}
+        }
+    }
+
+    let mut value = serde_json::to_value(ast).unwrap_or_default();
+    strip(&mut value);
+    value
+}
+
+fn comments<'a>(tokens: &'a [SpannedToken], source: &'a str) -> Vec<&'a str> {
+    tokens
+        .iter()
+        .filter(|spanned| matches!(spanned.value, Token::Comment(_)))
+        .map(|spanned| spanned.span.slice(source).trim_end())
+        .collect()
+}
+
+/// The significant tokens of one source line and its trailing comment
+#[derive(Default)]
+struct SourceLine<'a> {
+    tokens: Vec<&'a SpannedToken>,
+    comment: Option<&'a str>,
+}
+
+impl SourceLine<'_> {
+    fn is_blank(&self) -> bool {
+        self.tokens.is_empty() && self.comment.is_none()
+    }
+}
+
+fn source_lines<'a>(tokens: &'a [SpannedToken], source: &'a str) -> Vec<SourceLine<'a>> {
+    let mut lines = Vec::new();
+    let mut line = SourceLine::default();
+    for spanned in tokens {
+        match &spanned.value {
+            Token::Space | Token::Tab | Token::Eof => {}
+            Token::Newline => lines.push(std::mem::take(&mut line)),
+            Token::Comment(_) => line.comment = Some(spanned.span.slice(source).trim_end()),
+            _ => line.tokens.push(spanned),
+        }
+    }
+    if !line.is_blank() {
+        lines.push(line);
+    }
+    lines
+}
+
+fn layout(tokens: &[SpannedToken], source: &str) -> String {
+    let mut output = String::new();
+    let mut depth = 0usize;
+    let mut blank_pending = false;
+    let mut opened_block = false;
+
+    for line in source_lines(tokens, source) {
+        if line.is_blank() {
+            blank_pending = !output.is_empty();
+            continue;
+        }
+
+        let statements = split_statements(&line.tokens);
+        let count = statements.len().max(1);
+        for index in 0..count {
+            let statement = statements.get(index).copied().unwrap_or_default();
+            let first = statement.first().map(|spanned| &spanned.value);
+            let closes = matches!(first, Some(Token::Keyword(k)) if k.is_block_end());
+            if closes {
+                depth = depth.saturating_sub(1);
+            }
+            if blank_pending && !opened_block && !closes {
+                output.push('\n');
+            }
+            blank_pending = false;
+
+            output.push_str(&INDENT.repeat(depth));
+            output.push_str(&join(statement, source));
+            if let Some(comment) = line.comment.filter(|_| index == count - 1) {
+                if !statement.is_empty() {
+                    output.push(' ');
+                }
+                output.push_str(comment);
+            }
+            output.push('\n');
+
+            opened_block = matches!(first, Some(Token::Keyword(k)) if k.is_block_start());
+            if opened_block {
+                depth += 1;
+            }
+        }
+    }
+    output
+}
+
+/// Break a line holding several statements before each block end, block
+/// header or line-leading statement, and after a block header's operands.
+/// Statements that can only be told apart by the grammar stay together
+fn split_statements<'t, 'a>(tokens: &'t [&'a SpannedToken]) -> Vec<&'t [&'a SpannedToken]> {
+    let mut statements = Vec::new();
+    let mut start = 0;
+    let mut index = 0;
+    while index < tokens.len() {
+        if index > start && starts_statement(&tokens[index].value) {
+            statements.push(&tokens[start..index]);
+            start = index;
+        }
+        if index == start {
+            if let Some(operands) = header_operands(&tokens[index..]) {
+                index = (index + 1 + operands).min(tokens.len());
+                if index < tokens.len() {
+                    statements.push(&tokens[start..index]);
+                    start = index;
+                }
+                continue;
+            }
+        }
+        index += 1;
+    }
+    if start < tokens.len() {
+        statements.push(&tokens[start..]);
+    }
+    statements
+}
+
+/// Keywords that only ever begin a statement. Lowercase block keywords are
+/// left out: `record` is also a data type
+fn starts_statement(kind: &Token) -> bool {
+    match kind {
+        Token::Keyword(keyword) => {
+            keyword.is_block_end()
+                || matches!(
+                    keyword,
+                    Keyword::Meta
+                        | Keyword::Def
+                        | Keyword::Cri
+                        | Keyword::CriRoot
+                        | Keyword::Import
+                        | Keyword::Ctn
+                        | Keyword::State
+                        | Keyword::Object
+                        | Keyword::Run
+                        | Keyword::Filter
+                        | Keyword::Set
+                        | Keyword::Test
+                        | Keyword::StateRef
+                        | Keyword::ObjectRef
+                        | Keyword::SetRef
+                        | Keyword::Control
+                        | Keyword::Severity
+                )
+        }
+        _ => false,
+    }
+}
+
+/// How many tokens after a block header keyword belong on its line
+fn header_operands(tokens: &[&SpannedToken]) -> Option<usize> {
+    let Token::Keyword(keyword) = &tokens[0].value else {
+        return None;
+    };
+    let operand = |n: usize| tokens.get(n).map(|spanned| &spanned.value);
+    match keyword {
+        Keyword::Meta | Keyword::Def => Some(0),
+        Keyword::Ctn | Keyword::State | Keyword::Object => Some(1),
+        Keyword::Run | Keyword::Set => Some(2),
+        Keyword::Cri | Keyword::CriRoot => match operand(2) {
+            Some(Token::Boolean(true)) => Some(2),
+            _ => Some(1),
+        },
+        Keyword::Filter => match operand(1) {
+            Some(Token::Keyword(Keyword::Include | Keyword::Exclude)) => Some(1),
+            _ => Some(0),
+        },
+        _ => None,
+    }
+}
+
+fn join(tokens: &[&SpannedToken], source: &str) -> String {
+    let mut text = String::new();
+    let mut previous: Option<&Token> = None;
+    for spanned in tokens {
+        if previous.is_some_and(|previous| spaced(previous, &spanned.value)) {
+            text.push(' ');
+        }
+        text.push_str(&token_text(spanned, source));
+        previous = Some(&spanned.value);
+    }
+    text
+}
+
+/// Whether a space separates two adjacent tokens on a line
+fn spaced(previous: &Token, next: &Token) -> bool {
+    !matches!(
+        previous,
+        Token::Dot | Token::At | Token::LeftBracket | Token::LeftParen
+    ) && !matches!(
+        next,
+        Token::Dot | Token::Comma | Token::RightBracket | Token::LeftParen | Token::RightParen
+    )
+}
+
+/// Literals and names as written; keywords and operators canonically spelled
+fn token_text<'a>(spanned: &SpannedToken, source: &'a str) -> Cow<'a, str> {
+    match &spanned.value {
+        Token::Identifier(_)
+        | Token::StringLiteral(_)
+        | Token::Integer(_)
+        | Token::Float(_)
+        | Token::Boolean(_) => Cow::Borrowed(spanned.span.slice(source)),
+        other => Cow::Owned(other.as_esp_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format(source: &str) -> String {
+        let _ = crate::logging::init_global_logging();
+        format_source(source).unwrap()
+    }
+
+    #[test]
+    fn test_indents_blocks_and_normalizes_spacing() {
+        let source = "\
+META
+version `1.0.0`
+META_END
+DEF
+OBJECT   cfg
+\tpath  `/etc/app.conf`
+  OBJECT_END
+STATE ok
+    mode int   ==  420
+STATE_END
+CRI AND
+CTN file_metadata
+TEST all all
+STATE_REF ok
+OBJECT_REF cfg
+CTN_END
+CRI_END
+DEF_END
+";
+        let expected = "\
+META
+    version `1.0.0`
+META_END
+DEF
+    OBJECT cfg
+        path `/etc/app.conf`
+    OBJECT_END
+    STATE ok
+        mode int = 420
+    STATE_END
+    CRI AND
+        CTN file_metadata
+            TEST all all
+            STATE_REF ok
+            OBJECT_REF cfg
+        CTN_END
+    CRI_END
+DEF_END
+";
+        assert_eq!(format(source), expected);
+        assert_eq!(format(expected), expected);
+    }
+
+    #[test]
+    fn test_statements_sharing_a_line_are_split() {
+        let source = "\
+DEF
+STATE ok exists boolean = true STATE_END
+CRI AND true CTN file_metadata TEST all all STATE_REF ok CTN_END CRI_END
+DEF_END
+";
+        let expected = "\
+DEF
+    STATE ok
+        exists boolean = true
+    STATE_END
+    CRI AND true
+        CTN file_metadata
+            TEST all all
+            STATE_REF ok
+        CTN_END
+    CRI_END
+DEF_END
+";
+        assert_eq!(format(source), expected);
+    }
+
+    #[test]
+    fn test_comments_kept_and_blank_lines_collapsed() {
+        let source = "
+
+# Policy header
+DEF
+
+
+    # States
+    STATE ok   # trailing
+
+        exists boolean = true
+    STATE_END
+
+
+
+    CRI AND
+        CTN file_metadata
+            TEST all all
+            STATE_REF ok
+
+        CTN_END
+    CRI_END
+DEF_END
+
+
+";
+        let expected = "\
+# Policy header
+DEF
+    # States
+    STATE ok # trailing
+        exists boolean = true
+    STATE_END
+
+    CRI AND
+        CTN file_metadata
+            TEST all all
+            STATE_REF ok
+        CTN_END
+    CRI_END
+DEF_END
+";
+        assert_eq!(format(source), expected);
+    }
+
+    #[test]
+    fn test_literals_and_punctuation() {
+        let source = "\
+DEF
+    VAR ratio float 1.50
+    STATE s
+        text string = ```first
+  indented ```
+        users string equals_any [ `a` , `b` ]
+        note string = r```raw ``` at_least ( 2 )
+    STATE_END
+    CRI AND
+        CTN xml_file
+            TEST all all
+            STATE_REF s
+        CTN_END
+    CRI_END
+DEF_END
+";
+        let formatted = format(source);
+        assert!(formatted.contains("    VAR ratio float 1.50\n"));
+        assert!(formatted.contains("= ```first\n  indented ```\n"));
+        assert!(formatted.contains("equals_any [`a`, `b`]\n"));
+        assert!(formatted.contains("r```raw ``` at_least(2)\n"));
+        assert_eq!(format(&formatted), formatted);
+    }
+
+    #[test]
+    fn test_import_files_without_criteria() {
+        let source = "DEF\nSTATE present\nexists boolean = true\nSTATE_END\nDEF_END\n";
+        assert_eq!(
+            format(source),
+            "DEF\n    STATE present\n        exists boolean = true\n    STATE_END\nDEF_END\n"
+        );
+    }
+
+    #[test]
+    fn test_syntax_errors_are_not_formatted() {
+        let _ = crate::logging::init_global_logging();
+        let error = format_source("DEF\n    STATE\nDEF_END\n").unwrap_err();
+        assert!(matches!(error, FormatError::Syntax(ref errors) if !errors.is_empty()));
+    }
+
+    #[test]
+    fn test_sample_policies_format_idempotently() {
+        let samples =
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../esp_scanner_sdk/esp");
+        let mut formatted_count = 0;
+        for entry in std::fs::read_dir(samples).unwrap() {
+            let path = entry.unwrap().path();
+            let source = std::fs::read_to_string(&path).unwrap();
+            let _ = crate::logging::init_global_logging();
+            match format_source(&source) {
+                Ok(formatted) => {
+                    assert_eq!(format(&formatted), formatted, "{}", path.display());
+                    formatted_count += 1;
+                }
+                Err(FormatError::Unsafe(reason)) => {
+                    panic!("{}: output {}", path.display(), reason)
+                }
+                // Samples exercising lexer and parser errors
+                Err(_) => {}
+            }
+        }
+        assert!(
+            formatted_count > 20,
+            "only {} samples formatted",
+            formatted_count
+        );
+    }
+}
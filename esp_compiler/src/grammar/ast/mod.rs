@@ -1,4 +1,6 @@
 pub mod nodes;
+pub mod version;
 
 // Re-export all node types for convenience
 pub use nodes::*;
+pub use version::{AstDocument, AST_VERSION, AST_VERSION_MAJOR, AST_VERSION_MINOR};
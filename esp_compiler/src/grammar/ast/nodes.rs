@@ -10,7 +10,7 @@
 //! - Parser ready: Structures that parser can directly populate
 //! - Serde compatible: Full serialization support for FFI consumption
 
-use crate::utils::Span;
+use crate::tokens::Span;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
@@ -33,6 +33,13 @@ pub enum DataType {
     RecordData,
     Version,
     EvrString,
+    /// IPv4 or IPv6 address, matched against CIDR networks
+    IpAddress,
+    /// File mode bits, written in octal (`0640`)
+    Octal,
+    /// Instant in time: RFC 3339, epoch seconds, or relative to the scan
+    /// (`now-30d`)
+    DateTime,
 }
 
 impl DataType {
@@ -47,10 +54,28 @@ impl DataType {
             "record_data" => Some(Self::RecordData),
             "version" => Some(Self::Version),
             "evr_string" => Some(Self::EvrString),
+            "ip_address" => Some(Self::IpAddress),
+            "octal" => Some(Self::Octal),
+            "datetime" => Some(Self::DateTime),
             _ => None,
         }
     }
 
+    /// Every variant, in canonical order
+    pub const ALL: &'static [Self] = &[
+        Self::String,
+        Self::Int,
+        Self::Float,
+        Self::Boolean,
+        Self::Binary,
+        Self::RecordData,
+        Self::Version,
+        Self::EvrString,
+        Self::IpAddress,
+        Self::Octal,
+        Self::DateTime,
+    ];
+
     pub fn as_str(&self) -> &'static str {
         match self {
             Self::String => "string",
@@ -61,10 +86,50 @@ impl DataType {
             Self::RecordData => "record_data",
             Self::Version => "version",
             Self::EvrString => "evr_string",
+            Self::IpAddress => "ip_address",
+            Self::Octal => "octal",
+            Self::DateTime => "datetime",
         }
     }
 }
 
+// === SPELLING ALIASES ===
+// Policy authors coming from other tools write `EQUALS`, `eq` or `==`. The
+// `parse` methods stay exact; `parse_lenient` also accepts any casing of the
+// canonical name and the documented alias table, and `parse_or_error` lists
+// the canonical spellings when nothing matches. Serialization always uses the
+// canonical `as_str` form.
+
+/// Resolve a spelling against canonical names (any case) and an alias table
+fn resolve_spelling<T: Copy>(
+    s: &str,
+    all: &[T],
+    name: fn(&T) -> &'static str,
+    aliases: &[(&str, T)],
+) -> Option<T> {
+    all.iter()
+        .find(|v| name(v).eq_ignore_ascii_case(s))
+        .or_else(|| {
+            aliases
+                .iter()
+                .find(|(alias, _)| alias.eq_ignore_ascii_case(s))
+                .map(|(_, v)| v)
+        })
+        .copied()
+}
+
+/// "unknown operation 'x'; valid operations: =, !=, ..."
+fn unknown_spelling<T>(kind: &str, s: &str, all: &[T], name: fn(&T) -> &'static str) -> String {
+    let valid: Vec<&str> = all.iter().map(name).collect();
+    format!(
+        "unknown {} '{}'; valid {}s: {}",
+        kind,
+        s,
+        kind,
+        valid.join(", ")
+    )
+}
+
 // === OPERATIONS ===
 
 /// All operations available in ESP (EBNF: operation)
@@ -92,6 +157,33 @@ pub enum Operation {
     // Set operations
     SubsetOf,
     SupersetOf,
+    // Membership operations (value is a list literal)
+    EqualsAny,
+    NotEqualsAny,
+    // Measure operations on strings (compare a derived integer)
+    LengthEquals,
+    LengthGreaterThan,
+    /// Value is `[pattern, count]`: exactly `count` non-overlapping
+    /// occurrences of the literal `pattern`
+    OccurrenceCount,
+    // Version operations (RPM-style ordering on any version-like string)
+    VersionEquals,
+    VersionNotEqual,
+    VersionGreaterThan,
+    VersionLessThan,
+    VersionGreaterOrEqual,
+    VersionLessOrEqual,
+    /// Network operations: value is a CIDR, or a list of CIDRs matched if
+    /// the address is in any of them
+    InSubnet,
+    NotInSubnet,
+    /// Shell-style glob over the whole value: `*`, `?` and `[...]` classes
+    GlobMatch,
+    NotGlobMatch,
+    /// Mode mask operations: at most sets no bits outside the value, at
+    /// least sets every bit of it (setuid, setgid and sticky included)
+    PermissionsAtMost,
+    PermissionsAtLeast,
 }
 
 impl Operation {
@@ -116,10 +208,117 @@ impl Operation {
             "matches" => Some(Self::Matches),
             "subset_of" => Some(Self::SubsetOf),
             "superset_of" => Some(Self::SupersetOf),
+            "equals_any" => Some(Self::EqualsAny),
+            "not_equals_any" => Some(Self::NotEqualsAny),
+            "length_equals" => Some(Self::LengthEquals),
+            "length_greater_than" => Some(Self::LengthGreaterThan),
+            "occurrence_count" => Some(Self::OccurrenceCount),
+            "version_equals" => Some(Self::VersionEquals),
+            "version_not_equal" => Some(Self::VersionNotEqual),
+            "version_greater_than" => Some(Self::VersionGreaterThan),
+            "version_less_than" => Some(Self::VersionLessThan),
+            "version_greater_or_equal" => Some(Self::VersionGreaterOrEqual),
+            "version_less_or_equal" => Some(Self::VersionLessOrEqual),
+            "in_subnet" => Some(Self::InSubnet),
+            "not_in_subnet" => Some(Self::NotInSubnet),
+            "glob_match" => Some(Self::GlobMatch),
+            "not_glob_match" => Some(Self::NotGlobMatch),
+            "permissions_at_most" => Some(Self::PermissionsAtMost),
+            "permissions_at_least" => Some(Self::PermissionsAtLeast),
             _ => None,
         }
     }
 
+    /// Every variant, in canonical order
+    pub const ALL: &'static [Self] = &[
+        Self::Equals,
+        Self::NotEqual,
+        Self::GreaterThan,
+        Self::LessThan,
+        Self::GreaterThanOrEqual,
+        Self::LessThanOrEqual,
+        Self::CaseInsensitiveEquals,
+        Self::CaseInsensitiveNotEqual,
+        Self::Contains,
+        Self::StartsWith,
+        Self::EndsWith,
+        Self::NotContains,
+        Self::NotStartsWith,
+        Self::NotEndsWith,
+        Self::PatternMatch,
+        Self::Matches,
+        Self::SubsetOf,
+        Self::SupersetOf,
+        Self::EqualsAny,
+        Self::NotEqualsAny,
+        Self::LengthEquals,
+        Self::LengthGreaterThan,
+        Self::OccurrenceCount,
+        Self::VersionEquals,
+        Self::VersionNotEqual,
+        Self::VersionGreaterThan,
+        Self::VersionLessThan,
+        Self::VersionGreaterOrEqual,
+        Self::VersionLessOrEqual,
+        Self::InSubnet,
+        Self::NotInSubnet,
+        Self::GlobMatch,
+        Self::NotGlobMatch,
+        Self::PermissionsAtMost,
+        Self::PermissionsAtLeast,
+    ];
+
+    /// Documented non-canonical spellings (matched case-insensitively)
+    pub const ALIASES: &'static [(&'static str, Self)] = &[
+        ("==", Self::Equals),
+        ("eq", Self::Equals),
+        ("equal", Self::Equals),
+        ("equals", Self::Equals),
+        ("<>", Self::NotEqual),
+        ("ne", Self::NotEqual),
+        ("neq", Self::NotEqual),
+        ("not_equal", Self::NotEqual),
+        ("not_equals", Self::NotEqual),
+        ("gt", Self::GreaterThan),
+        ("greater_than", Self::GreaterThan),
+        ("lt", Self::LessThan),
+        ("less_than", Self::LessThan),
+        ("ge", Self::GreaterThanOrEqual),
+        ("gte", Self::GreaterThanOrEqual),
+        ("greater_than_or_equal", Self::GreaterThanOrEqual),
+        ("le", Self::LessThanOrEqual),
+        ("lte", Self::LessThanOrEqual),
+        ("less_than_or_equal", Self::LessThanOrEqual),
+        ("iequals", Self::CaseInsensitiveEquals),
+        ("case_insensitive_equals", Self::CaseInsensitiveEquals),
+        ("inot_equals", Self::CaseInsensitiveNotEqual),
+        ("case_insensitive_not_equals", Self::CaseInsensitiveNotEqual),
+        ("equals_ignore_case", Self::CaseInsensitiveEquals),
+        ("not_equals_ignore_case", Self::CaseInsensitiveNotEqual),
+        ("starts_with", Self::StartsWith),
+        ("ends_with", Self::EndsWith),
+        ("not_starts_with", Self::NotStartsWith),
+        ("not_ends_with", Self::NotEndsWith),
+        ("regex", Self::PatternMatch),
+        ("subset", Self::SubsetOf),
+        ("superset", Self::SupersetOf),
+        ("in", Self::EqualsAny),
+        ("not_in", Self::NotEqualsAny),
+        ("glob", Self::GlobMatch),
+        ("not_glob", Self::NotGlobMatch),
+    ];
+
+    /// Parse accepting any casing of the canonical spelling or an alias
+    pub fn parse_lenient(s: &str) -> Option<Self> {
+        Self::parse(s).or_else(|| resolve_spelling(s, Self::ALL, Self::as_str, Self::ALIASES))
+    }
+
+    /// Lenient parse with an error naming the valid spellings
+    pub fn parse_or_error(s: &str) -> Result<Self, String> {
+        Self::parse_lenient(s)
+            .ok_or_else(|| unknown_spelling("operation", s, Self::ALL, Self::as_str))
+    }
+
     pub fn as_str(&self) -> &'static str {
         match self {
             Self::Equals => "=",
@@ -140,8 +339,55 @@ impl Operation {
             Self::Matches => "matches",
             Self::SubsetOf => "subset_of",
             Self::SupersetOf => "superset_of",
+            Self::EqualsAny => "equals_any",
+            Self::NotEqualsAny => "not_equals_any",
+            Self::LengthEquals => "length_equals",
+            Self::LengthGreaterThan => "length_greater_than",
+            Self::OccurrenceCount => "occurrence_count",
+            Self::VersionEquals => "version_equals",
+            Self::VersionNotEqual => "version_not_equal",
+            Self::VersionGreaterThan => "version_greater_than",
+            Self::VersionLessThan => "version_less_than",
+            Self::VersionGreaterOrEqual => "version_greater_or_equal",
+            Self::VersionLessOrEqual => "version_less_or_equal",
+            Self::InSubnet => "in_subnet",
+            Self::NotInSubnet => "not_in_subnet",
+            Self::GlobMatch => "glob_match",
+            Self::NotGlobMatch => "not_glob_match",
+            Self::PermissionsAtMost => "permissions_at_most",
+            Self::PermissionsAtLeast => "permissions_at_least",
         }
     }
+
+    /// Whether this is one of the `version_*` operations
+    pub fn is_version_comparison(&self) -> bool {
+        matches!(
+            self,
+            Self::VersionEquals
+                | Self::VersionNotEqual
+                | Self::VersionGreaterThan
+                | Self::VersionLessThan
+                | Self::VersionGreaterOrEqual
+                | Self::VersionLessOrEqual
+        )
+    }
+
+    /// Whether the operation takes a whole value list as its operand
+    ///
+    /// Any other operation compared against a list-valued variable is applied
+    /// to each list item and combined by the field's entity check.
+    pub fn takes_value_list(&self) -> bool {
+        matches!(
+            self,
+            Self::EqualsAny
+                | Self::NotEqualsAny
+                | Self::OccurrenceCount
+                | Self::InSubnet
+                | Self::NotInSubnet
+                | Self::SubsetOf
+                | Self::SupersetOf
+        )
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -171,6 +417,23 @@ impl LogicalOp {
         }
     }
 
+    /// Every variant, in canonical order
+    pub const ALL: &'static [Self] = &[Self::And, Self::Or];
+
+    /// Documented non-canonical spellings (matched case-insensitively)
+    pub const ALIASES: &'static [(&'static str, Self)] = &[("&&", Self::And), ("||", Self::Or)];
+
+    /// Parse accepting any casing of the canonical spelling or an alias
+    pub fn parse_lenient(s: &str) -> Option<Self> {
+        Self::parse(s).or_else(|| resolve_spelling(s, Self::ALL, Self::as_str, Self::ALIASES))
+    }
+
+    /// Lenient parse with an error naming the valid spellings
+    pub fn parse_or_error(s: &str) -> Result<Self, String> {
+        Self::parse_lenient(s)
+            .ok_or_else(|| unknown_spelling("logical operator", s, Self::ALL, Self::as_str))
+    }
+
     /// Get the operator as it appears in ESP source
     pub fn as_str(&self) -> &'static str {
         match self {
@@ -234,6 +497,32 @@ impl ExistenceCheck {
         }
     }
 
+    /// Every variant, in canonical order
+    pub const ALL: &'static [Self] = &[
+        Self::Any,
+        Self::All,
+        Self::None,
+        Self::AtLeastOne,
+        Self::OnlyOne,
+    ];
+
+    /// Documented non-canonical spellings (matched case-insensitively)
+    pub const ALIASES: &'static [(&'static str, Self)] = &[
+        ("one_or_more", Self::AtLeastOne),
+        ("exactly_one", Self::OnlyOne),
+    ];
+
+    /// Parse accepting any casing of the canonical spelling or an alias
+    pub fn parse_lenient(s: &str) -> Option<Self> {
+        Self::parse(s).or_else(|| resolve_spelling(s, Self::ALL, Self::as_str, Self::ALIASES))
+    }
+
+    /// Lenient parse with an error naming the valid spellings
+    pub fn parse_or_error(s: &str) -> Result<Self, String> {
+        Self::parse_lenient(s)
+            .ok_or_else(|| unknown_spelling("existence check", s, Self::ALL, Self::as_str))
+    }
+
     /// Get the check as it appears in ESP source
     pub fn as_str(&self) -> &'static str {
         match self {
@@ -267,6 +556,32 @@ impl ItemCheck {
         }
     }
 
+    /// Every variant, in canonical order
+    pub const ALL: &'static [Self] = &[
+        Self::All,
+        Self::AtLeastOne,
+        Self::OnlyOne,
+        Self::NoneSatisfy,
+    ];
+
+    /// Documented non-canonical spellings (matched case-insensitively)
+    pub const ALIASES: &'static [(&'static str, Self)] = &[
+        ("one_or_more", Self::AtLeastOne),
+        ("exactly_one", Self::OnlyOne),
+        ("none_exist", Self::NoneSatisfy),
+    ];
+
+    /// Parse accepting any casing of the canonical spelling or an alias
+    pub fn parse_lenient(s: &str) -> Option<Self> {
+        Self::parse(s).or_else(|| resolve_spelling(s, Self::ALL, Self::as_str, Self::ALIASES))
+    }
+
+    /// Lenient parse with an error naming the valid spellings
+    pub fn parse_or_error(s: &str) -> Result<Self, String> {
+        Self::parse_lenient(s)
+            .ok_or_else(|| unknown_spelling("item check", s, Self::ALL, Self::as_str))
+    }
+
     /// Get the check as it appears in ESP source
     pub fn as_str(&self) -> &'static str {
         match self {
@@ -285,6 +600,10 @@ pub enum EntityCheck {
     AtLeastOne, // at_least_one
     None,       // none
     OnlyOne,    // only_one
+    /// At least N entities pass (`at_least(N)`, N >= 1)
+    AtLeastN(u32),
+    /// At least P percent of entities pass (`at_least_percent(P)`, 1..=100)
+    AtLeastPercent(u8),
 }
 
 impl EntityCheck {
@@ -295,21 +614,81 @@ impl EntityCheck {
             "at_least_one" => Some(Self::AtLeastOne),
             "none" => Some(Self::None),
             "only_one" => Some(Self::OnlyOne),
+            _ => Self::parse_counted(s),
+        }
+    }
+
+    /// Parse `at_least(N)` or `at_least_percent(P)`
+    fn parse_counted(s: &str) -> Option<Self> {
+        let (name, argument) = s.strip_suffix(')')?.split_once('(')?;
+        let argument = argument.trim();
+        match name.trim() {
+            "at_least" => Self::at_least(argument.parse().ok()?),
+            "at_least_percent" => Self::at_least_percent(argument.parse().ok()?),
             _ => None,
         }
     }
 
-    /// Get the check as it appears in ESP source
+    /// `at_least(n)`; a count of zero is rejected
+    pub fn at_least(n: u32) -> Option<Self> {
+        (n >= 1).then_some(Self::AtLeastN(n))
+    }
+
+    /// `at_least_percent(p)`; only 1 through 100 are accepted
+    pub fn at_least_percent(p: u32) -> Option<Self> {
+        u8::try_from(p)
+            .ok()
+            .filter(|p| (1..=100).contains(p))
+            .map(Self::AtLeastPercent)
+    }
+
+    /// Every keyword variant, in canonical order (the counted forms take an argument)
+    pub const ALL: &'static [Self] = &[Self::All, Self::AtLeastOne, Self::None, Self::OnlyOne];
+
+    /// Documented non-canonical spellings (matched case-insensitively)
+    pub const ALIASES: &'static [(&'static str, Self)] = &[
+        ("one_or_more", Self::AtLeastOne),
+        ("exactly_one", Self::OnlyOne),
+    ];
+
+    /// Parse accepting any casing of the canonical spelling or an alias
+    pub fn parse_lenient(s: &str) -> Option<Self> {
+        Self::parse(s)
+            .or_else(|| resolve_spelling(s, Self::ALL, Self::as_str, Self::ALIASES))
+            .or_else(|| Self::parse_counted(&s.to_ascii_lowercase()))
+    }
+
+    /// Lenient parse with an error naming the valid spellings
+    pub fn parse_or_error(s: &str) -> Result<Self, String> {
+        Self::parse_lenient(s).ok_or_else(|| {
+            format!(
+                "{}, at_least(N) or at_least_percent(P)",
+                unknown_spelling("entity check", s, Self::ALL, Self::as_str)
+            )
+        })
+    }
+
+    /// Get the check keyword as it appears in ESP source (without any count)
     pub fn as_str(&self) -> &'static str {
         match self {
             Self::All => "all",
             Self::AtLeastOne => "at_least_one",
             Self::None => "none",
             Self::OnlyOne => "only_one",
+            Self::AtLeastN(_) => "at_least",
+            Self::AtLeastPercent(_) => "at_least_percent",
         }
     }
 }
 
+impl std::str::FromStr for EntityCheck {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_or_error(s)
+    }
+}
+
 // === VALUES ===
 
 /// Value specification as per EBNF (value_spec)
@@ -325,6 +704,8 @@ pub enum Value {
     Boolean(bool),
     /// Variable reference (VAR variable_name)
     Variable(Identifier),
+    /// Value list (`[value, value, ...]`) for membership operations
+    List(Vec<Value>),
 }
 
 impl Value {
@@ -357,6 +738,11 @@ impl Value {
     pub fn is_variable(&self) -> bool {
         matches!(self, Self::Variable(_))
     }
+
+    /// Check if this is a value list
+    pub fn is_list(&self) -> bool {
+        matches!(self, Self::List(_))
+    }
 }
 
 /// Field path for record datatypes (EBNF: field_path)
@@ -406,6 +792,9 @@ pub enum ArithmeticOperator {
     Subtract, // -
     Divide,   // /
     Modulus,  // %
+    Min,      // min
+    Max,      // max
+    Abs,      // abs (no operand)
 }
 
 impl ArithmeticOperator {
@@ -417,10 +806,18 @@ impl ArithmeticOperator {
             "-" => Some(Self::Subtract),
             "/" => Some(Self::Divide),
             "%" => Some(Self::Modulus),
+            "min" => Some(Self::Min),
+            "max" => Some(Self::Max),
+            "abs" => Some(Self::Abs),
             _ => None,
         }
     }
 
+    /// Whether the operator is followed by an operand value
+    pub fn takes_operand(&self) -> bool {
+        !matches!(self, Self::Abs)
+    }
+
     /// Get the operator as it appears in ESP source
     pub fn as_str(&self) -> &'static str {
         match self {
@@ -429,6 +826,9 @@ impl ArithmeticOperator {
             Self::Subtract => "-",
             Self::Divide => "/",
             Self::Modulus => "%",
+            Self::Min => "min",
+            Self::Max => "max",
+            Self::Abs => "abs",
         }
     }
 }
@@ -453,8 +853,9 @@ pub enum RunParameter {
     StartPosition(i64),
     /// Length value (length space integer_value)
     Length(i64),
-    /// Arithmetic operation with operand (+ value, * value, etc.)
-    ArithmeticOp(ArithmeticOperator, Value),
+    /// Arithmetic operation with operand (+ value, min value, etc.);
+    /// `abs` has no operand
+    ArithmeticOp(ArithmeticOperator, Option<Value>),
 }
 
 impl RunParameter {
@@ -488,7 +889,12 @@ impl RunParameter {
 
     /// Create an arithmetic operation parameter with value
     pub fn arithmetic_op(operator: ArithmeticOperator, value: Value) -> Self {
-        Self::ArithmeticOp(operator, value)
+        Self::ArithmeticOp(operator, Some(value))
+    }
+
+    /// Create the operand-less `abs` parameter
+    pub fn absolute_value() -> Self {
+        Self::ArithmeticOp(ArithmeticOperator::Abs, None)
     }
 }
 
@@ -579,6 +985,11 @@ pub enum RuntimeOperationType {
     End,          // END
     Merge,        // MERGE
     Extract,      // EXTRACT
+    Replace,      // REPLACE
+    Length,       // LENGTH
+    Uppercase,    // UPPERCASE
+    Lowercase,    // LOWERCASE
+    Trim,         // TRIM
 }
 
 impl RuntimeOperationType {
@@ -595,10 +1006,34 @@ impl RuntimeOperationType {
             "END" => Some(Self::End),
             "MERGE" => Some(Self::Merge),
             "EXTRACT" => Some(Self::Extract),
+            "REPLACE" => Some(Self::Replace),
+            "LENGTH" => Some(Self::Length),
+            "UPPERCASE" => Some(Self::Uppercase),
+            "LOWERCASE" => Some(Self::Lowercase),
+            "TRIM" => Some(Self::Trim),
             _ => None,
         }
     }
 
+    /// Every variant, in canonical order
+    pub const ALL: &'static [Self] = &[
+        Self::Concat,
+        Self::Split,
+        Self::Substring,
+        Self::RegexCapture,
+        Self::Arithmetic,
+        Self::Count,
+        Self::Unique,
+        Self::End,
+        Self::Merge,
+        Self::Extract,
+        Self::Replace,
+        Self::Length,
+        Self::Uppercase,
+        Self::Lowercase,
+        Self::Trim,
+    ];
+
     /// Get the operation as it appears in ESP source
     pub fn as_str(&self) -> &'static str {
         match self {
@@ -612,6 +1047,11 @@ impl RuntimeOperationType {
             Self::End => "END",
             Self::Merge => "MERGE",
             Self::Extract => "EXTRACT",
+            Self::Replace => "REPLACE",
+            Self::Length => "LENGTH",
+            Self::Uppercase => "UPPERCASE",
+            Self::Lowercase => "LOWERCASE",
+            Self::Trim => "TRIM",
         }
     }
 }
@@ -697,6 +1137,24 @@ pub struct DefinitionNode {
     pub set_operations: Vec<SetOperation>,
     /// Criteria blocks (required - at least one)
     pub criteria: Vec<CriteriaNode>,
+    /// How the criteria blocks combine (AND when absent)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub criteria_root: Option<CriteriaRootNode>,
+    /// Files imported into this definition; the pipeline merges their
+    /// declarations and empties this list before the AST is emitted
+    #[serde(skip)]
+    pub imports: Vec<ImportNode>,
+    /// Source location information
+    #[serde(skip)]
+    pub span: Option<Span>,
+}
+
+/// Import directive node
+/// EBNF: import ::= "IMPORT" space backtick_string statement_end
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImportNode {
+    /// Path of the imported file, relative to the importing file
+    pub path: String,
     /// Source location information
     #[serde(skip)]
     pub span: Option<Span>,
@@ -904,6 +1362,19 @@ pub struct CriteriaNode {
     pub span: Option<Span>,
 }
 
+/// Top-level criteria directive (CRI_ROOT)
+/// EBNF: criteria_root ::= "CRI_ROOT" space logical_operator space? negate_flag? statement_end
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CriteriaRootNode {
+    /// Logical operator combining the top-level CRI blocks
+    pub logical_op: LogicalOp,
+    /// Whether to negate the combined result
+    pub negate: bool,
+    /// Source location information
+    #[serde(skip)]
+    pub span: Option<Span>,
+}
+
 /// Criteria content types
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum CriteriaContent {
@@ -929,6 +1400,12 @@ pub struct CriterionNode {
     pub local_states: Vec<StateDefinition>,
     /// Local object (CTN-level, non-referenceable)
     pub local_object: Option<ObjectDefinition>,
+    /// Benchmark controls this criterion implements
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub controls: Vec<ControlRef>,
+    /// Severity of a failure of this criterion, overriding the META severity
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub severity: Option<Identifier>,
     /// Source location information
     #[serde(skip)]
     pub span: Option<Span>,
@@ -973,6 +1450,22 @@ pub struct ObjectRef {
     pub span: Option<Span>,
 }
 
+/// Control reference node
+/// EBNF: control_reference ::= "CONTROL" space framework space string_literal (space string_literal)? statement_end
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ControlRef {
+    /// Framework the control belongs to, e.g. `CIS` or `STIG`
+    pub framework: Identifier,
+    /// Control identifier within the framework, e.g. `5.2.10`
+    pub control_id: String,
+    /// Link to the control's documentation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Source location information
+    #[serde(skip)]
+    pub span: Option<Span>,
+}
+
 /// Set reference node
 /// EBNF: set_reference ::= "SET_REF" space set_identifier statement_end
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -1029,6 +1522,38 @@ impl fmt::Display for ArithmeticOperator {
     }
 }
 
+impl fmt::Display for EntityCheck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::AtLeastN(n) => write!(f, "at_least({})", n),
+            Self::AtLeastPercent(p) => write!(f, "at_least_percent({})", p),
+            _ => write!(f, "{}", self.as_str()),
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::String(s) => write!(f, "`{}`", s),
+            Self::Integer(i) => write!(f, "{}", i),
+            Self::Float(fl) => write!(f, "{}", fl),
+            Self::Boolean(b) => write!(f, "{}", b),
+            Self::Variable(v) => write!(f, "VAR {}", v),
+            Self::List(items) => {
+                write!(f, "[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                write!(f, "]")
+            }
+        }
+    }
+}
+
 impl fmt::Display for FieldPath {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.components.join("."))
@@ -1038,17 +1563,7 @@ impl fmt::Display for FieldPath {
 impl fmt::Display for RunParameter {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Self::Literal(value) => write!(
-                f,
-                "literal {}",
-                match value {
-                    Value::String(s) => format!("`{}`", s),
-                    Value::Integer(i) => i.to_string(),
-                    Value::Float(fl) => fl.to_string(),
-                    Value::Boolean(b) => b.to_string(),
-                    Value::Variable(v) => format!("VAR {}", v),
-                }
-            ),
+            Self::Literal(value) => write!(f, "literal {}", value),
             Self::Variable(name) => write!(f, "VAR {}", name),
             Self::ObjectExtraction { object_id, field } => write!(f, "OBJ {} {}", object_id, field),
             Self::Pattern(pattern) => write!(f, "pattern `{}`", pattern),
@@ -1056,16 +1571,146 @@ impl fmt::Display for RunParameter {
             Self::Character(character) => write!(f, "character `{}`", character),
             Self::StartPosition(pos) => write!(f, "start {}", pos),
             Self::Length(len) => write!(f, "length {}", len),
-            Self::ArithmeticOp(op, value) => {
-                write!(f, "{} ", op)?;
-                match value {
-                    Value::String(s) => write!(f, "`{}`", s),
-                    Value::Integer(i) => write!(f, "{}", i),
-                    Value::Float(fl) => write!(f, "{}", fl),
-                    Value::Boolean(b) => write!(f, "{}", b),
-                    Value::Variable(v) => write!(f, "VAR {}", v),
-                }
+            Self::ArithmeticOp(op, Some(value)) => write!(f, "{} {}", op, value),
+            Self::ArithmeticOp(op, None) => write!(f, "{}", op),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_operation_aliases_canonicalize() {
+        for (alias, expected) in Operation::ALIASES {
+            assert_eq!(Operation::parse(alias), None, "{} is canonical", alias);
+            for spelling in [alias.to_string(), alias.to_ascii_uppercase()] {
+                let op = Operation::parse_lenient(&spelling).unwrap();
+                assert_eq!(op, *expected, "{}", spelling);
+                assert_eq!(Operation::parse(op.as_str()), Some(op));
             }
         }
+        for op in Operation::ALL {
+            assert_eq!(Operation::parse_lenient(op.as_str()), Some(*op));
+            let upper = op.as_str().to_ascii_uppercase();
+            assert_eq!(Operation::parse_lenient(&upper), Some(*op));
+        }
+        assert_eq!(Operation::parse_lenient("EQUALS"), Some(Operation::Equals));
+        assert_eq!(Operation::parse_lenient("eq"), Some(Operation::Equals));
+    }
+
+    #[test]
+    fn test_check_and_logical_aliases_canonicalize() {
+        fn check<T: Copy + PartialEq + fmt::Debug>(
+            all: &[T],
+            aliases: &[(&str, T)],
+            lenient: fn(&str) -> Option<T>,
+            name: fn(&T) -> &'static str,
+        ) {
+            for (alias, expected) in aliases {
+                assert_eq!(lenient(alias), Some(*expected), "{}", alias);
+                assert_eq!(lenient(&alias.to_ascii_uppercase()), Some(*expected));
+            }
+            for value in all {
+                assert_eq!(lenient(&name(value).to_ascii_lowercase()), Some(*value));
+                assert_eq!(lenient(&name(value).to_ascii_uppercase()), Some(*value));
+            }
+        }
+        check(
+            ExistenceCheck::ALL,
+            ExistenceCheck::ALIASES,
+            ExistenceCheck::parse_lenient,
+            ExistenceCheck::as_str,
+        );
+        check(
+            ItemCheck::ALL,
+            ItemCheck::ALIASES,
+            ItemCheck::parse_lenient,
+            ItemCheck::as_str,
+        );
+        check(
+            EntityCheck::ALL,
+            EntityCheck::ALIASES,
+            EntityCheck::parse_lenient,
+            EntityCheck::as_str,
+        );
+        check(
+            LogicalOp::ALL,
+            LogicalOp::ALIASES,
+            LogicalOp::parse_lenient,
+            LogicalOp::as_str,
+        );
+        assert_eq!(LogicalOp::parse_lenient("and").unwrap().as_str(), "AND");
+    }
+
+    #[test]
+    fn test_counted_entity_checks_round_trip() {
+        assert_eq!(
+            EntityCheck::parse("at_least(2)"),
+            Some(EntityCheck::AtLeastN(2))
+        );
+        assert_eq!(
+            "AT_LEAST_PERCENT( 90 )".parse::<EntityCheck>(),
+            Ok(EntityCheck::AtLeastPercent(90))
+        );
+        for rejected in [
+            "at_least(0)",
+            "at_least(-1)",
+            "at_least_percent(101)",
+            "at_least(2",
+        ] {
+            assert_eq!(EntityCheck::parse_lenient(rejected), None, "{}", rejected);
+        }
+
+        for check in [EntityCheck::AtLeastN(3), EntityCheck::AtLeastPercent(50)] {
+            assert_eq!(check.to_string().parse::<EntityCheck>(), Ok(check));
+            let json = serde_json::to_string(&check).unwrap();
+            assert_eq!(serde_json::from_str::<EntityCheck>(&json).unwrap(), check);
+        }
+        assert_eq!(
+            serde_json::to_string(&EntityCheck::AtLeastN(3)).unwrap(),
+            r#"{"AtLeastN":3}"#
+        );
+        assert_eq!(EntityCheck::All.to_string(), "all");
+    }
+
+    #[test]
+    fn test_unknown_spelling_lists_valid_operations() {
+        let err = Operation::parse_or_error("aprox_equals").unwrap_err();
+        assert!(err.starts_with("unknown operation 'aprox_equals'; valid operations: =, !=,"));
+        assert!(err.contains("not_equals_any"));
+
+        let err = ExistenceCheck::parse_or_error("some").unwrap_err();
+        assert_eq!(
+            err,
+            "unknown existence check 'some'; valid existence checks: any, all, none, at_least_one, only_one"
+        );
+    }
+
+    #[test]
+    fn test_arithmetic_operand_encoding() {
+        // Documents written before `abs` existed carry the operand as a bare value
+        let add: RunParameter =
+            serde_json::from_str(r#"{"ArithmeticOp":["Add",{"Integer":-10}]}"#).unwrap();
+        assert_eq!(
+            add,
+            RunParameter::arithmetic_op(ArithmeticOperator::Add, Value::Integer(-10))
+        );
+        assert_eq!(
+            serde_json::to_string(&add).unwrap(),
+            r#"{"ArithmeticOp":["Add",{"Integer":-10}]}"#
+        );
+
+        let abs: RunParameter = serde_json::from_str(r#"{"ArithmeticOp":["Abs",null]}"#).unwrap();
+        assert_eq!(abs, RunParameter::absolute_value());
+        assert_eq!(abs.to_string(), "abs");
+
+        for (spelling, takes_operand) in [("%", true), ("min", true), ("max", true), ("abs", false)]
+        {
+            let operator = ArithmeticOperator::parse(spelling).unwrap();
+            assert_eq!(operator.as_str(), spelling);
+            assert_eq!(operator.takes_operand(), takes_operand);
+        }
     }
 }
@@ -0,0 +1,81 @@
+//! # Versioned AST Contract
+//!
+//! The serialized AST is a contract between the compiler (producer) and any
+//! scanner that reads it back (consumer). Every JSON document carries an
+//! `ast_version` of the form `MAJOR.MINOR` so consumers can reject documents
+//! they cannot decode before touching the tree.
+//!
+//! ## Bump policy
+//!
+//! - **Minor** (`1.0` -> `1.1`): additive changes only — a new optional field,
+//!   a new `Operation` or `Value` variant. Existing documents still decode, and
+//!   consumers built against an older minor tolerate the newer one as long as
+//!   the new constructs are not used.
+//! - **Major** (`1.x` -> `2.0`): anything else — renamed or removed fields,
+//!   changed field types, restructured nodes. Consumers reject other majors.
+//!
+//! Every bump adds a frozen fixture under
+//! `esp_scanner_base/tests/fixtures/ast/` that the scanner must keep accepting.
+//!
+//! History:
+//! - `1.0` — initial versioned AST
+//! - `1.1` — value lists (`Value::List`) with `equals_any` / `not_equals_any`
+//! - `1.2` — `length_equals`, `length_greater_than`, `occurrence_count`
+//! - `1.3` — `CONTROL` references on CTN blocks (`CriterionNode::controls`)
+//! - `1.4` — `SEVERITY` on CTN blocks (`CriterionNode::severity`)
+//! - `1.5` — `version_*` operations (`version_greater_than`, ...)
+//! - `1.6` — `ip_address` data type with `in_subnet` / `not_in_subnet`
+//! - `1.7` — `glob_match` / `not_glob_match` operations
+//! - `1.8` — `octal` data type with `permissions_at_most` / `permissions_at_least`
+//! - `1.9` — `datetime` data type
+//! - `1.10` — counted entity checks (`EntityCheck::AtLeastN`, `EntityCheck::AtLeastPercent`)
+//! - `1.11` — `REPLACE` runtime operation
+//! - `1.12` — `LENGTH` runtime operation
+//! - `1.13` — `UPPERCASE`, `LOWERCASE` and `TRIM` runtime operations
+//! - `1.14` — `min`, `max` and operand-less `abs` arithmetic operators (`ArithmeticOp` operand is now optional)
+//! - `1.15` — list-valued variables (`Value::List` as a `VAR` initial value)
+//! - `1.16` — `CRI_ROOT` directive for the top-level criteria (`DefinitionNode::criteria_root`)
+
+use super::nodes::EspFile;
+use serde::{Deserialize, Serialize};
+
+pub const AST_VERSION_MAJOR: u32 = 1;
+pub const AST_VERSION_MINOR: u32 = 16;
+
+/// Version emitted in every serialized AST document
+pub const AST_VERSION: &str = "1.16";
+
+/// Serialized AST with its contract version
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AstDocument {
+    pub ast_version: String,
+    pub ast: EspFile,
+}
+
+impl AstDocument {
+    /// Wrap an AST with the current contract version
+    pub fn new(ast: EspFile) -> Self {
+        Self {
+            ast_version: AST_VERSION.to_string(),
+            ast,
+        }
+    }
+
+    /// Pretty-printed JSON, `ast_version` first
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_version_string_matches_components() {
+        assert_eq!(
+            AST_VERSION,
+            format!("{}.{}", AST_VERSION_MAJOR, AST_VERSION_MINOR)
+        );
+    }
+}
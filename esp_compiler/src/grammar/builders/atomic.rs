@@ -7,13 +7,16 @@
 
 use crate::grammar::ast::nodes::*;
 use crate::grammar::keywords::Keyword;
+use crate::log_warning;
+use crate::tokens::Span;
 use crate::tokens::Token;
-use crate::utils::Span;
 
 /// Enhanced parser trait that builders expect
 pub trait Parser {
     // === BASIC NAVIGATION ===
     fn current_token(&self) -> Option<&Token>;
+    /// The significant token after the current one
+    fn peek_token(&self) -> Option<&Token>;
     fn advance(&mut self);
 
     // === EXPECTATION METHODS ===
@@ -25,6 +28,20 @@ pub trait Parser {
 
     // === SPAN REPORTING ===
     fn current_span(&self) -> Span;
+    /// Span from the token at `start` through the last token consumed, so a
+    /// node covers its own source rather than the token after it
+    fn span_since(&self, start: usize) -> Span;
+
+    // === ERROR RECOVERY ===
+    /// Position of the current token, marking where a block element starts
+    fn position(&self) -> usize;
+    /// Record a syntax error at the current token and carry on parsing
+    fn report_error(&mut self, error: String);
+    /// Return to `start` and skip the element found there: the whole block
+    /// for a block keyword, otherwise the rest of its line
+    fn skip_element(&mut self, start: usize);
+    /// Number of errors recorded so far
+    fn error_count(&self) -> usize;
 }
 
 // === VALUE BUILDERS ===
@@ -59,10 +76,90 @@ pub fn parse_value(parser: &mut dyn Parser) -> Result<Value, String> {
     }
 }
 
+/// Parse field_value ::= value_spec | value_list
+/// value_list ::= "[" direct_value ("," direct_value)* "]"
+///
+/// Value lists are the operand of the membership operations (`equals_any`,
+/// `not_equals_any`); elements must be literals so every one can be type-checked.
+pub fn parse_field_value(parser: &mut dyn Parser) -> Result<Value, String> {
+    if !matches!(parser.current_token(), Some(Token::LeftBracket)) {
+        return parse_value(parser);
+    }
+    parser.advance();
+
+    let mut items = Vec::new();
+    loop {
+        match parser.current_token() {
+            Some(Token::RightBracket) if items.is_empty() => {
+                return Err("Value list must contain at least one value".to_string());
+            }
+            Some(Token::Keyword(Keyword::Var)) => {
+                return Err("Value list elements must be literals, not VAR references".to_string());
+            }
+            Some(Token::LeftBracket) => {
+                return Err("Value lists cannot be nested".to_string());
+            }
+            _ => items.push(parse_value(parser)?),
+        }
+
+        match parser.current_token() {
+            Some(Token::Comma) => parser.advance(),
+            Some(Token::RightBracket) => {
+                parser.advance();
+                return Ok(Value::List(items));
+            }
+            Some(other) => {
+                return Err(format!(
+                    "Expected ',' or ']' in value list, found '{}'",
+                    other.as_esp_string()
+                ))
+            }
+            None => return Err("Unterminated value list, reached end of input".to_string()),
+        }
+    }
+}
+
+/// Parse a field value for a declared data type. The lexer reads `0640` as
+/// the decimal integer 640; for an `octal` field the digits are re-read in
+/// base 8, which leading zeros do not change
+pub fn parse_typed_field_value(
+    parser: &mut dyn Parser,
+    data_type: DataType,
+) -> Result<Value, String> {
+    let value = parse_field_value(parser)?;
+    if data_type == DataType::Octal {
+        octal_value(value)
+    } else {
+        Ok(value)
+    }
+}
+
+/// Re-read integer literals as octal; other values are left for the type
+/// checker to reject
+pub fn octal_value(value: Value) -> Result<Value, String> {
+    match value {
+        Value::Integer(digits) => i64::from_str_radix(&digits.to_string(), 8)
+            .map(Value::Integer)
+            .map_err(|_| {
+                format!(
+                    "'{}' is not an octal mode; use the digits 0-7, e.g. 0640",
+                    digits
+                )
+            }),
+        Value::List(items) => items
+            .into_iter()
+            .map(octal_value)
+            .collect::<Result<_, _>>()
+            .map(Value::List),
+        other => Ok(other),
+    }
+}
+
 // === DATA TYPE BUILDERS ===
 
 /// Parse data_type - NOW HANDLES DATA TYPES AS IDENTIFIERS
 /// data_type ::= "string" | "int" | "float" | "boolean" | "binary" | "record_data" | "version" | "evr_string"
+///             | "ip_address" | "octal" | "datetime"
 ///
 /// All data types are now identifiers, parsed semantically rather than lexically
 pub fn parse_data_type(parser: &mut dyn Parser) -> Result<DataType, String> {
@@ -77,8 +174,11 @@ pub fn parse_data_type(parser: &mut dyn Parser) -> Result<DataType, String> {
                 "record_data" => DataType::RecordData,
                 "version" => DataType::Version,
                 "evr_string" => DataType::EvrString,
+                "ip_address" => DataType::IpAddress,
+                "octal" => DataType::Octal,
+                "datetime" => DataType::DateTime,
                 _ => return Err(format!(
-                    "Unknown data type '{}'. Valid types: string, int, float, boolean, binary, record_data, version, evr_string",
+                    "Unknown data type '{}'. Valid types: string, int, float, boolean, binary, record_data, version, evr_string, ip_address, octal, datetime",
                     name
                 )),
             };
@@ -181,10 +281,113 @@ pub fn parse_operation(parser: &mut dyn Parser) -> Result<Operation, String> {
             Ok(Operation::SupersetOf)
         }
 
-        _ => Err("Expected operation symbol token".to_string()),
+        // Membership operations (dedicated symbol tokens)
+        Some(Token::EqualsAny) => {
+            parser.advance();
+            Ok(Operation::EqualsAny)
+        }
+        Some(Token::NotEqualsAny) => {
+            parser.advance();
+            Ok(Operation::NotEqualsAny)
+        }
+
+        // Measure operations (dedicated symbol tokens)
+        Some(Token::LengthEquals) => {
+            parser.advance();
+            Ok(Operation::LengthEquals)
+        }
+        Some(Token::LengthGreaterThan) => {
+            parser.advance();
+            Ok(Operation::LengthGreaterThan)
+        }
+        Some(Token::OccurrenceCount) => {
+            parser.advance();
+            Ok(Operation::OccurrenceCount)
+        }
+
+        // Version operations (dedicated symbol tokens)
+        Some(Token::VersionEquals) => {
+            parser.advance();
+            Ok(Operation::VersionEquals)
+        }
+        Some(Token::VersionNotEqual) => {
+            parser.advance();
+            Ok(Operation::VersionNotEqual)
+        }
+        Some(Token::VersionGreaterThan) => {
+            parser.advance();
+            Ok(Operation::VersionGreaterThan)
+        }
+        Some(Token::VersionLessThan) => {
+            parser.advance();
+            Ok(Operation::VersionLessThan)
+        }
+        Some(Token::VersionGreaterOrEqual) => {
+            parser.advance();
+            Ok(Operation::VersionGreaterOrEqual)
+        }
+        Some(Token::VersionLessOrEqual) => {
+            parser.advance();
+            Ok(Operation::VersionLessOrEqual)
+        }
+
+        // Network operations (dedicated symbol tokens)
+        Some(Token::InSubnet) => {
+            parser.advance();
+            Ok(Operation::InSubnet)
+        }
+        Some(Token::NotInSubnet) => {
+            parser.advance();
+            Ok(Operation::NotInSubnet)
+        }
+
+        // Glob operations (dedicated symbol tokens)
+        Some(Token::GlobMatch) => {
+            parser.advance();
+            Ok(Operation::GlobMatch)
+        }
+        Some(Token::NotGlobMatch) => {
+            parser.advance();
+            Ok(Operation::NotGlobMatch)
+        }
+
+        // Permission operations (dedicated symbol tokens)
+        Some(Token::PermissionsAtMost) => {
+            parser.advance();
+            Ok(Operation::PermissionsAtMost)
+        }
+        Some(Token::PermissionsAtLeast) => {
+            parser.advance();
+            Ok(Operation::PermissionsAtLeast)
+        }
+
+        // Non-canonical spellings (`EQUALS`, `eq`, `starts_with`, ...)
+        Some(Token::Identifier(word)) => {
+            let word = word.clone();
+            let operation = Operation::parse_or_error(&word)?;
+            warn_noncanonical_spelling(parser, "operation", &word, operation.as_str());
+            parser.advance();
+            Ok(operation)
+        }
+
+        Some(other) => Err(Operation::parse_or_error(&other.as_esp_string())
+            .err()
+            .unwrap_or_else(|| "Expected operation".to_string())),
+        None => Err("Expected operation, found end of input".to_string()),
     }
 }
 
+/// Style warning for an accepted but non-canonical keyword or operator spelling
+fn warn_noncanonical_spelling(parser: &dyn Parser, kind: &str, spelling: &str, canonical: &str) {
+    let span = parser.current_span();
+    log_warning!("Non-canonical spelling; use the canonical form",
+        "kind" => kind,
+        "spelling" => spelling,
+        "canonical" => canonical,
+        "line" => span.start().line
+    );
+}
+
 /// Parse arithmetic_operator using dedicated symbol tokens
 /// arithmetic_operator ::= "+" | "*" | "-" | "/" | "%"
 ///
@@ -241,6 +444,13 @@ pub fn parse_existence_check(parser: &mut dyn Parser) -> Result<ExistenceCheck,
             parser.advance();
             Ok(ExistenceCheck::OnlyOne)
         }
+        Some(Token::Identifier(word)) => {
+            let word = word.clone();
+            let check = ExistenceCheck::parse_or_error(&word)?;
+            warn_noncanonical_spelling(parser, "existence check", &word, check.as_str());
+            parser.advance();
+            Ok(check)
+        }
         _ => Err(
             "Expected existence check keyword (any, all, none, at_least_one, only_one)".to_string(),
         ),
@@ -272,6 +482,13 @@ pub fn parse_item_check(parser: &mut dyn Parser) -> Result<ItemCheck, String> {
             parser.advance();
             Ok(ItemCheck::NoneSatisfy)
         }
+        Some(Token::Identifier(word)) => {
+            let word = word.clone();
+            let check = ItemCheck::parse_or_error(&word)?;
+            warn_noncanonical_spelling(parser, "item check", &word, check.as_str());
+            parser.advance();
+            Ok(check)
+        }
         _ => Err(
             "Expected item check keyword (all, at_least_one, only_one, none_satisfy)".to_string(),
         ),
@@ -279,8 +496,12 @@ pub fn parse_item_check(parser: &mut dyn Parser) -> Result<ItemCheck, String> {
 }
 
 /// Parse entity_check ::= "all" | "at_least_one" | "none" | "only_one"
-/// Uses keyword tokens (unchanged - these remain structural keywords)
+///                        | "at_least(" integer ")" | "at_least_percent(" integer ")"
+/// Uses keyword tokens for the bare checks; the counted forms are identifiers
 pub fn parse_entity_check(parser: &mut dyn Parser) -> Result<EntityCheck, String> {
+    if at_counted_entity_check(parser) {
+        return parse_counted_entity_check(parser);
+    }
     match parser.current_token() {
         Some(Token::Keyword(Keyword::All)) => {
             parser.advance();
@@ -298,7 +519,63 @@ pub fn parse_entity_check(parser: &mut dyn Parser) -> Result<EntityCheck, String
             parser.advance();
             Ok(EntityCheck::OnlyOne)
         }
-        _ => Err("Expected entity check keyword (all, at_least_one, none, only_one)".to_string()),
+        _ => Err(
+            "Expected entity check (all, at_least_one, none, only_one, at_least(N), at_least_percent(P))"
+                .to_string(),
+        ),
+    }
+}
+
+/// Whether the parser is at `at_least(` or `at_least_percent(`
+///
+/// Both names are ordinary identifiers, so the parenthesis is what tells a
+/// counted check apart from a following field named `at_least`.
+pub fn at_counted_entity_check(parser: &dyn Parser) -> bool {
+    matches!(
+        parser.current_token(),
+        Some(Token::Identifier(name)) if name == "at_least" || name == "at_least_percent"
+    ) && matches!(parser.peek_token(), Some(Token::LeftParen))
+}
+
+/// Parse "at_least(" integer ")" | "at_least_percent(" integer ")"
+fn parse_counted_entity_check(parser: &mut dyn Parser) -> Result<EntityCheck, String> {
+    let name = parser.expect_identifier()?;
+    expect_punctuation(parser, Token::LeftParen)?;
+    let count = parser.expect_integer()?;
+    expect_punctuation(parser, Token::RightParen)?;
+
+    let argument = u32::try_from(count).ok();
+    if name == "at_least" {
+        argument
+            .and_then(EntityCheck::at_least)
+            .ok_or_else(|| format!("at_least count must be 1 or more, found {}", count))
+    } else {
+        argument
+            .and_then(EntityCheck::at_least_percent)
+            .ok_or_else(|| {
+                format!(
+                    "at_least_percent must be between 1 and 100, found {}",
+                    count
+                )
+            })
+    }
+}
+
+fn expect_punctuation(parser: &mut dyn Parser, expected: Token) -> Result<(), String> {
+    match parser.current_token() {
+        Some(found) if *found == expected => {
+            parser.advance();
+            Ok(())
+        }
+        Some(found) => Err(format!(
+            "Expected '{}', found '{}'",
+            expected.as_esp_string(),
+            found.as_esp_string()
+        )),
+        None => Err(format!(
+            "Expected '{}', reached end of input",
+            expected.as_esp_string()
+        )),
     }
 }
 
@@ -350,6 +627,13 @@ pub fn parse_logical_op(parser: &mut dyn Parser) -> Result<LogicalOp, String> {
             parser.advance();
             Ok(LogicalOp::Or)
         }
+        Some(Token::Identifier(word)) => {
+            let word = word.clone();
+            let op = LogicalOp::parse_or_error(&word)?;
+            warn_noncanonical_spelling(parser, "logical operator", &word, op.as_str());
+            parser.advance();
+            Ok(op)
+        }
         _ => Err("Expected logical operator (AND, OR)".to_string()),
     }
 }
@@ -400,6 +684,26 @@ pub fn parse_runtime_operation_type(
             parser.advance();
             Ok(RuntimeOperationType::Extract)
         }
+        Some(Token::Keyword(Keyword::Replace)) => {
+            parser.advance();
+            Ok(RuntimeOperationType::Replace)
+        }
+        Some(Token::Keyword(Keyword::Length)) => {
+            parser.advance();
+            Ok(RuntimeOperationType::Length)
+        }
+        Some(Token::Keyword(Keyword::Uppercase)) => {
+            parser.advance();
+            Ok(RuntimeOperationType::Uppercase)
+        }
+        Some(Token::Keyword(Keyword::Lowercase)) => {
+            parser.advance();
+            Ok(RuntimeOperationType::Lowercase)
+        }
+        Some(Token::Keyword(Keyword::Trim)) => {
+            parser.advance();
+            Ok(RuntimeOperationType::Trim)
+        }
         _ => Err("Expected runtime operation type".to_string()),
     }
 }
@@ -450,6 +754,9 @@ pub fn validate_unified_symbol_consistency() -> Result<(), String> {
         "record_data",
         "version",
         "evr_string",
+        "ip_address",
+        "octal",
+        "datetime",
     ];
     for data_type in &data_types {
         if crate::grammar::keywords::is_reserved_keyword(data_type) {
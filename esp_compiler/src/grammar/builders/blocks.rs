@@ -28,6 +28,9 @@ fn is_data_type_name(name: &str) -> bool {
             | "record_data"
             | "version"
             | "evr_string"
+            | "ip_address"
+            | "octal"
+            | "datetime"
     )
 }
 
@@ -37,6 +40,7 @@ fn is_data_type_name(name: &str) -> bool {
 
 /// Parse esp_file ::= metadata? definition
 pub fn parse_esp_file(parser: &mut dyn Parser) -> Result<EspFile, String> {
+    let start = parser.position();
     let metadata = if matches!(parser.current_token(), Some(Token::Keyword(Keyword::Meta))) {
         Some(parse_metadata_block(parser)?)
     } else {
@@ -48,12 +52,54 @@ pub fn parse_esp_file(parser: &mut dyn Parser) -> Result<EspFile, String> {
     Ok(EspFile {
         metadata,
         definition,
-        span: Some(parser.current_span()),
+        span: Some(parser.span_since(start)),
+    })
+}
+
+/// Parse an imported file: metadata? definition, where the definition holds
+/// only declarations to share (no CRI blocks or CRI_ROOT)
+pub fn parse_import_file(parser: &mut dyn Parser) -> Result<EspFile, String> {
+    let start = parser.position();
+    let metadata = if matches!(parser.current_token(), Some(Token::Keyword(Keyword::Meta))) {
+        Some(parse_metadata_block(parser)?)
+    } else {
+        None
+    };
+
+    let definition = parse_definition_content(parser)?;
+    if !definition.criteria.is_empty() || definition.criteria_root.is_some() {
+        return Err(
+            "Imported files cannot declare CRI blocks or CRI_ROOT; only VAR, STATE, OBJECT, \
+             RUN and SET declarations are imported"
+                .to_string(),
+        );
+    }
+
+    Ok(EspFile {
+        metadata,
+        definition,
+        span: Some(parser.span_since(start)),
     })
 }
 
 /// Parse definition ::= "DEF" statement_end definition_content "DEF_END" statement_end
 pub fn parse_definition(parser: &mut dyn Parser) -> Result<DefinitionNode, String> {
+    let errors_before = parser.error_count();
+    let definition = parse_definition_content(parser)?;
+
+    // Validate EBNF constraint: must have at least one criteria (unless the
+    // criteria were skipped over an error already reported)
+    if definition.criteria.is_empty() && parser.error_count() == errors_before {
+        return Err("Definition must contain at least one criteria block".to_string());
+    }
+
+    Ok(definition)
+}
+
+/// Parse a DEF block without requiring criteria, shared by policies and
+/// imported files
+fn parse_definition_content(parser: &mut dyn Parser) -> Result<DefinitionNode, String> {
+    let start = parser.position();
     parser.expect_keyword(Keyword::Def)?;
 
     let mut variables = Vec::new();
@@ -62,51 +108,82 @@ pub fn parse_definition(parser: &mut dyn Parser) -> Result<DefinitionNode, Strin
     let mut runtime_operations = Vec::new();
     let mut set_operations = Vec::new();
     let mut criteria = Vec::new();
+    let mut criteria_root = None;
+    let mut imports = Vec::new();
 
     // Parse definition content until DEF_END
     loop {
+        if close_unterminated_block(parser, Keyword::DefEnd) {
+            break;
+        }
         match parser.current_token() {
             Some(Token::Keyword(Keyword::DefEnd)) => {
                 parser.advance();
                 break;
             }
+            Some(Token::Keyword(Keyword::Import)) => {
+                let declared_before = !variables.is_empty()
+                    || !states.is_empty()
+                    || !objects.is_empty()
+                    || !runtime_operations.is_empty()
+                    || !set_operations.is_empty()
+                    || !criteria.is_empty()
+                    || criteria_root.is_some();
+                if declared_before {
+                    skip_unexpected(
+                        parser,
+                        "IMPORT must come before the other definition elements".to_string(),
+                    );
+                } else if let Some(import) = parse_or_recover(parser, parse_import) {
+                    imports.push(import);
+                }
+            }
             Some(Token::Keyword(Keyword::Var)) => {
-                variables.push(parse_variable_declaration(parser)?);
+                variables.extend(parse_or_recover(parser, parse_variable_declaration));
             }
             Some(Token::Keyword(Keyword::State)) => {
-                let mut state = parse_state_definition(parser)?;
-                state.is_global = true; // Definition-level states are global
-                states.push(state);
+                if let Some(mut state) = parse_or_recover(parser, parse_state_definition) {
+                    state.is_global = true; // Definition-level states are global
+                    states.push(state);
+                }
             }
             Some(Token::Keyword(Keyword::Object)) => {
-                let mut object = parse_object_definition(parser)?;
-                object.is_global = true; // Definition-level objects are global
-                objects.push(object);
+                if let Some(mut object) = parse_or_recover(parser, parse_object_definition) {
+                    object.is_global = true; // Definition-level objects are global
+                    objects.push(object);
+                }
             }
             Some(Token::Keyword(Keyword::Run)) => {
-                runtime_operations.push(parse_runtime_operation(parser)?);
+                runtime_operations.extend(parse_or_recover(parser, parse_runtime_operation));
             }
             Some(Token::Keyword(Keyword::Set)) => {
-                set_operations.push(parse_set_operation(parser)?);
+                set_operations.extend(parse_or_recover(parser, parse_set_operation));
+            }
+            Some(Token::Keyword(Keyword::CriRoot)) => {
+                if criteria_root.is_some() {
+                    skip_unexpected(parser, "DEF can only declare one CRI_ROOT".to_string());
+                } else if !criteria.is_empty() {
+                    skip_unexpected(
+                        parser,
+                        "CRI_ROOT must come before the first CRI block".to_string(),
+                    );
+                } else {
+                    criteria_root = parse_or_recover(parser, parse_criteria_root);
+                }
             }
             Some(Token::Keyword(Keyword::Cri)) => {
-                criteria.push(parse_criteria_node(parser)?);
+                criteria.extend(parse_or_recover(parser, parse_criteria_node));
             }
-            None => return Err("Expected DEF_END, reached end of input".to_string()),
             _ => {
-                return Err(format!(
+                let error = format!(
                     "Unexpected token in definition: {:?}",
                     parser.current_token()
-                ))
+                );
+                skip_unexpected(parser, error);
             }
         }
     }
 
-    // Validate EBNF constraint: must have at least one criteria
-    if criteria.is_empty() {
-        return Err("Definition must contain at least one criteria block".to_string());
-    }
-
     Ok(DefinitionNode {
         variables,
         states,
@@ -114,7 +191,24 @@ pub fn parse_definition(parser: &mut dyn Parser) -> Result<DefinitionNode, Strin
         runtime_operations,
         set_operations,
         criteria,
-        span: Some(parser.current_span()),
+        criteria_root,
+        imports,
+        span: Some(parser.span_since(start)),
+    })
+}
+
+/// Parse import ::= "IMPORT" space backtick_string statement_end
+pub fn parse_import(parser: &mut dyn Parser) -> Result<ImportNode, String> {
+    let start = parser.position();
+    parser.expect_keyword(Keyword::Import)?;
+    let path = parser.expect_string_literal()?;
+    if path.trim().is_empty() {
+        return Err("IMPORT requires a file path".to_string());
+    }
+
+    Ok(ImportNode {
+        path,
+        span: Some(parser.span_since(start)),
     })
 }
 
@@ -124,33 +218,37 @@ pub fn parse_definition(parser: &mut dyn Parser) -> Result<DefinitionNode, Strin
 
 /// Parse metadata ::= "META" statement_end metadata_content "META_END" statement_end
 pub fn parse_metadata_block(parser: &mut dyn Parser) -> Result<MetadataBlock, String> {
+    let start = parser.position();
     parser.expect_keyword(Keyword::Meta)?;
 
     let mut fields = Vec::new();
 
     // Parse metadata fields until META_END
     loop {
+        if close_unterminated_block(parser, Keyword::MetaEnd) {
+            break;
+        }
         match parser.current_token() {
             Some(Token::Keyword(Keyword::MetaEnd)) => {
                 parser.advance();
                 break;
             }
             Some(Token::Identifier(_)) => {
-                fields.push(parse_metadata_field(parser)?);
+                fields.extend(parse_or_recover(parser, parse_metadata_field));
             }
-            None => return Err("Expected META_END, reached end of input".to_string()),
-            _ => return Err("Expected metadata field or META_END".to_string()),
+            _ => skip_unexpected(parser, "Expected metadata field or META_END".to_string()),
         }
     }
 
     Ok(MetadataBlock {
         fields,
-        span: Some(parser.current_span()),
+        span: Some(parser.span_since(start)),
     })
 }
 
 /// Parse metadata_field ::= field_name space field_value statement_end
 pub fn parse_metadata_field(parser: &mut dyn Parser) -> Result<MetadataField, String> {
+    let start = parser.position();
     let name = parser.expect_identifier()?;
     let value_obj = parse_value(parser)?;
 
@@ -161,12 +259,13 @@ pub fn parse_metadata_field(parser: &mut dyn Parser) -> Result<MetadataField, St
         Value::Float(f) => f.to_string(),
         Value::Boolean(b) => b.to_string(),
         Value::Variable(var) => format!("VAR {}", var),
+        list @ Value::List(_) => list.to_string(),
     };
 
     Ok(MetadataField {
         name,
         value,
-        span: Some(parser.current_span()),
+        span: Some(parser.span_since(start)),
     })
 }
 
@@ -176,6 +275,7 @@ pub fn parse_metadata_field(parser: &mut dyn Parser) -> Result<MetadataField, St
 
 /// Parse variable_declaration ::= "VAR" space variable_name space data_type (space initial_value)? statement_end
 pub fn parse_variable_declaration(parser: &mut dyn Parser) -> Result<VariableDeclaration, String> {
+    let start = parser.position();
     parser.expect_keyword(Keyword::Var)?;
     let name = parser.expect_identifier()?;
     let data_type = parse_data_type(parser)?;
@@ -184,15 +284,15 @@ pub fn parse_variable_declaration(parser: &mut dyn Parser) -> Result<VariableDec
     let initial_value = match parser.current_token() {
         // If we see another keyword or end of input, no initial value
         Some(Token::Keyword(_)) | None => None,
-        // Otherwise, parse the value
-        _ => Some(parse_value(parser)?),
+        // Otherwise, parse the value; a value list makes a list-valued variable
+        _ => Some(parse_typed_field_value(parser, data_type)?),
     };
 
     Ok(VariableDeclaration {
         name,
         data_type,
         initial_value,
-        span: Some(parser.current_span()),
+        span: Some(parser.span_since(start)),
     })
 }
 
@@ -202,6 +302,7 @@ pub fn parse_variable_declaration(parser: &mut dyn Parser) -> Result<VariableDec
 
 /// Parse state_definition ::= "STATE" space state_identifier statement_end state_content "STATE_END" statement_end
 pub fn parse_state_definition(parser: &mut dyn Parser) -> Result<StateDefinition, String> {
+    let start = parser.position();
     parser.expect_keyword(Keyword::State)?;
     let id = parser.expect_identifier()?;
 
@@ -210,19 +311,24 @@ pub fn parse_state_definition(parser: &mut dyn Parser) -> Result<StateDefinition
 
     // Parse state content until STATE_END
     loop {
+        if close_unterminated_block(parser, Keyword::StateEnd) {
+            break;
+        }
         match parser.current_token() {
             Some(Token::Keyword(Keyword::StateEnd)) => {
                 parser.advance();
                 break;
             }
             Some(Token::Keyword(Keyword::Record)) => {
-                record_checks.push(parse_record_check(parser)?);
+                record_checks.extend(parse_or_recover(parser, parse_record_check));
             }
             Some(Token::Identifier(_)) => {
-                fields.push(parse_state_field(parser)?);
+                fields.extend(parse_or_recover(parser, parse_state_field));
             }
-            None => return Err("Expected STATE_END, reached end of input".to_string()),
-            _ => return Err("Expected state field, record check, or STATE_END".to_string()),
+            _ => skip_unexpected(
+                parser,
+                "Expected state field, record check, or STATE_END".to_string(),
+            ),
         }
     }
 
@@ -231,16 +337,17 @@ pub fn parse_state_definition(parser: &mut dyn Parser) -> Result<StateDefinition
         fields,
         record_checks,
         is_global: false, // Will be set by caller if needed
-        span: Some(parser.current_span()),
+        span: Some(parser.span_since(start)),
     })
 }
 
 /// Parse state_field ::= field_name space data_type space operation space value_spec (space entity_check)? statement_end
 pub fn parse_state_field(parser: &mut dyn Parser) -> Result<StateField, String> {
+    let start = parser.position();
     let name = parser.expect_identifier()?;
     let data_type = parse_data_type(parser)?;
     let operation = parse_operation(parser)?;
-    let value = parse_value(parser)?;
+    let value = parse_typed_field_value(parser, data_type)?;
     let entity_check = parse_optional_entity_check(parser)?;
 
     Ok(StateField {
@@ -249,7 +356,7 @@ pub fn parse_state_field(parser: &mut dyn Parser) -> Result<StateField, String>
         operation,
         value,
         entity_check,
-        span: Some(parser.current_span()),
+        span: Some(parser.span_since(start)),
     })
 }
 
@@ -264,6 +371,7 @@ pub fn parse_state_field(parser: &mut dyn Parser) -> Result<StateField, String>
 /// - 'field' keyword for nested field operations
 /// - Direct operation symbols (=, contains, etc.)
 pub fn parse_record_check(parser: &mut dyn Parser) -> Result<RecordCheck, String> {
+    let start = parser.position();
     parser.expect_keyword(Keyword::Record)?;
 
     // FIXED: Check for optional data type - but ONLY if it's actually a data type,
@@ -280,12 +388,13 @@ pub fn parse_record_check(parser: &mut dyn Parser) -> Result<RecordCheck, String
     Ok(RecordCheck {
         data_type,
         content,
-        span: Some(parser.current_span()),
+        span: Some(parser.span_since(start)),
     })
 }
 
 /// Parse record_field ::= "field" space field_path space data_type space operation space value_spec (space entity_check)? statement_end
 pub fn parse_record_field(parser: &mut dyn Parser) -> Result<RecordField, String> {
+    let start = parser.position();
     // Expect "field" identifier
     match parser.current_token() {
         Some(Token::Identifier(name)) if name == "field" => {
@@ -297,7 +406,7 @@ pub fn parse_record_field(parser: &mut dyn Parser) -> Result<RecordField, String
     let path = parse_field_path(parser)?;
     let data_type = parse_data_type(parser)?;
     let operation = parse_operation(parser)?;
-    let value = parse_value(parser)?;
+    let value = parse_typed_field_value(parser, data_type)?;
     let entity_check = parse_optional_entity_check(parser)?;
 
     Ok(RecordField {
@@ -306,7 +415,7 @@ pub fn parse_record_field(parser: &mut dyn Parser) -> Result<RecordField, String
         operation,
         value,
         entity_check,
-        span: Some(parser.current_span()),
+        span: Some(parser.span_since(start)),
     })
 }
 
@@ -316,6 +425,7 @@ pub fn parse_record_field(parser: &mut dyn Parser) -> Result<RecordField, String
 
 /// Parse object_definition ::= "OBJECT" space object_identifier statement_end object_content "OBJECT_END" statement_end
 pub fn parse_object_definition(parser: &mut dyn Parser) -> Result<ObjectDefinition, String> {
+    let start = parser.position();
     parser.expect_keyword(Keyword::Object)?;
     let id = parser.expect_identifier()?;
 
@@ -323,14 +433,16 @@ pub fn parse_object_definition(parser: &mut dyn Parser) -> Result<ObjectDefiniti
 
     // Parse object elements until OBJECT_END
     loop {
+        if close_unterminated_block(parser, Keyword::ObjectEnd) {
+            break;
+        }
         match parser.current_token() {
             Some(Token::Keyword(Keyword::ObjectEnd)) => {
                 parser.advance();
                 break;
             }
-            None => return Err("Expected OBJECT_END, reached end of input".to_string()),
             _ => {
-                elements.push(parse_object_element(parser)?);
+                elements.extend(parse_or_recover(parser, parse_object_element));
             }
         }
     }
@@ -339,7 +451,7 @@ pub fn parse_object_definition(parser: &mut dyn Parser) -> Result<ObjectDefiniti
         id,
         elements,
         is_global: false, // Will be set by caller if needed
-        span: Some(parser.current_span()),
+        span: Some(parser.span_since(start)),
     })
 }
 
@@ -349,6 +461,7 @@ pub fn parse_object_definition(parser: &mut dyn Parser) -> Result<ObjectDefiniti
 
 /// Parse runtime_operation ::= "RUN" space variable_name space operation_type statement_end run_parameters "RUN_END" statement_end
 pub fn parse_runtime_operation(parser: &mut dyn Parser) -> Result<RuntimeOperation, String> {
+    let start = parser.position();
     parser.expect_keyword(Keyword::Run)?;
     let target_variable = parser.expect_identifier()?;
     let operation_type = parse_runtime_operation_type(parser)?;
@@ -357,14 +470,16 @@ pub fn parse_runtime_operation(parser: &mut dyn Parser) -> Result<RuntimeOperati
 
     // Parse run parameters until RUN_END
     loop {
+        if close_unterminated_block(parser, Keyword::RunEnd) {
+            break;
+        }
         match parser.current_token() {
             Some(Token::Keyword(Keyword::RunEnd)) => {
                 parser.advance();
                 break;
             }
-            None => return Err("Expected RUN_END, reached end of input".to_string()),
             _ => {
-                parameters.push(parse_run_parameter(parser)?);
+                parameters.extend(parse_or_recover(parser, parse_run_parameter));
             }
         }
     }
@@ -373,7 +488,7 @@ pub fn parse_runtime_operation(parser: &mut dyn Parser) -> Result<RuntimeOperati
         target_variable,
         operation_type,
         parameters,
-        span: Some(parser.current_span()),
+        span: Some(parser.span_since(start)),
     })
 }
 
@@ -383,15 +498,20 @@ pub fn parse_runtime_operation(parser: &mut dyn Parser) -> Result<RuntimeOperati
 
 /// Parse set_operation ::= "SET" space set_identifier space set_operation statement_end set_content "SET_END" statement_end
 pub fn parse_set_operation(parser: &mut dyn Parser) -> Result<SetOperation, String> {
+    let start = parser.position();
     parser.expect_keyword(Keyword::Set)?;
     let set_id = parser.expect_identifier()?;
     let operation = parse_set_operation_type(parser)?;
 
     let mut operands = Vec::new();
     let mut filter = None;
+    let errors_before = parser.error_count();
 
     // Parse set content until SET_END
     loop {
+        if close_unterminated_block(parser, Keyword::SetEnd) {
+            break;
+        }
         match parser.current_token() {
             Some(Token::Keyword(Keyword::SetEnd)) => {
                 parser.advance();
@@ -399,27 +519,28 @@ pub fn parse_set_operation(parser: &mut dyn Parser) -> Result<SetOperation, Stri
             }
             Some(Token::Keyword(Keyword::Filter)) => {
                 // FIXED: Use filter_spec from expressions module
-                filter = Some(parse_filter_spec(parser)?);
+                filter = parse_or_recover(parser, parse_filter_spec);
                 // Filter typically ends the operand list
             }
-            None => return Err("Expected SET_END, reached end of input".to_string()),
             _ => {
-                operands.push(parse_set_operand(parser)?);
+                operands.extend(parse_or_recover(parser, parse_set_operand));
             }
         }
     }
 
-    // Validate set operation constraints
-    operation
-        .validate_operand_count(operands.len())
-        .map_err(|e| format!("Set operation validation failed: {}", e))?;
+    // Validate set operation constraints, unless an operand was skipped
+    if parser.error_count() == errors_before {
+        operation
+            .validate_operand_count(operands.len())
+            .map_err(|e| format!("Set operation validation failed: {}", e))?;
+    }
 
     Ok(SetOperation {
         set_id,
         operation,
         operands,
         filter,
-        span: Some(parser.current_span()),
+        span: Some(parser.span_since(start)),
     })
 }
 
@@ -427,8 +548,23 @@ pub fn parse_set_operation(parser: &mut dyn Parser) -> Result<SetOperation, Stri
 // CRITERIA PARSING
 // ============================================================================
 
+/// Parse criteria_root ::= "CRI_ROOT" space logical_operator space? negate_flag? statement_end
+pub fn parse_criteria_root(parser: &mut dyn Parser) -> Result<CriteriaRootNode, String> {
+    let start = parser.position();
+    parser.expect_keyword(Keyword::CriRoot)?;
+    let logical_op = parse_logical_op(parser)?;
+    let negate = parse_optional_boolean(parser, false)?;
+
+    Ok(CriteriaRootNode {
+        logical_op,
+        negate,
+        span: Some(parser.span_since(start)),
+    })
+}
+
 /// Parse criteria ::= "CRI" space logical_operator space? negate_flag? statement_end criteria_content "CRI_END" statement_end
 pub fn parse_criteria_node(parser: &mut dyn Parser) -> Result<CriteriaNode, String> {
+    let start = parser.position();
     parser.expect_keyword(Keyword::Cri)?;
     let logical_op = parse_logical_op(parser)?;
 
@@ -436,24 +572,27 @@ pub fn parse_criteria_node(parser: &mut dyn Parser) -> Result<CriteriaNode, Stri
     let negate = parse_optional_boolean(parser, false)?;
 
     let mut content = Vec::new();
+    let errors_before = parser.error_count();
 
     // Parse criteria content until CRI_END
     loop {
+        if close_unterminated_block(parser, Keyword::CriEnd) {
+            break;
+        }
         match parser.current_token() {
             Some(Token::Keyword(Keyword::CriEnd)) => {
                 parser.advance();
                 break;
             }
             Some(Token::Keyword(Keyword::Cri | Keyword::Ctn)) => {
-                content.push(parse_criteria_content(parser)?);
+                content.extend(parse_or_recover(parser, parse_criteria_content));
             }
-            None => return Err("Expected CRI_END, reached end of input".to_string()),
-            _ => return Err("Expected CRI or CTN block".to_string()),
+            _ => skip_unexpected(parser, "Expected CRI or CTN block".to_string()),
         }
     }
 
     // Validate EBNF constraint: must have at least one content element
-    if content.is_empty() {
+    if content.is_empty() && parser.error_count() == errors_before {
         return Err("Criteria must contain at least one CTN or nested CRI".to_string());
     }
 
@@ -461,7 +600,7 @@ pub fn parse_criteria_node(parser: &mut dyn Parser) -> Result<CriteriaNode, Stri
         logical_op,
         negate,
         content,
-        span: Some(parser.current_span()),
+        span: Some(parser.span_since(start)),
     })
 }
 
@@ -471,6 +610,7 @@ pub fn parse_criteria_node(parser: &mut dyn Parser) -> Result<CriteriaNode, Stri
 
 /// Parse criterion ::= "CTN" space criterion_type statement_end ctn_content "CTN_END" statement_end
 pub fn parse_criterion_node(parser: &mut dyn Parser) -> Result<CriterionNode, String> {
+    let start = parser.position();
     parser.expect_keyword(Keyword::Ctn)?;
     let criterion_type = parser.expect_identifier()?;
 
@@ -481,45 +621,66 @@ pub fn parse_criterion_node(parser: &mut dyn Parser) -> Result<CriterionNode, St
     let mut object_refs = Vec::new();
     let mut local_states = Vec::new();
     let mut local_object = None;
+    let mut controls = Vec::new();
+    let mut severity = None;
 
     // Parse CTN content until CTN_END (following EBNF order)
     loop {
+        if close_unterminated_block(parser, Keyword::CtnEnd) {
+            break;
+        }
         match parser.current_token() {
             Some(Token::Keyword(Keyword::CtnEnd)) => {
                 parser.advance();
                 break;
             }
             Some(Token::Keyword(Keyword::StateRef)) => {
-                parser.advance(); // consume STATE_REF
-                let state_id = parser.expect_identifier()?;
-                state_refs.push(StateRef {
-                    state_id,
-                    span: Some(parser.current_span()),
-                });
+                state_refs.extend(parse_or_recover(parser, |parser| {
+                    let start = parser.position();
+                    parser.advance(); // consume STATE_REF
+                    let state_id = parser.expect_identifier()?;
+                    Ok(StateRef {
+                        state_id,
+                        span: Some(parser.span_since(start)),
+                    })
+                }));
             }
             Some(Token::Keyword(Keyword::ObjectRef)) => {
-                parser.advance(); // consume OBJECT_REF
-                let object_id = parser.expect_identifier()?;
-                object_refs.push(ObjectRef {
-                    object_id,
-                    span: Some(parser.current_span()),
-                });
+                object_refs.extend(parse_or_recover(parser, |parser| {
+                    let start = parser.position();
+                    parser.advance(); // consume OBJECT_REF
+                    let object_id = parser.expect_identifier()?;
+                    Ok(ObjectRef {
+                        object_id,
+                        span: Some(parser.span_since(start)),
+                    })
+                }));
             }
             Some(Token::Keyword(Keyword::State)) => {
-                let mut state = parse_state_definition(parser)?;
-                state.is_global = false; // CTN-level states are local
-                local_states.push(state);
+                if let Some(mut state) = parse_or_recover(parser, parse_state_definition) {
+                    state.is_global = false; // CTN-level states are local
+                    local_states.push(state);
+                }
             }
             Some(Token::Keyword(Keyword::Object)) => {
                 if local_object.is_some() {
-                    return Err("CTN can only contain one local object".to_string());
+                    skip_unexpected(parser, "CTN can only contain one local object".to_string());
+                } else if let Some(mut object) = parse_or_recover(parser, parse_object_definition) {
+                    object.is_global = false; // CTN-level objects are local
+                    local_object = Some(object);
+                }
+            }
+            Some(Token::Keyword(Keyword::Control)) => {
+                controls.extend(parse_or_recover(parser, parse_control_reference));
+            }
+            Some(Token::Keyword(Keyword::Severity)) => {
+                if severity.is_some() {
+                    skip_unexpected(parser, "CTN can only declare one SEVERITY".to_string());
+                } else {
+                    severity = parse_or_recover(parser, parse_severity);
                 }
-                let mut object = parse_object_definition(parser)?;
-                object.is_global = false; // CTN-level objects are local
-                local_object = Some(object);
             }
-            None => return Err("Expected CTN_END, reached end of input".to_string()),
-            _ => return Err("Unexpected token in CTN content".to_string()),
+            _ => skip_unexpected(parser, "Unexpected token in CTN content".to_string()),
         }
     }
 
@@ -530,7 +691,9 @@ pub fn parse_criterion_node(parser: &mut dyn Parser) -> Result<CriterionNode, St
         object_refs,
         local_states,
         local_object,
-        span: Some(parser.current_span()),
+        controls,
+        severity,
+        span: Some(parser.span_since(start)),
     })
 }
 
@@ -540,6 +703,7 @@ pub fn parse_criterion_node(parser: &mut dyn Parser) -> Result<CriterionNode, St
 
 /// Parse test_specification ::= "TEST" space existence_check space item_check (space state_operator)? statement_end
 pub fn parse_test_specification(parser: &mut dyn Parser) -> Result<TestSpecification, String> {
+    let start = parser.position();
     parser.expect_keyword(Keyword::Test)?;
     let existence_check = parse_existence_check(parser)?;
     let item_check = parse_item_check(parser)?;
@@ -560,6 +724,6 @@ pub fn parse_test_specification(parser: &mut dyn Parser) -> Result<TestSpecifica
         item_check,
         state_operator,
         entity_check,
-        span: Some(parser.current_span()),
+        span: Some(parser.span_since(start)),
     })
 }
@@ -8,7 +8,11 @@
 
 use crate::grammar::ast::nodes::*;
 use crate::grammar::builders::atomic::{
-    parse_data_type, parse_filter_action, parse_operation, parse_value, Parser,
+    parse_data_type, parse_field_value, parse_filter_action, parse_operation,
+    parse_typed_field_value, parse_value, Parser,
+};
+use crate::grammar::builders::helpers::{
+    close_unterminated_block, parse_or_recover, skip_unexpected,
 };
 use crate::grammar::keywords::Keyword;
 use crate::tokens::Token;
@@ -56,8 +60,19 @@ pub fn parse_run_parameter(parser: &mut dyn Parser) -> Result<RunParameter, Stri
                     let length = parser.expect_integer()?;
                     Ok(RunParameter::Length(length))
                 }
+                "min" | "max" => {
+                    let operator = ArithmeticOperator::parse(&param_name)
+                        .ok_or_else(|| format!("Unknown arithmetic operator '{}'", param_name))?;
+                    parser.advance(); // consume "min" / "max" identifier
+                    let operand = parse_value(parser)?;
+                    Ok(RunParameter::ArithmeticOp(operator, Some(operand)))
+                }
+                "abs" => {
+                    parser.advance(); // consume "abs" identifier; it takes no operand
+                    Ok(RunParameter::ArithmeticOp(ArithmeticOperator::Abs, None))
+                }
                 _ => Err(format!(
-                    "Unknown run parameter identifier: '{}'. Expected: literal, pattern, delimiter, character, start, length",
+                    "Unknown run parameter identifier: '{}'. Expected: literal, pattern, delimiter, character, start, length, min, max, abs",
                     param_name
                 )),
             }
@@ -81,32 +96,47 @@ pub fn parse_run_parameter(parser: &mut dyn Parser) -> Result<RunParameter, Stri
         Some(Token::Plus) => {
             parser.advance(); // consume the + token
             let operand = parse_value(parser)?; // consume the operand value
-            Ok(RunParameter::ArithmeticOp(ArithmeticOperator::Add, operand))
+            Ok(RunParameter::ArithmeticOp(
+                ArithmeticOperator::Add,
+                Some(operand),
+            ))
         }
         Some(Token::Multiply) => {
             parser.advance(); // consume the * token
             let operand = parse_value(parser)?; // consume the operand value
-            Ok(RunParameter::ArithmeticOp(ArithmeticOperator::Multiply, operand))
+            Ok(RunParameter::ArithmeticOp(
+                ArithmeticOperator::Multiply,
+                Some(operand),
+            ))
         }
         Some(Token::Minus) => {
             parser.advance(); // consume the - token
             let operand = parse_value(parser)?; // consume the operand value
-            Ok(RunParameter::ArithmeticOp(ArithmeticOperator::Subtract, operand))
+            Ok(RunParameter::ArithmeticOp(
+                ArithmeticOperator::Subtract,
+                Some(operand),
+            ))
         }
         Some(Token::Divide) => {
             parser.advance(); // consume the / token
             let operand = parse_value(parser)?; // consume the operand value
-            Ok(RunParameter::ArithmeticOp(ArithmeticOperator::Divide, operand))
+            Ok(RunParameter::ArithmeticOp(
+                ArithmeticOperator::Divide,
+                Some(operand),
+            ))
         }
         Some(Token::Modulus) => {
             parser.advance(); // consume the % token
             let operand = parse_value(parser)?; // consume the operand value
-            Ok(RunParameter::ArithmeticOp(ArithmeticOperator::Modulus, operand))
+            Ok(RunParameter::ArithmeticOp(
+                ArithmeticOperator::Modulus,
+                Some(operand),
+            ))
         }
 
         Some(token) => Err(format!(
             "Unexpected token '{}' for run parameter. Expected:\n\
-             - Context-sensitive identifiers: literal, pattern, delimiter, character, start, length\n\
+             - Context-sensitive identifiers: literal, pattern, delimiter, character, start, length, min, max, abs\n\
              - Keywords: VAR, OBJ\n\
              - Arithmetic operators: +, *, -, /, %",
             token.as_esp_string()
@@ -143,6 +173,7 @@ pub fn parse_set_operand(parser: &mut dyn Parser) -> Result<SetOperand, String>
 ///
 /// EBNF: filter_spec ::= "FILTER" space filter_action? statement_end filter_references "FILTER_END" statement_end
 pub fn parse_filter_spec(parser: &mut dyn Parser) -> Result<FilterSpec, String> {
+    let start = parser.position();
     parser.expect_keyword(Keyword::Filter)?;
 
     // Parse optional filter action (defaults to include)
@@ -161,11 +192,12 @@ pub fn parse_filter_spec(parser: &mut dyn Parser) -> Result<FilterSpec, String>
                 break;
             }
             Some(Token::Keyword(Keyword::StateRef)) => {
+                let ref_start = parser.position();
                 parser.advance();
                 let state_id = parser.expect_identifier()?;
                 state_refs.push(StateRef {
                     state_id,
-                    span: Some(parser.current_span()),
+                    span: Some(parser.span_since(ref_start)),
                 });
             }
             None => return Err("Expected FILTER_END, reached end of input".to_string()),
@@ -180,15 +212,52 @@ pub fn parse_filter_spec(parser: &mut dyn Parser) -> Result<FilterSpec, String>
     Ok(FilterSpec {
         action,
         state_refs,
-        span: Some(parser.current_span()),
+        span: Some(parser.span_since(start)),
+    })
+}
+
+/// Parse control_reference
+///
+/// EBNF: control_reference ::= "CONTROL" space framework space string_literal (space string_literal)? statement_end
+pub fn parse_control_reference(parser: &mut dyn Parser) -> Result<ControlRef, String> {
+    let start = parser.position();
+    parser.expect_keyword(Keyword::Control)?;
+    let framework = parser.expect_identifier()?;
+    let control_id = parser.expect_string_literal()?;
+    let url = match parser.current_token() {
+        Some(Token::StringLiteral(_)) => Some(parser.expect_string_literal()?),
+        _ => None,
+    };
+
+    Ok(ControlRef {
+        framework,
+        control_id,
+        url,
+        span: Some(parser.span_since(start)),
     })
 }
 
+/// Parse severity_declaration
+///
+/// EBNF: severity_declaration ::= "SEVERITY" space severity_level statement_end
+pub fn parse_severity(parser: &mut dyn Parser) -> Result<Identifier, String> {
+    parser.expect_keyword(Keyword::Severity)?;
+    let level = parser.expect_identifier()?;
+    match level.as_str() {
+        "critical" | "high" | "medium" | "low" | "info" => Ok(level),
+        _ => Err(format!(
+            "Unknown severity '{}'; expected critical, high, medium, low or info",
+            level
+        )),
+    }
+}
+
 /// Parse object_element with systematic data type handling
 ///
 /// EBNF: object_element ::= module_element | parameter_element | select_element |
 ///                         behavior_element | filter_spec | set_reference | object_field
 pub fn parse_object_element(parser: &mut dyn Parser) -> Result<ObjectElement, String> {
+    let start = parser.position();
     match parser.current_token() {
         // Module fields (unambiguous keywords)
         Some(Token::Keyword(Keyword::ModuleName)) => {
@@ -254,6 +323,7 @@ pub fn parse_object_element(parser: &mut dyn Parser) -> Result<ObjectElement, St
                             Value::Float(f) => f.to_string(),
                             Value::Boolean(b) => b.to_string(),
                             Value::Variable(v) => format!("VAR {}", v),
+                            list @ Value::List(_) => list.to_string(),
                         };
                         fields.push((name, value));
                     }
@@ -291,6 +361,7 @@ pub fn parse_object_element(parser: &mut dyn Parser) -> Result<ObjectElement, St
                             Value::Float(f) => f.to_string(),
                             Value::Boolean(b) => b.to_string(),
                             Value::Variable(v) => format!("VAR {}", v),
+                            list @ Value::List(_) => list.to_string(),
                         };
                         fields.push((name, value));
                     }
@@ -307,7 +378,7 @@ pub fn parse_object_element(parser: &mut dyn Parser) -> Result<ObjectElement, St
             Ok(ObjectElement::Select { data_type, fields })
         }
 
-        // UPDATED: Behavior with mixed value types (identifiers, integers, booleans)
+        // Behavior with mixed value types (identifiers, strings, integers, booleans)
         Some(Token::Keyword(Keyword::Behavior)) => {
             parser.advance();
             let mut values = Vec::new();
@@ -334,6 +405,9 @@ pub fn parse_object_element(parser: &mut dyn Parser) -> Result<ObjectElement, St
                         let float_val = parser.expect_float()?;
                         values.push(float_val.to_string());
                     }
+                    Some(Token::StringLiteral(_)) => {
+                        values.push(parser.expect_string_literal()?);
+                    }
                     _ => break,
                 }
             }
@@ -355,7 +429,7 @@ pub fn parse_object_element(parser: &mut dyn Parser) -> Result<ObjectElement, St
             let set_id = parser.expect_identifier()?;
             Ok(ObjectElement::SetRef {
                 set_id,
-                span: Some(parser.current_span()),
+                span: Some(parser.span_since(start)),
             })
         }
 
@@ -375,7 +449,7 @@ pub fn parse_object_element(parser: &mut dyn Parser) -> Result<ObjectElement, St
             Ok(ObjectElement::Field(ObjectField {
                 name,
                 value,
-                span: Some(parser.current_span()),
+                span: Some(parser.span_since(start)),
             }))
         }
 
@@ -417,7 +491,7 @@ pub fn parse_record_content(parser: &mut dyn Parser) -> Result<RecordContent, St
         // Check for operation symbol token
         token if is_operation_symbol_token(token) => {
             let operation = parse_operation(parser)?;
-            let value = parse_value(parser)?;
+            let value = parse_field_value(parser)?;
             Ok(RecordContent::Direct { operation, value })
         }
         // Check for field specification
@@ -451,20 +525,23 @@ pub fn parse_record_content(parser: &mut dyn Parser) -> Result<RecordContent, St
 
 /// Parse inline object definition
 fn parse_inline_object_definition(parser: &mut dyn Parser) -> Result<ObjectDefinition, String> {
+    let start = parser.position();
     parser.expect_keyword(Keyword::Object)?;
     let id = parser.expect_identifier()?;
 
     let mut elements = Vec::new();
 
     loop {
+        if close_unterminated_block(parser, Keyword::ObjectEnd) {
+            break;
+        }
         match parser.current_token() {
             Some(Token::Keyword(Keyword::ObjectEnd)) => {
                 parser.advance();
                 break;
             }
-            None => return Err("Expected OBJECT_END, reached end of input".to_string()),
             _ => {
-                elements.push(parse_object_element(parser)?);
+                elements.extend(parse_or_recover(parser, parse_object_element));
             }
         }
     }
@@ -473,12 +550,13 @@ fn parse_inline_object_definition(parser: &mut dyn Parser) -> Result<ObjectDefin
         id,
         elements,
         is_global: false,
-        span: Some(parser.current_span()),
+        span: Some(parser.span_since(start)),
     })
 }
 
 /// Parse inline record check
 fn parse_inline_record_check(parser: &mut dyn Parser) -> Result<RecordCheck, String> {
+    let start = parser.position();
     parser.expect_keyword(Keyword::Record)?;
 
     // Check for optional data type (now identifier-based)
@@ -496,12 +574,13 @@ fn parse_inline_record_check(parser: &mut dyn Parser) -> Result<RecordCheck, Str
     Ok(RecordCheck {
         data_type,
         content,
-        span: Some(parser.current_span()),
+        span: Some(parser.span_since(start)),
     })
 }
 
 /// Parse inline set operation
 fn parse_inline_set_operation(parser: &mut dyn Parser) -> Result<SetOperation, String> {
+    let start = parser.position();
     use crate::grammar::builders::atomic::parse_set_operation_type;
 
     parser.expect_keyword(Keyword::Set)?;
@@ -510,38 +589,44 @@ fn parse_inline_set_operation(parser: &mut dyn Parser) -> Result<SetOperation, S
 
     let mut operands = Vec::new();
     let mut filter = None;
+    let errors_before = parser.error_count();
 
     loop {
+        if close_unterminated_block(parser, Keyword::SetEnd) {
+            break;
+        }
         match parser.current_token() {
             Some(Token::Keyword(Keyword::SetEnd)) => {
                 parser.advance();
                 break;
             }
             Some(Token::Keyword(Keyword::Filter)) => {
-                filter = Some(parse_filter_spec(parser)?);
+                filter = parse_or_recover(parser, parse_filter_spec);
             }
-            None => return Err("Expected SET_END, reached end of input".to_string()),
             _ => {
-                operands.push(parse_set_operand(parser)?);
+                operands.extend(parse_or_recover(parser, parse_set_operand));
             }
         }
     }
 
-    operation
-        .validate_operand_count(operands.len())
-        .map_err(|e| format!("Set operation validation failed: {}", e))?;
+    if parser.error_count() == errors_before {
+        operation
+            .validate_operand_count(operands.len())
+            .map_err(|e| format!("Set operation validation failed: {}", e))?;
+    }
 
     Ok(SetOperation {
         set_id,
         operation,
         operands,
         filter,
-        span: Some(parser.current_span()),
+        span: Some(parser.span_since(start)),
     })
 }
 
 /// Parse inline record field
 fn parse_inline_record_field(parser: &mut dyn Parser) -> Result<RecordField, String> {
+    let start = parser.position();
     use crate::grammar::builders::helpers::{parse_field_path, parse_optional_entity_check};
 
     match parser.current_token() {
@@ -554,7 +639,7 @@ fn parse_inline_record_field(parser: &mut dyn Parser) -> Result<RecordField, Str
     let path = parse_field_path(parser)?;
     let data_type = parse_data_type(parser)?; // Now uses identifier parsing
     let operation = parse_operation(parser)?; // Now uses symbol tokens
-    let value = parse_value(parser)?;
+    let value = parse_typed_field_value(parser, data_type)?;
     let entity_check = parse_optional_entity_check(parser)?;
 
     Ok(RecordField {
@@ -563,12 +648,13 @@ fn parse_inline_record_field(parser: &mut dyn Parser) -> Result<RecordField, Str
         operation,
         value,
         entity_check,
-        span: Some(parser.current_span()),
+        span: Some(parser.span_since(start)),
     })
 }
 
 /// Parse inline criteria node
 fn parse_inline_criteria_node(parser: &mut dyn Parser) -> Result<CriteriaNode, String> {
+    let start = parser.position();
     use crate::grammar::builders::atomic::parse_logical_op;
     use crate::grammar::builders::helpers::parse_optional_boolean;
 
@@ -577,22 +663,25 @@ fn parse_inline_criteria_node(parser: &mut dyn Parser) -> Result<CriteriaNode, S
     let negate = parse_optional_boolean(parser, false)?;
 
     let mut content = Vec::new();
+    let errors_before = parser.error_count();
 
     loop {
+        if close_unterminated_block(parser, Keyword::CriEnd) {
+            break;
+        }
         match parser.current_token() {
             Some(Token::Keyword(Keyword::CriEnd)) => {
                 parser.advance();
                 break;
             }
             Some(Token::Keyword(Keyword::Cri | Keyword::Ctn)) => {
-                content.push(parse_criteria_content(parser)?);
+                content.extend(parse_or_recover(parser, parse_criteria_content));
             }
-            None => return Err("Expected CRI_END, reached end of input".to_string()),
-            _ => return Err("Expected CRI or CTN block".to_string()),
+            _ => skip_unexpected(parser, "Expected CRI or CTN block".to_string()),
         }
     }
 
-    if content.is_empty() {
+    if content.is_empty() && parser.error_count() == errors_before {
         return Err("Criteria must contain at least one CTN or nested CRI".to_string());
     }
 
@@ -600,12 +689,13 @@ fn parse_inline_criteria_node(parser: &mut dyn Parser) -> Result<CriteriaNode, S
         logical_op,
         negate,
         content,
-        span: Some(parser.current_span()),
+        span: Some(parser.span_since(start)),
     })
 }
 
 /// Parse inline criterion node
 fn parse_inline_criterion_node(parser: &mut dyn Parser) -> Result<CriterionNode, String> {
+    let start = parser.position();
     use crate::grammar::builders::atomic::{
         parse_existence_check, parse_item_check, parse_state_operator,
     };
@@ -633,55 +723,82 @@ fn parse_inline_criterion_node(parser: &mut dyn Parser) -> Result<CriterionNode,
         item_check,
         state_operator,
         entity_check,
-        span: Some(parser.current_span()),
+        span: Some(parser.span_since(start)),
     };
 
     let mut state_refs = Vec::new();
     let mut object_refs = Vec::new();
     let mut local_states = Vec::new();
     let mut local_object = None;
+    let mut controls = Vec::new();
+    let mut severity = None;
 
     loop {
+        if close_unterminated_block(parser, Keyword::CtnEnd) {
+            break;
+        }
         match parser.current_token() {
             Some(Token::Keyword(Keyword::CtnEnd)) => {
                 parser.advance();
                 break;
             }
             Some(Token::Keyword(Keyword::StateRef)) => {
-                parser.advance();
-                let state_id = parser.expect_identifier()?;
-                state_refs.push(StateRef {
-                    state_id,
-                    span: Some(parser.current_span()),
-                });
+                state_refs.extend(parse_or_recover(parser, |parser| {
+                    let start = parser.position();
+                    parser.advance();
+                    let state_id = parser.expect_identifier()?;
+                    Ok(StateRef {
+                        state_id,
+                        span: Some(parser.span_since(start)),
+                    })
+                }));
             }
             Some(Token::Keyword(Keyword::ObjectRef)) => {
-                parser.advance();
-                let object_id = parser.expect_identifier()?;
-                object_refs.push(ObjectRef {
-                    object_id,
-                    span: Some(parser.current_span()),
-                });
+                object_refs.extend(parse_or_recover(parser, |parser| {
+                    let start = parser.position();
+                    parser.advance();
+                    let object_id = parser.expect_identifier()?;
+                    Ok(ObjectRef {
+                        object_id,
+                        span: Some(parser.span_since(start)),
+                    })
+                }));
             }
             Some(Token::Keyword(Keyword::State)) => {
-                let mut state = parse_inline_state_definition(parser)?;
-                state.is_global = false;
-                local_states.push(state);
+                if let Some(mut state) = parse_or_recover(parser, parse_inline_state_definition) {
+                    state.is_global = false;
+                    local_states.push(state);
+                }
             }
             Some(Token::Keyword(Keyword::Object)) => {
                 if local_object.is_some() {
-                    return Err("CTN can only contain one local object".to_string());
+                    skip_unexpected(parser, "CTN can only contain one local object".to_string());
+                } else if let Some(mut object) =
+                    parse_or_recover(parser, parse_inline_object_definition)
+                {
+                    object.is_global = false;
+                    local_object = Some(object);
                 }
-                let mut object = parse_inline_object_definition(parser)?;
-                object.is_global = false;
-                local_object = Some(object);
             }
-            None => return Err("Expected CTN_END, reached end of input".to_string()),
-            Some(token) => {
-                return Err(format!(
-                    "Unexpected token '{}' in CTN content",
-                    token.as_esp_string()
-                ))
+            Some(Token::Keyword(Keyword::Control)) => {
+                controls.extend(parse_or_recover(parser, parse_control_reference));
+            }
+            Some(Token::Keyword(Keyword::Severity)) => {
+                if severity.is_some() {
+                    skip_unexpected(parser, "CTN can only declare one SEVERITY".to_string());
+                } else {
+                    severity = parse_or_recover(parser, parse_severity);
+                }
+            }
+            _ => {
+                let found = parser
+                    .current_token()
+                    .map(Token::as_esp_string)
+                    .unwrap_or_default();
+                skip_unexpected(
+                    parser,
+                    format!("Unexpected token '{}' in CTN content", found),
+                );
             }
         }
     }
@@ -693,12 +810,15 @@ fn parse_inline_criterion_node(parser: &mut dyn Parser) -> Result<CriterionNode,
         object_refs,
         local_states,
         local_object,
-        span: Some(parser.current_span()),
+        controls,
+        severity,
+        span: Some(parser.span_since(start)),
     })
 }
 
 /// Parse inline state definition
 fn parse_inline_state_definition(parser: &mut dyn Parser) -> Result<StateDefinition, String> {
+    let start = parser.position();
     use crate::grammar::builders::helpers::parse_optional_entity_check;
 
     parser.expect_keyword(Keyword::State)?;
@@ -706,38 +826,47 @@ fn parse_inline_state_definition(parser: &mut dyn Parser) -> Result<StateDefinit
 
     let mut fields = Vec::new();
     let mut record_checks = Vec::new();
+    let errors_before = parser.error_count();
 
     loop {
+        if close_unterminated_block(parser, Keyword::StateEnd) {
+            break;
+        }
         match parser.current_token() {
             Some(Token::Keyword(Keyword::StateEnd)) => {
                 parser.advance();
                 break;
             }
             Some(Token::Keyword(Keyword::Record)) => {
-                record_checks.push(parse_inline_record_check(parser)?);
+                record_checks.extend(parse_or_recover(parser, parse_inline_record_check));
             }
             Some(Token::Identifier(_)) => {
-                let name = parser.expect_identifier()?;
-                let data_type = parse_data_type(parser)?; // Now identifier-based
-                let operation = parse_operation(parser)?; // Now symbol tokens
-                let value = parse_value(parser)?;
-                let entity_check = parse_optional_entity_check(parser)?;
-
-                fields.push(StateField {
-                    name,
-                    data_type,
-                    operation,
-                    value,
-                    entity_check,
-                    span: Some(parser.current_span()),
-                });
+                fields.extend(parse_or_recover(parser, |parser| {
+                    let start = parser.position();
+                    let name = parser.expect_identifier()?;
+                    let data_type = parse_data_type(parser)?; // Now identifier-based
+                    let operation = parse_operation(parser)?; // Now symbol tokens
+                    let value = parse_typed_field_value(parser, data_type)?;
+                    let entity_check = parse_optional_entity_check(parser)?;
+
+                    Ok(StateField {
+                        name,
+                        data_type,
+                        operation,
+                        value,
+                        entity_check,
+                        span: Some(parser.span_since(start)),
+                    })
+                }));
             }
-            None => return Err("Expected STATE_END, reached end of input".to_string()),
-            _ => return Err("Expected state field, record check, or STATE_END".to_string()),
+            _ => skip_unexpected(
+                parser,
+                "Expected state field, record check, or STATE_END".to_string(),
+            ),
         }
     }
 
-    if fields.is_empty() && record_checks.is_empty() {
+    if fields.is_empty() && record_checks.is_empty() && parser.error_count() == errors_before {
         return Err("State must have at least one field or record check".to_string());
     }
 
@@ -746,7 +875,7 @@ fn parse_inline_state_definition(parser: &mut dyn Parser) -> Result<StateDefinit
         fields,
         record_checks,
         is_global: false,
-        span: Some(parser.current_span()),
+        span: Some(parser.span_since(start)),
     })
 }
 
@@ -775,6 +904,23 @@ fn is_operation_symbol_token(token: Option<&Token>) -> bool {
                 | Token::Matches
                 | Token::SubsetOf
                 | Token::SupersetOf
+                | Token::EqualsAny
+                | Token::NotEqualsAny
+                | Token::LengthEquals
+                | Token::LengthGreaterThan
+                | Token::OccurrenceCount
+                | Token::VersionEquals
+                | Token::VersionNotEqual
+                | Token::VersionGreaterThan
+                | Token::VersionLessThan
+                | Token::VersionGreaterOrEqual
+                | Token::VersionLessOrEqual
+                | Token::InSubnet
+                | Token::NotInSubnet
+                | Token::GlobMatch
+                | Token::NotGlobMatch
+                | Token::PermissionsAtMost
+                | Token::PermissionsAtLeast
         )
     )
 }
@@ -791,6 +937,9 @@ fn is_data_type_identifier(name: &str) -> bool {
             | "record_data"
             | "version"
             | "evr_string"
+            | "ip_address"
+            | "octal"
+            | "datetime"
     )
 }
 
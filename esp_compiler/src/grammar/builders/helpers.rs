@@ -4,23 +4,25 @@
 //! only use Token::Boolean(bool) and not Keyword::True/False
 
 use crate::grammar::ast::nodes::*;
-use crate::grammar::builders::atomic::{parse_entity_check, Parser};
+use crate::grammar::builders::atomic::{at_counted_entity_check, parse_entity_check, Parser};
 use crate::grammar::keywords::Keyword;
 use crate::tokens::Token;
 
 /// Parse field_path ::= path_component ("." path_component)*
-/// where path_component ::= identifier | wildcard ("*") | index (integer)
+/// where path_component ::= identifier | attribute ("@" identifier) | wildcard ("*") | index (integer)
 ///
 /// FIXED: Now supports numeric indices like spec.containers.0.name
+/// Attribute components such as `Connector.@port` keep their `@` prefix.
 pub fn parse_field_path(parser: &mut dyn Parser) -> Result<FieldPath, String> {
     let mut components = Vec::new();
 
-    // Parse first component (required) - can be identifier, wildcard, or integer index
+    // Parse first component (required) - can be identifier, attribute, wildcard, or integer index
     let first_component = match parser.current_token() {
         Some(Token::Multiply) => {
             parser.advance();
             "*".to_string()
         }
+        Some(Token::At) => parse_attribute_component(parser)?,
         Some(Token::Identifier(_)) => parser.expect_identifier()?,
         Some(Token::Integer(n)) => {
             let idx = (*n).to_string();
@@ -46,6 +48,7 @@ pub fn parse_field_path(parser: &mut dyn Parser) -> Result<FieldPath, String> {
                 parser.advance();
                 "*".to_string()
             }
+            Some(Token::At) => parse_attribute_component(parser)?,
             Some(Token::Identifier(_)) => parser.expect_identifier()?,
             Some(Token::Integer(n)) => {
                 let idx = (*n).to_string();
@@ -71,6 +74,19 @@ pub fn parse_field_path(parser: &mut dyn Parser) -> Result<FieldPath, String> {
     Ok(FieldPath::new(components))
 }
 
+/// Parse attribute ::= "@" identifier
+fn parse_attribute_component(parser: &mut dyn Parser) -> Result<String, String> {
+    parser.advance(); // consume @
+    match parser.current_token() {
+        Some(Token::Identifier(_)) => Ok(format!("@{}", parser.expect_identifier()?)),
+        Some(other) => Err(format!(
+            "Expected attribute name after '@' in field path, found {:?}",
+            other
+        )),
+        None => Err("Expected attribute name after '@', reached end of input".to_string()),
+    }
+}
+
 /// Parse a list of identifiers separated by whitespace
 pub fn parse_identifier_list(parser: &mut dyn Parser) -> Result<Vec<Identifier>, String> {
     let mut identifiers = Vec::new();
@@ -86,8 +102,12 @@ pub fn parse_identifier_list(parser: &mut dyn Parser) -> Result<Vec<Identifier>,
     }
 }
 
-/// Parse optional entity_check ::= ("all" | "at_least_one" | "none" | "only_one")?
+/// Parse optional entity_check ::= ("all" | "at_least_one" | "none" | "only_one"
+///                                   | "at_least(" integer ")" | "at_least_percent(" integer ")")?
 pub fn parse_optional_entity_check(parser: &mut dyn Parser) -> Result<Option<EntityCheck>, String> {
+    if at_counted_entity_check(parser) {
+        return Ok(Some(parse_entity_check(parser)?));
+    }
     match parser.current_token() {
         Some(Token::Keyword(
             Keyword::All | Keyword::AtLeastOne | Keyword::None | Keyword::OnlyOne,
@@ -172,6 +192,8 @@ pub fn at_block_boundary(parser: &dyn Parser) -> bool {
             Keyword::Meta
                 | Keyword::Def
                 | Keyword::Cri
+                | Keyword::CriRoot
+                | Keyword::Import
                 | Keyword::Ctn
                 | Keyword::State
                 | Keyword::Object
@@ -215,6 +237,61 @@ where
     Ok(elements)
 }
 
+/// Parse one element of a block. A failing element is recorded and skipped
+/// so the errors after it are still found; None means it was skipped
+pub fn parse_or_recover<T>(
+    parser: &mut dyn Parser,
+    parse_element: impl FnOnce(&mut dyn Parser) -> Result<T, String>,
+) -> Option<T> {
+    let start = parser.position();
+    match parse_element(parser) {
+        Ok(element) => Some(element),
+        Err(error) => {
+            parser.report_error(error);
+            parser.skip_element(start);
+            None
+        }
+    }
+}
+
+/// Record a token that cannot start an element of the current block and skip
+/// it with the rest of its line
+pub fn skip_unexpected(parser: &mut dyn Parser, error: String) {
+    let start = parser.position();
+    parser.report_error(error);
+    parser.skip_element(start);
+}
+
+/// Close the block ended by `end` early when that keyword is missing: at EOF,
+/// or at the end of a block that can enclose it. The missing end is recorded
+/// and true returned, so the caller stops at the current token
+pub fn close_unterminated_block(parser: &mut dyn Parser, end: Keyword) -> bool {
+    let error = match parser.current_token() {
+        None | Some(Token::Eof) => {
+            format!("Expected {}, reached end of input", end.as_str())
+        }
+        Some(Token::Keyword(kw))
+            if kw.is_block_end() && nesting_level(*kw) > nesting_level(end) =>
+        {
+            format!("Expected {}, found '{}'", end.as_str(), kw.as_str())
+        }
+        _ => return false,
+    };
+    parser.report_error(error);
+    true
+}
+
+/// How far out a block sits: DEF holds CRI, CRI holds CTN, and CTN holds the
+/// local STATE and OBJECT blocks
+fn nesting_level(end: Keyword) -> u8 {
+    match end {
+        Keyword::DefEnd => 3,
+        Keyword::CriEnd => 2,
+        Keyword::CtnEnd => 1,
+        _ => 0,
+    }
+}
+
 /// Validate that a keyword is in its expected context
 pub fn validate_keyword_context(
     parser: &dyn Parser,
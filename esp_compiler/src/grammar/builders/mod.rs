@@ -10,16 +10,17 @@ pub mod helpers;
 // Re-export all atomic builders (no duplicates with expressions)
 pub use atomic::{
     parse_arithmetic_operator, parse_data_type, parse_entity_check, parse_existence_check,
-    parse_filter_action, parse_item_check, parse_logical_op, parse_operation,
+    parse_field_value, parse_filter_action, parse_item_check, parse_logical_op, parse_operation,
     parse_runtime_operation_type, parse_set_operation_type, parse_state_operator, parse_value,
 };
 
 // Re-export all block builders
 pub use blocks::{
-    parse_criteria_node, parse_criterion_node, parse_definition, parse_esp_file,
-    parse_metadata_block, parse_metadata_field, parse_object_definition, parse_record_check,
-    parse_record_field, parse_runtime_operation, parse_set_operation, parse_state_definition,
-    parse_state_field, parse_test_specification, parse_variable_declaration,
+    parse_criteria_node, parse_criteria_root, parse_criterion_node, parse_definition,
+    parse_esp_file, parse_import, parse_import_file, parse_metadata_block, parse_metadata_field,
+    parse_object_definition, parse_record_check, parse_record_field, parse_runtime_operation,
+    parse_set_operation, parse_state_definition, parse_state_field, parse_test_specification,
+    parse_variable_declaration,
 };
 
 // Re-export expression builders (excluding arithmetic_operator to avoid duplicate)
@@ -13,6 +13,8 @@ pub enum Keyword {
     DefEnd,
     Cri,
     CriEnd,
+    CriRoot,
+    Import,
     Ctn,
     CtnEnd,
     State,
@@ -40,6 +42,8 @@ pub enum Keyword {
     StateRef,
     ObjectRef,
     SetRef,
+    Control,
+    Severity,
 
     // === LOGICAL OPERATORS (UPPERCASE) ===
     And,
@@ -57,6 +61,11 @@ pub enum Keyword {
     End,
     Merge,
     Extract,
+    Replace,
+    Length,
+    Uppercase,
+    Lowercase,
+    Trim,
 
     // === OBJECT OPERATIONS (UPPERCASE) ===
     Obj,
@@ -109,6 +118,8 @@ impl Keyword {
             Self::DefEnd => "DEF_END",
             Self::Cri => "CRI",
             Self::CriEnd => "CRI_END",
+            Self::CriRoot => "CRI_ROOT",
+            Self::Import => "IMPORT",
             Self::Ctn => "CTN",
             Self::CtnEnd => "CTN_END",
             Self::State => "STATE",
@@ -136,6 +147,8 @@ impl Keyword {
             Self::StateRef => "STATE_REF",
             Self::ObjectRef => "OBJECT_REF",
             Self::SetRef => "SET_REF",
+            Self::Control => "CONTROL",
+            Self::Severity => "SEVERITY",
 
             // Logical operators
             Self::And => "AND",
@@ -153,6 +166,11 @@ impl Keyword {
             Self::End => "END",
             Self::Merge => "MERGE",
             Self::Extract => "EXTRACT",
+            Self::Replace => "REPLACE",
+            Self::Length => "LENGTH",
+            Self::Uppercase => "UPPERCASE",
+            Self::Lowercase => "LOWERCASE",
+            Self::Trim => "TRIM",
 
             // Object operations
             Self::Obj => "OBJ",
@@ -201,6 +219,8 @@ impl Keyword {
             "DEF_END" => Some(Self::DefEnd),
             "CRI" => Some(Self::Cri),
             "CRI_END" => Some(Self::CriEnd),
+            "CRI_ROOT" => Some(Self::CriRoot),
+            "IMPORT" => Some(Self::Import),
             "CTN" => Some(Self::Ctn),
             "CTN_END" => Some(Self::CtnEnd),
             "STATE" => Some(Self::State),
@@ -228,6 +248,8 @@ impl Keyword {
             "STATE_REF" => Some(Self::StateRef),
             "OBJECT_REF" => Some(Self::ObjectRef),
             "SET_REF" => Some(Self::SetRef),
+            "CONTROL" => Some(Self::Control),
+            "SEVERITY" => Some(Self::Severity),
 
             // Logical operators
             "AND" => Some(Self::And),
@@ -249,6 +271,11 @@ impl Keyword {
             "END" => Some(Self::End),
             "MERGE" => Some(Self::Merge),
             "EXTRACT" => Some(Self::Extract),
+            "REPLACE" => Some(Self::Replace),
+            "LENGTH" => Some(Self::Length),
+            "UPPERCASE" => Some(Self::Uppercase),
+            "LOWERCASE" => Some(Self::Lowercase),
+            "TRIM" => Some(Self::Trim),
 
             // Object operations
             "OBJ" => Some(Self::Obj),
@@ -372,6 +399,11 @@ impl Keyword {
                 | Self::End
                 | Self::Merge
                 | Self::Extract
+                | Self::Replace
+                | Self::Length
+                | Self::Uppercase
+                | Self::Lowercase
+                | Self::Trim
         )
     }
 
@@ -433,6 +465,8 @@ pub fn reserved_keywords() -> &'static [&'static str] {
         "DEF_END",
         "CRI",
         "CRI_END",
+        "CRI_ROOT",
+        "IMPORT",
         "CTN",
         "CTN_END",
         "STATE",
@@ -458,6 +492,8 @@ pub fn reserved_keywords() -> &'static [&'static str] {
         "STATE_REF",
         "OBJECT_REF",
         "SET_REF",
+        "CONTROL",
+        "SEVERITY",
         // Logical operators
         "AND",
         "OR",
@@ -473,6 +509,11 @@ pub fn reserved_keywords() -> &'static [&'static str] {
         "END",
         "MERGE",
         "EXTRACT",
+        "REPLACE",
+        "LENGTH",
+        "UPPERCASE",
+        "LOWERCASE",
+        "TRIM",
         "OBJ",
         // Module fields
         "module_name",
@@ -510,6 +551,9 @@ pub fn data_type_identifiers() -> &'static [&'static str] {
         "record_data",
         "version",
         "evr_string",
+        "ip_address",
+        "octal",
+        "datetime",
     ]
 }
 
@@ -554,6 +598,23 @@ pub fn symbol_operators() -> &'static [&'static str] {
         "matches",
         "subset_of",
         "superset_of",
+        "equals_any",
+        "not_equals_any",
+        "length_equals",
+        "length_greater_than",
+        "occurrence_count",
+        "version_equals",
+        "version_not_equal",
+        "version_greater_than",
+        "version_less_than",
+        "version_greater_or_equal",
+        "version_less_or_equal",
+        "in_subnet",
+        "not_in_subnet",
+        "glob_match",
+        "not_glob_match",
+        "permissions_at_most",
+        "permissions_at_least",
     ]
 }
 
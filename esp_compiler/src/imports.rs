@@ -0,0 +1,429 @@
+//! IMPORT resolution
+//!
+//! An `IMPORT` line at the top of a DEF pulls another file's VAR, STATE,
+//! OBJECT, RUN and SET declarations into the importing definition. Imports
+//! are flattened right after parsing, before symbol discovery, so every later
+//! stage sees a single definition and the emitted AST never mentions them.
+//!
+//! Paths are relative to the importing file. A file reached through several
+//! imports is merged once; a file importing itself, directly or through
+//! others, is an error, as is a name declared by two of the files.
+
+use crate::file_processor::FileMetadata;
+use crate::grammar::ast::nodes::{DefinitionNode, EspFile};
+use crate::logging::{self, codes, Code};
+use crate::pipeline::PipelineError;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// An IMPORT that cannot be resolved
+#[derive(Debug, thiserror::Error)]
+pub enum ImportError {
+    #[error("cannot import '{path}' from {}: {reason}", importer.display())]
+    Invalid {
+        importer: PathBuf,
+        path: String,
+        reason: String,
+    },
+
+    #[error("import cycle: {}", format_chain(chain))]
+    Cycle { chain: Vec<PathBuf> },
+
+    #[error(
+        "{kind} '{name}' is declared in both {} and {}",
+        first.display(),
+        second.display()
+    )]
+    Conflict {
+        kind: &'static str,
+        name: String,
+        first: PathBuf,
+        second: PathBuf,
+    },
+}
+
+impl ImportError {
+    pub fn error_code(&self) -> Code {
+        match self {
+            Self::Invalid { .. } => codes::imports::INVALID_IMPORT,
+            Self::Cycle { .. } => codes::imports::IMPORT_CYCLE,
+            Self::Conflict { .. } => codes::imports::IMPORT_CONFLICT,
+        }
+    }
+}
+
+fn format_chain(chain: &[PathBuf]) -> String {
+    chain
+        .iter()
+        .map(|path| path.display().to_string())
+        .collect::<Vec<_>>()
+        .join(" -> ")
+}
+
+/// Merge every file imported by `ast`, directly or not, into its definition
+///
+/// Returns the flattened AST and the metadata of each imported file, in the
+/// order they were merged.
+pub fn resolve_imports(
+    mut ast: EspFile,
+    file_path: &Path,
+) -> Result<(EspFile, Vec<FileMetadata>), PipelineError> {
+    if ast.definition.imports.is_empty() {
+        return Ok((ast, Vec::new()));
+    }
+
+    let root = file_path
+        .canonicalize()
+        .unwrap_or_else(|_| file_path.to_path_buf());
+    let mut resolver = ImportResolver {
+        stack: vec![root.clone()],
+        merged: HashSet::new(),
+        origins: HashMap::new(),
+        files: Vec::new(),
+    };
+    resolver.flatten(&mut ast.definition, &root)?;
+
+    crate::log_info!("Imports resolved",
+        "file" => file_path.display().to_string(),
+        "imported_files" => resolver.files.len()
+    );
+    Ok((ast, resolver.files))
+}
+
+struct ImportResolver {
+    /// Files being flattened, outermost first, for cycle reports
+    stack: Vec<PathBuf>,
+    /// Files already merged somewhere in the tree
+    merged: HashSet<PathBuf>,
+    /// File declaring each (kind, name) seen so far
+    origins: HashMap<(&'static str, String), PathBuf>,
+    files: Vec<FileMetadata>,
+}
+
+impl ImportResolver {
+    /// Register the file's own declarations, then prepend everything its
+    /// imports declare
+    fn flatten(
+        &mut self,
+        definition: &mut DefinitionNode,
+        file: &Path,
+    ) -> Result<(), PipelineError> {
+        self.register(definition, file)?;
+
+        let mut imported = DefinitionNode {
+            variables: Vec::new(),
+            states: Vec::new(),
+            objects: Vec::new(),
+            runtime_operations: Vec::new(),
+            set_operations: Vec::new(),
+            criteria: Vec::new(),
+            criteria_root: None,
+            imports: Vec::new(),
+            span: None,
+        };
+        for import in std::mem::take(&mut definition.imports) {
+            let path = resolve_path(file, &import.path)?;
+            if let Some(start) = self.stack.iter().position(|open| *open == path) {
+                let mut chain = self.stack[start..].to_vec();
+                chain.push(path);
+                return Err(ImportError::Cycle { chain }.into());
+            }
+            if !self.merged.insert(path.clone()) {
+                continue;
+            }
+
+            let (mut ast, metadata) = load_import(&path)?;
+            self.files.push(metadata);
+            self.stack.push(path.clone());
+            self.flatten(&mut ast.definition, &path)?;
+            self.stack.pop();
+
+            let definition = ast.definition;
+            imported.variables.extend(definition.variables);
+            imported.states.extend(definition.states);
+            imported.objects.extend(definition.objects);
+            imported
+                .runtime_operations
+                .extend(definition.runtime_operations);
+            imported.set_operations.extend(definition.set_operations);
+        }
+
+        prepend(&mut definition.variables, imported.variables);
+        prepend(&mut definition.states, imported.states);
+        prepend(&mut definition.objects, imported.objects);
+        prepend(
+            &mut definition.runtime_operations,
+            imported.runtime_operations,
+        );
+        prepend(&mut definition.set_operations, imported.set_operations);
+        Ok(())
+    }
+
+    /// Record where each global name of `definition` comes from; a name
+    /// repeated within one file is left for symbol discovery to report
+    fn register(&mut self, definition: &DefinitionNode, file: &Path) -> Result<(), ImportError> {
+        let names = definition
+            .variables
+            .iter()
+            .map(|v| ("VAR", &v.name))
+            .chain(definition.states.iter().map(|s| ("STATE", &s.id)))
+            .chain(definition.objects.iter().map(|o| ("OBJECT", &o.id)))
+            .chain(definition.set_operations.iter().map(|s| ("SET", &s.set_id)));
+
+        for (kind, name) in names {
+            match self.origins.get(&(kind, name.clone())) {
+                Some(first) if first != file => {
+                    return Err(ImportError::Conflict {
+                        kind,
+                        name: name.clone(),
+                        first: first.clone(),
+                        second: file.to_path_buf(),
+                    });
+                }
+                Some(_) => {}
+                None => {
+                    self.origins
+                        .insert((kind, name.clone()), file.to_path_buf());
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// An import path, resolved against the directory of the importing file
+fn resolve_path(importer: &Path, import: &str) -> Result<PathBuf, ImportError> {
+    let base = importer.parent().unwrap_or_else(|| Path::new("."));
+    base.join(import)
+        .canonicalize()
+        .map_err(|e| ImportError::Invalid {
+            importer: importer.to_path_buf(),
+            path: import.to_string(),
+            reason: e.to_string(),
+        })
+}
+
+/// Read, tokenize and parse an imported file, attributing any failure (and
+/// the diagnostics logged on the way) to that file
+fn load_import(path: &Path) -> Result<(EspFile, FileMetadata), PipelineError> {
+    let outer = logging::get_current_file_context();
+    let result = logging::with_file_context(path.to_path_buf(), 0, || {
+        let file_result = crate::file_processor::process_file(&path.display().to_string())?;
+        let tokens = crate::lexical::tokenize_file_result(file_result.clone())?;
+        let ast = crate::syntax::parse_import_file(tokens)?;
+        Ok::<_, PipelineError>((ast, file_result.metadata))
+    });
+    if let Some(outer) = outer {
+        logging::set_file_context(outer.file_path, outer.file_id);
+    }
+
+    result.map_err(|source| PipelineError::Imported {
+        file: path.to_path_buf(),
+        source: Box::new(source),
+    })
+}
+
+fn prepend<T>(own: &mut Vec<T>, mut imported: Vec<T>) {
+    imported.append(own);
+    *own = imported;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const META: &str = "META
+    version `1.0.0`
+    esp_version `1.0`
+    author `test`
+    date `2026-01-01`
+    severity `low`
+    platform `linux`
+    description `imports`
+    control_framework `TEST`
+    control `IMP-1`
+    esp_scan_id `imports`
+    criticality `low`
+    tags `test`
+META_END
+";
+
+    const POLICY: &str = "DEF
+    IMPORT `common/states.esp`
+
+    OBJECT motd
+        path `/etc/motd`
+    OBJECT_END
+
+    CRI AND
+        CTN file_metadata
+            TEST all all
+            STATE_REF present
+            OBJECT_REF motd
+        CTN_END
+    CRI_END
+DEF_END
+";
+
+    const STATES: &str = "DEF
+    STATE present
+        exists boolean = true
+    STATE_END
+DEF_END
+";
+
+    fn write(dir: &Path, name: &str, content: &str) -> PathBuf {
+        let path = dir.join(name);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, content).unwrap();
+        path
+    }
+
+    fn compile(path: &Path) -> Result<crate::PipelineResult, PipelineError> {
+        let _ = crate::logging::init_global_logging();
+        crate::pipeline::process_file(&path.display().to_string())
+    }
+
+    #[test]
+    fn test_imported_declarations_are_flattened() {
+        let dir = tempfile::tempdir().unwrap();
+        write(dir.path(), "common/states.esp", STATES);
+        let policy = write(dir.path(), "policy.esp", &format!("{}{}", META, POLICY));
+
+        let result = compile(&policy).unwrap();
+        let definition = &result.ast.definition;
+        assert!(definition.imports.is_empty());
+        assert_eq!(definition.states[0].id, "present");
+        assert_eq!(definition.objects[0].id, "motd");
+        assert_eq!(result.imported_files.len(), 1);
+        assert!(result.imported_files[0].path.ends_with("common/states.esp"));
+
+        let json = serde_json::to_string(&result.ast).unwrap();
+        assert!(!json.contains("\"imports\":"), "{}", json);
+    }
+
+    #[test]
+    fn test_nested_imports_resolve_relative_to_the_importer_once() {
+        let dir = tempfile::tempdir().unwrap();
+        write(
+            dir.path(),
+            "common/states.esp",
+            "DEF\n    IMPORT `base/vars.esp`\n\n    STATE present\n        exists boolean = true\n    STATE_END\nDEF_END\n",
+        );
+        write(
+            dir.path(),
+            "common/base/vars.esp",
+            "DEF\n    VAR owner string `root`\nDEF_END\n",
+        );
+        // Reaches vars.esp a second time, through a different path
+        write(
+            dir.path(),
+            "common/paths.esp",
+            "DEF\n    IMPORT `./base/../base/vars.esp`\nDEF_END\n",
+        );
+        let policy = write(
+            dir.path(),
+            "policy.esp",
+            &format!(
+                "{}{}",
+                META,
+                POLICY.replace(
+                    "IMPORT `common/states.esp`",
+                    "IMPORT `common/states.esp`\n    IMPORT `common/paths.esp`"
+                )
+            ),
+        );
+
+        let result = compile(&policy).unwrap();
+        assert_eq!(result.ast.definition.variables.len(), 1);
+        assert_eq!(result.ast.definition.variables[0].name, "owner");
+        assert_eq!(result.imported_files.len(), 3);
+    }
+
+    #[test]
+    fn test_mutual_imports_are_a_cycle() {
+        let dir = tempfile::tempdir().unwrap();
+        write(dir.path(), "a.esp", "DEF\n    IMPORT `b.esp`\nDEF_END\n");
+        write(dir.path(), "b.esp", "DEF\n    IMPORT `a.esp`\nDEF_END\n");
+        let policy = write(
+            dir.path(),
+            "policy.esp",
+            &format!("{}{}", META, POLICY.replace("common/states.esp", "a.esp")),
+        );
+
+        let error = compile(&policy).unwrap_err();
+        assert_eq!(error.error_code(), codes::imports::IMPORT_CYCLE);
+        let message = error.to_string();
+        assert!(message.contains("a.esp -> "), "{}", message);
+        assert!(message.contains("b.esp -> "), "{}", message);
+        assert!(message.ends_with("a.esp"), "{}", message);
+        assert!(!message.contains("policy.esp"), "{}", message);
+    }
+
+    #[test]
+    fn test_duplicate_identifier_names_both_files() {
+        let dir = tempfile::tempdir().unwrap();
+        write(
+            dir.path(),
+            "common/states.esp",
+            "DEF\n    STATE present\n        exists boolean = true\n    STATE_END\n\n    OBJECT motd\n        path `/etc/issue`\n    OBJECT_END\nDEF_END\n",
+        );
+        let policy = write(dir.path(), "policy.esp", &format!("{}{}", META, POLICY));
+
+        let error = compile(&policy).unwrap_err();
+        assert_eq!(error.error_code(), codes::imports::IMPORT_CONFLICT);
+        let message = error.to_string();
+        assert!(
+            message.contains("OBJECT 'motd' is declared in both"),
+            "{}",
+            message
+        );
+        assert!(message.contains("policy.esp"), "{}", message);
+        assert!(message.contains("states.esp"), "{}", message);
+    }
+
+    #[test]
+    fn test_errors_in_imported_files_name_that_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let shared = write(
+            dir.path(),
+            "common/states.esp",
+            "DEF\n    STATE present\n        exists boolean = true\nDEF_END\n",
+        );
+        let policy = write(dir.path(), "policy.esp", &format!("{}{}", META, POLICY));
+
+        let error = compile(&policy).unwrap_err();
+        assert_eq!(error.stage(), Some("Syntax analysis"));
+        match &error {
+            PipelineError::Imported { file, .. } => {
+                assert_eq!(*file, shared.canonicalize().unwrap())
+            }
+            other => panic!("expected an imported file error, got {}", other),
+        }
+        assert!(
+            error.detail().contains("states.esp: "),
+            "{}",
+            error.detail()
+        );
+
+        let with_criteria = POLICY.replace("    IMPORT `common/states.esp`\n", "");
+        std::fs::write(&shared, with_criteria).unwrap();
+        let error = compile(&policy).unwrap_err();
+        assert!(
+            error.to_string().contains("cannot declare CRI blocks"),
+            "{}",
+            error
+        );
+
+        std::fs::remove_file(&shared).unwrap();
+        let error = compile(&policy).unwrap_err();
+        assert_eq!(error.error_code(), codes::imports::INVALID_IMPORT);
+        assert!(
+            error
+                .to_string()
+                .contains("cannot import 'common/states.esp'"),
+            "{}",
+            error
+        );
+    }
+}
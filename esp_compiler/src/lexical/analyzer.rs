@@ -9,8 +9,8 @@ use crate::file_processor::FileProcessingResult;
 use crate::grammar::keywords::{classify_word_type, Keyword, WordType};
 use crate::logging::codes;
 use crate::tokens::{classify_operator_word, StringLiteral, Token, TokenStream};
-use crate::utils::{Position, Span, Spanned};
-use crate::{log_debug, log_error, log_success};
+use crate::tokens::{Position, Span, Spanned};
+use crate::{log_debug, log_error, log_success, log_warning};
 
 /// Lexical analysis errors with compile-time security boundaries
 #[derive(Debug, Clone, thiserror::Error)]
@@ -78,10 +78,10 @@ pub struct LexicalMetrics {
 }
 
 impl LexicalMetrics {
-    pub(crate) fn record_token(&mut self, token: &Token, preferences: &LexicalPreferences) {
+    pub(crate) fn record_token(&mut self, kind: &Token, preferences: &LexicalPreferences) {
         self.total_tokens += 1;
 
-        match token {
+        match kind {
             Token::Keyword(_) => self.keyword_tokens += 1,
             Token::Identifier(_) => self.identifier_tokens += 1,
             Token::Equals
@@ -106,12 +106,29 @@ impl LexicalMetrics {
             | Token::PatternMatch
             | Token::Matches
             | Token::SubsetOf
-            | Token::SupersetOf => {
+            | Token::SupersetOf
+            | Token::EqualsAny
+            | Token::NotEqualsAny
+            | Token::LengthEquals
+            | Token::LengthGreaterThan
+            | Token::OccurrenceCount
+            | Token::VersionEquals
+            | Token::VersionNotEqual
+            | Token::VersionGreaterThan
+            | Token::VersionLessThan
+            | Token::VersionGreaterOrEqual
+            | Token::VersionLessOrEqual
+            | Token::InSubnet
+            | Token::NotInSubnet
+            | Token::GlobMatch
+            | Token::NotGlobMatch
+            | Token::PermissionsAtMost
+            | Token::PermissionsAtLeast => {
                 self.operator_tokens += 1;
 
                 // Track operator patterns if enabled
                 if preferences.track_operator_patterns {
-                    let op_name = format!("{:?}", token);
+                    let op_name = format!("{:?}", kind);
                     *self.operator_usage_patterns.entry(op_name).or_insert(0) += 1;
                 }
             }
@@ -215,26 +232,26 @@ impl LexicalAnalyzer {
             let result = match ch {
                 // Whitespace
                 ' ' => {
-                    let token = self.create_token(Token::Space, current_pos, 1);
-                    self.metrics.record_token(&token.value, &self.preferences);
-                    tokens.push(token);
-                    current_pos = current_pos.advance(' ');
+                    let spanned = self.create_token(Token::Space, current_pos, source, 1);
+                    self.metrics.record_token(&spanned.value, &self.preferences);
+                    current_pos = spanned.span.end;
+                    tokens.push(spanned);
                     token_count += 1;
                     Ok(())
                 }
                 '\t' => {
-                    let token = self.create_token(Token::Tab, current_pos, 1);
-                    self.metrics.record_token(&token.value, &self.preferences);
-                    tokens.push(token);
-                    current_pos = current_pos.advance('\t');
+                    let spanned = self.create_token(Token::Tab, current_pos, source, 1);
+                    self.metrics.record_token(&spanned.value, &self.preferences);
+                    current_pos = spanned.span.end;
+                    tokens.push(spanned);
                     token_count += 1;
                     Ok(())
                 }
                 '\n' => {
-                    let token = self.create_token(Token::Newline, current_pos, 1);
-                    self.metrics.record_token(&token.value, &self.preferences);
-                    tokens.push(token);
-                    current_pos = current_pos.advance('\n');
+                    let spanned = self.create_token(Token::Newline, current_pos, source, 1);
+                    self.metrics.record_token(&spanned.value, &self.preferences);
+                    current_pos = spanned.span.end;
+                    tokens.push(spanned);
                     token_count += 1;
                     Ok(())
                 }
@@ -242,15 +259,14 @@ impl LexicalAnalyzer {
                     // Handle CRLF
                     if chars.peek().map(|(_, c)| *c) == Some('\n') {
                         chars.next();
-                        let token = self.create_token(Token::Newline, current_pos, 2);
-                        self.metrics.record_token(&token.value, &self.preferences);
-                        tokens.push(token);
-                        current_pos = current_pos.advance_bytes(2);
-                        current_pos = Position::new(current_pos.offset, current_pos.line + 1, 1);
+                        let spanned = self.create_token(Token::Newline, current_pos, source, 2);
+                        self.metrics.record_token(&spanned.value, &self.preferences);
+                        current_pos = spanned.span.end;
+                        tokens.push(spanned);
                     } else {
-                        let token = self.create_token(Token::Newline, current_pos, 1);
-                        self.metrics.record_token(&token.value, &self.preferences);
-                        tokens.push(token);
+                        let spanned = self.create_token(Token::Newline, current_pos, source, 1);
+                        self.metrics.record_token(&spanned.value, &self.preferences);
+                        tokens.push(spanned);
                         current_pos =
                             Position::new(current_pos.offset + 1, current_pos.line + 1, 1);
                     }
@@ -261,10 +277,10 @@ impl LexicalAnalyzer {
                 // Comments
                 '#' => match self.parse_comment(&mut chars) {
                     Ok((token_val, len)) => {
-                        let token = self.create_token(token_val, current_pos, len);
-                        self.metrics.record_token(&token.value, &self.preferences);
-                        tokens.push(token);
-                        current_pos = current_pos.advance_bytes(len);
+                        let spanned = self.create_token(token_val, current_pos, source, len);
+                        self.metrics.record_token(&spanned.value, &self.preferences);
+                        current_pos = spanned.span.end;
+                        tokens.push(spanned);
                         token_count += 1;
                         Ok(())
                     }
@@ -274,10 +290,10 @@ impl LexicalAnalyzer {
                 // String literals
                 '`' => match self.parse_string_literal(byte_offset, source, &mut chars, 0) {
                     Ok((token_val, len)) => {
-                        let token = self.create_token(token_val, current_pos, len);
-                        self.metrics.record_token(&token.value, &self.preferences);
-                        tokens.push(token);
-                        current_pos = current_pos.advance_bytes(len);
+                        let spanned = self.create_token(token_val, current_pos, source, len);
+                        self.metrics.record_token(&spanned.value, &self.preferences);
+                        current_pos = spanned.span.end;
+                        tokens.push(spanned);
                         token_count += 1;
                         Ok(())
                     }
@@ -289,10 +305,11 @@ impl LexicalAnalyzer {
                     if chars.peek().map(|(_, c)| *c) == Some('`') {
                         match self.parse_raw_string(byte_offset, source, &mut chars, 0) {
                             Ok((token_val, len)) => {
-                                let token = self.create_token(token_val, current_pos, len);
-                                self.metrics.record_token(&token.value, &self.preferences);
-                                tokens.push(token);
-                                current_pos = current_pos.advance_bytes(len);
+                                let spanned =
+                                    self.create_token(token_val, current_pos, source, len);
+                                self.metrics.record_token(&spanned.value, &self.preferences);
+                                current_pos = spanned.span.end;
+                                tokens.push(spanned);
                                 token_count += 1;
                                 Ok(())
                             }
@@ -301,10 +318,11 @@ impl LexicalAnalyzer {
                     } else {
                         match self.parse_identifier_or_operator(byte_offset, source, &mut chars) {
                             Ok((token_val, len)) => {
-                                let token = self.create_token(token_val, current_pos, len);
-                                self.metrics.record_token(&token.value, &self.preferences);
-                                tokens.push(token);
-                                current_pos = current_pos.advance_bytes(len);
+                                let spanned =
+                                    self.create_token(token_val, current_pos, source, len);
+                                self.metrics.record_token(&spanned.value, &self.preferences);
+                                current_pos = spanned.span.end;
+                                tokens.push(spanned);
                                 token_count += 1;
                                 Ok(())
                             }
@@ -315,9 +333,51 @@ impl LexicalAnalyzer {
 
                 // Punctuation
                 '.' => {
-                    let token = self.create_token(Token::Dot, current_pos, 1);
-                    tokens.push(token);
-                    current_pos = current_pos.advance('.');
+                    let spanned = self.create_token(Token::Dot, current_pos, source, 1);
+                    current_pos = spanned.span.end;
+                    tokens.push(spanned);
+                    token_count += 1;
+                    Ok(())
+                }
+                '@' => {
+                    let punct = self.create_token(Token::At, current_pos, source, 1);
+                    current_pos = punct.span.end;
+                    tokens.push(punct);
+                    token_count += 1;
+                    Ok(())
+                }
+                '[' => {
+                    let punct = self.create_token(Token::LeftBracket, current_pos, source, 1);
+                    current_pos = punct.span.end;
+                    tokens.push(punct);
+                    token_count += 1;
+                    Ok(())
+                }
+                ']' => {
+                    let punct = self.create_token(Token::RightBracket, current_pos, source, 1);
+                    current_pos = punct.span.end;
+                    tokens.push(punct);
+                    token_count += 1;
+                    Ok(())
+                }
+                ',' => {
+                    let punct = self.create_token(Token::Comma, current_pos, source, 1);
+                    current_pos = punct.span.end;
+                    tokens.push(punct);
+                    token_count += 1;
+                    Ok(())
+                }
+                '(' => {
+                    let punct = self.create_token(Token::LeftParen, current_pos, source, 1);
+                    current_pos = punct.span.end;
+                    tokens.push(punct);
+                    token_count += 1;
+                    Ok(())
+                }
+                ')' => {
+                    let punct = self.create_token(Token::RightParen, current_pos, source, 1);
+                    current_pos = punct.span.end;
+                    tokens.push(punct);
                     token_count += 1;
                     Ok(())
                 }
@@ -325,10 +385,10 @@ impl LexicalAnalyzer {
                 // Numbers
                 '0'..='9' => match self.parse_number(byte_offset, source, &mut chars) {
                     Ok((token_val, len)) => {
-                        let token = self.create_token(token_val, current_pos, len);
-                        self.metrics.record_token(&token.value, &self.preferences);
-                        tokens.push(token);
-                        current_pos = current_pos.advance_bytes(len);
+                        let spanned = self.create_token(token_val, current_pos, source, len);
+                        self.metrics.record_token(&spanned.value, &self.preferences);
+                        current_pos = spanned.span.end;
+                        tokens.push(spanned);
                         token_count += 1;
                         Ok(())
                     }
@@ -341,28 +401,29 @@ impl LexicalAnalyzer {
                         if next_ch.is_ascii_digit() {
                             match self.parse_number(byte_offset, source, &mut chars) {
                                 Ok((token_val, len)) => {
-                                    let token = self.create_token(token_val, current_pos, len);
-                                    self.metrics.record_token(&token.value, &self.preferences);
-                                    tokens.push(token);
-                                    current_pos = current_pos.advance_bytes(len);
+                                    let spanned =
+                                        self.create_token(token_val, current_pos, source, len);
+                                    self.metrics.record_token(&spanned.value, &self.preferences);
+                                    current_pos = spanned.span.end;
+                                    tokens.push(spanned);
                                     token_count += 1;
                                     Ok(())
                                 }
                                 Err(e) => Err(e),
                             }
                         } else {
-                            let token = self.create_token(Token::Minus, current_pos, 1);
-                            self.metrics.record_token(&token.value, &self.preferences);
-                            tokens.push(token);
-                            current_pos = current_pos.advance('-');
+                            let spanned = self.create_token(Token::Minus, current_pos, source, 1);
+                            self.metrics.record_token(&spanned.value, &self.preferences);
+                            current_pos = spanned.span.end;
+                            tokens.push(spanned);
                             token_count += 1;
                             Ok(())
                         }
                     } else {
-                        let token = self.create_token(Token::Minus, current_pos, 1);
-                        self.metrics.record_token(&token.value, &self.preferences);
-                        tokens.push(token);
-                        current_pos = current_pos.advance('-');
+                        let spanned = self.create_token(Token::Minus, current_pos, source, 1);
+                        self.metrics.record_token(&spanned.value, &self.preferences);
+                        current_pos = spanned.span.end;
+                        tokens.push(spanned);
                         token_count += 1;
                         Ok(())
                     }
@@ -370,42 +431,50 @@ impl LexicalAnalyzer {
 
                 // Single character operators
                 '+' => {
-                    let token = self.create_token(Token::Plus, current_pos, 1);
-                    self.metrics.record_token(&token.value, &self.preferences);
-                    tokens.push(token);
-                    current_pos = current_pos.advance('+');
+                    let spanned = self.create_token(Token::Plus, current_pos, source, 1);
+                    self.metrics.record_token(&spanned.value, &self.preferences);
+                    current_pos = spanned.span.end;
+                    tokens.push(spanned);
                     token_count += 1;
                     Ok(())
                 }
                 '*' => {
-                    let token = self.create_token(Token::Multiply, current_pos, 1);
-                    self.metrics.record_token(&token.value, &self.preferences);
-                    tokens.push(token);
-                    current_pos = current_pos.advance('*');
+                    let spanned = self.create_token(Token::Multiply, current_pos, source, 1);
+                    self.metrics.record_token(&spanned.value, &self.preferences);
+                    current_pos = spanned.span.end;
+                    tokens.push(spanned);
                     token_count += 1;
                     Ok(())
                 }
                 '/' => {
-                    let token = self.create_token(Token::Divide, current_pos, 1);
-                    self.metrics.record_token(&token.value, &self.preferences);
-                    tokens.push(token);
-                    current_pos = current_pos.advance('/');
+                    let spanned = self.create_token(Token::Divide, current_pos, source, 1);
+                    self.metrics.record_token(&spanned.value, &self.preferences);
+                    current_pos = spanned.span.end;
+                    tokens.push(spanned);
                     token_count += 1;
                     Ok(())
                 }
                 '%' => {
-                    let token = self.create_token(Token::Modulus, current_pos, 1);
-                    self.metrics.record_token(&token.value, &self.preferences);
-                    tokens.push(token);
-                    current_pos = current_pos.advance('%');
+                    let spanned = self.create_token(Token::Modulus, current_pos, source, 1);
+                    self.metrics.record_token(&spanned.value, &self.preferences);
+                    current_pos = spanned.span.end;
+                    tokens.push(spanned);
                     token_count += 1;
                     Ok(())
                 }
                 '=' => {
-                    let token = self.create_token(Token::Equals, current_pos, 1);
-                    self.metrics.record_token(&token.value, &self.preferences);
-                    tokens.push(token);
-                    current_pos = current_pos.advance('=');
+                    // `==` is accepted as an alias for `=`
+                    let width = if chars.peek().map(|(_, c)| *c) == Some('=') {
+                        chars.next();
+                        warn_symbol_alias("==", "=", current_pos);
+                        2
+                    } else {
+                        1
+                    };
+                    let spanned = self.create_token(Token::Equals, current_pos, source, width);
+                    self.metrics.record_token(&spanned.value, &self.preferences);
+                    current_pos = spanned.span.end;
+                    tokens.push(spanned);
                     token_count += 1;
                     Ok(())
                 }
@@ -414,10 +483,10 @@ impl LexicalAnalyzer {
                 '!' => {
                     if chars.peek().map(|(_, c)| *c) == Some('=') {
                         chars.next();
-                        let token = self.create_token(Token::NotEquals, current_pos, 2);
-                        self.metrics.record_token(&token.value, &self.preferences);
-                        tokens.push(token);
-                        current_pos = current_pos.advance_bytes(2);
+                        let spanned = self.create_token(Token::NotEquals, current_pos, source, 2);
+                        self.metrics.record_token(&spanned.value, &self.preferences);
+                        current_pos = spanned.span.end;
+                        tokens.push(spanned);
                         token_count += 1;
                         Ok(())
                     } else {
@@ -432,15 +501,16 @@ impl LexicalAnalyzer {
                 '>' => {
                     if chars.peek().map(|(_, c)| *c) == Some('=') {
                         chars.next();
-                        let token = self.create_token(Token::GreaterThanOrEqual, current_pos, 2);
-                        self.metrics.record_token(&token.value, &self.preferences);
-                        tokens.push(token);
-                        current_pos = current_pos.advance_bytes(2);
+                        let spanned =
+                            self.create_token(Token::GreaterThanOrEqual, current_pos, source, 2);
+                        self.metrics.record_token(&spanned.value, &self.preferences);
+                        current_pos = spanned.span.end;
+                        tokens.push(spanned);
                     } else {
-                        let token = self.create_token(Token::GreaterThan, current_pos, 1);
-                        self.metrics.record_token(&token.value, &self.preferences);
-                        tokens.push(token);
-                        current_pos = current_pos.advance('>');
+                        let spanned = self.create_token(Token::GreaterThan, current_pos, source, 1);
+                        self.metrics.record_token(&spanned.value, &self.preferences);
+                        current_pos = spanned.span.end;
+                        tokens.push(spanned);
                     }
                     token_count += 1;
                     Ok(())
@@ -448,15 +518,24 @@ impl LexicalAnalyzer {
                 '<' => {
                     if chars.peek().map(|(_, c)| *c) == Some('=') {
                         chars.next();
-                        let token = self.create_token(Token::LessThanOrEqual, current_pos, 2);
-                        self.metrics.record_token(&token.value, &self.preferences);
-                        tokens.push(token);
-                        current_pos = current_pos.advance_bytes(2);
+                        let spanned =
+                            self.create_token(Token::LessThanOrEqual, current_pos, source, 2);
+                        self.metrics.record_token(&spanned.value, &self.preferences);
+                        current_pos = spanned.span.end;
+                        tokens.push(spanned);
+                    } else if chars.peek().map(|(_, c)| *c) == Some('>') {
+                        // `<>` is accepted as an alias for `!=`
+                        chars.next();
+                        warn_symbol_alias("<>", "!=", current_pos);
+                        let spanned = self.create_token(Token::NotEquals, current_pos, source, 2);
+                        self.metrics.record_token(&spanned.value, &self.preferences);
+                        current_pos = spanned.span.end;
+                        tokens.push(spanned);
                     } else {
-                        let token = self.create_token(Token::LessThan, current_pos, 1);
-                        self.metrics.record_token(&token.value, &self.preferences);
-                        tokens.push(token);
-                        current_pos = current_pos.advance('<');
+                        let spanned = self.create_token(Token::LessThan, current_pos, source, 1);
+                        self.metrics.record_token(&spanned.value, &self.preferences);
+                        current_pos = spanned.span.end;
+                        tokens.push(spanned);
                     }
                     token_count += 1;
                     Ok(())
@@ -466,10 +545,10 @@ impl LexicalAnalyzer {
                 'a'..='z' | 'A'..='Z' | '_' => {
                     match self.parse_identifier_or_operator(byte_offset, source, &mut chars) {
                         Ok((token_val, len)) => {
-                            let token = self.create_token(token_val, current_pos, len);
-                            self.metrics.record_token(&token.value, &self.preferences);
-                            tokens.push(token);
-                            current_pos = current_pos.advance_bytes(len);
+                            let spanned = self.create_token(token_val, current_pos, source, len);
+                            self.metrics.record_token(&spanned.value, &self.preferences);
+                            current_pos = spanned.span.end;
+                            tokens.push(spanned);
                             token_count += 1;
                             Ok(())
                         }
@@ -512,7 +591,7 @@ impl LexicalAnalyzer {
         }
 
         // Add EOF token
-        let eof_token = self.create_token(Token::Eof, current_pos, 0);
+        let eof_token = self.create_token(Token::Eof, current_pos, source, 0);
         tokens.push(eof_token);
 
         let token_stream = TokenStream::new(tokens);
@@ -566,14 +645,18 @@ impl LexicalAnalyzer {
     // Private parsing methods with security boundaries
     // ========================================================================
 
-    fn create_token(&self, token: Token, start_pos: Position, length: usize) -> Spanned<Token> {
-        let end_pos = Position::new(
-            start_pos.offset + length,
-            start_pos.line,
-            start_pos.column + length as u32,
-        );
-        let span = Span::new(start_pos, end_pos);
-        Spanned::new(token, span)
+    /// Span a token over its `length` bytes of source text, so multi-byte
+    /// characters count as one column and multiline strings end on their last line
+    fn create_token(
+        &self,
+        kind: Token,
+        start_pos: Position,
+        source: &str,
+        length: usize,
+    ) -> Spanned<Token> {
+        let text = &source[start_pos.offset..start_pos.offset + length];
+        let span = Span::new(start_pos, start_pos.advance_str(text));
+        Spanned::new(kind, span)
     }
 
     fn parse_identifier_or_operator(
@@ -585,7 +668,7 @@ impl LexicalAnalyzer {
         let mut word = String::new();
         let mut len = 0;
 
-        if let Some(ch) = source.chars().nth(start_offset) {
+        if let Some(ch) = source[start_offset..].chars().next() {
             word.push(ch);
             len += 1;
         }
@@ -668,11 +751,8 @@ impl LexicalAnalyzer {
         self.metrics.record_string_nesting(nesting_depth);
 
         // Check for multiline string
-        if start_offset + 2 < source.len() {
-            let next_chars: String = source.chars().skip(start_offset).take(3).collect();
-            if next_chars == "```" {
-                return self.parse_multiline_string(chars, nesting_depth);
-            }
+        if source[start_offset..].starts_with("```") {
+            return self.parse_multiline_string(chars, nesting_depth);
         }
 
         let mut content = String::new();
@@ -680,7 +760,7 @@ impl LexicalAnalyzer {
 
         loop {
             if let Some((_, ch)) = chars.next() {
-                len += 1;
+                len += ch.len_utf8();
                 if ch == '`' {
                     if chars.peek().map(|(_, c)| *c) == Some('`') {
                         chars.next(); // consume second backtick
@@ -727,11 +807,8 @@ impl LexicalAnalyzer {
             });
         }
 
-        if start_offset + 3 < source.len() {
-            let next_chars: String = source.chars().skip(start_offset).take(4).collect();
-            if next_chars == "r```" {
-                return self.parse_raw_multiline_string(chars, nesting_depth);
-            }
+        if source[start_offset..].starts_with("r```") {
+            return self.parse_raw_multiline_string(chars, nesting_depth);
         }
 
         if let Some((_, ch)) = chars.next() {
@@ -747,7 +824,7 @@ impl LexicalAnalyzer {
 
         loop {
             if let Some((_, ch)) = chars.next() {
-                len += 1;
+                len += ch.len_utf8();
                 if ch == '`' {
                     self.validate_string_size(&content)?;
                     self.metrics
@@ -779,7 +856,7 @@ impl LexicalAnalyzer {
         chars: &mut std::iter::Peekable<std::str::CharIndices<'_>>,
         _nesting_depth: u32,
     ) -> Result<(Token, usize), LexerError> {
-        chars.next(); // first `
+        // The first ` was consumed by the caller
         chars.next(); // second `
         chars.next(); // third `
 
@@ -788,7 +865,7 @@ impl LexicalAnalyzer {
         let mut backtick_count = 0;
 
         while let Some((_, ch)) = chars.next() {
-            len += 1;
+            len += ch.len_utf8();
             if ch == '`' {
                 backtick_count += 1;
                 if backtick_count == 3 {
@@ -821,7 +898,7 @@ impl LexicalAnalyzer {
         chars: &mut std::iter::Peekable<std::str::CharIndices<'_>>,
         _nesting_depth: u32,
     ) -> Result<(Token, usize), LexerError> {
-        chars.next(); // r
+        // The r was consumed by the caller
         chars.next(); // first `
         chars.next(); // second `
         chars.next(); // third `
@@ -831,7 +908,7 @@ impl LexicalAnalyzer {
         let mut backtick_count = 0;
 
         while let Some((_, ch)) = chars.next() {
-            len += 1;
+            len += ch.len_utf8();
             if ch == '`' {
                 backtick_count += 1;
                 if backtick_count == 3 {
@@ -872,7 +949,7 @@ impl LexicalAnalyzer {
         let mut has_dot = false;
         let mut len = 0;
 
-        if let Some(first_char) = source.chars().nth(start_offset) {
+        if let Some(first_char) = source[start_offset..].chars().next() {
             number_text.push(first_char);
             len += 1;
 
@@ -950,3 +1027,13 @@ impl Default for LexicalAnalyzer {
         Self::new()
     }
 }
+
+/// Style warning for a symbolic operator alias (`==`, `<>`) the lexer accepted
+fn warn_symbol_alias(spelling: &str, canonical: &str, pos: Position) {
+    log_warning!("Non-canonical spelling; use the canonical form",
+        "kind" => "operation",
+        "spelling" => spelling,
+        "canonical" => canonical,
+        "line" => pos.line
+    );
+}
@@ -220,6 +220,23 @@ pub fn get_token_counts(token_stream: &TokenStream) -> TokenCounts {
             | crate::tokens::Token::Matches
             | crate::tokens::Token::SubsetOf
             | crate::tokens::Token::SupersetOf
+            | crate::tokens::Token::EqualsAny
+            | crate::tokens::Token::NotEqualsAny
+            | crate::tokens::Token::LengthEquals
+            | crate::tokens::Token::LengthGreaterThan
+            | crate::tokens::Token::OccurrenceCount
+            | crate::tokens::Token::VersionEquals
+            | crate::tokens::Token::VersionNotEqual
+            | crate::tokens::Token::VersionGreaterThan
+            | crate::tokens::Token::VersionLessThan
+            | crate::tokens::Token::VersionGreaterOrEqual
+            | crate::tokens::Token::VersionLessOrEqual
+            | crate::tokens::Token::InSubnet
+            | crate::tokens::Token::NotInSubnet
+            | crate::tokens::Token::GlobMatch
+            | crate::tokens::Token::NotGlobMatch
+            | crate::tokens::Token::PermissionsAtMost
+            | crate::tokens::Token::PermissionsAtLeast
             | crate::tokens::Token::Plus
             | crate::tokens::Token::Minus
             | crate::tokens::Token::Multiply
@@ -312,7 +329,7 @@ pub enum SecurityRiskLevel {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::tokens::Token;
+    use crate::tokens::{Span, Token};
 
     #[test]
     fn test_create_analyzer() {
@@ -369,8 +386,8 @@ mod tests {
 
     #[test]
     fn test_token_counts_with_security() {
+        use crate::tokens::{Span, Spanned};
         use crate::tokens::{Token, TokenStream};
-        use crate::utils::{Span, Spanned};
 
         let tokens = vec![
             Spanned::new(
@@ -426,4 +443,79 @@ mod tests {
         assert!(MAX_TOKEN_COUNT > 0);
         assert!(MAX_STRING_NESTING_DEPTH > 0);
     }
+
+    fn tokenize_source(source: &str) -> Vec<(Token, Span)> {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("columns.esp");
+        std::fs::write(&path, source).unwrap();
+        let file_result = crate::file_processor::process_file(path.to_str().unwrap()).unwrap();
+        let stream = tokenize_file_result(file_result).unwrap();
+        stream
+            .all_tokens()
+            .iter()
+            .filter(|token| !matches!(token.value, Token::Space | Token::Tab | Token::Newline))
+            .map(|token| (token.value.clone(), token.span))
+            .collect()
+    }
+
+    fn start(span: &Span) -> (u32, u32) {
+        (span.start().line, span.start().column)
+    }
+
+    #[test]
+    fn test_tab_is_one_column() {
+        let tokens = tokenize_source("STATE s\n\t\tsize int > 1\nSTATE_END\n");
+        let size = tokens
+            .iter()
+            .find(|(token, _)| *token == Token::Identifier("size".to_string()))
+            .unwrap();
+        assert_eq!(start(&size.1), (2, 3));
+        assert_eq!(size.1.start().offset, 10);
+    }
+
+    #[test]
+    fn test_multibyte_text_columns_and_offsets() {
+        let source = "OBJECT o\n    path `/etc/é测` # ünïcode\n    owner `root`\nOBJECT_END\n";
+        let tokens = tokenize_source(source);
+
+        // The string ends after its closing backtick, in characters and bytes
+        let (_, path) = &tokens[3];
+        assert_eq!(start(path), (2, 10));
+        assert_eq!((path.end().line, path.end().column), (2, 19));
+        assert_eq!(path.slice(source), "`/etc/é测`");
+
+        // Text after multi-byte characters keeps its keyword and position
+        let (owner, span) = &tokens[5];
+        assert_eq!(*owner, Token::Identifier("owner".to_string()));
+        assert_eq!(start(span), (3, 5));
+        let (end, span) = &tokens[7];
+        assert_eq!(
+            *end,
+            Token::Keyword(crate::grammar::keywords::Keyword::ObjectEnd)
+        );
+        assert_eq!(start(span), (4, 1));
+    }
+
+    #[test]
+    fn test_multiline_string_moves_to_its_last_line() {
+        let source = "VAR v string ```a\nbé\n```\nDEF r```x```\n";
+        let tokens = tokenize_source(source);
+        let (string, span) = &tokens[3];
+        assert_eq!(
+            *string,
+            Token::StringLiteral(crate::tokens::StringLiteral::Multiline(
+                "a\nbé\n".to_string()
+            ))
+        );
+        assert_eq!((span.end().line, span.end().column), (3, 4));
+        let (def, span) = &tokens[4];
+        assert_eq!(*def, Token::Keyword(crate::grammar::keywords::Keyword::Def));
+        assert_eq!(start(span), (4, 1));
+        let (raw, span) = &tokens[5];
+        assert_eq!(
+            *raw,
+            Token::StringLiteral(crate::tokens::StringLiteral::RawMultiline("x".to_string()))
+        );
+        assert_eq!((span.end().line, span.end().column), (4, 13));
+    }
 }
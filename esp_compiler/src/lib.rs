@@ -1,8 +1,11 @@
 // Internal modules
 pub mod batch;
+pub mod compatibility;
 pub mod config;
 pub mod file_processor;
+pub mod formatter;
 pub mod grammar;
+pub mod imports;
 pub mod lexical;
 #[macro_use]
 pub mod logging;
@@ -11,6 +14,7 @@ pub mod reference_resolution;
 pub mod semantic_analysis;
 pub mod symbols;
 pub mod syntax;
+pub mod terminal;
 pub mod tokens;
 pub mod utils;
 pub mod validation;
@@ -167,6 +167,15 @@ pub mod symbols {
     pub const SYMBOL_SCOPE_VALIDATION_ERROR: Code = Code::new("E095");
 }
 
+/// Import resolution error codes
+pub mod imports {
+    use super::Code;
+
+    pub const INVALID_IMPORT: Code = Code::new("E100");
+    pub const IMPORT_CYCLE: Code = Code::new("E101");
+    pub const IMPORT_CONFLICT: Code = Code::new("E102");
+}
+
 /// Reference resolution error codes
 pub mod references {
     use super::Code;
@@ -195,6 +204,7 @@ pub mod structural {
     pub const COMPLEXITY_VIOLATION: Code = Code::new("E243");
     pub const CONSISTENCY_VIOLATION: Code = Code::new("E244");
     pub const MULTIPLE_STRUCTURAL_ERRORS: Code = Code::new("E245");
+    pub const INVALID_METADATA: Code = Code::new("E246");
 }
 
 /// Consumer integration error codes
@@ -720,6 +730,44 @@ fn get_error_registry() -> &'static HashMap<&'static str, ErrorMetadata> {
             ),
         );
 
+        // Import resolution errors
+        registry.insert(
+            "E100",
+            ErrorMetadata::new(
+                "E100",
+                "Imports",
+                Severity::High,
+                false,
+                false,
+                "Imported file is missing or cannot be imported",
+                "Check the IMPORT path, which is relative to the importing file",
+            ),
+        );
+        registry.insert(
+            "E101",
+            ErrorMetadata::new(
+                "E101",
+                "Imports",
+                Severity::High,
+                false,
+                false,
+                "Files import each other",
+                "Move the shared declarations into a file that neither imports",
+            ),
+        );
+        registry.insert(
+            "E102",
+            ErrorMetadata::new(
+                "E102",
+                "Imports",
+                Severity::High,
+                false,
+                false,
+                "Imported declaration has the same name as another declaration",
+                "Rename one of the declarations or stop importing one of the files",
+            ),
+        );
+
         // Reference resolution errors
         registry.insert(
             "E110",
@@ -862,6 +910,18 @@ fn get_error_registry() -> &'static HashMap<&'static str, ErrorMetadata> {
                 "Review and fix all structural validation errors",
             ),
         );
+        registry.insert(
+            "E246",
+            ErrorMetadata::new(
+                "E246",
+                "StructuralValidation",
+                Severity::Medium,
+                true,
+                false,
+                "Invalid or duplicate MET field",
+                "Give each MET field a unique identifier name and a non-empty printable value",
+            ),
+        );
         registry.insert(
             "I004",
             ErrorMetadata::new(
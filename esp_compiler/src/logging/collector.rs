@@ -2,8 +2,10 @@
 //!
 //! Provides organized error collection and reporting for parallel file processing
 
+use super::diagnostic::Diagnostic;
 use super::events::LogEvent;
 use crate::config::compile_time::logging::*;
+use crate::terminal::Palette;
 use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 use std::sync::Mutex;
@@ -487,6 +489,11 @@ impl CollectorResourceUsage {
 
 /// Format errors in cargo-style output
 pub fn format_cargo_style_errors(collector: &ErrorCollector) -> String {
+    format_cargo_style_errors_with(collector, Palette::current())
+}
+
+/// [`format_cargo_style_errors`] with an explicit palette
+pub fn format_cargo_style_errors_with(collector: &ErrorCollector, palette: Palette) -> String {
     let mut output = String::new();
     let all_events = collector.get_all_file_events();
 
@@ -498,32 +505,22 @@ pub fn format_cargo_style_errors(collector: &ErrorCollector) -> String {
         if !error_events.is_empty() || !warning_events.is_empty() {
             output.push_str(&format!("Checking {}...\n", file_path.display()));
 
+            // Spans are shown against the file as it is now
+            let source = std::fs::read_to_string(file_path).ok();
+            let file = file_path.display().to_string();
+
             // Print errors
             for event in error_events {
-                let span_info = event
-                    .span
-                    .as_ref()
-                    .map(|s| {
-                        format!(
-                            " --> {}:{}:{}",
-                            file_path.display(),
-                            s.start().line,
-                            s.start().column
-                        )
-                    })
-                    .unwrap_or_default();
-
-                output.push_str(&format!(
-                    "error[{}]: {}{}\n",
-                    event.code.as_str(),
-                    event.message,
-                    span_info
+                output.push_str(&Diagnostic::from_event(event).render(
+                    &file,
+                    source.as_deref(),
+                    palette,
                 ));
 
                 // Add severity and category for errors
                 output.push_str(&format!(
                     "  = severity: {}, category: {}\n",
-                    event.severity(),
+                    palette.severity(event.severity()),
                     event.category()
                 ));
 
@@ -546,24 +543,10 @@ pub fn format_cargo_style_errors(collector: &ErrorCollector) -> String {
 
             // Print warnings
             for event in warning_events {
-                let span_info = event
-                    .span
-                    .as_ref()
-                    .map(|s| {
-                        format!(
-                            " --> {}:{}:{}",
-                            file_path.display(),
-                            s.start().line,
-                            s.start().column
-                        )
-                    })
-                    .unwrap_or_default();
-
-                output.push_str(&format!(
-                    "warning[{}]: {}{}\n",
-                    event.code.as_str(),
-                    event.message,
-                    span_info
+                output.push_str(&Diagnostic::from_event(event).render(
+                    &file,
+                    source.as_deref(),
+                    palette,
                 ));
 
                 // Add context for warnings if present
@@ -674,6 +657,26 @@ mod tests {
         assert_eq!(summary.total_warnings, 1);
     }
 
+    #[test]
+    fn test_cargo_style_output_plain_and_colored() {
+        let collector = ErrorCollector::new();
+        let file_path = PathBuf::from("policy.esp");
+        collector.record_event(
+            &file_path,
+            LogEvent::error(codes::lexical::INVALID_CHARACTER, "Unexpected '@'"),
+        );
+        collector.record_event(&file_path, LogEvent::warning("Unused variable"));
+
+        let plain = format_cargo_style_errors_with(&collector, Palette::new(false));
+        let colored = format_cargo_style_errors_with(&collector, Palette::new(true));
+
+        assert!(plain.starts_with("Checking policy.esp...\nerror[E020]: Unexpected '@'\n"));
+        assert!(!plain.contains('\x1b'));
+        assert!(colored.contains("\x1b[1;31merror\x1b[0m[E020]: Unexpected '@'"));
+        assert!(colored.contains("\x1b[33mwarning\x1b[0m["));
+        assert_eq!(crate::terminal::strip_styles(&colored), plain);
+    }
+
     #[test]
     fn test_critical_errors() {
         let collector = ErrorCollector::new();
@@ -0,0 +1,252 @@
+//! Source-annotated diagnostics for ESP Compiler
+//!
+//! Renders a diagnostic rustc style, with the offending source line and a
+//! caret underline beneath the span:
+//!
+//! ```text
+//! error[E110]: Undefined reference target
+//!   --> policy.esp:12:9
+//!    |
+//! 12 |         OBJECT_REF missing
+//!    |         ^^^^^^^^^^^^^^^^^^
+//! ```
+//!
+//! Span columns count characters, so a tab or a multi-byte character is one
+//! column. For display, tabs expand to the next multiple of [`TAB_WIDTH`] and
+//! the carets are shifted to match.
+
+use super::events::{LogEvent, LogLevel};
+use crate::terminal::{Palette, Style};
+use crate::tokens::Span;
+use serde::{Deserialize, Serialize};
+
+/// Columns between tab stops when a source line is displayed
+pub const TAB_WIDTH: usize = 4;
+
+/// A diagnostic with its code and source location, in a form tooling can
+/// read back from serialized pipeline output
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Diagnostic {
+    /// `error`, `warning` or `info`
+    pub level: String,
+    pub code: String,
+    pub message: String,
+    pub span: Option<Span>,
+}
+
+impl Diagnostic {
+    pub fn from_event(event: &LogEvent) -> Self {
+        let level = match event.level {
+            LogLevel::Error => "error",
+            LogLevel::Warning => "warning",
+            LogLevel::Info | LogLevel::Debug => "info",
+        };
+        Self {
+            level: level.to_string(),
+            code: event.code.as_str().to_string(),
+            message: event.message.clone(),
+            span: event.span,
+        }
+    }
+
+    /// Render as `level[code]: message`, followed by the location and source
+    /// snippet when the span points into `source`
+    pub fn render(&self, file: &str, source: Option<&str>, palette: Palette) -> String {
+        let style = if self.level == "error" {
+            Style::Error
+        } else {
+            Style::Warning
+        };
+        let mut output = format!(
+            "{}[{}]: {}\n",
+            palette.paint(style, &self.level),
+            self.code,
+            self.message
+        );
+        if let Some(span) = &self.span {
+            output.push_str(&render_location(file, source, span, style, palette));
+        }
+        output
+    }
+}
+
+/// The `-->` line for a span, then the source line it starts on with the span
+/// underlined in `style`. Without the source only the `-->` line is given;
+/// spans without a line number (test spans built from offsets) give nothing
+pub fn render_location(
+    file: &str,
+    source: Option<&str>,
+    span: &Span,
+    style: Style,
+    palette: Palette,
+) -> String {
+    let start = span.start();
+    if start.line == 0 {
+        return String::new();
+    }
+
+    let line_number = start.line.to_string();
+    let gutter = " ".repeat(line_number.len());
+    let mut output = format!(
+        "{}{} {}:{}:{}\n",
+        gutter,
+        palette.paint(Style::Note, "-->"),
+        file,
+        start.line,
+        start.column
+    );
+
+    let Some(source) = source else {
+        return output;
+    };
+    // The end of input sits on the line after a trailing newline
+    let text = source
+        .lines()
+        .nth(start.line as usize - 1)
+        .unwrap_or_default();
+    let (display, carets_at, caret_count) = underline(text, span);
+
+    let bar = palette.paint(Style::Note, "|");
+    output.push_str(&format!("{} {}\n", gutter, bar));
+    output.push_str(&format!(
+        "{} {} {}\n",
+        palette.paint(Style::Note, &line_number),
+        bar,
+        display
+    ));
+    output.push_str(&format!(
+        "{} {} {}{}\n",
+        gutter,
+        bar,
+        " ".repeat(carets_at),
+        palette.paint(style, &"^".repeat(caret_count))
+    ));
+    output
+}
+
+/// Expand tabs in `text` and work out where the carets for `span` go: the
+/// display column they start at and how many there are. A span running past
+/// the end of the line is underlined to the end of it
+fn underline(text: &str, span: &Span) -> (String, usize, usize) {
+    let start_column = span.start().column as usize;
+    let end_column = if span.end().line == span.start().line {
+        span.end().column as usize
+    } else {
+        usize::MAX
+    };
+
+    let mut display = String::new();
+    let mut carets_at = None;
+    let mut carets_end = None;
+    for (index, ch) in text.chars().enumerate() {
+        let column = index + 1;
+        if column == start_column {
+            carets_at = Some(display.chars().count());
+        }
+        if column == end_column {
+            carets_end = Some(display.chars().count());
+        }
+        if ch == '\t' {
+            let width = TAB_WIDTH - display.chars().count() % TAB_WIDTH;
+            display.push_str(&" ".repeat(width));
+        } else {
+            display.push(ch);
+        }
+    }
+
+    let line_width = display.chars().count();
+    let carets_at = carets_at.unwrap_or(line_width);
+    let carets_end = carets_end.unwrap_or(line_width);
+    let caret_count = carets_end.saturating_sub(carets_at).max(1);
+    (display, carets_at, caret_count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logging::codes;
+    use crate::tokens::Position;
+
+    fn span(line: u32, start: u32, end: u32) -> Span {
+        Span::new(Position::new(0, line, start), Position::new(0, line, end))
+    }
+
+    fn plain(file: &str, source: &str, span: &Span) -> String {
+        render_location(file, Some(source), span, Style::Error, Palette::new(false))
+    }
+
+    #[test]
+    fn test_caret_underlines_span() {
+        let source = "DEF\n    STATE_REF missing\nDEF_END\n";
+        assert_eq!(
+            plain("policy.esp", source, &span(2, 5, 22)),
+            " --> policy.esp:2:5\n  |\n2 |     STATE_REF missing\n  |     ^^^^^^^^^^^^^^^^^\n"
+        );
+    }
+
+    #[test]
+    fn test_tabs_expand_to_tab_stops() {
+        // Column 3 is the third character, after two tabs
+        let source = "\t\tOBJECT_REF x\n";
+        let rendered = plain("p.esp", source, &span(1, 3, 15));
+        assert!(rendered.contains("1 |         OBJECT_REF x\n"));
+        assert!(rendered.ends_with("  |         ^^^^^^^^^^^^\n"));
+
+        // A tab after text only fills up to the next stop
+        let source = "ab\tc";
+        let rendered = plain("p.esp", source, &span(1, 4, 5));
+        assert!(rendered.contains("1 | ab  c\n"));
+        assert!(rendered.ends_with("  |     ^\n"));
+    }
+
+    #[test]
+    fn test_multibyte_characters_count_one_column() {
+        let source = "    path `/etc/é测` bad\n";
+        // `bad` starts at character column 20, byte offset 22
+        let rendered = plain("p.esp", source, &span(1, 20, 23));
+        assert!(rendered.ends_with(&format!("  | {}^^^\n", " ".repeat(19))));
+    }
+
+    #[test]
+    fn test_span_past_line_end_and_end_of_input() {
+        let source = "STATE s\n";
+        let multi_line = Span::new(Position::new(0, 1, 1), Position::new(0, 3, 2));
+        assert!(plain("p.esp", source, &multi_line).ends_with("  | ^^^^^^^\n"));
+
+        // EOF on the line after the trailing newline still gets a caret
+        let eof = plain("p.esp", source, &span(2, 1, 1));
+        assert!(eof.ends_with("2 | \n  | ^\n"));
+    }
+
+    #[test]
+    fn test_location_without_source_or_line() {
+        let location = render_location(
+            "p.esp",
+            None,
+            &span(12, 3, 4),
+            Style::Error,
+            Palette::new(false),
+        );
+        assert_eq!(location, "  --> p.esp:12:3\n");
+        assert!(plain("p.esp", "x", &Span::from_offsets(0, 1)).is_empty());
+    }
+
+    #[test]
+    fn test_diagnostic_from_event_renders_code() {
+        let event = LogEvent::error(codes::references::UNDEFINED_REFERENCE, "Undefined")
+            .with_span(span(1, 1, 4));
+        let diagnostic = Diagnostic::from_event(&event);
+        assert_eq!(diagnostic.level, "error");
+
+        let rendered = diagnostic.render("p.esp", Some("DEF"), Palette::new(false));
+        assert!(rendered.starts_with(&format!(
+            "error[{}]: Undefined\n --> p.esp:1:1\n",
+            diagnostic.code
+        )));
+        assert!(rendered.ends_with("  | ^^^\n"));
+
+        let json = serde_json::to_string(&diagnostic).unwrap();
+        let back: Diagnostic = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, diagnostic);
+    }
+}
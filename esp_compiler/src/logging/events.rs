@@ -1,7 +1,7 @@
 //! Event system for ESP Parser logging
 
 use super::codes::Code;
-use crate::utils::Span;
+use crate::tokens::Span;
 use std::collections::HashMap;
 use std::time::SystemTime;
 
@@ -6,6 +6,7 @@
 pub mod codes;
 pub mod collector;
 pub mod config;
+pub mod diagnostic;
 pub mod events;
 pub mod macros;
 pub mod service;
@@ -17,6 +18,7 @@ use std::sync::{Arc, OnceLock};
 // Re-export main types
 pub use codes::Code;
 pub use collector::{ErrorCollector, FileProcessingContext, ProcessingSummary};
+pub use diagnostic::Diagnostic;
 pub use events::{LogEvent, LogLevel};
 pub use service::{ConsoleLogger, Logger, LoggingService, MemoryLogger, StructuredLogger};
 
@@ -37,9 +39,19 @@ thread_local! {
 
 /// Initialize global logging system
 pub fn init_global_logging() -> Result<(), String> {
+    init_configured_logging(service::create_configured_service())
+}
+
+/// [`init_global_logging`] with console output on stderr, for programs whose
+/// stdout carries their output
+pub fn init_global_logging_on_stderr() -> Result<(), String> {
+    init_configured_logging(service::LoggingService::with_config_on_stderr())
+}
+
+fn init_configured_logging(service: service::LoggingService) -> Result<(), String> {
     config::validate_config().map_err(|e| format!("Configuration validation failed: {}", e))?;
 
-    let logging_service = Arc::new(service::create_configured_service());
+    let logging_service = Arc::new(service);
     let error_collector = Arc::new(ErrorCollector::new());
 
     GLOBAL_LOGGER
@@ -168,7 +180,7 @@ pub fn get_current_file_context() -> Option<FileProcessingContext> {
 pub fn log_error_with_context(
     code: Code,
     message: &str,
-    span: Option<crate::utils::Span>,
+    span: Option<crate::tokens::Span>,
     context: Vec<(&str, &str)>,
 ) {
     let mut event = LogEvent::error(code, message);
@@ -250,6 +262,20 @@ pub fn get_file_errors(file_path: &Path) -> Vec<LogEvent> {
         .unwrap_or_default()
 }
 
+/// Errors and warnings for specific file, with their source spans
+pub fn get_file_diagnostics(file_path: &Path) -> Vec<Diagnostic> {
+    try_get_global_error_collector()
+        .map(|collector| {
+            collector
+                .get_file_events(file_path)
+                .iter()
+                .filter(|event| event.is_error() || event.is_warning())
+                .map(Diagnostic::from_event)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
 /// Print cargo-style summary
 pub fn print_cargo_style_summary() {
     if let Some(collector) = try_get_global_error_collector() {
@@ -3,7 +3,7 @@
 use super::codes::Code;
 use super::config;
 use super::events::{LogEvent, LogLevel};
-use crate::utils::Span;
+use crate::tokens::Span;
 use std::sync::{Arc, Mutex};
 
 /// Simple logger trait
@@ -25,11 +25,21 @@ impl LoggingService {
 
     /// Create service with configuration-aware settings
     pub fn with_config() -> Self {
+        Self::configured(false)
+    }
+
+    /// [`LoggingService::with_config`] writing every event to stderr, for
+    /// programs whose stdout carries their output
+    pub fn with_config_on_stderr() -> Self {
+        Self::configured(true)
+    }
+
+    fn configured(stderr: bool) -> Self {
         let min_level = config::get_min_log_level();
         let logger: Arc<dyn Logger> = if config::use_structured_logging() {
-            Arc::new(StructuredLogger::new(min_level))
+            Arc::new(StructuredLogger { min_level, stderr })
         } else {
-            Arc::new(ConsoleLogger::new(min_level))
+            Arc::new(ConsoleLogger { min_level, stderr })
         };
 
         Self::new(logger, min_level)
@@ -135,24 +145,41 @@ impl LoggingService {
     }
 }
 
+/// Print a console line: errors (and everything when `stderr` is set) to
+/// stderr, the rest to stdout
+fn print_console(level: LogLevel, stderr: bool, line: &str) {
+    if stderr || level == LogLevel::Error {
+        eprintln!("{}", line);
+    } else {
+        println!("{}", line);
+    }
+}
+
 /// Simple console logger
 pub struct ConsoleLogger {
     min_level: LogLevel,
+    stderr: bool,
 }
 
 impl ConsoleLogger {
     pub fn new(min_level: LogLevel) -> Self {
-        Self { min_level }
+        Self {
+            min_level,
+            stderr: false,
+        }
+    }
+
+    /// Write every event to stderr
+    pub fn on_stderr(mut self) -> Self {
+        self.stderr = true;
+        self
     }
 }
 
 impl Logger for ConsoleLogger {
     fn log(&self, event: &LogEvent) {
         if event.level <= self.min_level {
-            match event.level {
-                LogLevel::Error => eprintln!("{}", event.format()),
-                _ => println!("{}", event.format()),
-            }
+            print_console(event.level, self.stderr, &event.format());
         }
     }
 }
@@ -160,30 +187,30 @@ impl Logger for ConsoleLogger {
 /// Structured logger for JSON output and better tooling integration
 pub struct StructuredLogger {
     min_level: LogLevel,
+    stderr: bool,
 }
 
 impl StructuredLogger {
     pub fn new(min_level: LogLevel) -> Self {
-        Self { min_level }
+        Self {
+            min_level,
+            stderr: false,
+        }
+    }
+
+    /// Write every event to stderr
+    pub fn on_stderr(mut self) -> Self {
+        self.stderr = true;
+        self
     }
 }
 
 impl Logger for StructuredLogger {
     fn log(&self, event: &LogEvent) {
         if event.level <= self.min_level {
-            match event.format_json() {
-                Ok(json) => match event.level {
-                    LogLevel::Error => eprintln!("{}", json),
-                    _ => println!("{}", json),
-                },
-                Err(_) => {
-                    // Fallback to regular format if JSON serialization fails
-                    match event.level {
-                        LogLevel::Error => eprintln!("{}", event.format()),
-                        _ => println!("{}", event.format()),
-                    }
-                }
-            }
+            // Fallback to regular format if JSON serialization fails
+            let line = event.format_json().unwrap_or_else(|_| event.format());
+            print_console(event.level, self.stderr, &line);
         }
     }
 }
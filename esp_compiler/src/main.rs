@@ -1,37 +1,79 @@
-use esp_compiler::{batch, logging, pipeline};
-use std::env;
-use std::path::Path;
+mod cli;
+
+use clap::{CommandFactory, FromArgMatches};
+use cli::{Cli, Command};
+use esp_compiler::compatibility::{self, CapabilityManifest};
+use esp_compiler::grammar::ast::nodes::EspFile;
+use esp_compiler::grammar::ast::{AstDocument, AST_VERSION};
+use esp_compiler::terminal::{self, Palette};
+use esp_compiler::{batch, file_processor, formatter, logging, pipeline};
+use std::path::{Path, PathBuf};
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let matches = Cli::command()
+        .after_long_help(long_help_with_capabilities())
+        .get_matches();
+    let cli = Cli::from_arg_matches(&matches)?;
+    terminal::set_color_choice(cli.color);
+
+    if let Some(Command::Completions { shell }) = cli.command {
+        cli::write_completions(shell, &mut std::io::stdout());
+        return Ok(());
+    }
+
+    if cli.man {
+        cli::write_man_page(&mut std::io::stdout())?;
+        return Ok(());
+    }
+
     // Initialize global logging system
     logging::init_global_logging()?;
 
     // Validate pipeline configuration
     pipeline::validate_pipeline()?;
 
-    let args: Vec<String> = env::args().collect();
-    if args.len() < 2 {
-        eprintln!("Usage: {} <input.esp|directory> [options]", args[0]);
-        eprintln!("       {} --help", args[0]);
-        std::process::exit(1);
-    }
+    let input_path = match cli.input.as_deref() {
+        Some(path) => path,
+        None => {
+            Cli::command().print_help()?;
+            std::process::exit(1);
+        }
+    };
+
+    let batch_config = cli.batch.to_batch_config();
 
-    if args[1] == "--help" {
-        print_help(&args[0]);
+    if cli.fmt {
+        let files = if input_path.is_dir() {
+            batch::discover_esp_files(input_path, &batch_config)?
+        } else {
+            vec![input_path.to_path_buf()]
+        };
+        format_files(&files, cli.check)?;
         return Ok(());
     }
 
-    let input_path = Path::new(&args[1]);
-
-    // Parse additional options
-    let batch_config = parse_batch_options(&args[2..]);
+    let compat = match &cli.target_capabilities {
+        Some(path) => Some(CompatCheck {
+            target: CapabilityManifest::load(path)?,
+            deny: cli.deny_incompatible,
+        }),
+        None => None,
+    };
 
     if input_path.is_file() {
         // Single file processing
-        process_single_file(&args[1])?;
+        process_single_file(
+            &input_path.display().to_string(),
+            cli.emit_ast.as_deref(),
+            compat.as_ref(),
+        )?;
     } else if input_path.is_dir() {
+        if cli.emit_ast.is_some() {
+            eprintln!("Error: --emit-ast requires a single input file");
+            std::process::exit(1);
+        }
         // Batch directory processing
-        process_directory_batch(input_path, &batch_config)?;
+        process_directory_batch(input_path, &batch_config, compat.as_ref())?;
     } else {
         eprintln!("Error: Input must be a file (.esp) or directory");
         eprintln!("  File: {}", input_path.display());
@@ -41,177 +83,155 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
-fn print_help(program_name: &str) {
-    println!("ESP Compiler v{}", env!("CARGO_PKG_VERSION"));
-    println!("Complete ESP Compilation pipeline with batch processing");
-    println!();
-    println!("USAGE:");
-    println!(
-        "    {} <input.esp>                    # Process single file",
-        program_name
-    );
-    println!(
-        "    {} <directory> [options]          # Process directory",
-        program_name
-    );
-    println!();
-    println!("ARGUMENTS:");
-    println!("    <input.esp>    Path to the ESP file to process");
-    println!("    <directory>    Path to directory containing ESP files");
-    println!();
-    println!("OPTIONS:");
-    println!("    --help              Show this help message");
-    println!("    --sequential        Force sequential processing (no parallelism)");
-    println!("    --parallel          Force parallel processing (default)");
-    println!("    --threads N         Set maximum number of threads (default: auto)");
-    println!("    --no-recursive      Don't search subdirectories");
-    println!("    --max-files N       Limit maximum files to process");
-    println!("    --fail-fast         Stop on first error");
-    println!("    --quiet             Suppress progress reporting");
-    println!();
-    println!("SINGLE FILE OUTPUT:");
-    println!("    Success: Detailed processing metrics, AST structure, symbol information");
-    println!("    Failure: Comprehensive error information with recommendations");
-    println!();
-    println!("BATCH PROCESSING OUTPUT:");
-    println!("    Cargo-style error reporting grouped by file");
-    println!("    Processing summary with success/failure statistics");
-    println!("    Performance metrics and throughput information");
-    println!();
-    println!("EXAMPLES:");
-    println!(
-        "    {} example.esp                     # Single file",
-        program_name
-    );
-    println!(
-        "    {} /path/to/esp-files/             # All files in directory",
-        program_name
-    );
-    println!(
-        "    {} configs/ --threads 4            # 4 threads max",
-        program_name
-    );
-    println!(
-        "    {} tests/ --sequential --fail-fast # Sequential with early exit",
-        program_name
-    );
-    println!(
-        "    {} large-dir/ --max-files 100      # Limit file count",
-        program_name
-    );
-    println!();
+/// Long help text: static exit codes/examples plus live pipeline and batch capabilities
+fn long_help_with_capabilities() -> String {
+    let mut help = String::from(cli::AFTER_LONG_HELP);
+    help.push_str("\n\nPIPELINE CAPABILITIES:\n");
 
-    // Print pipeline capabilities
     let pipeline_info = pipeline::get_pipeline_info();
-    println!("PIPELINE CAPABILITIES:");
     for line in pipeline_info.report().lines() {
-        println!("    {}", line);
+        help.push_str(&format!("    {}\n", line));
     }
-    println!();
 
-    // Print batch capabilities
     let batch_info = batch::get_batch_info();
-    println!("BATCH PROCESSING CAPABILITIES:");
-    println!(
-        "    Max recommended threads: {}",
+    help.push_str("\nBATCH PROCESSING CAPABILITIES:\n");
+    help.push_str(&format!(
+        "    Max recommended threads: {}\n",
         batch_info.max_recommended_threads
-    );
-    println!(
-        "    Recursive discovery: {}",
+    ));
+    help.push_str(&format!(
+        "    Recursive discovery: {}\n",
         batch_info.supports_recursive_discovery
-    );
-    println!(
-        "    Parallel processing: {}",
+    ));
+    help.push_str(&format!(
+        "    Parallel processing: {}\n",
         batch_info.supports_parallel_processing
-    );
-    println!(
-        "    Progress reporting: {}",
+    ));
+    help.push_str(&format!(
+        "    Progress reporting: {}\n",
         batch_info.supports_progress_reporting
-    );
-    println!("    Fail-fast mode: {}", batch_info.supports_fail_fast);
-    println!(
+    ));
+    help.push_str(&format!(
+        "    Fail-fast mode: {}\n",
+        batch_info.supports_fail_fast
+    ));
+    help.push_str(&format!(
         "    Supported extensions: {}",
         batch_info.supported_file_extensions.join(", ")
-    );
+    ));
+    help
 }
 
-fn parse_batch_options(args: &[String]) -> batch::BatchConfig {
-    let mut config = batch::BatchConfig::default();
-
-    let mut i = 0;
-    while i < args.len() {
-        match args[i].as_str() {
-            "--sequential" => {
-                config.max_threads = 1;
-            }
-            "--parallel" => {
-                // Keep default parallel setting
-            }
-            "--threads" => {
-                if i + 1 < args.len() {
-                    if let Ok(threads) = args[i + 1].parse::<usize>() {
-                        config.max_threads = threads.max(1).min(32); // Reasonable bounds
-                        i += 1; // Skip the number argument
-                    } else {
-                        eprintln!(
-                            "Warning: Invalid thread count '{}', using default",
-                            args[i + 1]
-                        );
-                        i += 1;
-                    }
+/// Format each file in place, or with `check` only list the files that would
+/// change. Exits 1 if a file cannot be formatted or, when checking, is not
+/// formatted
+fn format_files(files: &[PathBuf], check: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let mut failed = 0;
+    let mut changed = 0;
+    for (file_id, path) in files.iter().enumerate() {
+        let display = path.display().to_string();
+        let result = logging::with_file_context(path.clone(), file_id, || {
+            let file = file_processor::process_file(&display)?;
+            let formatted = formatter::format_source(&file.source)?;
+            Ok::<_, Box<dyn std::error::Error>>((file.source != formatted).then_some(formatted))
+        });
+
+        match result {
+            Ok(None) => {}
+            Ok(Some(formatted)) => {
+                changed += 1;
+                if check {
+                    println!("Would reformat: {}", display);
                 } else {
-                    eprintln!("Warning: --threads requires a number");
+                    std::fs::write(path, formatted)?;
+                    println!("Formatted: {}", display);
                 }
             }
-            "--no-recursive" => {
-                config.recursive = false;
-            }
-            "--max-files" => {
-                if i + 1 < args.len() {
-                    if let Ok(max_files) = args[i + 1].parse::<usize>() {
-                        config.max_files = Some(max_files);
-                        i += 1; // Skip the number argument
-                    } else {
-                        eprintln!("Warning: Invalid max files '{}', ignoring", args[i + 1]);
-                        i += 1;
-                    }
-                } else {
-                    eprintln!("Warning: --max-files requires a number");
-                }
-            }
-            "--fail-fast" => {
-                config.fail_fast = true;
-            }
-            "--quiet" => {
-                config.progress_reporting = false;
-            }
-            _ => {
-                eprintln!("Warning: Unknown option '{}'", args[i]);
+            Err(error) => {
+                failed += 1;
+                eprintln!(
+                    "{}: {}: {}",
+                    Palette::current().status("error"),
+                    display,
+                    error
+                );
             }
         }
-        i += 1;
     }
 
-    config
+    if failed > 0 {
+        logging::print_cargo_style_summary();
+    }
+    let verb = if check {
+        "would be reformatted"
+    } else {
+        "reformatted"
+    };
+    println!(
+        "\n{} file(s) checked: {} {}, {} failed",
+        files.len(),
+        changed,
+        verb,
+        failed
+    );
+
+    if failed > 0 || (check && changed > 0) {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// Capability manifest to check compiled policies against
+struct CompatCheck {
+    target: CapabilityManifest,
+    deny: bool,
+}
+
+impl CompatCheck {
+    /// Print one diagnostic per unsupported construct; true if any were found
+    fn report(&self, file_path: &str, ast: &EspFile) -> bool {
+        let found = compatibility::check(ast, &self.target);
+        let level = if self.deny { "error" } else { "warning" };
+        let level = Palette::current().status(level);
+        for incompatibility in &found {
+            eprintln!("{}: {}: {}", level, file_path, incompatibility);
+        }
+        !found.is_empty()
+    }
 }
 
-fn process_single_file(file_path: &str) -> Result<(), Box<dyn std::error::Error>> {
+fn process_single_file(
+    file_path: &str,
+    emit_ast: Option<&Path>,
+    compat: Option<&CompatCheck>,
+) -> Result<(), Box<dyn std::error::Error>> {
     println!("Processing file: {}", file_path);
 
     // Process through complete 7-stage pipeline
     match pipeline::process_file(file_path) {
-        Ok(_) => {
-            println!("\nSUCCESS: Complete parsing and validation successful");
+        Ok(result) => {
+            println!(
+                "\n{}: Complete parsing and validation successful",
+                Palette::current().status("SUCCESS")
+            );
+
+            let incompatible = compat.is_some_and(|c| c.report(file_path, &result.ast));
+
+            if let Some(out) = emit_ast {
+                let json = AstDocument::new(result.ast).to_json()?;
+                std::fs::write(out, json)?;
+                println!("AST (version {}) written to {}", AST_VERSION, out.display());
+            }
 
             // Print cargo-style summary (if any errors were collected during processing)
             logging::print_cargo_style_summary();
+
+            if incompatible && compat.is_some_and(|c| c.deny) {
+                std::process::exit(1);
+            }
         }
         Err(error) => {
-            eprintln!("\nFAILED: {}", error);
-            print_detailed_error(&error);
-
-            // Print cargo-style error summary
-            logging::print_cargo_style_summary();
+            pipeline::report_failure(&error);
             std::process::exit(1);
         }
     }
@@ -222,6 +242,7 @@ fn process_single_file(file_path: &str) -> Result<(), Box<dyn std::error::Error>
 fn process_directory_batch(
     dir_path: &Path,
     config: &batch::BatchConfig,
+    compat: Option<&CompatCheck>,
 ) -> Result<(), Box<dyn std::error::Error>> {
     println!("Starting batch processing: {}", dir_path.display());
     println!(
@@ -241,8 +262,15 @@ fn process_directory_batch(
             // Print detailed cargo-style error report
             logging::print_cargo_style_summary();
 
+            let mut incompatible = false;
+            if let Some(compat) = compat {
+                for (path, result) in &results.successful_files {
+                    incompatible |= compat.report(&path.display().to_string(), &result.ast);
+                }
+            }
+
             // Exit with error code if any files failed
-            if results.failure_count() > 0 {
+            if results.failure_count() > 0 || (incompatible && compat.is_some_and(|c| c.deny)) {
                 std::process::exit(1);
             }
         }
@@ -307,7 +335,7 @@ fn print_batch_results(results: &batch::BatchResults) {
     if results.failure_count() > 0 {
         println!("\nFailed Files:");
         for (file_path, error) in &results.failed_files {
-            println!("  {}: {}", file_path.display(), get_error_summary(error));
+            println!("  {}: {}", file_path.display(), error.summary());
         }
     }
 
@@ -339,71 +367,25 @@ fn print_batch_results(results: &batch::BatchResults) {
     }
 }
 
-fn get_error_summary(error: &pipeline::PipelineError) -> String {
-    match error {
-        pipeline::PipelineError::FileProcessing(_) => "File processing error".to_string(),
-        pipeline::PipelineError::LexicalAnalysis(_) => "Lexical analysis error".to_string(),
-        pipeline::PipelineError::SyntaxAnalysis(_) => "Syntax analysis error".to_string(),
-        pipeline::PipelineError::SymbolDiscovery(_) => "Symbol discovery error".to_string(),
-        pipeline::PipelineError::ReferenceValidation(_) => "Reference validation error".to_string(),
-        pipeline::PipelineError::SemanticAnalysis(_) => "Semantic analysis error".to_string(),
-        pipeline::PipelineError::StructuralValidation(_) => {
-            "Structural validation error".to_string()
-        }
-        pipeline::PipelineError::Pipeline { .. } => "Pipeline error".to_string(),
-    }
-}
-
-fn print_detailed_error(error: &pipeline::PipelineError) {
-    match error {
-        pipeline::PipelineError::FileProcessing(ref file_err) => {
-            eprintln!("File processing stage failed:");
-            eprintln!("  {}", file_err);
-        }
-        pipeline::PipelineError::LexicalAnalysis(ref lex_err) => {
-            eprintln!("Lexical analysis stage failed:");
-            eprintln!("  {}", lex_err);
-        }
-        pipeline::PipelineError::SyntaxAnalysis(ref syntax_err) => {
-            eprintln!("Syntax analysis stage failed:");
-            eprintln!("  {}", syntax_err);
-        }
-        pipeline::PipelineError::SymbolDiscovery(ref symbol_err) => {
-            eprintln!("Symbol discovery stage failed:");
-            eprintln!("  {}", symbol_err);
-        }
-        pipeline::PipelineError::ReferenceValidation(ref ref_err) => {
-            eprintln!("Reference validation stage failed:");
-            eprintln!("  {}", ref_err);
-        }
-        pipeline::PipelineError::SemanticAnalysis(ref semantic_err) => {
-            eprintln!("Semantic analysis stage failed:");
-            eprintln!("  {}", semantic_err);
-        }
-        pipeline::PipelineError::StructuralValidation(ref structural_err) => {
-            eprintln!("Structural validation stage failed:");
-            eprintln!("  {}", structural_err);
-        }
-        pipeline::PipelineError::Pipeline { message } => {
-            eprintln!("Pipeline error: {}", message);
-        }
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    use clap::Parser;
+
     #[test]
     fn test_parse_batch_options() {
-        let args = vec![
-            "--threads".to_string(),
-            "4".to_string(),
-            "--fail-fast".to_string(),
-            "--no-recursive".to_string(),
-        ];
-
-        let config = parse_batch_options(&args);
+        let cli = Cli::try_parse_from([
+            "esp_compiler",
+            "dir/",
+            "--threads",
+            "4",
+            "--fail-fast",
+            "--no-recursive",
+        ])
+        .unwrap();
+
+        let config = cli.batch.to_batch_config();
         assert_eq!(config.max_threads, 4);
         assert!(config.fail_fast);
         assert!(!config.recursive);
@@ -411,21 +393,12 @@ mod tests {
 
     #[test]
     fn test_parse_batch_options_invalid() {
-        let args = vec![
-            "--threads".to_string(),
-            "invalid".to_string(),
-            "--unknown-option".to_string(),
-        ];
-
-        let config = parse_batch_options(&args);
-        // Should use default threads when invalid number provided
-        assert_ne!(config.max_threads, 0);
-    }
+        // Invalid values and unknown options are rejected rather than ignored
+        assert!(Cli::try_parse_from(["esp_compiler", "dir/", "--threads", "invalid"]).is_err());
+        assert!(Cli::try_parse_from(["esp_compiler", "dir/", "--unknown-option"]).is_err());
 
-    #[test]
-    fn test_get_error_summary() {
-        let error = pipeline::PipelineError::pipeline_error("test");
-        let summary = get_error_summary(&error);
-        assert_eq!(summary, "Pipeline error");
+        // Out-of-range thread counts are clamped
+        let cli = Cli::try_parse_from(["esp_compiler", "dir/", "--threads", "0"]).unwrap();
+        assert_ne!(cli.batch.to_batch_config().max_threads, 0);
     }
 }
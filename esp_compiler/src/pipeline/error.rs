@@ -1,5 +1,7 @@
 use crate::file_processor::FileProcessorError;
+use crate::imports::ImportError;
 use crate::lexical::LexerError;
+use crate::logging::codes::{self, Code};
 use crate::reference_resolution::ReferenceValidationError;
 use crate::semantic_analysis::SemanticError;
 use crate::symbols::SymbolDiscoveryError;
@@ -15,8 +17,19 @@ pub enum PipelineError {
     #[error("Lexical analysis failed: {0}")]
     LexicalAnalysis(#[from] LexerError),
 
-    #[error("Syntax analysis failed: {0}")]
-    SyntaxAnalysis(#[from] SyntaxError),
+    /// Every syntax error in the file, in source order
+    #[error("Syntax analysis failed: {}", describe_syntax_errors(.0))]
+    SyntaxAnalysis(Vec<SyntaxError>),
+
+    #[error("Import resolution failed: {0}")]
+    Import(#[from] ImportError),
+
+    /// A failure while reading or parsing an imported file
+    #[error("In imported file {}: {source}", file.display())]
+    Imported {
+        file: std::path::PathBuf,
+        source: Box<PipelineError>,
+    },
 
     #[error("Symbol discovery failed: {0}")]
     SymbolDiscovery(#[from] SymbolDiscoveryError),
@@ -34,10 +47,87 @@ pub enum PipelineError {
     Pipeline { message: String },
 }
 
+impl From<Vec<SyntaxError>> for PipelineError {
+    fn from(errors: Vec<SyntaxError>) -> Self {
+        Self::SyntaxAnalysis(errors)
+    }
+}
+
 impl PipelineError {
     pub fn pipeline_error(message: &str) -> Self {
         Self::Pipeline {
             message: message.to_string(),
         }
     }
+
+    /// Diagnostic code reported by the failing stage
+    pub fn error_code(&self) -> Code {
+        match self {
+            Self::FileProcessing(e) => e.error_code(),
+            Self::LexicalAnalysis(e) => e.error_code(),
+            Self::SyntaxAnalysis(errors) => errors
+                .first()
+                .map_or(codes::syntax::GRAMMAR_VIOLATION, SyntaxError::error_code),
+            Self::Import(e) => e.error_code(),
+            Self::Imported { source, .. } => source.error_code(),
+            Self::SymbolDiscovery(e) => e.error_code(),
+            Self::ReferenceValidation(e) => e.error_code(),
+            Self::SemanticAnalysis(e) => e.error_code(),
+            Self::StructuralValidation(e) => e.error_code(),
+            Self::Pipeline { .. } => codes::system::INTERNAL_ERROR,
+        }
+    }
+
+    /// Name of the failing stage, None for errors outside any stage
+    pub fn stage(&self) -> Option<&'static str> {
+        match self {
+            Self::FileProcessing(_) => Some("File processing"),
+            Self::LexicalAnalysis(_) => Some("Lexical analysis"),
+            Self::SyntaxAnalysis(_) => Some("Syntax analysis"),
+            Self::Import(_) => Some("Import resolution"),
+            Self::Imported { source, .. } => source.stage(),
+            Self::SymbolDiscovery(_) => Some("Symbol discovery"),
+            Self::ReferenceValidation(_) => Some("Reference validation"),
+            Self::SemanticAnalysis(_) => Some("Semantic analysis"),
+            Self::StructuralValidation(_) => Some("Structural validation"),
+            Self::Pipeline { .. } => None,
+        }
+    }
+
+    /// One-line description of the kind of failure, for batch summaries
+    pub fn summary(&self) -> String {
+        match self.stage() {
+            Some(stage) => format!("{} error", stage),
+            None => "Pipeline error".to_string(),
+        }
+    }
+
+    /// The error reported by the failing stage, without the stage prefix
+    pub fn detail(&self) -> String {
+        match self {
+            Self::FileProcessing(e) => e.to_string(),
+            Self::LexicalAnalysis(e) => e.to_string(),
+            Self::SyntaxAnalysis(errors) => errors
+                .iter()
+                .map(SyntaxError::to_string)
+                .collect::<Vec<_>>()
+                .join("\n  "),
+            Self::Import(e) => e.to_string(),
+            Self::Imported { file, source } => format!("{}: {}", file.display(), source.detail()),
+            Self::SymbolDiscovery(e) => e.to_string(),
+            Self::ReferenceValidation(e) => e.to_string(),
+            Self::SemanticAnalysis(e) => e.to_string(),
+            Self::StructuralValidation(e) => e.to_string(),
+            Self::Pipeline { message } => message.clone(),
+        }
+    }
+}
+
+/// The first syntax error, noting how many more follow it
+fn describe_syntax_errors(errors: &[SyntaxError]) -> String {
+    match errors {
+        [] => "no errors recorded".to_string(),
+        [error] => error.to_string(),
+        [first, rest @ ..] => format!("{} (and {} more)", first, rest.len()),
+    }
 }
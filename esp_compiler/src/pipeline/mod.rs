@@ -1,6 +1,7 @@
 mod error;
 mod info;
 pub mod output; // This was missing from your original mod.rs
+mod report;
 mod result;
 mod stats;
 mod validation;
@@ -9,13 +10,14 @@ mod validation;
 pub use error::PipelineError;
 pub use info::{get_pipeline_info, PipelineInfo};
 pub use output::PipelineOutput; // Export the output module
+pub use report::{format_failure, report_failure};
 pub use result::PipelineResult;
 pub use stats::{get_pipeline_stats, PipelineStats};
 pub use validation::validate_pipeline;
 
 use crate::config::runtime::ReferenceValidationPreferences;
 use crate::logging;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::Instant;
 
 /// Process a single file through the complete pipeline (file -> lexical -> syntax -> symbols -> references -> semantics -> validation)
@@ -37,8 +39,9 @@ pub fn process_file(file_path: &str) -> Result<PipelineResult, PipelineError> {
         let _ = analyzer.tokenize_file_result(file_result.clone())?; // Re-tokenize to get metrics
         let lexical_metrics = analyzer.metrics().clone();
 
-        // Stage 3: Syntax analysis
+        // Stage 3: Syntax analysis, then flatten IMPORTed declarations
         let ast = crate::syntax::parse_esp_file(tokens.clone())?;
+        let (ast, imported_files) = crate::imports::resolve_imports(ast, Path::new(file_path))?;
 
         // Stage 4: Symbol discovery
         let symbol_discovery_result = crate::symbols::discover_symbols_from_ast(ast.clone())?;
@@ -70,6 +73,7 @@ pub fn process_file(file_path: &str) -> Result<PipelineResult, PipelineError> {
         let result = PipelineResult::new(
             ast,
             file_result.metadata,
+            imported_files,
             lexical_metrics,
             symbol_discovery_result,
             reference_validation_result,
@@ -107,6 +111,7 @@ pub fn process_file_with_preferences(
         let _ = analyzer.tokenize_file_result(file_result.clone())?;
         let lexical_metrics = analyzer.metrics().clone();
         let ast = crate::syntax::parse_esp_file(tokens.clone())?;
+        let (ast, imported_files) = crate::imports::resolve_imports(ast, Path::new(file_path))?;
         let symbol_discovery_result = crate::symbols::discover_symbols_from_ast(ast.clone())?;
 
         // Stage 5: Reference validation with custom preferences
@@ -136,6 +141,7 @@ pub fn process_file_with_preferences(
         let result = PipelineResult::new(
             ast,
             file_result.metadata,
+            imported_files,
             lexical_metrics,
             symbol_discovery_result,
             reference_validation_result,
@@ -170,4 +176,1100 @@ mod tests {
             _ => panic!("Wrong error type"),
         }
     }
+
+    #[test]
+    fn test_syntax_errors_are_all_reported() {
+        let _ = crate::logging::init_global_logging();
+        let fixture = "tests/fixtures/syntax_errors.esp";
+
+        let errors = match process_file(fixture) {
+            Err(PipelineError::SyntaxAnalysis(errors)) => errors,
+            other => panic!("expected syntax errors, got {:?}", other.map(|_| ())),
+        };
+        let lines: Vec<u32> = errors
+            .iter()
+            .map(|error| error.span().expect("span").start().line)
+            .collect();
+        assert_eq!(lines, [4, 5, 9, 21, 24]);
+
+        // Each one is in the cargo-style summary, under the file
+        let collector = crate::logging::try_get_global_error_collector().unwrap();
+        assert_eq!(collector.get_file_errors(Path::new(fixture)).len(), 5);
+        let summary = crate::logging::collector::format_cargo_style_errors_with(
+            collector,
+            crate::terminal::Palette::new(false),
+        );
+        let section = summary
+            .split("Checking ")
+            .find(|section| section.starts_with(fixture))
+            .unwrap();
+        for line in lines {
+            assert!(section.contains(&format!(" --> {}:{}:", fixture, line)));
+        }
+    }
+
+    #[test]
+    fn test_syntax_recovery_does_not_cascade() {
+        let _ = crate::logging::init_global_logging();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("unterminated.esp");
+        std::fs::write(
+            &path,
+            "DEF
+    OBJECT passwd
+        path `/etc/passwd`
+    OBJECT_END
+    CRI AND
+        CTN file_metadata
+            TEST all all
+            STATE local
+                owner string = `root`
+        CTN_END
+        CTN_END
+        CTN file_metadata
+            TEST all all
+            OBJECT_REF
+        CTN_END
+    CRI_END
+",
+        )
+        .unwrap();
+
+        let errors = match process_file(path.to_str().unwrap()) {
+            Err(PipelineError::SyntaxAnalysis(errors)) => errors,
+            other => panic!("expected syntax errors, got {:?}", other.map(|_| ())),
+        };
+        let messages: Vec<String> = errors.iter().map(|error| error.to_string()).collect();
+        assert_eq!(messages.len(), 4, "{:#?}", messages);
+        assert!(messages[0].contains("Expected STATE_END, found 'CTN_END'"));
+        assert!(messages[1].contains("Expected CRI or CTN block"));
+        assert!(messages[2].contains("Expected identifier, found 'CTN_END'"));
+        assert!(messages[3].contains("Expected DEF_END, reached end of input"));
+    }
+
+    #[test]
+    fn test_diagnostics_point_at_the_offending_source() {
+        let _ = crate::logging::init_global_logging();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("columns.esp");
+        std::fs::write(
+            &path,
+            "DEF\n\tOBJECT passwd\n\t\tpath `/etc/é测`\n\tOBJECT_END\n\tCRI AND\n\t\tCTN file_metadata\n\t\t\tTEST all all\n\t\t\tOBJECT_REF missing\n\t\tCTN_END\n\tCRI_END\nDEF_END\n",
+        )
+        .unwrap();
+        assert!(process_file(path.to_str().unwrap()).is_err());
+
+        // The reference itself, not the token after it; tabs are one column
+        let diagnostics = crate::logging::get_file_diagnostics(&path);
+        let undefined = diagnostics
+            .iter()
+            .find(|diagnostic| diagnostic.code == "E110")
+            .expect("undefined reference");
+        let span = undefined.span.expect("span");
+        assert_eq!((span.start().line, span.start().column), (8, 4));
+        assert_eq!((span.end().line, span.end().column), (8, 22));
+
+        let collector = crate::logging::try_get_global_error_collector().unwrap();
+        let summary = crate::logging::collector::format_cargo_style_errors_with(
+            collector,
+            crate::terminal::Palette::new(false),
+        );
+        let file = path.display().to_string();
+        let section = summary
+            .split("Checking ")
+            .find(|section| section.starts_with(&file))
+            .unwrap();
+        assert!(section.contains(&format!(
+            " --> {}:8:4\n  |\n8 |             OBJECT_REF missing\n  |             {}\n",
+            file,
+            "^".repeat(18)
+        )));
+    }
+
+    #[test]
+    fn test_operation_aliases_compile_to_canonical_operations() {
+        use crate::grammar::ast::nodes::{LogicalOp, Operation};
+
+        let _ = crate::logging::init_global_logging();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("aliases.esp");
+        std::fs::write(
+            &path,
+            "META
+    version `1.0.0`
+    esp_version `1.0`
+    author `test`
+    date `2026-01-01`
+    severity `high`
+    platform `linux`
+    description `Operation aliases`
+    control_framework `TEST`
+    control `ALIAS-1`
+    esp_scan_id `aliases`
+    criticality `high`
+    tags `test`
+META_END
+
+DEF
+    OBJECT target
+        path `/etc/hosts`
+    OBJECT_END
+
+    STATE aliased
+        owner string EQUALS `root`
+        group string == `root`
+        permissions string <> `0777`
+        content string Starts_With `#`
+    STATE_END
+
+    CRI and
+        CTN file_metadata
+            TEST ALL all
+            STATE_REF aliased
+            OBJECT_REF target
+        CTN_END
+    CRI_END
+DEF_END
+",
+        )
+        .unwrap();
+
+        let result = process_file(path.to_str().unwrap()).unwrap();
+        let definition = &result.ast.definition;
+        let operations: Vec<Operation> = definition.states[0]
+            .fields
+            .iter()
+            .map(|f| f.operation)
+            .collect();
+        assert_eq!(
+            operations,
+            vec![
+                Operation::Equals,
+                Operation::Equals,
+                Operation::NotEqual,
+                Operation::StartsWith
+            ]
+        );
+        assert_eq!(definition.criteria[0].logical_op, LogicalOp::And);
+    }
+
+    #[test]
+    fn test_ctn_controls_and_severity() {
+        use crate::grammar::ast::nodes::CriteriaContent;
+
+        let _ = crate::logging::init_global_logging();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("controls.esp");
+        std::fs::write(
+            &path,
+            "META
+    version `1.0.0`
+    esp_version `1.0`
+    author `test`
+    date `2026-01-01`
+    severity `high`
+    platform `linux`
+    description `Control references`
+    control_framework `CIS`
+    control `5.2.10`
+    esp_scan_id `controls`
+    criticality `high`
+    tags `test`
+META_END
+
+DEF
+    OBJECT sshd_config
+        path `/etc/ssh/sshd_config`
+    OBJECT_END
+
+    STATE present
+        exists boolean = true
+    STATE_END
+
+    CRI AND
+        CTN file_metadata
+            TEST all all
+            STATE_REF present
+            OBJECT_REF sshd_config
+            CONTROL CIS `5.2.10` `https://example.test/cis/5.2.10`
+            CONTROL STIG `RHEL-09-255045`
+            SEVERITY medium
+        CTN_END
+    CRI_END
+DEF_END
+",
+        )
+        .unwrap();
+
+        let result = process_file(path.to_str().unwrap()).unwrap();
+        let CriteriaContent::Criterion(ctn) = &result.ast.definition.criteria[0].content[0] else {
+            panic!("expected a CTN");
+        };
+        assert_eq!(ctn.controls.len(), 2);
+        assert_eq!(ctn.controls[0].framework, "CIS");
+        assert_eq!(
+            ctn.controls[0].url.as_deref(),
+            Some("https://example.test/cis/5.2.10")
+        );
+        assert_eq!(ctn.controls[1].control_id, "RHEL-09-255045");
+        assert!(ctn.controls[1].url.is_none());
+        assert_eq!(ctn.severity.as_deref(), Some("medium"));
+
+        let source = std::fs::read_to_string(&path).unwrap();
+        std::fs::write(&path, source.replace("SEVERITY medium", "SEVERITY urgent")).unwrap();
+        assert!(process_file(path.to_str().unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_criteria_root_directive() {
+        use crate::grammar::ast::nodes::LogicalOp;
+
+        let _ = crate::logging::init_global_logging();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("root.esp");
+        let source = "META
+    version `1.0.0`
+    esp_version `1.0`
+    author `test`
+    date `2026-01-01`
+    severity `high`
+    platform `linux`
+    description `Criteria root`
+    control_framework `CIS`
+    control `1.1`
+    esp_scan_id `criteria_root`
+    criticality `high`
+    tags `test`
+META_END
+
+DEF
+    OBJECT shadow
+        path `/etc/shadow`
+    OBJECT_END
+
+    STATE present
+        exists boolean = true
+    STATE_END
+
+    ROOT
+    CRI AND
+        CTN file_metadata
+            TEST all all
+            STATE_REF present
+            OBJECT_REF shadow
+        CTN_END
+    CRI_END
+    CRI AND
+        CTN file_metadata
+            TEST all all
+            STATE_REF present
+            OBJECT_REF shadow
+        CTN_END
+    CRI_END
+DEF_END
+";
+        let compile = |root: &str| {
+            std::fs::write(&path, source.replace("ROOT", root)).unwrap();
+            process_file(path.to_str().unwrap())
+        };
+
+        let root = compile("CRI_ROOT OR true")
+            .unwrap()
+            .ast
+            .definition
+            .criteria_root;
+        let root = root.expect("CRI_ROOT should be kept");
+        assert_eq!(root.logical_op, LogicalOp::Or);
+        assert!(root.negate);
+
+        let root = compile("CRI_ROOT OR").unwrap().ast.definition.criteria_root;
+        assert!(!root.unwrap().negate);
+        assert!(compile("").unwrap().ast.definition.criteria_root.is_none());
+        assert!(compile("CRI_ROOT OR\n    CRI_ROOT AND").is_err());
+
+        std::fs::write(
+            &path,
+            source
+                .replace("ROOT", "")
+                .replace("DEF_END", "    CRI_ROOT OR\nDEF_END"),
+        )
+        .unwrap();
+        assert!(process_file(path.to_str().unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_version_operations_compile() {
+        use crate::grammar::ast::nodes::Operation;
+
+        let _ = crate::logging::init_global_logging();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("versions.esp");
+        std::fs::write(
+            &path,
+            "META
+    version `1.0.0`
+    esp_version `1.0`
+    author `test`
+    date `2026-01-01`
+    severity `high`
+    platform `linux`
+    description `Version operations`
+    control_framework `TEST`
+    control `VERSION-1`
+    esp_scan_id `versions`
+    criticality `high`
+    tags `test`
+META_END
+
+DEF
+    OBJECT openssl
+        package_name `openssl`
+    OBJECT_END
+
+    STATE patched
+        version string version_greater_or_equal `1:3.0.7-27.el9`
+        version evr_string version_less_than `3.2~rc1`
+        version version version_not_equal `3.0.1`
+    STATE_END
+
+    CRI AND
+        CTN rpm_package
+            TEST all all
+            STATE_REF patched
+            OBJECT_REF openssl
+        CTN_END
+    CRI_END
+DEF_END
+",
+        )
+        .unwrap();
+
+        let result = process_file(path.to_str().unwrap()).unwrap();
+        let operations: Vec<Operation> = result.ast.definition.states[0]
+            .fields
+            .iter()
+            .map(|f| f.operation)
+            .collect();
+        assert_eq!(
+            operations,
+            vec![
+                Operation::VersionGreaterOrEqual,
+                Operation::VersionLessThan,
+                Operation::VersionNotEqual
+            ]
+        );
+
+        let source = std::fs::read_to_string(&path).unwrap();
+        std::fs::write(
+            &path,
+            source.replace(
+                "version version version_not_equal `3.0.1`",
+                "size int version_equals 3",
+            ),
+        )
+        .unwrap();
+        let result = process_file(path.to_str().unwrap()).unwrap();
+        assert!(!result.semantic_analysis_result.is_successful);
+        assert_eq!(result.semantic_analysis_result.errors.len(), 1);
+    }
+
+    #[test]
+    fn test_glob_and_ignore_case_operations_compile() {
+        use crate::grammar::ast::nodes::Operation;
+
+        let _ = crate::logging::init_global_logging();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("globs.esp");
+        std::fs::write(
+            &path,
+            "META
+    version `1.0.0`
+    esp_version `1.0`
+    author `test`
+    date `2026-01-01`
+    severity `high`
+    platform `linux`
+    description `Glob operations`
+    control_framework `TEST`
+    control `GLOB-1`
+    esp_scan_id `globs`
+    criticality `high`
+    tags `test`
+META_END
+
+DEF
+    OBJECT sshd
+        path `/etc/ssh/sshd_config`
+    OBJECT_END
+
+    STATE hardened
+        ciphers string not_glob_match `*cbc*`
+        loglevel string glob_match `[Vv]erbose`
+        permitrootlogin string equals_ignore_case `NO`
+    STATE_END
+
+    CRI AND
+        CTN sshd_config
+            TEST all all
+            STATE_REF hardened
+            OBJECT_REF sshd
+        CTN_END
+    CRI_END
+DEF_END
+",
+        )
+        .unwrap();
+
+        let result = process_file(path.to_str().unwrap()).unwrap();
+        assert!(result.semantic_analysis_result.is_successful);
+        let operations: Vec<Operation> = result.ast.definition.states[0]
+            .fields
+            .iter()
+            .map(|f| f.operation)
+            .collect();
+        assert_eq!(
+            operations,
+            vec![
+                Operation::NotGlobMatch,
+                Operation::GlobMatch,
+                Operation::CaseInsensitiveEquals
+            ]
+        );
+
+        // Globs only apply to strings, and a class must be closed
+        let source = std::fs::read_to_string(&path).unwrap();
+        for (from, to) in [
+            (
+                "loglevel string glob_match `[Vv]erbose`",
+                "maxauthtries int glob_match `4`",
+            ),
+            (
+                "loglevel string glob_match `[Vv]erbose`",
+                "loglevel string glob_match `[Vv`",
+            ),
+        ] {
+            std::fs::write(&path, source.replace(from, to)).unwrap();
+            let result = process_file(path.to_str().unwrap()).unwrap();
+            assert!(!result.semantic_analysis_result.is_successful, "{}", to);
+            assert_eq!(result.semantic_analysis_result.errors.len(), 1, "{}", to);
+        }
+    }
+
+    #[test]
+    fn test_octal_values_are_read_in_base_8() {
+        use crate::grammar::ast::nodes::{Operation, Value};
+
+        let _ = crate::logging::init_global_logging();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("modes.esp");
+        std::fs::write(
+            &path,
+            "META
+    version `1.0.0`
+    esp_version `1.0`
+    author `test`
+    date `2026-01-01`
+    severity `high`
+    platform `linux`
+    description `Permission masks`
+    control_framework `TEST`
+    control `FILE-1`
+    esp_scan_id `modes`
+    criticality `high`
+    tags `test`
+META_END
+
+DEF
+    OBJECT shadow
+        path `/etc/shadow`
+    OBJECT_END
+
+    STATE restricted
+        permissions octal permissions_at_most 0640
+        permissions octal not_equals_any [4000, 2000]
+        size int = 0640
+    STATE_END
+
+    CRI AND
+        CTN file_metadata
+            TEST all all
+            STATE_REF restricted
+            OBJECT_REF shadow
+        CTN_END
+    CRI_END
+DEF_END
+",
+        )
+        .unwrap();
+
+        let result = process_file(path.to_str().unwrap()).unwrap();
+        assert!(result.semantic_analysis_result.is_successful);
+        let fields = &result.ast.definition.states[0].fields;
+        assert_eq!(fields[0].operation, Operation::PermissionsAtMost);
+        assert_eq!(fields[0].value, Value::Integer(0o640));
+        assert_eq!(
+            fields[1].value,
+            Value::List(vec![Value::Integer(0o4000), Value::Integer(0o2000)])
+        );
+        // Only octal fields change how the digits are read
+        assert_eq!(fields[2].value, Value::Integer(640));
+
+        let source = std::fs::read_to_string(&path).unwrap();
+        std::fs::write(
+            &path,
+            source.replace("permissions_at_most 0640", "permissions_at_most 0649"),
+        )
+        .unwrap();
+        assert!(process_file(path.to_str().unwrap()).is_err());
+
+        std::fs::write(
+            &path,
+            source.replace("permissions_at_most 0640", "permissions_at_most 17777"),
+        )
+        .unwrap();
+        let result = process_file(path.to_str().unwrap()).unwrap();
+        assert!(!result.semantic_analysis_result.is_successful);
+    }
+
+    #[test]
+    fn test_datetime_literals_are_checked_at_compile_time() {
+        use crate::grammar::ast::nodes::{DataType, Value};
+
+        let _ = crate::logging::init_global_logging();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("stale.esp");
+        let source = "META
+    version `1.0.0`
+    esp_version `1.0`
+    author `test`
+    date `2026-01-01`
+    severity `high`
+    platform `linux`
+    description `Stale files`
+    control_framework `TEST`
+    control `FILE-2`
+    esp_scan_id `stale`
+    criticality `high`
+    tags `test`
+META_END
+
+DEF
+    OBJECT passwd
+        path `/etc/passwd`
+    OBJECT_END
+
+    STATE recently_changed
+        modified datetime > `now-30d`
+        modified datetime < `2030-01-01T00:00:00Z`
+        modified datetime != 0
+    STATE_END
+
+    CRI AND
+        CTN file_metadata
+            TEST all all
+            STATE_REF recently_changed
+            OBJECT_REF passwd
+        CTN_END
+    CRI_END
+DEF_END
+";
+        std::fs::write(&path, source).unwrap();
+
+        let result = process_file(path.to_str().unwrap()).unwrap();
+        assert!(result.semantic_analysis_result.is_successful);
+        let fields = &result.ast.definition.states[0].fields;
+        assert_eq!(fields[0].data_type, DataType::DateTime);
+        assert_eq!(fields[0].value, Value::String("now-30d".to_string()));
+
+        for malformed in ["`now-30y`", "`2030-01-01`", "true"] {
+            std::fs::write(&path, source.replace("`now-30d`", malformed)).unwrap();
+            let result = process_file(path.to_str().unwrap()).unwrap();
+            assert!(
+                !result.semantic_analysis_result.is_successful,
+                "{}",
+                malformed
+            );
+        }
+    }
+
+    #[test]
+    fn test_counted_entity_checks_parse() {
+        use crate::grammar::ast::nodes::{CriteriaContent, EntityCheck};
+
+        let _ = crate::logging::init_global_logging();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("logs.esp");
+        let source = "META
+    version `1.0.0`
+    esp_version `1.0`
+    author `test`
+    date `2026-01-01`
+    severity `high`
+    platform `linux`
+    description `Log ownership`
+    control_framework `TEST`
+    control `FILE-3`
+    esp_scan_id `logs`
+    criticality `high`
+    tags `test`
+META_END
+
+DEF
+    OBJECT logs
+        path `/var/log/messages`
+    OBJECT_END
+
+    STATE root_owned
+        file_owner string = `0` at_least(2)
+        at_least string = `0`
+        file_group string = `0` at_least_percent(90)
+    STATE_END
+
+    CRI AND
+        CTN file_metadata
+            TEST all all at_least_percent(75)
+            STATE_REF root_owned
+            OBJECT_REF logs
+        CTN_END
+    CRI_END
+DEF_END
+";
+        std::fs::write(&path, source).unwrap();
+
+        let result = process_file(path.to_str().unwrap()).unwrap();
+        let fields = &result.ast.definition.states[0].fields;
+        assert_eq!(fields[0].entity_check, Some(EntityCheck::AtLeastN(2)));
+        // A field named `at_least` is not mistaken for a counted check
+        assert_eq!(fields[1].name, "at_least");
+        assert_eq!(fields[1].entity_check, None);
+        assert_eq!(
+            fields[2].entity_check,
+            Some(EntityCheck::AtLeastPercent(90))
+        );
+        let CriteriaContent::Criterion(ctn) = &result.ast.definition.criteria[0].content[0] else {
+            panic!("expected a CTN");
+        };
+        assert_eq!(ctn.test.entity_check, Some(EntityCheck::AtLeastPercent(75)));
+
+        for rejected in ["at_least(0)", "at_least_percent(101)", "at_least(2"] {
+            std::fs::write(&path, source.replace("at_least(2)", rejected)).unwrap();
+            assert!(
+                process_file(path.to_str().unwrap()).is_err(),
+                "{}",
+                rejected
+            );
+        }
+    }
+
+    #[test]
+    fn test_concat_and_replace_parameters_are_checked() {
+        use crate::grammar::ast::nodes::RuntimeOperationType;
+
+        let _ = crate::logging::init_global_logging();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ciphers.esp");
+        let source = "META
+    version `1.0.0`
+    esp_version `1.0`
+    author `test`
+    date `2026-01-01`
+    severity `high`
+    platform `linux`
+    description `Cipher list`
+    control_framework `TEST`
+    control `SSH-1`
+    esp_scan_id `ciphers`
+    criticality `high`
+    tags `test`
+META_END
+
+DEF
+    VAR raw_ciphers string `aes256-gcm,chacha20`
+
+    RUN cipher_list REPLACE
+        VAR raw_ciphers
+        pattern `,\\s*`
+        literal ` `
+    RUN_END
+
+    RUN config_path CONCAT
+        literal `/etc/ssh/`
+        literal `sshd_config`
+    RUN_END
+
+    OBJECT sshd
+        path VAR config_path
+    OBJECT_END
+
+    STATE strong_ciphers
+        ciphers string = VAR cipher_list
+    STATE_END
+
+    CRI AND
+        CTN sshd_config
+            TEST all all
+            STATE_REF strong_ciphers
+            OBJECT_REF sshd
+        CTN_END
+    CRI_END
+DEF_END
+";
+        std::fs::write(&path, source).unwrap();
+
+        let result = process_file(path.to_str().unwrap()).unwrap();
+        assert!(result.semantic_analysis_result.is_successful);
+        let operations = &result.ast.definition.runtime_operations;
+        assert_eq!(operations[0].operation_type, RuntimeOperationType::Replace);
+        assert_eq!(operations[1].operation_type, RuntimeOperationType::Concat);
+
+        let single_part_concat = source.replace("        literal `sshd_config`\n", "");
+        let pattern_first_replace = source.replace(
+            "        VAR raw_ciphers\n        pattern `,\\s*`",
+            "        pattern `,\\s*`\n        VAR raw_ciphers",
+        );
+        let concat_of_pattern = source.replace("literal `sshd_config`", "pattern `sshd`");
+        for rejected in [single_part_concat, pattern_first_replace, concat_of_pattern] {
+            assert_ne!(rejected, source);
+            std::fs::write(&path, &rejected).unwrap();
+            let result = process_file(path.to_str().unwrap()).unwrap();
+            assert!(
+                !result.semantic_analysis_result.is_successful,
+                "{}",
+                rejected
+            );
+        }
+    }
+
+    #[test]
+    fn test_count_and_length_parameters_are_checked() {
+        use crate::grammar::ast::nodes::RuntimeOperationType;
+
+        let _ = crate::logging::init_global_logging();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("accounts.esp");
+        let source = "META
+    version `1.0.0`
+    esp_version `1.0`
+    author `test`
+    date `2026-01-01`
+    severity `high`
+    platform `linux`
+    description `Privileged accounts`
+    control_framework `TEST`
+    control `ACCT-1`
+    esp_scan_id `accounts`
+    criticality `high`
+    tags `test`
+META_END
+
+DEF
+    VAR banner string `Authorized use only`
+
+    OBJECT passwd
+        path `/etc/passwd`
+    OBJECT_END
+
+    RUN uid_zero_count COUNT
+        OBJ passwd uid_zero_accounts
+    RUN_END
+
+    RUN banner_length LENGTH
+        VAR banner
+    RUN_END
+
+    STATE single_root
+        accounts int = VAR uid_zero_count
+        banner_size int > VAR banner_length
+    STATE_END
+
+    CRI AND
+        CTN file_content
+            TEST all all
+            STATE_REF single_root
+            OBJECT_REF passwd
+        CTN_END
+    CRI_END
+DEF_END
+";
+        std::fs::write(&path, source).unwrap();
+
+        let result = process_file(path.to_str().unwrap()).unwrap();
+        assert!(result.semantic_analysis_result.is_successful);
+        let operations = &result.ast.definition.runtime_operations;
+        assert_eq!(operations[0].operation_type, RuntimeOperationType::Count);
+        assert_eq!(operations[1].operation_type, RuntimeOperationType::Length);
+
+        let count_of_literal = source.replace("OBJ passwd uid_zero_accounts", "literal `root`");
+        let length_of_int = source.replace("VAR banner\n", "literal 42\n");
+        let length_of_two = source.replace("VAR banner\n", "VAR banner\n        VAR banner\n");
+        for rejected in [count_of_literal, length_of_int, length_of_two] {
+            assert_ne!(rejected, source);
+            std::fs::write(&path, &rejected).unwrap();
+            let result = process_file(path.to_str().unwrap()).unwrap();
+            assert!(
+                !result.semantic_analysis_result.is_successful,
+                "{}",
+                rejected
+            );
+        }
+    }
+
+    #[test]
+    fn test_string_transforms_chain_and_cycles_are_rejected() {
+        use crate::grammar::ast::nodes::RuntimeOperationType;
+
+        let _ = crate::logging::init_global_logging();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("hostname.esp");
+        let source = "META
+    version `1.0.0`
+    esp_version `1.0`
+    author `test`
+    date `2026-01-01`
+    severity `medium`
+    platform `linux`
+    description `Hostname case`
+    control_framework `TEST`
+    control `HOST-1`
+    esp_scan_id `hostname`
+    criticality `medium`
+    tags `test`
+META_END
+
+DEF
+    VAR raw_hostname string `  Build-Unit.EXAMPLE  `
+
+    RUN hostname LOWERCASE
+        VAR trimmed_hostname
+    RUN_END
+
+    RUN trimmed_hostname TRIM
+        VAR raw_hostname
+    RUN_END
+
+    RUN banner UPPERCASE
+        literal `authorized use only`
+    RUN_END
+
+    OBJECT hostname_file
+        path `/etc/hostname`
+    OBJECT_END
+
+    STATE expected_hostname
+        content string = VAR hostname
+        banner string = VAR banner
+    STATE_END
+
+    CRI AND
+        CTN file_content
+            TEST all all
+            STATE_REF expected_hostname
+            OBJECT_REF hostname_file
+        CTN_END
+    CRI_END
+DEF_END
+";
+        std::fs::write(&path, source).unwrap();
+
+        let result = process_file(path.to_str().unwrap()).unwrap();
+        assert!(result.semantic_analysis_result.is_successful);
+        let operations: Vec<_> = result
+            .ast
+            .definition
+            .runtime_operations
+            .iter()
+            .map(|op| op.operation_type)
+            .collect();
+        assert_eq!(
+            operations,
+            [
+                RuntimeOperationType::Lowercase,
+                RuntimeOperationType::Trim,
+                RuntimeOperationType::Uppercase
+            ]
+        );
+
+        let cycle = source.replace("        VAR raw_hostname\n", "        VAR hostname\n");
+        let uppercase_of_int = source.replace("literal `authorized use only`", "literal 42");
+        for rejected in [cycle, uppercase_of_int] {
+            assert_ne!(rejected, source);
+            std::fs::write(&path, &rejected).unwrap();
+            let result = process_file(path.to_str().unwrap()).unwrap();
+            assert!(
+                !result.semantic_analysis_result.is_successful,
+                "{}",
+                rejected
+            );
+        }
+    }
+
+    #[test]
+    fn test_min_max_abs_and_modulo_parameters_parse() {
+        use crate::grammar::ast::nodes::{ArithmeticOperator, RunParameter, Value};
+
+        let _ = crate::logging::init_global_logging();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ports.esp");
+        let source = "META
+    version `1.0.0`
+    esp_version `1.0`
+    author `test`
+    date `2026-01-01`
+    severity `medium`
+    platform `linux`
+    description `Listener ports`
+    control_framework `TEST`
+    control `NET-1`
+    esp_scan_id `ports`
+    criticality `medium`
+    tags `test`
+META_END
+
+DEF
+    VAR port int 8443
+
+    RUN port_parity ARITHMETIC
+        VAR port
+        % 2
+    RUN_END
+
+    RUN clamped_offset ARITHMETIC
+        literal -70000
+        abs
+        min 65535
+        max 1
+    RUN_END
+
+    OBJECT sshd
+        path `/etc/ssh/sshd_config`
+    OBJECT_END
+
+    STATE even_port
+        parity int = VAR port_parity
+        offset int <= VAR clamped_offset
+    STATE_END
+
+    CRI AND
+        CTN file_content
+            TEST all all
+            STATE_REF even_port
+            OBJECT_REF sshd
+        CTN_END
+    CRI_END
+DEF_END
+";
+        std::fs::write(&path, source).unwrap();
+
+        let result = process_file(path.to_str().unwrap()).unwrap();
+        assert!(result.semantic_analysis_result.is_successful);
+        let clamp = &result.ast.definition.runtime_operations[1].parameters;
+        assert_eq!(clamp[0], RunParameter::Literal(Value::Integer(-70000)));
+        assert_eq!(clamp[1], RunParameter::absolute_value());
+        assert_eq!(
+            clamp[2],
+            RunParameter::arithmetic_op(ArithmeticOperator::Min, Value::Integer(65535))
+        );
+
+        // `abs` has no operand, `min` needs one
+        for malformed in [
+            source.replace("        abs\n", "        abs 3\n"),
+            source.replace("        min 65535\n", "        min\n"),
+        ] {
+            assert_ne!(malformed, source);
+            std::fs::write(&path, &malformed).unwrap();
+            assert!(
+                process_file(path.to_str().unwrap()).is_err(),
+                "{}",
+                malformed
+            );
+        }
+    }
+
+    #[test]
+    fn test_list_variables_warn_where_a_single_value_is_required() {
+        use crate::grammar::ast::nodes::Value;
+
+        let _ = crate::logging::init_global_logging();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("dns.esp");
+        let source = "META
+    version `1.0.0`
+    esp_version `1.0`
+    author `test`
+    date `2026-01-01`
+    severity `medium`
+    platform `linux`
+    description `DNS servers`
+    control_framework `TEST`
+    control `DNS-1`
+    esp_scan_id `dns`
+    criticality `medium`
+    tags `test`
+META_END
+
+DEF
+    VAR approved_servers string [`10.0.0.53`, `10.0.1.53`]
+    VAR server_csv string `10.0.0.53,10.0.1.53`
+
+    RUN server_count COUNT
+        VAR configured_servers
+    RUN_END
+
+    RUN configured_servers SPLIT
+        VAR server_csv
+        delimiter `,`
+    RUN_END
+
+    OBJECT resolv
+        path `/etc/resolv.conf`
+    OBJECT_END
+
+    STATE approved
+        content string = VAR approved_servers
+        content string != VAR configured_servers
+    STATE_END
+
+    CRI AND
+        CTN file_content
+            TEST all all
+            STATE_REF approved
+            OBJECT_REF resolv
+        CTN_END
+    CRI_END
+DEF_END
+";
+        std::fs::write(&path, source).unwrap();
+
+        // Lists in state fields and as COUNT input need no warning
+        let result = process_file(path.to_str().unwrap()).unwrap();
+        assert!(result.semantic_analysis_result.is_successful);
+        assert!(result.semantic_analysis_result.warnings.is_empty());
+        assert!(matches!(
+            result.ast.definition.variables[0].initial_value,
+            Some(Value::List(ref items)) if items.len() == 2
+        ));
+
+        let misused = source
+            .replace(
+                "server_count COUNT\n",
+                "server_banner CONCAT\n        literal `dns: `\n",
+            )
+            .replace("path `/etc/resolv.conf`", "path VAR approved_servers");
+        std::fs::write(&path, &misused).unwrap();
+        let result = process_file(path.to_str().unwrap()).unwrap();
+        assert!(result.semantic_analysis_result.is_successful);
+        let warnings: Vec<&str> = result
+            .semantic_analysis_result
+            .warnings
+            .iter()
+            .map(|warning| warning.message.as_str())
+            .collect();
+        assert_eq!(
+            warnings,
+            [
+                "RUN server_banner CONCAT: VAR configured_servers holds a list, but CONCAT takes a single value",
+                "OBJECT resolv field 'path': VAR approved_servers holds a list, but object fields take a single value",
+            ]
+        );
+    }
 }
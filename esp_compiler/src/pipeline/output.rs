@@ -1,4 +1,5 @@
 use crate::grammar::EspFile;
+use crate::logging::Diagnostic;
 use crate::symbols::SymbolDiscoveryResult;
 use serde::{Deserialize, Serialize};
 
@@ -6,10 +7,22 @@ use serde::{Deserialize, Serialize};
 pub struct PipelineOutput {
     pub ast_tree: EspFile,
     pub symbols: SymbolDiscoveryResult,
+    /// Warnings raised while compiling, each with the span it points at
+    #[serde(default)]
+    pub diagnostics: Vec<Diagnostic>,
 }
 
 impl PipelineOutput {
     pub fn new(ast_tree: EspFile, symbols: SymbolDiscoveryResult) -> Self {
-        Self { ast_tree, symbols }
+        Self {
+            ast_tree,
+            symbols,
+            diagnostics: Vec::new(),
+        }
+    }
+
+    pub fn with_diagnostics(mut self, diagnostics: Vec<Diagnostic>) -> Self {
+        self.diagnostics = diagnostics;
+        self
     }
 }
@@ -0,0 +1,64 @@
+//! Failure report for a policy that did not compile
+//!
+//! Shared by every binary that compiles policies so a broken file reads the
+//! same whether it was checked with `esp_compiler` or scanned with `scanner`.
+
+use super::PipelineError;
+use crate::logging;
+use crate::terminal::Palette;
+
+/// The stderr block for a failed compile: the error, then the stage that
+/// failed with its diagnostic code
+pub fn format_failure(error: &PipelineError, palette: Palette) -> String {
+    let mut out = format!("\n{}: {}\n", palette.status("FAILED"), error);
+    match error.stage() {
+        Some(stage) => out.push_str(&format!(
+            "{} stage failed [{}]:\n  {}\n",
+            stage,
+            error.error_code(),
+            error.detail()
+        )),
+        None => out.push_str(&format!(
+            "Pipeline error [{}]: {}\n",
+            error.error_code(),
+            error.detail()
+        )),
+    }
+    out
+}
+
+/// Print the failure block to stderr and the cargo-style summary of the
+/// diagnostics collected while compiling to stdout
+pub fn report_failure(error: &PipelineError) {
+    eprint!("{}", format_failure(error, Palette::current()));
+    logging::print_cargo_style_summary();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_failure_names_stage_and_code() {
+        let error = crate::pipeline::process_file("does/not/exist.esp").unwrap_err();
+        let report = format_failure(&error, Palette::new(false));
+
+        assert!(report.starts_with("\nFAILED: File processing failed: "));
+        assert!(
+            report.contains("File processing stage failed [E005]:\n  "),
+            "{}",
+            report
+        );
+        assert_eq!(error.summary(), "File processing error");
+    }
+
+    #[test]
+    fn test_pipeline_error_has_no_stage() {
+        let error = PipelineError::pipeline_error("test");
+        assert_eq!(error.summary(), "Pipeline error");
+        assert_eq!(
+            format_failure(&error, Palette::new(false)),
+            "\nFAILED: Pipeline error: test\nPipeline error [ERR001]: test\n"
+        );
+    }
+}
@@ -11,6 +11,9 @@ use std::time::Duration;
 pub struct PipelineResult {
     pub ast: EspFile,
     pub file_metadata: crate::file_processor::FileMetadata,
+    /// Files merged in through IMPORT, directly or not; the result depends
+    /// on their contents as much as on the policy file's
+    pub imported_files: Vec<crate::file_processor::FileMetadata>,
     pub lexical_metrics: LexicalMetrics,
     pub symbol_discovery_result: SymbolDiscoveryResult,
     pub reference_validation_result: ReferenceValidationResult,
@@ -24,6 +27,7 @@ impl PipelineResult {
     pub fn new(
         ast: EspFile,
         file_metadata: crate::file_processor::FileMetadata,
+        imported_files: Vec<crate::file_processor::FileMetadata>,
         lexical_metrics: LexicalMetrics,
         symbol_discovery_result: SymbolDiscoveryResult,
         reference_validation_result: ReferenceValidationResult,
@@ -35,6 +39,7 @@ impl PipelineResult {
         Self {
             ast,
             file_metadata,
+            imported_files,
             lexical_metrics,
             symbol_discovery_result,
             reference_validation_result,
@@ -3,7 +3,7 @@
 //! Integrated with global logging system and compile-time security limits.
 
 use crate::logging::codes;
-use crate::utils::Span;
+use crate::tokens::Span;
 use thiserror::Error;
 
 /// Result type for Reference Validation operations
@@ -452,7 +452,7 @@ pub fn create_security_violation_error(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::utils::{Position, Span};
+    use crate::tokens::{Position, Span};
 
     #[test]
     fn test_undefined_reference_error() {
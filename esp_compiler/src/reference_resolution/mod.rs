@@ -404,7 +404,7 @@ pub fn init_reference_validation() -> ValidationResult<()> {
 mod tests {
     use super::*;
     use crate::symbols::table::{GlobalSymbolTable, RelationshipType, SymbolRelationship};
-    use crate::utils::{Position, Span};
+    use crate::tokens::{Position, Span};
 
     fn create_test_symbol_result() -> SymbolDiscoveryResult {
         let mut symbol_result = SymbolDiscoveryResult::new();
@@ -3,7 +3,7 @@
 //! Simplified cycle detection that works with existing validation results and enforces security boundaries.
 
 use super::types::{SemanticError, SemanticInput};
-use crate::{config::constants::compile_time::semantic::*, log_debug, log_info, utils::Span};
+use crate::{config::constants::compile_time::semantic::*, log_debug, log_info, tokens::Span};
 
 /// Analyze dependency cycles from validation results with security limits
 pub fn analyze_dependency_cycles(
@@ -90,6 +90,8 @@ mod tests {
                 objects: Vec::new(),
                 set_operations: Vec::new(),
                 criteria: Vec::new(),
+                criteria_root: None,
+                imports: Vec::new(),
                 span: Some(Span::dummy()),
             },
             span: Some(Span::dummy()),
@@ -115,6 +117,8 @@ mod tests {
                 objects: Vec::new(),
                 set_operations: Vec::new(),
                 criteria: Vec::new(),
+                criteria_root: None,
+                imports: Vec::new(),
                 span: Some(Span::dummy()),
             },
             span: Some(Span::dummy()),
@@ -0,0 +1,169 @@
+//! List-Valued Variable Tracking
+//!
+//! Works out which variables hold lists (value-list initial values, copies of
+//! list variables, and the results of SPLIT, UNIQUE and MERGE) and warns where
+//! one is used in a place that needs a single value. State fields are not
+//! flagged: a list there is compared item by item under the field's entity check.
+
+use super::types::{SemanticInput, SemanticWarning};
+use crate::{
+    grammar::ast::nodes::{
+        CriteriaContent, CriteriaNode, DefinitionNode, ObjectDefinition, ObjectElement,
+        RunParameter, RuntimeOperation, RuntimeOperationType, Value,
+    },
+    log_debug, log_warning,
+};
+use std::collections::HashSet;
+
+/// Warn about list-valued variables used where a single value is required
+pub fn check_list_usage(input: &SemanticInput) -> Vec<SemanticWarning> {
+    let definition = &input.ast.definition;
+    let lists = list_variables(definition);
+
+    log_debug!("List-valued variables identified", "count" => lists.len());
+
+    if lists.is_empty() {
+        return Vec::new();
+    }
+
+    let mut warnings = Vec::new();
+    for operation in &definition.runtime_operations {
+        check_runtime_operation(operation, &lists, &mut warnings);
+    }
+    for object in &definition.objects {
+        check_object(object, &lists, &mut warnings);
+    }
+    for criteria in &definition.criteria {
+        check_criteria_objects(criteria, &lists, &mut warnings);
+    }
+
+    for warning in &warnings {
+        log_warning!(&warning.message,
+            "line" => warning.span.map_or(0, |span| span.start().line));
+    }
+
+    warnings
+}
+
+/// Names of the variables that hold a list once resolved
+///
+/// Repeats until nothing changes, since a RUN or VAR may copy a list that is
+/// declared further down the file.
+pub fn list_variables(definition: &DefinitionNode) -> HashSet<String> {
+    let mut lists = HashSet::new();
+
+    loop {
+        let before = lists.len();
+
+        for variable in &definition.variables {
+            let is_list = match &variable.initial_value {
+                Some(Value::List(_)) => true,
+                Some(Value::Variable(source)) => lists.contains(source),
+                _ => false,
+            };
+            if is_list {
+                lists.insert(variable.name.clone());
+            }
+        }
+
+        for operation in &definition.runtime_operations {
+            if produces_list(operation.operation_type) {
+                lists.insert(operation.target_variable.clone());
+            }
+        }
+
+        if lists.len() == before {
+            return lists;
+        }
+    }
+}
+
+/// Operations whose result is always a list. EXTRACT yields a list only when
+/// the collected field has several values, which is not known until a scan.
+fn produces_list(operation_type: RuntimeOperationType) -> bool {
+    matches!(
+        operation_type,
+        RuntimeOperationType::Split | RuntimeOperationType::Unique | RuntimeOperationType::Merge
+    )
+}
+
+/// Operations that read each input as a list rather than a single value
+fn accepts_lists(operation_type: RuntimeOperationType) -> bool {
+    matches!(
+        operation_type,
+        RuntimeOperationType::Count
+            | RuntimeOperationType::Unique
+            | RuntimeOperationType::Merge
+            | RuntimeOperationType::Extract
+            | RuntimeOperationType::End
+    )
+}
+
+fn check_runtime_operation(
+    operation: &RuntimeOperation,
+    lists: &HashSet<String>,
+    warnings: &mut Vec<SemanticWarning>,
+) {
+    if accepts_lists(operation.operation_type) {
+        return;
+    }
+
+    for parameter in &operation.parameters {
+        let variable = match parameter {
+            RunParameter::Variable(name) | RunParameter::Literal(Value::Variable(name)) => name,
+            RunParameter::ArithmeticOp(_, Some(Value::Variable(name))) => name,
+            _ => continue,
+        };
+        if lists.contains(variable) {
+            warnings.push(SemanticWarning {
+                message: format!(
+                    "RUN {} {}: VAR {} holds a list, but {} takes a single value",
+                    operation.target_variable,
+                    operation.operation_type.as_str(),
+                    variable,
+                    operation.operation_type.as_str()
+                ),
+                span: operation.span,
+            });
+        }
+    }
+}
+
+fn check_object(
+    object: &ObjectDefinition,
+    lists: &HashSet<String>,
+    warnings: &mut Vec<SemanticWarning>,
+) {
+    for element in &object.elements {
+        if let ObjectElement::Field(field) = element {
+            if let Value::Variable(variable) = &field.value {
+                if lists.contains(variable) {
+                    warnings.push(SemanticWarning {
+                        message: format!(
+                            "OBJECT {} field '{}': VAR {} holds a list, but object fields take a single value",
+                            object.id, field.name, variable
+                        ),
+                        span: field.span.or(object.span),
+                    });
+                }
+            }
+        }
+    }
+}
+
+fn check_criteria_objects(
+    criteria: &CriteriaNode,
+    lists: &HashSet<String>,
+    warnings: &mut Vec<SemanticWarning>,
+) {
+    for content in &criteria.content {
+        match content {
+            CriteriaContent::Criteria(nested) => check_criteria_objects(nested, lists, warnings),
+            CriteriaContent::Criterion(ctn) => {
+                if let Some(object) = &ctn.local_object {
+                    check_object(object, lists, warnings);
+                }
+            }
+        }
+    }
+}
@@ -4,13 +4,14 @@
 //! Focuses on core business logic with SSDF-compliant security limits.
 
 pub mod cycle_analyzer;
+pub mod list_checker;
 pub mod runtime_checker;
 pub mod set_checker;
 pub mod type_checker;
 pub mod types;
 
 // Re-export main types
-pub use types::{SemanticError, SemanticInput, SemanticOutput, SemanticResult};
+pub use types::{SemanticError, SemanticInput, SemanticOutput, SemanticResult, SemanticWarning};
 
 use crate::{
     config::constants::compile_time::semantic::*, grammar::ast::nodes::EspFile, log_debug,
@@ -213,6 +214,10 @@ impl SemanticAnalyzer {
             }
         }
 
+        // Step 5: List-valued variables used where a single value is required
+        log_debug!("Step 5: Checking list-valued variable usage");
+        output.warnings = list_checker::check_list_usage(&input);
+
         // Calculate final results
         let total_errors = output.errors.len();
         let is_successful = total_errors == 0;
@@ -225,8 +230,9 @@ impl SemanticAnalyzer {
             log_success!(codes::success::SEMANTIC_ANALYSIS_COMPLETE,
                 "Semantic analysis completed successfully",
                 "duration_ms" => format!("{:.2}", analysis_duration.as_secs_f64() * 1000.0),
-                "steps_completed" => 4,
-                "total_errors" => total_errors
+                "steps_completed" => 5,
+                "total_errors" => total_errors,
+                "total_warnings" => output.warnings.len()
             );
         } else {
             let completion_message = if error_collection_stopped {
@@ -240,7 +246,7 @@ impl SemanticAnalyzer {
                 "error_limit" => MAX_SEMANTIC_ERRORS,
                 "limit_reached" => error_collection_stopped,
                 "duration_ms" => format!("{:.2}", analysis_duration.as_secs_f64() * 1000.0),
-                "steps_completed" => 4
+                "steps_completed" => 5
             );
         }
 
@@ -325,7 +331,7 @@ pub fn init_semantic_analysis_logging() -> Result<(), String> {
 mod tests {
     use super::*;
     use crate::grammar::ast::nodes::{DefinitionNode, EspFile};
-    use crate::utils::Span;
+    use crate::tokens::Span;
 
     fn create_empty_test_input() -> SemanticInput {
         let ast = EspFile {
@@ -337,6 +343,8 @@ mod tests {
                 objects: Vec::new(),
                 set_operations: Vec::new(),
                 criteria: Vec::new(),
+                criteria_root: None,
+                imports: Vec::new(),
                 span: Some(Span::dummy()),
             },
             span: Some(Span::dummy()),
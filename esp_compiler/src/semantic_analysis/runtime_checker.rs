@@ -9,7 +9,7 @@ use crate::{
     grammar::ast::nodes::{DataType, RunParameter, RuntimeOperation, RuntimeOperationType, Value},
     log_debug, log_error, log_info, log_success,
     logging::codes,
-    utils::Span,
+    tokens::Span,
 };
 use std::collections::HashMap;
 
@@ -137,6 +137,7 @@ impl RuntimeOperationValidator {
         self.validate_operation_constraints(
             operation_type,
             variable_name,
+            &runtime_op.parameters,
             &parameter_analysis,
             span,
         )?;
@@ -211,7 +212,28 @@ impl RuntimeOperationValidator {
             RunParameter::Length(_) => Ok(ParameterInfo {
                 inferred_type: Some(DataType::Int),
             }),
-            RunParameter::ArithmeticOp(_, value) => {
+            RunParameter::ArithmeticOp(operator, value)
+                if operator.takes_operand() != value.is_some() =>
+            {
+                Err(SemanticError::runtime_operation_error(
+                    "arithmetic_parameter",
+                    RuntimeOperationType::Arithmetic,
+                    &format!(
+                        "Arithmetic operator '{}' {} an operand",
+                        operator,
+                        if operator.takes_operand() {
+                            "requires"
+                        } else {
+                            "does not take"
+                        }
+                    ),
+                    span,
+                ))
+            }
+            RunParameter::ArithmeticOp(_, None) => Ok(ParameterInfo {
+                inferred_type: None,
+            }),
+            RunParameter::ArithmeticOp(_, Some(value)) => {
                 let value_type = self.infer_literal_type(value);
                 if matches!(value_type, DataType::Int | DataType::Float) {
                     Ok(ParameterInfo {
@@ -245,6 +267,10 @@ impl RuntimeOperationValidator {
                     .copied()
                     .unwrap_or(DataType::String)
             }
+            Value::List(items) => items
+                .first()
+                .map(|item| self.infer_literal_type(item))
+                .unwrap_or(DataType::String),
         }
     }
 
@@ -309,7 +335,7 @@ impl RuntimeOperationValidator {
         operation_type: RuntimeOperationType,
     ) -> (usize, Option<usize>) {
         match operation_type {
-            RuntimeOperationType::Concat => (1, None),
+            RuntimeOperationType::Concat => (2, None),
             RuntimeOperationType::Split => (2, Some(3)),
             RuntimeOperationType::Substring => (2, Some(3)),
             RuntimeOperationType::RegexCapture => (2, Some(3)),
@@ -319,6 +345,11 @@ impl RuntimeOperationValidator {
             RuntimeOperationType::Merge => (2, None),
             RuntimeOperationType::Extract => (1, None),
             RuntimeOperationType::End => (0, Some(1)),
+            RuntimeOperationType::Replace => (3, Some(3)),
+            RuntimeOperationType::Length
+            | RuntimeOperationType::Uppercase
+            | RuntimeOperationType::Lowercase
+            | RuntimeOperationType::Trim => (1, Some(1)),
         }
     }
 
@@ -326,6 +357,7 @@ impl RuntimeOperationValidator {
         &self,
         operation_type: RuntimeOperationType,
         variable_name: &str,
+        parameters: &[RunParameter],
         parameter_analysis: &[ParameterInfo],
         span: Span,
     ) -> Result<(), SemanticError> {
@@ -334,9 +366,12 @@ impl RuntimeOperationValidator {
             "parameter_count" => parameter_analysis.len());
 
         match operation_type {
-            RuntimeOperationType::Concat => {
-                self.validate_concat_constraints(variable_name, parameter_analysis, span)
-            }
+            RuntimeOperationType::Concat => self.validate_concat_constraints(
+                variable_name,
+                parameters,
+                parameter_analysis,
+                span,
+            ),
             RuntimeOperationType::Split | RuntimeOperationType::Substring => self
                 .validate_string_operation_constraints(
                     variable_name,
@@ -350,7 +385,10 @@ impl RuntimeOperationValidator {
             RuntimeOperationType::Arithmetic => {
                 self.validate_arithmetic_constraints(variable_name, parameter_analysis, span)
             }
-            RuntimeOperationType::Count | RuntimeOperationType::Unique => {
+            RuntimeOperationType::Count => {
+                self.validate_count_constraints(variable_name, parameters, span)
+            }
+            RuntimeOperationType::Unique => {
                 self.validate_collection_constraints(variable_name, parameter_analysis, span)
             }
             RuntimeOperationType::Merge => {
@@ -363,15 +401,46 @@ impl RuntimeOperationValidator {
                 // END operation has no constraints
                 Ok(())
             }
+            RuntimeOperationType::Length
+            | RuntimeOperationType::Uppercase
+            | RuntimeOperationType::Lowercase
+            | RuntimeOperationType::Trim => self.validate_unary_string_constraints(
+                variable_name,
+                operation_type,
+                parameters,
+                parameter_analysis,
+                span,
+            ),
+            RuntimeOperationType::Replace => self.validate_replace_constraints(
+                variable_name,
+                parameters,
+                parameter_analysis,
+                span,
+            ),
         }
     }
 
     fn validate_concat_constraints(
         &self,
         variable_name: &str,
+        parameters: &[RunParameter],
         parameter_analysis: &[ParameterInfo],
         span: Span,
     ) -> Result<(), SemanticError> {
+        // CONCAT joins VAR and literal parameters in order
+        if let Some(index) = parameters.iter().position(|p| !is_value_parameter(p)) {
+            return Err(SemanticError::runtime_operation_error(
+                variable_name,
+                RuntimeOperationType::Concat,
+                &format!(
+                    "CONCAT takes only VAR and literal parameters, found {} at position {}",
+                    parameter_kind(&parameters[index]),
+                    index + 1
+                ),
+                span,
+            ));
+        }
+
         // CONCAT requires all parameters to be string-compatible
         for (index, param_info) in parameter_analysis.iter().enumerate() {
             if let Some(param_type) = param_info.inferred_type {
@@ -392,6 +461,54 @@ impl RuntimeOperationValidator {
         Ok(())
     }
 
+    fn validate_replace_constraints(
+        &self,
+        variable_name: &str,
+        parameters: &[RunParameter],
+        parameter_analysis: &[ParameterInfo],
+        span: Span,
+    ) -> Result<(), SemanticError> {
+        // REPLACE is: input (VAR or literal), pattern, replacement literal
+        let error = |reason: String| {
+            SemanticError::runtime_operation_error(
+                variable_name,
+                RuntimeOperationType::Replace,
+                &reason,
+                span,
+            )
+        };
+
+        let expected = ["VAR or literal input", "pattern", "replacement literal"];
+        for (index, parameter) in parameters.iter().enumerate() {
+            let in_place = match index {
+                0 => is_value_parameter(parameter),
+                1 => matches!(parameter, RunParameter::Pattern(_)),
+                _ => matches!(parameter, RunParameter::Literal(_)),
+            };
+            if !in_place {
+                return Err(error(format!(
+                    "REPLACE expects a {} at position {}, found {}",
+                    expected[index.min(2)],
+                    index + 1,
+                    parameter_kind(parameter)
+                )));
+            }
+        }
+
+        for (index, param_info) in parameter_analysis.iter().enumerate() {
+            if let Some(param_type) = param_info.inferred_type {
+                if param_type != DataType::String {
+                    return Err(error(format!(
+                        "REPLACE requires string parameters, found {} at position {}",
+                        param_type.as_str(),
+                        index + 1
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
     fn validate_string_operation_constraints(
         &self,
         variable_name: &str,
@@ -495,6 +612,59 @@ impl RuntimeOperationValidator {
         Ok(())
     }
 
+    fn validate_count_constraints(
+        &self,
+        variable_name: &str,
+        parameters: &[RunParameter],
+        span: Span,
+    ) -> Result<(), SemanticError> {
+        // COUNT counts the items of a variable or of an extracted object field
+        match parameters.first() {
+            Some(RunParameter::Variable(_) | RunParameter::ObjectExtraction { .. }) | None => Ok(()),
+            Some(parameter) => Err(SemanticError::runtime_operation_error(
+                variable_name,
+                RuntimeOperationType::Count,
+                &format!(
+                    "COUNT takes a VAR or OBJ parameter, found {}; use LENGTH for the length of a string",
+                    parameter_kind(parameter)
+                ),
+                span,
+            )),
+        }
+    }
+
+    fn validate_unary_string_constraints(
+        &self,
+        variable_name: &str,
+        operation_type: RuntimeOperationType,
+        parameters: &[RunParameter],
+        parameter_analysis: &[ParameterInfo],
+        span: Span,
+    ) -> Result<(), SemanticError> {
+        // LENGTH, UPPERCASE, LOWERCASE and TRIM take one string, given as VAR or literal
+        let error = |reason: String| {
+            SemanticError::runtime_operation_error(variable_name, operation_type, &reason, span)
+        };
+
+        if let Some(parameter) = parameters.iter().find(|p| !is_value_parameter(p)) {
+            return Err(error(format!(
+                "{} takes a VAR or literal parameter, found {}",
+                operation_type.as_str(),
+                parameter_kind(parameter)
+            )));
+        }
+        if let Some(param_type) = parameter_analysis.first().and_then(|p| p.inferred_type) {
+            if param_type != DataType::String {
+                return Err(error(format!(
+                    "{} requires a string, found {}",
+                    operation_type.as_str(),
+                    param_type.as_str()
+                )));
+            }
+        }
+        Ok(())
+    }
+
     fn validate_merge_constraints(
         &self,
         variable_name: &str,
@@ -549,3 +719,26 @@ impl RuntimeOperationValidator {
 struct ParameterInfo {
     inferred_type: Option<DataType>,
 }
+
+/// VAR and literal parameters carry a value; the others configure the operation
+fn is_value_parameter(parameter: &RunParameter) -> bool {
+    matches!(
+        parameter,
+        RunParameter::Literal(_) | RunParameter::Variable(_)
+    )
+}
+
+/// Parameter keyword as written in a RUN block
+fn parameter_kind(parameter: &RunParameter) -> &'static str {
+    match parameter {
+        RunParameter::Literal(_) => "literal",
+        RunParameter::Variable(_) => "VAR",
+        RunParameter::ObjectExtraction { .. } => "OBJ",
+        RunParameter::Pattern(_) => "pattern",
+        RunParameter::Delimiter(_) => "delimiter",
+        RunParameter::Character(_) => "character",
+        RunParameter::StartPosition(_) => "start",
+        RunParameter::Length(_) => "length",
+        RunParameter::ArithmeticOp(..) => "arithmetic operator",
+    }
+}
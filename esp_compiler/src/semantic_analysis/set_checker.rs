@@ -5,7 +5,7 @@
 use super::types::{SemanticError, SemanticInput};
 use crate::{
     config::constants::compile_time::semantic::*, grammar::ast::nodes::SetOperationType, log_debug,
-    log_error, log_info, logging::codes, utils::Span,
+    log_error, log_info, logging::codes, tokens::Span,
 };
 
 /// Validate all SET operations in the AST with security limits
@@ -229,7 +229,7 @@ fn validate_filter_references(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::utils::Span;
+    use crate::tokens::Span;
 
     fn create_test_input() -> SemanticInput {
         // Create minimal test input
@@ -242,6 +242,8 @@ mod tests {
                 objects: Vec::new(),
                 set_operations: Vec::new(),
                 criteria: Vec::new(),
+                criteria_root: None,
+                imports: Vec::new(),
                 span: Some(Span::dummy()),
             },
             span: Some(Span::dummy()),
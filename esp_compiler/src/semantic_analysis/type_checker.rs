@@ -5,9 +5,9 @@
 
 use super::types::{SemanticError, SemanticInput};
 use crate::{
-    grammar::ast::nodes::{DataType, Operation},
+    grammar::ast::nodes::{DataType, Operation, Value},
     log_debug, log_error, log_info,
-    utils::Span,
+    tokens::Span,
 };
 
 /// Validate type compatibility for all field operations in the AST
@@ -32,6 +32,7 @@ pub fn validate_type_compatibility(
                 &field.name,
                 field.data_type,
                 field.operation,
+                &field.value,
                 field.span.unwrap_or_else(Span::dummy),
             ) {
                 Ok(()) => {
@@ -108,6 +109,7 @@ fn validate_criteria_states(
                             &field.name,
                             field.data_type,
                             field.operation,
+                            &field.value,
                             field.span.unwrap_or_else(Span::dummy),
                         ) {
                             Ok(()) => {
@@ -141,6 +143,7 @@ fn validate_field_operation(
     field_name: &str,
     data_type: DataType,
     operation: Operation,
+    value: &Value,
     span: Span,
 ) -> Result<(), SemanticError> {
     log_debug!("Checking field compatibility",
@@ -159,9 +162,341 @@ fn validate_field_operation(
         ));
     }
 
+    match operation {
+        Operation::LengthEquals | Operation::LengthGreaterThan => {
+            validate_length_operand(field_name, data_type, operation, value, span)
+        }
+        Operation::OccurrenceCount => {
+            validate_occurrence_operand(field_name, data_type, value, span)
+        }
+        Operation::InSubnet | Operation::NotInSubnet => {
+            validate_subnet_operand(field_name, data_type, operation, value, span)
+        }
+        Operation::GlobMatch | Operation::NotGlobMatch => {
+            validate_glob_operand(field_name, data_type, operation, value, span)
+        }
+        Operation::PermissionsAtMost | Operation::PermissionsAtLeast => {
+            validate_mode_operand(field_name, data_type, operation, value, span)
+        }
+        Operation::Equals | Operation::NotEqual if data_type == DataType::Octal => {
+            validate_mode_operand(field_name, data_type, operation, value, span)
+        }
+        _ if data_type == DataType::DateTime => {
+            validate_datetime_operand(field_name, data_type, operation, value, span)
+        }
+        _ => validate_value_list(field_name, data_type, operation, value, span),
+    }
+}
+
+/// Length operations compare the string's character count to an integer
+fn validate_length_operand(
+    field_name: &str,
+    data_type: DataType,
+    operation: Operation,
+    value: &Value,
+    span: Span,
+) -> Result<(), SemanticError> {
+    match value {
+        Value::Integer(n) if *n >= 0 => Ok(()),
+        // Variable types are checked when the variable resolves
+        Value::Variable(_) => Ok(()),
+        other => Err(SemanticError::operand_mismatch(
+            field_name,
+            data_type,
+            operation,
+            &format!("expected a non-negative int length, found {}", other),
+            span,
+        )),
+    }
+}
+
+/// Subnet operations take a CIDR or a list of CIDRs; a malformed network
+/// is a compile error rather than a scan-time failure
+fn validate_subnet_operand(
+    field_name: &str,
+    data_type: DataType,
+    operation: Operation,
+    value: &Value,
+    span: Span,
+) -> Result<(), SemanticError> {
+    let error = |reason: String| {
+        SemanticError::operand_mismatch(field_name, data_type, operation, &reason, span)
+    };
+    let check = |item: &Value| match item {
+        Value::String(network) => check_cidr(network).map_err(error),
+        other => Err(error(format!(
+            "expected a CIDR such as `10.0.0.0/8`, found {}",
+            other
+        ))),
+    };
+
+    match value {
+        // Variable types are checked when the variable resolves
+        Value::Variable(_) => Ok(()),
+        Value::List(items) if items.is_empty() => {
+            Err(error("the list of networks is empty".to_string()))
+        }
+        Value::List(items) => items.iter().try_for_each(check),
+        other => check(other),
+    }
+}
+
+/// Check `address/prefix` (or a bare address, a single host) the way the
+/// scanner's `Cidr` parses it
+fn check_cidr(network: &str) -> Result<(), String> {
+    let (address, prefix) = match network.split_once('/') {
+        Some((address, prefix)) => (address, Some(prefix)),
+        None => (network, None),
+    };
+    let address: std::net::IpAddr = address
+        .parse()
+        .map_err(|_| format!("'{}' is not a CIDR network: bad address", network))?;
+    let max_prefix = if address.is_ipv4() { 32 } else { 128 };
+    match prefix.map(str::parse::<u8>) {
+        None => Ok(()),
+        Some(Ok(prefix)) if prefix <= max_prefix => Ok(()),
+        Some(_) => Err(format!(
+            "'{}' is not a CIDR network: the prefix length must be 0 to {}",
+            network, max_prefix
+        )),
+    }
+}
+
+/// Glob operations take a single pattern; an unterminated `[` class is a
+/// compile error rather than a pattern that never matches
+fn validate_glob_operand(
+    field_name: &str,
+    data_type: DataType,
+    operation: Operation,
+    value: &Value,
+    span: Span,
+) -> Result<(), SemanticError> {
+    let error = |reason: String| {
+        SemanticError::operand_mismatch(field_name, data_type, operation, &reason, span)
+    };
+
+    match value {
+        Value::String(pattern) => check_glob(pattern).map_err(error),
+        // Variable types are checked when the variable resolves
+        Value::Variable(_) => Ok(()),
+        other => Err(error(format!(
+            "expected a glob pattern such as `*.conf`, found {}",
+            other
+        ))),
+    }
+}
+
+/// Check that every `[` class is closed, the way the scanner's glob
+/// matcher reads it (`\` escapes, a leading `]` is a member)
+fn check_glob(pattern: &str) -> Result<(), String> {
+    let chars: Vec<char> = pattern.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '\\' => i += 2,
+            '[' => {
+                let mut j = i + 1;
+                if matches!(chars.get(j), Some('!') | Some('^')) {
+                    j += 1;
+                }
+                if chars.get(j) == Some(&']') {
+                    j += 1;
+                }
+                match chars[j.min(chars.len())..].iter().position(|&c| c == ']') {
+                    Some(offset) => i = j + offset + 1,
+                    None => {
+                        return Err(format!(
+                            "'{}' is not a glob pattern: unterminated `[` character class",
+                            pattern
+                        ))
+                    }
+                }
+            }
+            _ => i += 1,
+        }
+    }
     Ok(())
 }
 
+/// Octal values are permission bits, `0000` through `7777`
+fn validate_mode_operand(
+    field_name: &str,
+    data_type: DataType,
+    operation: Operation,
+    value: &Value,
+    span: Span,
+) -> Result<(), SemanticError> {
+    match value {
+        Value::Integer(mode) if (0..=0o7777).contains(mode) => Ok(()),
+        // Variable types are checked when the variable resolves
+        Value::Variable(_) => Ok(()),
+        other => {
+            let found = match other {
+                Value::Integer(mode) => format!("0{:o}", mode),
+                other => other.to_string(),
+            };
+            Err(SemanticError::operand_mismatch(
+                field_name,
+                data_type,
+                operation,
+                &format!("expected an octal mode from 0000 to 7777, found {}", found),
+                span,
+            ))
+        }
+    }
+}
+
+/// Datetime values are RFC 3339 timestamps, epoch seconds, or an offset
+/// from the scan time; a malformed timestamp is a compile error
+fn validate_datetime_operand(
+    field_name: &str,
+    data_type: DataType,
+    operation: Operation,
+    value: &Value,
+    span: Span,
+) -> Result<(), SemanticError> {
+    let error = |reason: String| {
+        SemanticError::operand_mismatch(field_name, data_type, operation, &reason, span)
+    };
+
+    match value {
+        Value::String(timestamp) => check_datetime(timestamp).map_err(error),
+        Value::Integer(_) => Ok(()),
+        // Variable types are checked when the variable resolves
+        Value::Variable(_) => Ok(()),
+        other => Err(error(format!(
+            "expected a timestamp such as `2025-01-01T00:00:00Z` or `now-30d`, found {}",
+            other
+        ))),
+    }
+}
+
+/// Check an RFC 3339 timestamp or `now[+-]<n><unit>`, the way the
+/// scanner's `Timestamp` parses it
+fn check_datetime(timestamp: &str) -> Result<(), String> {
+    let Some(offset) = timestamp.strip_prefix("now") else {
+        return chrono::DateTime::parse_from_rfc3339(timestamp)
+            .map(|_| ())
+            .map_err(|e| format!("'{}' is not an RFC 3339 timestamp: {}", timestamp, e));
+    };
+    if offset.is_empty() {
+        return Ok(());
+    }
+    let malformed = || {
+        format!(
+            "'{}' is not a relative time: expected `now-<n><unit>` with unit s, m, h, d or w",
+            timestamp
+        )
+    };
+    let digits = offset
+        .strip_prefix(['+', '-'])
+        .and_then(|amount| amount.strip_suffix(['s', 'm', 'h', 'd', 'w']))
+        .ok_or_else(malformed)?;
+    match digits.parse::<u32>() {
+        Ok(_) if digits.bytes().all(|b| b.is_ascii_digit()) => Ok(()),
+        _ => Err(malformed()),
+    }
+}
+
+/// `occurrence_count` takes exactly `[pattern, count]`
+fn validate_occurrence_operand(
+    field_name: &str,
+    data_type: DataType,
+    value: &Value,
+    span: Span,
+) -> Result<(), SemanticError> {
+    let error = |reason: String| {
+        SemanticError::value_list_mismatch(
+            field_name,
+            data_type,
+            Operation::OccurrenceCount,
+            &reason,
+            span,
+        )
+    };
+
+    match value {
+        Value::List(items) => match items.as_slice() {
+            [Value::String(pattern), Value::Integer(count)]
+                if !pattern.is_empty() && *count >= 0 =>
+            {
+                Ok(())
+            }
+            [Value::String(pattern), Value::Integer(_)] if pattern.is_empty() => {
+                Err(error("the pattern must not be empty".to_string()))
+            }
+            [Value::String(_), Value::Integer(_)] => {
+                Err(error("the count must not be negative".to_string()))
+            }
+            _ => Err(error(format!(
+                "expected [`pattern`, count], found {}",
+                value
+            ))),
+        },
+        _ => Err(error(format!(
+            "'occurrence_count' requires [`pattern`, count], found {}",
+            value
+        ))),
+    }
+}
+
+/// Membership operations take a value list and nothing else does; every
+/// list element must be a literal of the field's declared type
+fn validate_value_list(
+    field_name: &str,
+    data_type: DataType,
+    operation: Operation,
+    value: &Value,
+    span: Span,
+) -> Result<(), SemanticError> {
+    let is_membership = matches!(operation, Operation::EqualsAny | Operation::NotEqualsAny);
+    let error = |reason: String| {
+        SemanticError::value_list_mismatch(field_name, data_type, operation, &reason, span)
+    };
+
+    match value {
+        Value::List(items) if is_membership => {
+            for (index, item) in items.iter().enumerate() {
+                if !literal_matches_type(item, data_type) {
+                    return Err(error(format!(
+                        "element {} ({}) is not a {} value",
+                        index + 1,
+                        item,
+                        data_type.as_str()
+                    )));
+                }
+            }
+            Ok(())
+        }
+        Value::List(_) => Err(error(
+            "value lists can only be used with equals_any, not_equals_any or occurrence_count"
+                .to_string(),
+        )),
+        _ if is_membership => Err(error(format!(
+            "'{}' requires a value list such as [`a`, `b`], found {}",
+            operation.as_str(),
+            value
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Whether a literal can be compared as the given data type
+fn literal_matches_type(value: &Value, data_type: DataType) -> bool {
+    use DataType::*;
+
+    match value {
+        Value::String(_) => matches!(
+            data_type,
+            String | Binary | Version | EvrString | IpAddress | DateTime
+        ),
+        Value::Integer(_) => matches!(data_type, Int | Float | Octal | DateTime),
+        Value::Float(_) => matches!(data_type, Float),
+        Value::Boolean(_) => matches!(data_type, Boolean),
+        Value::Variable(_) | Value::List(_) => false,
+    }
+}
+
 /// Check if operation is compatible with data type according to EBNF matrix
 fn is_operation_compatible(data_type: DataType, operation: Operation) -> bool {
     use DataType::*;
@@ -186,8 +521,21 @@ fn is_operation_compatible(data_type: DataType, operation: Operation) -> bool {
                 | NotEndsWith
                 | PatternMatch
                 | Matches
+                | GlobMatch
+                | NotGlobMatch
                 | SubsetOf
                 | SupersetOf
+                | EqualsAny
+                | NotEqualsAny
+                | LengthEquals
+                | LengthGreaterThan
+                | OccurrenceCount
+                | VersionEquals
+                | VersionNotEqual
+                | VersionGreaterThan
+                | VersionLessThan
+                | VersionGreaterOrEqual
+                | VersionLessOrEqual
         ),
         Int | Float => matches!(
             operation,
@@ -199,11 +547,38 @@ fn is_operation_compatible(data_type: DataType, operation: Operation) -> bool {
                 | LessThanOrEqual
                 | SubsetOf
                 | SupersetOf
+                | EqualsAny
+                | NotEqualsAny
         ),
         Boolean => matches!(operation, Equals | NotEqual),
         Binary => matches!(operation, Equals | NotEqual | Contains),
         RecordData => matches!(operation, Equals | NotEqual),
         Version | EvrString => matches!(
+            operation,
+            Equals
+                | NotEqual
+                | GreaterThan
+                | LessThan
+                | GreaterThanOrEqual
+                | LessThanOrEqual
+                | EqualsAny
+                | NotEqualsAny
+                | VersionEquals
+                | VersionNotEqual
+                | VersionGreaterThan
+                | VersionLessThan
+                | VersionGreaterOrEqual
+                | VersionLessOrEqual
+        ),
+        IpAddress => matches!(
+            operation,
+            Equals | NotEqual | EqualsAny | NotEqualsAny | InSubnet | NotInSubnet
+        ),
+        Octal => matches!(
+            operation,
+            Equals | NotEqual | EqualsAny | NotEqualsAny | PermissionsAtMost | PermissionsAtLeast
+        ),
+        DateTime => matches!(
             operation,
             Equals | NotEqual | GreaterThan | LessThan | GreaterThanOrEqual | LessThanOrEqual
         ),
@@ -232,8 +607,21 @@ fn get_supported_operations_string(data_type: DataType) -> String {
             "not_ends_with",
             "pattern_match",
             "matches",
+            "glob_match",
+            "not_glob_match",
             "subset_of",
             "superset_of",
+            "equals_any",
+            "not_equals_any",
+            "length_equals",
+            "length_greater_than",
+            "occurrence_count",
+            "version_equals",
+            "version_not_equal",
+            "version_greater_than",
+            "version_less_than",
+            "version_greater_or_equal",
+            "version_less_or_equal",
         ],
         Int | Float => vec![
             "equals",
@@ -244,6 +632,8 @@ fn get_supported_operations_string(data_type: DataType) -> String {
             "less_than_or_equal",
             "subset_of",
             "superset_of",
+            "equals_any",
+            "not_equals_any",
         ],
         Boolean => vec!["equals", "not_equal"],
         Binary => vec!["equals", "not_equal", "contains"],
@@ -255,6 +645,38 @@ fn get_supported_operations_string(data_type: DataType) -> String {
             "less_than",
             "greater_than_or_equal",
             "less_than_or_equal",
+            "equals_any",
+            "not_equals_any",
+            "version_equals",
+            "version_not_equal",
+            "version_greater_than",
+            "version_less_than",
+            "version_greater_or_equal",
+            "version_less_or_equal",
+        ],
+        IpAddress => vec![
+            "equals",
+            "not_equal",
+            "equals_any",
+            "not_equals_any",
+            "in_subnet",
+            "not_in_subnet",
+        ],
+        Octal => vec![
+            "equals",
+            "not_equal",
+            "equals_any",
+            "not_equals_any",
+            "permissions_at_most",
+            "permissions_at_least",
+        ],
+        DateTime => vec![
+            "equals",
+            "not_equal",
+            "greater_than",
+            "less_than",
+            "greater_than_or_equal",
+            "less_than_or_equal",
         ],
     };
 
@@ -293,4 +715,262 @@ mod tests {
         assert!(boolean_ops.contains("equals"));
         assert!(!boolean_ops.contains("contains"));
     }
+
+    #[test]
+    fn test_value_list_elements_match_declared_type() {
+        let list = Value::List(vec![Value::string("aes256-gcm"), Value::string("chacha20")]);
+        assert!(validate_field_operation(
+            "cipher",
+            DataType::String,
+            Operation::EqualsAny,
+            &list,
+            Span::dummy()
+        )
+        .is_ok());
+
+        let mixed = Value::List(vec![Value::integer(22), Value::string("2222")]);
+        let error = validate_field_operation(
+            "port",
+            DataType::Int,
+            Operation::NotEqualsAny,
+            &mixed,
+            Span::dummy(),
+        )
+        .unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("element 2 (`2222`) is not a int value"));
+    }
+
+    #[test]
+    fn test_value_list_requires_membership_operation() {
+        let list = Value::List(vec![Value::string("a")]);
+        assert!(validate_field_operation(
+            "f",
+            DataType::String,
+            Operation::Equals,
+            &list,
+            Span::dummy()
+        )
+        .is_err());
+        assert!(validate_field_operation(
+            "f",
+            DataType::String,
+            Operation::EqualsAny,
+            &Value::string("a"),
+            Span::dummy()
+        )
+        .is_err());
+        assert!(!is_operation_compatible(
+            DataType::Boolean,
+            Operation::EqualsAny
+        ));
+    }
+
+    #[test]
+    fn test_string_measures_require_string_field_and_counted_operand() {
+        assert!(validate_field_operation(
+            "banner",
+            DataType::String,
+            Operation::LengthGreaterThan,
+            &Value::integer(0),
+            Span::dummy()
+        )
+        .is_ok());
+        assert!(!is_operation_compatible(
+            DataType::Int,
+            Operation::LengthEquals
+        ));
+
+        let negative = validate_field_operation(
+            "banner",
+            DataType::String,
+            Operation::LengthEquals,
+            &Value::integer(-1),
+            Span::dummy(),
+        );
+        assert!(negative.is_err());
+        assert!(validate_field_operation(
+            "banner",
+            DataType::String,
+            Operation::LengthEquals,
+            &Value::string("10"),
+            Span::dummy()
+        )
+        .is_err());
+
+        let spec = Value::List(vec![Value::string("PermitRootLogin"), Value::integer(1)]);
+        assert!(validate_field_operation(
+            "content",
+            DataType::String,
+            Operation::OccurrenceCount,
+            &spec,
+            Span::dummy()
+        )
+        .is_ok());
+        let empty_pattern = Value::List(vec![Value::string(""), Value::integer(1)]);
+        assert!(validate_field_operation(
+            "content",
+            DataType::String,
+            Operation::OccurrenceCount,
+            &empty_pattern,
+            Span::dummy()
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_subnet_operands_are_checked_at_compile_time() {
+        let check = |value: Value| {
+            validate_field_operation(
+                "local_address",
+                DataType::IpAddress,
+                Operation::InSubnet,
+                &value,
+                Span::dummy(),
+            )
+        };
+
+        assert!(check(Value::string("10.0.0.0/8")).is_ok());
+        assert!(check(Value::string("fd00::/8")).is_ok());
+        assert!(check(Value::string("192.0.2.1")).is_ok());
+        assert!(check(Value::List(vec![
+            Value::string("10.0.0.0/8"),
+            Value::string("192.168.0.0/16")
+        ]))
+        .is_ok());
+
+        for malformed in ["10.0.0.0/33", "10.0.0/8", "fd00::/129", "10.0.0.0/"] {
+            let err = check(Value::string(malformed)).unwrap_err().to_string();
+            assert!(err.contains(malformed), "{}", err);
+        }
+        assert!(check(Value::List(vec![])).is_err());
+        assert!(check(Value::integer(10)).is_err());
+        assert!(!is_operation_compatible(DataType::Int, Operation::InSubnet));
+        assert!(!is_operation_compatible(
+            DataType::IpAddress,
+            Operation::Contains
+        ));
+    }
+
+    #[test]
+    fn test_glob_and_ignore_case_are_string_only() {
+        for operation in [
+            Operation::GlobMatch,
+            Operation::NotGlobMatch,
+            Operation::CaseInsensitiveEquals,
+            Operation::CaseInsensitiveNotEqual,
+        ] {
+            assert!(is_operation_compatible(DataType::String, operation));
+            for data_type in [
+                DataType::Int,
+                DataType::Boolean,
+                DataType::Version,
+                DataType::IpAddress,
+            ] {
+                assert!(!is_operation_compatible(data_type, operation));
+            }
+        }
+
+        let check = |pattern: &str| {
+            validate_field_operation(
+                "path",
+                DataType::String,
+                Operation::GlobMatch,
+                &Value::string(pattern),
+                Span::dummy(),
+            )
+        };
+        for valid in [
+            "*.conf",
+            "/etc/ssh/sshd_config.d/?*",
+            "[]a]*",
+            "[!0-9]",
+            "\\[x",
+        ] {
+            assert!(check(valid).is_ok(), "{}", valid);
+        }
+        let err = check("log[0-9").unwrap_err().to_string();
+        assert!(err.contains("log[0-9"), "{}", err);
+        assert!(validate_field_operation(
+            "path",
+            DataType::String,
+            Operation::GlobMatch,
+            &Value::integer(1),
+            Span::dummy()
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_mode_operands_are_checked_at_compile_time() {
+        let check = |operation: Operation, value: Value| {
+            validate_field_operation(
+                "permissions",
+                DataType::Octal,
+                operation,
+                &value,
+                Span::dummy(),
+            )
+        };
+
+        assert!(check(Operation::PermissionsAtMost, Value::integer(0o640)).is_ok());
+        assert!(check(Operation::PermissionsAtLeast, Value::integer(0o4000)).is_ok());
+        assert!(check(Operation::Equals, Value::integer(0o7777)).is_ok());
+        let err = check(Operation::PermissionsAtMost, Value::integer(0o10000))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("010000"), "{}", err);
+        assert!(check(Operation::Equals, Value::integer(-1)).is_err());
+        assert!(check(Operation::PermissionsAtMost, Value::string("0640")).is_err());
+        assert!(!is_operation_compatible(
+            DataType::Int,
+            Operation::PermissionsAtMost
+        ));
+        assert!(!is_operation_compatible(
+            DataType::Octal,
+            Operation::GreaterThan
+        ));
+    }
+
+    #[test]
+    fn test_datetime_operands_are_checked_at_compile_time() {
+        let check = |value: Value| {
+            validate_field_operation(
+                "modified",
+                DataType::DateTime,
+                Operation::GreaterThan,
+                &value,
+                Span::dummy(),
+            )
+        };
+
+        for valid in [
+            "2025-01-01T00:00:00Z",
+            "2025-06-30T12:30:00+02:00",
+            "now",
+            "now-30d",
+            "now+12h",
+            "now-90s",
+        ] {
+            assert!(check(Value::string(valid)).is_ok(), "{}", valid);
+        }
+        assert!(check(Value::integer(1_700_000_000)).is_ok());
+        for invalid in [
+            "2025-01-01",
+            "yesterday",
+            "now-30",
+            "now-30y",
+            "now30d",
+            "now--1d",
+        ] {
+            let err = check(Value::string(invalid)).unwrap_err().to_string();
+            assert!(err.contains(invalid), "{}", err);
+        }
+        assert!(check(Value::boolean(true)).is_err());
+        assert!(!is_operation_compatible(
+            DataType::DateTime,
+            Operation::Contains
+        ));
+    }
 }
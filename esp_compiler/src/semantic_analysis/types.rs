@@ -8,7 +8,7 @@ use crate::{
     grammar::ast::nodes::{DataType, Operation, RuntimeOperationType, SetOperationType},
     log_error,
     logging::codes,
-    utils::Span,
+    tokens::Span,
 };
 use thiserror::Error;
 
@@ -27,6 +27,30 @@ pub enum SemanticError {
         span: Span,
     },
 
+    /// Value list misuse or element type mismatch (E180)
+    #[error(
+        "Invalid value list on field '{field_name}' ({data_type} {operation}): {reason} at {span}"
+    )]
+    ValueListMismatch {
+        field_name: String,
+        data_type: DataType,
+        operation: Operation,
+        reason: String,
+        span: Span,
+    },
+
+    /// Operand of a measure operation is not the integer it compares against
+    #[error(
+        "Invalid operand on field '{field_name}' ({data_type} {operation}): {reason} at {span}"
+    )]
+    OperandMismatch {
+        field_name: String,
+        data_type: DataType,
+        operation: Operation,
+        reason: String,
+        span: Span,
+    },
+
     /// Runtime operation type error (E181)
     #[error("Runtime operation '{operation_type}' type error for variable '{variable_name}': {reason} at {span}")]
     RuntimeOperationError {
@@ -64,6 +88,8 @@ impl SemanticError {
     pub fn error_code(&self) -> codes::Code {
         match self {
             SemanticError::TypeIncompatibility { .. } => codes::semantic::TYPE_INCOMPATIBILITY,
+            SemanticError::ValueListMismatch { .. } => codes::semantic::TYPE_INCOMPATIBILITY,
+            SemanticError::OperandMismatch { .. } => codes::semantic::TYPE_INCOMPATIBILITY,
             SemanticError::RuntimeOperationError { .. } => codes::semantic::RUNTIME_OPERATION_ERROR,
             SemanticError::SetConstraintViolation { .. } => {
                 codes::semantic::SET_CONSTRAINT_VIOLATION
@@ -77,6 +103,8 @@ impl SemanticError {
     pub fn error_type(&self) -> &'static str {
         match self {
             SemanticError::TypeIncompatibility { .. } => "TypeIncompatibility",
+            SemanticError::ValueListMismatch { .. } => "ValueListMismatch",
+            SemanticError::OperandMismatch { .. } => "OperandMismatch",
             SemanticError::RuntimeOperationError { .. } => "RuntimeOperationError",
             SemanticError::SetConstraintViolation { .. } => "SetConstraintViolation",
             SemanticError::CircularDependency { .. } => "CircularDependency",
@@ -93,6 +121,8 @@ impl SemanticError {
     pub fn span(&self) -> Option<Span> {
         match self {
             Self::TypeIncompatibility { span, .. }
+            | Self::ValueListMismatch { span, .. }
+            | Self::OperandMismatch { span, .. }
             | Self::RuntimeOperationError { span, .. }
             | Self::SetConstraintViolation { span, .. }
             | Self::CircularDependency { span, .. } => Some(*span),
@@ -127,6 +157,40 @@ impl SemanticError {
         }
     }
 
+    /// Create measure operand error with message length validation
+    pub fn operand_mismatch(
+        field_name: &str,
+        data_type: DataType,
+        operation: Operation,
+        reason: &str,
+        span: Span,
+    ) -> Self {
+        Self::OperandMismatch {
+            field_name: Self::truncate_string(field_name),
+            data_type,
+            operation,
+            reason: Self::truncate_message(reason),
+            span,
+        }
+    }
+
+    /// Create value list error with message length validation
+    pub fn value_list_mismatch(
+        field_name: &str,
+        data_type: DataType,
+        operation: Operation,
+        reason: &str,
+        span: Span,
+    ) -> Self {
+        Self::ValueListMismatch {
+            field_name: Self::truncate_string(field_name),
+            data_type,
+            operation,
+            reason: Self::truncate_message(reason),
+            span,
+        }
+    }
+
     /// Create runtime operation error with message length validation
     pub fn runtime_operation_error(
         variable_name: &str,
@@ -254,6 +318,23 @@ impl SemanticError {
                     "data_type" => data_type.as_str(),
                     "operation" => operation.as_str());
             }
+            SemanticError::ValueListMismatch {
+                field_name,
+                data_type,
+                operation,
+                ..
+            }
+            | SemanticError::OperandMismatch {
+                field_name,
+                data_type,
+                operation,
+                ..
+            } => {
+                log_error!(self.error_code(), "Operand details",
+                    "field_name" => field_name,
+                    "data_type" => data_type.as_str(),
+                    "operation" => operation.as_str());
+            }
             SemanticError::RuntimeOperationError {
                 variable_name,
                 operation_type,
@@ -366,10 +447,18 @@ impl SemanticInput {
     }
 }
 
+/// A semantic finding that does not stop compilation
+#[derive(Debug, Clone, PartialEq)]
+pub struct SemanticWarning {
+    pub message: String,
+    pub span: Option<Span>,
+}
+
 /// Output data from semantic analysis with security-aware error handling
 #[derive(Debug, Clone)]
 pub struct SemanticOutput {
     pub errors: Vec<SemanticError>,
+    pub warnings: Vec<SemanticWarning>,
     pub is_successful: bool,
     pub error_limit_reached: bool,
 }
@@ -378,6 +467,7 @@ impl SemanticOutput {
     pub fn new() -> Self {
         Self {
             errors: Vec::new(),
+            warnings: Vec::new(),
             is_successful: false,
             error_limit_reached: false,
         }
@@ -387,6 +477,7 @@ impl SemanticOutput {
     pub fn success() -> Self {
         Self {
             errors: Vec::new(),
+            warnings: Vec::new(),
             is_successful: true,
             error_limit_reached: false,
         }
@@ -399,6 +490,7 @@ impl SemanticOutput {
 
         Self {
             errors,
+            warnings: Vec::new(),
             is_successful,
             error_limit_reached,
         }
@@ -463,7 +555,7 @@ impl Default for SemanticOutput {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::utils::Span;
+    use crate::tokens::Span;
 
     #[test]
     fn test_semantic_error_creation() {
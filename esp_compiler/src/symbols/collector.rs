@@ -15,7 +15,7 @@ use crate::symbols::{
     table::{RelationshipType, SymbolTableBuilder},
     SymbolDiscoveryError, SymbolDiscoveryResult,
 };
-use crate::utils::{Position, Span};
+use crate::tokens::{Position, Span};
 use crate::{log_debug, log_error, log_info, log_success, log_warning};
 
 /// Symbol scope for tracking global vs local symbols
@@ -731,9 +731,15 @@ impl SymbolCollector {
             | RuntimeOperationType::Split
             | RuntimeOperationType::Substring
             | RuntimeOperationType::RegexCapture
+            | RuntimeOperationType::Replace
+            | RuntimeOperationType::Uppercase
+            | RuntimeOperationType::Lowercase
+            | RuntimeOperationType::Trim
             | RuntimeOperationType::End => DataType::String,
 
-            RuntimeOperationType::Arithmetic | RuntimeOperationType::Count => DataType::Int,
+            RuntimeOperationType::Arithmetic
+            | RuntimeOperationType::Count
+            | RuntimeOperationType::Length => DataType::Int,
 
             RuntimeOperationType::Unique
             | RuntimeOperationType::Merge
@@ -1152,7 +1158,7 @@ impl SymbolCollector {
 mod tests {
     use super::*;
     use crate::grammar::ast::nodes::{DefinitionNode, EspFile};
-    use crate::utils::Span;
+    use crate::tokens::Span;
 
     fn create_empty_ast() -> EspFile {
         EspFile {
@@ -1165,6 +1171,8 @@ mod tests {
                 objects: Vec::new(),
                 set_operations: Vec::new(),
                 criteria: Vec::new(),
+                criteria_root: None,
+                imports: Vec::new(),
                 span: Some(Span::dummy()),
             },
         }
@@ -1,7 +1,7 @@
 //! Simplified Error types for Pass 3: Symbol Discovery
 
 use crate::grammar::ast::nodes::SetOperationType;
-use crate::utils::Span;
+use crate::tokens::Span;
 
 /// Result type for symbol discovery operations
 pub type SymbolResult<T> = Result<T, SymbolDiscoveryError>;
@@ -3,7 +3,7 @@
 use crate::config::constants::compile_time::symbols::*;
 use crate::grammar::ast::nodes::{DataType, SetOperationType, Value};
 use crate::symbols::SymbolDiscoveryError;
-use crate::utils::Span;
+use crate::tokens::Span;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -4,7 +4,7 @@
 //! and span-accurate error reporting.
 
 use crate::logging::{codes, Code};
-use crate::utils::Span;
+use crate::tokens::Span;
 
 pub type SyntaxResult<T> = Result<T, SyntaxError>;
 
@@ -321,7 +321,7 @@ impl std::error::Error for ContextualSyntaxError {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::utils::{Position, Span};
+    use crate::tokens::{Position, Span};
 
     #[test]
     fn test_error_code_mapping() {
@@ -19,7 +19,9 @@ use crate::{log_debug, log_error, log_info, log_success};
 /// Parse ESP file from token stream with global logging
 ///
 /// This function provides the main API for syntax analysis using the global logging system.
-pub fn parse_esp_file(token_stream: TokenStream) -> SyntaxResult<EspFile> {
+/// The parser recovers from each syntax error, so a failed parse returns every
+/// error in the file, each logged with its span as it was found.
+pub fn parse_esp_file(token_stream: TokenStream) -> Result<EspFile, Vec<SyntaxError>> {
     log_debug!("Starting syntax analysis", "tokens" => token_stream.len());
 
     // Use the enhanced parser implementation
@@ -32,28 +34,36 @@ pub fn parse_esp_file(token_stream: TokenStream) -> SyntaxResult<EspFile> {
                 "Syntax analysis completed successfully"
             );
         }
-        Err(error) => {
-            log_error!(error.error_code(), "Syntax analysis failed",
-                "error" => error.to_string()
-            );
+        Err(errors) => {
+            log_info!("Syntax analysis failed", "errors" => errors.len());
         }
     }
 
     result
 }
 
+/// Parse a file brought in by IMPORT: a DEF of shared declarations that may
+/// not declare criteria
+pub fn parse_import_file(token_stream: TokenStream) -> Result<EspFile, Vec<SyntaxError>> {
+    let result = parser::parse_import_token_stream(token_stream);
+    if let Err(errors) = &result {
+        log_info!("Syntax analysis of imported file failed", "errors" => errors.len());
+    }
+    result
+}
+
 /// Legacy compatibility function (maintains existing API contract)
 pub fn parse_esp_file_with_custom_logging(
     token_stream: TokenStream,
     _logging_service: std::sync::Arc<crate::logging::LoggingService>,
-) -> SyntaxResult<EspFile> {
+) -> Result<EspFile, Vec<SyntaxError>> {
     // Ignore the passed logging service and use global logging
     parse_esp_file(token_stream)
 }
 
 /// Convenience function for testing with debug logging
 #[cfg(test)]
-pub fn parse_esp_file_debug(token_stream: TokenStream) -> SyntaxResult<EspFile> {
+pub fn parse_esp_file_debug(token_stream: TokenStream) -> Result<EspFile, Vec<SyntaxError>> {
     log_debug!("Debug parsing session started");
     let result = parse_esp_file(token_stream);
     log_debug!("Debug parsing session completed", "success" => result.is_ok());
@@ -256,8 +266,8 @@ mod tests {
 
         // Should fail with empty token stream error
         assert!(result.is_err());
-        if let Err(error) = result {
-            assert_eq!(error.error_code().as_str(), "E041"); // EMPTY_TOKEN_STREAM
+        if let Err(errors) = result {
+            assert_eq!(errors[0].error_code().as_str(), "E041"); // EMPTY_TOKEN_STREAM
         }
     }
 
@@ -270,10 +280,10 @@ mod tests {
         // Either succeeds or fails gracefully with proper error
         match result {
             Ok(_) => println!("Parse succeeded with minimal input"),
-            Err(e) => {
-                println!("Parse failed as expected with minimal input: {}", e);
+            Err(errors) => {
+                println!("Parse failed as expected with minimal input: {:?}", errors);
                 // Should be a grammar error, not a system error
-                assert!(e.is_recoverable());
+                assert!(errors.iter().all(SyntaxError::is_recoverable));
             }
         }
     }
@@ -294,7 +304,7 @@ mod tests {
         match (result1, result2) {
             (Ok(_), Ok(_)) => (),
             (Err(e1), Err(e2)) => {
-                assert_eq!(e1.error_code(), e2.error_code());
+                assert_eq!(e1[0].error_code(), e2[0].error_code());
             }
             _ => panic!("Legacy and new APIs produced different result types"),
         }
@@ -314,9 +324,9 @@ mod tests {
         let tokens = create_empty_token_stream();
         let result = parse_esp_file(tokens);
 
-        if let Err(error) = result {
+        if let Err(errors) = result {
             // Verify error has proper metadata
-            let code = error.error_code();
+            let code = errors[0].error_code();
             let description = crate::logging::codes::get_description(code.as_str());
             assert_ne!(description, "Unknown error");
 
@@ -6,13 +6,13 @@
 use crate::config::constants::compile_time::syntax::*;
 use crate::grammar::{
     ast::nodes::EspFile,
-    builders::{atomic::Parser, parse_esp_file},
+    builders::{atomic::Parser, parse_esp_file, parse_import_file},
     keywords::Keyword,
 };
 use crate::logging::codes;
-use crate::syntax::error::{ContextualSyntaxError, ErrorContext, SyntaxError, SyntaxResult};
+use crate::syntax::error::{ContextualSyntaxError, ErrorContext, SyntaxError};
+use crate::tokens::Span;
 use crate::tokens::{Token, TokenStream, TokenStreamError};
-use crate::utils::Span;
 use crate::{log_debug, log_error, log_info, log_success, log_warning};
 use std::collections::VecDeque;
 
@@ -32,6 +32,8 @@ pub struct EspParser {
     tokens: TokenStream,
     context_stack: Vec<String>,
     error_history: VecDeque<SyntaxError>,
+    /// Every error found so far, parsing carries on past each one
+    errors: Vec<SyntaxError>,
     parse_depth: usize,
 }
 
@@ -44,6 +46,7 @@ impl EspParser {
             tokens,
             context_stack: Vec::new(),
             error_history: VecDeque::new(),
+            errors: Vec::new(),
             parse_depth: 0,
         }
     }
@@ -87,9 +90,23 @@ impl EspParser {
         false
     }
 
-    /// Parse TokenStream into AST with comprehensive error reporting
-    pub fn parse_esp_file(&mut self) -> SyntaxResult<EspFile> {
-        self.push_context("esp_file");
+    /// Parse TokenStream into AST with comprehensive error reporting. Parsing
+    /// recovers from each error, so all of them are returned in source order
+    pub fn parse_esp_file(&mut self) -> Result<EspFile, Vec<SyntaxError>> {
+        self.parse_with("esp_file", parse_esp_file)
+    }
+
+    /// Parse an imported file (shared declarations, no criteria)
+    pub fn parse_import_file(&mut self) -> Result<EspFile, Vec<SyntaxError>> {
+        self.parse_with("import_file", parse_import_file)
+    }
+
+    fn parse_with(
+        &mut self,
+        context: &str,
+        build: fn(&mut dyn Parser) -> Result<EspFile, String>,
+    ) -> Result<EspFile, Vec<SyntaxError>> {
+        self.push_context(context);
 
         log_info!("Starting ESP file parsing",
             "tokens" => self.tokens.len(),
@@ -100,14 +117,14 @@ impl EspParser {
         if self.tokens.len() == 0 {
             let error = SyntaxError::empty_token_stream();
             log_error!(error.error_code(), "Cannot parse empty token stream");
-            return Err(error);
+            return Err(vec![error]);
         }
 
         // Validate token stream has EOF
         if !self.has_eof_token() {
             let error = SyntaxError::missing_eof();
             log_error!(error.error_code(), "Token stream missing EOF token");
-            return Err(error);
+            return Err(vec![error]);
         }
 
         // Prevent excessive recursion - NOW USING COMPILE-TIME CONSTANT
@@ -117,39 +134,36 @@ impl EspParser {
                 "depth" => self.parse_depth,
                 "max_depth" => MAX_PARSE_DEPTH
             );
-            return Err(error);
+            return Err(vec![error]);
         }
 
         self.parse_depth += 1;
 
         // Use existing grammar builder with enhanced error context
-        let result = parse_esp_file(self);
+        let result = build(self);
 
         self.parse_depth -= 1;
 
+        if let Err(builder_error) = &result {
+            self.report_error(builder_error.clone());
+        }
+        self.pop_context();
+
         match result {
-            Ok(esp_file) => {
+            Ok(esp_file) if self.errors.is_empty() => {
                 log_success!(codes::success::AST_CONSTRUCTION_COMPLETE,
                     "ESP file parsing completed successfully",
-                    "context" => self.current_context(),
+                    "context" => context,
                     "final_position" => self.tokens.position()
                 );
-                self.pop_context();
                 Ok(esp_file)
             }
-            Err(builder_error) => {
-                let error = self.create_enhanced_error(&builder_error);
-                self.record_error(error.clone());
-
-                log_error!(error.error_code(), "ESP file parsing failed",
-                    span = error.span().unwrap_or_else(|| self.current_span()),
-                    "context" => self.current_context(),
-                    "error_message" => builder_error,
-                    "position" => self.tokens.position()
+            _ => {
+                log_info!("ESP file parsing found errors",
+                    "context" => context,
+                    "errors" => self.errors.len()
                 );
-
-                self.pop_context();
-                Err(error)
+                Err(std::mem::take(&mut self.errors))
             }
         }
     }
@@ -193,6 +207,8 @@ impl EspParser {
             Keyword::DefEnd,
             Keyword::Cri,
             Keyword::CriEnd,
+            Keyword::CriRoot,
+            Keyword::Import,
             Keyword::Ctn,
             Keyword::CtnEnd,
             Keyword::State,
@@ -263,6 +279,10 @@ impl Parser for EspParser {
         self.tokens.current_token()
     }
 
+    fn peek_token(&self) -> Option<&Token> {
+        self.tokens.peek().map(|spanned| &spanned.value)
+    }
+
     fn advance(&mut self) {
         let old_pos = self.tokens.position();
         self.tokens.advance();
@@ -287,15 +307,13 @@ impl Parser for EspParser {
                 Ok(())
             }
             Some(token) => {
-                let span = self.current_span();
                 let error_msg = format!(
                     "Expected keyword '{}', found '{}'",
                     keyword.as_str(),
                     token.as_esp_string()
                 );
 
-                log_error!(codes::syntax::UNEXPECTED_TOKEN, "Keyword expectation failed",
-                    span = span,
+                log_debug!("Keyword expectation failed",
                     "expected" => keyword.as_str(),
                     "found" => token.as_esp_string(),
                     "context" => self.current_context()
@@ -309,7 +327,7 @@ impl Parser for EspParser {
                     keyword.as_str()
                 );
 
-                log_error!(codes::syntax::MISSING_EOF, "Unexpected end while expecting keyword",
+                log_debug!("Unexpected end while expecting keyword",
                     "expected" => keyword.as_str(),
                     "context" => self.current_context()
                 );
@@ -330,11 +348,9 @@ impl Parser for EspParser {
                 Ok(identifier)
             }
             Some(token) => {
-                let span = self.current_span();
                 let error_msg = format!("Expected identifier, found '{}'", token.as_esp_string());
 
-                log_error!(codes::syntax::UNEXPECTED_TOKEN, "Identifier expectation failed",
-                    span = span,
+                log_debug!("Identifier expectation failed",
                     "expected" => "identifier",
                     "found" => token.as_esp_string(),
                     "context" => self.current_context()
@@ -345,7 +361,7 @@ impl Parser for EspParser {
             None => {
                 let error_msg = "Expected identifier, reached end of input".to_string();
 
-                log_error!(codes::syntax::MISSING_EOF, "Unexpected end while expecting identifier",
+                log_debug!("Unexpected end while expecting identifier",
                     "context" => self.current_context()
                 );
 
@@ -365,12 +381,10 @@ impl Parser for EspParser {
                 Ok(content)
             }
             Some(token) => {
-                let span = self.current_span();
                 let error_msg =
                     format!("Expected string literal, found '{}'", token.as_esp_string());
 
-                log_error!(codes::syntax::UNEXPECTED_TOKEN, "String literal expectation failed",
-                    span = span,
+                log_debug!("String literal expectation failed",
                     "expected" => "string literal",
                     "found" => token.as_esp_string(),
                     "context" => self.current_context()
@@ -381,7 +395,7 @@ impl Parser for EspParser {
             None => {
                 let error_msg = "Expected string literal, reached end of input".to_string();
 
-                log_error!(codes::syntax::MISSING_EOF, "Unexpected end while expecting string literal",
+                log_debug!("Unexpected end while expecting string literal",
                     "context" => self.current_context()
                 );
 
@@ -401,11 +415,9 @@ impl Parser for EspParser {
                 Ok(int_value)
             }
             Some(token) => {
-                let span = self.current_span();
                 let error_msg = format!("Expected integer, found '{}'", token.as_esp_string());
 
-                log_error!(codes::syntax::UNEXPECTED_TOKEN, "Integer expectation failed",
-                    span = span,
+                log_debug!("Integer expectation failed",
                     "expected" => "integer",
                     "found" => token.as_esp_string(),
                     "context" => self.current_context()
@@ -416,7 +428,7 @@ impl Parser for EspParser {
             None => {
                 let error_msg = "Expected integer, reached end of input".to_string();
 
-                log_error!(codes::syntax::MISSING_EOF, "Unexpected end while expecting integer",
+                log_debug!("Unexpected end while expecting integer",
                     "context" => self.current_context()
                 );
 
@@ -436,11 +448,9 @@ impl Parser for EspParser {
                 Ok(float_value)
             }
             Some(token) => {
-                let span = self.current_span();
                 let error_msg = format!("Expected float, found '{}'", token.as_esp_string());
 
-                log_error!(codes::syntax::UNEXPECTED_TOKEN, "Float expectation failed",
-                    span = span,
+                log_debug!("Float expectation failed",
                     "expected" => "float",
                     "found" => token.as_esp_string(),
                     "context" => self.current_context()
@@ -451,7 +461,7 @@ impl Parser for EspParser {
             None => {
                 let error_msg = "Expected float, reached end of input".to_string();
 
-                log_error!(codes::syntax::MISSING_EOF, "Unexpected end while expecting float",
+                log_debug!("Unexpected end while expecting float",
                     "context" => self.current_context()
                 );
 
@@ -463,6 +473,82 @@ impl Parser for EspParser {
     fn current_span(&self) -> Span {
         self.tokens.current_span().unwrap_or_else(Span::dummy)
     }
+
+    fn span_since(&self, start: usize) -> Span {
+        match self.tokens.position().checked_sub(1) {
+            Some(last) if last >= start => self.tokens.span_range(start, last),
+            _ => self.current_span(),
+        }
+    }
+
+    fn position(&self) -> usize {
+        self.tokens.position()
+    }
+
+    fn report_error(&mut self, message: String) {
+        let error = self.create_enhanced_error(&message);
+
+        log_error!(error.error_code(), &message,
+            span = error.span().unwrap_or_else(|| self.current_span()),
+            "context" => self.current_context(),
+            "position" => self.tokens.position()
+        );
+
+        self.record_error(error.clone());
+        self.errors.push(error);
+    }
+
+    fn skip_element(&mut self, start: usize) {
+        self.tokens.restore_position(start);
+        let Some(first) = self.tokens.current().cloned() else {
+            return;
+        };
+        if matches!(first.value, Token::Eof) {
+            return;
+        }
+
+        // End keywords of the blocks being skipped, innermost last
+        let mut open: Vec<Keyword> = match &first.value {
+            Token::Keyword(kw) => kw.corresponding_end().into_iter().collect(),
+            _ => Vec::new(),
+        };
+        let line = first.span.start().line;
+        self.tokens.advance();
+
+        while let Some(current) = self.tokens.current() {
+            match &current.value {
+                Token::Eof => break,
+                Token::Keyword(kw) if kw.is_block_end() => {
+                    // An end with no block open here closes an enclosing block
+                    let Some(depth) = open.iter().rposition(|end| end == kw) else {
+                        break;
+                    };
+                    open.truncate(depth);
+                    self.tokens.advance();
+                    if open.is_empty() {
+                        break;
+                    }
+                }
+                Token::Keyword(kw) if kw.is_block_start() && !open.is_empty() => {
+                    open.extend(kw.corresponding_end());
+                    self.tokens.advance();
+                }
+                _ if open.is_empty() && current.span.start().line != line => break,
+                _ => {
+                    self.tokens.advance();
+                }
+            }
+        }
+
+        log_debug!("Skipped element after syntax error",
+            "from" => start,
+            "to" => self.tokens.position()
+        );
+    }
+
+    fn error_count(&self) -> usize {
+        self.errors.len()
+    }
 }
 
 /// Enhanced parser interface with context tracking and diagnostics
@@ -700,7 +786,7 @@ pub fn create_parser(tokens: TokenStream) -> EspParser {
 }
 
 /// Parse TokenStream directly with global logging and enhanced error reporting
-pub fn parse_token_stream_enhanced(tokens: TokenStream) -> SyntaxResult<EspFile> {
+pub fn parse_token_stream_enhanced(tokens: TokenStream) -> Result<EspFile, Vec<SyntaxError>> {
     log_info!("Starting enhanced token stream parsing", "tokens" => tokens.len());
 
     let mut parser = EspParser::new(tokens);
@@ -712,17 +798,23 @@ pub fn parse_token_stream_enhanced(tokens: TokenStream) -> SyntaxResult<EspFile>
             validation_error
         ));
         log_error!(error.error_code(), "Parser validation failed", "error" => validation_error);
-        return Err(error);
+        return Err(vec![error]);
     }
 
     parser.parse_esp_file()
 }
 
+/// Parse the token stream of an imported file
+pub fn parse_import_token_stream(tokens: TokenStream) -> Result<EspFile, Vec<SyntaxError>> {
+    log_info!("Parsing imported file", "tokens" => tokens.len());
+    EspParser::new(tokens).parse_import_file()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::tokens::{Position, Span};
     use crate::tokens::{Token, TokenStreamBuilder};
-    use crate::utils::{Position, Span};
 
     fn create_empty_token_stream() -> TokenStream {
         TokenStreamBuilder::new().build()
@@ -847,8 +939,8 @@ mod tests {
 
         // Should fail with empty token stream error
         assert!(result.is_err());
-        if let Err(error) = result {
-            assert_eq!(error.error_code().as_str(), "E041"); // EMPTY_TOKEN_STREAM
+        if let Err(errors) = result {
+            assert_eq!(errors[0].error_code().as_str(), "E041"); // EMPTY_TOKEN_STREAM
         }
     }
 
@@ -0,0 +1,219 @@
+//! # Terminal Output
+//!
+//! Color for statuses and severities printed by the compiler and scanner
+//! CLIs. Colors are chosen once per process with [`set_color_choice`]; when
+//! they are off every helper returns its input unchanged, so plain output is
+//! byte-identical to uncolored builds and safe for log scrapers.
+
+use std::fmt;
+use std::io::IsTerminal;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static COLOR_ENABLED: AtomicBool = AtomicBool::new(false);
+
+const RESET: &str = "\x1b[0m";
+
+/// When to color output (`--color`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ColorChoice {
+    /// Color when stdout is a terminal and `NO_COLOR` is not set
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    pub const VALUES: [&'static str; 3] = ["auto", "always", "never"];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Auto => "auto",
+            Self::Always => "always",
+            Self::Never => "never",
+        }
+    }
+
+    /// Whether output should be colored under this choice
+    pub fn enabled(self) -> bool {
+        match self {
+            Self::Always => true,
+            Self::Never => false,
+            Self::Auto => !no_color_requested() && std::io::stdout().is_terminal(),
+        }
+    }
+}
+
+impl FromStr for ColorChoice {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(Self::Auto),
+            "always" => Ok(Self::Always),
+            "never" => Ok(Self::Never),
+            other => Err(format!(
+                "invalid color choice '{}' (expected auto, always or never)",
+                other
+            )),
+        }
+    }
+}
+
+impl fmt::Display for ColorChoice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// `NO_COLOR` set to any non-empty value (https://no-color.org)
+fn no_color_requested() -> bool {
+    std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty())
+}
+
+/// Apply a color choice for the rest of the process
+pub fn set_color_choice(choice: ColorChoice) {
+    COLOR_ENABLED.store(choice.enabled(), Ordering::Relaxed);
+}
+
+/// Text with the color sequences this module emits removed
+pub fn strip_styles(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            chars.by_ref().find(|&c| c == 'm');
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Role of a piece of output, mapped to one color everywhere
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Style {
+    Success,
+    Failure,
+    Error,
+    Warning,
+    Note,
+}
+
+impl Style {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::Success => "\x1b[32m",
+            Self::Failure => "\x1b[31m",
+            Self::Error => "\x1b[1;31m",
+            Self::Warning => "\x1b[33m",
+            Self::Note => "\x1b[36m",
+        }
+    }
+
+    /// Style for a status word (PASS, COMPLIANT, FAILED, error, ...)
+    pub fn for_status(status: &str) -> Option<Self> {
+        match status.to_ascii_lowercase().as_str() {
+            "pass" | "passed" | "compliant" | "success" | "ok" | "✓" => Some(Self::Success),
+            "fail" | "failed" | "non-compliant" | "✗" => Some(Self::Failure),
+            "error" | "critical" => Some(Self::Error),
+            "warning" | "unknown" | "not_evaluated" => Some(Self::Warning),
+            _ => None,
+        }
+    }
+
+    /// Style for a severity or criticality (critical, high, medium, ...)
+    pub fn for_severity(severity: &str) -> Option<Self> {
+        match severity.to_ascii_lowercase().as_str() {
+            "critical" => Some(Self::Error),
+            "high" => Some(Self::Failure),
+            "medium" => Some(Self::Warning),
+            "low" | "info" => Some(Self::Note),
+            _ => None,
+        }
+    }
+}
+
+/// Colors for one piece of output; plain when disabled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Palette {
+    enabled: bool,
+}
+
+impl Palette {
+    pub fn new(enabled: bool) -> Self {
+        Self { enabled }
+    }
+
+    /// Palette for the process-wide color choice
+    pub fn current() -> Self {
+        Self::new(COLOR_ENABLED.load(Ordering::Relaxed))
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn paint(&self, style: Style, text: &str) -> String {
+        if self.enabled {
+            format!("{}{}{}", style.code(), text, RESET)
+        } else {
+            text.to_string()
+        }
+    }
+
+    /// Color a status word; unrecognized words are returned unchanged
+    pub fn status(&self, status: &str) -> String {
+        match Style::for_status(status) {
+            Some(style) => self.paint(style, status),
+            None => status.to_string(),
+        }
+    }
+
+    /// Color a severity; unrecognized severities are returned unchanged
+    pub fn severity(&self, severity: &str) -> String {
+        match Style::for_severity(severity) {
+            Some(style) => self.paint(style, severity),
+            None => severity.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plain_palette_leaves_text_untouched() {
+        let plain = Palette::new(false);
+        for word in ["COMPLIANT", "NON-COMPLIANT", "error", "high", "anything"] {
+            assert_eq!(plain.status(word), word);
+            assert_eq!(plain.severity(word), word);
+        }
+    }
+
+    #[test]
+    fn test_colored_statuses_and_severities() {
+        let color = Palette::new(true);
+        assert_eq!(color.status("COMPLIANT"), "\x1b[32mCOMPLIANT\x1b[0m");
+        assert_eq!(
+            color.status("NON-COMPLIANT"),
+            "\x1b[31mNON-COMPLIANT\x1b[0m"
+        );
+        assert_eq!(color.status("error"), "\x1b[1;31merror\x1b[0m");
+        assert_eq!(color.severity("medium"), "\x1b[33mmedium\x1b[0m");
+        assert_eq!(color.status("Processing"), "Processing");
+        assert_eq!(strip_styles(&color.status("FAILED")), "FAILED");
+    }
+
+    #[test]
+    fn test_color_choice_parsing() {
+        for value in ColorChoice::VALUES {
+            assert_eq!(value.parse::<ColorChoice>().unwrap().as_str(), value);
+        }
+        assert!("sometimes".parse::<ColorChoice>().is_err());
+        assert!(ColorChoice::Always.enabled());
+        assert!(!ColorChoice::Never.enabled());
+    }
+}
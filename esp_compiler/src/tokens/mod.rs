@@ -16,6 +16,7 @@
 //! - **[`StringLiteral`]** - Handles ESP string literal variants (backtick, raw, multiline)
 //! - **[`TokenStream`]** - Efficient stream management with lookahead and filtering
 //! - **[`SpannedToken`]** - Tokens with source location information
+//! - **[`Span`]** / **[`Position`]** - Source locations carried by every diagnostic
 //!
 //! ## Token Types
 //!
@@ -33,6 +34,13 @@
 //! - **String operations**: `ieq`, `contains`, `starts`, `ends`, etc.
 //! - **Pattern operations**: `pattern_match`, `matches`
 //! - **Set operations**: `subset_of`, `superset_of`
+//! - **Membership operations**: `equals_any`, `not_equals_any` (against a `[...]` value list)
+//! - **Measure operations**: `length_equals`, `length_greater_than`, `occurrence_count`
+//! - **Version operations**: `version_equals`, `version_not_equal`, `version_greater_than`,
+//!   `version_less_than`, `version_greater_or_equal`, `version_less_or_equal`
+//! - **Network operations**: `in_subnet`, `not_in_subnet`
+//! - **Glob operations**: `glob_match`, `not_glob_match`
+//! - **Permission operations**: `permissions_at_most`, `permissions_at_least`
 //! - **Logical operations**: `AND`, `OR`, `ONE`
 //!
 //! ### Identifier Tokens
@@ -75,6 +83,7 @@
 //! All tokens include span information for precise error reporting and
 //! source location tracking throughout the parsing pipeline.
 
+pub mod span;
 pub mod token;
 pub mod token_stream;
 
@@ -90,8 +99,7 @@ pub use token::{
     is_context_sensitive_identifier, is_data_type_identifier, is_operator_symbol,
 };
 
-// Re-export span types from utils
-pub use crate::utils::{Position, SourceMap, Span, Spanned};
+pub use span::{Position, SourceMap, Span, Spanned};
 
 /// Module version
 pub const VERSION: &str = "1.0.0";
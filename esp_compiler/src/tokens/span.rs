@@ -7,6 +7,9 @@ use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// A position in source text with line, column, and byte offset.
+///
+/// Columns count characters, not bytes or display cells: a tab and a
+/// multi-byte UTF-8 character are each one column, as editors report them.
 #[derive(
     Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default, Serialize, Deserialize,
 )]
@@ -15,7 +18,7 @@ pub struct Position {
     pub offset: usize,
     /// Line number (1-based)
     pub line: u32,
-    /// Column number (1-based)
+    /// Column number (1-based, in characters)
     pub column: u32,
 }
 
@@ -46,11 +49,6 @@ impl Position {
                 line: self.line + 1,
                 column: 1,
             },
-            '\t' => Self {
-                offset: self.offset + 1,
-                line: self.line,
-                column: self.column + 4 - ((self.column - 1) % 4),
-            },
             _ => Self {
                 offset: self.offset + ch.len_utf8(),
                 line: self.line,
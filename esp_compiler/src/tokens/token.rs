@@ -83,6 +83,35 @@ pub enum Token {
     SubsetOf,   // subset_of
     SupersetOf, // superset_of
 
+    // Membership operators
+    EqualsAny,    // equals_any
+    NotEqualsAny, // not_equals_any
+
+    // Measure operators (compare a derived integer of a string)
+    LengthEquals,      // length_equals
+    LengthGreaterThan, // length_greater_than
+    OccurrenceCount,   // occurrence_count
+
+    // Version operators (RPM-style version ordering)
+    VersionEquals,         // version_equals
+    VersionNotEqual,       // version_not_equal
+    VersionGreaterThan,    // version_greater_than
+    VersionLessThan,       // version_less_than
+    VersionGreaterOrEqual, // version_greater_or_equal
+    VersionLessOrEqual,    // version_less_or_equal
+
+    // Network operators (address against CIDR)
+    InSubnet,    // in_subnet
+    NotInSubnet, // not_in_subnet
+
+    // Glob operators (shell-style wildcards)
+    GlobMatch,    // glob_match
+    NotGlobMatch, // not_glob_match
+
+    // Permission operators (mode bit masks)
+    PermissionsAtMost,  // permissions_at_most
+    PermissionsAtLeast, // permissions_at_least
+
     // Arithmetic operators
     Plus,     // +
     Minus,    // -
@@ -108,6 +137,18 @@ pub enum Token {
     // === PUNCTUATION ===
     /// Dot character for field paths
     Dot,
+    /// At sign marking an attribute in a field path
+    At,
+    /// Opening bracket of a value list
+    LeftBracket,
+    /// Closing bracket of a value list
+    RightBracket,
+    /// Value list separator
+    Comma,
+    /// Opening parenthesis of a counted entity check
+    LeftParen,
+    /// Closing parenthesis of a counted entity check
+    RightParen,
 
     // === WHITESPACE AND STRUCTURE ===
     /// Single space character
@@ -176,6 +217,47 @@ impl Token {
         matches!(self, Self::SubsetOf | Self::SupersetOf)
     }
 
+    /// Check if this token is a membership (value list) operator
+    pub fn is_membership_operator(&self) -> bool {
+        matches!(self, Self::EqualsAny | Self::NotEqualsAny)
+    }
+
+    /// Check if this token is a measure (length / occurrence count) operator
+    pub fn is_measure_operator(&self) -> bool {
+        matches!(
+            self,
+            Self::LengthEquals | Self::LengthGreaterThan | Self::OccurrenceCount
+        )
+    }
+
+    /// Check if this token is a version comparison operator
+    pub fn is_version_operator(&self) -> bool {
+        matches!(
+            self,
+            Self::VersionEquals
+                | Self::VersionNotEqual
+                | Self::VersionGreaterThan
+                | Self::VersionLessThan
+                | Self::VersionGreaterOrEqual
+                | Self::VersionLessOrEqual
+        )
+    }
+
+    /// Check if this token is a network (subnet) operator
+    pub fn is_subnet_operator(&self) -> bool {
+        matches!(self, Self::InSubnet | Self::NotInSubnet)
+    }
+
+    /// Check if this token is a glob operator
+    pub fn is_glob_operator(&self) -> bool {
+        matches!(self, Self::GlobMatch | Self::NotGlobMatch)
+    }
+
+    /// Check if this token is a permission mask operator
+    pub fn is_permission_operator(&self) -> bool {
+        matches!(self, Self::PermissionsAtMost | Self::PermissionsAtLeast)
+    }
+
     /// Check if this token is an arithmetic operator
     pub fn is_arithmetic_operator(&self) -> bool {
         matches!(
@@ -190,6 +272,12 @@ impl Token {
             || self.is_string_operator()
             || self.is_pattern_operator()
             || self.is_set_operator()
+            || self.is_membership_operator()
+            || self.is_measure_operator()
+            || self.is_version_operator()
+            || self.is_subnet_operator()
+            || self.is_glob_operator()
+            || self.is_permission_operator()
     }
 
     /// Check if this token is a literal value
@@ -223,6 +311,9 @@ impl Token {
                     | "record_data"
                     | "version"
                     | "evr_string"
+                    | "ip_address"
+                    | "octal"
+                    | "datetime"
             ),
             _ => false,
         }
@@ -318,6 +409,35 @@ impl Token {
             Self::SubsetOf => "subset_of".to_string(),
             Self::SupersetOf => "superset_of".to_string(),
 
+            // Membership operators
+            Self::EqualsAny => "equals_any".to_string(),
+            Self::NotEqualsAny => "not_equals_any".to_string(),
+
+            // Measure operators
+            Self::LengthEquals => "length_equals".to_string(),
+            Self::LengthGreaterThan => "length_greater_than".to_string(),
+            Self::OccurrenceCount => "occurrence_count".to_string(),
+
+            // Version operators
+            Self::VersionEquals => "version_equals".to_string(),
+            Self::VersionNotEqual => "version_not_equal".to_string(),
+            Self::VersionGreaterThan => "version_greater_than".to_string(),
+            Self::VersionLessThan => "version_less_than".to_string(),
+            Self::VersionGreaterOrEqual => "version_greater_or_equal".to_string(),
+            Self::VersionLessOrEqual => "version_less_or_equal".to_string(),
+
+            // Network operators
+            Self::InSubnet => "in_subnet".to_string(),
+            Self::NotInSubnet => "not_in_subnet".to_string(),
+
+            // Glob operators
+            Self::GlobMatch => "glob_match".to_string(),
+            Self::NotGlobMatch => "not_glob_match".to_string(),
+
+            // Permission operators
+            Self::PermissionsAtMost => "permissions_at_most".to_string(),
+            Self::PermissionsAtLeast => "permissions_at_least".to_string(),
+
             // Arithmetic operators
             Self::Plus => "+".to_string(),
             Self::Minus => "-".to_string(),
@@ -334,6 +454,12 @@ impl Token {
             // Other tokens
             Self::Identifier(id) => id.clone(),
             Self::Dot => ".".to_string(),
+            Self::At => "@".to_string(),
+            Self::LeftBracket => "[".to_string(),
+            Self::RightBracket => "]".to_string(),
+            Self::Comma => ",".to_string(),
+            Self::LeftParen => "(".to_string(),
+            Self::RightParen => ")".to_string(),
             Self::Space => " ".to_string(),
             Self::Tab => "\t".to_string(),
             Self::Newline => "\n".to_string(),
@@ -398,6 +524,23 @@ impl Token {
             | Self::Matches
             | Self::SubsetOf
             | Self::SupersetOf
+            | Self::EqualsAny
+            | Self::NotEqualsAny
+            | Self::LengthEquals
+            | Self::LengthGreaterThan
+            | Self::OccurrenceCount
+            | Self::VersionEquals
+            | Self::VersionNotEqual
+            | Self::VersionGreaterThan
+            | Self::VersionLessThan
+            | Self::VersionGreaterOrEqual
+            | Self::VersionLessOrEqual
+            | Self::InSubnet
+            | Self::NotInSubnet
+            | Self::GlobMatch
+            | Self::NotGlobMatch
+            | Self::PermissionsAtMost
+            | Self::PermissionsAtLeast
             | Self::Plus
             | Self::Minus
             | Self::Multiply
@@ -409,7 +552,13 @@ impl Token {
             }
 
             Self::Identifier(_) => TokenClass::Identifier,
-            Self::Dot => TokenClass::Punctuation,
+            Self::Dot
+            | Self::At
+            | Self::LeftBracket
+            | Self::RightBracket
+            | Self::Comma
+            | Self::LeftParen
+            | Self::RightParen => TokenClass::Punctuation,
             Self::Space | Self::Tab | Self::Newline => TokenClass::Whitespace,
             Self::Comment(_) | Self::Eof => TokenClass::Special,
         }
@@ -450,6 +599,9 @@ pub fn is_data_type_identifier(word: &str) -> bool {
             | "record_data"
             | "version"
             | "evr_string"
+            | "ip_address"
+            | "octal"
+            | "datetime"
     )
 }
 
@@ -482,6 +634,35 @@ pub fn classify_operator_word(word: &str) -> Option<Token> {
         "subset_of" => Some(Token::SubsetOf),
         "superset_of" => Some(Token::SupersetOf),
 
+        // Membership operators
+        "equals_any" => Some(Token::EqualsAny),
+        "not_equals_any" => Some(Token::NotEqualsAny),
+
+        // Measure operators
+        "length_equals" => Some(Token::LengthEquals),
+        "length_greater_than" => Some(Token::LengthGreaterThan),
+        "occurrence_count" => Some(Token::OccurrenceCount),
+
+        // Version operators
+        "version_equals" => Some(Token::VersionEquals),
+        "version_not_equal" => Some(Token::VersionNotEqual),
+        "version_greater_than" => Some(Token::VersionGreaterThan),
+        "version_less_than" => Some(Token::VersionLessThan),
+        "version_greater_or_equal" => Some(Token::VersionGreaterOrEqual),
+        "version_less_or_equal" => Some(Token::VersionLessOrEqual),
+
+        // Network operators
+        "in_subnet" => Some(Token::InSubnet),
+        "not_in_subnet" => Some(Token::NotInSubnet),
+
+        // Glob operators
+        "glob_match" => Some(Token::GlobMatch),
+        "not_glob_match" => Some(Token::NotGlobMatch),
+
+        // Permission operators
+        "permissions_at_most" => Some(Token::PermissionsAtMost),
+        "permissions_at_least" => Some(Token::PermissionsAtLeast),
+
         _ => None,
     }
 }
@@ -5,7 +5,7 @@
 
 use crate::{
     tokens::token::*,
-    utils::{Position, SourceMap, Span, Spanned},
+    tokens::{Position, SourceMap, Span, Spanned},
 };
 
 /// A token with span information
@@ -1,8 +1,8 @@
 //! ESP Utils - Shared types and utilities for ESP lexer and parser
 //!
-//! This crate provides dependency-free, shared primitive types, enums, identifiers,
-//! and helper utilities used by both the lexer and AST/parser for the ESP language.
+//! The span types now live in [`crate::tokens`]; they are re-exported here so
+//! existing `utils::Span` paths keep working.
 
-pub mod span;
+pub use crate::tokens::span;
 
-pub use span::{Position, SourceMap, Span, Spanned};
+pub use crate::tokens::{Position, SourceMap, Span, Spanned};
@@ -1,7 +1,7 @@
 //! Error types for Pass 6: Structural Validation with global logging integration
 
 use crate::logging::codes;
-use crate::utils::Span;
+use crate::tokens::Span;
 use thiserror::Error;
 
 /// Result type for structural validation operations
@@ -53,6 +53,22 @@ pub enum StructuralError {
     /// Structural consistency violation
     #[error("Structural consistency violation: {inconsistency} detected at {span}")]
     ConsistencyViolation { inconsistency: String, span: Span },
+
+    /// MET field declared more than once
+    #[error("Duplicate MET field '{name}' at {duplicate_span} (first declared at {first_span})")]
+    DuplicateMetadataField {
+        name: String,
+        first_span: Span,
+        duplicate_span: Span,
+    },
+
+    /// MET field with a malformed name or value
+    #[error("Invalid MET field '{name}': {reason} at {span}")]
+    InvalidMetadataField {
+        name: String,
+        reason: String,
+        span: Span,
+    },
 }
 
 impl StructuralError {
@@ -118,6 +134,24 @@ impl StructuralError {
         }
     }
 
+    /// Create duplicate MET field error
+    pub fn duplicate_metadata_field(name: &str, first_span: Span, duplicate_span: Span) -> Self {
+        Self::DuplicateMetadataField {
+            name: name.to_string(),
+            first_span,
+            duplicate_span,
+        }
+    }
+
+    /// Create invalid MET field error
+    pub fn invalid_metadata_field(name: &str, reason: &str, span: Span) -> Self {
+        Self::InvalidMetadataField {
+            name: name.to_string(),
+            reason: reason.to_string(),
+            span,
+        }
+    }
+
     /// Get error span if available
     pub fn span(&self) -> Option<Span> {
         match self {
@@ -125,7 +159,9 @@ impl StructuralError {
             | Self::BlockOrderingViolation { span, .. }
             | Self::EmptyDefinition { span }
             | Self::EmptyCriteria { span }
-            | Self::ConsistencyViolation { span, .. } => Some(*span),
+            | Self::ConsistencyViolation { span, .. }
+            | Self::InvalidMetadataField { span, .. } => Some(*span),
+            Self::DuplicateMetadataField { duplicate_span, .. } => Some(*duplicate_span),
             Self::ImplementationLimitExceeded { .. }
             | Self::InternalError { .. }
             | Self::ComplexityViolation { .. } => None,
@@ -147,6 +183,9 @@ impl StructuralError {
             Self::InternalError { .. } => codes::system::INTERNAL_ERROR,
             Self::ComplexityViolation { .. } => codes::structural::COMPLEXITY_VIOLATION,
             Self::ConsistencyViolation { .. } => codes::structural::CONSISTENCY_VIOLATION,
+            Self::DuplicateMetadataField { .. } | Self::InvalidMetadataField { .. } => {
+                codes::structural::INVALID_METADATA
+            }
         }
     }
 
@@ -176,6 +215,8 @@ impl StructuralError {
             Self::InternalError { .. } => "InternalError",
             Self::ComplexityViolation { .. } => "ComplexityViolation",
             Self::ConsistencyViolation { .. } => "ConsistencyViolation",
+            Self::DuplicateMetadataField { .. } => "DuplicateMetadataField",
+            Self::InvalidMetadataField { .. } => "InvalidMetadataField",
         }
     }
 
@@ -196,7 +237,9 @@ impl StructuralError {
             Self::EmptyDefinition { .. } => "High",
             Self::MissingRequiredComponent { .. }
             | Self::BlockOrderingViolation { .. }
-            | Self::ConsistencyViolation { .. } => "Medium",
+            | Self::ConsistencyViolation { .. }
+            | Self::DuplicateMetadataField { .. }
+            | Self::InvalidMetadataField { .. } => "Medium",
             Self::EmptyCriteria { .. } | Self::ComplexityViolation { .. } => "Low",
         }
     }
@@ -218,6 +261,10 @@ impl StructuralError {
             Self::InternalError { .. } => "Contact system administrator or file bug report",
             Self::ComplexityViolation { .. } => "Simplify structure to reduce complexity",
             Self::ConsistencyViolation { .. } => "Fix structural inconsistency",
+            Self::DuplicateMetadataField { .. } => "Remove or rename the repeated MET field",
+            Self::InvalidMetadataField { .. } => {
+                "Use an identifier name and a non-empty printable value within the length limit"
+            }
         }
     }
 }
@@ -0,0 +1,169 @@
+//! MET block validation with global logging
+//!
+//! The scanner copies MET fields into its results verbatim, so malformed
+//! metadata is rejected here rather than silently last-wins downstream:
+//! - Field names are unique and relaxed identifiers (`[A-Za-z_][A-Za-z0-9_.-]*`)
+//! - Values are non-empty, free of control characters, and within
+//!   `MAX_METADATA_VALUE_LENGTH`
+
+use super::{error::StructuralError, types::StructuralValidationInput};
+use crate::config::compile_time::structural::MAX_METADATA_VALUE_LENGTH;
+use crate::grammar::ast::nodes::MetadataBlock;
+use crate::log_debug;
+use crate::tokens::Span;
+use std::collections::HashMap;
+
+/// Validate the MET block, if present
+pub fn validate_metadata(input: &StructuralValidationInput) -> Result<(), Vec<StructuralError>> {
+    match &input.ast.metadata {
+        Some(metadata) => validate_metadata_block(metadata, MAX_METADATA_VALUE_LENGTH),
+        None => Ok(()),
+    }
+}
+
+/// Validate MET fields against a value length cap
+pub fn validate_metadata_block(
+    metadata: &MetadataBlock,
+    max_value_length: usize,
+) -> Result<(), Vec<StructuralError>> {
+    let mut errors = Vec::new();
+    let mut first_seen: HashMap<&str, Span> = HashMap::new();
+
+    log_debug!("Validating MET block", "fields" => metadata.fields.len());
+
+    for field in &metadata.fields {
+        let span = field.span.unwrap_or_else(Span::dummy);
+
+        if let Some(first_span) = first_seen.get(field.name.as_str()) {
+            errors.push(StructuralError::duplicate_metadata_field(
+                &field.name,
+                *first_span,
+                span,
+            ));
+        } else {
+            first_seen.insert(&field.name, span);
+        }
+
+        if !is_relaxed_identifier(&field.name) {
+            errors.push(StructuralError::invalid_metadata_field(
+                &field.name,
+                "field name must start with a letter or '_' and contain only letters, digits, '_', '-' or '.'",
+                span,
+            ));
+        }
+
+        if let Some(reason) = value_problem(&field.value, max_value_length) {
+            errors.push(StructuralError::invalid_metadata_field(
+                &field.name,
+                &reason,
+                span,
+            ));
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+fn is_relaxed_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '_' => {}
+        _ => return false,
+    }
+    chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+}
+
+fn value_problem(value: &str, max_value_length: usize) -> Option<String> {
+    if value.trim().is_empty() {
+        return Some("value must not be empty".to_string());
+    }
+    if let Some(c) = value.chars().find(|c| c.is_control()) {
+        return Some(format!("value contains control character {:?}", c));
+    }
+    if value.len() > max_value_length {
+        return Some(format!(
+            "value is {} bytes, maximum allowed is {}",
+            value.len(),
+            max_value_length
+        ));
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grammar::ast::nodes::MetadataField;
+    use crate::tokens::Position;
+
+    fn field(name: &str, value: &str, line: u32) -> MetadataField {
+        let offset = line as usize * 10;
+        let start = Position::new(offset, line, 1);
+        let end = Position::new(offset + 4, line, 5);
+        MetadataField {
+            name: name.to_string(),
+            value: value.to_string(),
+            span: Some(Span::new(start, end)),
+        }
+    }
+
+    fn block(fields: Vec<MetadataField>) -> MetadataBlock {
+        MetadataBlock { fields, span: None }
+    }
+
+    #[test]
+    fn test_valid_metadata_passes() {
+        let metadata = block(vec![
+            field("esp_scan_id", "scan-001", 2),
+            field("control.ref", "AC-2", 3),
+            field("tags", "linux,audit", 4),
+        ]);
+        assert!(validate_metadata_block(&metadata, 64).is_ok());
+    }
+
+    #[test]
+    fn test_duplicate_field_reports_both_occurrences() {
+        let metadata = block(vec![
+            field("control", "AC-2", 2),
+            field("platform", "linux", 3),
+            field("control", "AC-3", 4),
+        ]);
+        let errors = validate_metadata_block(&metadata, 64).unwrap_err();
+
+        assert_eq!(errors.len(), 1);
+        let message = errors[0].to_string();
+        assert!(message.contains("'control'"));
+        assert!(message.contains("at 4:"));
+        assert!(message.contains("first declared at 2:"));
+    }
+
+    #[test]
+    fn test_empty_and_control_character_values() {
+        let metadata = block(vec![
+            field("description", "  ", 2),
+            field("title", "bad\u{7}bell", 3),
+        ]);
+        let errors = validate_metadata_block(&metadata, 64).unwrap_err();
+
+        assert_eq!(errors.len(), 2);
+        assert!(errors[0].to_string().contains("must not be empty"));
+        assert!(errors[1].to_string().contains("control character"));
+    }
+
+    #[test]
+    fn test_length_cap_and_name_shape() {
+        let metadata = block(vec![
+            field("summary", &"x".repeat(65), 2),
+            field("9lives", "ok", 3),
+        ]);
+        let errors = validate_metadata_block(&metadata, 64).unwrap_err();
+
+        assert_eq!(errors.len(), 2);
+        assert!(errors[0].to_string().contains("maximum allowed is 64"));
+        assert!(errors[1].to_string().contains("field name"));
+    }
+}
@@ -5,6 +5,7 @@
 
 pub mod error;
 pub mod limits;
+pub mod metadata;
 pub mod ordering;
 pub mod requirements;
 pub mod types;
@@ -149,6 +150,36 @@ pub fn validate_structure_and_limits(
     metrics.ordering_check_duration_ms = order_start.elapsed().as_secs_f64() * 1000.0;
     metrics.total_checks_performed += 1;
 
+    // Validate MET block fields
+    log_debug!("Validating MET block");
+
+    match metadata::validate_metadata(&input) {
+        Ok(()) => {
+            log_debug!("MET block validation passed");
+        }
+        Err(metadata_errors) => {
+            for metadata_error in &metadata_errors {
+                let error_message = metadata_error.to_string();
+                if let Some(span) = metadata_error.span() {
+                    log_error!(metadata_error.error_code(), &error_message,
+                        span = span,
+                        "error_type" => metadata_error.error_type(),
+                        "severity" => metadata_error.severity()
+                    );
+                } else {
+                    log_error!(metadata_error.error_code(), &error_message,
+                        "error_type" => metadata_error.error_type(),
+                        "severity" => metadata_error.severity()
+                    );
+                }
+            }
+
+            errors.extend(metadata_errors);
+        }
+    }
+
+    metrics.total_checks_performed += 1;
+
     // Check implementation limits
     log_debug!("Checking implementation limits");
     let limits_start = Instant::now();
@@ -305,6 +336,7 @@ pub fn init_structural_validation_logging() -> Result<(), String> {
         codes::structural::COMPLEXITY_VIOLATION,
         codes::structural::CONSISTENCY_VIOLATION,
         codes::structural::MULTIPLE_STRUCTURAL_ERRORS,
+        codes::structural::INVALID_METADATA,
     ];
 
     for code in &test_codes {
@@ -2,7 +2,7 @@
 
 use super::{error::StructuralError, types::StructuralValidationInput};
 use crate::grammar::ast::nodes;
-use crate::utils::Span;
+use crate::tokens::Span;
 use crate::{log_debug, log_error, log_info, log_success};
 use std::time::Instant;
 
@@ -123,6 +123,8 @@ fn validate_ctn_ordering(
     // 3. OBJECT_REF references (optional, multiple allowed)
     // 4. Local STATE blocks (optional, multiple allowed)
     // 5. Local OBJECT block (optional, only one allowed)
+    // 6. CONTROL references (optional, multiple allowed)
+    // 7. SEVERITY declaration (optional, only one allowed)
 
     log_debug!("CTN validation details",
         "state_refs" => ctn.state_refs.len(),
@@ -2,7 +2,7 @@
 
 use super::{error::StructuralError, types::StructuralValidationInput};
 use crate::grammar::ast::nodes;
-use crate::utils::Span;
+use crate::tokens::Span;
 use crate::{log_debug, log_error, log_info, log_success};
 use std::time::Instant;
 
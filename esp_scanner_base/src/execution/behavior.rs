@@ -2,9 +2,14 @@
 //!
 //! Converts raw behavior strings into structured hints for collectors.
 //! Behaviors follow the pattern: flag_name [parameter_value]*
+//!
+//! Hints are untyped. A contract checks them against the behaviors it declares
+//! and turns them into [`BehaviorValues`], which collectors read instead of
+//! looking up raw strings.
 
+use crate::types::common::{DataType, ResolvedValue, Timestamp};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 
 /// Structured behavior hints for collectors
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -24,7 +29,7 @@ impl BehaviorHints {
     /// - Identifiers followed by non-identifier values are parameters
     /// - Boolean values (true/false) are treated as parameter values
     /// - Numeric values are treated as parameter values
-
+    /// - Values that are not identifiers (e.g. paths) are parameter values
     pub fn parse(behavior_values: &[String]) -> Self {
         let mut flags = Vec::new();
         let mut parameters = HashMap::new();
@@ -55,9 +60,12 @@ impl BehaviorHints {
 
     /// Check if value looks like a flag (contains underscore or is a single word)
     fn is_flag_like(value: &str) -> bool {
-        // Flags typically have underscores or are single identifiers
-        value.contains('_')
-            || (value.chars().all(|c| c.is_alphabetic()) && !Self::is_boolean(value))
+        // Flags typically have underscores or are single identifiers; paths
+        // and other string values never are
+        let identifier = value.chars().all(|c| c.is_alphanumeric() || c == '_');
+        identifier
+            && (value.contains('_')
+                || (value.chars().all(|c| c.is_alphabetic()) && !Self::is_boolean(value)))
     }
 
     /// Check if value is a boolean literal
@@ -105,6 +113,104 @@ impl BehaviorHints {
     }
 }
 
+/// Behaviors of one object, checked against its contract
+///
+/// Parameters the object does not set carry the contract's declared default.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BehaviorValues {
+    flags: BTreeSet<String>,
+    values: BTreeMap<String, ResolvedValue>,
+}
+
+impl BehaviorValues {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_flag(mut self, name: &str) -> Self {
+        self.flags.insert(name.to_string());
+        self
+    }
+
+    pub fn with_value(mut self, name: &str, value: ResolvedValue) -> Self {
+        self.values.insert(name.to_string(), value);
+        self
+    }
+
+    /// Whether a flag behavior is set
+    pub fn flag(&self, name: &str) -> bool {
+        self.flags.contains(name)
+    }
+
+    pub fn value(&self, name: &str) -> Option<&ResolvedValue> {
+        self.values.get(name)
+    }
+
+    pub fn int(&self, name: &str) -> Option<i64> {
+        match self.value(name)? {
+            ResolvedValue::Integer(i) => Some(*i),
+            _ => None,
+        }
+    }
+
+    pub fn float(&self, name: &str) -> Option<f64> {
+        match self.value(name)? {
+            ResolvedValue::Float(f) => Some(*f),
+            ResolvedValue::Integer(i) => Some(*i as f64),
+            _ => None,
+        }
+    }
+
+    pub fn boolean(&self, name: &str) -> Option<bool> {
+        match self.value(name)? {
+            ResolvedValue::Boolean(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    pub fn string(&self, name: &str) -> Option<&str> {
+        match self.value(name)? {
+            ResolvedValue::String(s) | ResolvedValue::Version(s) | ResolvedValue::EvrString(s) => {
+                Some(s)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Parse a raw behavior value as the declared type
+pub fn parse_behavior_value(data_type: DataType, raw: &str) -> Result<ResolvedValue, String> {
+    let mismatch = || format!("expected {}, got '{}'", data_type.as_str(), raw);
+    match data_type {
+        DataType::Int => raw
+            .parse()
+            .map(ResolvedValue::Integer)
+            .map_err(|_| mismatch()),
+        DataType::Float => raw
+            .parse()
+            .map(ResolvedValue::Float)
+            .map_err(|_| mismatch()),
+        DataType::Boolean => match raw.to_lowercase().as_str() {
+            "true" => Ok(ResolvedValue::Boolean(true)),
+            "false" => Ok(ResolvedValue::Boolean(false)),
+            _ => Err(mismatch()),
+        },
+        DataType::Version => Ok(ResolvedValue::Version(raw.to_string())),
+        DataType::EvrString => Ok(ResolvedValue::EvrString(raw.to_string())),
+        DataType::Octal => i64::from_str_radix(raw, 8)
+            .map(ResolvedValue::Integer)
+            .map_err(|_| mismatch()),
+        DataType::DateTime => match raw.parse() {
+            Ok(epoch) => Ok(ResolvedValue::Integer(epoch)),
+            Err(_) => match Timestamp::parse(raw) {
+                Ok(Timestamp::Absolute(epoch)) => Ok(ResolvedValue::Integer(epoch)),
+                _ => Err(mismatch()),
+            },
+        },
+        _ => Ok(ResolvedValue::String(raw.to_string())),
+    }
+}
+
 /// Extract behavior hints from ExecutableObject
 pub fn extract_behavior_hints(
     object: &crate::types::execution_context::ExecutableObject,
@@ -209,6 +315,21 @@ mod tests {
         assert_eq!(hints.get_parameter_as_int("max_depth"), Some(10));
     }
 
+    #[test]
+    fn test_parse_path_parameter() {
+        let hints = BehaviorHints::parse(&[
+            "archive_member".to_string(),
+            "etc/ssh/sshd_config".to_string(),
+            "include_hidden".to_string(),
+        ]);
+
+        assert_eq!(
+            hints.get_parameter("archive_member"),
+            Some("etc/ssh/sshd_config")
+        );
+        assert!(hints.has_flag("include_hidden"));
+    }
+
     #[test]
     fn test_parameter_type_conversion() {
         let hints = BehaviorHints::parse(&[
@@ -263,4 +384,89 @@ mod tests {
         assert_eq!(hints1.get_parameter("max_depth"), Some("5"));
         assert_eq!(hints1.get_parameter("timeout"), Some("30"));
     }
+
+    fn contract_with_behaviors() -> crate::strategies::CtnContract {
+        use crate::strategies::{BehaviorParameter, BehaviorType, SupportedBehavior};
+
+        let mut contract = crate::strategies::CtnContract::new("file_content".to_string());
+        contract.add_supported_behavior(SupportedBehavior {
+            name: "recursive_scan".to_string(),
+            behavior_type: BehaviorType::Flag,
+            parameters: vec![BehaviorParameter {
+                name: "max_depth".to_string(),
+                data_type: DataType::Int,
+                required: false,
+                default_value: Some("3".to_string()),
+                description: String::new(),
+            }],
+            description: String::new(),
+            example: String::new(),
+        });
+        contract.add_supported_behavior(SupportedBehavior {
+            name: "include_hidden".to_string(),
+            behavior_type: BehaviorType::Flag,
+            parameters: vec![],
+            description: String::new(),
+            example: String::new(),
+        });
+        contract
+    }
+
+    fn hints(values: &[&str]) -> BehaviorHints {
+        BehaviorHints::parse(&values.iter().map(|v| v.to_string()).collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn test_contract_types_behaviors_and_fills_defaults() {
+        let contract = contract_with_behaviors();
+
+        let behaviors = contract
+            .resolve_behaviors(&hints(&["recursive_scan", "include_hidden"]))
+            .unwrap();
+        assert!(behaviors.flag("recursive_scan"));
+        assert!(behaviors.flag("include_hidden"));
+        assert_eq!(behaviors.int("max_depth"), Some(3));
+
+        let behaviors = contract
+            .resolve_behaviors(&hints(&["recursive_scan", "max_depth", "7"]))
+            .unwrap();
+        assert_eq!(behaviors.int("max_depth"), Some(7));
+        assert!(!behaviors.flag("include_hidden"));
+        assert_eq!(behaviors.string("max_depth"), None);
+    }
+
+    #[test]
+    fn test_contract_rejects_unknown_and_mistyped_behaviors() {
+        let contract = contract_with_behaviors();
+
+        let err = contract
+            .resolve_behaviors(&hints(&[
+                "recursive_scna",
+                "max_depth",
+                "1.5",
+                "include_hidden",
+                "2",
+            ]))
+            .unwrap_err();
+        let crate::strategies::CtnContractError::InvalidBehaviors { problems, .. } = err else {
+            panic!("expected invalid behaviors, got {:?}", err);
+        };
+        assert_eq!(
+            problems,
+            vec![
+                "unknown behavior 'recursive_scna' (did you mean 'recursive_scan'?)",
+                "behavior 'include_hidden' takes no value (got '2')",
+                "behavior 'max_depth': expected int, got '1.5'",
+            ]
+        );
+
+        let err = contract
+            .validate_behavior_hints(&hints(&["max_depth", "follow_links"]))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("behavior 'max_depth' needs a value of type int"));
+        assert!(err.contains(
+            "unknown behavior 'follow_links' (supported: include_hidden, max_depth, recursive_scan)"
+        ));
+    }
 }
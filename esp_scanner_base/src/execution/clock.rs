@@ -0,0 +1,141 @@
+//! # Scan Clock
+//!
+//! Single source of time for a scan. The instant the clock is created is the
+//! scan's "now": every relative comparison (file age, certificate expiry,
+//! waiver expiry) is made against it, so results are consistent within one
+//! scan no matter how long the scan runs. A fixed clock makes scans
+//! deterministic for tests and reproducible reruns.
+//!
+//! All values are UTC; nothing here depends on the host locale or timezone.
+
+use chrono::{DateTime, Duration, Utc};
+use std::time::SystemTime;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScanClock {
+    now: DateTime<Utc>,
+    fixed: bool,
+}
+
+impl ScanClock {
+    /// Real clock; "now" is the moment of creation
+    pub fn system() -> Self {
+        Self {
+            now: Utc::now(),
+            fixed: false,
+        }
+    }
+
+    /// Frozen clock for deterministic scans and tests
+    pub fn fixed(now: DateTime<Utc>) -> Self {
+        Self { now, fixed: true }
+    }
+
+    /// Parse an RFC 3339 timestamp into a fixed clock
+    pub fn parse_fixed(value: &str) -> Result<Self, String> {
+        DateTime::parse_from_rfc3339(value)
+            .map(|t| Self::fixed(t.with_timezone(&Utc)))
+            .map_err(|e| format!("Invalid RFC 3339 timestamp '{}': {}", value, e))
+    }
+
+    pub fn is_fixed(&self) -> bool {
+        self.fixed
+    }
+
+    /// The scan's reference instant for all relative comparisons
+    pub fn now(&self) -> DateTime<Utc> {
+        self.now
+    }
+
+    /// Current wall time for elapsed-time bookkeeping (scan end)
+    ///
+    /// A fixed clock never advances.
+    pub fn wall_time(&self) -> DateTime<Utc> {
+        if self.fixed {
+            self.now
+        } else {
+            Utc::now()
+        }
+    }
+
+    /// How long before the scan instant `at` happened (negative if in the future)
+    pub fn age_of(&self, at: DateTime<Utc>) -> Duration {
+        self.now - at
+    }
+
+    /// [`age_of`](Self::age_of) for filesystem timestamps such as mtime
+    pub fn age_of_system_time(&self, at: SystemTime) -> Duration {
+        self.age_of(DateTime::<Utc>::from(at))
+    }
+
+    /// Time remaining from the scan instant until `at` (negative once passed)
+    pub fn until(&self, at: DateTime<Utc>) -> Duration {
+        at - self.now
+    }
+
+    /// Whether `at` is at or before the scan instant
+    pub fn is_expired(&self, at: DateTime<Utc>) -> bool {
+        at <= self.now
+    }
+
+    /// Whether `at` falls within `window` of the scan instant (or has passed)
+    pub fn expires_within(&self, at: DateTime<Utc>, window: Duration) -> bool {
+        self.until(at) <= window
+    }
+}
+
+impl Default for ScanClock {
+    fn default() -> Self {
+        Self::system()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(value)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_fixed_clock_does_not_advance() {
+        let clock = ScanClock::parse_fixed("2026-03-01T12:00:00Z").unwrap();
+        assert!(clock.is_fixed());
+        assert_eq!(clock.now(), at("2026-03-01T12:00:00Z"));
+        assert_eq!(clock.wall_time(), clock.now());
+    }
+
+    #[test]
+    fn test_certificate_expiring_tomorrow_flips_with_clock() {
+        let not_after = at("2026-03-02T12:00:00Z");
+        let thirty_days = Duration::days(30);
+
+        let today = ScanClock::fixed(at("2026-03-01T12:00:00Z"));
+        assert!(!today.is_expired(not_after));
+        assert!(today.expires_within(not_after, thirty_days));
+        assert_eq!(today.until(not_after), Duration::days(1));
+
+        let two_days_later = ScanClock::fixed(at("2026-03-03T12:00:00Z"));
+        assert!(two_days_later.is_expired(not_after));
+
+        let two_months_earlier = ScanClock::fixed(at("2026-01-01T12:00:00Z"));
+        assert!(!two_months_earlier.expires_within(not_after, thirty_days));
+    }
+
+    #[test]
+    fn test_age_of_system_time() {
+        let clock = ScanClock::fixed(at("1970-01-02T00:00:00Z"));
+        let mtime = SystemTime::UNIX_EPOCH;
+        assert_eq!(clock.age_of_system_time(mtime), Duration::days(1));
+    }
+
+    #[test]
+    fn test_offsets_normalize_to_utc() {
+        let clock = ScanClock::parse_fixed("2026-03-01T07:00:00-05:00").unwrap();
+        assert_eq!(clock.now(), at("2026-03-01T12:00:00Z"));
+        assert!(ScanClock::parse_fixed("yesterday").is_err());
+    }
+}
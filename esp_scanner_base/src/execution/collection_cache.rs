@@ -0,0 +1,285 @@
+//! # Collection Cache
+//!
+//! Criteria of one policy often collect the same thing: several checks on
+//! `/etc/ssh/sshd_config`, or every package check running `rpm -q` for the
+//! same package. The engine keeps what each collection produced for the rest
+//! of the scan, keyed by collector, criterion type and the object's
+//! normalized elements. The elements carry everything a collector turns into
+//! a path or command arguments (paths, parameters, behaviors), so two
+//! objects share an entry only when they would run the same collection; the
+//! object identifier is not part of the key.
+//!
+//! Only successful collections are kept. Collectors whose data is volatile
+//! opt out through [`CtnDataCollector::supports_result_caching`]. The cache
+//! is bounded by entry count and by an estimate of the bytes held; the
+//! oldest entries are evicted first.
+//!
+//! [`CtnDataCollector::supports_result_caching`]: crate::strategies::CtnDataCollector::supports_result_caching
+
+use crate::results::CollectionCacheStats;
+use crate::strategies::CollectedData;
+use crate::types::common::ResolvedValue;
+use crate::types::execution_context::ExecutableObject;
+use std::collections::{HashMap, VecDeque};
+
+/// Entries kept when no limit is configured
+pub const DEFAULT_CACHE_ENTRIES: usize = 4096;
+
+/// Estimated bytes kept when no limit is configured
+pub const DEFAULT_CACHE_BYTES: usize = 64 * 1024 * 1024;
+
+/// Identifies one collection: collector, criterion type and object spec
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CacheKey {
+    collector_id: String,
+    ctn_type: String,
+    spec: String,
+}
+
+impl CacheKey {
+    /// Key for collecting `object` for `ctn_type` with `collector_id`
+    ///
+    /// Elements are serialized with sorted map keys and then sorted, so
+    /// element order and the object identifier do not matter.
+    pub fn new(collector_id: &str, ctn_type: &str, object: &ExecutableObject) -> Self {
+        let mut elements: Vec<String> = object
+            .elements
+            .iter()
+            .map(|element| {
+                serde_json::to_value(element)
+                    .map(|value| value.to_string())
+                    .unwrap_or_else(|_| format!("{:?}", element))
+            })
+            .collect();
+        elements.sort();
+        Self {
+            collector_id: collector_id.to_string(),
+            ctn_type: ctn_type.to_string(),
+            spec: elements.join("\n"),
+        }
+    }
+}
+
+struct CacheEntry {
+    data: CollectedData,
+    bytes: usize,
+}
+
+/// Bounded per-scan store of collected data
+pub struct CollectionCache {
+    entries: HashMap<CacheKey, CacheEntry>,
+    /// Keys in insertion order, for eviction
+    order: VecDeque<CacheKey>,
+    max_entries: usize,
+    max_bytes: usize,
+    bytes: usize,
+    enabled: bool,
+    stats: CollectionCacheStats,
+}
+
+impl Default for CollectionCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_CACHE_ENTRIES, DEFAULT_CACHE_BYTES)
+    }
+}
+
+impl CollectionCache {
+    /// Cache holding at most `max_entries` collections and about
+    /// `max_bytes` of collected data
+    pub fn new(max_entries: usize, max_bytes: usize) -> Self {
+        Self {
+            entries: HashMap::new(),
+            order: VecDeque::new(),
+            max_entries,
+            max_bytes,
+            bytes: 0,
+            enabled: max_entries > 0 && max_bytes > 0,
+            stats: CollectionCacheStats::default(),
+        }
+    }
+
+    /// Cache that never stores anything
+    pub fn disabled() -> Self {
+        Self::new(0, 0)
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Drop every entry and stop caching, e.g. when memory runs short
+    pub fn disable(&mut self) {
+        self.enabled = false;
+        self.entries.clear();
+        self.order.clear();
+        self.bytes = 0;
+    }
+
+    /// Data collected earlier under `cache_id`, relabeled for `object_id`
+    ///
+    /// Counts a hit or a miss while the cache is enabled.
+    pub fn get(&mut self, cache_id: &CacheKey, object_id: &str) -> Option<CollectedData> {
+        if !self.enabled {
+            return None;
+        }
+        match self.entries.get(cache_id) {
+            Some(entry) => {
+                self.stats.hits += 1;
+                let mut data = entry.data.clone();
+                data.object_id = object_id.to_string();
+                Some(data)
+            }
+            None => {
+                self.stats.misses += 1;
+                None
+            }
+        }
+    }
+
+    /// Keep `data` under `cache_id`, evicting the oldest entries to stay
+    /// within bounds; data larger than the whole budget is not kept
+    pub fn insert(&mut self, cache_id: CacheKey, data: &CollectedData) {
+        if !self.enabled || self.entries.contains_key(&cache_id) {
+            return;
+        }
+        let bytes = estimated_size(data);
+        if bytes > self.max_bytes {
+            return;
+        }
+        while self.entries.len() >= self.max_entries || self.bytes + bytes > self.max_bytes {
+            let Some(oldest) = self.order.pop_front() else {
+                break;
+            };
+            if let Some(evicted) = self.entries.remove(&oldest) {
+                self.bytes -= evicted.bytes;
+                self.stats.evictions += 1;
+            }
+        }
+        self.bytes += bytes;
+        self.order.push_back(cache_id.clone());
+        self.entries.insert(
+            cache_id,
+            CacheEntry {
+                data: data.clone(),
+                bytes,
+            },
+        );
+    }
+
+    /// Hit, miss and eviction counts, with the current size
+    pub fn stats(&self) -> CollectionCacheStats {
+        CollectionCacheStats {
+            entries: self.entries.len() as u64,
+            bytes: self.bytes as u64,
+            ..self.stats.clone()
+        }
+    }
+}
+
+/// Rough heap size of collected data: field names and values
+fn estimated_size(data: &CollectedData) -> usize {
+    data.object_id.len()
+        + data
+            .fields
+            .iter()
+            .map(|(name, value)| name.len() + value_size(value))
+            .sum::<usize>()
+        + data
+            .metadata
+            .warnings
+            .iter()
+            .map(String::len)
+            .sum::<usize>()
+}
+
+fn value_size(value: &ResolvedValue) -> usize {
+    match value {
+        ResolvedValue::String(s) | ResolvedValue::Version(s) | ResolvedValue::EvrString(s) => {
+            s.len()
+        }
+        ResolvedValue::Collection(items) => items.iter().map(value_size).sum(),
+        ResolvedValue::RecordData(record) => record.data.to_string().len(),
+        ResolvedValue::Binary(bytes) => bytes.len(),
+        ResolvedValue::Integer(_) | ResolvedValue::Float(_) | ResolvedValue::Boolean(_) => 8,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::execution_context::ExecutableObjectElement;
+
+    fn object(id: &str, path: &str) -> ExecutableObject {
+        ExecutableObject {
+            identifier: id.to_string(),
+            elements: vec![
+                ExecutableObjectElement::Field {
+                    name: "path".to_string(),
+                    value: ResolvedValue::String(path.to_string()),
+                },
+                ExecutableObjectElement::Behavior {
+                    values: vec!["recursive".to_string()],
+                },
+            ],
+            is_global: false,
+        }
+    }
+
+    fn data(id: &str, content: &str) -> CollectedData {
+        let mut data = CollectedData::new(
+            id.to_string(),
+            "file_content".to_string(),
+            "test_collector".to_string(),
+        );
+        data.add_field(
+            "content".to_string(),
+            ResolvedValue::String(content.to_string()),
+        );
+        data
+    }
+
+    #[test]
+    fn test_identical_specs_share_an_entry() {
+        let mut cache = CollectionCache::default();
+        let first = object("sshd_a", "/etc/ssh/sshd_config");
+        let mut second = object("sshd_b", "/etc/ssh/sshd_config");
+        second.elements.reverse();
+
+        let key = CacheKey::new("test_collector", "file_content", &first);
+        assert!(cache.get(&key, "sshd_a").is_none());
+        cache.insert(key, &data("sshd_a", "PermitRootLogin no"));
+
+        let key = CacheKey::new("test_collector", "file_content", &second);
+        let hit = cache.get(&key, "sshd_b").unwrap();
+        assert_eq!(hit.object_id, "sshd_b");
+
+        let other = object("hosts", "/etc/hosts");
+        assert_ne!(key, CacheKey::new("test_collector", "file_content", &other));
+        assert_ne!(
+            key,
+            CacheKey::new("test_collector", "file_metadata", &second)
+        );
+
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses, stats.entries), (1, 1, 1));
+    }
+
+    #[test]
+    fn test_bounds_evict_oldest_entries() {
+        let mut cache = CollectionCache::new(2, 1024);
+        for path in ["/a", "/b", "/c"] {
+            let key = CacheKey::new("test_collector", "file_content", &object(path, path));
+            cache.insert(key, &data(path, "x"));
+        }
+        let oldest = CacheKey::new("test_collector", "file_content", &object("/a", "/a"));
+        assert!(cache.get(&oldest, "/a").is_none());
+        assert_eq!(cache.stats().evictions, 1);
+        assert_eq!(cache.stats().entries, 2);
+
+        let mut small = CollectionCache::new(16, 64);
+        let key = CacheKey::new("test_collector", "file_content", &object("/big", "/big"));
+        small.insert(key.clone(), &data("/big", &"x".repeat(128)));
+        assert!(small.get(&key, "/big").is_none());
+        assert_eq!(small.stats().bytes, 0);
+    }
+}
@@ -1,8 +1,11 @@
 //! # Binary and EVR String Comparison Operations
 //!
 //! Implements specialized comparison logic for Binary and EVR (Epoch-Version-Release) data types.
+//! Versions, EVR strings and the `version_*` operations all order through [`evr::rpmvercmp`].
 
+use crate::execution::helpers::evaluate_list_entity_check;
 use crate::types::common::{Operation, ResolvedValue};
+use crate::types::EntityCheck;
 use std::cmp::Ordering;
 
 /// Error types for comparison operations
@@ -14,6 +17,12 @@ pub enum ComparisonError {
     #[error("Invalid binary data: {0}")]
     InvalidBinaryData(String),
 
+    #[error("Invalid network address: {0}")]
+    InvalidNetwork(String),
+
+    #[error("Invalid file mode: {0}")]
+    InvalidMode(String),
+
     #[error("Invalid pattern '{pattern}': {reason}")]
     InvalidPattern { pattern: String, reason: String },
 
@@ -42,13 +51,9 @@ pub mod string {
             Operation::Equals => Ok(actual == expected),
             Operation::NotEqual => Ok(actual != expected),
 
-            // Case-insensitive comparisons (NEW)
-            Operation::CaseInsensitiveEquals => {
-                Ok(actual.to_lowercase() == expected.to_lowercase())
-            }
-            Operation::CaseInsensitiveNotEqual => {
-                Ok(actual.to_lowercase() != expected.to_lowercase())
-            }
+            // Case-insensitive comparisons
+            Operation::CaseInsensitiveEquals => Ok(fold_case(actual) == fold_case(expected)),
+            Operation::CaseInsensitiveNotEqual => Ok(fold_case(actual) != fold_case(expected)),
 
             // Contains operations
             Operation::Contains => Ok(actual.contains(expected)),
@@ -71,6 +76,10 @@ pub mod string {
                 }),
             },
 
+            // Shell-style wildcards over the whole value
+            Operation::GlobMatch => glob_matches(expected, actual),
+            Operation::NotGlobMatch => glob_matches(expected, actual).map(|m| !m),
+
             // Ordering operations (for string comparison)
             Operation::GreaterThan => Ok(actual > expected),
             Operation::LessThan => Ok(actual < expected),
@@ -85,6 +94,172 @@ pub mod string {
         }
     }
 
+    /// Unicode case folding: upper-casing first maps `ß` to `SS` and final
+    /// `ς` to `Σ`, so after lower-casing `Straße` and `STRASSE` compare equal
+    pub fn fold_case(s: &str) -> String {
+        s.chars()
+            .flat_map(char::to_uppercase)
+            .flat_map(char::to_lowercase)
+            .collect()
+    }
+
+    /// One element of a compiled glob pattern
+    enum GlobToken {
+        Literal(char),
+        AnyChar,
+        AnySequence,
+        Class {
+            negated: bool,
+            ranges: Vec<(char, char)>,
+        },
+    }
+
+    fn parse_glob(pattern: &str) -> Result<Vec<GlobToken>, ComparisonError> {
+        let chars: Vec<char> = pattern.chars().collect();
+        let mut tokens = Vec::new();
+        let mut i = 0;
+        while i < chars.len() {
+            match chars[i] {
+                '*' => tokens.push(GlobToken::AnySequence),
+                '?' => tokens.push(GlobToken::AnyChar),
+                '\\' if i + 1 < chars.len() => {
+                    i += 1;
+                    tokens.push(GlobToken::Literal(chars[i]));
+                }
+                '[' => {
+                    let mut j = i + 1;
+                    let negated = matches!(chars.get(j), Some('!') | Some('^'));
+                    if negated {
+                        j += 1;
+                    }
+                    let mut ranges = Vec::new();
+                    // A `]` right after the opening bracket is a member
+                    let mut first = true;
+                    loop {
+                        match chars.get(j) {
+                            None => {
+                                return Err(ComparisonError::InvalidPattern {
+                                    pattern: pattern.to_string(),
+                                    reason: "unterminated `[` character class".to_string(),
+                                })
+                            }
+                            Some(']') if !first => break,
+                            Some(&start) => {
+                                if chars.get(j + 1) == Some(&'-')
+                                    && chars.get(j + 2).is_some_and(|&end| end != ']')
+                                {
+                                    ranges.push((start, chars[j + 2]));
+                                    j += 3;
+                                } else {
+                                    ranges.push((start, start));
+                                    j += 1;
+                                }
+                            }
+                        }
+                        first = false;
+                    }
+                    tokens.push(GlobToken::Class { negated, ranges });
+                    i = j;
+                }
+                c => tokens.push(GlobToken::Literal(c)),
+            }
+            i += 1;
+        }
+        Ok(tokens)
+    }
+
+    fn token_matches(part: &GlobToken, c: char) -> bool {
+        match part {
+            GlobToken::Literal(literal) => *literal == c,
+            GlobToken::AnyChar => true,
+            GlobToken::AnySequence => false,
+            GlobToken::Class { negated, ranges } => {
+                ranges.iter().any(|&(lo, hi)| lo <= c && c <= hi) != *negated
+            }
+        }
+    }
+
+    /// Match `text` against a shell-style glob: `*` (any run, including
+    /// `/`), `?` (one character), `[abc]`, `[a-z]`, `[!x]` / `[^x]`, and `\`
+    /// to escape the next character. The whole value must match.
+    pub fn glob_matches(pattern: &str, text: &str) -> Result<bool, ComparisonError> {
+        let tokens = parse_glob(pattern)?;
+        let text: Vec<char> = text.chars().collect();
+
+        // Greedy scan that backtracks only to the most recent `*`, which
+        // keeps matching linear in practice rather than exponential
+        let (mut p, mut t) = (0, 0);
+        let mut backtrack: Option<(usize, usize)> = None;
+        while t < text.len() {
+            match tokens.get(p) {
+                Some(GlobToken::AnySequence) => {
+                    backtrack = Some((p, t));
+                    p += 1;
+                }
+                Some(part) if token_matches(part, text[t]) => {
+                    p += 1;
+                    t += 1;
+                }
+                _ => match backtrack {
+                    Some((star, consumed)) => {
+                        p = star + 1;
+                        t = consumed + 1;
+                        backtrack = Some((star, consumed + 1));
+                    }
+                    None => return Ok(false),
+                },
+            }
+        }
+        Ok(tokens[p..]
+            .iter()
+            .all(|part| matches!(part, GlobToken::AnySequence)))
+    }
+
+    /// Compare a string's length in characters (not bytes) with an integer
+    pub fn compare_length(
+        actual: &str,
+        expected: i64,
+        operation: Operation,
+    ) -> Result<bool, ComparisonError> {
+        let length = actual.chars().count() as i64;
+        match operation {
+            Operation::LengthEquals => Ok(length == expected),
+            Operation::LengthGreaterThan => Ok(length > expected),
+            _ => Err(ComparisonError::UnsupportedOperation {
+                operation,
+                data_type: "string length".to_string(),
+            }),
+        }
+    }
+
+    /// Count occurrences of a literal pattern, scanning left to right and
+    /// resuming after each match, so occurrences never overlap: `"aaaa"`
+    /// holds `"aa"` twice, not three times. An empty pattern counts zero.
+    pub fn count_occurrences(actual: &str, pattern: &str) -> usize {
+        if pattern.is_empty() {
+            return 0;
+        }
+        actual.matches(pattern).count()
+    }
+
+    /// `occurrence_count` against a policy `[pattern, count]` list
+    pub fn compare_occurrences(
+        actual: &str,
+        spec: &[ResolvedValue],
+    ) -> Result<bool, ComparisonError> {
+        match spec {
+            [ResolvedValue::String(pattern), ResolvedValue::Integer(count)] => {
+                Ok(count_occurrences(actual, pattern) as i64 == *count)
+            }
+            _ => Err(ComparisonError::TypeMismatch {
+                message: format!(
+                    "occurrence_count expects [pattern, count], found {:?}",
+                    spec
+                ),
+            }),
+        }
+    }
+
     #[cfg(test)]
     mod tests {
         use super::*;
@@ -102,6 +277,101 @@ pub mod string {
             assert!(!compare("Hello", "hello", Operation::CaseInsensitiveNotEqual).unwrap());
         }
 
+        #[test]
+        fn test_case_insensitive_folds_unicode() {
+            assert!(compare("Straße", "STRASSE", Operation::CaseInsensitiveEquals).unwrap());
+            // Medial and final sigma both fold to σ
+            assert!(compare("ΟΔΟΣ", "οδοσ", Operation::CaseInsensitiveEquals).unwrap());
+            assert!(compare("ΟΔΟΣ", "οδο\u{3c2}", Operation::CaseInsensitiveEquals).unwrap());
+            assert!(compare("Ünïcode", "üNÏCODE", Operation::CaseInsensitiveEquals).unwrap());
+            assert!(compare("Straße", "Strase", Operation::CaseInsensitiveNotEqual).unwrap());
+        }
+
+        #[test]
+        fn test_glob_matches_table() {
+            let cases = [
+                ("*.conf", "sshd.conf", true),
+                ("*.conf", "sshd.conf.bak", false),
+                ("*", "", true),
+                ("?", "", false),
+                ("?", "é", true),
+                ("a*b*c", "axxbyyc", true),
+                ("a*b*c", "axxbyy", false),
+                ("/etc/*/config", "/etc/ssh/d/config", true),
+                ("log[0-9]", "log7", true),
+                ("log[0-9]", "logx", false),
+                ("log[!0-9]", "logx", true),
+                ("log[^0-9]", "log7", false),
+                ("[abc]??", "bxy", true),
+                ("[]x]", "]", true),
+                ("[a-]", "-", true),
+                ("\\*literal", "*literal", true),
+                ("\\*literal", "xliteral", false),
+                ("no_wildcards", "no_wildcards", true),
+                ("no_wildcards", "No_wildcards", false),
+            ];
+            for (pattern, text, expected) in cases {
+                assert_eq!(
+                    glob_matches(pattern, text).unwrap(),
+                    expected,
+                    "{} ~ {}",
+                    pattern,
+                    text
+                );
+            }
+
+            // Backtracking stays cheap on patterns that would blow up a
+            // naive recursive matcher
+            let text = "a".repeat(64);
+            assert!(!glob_matches("*a*a*a*a*a*a*a*a*b", &text).unwrap());
+
+            assert!(compare("PermitRootLogin", "permit*", Operation::GlobMatch).is_ok());
+            assert!(compare("sshd.conf", "*.conf", Operation::GlobMatch).unwrap());
+            assert!(compare("sshd.conf", "*.bak", Operation::NotGlobMatch).unwrap());
+            assert!(matches!(
+                compare("x", "[abc", Operation::GlobMatch),
+                Err(ComparisonError::InvalidPattern { .. })
+            ));
+        }
+
+        #[test]
+        fn test_starts_and_ends_with_empty_strings() {
+            assert!(compare("Defaults env_reset", "Defaults", Operation::StartsWith).unwrap());
+            assert!(compare("PermitRootLogin no", "no", Operation::EndsWith).unwrap());
+            // Every string starts and ends with the empty string
+            assert!(compare("", "", Operation::StartsWith).unwrap());
+            assert!(compare("abc", "", Operation::EndsWith).unwrap());
+            assert!(!compare("", "a", Operation::StartsWith).unwrap());
+        }
+
+        #[test]
+        fn test_compare_length() {
+            assert!(compare_length("", 0, Operation::LengthEquals).unwrap());
+            assert!(!compare_length("", 0, Operation::LengthGreaterThan).unwrap());
+            assert!(compare_length("banner", 0, Operation::LengthGreaterThan).unwrap());
+            assert!(compare_length("banner", 6, Operation::LengthEquals).unwrap());
+            // Characters, not bytes
+            assert!(compare_length("héllo", 5, Operation::LengthEquals).unwrap());
+            assert!(compare_length("x", 1, Operation::Equals).is_err());
+        }
+
+        #[test]
+        fn test_count_occurrences_does_not_overlap() {
+            assert_eq!(count_occurrences("aaaa", "aa"), 2);
+            assert_eq!(count_occurrences("aaa", "aa"), 1);
+            assert_eq!(count_occurrences("", "a"), 0);
+            assert_eq!(count_occurrences("abc", ""), 0);
+
+            let config = "PermitRootLogin no\n#PermitRootLogin yes\n";
+            let spec = [
+                ResolvedValue::String("PermitRootLogin".to_string()),
+                ResolvedValue::Integer(2),
+            ];
+            assert!(compare_occurrences(config, &spec).unwrap());
+            assert!(!compare_occurrences("", &spec).unwrap());
+            assert!(compare_occurrences(config, &spec[..1]).is_err());
+        }
+
         #[test]
         fn test_not_starts_with() {
             assert!(compare("Hello World", "Goodbye", Operation::NotStartsWith).unwrap());
@@ -380,6 +650,40 @@ pub mod collection {
         actual_set == expected_set
     }
 
+    /// One-of matching for `equals_any` / `not_equals_any`
+    ///
+    /// `actual` is a single collected value; `allowed` is the policy's value list.
+    /// Elements are compared with `Equals`, so a type mismatch is an error rather
+    /// than a silent non-match.
+    pub fn matches_any(
+        actual: &ResolvedValue,
+        allowed: &[ResolvedValue],
+        operation: Operation,
+    ) -> Result<bool, ComparisonError> {
+        let mut found = false;
+        for candidate in allowed {
+            if actual.compare_with(candidate, Operation::Equals)? {
+                found = true;
+                break;
+            }
+        }
+
+        match operation {
+            Operation::EqualsAny => Ok(found),
+            Operation::NotEqualsAny => Ok(!found),
+            _ => Err(ComparisonError::UnsupportedOperation {
+                operation,
+                data_type: "value list".to_string(),
+            }),
+        }
+    }
+
+    /// Render a value list for findings, e.g. `[aes256-gcm, chacha20]`
+    pub fn format_list(values: &[ResolvedValue]) -> String {
+        let items: Vec<String> = values.iter().map(serialize_value).collect();
+        format!("[{}]", items.join(", "))
+    }
+
     /// Serialize ResolvedValue to string for comparison
     /// This allows comparing different types in collections
     fn serialize_value(value: &ResolvedValue) -> String {
@@ -491,6 +795,31 @@ pub mod collection {
             // Empty sets are equal
             assert!(compare(&empty, &empty, Operation::Equals).unwrap());
         }
+
+        #[test]
+        fn test_matches_any() {
+            let allowed = vec![
+                ResolvedValue::String("aes256-gcm".to_string()),
+                ResolvedValue::String("chacha20-poly1305".to_string()),
+            ];
+            let actual = ResolvedValue::String("chacha20-poly1305".to_string());
+            let other = ResolvedValue::String("3des-cbc".to_string());
+
+            assert!(matches_any(&actual, &allowed, Operation::EqualsAny).unwrap());
+            assert!(!matches_any(&other, &allowed, Operation::EqualsAny).unwrap());
+            assert!(matches_any(&other, &allowed, Operation::NotEqualsAny).unwrap());
+
+            // Routed through ComparisonExt when the policy value is a list
+            let expected = ResolvedValue::Collection(allowed.clone());
+            assert!(actual
+                .compare_with(&expected, Operation::EqualsAny)
+                .unwrap());
+            assert!(
+                matches_any(&ResolvedValue::Integer(1), &allowed, Operation::EqualsAny).is_err()
+            );
+
+            assert_eq!(format_list(&allowed), "[aes256-gcm, chacha20-poly1305]");
+        }
     }
 }
 
@@ -523,8 +852,8 @@ pub mod evr {
                 ComparisonError::InvalidEvrFormat(format!("Invalid epoch: {}", epoch_str))
             })?;
 
-            // Split on hyphen for version and release
-            let (version, release) = if let Some(hyphen_pos) = rest.find('-') {
+            // Split on the last hyphen for version and release
+            let (version, release) = if let Some(hyphen_pos) = rest.rfind('-') {
                 let (v, r) = rest.split_at(hyphen_pos);
                 (v.to_string(), r[1..].to_string()) // Skip the hyphen
             } else {
@@ -553,108 +882,167 @@ pub mod evr {
             }
 
             // Then compare versions using RPM-style version comparison
-            match compare_version_strings(&self.version, &other.version) {
+            match rpmvercmp(&self.version, &other.version) {
                 Ordering::Equal => {}
                 other => return other,
             }
 
             // Finally compare releases
-            compare_version_strings(&self.release, &other.release)
-        }
-    }
-
-    /// RPM-style version comparison
-    /// Compares version strings segment by segment, handling numeric and alphabetic parts
-    fn compare_version_strings(a: &str, b: &str) -> Ordering {
-        let mut a_segments = VersionSegments::new(a);
-        let mut b_segments = VersionSegments::new(b);
-
-        loop {
-            match (a_segments.next(), b_segments.next()) {
-                (None, None) => return Ordering::Equal,
-                (None, Some(_)) => return Ordering::Less,
-                (Some(_), None) => return Ordering::Greater,
-                (Some(seg_a), Some(seg_b)) => match compare_segments(&seg_a, &seg_b) {
-                    Ordering::Equal => continue,
-                    other => return other,
-                },
-            }
+            rpmvercmp(&self.release, &other.release)
         }
     }
 
-    /// Compare individual version segments
-    fn compare_segments(a: &str, b: &str) -> Ordering {
-        // Try numeric comparison first
-        match (a.parse::<u64>(), b.parse::<u64>()) {
-            (Ok(num_a), Ok(num_b)) => num_a.cmp(&num_b),
-            _ => a.cmp(b), // Fallback to string comparison
+    /// RPM's `rpmvercmp`: compare two version (or release) strings
+    ///
+    /// Both strings are split into runs of ASCII digits or letters; anything
+    /// else only separates runs. Runs compare pairwise: numbers numerically
+    /// (leading zeros ignored), letters byte-wise, and a number is newer than
+    /// letters. `~` sorts before everything, even the end of the string
+    /// (`1.0~rc1 < 1.0`), and `^` sorts after the end but before any further
+    /// run (`1.0 < 1.0^git1 < 1.0.1`). Otherwise the string with runs left
+    /// over is newer (`1.0.0a > 1.0.0`).
+    pub fn rpmvercmp(a: &str, b: &str) -> Ordering {
+        if a == b {
+            return Ordering::Equal;
         }
-    }
 
-    /// Iterator over version string segments (split by dots and non-alphanumeric)
-    struct VersionSegments<'a> {
-        remaining: &'a str,
-    }
+        let is_separator = |c: &u8| !c.is_ascii_alphanumeric() && *c != b'~' && *c != b'^';
+        let (mut a, mut b) = (a.as_bytes(), b.as_bytes());
 
-    impl<'a> VersionSegments<'a> {
-        fn new(s: &'a str) -> Self {
-            Self { remaining: s }
-        }
-    }
+        loop {
+            while a.first().is_some_and(is_separator) {
+                a = &a[1..];
+            }
+            while b.first().is_some_and(is_separator) {
+                b = &b[1..];
+            }
 
-    impl<'a> Iterator for VersionSegments<'a> {
-        type Item = String;
+            // Tilde: pre-release, older than anything else
+            if a.first() == Some(&b'~') || b.first() == Some(&b'~') {
+                if a.first() != Some(&b'~') {
+                    return Ordering::Greater;
+                }
+                if b.first() != Some(&b'~') {
+                    return Ordering::Less;
+                }
+                a = &a[1..];
+                b = &b[1..];
+                continue;
+            }
 
-        fn next(&mut self) -> Option<Self::Item> {
-            if self.remaining.is_empty() {
-                return None;
+            // Caret: post-release, newer than the end of the string only
+            if a.first() == Some(&b'^') || b.first() == Some(&b'^') {
+                if a.is_empty() {
+                    return Ordering::Less;
+                }
+                if b.is_empty() {
+                    return Ordering::Greater;
+                }
+                if a.first() != Some(&b'^') {
+                    return Ordering::Greater;
+                }
+                if b.first() != Some(&b'^') {
+                    return Ordering::Less;
+                }
+                a = &a[1..];
+                b = &b[1..];
+                continue;
             }
 
-            // Find the next segment boundary (dot or non-alphanumeric)
-            let end = self
-                .remaining
-                .find(|c: char| c == '.' || (!c.is_alphanumeric() && c != '_'))
-                .unwrap_or(self.remaining.len());
+            if a.is_empty() || b.is_empty() {
+                break;
+            }
 
-            if end == 0 {
-                // Skip delimiter
-                self.remaining = &self.remaining[1..];
-                return self.next();
+            let numeric = a[0].is_ascii_digit();
+            let run = |s: &[u8]| {
+                s.iter()
+                    .take_while(|c| {
+                        if numeric {
+                            c.is_ascii_digit()
+                        } else {
+                            c.is_ascii_alphabetic()
+                        }
+                    })
+                    .count()
+            };
+            let (a_len, b_len) = (run(a), run(b));
+            let (a_run, b_run) = (&a[..a_len], &b[..b_len]);
+            a = &a[a_len..];
+            b = &b[b_len..];
+
+            // Runs of different kinds: the number is newer
+            if b_run.is_empty() {
+                return if numeric {
+                    Ordering::Greater
+                } else {
+                    Ordering::Less
+                };
             }
 
-            let segment = self.remaining[..end].to_string();
-            self.remaining = &self.remaining[end..];
+            let ordering = if numeric {
+                let a_run = trim_leading_zeros(a_run);
+                let b_run = trim_leading_zeros(b_run);
+                a_run.len().cmp(&b_run.len()).then_with(|| a_run.cmp(b_run))
+            } else {
+                a_run.cmp(b_run)
+            };
+            if ordering != Ordering::Equal {
+                return ordering;
+            }
+        }
 
-            Some(segment)
+        match (a.is_empty(), b.is_empty()) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Less,
+            _ => Ordering::Greater,
         }
     }
 
+    fn trim_leading_zeros(digits: &[u8]) -> &[u8] {
+        let zeros = digits.iter().take_while(|c| **c == b'0').count();
+        &digits[zeros..]
+    }
+
+    /// Whether `actual`'s `ordering` relative to the expected value
+    /// satisfies `operation`; `None` for operations that do not order
+    pub fn ordering_satisfies(ordering: Ordering, operation: Operation) -> Option<bool> {
+        Some(match operation {
+            Operation::Equals | Operation::VersionEquals => ordering == Ordering::Equal,
+            Operation::NotEqual | Operation::VersionNotEqual => ordering != Ordering::Equal,
+            Operation::GreaterThan | Operation::VersionGreaterThan => ordering == Ordering::Greater,
+            Operation::LessThan | Operation::VersionLessThan => ordering == Ordering::Less,
+            Operation::GreaterThanOrEqual | Operation::VersionGreaterOrEqual => {
+                ordering != Ordering::Less
+            }
+            Operation::LessThanOrEqual | Operation::VersionLessOrEqual => {
+                ordering != Ordering::Greater
+            }
+            _ => return None,
+        })
+    }
+
     /// Compare two EVR strings with operation
+    ///
+    /// As with RPM dependencies, an expected value without a release
+    /// matches every release of its version (`1.0` equals `1.0-3.el9`).
     pub fn compare(
         expected: &str,
         actual: &str,
         operation: Operation,
     ) -> Result<bool, ComparisonError> {
         let expected_evr = EvrString::parse(expected)?;
-        let actual_evr = EvrString::parse(actual)?;
+        let mut actual_evr = EvrString::parse(actual)?;
+        if expected_evr.release.is_empty() {
+            actual_evr.release.clear();
+        }
 
         // Compare actual to expected (actual OP expected)
         let ordering = actual_evr.compare(&expected_evr);
 
-        Ok(match operation {
-            Operation::Equals => ordering == Ordering::Equal,
-            Operation::NotEqual => ordering != Ordering::Equal,
-            Operation::GreaterThan => ordering == Ordering::Greater,
-            Operation::LessThan => ordering == Ordering::Less,
-            Operation::GreaterThanOrEqual => {
-                ordering == Ordering::Greater || ordering == Ordering::Equal
-            }
-            Operation::LessThanOrEqual => ordering == Ordering::Less || ordering == Ordering::Equal,
-            _ => {
-                return Err(ComparisonError::UnsupportedOperation {
-                    operation,
-                    data_type: "evr_string".to_string(),
-                })
+        ordering_satisfies(ordering, operation).ok_or_else(|| {
+            ComparisonError::UnsupportedOperation {
+                operation,
+                data_type: "evr_string".to_string(),
             }
         })
     }
@@ -701,10 +1089,299 @@ pub mod evr {
         }
 
         #[test]
-        fn test_version_segment_comparison() {
-            assert_eq!(compare_segments("1", "2"), Ordering::Less);
-            assert_eq!(compare_segments("10", "9"), Ordering::Greater);
-            assert_eq!(compare_segments("alpha", "beta"), Ordering::Less);
+        fn test_rpmvercmp_table() {
+            use Ordering::{Equal, Greater, Less};
+
+            // (a, b, rpmvercmp(a, b)); every row is also checked reversed
+            let cases = [
+                ("1.0", "1.0", Equal),
+                ("1.0", "2.0", Less),
+                ("2.0.1", "2.0", Greater),
+                ("10", "9", Greater),
+                ("1.01", "1.1", Equal),
+                ("1.0010", "1.9", Greater),
+                ("1..0", "1.0", Equal),
+                ("2_0", "2.0", Equal),
+                ("1.0.0a", "1.0.0", Greater),
+                ("1.0a", "1.0b", Less),
+                ("alpha", "beta", Less),
+                ("1.a", "1.1", Less),
+                ("5.5p1", "5.5p10", Less),
+                ("1.0~rc1", "1.0", Less),
+                ("1.0~rc1", "1.0~rc2", Less),
+                ("1.0~~", "1.0~", Less),
+                ("1.0~rc1", "1.0~rc1", Equal),
+                ("1.0^", "1.0", Greater),
+                ("1.0^git1", "1.0", Greater),
+                ("1.0^git1", "1.0.1", Less),
+                ("1.0^git1", "1.0~rc1", Greater),
+            ];
+
+            for (a, b, expected) in cases {
+                assert_eq!(rpmvercmp(a, b), expected, "{} vs {}", a, b);
+                assert_eq!(rpmvercmp(b, a), expected.reverse(), "{} vs {}", b, a);
+            }
+        }
+
+        #[test]
+        fn test_evr_epoch_release_and_version_operations() {
+            // (expected, actual, operation) that must hold
+            let cases = [
+                ("2.0", "1:1.0", Operation::VersionGreaterThan),
+                ("1:1.0", "2.0", Operation::VersionLessThan),
+                ("0:1.0", "1.0", Operation::VersionEquals),
+                ("1.0", "1.0~rc1", Operation::VersionLessThan),
+                ("1.0.0", "1.0.0a", Operation::VersionGreaterOrEqual),
+                ("1.0-2", "1.0-10", Operation::VersionGreaterThan),
+                ("1.0-1.el9", "1.0-1.el9", Operation::VersionLessOrEqual),
+                // An expected value without a release matches any release
+                ("3.0.7", "3.0.7-27.el9", Operation::VersionEquals),
+                ("3.0.7-25.el9", "3.0.7-27.el9", Operation::VersionNotEqual),
+                ("3.0.7", "3.0.7-27.el9", Operation::LessThanOrEqual),
+            ];
+
+            for (expected, actual, operation) in cases {
+                assert!(
+                    compare(expected, actual, operation).unwrap(),
+                    "{} {} {}",
+                    actual,
+                    operation.as_str(),
+                    expected
+                );
+            }
+
+            assert!(compare("1.0", "1.0", Operation::Contains).is_err());
+            assert!(compare("x:1.0", "1.0", Operation::VersionEquals).is_err());
+        }
+    }
+}
+
+/// Address against CIDR networks (`in_subnet` / `not_in_subnet`)
+pub mod network {
+    use super::*;
+    use crate::types::common::{parse_ip_address, Cidr};
+
+    /// `in_subnet` passes when `actual` is in any of `networks`,
+    /// `not_in_subnet` when it is in none of them
+    pub fn compare(
+        actual: &str,
+        networks: &[ResolvedValue],
+        operation: Operation,
+    ) -> Result<bool, ComparisonError> {
+        let address = parse_ip_address(actual).map_err(ComparisonError::InvalidNetwork)?;
+        let mut contained = false;
+        for network in networks {
+            let ResolvedValue::String(network) = network else {
+                return Err(ComparisonError::TypeMismatch {
+                    message: format!(
+                        "{} expects CIDR strings, found {:?}",
+                        operation.as_str(),
+                        network
+                    ),
+                });
+            };
+            let cidr = Cidr::parse(network).map_err(ComparisonError::InvalidNetwork)?;
+            contained |= cidr.contains(address);
+        }
+
+        match operation {
+            Operation::InSubnet => Ok(contained),
+            Operation::NotInSubnet => Ok(!contained),
+            _ => Err(ComparisonError::UnsupportedOperation {
+                operation,
+                data_type: "ip_address".to_string(),
+            }),
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn networks(cidrs: &[&str]) -> Vec<ResolvedValue> {
+            cidrs
+                .iter()
+                .map(|c| ResolvedValue::String(c.to_string()))
+                .collect()
+        }
+
+        #[test]
+        fn test_subnet_membership() {
+            let private = networks(&["10.0.0.0/8", "172.16.0.0/12", "192.168.0.0/16"]);
+            assert!(compare("172.20.1.1", &private, Operation::InSubnet).unwrap());
+            assert!(compare("8.8.8.8", &private, Operation::NotInSubnet).unwrap());
+            assert!(!compare("192.168.1.10", &private, Operation::NotInSubnet).unwrap());
+            assert!(compare("::1", &networks(&["::1/128"]), Operation::InSubnet).unwrap());
+
+            assert!(matches!(
+                compare("not-an-ip", &private, Operation::InSubnet),
+                Err(ComparisonError::InvalidNetwork(_))
+            ));
+            assert!(compare("10.0.0.1", &private, Operation::Equals).is_err());
+        }
+    }
+}
+
+/// File mode bits: collectors report modes as octal strings (`0644`) and
+/// `octal` policy values arrive as integers holding the bits
+pub mod mode {
+    use super::*;
+
+    /// Permission bits, including setuid (4000), setgid (2000) and sticky
+    /// (1000); file type bits of a full `st_mode` are dropped
+    pub const PERMISSION_BITS: u32 = 0o7777;
+
+    /// Mode bits of an octal string or an integer
+    pub fn parse(value: &ResolvedValue) -> Result<u32, ComparisonError> {
+        let bits = match value {
+            ResolvedValue::String(text) => {
+                let digits = text.trim();
+                let digits = digits.strip_prefix("0o").unwrap_or(digits);
+                u32::from_str_radix(digits, 8)
+                    .map_err(|_| ComparisonError::InvalidMode(text.clone()))?
+            }
+            ResolvedValue::Integer(bits) => {
+                u32::try_from(*bits).map_err(|_| ComparisonError::InvalidMode(bits.to_string()))?
+            }
+            other => {
+                return Err(ComparisonError::TypeMismatch {
+                    message: format!("expected a file mode, found {:?}", other),
+                })
+            }
+        };
+        Ok(bits & PERMISSION_BITS)
+    }
+
+    /// Four octal digits, the way `stat` prints them
+    pub fn format(bits: u32) -> String {
+        format!("{:04o}", bits & PERMISSION_BITS)
+    }
+
+    /// A mode (or list of modes) as octal strings for findings and reports;
+    /// anything that is not a mode is returned unchanged
+    pub fn render(value: &ResolvedValue) -> ResolvedValue {
+        match value {
+            ResolvedValue::Collection(items) => {
+                ResolvedValue::Collection(items.iter().map(render).collect())
+            }
+            other => match parse(other) {
+                Ok(bits) => ResolvedValue::String(format(bits)),
+                Err(_) => other.clone(),
+            },
+        }
+    }
+
+    /// `permissions_at_most` passes when `actual` sets no bit outside
+    /// `expected`, `permissions_at_least` when it sets every bit of it
+    pub fn compare(
+        actual: u32,
+        expected: u32,
+        operation: Operation,
+    ) -> Result<bool, ComparisonError> {
+        match operation {
+            Operation::Equals => Ok(actual == expected),
+            Operation::NotEqual => Ok(actual != expected),
+            Operation::PermissionsAtMost => Ok(actual & !expected == 0),
+            Operation::PermissionsAtLeast => Ok(actual & expected == expected),
+            _ => Err(ComparisonError::UnsupportedOperation {
+                operation,
+                data_type: "octal".to_string(),
+            }),
+        }
+    }
+
+    /// Compare collected and policy modes in any mix of octal strings and
+    /// integers; `equals_any` / `not_equals_any` take a list of modes
+    pub fn compare_values(
+        actual: &ResolvedValue,
+        expected: &ResolvedValue,
+        operation: Operation,
+    ) -> Result<bool, ComparisonError> {
+        let actual = parse(actual)?;
+        match (expected, operation) {
+            (ResolvedValue::Collection(modes), Operation::EqualsAny | Operation::NotEqualsAny) => {
+                let mut found = false;
+                for expected in modes {
+                    found |= parse(expected)? == actual;
+                }
+                Ok(found == (operation == Operation::EqualsAny))
+            }
+            (expected, _) => compare(actual, parse(expected)?, operation),
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_mode_masks_include_special_bits() {
+            let cases = [
+                (0o640, 0o640, Operation::PermissionsAtMost, true),
+                (0o600, 0o640, Operation::PermissionsAtMost, true),
+                (0o644, 0o640, Operation::PermissionsAtMost, false),
+                (0o4640, 0o640, Operation::PermissionsAtMost, false),
+                (0o4755, 0o4755, Operation::PermissionsAtMost, true),
+                (0o1777, 0o1000, Operation::PermissionsAtLeast, true),
+                (0o777, 0o1000, Operation::PermissionsAtLeast, false),
+                (0o750, 0o700, Operation::PermissionsAtLeast, true),
+                (0o2755, 0o2755, Operation::Equals, true),
+                (0o755, 0o2755, Operation::NotEqual, true),
+            ];
+            for (actual, expected, operation, result) in cases {
+                assert_eq!(
+                    compare(actual, expected, operation).unwrap(),
+                    result,
+                    "{:o} {} {:o}",
+                    actual,
+                    operation.as_str(),
+                    expected
+                );
+            }
+            assert!(compare(0o640, 0o640, Operation::GreaterThan).is_err());
+        }
+
+        #[test]
+        fn test_modes_parse_and_render_in_octal() {
+            let collected = ResolvedValue::String("4750".to_string());
+            assert_eq!(parse(&collected).unwrap(), 0o4750);
+            assert_eq!(
+                parse(&ResolvedValue::String("0o640".to_string())).unwrap(),
+                0o640
+            );
+            // File type bits of a full st_mode are dropped
+            assert_eq!(parse(&ResolvedValue::Integer(0o100644)).unwrap(), 0o644);
+            assert!(matches!(
+                parse(&ResolvedValue::String("0689".to_string())),
+                Err(ComparisonError::InvalidMode(_))
+            ));
+
+            assert_eq!(
+                render(&ResolvedValue::Integer(0o640)),
+                ResolvedValue::String("0640".to_string())
+            );
+            assert_eq!(
+                render(&ResolvedValue::Boolean(true)),
+                ResolvedValue::Boolean(true)
+            );
+
+            let allowed = ResolvedValue::Collection(vec![
+                ResolvedValue::Integer(0o600),
+                ResolvedValue::Integer(0o640),
+            ]);
+            assert!(compare_values(&collected, &allowed, Operation::NotEqualsAny).unwrap());
+            assert!(compare_values(
+                &ResolvedValue::String("0640".to_string()),
+                &allowed,
+                Operation::EqualsAny
+            )
+            .unwrap());
+            assert!(compare_values(
+                &collected,
+                &ResolvedValue::Integer(0o4000),
+                Operation::PermissionsAtLeast
+            )
+            .unwrap());
         }
     }
 }
@@ -717,6 +1394,18 @@ pub trait ComparisonExt {
         other: &ResolvedValue,
         operation: Operation,
     ) -> Result<bool, ComparisonError>;
+
+    /// Compare against a policy value that may be a list-valued variable
+    ///
+    /// When `other` is a list and the operation does not take a value list,
+    /// each item is compared on its own and the results are combined by
+    /// `entity_check` (`all` when absent). Anything else is `compare_with`.
+    fn compare_with_entity_check(
+        &self,
+        other: &ResolvedValue,
+        operation: Operation,
+        entity_check: Option<EntityCheck>,
+    ) -> Result<bool, ComparisonError>;
 }
 
 impl ComparisonExt for ResolvedValue {
@@ -727,6 +1416,71 @@ impl ComparisonExt for ResolvedValue {
     ) -> Result<bool, ComparisonError> {
         // FIXED: self is actual (collected data), other is expected (policy value)
         match (self, other) {
+            // Membership against a policy value list
+            (actual, ResolvedValue::Collection(allowed))
+                if matches!(operation, Operation::EqualsAny | Operation::NotEqualsAny)
+                    && !matches!(actual, ResolvedValue::Collection(_)) =>
+            {
+                collection::matches_any(actual, allowed, operation)
+            }
+
+            // String length against an integer
+            (ResolvedValue::String(actual), ResolvedValue::Integer(expected))
+                if matches!(
+                    operation,
+                    Operation::LengthEquals | Operation::LengthGreaterThan
+                ) =>
+            {
+                string::compare_length(actual, *expected, operation)
+            }
+
+            // Occurrence count against [pattern, count]
+            (ResolvedValue::String(actual), ResolvedValue::Collection(spec))
+                if operation == Operation::OccurrenceCount =>
+            {
+                string::compare_occurrences(actual, spec)
+            }
+
+            // Address against one CIDR or a list of them
+            (ResolvedValue::String(actual), ResolvedValue::String(_))
+                if matches!(operation, Operation::InSubnet | Operation::NotInSubnet) =>
+            {
+                network::compare(actual, std::slice::from_ref(other), operation)
+            }
+            (ResolvedValue::String(actual), ResolvedValue::Collection(networks))
+                if matches!(operation, Operation::InSubnet | Operation::NotInSubnet) =>
+            {
+                network::compare(actual, networks, operation)
+            }
+
+            // Mode bits against a mask
+            (
+                ResolvedValue::String(_) | ResolvedValue::Integer(_),
+                ResolvedValue::String(_) | ResolvedValue::Integer(_),
+            ) if matches!(
+                operation,
+                Operation::PermissionsAtMost | Operation::PermissionsAtLeast
+            ) =>
+            {
+                mode::compare_values(self, other, operation)
+            }
+
+            // version_* orders any version-like strings the way RPM does
+            (
+                ResolvedValue::String(actual)
+                | ResolvedValue::Version(actual)
+                | ResolvedValue::EvrString(actual),
+                ResolvedValue::String(expected)
+                | ResolvedValue::Version(expected)
+                | ResolvedValue::EvrString(expected),
+            ) if operation.is_version_comparison() => evr::compare(expected, actual, operation),
+
+            // Version comparison
+            // self = actual (collected), other = expected (policy)
+            (ResolvedValue::Version(actual), ResolvedValue::Version(expected)) => {
+                evr::compare(expected, actual, operation)
+            }
+
             // String comparison
             // self = actual (collected), other = expected (policy)
             (ResolvedValue::String(actual), ResolvedValue::String(expected)) => {
@@ -783,7 +1537,7 @@ impl ComparisonExt for ResolvedValue {
             // EVR string comparison
             // self = actual (collected), other = expected (policy)
             (ResolvedValue::EvrString(actual), ResolvedValue::EvrString(expected)) => {
-                evr::compare(actual, expected, operation)
+                evr::compare(expected, actual, operation)
             }
 
             // Collection comparison
@@ -792,12 +1546,40 @@ impl ComparisonExt for ResolvedValue {
                 collection::compare(actual, expected, operation)
             }
 
+            // Single value against a list-valued variable: every item must hold
+            (actual, ResolvedValue::Collection(_)) if expects_each_item(actual, operation) => {
+                self.compare_with_entity_check(other, operation, None)
+            }
+
             // Type mismatch
             _ => Err(ComparisonError::TypeMismatch {
                 message: format!("Cannot compare {:?} with {:?}", self, other),
             }),
         }
     }
+
+    fn compare_with_entity_check(
+        &self,
+        other: &ResolvedValue,
+        operation: Operation,
+        entity_check: Option<EntityCheck>,
+    ) -> Result<bool, ComparisonError> {
+        match other {
+            ResolvedValue::Collection(items) if expects_each_item(self, operation) => {
+                let results = items
+                    .iter()
+                    .map(|item| self.compare_with(item, operation))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(evaluate_list_entity_check(entity_check, &results))
+            }
+            _ => self.compare_with(other, operation),
+        }
+    }
+}
+
+/// Whether a list operand is compared item by item against `actual`
+fn expects_each_item(actual: &ResolvedValue, operation: Operation) -> bool {
+    !operation.takes_value_list() && !matches!(actual, ResolvedValue::Collection(_))
 }
 
 #[cfg(test)]
@@ -825,4 +1607,69 @@ mod integration_tests {
         assert!(val1.compare_with(&val2, Operation::LessThan).unwrap());
         assert!(val2.compare_with(&val1, Operation::GreaterThan).unwrap());
     }
+
+    #[test]
+    fn test_evr_string_compares_collected_against_policy() {
+        // self is the collected version, other the policy's
+        let installed = ResolvedValue::EvrString("0:2.4.6-7.el8".to_string());
+        let required = ResolvedValue::EvrString("0:2.4.6-5.el8".to_string());
+
+        assert!(installed
+            .compare_with(&required, Operation::GreaterThan)
+            .unwrap());
+        assert!(installed
+            .compare_with(&required, Operation::GreaterThanOrEqual)
+            .unwrap());
+        assert!(!installed
+            .compare_with(&required, Operation::LessThan)
+            .unwrap());
+        assert!(required
+            .compare_with(&installed, Operation::LessThan)
+            .unwrap());
+
+        // A higher epoch outranks any version
+        let epoch = ResolvedValue::EvrString("1:0.9-1".to_string());
+        assert!(epoch
+            .compare_with(&installed, Operation::GreaterThan)
+            .unwrap());
+        assert!(!installed
+            .compare_with(&epoch, Operation::GreaterThan)
+            .unwrap());
+    }
+
+    #[test]
+    fn test_single_value_against_list_variable() {
+        let string = |s: &str| ResolvedValue::String(s.to_string());
+        let servers = ResolvedValue::Collection(vec![string("10.0.0.53"), string("10.0.1.53")]);
+        let actual = string("10.0.0.53");
+
+        // Without an entity check every item must hold
+        assert!(!actual.compare_with(&servers, Operation::Equals).unwrap());
+        assert!(string("10.0.2.53")
+            .compare_with(&servers, Operation::NotEqual)
+            .unwrap());
+        assert!(actual
+            .compare_with_entity_check(&servers, Operation::Equals, Some(EntityCheck::AtLeastOne))
+            .unwrap());
+        assert!(!actual
+            .compare_with_entity_check(&servers, Operation::Equals, Some(EntityCheck::None))
+            .unwrap());
+
+        // An empty list holds for `all` and `none` only
+        let empty = ResolvedValue::Collection(Vec::new());
+        assert!(actual.compare_with(&empty, Operation::Equals).unwrap());
+        assert!(!actual
+            .compare_with_entity_check(&empty, Operation::Equals, Some(EntityCheck::AtLeastOne))
+            .unwrap());
+
+        // Value-list operations still take the list as a whole
+        assert!(actual
+            .compare_with_entity_check(&servers, Operation::EqualsAny, Some(EntityCheck::None))
+            .unwrap());
+
+        // An item of another type is an error, not a silent mismatch
+        let mixed =
+            ResolvedValue::Collection(vec![string("10.0.0.53"), ResolvedValue::Integer(53)]);
+        assert!(actual.compare_with(&mixed, Operation::Equals).is_err());
+    }
 }
@@ -0,0 +1,157 @@
+//! # Scan Consistency
+//!
+//! A file that changes between the moment one criterion reads it and another
+//! criterion stats it leaves the scan internally inconsistent. Collectors
+//! attach a [`FileObservation`] built from what they already gathered (size,
+//! modification time, a hash of read content); the engine compares each one
+//! against the earlier observations of the same path. A mismatch is recorded
+//! with both observations and flags both criteria.
+
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+/// Reason and advisory code for criteria that saw a file change mid-scan
+pub const DATA_CHANGED_DURING_SCAN: &str = "data_changed_during_scan";
+
+/// What a collector saw of one file; unset attributes are not compared
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileObservation {
+    pub path: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size: Option<u64>,
+    /// Modification time in nanoseconds since the Unix epoch
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub modified_ns: Option<i128>,
+    /// Hash of the content read; only compared within one scan
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_hash: Option<String>,
+}
+
+impl FileObservation {
+    pub fn new(path: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            size: None,
+            modified_ns: None,
+            content_hash: None,
+        }
+    }
+
+    pub fn with_size(mut self, size: u64) -> Self {
+        self.size = Some(size);
+        self
+    }
+
+    pub fn with_modified(mut self, modified: std::time::SystemTime) -> Self {
+        self.modified_ns = modified
+            .duration_since(std::time::UNIX_EPOCH)
+            .ok()
+            .map(|d| d.as_nanos() as i128);
+        self
+    }
+
+    /// Size and hash of content the collector read
+    pub fn with_content(mut self, content: &[u8]) -> Self {
+        let mut hasher = DefaultHasher::new();
+        content.hash(&mut hasher);
+        self.size = Some(content.len() as u64);
+        self.content_hash = Some(format!("{:016x}", hasher.finish()));
+        self
+    }
+
+    /// Whether an attribute both observations carry differs
+    pub fn conflicts_with(&self, other: &FileObservation) -> bool {
+        fn differs<T: PartialEq>(a: &Option<T>, b: &Option<T>) -> bool {
+            matches!((a, b), (Some(a), Some(b)) if a != b)
+        }
+        differs(&self.size, &other.size)
+            || differs(&self.modified_ns, &other.modified_ns)
+            || differs(&self.content_hash, &other.content_hash)
+    }
+}
+
+/// A file observed differently by two criteria of the same scan
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConsistencyMismatch {
+    pub path: String,
+    /// Criterion that first observed the file (`<type>#<node>`)
+    pub first_criterion: String,
+    pub first: FileObservation,
+    /// Criterion whose observation no longer matched
+    pub second_criterion: String,
+    pub second: FileObservation,
+}
+
+impl ConsistencyMismatch {
+    pub fn involves(&self, criterion_id: &str) -> bool {
+        self.first_criterion == criterion_id || self.second_criterion == criterion_id
+    }
+}
+
+/// Observations of every file read during one scan
+#[derive(Debug, Default)]
+pub struct ConsistencySnapshot {
+    seen: HashMap<String, Vec<(String, FileObservation)>>,
+    mismatches: Vec<ConsistencyMismatch>,
+}
+
+impl ConsistencySnapshot {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record an observation made by `criterion_id`, returning the mismatch
+    /// if it conflicts with an earlier observation of the same path
+    pub fn observe(
+        &mut self,
+        criterion_id: &str,
+        observation: &FileObservation,
+    ) -> Option<&ConsistencyMismatch> {
+        let earlier = self.seen.entry(observation.path.clone()).or_default();
+        let conflict = earlier
+            .iter()
+            .find(|(id, seen)| id != criterion_id && seen.conflicts_with(observation))
+            .cloned();
+        earlier.push((criterion_id.to_string(), observation.clone()));
+
+        let (first_criterion, first) = conflict?;
+        self.mismatches.push(ConsistencyMismatch {
+            path: observation.path.clone(),
+            first_criterion,
+            first,
+            second_criterion: criterion_id.to_string(),
+            second: observation.clone(),
+        });
+        self.mismatches.last()
+    }
+
+    pub fn mismatches(&self) -> &[ConsistencyMismatch] {
+        &self.mismatches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_shared_attributes_are_compared() {
+        let stat = FileObservation::new("/etc/issue").with_size(5);
+        let read = FileObservation::new("/etc/issue").with_content(b"hello");
+        let changed = FileObservation::new("/etc/issue").with_content(b"hello!");
+
+        let mut snapshot = ConsistencySnapshot::new();
+        assert!(snapshot.observe("file_metadata#1", &stat).is_none());
+        assert!(snapshot.observe("file_content#2", &read).is_none());
+
+        let mismatch = snapshot.observe("file_content#3", &changed).unwrap();
+        assert_eq!(mismatch.first_criterion, "file_metadata#1");
+        assert_eq!(mismatch.first, stat);
+        assert_eq!(mismatch.second.size, Some(6));
+        assert!(mismatch.involves("file_content#3"));
+        assert!(!mismatch.involves("file_content#2"));
+        assert_eq!(snapshot.mismatches().len(), 1);
+    }
+}
@@ -0,0 +1,165 @@
+//! # Criterion and Scan Deadlines
+//!
+//! Each criterion runs under a deadline: its own timeout, cut short by the
+//! scan's overall deadline when one is set. The engine enters the deadline on
+//! the scanning thread before collecting, and command execution shortens its
+//! wait to what is left of it, killing the command when it runs out, so a
+//! hung tool costs one criterion instead of the whole scan.
+//!
+//! A criterion whose deadline passes is reported as an error with reason
+//! [`CRITERION_TIMEOUT`]; criteria reached after the scan deadline are not
+//! started and carry [`SCAN_DEADLINE_EXCEEDED`].
+
+use std::cell::Cell;
+use std::time::{Duration, Instant};
+
+/// Reason recorded on criteria that ran past their timeout
+pub const CRITERION_TIMEOUT: &str = "criterion_timeout";
+
+/// Reason recorded on criteria skipped once the scan deadline has passed
+pub const SCAN_DEADLINE_EXCEEDED: &str = "scan_deadline_exceeded";
+
+/// Timeout of a criterion when none is configured
+pub const DEFAULT_CRITERION_TIMEOUT: Duration = Duration::from_secs(30);
+
+thread_local! {
+    static DEADLINE: Cell<Option<Instant>> = const { Cell::new(None) };
+}
+
+/// Time limits for a scan
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timeouts {
+    /// Longest a single criterion may take, collection and evaluation
+    pub criterion: Duration,
+    /// Longest the whole scan may take; unlimited when `None`
+    pub scan: Option<Duration>,
+}
+
+impl Default for Timeouts {
+    fn default() -> Self {
+        Self {
+            criterion: DEFAULT_CRITERION_TIMEOUT,
+            scan: None,
+        }
+    }
+}
+
+impl Timeouts {
+    /// Limit each criterion to `timeout`
+    pub fn with_criterion(mut self, timeout: Duration) -> Self {
+        self.criterion = timeout;
+        self
+    }
+
+    /// Limit the whole scan to `timeout`
+    pub fn with_scan(mut self, timeout: Duration) -> Self {
+        self.scan = Some(timeout);
+        self
+    }
+}
+
+/// Parse a duration such as `30s`, `5m`, `1h` or `500ms`; a bare number is
+/// seconds
+pub fn parse_timeout(value: &str) -> Result<Duration, String> {
+    let invalid = || format!("invalid timeout '{}': expected e.g. 30s, 5m or 1h", value);
+    let value = value.trim();
+    let digits_end = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    let (count, unit) = value.split_at(digits_end);
+    let count: u64 = count.parse().map_err(|_| invalid())?;
+    let timeout = match unit {
+        "ms" => Duration::from_millis(count),
+        "" | "s" => Duration::from_secs(count),
+        "m" => Duration::from_secs(count.checked_mul(60).ok_or_else(invalid)?),
+        "h" => Duration::from_secs(count.checked_mul(3600).ok_or_else(invalid)?),
+        _ => return Err(invalid()),
+    };
+    if timeout.is_zero() {
+        return Err(format!("invalid timeout '{}': must be positive", value));
+    }
+    Ok(timeout)
+}
+
+/// Restores the enclosing deadline when dropped
+#[must_use = "the deadline only applies while the guard is alive"]
+pub struct DeadlineGuard {
+    previous: Option<Instant>,
+}
+
+impl Drop for DeadlineGuard {
+    fn drop(&mut self) {
+        DEADLINE.with(|deadline| deadline.set(self.previous));
+    }
+}
+
+/// Run the current thread under `deadline` until the guard is dropped
+///
+/// An enclosing deadline that is earlier stays in force.
+pub fn enter(deadline: Instant) -> DeadlineGuard {
+    let previous = DEADLINE.with(Cell::get);
+    let effective = previous.map_or(deadline, |previous| previous.min(deadline));
+    DEADLINE.with(|current| current.set(Some(effective)));
+    DeadlineGuard { previous }
+}
+
+/// Time left before the current thread's deadline, if it has one
+pub fn remaining() -> Option<Duration> {
+    DEADLINE
+        .with(Cell::get)
+        .map(|deadline| deadline.saturating_duration_since(Instant::now()))
+}
+
+/// Whether the current thread's deadline has passed
+pub fn expired() -> bool {
+    remaining().is_some_and(|left| left.is_zero())
+}
+
+/// `timeout`, shortened to what is left of the current deadline
+pub fn bounded(timeout: Duration) -> Duration {
+    remaining().map_or(timeout, |left| left.min(timeout))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_timeout_units() {
+        assert_eq!(parse_timeout("30s"), Ok(Duration::from_secs(30)));
+        assert_eq!(parse_timeout("45"), Ok(Duration::from_secs(45)));
+        assert_eq!(parse_timeout("5m"), Ok(Duration::from_secs(300)));
+        assert_eq!(parse_timeout("1h"), Ok(Duration::from_secs(3600)));
+        assert_eq!(parse_timeout("250ms"), Ok(Duration::from_millis(250)));
+        assert!(parse_timeout("0s").is_err());
+        assert!(parse_timeout("soon").is_err());
+        assert!(parse_timeout("5d").is_err());
+        // Too large to represent in seconds
+        assert!(parse_timeout(&format!("{}h", u64::MAX / 60)).is_err());
+        assert!(parse_timeout(&format!("{}m", u64::MAX)).is_err());
+    }
+
+    #[test]
+    fn test_nested_deadline_keeps_the_earlier_one() {
+        assert_eq!(remaining(), None);
+        let now = Instant::now();
+        {
+            let _scan = enter(now + Duration::from_secs(10));
+            {
+                let _criterion = enter(now + Duration::from_secs(60));
+                assert!(remaining().unwrap() <= Duration::from_secs(10));
+                assert_eq!(
+                    bounded(Duration::from_secs(5)),
+                    Duration::from_secs(5).min(remaining().unwrap())
+                );
+            }
+            {
+                let _criterion = enter(now);
+                assert!(expired());
+                assert_eq!(bounded(Duration::from_secs(5)), Duration::ZERO);
+            }
+            assert!(!expired());
+        }
+        assert_eq!(remaining(), None);
+    }
+}
@@ -3,6 +3,7 @@
 //! Handles scan-time operations that require collected data.
 
 use crate::execution::engine::ExecutionError;
+use crate::resolution::runtime_operations::count_items;
 use crate::strategies::{CollectedData, CtnStrategyRegistry};
 use crate::types::common::{DataType, ResolvedValue};
 use crate::types::execution_context::ExecutionContext;
@@ -42,11 +43,17 @@ fn execute_single_operation(
         RuntimeOperationType::Split => execute_split(operation, context),
         RuntimeOperationType::Substring => execute_substring(operation, context),
         RuntimeOperationType::RegexCapture => execute_regex_capture(operation, context),
+        RuntimeOperationType::Count if operation.operation.has_object_dependency() => {
+            execute_count_extracted(operation, context, collected_data)
+        }
         RuntimeOperationType::Count => execute_count(operation, context),
-        _ => Err(ExecutionError::DeferredOperationFailed {
-            operation: format!("{:?}", operation.operation.operation_type),
-            reason: "Not a scan-time operation".to_string(),
-        }),
+        RuntimeOperationType::Concat
+        | RuntimeOperationType::Arithmetic
+        | RuntimeOperationType::Replace
+        | RuntimeOperationType::Length
+        | RuntimeOperationType::Uppercase
+        | RuntimeOperationType::Lowercase
+        | RuntimeOperationType::Trim => execute_from_variables(operation, context),
     }
 }
 
@@ -232,6 +239,26 @@ fn execute_split(
     Ok(())
 }
 
+/// Execute an operation that only reads variables (CONCAT, REPLACE, LENGTH,
+/// the case/trim transforms and ARITHMETIC) over values computed at scan time,
+/// the same way resolution evaluates it when every input is known up front.
+/// Failures such as division by zero surface as errors, never panics.
+fn execute_from_variables(
+    operation: &DeferredOperation,
+    context: &mut ExecutionContext,
+) -> Result<(), ExecutionError> {
+    let value = crate::resolution::runtime_operations::execute_runtime_operation(
+        &operation.operation,
+        &context.global_variables,
+    )
+    .map_err(|e| ExecutionError::DeferredOperationFailed {
+        operation: operation.operation.operation_type.as_str().to_string(),
+        reason: e.to_string(),
+    })?;
+
+    update_target_variable(context, &operation.target_variable, value)
+}
+
 /// Execute REGEX_CAPTURE operation - extract matched groups
 fn execute_regex_capture(
     operation: &DeferredOperation,
@@ -310,19 +337,7 @@ fn execute_count(
         })?;
 
     // 3. Count based on type
-    let count = match &source_value.value {
-        ResolvedValue::Collection(items) => items.len() as i64,
-        ResolvedValue::String(s) => {
-            // Empty string counts as 0, non-empty as 1
-            if s.is_empty() {
-                0
-            } else {
-                1
-            }
-        }
-        // Any other single value counts as 1
-        _ => 1,
-    };
+    let count = count_items(&source_value.value);
 
     // 4. Update target variable with count
     update_target_variable(
@@ -334,6 +349,34 @@ fn execute_count(
     Ok(())
 }
 
+/// Execute COUNT over a field of a collected object
+///
+/// An object that collected nothing, or has no such field, counts 0 items.
+fn execute_count_extracted(
+    operation: &DeferredOperation,
+    context: &mut ExecutionContext,
+    collected_data: &HashMap<String, CollectedData>,
+) -> Result<(), ExecutionError> {
+    let object_id = operation.operation.extract_object_id().ok_or_else(|| {
+        ExecutionError::DeferredOperationFailed {
+            operation: "COUNT".to_string(),
+            reason: "Missing ObjectExtraction parameter".to_string(),
+        }
+    })?;
+    let field_name = extract_field_name_from_parameters(&operation.operation.parameters)?;
+
+    let count = collected_data
+        .get(&object_id)
+        .and_then(|data| data.get_field(&field_name))
+        .map_or(0, count_items);
+
+    update_target_variable(
+        context,
+        &operation.target_variable,
+        ResolvedValue::Integer(count),
+    )
+}
+
 /// Helper: Update target variable in context
 fn update_target_variable(
     context: &mut ExecutionContext,
@@ -574,7 +617,6 @@ pub fn execute_deferred_operation(
 mod tests {
     use super::*;
     use crate::types::resolution_context::ResolutionContext;
-    use crate::types::runtime_operation::RuntimeOperation;
     use crate::types::variable::ResolvedVariable;
 
     fn create_empty_test_context() -> ExecutionContext {
@@ -850,4 +892,154 @@ mod tests {
         let result = context.global_variables.get("part_count").unwrap();
         assert_eq!(result.value, ResolvedValue::Integer(5));
     }
+
+    #[test]
+    fn test_concat_and_replace_chain_on_extracted_values() {
+        use crate::types::common::Value;
+        use crate::types::runtime_operation::RuntimeOperation;
+
+        let mut res_context =
+            ResolutionContext::new(vec![], vec![], vec![], vec![], vec![], vec![]);
+        // As if RUN ... EXTRACT had just read it from a collected object
+        res_context.resolved_variables.insert(
+            "unit_file".to_string(),
+            ResolvedVariable::new(
+                "unit_file".to_string(),
+                DataType::String,
+                ResolvedValue::String("sshd.service".to_string()),
+            ),
+        );
+        let mut context = ExecutionContext::from_resolution_context(&res_context).unwrap();
+
+        let chain = [
+            RuntimeOperation::new(
+                "unit_path".to_string(),
+                RuntimeOperationType::Concat,
+                vec![
+                    RunParameter::Literal(Value::String("/etc/systemd/system/".to_string())),
+                    RunParameter::Variable("unit_file".to_string()),
+                ],
+            ),
+            RuntimeOperation::new(
+                "dropin_dir".to_string(),
+                RuntimeOperationType::Replace,
+                vec![
+                    RunParameter::Variable("unit_path".to_string()),
+                    RunParameter::Pattern(r"\.service$".to_string()),
+                    RunParameter::Literal(Value::String(".service.d".to_string())),
+                ],
+            ),
+        ];
+        for operation in chain {
+            let deferred = DeferredOperation {
+                target_variable: operation.target_variable.clone(),
+                dependencies: operation.get_variable_references(),
+                operation,
+            };
+            execute_single_operation(&deferred, &mut context, &HashMap::new()).unwrap();
+        }
+
+        assert_eq!(
+            context.global_variables["dropin_dir"].value,
+            ResolvedValue::String("/etc/systemd/system/sshd.service.d".to_string())
+        );
+    }
+
+    #[test]
+    fn test_count_of_extracted_field_is_zero_when_nothing_was_collected() {
+        use crate::types::runtime_operation::RuntimeOperation;
+
+        let mut context = create_empty_test_context();
+        let mut data = CollectedData::new(
+            "shadow".to_string(),
+            "file_content".to_string(),
+            "test".to_string(),
+        );
+        data.add_field(
+            "uid_zero_accounts".to_string(),
+            ResolvedValue::Collection(vec![ResolvedValue::String("root".to_string())]),
+        );
+        let mut collected = HashMap::new();
+        collected.insert("shadow".to_string(), data);
+
+        let count_of = |object_id: &str, field: &str| DeferredOperation {
+            target_variable: "uid_zero_count".to_string(),
+            operation: RuntimeOperation::new(
+                "uid_zero_count".to_string(),
+                RuntimeOperationType::Count,
+                vec![RunParameter::ObjectExtraction {
+                    object_id: object_id.to_string(),
+                    field: field.to_string(),
+                }],
+            ),
+            dependencies: vec![],
+        };
+
+        for (object_id, field, expected) in [
+            ("shadow", "uid_zero_accounts", 1),
+            ("shadow", "no_such_field", 0),
+            ("never_collected", "uid_zero_accounts", 0),
+        ] {
+            execute_single_operation(&count_of(object_id, field), &mut context, &collected)
+                .unwrap();
+            assert_eq!(
+                context.global_variables["uid_zero_count"].value,
+                ResolvedValue::Integer(expected),
+                "{}.{}",
+                object_id,
+                field
+            );
+        }
+    }
+
+    #[test]
+    fn test_arithmetic_on_extracted_value_reports_division_by_zero() {
+        use crate::types::common::Value;
+        use crate::types::runtime_operation::{ArithmeticOperator, RuntimeOperation};
+
+        let mut res_context =
+            ResolutionContext::new(vec![], vec![], vec![], vec![], vec![], vec![]);
+        // As if RUN ... EXTRACT had just read the listening port
+        res_context.resolved_variables.insert(
+            "listen_port".to_string(),
+            ResolvedVariable::new(
+                "listen_port".to_string(),
+                DataType::Int,
+                ResolvedValue::Integer(-8443),
+            ),
+        );
+        let mut context = ExecutionContext::from_resolution_context(&res_context).unwrap();
+
+        let arithmetic = |operand: i64| {
+            let operation = RuntimeOperation::new(
+                "port_parity".to_string(),
+                RuntimeOperationType::Arithmetic,
+                vec![
+                    RunParameter::Variable("listen_port".to_string()),
+                    RunParameter::ArithmeticOp(
+                        ArithmeticOperator::Modulus,
+                        Some(Value::Integer(operand)),
+                    ),
+                ],
+            );
+            DeferredOperation {
+                target_variable: operation.target_variable.clone(),
+                dependencies: operation.get_variable_references(),
+                operation,
+            }
+        };
+
+        execute_single_operation(&arithmetic(2), &mut context, &HashMap::new()).unwrap();
+        assert_eq!(
+            context.global_variables["port_parity"].value,
+            ResolvedValue::Integer(-1)
+        );
+
+        let result = execute_single_operation(&arithmetic(0), &mut context, &HashMap::new());
+        assert!(matches!(
+            result,
+            Err(ExecutionError::DeferredOperationFailed { ref reason, .. })
+                if reason.contains("Modulus by zero")
+        ));
+    }
 }
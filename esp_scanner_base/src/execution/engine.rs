@@ -2,36 +2,418 @@
 //!
 //! Orchestrates TEST-driven compliance validation with CTN contracts and tree traversal.
 use crate::execution::behavior::extract_behavior_hints;
+use crate::execution::clock::ScanClock;
+use crate::execution::collection_cache::{CacheKey, CollectionCache};
 use crate::execution::comparisons::{string, ComparisonExt};
+use crate::execution::consistency::{ConsistencySnapshot, DATA_CHANGED_DURING_SCAN};
+use crate::execution::deadline::{self, Timeouts, CRITERION_TIMEOUT, SCAN_DEADLINE_EXCEEDED};
 use crate::execution::deferred_ops;
 use crate::execution::filter_evaluation::FilterEvaluator;
+use crate::execution::helpers::evaluate_list_entity_check;
+use crate::execution::host_facts::{HostFacts, HostFactsCache};
+use crate::execution::memory::{
+    DegradationStep, MemoryBudget, MemoryMonitor, MEMORY_BUDGET_EXCEEDED,
+};
+use crate::execution::privilege::{EffectivePrivileges, INSUFFICIENT_PRIVILEGES};
+use crate::execution::progress::{ProgressHandler, ProgressReporter};
+use crate::execution::scan_plan::{PlannedCriterion, ScanPlan};
+use crate::execution::usage::{StrategyUsageTracker, NO_STRATEGY_REGISTERED};
+use crate::resolution::engine::ResolutionEngine;
+use crate::results::annotations::criterion_id;
+use crate::results::controls::{self, Control, ControlMap};
+use crate::results::diff::{criterion_fingerprint, CriterionOutcome};
+use crate::results::evidence::{observed_fields, DEFAULT_OBSERVED_MAX_BYTES};
+use crate::results::target::REDACTED_HOSTNAME;
 use crate::results::{
-    ComplianceFinding, EspMetadata, FindingSeverity, HostContext, ResultGenerationError,
-    ScanResult, UserContext,
+    Advisory, ComplianceFinding, ComplianceStatus as ResultComplianceStatus, CriterionEvidence,
+    EspMetadata, FactsSource, FindingSeverity, OsRelease, PolicySeverity, ResultGenerationError,
+    ScanPerformance, ScanProvenance, ScanResult, TargetInfo, Waiver, WaiverSet, WaiverTarget,
 };
 use crate::strategies::CtnExecutionError;
 use crate::strategies::{
-    CollectedData, ComplianceStatus, CtnContract, CtnExecutionResult, CtnStrategyRegistry,
+    CollectedData, CollectionError, ComplianceStatus, CtnContract, CtnExecutionResult,
+    CtnStrategyRegistry, PrivilegeRequirement, ScanTarget, DATA_UNAVAILABLE, MISSING_CAPABILITY,
+    OFFLINE_TARGET_UNSUPPORTED, PATH_DENIED,
 };
 use crate::types::common::{LogicalOp, ResolvedValue};
 use crate::types::criterion::CtnNodeId;
 use crate::types::execution_context::{
     ExecutableCriteriaTree, ExecutableCriterion, ExecutableObject, ExecutionContext,
 };
+use crate::types::execution_plan::PhaseKind;
+use crate::types::EntityCheck;
 use esp_compiler::grammar::ast::nodes::FilterAction;
-use esp_compiler::{log_debug, log_info};
+use esp_compiler::{log_debug, log_info, log_warning};
+use serde::Serialize;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 /// Main execution engine that orchestrates compliance scanning
 pub struct ExecutionEngine {
     context: ExecutionContext,
     registry: Arc<CtnStrategyRegistry>,
+    memory_monitor: Option<MemoryMonitor>,
+    evidence_summarized: bool,
+    field_evidence: bool,
+    advisories: bool,
+    strict_consistency: bool,
+    allow_unknown_criteria: bool,
+    fail_on_vacuous: bool,
+    empty_policy: EmptyPolicy,
+    error_mode: ErrorMode,
+    /// Files observed by collectors so far, compared across criteria
+    consistency: ConsistencySnapshot,
+    privileges: Option<EffectivePrivileges>,
+    host_facts_cache: Option<Arc<HostFactsCache>>,
+    /// Facts for this engine's scan, gathered on first use
+    host_facts: Option<(HostFacts, FactsSource)>,
+    skip_unprivileged: bool,
+    usage: Arc<StrategyUsageTracker>,
+    /// Objects collected for RUN EXTRACT, keyed by object id with the CTN
+    /// type whose collector produced them; reused by criteria of that type
+    extraction_data: HashMap<String, (String, CollectedData)>,
+    /// Controls from a control map file, overriding CONTROL declarations
+    control_map: Option<Arc<ControlMap>>,
+    /// Hashes of the policy being scanned, recorded in the result
+    provenance: ScanProvenance,
+    /// Filesystem the registry's collectors read
+    scan_target: Arc<ScanTarget>,
+    timeouts: Timeouts,
+    /// Collections reused by later criteria of the scan
+    collection_cache: CollectionCache,
+    /// When the running scan must end, from `timeouts.scan`
+    scan_deadline: Option<Instant>,
+    progress: ProgressReporter,
+    /// Approved exceptions for failing criteria
+    waivers: Option<Arc<WaiverSet>>,
+    /// Expired waivers that matched a criterion, reported in the result
+    expired_waivers: Vec<Waiver>,
+    /// Whether results name the host; off for privacy-sensitive pipelines
+    host_identification: bool,
+    /// Byte limit for each expected and observed value on a finding
+    observed_max_bytes: usize,
+}
+
+/// Where one criterion's execution time went, and which strategy served it
+#[derive(Debug, Default)]
+struct CriterionTimings {
+    strategy: Option<String>,
+    collection: Duration,
+    evaluation: Duration,
+}
+
+/// A criterion whose contract declares an elevated privilege requirement
+#[derive(Debug, Clone)]
+pub struct PrivilegeCheck {
+    pub ctn_node_id: CtnNodeId,
+    pub criterion_type: String,
+    pub required: PrivilegeRequirement,
+    pub satisfied: bool,
+}
+/// How a policy with no criteria is reported
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EmptyPolicy {
+    /// Refuse to produce a result
+    #[default]
+    Error,
+    /// Report the policy as compliant
+    Pass,
+    /// Report the policy as non-compliant
+    Fail,
+}
+
+/// How criteria that could not be decided count toward `results.passed`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ErrorMode {
+    /// An error or unknown criterion leaves its block undecided unless a
+    /// sibling decides it, so the policy does not pass
+    #[default]
+    Strict,
+    /// Errors and unknown criteria are left out, as if not applicable
+    Lenient,
+}
+
+/// A criterion type the registry has no strategy for, with the CTNs using it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct UnknownCriterionType {
+    pub criterion_type: String,
+    pub ctn_node_ids: Vec<CtnNodeId>,
+}
+
+impl std::fmt::Display for UnknownCriterionType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let nodes: Vec<String> = self
+            .ctn_node_ids
+            .iter()
+            .map(|id| format!("#{}", id))
+            .collect();
+        write!(f, "'{}' (CTN {})", self.criterion_type, nodes.join(", "))
+    }
 }
+
 impl ExecutionEngine {
     /// Create with strategy registry
     pub fn new(context: ExecutionContext, registry: Arc<CtnStrategyRegistry>) -> Self {
-        Self { context, registry }
+        Self {
+            context,
+            registry,
+            memory_monitor: None,
+            evidence_summarized: false,
+            field_evidence: false,
+            advisories: true,
+            strict_consistency: false,
+            allow_unknown_criteria: false,
+            fail_on_vacuous: false,
+            empty_policy: EmptyPolicy::default(),
+            error_mode: ErrorMode::default(),
+            consistency: ConsistencySnapshot::new(),
+            privileges: None,
+            host_facts_cache: None,
+            host_facts: None,
+            skip_unprivileged: false,
+            usage: Arc::new(StrategyUsageTracker::new()),
+            extraction_data: HashMap::new(),
+            control_map: None,
+            provenance: ScanProvenance::default(),
+            scan_target: ScanTarget::shared_host(),
+            timeouts: Timeouts::default(),
+            collection_cache: CollectionCache::default(),
+            scan_deadline: None,
+            progress: ProgressReporter::default(),
+            waivers: None,
+            expired_waivers: Vec::new(),
+            host_identification: true,
+            observed_max_bytes: DEFAULT_OBSERVED_MAX_BYTES,
+        }
+    }
+
+    /// Record strategy usage into a shared tracker (aggregate across engines)
+    pub fn with_usage_tracker(mut self, usage: Arc<StrategyUsageTracker>) -> Self {
+        self.usage = usage;
+        self
+    }
+
+    /// Strategy usage recorded so far
+    pub fn strategy_usage(&self) -> ScanPerformance {
+        self.usage.snapshot()
+    }
+
+    /// Use known privileges instead of probing the current process
+    pub fn with_privileges(mut self, privileges: EffectivePrivileges) -> Self {
+        self.privileges = Some(privileges);
+        self
+    }
+
+    /// Take host facts from a cache shared with other engines instead of
+    /// probing the host for this scan
+    pub fn with_host_facts(mut self, cache: Arc<HostFactsCache>) -> Self {
+        self.host_facts_cache = Some(cache);
+        self
+    }
+
+    /// Report criteria lacking privileges as not evaluated rather than errors
+    pub fn with_skip_unprivileged(mut self, skip: bool) -> Self {
+        self.skip_unprivileged = skip;
+        self
+    }
+
+    /// Record the fields each criterion compared in the scan result
+    pub fn with_field_evidence(mut self, enabled: bool) -> Self {
+        self.field_evidence = enabled;
+        self
+    }
+
+    /// Include executor advisories in the scan result (on by default)
+    pub fn with_advisories(mut self, enabled: bool) -> Self {
+        self.advisories = enabled;
+        self
+    }
+
+    /// Report criteria that saw a file change during the scan as errors
+    /// instead of only flagging them
+    pub fn with_strict_consistency(mut self, strict: bool) -> Self {
+        self.strict_consistency = strict;
+        self
+    }
+
+    /// Execute policies with unregistered criterion types, reporting each
+    /// such criterion as an error instead of refusing to start
+    pub fn with_allow_unknown_criteria(mut self, allow: bool) -> Self {
+        self.allow_unknown_criteria = allow;
+        self
+    }
+
+    /// Fail criteria that pass without observing anything instead of only
+    /// flagging them as vacuous
+    pub fn with_fail_on_vacuous(mut self, fail: bool) -> Self {
+        self.fail_on_vacuous = fail;
+        self
+    }
+
+    /// Whether errors and unknown criteria fail the policy (strict, the
+    /// default) or are left out of `results.passed` (lenient)
+    pub fn with_error_mode(mut self, error_mode: ErrorMode) -> Self {
+        self.error_mode = error_mode;
+        self
+    }
+
+    /// Map criteria to benchmark controls, taking precedence over the
+    /// policy's CONTROL declarations per framework
+    pub fn with_control_map(mut self, control_map: Arc<ControlMap>) -> Self {
+        self.control_map = Some(control_map);
+        self
+    }
+
+    /// Record the policy and compiled artifact hashes in the result; the
+    /// strategy set hash is always taken from the registry
+    pub fn with_provenance(mut self, provenance: ScanProvenance) -> Self {
+        self.provenance = provenance;
+        self
+    }
+
+    /// Scan a mounted root instead of the host
+    ///
+    /// The registry's collectors must be configured with the same target;
+    /// the engine only reports criteria whose collector cannot read it as
+    /// not evaluated and records the root in the result.
+    pub fn with_scan_target(mut self, scan_target: Arc<ScanTarget>) -> Self {
+        self.scan_target = scan_target;
+        self
+    }
+
+    /// Reuse collections through `cache`, e.g. with other bounds or
+    /// [`CollectionCache::disabled`]
+    pub fn with_collection_cache(mut self, cache: CollectionCache) -> Self {
+        self.collection_cache = cache;
+        self
+    }
+
+    /// Receive progress events while the scan runs
+    pub fn with_progress_handler(mut self, handler: ProgressHandler) -> Self {
+        self.progress = ProgressReporter::new(handler);
+        self
+    }
+
+    /// Report failing criteria covered by an unexpired waiver as waived
+    pub fn with_waivers(mut self, waivers: Arc<WaiverSet>) -> Self {
+        self.waivers = Some(waivers);
+        self
+    }
+
+    /// Keep at most `max_bytes` of each expected and observed value in a
+    /// finding's evidence; longer values are cut and marked truncated
+    pub fn with_observed_max_bytes(mut self, max_bytes: usize) -> Self {
+        self.observed_max_bytes = max_bytes;
+        self
+    }
+
+    /// Leave the hostname, IP address and asset id out of results
+    pub fn with_host_identification(mut self, enabled: bool) -> Self {
+        self.host_identification = enabled;
+        self
+    }
+
+    /// Limit each criterion, and optionally the whole scan, in time
+    pub fn with_timeouts(mut self, timeouts: Timeouts) -> Self {
+        self.timeouts = timeouts;
+        self
+    }
+
+    /// How to report a policy with no criteria
+    pub fn with_empty_policy(mut self, empty_policy: EmptyPolicy) -> Self {
+        self.empty_policy = empty_policy;
+        self
+    }
+
+    /// Criterion types in the policy that no registered strategy handles
+    pub fn unknown_criteria(&self) -> Vec<UnknownCriterionType> {
+        let mut unknown: Vec<UnknownCriterionType> = Vec::new();
+        for criterion in self.context.get_all_criteria() {
+            if self.registry.has_ctn_type(&criterion.criterion_type) {
+                continue;
+            }
+            match unknown
+                .iter_mut()
+                .find(|u| u.criterion_type == criterion.criterion_type)
+            {
+                Some(entry) => entry.ctn_node_ids.push(criterion.ctn_node_id),
+                None => unknown.push(UnknownCriterionType {
+                    criterion_type: criterion.criterion_type.clone(),
+                    ctn_node_ids: vec![criterion.ctn_node_id],
+                }),
+            }
+        }
+        unknown
+    }
+
+    /// What a scan would collect for each criterion and with which
+    /// strategy, without collecting anything
+    pub fn plan(&self) -> ScanPlan {
+        ScanPlan {
+            criteria: self
+                .context
+                .get_all_criteria()
+                .into_iter()
+                .map(|criterion| PlannedCriterion::new(criterion, &self.registry))
+                .collect(),
+            unmatched_criterion_types: self.unknown_criteria(),
+        }
+    }
+
+    /// Effective privileges used for requirement checks
+    pub fn privileges(&mut self) -> EffectivePrivileges {
+        match self.privileges {
+            Some(privileges) => privileges,
+            None => self.host_facts().0.privileges,
+        }
+    }
+
+    /// Host facts for this scan, from the shared cache or a fresh probe
+    fn host_facts(&mut self) -> &(HostFacts, FactsSource) {
+        let cache = &self.host_facts_cache;
+        self.host_facts.get_or_insert_with(|| match cache {
+            Some(cache) => cache.get(),
+            None => (HostFacts::probe(), FactsSource::Fresh),
+        })
+    }
+
+    /// Criteria that declare an elevated privilege requirement, in tree order
+    pub fn privilege_checks(&mut self) -> Vec<PrivilegeCheck> {
+        let privileges = self.privileges();
+        self.context
+            .criteria_tree
+            .get_all_criteria()
+            .into_iter()
+            .filter_map(|criterion| {
+                let required = self.required_privilege(criterion);
+                required.is_elevated().then(|| PrivilegeCheck {
+                    ctn_node_id: criterion.ctn_node_id,
+                    criterion_type: criterion.criterion_type.clone(),
+                    satisfied: privileges.satisfies(&required),
+                    required,
+                })
+            })
+            .collect()
+    }
+
+    /// Enforce a soft memory budget while executing
+    pub fn with_memory_budget(self, budget: MemoryBudget) -> Self {
+        self.with_memory_monitor(MemoryMonitor::new(budget))
+    }
+
+    /// Use a preconfigured memory monitor (custom samplers, tests)
+    pub fn with_memory_monitor(mut self, monitor: MemoryMonitor) -> Self {
+        self.memory_monitor = Some(monitor);
+        self
+    }
+
+    /// Degradation steps applied during the last execution
+    pub fn degradation_steps(&self) -> &[DegradationStep] {
+        self.memory_monitor
+            .as_ref()
+            .map(|m| m.steps())
+            .unwrap_or(&[])
     }
 
     /// Main execution entry point
@@ -45,8 +427,50 @@ impl ExecutionEngine {
                 reason: e,
             })?;
 
-        // Execute the criteria tree recursively
-        let tree_result = self.execute_tree(&self.context.criteria_tree.clone())?;
+        if self.context.count_criteria() == 0 {
+            return self.empty_policy_result();
+        }
+
+        if !self.allow_unknown_criteria {
+            let unknown = self.unknown_criteria();
+            if !unknown.is_empty() {
+                return Err(ExecutionError::UnknownCriterionTypes { unknown });
+            }
+        }
+        self.validate_object_elements()?;
+
+        // A real clock is anchored to the start of execution, not of resolution
+        if !self.context.clock.is_fixed() {
+            self.context.clock = ScanClock::system();
+        }
+
+        self.scan_deadline = self.timeouts.scan.map(|timeout| Instant::now() + timeout);
+        let _scan_deadline = self.scan_deadline.map(deadline::enter);
+        self.progress.scan_started(self.context.count_criteria());
+
+        let privileges = self.privileges();
+        log_debug!("Effective privileges",
+            "root" => privileges.is_root(),
+            "capabilities" => format!("{:#x}", privileges.capabilities)
+        );
+
+        // Collect for extraction, compute variables, substitute
+        self.run_scan_time_phases()?;
+
+        // Relative datetimes (`now-30d`) are read against this scan's clock
+        let now = self.context.clock.now().timestamp();
+        ResolutionEngine::resolve_datetime_fields(&mut self.context, now).map_err(|e| {
+            ExecutionError::ExecutorFailed {
+                ctn_type: "datetime_resolution".to_string(),
+                reason: e.to_string(),
+            }
+        })?;
+
+        // Collect for criteria and evaluate, recursively over the tree
+        let mut tree_result = self.execute_tree(&self.context.criteria_tree.clone())?;
+        if !self.consistency.mismatches().is_empty() {
+            self.flag_inconsistent_criteria(&mut tree_result);
+        }
 
         // Calculate flat statistics from tree (for metrics/dashboards)
         let stats = tree_result.calculate_stats();
@@ -54,39 +478,168 @@ impl ExecutionEngine {
         // Convert tree results to findings
         let findings = self.tree_result_to_findings(&tree_result, vec![])?;
 
-        // Extract metadata
-        let esp_metadata = self.extract_esp_metadata()?;
-        let host = HostContext::from_system();
-        let user_context = UserContext::from_environment();
-
-        // Generate scan ID
-        let scan_id = format!(
-            "scan_{}",
-            std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap()
-                .as_secs()
-        );
-
         // Create scan result
-        let mut scan_result = ScanResult::new(scan_id, esp_metadata, host, user_context);
+        let clock = self.context.clock;
+        let mut scan_result = self.new_scan_result()?;
 
         // Update criteria counts with flat statistics
         scan_result.update_criteria_counts(stats.total, stats.passed, stats.failed, stats.errors);
+        scan_result.set_not_evaluated_count(stats.not_evaluated);
+        scan_result.set_not_applicable_count(stats.not_applicable);
+        scan_result.set_waived_count(stats.waived);
+        scan_result.set_path_denied_count(stats.path_denied);
+        scan_result.set_vacuous_pass_count(stats.vacuous);
+        scan_result.performance = self.usage.snapshot();
+        scan_result.performance.collection_cache = self.collection_cache.stats();
+        scan_result.plan = self.context.plan.clone();
+        if self.field_evidence {
+            tree_result.collect_evidence(&mut scan_result.evidence);
+        }
+        if self.advisories {
+            tree_result.collect_advisories(&mut scan_result.advisories);
+        }
+        let criteria: HashMap<CtnNodeId, &ExecutableCriterion> = self
+            .context
+            .get_all_criteria()
+            .into_iter()
+            .map(|criterion| (criterion.ctn_node_id, criterion))
+            .collect();
+        tree_result.collect_outcomes(&criteria, &mut scan_result.criteria);
+        let mut outcomes = Vec::new();
+        tree_result.collect_control_outcomes(&mut outcomes);
+        scan_result.controls = controls::summarize(outcomes);
+        scan_result.consistency = self.consistency.mismatches().to_vec();
+        scan_result.expired_waivers = std::mem::take(&mut self.expired_waivers);
+        scan_result.degraded = self
+            .memory_monitor
+            .as_ref()
+            .map(|m| m.is_degraded())
+            .unwrap_or(false);
 
         // Set findings
         scan_result.results.findings = findings;
 
         // Finalize calculates timestamps and sets check.status based on flat stats
-        scan_result.finalize();
+        scan_result.finalize_at(&clock);
+        self.record_target(&mut scan_result);
 
         // CRITICAL: Override with tree logic for overall pass/fail
         // This respects CRI OR/AND/NOT structure, done AFTER finalize()
-        scan_result.results.passed = tree_result.status == ComplianceStatus::Pass;
+        scan_result.results.passed = tree_result.passes(self.error_mode);
+        self.progress.scan_completed(scan_result.results.passed);
+
+        Ok(scan_result)
+    }
 
+    /// Result for a policy with no criteria; nothing is collected
+    fn empty_policy_result(&mut self) -> Result<ScanResult, ExecutionError> {
+        let passed = match self.empty_policy {
+            EmptyPolicy::Error => return Err(ExecutionError::EmptyPolicy),
+            EmptyPolicy::Pass => true,
+            EmptyPolicy::Fail => false,
+        };
+
+        let clock = self.context.clock;
+        let mut scan_result = self.new_scan_result()?;
+        scan_result.empty_policy = true;
+        scan_result.plan = self.context.plan.clone();
+        scan_result.finalize_at(&clock);
+        self.record_target(&mut scan_result);
+        if !passed {
+            scan_result.results.check.status = ResultComplianceStatus::NonCompliant;
+        }
+        scan_result.results.passed = passed;
+        Ok(scan_result)
+    }
+
+    /// Scan result carrying the policy metadata and host facts, started at
+    /// the scan clock's instant
+    fn new_scan_result(&mut self) -> Result<ScanResult, ExecutionError> {
+        let esp_metadata = self.extract_esp_metadata()?;
+        let (facts, source) = self.host_facts().clone();
+
+        // Scan ID from the scan clock so fixed-clock scans are reproducible
+        let clock = self.context.clock;
+        let mut scan_result = ScanResult::new_at(
+            format!("scan_{}", clock.now().timestamp()),
+            esp_metadata,
+            facts.host.clone(),
+            facts.user.clone(),
+            &clock,
+        );
+        scan_result.metadata.host_facts = Some(facts.provenance(source));
+        scan_result.metadata.provenance = self
+            .provenance
+            .clone()
+            .with_strategy_set_hash(self.registry.strategy_set_hash());
+        scan_result.metadata.scan_root = self
+            .scan_target
+            .root()
+            .map(|root| root.display().to_string());
+        if !self.host_identification {
+            let host = &mut scan_result.metadata.host;
+            host.hostname = REDACTED_HOSTNAME.to_string();
+            host.ip_address = None;
+            host.asset_id = None;
+        }
         Ok(scan_result)
     }
 
+    /// Record the host and environment of a finished scan; a mounted root
+    /// reports its own OS release and no kernel
+    fn record_target(&mut self, scan_result: &mut ScanResult) {
+        let scan_root = self.scan_target.clone();
+        let (facts, _) = self.host_facts();
+        let (os_release, kernel_version) = match scan_root.root() {
+            Some(root) => (OsRelease::read(root), None),
+            None => (facts.os_release.clone(), facts.kernel_version.clone()),
+        };
+        let target = TargetInfo::new(
+            &facts.host.hostname,
+            &os_release,
+            kernel_version,
+            &scan_result.metadata.timestamp,
+            &scan_result.metadata.provenance.policy_hash,
+        );
+        scan_result.target = Some(if self.host_identification {
+            target
+        } else {
+            target.without_hostname()
+        });
+    }
+
+    /// Unexpired waiver covering a failed criterion; expired waivers that
+    /// match it are recorded for the result and logged
+    fn find_waiver(&mut self, criterion: &ExecutableCriterion) -> Option<Waiver> {
+        let waivers = self.waivers.clone()?;
+        let esp_scan_id = self
+            .context
+            .metadata
+            .as_ref()
+            .and_then(|metadata| metadata.fields.get("esp_scan_id"))
+            .cloned()
+            .unwrap_or_default();
+        let host = self.host_facts().0.host.hostname.clone();
+        let target = WaiverTarget {
+            criterion_type: &criterion.criterion_type,
+            ctn_node_id: criterion.ctn_node_id,
+            esp_scan_id: &esp_scan_id,
+            host: &host,
+        };
+        let found = waivers.find(&target, self.context.clock.now().date_naive());
+        for expired in found.expired {
+            if !self.expired_waivers.contains(expired) {
+                log_warning!("Ignoring expired waiver",
+                    "criterion" => criterion_id(&criterion.criterion_type, criterion.ctn_node_id),
+                    "expired" => expired.expires.to_string(),
+                    "justification" => expired.justification.as_str()
+                );
+                self.expired_waivers.push(expired.clone());
+            }
+        }
+        found.active.cloned()
+    }
+
     /// Extract ESP metadata from execution context
     fn extract_esp_metadata(&self) -> Result<EspMetadata, ExecutionError> {
         let metadata =
@@ -111,22 +664,87 @@ impl ExecutionEngine {
     ) -> Result<TreeResult, ExecutionError> {
         match tree {
             ExecutableCriteriaTree::Criterion(criterion) => {
-                // Clone the criterion so we can mutate it
-                let mut mutable_criterion = criterion.clone();
-
-                // Execute with mutable reference
-                let result = self.execute_single_criterion(&mut mutable_criterion)?;
+                let started = Instant::now();
+                self.progress
+                    .criterion_started(criterion.ctn_node_id, &criterion.criterion_type);
+                let required = self.required_privilege(criterion);
+                let result = if self.memory_budget_exhausted() {
+                    CtnExecutionResult::not_evaluated(
+                        criterion.criterion_type.clone(),
+                        MEMORY_BUDGET_EXCEEDED,
+                    )
+                } else if self.scan_deadline_passed() {
+                    self.scan_deadline_result(criterion)
+                } else if self.offline_unsupported(&criterion.criterion_type) {
+                    self.offline_target_result(criterion)
+                } else if !self.privileges().satisfies(&required) {
+                    self.insufficient_privileges_result(criterion, &required)
+                } else {
+                    // Clone the criterion so we can mutate it
+                    let mut mutable_criterion = criterion.clone();
+
+                    // Execute with mutable reference
+                    let mut result = match self.execute_single_criterion(&mut mutable_criterion) {
+                        Err(ExecutionError::PathDenied {
+                            object_id,
+                            path,
+                            reason,
+                        }) => Self::path_denied_result(criterion, &object_id, &path, &reason),
+                        Err(ExecutionError::DataUnavailable { object_id, reason }) => {
+                            Self::data_unavailable_result(criterion, &object_id, &reason)
+                        }
+                        Err(ExecutionError::MissingCapability {
+                            object_id,
+                            capability,
+                        }) => Self::missing_capability_result(criterion, &object_id, &capability),
+                        Err(ExecutionError::OfflineTargetUnsupported { .. }) => {
+                            self.offline_target_result(criterion)
+                        }
+                        Err(ExecutionError::CriterionTimeout {
+                            collector, phase, ..
+                        }) => self.criterion_timeout_result(criterion, &collector, &phase),
+                        Err(ExecutionError::NoContractRegistered { reason, .. })
+                        | Err(ExecutionError::NoCollectorRegistered { reason, .. })
+                        | Err(ExecutionError::NoExecutorRegistered { reason, .. }) => {
+                            self.usage.record_unmatched(&criterion.criterion_type);
+                            Self::no_strategy_result(criterion, &reason)
+                        }
+                        other => other?,
+                    };
+                    if self.evidence_summarized {
+                        result.summarize_evidence();
+                    }
+                    self.check_memory_budget();
+                    result
+                };
+                let waiver = match result.status {
+                    ComplianceStatus::Fail => self.find_waiver(criterion),
+                    _ => None,
+                };
+                let status = match waiver {
+                    Some(_) => ComplianceStatus::Waived,
+                    None => result.status,
+                };
+                self.progress.criterion_completed(
+                    criterion.ctn_node_id,
+                    &criterion.criterion_type,
+                    status,
+                    started.elapsed(),
+                );
 
                 Ok(TreeResult {
-                    status: result.status,
+                    status,
                     logical_op: None,
                     negated: false,
                     ctn_results: vec![CtnResult {
                         ctn_node_id: criterion.ctn_node_id,
                         criterion_type: criterion.criterion_type.clone(),
-                        status: result.status,
+                        status,
                         execution_result: result,
                         execution_time_ms: 0,
+                        controls: self.criterion_controls(criterion),
+                        severity: self.criterion_severity(criterion),
+                        waiver,
                     }],
                     child_results: vec![],
                 })
@@ -136,10 +754,19 @@ impl ExecutionEngine {
                 negate,
                 children,
             } => {
-                let mut child_results = Vec::new();
+                let mut child_results: Vec<TreeResult> = Vec::new();
+                let mut children_summarized = false;
                 for child in children {
                     let child_result = self.execute_tree(child)?;
                     child_results.push(child_result);
+
+                    // Siblings completed before evidence summarization still hold full evidence
+                    if self.evidence_summarized && !children_summarized {
+                        child_results
+                            .iter_mut()
+                            .for_each(TreeResult::summarize_evidence);
+                        children_summarized = true;
+                    }
                 }
 
                 let combined = self.apply_logical_op(&child_results, *logical_op);
@@ -156,155 +783,592 @@ impl ExecutionEngine {
         }
     }
 
-    /// Apply logical operator to child tree results
-    fn apply_logical_op(&self, children: &[TreeResult], op: LogicalOp) -> ComplianceStatus {
-        if children.is_empty() {
-            return ComplianceStatus::Error;
+    /// Controls a criterion implements, after applying the control map
+    fn criterion_controls(&self, criterion: &ExecutableCriterion) -> Vec<Control> {
+        let declared: Vec<Control> = criterion.controls.iter().map(Control::from).collect();
+        match &self.control_map {
+            Some(map) => map.resolve(
+                &criterion_id(&criterion.criterion_type, criterion.ctn_node_id),
+                &declared,
+            ),
+            None => declared,
         }
+    }
 
-        match op {
-            LogicalOp::And => {
-                // ALL children must pass
-                if children.iter().all(|c| c.status == ComplianceStatus::Pass) {
-                    ComplianceStatus::Pass
-                } else if children.iter().any(|c| c.status == ComplianceStatus::Error) {
-                    ComplianceStatus::Error
-                } else {
-                    ComplianceStatus::Fail
-                }
-            }
-            LogicalOp::Or => {
-                // ANY child passes = pass
-                if children.iter().any(|c| c.status == ComplianceStatus::Pass) {
-                    ComplianceStatus::Pass
-                } else if children.iter().all(|c| c.status == ComplianceStatus::Error) {
-                    ComplianceStatus::Error
-                } else {
-                    ComplianceStatus::Fail
-                }
+    /// Severity the policy assigns to a criterion: its CTN SEVERITY, else the
+    /// META severity
+    fn criterion_severity(&self, criterion: &ExecutableCriterion) -> PolicySeverity {
+        let meta = || {
+            self.context
+                .metadata
+                .as_ref()
+                .and_then(|m| m.fields.get("severity"))
+        };
+        criterion
+            .severity
+            .as_ref()
+            .or_else(meta)
+            .map(|label| PolicySeverity::from_label(label))
+            .unwrap_or_default()
+    }
+
+    /// Check every object's BEHAVIOR and PARAMETERS elements against its
+    /// criterion's contract
+    ///
+    /// Runs before any collection so a misspelled behavior fails the scan
+    /// instead of being ignored, and a parameter that is unsafe as a command
+    /// argument never reaches a collector. Unregistered types are reported
+    /// later, when their criteria execute.
+    fn validate_object_elements(&self) -> Result<(), ExecutionError> {
+        let mut failed_type = None;
+        let mut errors = Vec::new();
+        for criterion in self.context.get_all_criteria() {
+            let problems = self.registry.check_object_elements(criterion);
+            if problems.is_empty() {
+                continue;
             }
+            failed_type.get_or_insert_with(|| criterion.criterion_type.clone());
+            errors.extend(
+                problems
+                    .into_iter()
+                    .map(|problem| format!("CTN #{} {}", problem.ctn_node_id, problem.message)),
+            );
         }
-    }
 
-    /// Execute a single criterion with timeout protection
-    fn execute_single_criterion(
-        &mut self,
-        criterion: &mut ExecutableCriterion,
-    ) -> Result<CtnExecutionResult, ExecutionError> {
-        use std::time::Instant;
+        match failed_type {
+            Some(ctn_type) => Err(ExecutionError::ContractValidationFailed { ctn_type, errors }),
+            None => Ok(()),
+        }
+    }
 
-        const CTN_TIMEOUT_SECS: u64 = 30;
-        let start = Instant::now();
+    /// Privilege declared by the contract for a criterion's CTN type
+    ///
+    /// Unregistered types report no requirement; execution surfaces that error.
+    fn required_privilege(&self, criterion: &ExecutableCriterion) -> PrivilegeRequirement {
+        self.registry
+            .get_ctn_contract(&criterion.criterion_type)
+            .map(|contract| contract.collection_strategy.required_privilege.clone())
+            .unwrap_or_default()
+    }
 
-        log_debug!("Starting CTN execution",
+    /// Result for a criterion the process lacks privileges to collect
+    fn insufficient_privileges_result(
+        &self,
+        criterion: &ExecutableCriterion,
+        required: &PrivilegeRequirement,
+    ) -> CtnExecutionResult {
+        log_info!("Skipping criterion: insufficient privileges",
             "ctn_type" => &criterion.criterion_type,
-            "ctn_node_id" => criterion.ctn_node_id
+            "ctn_node_id" => criterion.ctn_node_id,
+            "required" => required.as_str()
         );
 
-        // Get contract for this CTN type
-        let contract = self
-            .registry
-            .get_ctn_contract(&criterion.criterion_type)
-            .map_err(|e| ExecutionError::NoContractRegistered {
-                ctn_type: criterion.criterion_type.clone(),
-                reason: e.to_string(),
-            })?;
+        let mut result = if self.skip_unprivileged {
+            CtnExecutionResult::not_evaluated(
+                criterion.criterion_type.clone(),
+                INSUFFICIENT_PRIVILEGES,
+            )
+        } else {
+            CtnExecutionResult::error(
+                criterion.criterion_type.clone(),
+                format!("Insufficient privileges: requires {}", required),
+            )
+            .with_details(serde_json::json!({ "reason": INSUFFICIENT_PRIVILEGES }))
+        };
+        result.details["required_privilege"] = serde_json::json!(required.as_str());
+        result
+    }
 
-        let contract_clone = Arc::clone(&contract);
+    /// Result for a criterion whose object touched a path the path policy denies
+    fn path_denied_result(
+        criterion: &ExecutableCriterion,
+        object_id: &str,
+        path: &str,
+        reason: &str,
+    ) -> CtnExecutionResult {
+        log_info!("Criterion blocked by path policy",
+            "ctn_type" => &criterion.criterion_type,
+            "ctn_node_id" => criterion.ctn_node_id,
+            "path" => path
+        );
 
-        // Get collector for this CTN type
-        let collector = self
-            .registry
-            .get_collector_for_ctn(&criterion.criterion_type)
-            .map_err(|e| ExecutionError::NoCollectorRegistered {
-                ctn_type: criterion.criterion_type.clone(),
-                reason: e.to_string(),
-            })?;
+        CtnExecutionResult::error(
+            criterion.criterion_type.clone(),
+            format!("Path denied by scanner policy: {}", reason),
+        )
+        .with_details(serde_json::json!({
+            "reason": PATH_DENIED,
+            "object_id": object_id,
+            "path": path,
+        }))
+    }
 
-        // Check timeout after setup
-        if start.elapsed().as_secs() > CTN_TIMEOUT_SECS {
-            return Err(ExecutionError::ExecutorFailed {
-                ctn_type: criterion.criterion_type.clone(),
-                reason: format!(
-                    "CTN execution exceeded timeout of {}s during setup",
-                    CTN_TIMEOUT_SECS
-                ),
-            });
-        }
+    /// Result for a criterion whose collector's data source has nothing for it
+    fn data_unavailable_result(
+        criterion: &ExecutableCriterion,
+        object_id: &str,
+        reason: &str,
+    ) -> CtnExecutionResult {
+        log_info!("Criterion not evaluated: data unavailable",
+            "ctn_type" => &criterion.criterion_type,
+            "ctn_node_id" => criterion.ctn_node_id,
+            "reason" => reason
+        );
 
-        // Attempt batch collection if supported
-        let mut collected_data =
-            if collector.supports_batch_collection() && !criterion.objects.is_empty() {
-                log_debug!("Attempting batch collection",
-                    "ctn_type" => &criterion.criterion_type,
-                    "object_count" => criterion.objects.len()
-                );
+        let mut result =
+            CtnExecutionResult::not_evaluated(criterion.criterion_type.clone(), DATA_UNAVAILABLE);
+        result.details["object_id"] = serde_json::json!(object_id);
+        result.details["detail"] = serde_json::json!(reason);
+        result
+    }
 
-                let object_refs: Vec<&ExecutableObject> = criterion.objects.iter().collect();
-                match collector.collect_batch(object_refs, &contract) {
-                    Ok(batch_data) => {
-                        log_debug!("Batch collection successful",
-                            "ctn_type" => &criterion.criterion_type,
-                            "objects_collected" => batch_data.len()
-                        );
-                        batch_data
-                    }
-                    Err(e) => {
-                        log_debug!("Batch collection failed, falling back to individual",
-                            "ctn_type" => &criterion.criterion_type,
-                            "error" => e
-                        );
-                        HashMap::new()
-                    }
-                }
-            } else {
-                HashMap::new()
-            };
+    /// Result for a criterion whose collector lacks a tool it needs, such as
+    /// a command from a package that is not installed
+    fn missing_capability_result(
+        criterion: &ExecutableCriterion,
+        object_id: &str,
+        capability: &str,
+    ) -> CtnExecutionResult {
+        log_warning!("Criterion collector is missing a capability",
+            "ctn_type" => &criterion.criterion_type,
+            "ctn_node_id" => criterion.ctn_node_id,
+            "capability" => capability
+        );
 
-        // Individual collection for any objects not batch-collected
-        for object in &criterion.objects {
-            if !collected_data.contains_key(&object.identifier) {
-                let data = self.collect_data_for_object(object, &contract)?;
-                collected_data.insert(object.identifier.clone(), data);
-            }
-        }
+        CtnExecutionResult::error(
+            criterion.criterion_type.clone(),
+            format!("Required capability missing: {}", capability),
+        )
+        .with_details(serde_json::json!({
+            "reason": MISSING_CAPABILITY,
+            "object_id": object_id,
+            "capability": capability,
+        }))
+    }
 
-        log_debug!("Data collection complete",
+    /// Whether the scan targets a mounted root that the collector for
+    /// `ctn_type` cannot read; unregistered types are left to execution
+    fn offline_unsupported(&self, ctn_type: &str) -> bool {
+        self.scan_target.is_offline()
+            && self
+                .registry
+                .get_collector_for_ctn(ctn_type)
+                .is_ok_and(|collector| !collector.supports_offline_target(ctn_type))
+    }
+
+    /// Result for a criterion whose collector would observe the scanning
+    /// host instead of the offline target
+    fn offline_target_result(&self, criterion: &ExecutableCriterion) -> CtnExecutionResult {
+        log_info!("Criterion not evaluated: not supported for offline target",
             "ctn_type" => &criterion.criterion_type,
-            "objects_collected" => collected_data.len()
+            "ctn_node_id" => criterion.ctn_node_id
         );
 
-        // ========================================================================
-        // UPDATED: Apply SET-level filters FIRST - pass mutable criterion
-        // ========================================================================
-        collected_data = self.apply_set_filters(collected_data, criterion)?; // ✅ Changed
+        let root = self
+            .scan_target
+            .root()
+            .map(|root| root.display().to_string())
+            .unwrap_or_default();
+        let mut result = CtnExecutionResult::not_evaluated(
+            criterion.criterion_type.clone(),
+            OFFLINE_TARGET_UNSUPPORTED,
+        );
+        result.details["detail"] = serde_json::json!(format!(
+            "'{}' is not supported for offline target '{}'",
+            criterion.criterion_type, root
+        ));
+        result.details["scan_root"] = serde_json::json!(root);
+        result
+    }
 
-        log_debug!("After SET filters",
+    /// Whether the scan deadline, if any, has passed
+    fn scan_deadline_passed(&self) -> bool {
+        self.scan_deadline
+            .is_some_and(|scan_deadline| Instant::now() >= scan_deadline)
+    }
+
+    /// Result for a criterion reached after the scan deadline; it is not
+    /// started
+    fn scan_deadline_result(&self, criterion: &ExecutableCriterion) -> CtnExecutionResult {
+        log_warning!("Criterion skipped: scan deadline exceeded",
             "ctn_type" => &criterion.criterion_type,
-            "objects_remaining" => collected_data.len(),
-            "expected_count" => criterion.expected_object_count()  // ✅ NEW
+            "ctn_node_id" => criterion.ctn_node_id
         );
 
-        // ========================================================================
-        // UPDATED: Apply object-level filters - pass mutable criterion
-        // ========================================================================
-        collected_data = self.apply_object_filters(collected_data, criterion, &contract)?; // ✅ Changed
+        let timeout_ms = self.timeouts.scan.unwrap_or_default().as_millis() as u64;
+        CtnExecutionResult::error(
+            criterion.criterion_type.clone(),
+            format!(
+                "Not started: the scan deadline of {}ms has passed",
+                timeout_ms
+            ),
+        )
+        .with_details(serde_json::json!({
+            "reason": SCAN_DEADLINE_EXCEEDED,
+            "timeout_ms": timeout_ms,
+        }))
+    }
 
-        log_debug!("After object filters",
+    /// Result for a criterion that ran out of time, naming the collector
+    /// that was running; the scan deadline is blamed when it was the
+    /// earlier of the two
+    fn criterion_timeout_result(
+        &self,
+        criterion: &ExecutableCriterion,
+        collector: &str,
+        phase: &str,
+    ) -> CtnExecutionResult {
+        let (reason, timeout) = if self.scan_deadline_passed() {
+            (
+                SCAN_DEADLINE_EXCEEDED,
+                self.timeouts.scan.unwrap_or_default(),
+            )
+        } else {
+            (CRITERION_TIMEOUT, self.timeouts.criterion)
+        };
+        let timeout_ms = timeout.as_millis() as u64;
+        log_warning!("Criterion timed out",
             "ctn_type" => &criterion.criterion_type,
-            "objects_remaining" => collected_data.len(),
-            "expected_count" => criterion.expected_object_count()  // ✅ NEW
+            "ctn_node_id" => criterion.ctn_node_id,
+            "collector" => collector,
+            "phase" => phase
         );
 
-        // Check timeout after collection and filtering
-        if start.elapsed().as_secs() > CTN_TIMEOUT_SECS {
-            return Err(ExecutionError::ExecutorFailed {
+        CtnExecutionResult::error(
+            criterion.criterion_type.clone(),
+            format!(
+                "Timed out after {}ms during {} (collector '{}')",
+                timeout_ms, phase, collector
+            ),
+        )
+        .with_details(serde_json::json!({
+            "reason": reason,
+            "collector": collector,
+            "phase": phase,
+            "timeout_ms": timeout_ms,
+        }))
+    }
+
+    /// Result for a criterion whose type has no registered strategy
+    fn no_strategy_result(criterion: &ExecutableCriterion, reason: &str) -> CtnExecutionResult {
+        log_warning!("No strategy registered for criterion type",
+            "ctn_type" => &criterion.criterion_type,
+            "ctn_node_id" => criterion.ctn_node_id
+        );
+
+        CtnExecutionResult::error(
+            criterion.criterion_type.clone(),
+            format!(
+                "No strategy registered for criterion type '{}': {}",
+                criterion.criterion_type, reason
+            ),
+        )
+        .with_details(serde_json::json!({ "reason": NO_STRATEGY_REGISTERED }))
+    }
+
+    /// Whether the memory budget has forced remaining criteria to be skipped
+    fn memory_budget_exhausted(&self) -> bool {
+        self.memory_monitor
+            .as_ref()
+            .map(|m| m.is_aborted())
+            .unwrap_or(false)
+    }
+
+    /// Sample memory after a criterion and apply the next degradation step if over budget
+    fn check_memory_budget(&mut self) {
+        let Some(step) = self
+            .memory_monitor
+            .as_mut()
+            .and_then(|monitor| monitor.after_criterion())
+        else {
+            return;
+        };
+
+        let peak = self
+            .memory_monitor
+            .as_ref()
+            .map(|m| m.peak_rss_bytes())
+            .unwrap_or(0);
+        log_info!("Memory budget exceeded, degrading scan",
+            "step" => step.as_str(),
+            "peak_rss_bytes" => peak
+        );
+
+        match step {
+            DegradationStep::FlushSinks => {
+                // Results are only written after execution; flush buffered console output
+                use std::io::Write;
+                let _ = std::io::stdout().flush();
+                let _ = std::io::stderr().flush();
+            }
+            DegradationStep::SummarizeEvidence => self.evidence_summarized = true,
+            DegradationStep::DisableCommandCache => self.collection_cache.disable(),
+            DegradationStep::Abort => {}
+        }
+    }
+
+    /// Compare the files this criterion's collectors saw against earlier ones
+    fn record_observations(
+        &mut self,
+        criterion: &ExecutableCriterion,
+        collected_data: &HashMap<String, CollectedData>,
+    ) {
+        let id = criterion_id(&criterion.criterion_type, criterion.ctn_node_id);
+        for data in collected_data.values() {
+            for observation in &data.metadata.observed_files {
+                if let Some(mismatch) = self.consistency.observe(&id, observation) {
+                    log_warning!("File changed during scan",
+                        "path" => &mismatch.path,
+                        "first_criterion" => &mismatch.first_criterion,
+                        "second_criterion" => &mismatch.second_criterion
+                    );
+                }
+            }
+        }
+    }
+
+    /// Flag both criteria of every consistency mismatch; in strict mode they
+    /// become errors and the tree statuses are recomputed
+    fn flag_inconsistent_criteria(&self, tree: &mut TreeResult) {
+        for ctn in &mut tree.ctn_results {
+            let id = criterion_id(&ctn.criterion_type, ctn.ctn_node_id);
+            for mismatch in self.consistency.mismatches() {
+                if !mismatch.involves(&id) {
+                    continue;
+                }
+                let message = format!(
+                    "{}: '{}' changed between {} and {}",
+                    DATA_CHANGED_DURING_SCAN,
+                    mismatch.path,
+                    mismatch.first_criterion,
+                    mismatch.second_criterion
+                );
+                let result = &mut ctn.execution_result;
+                result.execution_metadata.warnings.push(message.clone());
+                result
+                    .execution_metadata
+                    .advisories
+                    .push(Advisory::new(message.clone()));
+                if self.strict_consistency && ctn.status != ComplianceStatus::Error {
+                    ctn.status = ComplianceStatus::Error;
+                    result.status = ComplianceStatus::Error;
+                    result.message = message;
+                    result.details = serde_json::json!({
+                        "reason": DATA_CHANGED_DURING_SCAN,
+                        "path": mismatch.path,
+                    });
+                }
+            }
+        }
+        for child in &mut tree.child_results {
+            self.flag_inconsistent_criteria(child);
+        }
+        if self.strict_consistency {
+            tree.status = match tree.logical_op {
+                Some(op) => {
+                    let combined = self.apply_logical_op(&tree.child_results, op);
+                    if tree.negated {
+                        combined.negate()
+                    } else {
+                        combined
+                    }
+                }
+                None => tree
+                    .ctn_results
+                    .first()
+                    .map(|ctn| ctn.status)
+                    .unwrap_or(tree.status),
+            };
+        }
+    }
+
+    /// Apply logical operator to child tree results
+    fn apply_logical_op(&self, children: &[TreeResult], op: LogicalOp) -> ComplianceStatus {
+        if children.is_empty() {
+            return ComplianceStatus::Error;
+        }
+
+        combine(op, children.iter().map(|c| c.status))
+    }
+
+    /// Execute a single criterion with timeout protection
+    fn execute_single_criterion(
+        &mut self,
+        criterion: &mut ExecutableCriterion,
+    ) -> Result<CtnExecutionResult, ExecutionError> {
+        let mut timings = CriterionTimings::default();
+        let result = self.run_criterion(criterion, &mut timings);
+
+        if let Some(strategy) = &timings.strategy {
+            let error = !matches!(&result, Ok(r) if r.status != ComplianceStatus::Error);
+            self.usage.record_invocation(
+                &criterion.criterion_type,
+                strategy,
+                timings.collection,
+                timings.evaluation,
+                error,
+            );
+        }
+
+        result
+    }
+
+    /// Collect and evaluate one criterion, filling in `timings` as it goes
+    fn run_criterion(
+        &mut self,
+        criterion: &mut ExecutableCriterion,
+        timings: &mut CriterionTimings,
+    ) -> Result<CtnExecutionResult, ExecutionError> {
+        let start = Instant::now();
+        let _deadline = deadline::enter(start + self.timeouts.criterion);
+
+        log_debug!("Starting CTN execution",
+            "ctn_type" => &criterion.criterion_type,
+            "ctn_node_id" => criterion.ctn_node_id
+        );
+
+        // Get contract for this CTN type
+        let contract = self
+            .registry
+            .get_ctn_contract(&criterion.criterion_type)
+            .map_err(|e| ExecutionError::NoContractRegistered {
                 ctn_type: criterion.criterion_type.clone(),
-                reason: format!(
-                    "CTN execution exceeded timeout of {}s during collection",
-                    CTN_TIMEOUT_SECS
-                ),
-            });
+                reason: e.to_string(),
+            })?;
+
+        let contract_clone = Arc::clone(&contract);
+
+        // Get collector for this CTN type
+        let collector = self
+            .registry
+            .get_collector_for_ctn(&criterion.criterion_type)
+            .map_err(|e| ExecutionError::NoCollectorRegistered {
+                ctn_type: criterion.criterion_type.clone(),
+                reason: e.to_string(),
+            })?;
+        timings.strategy = Some(collector.collector_id().to_string());
+        let (ctn_type, collector_id) = (
+            criterion.criterion_type.clone(),
+            collector.collector_id().to_string(),
+        );
+        let timed_out = move |phase: &str| ExecutionError::CriterionTimeout {
+            ctn_type: ctn_type.clone(),
+            collector: collector_id.clone(),
+            phase: phase.to_string(),
+        };
+
+        // Check timeout after setup
+        if deadline::expired() {
+            return Err(timed_out("setup"));
+        }
+
+        // Objects already collected for extraction or by earlier criteria
+        // are not collected again
+        let mut collected_data = self.shared_extraction_data(criterion);
+        let cacheable = self.collection_cache.is_enabled()
+            && collector.supports_result_caching(&criterion.criterion_type);
+        let cache_key = |object: &ExecutableObject| {
+            CacheKey::new(collector.collector_id(), &contract.ctn_type, object)
+        };
+        if cacheable {
+            for object in &criterion.objects {
+                if collected_data.contains_key(&object.identifier) {
+                    continue;
+                }
+                if let Some(data) = self
+                    .collection_cache
+                    .get(&cache_key(object), &object.identifier)
+                {
+                    collected_data.insert(object.identifier.clone(), data);
+                }
+            }
+        }
+        let object_refs: Vec<&ExecutableObject> = criterion
+            .objects
+            .iter()
+            .filter(|o| !collected_data.contains_key(&o.identifier))
+            .collect();
+
+        // Attempt batch collection if supported
+        if collector.supports_batch_collection() && !object_refs.is_empty() {
+            log_debug!("Attempting batch collection",
+                "ctn_type" => &criterion.criterion_type,
+                "object_count" => object_refs.len()
+            );
+
+            match collector.collect_batch(object_refs, &contract) {
+                Ok(batch_data) => {
+                    log_debug!("Batch collection successful",
+                        "ctn_type" => &criterion.criterion_type,
+                        "objects_collected" => batch_data.len()
+                    );
+                    if cacheable {
+                        for object in &criterion.objects {
+                            if let Some(data) = batch_data.get(&object.identifier) {
+                                self.collection_cache.insert(cache_key(object), data);
+                            }
+                        }
+                    }
+                    collected_data.extend(batch_data);
+                }
+                Err(e) => {
+                    log_debug!("Batch collection failed, falling back to individual",
+                        "ctn_type" => &criterion.criterion_type,
+                        "error" => e
+                    );
+                }
+            }
+        }
+
+        // Individual collection for any objects not batch-collected
+        for object in &criterion.objects {
+            if !collected_data.contains_key(&object.identifier) {
+                // A collector failing once time is up failed because of it
+                let data = match self.collect_data_for_object(object, &contract) {
+                    Err(_) if deadline::expired() => return Err(timed_out("collection")),
+                    data => data?,
+                };
+                if cacheable {
+                    self.collection_cache.insert(cache_key(object), &data);
+                }
+                collected_data.insert(object.identifier.clone(), data);
+            }
+        }
+
+        log_debug!("Data collection complete",
+            "ctn_type" => &criterion.criterion_type,
+            "objects_collected" => collected_data.len()
+        );
+        self.record_observations(criterion, &collected_data);
+        let collection_warnings = collection_warnings(&collected_data);
+
+        // ========================================================================
+        // UPDATED: Apply SET-level filters FIRST - pass mutable criterion
+        // ========================================================================
+        collected_data = self.apply_set_filters(collected_data, criterion)?; // ✅ Changed
+
+        log_debug!("After SET filters",
+            "ctn_type" => &criterion.criterion_type,
+            "objects_remaining" => collected_data.len(),
+            "expected_count" => criterion.expected_object_count()  // ✅ NEW
+        );
+
+        // ========================================================================
+        // UPDATED: Apply object-level filters - pass mutable criterion
+        // ========================================================================
+        collected_data = self.apply_object_filters(collected_data, criterion, &contract)?; // ✅ Changed
+
+        log_debug!("After object filters",
+            "ctn_type" => &criterion.criterion_type,
+            "objects_remaining" => collected_data.len(),
+            "expected_count" => criterion.expected_object_count()  // ✅ NEW
+        );
+
+        timings.collection = start.elapsed();
+
+        // Check timeout after collection and filtering
+        if deadline::expired() {
+            return Err(timed_out("collection"));
         }
 
         // Get executor for this CTN type
@@ -317,12 +1381,17 @@ impl ExecutionEngine {
             })?;
 
         // Execute validation
-        let result = executor
-            .execute_with_contract(criterion, &collected_data, &contract_clone) // ✅ Note: criterion still &
-            .map_err(|e| ExecutionError::ExecutorFailed {
-                ctn_type: criterion.criterion_type.clone(),
-                reason: format!("Executor failed: {}", e),
-            })?;
+        let evaluation_start = Instant::now();
+        let result = executor.execute_with_contract(criterion, &collected_data, &contract_clone); // ✅ Note: criterion still &
+        timings.evaluation = evaluation_start.elapsed();
+        if deadline::expired() {
+            return Err(timed_out("evaluation"));
+        }
+        let mut result = result.map_err(|e| ExecutionError::ExecutorFailed {
+            ctn_type: criterion.criterion_type.clone(),
+            reason: format!("Executor failed: {}", e),
+        })?;
+        self.check_vacuous_pass(&mut result, &collected_data, &collection_warnings);
 
         log_debug!("CTN execution completed",
             "ctn_type" => &criterion.criterion_type,
@@ -332,6 +1401,27 @@ impl ExecutionEngine {
 
         Ok(result)
     }
+
+    /// Flag a vacuous pass, failing it when configured to
+    fn check_vacuous_pass(
+        &self,
+        result: &mut CtnExecutionResult,
+        collected_data: &HashMap<String, CollectedData>,
+        warnings: &[String],
+    ) {
+        let Some(reason) = flag_vacuous_pass(result, collected_data, warnings) else {
+            return;
+        };
+        log_warning!("Criterion passed without observing anything",
+            "ctn_type" => &result.ctn_type,
+            "reason" => &reason
+        );
+        if self.fail_on_vacuous {
+            result.status = ComplianceStatus::Fail;
+            result.message = format!("Vacuous pass: {}", reason);
+        }
+    }
+
     /// Convert CTN execution result to compliance finding
     fn ctn_result_to_finding(
         &self,
@@ -363,14 +1453,28 @@ impl ExecutionEngine {
 
         // Determine severity from CTN metadata
         let severity = match ctn_result.status {
+            ComplianceStatus::Pass if ctn_result.is_vacuous() => FindingSeverity::Low,
             ComplianceStatus::Fail => FindingSeverity::High,
             ComplianceStatus::Error => FindingSeverity::Critical,
+            ComplianceStatus::Unknown if ctn_result.is_not_evaluated() => FindingSeverity::Info,
             _ => FindingSeverity::Medium,
         };
 
         // Build title and description
-        let title = format!("{} validation failed", ctn_result.ctn_type);
-        let description = ctn_result.message.clone();
+        let title = if ctn_result.is_not_evaluated() {
+            format!("{} not evaluated", ctn_result.ctn_type)
+        } else if ctn_result.is_vacuous() {
+            format!("{} passed vacuously", ctn_result.ctn_type)
+        } else {
+            format!("{} validation failed", ctn_result.ctn_type)
+        };
+        let description = match ctn_result.details.get("vacuous_reason") {
+            Some(reason) if ctn_result.status == ComplianceStatus::Pass => format!(
+                "Passed without observing anything: {}",
+                reason.as_str().unwrap_or_default()
+            ),
+            _ => ctn_result.message.clone(),
+        };
 
         // Convert to JSON values
         let expected_json =
@@ -395,7 +1499,11 @@ impl ExecutionEngine {
             expected_truncated,
             actual_truncated,
         )
-        .with_field_path(field_path.join(" > ")))
+        .with_field_path(field_path.join(" > "))
+        .with_evidence(observed_fields(
+            &ctn_result.state_results,
+            self.observed_max_bytes,
+        )))
     }
 
     /// Truncate large values in findings to prevent JSON bloat
@@ -438,15 +1546,46 @@ impl ExecutionEngine {
                 reason: e.to_string(),
             })?;
 
+        if self.offline_unsupported(&contract.ctn_type) {
+            return Err(ExecutionError::OfflineTargetUnsupported {
+                ctn_type: contract.ctn_type.clone(),
+                root: self
+                    .scan_target
+                    .root()
+                    .map(|root| root.display().to_string())
+                    .unwrap_or_default(),
+            });
+        }
+
         // Extract behavior hints from the object
         let hints = extract_behavior_hints(object);
 
         // Call the new method with hints
         collector
             .collect_for_ctn_with_hints(object, contract, &hints)
-            .map_err(|e| ExecutionError::DataCollectionFailed {
-                object_id: object.identifier.clone(),
-                reason: e.to_string(),
+            .map_err(|e| match e {
+                CollectionError::PathDenied {
+                    object_id,
+                    path,
+                    reason,
+                } => ExecutionError::PathDenied {
+                    object_id,
+                    path,
+                    reason,
+                },
+                CollectionError::DataUnavailable { object_id, reason } => {
+                    ExecutionError::DataUnavailable { object_id, reason }
+                }
+                CollectionError::MissingCapability { capability } => {
+                    ExecutionError::MissingCapability {
+                        object_id: object.identifier.clone(),
+                        capability,
+                    }
+                }
+                e => ExecutionError::DataCollectionFailed {
+                    object_id: object.identifier.clone(),
+                    reason: e.to_string(),
+                },
             })
     }
 
@@ -552,8 +1691,12 @@ impl ExecutionEngine {
 
                 // Get collected value for this field
                 if let Some(collected_value) = data.get_field(data_field_name) {
-                    let matches =
-                        self.compare_for_filter(collected_value, &field.value, field.operation)?;
+                    let matches = self.compare_for_filter(
+                        collected_value,
+                        &field.value,
+                        field.operation,
+                        field.entity_check,
+                    )?;
 
                     // Short-circuit: If any field fails, entire filter fails
                     if !matches {
@@ -577,10 +1720,83 @@ impl ExecutionEngine {
         actual: &ResolvedValue,
         expected: &ResolvedValue,
         operation: crate::types::common::Operation,
+        entity_check: Option<EntityCheck>,
     ) -> Result<bool, ExecutionError> {
         use crate::types::common::Operation;
 
         let result = match (actual, expected, operation) {
+            // ============================================================
+            // Single value against a list-valued variable, item by item
+            // ============================================================
+            (_, ResolvedValue::Collection(items), op)
+                if !op.takes_value_list() && !matches!(actual, ResolvedValue::Collection(_)) =>
+            {
+                let mut results = Vec::with_capacity(items.len());
+                for item in items {
+                    results.push(self.compare_for_filter(actual, item, op, None)?);
+                }
+                evaluate_list_entity_check(entity_check, &results)
+            }
+
+            // ============================================================
+            // Membership against a value list (equals_any / not_equals_any)
+            // ============================================================
+            (_, ResolvedValue::Collection(e), Operation::EqualsAny | Operation::NotEqualsAny) => {
+                use crate::execution::comparisons::collection;
+                collection::matches_any(actual, e, operation).map_err(|e| {
+                    ExecutionError::ExecutorFailed {
+                        ctn_type: "filter_evaluation".to_string(),
+                        reason: format!("Value list comparison failed: {}", e),
+                    }
+                })?
+            }
+
+            // ============================================================
+            // String measures (length_* / occurrence_count)
+            // ============================================================
+            (
+                ResolvedValue::String(_),
+                ResolvedValue::Integer(_) | ResolvedValue::Collection(_),
+                Operation::LengthEquals | Operation::LengthGreaterThan | Operation::OccurrenceCount,
+            ) => actual.compare_with(expected, operation).map_err(|e| {
+                ExecutionError::ExecutorFailed {
+                    ctn_type: "filter_evaluation".to_string(),
+                    reason: format!("String measure comparison failed: {}", e),
+                }
+            })?,
+
+            // ============================================================
+            // Address against CIDR networks (in_subnet / not_in_subnet)
+            // ============================================================
+            (_, _, Operation::InSubnet | Operation::NotInSubnet) => actual
+                .compare_with(expected, operation)
+                .map_err(|e| ExecutionError::ExecutorFailed {
+                    ctn_type: "filter_evaluation".to_string(),
+                    reason: format!("Subnet comparison failed: {}", e),
+                })?,
+
+            // ============================================================
+            // Mode bits against a mask (permissions_at_most / _at_least)
+            // ============================================================
+            (_, _, Operation::PermissionsAtMost | Operation::PermissionsAtLeast) => actual
+                .compare_with(expected, operation)
+                .map_err(|e| ExecutionError::ExecutorFailed {
+                    ctn_type: "filter_evaluation".to_string(),
+                    reason: format!("Permission comparison failed: {}", e),
+                })?,
+
+            // ============================================================
+            // Version ordering (version_* on any version-like strings)
+            // ============================================================
+            (_, _, op) if op.is_version_comparison() => {
+                actual
+                    .compare_with(expected, op)
+                    .map_err(|e| ExecutionError::ExecutorFailed {
+                        ctn_type: "filter_evaluation".to_string(),
+                        reason: format!("Version comparison failed: {}", e),
+                    })?
+            }
+
             // ============================================================
             // String operations (all supported)
             // ============================================================
@@ -641,13 +1857,12 @@ impl ExecutionEngine {
             // ============================================================
             // EVR string comparisons (RPM-style versions)
             // ============================================================
-            (ResolvedValue::EvrString(a), ResolvedValue::EvrString(e), op) => {
-                use crate::execution::comparisons::evr;
-                evr::compare(a, e, op).map_err(|e| ExecutionError::ExecutorFailed {
+            (ResolvedValue::EvrString(_), ResolvedValue::EvrString(_), _) => actual
+                .compare_with(expected, operation)
+                .map_err(|e| ExecutionError::ExecutorFailed {
                     ctn_type: "filter_evaluation".to_string(),
                     reason: format!("EVR comparison failed: {}", e),
-                })?
-            }
+                })?,
 
             // ============================================================
             // Binary operations (contains operation for byte sequences)
@@ -677,26 +1892,135 @@ impl ExecutionEngine {
         Ok(result)
     }
 
-    /// Execute deferred operations for this criterion ONLY
-    /// Only executes operations relevant to this CTN's collected objects
-    #[allow(dead_code)]
-    fn execute_deferred_operations_for_criterion(
-        &mut self,
-        _criterion: &ExecutableCriterion,
-        collected_data: &HashMap<String, CollectedData>,
-    ) -> Result<(), ExecutionError> {
-        // Simply call the deferred ops module - it will execute ALL deferred operations
-        // This is correct because deferred operations update global context variables
-        // that are visible to all subsequent CTNs
+    /// Run the phases that precede criteria: collect the objects RUN
+    /// EXTRACT reads, compute scan-time variables, then bind them into
+    /// late-bound objects
+    fn run_scan_time_phases(&mut self) -> Result<(), ExecutionError> {
+        if !self.context.plan.has_scan_time_work() {
+            return Ok(());
+        }
+
+        // Phase: collect for extraction
+        let mut extraction_view: HashMap<String, CollectedData> = HashMap::new();
+        for object_id in self
+            .context
+            .plan
+            .phase(PhaseKind::CollectForExtraction)
+            .to_vec()
+        {
+            let (ctn_type, object) = self.extraction_source(&object_id)?;
+            let contract = self.registry.get_ctn_contract(&ctn_type).map_err(|e| {
+                ExecutionError::NoContractRegistered {
+                    ctn_type: ctn_type.clone(),
+                    reason: e.to_string(),
+                }
+            })?;
+            let data = self.collect_data_for_object(&object, &contract)?;
+            log_debug!("Collected object for extraction",
+                "object_id" => &object_id,
+                "ctn_type" => &ctn_type
+            );
+
+            // EXTRACT names fields the way states do; expose collected
+            // fields under their state names as well
+            let mut view = data.clone();
+            for (state_field, data_field) in
+                &contract.field_mappings.validation_mappings.state_to_data
+            {
+                if let Some(value) = data.fields.get(data_field) {
+                    view.fields
+                        .entry(state_field.clone())
+                        .or_insert_with(|| value.clone());
+                }
+            }
+            extraction_view.insert(object_id.clone(), view);
+            self.extraction_data.insert(object_id, (ctn_type, data));
+        }
+
+        // Phase: compute variables
         deferred_ops::execute_all_deferred_operations(
             &mut self.context,
             &self.registry,
-            collected_data,
+            &extraction_view,
         )?;
 
+        // Phase: substitute
+        self.substitute_late_bound_objects()
+    }
+
+    /// The first criterion referencing an object decides which collector
+    /// gathers it for extraction
+    fn extraction_source(
+        &self,
+        object_id: &str,
+    ) -> Result<(String, ExecutableObject), ExecutionError> {
+        self.context
+            .criteria_tree
+            .get_all_criteria()
+            .into_iter()
+            .find_map(|criterion| {
+                criterion
+                    .objects
+                    .iter()
+                    .find(|o| o.identifier == object_id)
+                    .map(|o| (criterion.criterion_type.clone(), o.clone()))
+            })
+            .ok_or_else(|| ExecutionError::DeferredOperationFailed {
+                operation: "EXTRACT".to_string(),
+                reason: format!(
+                    "Object '{}' is not referenced by any criterion, so no collector can gather it",
+                    object_id
+                ),
+            })
+    }
+
+    /// Re-resolve late-bound objects against the computed variables and
+    /// replace their placeholders in the criteria tree
+    fn substitute_late_bound_objects(&mut self) -> Result<(), ExecutionError> {
+        let resolver = ResolutionEngine::new();
+        for object_id in self.context.plan.phase(PhaseKind::Substitute).to_vec() {
+            let Some(declaration) = self.context.late_bound_objects.get(&object_id) else {
+                continue;
+            };
+            let resolved = resolver
+                .resolve_object_fields(declaration, &self.context.global_variables)
+                .map_err(|e| ExecutionError::DeferredOperationFailed {
+                    operation: "SUBSTITUTE".to_string(),
+                    reason: format!("Object '{}': {}", object_id, e),
+                })?;
+            let executable = ExecutableObject::from_resolved_object(&resolved);
+
+            for criterion in self.context.criteria_tree.get_all_criteria_mut() {
+                for object in criterion
+                    .objects
+                    .iter_mut()
+                    .filter(|o| o.identifier == object_id)
+                {
+                    *object = executable.clone();
+                }
+            }
+            self.context.global_objects.insert(object_id, resolved);
+        }
         Ok(())
     }
 
+    /// Data already collected for extraction that this criterion can reuse
+    fn shared_extraction_data(
+        &self,
+        criterion: &ExecutableCriterion,
+    ) -> HashMap<String, CollectedData> {
+        criterion
+            .objects
+            .iter()
+            .filter_map(|object| {
+                self.extraction_data
+                    .get(&object.identifier)
+                    .filter(|(ctn_type, _)| *ctn_type == criterion.criterion_type)
+                    .map(|(_, data)| (object.identifier.clone(), data.clone()))
+            })
+            .collect()
+    }
+
     /// Convert tree result to findings with logical paths
     fn tree_result_to_findings(
         &self,
@@ -722,13 +2046,26 @@ impl ExecutionEngine {
         // If this is a leaf node (has CTN results), process them
         if !tree_result.ctn_results.is_empty() {
             for ctn_result in &tree_result.ctn_results {
-                if ctn_result.status != ComplianceStatus::Pass {
+                let vacuous = ctn_result.execution_result.is_vacuous();
+                let decided = matches!(
+                    ctn_result.status,
+                    ComplianceStatus::Pass | ComplianceStatus::NotApplicable
+                );
+                if !decided || vacuous {
                     let mut finding_path = path.clone();
                     finding_path.push(format!("CTN_{}", ctn_result.criterion_type));
 
                     // Pass the execution_result (CtnExecutionResult), not the wrapper (CtnResult)
-                    let finding =
-                        self.ctn_result_to_finding(&ctn_result.execution_result, finding_path)?;
+                    let finding = self
+                        .ctn_result_to_finding(&ctn_result.execution_result, finding_path)?
+                        .with_criterion_id(criterion_id(
+                            &ctn_result.criterion_type,
+                            ctn_result.ctn_node_id,
+                        ))
+                        .with_policy_severity(ctn_result.severity)
+                        .with_controls(ctn_result.controls.clone())
+                        .with_vacuous(vacuous)
+                        .with_waiver(ctn_result.waiver.clone());
                     findings.push(finding);
                 }
             }
@@ -848,6 +2185,12 @@ pub struct CtnResult {
     pub status: ComplianceStatus,
     pub execution_result: CtnExecutionResult,
     pub execution_time_ms: u64,
+    /// Benchmark controls the criterion implements
+    pub controls: Vec<Control>,
+    /// Severity the policy assigns to the criterion
+    pub severity: PolicySeverity,
+    /// Waiver covering the criterion's failure, when `status` is `Waived`
+    pub waiver: Option<Waiver>,
 }
 /// Tree traversal result (internal)
 #[derive(Debug, Clone)]
@@ -859,14 +2202,59 @@ struct TreeResult {
     pub child_results: Vec<TreeResult>,
 }
 impl TreeResult {
-    fn calculate_stats(&self) -> TreeStats {
-        let mut stats = TreeStats::default();
-        for ctn in &self.ctn_results {
-            stats.total += 1;
+    /// Whether the policy passes: it passed, or nothing in it applied
+    fn passes(&self, error_mode: ErrorMode) -> bool {
+        let status = match error_mode {
+            ErrorMode::Strict => self.status,
+            ErrorMode::Lenient => self.lenient_status(),
+        };
+        matches!(
+            status,
+            ComplianceStatus::Pass | ComplianceStatus::NotApplicable | ComplianceStatus::Waived
+        )
+    }
+
+    /// Status with error and unknown criteria left out
+    fn lenient_status(&self) -> ComplianceStatus {
+        let combined = match self.logical_op {
+            Some(op) => combine(op, self.child_results.iter().map(Self::lenient_status)),
+            None => match self.status {
+                ComplianceStatus::Error | ComplianceStatus::Unknown => {
+                    ComplianceStatus::NotApplicable
+                }
+                status => status,
+            },
+        };
+        if self.negated {
+            combined.negate()
+        } else {
+            combined
+        }
+    }
+
+    fn calculate_stats(&self) -> TreeStats {
+        let mut stats = TreeStats::default();
+        for ctn in &self.ctn_results {
+            stats.total += 1;
+            if ctn.execution_result.is_vacuous() {
+                stats.vacuous += 1;
+            }
             match ctn.status {
                 ComplianceStatus::Pass => stats.passed += 1,
                 ComplianceStatus::Fail => stats.failed += 1,
-                ComplianceStatus::Error => stats.errors += 1,
+                ComplianceStatus::Error => {
+                    stats.errors += 1;
+                    if ctn.execution_result.details.get("reason")
+                        == Some(&serde_json::json!(PATH_DENIED))
+                    {
+                        stats.path_denied += 1;
+                    }
+                }
+                ComplianceStatus::Unknown if ctn.execution_result.is_not_evaluated() => {
+                    stats.not_evaluated += 1
+                }
+                ComplianceStatus::NotApplicable => stats.not_applicable += 1,
+                ComplianceStatus::Waived => stats.waived += 1,
                 _ => {}
             }
         }
@@ -878,10 +2266,90 @@ impl TreeResult {
             stats.passed += child_stats.passed;
             stats.failed += child_stats.failed;
             stats.errors += child_stats.errors;
+            stats.not_evaluated += child_stats.not_evaluated;
+            stats.not_applicable += child_stats.not_applicable;
+            stats.waived += child_stats.waived;
+            stats.path_denied += child_stats.path_denied;
+            stats.vacuous += child_stats.vacuous;
         }
 
         stats
     }
+
+    /// Compared fields of every criterion in this subtree, in tree order
+    fn collect_evidence(&self, out: &mut Vec<CriterionEvidence>) {
+        for ctn in &self.ctn_results {
+            out.push(CriterionEvidence::new(
+                criterion_id(&ctn.criterion_type, ctn.ctn_node_id),
+                ctn.status.as_str(),
+                &ctn.execution_result.state_results,
+            ));
+        }
+        for child in &self.child_results {
+            child.collect_evidence(out);
+        }
+    }
+
+    /// Advisories of every criterion in this subtree, tagged with the criterion
+    fn collect_advisories(&self, out: &mut Vec<Advisory>) {
+        for ctn in &self.ctn_results {
+            let id = criterion_id(&ctn.criterion_type, ctn.ctn_node_id);
+            out.extend(
+                ctn.execution_result
+                    .advisories()
+                    .iter()
+                    .map(|advisory| Advisory {
+                        criterion_id: id.clone(),
+                        ..advisory.clone()
+                    }),
+            );
+        }
+        for child in &self.child_results {
+            child.collect_advisories(out);
+        }
+    }
+
+    /// Status of every criterion in this subtree, with the fingerprint of
+    /// its definition in `criteria`
+    fn collect_outcomes(
+        &self,
+        criteria: &HashMap<CtnNodeId, &ExecutableCriterion>,
+        out: &mut Vec<CriterionOutcome>,
+    ) {
+        for ctn in &self.ctn_results {
+            out.push(CriterionOutcome::new(
+                &ctn.criterion_type,
+                ctn.ctn_node_id,
+                ctn.status.as_str(),
+                criteria
+                    .get(&ctn.ctn_node_id)
+                    .map(|criterion| criterion_fingerprint(criterion)),
+            ));
+        }
+        for child in &self.child_results {
+            child.collect_outcomes(criteria, out);
+        }
+    }
+
+    /// Controls and status of every criterion in this subtree
+    fn collect_control_outcomes<'a>(&'a self, out: &mut Vec<(&'a [Control], ComplianceStatus)>) {
+        for ctn in &self.ctn_results {
+            out.push((&ctn.controls, ctn.status));
+        }
+        for child in &self.child_results {
+            child.collect_control_outcomes(out);
+        }
+    }
+
+    /// Reduce retained evidence in this subtree to summaries
+    fn summarize_evidence(&mut self) {
+        for ctn in &mut self.ctn_results {
+            ctn.execution_result.summarize_evidence();
+        }
+        for child in &mut self.child_results {
+            child.summarize_evidence();
+        }
+    }
 }
 #[derive(Debug, Default)]
 struct TreeStats {
@@ -889,6 +2357,22 @@ struct TreeStats {
     passed: u32,
     failed: u32,
     errors: u32,
+    not_evaluated: u32,
+    not_applicable: u32,
+    waived: u32,
+    path_denied: u32,
+    vacuous: u32,
+}
+
+/// Status of a block of `op` over its children's statuses
+fn combine(
+    op: LogicalOp,
+    statuses: impl IntoIterator<Item = ComplianceStatus>,
+) -> ComplianceStatus {
+    match op {
+        LogicalOp::And => ComplianceStatus::all(statuses),
+        LogicalOp::Or => ComplianceStatus::any(statuses),
+    }
 }
 // ============================================================================
 // Error Types
@@ -897,6 +2381,13 @@ struct TreeStats {
 pub enum ExecutionError {
     #[error("No contract registered for CTN type '{ctn_type}': {reason}")]
     NoContractRegistered { ctn_type: String, reason: String },
+    #[error(
+        "No strategy registered for criterion types: {}",
+        unknown.iter().map(|u| u.to_string()).collect::<Vec<_>>().join(", ")
+    )]
+    UnknownCriterionTypes { unknown: Vec<UnknownCriterionType> },
+    #[error("Policy has no criteria; nothing to scan")]
+    EmptyPolicy,
     #[error("Contract validation failed for '{ctn_type}': {errors:?}")]
     ContractValidationFailed {
         ctn_type: String,
@@ -929,6 +2420,32 @@ pub enum ExecutionError {
 
     #[error("Object '{object_id}' not found in criterion object list")]
     ObjectNotFoundInCriterion { object_id: String },
+
+    #[error("Path '{path}' denied for object '{object_id}': {reason}")]
+    PathDenied {
+        object_id: String,
+        path: String,
+        reason: String,
+    },
+
+    #[error("Data unavailable for object '{object_id}': {reason}")]
+    DataUnavailable { object_id: String, reason: String },
+
+    #[error("Required capability missing for object '{object_id}': {capability}")]
+    MissingCapability {
+        object_id: String,
+        capability: String,
+    },
+
+    #[error("CTN type '{ctn_type}' is not supported for offline target '{root}'")]
+    OfflineTargetUnsupported { ctn_type: String, root: String },
+
+    #[error("CTN type '{ctn_type}' timed out during {phase} (collector '{collector}')")]
+    CriterionTimeout {
+        ctn_type: String,
+        collector: String,
+        phase: String,
+    },
 }
 
 impl From<CtnExecutionError> for ExecutionError {
@@ -942,9 +2459,1495 @@ impl From<CtnExecutionError> for ExecutionError {
 // ============================================================================
 // Helper Functions
 // ============================================================================
+/// Collection warnings of every object, prefixed with the object id and
+/// in object order
+pub fn collection_warnings(collected_data: &HashMap<String, CollectedData>) -> Vec<String> {
+    let mut warnings: Vec<String> = collected_data
+        .iter()
+        .flat_map(|(object_id, data)| {
+            data.metadata
+                .warnings
+                .iter()
+                .map(move |warning| format!("{}: {}", object_id, warning))
+        })
+        .collect();
+    warnings.sort();
+    warnings
+}
+
+/// Flag a pass that rests on nothing: no object was observed and the
+/// collection warned it could not look. Objects confirmed absent carry no
+/// warnings, so legitimate `none_exist` checks are not flagged.
+///
+/// Returns the reason when the result was flagged.
+pub fn flag_vacuous_pass(
+    result: &mut CtnExecutionResult,
+    collected_data: &HashMap<String, CollectedData>,
+    warnings: &[String],
+) -> Option<String> {
+    let observed = collected_data
+        .values()
+        .filter(|data| data.metadata.warnings.is_empty())
+        .count();
+    if result.status != ComplianceStatus::Pass || observed > 0 || warnings.is_empty() {
+        return None;
+    }
+
+    let reason = warnings.join("; ");
+    result.mark_vacuous(&reason);
+    Some(reason)
+}
+
 fn logical_op_to_string(op: LogicalOp) -> &'static str {
     match op {
         LogicalOp::And => "AND",
         LogicalOp::Or => "OR",
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::execution::behavior::BehaviorHints;
+    use crate::execution::consistency::FileObservation;
+    use crate::execution::memory::{MemoryBudget, MemoryMonitor};
+    use crate::execution::progress::ExecutionEvent;
+    use crate::results::CollectionCacheStats;
+    use crate::strategies::{
+        CollectionError, CtnDataCollector, CtnExecutor, FieldOutcome, FieldValidationResult,
+        StateValidationResult,
+    };
+    use crate::types::execution_context::{
+        ExecutableObjectElement, ExecutableState, ExecutableStateField,
+    };
+    use crate::types::metadata::MetaDataBlock;
+    use crate::types::{ExistenceCheck, ItemCheck, TestSpecification};
+
+    const CTN_TYPE: &str = "evidence_heavy";
+
+    struct NoopCollector;
+
+    impl CtnDataCollector for NoopCollector {
+        fn collect_for_ctn_with_hints(
+            &self,
+            object: &ExecutableObject,
+            contract: &CtnContract,
+            _hints: &BehaviorHints,
+        ) -> Result<CollectedData, CollectionError> {
+            Ok(CollectedData::new(
+                object.identifier.clone(),
+                contract.ctn_type.clone(),
+                self.collector_id().to_string(),
+            ))
+        }
+
+        fn supported_ctn_types(&self) -> Vec<String> {
+            vec![CTN_TYPE.to_string()]
+        }
+
+        fn validate_ctn_compatibility(
+            &self,
+            _contract: &CtnContract,
+        ) -> Result<(), CollectionError> {
+            Ok(())
+        }
+
+        fn collector_id(&self) -> &str {
+            "noop-collector"
+        }
+    }
+
+    /// Produces large passing evidence so summarization is observable
+    struct EvidenceHeavyExecutor {
+        required_privilege: PrivilegeRequirement,
+    }
+
+    impl CtnExecutor for EvidenceHeavyExecutor {
+        fn execute_with_contract(
+            &self,
+            _criterion: &ExecutableCriterion,
+            _collected_data: &HashMap<String, CollectedData>,
+            _contract: &CtnContract,
+        ) -> Result<CtnExecutionResult, CtnExecutionError> {
+            let fields = (0..50)
+                .map(|i| FieldValidationResult {
+                    field_name: format!("field_{}", i),
+                    expected_value: ResolvedValue::String("x".repeat(256)),
+                    actual_value: ResolvedValue::String("x".repeat(256)),
+                    operation: crate::types::common::Operation::Equals,
+                    passed: true,
+                    message: String::new(),
+                    outcome: FieldOutcome::Pass,
+                })
+                .collect();
+
+            Ok(
+                CtnExecutionResult::pass(CTN_TYPE.to_string(), "ok".to_string())
+                    .with_state_results(vec![StateValidationResult {
+                        object_id: "obj".to_string(),
+                        state_results: fields,
+                        combined_result: true,
+                        state_operator: None,
+                        message: String::new(),
+                    }])
+                    .with_details(serde_json::json!({ "blob": "y".repeat(4096) })),
+            )
+        }
+
+        fn get_ctn_contract(&self) -> CtnContract {
+            let mut contract = CtnContract::new(CTN_TYPE.to_string());
+            contract.collection_strategy.required_privilege = self.required_privilege.clone();
+            contract
+        }
+
+        fn validate_collected_data(
+            &self,
+            _collected_data: &HashMap<String, CollectedData>,
+            _contract: &CtnContract,
+        ) -> Result<(), CtnExecutionError> {
+            Ok(())
+        }
+
+        fn ctn_type(&self) -> &str {
+            CTN_TYPE
+        }
+    }
+
+    fn test_registry() -> Arc<CtnStrategyRegistry> {
+        test_registry_requiring(PrivilegeRequirement::None)
+    }
+
+    fn test_registry_requiring(
+        required_privilege: PrivilegeRequirement,
+    ) -> Arc<CtnStrategyRegistry> {
+        let mut registry = CtnStrategyRegistry::new_unvalidated();
+        registry
+            .register_ctn_strategy(
+                Box::new(NoopCollector),
+                Box::new(EvidenceHeavyExecutor { required_privilege }),
+            )
+            .unwrap();
+        Arc::new(registry)
+    }
+
+    fn test_context(criteria: usize) -> ExecutionContext {
+        let children = (1..=criteria)
+            .map(|id| {
+                ExecutableCriteriaTree::Criterion(ExecutableCriterion {
+                    ctn_node_id: id,
+                    criterion_type: CTN_TYPE.to_string(),
+                    test: TestSpecification {
+                        existence_check: ExistenceCheck::Any,
+                        item_check: ItemCheck::All,
+                        state_operator: None,
+                        entity_check: None,
+                        span: None,
+                    },
+                    objects: vec![],
+                    states: vec![],
+                    set_filters: HashMap::new(),
+                    active_object_ids: None,
+                    controls: vec![],
+                    severity: None,
+                })
+            })
+            .collect();
+
+        let fields = [
+            ("esp_scan_id", "test-001"),
+            ("control_framework", "TEST"),
+            ("control", "TEST-1"),
+            ("platform", "linux"),
+            ("criticality", "low"),
+            ("tags", "test"),
+        ];
+
+        ExecutionContext {
+            metadata: Some(MetaDataBlock::from_ordered(fields)),
+            criteria_tree: ExecutableCriteriaTree::Block {
+                logical_op: LogicalOp::And,
+                negate: false,
+                children,
+            },
+            global_variables: HashMap::new(),
+            global_states: HashMap::new(),
+            global_objects: HashMap::new(),
+            global_sets: HashMap::new(),
+            deferred_operations: vec![],
+            late_bound_objects: HashMap::new(),
+            plan: Default::default(),
+            local_states: HashMap::new(),
+            local_objects: HashMap::new(),
+            clock: ScanClock::system(),
+        }
+    }
+
+    #[test]
+    fn test_execute_without_budget_is_not_degraded() {
+        let mut engine = ExecutionEngine::new(test_context(3), test_registry());
+        let result = engine.execute().unwrap();
+
+        assert!(result.results.passed);
+        assert!(!result.degraded);
+        assert_eq!(result.results.check.not_evaluated_criteria, 0);
+    }
+
+    #[test]
+    fn test_field_evidence_is_opt_in() {
+        let mut engine = ExecutionEngine::new(test_context(2), test_registry());
+        assert!(engine.execute().unwrap().evidence.is_empty());
+
+        let mut engine =
+            ExecutionEngine::new(test_context(2), test_registry()).with_field_evidence(true);
+        let result = engine.execute().unwrap();
+
+        assert_eq!(result.evidence.len(), 2);
+        let evidence = &result.evidence[0];
+        assert_eq!(evidence.status, "pass");
+        assert_eq!(evidence.items[0].fields.len(), 50);
+        assert!(evidence.items[0]
+            .fields
+            .iter()
+            .all(|f| f.outcome == FieldOutcome::Pass && f.actual.is_some()));
+    }
+
+    #[test]
+    fn test_memory_budget_degrades_in_order_then_aborts() {
+        let budget = MemoryBudget::new(1024)
+            .with_sample_interval(1)
+            .with_abort_after(1);
+        let monitor = MemoryMonitor::with_sampler(budget, || Some(u64::MAX));
+        let mut engine =
+            ExecutionEngine::new(test_context(10), test_registry()).with_memory_monitor(monitor);
+
+        let result = engine.execute().unwrap();
+
+        assert_eq!(
+            engine.degradation_steps(),
+            &[
+                DegradationStep::FlushSinks,
+                DegradationStep::SummarizeEvidence,
+                DegradationStep::DisableCommandCache,
+                DegradationStep::Abort,
+            ]
+        );
+        assert!(result.degraded);
+        assert!(!result.results.passed);
+        assert_eq!(result.results.check.total_criteria, 10);
+        assert_eq!(result.results.check.passed_criteria, 4);
+        assert_eq!(result.results.check.not_evaluated_criteria, 6);
+        assert!(result
+            .results
+            .findings
+            .iter()
+            .all(|f| matches!(f.severity, FindingSeverity::Info)));
+    }
+
+    #[test]
+    fn test_summarized_evidence_drops_passing_fields() {
+        let budget = MemoryBudget::new(1024)
+            .with_sample_interval(1)
+            .with_abort_after(usize::MAX);
+        let monitor = MemoryMonitor::with_sampler(budget, || Some(u64::MAX));
+        let mut engine =
+            ExecutionEngine::new(test_context(4), test_registry()).with_memory_monitor(monitor);
+
+        let tree = engine.context.criteria_tree.clone();
+        let tree_result = engine.execute_tree(&tree).unwrap();
+
+        for child in &tree_result.child_results {
+            let ctn = &child.ctn_results[0].execution_result;
+            assert!(ctn.state_results[0].state_results.is_empty());
+            assert_eq!(ctn.details["summarized"], serde_json::json!(true));
+        }
+    }
+
+    #[test]
+    fn test_unprivileged_criteria_are_errors() {
+        let mut engine = ExecutionEngine::new(
+            test_context(2),
+            test_registry_requiring(PrivilegeRequirement::Root),
+        )
+        .with_privileges(EffectivePrivileges::unprivileged());
+
+        let result = engine.execute().unwrap();
+
+        assert!(!result.results.passed);
+        assert_eq!(result.results.check.error_criteria, 2);
+        assert_eq!(result.results.check.not_evaluated_criteria, 0);
+    }
+
+    #[test]
+    fn test_controls_on_findings_with_map_precedence() {
+        use esp_compiler::grammar::ast::nodes::ControlRef;
+
+        let mut context = test_context(2);
+        let declared = |framework: &str, control_id: &str| ControlRef {
+            framework: framework.to_string(),
+            control_id: control_id.to_string(),
+            url: None,
+            span: None,
+        };
+        context.criteria_tree.get_all_criteria_mut()[0].controls =
+            vec![declared("CIS", "5.2.9"), declared("STIG", "RHEL-09-255045")];
+
+        let mut map = ControlMap::new();
+        map.insert(
+            criterion_id(CTN_TYPE, 1),
+            Control::new("CIS", "5.2.10").with_url("https://example.test/cis"),
+        );
+        map.insert(criterion_id(CTN_TYPE, 2), Control::new("INTERNAL", "SEC-7"));
+
+        let mut engine =
+            ExecutionEngine::new(context, test_registry_requiring(PrivilegeRequirement::Root))
+                .with_privileges(EffectivePrivileges::unprivileged())
+                .with_control_map(Arc::new(map));
+        let result = engine.execute().unwrap();
+
+        let findings = &result.results.findings;
+        assert_eq!(
+            findings[0].controls,
+            vec![
+                Control::new("STIG", "RHEL-09-255045"),
+                Control::new("CIS", "5.2.10").with_url("https://example.test/cis"),
+            ]
+        );
+        assert_eq!(
+            findings[1].controls,
+            vec![Control::new("INTERNAL", "SEC-7")]
+        );
+
+        let summarized: Vec<String> = result
+            .controls
+            .iter()
+            .map(|s| format!("{} {}", s.control, s.errors))
+            .collect();
+        assert_eq!(
+            summarized,
+            vec!["CIS 5.2.10 1", "INTERNAL SEC-7 1", "STIG RHEL-09-255045 1"]
+        );
+    }
+
+    #[test]
+    fn test_policy_severity_from_ctn_then_meta() {
+        let scan = |meta_severity: Option<&str>| {
+            let mut context = test_context(2);
+            context.criteria_tree.get_all_criteria_mut()[0].severity = Some("critical".to_string());
+            if let (Some(metadata), Some(severity)) = (&mut context.metadata, meta_severity) {
+                metadata
+                    .fields
+                    .insert("severity".to_string(), severity.to_string());
+            }
+            ExecutionEngine::new(context, test_registry_requiring(PrivilegeRequirement::Root))
+                .with_privileges(EffectivePrivileges::unprivileged())
+                .execute()
+                .unwrap()
+        };
+
+        let result = scan(None);
+        let severities: Vec<PolicySeverity> = result
+            .results
+            .findings
+            .iter()
+            .map(|f| f.policy_severity)
+            .collect();
+        assert_eq!(
+            severities,
+            vec![PolicySeverity::Critical, PolicySeverity::Unknown]
+        );
+        assert_eq!(
+            result.results.check.failed_by_severity,
+            [(PolicySeverity::Unknown, 1), (PolicySeverity::Critical, 1)].into()
+        );
+        assert!(result.has_failures_at_or_above(PolicySeverity::High));
+
+        let result = scan(Some("low"));
+        assert_eq!(
+            result.results.findings[1].policy_severity,
+            PolicySeverity::Low
+        );
+        assert_eq!(
+            result.results.check.failed_by_severity,
+            [(PolicySeverity::Low, 1), (PolicySeverity::Critical, 1)].into()
+        );
+    }
+
+    #[test]
+    fn test_skip_unprivileged_marks_not_evaluated() {
+        let mut engine = ExecutionEngine::new(
+            test_context(2),
+            test_registry_requiring(PrivilegeRequirement::Root),
+        )
+        .with_privileges(EffectivePrivileges::unprivileged())
+        .with_skip_unprivileged(true);
+
+        let tree = engine.context.criteria_tree.clone();
+        let tree_result = engine.execute_tree(&tree).unwrap();
+
+        for child in &tree_result.child_results {
+            let ctn = &child.ctn_results[0].execution_result;
+            assert!(ctn.is_not_evaluated());
+            assert_eq!(ctn.details["reason"], INSUFFICIENT_PRIVILEGES);
+            assert_eq!(ctn.details["required_privilege"], "root");
+        }
+    }
+
+    #[test]
+    fn test_offline_target_skips_host_only_collectors() {
+        let root = tempfile::tempdir().unwrap();
+        let target = Arc::new(ScanTarget::offline(root.path()).unwrap());
+        let mut engine = ExecutionEngine::new(test_context(2), test_registry())
+            .with_privileges(EffectivePrivileges::unprivileged())
+            .with_scan_target(target.clone());
+
+        let result = engine.execute().unwrap();
+
+        assert_eq!(result.results.check.not_evaluated_criteria, 2);
+        assert_eq!(
+            result.metadata.scan_root.as_deref(),
+            target.root().and_then(|root| root.to_str())
+        );
+        let tree = engine.context.criteria_tree.clone();
+        let tree_result = engine.execute_tree(&tree).unwrap();
+        let ctn = &tree_result.child_results[0].ctn_results[0].execution_result;
+        assert_eq!(ctn.details["reason"], OFFLINE_TARGET_UNSUPPORTED);
+    }
+
+    #[test]
+    fn test_target_reads_os_release_under_root_and_can_omit_hostname() {
+        let root = tempfile::tempdir().unwrap();
+        std::fs::create_dir(root.path().join("etc")).unwrap();
+        std::fs::write(
+            root.path().join("etc/os-release"),
+            "ID=rhel\nVERSION_ID=\"9.4\"\n",
+        )
+        .unwrap();
+        let mut engine = ExecutionEngine::new(test_context(1), test_registry())
+            .with_scan_target(Arc::new(ScanTarget::offline(root.path()).unwrap()))
+            .with_provenance(ScanProvenance::new("sha256:policy", "sha256:ast"))
+            .with_host_identification(false);
+
+        let result = engine.execute().unwrap();
+
+        let target = result.target.unwrap();
+        assert_eq!(target.hostname, None);
+        assert_eq!(result.metadata.host.hostname, REDACTED_HOSTNAME);
+        assert_eq!(
+            (target.os_id.as_deref(), target.os_version_id.as_deref()),
+            (Some("rhel"), Some("9.4"))
+        );
+        assert_eq!(target.kernel_version, None);
+        assert_eq!(target.scan_end, result.metadata.timestamp.scan_end);
+        assert_eq!(target.policy_sha256.as_deref(), Some("sha256:policy"));
+        assert_eq!(target.scanner_version, env!("CARGO_PKG_VERSION"));
+    }
+
+    #[test]
+    fn test_capability_satisfies_requirement_without_root() {
+        let requirement = PrivilegeRequirement::Capability("cap_dac_read_search".to_string());
+        let privileges = EffectivePrivileges::unprivileged().with_capability("cap_dac_read_search");
+        let mut engine =
+            ExecutionEngine::new(test_context(2), test_registry_requiring(requirement))
+                .with_privileges(privileges);
+
+        let checks = engine.privilege_checks();
+        assert_eq!(checks.len(), 2);
+        assert!(checks.iter().all(|c| c.satisfied));
+
+        let result = engine.execute().unwrap();
+        assert!(result.results.passed);
+    }
+
+    #[test]
+    fn test_privilege_checks_skip_unprivileged_types() {
+        let mut engine = ExecutionEngine::new(test_context(3), test_registry())
+            .with_privileges(EffectivePrivileges::unprivileged());
+
+        assert!(engine.privilege_checks().is_empty());
+    }
+
+    /// Refuses every object, as a filesystem collector does for a denied path
+    struct DenyingCollector;
+
+    impl CtnDataCollector for DenyingCollector {
+        fn collect_for_ctn_with_hints(
+            &self,
+            object: &ExecutableObject,
+            _contract: &CtnContract,
+            _hints: &BehaviorHints,
+        ) -> Result<CollectedData, CollectionError> {
+            Err(CollectionError::PathDenied {
+                object_id: object.identifier.clone(),
+                path: "/home/alice/.ssh".to_string(),
+                reason: "'/home/alice/.ssh' is under denied prefix '/home'".to_string(),
+            })
+        }
+
+        fn supported_ctn_types(&self) -> Vec<String> {
+            vec![CTN_TYPE.to_string()]
+        }
+
+        fn validate_ctn_compatibility(
+            &self,
+            _contract: &CtnContract,
+        ) -> Result<(), CollectionError> {
+            Ok(())
+        }
+
+        fn collector_id(&self) -> &str {
+            "denying-collector"
+        }
+    }
+
+    #[test]
+    fn test_path_denied_marks_criterion_error_and_continues() {
+        let mut registry = CtnStrategyRegistry::new_unvalidated();
+        registry
+            .register_ctn_strategy(
+                Box::new(DenyingCollector),
+                Box::new(EvidenceHeavyExecutor {
+                    required_privilege: PrivilegeRequirement::None,
+                }),
+            )
+            .unwrap();
+
+        let mut context = test_context(2);
+        if let ExecutableCriteriaTree::Block { children, .. } = &mut context.criteria_tree {
+            if let ExecutableCriteriaTree::Criterion(criterion) = &mut children[0] {
+                criterion.objects.push(ExecutableObject {
+                    identifier: "ssh_dir".to_string(),
+                    elements: vec![],
+                    is_global: false,
+                });
+            }
+        }
+
+        let mut engine = ExecutionEngine::new(context, Arc::new(registry));
+        let result = engine.execute().unwrap();
+
+        assert!(!result.results.passed);
+        assert_eq!(result.results.check.error_criteria, 1);
+        assert_eq!(result.results.check.passed_criteria, 1);
+        assert_eq!(result.results.check.path_denied_criteria, 1);
+    }
+
+    /// Outlives any short timeout, then fails as a killed command does
+    struct HangingCollector;
+
+    impl CtnDataCollector for HangingCollector {
+        fn collect_for_ctn_with_hints(
+            &self,
+            object: &ExecutableObject,
+            _contract: &CtnContract,
+            _hints: &BehaviorHints,
+        ) -> Result<CollectedData, CollectionError> {
+            std::thread::sleep(Duration::from_millis(50));
+            Err(CollectionError::CollectionFailed {
+                object_id: object.identifier.clone(),
+                reason: "Command timed out after 0ms".to_string(),
+            })
+        }
+
+        fn supported_ctn_types(&self) -> Vec<String> {
+            vec![CTN_TYPE.to_string()]
+        }
+
+        fn validate_ctn_compatibility(
+            &self,
+            _contract: &CtnContract,
+        ) -> Result<(), CollectionError> {
+            Ok(())
+        }
+
+        fn collector_id(&self) -> &str {
+            "hanging-collector"
+        }
+    }
+
+    #[test]
+    fn test_criterion_timeout_names_the_collector() {
+        let mut registry = CtnStrategyRegistry::new_unvalidated();
+        registry
+            .register_ctn_strategy(
+                Box::new(HangingCollector),
+                Box::new(EvidenceHeavyExecutor {
+                    required_privilege: PrivilegeRequirement::None,
+                }),
+            )
+            .unwrap();
+
+        let mut context = test_context(1);
+        if let ExecutableCriteriaTree::Block { children, .. } = &mut context.criteria_tree {
+            if let ExecutableCriteriaTree::Criterion(criterion) = &mut children[0] {
+                criterion.objects.push(ExecutableObject {
+                    identifier: "slow".to_string(),
+                    elements: vec![],
+                    is_global: false,
+                });
+            }
+        }
+
+        let mut engine = ExecutionEngine::new(context, Arc::new(registry))
+            .with_timeouts(Timeouts::default().with_criterion(Duration::from_millis(10)));
+        let tree = engine.context.criteria_tree.clone();
+        let tree_result = engine.execute_tree(&tree).unwrap();
+        let ctn = &tree_result.child_results[0].ctn_results[0].execution_result;
+
+        assert_eq!(ctn.status, ComplianceStatus::Error);
+        assert_eq!(ctn.details["reason"], CRITERION_TIMEOUT);
+        assert_eq!(ctn.details["collector"], "hanging-collector");
+        assert_eq!(ctn.details["timeout_ms"], 10);
+        assert!(ctn.message.contains("hanging-collector"));
+    }
+
+    #[test]
+    fn test_scan_deadline_stops_remaining_criteria() {
+        let mut engine = ExecutionEngine::new(test_context(2), test_registry())
+            .with_timeouts(Timeouts::default().with_scan(Duration::from_nanos(1)));
+
+        let result = engine.execute().unwrap();
+
+        assert!(!result.results.passed);
+        assert_eq!(result.results.check.error_criteria, 2);
+    }
+
+    #[test]
+    fn test_progress_events_bracket_each_criterion() {
+        let events = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = Arc::clone(&events);
+        let mut engine = ExecutionEngine::new(test_context(2), test_registry())
+            .with_progress_handler(Arc::new(move |event: &ExecutionEvent| {
+                sink.lock().unwrap().push(event.clone())
+            }));
+
+        let result = engine.execute().unwrap();
+
+        let events = events.lock().unwrap();
+        assert_eq!(events.len(), 6);
+        assert_eq!(events[0], ExecutionEvent::ScanStarted { total_criteria: 2 });
+        assert!(matches!(
+            events[1],
+            ExecutionEvent::CriterionStarted { ctn_node_id: 1, .. }
+        ));
+        match &events[4] {
+            ExecutionEvent::CriterionCompleted {
+                ctn_node_id,
+                status,
+                completed,
+                total_criteria,
+                ..
+            } => {
+                assert_eq!(*ctn_node_id, 2);
+                assert_eq!(*status, ComplianceStatus::Pass);
+                assert_eq!((*completed, *total_criteria), (2, 2));
+            }
+            other => panic!("expected a completed criterion, got {:?}", other),
+        }
+        assert!(matches!(
+            events[5],
+            ExecutionEvent::ScanCompleted { passed, .. } if passed == result.results.passed
+        ));
+    }
+
+    /// Fails every criterion of `CTN_TYPE`
+    struct FailingExecutor;
+
+    impl CtnExecutor for FailingExecutor {
+        fn execute_with_contract(
+            &self,
+            _criterion: &ExecutableCriterion,
+            _collected_data: &HashMap<String, CollectedData>,
+            _contract: &CtnContract,
+        ) -> Result<CtnExecutionResult, CtnExecutionError> {
+            Ok(CtnExecutionResult::fail(
+                CTN_TYPE.to_string(),
+                "mismatch".to_string(),
+            ))
+        }
+
+        fn get_ctn_contract(&self) -> CtnContract {
+            CtnContract::new(CTN_TYPE.to_string())
+        }
+
+        fn validate_collected_data(
+            &self,
+            _collected_data: &HashMap<String, CollectedData>,
+            _contract: &CtnContract,
+        ) -> Result<(), CtnExecutionError> {
+            Ok(())
+        }
+
+        fn ctn_type(&self) -> &str {
+            CTN_TYPE
+        }
+    }
+
+    #[test]
+    fn test_failed_fields_are_attached_to_the_finding() {
+        let field = |name: &str, actual: String| FieldValidationResult {
+            field_name: name.to_string(),
+            expected_value: ResolvedValue::String("0600".to_string()),
+            actual_value: ResolvedValue::String(actual),
+            operation: crate::types::common::Operation::Equals,
+            passed: false,
+            message: String::new(),
+            outcome: FieldOutcome::Fail,
+        };
+        let result = CtnExecutionResult::fail(CTN_TYPE.to_string(), "mismatch".to_string())
+            .with_state_results(vec![StateValidationResult {
+                object_id: "sshd_config".to_string(),
+                state_results: vec![
+                    field("mode", "0644".to_string()),
+                    field("content", "x".repeat(64)),
+                ],
+                combined_result: false,
+                state_operator: None,
+                message: String::new(),
+            }]);
+        let engine =
+            ExecutionEngine::new(test_context(1), test_registry()).with_observed_max_bytes(16);
+
+        let finding = engine.ctn_result_to_finding(&result, vec![]).unwrap();
+
+        let mode = &finding.evidence["sshd_config/mode"];
+        assert_eq!(mode.to_string(), "expected mode = 0600, observed 0644");
+        let content = &finding.evidence["sshd_config/content"];
+        assert_eq!(content.observed.as_deref(), Some("x".repeat(16).as_str()));
+        assert!(content.truncated && !mode.truncated);
+    }
+
+    #[test]
+    fn test_waived_failures_pass_and_expired_waivers_are_reported() {
+        let mut registry = CtnStrategyRegistry::new_unvalidated();
+        registry
+            .register_ctn_strategy(Box::new(NoopCollector), Box::new(FailingExecutor))
+            .unwrap();
+        let registry = Arc::new(registry);
+        let waivers = WaiverSet::from_yaml(
+            r#"
+waivers:
+  - ctn_node_id: 1
+    esp_scan_id: test-001
+    expires: 2025-06-01
+    justification: Replaced by the SIEM agent
+  - criterion_type: evidence_heavy
+    ctn_node_id: 2
+    expires: 2025-06-01
+    justification: Replaced by the SIEM agent
+  - ctn_node_id: 2
+    expires: 2025-01-31
+    justification: Pending migration
+"#,
+        )
+        .unwrap();
+        let mut context = test_context(2);
+        context.clock = ScanClock::parse_fixed("2025-05-01T00:00:00Z").unwrap();
+
+        let result = ExecutionEngine::new(context, registry)
+            .with_waivers(Arc::new(waivers))
+            .execute()
+            .unwrap();
+
+        assert!(result.results.passed);
+        assert_eq!(result.results.check.failed_criteria, 0);
+        assert_eq!(result.results.check.waived_criteria, 2);
+        assert_eq!(result.results.findings.len(), 2);
+        assert_eq!(
+            result.results.findings[1]
+                .waiver
+                .as_ref()
+                .map(|w| w.ctn_node_id),
+            Some(Some(2))
+        );
+        assert_eq!(result.expired_waivers.len(), 1);
+        assert_eq!(result.expired_waivers[0].justification, "Pending migration");
+    }
+
+    #[test]
+    fn test_identical_objects_are_collected_once() {
+        let context = || {
+            let mut context = test_context(3);
+            if let ExecutableCriteriaTree::Block { children, .. } = &mut context.criteria_tree {
+                for (n, child) in children.iter_mut().enumerate() {
+                    if let ExecutableCriteriaTree::Criterion(criterion) = child {
+                        criterion.objects.push(ExecutableObject {
+                            identifier: format!("sshd_config_{}", n),
+                            elements: vec![ExecutableObjectElement::Field {
+                                name: "path".to_string(),
+                                value: ResolvedValue::String("/etc/ssh/sshd_config".to_string()),
+                            }],
+                            is_global: false,
+                        });
+                    }
+                }
+            }
+            context
+        };
+
+        let result = ExecutionEngine::new(context(), test_registry())
+            .execute()
+            .unwrap();
+        let cache = &result.performance.collection_cache;
+        assert_eq!((cache.misses, cache.hits, cache.entries), (1, 2, 1));
+
+        let uncached = ExecutionEngine::new(context(), test_registry())
+            .with_collection_cache(CollectionCache::disabled())
+            .execute()
+            .unwrap();
+        assert_eq!(
+            uncached.performance.collection_cache,
+            CollectionCacheStats::default()
+        );
+        assert_eq!(uncached.results.passed, result.results.passed);
+    }
+
+    fn leaf(status: ComplianceStatus) -> TreeResult {
+        TreeResult {
+            status,
+            logical_op: None,
+            negated: false,
+            ctn_results: vec![],
+            child_results: vec![],
+        }
+    }
+
+    fn block(op: LogicalOp, negated: bool, child_results: Vec<TreeResult>) -> TreeResult {
+        let combined = combine(op, child_results.iter().map(|c| c.status));
+        TreeResult {
+            status: if negated { combined.negate() } else { combined },
+            logical_op: Some(op),
+            negated,
+            ctn_results: vec![],
+            child_results,
+        }
+    }
+
+    #[test]
+    fn test_block_status_propagation() {
+        use ComplianceStatus::*;
+
+        assert_eq!(ComplianceStatus::all([Pass, Error]), Error);
+        assert_eq!(ComplianceStatus::all([Error, Fail]), Fail);
+        assert_eq!(ComplianceStatus::all([Pass, Unknown]), Unknown);
+        assert_eq!(ComplianceStatus::all([Unknown, Error]), Error);
+        assert_eq!(ComplianceStatus::all([Pass, NotApplicable]), Pass);
+        assert_eq!(
+            ComplianceStatus::all([NotApplicable, NotApplicable]),
+            NotApplicable
+        );
+
+        assert_eq!(ComplianceStatus::any([Fail, Error]), Error);
+        assert_eq!(ComplianceStatus::any([Error, Pass]), Pass);
+        assert_eq!(ComplianceStatus::any([Fail, Unknown]), Unknown);
+        assert_eq!(ComplianceStatus::any([Fail, NotApplicable]), Fail);
+        assert_eq!(ComplianceStatus::any([NotApplicable]), NotApplicable);
+    }
+
+    #[test]
+    fn test_negate_leaves_error_undecided() {
+        use ComplianceStatus::*;
+
+        // NOT over an undecided AND stays undecided
+        let tree = block(LogicalOp::And, true, vec![leaf(Pass), leaf(Error)]);
+        assert_eq!(tree.status, Error);
+        assert!(!tree.passes(ErrorMode::Strict));
+        // Leaving the error out, the AND passes and its negation fails
+        assert!(!tree.passes(ErrorMode::Lenient));
+
+        // A failure decides the AND, so the negation passes despite the error
+        let tree = block(LogicalOp::And, true, vec![leaf(Fail), leaf(Error)]);
+        assert_eq!(tree.status, Pass);
+        assert!(tree.passes(ErrorMode::Strict));
+
+        // Nothing decided is left, so nothing fails
+        let tree = block(LogicalOp::Or, true, vec![leaf(Error), leaf(NotApplicable)]);
+        assert_eq!(tree.status, Error);
+        assert!(!tree.passes(ErrorMode::Strict));
+        assert!(tree.passes(ErrorMode::Lenient));
+    }
+
+    #[test]
+    fn test_error_mode_decides_passed() {
+        let registry = || {
+            let mut registry = CtnStrategyRegistry::new_unvalidated();
+            registry
+                .register_ctn_strategy(
+                    Box::new(DenyingCollector),
+                    Box::new(EvidenceHeavyExecutor {
+                        required_privilege: PrivilegeRequirement::None,
+                    }),
+                )
+                .unwrap();
+            Arc::new(registry)
+        };
+        let context = || {
+            let mut context = test_context(1);
+            if let ExecutableCriteriaTree::Block {
+                children, negate, ..
+            } = &mut context.criteria_tree
+            {
+                *negate = true;
+                if let ExecutableCriteriaTree::Criterion(criterion) = &mut children[0] {
+                    criterion.objects.push(ExecutableObject {
+                        identifier: "ssh_dir".to_string(),
+                        elements: vec![],
+                        is_global: false,
+                    });
+                }
+            }
+            context
+        };
+
+        let strict = ExecutionEngine::new(context(), registry())
+            .execute()
+            .unwrap();
+        assert!(!strict.results.passed);
+        assert_eq!(strict.results.check.error_criteria, 1);
+
+        let lenient = ExecutionEngine::new(context(), registry())
+            .with_error_mode(ErrorMode::Lenient)
+            .execute()
+            .unwrap();
+        assert!(lenient.results.passed);
+        assert_eq!(lenient.results.check.error_criteria, 1);
+    }
+
+    /// Fails every object as a collector whose command is not installed does
+    struct MissingToolCollector;
+
+    impl CtnDataCollector for MissingToolCollector {
+        fn collect_for_ctn_with_hints(
+            &self,
+            _object: &ExecutableObject,
+            _contract: &CtnContract,
+            _hints: &BehaviorHints,
+        ) -> Result<CollectedData, CollectionError> {
+            Err(CollectionError::MissingCapability {
+                capability: "auditctl (audit is not installed)".to_string(),
+            })
+        }
+
+        fn supported_ctn_types(&self) -> Vec<String> {
+            vec![CTN_TYPE.to_string()]
+        }
+
+        fn validate_ctn_compatibility(
+            &self,
+            _contract: &CtnContract,
+        ) -> Result<(), CollectionError> {
+            Ok(())
+        }
+
+        fn collector_id(&self) -> &str {
+            "missing-tool-collector"
+        }
+    }
+
+    #[test]
+    fn test_missing_capability_marks_criterion_error_and_continues() {
+        let mut registry = CtnStrategyRegistry::new_unvalidated();
+        registry
+            .register_ctn_strategy(
+                Box::new(MissingToolCollector),
+                Box::new(EvidenceHeavyExecutor {
+                    required_privilege: PrivilegeRequirement::None,
+                }),
+            )
+            .unwrap();
+
+        let mut context = test_context(2);
+        if let ExecutableCriteriaTree::Block { children, .. } = &mut context.criteria_tree {
+            if let ExecutableCriteriaTree::Criterion(criterion) = &mut children[0] {
+                criterion.objects.push(ExecutableObject {
+                    identifier: "audit_rules".to_string(),
+                    elements: vec![],
+                    is_global: false,
+                });
+            }
+        }
+
+        let mut engine = ExecutionEngine::new(context, Arc::new(registry));
+        let tree = engine.context.criteria_tree.clone();
+        let tree_result = engine.execute_tree(&tree).unwrap();
+
+        let ctn = &tree_result.child_results[0].ctn_results[0].execution_result;
+        assert_eq!(ctn.status, ComplianceStatus::Error);
+        assert_eq!(ctn.details["reason"], MISSING_CAPABILITY);
+        assert!(ctn.message.contains("auditctl"));
+        let other = &tree_result.child_results[1].ctn_results[0].execution_result;
+        assert_eq!(other.status, ComplianceStatus::Pass);
+    }
+
+    /// Minimal strategy for an arbitrary criterion type
+    struct TypedCollector(&'static str);
+
+    impl CtnDataCollector for TypedCollector {
+        fn collect_for_ctn_with_hints(
+            &self,
+            object: &ExecutableObject,
+            contract: &CtnContract,
+            _hints: &BehaviorHints,
+        ) -> Result<CollectedData, CollectionError> {
+            Ok(CollectedData::new(
+                object.identifier.clone(),
+                contract.ctn_type.clone(),
+                self.collector_id().to_string(),
+            ))
+        }
+
+        fn supported_ctn_types(&self) -> Vec<String> {
+            vec![self.0.to_string()]
+        }
+
+        fn validate_ctn_compatibility(
+            &self,
+            _contract: &CtnContract,
+        ) -> Result<(), CollectionError> {
+            Ok(())
+        }
+
+        fn collector_id(&self) -> &str {
+            self.0
+        }
+    }
+
+    struct TypedExecutor(&'static str);
+
+    impl CtnExecutor for TypedExecutor {
+        fn execute_with_contract(
+            &self,
+            _criterion: &ExecutableCriterion,
+            _collected_data: &HashMap<String, CollectedData>,
+            _contract: &CtnContract,
+        ) -> Result<CtnExecutionResult, CtnExecutionError> {
+            Ok(CtnExecutionResult::pass(
+                self.0.to_string(),
+                "ok".to_string(),
+            ))
+        }
+
+        fn get_ctn_contract(&self) -> CtnContract {
+            CtnContract::new(self.0.to_string())
+        }
+
+        fn validate_collected_data(
+            &self,
+            _collected_data: &HashMap<String, CollectedData>,
+            _contract: &CtnContract,
+        ) -> Result<(), CtnExecutionError> {
+            Ok(())
+        }
+
+        fn ctn_type(&self) -> &str {
+            self.0
+        }
+    }
+
+    #[test]
+    fn test_strategy_usage_counts_per_criterion_type() {
+        let mut registry = CtnStrategyRegistry::new_unvalidated();
+        for ctn_type in ["alpha", "beta", "gamma"] {
+            registry
+                .register_ctn_strategy(
+                    Box::new(TypedCollector(ctn_type)),
+                    Box::new(TypedExecutor(ctn_type)),
+                )
+                .unwrap();
+        }
+
+        // alpha x3, beta x2, gamma x1, unregistered x2
+        let types = [
+            "alpha", "alpha", "beta", "gamma", "alpha", "beta", "delta", "delta",
+        ];
+        let mut context = test_context(types.len());
+        if let ExecutableCriteriaTree::Block { children, .. } = &mut context.criteria_tree {
+            for (child, ctn_type) in children.iter_mut().zip(types) {
+                if let ExecutableCriteriaTree::Criterion(criterion) = child {
+                    criterion.criterion_type = ctn_type.to_string();
+                }
+            }
+        }
+
+        let mut engine =
+            ExecutionEngine::new(context, Arc::new(registry)).with_allow_unknown_criteria(true);
+        let result = engine.execute().unwrap();
+        let performance = &result.performance;
+
+        assert_eq!(performance.strategies.len(), 3);
+        assert_eq!(performance.strategies["alpha"].invocations, 3);
+        assert_eq!(performance.strategies["beta"].invocations, 2);
+        assert_eq!(performance.strategies["gamma"].invocations, 1);
+        assert_eq!(performance.strategies["gamma"].strategy, "gamma");
+        assert!(performance.strategies.values().all(|u| u.errors == 0));
+        assert_eq!(performance.unmatched_criterion_types, vec!["delta"]);
+
+        // Allowed unregistered types are per-criterion errors, not a failed scan
+        assert_eq!(result.results.check.error_criteria, 2);
+        assert_eq!(result.results.check.passed_criteria, 6);
+    }
+
+    #[test]
+    fn test_unknown_criterion_types_abort_before_execution() {
+        let mut context = test_context(3);
+        if let ExecutableCriteriaTree::Block { children, .. } = &mut context.criteria_tree {
+            if let ExecutableCriteriaTree::Criterion(criterion) = &mut children[1] {
+                criterion.criterion_type = "ldap_bind".to_string();
+            }
+        }
+
+        let mut engine = ExecutionEngine::new(context, test_registry());
+        let unknown = engine.unknown_criteria();
+        assert_eq!(unknown.len(), 1);
+        assert_eq!(unknown[0].criterion_type, "ldap_bind");
+        let node_id = unknown[0].ctn_node_ids[0];
+
+        let err = engine.execute().unwrap_err();
+        assert!(matches!(err, ExecutionError::UnknownCriterionTypes { .. }));
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "No strategy registered for criterion types: 'ldap_bind' (CTN #{})",
+                node_id
+            )
+        );
+        // Nothing ran
+        assert!(engine.strategy_usage().strategies.is_empty());
+    }
+
+    #[test]
+    fn test_plan_lists_objects_strategies_and_unmatched_types() {
+        let mut context = test_context(2);
+        if let ExecutableCriteriaTree::Block { children, .. } = &mut context.criteria_tree {
+            if let ExecutableCriteriaTree::Criterion(criterion) = &mut children[0] {
+                criterion.objects.push(ExecutableObject {
+                    identifier: "sshd".to_string(),
+                    elements: vec![
+                        ExecutableObjectElement::Field {
+                            name: "path".to_string(),
+                            value: ResolvedValue::String("/etc/ssh/sshd_config".to_string()),
+                        },
+                        ExecutableObjectElement::Behavior {
+                            values: vec!["recursive_scan".to_string()],
+                        },
+                    ],
+                    is_global: false,
+                });
+            }
+            if let ExecutableCriteriaTree::Criterion(criterion) = &mut children[1] {
+                criterion.criterion_type = "ldap_bind".to_string();
+            }
+        }
+
+        let engine = ExecutionEngine::new(context, test_registry());
+        let plan = engine.plan();
+        assert_eq!(plan.criteria.len(), 2);
+        assert!(!plan.is_fully_matched());
+        assert_eq!(
+            plan.unmatched_criterion_types[0].criterion_type,
+            "ldap_bind"
+        );
+        assert!(plan.criteria[1].strategy.is_none());
+
+        let json: serde_json::Value = serde_json::from_str(&plan.to_json().unwrap()).unwrap();
+        let planned = &json["criteria"][0];
+        assert_eq!(planned["criterion_id"], format!("{}#1", CTN_TYPE));
+        assert_eq!(planned["strategy"]["required_privilege"], "none");
+        assert_eq!(
+            planned["objects"][0]["fields"]["path"],
+            "/etc/ssh/sshd_config"
+        );
+        assert_eq!(planned["objects"][0]["behaviors"][0], "recursive_scan");
+        assert_eq!(json["unmatched_criterion_types"][0]["ctn_node_ids"][0], 2);
+    }
+
+    #[test]
+    fn test_reordered_policy_diffs_clean() {
+        let with_object = |criterion: &mut ExecutableCriterion| {
+            criterion.objects.push(ExecutableObject {
+                identifier: "hosts".to_string(),
+                elements: vec![ExecutableObjectElement::Field {
+                    name: "path".to_string(),
+                    value: ResolvedValue::String("/etc/hosts".to_string()),
+                }],
+                is_global: false,
+            });
+        };
+        let mut original = test_context(2);
+        let mut reordered = test_context(2);
+        if let ExecutableCriteriaTree::Block { children, .. } = &mut original.criteria_tree {
+            if let ExecutableCriteriaTree::Criterion(criterion) = &mut children[0] {
+                with_object(criterion);
+            }
+        }
+        if let ExecutableCriteriaTree::Block { children, .. } = &mut reordered.criteria_tree {
+            if let ExecutableCriteriaTree::Criterion(criterion) = &mut children[1] {
+                with_object(criterion);
+            }
+        }
+
+        let previous = ExecutionEngine::new(original, test_registry())
+            .execute()
+            .unwrap();
+        let current = ExecutionEngine::new(reordered, test_registry())
+            .execute()
+            .unwrap();
+
+        assert_eq!(current.criteria.len(), 2);
+        assert_ne!(
+            current.criteria[0].fingerprint,
+            current.criteria[1].fingerprint
+        );
+        assert_eq!(
+            current.criteria[1].fingerprint,
+            previous.criteria[0].fingerprint
+        );
+        assert!(current.diff(&previous).is_empty());
+    }
+
+    #[test]
+    fn test_empty_policy_is_reported_without_scanning() {
+        let err = ExecutionEngine::new(test_context(0), test_registry())
+            .execute()
+            .unwrap_err();
+        assert!(matches!(err, ExecutionError::EmptyPolicy));
+
+        let mut engine = ExecutionEngine::new(test_context(0), test_registry())
+            .with_empty_policy(EmptyPolicy::Pass);
+        let result = engine.execute().unwrap();
+        assert!(result.empty_policy && result.results.passed);
+        assert!(engine.strategy_usage().strategies.is_empty());
+        let json: serde_json::Value = serde_json::from_str(&result.to_json().unwrap()).unwrap();
+        assert_eq!(json["empty_policy"], true);
+        assert!(json["results"]["check"]["pass_percentage"].is_null());
+
+        let result = ExecutionEngine::new(test_context(0), test_registry())
+            .with_empty_policy(EmptyPolicy::Fail)
+            .execute()
+            .unwrap();
+        assert!(result.empty_policy && !result.results.passed);
+        assert!(matches!(
+            result.results.check.status,
+            ResultComplianceStatus::NonCompliant
+        ));
+    }
+
+    #[test]
+    fn test_shared_usage_tracker_aggregates_engines() {
+        let tracker = Arc::new(StrategyUsageTracker::new());
+        for _ in 0..2 {
+            ExecutionEngine::new(test_context(2), test_registry())
+                .with_usage_tracker(Arc::clone(&tracker))
+                .execute()
+                .unwrap();
+        }
+        assert_eq!(tracker.snapshot().strategies[CTN_TYPE].invocations, 4);
+    }
+
+    #[test]
+    fn test_fixed_clock_sets_scan_timestamps() {
+        let clock = ScanClock::parse_fixed("2026-03-01T12:00:00Z").unwrap();
+        let context = test_context(1).with_clock(clock);
+        let result = ExecutionEngine::new(context, test_registry())
+            .execute()
+            .unwrap();
+
+        let timestamp = &result.metadata.timestamp;
+        assert!(timestamp.fixed_clock);
+        assert_eq!(timestamp.scan_start, clock.now());
+        assert_eq!(timestamp.scan_end, clock.now());
+        assert_eq!(result.scan_id, format!("scan_{}", clock.now().timestamp()));
+    }
+
+    /// Reads a fixture file, then runs a hook (e.g. editing the file)
+    struct ObservingCollector {
+        path: std::path::PathBuf,
+        after_collect: Box<dyn Fn(&std::path::Path) + Send + Sync>,
+    }
+
+    impl CtnDataCollector for ObservingCollector {
+        fn collect_for_ctn_with_hints(
+            &self,
+            object: &ExecutableObject,
+            contract: &CtnContract,
+            _hints: &BehaviorHints,
+        ) -> Result<CollectedData, CollectionError> {
+            let content = std::fs::read(&self.path).unwrap();
+            let mut data = CollectedData::new(
+                object.identifier.clone(),
+                contract.ctn_type.clone(),
+                self.collector_id().to_string(),
+            );
+            data.observe_file(
+                FileObservation::new(self.path.display().to_string()).with_content(&content),
+            );
+            (self.after_collect)(&self.path);
+            Ok(data)
+        }
+
+        fn supported_ctn_types(&self) -> Vec<String> {
+            vec!["observed".to_string()]
+        }
+
+        fn validate_ctn_compatibility(
+            &self,
+            _contract: &CtnContract,
+        ) -> Result<(), CollectionError> {
+            Ok(())
+        }
+
+        fn collector_id(&self) -> &str {
+            "observing-collector"
+        }
+
+        // Every criterion reads the file afresh, so edits between them show
+        fn supports_result_caching(&self, _ctn_type: &str) -> bool {
+            false
+        }
+    }
+
+    fn changing_file_context(
+        dir: &std::path::Path,
+    ) -> (ExecutionContext, Arc<CtnStrategyRegistry>) {
+        let path = dir.join("sshd_config");
+        std::fs::write(&path, "PermitRootLogin no\n").unwrap();
+
+        let mut registry = CtnStrategyRegistry::new_unvalidated();
+        registry
+            .register_ctn_strategy(
+                Box::new(ObservingCollector {
+                    path,
+                    after_collect: Box::new(|path| {
+                        let mut content = std::fs::read_to_string(path).unwrap();
+                        content.push_str("# edited\n");
+                        std::fs::write(path, content).unwrap();
+                    }),
+                }),
+                Box::new(TypedExecutor("observed")),
+            )
+            .unwrap();
+
+        let mut context = test_context(2);
+        if let ExecutableCriteriaTree::Block { children, .. } = &mut context.criteria_tree {
+            for child in children {
+                if let ExecutableCriteriaTree::Criterion(criterion) = child {
+                    criterion.criterion_type = "observed".to_string();
+                    criterion.objects.push(ExecutableObject {
+                        identifier: "sshd_config".to_string(),
+                        elements: vec![],
+                        is_global: false,
+                    });
+                }
+            }
+        }
+        (context, Arc::new(registry))
+    }
+
+    #[test]
+    fn test_file_changed_between_criteria_is_flagged() {
+        let dir = tempfile::tempdir().unwrap();
+        let (context, registry) = changing_file_context(dir.path());
+        let result = ExecutionEngine::new(context, registry).execute().unwrap();
+
+        assert!(result.results.passed);
+        assert_eq!(result.consistency.len(), 1);
+        let mismatch = &result.consistency[0];
+        assert_eq!(mismatch.first_criterion, "observed#1");
+        assert_eq!(mismatch.second_criterion, "observed#2");
+        assert_ne!(mismatch.first.content_hash, mismatch.second.content_hash);
+
+        let flagged: Vec<&str> = result
+            .advisories
+            .iter()
+            .filter(|a| a.message.starts_with(DATA_CHANGED_DURING_SCAN))
+            .map(|a| a.criterion_id.as_str())
+            .collect();
+        assert_eq!(flagged, ["observed#1", "observed#2"]);
+    }
+
+    #[test]
+    fn test_strict_consistency_turns_mismatch_into_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let (context, registry) = changing_file_context(dir.path());
+        let result = ExecutionEngine::new(context, registry)
+            .with_strict_consistency(true)
+            .execute()
+            .unwrap();
+
+        assert!(!result.results.passed);
+        assert_eq!(result.results.check.error_criteria, 2);
+        assert_eq!(result.consistency.len(), 1);
+    }
+
+    #[test]
+    fn test_metadata_order_preserved_in_scan_result() {
+        let result = ExecutionEngine::new(test_context(1), test_registry())
+            .execute()
+            .unwrap();
+
+        let names: Vec<&str> = result
+            .metadata
+            .esp_metadata
+            .fields
+            .iter()
+            .map(|f| f.name.as_str())
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "esp_scan_id",
+                "control_framework",
+                "control",
+                "platform",
+                "criticality",
+                "tags"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_relative_datetimes_resolve_against_the_scan_clock() {
+        let datetime = |value: &str| ExecutableStateField {
+            name: "modified".to_string(),
+            data_type: crate::types::common::DataType::DateTime,
+            operation: crate::types::common::Operation::GreaterThan,
+            value: ResolvedValue::String(value.to_string()),
+            entity_check: None,
+        };
+        let context = |value: &str| {
+            let mut context = test_context(1);
+            context.clock = ScanClock::parse_fixed("2025-05-01T00:00:00Z").unwrap();
+            for criterion in context.criteria_tree.get_all_criteria_mut() {
+                criterion.states.push(ExecutableState {
+                    identifier: "recent".to_string(),
+                    fields: vec![datetime(value), datetime("2025-01-01T00:00:00Z")],
+                    record_checks: vec![],
+                    is_global: false,
+                });
+            }
+            context
+        };
+
+        let mut engine = ExecutionEngine::new(context("now-30d"), test_registry());
+        engine.execute().unwrap();
+        let criteria = engine.context.criteria_tree.get_all_criteria();
+        let values: Vec<&ResolvedValue> = criteria[0].states[0]
+            .fields
+            .iter()
+            .map(|field| &field.value)
+            .collect();
+        let now = 1_746_057_600;
+        assert_eq!(
+            values,
+            [
+                &ResolvedValue::Integer(now - 30 * 86_400),
+                &ResolvedValue::Integer(1_735_689_600)
+            ]
+        );
+
+        let err = ExecutionEngine::new(context("last month"), test_registry())
+            .execute()
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("state 'recent' field 'modified'"), "{}", err);
+    }
+}
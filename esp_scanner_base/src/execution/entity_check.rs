@@ -124,15 +124,24 @@ pub fn get_collection_strategy(
 /// Example collector implementation with entity check support
 #[cfg(test)]
 mod example_collector {
-    use super::*;
-    use crate::strategies::{CollectedData, CollectionError, DataCollector};
+    use crate::execution::BehaviorHints;
+    use crate::strategies::{CollectedData, CollectionError, CtnContract, CtnDataCollector};
     use crate::types::execution_context::ExecutableObject;
 
     pub struct ExampleEntityAwareCollector;
 
-    impl DataCollector for ExampleEntityAwareCollector {
-        fn collect(&self, object: &ExecutableObject) -> Result<CollectedData, CollectionError> {
-            let mut data = CollectedData::new(object.identifier.clone());
+    impl CtnDataCollector for ExampleEntityAwareCollector {
+        fn collect_for_ctn_with_hints(
+            &self,
+            object: &ExecutableObject,
+            ctn_contract: &CtnContract,
+            _hints: &BehaviorHints,
+        ) -> Result<CollectedData, CollectionError> {
+            let data = CollectedData::new(
+                object.identifier.clone(),
+                ctn_contract.ctn_type.clone(),
+                self.collector_id().to_string(),
+            );
 
             // This would normally be passed from executor context
             // For now, we'd need to extract entity check info from object somehow
@@ -150,20 +159,18 @@ mod example_collector {
             Ok(data)
         }
 
-        fn extract_field(
+        fn supported_ctn_types(&self) -> Vec<String> {
+            vec!["test".to_string()]
+        }
+
+        fn validate_ctn_compatibility(
             &self,
-            data: &CollectedData,
-            field_path: &str,
-        ) -> Result<ResolvedValue, CollectionError> {
-            data.get_field(field_path)
-                .cloned()
-                .ok_or_else(|| CollectionError::FieldNotAccessible {
-                    object_id: data.object_id.clone(),
-                    field_name: field_path.to_string(),
-                })
+            _ctn_contract: &CtnContract,
+        ) -> Result<(), CollectionError> {
+            Ok(())
         }
 
-        fn collector_type(&self) -> &str {
+        fn collector_id(&self) -> &str {
             "example_entity_aware"
         }
     }
@@ -174,8 +181,7 @@ mod tests {
     use super::*;
     use crate::types::common::*;
     use crate::types::execution_context::*;
-    use crate::types::state::EntityCheck;
-    use crate::types::test::*;
+    use crate::types::{EntityCheck, ExistenceCheck, ItemCheck, TestSpecification};
 
     fn create_test_criterion_with_entity_check() -> ExecutableCriterion {
         let field_with_entity_check = ExecutableStateField {
@@ -198,18 +204,25 @@ mod tests {
             identifier: "test_state".to_string(),
             fields: vec![field_with_entity_check, field_without_entity_check],
             record_checks: vec![],
-            source: StateSource::Local,
-            is_valid: true,
+            is_global: false,
         };
 
         ExecutableCriterion {
             criterion_type: "test".to_string(),
-            test: TestSpecification::simple(ExistenceCheck::Any, ItemCheck::All),
+            test: TestSpecification {
+                existence_check: ExistenceCheck::Any,
+                item_check: ItemCheck::All,
+                state_operator: None,
+                entity_check: None,
+                span: None,
+            },
             ctn_node_id: 0,
             states: vec![state],
             objects: vec![],
-            referenced_global_states: HashMap::new(),
-            referenced_global_objects: HashMap::new(),
+            set_filters: HashMap::new(),
+            active_object_ids: None,
+            controls: vec![],
+            severity: None,
         }
     }
 
@@ -258,4 +271,33 @@ mod tests {
         assert_eq!(field_names.len(), 1);
         assert!(field_names.contains("test_field"));
     }
+
+    #[test]
+    fn test_example_collector_wraps_entity_fields() {
+        use super::example_collector::ExampleEntityAwareCollector;
+        use crate::strategies::{CollectedData, CtnDataCollector};
+
+        let criterion = create_test_criterion_with_entity_check();
+        let collector = ExampleEntityAwareCollector;
+        let strategy = get_collection_strategy(&criterion, "obj");
+
+        let mut data = CollectedData::new(
+            "obj".to_string(),
+            "test".to_string(),
+            collector.collector_id().to_string(),
+        );
+        for (field, needs_collection) in &strategy {
+            let value = ResolvedValue::String("value".to_string());
+            data.add_field(
+                field.clone(),
+                wrap_for_entity_check(value, *needs_collection),
+            );
+        }
+
+        let test_field = collector.extract_field(&data, "test_field").unwrap();
+        assert!(matches!(test_field, ResolvedValue::Collection(_)));
+        let normal_field = collector.extract_field(&data, "normal_field").unwrap();
+        assert!(matches!(normal_field, ResolvedValue::String(_)));
+        assert!(collector.extract_field(&data, "missing").is_err());
+    }
 }
@@ -99,8 +99,12 @@ impl FilterEvaluator {
                 }
             })?;
 
-            // FIXED: Use compare_with method on ResolvedValue (it's a trait method)
-            let field_passed = field.value.compare_with(actual_value, field.operation)?;
+            // Collected value first; a list-valued expectation honours the entity check
+            let field_passed = actual_value.compare_with_entity_check(
+                &field.value,
+                field.operation,
+                field.entity_check,
+            )?;
 
             if !field_passed {
                 return Ok(false); // One field fails = state fails
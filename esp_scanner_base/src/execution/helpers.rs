@@ -3,9 +3,11 @@
 //! Centralized helper functions for TEST specification evaluation.
 //! These functions provide consistent logic for all executors to use.
 
+use crate::strategies::CollectedData;
 // FIXED: Import EntityCheck directly from compiler (it's re-exported in types/mod.rs now)
 use crate::types::EntityCheck;
-use crate::types::{ExistenceCheck, ItemCheck, StateJoinOp};
+use crate::types::{ExistenceCheck, ItemCheck, RecordData, ResolvedValue, StateJoinOp};
+use std::collections::HashMap;
 
 /// Evaluate existence check against collected object counts
 pub fn evaluate_existence_check(
@@ -32,6 +34,21 @@ pub fn evaluate_item_check(check: ItemCheck, items_passing: usize, items_total:
     }
 }
 
+/// Evaluate the item check of a TEST whose existence check has passed
+///
+/// A passing `none` existence check means nothing was found, so the item
+/// check holds vacuously over zero items; under any other existence check
+/// zero items fail a positive item check as usual.
+pub fn evaluate_test_item_check(
+    existence_check: ExistenceCheck,
+    item_check: ItemCheck,
+    items_passing: usize,
+    items_total: usize,
+) -> bool {
+    (matches!(existence_check, ExistenceCheck::None) && items_total == 0)
+        || evaluate_item_check(item_check, items_passing, items_total)
+}
+
 /// Evaluate state operator to combine multiple state results
 /// FIXED: None now defaults to AND behavior
 pub fn evaluate_state_operator(operator: Option<StateJoinOp>, state_results: &[bool]) -> bool {
@@ -55,14 +72,40 @@ pub fn evaluate_entity_check(check: Option<EntityCheck>, entity_results: &[bool]
         return check.is_none();
     }
 
+    // DEFAULT TO ALL when no check specified
+    let passing = entity_results.iter().filter(|&&result| result).count();
+    evaluate_entity_counts(
+        check.unwrap_or(EntityCheck::All),
+        passing,
+        entity_results.len(),
+    )
+}
+
+/// Entity check over list items; an empty list holds for `all` and `none`
+pub fn evaluate_list_entity_check(check: Option<EntityCheck>, item_results: &[bool]) -> bool {
+    if item_results.is_empty() {
+        return matches!(
+            check,
+            None | Some(EntityCheck::All) | Some(EntityCheck::None)
+        );
+    }
+    evaluate_entity_check(check, item_results)
+}
+
+/// Evaluate entity check against how many of the entities passed
+///
+/// `at_least(n)` fails when fewer than n entities exist, and
+/// `at_least_percent(p)` fails when there are no entities at all.
+pub fn evaluate_entity_counts(check: EntityCheck, passing: usize, total: usize) -> bool {
     match check {
-        Some(EntityCheck::All) | None => {
-            // DEFAULT TO ALL when no check specified
-            entity_results.iter().all(|&result| result)
+        EntityCheck::All => passing == total,
+        EntityCheck::AtLeastOne => passing >= 1,
+        EntityCheck::None => passing == 0,
+        EntityCheck::OnlyOne => passing == 1,
+        EntityCheck::AtLeastN(n) => passing as u64 >= u64::from(n),
+        EntityCheck::AtLeastPercent(p) => {
+            total > 0 && passing as u64 * 100 >= u64::from(p) * total as u64
         }
-        Some(EntityCheck::AtLeastOne) => entity_results.iter().any(|&result| result),
-        Some(EntityCheck::None) => entity_results.iter().all(|&result| !result),
-        Some(EntityCheck::OnlyOne) => entity_results.iter().filter(|&&result| result).count() == 1,
     }
 }
 
@@ -143,6 +186,79 @@ pub fn create_item_check_result(
     }
 }
 
+/// Read one record field as a resolved value
+///
+/// Strings, integers, floats and booleans map to their scalar values; an
+/// array becomes a `Collection` of its scalar entries.
+pub fn record_field(record: &RecordData, name: &str) -> Option<ResolvedValue> {
+    match record.get_field_by_path(name)? {
+        serde_json::Value::Array(entries) => Some(ResolvedValue::Collection(
+            entries.iter().filter_map(json_scalar).collect(),
+        )),
+        value => json_scalar(value),
+    }
+}
+
+/// A scalar record field as text, empty when absent
+pub fn record_text(record: &RecordData, name: &str) -> String {
+    match record.get_field_by_path(name) {
+        Some(serde_json::Value::String(s)) => s.clone(),
+        Some(value @ (serde_json::Value::Number(_) | serde_json::Value::Bool(_))) => {
+            value.to_string()
+        }
+        _ => String::new(),
+    }
+}
+
+/// Expand collected objects into one item per record of a collection field
+///
+/// Items are named `object_id[key]`, with `key_fn` naming each record; a key
+/// repeated within one object is numbered (`object_id[key#2]`). Items come
+/// back sorted by name so results are stable across runs.
+pub fn collection_items<'a, F>(
+    collected_data: &'a HashMap<String, CollectedData>,
+    field: &str,
+    key_fn: F,
+) -> Vec<(String, &'a RecordData)>
+where
+    F: Fn(&CollectedData, &RecordData) -> String,
+{
+    let mut items = Vec::new();
+    for (object_id, data) in collected_data {
+        let Some(ResolvedValue::Collection(records)) = data.get_field(field) else {
+            continue;
+        };
+        let mut seen: HashMap<String, usize> = HashMap::new();
+        for record in records {
+            if let ResolvedValue::RecordData(record) = record {
+                let label = key_fn(data, record);
+                let count = seen.entry(label.clone()).or_default();
+                *count += 1;
+                let item_id = if *count == 1 {
+                    format!("{}[{}]", object_id, label)
+                } else {
+                    format!("{}[{}#{}]", object_id, label, count)
+                };
+                items.push((item_id, record.as_ref()));
+            }
+        }
+    }
+    items.sort_by(|a, b| a.0.cmp(&b.0));
+    items
+}
+
+fn json_scalar(value: &serde_json::Value) -> Option<ResolvedValue> {
+    match value {
+        serde_json::Value::String(s) => Some(ResolvedValue::String(s.clone())),
+        serde_json::Value::Bool(b) => Some(ResolvedValue::Boolean(*b)),
+        serde_json::Value::Number(n) => match n.as_i64() {
+            Some(i) => Some(ResolvedValue::Integer(i)),
+            None => n.as_f64().map(ResolvedValue::Float),
+        },
+        _ => None,
+    }
+}
+
 // Helper functions for string conversion
 fn existence_check_to_str(check: ExistenceCheck) -> &'static str {
     match check {
@@ -175,6 +291,73 @@ mod tests {
         assert!(!evaluate_state_operator(None, &[false, false, false]));
     }
 
+    #[test]
+    fn test_item_check_fails_on_zero_items() {
+        assert!(!evaluate_item_check(ItemCheck::All, 0, 0));
+        assert!(!evaluate_item_check(ItemCheck::AtLeastOne, 0, 0));
+        assert!(!evaluate_item_check(ItemCheck::OnlyOne, 0, 0));
+        assert!(evaluate_item_check(ItemCheck::NoneSatisfy, 0, 0));
+    }
+
+    #[test]
+    fn test_test_item_check_is_vacuous_only_after_existence_none() {
+        for check in [ItemCheck::All, ItemCheck::AtLeastOne, ItemCheck::OnlyOne] {
+            assert!(evaluate_test_item_check(ExistenceCheck::None, check, 0, 0));
+            assert!(!evaluate_test_item_check(ExistenceCheck::Any, check, 0, 0));
+            assert!(!evaluate_test_item_check(
+                ExistenceCheck::AtLeastOne,
+                check,
+                0,
+                0
+            ));
+        }
+        assert!(!evaluate_test_item_check(
+            ExistenceCheck::None,
+            ItemCheck::All,
+            1,
+            2
+        ));
+        assert!(evaluate_test_item_check(
+            ExistenceCheck::Any,
+            ItemCheck::OnlyOne,
+            1,
+            3
+        ));
+    }
+
+    #[test]
+    fn test_collection_items_name_and_number_records() {
+        let record = |name: &str| {
+            ResolvedValue::RecordData(Box::new(RecordData::from_json_value(
+                serde_json::json!({ "name": name, "uid": 0, "groups": ["wheel", 10] }),
+            )))
+        };
+        let mut data = CollectedData::new("users".into(), "test".into(), "test".into());
+        data.add_field(
+            "entries".into(),
+            ResolvedValue::Collection(vec![record("root"), record("bin"), record("root")]),
+        );
+        let collected = HashMap::from([("users".to_string(), data)]);
+
+        let items = collection_items(&collected, "entries", |_, r| record_text(r, "name"));
+        let names: Vec<&str> = items.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, ["users[bin]", "users[root#2]", "users[root]"]);
+        assert!(collection_items(&collected, "missing", |_, _| String::new()).is_empty());
+
+        let (_, root) = items[2];
+        assert_eq!(record_field(root, "uid"), Some(ResolvedValue::Integer(0)));
+        assert_eq!(
+            record_field(root, "groups"),
+            Some(ResolvedValue::Collection(vec![
+                ResolvedValue::String("wheel".into()),
+                ResolvedValue::Integer(10),
+            ]))
+        );
+        assert_eq!(record_field(root, "shell"), None);
+        assert_eq!(record_text(root, "uid"), "0");
+        assert_eq!(record_text(root, "groups"), "");
+    }
+
     #[test]
     fn test_entity_check_none_defaults_to_all() {
         // None should default to ALL behavior
@@ -182,6 +365,49 @@ mod tests {
         assert!(!evaluate_entity_check(None, &[true, false, true]));
     }
 
+    #[test]
+    fn test_counted_entity_checks() {
+        let results = [true, true, false, true];
+        assert!(evaluate_entity_check(
+            Some(EntityCheck::AtLeastN(3)),
+            &results
+        ));
+        assert!(!evaluate_entity_check(
+            Some(EntityCheck::AtLeastN(4)),
+            &results
+        ));
+        // More required than were collected can never pass
+        assert!(!evaluate_entity_check(
+            Some(EntityCheck::AtLeastN(9)),
+            &[true; 3]
+        ));
+
+        assert!(evaluate_entity_check(
+            Some(EntityCheck::AtLeastPercent(75)),
+            &results
+        ));
+        assert!(!evaluate_entity_check(
+            Some(EntityCheck::AtLeastPercent(76)),
+            &results
+        ));
+        assert!(evaluate_entity_check(
+            Some(EntityCheck::AtLeastPercent(100)),
+            &[true; 3]
+        ));
+
+        // Zero entities collected fails both counted checks
+        assert!(!evaluate_entity_check(Some(EntityCheck::AtLeastN(1)), &[]));
+        assert!(!evaluate_entity_check(
+            Some(EntityCheck::AtLeastPercent(1)),
+            &[]
+        ));
+        assert!(!evaluate_entity_counts(
+            EntityCheck::AtLeastPercent(50),
+            0,
+            0
+        ));
+    }
+
     #[test]
     fn test_requires_state_evaluation() {
         // None existence check doesn't need state evaluation
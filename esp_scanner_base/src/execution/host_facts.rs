@@ -0,0 +1,176 @@
+//! # Host Facts
+//!
+//! Facts about the scanning host (hostname and platform, OS release and
+//! kernel, the invoking user, effective privileges) that every scan reports but that do not change
+//! between policies. A [`HostFactsCache`] owned above a per-file scan loop
+//! probes them once and hands the same facts to each engine, so a batch of
+//! 200 policies on one host costs one probe set instead of 200.
+//!
+//! Facts can change under a long-running process (privileges dropped, host
+//! renamed), so cached facts expire after a TTL (five minutes by default)
+//! and [`HostFactsCache::invalidate`] forces the next scan to probe again.
+
+use crate::execution::privilege::EffectivePrivileges;
+use crate::results::target::{self, OsRelease};
+use crate::results::{FactsSource, HostContext, HostFactsProvenance, UserContext};
+use chrono::{DateTime, Utc};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// How long cached facts are reused before probing again
+pub const DEFAULT_HOST_FACTS_TTL: Duration = Duration::from_secs(5 * 60);
+
+/// One probe of the scanning host
+#[derive(Debug, Clone)]
+pub struct HostFacts {
+    pub host: HostContext,
+    pub user: UserContext,
+    pub privileges: EffectivePrivileges,
+    pub os_release: OsRelease,
+    pub kernel_version: Option<String>,
+    /// When the probe ran
+    pub collected_at: DateTime<Utc>,
+}
+
+impl HostFacts {
+    /// Probe the current host and process
+    pub fn probe() -> Self {
+        Self {
+            host: HostContext::from_system(),
+            user: UserContext::from_environment(),
+            privileges: EffectivePrivileges::detect(),
+            os_release: OsRelease::probe(),
+            kernel_version: target::kernel_version(),
+            collected_at: Utc::now(),
+        }
+    }
+
+    /// Provenance to record in a scan result
+    pub fn provenance(&self, source: FactsSource) -> HostFactsProvenance {
+        HostFactsProvenance {
+            source,
+            collected_at: self.collected_at,
+        }
+    }
+}
+
+type Probe = Box<dyn Fn() -> HostFacts + Send + Sync>;
+
+/// Host facts shared by several engines, re-probed once they are stale
+pub struct HostFactsCache {
+    ttl: Duration,
+    probe: Probe,
+    cached: Mutex<Option<(HostFacts, Instant)>>,
+    probes: AtomicUsize,
+}
+
+impl std::fmt::Debug for HostFactsCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HostFactsCache")
+            .field("ttl", &self.ttl)
+            .field("probes", &self.probe_count())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for HostFactsCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HostFactsCache {
+    pub fn new() -> Self {
+        Self {
+            ttl: DEFAULT_HOST_FACTS_TTL,
+            probe: Box::new(HostFacts::probe),
+            cached: Mutex::new(None),
+            probes: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Gather facts with `probe` instead of from the current host
+    pub fn with_probe(mut self, probe: impl Fn() -> HostFacts + Send + Sync + 'static) -> Self {
+        self.probe = Box::new(probe);
+        self
+    }
+
+    /// Facts for one scan, and whether they were probed for it
+    pub fn get(&self) -> (HostFacts, FactsSource) {
+        let mut cached = self
+            .cached
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some((facts, probed)) = cached.as_ref() {
+            if probed.elapsed() < self.ttl {
+                return (facts.clone(), FactsSource::Cached);
+            }
+        }
+
+        let facts = (self.probe)();
+        self.probes.fetch_add(1, Ordering::Relaxed);
+        *cached = Some((facts.clone(), Instant::now()));
+        (facts, FactsSource::Fresh)
+    }
+
+    /// Drop the cached facts; the next scan probes again
+    pub fn invalidate(&self) {
+        *self
+            .cached
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = None;
+    }
+
+    /// How many times the host has been probed
+    pub fn probe_count(&self) -> usize {
+        self.probes.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_facts() -> HostFacts {
+        HostFacts {
+            host: HostContext::new("test-host".to_string(), "linux x86_64".to_string()),
+            user: UserContext::from_environment(),
+            privileges: EffectivePrivileges::unprivileged(),
+            os_release: OsRelease::default(),
+            kernel_version: None,
+            collected_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_facts_are_cached_until_invalidated() {
+        let cache = HostFactsCache::new().with_probe(test_facts);
+
+        assert_eq!(cache.get().1, FactsSource::Fresh);
+        let (facts, source) = cache.get();
+        assert_eq!(source, FactsSource::Cached);
+        assert_eq!(facts.host.hostname, "test-host");
+        assert_eq!(cache.probe_count(), 1);
+
+        cache.invalidate();
+        assert_eq!(cache.get().1, FactsSource::Fresh);
+        assert_eq!(cache.probe_count(), 2);
+    }
+
+    #[test]
+    fn test_stale_facts_are_probed_again() {
+        let cache = HostFactsCache::new()
+            .with_probe(test_facts)
+            .with_ttl(Duration::ZERO);
+
+        cache.get();
+        assert_eq!(cache.get().1, FactsSource::Fresh);
+        assert_eq!(cache.probe_count(), 2);
+    }
+}
@@ -0,0 +1,332 @@
+//! # Memory Budget
+//!
+//! Soft memory budget for scans on constrained hosts. The engine samples its
+//! own resident set size between criteria and, when over budget, degrades in a
+//! fixed order before giving up on the remaining criteria.
+//!
+//! Sampling reads `/proc/self/status` and is only available on Linux. On other
+//! platforms the sampler returns `None` and the budget is never enforced.
+
+use std::fmt;
+
+/// Reason recorded on criteria skipped after the budget is exhausted
+pub const MEMORY_BUDGET_EXCEEDED: &str = "memory_budget_exceeded";
+
+/// Default number of criteria between RSS samples
+pub const DEFAULT_SAMPLE_INTERVAL: usize = 4;
+
+/// Default number of criteria to tolerate after the last mitigation before aborting
+pub const DEFAULT_ABORT_AFTER: usize = 8;
+
+/// Soft memory budget configuration
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryBudget {
+    /// Budget in bytes of resident memory
+    pub limit_bytes: u64,
+    /// Sample RSS every N criteria
+    pub sample_interval: usize,
+    /// Criteria to keep evaluating once all mitigations are spent
+    pub abort_after: usize,
+}
+
+impl MemoryBudget {
+    /// Create a budget with default sampling behavior
+    pub fn new(limit_bytes: u64) -> Self {
+        Self {
+            limit_bytes,
+            sample_interval: DEFAULT_SAMPLE_INTERVAL,
+            abort_after: DEFAULT_ABORT_AFTER,
+        }
+    }
+
+    /// Parse a budget from a human-readable size (e.g. `256M`, `1G`, `512k`)
+    pub fn parse(s: &str) -> Result<Self, String> {
+        parse_memory_size(s).map(Self::new)
+    }
+
+    /// Set how often RSS is sampled
+    pub fn with_sample_interval(mut self, interval: usize) -> Self {
+        self.sample_interval = interval.max(1);
+        self
+    }
+
+    /// Set how many criteria run after the last mitigation before aborting
+    pub fn with_abort_after(mut self, criteria: usize) -> Self {
+        self.abort_after = criteria;
+        self
+    }
+}
+
+/// Parse a memory size with an optional K/M/G suffix (binary units)
+pub fn parse_memory_size(s: &str) -> Result<u64, String> {
+    let trimmed = s.trim();
+    if trimmed.is_empty() {
+        return Err("Memory size cannot be empty".to_string());
+    }
+
+    let upper = trimmed.to_ascii_uppercase();
+    let without_b = upper.strip_suffix("IB").or_else(|| upper.strip_suffix('B'));
+    let body = without_b.unwrap_or(&upper);
+
+    let (digits, multiplier) = match body.chars().last() {
+        Some('K') => (&body[..body.len() - 1], 1024u64),
+        Some('M') => (&body[..body.len() - 1], 1024 * 1024),
+        Some('G') => (&body[..body.len() - 1], 1024 * 1024 * 1024),
+        _ => (body, 1),
+    };
+
+    let value: u64 = digits
+        .trim()
+        .parse()
+        .map_err(|_| format!("Invalid memory size '{}'", s))?;
+
+    if value == 0 {
+        return Err(format!("Memory size '{}' must be greater than zero", s));
+    }
+
+    value
+        .checked_mul(multiplier)
+        .ok_or_else(|| format!("Memory size '{}' is too large", s))
+}
+
+/// Current resident set size of this process in bytes
+#[cfg(target_os = "linux")]
+pub fn current_rss_bytes() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    status
+        .lines()
+        .find(|line| line.starts_with("VmRSS:"))
+        .and_then(|line| line.split_whitespace().nth(1))
+        .and_then(|kb| kb.parse::<u64>().ok())
+        .map(|kb| kb * 1024)
+}
+
+/// Current resident set size of this process in bytes
+#[cfg(not(target_os = "linux"))]
+pub fn current_rss_bytes() -> Option<u64> {
+    None
+}
+
+/// Degradation steps, applied in declaration order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DegradationStep {
+    /// Flush any buffered output sinks
+    FlushSinks,
+    /// Reduce retained evidence to summaries
+    SummarizeEvidence,
+    /// Stop caching command output
+    DisableCommandCache,
+    /// Stop evaluating remaining criteria
+    Abort,
+}
+
+impl DegradationStep {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::FlushSinks => "flush_sinks",
+            Self::SummarizeEvidence => "summarize_evidence",
+            Self::DisableCommandCache => "disable_command_cache",
+            Self::Abort => "abort",
+        }
+    }
+}
+
+impl fmt::Display for DegradationStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+const MITIGATIONS: [DegradationStep; 3] = [
+    DegradationStep::FlushSinks,
+    DegradationStep::SummarizeEvidence,
+    DegradationStep::DisableCommandCache,
+];
+
+type RssSampler = Box<dyn Fn() -> Option<u64> + Send + Sync>;
+
+/// Tracks memory use across criteria and decides when to degrade
+pub struct MemoryMonitor {
+    budget: MemoryBudget,
+    sampler: RssSampler,
+    criteria_seen: usize,
+    mitigations_applied: usize,
+    exhausted_at: Option<usize>,
+    steps: Vec<DegradationStep>,
+    peak_rss_bytes: u64,
+}
+
+impl MemoryMonitor {
+    /// Create a monitor sampling this process's RSS
+    pub fn new(budget: MemoryBudget) -> Self {
+        Self::with_sampler(budget, current_rss_bytes)
+    }
+
+    /// Create a monitor with a custom RSS sampler
+    pub fn with_sampler<F>(budget: MemoryBudget, sampler: F) -> Self
+    where
+        F: Fn() -> Option<u64> + Send + Sync + 'static,
+    {
+        Self {
+            budget,
+            sampler: Box::new(sampler),
+            criteria_seen: 0,
+            mitigations_applied: 0,
+            exhausted_at: None,
+            steps: Vec::new(),
+            peak_rss_bytes: 0,
+        }
+    }
+
+    /// Record a completed criterion and return the next step to apply, if any
+    pub fn after_criterion(&mut self) -> Option<DegradationStep> {
+        if self.is_aborted() {
+            return None;
+        }
+
+        self.criteria_seen += 1;
+        if self.criteria_seen % self.budget.sample_interval.max(1) != 0 {
+            return None;
+        }
+
+        let rss = (self.sampler)()?;
+        self.peak_rss_bytes = self.peak_rss_bytes.max(rss);
+        if rss <= self.budget.limit_bytes {
+            return None;
+        }
+
+        let step = if self.mitigations_applied < MITIGATIONS.len() {
+            let step = MITIGATIONS[self.mitigations_applied];
+            self.mitigations_applied += 1;
+            if self.mitigations_applied == MITIGATIONS.len() {
+                self.exhausted_at = Some(self.criteria_seen);
+            }
+            step
+        } else {
+            let exhausted_at = self.exhausted_at.unwrap_or(self.criteria_seen);
+            if self.criteria_seen - exhausted_at < self.budget.abort_after {
+                return None;
+            }
+            DegradationStep::Abort
+        };
+
+        self.steps.push(step);
+        Some(step)
+    }
+
+    /// Whether remaining criteria should be skipped
+    pub fn is_aborted(&self) -> bool {
+        self.steps.contains(&DegradationStep::Abort)
+    }
+
+    /// Whether any degradation step has been applied
+    pub fn is_degraded(&self) -> bool {
+        !self.steps.is_empty()
+    }
+
+    /// Steps applied so far, in order
+    pub fn steps(&self) -> &[DegradationStep] {
+        &self.steps
+    }
+
+    /// Highest RSS observed by the sampler
+    pub fn peak_rss_bytes(&self) -> u64 {
+        self.peak_rss_bytes
+    }
+
+    pub fn budget(&self) -> &MemoryBudget {
+        &self.budget
+    }
+}
+
+impl fmt::Debug for MemoryMonitor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MemoryMonitor")
+            .field("budget", &self.budget)
+            .field("criteria_seen", &self.criteria_seen)
+            .field("steps", &self.steps)
+            .field("peak_rss_bytes", &self.peak_rss_bytes)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_memory_size() {
+        assert_eq!(parse_memory_size("256M").unwrap(), 256 * 1024 * 1024);
+        assert_eq!(parse_memory_size("1g").unwrap(), 1024 * 1024 * 1024);
+        assert_eq!(parse_memory_size("512KiB").unwrap(), 512 * 1024);
+        assert_eq!(parse_memory_size("4096").unwrap(), 4096);
+        assert!(parse_memory_size("").is_err());
+        assert!(parse_memory_size("0M").is_err());
+        assert!(parse_memory_size("lots").is_err());
+    }
+
+    #[test]
+    fn test_under_budget_never_degrades() {
+        let budget = MemoryBudget::new(1024).with_sample_interval(1);
+        let mut monitor = MemoryMonitor::with_sampler(budget, || Some(512));
+
+        for _ in 0..20 {
+            assert_eq!(monitor.after_criterion(), None);
+        }
+        assert!(!monitor.is_degraded());
+        assert_eq!(monitor.peak_rss_bytes(), 512);
+    }
+
+    #[test]
+    fn test_degradation_steps_in_order() {
+        let budget = MemoryBudget::new(1024)
+            .with_sample_interval(1)
+            .with_abort_after(2);
+        let mut monitor = MemoryMonitor::with_sampler(budget, || Some(4096));
+
+        assert_eq!(monitor.after_criterion(), Some(DegradationStep::FlushSinks));
+        assert_eq!(
+            monitor.after_criterion(),
+            Some(DegradationStep::SummarizeEvidence)
+        );
+        assert_eq!(
+            monitor.after_criterion(),
+            Some(DegradationStep::DisableCommandCache)
+        );
+        assert_eq!(monitor.after_criterion(), None);
+        assert_eq!(monitor.after_criterion(), Some(DegradationStep::Abort));
+        assert!(monitor.is_aborted());
+        assert_eq!(monitor.after_criterion(), None);
+        assert_eq!(monitor.steps().len(), 4);
+    }
+
+    #[test]
+    fn test_sampling_interval() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        let samples = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&samples);
+        let budget = MemoryBudget::new(1024).with_sample_interval(5);
+        let mut monitor = MemoryMonitor::with_sampler(budget, move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+            Some(0)
+        });
+
+        for _ in 0..20 {
+            monitor.after_criterion();
+        }
+        assert_eq!(samples.load(Ordering::SeqCst), 4);
+    }
+
+    #[test]
+    fn test_unavailable_sampler_is_noop() {
+        let budget = MemoryBudget::new(1).with_sample_interval(1);
+        let mut monitor = MemoryMonitor::with_sampler(budget, || None);
+
+        for _ in 0..10 {
+            assert_eq!(monitor.after_criterion(), None);
+        }
+        assert!(!monitor.is_degraded());
+    }
+}
@@ -1,24 +1,55 @@
 pub mod behavior;
+pub mod clock;
+pub mod collection_cache;
 pub mod comparisons;
+pub mod consistency;
+pub mod deadline;
 pub mod deferred_ops;
 pub mod engine;
 pub mod entity_check;
 pub mod filter_evaluation;
 pub mod helpers;
+pub mod host_facts;
+pub mod memory;
 pub mod module_version;
+pub mod object_parameters;
+pub mod privilege;
+pub mod progress;
 pub mod record_validation;
+pub mod scan_plan;
 pub mod structured_params;
+pub mod usage;
 pub use filter_evaluation::FilterEvaluator;
 // REMOVED: ComplianceStatus export (use from strategies module instead)
 // Export only engine-specific types
-pub use engine::{CtnResult, ExecutionEngine, ExecutionError};
+pub use clock::ScanClock;
+pub use collection_cache::{CollectionCache, DEFAULT_CACHE_BYTES, DEFAULT_CACHE_ENTRIES};
+pub use consistency::{
+    ConsistencyMismatch, ConsistencySnapshot, FileObservation, DATA_CHANGED_DURING_SCAN,
+};
+pub use deadline::{
+    Timeouts, CRITERION_TIMEOUT, DEFAULT_CRITERION_TIMEOUT, SCAN_DEADLINE_EXCEEDED,
+};
+pub use engine::{
+    CtnResult, EmptyPolicy, ErrorMode, ExecutionEngine, ExecutionError, PrivilegeCheck,
+    UnknownCriterionType,
+};
+pub use host_facts::{HostFacts, HostFactsCache, DEFAULT_HOST_FACTS_TTL};
+pub use memory::{DegradationStep, MemoryBudget, MemoryMonitor};
+pub use privilege::{EffectivePrivileges, INSUFFICIENT_PRIVILEGES};
+pub use progress::{ExecutionEvent, ProgressHandler};
+pub use scan_plan::{MatchedStrategy, PlannedCriterion, PlannedObject, ScanPlan};
+pub use usage::{StrategyUsageTracker, NO_STRATEGY_REGISTERED};
 // Export behavior utilities
-pub use behavior::{extract_behavior_hints, BehaviorHints};
+pub use behavior::{extract_behavior_hints, BehaviorHints, BehaviorValues};
+pub use object_parameters::{ArgvSlot, CommandTemplate, ParameterValues};
 // Helper functions for executors
 pub use helpers::{
-    evaluate_entity_check, evaluate_existence_check, evaluate_item_check, evaluate_state_operator,
+    collection_items, evaluate_entity_check, evaluate_entity_counts, evaluate_existence_check,
+    evaluate_item_check, evaluate_list_entity_check, evaluate_state_operator,
+    evaluate_test_item_check, record_field, record_text,
 };
 // Comparison utilities
-pub use comparisons::{binary, collection, evr, string, ComparisonExt};
+pub use comparisons::{binary, collection, evr, mode, network, string, ComparisonExt};
 pub use record_validation::{validate_record_checks, RecordValidationResult};
 pub use structured_params::parse_parameters;
@@ -0,0 +1,277 @@
+//! # Object Parameters
+//!
+//! A `parameters ... parameters_end` element hands values to a collector,
+//! such as the systemd properties to query. A contract declares the names it
+//! accepts and their types; [`CtnContract::resolve_parameters`] checks an
+//! object's parameters against those declarations and types their values.
+//!
+//! Command collectors pass a parameter to a whitelisted command as exactly one
+//! argv element and never through a shell. Every value is therefore also
+//! checked with [`check_argv_value`] during resolution: a space or a leading
+//! `-` would otherwise let a policy smuggle in extra options. Where a value
+//! may appear is fixed by the collector's [`CommandTemplate`]; only its
+//! [`ArgvSlot::Parameter`] slots take parameter values.
+//!
+//! [`CtnContract::resolve_parameters`]: crate::strategies::CtnContract::resolve_parameters
+
+use crate::types::common::ResolvedValue;
+use crate::types::execution_context::{ExecutableObject, ExecutableObjectElement};
+use serde_json::Value as JsonValue;
+use std::collections::BTreeMap;
+
+/// Longest value accepted as an argv element
+pub const MAX_ARGV_VALUE_LEN: usize = 256;
+
+/// Punctuation allowed in an argv value besides ASCII letters and digits
+const ARGV_PUNCTUATION: &[char] = &['.', '_', ',', ':', '@', '/', '+', '-'];
+
+/// Parameters of one object, checked against its contract
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ParameterValues {
+    values: BTreeMap<String, ResolvedValue>,
+}
+
+impl ParameterValues {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_value(mut self, name: &str, value: ResolvedValue) -> Self {
+        self.values.insert(name.to_string(), value);
+        self
+    }
+
+    pub fn get(&self, name: &str) -> Option<&ResolvedValue> {
+        self.values.get(name)
+    }
+
+    /// Value of a parameter as it is passed on a command line
+    pub fn argv_value(&self, name: &str) -> Option<String> {
+        self.get(name).map(|value| match value {
+            ResolvedValue::String(s) | ResolvedValue::Version(s) | ResolvedValue::EvrString(s) => {
+                s.clone()
+            }
+            ResolvedValue::Integer(i) => i.to_string(),
+            ResolvedValue::Float(f) => f.to_string(),
+            ResolvedValue::Boolean(b) => b.to_string(),
+            other => format!("{:?}", other),
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+/// Raw `name value` pairs from an object's PARAMETERS elements
+///
+/// Command arguments are flat, so a nested record or list is reported as a
+/// problem rather than flattened.
+pub fn extract_parameters(object: &ExecutableObject) -> (Vec<(String, String)>, Vec<String>) {
+    let mut pairs = Vec::new();
+    let mut problems = Vec::new();
+    for element in &object.elements {
+        let ExecutableObjectElement::Parameter { data, .. } = element else {
+            continue;
+        };
+        let JsonValue::Object(fields) = data.as_json_value() else {
+            continue;
+        };
+        for (name, value) in fields {
+            match value {
+                JsonValue::String(s) => pairs.push((name.clone(), s.clone())),
+                JsonValue::Number(n) => pairs.push((name.clone(), n.to_string())),
+                JsonValue::Bool(b) => pairs.push((name.clone(), b.to_string())),
+                _ => problems.push(format!(
+                    "parameter '{}' must be a single value, not a nested record or list",
+                    name
+                )),
+            }
+        }
+    }
+    (pairs, problems)
+}
+
+/// Check that a value can be passed as one argv element without changing the
+/// command's meaning
+pub fn check_argv_value(value: &str) -> Result<(), String> {
+    if value.is_empty() {
+        return Err("value is empty".to_string());
+    }
+    if value.len() > MAX_ARGV_VALUE_LEN {
+        return Err(format!(
+            "value is longer than {} characters",
+            MAX_ARGV_VALUE_LEN
+        ));
+    }
+    if value.starts_with('-') {
+        return Err(format!(
+            "value '{}' starts with '-' and would be read as an option",
+            value
+        ));
+    }
+    if let Some(c) = value
+        .chars()
+        .find(|c| !c.is_ascii_alphanumeric() && !ARGV_PUNCTUATION.contains(c))
+    {
+        return Err(if c.is_whitespace() {
+            format!("value '{}' contains whitespace", value.escape_debug())
+        } else {
+            format!("value '{}' contains '{}'", value.escape_debug(), c)
+        });
+    }
+    Ok(())
+}
+
+/// One position in a command invocation
+#[derive(Debug, Clone, PartialEq)]
+pub enum ArgvSlot {
+    /// Literal argument fixed by the collector
+    Fixed(&'static str),
+    /// The object being collected, e.g. a unit or package name
+    Subject,
+    /// A parameter value appended to `prefix` as a single element
+    Parameter {
+        name: &'static str,
+        prefix: &'static str,
+    },
+}
+
+/// Invocation of a whitelisted command with the positions parameters may fill
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommandTemplate {
+    pub command: &'static str,
+    pub slots: Vec<ArgvSlot>,
+}
+
+impl CommandTemplate {
+    pub fn new(command: &'static str, slots: Vec<ArgvSlot>) -> Self {
+        Self { command, slots }
+    }
+
+    /// Build the argument list; every slot becomes exactly one element
+    ///
+    /// Parameter values are checked again here, so a template cannot be fed
+    /// values that skipped contract resolution.
+    pub fn argv(&self, subject: &str, parameters: &ParameterValues) -> Result<Vec<String>, String> {
+        self.slots
+            .iter()
+            .map(|slot| match slot {
+                ArgvSlot::Fixed(arg) => Ok(arg.to_string()),
+                ArgvSlot::Subject => Ok(subject.to_string()),
+                ArgvSlot::Parameter { name, prefix } => {
+                    let value = parameters
+                        .argv_value(name)
+                        .ok_or_else(|| format!("parameter '{}' is not set", name))?;
+                    check_argv_value(&value)
+                        .map_err(|reason| format!("parameter '{}': {}", name, reason))?;
+                    Ok(format!("{}{}", prefix, value))
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategies::{CtnContract, CtnContractError, SupportedParameter};
+    use crate::types::common::{DataType, RecordData};
+
+    fn object(parameters: &[(&str, &str)]) -> ExecutableObject {
+        let fields = parameters
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect::<Vec<_>>();
+        ExecutableObject {
+            identifier: "sshd".to_string(),
+            elements: vec![ExecutableObjectElement::Parameter {
+                data_type: DataType::String,
+                data: RecordData::from_string_pairs(fields),
+            }],
+            is_global: false,
+        }
+    }
+
+    fn contract() -> CtnContract {
+        let mut contract = CtnContract::new("systemd_service".to_string());
+        contract.add_supported_parameter(SupportedParameter {
+            name: "properties".to_string(),
+            data_type: DataType::String,
+            description: "Unit properties to query".to_string(),
+            example: "properties `ActiveState,SubState`".to_string(),
+        });
+        contract
+    }
+
+    #[test]
+    fn test_argv_values_cannot_add_options() {
+        assert!(check_argv_value("ActiveState,SubState").is_ok());
+        assert!(check_argv_value("getty@tty1.service").is_ok());
+        for value in [
+            "ActiveState --host=evil",
+            "-H",
+            "--user",
+            "a;b",
+            "$(id)",
+            "",
+        ] {
+            assert!(check_argv_value(value).is_err(), "accepted {:?}", value);
+        }
+    }
+
+    #[test]
+    fn test_contract_resolves_declared_parameters() {
+        let values = contract()
+            .resolve_parameters(&object(&[("properties", "ActiveState,SubState")]))
+            .unwrap();
+        assert_eq!(
+            values.argv_value("properties").as_deref(),
+            Some("ActiveState,SubState")
+        );
+
+        let template = CommandTemplate::new(
+            "systemctl",
+            vec![
+                ArgvSlot::Fixed("show"),
+                ArgvSlot::Subject,
+                ArgvSlot::Parameter {
+                    name: "properties",
+                    prefix: "--property=",
+                },
+            ],
+        );
+        assert_eq!(
+            template.argv("sshd.service", &values).unwrap(),
+            ["show", "sshd.service", "--property=ActiveState,SubState"]
+        );
+        assert!(template
+            .argv("sshd.service", &ParameterValues::new())
+            .is_err());
+    }
+
+    #[test]
+    fn test_contract_rejects_injection_and_unknown_parameters() {
+        let err = contract()
+            .resolve_parameters(&object(&[
+                ("properties", "ActiveState -H root@host"),
+                ("propertys", "SubState"),
+            ]))
+            .unwrap_err();
+        let CtnContractError::InvalidParameters { problems, .. } = err else {
+            panic!("expected invalid parameters, got {:?}", err);
+        };
+        assert_eq!(problems.len(), 2);
+        assert!(problems[0].contains("whitespace"), "{}", problems[0]);
+        assert!(
+            problems[1].contains("did you mean 'properties'"),
+            "{}",
+            problems[1]
+        );
+
+        let err = contract()
+            .resolve_parameters(&object(&[("properties", "--host=root@evil")]))
+            .unwrap_err();
+        assert!(err.to_string().contains("would be read as an option"));
+    }
+}
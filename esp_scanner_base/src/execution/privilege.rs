@@ -0,0 +1,203 @@
+//! # Privilege Detection
+//!
+//! Determines the effective privileges of the scanning process so criteria
+//! whose contracts declare a [`PrivilegeRequirement`] the process can't meet
+//! are reported as such instead of failing confusingly during collection.
+//!
+//! Detection reads the effective uid and the `CapEff` capability mask from
+//! `/proc/self/status`. Root without the needed capability (e.g. inside a
+//! restricted container) does not satisfy a capability requirement, and a
+//! non-root process holding the capability does.
+
+use crate::strategies::PrivilegeRequirement;
+
+/// Reason recorded on criteria skipped for lack of privileges
+pub const INSUFFICIENT_PRIVILEGES: &str = "insufficient_privileges";
+
+/// Linux capability names and their bit positions in `CapEff`
+const CAPABILITIES: &[(&str, u32)] = &[
+    ("cap_chown", 0),
+    ("cap_dac_override", 1),
+    ("cap_dac_read_search", 2),
+    ("cap_fowner", 3),
+    ("cap_fsetid", 4),
+    ("cap_kill", 5),
+    ("cap_setgid", 6),
+    ("cap_setuid", 7),
+    ("cap_net_bind_service", 10),
+    ("cap_net_admin", 12),
+    ("cap_net_raw", 13),
+    ("cap_ipc_lock", 14),
+    ("cap_sys_module", 16),
+    ("cap_sys_rawio", 17),
+    ("cap_sys_chroot", 18),
+    ("cap_sys_ptrace", 19),
+    ("cap_sys_admin", 21),
+    ("cap_sys_boot", 22),
+    ("cap_sys_resource", 24),
+    ("cap_sys_time", 25),
+    ("cap_audit_write", 29),
+    ("cap_audit_control", 30),
+    ("cap_setfcap", 31),
+    ("cap_mac_override", 32),
+    ("cap_mac_admin", 33),
+    ("cap_syslog", 34),
+    ("cap_audit_read", 37),
+];
+
+/// Bit position of a capability in the effective set, if known
+pub fn capability_bit(name: &str) -> Option<u32> {
+    let lower = name.to_ascii_lowercase();
+    CAPABILITIES
+        .iter()
+        .find(|(cap, _)| *cap == lower)
+        .map(|(_, bit)| *bit)
+}
+
+/// Effective privileges of a process
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EffectivePrivileges {
+    /// Effective user id, if it could be determined
+    pub euid: Option<u32>,
+    /// Effective capability mask (`CapEff`)
+    pub capabilities: u64,
+}
+
+impl EffectivePrivileges {
+    /// Unprivileged user with no capabilities
+    pub fn unprivileged() -> Self {
+        Self {
+            euid: None,
+            capabilities: 0,
+        }
+    }
+
+    /// Root holding every capability
+    pub fn root() -> Self {
+        Self {
+            euid: Some(0),
+            capabilities: u64::MAX,
+        }
+    }
+
+    /// Grant an additional capability by name (unknown names are ignored)
+    pub fn with_capability(mut self, name: &str) -> Self {
+        if let Some(bit) = capability_bit(name) {
+            self.capabilities |= 1 << bit;
+        }
+        self
+    }
+
+    /// Probe the current process
+    pub fn detect() -> Self {
+        std::fs::read_to_string("/proc/self/status")
+            .map(|status| Self::from_proc_status(&status))
+            .unwrap_or_else(|_| Self::unprivileged())
+    }
+
+    /// Parse the `Uid:` and `CapEff:` lines of a `/proc/<pid>/status` file
+    pub fn from_proc_status(status: &str) -> Self {
+        let mut privileges = Self::unprivileged();
+        for line in status.lines() {
+            if let Some(rest) = line.strip_prefix("Uid:") {
+                // Real, effective, saved, filesystem
+                privileges.euid = rest.split_whitespace().nth(1).and_then(|s| s.parse().ok());
+            } else if let Some(rest) = line.strip_prefix("CapEff:") {
+                privileges.capabilities = u64::from_str_radix(rest.trim(), 16).unwrap_or(0);
+            }
+        }
+        privileges
+    }
+
+    pub fn is_root(&self) -> bool {
+        self.euid == Some(0)
+    }
+
+    /// Whether the named capability is in the effective set
+    pub fn has_capability(&self, name: &str) -> bool {
+        capability_bit(name)
+            .map(|bit| self.capabilities & (1 << bit) != 0)
+            .unwrap_or(false)
+    }
+
+    /// Whether these privileges meet a contract's requirement
+    ///
+    /// Unknown capability names can only be met by root.
+    pub fn satisfies(&self, requirement: &PrivilegeRequirement) -> bool {
+        match requirement {
+            PrivilegeRequirement::None => true,
+            PrivilegeRequirement::Root => self.is_root(),
+            PrivilegeRequirement::Capability(cap) => match capability_bit(cap) {
+                Some(_) => self.has_capability(cap),
+                None => self.is_root(),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ROOT_STATUS: &str = "Name:\tscanner\nUid:\t0\t0\t0\t0\nCapEff:\t000001ffffffffff\n";
+    const USER_STATUS: &str =
+        "Name:\tscanner\nUid:\t1000\t1000\t1000\t1000\nCapEff:\t0000000000000000\n";
+
+    #[test]
+    fn test_parse_proc_status() {
+        let root = EffectivePrivileges::from_proc_status(ROOT_STATUS);
+        assert!(root.is_root());
+        assert!(root.has_capability("cap_dac_read_search"));
+
+        let user = EffectivePrivileges::from_proc_status(USER_STATUS);
+        assert_eq!(user.euid, Some(1000));
+        assert!(!user.has_capability("cap_dac_read_search"));
+    }
+
+    #[test]
+    fn test_effective_uid_is_second_field() {
+        // setuid binary: real uid 1000, effective uid 0
+        let status = "Uid:\t1000\t0\t0\t0\nCapEff:\t0\n";
+        assert!(EffectivePrivileges::from_proc_status(status).is_root());
+    }
+
+    #[test]
+    fn test_capability_without_root() {
+        let privileges = EffectivePrivileges::unprivileged().with_capability("cap_dac_read_search");
+        let cap = PrivilegeRequirement::Capability("cap_dac_read_search".to_string());
+
+        assert!(privileges.satisfies(&cap));
+        assert!(!privileges.satisfies(&PrivilegeRequirement::Root));
+        assert!(privileges.satisfies(&PrivilegeRequirement::None));
+    }
+
+    #[test]
+    fn test_root_without_capability() {
+        // Root in a restricted container
+        let privileges = EffectivePrivileges {
+            euid: Some(0),
+            capabilities: 0,
+        };
+
+        assert!(privileges.satisfies(&PrivilegeRequirement::Root));
+        assert!(!privileges.satisfies(&PrivilegeRequirement::Capability(
+            "cap_dac_read_search".to_string()
+        )));
+        assert!(privileges.satisfies(&PrivilegeRequirement::Capability(
+            "cap_unknown_future".to_string()
+        )));
+    }
+
+    #[test]
+    fn test_parse_requirement() {
+        assert_eq!(
+            PrivilegeRequirement::parse("ROOT").unwrap(),
+            PrivilegeRequirement::Root
+        );
+        assert_eq!(
+            PrivilegeRequirement::parse("cap_dac_read_search").unwrap(),
+            PrivilegeRequirement::Capability("cap_dac_read_search".to_string())
+        );
+        assert!(PrivilegeRequirement::parse("admin").is_err());
+    }
+}
@@ -0,0 +1,107 @@
+//! # Scan Progress
+//!
+//! Events the engine emits while a scan runs, for callers that show progress
+//! instead of waiting for the final `ScanResult`. Handlers must be `Send +
+//! Sync` so events can come from worker threads once criteria run in
+//! parallel; every criterion event carries its CTN node id, so a consumer can
+//! restore tree order if completions arrive out of order.
+
+use crate::strategies::ComplianceStatus;
+use crate::types::criterion::CtnNodeId;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Something that happened during a scan
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExecutionEvent {
+    ScanStarted {
+        total_criteria: usize,
+    },
+    CriterionStarted {
+        ctn_node_id: CtnNodeId,
+        criterion_type: String,
+    },
+    CriterionCompleted {
+        ctn_node_id: CtnNodeId,
+        criterion_type: String,
+        status: ComplianceStatus,
+        duration: Duration,
+        /// Criteria finished so far, this one included
+        completed: usize,
+        total_criteria: usize,
+    },
+    ScanCompleted {
+        total_criteria: usize,
+        passed: bool,
+        duration: Duration,
+    },
+}
+
+/// Callback receiving every [`ExecutionEvent`] of a scan
+pub type ProgressHandler = Arc<dyn Fn(&ExecutionEvent) + Send + Sync>;
+
+/// Emits events to an optional handler, counting completed criteria
+#[derive(Default)]
+pub(crate) struct ProgressReporter {
+    handler: Option<ProgressHandler>,
+    total_criteria: usize,
+    completed: AtomicUsize,
+    started: Option<Instant>,
+}
+
+impl ProgressReporter {
+    pub(crate) fn new(handler: ProgressHandler) -> Self {
+        Self {
+            handler: Some(handler),
+            ..Self::default()
+        }
+    }
+
+    pub(crate) fn scan_started(&mut self, total_criteria: usize) {
+        self.total_criteria = total_criteria;
+        self.completed.store(0, Ordering::Relaxed);
+        self.started = Some(Instant::now());
+        self.emit(|| ExecutionEvent::ScanStarted { total_criteria });
+    }
+
+    pub(crate) fn criterion_started(&self, ctn_node_id: CtnNodeId, criterion_type: &str) {
+        self.emit(|| ExecutionEvent::CriterionStarted {
+            ctn_node_id,
+            criterion_type: criterion_type.to_string(),
+        });
+    }
+
+    pub(crate) fn criterion_completed(
+        &self,
+        ctn_node_id: CtnNodeId,
+        criterion_type: &str,
+        status: ComplianceStatus,
+        duration: Duration,
+    ) {
+        let completed = self.completed.fetch_add(1, Ordering::Relaxed) + 1;
+        self.emit(|| ExecutionEvent::CriterionCompleted {
+            ctn_node_id,
+            criterion_type: criterion_type.to_string(),
+            status,
+            duration,
+            completed,
+            total_criteria: self.total_criteria,
+        });
+    }
+
+    pub(crate) fn scan_completed(&self, passed: bool) {
+        self.emit(|| ExecutionEvent::ScanCompleted {
+            total_criteria: self.total_criteria,
+            passed,
+            duration: self.started.map(|s| s.elapsed()).unwrap_or_default(),
+        });
+    }
+
+    /// Build the event only when someone listens
+    fn emit(&self, event: impl FnOnce() -> ExecutionEvent) {
+        if let Some(handler) = &self.handler {
+            handler(&event());
+        }
+    }
+}
@@ -1,8 +1,33 @@
 //! Record check validation logic
 //!
 //! Handles validation of RecordData against record checks from states.
+//!
+//! ## Numbers
+//!
+//! JSON numbers are held as a 64-bit integer when written without a fraction
+//! or exponent and fit, and as the nearest `f64` otherwise. Numeric
+//! comparisons follow these rules:
+//!
+//! - `int` fields compare exactly. A JSON float with no fractional part is the
+//!   same integer (`1.0` equals `1`); one with a fractional part equals no
+//!   integer but still orders against them.
+//! - `float` fields compare exactly unless a tolerance is configured (the
+//!   `float_epsilon` behavior of `json_record`); values within the tolerance
+//!   are equal and neither greater nor less than each other.
+//! - Comparisons the stored value cannot decide, such as an integer-valued
+//!   float beyond 2^53 against an `int` state or an integer beyond 2^53 against
+//!   a `float` state, are reported as undetermined rather than passed or
+//!   failed.
+//!
+//! ## Type mismatches
+//!
+//! A single field whose value cannot be read as the state's type, such as a
+//! string where an `int` is expected, fails with a message naming the type and
+//! value the record actually holds. In a wildcard collection such a value is
+//! simply an item that does not match.
 
 use crate::execution::comparisons::ComparisonExt;
+use crate::execution::helpers::evaluate_entity_counts;
 use crate::types::common::{DataType, Operation, RecordData, ResolvedValue};
 use crate::types::execution_context::{
     ExecutableRecordCheck, ExecutableRecordContent, ExecutableRecordField,
@@ -18,12 +43,41 @@ pub struct RecordValidationResult {
     pub message: String,
     pub expected: Option<String>,
     pub actual: Option<String>,
+    /// Why the comparison could not be decided; `passed` is false when set
+    pub undetermined: Option<String>,
+}
+
+/// Options for record validation
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RecordValidationOptions {
+    /// Tolerance for float comparisons; exact when unset
+    pub float_epsilon: Option<f64>,
+}
+
+impl RecordValidationOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_float_epsilon(mut self, epsilon: Option<f64>) -> Self {
+        self.float_epsilon = epsilon;
+        self
+    }
 }
 
 /// Validate all record checks against collected record data
 pub fn validate_record_checks(
     record_data: &RecordData,
     record_checks: &[ExecutableRecordCheck],
+) -> Result<Vec<RecordValidationResult>, String> {
+    validate_record_checks_with(record_data, record_checks, &RecordValidationOptions::new())
+}
+
+/// Validate all record checks against collected record data with options
+pub fn validate_record_checks_with(
+    record_data: &RecordData,
+    record_checks: &[ExecutableRecordCheck],
+    options: &RecordValidationOptions,
 ) -> Result<Vec<RecordValidationResult>, String> {
     let mut results = Vec::new();
 
@@ -34,7 +88,7 @@ pub fn validate_record_checks(
                 results.push(result);
             }
             ExecutableRecordContent::Nested { fields } => {
-                let field_results = validate_nested_fields(record_data, fields)?;
+                let field_results = validate_nested_fields(record_data, fields, options)?;
                 results.extend(field_results);
             }
         }
@@ -65,6 +119,7 @@ fn validate_direct_record(
         },
         expected: Some(format!("{:?}", expected)),
         actual: Some("<record_data>".to_string()),
+        undetermined: None,
     })
 }
 
@@ -72,6 +127,7 @@ fn validate_direct_record(
 fn validate_nested_fields(
     record_data: &RecordData,
     fields: &[ExecutableRecordField],
+    options: &RecordValidationOptions,
 ) -> Result<Vec<RecordValidationResult>, String> {
     let mut results = Vec::new();
 
@@ -79,11 +135,11 @@ fn validate_nested_fields(
         // Check if field path has wildcards
         if field.path.has_wildcards() {
             // Collection validation with entity check
-            let collection_result = validate_field_collection(record_data, field)?;
+            let collection_result = validate_field_collection(record_data, field, options)?;
             results.push(collection_result);
         } else {
             // Single value validation (existing logic)
-            let single_result = validate_field_single(record_data, field)?;
+            let single_result = validate_field_single(record_data, field, options)?;
             results.push(single_result);
         }
     }
@@ -163,6 +219,7 @@ fn expand_wildcard_path<'a>(
 fn validate_field_collection(
     record_data: &RecordData,
     field: &ExecutableRecordField,
+    options: &RecordValidationOptions,
 ) -> Result<RecordValidationResult, String> {
     // Parse field path into components
     let components = field.path.parse_components();
@@ -188,14 +245,27 @@ fn validate_field_collection(
             ),
             expected: Some(format!("{:?}", field.value)),
             actual: Some("no matching values".to_string()),
+            undetermined: None,
         });
     }
 
     // Convert each JSON value and perform comparison
     let mut comparison_results = Vec::new();
     let mut actual_values = Vec::new();
+    let mut undetermined = Vec::new();
 
     for json_value in &json_values {
+        if let Some(comparison) = compare_json_number(json_value, field, options) {
+            match comparison {
+                NumberComparison::Decided(passed) => comparison_results.push(passed),
+                NumberComparison::Undetermined(reason) => undetermined.push(reason),
+            }
+            if let Some(actual) = json_number_value(json_value) {
+                actual_values.push(actual);
+            }
+            continue;
+        }
+
         // Convert JSON to ResolvedValue based on expected type
         let actual_value = json_to_resolved_value(json_value, field.data_type);
 
@@ -222,6 +292,59 @@ fn validate_field_collection(
     let entity_check = field.entity_check.unwrap_or(EntityCheck::All);
     let final_passed = apply_entity_check_to_collection(&comparison_results, entity_check);
 
+    // Undetermined items matter only if deciding them differently changes the outcome
+    if !undetermined.is_empty() {
+        let with = |passed: bool| {
+            let mut results = comparison_results.clone();
+            results.extend(std::iter::repeat(passed).take(undetermined.len()));
+            apply_entity_check_to_collection(&results, entity_check)
+        };
+        if with(true) != with(false) {
+            return Ok(RecordValidationResult {
+                field_path: field.path.to_dot_notation(),
+                passed: false,
+                message: format!(
+                    "Collection validation undetermined ({}): {} of {} items could not be compared: {}",
+                    field.path.to_dot_notation(),
+                    undetermined.len(),
+                    json_values.len(),
+                    undetermined[0]
+                ),
+                expected: Some(format!("{:?}", field.value)),
+                actual: Some(format!(
+                    "{} values: {:?}",
+                    actual_values.len(),
+                    actual_values
+                )),
+                undetermined: Some(undetermined.swap_remove(0)),
+            });
+        }
+        return Ok(collection_result(
+            field,
+            entity_check,
+            with(false),
+            &comparison_results,
+            &actual_values,
+        ));
+    }
+
+    Ok(collection_result(
+        field,
+        entity_check,
+        final_passed,
+        &comparison_results,
+        &actual_values,
+    ))
+}
+
+/// Result for a decided collection comparison
+fn collection_result(
+    field: &ExecutableRecordField,
+    entity_check: EntityCheck,
+    final_passed: bool,
+    comparison_results: &[bool],
+    actual_values: &[ResolvedValue],
+) -> RecordValidationResult {
     // Create detailed message
     let passing_count = comparison_results.iter().filter(|&&p| p).count();
     let total_count = comparison_results.len();
@@ -242,15 +365,17 @@ fn validate_field_collection(
             total_count,
             entity_check.as_str(),
             match entity_check {
-                EntityCheck::All => "all items",
-                EntityCheck::AtLeastOne => "at least one item",
-                EntityCheck::None => "no items",
-                EntityCheck::OnlyOne => "exactly one item",
+                EntityCheck::All => "all items".to_string(),
+                EntityCheck::AtLeastOne => "at least one item".to_string(),
+                EntityCheck::None => "no items".to_string(),
+                EntityCheck::OnlyOne => "exactly one item".to_string(),
+                EntityCheck::AtLeastN(n) => format!("at least {} items", n),
+                EntityCheck::AtLeastPercent(p) => format!("at least {}% of items", p),
             }
         )
     };
 
-    Ok(RecordValidationResult {
+    RecordValidationResult {
         field_path: field.path.to_dot_notation(),
         passed: final_passed,
         message,
@@ -260,13 +385,15 @@ fn validate_field_collection(
             actual_values.len(),
             actual_values
         )),
-    })
+        undetermined: None,
+    }
 }
 
 /// Validate a single field value
 fn validate_field_single(
     record_data: &RecordData,
     field: &ExecutableRecordField,
+    options: &RecordValidationOptions,
 ) -> Result<RecordValidationResult, String> {
     // Parse components to handle both dot notation and numeric indices
     let components = field.path.parse_components();
@@ -286,22 +413,24 @@ fn validate_field_single(
                 ),
                 expected: Some(format!("{:?}", field.value)),
                 actual: None,
+                undetermined: None,
             });
         }
     };
 
+    if let Some(comparison) = compare_json_number(json_value, field, options) {
+        return Ok(single_number_result(field, json_value, comparison));
+    }
+
     // Convert to ResolvedValue
-    let actual_value = json_to_resolved_value(json_value, field.data_type).map_err(|e| {
-        format!(
-            "Type conversion failed for field '{}': {}",
-            field.path.to_dot_notation(),
-            e
-        )
-    })?;
+    let actual_value = match json_to_resolved_value(json_value, field.data_type) {
+        Ok(value) => value,
+        Err(_) => return Ok(type_mismatch_result(field, json_value)),
+    };
 
     // Use ComparisonExt for comparison
     let comparison_passed = actual_value
-        .compare_with(&field.value, field.operation)
+        .compare_with_entity_check(&field.value, field.operation, field.entity_check)
         .unwrap_or_else(|_e| {
             // If comparison trait fails, try direct comparison for simple types
             match (field.operation, &actual_value, &field.value) {
@@ -350,9 +479,249 @@ fn validate_field_single(
         },
         expected: Some(format_value(&field.value)),
         actual: Some(format_value(&actual_value)),
+        undetermined: None,
+    })
+}
+
+/// Failed result for a single field holding a value of another type
+fn type_mismatch_result(
+    field: &ExecutableRecordField,
+    json_value: &serde_json::Value,
+) -> RecordValidationResult {
+    let path = field.path.to_dot_notation();
+    let actual = format!("{} {}", json_type_name(json_value), json_value);
+    RecordValidationResult {
+        message: format!(
+            "Field '{}' validation failed: expected {} {:?} {}, found {}",
+            path,
+            field.data_type.as_str(),
+            field.operation,
+            format_value(&field.value),
+            actual
+        ),
+        field_path: path,
+        passed: false,
+        expected: Some(format_value(&field.value)),
+        actual: Some(actual),
+        undetermined: None,
+    }
+}
+
+/// Name of a JSON value's type, in ESP data type terms where one applies
+fn json_type_name(json: &serde_json::Value) -> &'static str {
+    match json {
+        serde_json::Value::Null => "null",
+        serde_json::Value::Bool(_) => "boolean",
+        serde_json::Value::Number(n) if n.is_f64() => "float",
+        serde_json::Value::Number(_) => "int",
+        serde_json::Value::String(_) => "string",
+        serde_json::Value::Array(_) => "array",
+        serde_json::Value::Object(_) => "record",
+    }
+}
+
+/// Result for a single numeric field compared under the number rules
+fn single_number_result(
+    field: &ExecutableRecordField,
+    json_value: &serde_json::Value,
+    comparison: NumberComparison,
+) -> RecordValidationResult {
+    let path = field.path.to_dot_notation();
+    let actual = json_value.to_string();
+    let (passed, undetermined) = match comparison {
+        NumberComparison::Decided(passed) => (
+            field
+                .entity_check
+                .map_or(passed, |check| apply_entity_check(passed, check)),
+            None,
+        ),
+        NumberComparison::Undetermined(reason) => (false, Some(reason)),
+    };
+
+    let message = match &undetermined {
+        Some(reason) => format!("Field '{}' validation undetermined: {}", path, reason),
+        None if passed => format!("Field '{}' validation passed", path),
+        None => format!(
+            "Field '{}' validation failed: expected {} {:?} {}",
+            path,
+            format_value(&field.value),
+            field.operation,
+            actual
+        ),
+    };
+
+    RecordValidationResult {
+        field_path: path,
+        passed,
+        message,
+        expected: Some(format_value(&field.value)),
+        actual: Some(actual),
+        undetermined,
+    }
+}
+
+// ============================================================================
+// NUMBER SEMANTICS
+// ============================================================================
+
+/// 2^53: every integer of smaller magnitude is exactly representable as f64
+const EXACT_FLOAT_LIMIT: f64 = 9_007_199_254_740_992.0;
+
+/// Outcome of a numeric record comparison
+#[derive(Debug, Clone, PartialEq)]
+enum NumberComparison {
+    Decided(bool),
+    /// The stored value cannot settle the comparison
+    Undetermined(String),
+}
+
+/// A number as held, without conversion
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Number {
+    Int(i128),
+    Float(f64),
+}
+
+impl Number {
+    fn from_json(n: &serde_json::Number) -> Option<Self> {
+        n.as_i64()
+            .map(|i| Self::Int(i.into()))
+            .or_else(|| n.as_u64().map(|u| Self::Int(u.into())))
+            .or_else(|| n.as_f64().map(Self::Float))
+    }
+
+    fn from_resolved(value: &ResolvedValue) -> Option<Self> {
+        match value {
+            ResolvedValue::Integer(i) => Some(Self::Int((*i).into())),
+            ResolvedValue::Float(f) => Some(Self::Float(*f)),
+            _ => None,
+        }
+    }
+
+    /// Exact f64 value, if the number has one
+    fn exact_float(self) -> Option<f64> {
+        match self {
+            Self::Float(f) => Some(f),
+            Self::Int(i) if (i as f64).abs() <= EXACT_FLOAT_LIMIT => Some(i as f64),
+            Self::Int(_) => None,
+        }
+    }
+}
+
+/// ResolvedValue for a JSON number, for display in collection results
+fn json_number_value(json: &serde_json::Value) -> Option<ResolvedValue> {
+    match Number::from_json(json.as_number()?)? {
+        Number::Int(i) => Some(
+            i64::try_from(i)
+                .map(ResolvedValue::Integer)
+                .unwrap_or(ResolvedValue::Float(i as f64)),
+        ),
+        Number::Float(f) => Some(ResolvedValue::Float(f)),
+    }
+}
+
+/// Compare a JSON number against an `int` or `float` record field
+///
+/// Returns None when the rules do not apply (not a number, non-numeric
+/// expected value or operation), leaving the general comparison in charge.
+fn compare_json_number(
+    json: &serde_json::Value,
+    field: &ExecutableRecordField,
+    options: &RecordValidationOptions,
+) -> Option<NumberComparison> {
+    if !matches!(field.data_type, DataType::Int | DataType::Float) {
+        return None;
+    }
+    let ordering_op = matches!(
+        field.operation,
+        Operation::Equals
+            | Operation::NotEqual
+            | Operation::GreaterThan
+            | Operation::LessThan
+            | Operation::GreaterThanOrEqual
+            | Operation::LessThanOrEqual
+    );
+    if !ordering_op {
+        return None;
+    }
+    let actual = Number::from_json(json.as_number()?)?;
+    let expected = Number::from_resolved(&field.value)?;
+
+    let ordering = match field.data_type {
+        DataType::Int => compare_as_int(actual, expected),
+        _ => compare_as_float(actual, expected, options.float_epsilon),
+    };
+    Some(match ordering {
+        Ok(ordering) => NumberComparison::Decided(operation_holds(field.operation, ordering)),
+        Err(reason) => NumberComparison::Undetermined(reason),
     })
 }
 
+fn operation_holds(operation: Operation, ordering: std::cmp::Ordering) -> bool {
+    use std::cmp::Ordering::*;
+    match operation {
+        Operation::Equals => ordering == Equal,
+        Operation::NotEqual => ordering != Equal,
+        Operation::GreaterThan => ordering == Greater,
+        Operation::LessThan => ordering == Less,
+        Operation::GreaterThanOrEqual => ordering != Less,
+        Operation::LessThanOrEqual => ordering != Greater,
+        _ => false,
+    }
+}
+
+/// Exact comparison for `int` fields
+fn compare_as_int(actual: Number, expected: Number) -> Result<std::cmp::Ordering, String> {
+    let actual = match actual {
+        Number::Float(f) if f.fract() == 0.0 && f.abs() >= EXACT_FLOAT_LIMIT => {
+            return Err(format!(
+                "JSON value {} is beyond 2^53 and may have been rounded; compare it as an integer literal",
+                f
+            ));
+        }
+        Number::Float(f) if f.fract() == 0.0 => Number::Int(f as i128),
+        other => other,
+    };
+    match (actual, expected) {
+        (Number::Int(a), Number::Int(b)) => Ok(a.cmp(&b)),
+        (a, b) => compare_exact_floats(a, b),
+    }
+}
+
+/// Comparison for `float` fields, within `epsilon` when given
+fn compare_as_float(
+    actual: Number,
+    expected: Number,
+    epsilon: Option<f64>,
+) -> Result<std::cmp::Ordering, String> {
+    if let (Number::Int(a), Number::Int(b)) = (actual, expected) {
+        return Ok(a.cmp(&b));
+    }
+    let (a, b) = exact_pair(actual, expected)?;
+    if epsilon.is_some_and(|e| (a - b).abs() <= e) {
+        return Ok(std::cmp::Ordering::Equal);
+    }
+    a.partial_cmp(&b)
+        .ok_or_else(|| format!("cannot order {} and {}", a, b))
+}
+
+fn compare_exact_floats(actual: Number, expected: Number) -> Result<std::cmp::Ordering, String> {
+    let (a, b) = exact_pair(actual, expected)?;
+    a.partial_cmp(&b)
+        .ok_or_else(|| format!("cannot order {} and {}", a, b))
+}
+
+/// Both numbers as exact f64 values, or why one has none
+fn exact_pair(actual: Number, expected: Number) -> Result<(f64, f64), String> {
+    let lossy = |n: Number| match n {
+        Number::Int(i) => format!("integer {} is beyond 2^53 and has no exact float value", i),
+        Number::Float(f) => format!("{} has no exact value", f),
+    };
+    let a = actual.exact_float().ok_or_else(|| lossy(actual))?;
+    let b = expected.exact_float().ok_or_else(|| lossy(expected))?;
+    Ok((a, b))
+}
+
 /// Convert JSON value to ResolvedValue with type hint
 fn json_to_resolved_value(
     json: &serde_json::Value,
@@ -428,6 +797,10 @@ fn apply_entity_check(passed: bool, entity_check: EntityCheck) -> bool {
         EntityCheck::AtLeastOne => passed, // Must pass
         EntityCheck::None => !passed,      // Must fail
         EntityCheck::OnlyOne => passed,    // Must pass (single value)
+        // A single value counts as one item
+        EntityCheck::AtLeastN(_) | EntityCheck::AtLeastPercent(_) => {
+            evaluate_entity_counts(entity_check, usize::from(passed), 1)
+        }
     }
 }
 
@@ -438,6 +811,10 @@ fn apply_entity_check_to_collection(results: &[bool], entity_check: EntityCheck)
         EntityCheck::AtLeastOne => results.iter().any(|&r| r),
         EntityCheck::None => !results.iter().any(|&r| r),
         EntityCheck::OnlyOne => results.iter().filter(|&&r| r).count() == 1,
+        EntityCheck::AtLeastN(_) | EntityCheck::AtLeastPercent(_) => {
+            let passing = results.iter().filter(|&&r| r).count();
+            evaluate_entity_counts(entity_check, passing, results.len())
+        }
     }
 }
 
@@ -548,6 +925,32 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_entity_check_collection_counted() {
+        assert!(apply_entity_check_to_collection(
+            &[true, false, true],
+            EntityCheck::AtLeastN(2)
+        ));
+        assert!(!apply_entity_check_to_collection(
+            &[true, true],
+            EntityCheck::AtLeastN(3)
+        ));
+        assert!(apply_entity_check_to_collection(
+            &[true, true, false],
+            EntityCheck::AtLeastPercent(66)
+        ));
+        assert!(!apply_entity_check_to_collection(
+            &[true, true, false],
+            EntityCheck::AtLeastPercent(67)
+        ));
+
+        // A single value is one item
+        assert!(apply_entity_check(true, EntityCheck::AtLeastN(1)));
+        assert!(!apply_entity_check(true, EntityCheck::AtLeastN(2)));
+        assert!(apply_entity_check(true, EntityCheck::AtLeastPercent(100)));
+        assert!(!apply_entity_check(false, EntityCheck::AtLeastPercent(1)));
+    }
+
     #[test]
     fn test_format_value() {
         assert_eq!(
@@ -558,6 +961,145 @@ mod tests {
         assert_eq!(format_value(&ResolvedValue::Boolean(true)), "true");
     }
 
+    // =========================================================================
+    // NUMBER SEMANTICS TESTS
+    // =========================================================================
+
+    fn check_number(
+        json: &str,
+        data_type: DataType,
+        operation: Operation,
+        value: ResolvedValue,
+        options: &RecordValidationOptions,
+    ) -> RecordValidationResult {
+        let record: serde_json::Value =
+            serde_json::from_str(&format!("{{\"n\": {}}}", json)).unwrap();
+        let check = ExecutableRecordCheck {
+            data_type: None,
+            content: ExecutableRecordContent::Nested {
+                fields: vec![ExecutableRecordField {
+                    path: crate::types::FieldPath::parse("n"),
+                    data_type,
+                    operation,
+                    value,
+                    entity_check: None,
+                }],
+            },
+        };
+        let mut results =
+            validate_record_checks_with(&RecordData::from_json_value(record), &[check], options)
+                .unwrap();
+        results.remove(0)
+    }
+
+    #[test]
+    fn test_int_comparison_is_exact_beyond_2_pow_53() {
+        let options = RecordValidationOptions::new();
+        let exact = check_number(
+            "9007199254740993",
+            DataType::Int,
+            Operation::Equals,
+            ResolvedValue::Integer(9_007_199_254_740_993),
+            &options,
+        );
+        assert!(exact.passed && exact.undetermined.is_none());
+
+        let neighbour = check_number(
+            "9007199254740992",
+            DataType::Int,
+            Operation::Equals,
+            ResolvedValue::Integer(9_007_199_254_740_993),
+            &options,
+        );
+        assert!(!neighbour.passed && neighbour.undetermined.is_none());
+
+        let rounded = check_number(
+            "9007199254740993.0",
+            DataType::Int,
+            Operation::Equals,
+            ResolvedValue::Integer(9_007_199_254_740_993),
+            &options,
+        );
+        assert!(!rounded.passed);
+        assert!(rounded.undetermined.unwrap().contains("2^53"));
+    }
+
+    #[test]
+    fn test_integral_float_equals_int() {
+        let result = check_number(
+            "1.0",
+            DataType::Int,
+            Operation::Equals,
+            ResolvedValue::Integer(1),
+            &RecordValidationOptions::new(),
+        );
+        assert!(result.passed);
+
+        let fractional = check_number(
+            "1.5",
+            DataType::Int,
+            Operation::GreaterThan,
+            ResolvedValue::Integer(1),
+            &RecordValidationOptions::new(),
+        );
+        assert!(fractional.passed);
+    }
+
+    #[test]
+    fn test_float_equality_uses_epsilon_when_set() {
+        let sum = (0.1_f64 + 0.2).to_string();
+        let exact = check_number(
+            &sum,
+            DataType::Float,
+            Operation::Equals,
+            ResolvedValue::Float(0.3),
+            &RecordValidationOptions::new(),
+        );
+        assert!(!exact.passed && exact.undetermined.is_none());
+
+        let tolerant = RecordValidationOptions::new().with_float_epsilon(Some(1e-9));
+        let close = check_number(
+            &sum,
+            DataType::Float,
+            Operation::Equals,
+            ResolvedValue::Float(0.3),
+            &tolerant,
+        );
+        assert!(close.passed);
+
+        let not_greater = check_number(
+            &sum,
+            DataType::Float,
+            Operation::GreaterThan,
+            ResolvedValue::Float(0.3),
+            &tolerant,
+        );
+        assert!(!not_greater.passed);
+    }
+
+    #[test]
+    fn test_type_mismatch_names_observed_type() {
+        let result = check_number(
+            "\"8080\"",
+            DataType::Int,
+            Operation::Equals,
+            ResolvedValue::Integer(8080),
+            &RecordValidationOptions::new(),
+        );
+        assert!(!result.passed && result.undetermined.is_none());
+        assert_eq!(result.actual.as_deref(), Some("string \"8080\""));
+        assert!(result.message.contains("expected int"));
+
+        let boolean = check_number(
+            "true",
+            DataType::Int,
+            Operation::Equals,
+            ResolvedValue::Integer(1),
+            &RecordValidationOptions::new(),
+        );
+        assert_eq!(boolean.actual.as_deref(), Some("boolean true"));
+    }
+
     // =========================================================================
     // WILDCARD EXPANSION TESTS
     // =========================================================================
@@ -0,0 +1,174 @@
+//! # Scan Plan
+//!
+//! What a scan of the resolved policy would do, without collecting anything:
+//! each criterion with the objects it would collect and the strategy the
+//! registry matched for its type. Criterion types with no strategy are listed
+//! separately. The plan serializes to JSON so two policy versions can be
+//! compared by diffing their plans.
+
+use crate::execution::engine::UnknownCriterionType;
+use crate::results::annotations::criterion_id;
+use crate::strategies::CtnStrategyRegistry;
+use crate::types::common::{RecordData, ResolvedValue};
+use crate::types::criterion::CtnNodeId;
+use crate::types::execution_context::{
+    ExecutableCriterion, ExecutableObject, ExecutableObjectElement,
+};
+use esp_compiler::grammar::ast::nodes::{ModuleField, TestSpecification};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Criteria of a resolved policy in tree order, with unmatched types
+#[derive(Debug, Clone, Serialize)]
+pub struct ScanPlan {
+    pub criteria: Vec<PlannedCriterion>,
+    pub unmatched_criterion_types: Vec<UnknownCriterionType>,
+}
+
+impl ScanPlan {
+    /// Whether every criterion type has a registered strategy
+    pub fn is_fully_matched(&self) -> bool {
+        self.unmatched_criterion_types.is_empty()
+    }
+
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+/// One criterion as it would be executed
+#[derive(Debug, Clone, Serialize)]
+pub struct PlannedCriterion {
+    pub ctn_node_id: CtnNodeId,
+    /// `<type>#<node id>`, as used in results and annotations
+    pub criterion_id: String,
+    pub criterion_type: String,
+    /// `None` when no strategy is registered for the type
+    pub strategy: Option<MatchedStrategy>,
+    pub test: TestSpecification,
+    pub objects: Vec<PlannedObject>,
+    /// Identifiers of the states objects are checked against
+    pub states: Vec<String>,
+}
+
+/// Strategy the registry matched for a criterion type
+#[derive(Debug, Clone, Serialize)]
+pub struct MatchedStrategy {
+    pub collector_id: String,
+    pub required_privilege: String,
+    pub batch_collection: bool,
+}
+
+/// An object with its resolved paths, commands and other fields
+#[derive(Debug, Clone, Serialize)]
+pub struct PlannedObject {
+    pub id: String,
+    /// Fields after variable resolution, e.g. `path` or `command`
+    pub fields: BTreeMap<String, serde_json::Value>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub behaviors: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub parameters: Vec<serde_json::Value>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub set_refs: Vec<String>,
+}
+
+impl PlannedCriterion {
+    pub fn new(criterion: &ExecutableCriterion, registry: &CtnStrategyRegistry) -> Self {
+        Self {
+            ctn_node_id: criterion.ctn_node_id,
+            criterion_id: criterion_id(&criterion.criterion_type, criterion.ctn_node_id),
+            criterion_type: criterion.criterion_type.clone(),
+            strategy: MatchedStrategy::find(&criterion.criterion_type, registry),
+            test: criterion.test.clone(),
+            objects: criterion.objects.iter().map(PlannedObject::new).collect(),
+            states: criterion
+                .states
+                .iter()
+                .map(|state| state.identifier.clone())
+                .collect(),
+        }
+    }
+}
+
+impl MatchedStrategy {
+    fn find(ctn_type: &str, registry: &CtnStrategyRegistry) -> Option<Self> {
+        let collector = registry.get_collector_for_ctn(ctn_type).ok()?;
+        let required_privilege = registry
+            .get_ctn_contract(ctn_type)
+            .map(|contract| contract.collection_strategy.required_privilege.clone())
+            .unwrap_or_default();
+        Some(Self {
+            collector_id: collector.collector_id().to_string(),
+            required_privilege: required_privilege.as_str().to_string(),
+            batch_collection: collector.supports_batch_collection(),
+        })
+    }
+}
+
+impl PlannedObject {
+    pub fn new(object: &ExecutableObject) -> Self {
+        let mut planned = Self {
+            id: object.identifier.clone(),
+            fields: BTreeMap::new(),
+            behaviors: Vec::new(),
+            parameters: Vec::new(),
+            set_refs: Vec::new(),
+        };
+        for element in &object.elements {
+            match element {
+                ExecutableObjectElement::Field { name, value } => {
+                    planned.fields.insert(name.clone(), plain_json(value));
+                }
+                ExecutableObjectElement::Module { field, value } => {
+                    let name = match field {
+                        ModuleField::ModuleName => "module_name",
+                        ModuleField::ModuleVersion => "module_version",
+                        ModuleField::ModuleType => "module_type",
+                        ModuleField::ModulePath => "module_path",
+                    };
+                    planned
+                        .fields
+                        .insert(name.to_string(), serde_json::Value::String(value.clone()));
+                }
+                ExecutableObjectElement::Behavior { values } => {
+                    planned.behaviors.extend(values.iter().cloned());
+                }
+                ExecutableObjectElement::Parameter { data, .. }
+                | ExecutableObjectElement::Select { data, .. } => {
+                    planned.parameters.push(record_json(data));
+                }
+                ExecutableObjectElement::SetRef { set_id } => {
+                    planned.set_refs.push(set_id.clone());
+                }
+                ExecutableObjectElement::Filter { .. } => {}
+            }
+        }
+        planned
+    }
+}
+
+fn record_json(data: &RecordData) -> serde_json::Value {
+    data.as_json_value().clone()
+}
+
+/// JSON for a resolved value without the type tag, `"/etc/passwd"` rather
+/// than `{"String": "/etc/passwd"}`
+fn plain_json(value: &ResolvedValue) -> serde_json::Value {
+    match value {
+        ResolvedValue::String(s) | ResolvedValue::Version(s) | ResolvedValue::EvrString(s) => {
+            serde_json::Value::String(s.clone())
+        }
+        ResolvedValue::Integer(i) => serde_json::json!(i),
+        ResolvedValue::Float(f) => serde_json::json!(f),
+        ResolvedValue::Boolean(b) => serde_json::Value::Bool(*b),
+        ResolvedValue::Collection(items) => {
+            serde_json::Value::Array(items.iter().map(plain_json).collect())
+        }
+        ResolvedValue::RecordData(record) => record_json(record),
+        ResolvedValue::Binary(bytes) => serde_json::Value::String(base64::Engine::encode(
+            &base64::engine::general_purpose::STANDARD,
+            bytes,
+        )),
+    }
+}
@@ -0,0 +1,96 @@
+//! # Strategy Usage Tracking
+//!
+//! Thread-safe accumulator the execution engine feeds as it routes criteria
+//! to strategies. One tracker can be shared by several engines (e.g. parallel
+//! batch scans) to produce an aggregate report.
+
+use crate::results::{ScanPerformance, StrategyUsage};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Reason recorded on criteria whose type has no registered strategy
+pub const NO_STRATEGY_REGISTERED: &str = "no_strategy_registered";
+
+#[derive(Debug, Default)]
+pub struct StrategyUsageTracker {
+    performance: Mutex<ScanPerformance>,
+}
+
+impl StrategyUsageTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record one criterion routed to `strategy`
+    pub fn record_invocation(
+        &self,
+        criterion_type: &str,
+        strategy: &str,
+        collection: Duration,
+        evaluation: Duration,
+        error: bool,
+    ) {
+        let mut performance = self.lock();
+        performance
+            .strategies
+            .entry(criterion_type.to_string())
+            .or_insert_with(|| StrategyUsage::new(strategy))
+            .record(
+                collection.as_secs_f64() * 1000.0,
+                evaluation.as_secs_f64() * 1000.0,
+                error,
+            );
+    }
+
+    /// Record a criterion type with no registered strategy
+    pub fn record_unmatched(&self, criterion_type: &str) {
+        self.lock().record_unmatched(criterion_type);
+    }
+
+    /// Copy of the usage recorded so far
+    pub fn snapshot(&self) -> ScanPerformance {
+        self.lock().clone()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, ScanPerformance> {
+        // Usage is diagnostic; a panic elsewhere shouldn't poison reporting
+        self.performance
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_concurrent_recording() {
+        let tracker = Arc::new(StrategyUsageTracker::new());
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let tracker = Arc::clone(&tracker);
+                std::thread::spawn(move || {
+                    for _ in 0..100 {
+                        tracker.record_invocation(
+                            "file_metadata",
+                            "filesystem_collector",
+                            Duration::from_micros(10),
+                            Duration::from_micros(5),
+                            false,
+                        );
+                    }
+                    tracker.record_unmatched("ldap_bind");
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let performance = tracker.snapshot();
+        assert_eq!(performance.strategies["file_metadata"].invocations, 800);
+        assert_eq!(performance.unmatched_criterion_types, vec!["ldap_bind"]);
+    }
+}
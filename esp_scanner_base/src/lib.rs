@@ -1,7 +1,14 @@
 //! # ESP Scanner Base
+//!
+//! With `--no-default-features` the crate is host-independent policy
+//! processing. The default `host` feature adds process execution
+//! (`strategies::SystemCommandExecutor`) and hostname lookup. The `testkit`
+//! feature exposes the strategy test helpers to other crates' tests.
 
 pub mod execution;
 pub mod resolution;
 pub mod results;
 pub mod strategies;
+#[cfg(any(test, feature = "testkit"))]
+pub mod testkit;
 pub mod types;
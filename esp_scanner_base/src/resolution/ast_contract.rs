@@ -0,0 +1,265 @@
+//! # AST Contract Check
+//!
+//! Entry point for reading a serialized AST document produced by the
+//! compiler (`esp_compiler --emit-ast`). The `ast_version` is checked before
+//! anything else is decoded, so schema drift surfaces as one clear error
+//! instead of a cryptic missing-field failure deep in the tree.
+//!
+//! This is the only place AST versions are compared; the bump policy is
+//! documented in `esp_compiler::grammar::ast::version`.
+
+use crate::resolution::error::ResolutionError;
+use esp_compiler::grammar::ast::{EspFile, AST_VERSION, AST_VERSION_MAJOR};
+
+/// Oldest minor version of the supported major this scanner still reads
+pub const MIN_SUPPORTED_AST_MINOR: u32 = 0;
+
+/// Human-readable supported range, e.g. `1.0 - 1.x`
+pub fn supported_ast_range() -> String {
+    format!(
+        "{}.{} - {}.x",
+        AST_VERSION_MAJOR, MIN_SUPPORTED_AST_MINOR, AST_VERSION_MAJOR
+    )
+}
+
+/// Accept any minor of the supported major; additive minor changes are tolerated
+pub fn check_ast_version(producer: &str) -> Result<(), ResolutionError> {
+    let mismatch = || ResolutionError::AstVersionMismatch {
+        producer: producer.to_string(),
+        consumer: AST_VERSION.to_string(),
+        supported: supported_ast_range(),
+    };
+
+    let (major, minor) = producer.split_once('.').ok_or_else(mismatch)?;
+    let major: u32 = major.parse().map_err(|_| mismatch())?;
+    let minor: u32 = minor.parse().map_err(|_| mismatch())?;
+
+    // Every minor of the current major is readable while the floor is 0;
+    // raise MIN_SUPPORTED_AST_MINOR to drop an old minor
+    if major != AST_VERSION_MAJOR || !(MIN_SUPPORTED_AST_MINOR..).contains(&minor) {
+        return Err(mismatch());
+    }
+    Ok(())
+}
+
+/// Decode a serialized AST document after checking its `ast_version`
+pub fn parse_ast_document(json: &str) -> Result<EspFile, ResolutionError> {
+    let mut document: serde_json::Value =
+        serde_json::from_str(json).map_err(|e| ResolutionError::InvalidInput {
+            message: format!("AST document is not valid JSON: {}", e),
+        })?;
+
+    let producer = match document.get("ast_version") {
+        Some(serde_json::Value::String(version)) => version.clone(),
+        _ => {
+            return Err(ResolutionError::AstVersionMismatch {
+                producer: "unversioned".to_string(),
+                consumer: AST_VERSION.to_string(),
+                supported: supported_ast_range(),
+            })
+        }
+    };
+    check_ast_version(&producer)?;
+
+    let ast = document
+        .get_mut("ast")
+        .map(serde_json::Value::take)
+        .ok_or_else(|| ResolutionError::InvalidInput {
+            message: "AST document has no 'ast' field".to_string(),
+        })?;
+
+    serde_json::from_value(ast).map_err(|e| ResolutionError::InvalidInput {
+        message: format!(
+            "AST document (version {}) could not be decoded: {}",
+            producer, e
+        ),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use esp_compiler::grammar::ast::{
+        ArithmeticOperator, AstDocument, CriteriaContent, DataType, EntityCheck, LogicalOp,
+        Operation, RunParameter, RuntimeOperationType, Value,
+    };
+
+    /// Frozen documents for every supported AST version; never edit, only add
+    const FIXTURES: &[(&str, &str)] = &[
+        ("1.0", include_str!("../../tests/fixtures/ast/v1_0.json")),
+        ("1.1", include_str!("../../tests/fixtures/ast/v1_1.json")),
+        ("1.2", include_str!("../../tests/fixtures/ast/v1_2.json")),
+        ("1.3", include_str!("../../tests/fixtures/ast/v1_3.json")),
+        ("1.4", include_str!("../../tests/fixtures/ast/v1_4.json")),
+        ("1.5", include_str!("../../tests/fixtures/ast/v1_5.json")),
+        ("1.6", include_str!("../../tests/fixtures/ast/v1_6.json")),
+        ("1.7", include_str!("../../tests/fixtures/ast/v1_7.json")),
+        ("1.8", include_str!("../../tests/fixtures/ast/v1_8.json")),
+        ("1.9", include_str!("../../tests/fixtures/ast/v1_9.json")),
+        ("1.10", include_str!("../../tests/fixtures/ast/v1_10.json")),
+        ("1.11", include_str!("../../tests/fixtures/ast/v1_11.json")),
+        ("1.12", include_str!("../../tests/fixtures/ast/v1_12.json")),
+        ("1.13", include_str!("../../tests/fixtures/ast/v1_13.json")),
+        ("1.14", include_str!("../../tests/fixtures/ast/v1_14.json")),
+        ("1.15", include_str!("../../tests/fixtures/ast/v1_15.json")),
+        ("1.16", include_str!("../../tests/fixtures/ast/v1_16.json")),
+    ];
+
+    #[test]
+    fn test_frozen_fixtures_still_decode() {
+        for (version, json) in FIXTURES {
+            let ast = parse_ast_document(json)
+                .unwrap_or_else(|e| panic!("fixture {} rejected: {}", version, e));
+            assert!(!ast.definition.states.is_empty(), "fixture {}", version);
+        }
+
+        let v1_1 = parse_ast_document(FIXTURES[1].1).unwrap();
+        assert!(matches!(
+            v1_1.definition.states[0].fields[0].value,
+            Value::List(_)
+        ));
+
+        let v1_2 = parse_ast_document(FIXTURES[2].1).unwrap();
+        assert_eq!(
+            v1_2.definition.states[0].fields[2].operation,
+            Operation::OccurrenceCount
+        );
+
+        let v1_3 = parse_ast_document(FIXTURES[3].1).unwrap();
+        let CriteriaContent::Criterion(ctn) = &v1_3.definition.criteria[0].content[0] else {
+            panic!("expected a CTN");
+        };
+        assert_eq!(ctn.controls.len(), 2);
+        assert_eq!(ctn.controls[1].control_id, "RHEL-09-255045");
+        assert!(ctn.controls[1].url.is_none());
+        assert!(ctn.severity.is_none());
+
+        let v1_4 = parse_ast_document(FIXTURES[4].1).unwrap();
+        let CriteriaContent::Criterion(ctn) = &v1_4.definition.criteria[0].content[0] else {
+            panic!("expected a CTN");
+        };
+        assert_eq!(ctn.severity.as_deref(), Some("critical"));
+
+        let v1_5 = parse_ast_document(FIXTURES[5].1).unwrap();
+        assert_eq!(
+            v1_5.definition.states[0].fields[1].operation,
+            Operation::VersionGreaterOrEqual
+        );
+
+        let v1_6 = parse_ast_document(FIXTURES[6].1).unwrap();
+        let field = &v1_6.definition.states[0].fields[0];
+        assert_eq!(field.data_type, DataType::IpAddress);
+        assert_eq!(field.operation, Operation::InSubnet);
+
+        let v1_7 = parse_ast_document(FIXTURES[7].1).unwrap();
+        let field = &v1_7.definition.states[0].fields[0];
+        assert_eq!(field.operation, Operation::NotGlobMatch);
+
+        let v1_8 = parse_ast_document(FIXTURES[8].1).unwrap();
+        let field = &v1_8.definition.states[0].fields[0];
+        assert_eq!(field.data_type, DataType::Octal);
+        assert_eq!(field.operation, Operation::PermissionsAtMost);
+
+        let v1_9 = parse_ast_document(FIXTURES[9].1).unwrap();
+        let field = &v1_9.definition.states[0].fields[0];
+        assert_eq!(field.data_type, DataType::DateTime);
+        assert_eq!(field.value, Value::String("now-30d".to_string()));
+
+        let v1_10 = parse_ast_document(FIXTURES[10].1).unwrap();
+        let fields = &v1_10.definition.states[0].fields;
+        assert_eq!(fields[0].entity_check, Some(EntityCheck::AtLeastN(2)));
+        assert_eq!(
+            fields[1].entity_check,
+            Some(EntityCheck::AtLeastPercent(90))
+        );
+
+        let v1_11 = parse_ast_document(FIXTURES[11].1).unwrap();
+        let replace = &v1_11.definition.runtime_operations[0];
+        assert_eq!(replace.operation_type, RuntimeOperationType::Replace);
+        assert_eq!(
+            replace.parameters[1],
+            RunParameter::Pattern(r",\s*".to_string())
+        );
+
+        let v1_12 = parse_ast_document(FIXTURES[12].1).unwrap();
+        let length = &v1_12.definition.runtime_operations[0];
+        assert_eq!(length.operation_type, RuntimeOperationType::Length);
+        assert_eq!(
+            length.parameters[0],
+            RunParameter::Variable("banner".to_string())
+        );
+
+        let v1_13 = parse_ast_document(FIXTURES[13].1).unwrap();
+        let operations: Vec<_> = v1_13
+            .definition
+            .runtime_operations
+            .iter()
+            .map(|op| op.operation_type)
+            .collect();
+        assert_eq!(
+            operations,
+            [
+                RuntimeOperationType::Lowercase,
+                RuntimeOperationType::Trim,
+                RuntimeOperationType::Uppercase
+            ]
+        );
+
+        let v1_14 = parse_ast_document(FIXTURES[14].1).unwrap();
+        let clamp = &v1_14.definition.runtime_operations[1].parameters;
+        assert_eq!(
+            clamp[1],
+            RunParameter::ArithmeticOp(ArithmeticOperator::Abs, None)
+        );
+        assert_eq!(
+            clamp[2],
+            RunParameter::ArithmeticOp(ArithmeticOperator::Min, Some(Value::Integer(65535)))
+        );
+
+        let v1_15 = parse_ast_document(FIXTURES[15].1).unwrap();
+        assert!(matches!(
+            v1_15.definition.variables[0].initial_value,
+            Some(Value::List(ref servers)) if servers.len() == 2
+        ));
+
+        let v1_16 = parse_ast_document(FIXTURES[16].1).unwrap();
+        let root = v1_16.definition.criteria_root.unwrap();
+        assert_eq!(root.logical_op, LogicalOp::Or);
+        assert!(root.negate);
+        assert!(v1_15.definition.criteria_root.is_none());
+    }
+
+    #[test]
+    fn test_round_trip_current_version() {
+        let ast = parse_ast_document(FIXTURES[FIXTURES.len() - 1].1).unwrap();
+        let json = AstDocument::new(ast.clone()).to_json().unwrap();
+        assert_eq!(parse_ast_document(&json).unwrap(), ast);
+    }
+
+    #[test]
+    fn test_newer_minor_with_additive_field_is_tolerated() {
+        let mut document: serde_json::Value = serde_json::from_str(FIXTURES[1].1).unwrap();
+        document["ast_version"] = format!("{}.99", AST_VERSION_MAJOR).into();
+        document["ast"]["future_field"] = serde_json::json!({"added": true});
+
+        assert!(parse_ast_document(&document.to_string()).is_ok());
+    }
+
+    #[test]
+    fn test_other_major_or_missing_version_is_one_clear_error() {
+        let mut document: serde_json::Value = serde_json::from_str(FIXTURES[0].1).unwrap();
+        document["ast_version"] = "2.0".into();
+        let message = parse_ast_document(&document.to_string())
+            .unwrap_err()
+            .to_string();
+        assert!(message.contains("2.0"));
+        assert!(message.contains(AST_VERSION));
+        assert!(message.contains(&supported_ast_range()));
+
+        document.as_object_mut().unwrap().remove("ast_version");
+        let message = parse_ast_document(&document.to_string())
+            .unwrap_err()
+            .to_string();
+        assert!(message.contains("unversioned"));
+    }
+}
@@ -0,0 +1,361 @@
+//! Compiler AST to scanner declarations
+//!
+//! Builds the unresolved [`ResolutionContext`] a scan starts from. The CRI/CTN
+//! hierarchy is preserved as a [`CriteriaRoot`] and every CTN gets a node id in
+//! document order.
+//!
+//! Each declaration is converted by its own `from_ast_node` in [`crate::types`];
+//! this module only walks the document and numbers the CTNs.
+
+use crate::types::common::LogicalOp;
+use crate::types::criteria::{CriteriaRoot, CriteriaTree};
+use crate::types::criterion::CriterionDeclaration;
+use crate::types::metadata::MetaDataBlock;
+use crate::types::object::ObjectDeclaration;
+use crate::types::resolution_context::ResolutionContext;
+use crate::types::runtime_operation::RuntimeOperation;
+use crate::types::set::SetOperation;
+use crate::types::state::StateDeclaration;
+use crate::types::variable::VariableDeclaration;
+use esp_compiler::grammar::ast::nodes::{
+    CriteriaContent, CriteriaNode, CriteriaRootNode, CriterionNode, EspFile,
+};
+
+/// Unresolved resolution context for a compiled or loaded AST
+pub fn resolution_context_from_ast(ast: &EspFile) -> ResolutionContext {
+    let metadata = match &ast.metadata {
+        Some(meta) => MetaDataBlock::from_ordered(
+            meta.fields
+                .iter()
+                .map(|field| (field.name.clone(), field.value.clone())),
+        ),
+        None => MetaDataBlock::default(),
+    };
+
+    let definition = &ast.definition;
+    let variables = definition
+        .variables
+        .iter()
+        .map(VariableDeclaration::from_ast_node)
+        .collect();
+    let states = definition
+        .states
+        .iter()
+        .map(StateDeclaration::from_ast_node)
+        .collect();
+    let objects = definition
+        .objects
+        .iter()
+        .map(ObjectDeclaration::from_ast_node)
+        .collect();
+    let runtime_operations = definition
+        .runtime_operations
+        .iter()
+        .map(RuntimeOperation::from_ast_node)
+        .collect();
+    let sets = definition
+        .set_operations
+        .iter()
+        .map(SetOperation::from_ast_node)
+        .collect();
+
+    let mut node_id_counter = 1;
+    let criteria_root = build_criteria_root(
+        &definition.criteria,
+        definition.criteria_root.as_ref(),
+        &mut node_id_counter,
+    );
+
+    ResolutionContext::from_ast_with_criteria_root(
+        variables,
+        states,
+        objects,
+        runtime_operations,
+        sets,
+        criteria_root,
+        metadata,
+    )
+}
+
+/// Top-level CRI blocks, combined with AND unless CRI_ROOT says otherwise
+fn build_criteria_root(
+    criteria_nodes: &[CriteriaNode],
+    root: Option<&CriteriaRootNode>,
+    node_id_counter: &mut usize,
+) -> CriteriaRoot {
+    CriteriaRoot {
+        trees: criteria_nodes
+            .iter()
+            .map(|cri_node| convert_criteria_node(cri_node, node_id_counter))
+            .collect(),
+        root_logical_op: root.map_or(LogicalOp::And, |root| root.logical_op),
+        root_negate: root.is_some_and(|root| root.negate),
+    }
+}
+
+fn convert_criteria_node(cri_node: &CriteriaNode, node_id_counter: &mut usize) -> CriteriaTree {
+    let mut children = Vec::new();
+
+    for content in &cri_node.content {
+        match content {
+            CriteriaContent::Criterion(ctn_node) => {
+                let node_id = *node_id_counter;
+                *node_id_counter += 1;
+
+                children.push(CriteriaTree::Criterion {
+                    declaration: convert_ctn(ctn_node, node_id),
+                    node_id,
+                });
+            }
+            CriteriaContent::Criteria(nested_cri) => {
+                children.push(convert_criteria_node(nested_cri, node_id_counter));
+            }
+        }
+    }
+
+    // A CRI with a single child is just that child
+    if children.len() == 1 {
+        if let Some(only) = children.pop() {
+            return only;
+        }
+    }
+
+    let logical_op = match cri_node.logical_op {
+        esp_compiler::grammar::ast::nodes::LogicalOp::And => LogicalOp::And,
+        esp_compiler::grammar::ast::nodes::LogicalOp::Or => LogicalOp::Or,
+    };
+
+    CriteriaTree::Block {
+        logical_op,
+        negate: cri_node.negate,
+        children,
+    }
+}
+
+/// CTN declaration whose local state and object are never global, even in a
+/// hand-written AST document that marks them so
+fn convert_ctn(ctn_node: &CriterionNode, node_id: usize) -> CriterionDeclaration {
+    let mut declaration = CriterionDeclaration::from_ast_node(ctn_node, node_id);
+    for state in &mut declaration.local_states {
+        state.is_global = false;
+    }
+    if let Some(object) = &mut declaration.local_object {
+        object.is_global = false;
+    }
+    declaration
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use esp_compiler::grammar::ast::nodes::ObjectElement;
+
+    const POLICY: &str = "META
+    version `1.0.0`
+    esp_version `1.0`
+    author `test`
+    date `2026-01-01`
+    severity `low`
+    platform `linux`
+    description `ast conversion`
+    control_framework `TEST`
+    control `AST-1`
+    esp_scan_id `ast_conversion`
+    criticality `low`
+    tags `test`
+META_END
+
+DEF
+    OBJECT sshd
+        service_name `sshd.service`
+        parameters string
+            properties `ActiveState`
+        parameters_end
+    OBJECT_END
+
+    STATE hardened
+        active boolean = true
+        record record_data
+            field ActiveState string = `active`
+        record_end
+    STATE_END
+
+    CRI AND
+        CTN systemd_service
+            TEST all all
+            STATE_REF hardened
+            OBJECT_REF sshd
+        CTN_END
+        CTN file_metadata
+            TEST all all
+            STATE local_mode
+                permissions string = `0600`
+            STATE_END
+            OBJECT local_file
+                path `/etc/shadow`
+            OBJECT_END
+        CTN_END
+    CRI_END
+DEF_END
+";
+
+    fn context() -> ResolutionContext {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("policy.esp");
+        std::fs::write(&path, POLICY).unwrap();
+        let ast = esp_compiler::pipeline::process_file(&path.display().to_string())
+            .unwrap()
+            .ast;
+        resolution_context_from_ast(&ast)
+    }
+
+    fn criteria(tree: &CriteriaTree) -> Vec<&CriterionDeclaration> {
+        match tree {
+            CriteriaTree::Criterion { declaration, .. } => vec![declaration],
+            CriteriaTree::Block { children, .. } => children.iter().flat_map(criteria).collect(),
+        }
+    }
+
+    #[test]
+    fn test_global_state_keeps_fields_and_record_checks() {
+        let context = context();
+        let state = &context.states[0];
+        assert_eq!(state.identifier, "hardened");
+        assert!(state.is_global);
+        assert_eq!(state.fields.len(), 1);
+        assert_eq!(state.fields[0].name, "active");
+        assert_eq!(state.record_checks.len(), 1);
+    }
+
+    #[test]
+    fn test_global_object_keeps_its_elements() {
+        let context = context();
+        let object = &context.objects[0];
+        assert_eq!(object.identifier, "sshd");
+        assert!(object.is_global);
+        assert!(object
+            .elements
+            .iter()
+            .any(|e| matches!(e, ObjectElement::Parameter { .. })));
+    }
+
+    #[test]
+    fn test_local_declarations_are_not_global() {
+        let context = context();
+        let root = &context.criteria_root;
+        let declarations: Vec<_> = root.trees.iter().flat_map(criteria).collect();
+        assert_eq!(declarations.len(), 2);
+
+        let local = declarations[1];
+        assert_eq!(local.local_states.len(), 1);
+        assert!(!local.local_states[0].is_global);
+        assert_eq!(local.local_states[0].fields[0].name, "permissions");
+        let object = local.local_object.as_ref().unwrap();
+        assert_eq!(object.identifier, "local_file");
+        assert!(!object.is_global);
+    }
+
+    #[test]
+    fn test_local_declarations_are_keyed_by_their_ctn() {
+        let context = context();
+        assert_eq!(context.ctn_local_states.len(), 1);
+        assert_eq!(context.ctn_local_states[&2][0].identifier, "local_mode");
+        assert_eq!(context.ctn_local_objects[&2].identifier, "local_file");
+        assert!(!context.ctn_local_states.contains_key(&1));
+    }
+
+    #[test]
+    fn test_each_ctn_gets_its_own_local_states() {
+        let local_ctn = |state: &str, mode: &str| {
+            format!(
+                "        CTN file_metadata
+            TEST all all
+            STATE {}
+                permissions string = `{}`
+            STATE_END
+            OBJECT {}_file
+                path `/etc/{}`
+            OBJECT_END
+        CTN_END
+",
+                state, mode, state, state
+            )
+        };
+        let criteria = format!(
+            "    CRI AND\n{}{}{}    CRI_END\n",
+            local_ctn("passwd", "0644"),
+            local_ctn("shadow", "0600"),
+            local_ctn("group", "0644")
+        );
+        let start = POLICY.find("    CRI AND").unwrap();
+        let end = POLICY.find("DEF_END").unwrap();
+        let source = format!("{}{}{}", &POLICY[..start], criteria, &POLICY[end..]);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("policy.esp");
+        std::fs::write(&path, source).unwrap();
+        let ast = esp_compiler::pipeline::process_file(&path.display().to_string())
+            .unwrap()
+            .ast;
+        let resolved = crate::resolution::engine::ResolutionEngine::new()
+            .resolve_context(&mut resolution_context_from_ast(&ast))
+            .unwrap();
+
+        let criteria = resolved.criteria_tree.get_all_criteria();
+        assert_eq!(criteria.len(), 3);
+        for (criterion, name) in criteria.iter().zip(["passwd", "shadow", "group"]) {
+            assert_eq!(criterion.states.len(), 1);
+            assert_eq!(criterion.states[0].identifier, name);
+            assert_eq!(criterion.objects.len(), 1);
+            assert_eq!(criterion.objects[0].identifier, format!("{}_file", name));
+        }
+    }
+
+    #[test]
+    fn test_local_states_keyed_to_a_missing_ctn_are_rejected() {
+        let mut context = context();
+        let states = context.ctn_local_states.remove(&2).unwrap();
+        context.ctn_local_states.insert(7, states);
+
+        let err = crate::resolution::engine::ResolutionEngine::new()
+            .resolve_context(&mut context)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("CTN node 7"), "{}", err);
+    }
+
+    #[test]
+    fn test_criteria_root_directive_reaches_the_executable_tree() {
+        use crate::types::execution_context::ExecutableCriteriaTree;
+
+        assert_eq!(context().criteria_root.root_logical_op, LogicalOp::And);
+        assert!(!context().criteria_root.root_negate);
+
+        let source = POLICY.replacen("    CRI AND", "    CRI_ROOT OR true\n    CRI AND", 1);
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("policy.esp");
+        std::fs::write(&path, source).unwrap();
+        let ast = esp_compiler::pipeline::process_file(&path.display().to_string())
+            .unwrap()
+            .ast;
+        let mut context = resolution_context_from_ast(&ast);
+        assert_eq!(context.criteria_root.root_logical_op, LogicalOp::Or);
+        assert!(context.criteria_root.root_negate);
+
+        // A single negated CRI block still gets a root block to carry the NOT
+        let resolved = crate::resolution::engine::ResolutionEngine::new()
+            .resolve_context(&mut context)
+            .unwrap();
+        let ExecutableCriteriaTree::Block {
+            logical_op,
+            negate,
+            children,
+        } = &resolved.criteria_tree
+        else {
+            panic!("expected a root block");
+        };
+        assert_eq!(*logical_op, LogicalOp::Or);
+        assert!(*negate);
+        assert_eq!(children.len(), 1);
+    }
+}
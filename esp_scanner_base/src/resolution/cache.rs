@@ -0,0 +1,325 @@
+//! On-disk cache of resolved policies
+//!
+//! Compiling and resolving a policy depends only on its source and on the
+//! scanner doing it, so a [`PolicyCache`] keeps each resolved
+//! [`ExecutionContext`] in a directory keyed by both. A scan that finds a
+//! valid entry skips the compile and resolve phases entirely. Host data is
+//! never cached: scan-time RUN operations stay deferred in the context.
+//!
+//! Files the policy IMPORTs are not part of the key, since finding them
+//! means compiling; each entry records their hashes instead and stops
+//! matching once one of them changes.
+
+use crate::results::provenance::sha256;
+use crate::results::ScanProvenance;
+use crate::types::execution_context::ExecutionContext;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// What a cache entry was resolved from
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PolicyCacheKey {
+    /// SHA-256 of the policy source, as in [`ScanProvenance::policy_hash`]
+    pub source_hash: String,
+    pub scanner_version: String,
+}
+
+impl PolicyCacheKey {
+    pub fn new(source: &[u8], scanner_version: &str) -> Self {
+        Self {
+            source_hash: sha256(source),
+            scanner_version: scanner_version.to_string(),
+        }
+    }
+
+    /// Cache file name: one hash over every part of the key
+    fn file_name(&self) -> String {
+        let digest = sha256(format!("{}\n{}", self.source_hash, self.scanner_version).as_bytes());
+        format!("{}.json", digest.trim_start_matches("sha256:"))
+    }
+}
+
+/// A file merged into a cached policy through IMPORT
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImportedSource {
+    pub path: PathBuf,
+    /// SHA-256 of the file when the policy was resolved
+    pub hash: String,
+}
+
+impl ImportedSource {
+    fn is_current(&self) -> bool {
+        std::fs::read(&self.path).is_ok_and(|bytes| sha256(&bytes) == self.hash)
+    }
+}
+
+/// A resolved policy as stored in the cache
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedPolicy {
+    pub key: PolicyCacheKey,
+    #[serde(default)]
+    pub imports: Vec<ImportedSource>,
+    /// Provenance of the policy, so results from a cached context still
+    /// record the AST it was compiled to
+    pub provenance: ScanProvenance,
+    pub context: ExecutionContext,
+}
+
+/// A cache entry that exists but cannot be used
+#[derive(Debug, thiserror::Error)]
+pub enum PolicyCacheError {
+    #[error("cannot read cache file {path}: {source}")]
+    Unreadable {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("cache file {path} is corrupted: {reason}")]
+    Corrupted { path: PathBuf, reason: String },
+}
+
+/// Directory of resolved policies
+#[derive(Debug, Clone)]
+pub struct PolicyCache {
+    dir: PathBuf,
+    scanner_version: String,
+}
+
+impl PolicyCache {
+    pub fn new(dir: impl Into<PathBuf>, scanner_version: impl Into<String>) -> Self {
+        Self {
+            dir: dir.into(),
+            scanner_version: scanner_version.into(),
+        }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn key(&self, source: &[u8]) -> PolicyCacheKey {
+        PolicyCacheKey::new(source, &self.scanner_version)
+    }
+
+    /// Cache file for a policy source
+    pub fn path_for(&self, source: &[u8]) -> PathBuf {
+        self.dir.join(self.key(source).file_name())
+    }
+
+    /// The resolved policy for `source`, or `None` when nothing is cached
+    /// or a file it imports has changed since
+    ///
+    /// An entry that does not parse or was written for another key is an
+    /// error, so callers can warn before resolving the policy again.
+    pub fn load(&self, source: &[u8]) -> Result<Option<CachedPolicy>, PolicyCacheError> {
+        let expected = self.key(source);
+        let path = self.dir.join(expected.file_name());
+        let json = match std::fs::read_to_string(&path) {
+            Ok(json) => json,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(source) => return Err(PolicyCacheError::Unreadable { path, source }),
+        };
+
+        let cached: CachedPolicy =
+            serde_json::from_str(&json).map_err(|e| PolicyCacheError::Corrupted {
+                path: path.clone(),
+                reason: e.to_string(),
+            })?;
+        if cached.key != expected {
+            return Err(PolicyCacheError::Corrupted {
+                path,
+                reason: format!(
+                    "entry is for {} (scanner {}), expected {} (scanner {})",
+                    cached.key.source_hash,
+                    cached.key.scanner_version,
+                    expected.source_hash,
+                    expected.scanner_version
+                ),
+            });
+        }
+        if !cached.imports.iter().all(ImportedSource::is_current) {
+            return Ok(None);
+        }
+        Ok(Some(cached))
+    }
+
+    /// Save a resolved policy, replacing any entry for the same source
+    ///
+    /// Written to a temporary file and renamed, so a concurrent scan never
+    /// reads a partial entry.
+    pub fn store(
+        &self,
+        source: &[u8],
+        imports: &[PathBuf],
+        provenance: &ScanProvenance,
+        context: &ExecutionContext,
+    ) -> std::io::Result<PathBuf> {
+        let imports = imports
+            .iter()
+            .map(|path| {
+                Ok(ImportedSource {
+                    path: path.clone(),
+                    hash: sha256(&std::fs::read(path)?),
+                })
+            })
+            .collect::<std::io::Result<_>>()?;
+        let cached = CachedPolicy {
+            key: self.key(source),
+            imports,
+            provenance: provenance.clone(),
+            context: context.clone(),
+        };
+        let json = serde_json::to_string(&cached)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+
+        std::fs::create_dir_all(&self.dir)?;
+        let path = self.dir.join(cached.key.file_name());
+        let partial = path.with_extension(format!("json.{}.tmp", std::process::id()));
+        std::fs::write(&partial, json)?;
+        std::fs::rename(&partial, &path)?;
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resolution::prepared::ResolvedPolicy;
+    use esp_compiler::grammar::ast::nodes::EspFile;
+
+    const POLICY: &str = "META
+    version `1.0.0`
+    esp_version `1.0`
+    author `test`
+    date `2026-01-01`
+    severity `low`
+    platform `linux`
+    description `policy cache`
+    control_framework `TEST`
+    control `RES-1`
+    esp_scan_id `cache-test`
+    criticality `low`
+    tags `test`
+META_END
+
+DEF
+    VAR greeting string `hello`
+
+    OBJECT motd
+        path `/etc/motd`
+    OBJECT_END
+
+    STATE welcoming
+        content string contains VAR greeting
+    STATE_END
+
+    CRI AND
+        CTN file_content
+            TEST all all
+            STATE_REF welcoming
+            OBJECT_REF motd
+        CTN_END
+    CRI_END
+DEF_END
+";
+
+    fn compile(source: &str) -> EspFile {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("policy.esp");
+        std::fs::write(&path, source).unwrap();
+        esp_compiler::pipeline::process_file(&path.display().to_string())
+            .expect("policy compiles")
+            .ast
+    }
+
+    fn resolved(source: &str) -> (ScanProvenance, ExecutionContext) {
+        let ast = compile(source);
+        let context = ResolvedPolicy::from_ast(&ast)
+            .expect("policy resolves")
+            .context()
+            .clone();
+        (ScanProvenance::for_policy(source.as_bytes(), &ast), context)
+    }
+
+    #[test]
+    fn test_cached_context_round_trips() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = PolicyCache::new(dir.path().join("cache"), "1.2.3");
+        assert!(cache.load(POLICY.as_bytes()).unwrap().is_none());
+
+        let (provenance, context) = resolved(POLICY);
+        let path = cache
+            .store(POLICY.as_bytes(), &[], &provenance, &context)
+            .unwrap();
+        assert_eq!(path, cache.path_for(POLICY.as_bytes()));
+
+        let cached = cache.load(POLICY.as_bytes()).unwrap().expect("cache hit");
+        assert_eq!(cached.provenance, provenance);
+        assert_eq!(cached.context.count_criteria(), 1);
+        assert_eq!(
+            serde_json::to_value(&cached.context).unwrap(),
+            serde_json::to_value(&context).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_source_and_scanner_version_change_the_key() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = PolicyCache::new(dir.path(), "1.2.3");
+        let (provenance, context) = resolved(POLICY);
+        cache
+            .store(POLICY.as_bytes(), &[], &provenance, &context)
+            .unwrap();
+
+        let edited = POLICY.replace("`hello`", "`goodbye`");
+        assert!(cache.load(edited.as_bytes()).unwrap().is_none());
+
+        let upgraded = PolicyCache::new(dir.path(), "1.2.4");
+        assert!(upgraded.load(POLICY.as_bytes()).unwrap().is_none());
+        assert!(cache.load(POLICY.as_bytes()).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_changed_import_invalidates_the_entry() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = PolicyCache::new(dir.path().join("cache"), "1.2.3");
+        let shared = dir.path().join("shared.esp");
+        std::fs::write(&shared, "DEF\nDEF_END\n").unwrap();
+
+        let (provenance, context) = resolved(POLICY);
+        cache
+            .store(
+                POLICY.as_bytes(),
+                std::slice::from_ref(&shared),
+                &provenance,
+                &context,
+            )
+            .unwrap();
+        assert!(cache.load(POLICY.as_bytes()).unwrap().is_some());
+
+        std::fs::write(&shared, "DEF\n    VAR extra string `x`\nDEF_END\n").unwrap();
+        assert!(cache.load(POLICY.as_bytes()).unwrap().is_none());
+
+        std::fs::remove_file(&shared).unwrap();
+        assert!(cache.load(POLICY.as_bytes()).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_corrupted_entry_is_an_error_not_a_hit() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = PolicyCache::new(dir.path(), "1.2.3");
+
+        std::fs::write(cache.path_for(POLICY.as_bytes()), "{ not json").unwrap();
+        let err = cache.load(POLICY.as_bytes()).unwrap_err();
+        assert!(matches!(err, PolicyCacheError::Corrupted { .. }), "{}", err);
+
+        // An entry copied over from another policy does not match its key
+        let other = POLICY.replace("cache-test", "other");
+        let (provenance, context) = resolved(&other);
+        let written = cache
+            .store(other.as_bytes(), &[], &provenance, &context)
+            .unwrap();
+        std::fs::rename(written, cache.path_for(POLICY.as_bytes())).unwrap();
+        let err = cache.load(POLICY.as_bytes()).unwrap_err().to_string();
+        assert!(err.contains("entry is for"), "{}", err);
+    }
+}
@@ -1,7 +1,7 @@
 //! Dependency Graph (DAG) implementation for ICS resolution engine
 //! Handles topological sorting and cycle detection for symbol resolution
 
-use crate::resolution::error::ResolutionError;
+use crate::resolution::error::{DependencyCycle, ResolutionError};
 use crate::types::criterion::CtnNodeId;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet, VecDeque};
@@ -46,6 +46,17 @@ impl SymbolType {
         }
     }
 
+    /// Keyword used to label the symbol in cycle paths (`SET:a`)
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Variable => "VAR",
+            Self::RuntimeOperation => "RUN",
+            Self::SetOperation => "SET",
+            Self::GlobalState | Self::LocalState => "STATE",
+            Self::GlobalObject | Self::LocalObject => "OBJECT",
+        }
+    }
+
     /// Check if this symbol type is global scope
     pub fn is_global(&self) -> bool {
         matches!(
@@ -128,6 +139,7 @@ pub struct DependencyGraph {
     pub nodes: HashMap<String, SymbolNode>,
     pub edges: HashMap<String, Vec<String>>, // symbol -> its dependencies
     pub reverse_edges: HashMap<String, Vec<String>>, // symbol -> symbols that depend on it
+    pub edge_contexts: HashMap<(String, String), String>, // (from, to) -> why from depends on to
     pub global_symbols: HashSet<String>,
     pub local_symbols: HashMap<CtnNodeId, HashSet<String>>,
 }
@@ -139,6 +151,7 @@ impl DependencyGraph {
             nodes: HashMap::new(),
             edges: HashMap::new(),
             reverse_edges: HashMap::new(),
+            edge_contexts: HashMap::new(),
             global_symbols: HashSet::new(),
             local_symbols: HashMap::new(),
         }
@@ -248,6 +261,20 @@ impl DependencyGraph {
         Ok(())
     }
 
+    /// Add dependency edge with a note on where it comes from, reported if
+    /// the edge turns out to be part of a cycle
+    pub fn add_dependency_with_context(
+        &mut self,
+        from: &str,
+        to: &str,
+        context: impl Into<String>,
+    ) -> Result<(), ResolutionError> {
+        self.add_dependency(from, to)?;
+        self.edge_contexts
+            .insert((from.to_string(), to.to_string()), context.into());
+        Ok(())
+    }
+
     /// Get dependencies for a symbol
     pub fn get_dependencies(&self, symbol: &str) -> Vec<String> {
         self.edges.get(symbol).cloned().unwrap_or_default()
@@ -261,8 +288,9 @@ impl DependencyGraph {
     /// Perform topological sort to get resolution order
     pub fn topological_sort(&self) -> Result<Vec<String>, ResolutionError> {
         // Detect cycles first
-        if let Some(cycle) = self.detect_cycle() {
-            return Err(ResolutionError::CircularDependency { cycle });
+        let cycles = self.detect_cycles();
+        if !cycles.is_empty() {
+            return Err(ResolutionError::CircularDependency { cycles });
         }
 
         // Kahn's algorithm for topological sorting
@@ -315,23 +343,32 @@ impl DependencyGraph {
         Ok(result)
     }
 
-    /// Detect cycles in the dependency graph using DFS
-    pub fn detect_cycle(&self) -> Option<Vec<String>> {
+    /// Detect every cycle in the dependency graph using DFS
+    ///
+    /// Each back edge found closes one cycle, so independent cycles are all
+    /// reported. Symbols are visited in sorted order to keep the output stable.
+    pub fn detect_cycles(&self) -> Vec<DependencyCycle> {
         let mut visited = HashSet::new();
         let mut rec_stack = HashSet::new();
         let mut path = Vec::new();
+        let mut cycles = Vec::new();
 
-        for node_id in self.nodes.keys() {
+        let mut node_ids: Vec<&String> = self.nodes.keys().collect();
+        node_ids.sort();
+
+        for node_id in node_ids {
             if !visited.contains(node_id) {
-                if let Some(cycle) =
-                    self.dfs_cycle_detect(node_id, &mut visited, &mut rec_stack, &mut path)
-                {
-                    return Some(cycle);
-                }
+                self.dfs_cycle_detect(
+                    node_id,
+                    &mut visited,
+                    &mut rec_stack,
+                    &mut path,
+                    &mut cycles,
+                );
             }
         }
 
-        None
+        cycles
     }
 
     /// DFS-based cycle detection helper
@@ -341,7 +378,8 @@ impl DependencyGraph {
         visited: &mut HashSet<String>,
         rec_stack: &mut HashSet<String>,
         path: &mut Vec<String>,
-    ) -> Option<Vec<String>> {
+        cycles: &mut Vec<DependencyCycle>,
+    ) {
         visited.insert(node.to_string());
         rec_stack.insert(node.to_string());
         path.push(node.to_string());
@@ -349,28 +387,41 @@ impl DependencyGraph {
         // Follow dependencies (outgoing edges)
         for dependency in self.get_dependencies(node) {
             if !visited.contains(&dependency) {
-                if let Some(cycle) = self.dfs_cycle_detect(&dependency, visited, rec_stack, path) {
-                    return Some(cycle);
-                }
+                self.dfs_cycle_detect(&dependency, visited, rec_stack, path, cycles);
             } else if rec_stack.contains(&dependency) {
-                // Back edge found - cycle detected
-                // Extract cycle from path starting at the back edge target
+                // Back edge found - the cycle runs from its target to here
                 if let Some(cycle_start_idx) = path.iter().position(|x| x == &dependency) {
                     let mut cycle = path[cycle_start_idx..].to_vec();
                     cycle.push(dependency);
-                    return Some(cycle);
-                } else {
-                    // Fallback: just return the current path as cycle
-                    let mut cycle = path.clone();
-                    cycle.push(dependency);
-                    return Some(cycle);
+                    cycles.push(self.describe_cycle(&cycle));
                 }
             }
         }
 
         rec_stack.remove(node);
         path.pop();
-        None
+    }
+
+    /// Label the symbols of a cycle by kind and collect the edge contexts
+    fn describe_cycle(&self, cycle: &[String]) -> DependencyCycle {
+        let path = cycle
+            .iter()
+            .map(|id| match self.nodes.get(id) {
+                Some(node) => format!("{}:{}", node.symbol_type.label(), id),
+                None => id.clone(),
+            })
+            .collect();
+
+        let contexts = cycle
+            .windows(2)
+            .filter_map(|step| {
+                self.edge_contexts
+                    .get(&(step[0].clone(), step[1].clone()))
+                    .cloned()
+            })
+            .collect();
+
+        DependencyCycle { path, contexts }
     }
 
     /// Get graph statistics for monitoring
@@ -487,3 +538,67 @@ impl Default for DependencyGraph {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph_with(nodes: &[(&str, SymbolType)]) -> DependencyGraph {
+        let mut graph = DependencyGraph::new();
+        for (id, symbol_type) in nodes {
+            graph.add_node(id.to_string(), *symbol_type).unwrap();
+        }
+        graph
+    }
+
+    #[test]
+    fn test_cycle_error_shows_the_full_path() {
+        let mut graph = graph_with(&[
+            ("a", SymbolType::SetOperation),
+            ("x", SymbolType::Variable),
+            ("y", SymbolType::RuntimeOperation),
+        ]);
+        graph
+            .add_dependency_with_context("a", "x", "SET a inline object uses VAR x")
+            .unwrap();
+        graph
+            .add_dependency_with_context("x", "y", "VAR x is initialized from VAR y")
+            .unwrap();
+        graph.add_dependency("y", "a").unwrap();
+
+        let error = graph.topological_sort().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Circular dependency detected: SET:a -> VAR:x -> RUN:y -> SET:a \
+             (SET a inline object uses VAR x, VAR x is initialized from VAR y)"
+        );
+    }
+
+    #[test]
+    fn test_independent_cycles_are_all_reported() {
+        let mut graph = graph_with(&[
+            ("a", SymbolType::SetOperation),
+            ("b", SymbolType::SetOperation),
+            ("p", SymbolType::Variable),
+            ("q", SymbolType::Variable),
+            ("free", SymbolType::Variable),
+        ]);
+        graph.add_dependency("a", "b").unwrap();
+        graph.add_dependency("b", "a").unwrap();
+        graph.add_dependency("p", "q").unwrap();
+        graph.add_dependency("q", "p").unwrap();
+        graph.add_dependency("free", "p").unwrap();
+
+        let cycles = graph.detect_cycles();
+        let paths: Vec<String> = cycles.iter().map(|c| c.path.join(" -> ")).collect();
+        assert_eq!(
+            paths,
+            vec!["SET:a -> SET:b -> SET:a", "VAR:p -> VAR:q -> VAR:p"]
+        );
+
+        match graph.topological_sort() {
+            Err(ResolutionError::CircularDependency { cycles }) => assert_eq!(cycles.len(), 2),
+            other => panic!("expected a circular dependency error, got {:?}", other),
+        }
+    }
+}
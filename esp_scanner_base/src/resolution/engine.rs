@@ -1,7 +1,8 @@
 use crate::resolution::dag::{DependencyGraph, SymbolType};
 use crate::resolution::error::ResolutionError;
 use crate::resolution::field_resolver::FieldResolver;
-use crate::types::execution_context::ExecutionContext;
+use crate::types::common::{DataType, ResolvedValue, Timestamp};
+use crate::types::execution_context::{ExecutableRecordContent, ExecutionContext};
 use crate::types::object::ObjectDeclaration;
 use crate::types::resolution_context::{DeferredOperation, ResolutionContext};
 use crate::types::set::SetOperand;
@@ -12,10 +13,13 @@ use crate::types::{
 };
 use esp_compiler::grammar::ast::nodes::{ObjectElement, RunParameter};
 use esp_compiler::{log_debug, log_info};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 pub struct ResolutionEngine {
     field_resolver: FieldResolver,
+    /// Variables only computable from collected data (RUN EXTRACT and
+    /// anything derived from it)
+    scan_time_variables: HashSet<String>,
 }
 
 impl ResolutionEngine {
@@ -23,6 +27,7 @@ impl ResolutionEngine {
         log_debug!("Creating DAG-based Resolution Engine");
         Self {
             field_resolver: FieldResolver::new(),
+            scan_time_variables: HashSet::new(),
         }
     }
 
@@ -37,12 +42,21 @@ impl ResolutionEngine {
             "objects" => context.objects.len()
         );
 
+        // Report every dangling reference before the DAG stops at the first one
+        crate::resolution::references::validate_references(context)?;
+
         // Perform DAG-based resolution
         self.resolve_dag(context)?;
 
         // Expand sets in resolution context
         crate::resolution::set_expansion::expand_sets_in_resolution_context(context)?;
 
+        // State counts are final once references are resolved and sets expanded
+        crate::resolution::test_specification::validate_test_specifications(context)?;
+
+        // Assign extraction, computation and criteria to ordered phases
+        context.execution_plan = crate::resolution::phases::plan_execution(context);
+
         // Create ExecutionContext
         let execution_context = ExecutionContext::from_resolution_context(context)
             .map_err(|e| ResolutionError::ContextError(e.to_string()))?;
@@ -61,6 +75,63 @@ impl ResolutionEngine {
         Ok(execution_context)
     }
 
+    /// Resolve `datetime` state values to epoch seconds against the scan
+    /// instant
+    ///
+    /// This runs at scan time rather than in [`resolve_context`](Self::resolve_context):
+    /// a resolved policy is reused across scans, so `now-30d` must be read
+    /// against each scan's clock.
+    pub fn resolve_datetime_fields(
+        context: &mut ExecutionContext,
+        now: i64,
+    ) -> Result<(), ResolutionError> {
+        let resolve = |value: &mut ResolvedValue, context: String| {
+            let timestamp = Timestamp::from_resolved(value).map_err(|reason| {
+                ResolutionError::InvalidState(format!("{}: {}", context, reason))
+            })?;
+            *value = ResolvedValue::Integer(timestamp.epoch_seconds(now));
+            Ok::<(), ResolutionError>(())
+        };
+
+        // Global states are what filters compare against
+        for state in context.global_states.values_mut() {
+            for field in &mut state.resolved_fields {
+                if field.data_type == DataType::DateTime {
+                    let context = format!("state '{}' field '{}'", state.identifier, field.name);
+                    resolve(&mut field.value, context)?;
+                }
+            }
+        }
+
+        for criterion in context.criteria_tree.get_all_criteria_mut() {
+            for state in &mut criterion.states {
+                for field in &mut state.fields {
+                    if field.data_type == DataType::DateTime {
+                        let context =
+                            format!("state '{}' field '{}'", state.identifier, field.name);
+                        resolve(&mut field.value, context)?;
+                    }
+                }
+                for check in &mut state.record_checks {
+                    let ExecutableRecordContent::Nested { fields } = &mut check.content else {
+                        continue;
+                    };
+                    for field in fields {
+                        if field.data_type == DataType::DateTime {
+                            let context = format!(
+                                "state '{}' record field '{}'",
+                                state.identifier,
+                                field.path.to_dot_notation()
+                            );
+                            resolve(&mut field.value, context)?;
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// Main DAG resolution method
     fn resolve_dag(&mut self, context: &mut ResolutionContext) -> Result<(), ResolutionError> {
         log_info!(
@@ -69,6 +140,8 @@ impl ResolutionEngine {
             "relationships" => context.relationships.len()
         );
 
+        self.scan_time_variables = Self::find_scan_time_variables(context);
+
         // Build dependency graph (only includes resolution-time operations)
         let graph = self.build_dependency_graph(context)?;
 
@@ -110,15 +183,29 @@ impl ResolutionEngine {
 
         let mut graph = DependencyGraph::new();
 
-        // Step 1: Add all declared variables as nodes
+        // Step 1: Add all declared variables as nodes, marking RUN targets so
+        // cycle reports can tell them apart from plain variables
+        let run_targets: HashSet<&str> = context
+            .runtime_operations
+            .iter()
+            .map(|op| op.target_variable.as_str())
+            .collect();
         for variable in &context.variables {
-            graph.add_node(variable.name.clone(), SymbolType::Variable)?;
+            let symbol_type = if run_targets.contains(variable.name.as_str()) {
+                SymbolType::RuntimeOperation
+            } else {
+                SymbolType::Variable
+            };
+            graph.add_node(variable.name.clone(), symbol_type)?;
         }
 
         // Step 2: Add RUN operation target variables as nodes (computed variables)
         for runtime_op in &context.runtime_operations {
             if !graph.nodes.contains_key(&runtime_op.target_variable) {
-                graph.add_node(runtime_op.target_variable.clone(), SymbolType::Variable)?;
+                graph.add_node(
+                    runtime_op.target_variable.clone(),
+                    SymbolType::RuntimeOperation,
+                )?;
 
                 log_debug!(
                     "Added computed variable node to DAG",
@@ -155,7 +242,13 @@ impl ResolutionEngine {
                 // Resolution-time operation - add to DAG
                 for param in &runtime_op.parameters {
                     if let Some(var_name) = Self::extract_variable_from_param(param) {
-                        graph.add_dependency(&runtime_op.target_variable, &var_name)?;
+                        let context =
+                            format!("RUN {} reads VAR {}", runtime_op.target_variable, var_name);
+                        graph.add_dependency_with_context(
+                            &runtime_op.target_variable,
+                            &var_name,
+                            context,
+                        )?;
                     }
                 }
 
@@ -164,8 +257,23 @@ impl ResolutionEngine {
                     "target" => runtime_op.target_variable.as_str()
                 );
             } else {
+                // Scan-time - the extracted object must resolve before the variable
+                if let Some(object_id) = runtime_op.extract_object_id() {
+                    if graph.nodes.contains_key(&object_id) {
+                        let context = format!(
+                            "RUN {} extracts from OBJECT {}",
+                            runtime_op.target_variable, object_id
+                        );
+                        graph.add_dependency_with_context(
+                            &runtime_op.target_variable,
+                            &object_id,
+                            context,
+                        )?;
+                    }
+                }
+
                 log_debug!(
-                    "Runtime operation deferred to scan-time (not in DAG)",
+                    "Runtime operation deferred to scan-time",
                     "target" => runtime_op.target_variable.as_str()
                 );
             }
@@ -174,7 +282,8 @@ impl ResolutionEngine {
         // Step 6: Add variable initialization dependencies
         for variable in &context.variables {
             if let Some(var_ref) = variable.get_variable_reference() {
-                graph.add_dependency(&variable.name, var_ref)?;
+                let context = format!("VAR {} is initialized from VAR {}", variable.name, var_ref);
+                graph.add_dependency_with_context(&variable.name, var_ref, context)?;
             }
         }
 
@@ -194,6 +303,35 @@ impl ResolutionEngine {
         Ok(graph)
     }
 
+    /// RUN targets that depend on collected data, directly or through other
+    /// RUN operations
+    fn find_scan_time_variables(context: &ResolutionContext) -> HashSet<String> {
+        let mut scan_time: HashSet<String> = context
+            .runtime_operations
+            .iter()
+            .filter(|op| op.has_object_dependency())
+            .map(|op| op.target_variable.clone())
+            .collect();
+
+        loop {
+            let derived: Vec<String> = context
+                .runtime_operations
+                .iter()
+                .filter(|op| !scan_time.contains(&op.target_variable))
+                .filter(|op| {
+                    op.get_variable_references()
+                        .iter()
+                        .any(|v| scan_time.contains(v))
+                })
+                .map(|op| op.target_variable.clone())
+                .collect();
+            if derived.is_empty() {
+                return scan_time;
+            }
+            scan_time.extend(derived);
+        }
+    }
+
     /// Extract variable name from RunParameter
     fn extract_variable_from_param(param: &RunParameter) -> Option<String> {
         match param {
@@ -202,7 +340,7 @@ impl ResolutionEngine {
                 use crate::types::common::ValueExt;
                 value.get_variable_name().map(|s| s.to_string())
             }
-            RunParameter::ArithmeticOp(_, value) => {
+            RunParameter::ArithmeticOp(_, Some(value)) => {
                 use crate::types::common::ValueExt;
                 value.get_variable_name().map(|s| s.to_string())
             }
@@ -221,16 +359,21 @@ impl ResolutionEngine {
             for operand in &set_op.operands {
                 match operand {
                     SetOperand::ObjectRef(obj_id) => {
-                        graph.add_dependency(&set_op.set_id, obj_id)?;
+                        let context = format!("SET {} uses OBJECT {}", set_op.set_id, obj_id);
+                        graph.add_dependency_with_context(&set_op.set_id, obj_id, context)?;
                     }
                     SetOperand::SetRef(other_set_id) => {
-                        graph.add_dependency(&set_op.set_id, other_set_id)?;
+                        let context =
+                            format!("SET {} uses SET_REF {}", set_op.set_id, other_set_id);
+                        graph.add_dependency_with_context(&set_op.set_id, other_set_id, context)?;
                     }
                     SetOperand::InlineObject(obj) => {
                         // Get variable references from the inline object definition
                         let scanner_obj = crate::types::ObjectDeclaration::from_ast_node(obj);
                         for var_ref in scanner_obj.get_variable_references() {
-                            graph.add_dependency(&set_op.set_id, &var_ref)?;
+                            let context =
+                                format!("SET {} inline object uses VAR {}", set_op.set_id, var_ref);
+                            graph.add_dependency_with_context(&set_op.set_id, &var_ref, context)?;
                         }
                     }
                     SetOperand::FilteredObjectRef {
@@ -238,7 +381,8 @@ impl ResolutionEngine {
                         filter: _,
                     } => {
                         // Add dependency on the referenced object
-                        graph.add_dependency(&set_op.set_id, object_id)?;
+                        let context = format!("SET {} filters OBJECT {}", set_op.set_id, object_id);
+                        graph.add_dependency_with_context(&set_op.set_id, object_id, context)?;
                         // Filter dependencies would be handled separately
                     }
                 }
@@ -247,24 +391,31 @@ impl ResolutionEngine {
             // SET filter dependencies
             if let Some(filter) = &set_op.filter {
                 for state_ref in &filter.state_refs {
-                    graph.add_dependency(&set_op.set_id, &state_ref.state_id)?;
+                    let context = format!(
+                        "SET {} filter uses STATE {}",
+                        set_op.set_id, state_ref.state_id
+                    );
+                    graph.add_dependency_with_context(
+                        &set_op.set_id,
+                        &state_ref.state_id,
+                        context,
+                    )?;
                 }
             }
         }
 
-        // State dependencies on variables
+        // State dependencies on variables, from fields and record checks
         for state in &context.global_states {
-            for field in &state.fields {
-                use crate::types::common::ValueExt;
-                if let Some(var_name) = field.value.get_variable_name() {
-                    graph.add_dependency(&state.identifier, var_name)?;
-                }
+            for var_ref in state.get_variable_references() {
+                let context = format!("STATE {} uses VAR {}", state.identifier, var_ref);
+                graph.add_dependency_with_context(&state.identifier, &var_ref, context)?;
             }
         }
 
         for object in &context.global_objects {
             for var_ref in object.get_variable_references() {
-                graph.add_dependency(&object.identifier, &var_ref)?;
+                let context = format!("OBJECT {} uses VAR {}", object.identifier, var_ref);
+                graph.add_dependency_with_context(&object.identifier, &var_ref, context)?;
             }
         }
 
@@ -330,9 +481,7 @@ impl ResolutionEngine {
                     .iter()
                     .find(|op| op.target_variable == variable_name)
                 {
-                    let has_object_dep = run_op.has_object_dependency();
-
-                    if !has_object_dep {
+                    if !self.scan_time_variables.contains(variable_name) {
                         // Resolution-time
                         let result =
                             crate::resolution::runtime_operations::execute_runtime_operation(
@@ -373,9 +522,7 @@ impl ResolutionEngine {
                 .iter()
                 .find(|op| op.target_variable == variable_name)
             {
-                let has_object_dep = run_op.has_object_dependency();
-
-                if !has_object_dep {
+                if !self.scan_time_variables.contains(variable_name) {
                     // Resolution-time
                     let result = crate::resolution::runtime_operations::execute_runtime_operation(
                         run_op,
@@ -479,7 +626,32 @@ impl ResolutionEngine {
             })?
             .clone();
 
-        let resolved_object = self.resolve_object_fields(&object, &context.resolved_variables)?;
+        let late_bound = object
+            .get_variable_references()
+            .iter()
+            .any(|v| self.scan_time_variables.contains(v));
+
+        let resolved_object = if late_bound {
+            // Placeholders keep the criteria tree buildable; the substitute
+            // phase re-resolves the object once the variables are computed
+            let mut variables = context.resolved_variables.clone();
+            for name in &self.scan_time_variables {
+                variables
+                    .entry(name.clone())
+                    .or_insert_with(|| ResolvedVariable {
+                        identifier: name.clone(),
+                        data_type: crate::types::common::DataType::String,
+                        value: crate::types::common::ResolvedValue::String(String::new()),
+                    });
+            }
+            log_debug!("Object late-bound to scan-time variables", "object" => object_id);
+            context
+                .late_bound_objects
+                .insert(object_id.to_string(), object.clone());
+            self.resolve_object_fields(&object, &variables)?
+        } else {
+            self.resolve_object_fields(&object, &context.resolved_variables)?
+        };
         context
             .resolved_global_objects
             .insert(object_id.to_string(), resolved_object);
@@ -516,6 +688,8 @@ impl ResolutionEngine {
         &mut self,
         context: &mut ResolutionContext,
     ) -> Result<(), ResolutionError> {
+        Self::check_local_symbol_keys(context)?;
+
         // Resolve local objects
         for (ctn_id, local_object) in context.ctn_local_objects.clone() {
             let resolved_object =
@@ -541,6 +715,46 @@ impl ResolutionEngine {
         Ok(())
     }
 
+    /// Every CTN-local state and object must be keyed by the node id of a
+    /// criterion in the tree, and each criterion must carry the id it sits under
+    fn check_local_symbol_keys(context: &ResolutionContext) -> Result<(), ResolutionError> {
+        let mut node_ids = HashSet::new();
+        for (node_id, declaration) in context.criteria_root.criteria_with_node_ids() {
+            if let Some(declared) = declaration.ctn_node_id.filter(|id| *id != node_id) {
+                return Err(ResolutionError::InvalidState(format!(
+                    "CTN {} is node {} in the criteria tree but declares node id {}",
+                    declaration.criterion_type, node_id, declared
+                )));
+            }
+            node_ids.insert(node_id);
+        }
+
+        let local_keys = context
+            .ctn_local_states
+            .iter()
+            .flat_map(|(node_id, states)| {
+                states
+                    .iter()
+                    .map(move |state| (*node_id, "state", state.identifier.as_str()))
+            })
+            .chain(
+                context
+                    .ctn_local_objects
+                    .iter()
+                    .map(|(node_id, object)| (*node_id, "object", object.identifier.as_str())),
+            );
+        for (node_id, kind, identifier) in local_keys {
+            if !node_ids.contains(&node_id) {
+                return Err(ResolutionError::InvalidState(format!(
+                    "Local {} '{}' belongs to CTN node {}, which is not in the criteria tree",
+                    kind, identifier, node_id
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
     // ========== Helper Methods ==========
 
     fn determine_symbol_type(
@@ -685,7 +899,7 @@ impl ResolutionEngine {
         })
     }
 
-    fn resolve_object_fields(
+    pub(crate) fn resolve_object_fields(
         &self,
         object: &ObjectDeclaration,
         resolved_variables: &HashMap<String, ResolvedVariable>,
@@ -1,6 +1,26 @@
+use crate::resolution::references::MissingReference;
 use crate::types::common::DataType;
 use crate::types::error::FieldResolutionError;
 
+/// One dependency cycle found while ordering symbols for resolution
+#[derive(Debug, Clone, PartialEq)]
+pub struct DependencyCycle {
+    /// Symbols labeled by kind (`SET:a`), ending with the first one again
+    pub path: Vec<String>,
+    /// Why each symbol depends on the next, one entry per step where known
+    pub contexts: Vec<String>,
+}
+
+impl std::fmt::Display for DependencyCycle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.path.join(" -> "))?;
+        if !self.contexts.is_empty() {
+            write!(f, " ({})", self.contexts.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug)]
 pub enum ResolutionError {
     FieldResolutionError(FieldResolutionError),
@@ -34,7 +54,7 @@ pub enum ResolutionError {
         symbol: String,
     },
     CircularDependency {
-        cycle: Vec<String>,
+        cycles: Vec<DependencyCycle>,
     },
     RuntimeOperationFailed {
         operation: String,
@@ -59,6 +79,19 @@ pub enum ResolutionError {
         key: String,
         reason: String,
     },
+    InvalidTestSpecification {
+        ctn_type: String,
+        ctn_id: usize,
+        reason: String,
+    },
+    AstVersionMismatch {
+        producer: String,
+        consumer: String,
+        supported: String,
+    },
+    UnresolvedReferences {
+        missing: Vec<MissingReference>,
+    },
 }
 
 impl std::fmt::Display for ResolutionError {
@@ -107,9 +140,18 @@ impl std::fmt::Display for ResolutionError {
                     symbol, expected, found
                 )
             }
-            ResolutionError::CircularDependency { cycle } => {
-                write!(f, "Circular dependency detected: {}", cycle.join(" -> "))
-            }
+            ResolutionError::CircularDependency { cycles } => match cycles.as_slice() {
+                [cycle] => write!(f, "Circular dependency detected: {}", cycle),
+                _ => {
+                    let rendered: Vec<String> = cycles.iter().map(|c| c.to_string()).collect();
+                    write!(
+                        f,
+                        "{} circular dependencies detected: {}",
+                        cycles.len(),
+                        rendered.join("; ")
+                    )
+                }
+            },
             ResolutionError::RuntimeOperationFailed { operation, reason } => {
                 write!(f, "Runtime operation '{}' failed: {}", operation, reason)
             }
@@ -135,6 +177,35 @@ impl std::fmt::Display for ResolutionError {
             ResolutionError::MemoizationError { key, reason } => {
                 write!(f, "Memoization error for '{}': {}", key, reason)
             }
+            ResolutionError::InvalidTestSpecification {
+                ctn_type,
+                ctn_id,
+                reason,
+            } => {
+                write!(
+                    f,
+                    "Invalid TEST specification in CTN '{}' (#{}): {}",
+                    ctn_type, ctn_id, reason
+                )
+            }
+            ResolutionError::AstVersionMismatch {
+                producer,
+                consumer,
+                supported,
+            } => {
+                write!(
+                    f,
+                    "AST version mismatch: document has AST version {}, this scanner reads {} (supported: {})",
+                    producer, consumer, supported
+                )
+            }
+            ResolutionError::UnresolvedReferences { missing } => {
+                write!(f, "{} undefined references:", missing.len())?;
+                for reference in missing {
+                    write!(f, "\n  - {}", reference)?;
+                }
+                Ok(())
+            }
         }
     }
 }
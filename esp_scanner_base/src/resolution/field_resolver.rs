@@ -27,6 +27,7 @@ impl FieldResolver {
                 Value::Float(_) => "float",
                 Value::Boolean(_) => "boolean",
                 Value::Variable(_) => "variable",
+                Value::List(_) => "list",
             },
             "available_variables" => resolved_variables.len()
         );
@@ -114,6 +115,18 @@ impl FieldResolver {
                     })
                 }
             }
+            Value::List(items) => {
+                log_debug!(
+                    "Resolved value list",
+                    "context" => context,
+                    "length" => items.len()
+                );
+                items
+                    .iter()
+                    .map(|item| self.resolve_value(item, context, resolved_variables))
+                    .collect::<Result<Vec<_>, _>>()
+                    .map(ResolvedValue::Collection)
+            }
         };
 
         if result.is_err() {
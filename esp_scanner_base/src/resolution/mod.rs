@@ -1,16 +1,29 @@
+pub mod ast_contract;
+pub mod ast_conversion;
+pub mod cache;
 pub mod dag;
 pub mod engine;
 pub mod error;
 pub mod field_resolver;
+pub mod phases;
+pub mod prepared;
+pub mod references;
 pub mod runtime_operations;
 pub mod set_expansion;
 pub mod set_operations;
+pub mod test_specification;
 
+pub use ast_contract::{check_ast_version, parse_ast_document};
+pub use ast_conversion::resolution_context_from_ast;
+pub use cache::{CachedPolicy, ImportedSource, PolicyCache, PolicyCacheError, PolicyCacheKey};
 pub use dag::*;
 // TODO: ResolutionEngine not yet implemented in this refactor
 // pub use engine::ResolutionEngine;
 pub use error::*;
 pub use field_resolver::*;
+pub use prepared::{HostScanContext, ResolvedPolicy};
+pub use references::{validate_references, MissingReference};
 pub use runtime_operations::*;
 pub use set_expansion::*;
 pub use set_operations::*;
+pub use test_specification::*;
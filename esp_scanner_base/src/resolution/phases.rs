@@ -0,0 +1,126 @@
+//! # Phase Planning
+//!
+//! Assigns scan-time RUN operations, late-bound objects and criteria to the
+//! ordered phases of an [`ExecutionPlan`]. Runs after DAG resolution, so
+//! deferred operations are already in dependency order.
+
+use crate::types::criteria::CriteriaTree;
+use crate::types::execution_plan::ExecutionPlan;
+use crate::types::resolution_context::ResolutionContext;
+use crate::types::CtnNodeId;
+use std::collections::HashMap;
+
+/// Derive the execution plan for a resolved context
+pub fn plan_execution(context: &ResolutionContext) -> ExecutionPlan {
+    let mut extraction_objects: Vec<String> = Vec::new();
+    for operation in &context.scan_time_operations {
+        if let Some(object_id) = operation.operation.extract_object_id() {
+            if !extraction_objects.contains(&object_id) {
+                extraction_objects.push(object_id);
+            }
+        }
+    }
+
+    let computed_variables = context
+        .scan_time_operations
+        .iter()
+        .map(|op| op.target_variable.clone())
+        .collect();
+
+    let substituted_objects = context
+        .global_objects
+        .iter()
+        .filter(|o| context.late_bound_objects.contains_key(&o.identifier))
+        .map(|o| o.identifier.clone())
+        .collect();
+
+    let mut planned = Vec::new();
+    for tree in &context.criteria_root.trees {
+        collect_criteria(tree, &mut planned);
+    }
+
+    // The first criterion referencing an extraction object collects it; later
+    // criteria of the same type reuse that collection
+    let extraction_types: HashMap<&str, &str> = extraction_objects
+        .iter()
+        .filter_map(|object_id| {
+            planned
+                .iter()
+                .find(|c| c.objects.contains(object_id))
+                .map(|c| (object_id.as_str(), c.criterion_type.as_str()))
+        })
+        .collect();
+
+    let criteria_collection = planned
+        .iter()
+        .map(|c| {
+            let objects: Vec<String> = c
+                .objects
+                .iter()
+                .map(|id| {
+                    if extraction_types.get(id.as_str()) == Some(&c.criterion_type.as_str()) {
+                        format!("{} (shared)", id)
+                    } else {
+                        id.clone()
+                    }
+                })
+                .collect();
+            if objects.is_empty() {
+                c.label()
+            } else {
+                format!("{}: {}", c.label(), objects.join(", "))
+            }
+        })
+        .collect();
+    let criteria = planned.iter().map(PlannedCriterion::label).collect();
+
+    ExecutionPlan::new(
+        extraction_objects,
+        computed_variables,
+        substituted_objects,
+        criteria_collection,
+        criteria,
+    )
+}
+
+/// A criterion and the objects it collects
+struct PlannedCriterion {
+    criterion_type: String,
+    node_id: CtnNodeId,
+    objects: Vec<String>,
+}
+
+impl PlannedCriterion {
+    fn label(&self) -> String {
+        format!("{}#{}", self.criterion_type, self.node_id)
+    }
+}
+
+/// Criteria in tree order
+fn collect_criteria(tree: &CriteriaTree, planned: &mut Vec<PlannedCriterion>) {
+    match tree {
+        CriteriaTree::Criterion {
+            declaration,
+            node_id,
+        } => planned.push(PlannedCriterion {
+            criterion_type: declaration.criterion_type.clone(),
+            node_id: *node_id,
+            objects: declaration
+                .object_refs
+                .iter()
+                .map(|r| r.object_id.clone())
+                .chain(
+                    declaration
+                        .local_object
+                        .iter()
+                        .map(|o| o.identifier.clone()),
+                )
+                .collect(),
+        }),
+        CriteriaTree::Block { children, .. } => {
+            for child in children {
+                collect_criteria(child, planned);
+            }
+        }
+    }
+}
@@ -0,0 +1,160 @@
+//! Resolve once, scan many times
+//!
+//! Everything resolution produces is host-independent: symbol tables, the
+//! DAG order, SET expansion and the execution plan. Values that depend on the
+//! host (RUN EXTRACT and anything computed from it) stay deferred in the plan
+//! and are computed by the execution engine during each scan. A
+//! [`ResolvedPolicy`] keeps that result so repeated scans of the same policy,
+//! on a schedule or across a fleet, only pay for a clone.
+
+use crate::execution::clock::ScanClock;
+use crate::resolution::ast_conversion::resolution_context_from_ast;
+use crate::resolution::engine::ResolutionEngine;
+use crate::resolution::error::ResolutionError;
+use crate::types::execution_context::ExecutionContext;
+use crate::types::resolution_context::ResolutionContext;
+use esp_compiler::grammar::ast::nodes::EspFile;
+
+/// Per-scan inputs applied when a prepared policy is finalized
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HostScanContext {
+    /// Fixed clock for deterministic scans; the system clock when unset
+    pub clock: Option<ScanClock>,
+}
+
+impl HostScanContext {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_clock(mut self, clock: ScanClock) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+}
+
+/// A policy resolved up to the point where host data is needed
+#[derive(Debug, Clone)]
+pub struct ResolvedPolicy {
+    prepared: ExecutionContext,
+}
+
+impl ResolvedPolicy {
+    /// Run the host-independent resolution pipeline once
+    pub fn prepare(context: &mut ResolutionContext) -> Result<Self, ResolutionError> {
+        let prepared = ResolutionEngine::new().resolve_context(context)?;
+        Ok(Self { prepared })
+    }
+
+    /// [`prepare`](Self::prepare) straight from a compiled or loaded AST
+    pub fn from_ast(ast: &EspFile) -> Result<Self, ResolutionError> {
+        Self::prepare(&mut resolution_context_from_ast(ast))
+    }
+
+    /// Execution context for one scan
+    ///
+    /// The prepared context is cloned, never modified, so the same policy can
+    /// be finalized for any number of scans.
+    pub fn finalize_for_host(&self, host: &HostScanContext) -> ExecutionContext {
+        let context = self.prepared.clone();
+        match host.clock {
+            Some(clock) => context.with_clock(clock),
+            None => context.with_clock(ScanClock::system()),
+        }
+    }
+
+    /// The prepared context, before any per-scan inputs are applied
+    pub fn context(&self) -> &ExecutionContext {
+        &self.prepared
+    }
+
+    pub fn criteria_count(&self) -> usize {
+        self.prepared.count_criteria()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POLICY: &str = "META
+    version `1.0.0`
+    esp_version `1.0`
+    author `test`
+    date `2026-01-01`
+    severity `low`
+    platform `linux`
+    description `prepared resolution`
+    control_framework `TEST`
+    control `RES-1`
+    esp_scan_id `prepared`
+    criticality `low`
+    tags `test`
+META_END
+
+DEF
+    VAR base string `/etc`
+    VAR expected_mode string `0644`
+
+    RUN passwd_path CONCAT
+        VAR base
+        literal `/passwd`
+    RUN_END
+
+    OBJECT passwd
+        path VAR passwd_path
+    OBJECT_END
+
+    STATE readable
+        permissions string = VAR expected_mode
+    STATE_END
+
+    CRI AND
+        CTN file_metadata
+            TEST all all
+            STATE_REF readable
+            OBJECT_REF passwd
+        CTN_END
+    CRI_END
+DEF_END
+";
+
+    fn compile() -> EspFile {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("policy.esp");
+        std::fs::write(&path, POLICY).unwrap();
+        esp_compiler::pipeline::process_file(&path.display().to_string())
+            .unwrap()
+            .ast
+    }
+
+    #[test]
+    fn test_finalize_matches_single_pass_and_leaves_prepared_untouched() {
+        let ast = compile();
+        let policy = ResolvedPolicy::from_ast(&ast).unwrap();
+        let single_pass = ResolutionEngine::new()
+            .resolve_context(&mut resolution_context_from_ast(&ast))
+            .unwrap();
+
+        let clock = ScanClock::parse_fixed("2026-03-01T12:00:00Z").unwrap();
+        let first = policy.finalize_for_host(&HostScanContext::new().with_clock(clock));
+        let second = policy.finalize_for_host(&HostScanContext::new());
+
+        for finalized in [&first, &second] {
+            assert_eq!(
+                serde_json::to_value(&finalized.criteria_tree).unwrap(),
+                serde_json::to_value(&single_pass.criteria_tree).unwrap()
+            );
+            assert_eq!(finalized.global_variables, single_pass.global_variables);
+            assert_eq!(finalized.global_states, single_pass.global_states);
+            assert_eq!(finalized.plan, single_pass.plan);
+        }
+        assert_eq!(
+            first.global_variables["passwd_path"].value,
+            crate::types::common::ResolvedValue::String("/etc/passwd".to_string())
+        );
+        assert_eq!(first.clock.now(), clock.now());
+        assert!(!second.clock.is_fixed());
+        assert!(!policy.context().clock.is_fixed());
+    }
+}
@@ -0,0 +1,525 @@
+//! Reference validation across CTNs, SETs and objects
+//!
+//! The compiler checks STATE_REF / OBJECT_REF / SET_REF targets and VAR
+//! references, but a resolution context can also be built from a serialized
+//! AST that never went through that pass. This check runs before DAG
+//! resolution so every dangling reference is reported together, instead of
+//! resolution stopping at the first one or a criterion failing later at
+//! execution. Each one lists the closest declared names of the same kind.
+
+use crate::resolution::ResolutionError;
+use crate::types::criteria::CriteriaTree;
+use crate::types::object::ObjectDeclaration;
+use crate::types::resolution_context::{RelationshipType, ResolutionContext};
+use crate::types::set::SetOperand;
+use esp_compiler::grammar::ast::nodes::{FilterSpec, ObjectElement};
+use std::collections::HashSet;
+use std::fmt;
+
+/// Most suggestions listed for one missing identifier
+const MAX_SUGGESTIONS: usize = 3;
+
+/// A reference whose target is not declared
+#[derive(Debug, Clone, PartialEq)]
+pub struct MissingReference {
+    /// Where the reference appears, e.g. `CTN file_content (#3)` or `SET all_logs`
+    pub source: String,
+    pub kind: RelationshipType,
+    /// The identifier that could not be found
+    pub identifier: String,
+    /// Declared identifiers of the same kind, closest first
+    pub suggestions: Vec<String>,
+}
+
+impl fmt::Display for MissingReference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let keyword = match self.kind {
+            RelationshipType::StateReference => "STATE_REF",
+            RelationshipType::ObjectReference => "OBJECT_REF",
+            RelationshipType::SetReference => "SET_REF",
+            RelationshipType::VariableReference => "VAR",
+        };
+        write!(
+            f,
+            "{}: {} '{}' is not declared",
+            self.source, keyword, self.identifier
+        )?;
+        if !self.suggestions.is_empty() {
+            write!(f, " (did you mean {}?)", self.suggestions.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Fail with every STATE_REF, OBJECT_REF, SET_REF and VAR reference whose target is missing
+pub fn validate_references(context: &ResolutionContext) -> Result<(), ResolutionError> {
+    let missing = find_missing_references(context);
+    if missing.is_empty() {
+        Ok(())
+    } else {
+        Err(ResolutionError::UnresolvedReferences { missing })
+    }
+}
+
+/// Every dangling reference, in declaration order (criteria, SETs, objects,
+/// states, then VARs and RUN operations)
+pub fn find_missing_references(context: &ResolutionContext) -> Vec<MissingReference> {
+    let mut checker = ReferenceChecker {
+        states: global_ids(context.states.iter().map(|s| (&s.identifier, s.is_global))),
+        objects: global_ids(context.objects.iter().map(|o| (&o.identifier, o.is_global))),
+        sets: context.sets.iter().map(|s| s.set_id.as_str()).collect(),
+        variables: context
+            .variables
+            .iter()
+            .map(|v| v.name.as_str())
+            .chain(
+                context
+                    .runtime_operations
+                    .iter()
+                    .map(|op| op.target_variable.as_str()),
+            )
+            .collect(),
+        missing: Vec::new(),
+    };
+
+    for tree in &context.criteria_root.trees {
+        checker.check_tree(tree);
+    }
+
+    for set in &context.sets {
+        let source = format!("SET {}", set.set_id);
+        for operand in &set.operands {
+            match operand {
+                SetOperand::ObjectRef(id) => {
+                    checker.check(&source, RelationshipType::ObjectReference, id)
+                }
+                SetOperand::SetRef(id) => {
+                    checker.check(&source, RelationshipType::SetReference, id)
+                }
+                SetOperand::FilteredObjectRef { object_id, filter } => {
+                    checker.check(&source, RelationshipType::ObjectReference, object_id);
+                    checker.check_filter(&source, filter);
+                }
+                SetOperand::InlineObject(object) => {
+                    checker.check_elements(&source, &object.elements);
+                    let object = ObjectDeclaration::from_ast_node(object);
+                    checker.check_variables(&source, object.get_variable_references());
+                }
+            }
+        }
+        if let Some(filter) = &set.filter {
+            checker.check_filter(&source, filter);
+        }
+    }
+
+    for object in context.objects.iter().filter(|o| o.is_global) {
+        checker.check_object(object);
+    }
+
+    for state in context.states.iter().filter(|s| s.is_global) {
+        let source = format!("STATE {}", state.identifier);
+        checker.check_variables(&source, state.get_variable_references());
+    }
+
+    for variable in &context.variables {
+        if let Some(var_ref) = variable.get_variable_reference() {
+            let source = format!("VAR {}", variable.name);
+            checker.check(&source, RelationshipType::VariableReference, var_ref);
+        }
+    }
+
+    for operation in &context.runtime_operations {
+        let source = format!("RUN {}", operation.target_variable);
+        checker.check_variables(&source, operation.get_variable_references());
+        if let Some(object_id) = operation.extract_object_id() {
+            checker.check(&source, RelationshipType::ObjectReference, &object_id);
+        }
+    }
+
+    checker.missing
+}
+
+fn global_ids<'a>(symbols: impl Iterator<Item = (&'a String, bool)>) -> HashSet<&'a str> {
+    symbols
+        .filter(|(_, is_global)| *is_global)
+        .map(|(id, _)| id.as_str())
+        .collect()
+}
+
+struct ReferenceChecker<'a> {
+    states: HashSet<&'a str>,
+    objects: HashSet<&'a str>,
+    sets: HashSet<&'a str>,
+    variables: HashSet<&'a str>,
+    missing: Vec<MissingReference>,
+}
+
+impl ReferenceChecker<'_> {
+    fn check_tree(&mut self, tree: &CriteriaTree) {
+        match tree {
+            CriteriaTree::Criterion {
+                declaration,
+                node_id,
+            } => {
+                let source = format!("CTN {} (#{})", declaration.criterion_type, node_id);
+                for state_ref in &declaration.state_refs {
+                    self.check(
+                        &source,
+                        RelationshipType::StateReference,
+                        &state_ref.state_id,
+                    );
+                }
+                for object_ref in &declaration.object_refs {
+                    self.check(
+                        &source,
+                        RelationshipType::ObjectReference,
+                        &object_ref.object_id,
+                    );
+                }
+                if let Some(object) = &declaration.local_object {
+                    self.check_elements(&source, &object.elements);
+                }
+                self.check_variables(&source, declaration.get_variable_references());
+            }
+            CriteriaTree::Block { children, .. } => {
+                for child in children {
+                    self.check_tree(child);
+                }
+            }
+        }
+    }
+
+    fn check_object(&mut self, object: &ObjectDeclaration) {
+        let source = format!("OBJECT {}", object.identifier);
+        self.check_elements(&source, &object.elements);
+        self.check_variables(&source, object.get_variable_references());
+    }
+
+    fn check_variables(&mut self, source: &str, var_refs: Vec<String>) {
+        for var_ref in var_refs {
+            self.check(source, RelationshipType::VariableReference, &var_ref);
+        }
+    }
+
+    fn check_elements(&mut self, source: &str, elements: &[ObjectElement]) {
+        for element in elements {
+            match element {
+                ObjectElement::SetRef { set_id, .. } => {
+                    self.check(source, RelationshipType::SetReference, set_id)
+                }
+                ObjectElement::Filter(filter) => self.check_filter(source, filter),
+                _ => {}
+            }
+        }
+    }
+
+    fn check_filter(&mut self, source: &str, filter: &FilterSpec) {
+        for state_ref in &filter.state_refs {
+            self.check(
+                source,
+                RelationshipType::StateReference,
+                &state_ref.state_id,
+            );
+        }
+    }
+
+    fn check(&mut self, source: &str, kind: RelationshipType, identifier: &str) {
+        let declared = match kind {
+            RelationshipType::StateReference => &self.states,
+            RelationshipType::ObjectReference => &self.objects,
+            RelationshipType::SetReference => &self.sets,
+            RelationshipType::VariableReference => &self.variables,
+        };
+        if declared.contains(identifier) {
+            return;
+        }
+        let suggestions = nearest_names(identifier, declared.iter().copied());
+        self.missing.push(MissingReference {
+            source: source.to_string(),
+            kind,
+            identifier: identifier.to_string(),
+            suggestions,
+        });
+    }
+}
+
+/// Declared names within a small edit distance of `name`, closest first
+///
+/// A candidate must keep at least one character of `name`, so very short
+/// names are not matched with anything of the same length.
+pub fn nearest_names<'a>(name: &str, candidates: impl Iterator<Item = &'a str>) -> Vec<String> {
+    let length = name.chars().count();
+    let threshold = (length / 3).max(2).min(length.saturating_sub(1));
+    let mut scored: Vec<(usize, &str)> = candidates
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= threshold)
+        .collect();
+    scored.sort();
+    scored
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, candidate)| candidate.to_string())
+        .collect()
+}
+
+/// Levenshtein distance over characters
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resolution::engine::ResolutionEngine;
+    use crate::types::common::{DataType, Operation};
+    use crate::types::criteria::CriteriaRoot;
+    use crate::types::criterion::CriterionDeclaration;
+    use crate::types::runtime_operation::RuntimeOperation;
+    use crate::types::state::{StateDeclaration, StateField};
+    use crate::types::variable::VariableDeclaration;
+    use crate::types::{ExistenceCheck, ItemCheck, TestSpecification};
+    use esp_compiler::grammar::ast::nodes::{
+        ObjectRef, RunParameter, RuntimeOperationType, StateRef, Value,
+    };
+
+    fn state(id: &str) -> StateDeclaration {
+        StateDeclaration {
+            identifier: id.to_string(),
+            fields: vec![],
+            record_checks: vec![],
+            is_global: true,
+        }
+    }
+
+    fn criterion(state_ids: &[&str], object_id: &str) -> CriterionDeclaration {
+        CriterionDeclaration {
+            criterion_type: "file_metadata".to_string(),
+            test: TestSpecification {
+                existence_check: ExistenceCheck::All,
+                item_check: ItemCheck::All,
+                state_operator: None,
+                entity_check: None,
+                span: None,
+            },
+            state_refs: state_ids
+                .iter()
+                .map(|id| StateRef {
+                    state_id: id.to_string(),
+                    span: None,
+                })
+                .collect(),
+            object_refs: vec![ObjectRef {
+                object_id: object_id.to_string(),
+                span: None,
+            }],
+            local_states: vec![],
+            local_object: None,
+            controls: vec![],
+            severity: None,
+            ctn_node_id: None,
+        }
+    }
+
+    #[test]
+    fn test_edit_distance_and_suggestions() {
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("owner_ok", "owner_ok"), 0);
+        assert_eq!(edit_distance("ownr_ok", "owner_ok"), 1);
+        assert_eq!(
+            nearest_names("ownr_ok", ["mode_ok", "owner_ok", "unrelated"].into_iter()),
+            ["owner_ok"]
+        );
+        assert!(nearest_names("x", ["owner_ok"].into_iter()).is_empty());
+    }
+
+    #[test]
+    fn test_suggestion_threshold_skips_unrelated_names() {
+        // Two edits are allowed for short names, more for longer ones
+        assert_eq!(
+            nearest_names("tls_mn", ["tls_min"].into_iter()),
+            ["tls_min"]
+        );
+        assert!(nearest_names("tls_mn", ["tls_max_age"].into_iter()).is_empty());
+        assert_eq!(
+            nearest_names(
+                "ssh_conifg_state",
+                ["ssh_config_state", "sshd_config"].into_iter()
+            ),
+            ["ssh_config_state"]
+        );
+        // Nothing in common is never a suggestion
+        assert!(nearest_names("ab", ["xy", "cd"].into_iter()).is_empty());
+        assert_eq!(nearest_names("ab", ["abc", "xy"].into_iter()), ["abc"]);
+        // At most three, closest first and ties by name
+        assert_eq!(
+            nearest_names(
+                "mode_ok",
+                ["mode_ok2", "mode_okk", "node_ok", "mod_ok", "mode"].into_iter()
+            ),
+            ["mod_ok", "mode_ok2", "mode_okk"]
+        );
+    }
+
+    #[test]
+    fn test_suggestions_only_come_from_the_referenced_kind() {
+        let mut context = ResolutionContext::new(
+            vec![VariableDeclaration::new(
+                "passwd_path".to_string(),
+                DataType::String,
+                None,
+            )],
+            vec![state("mode_ok")],
+            vec![ObjectDeclaration {
+                identifier: "passwd_file".to_string(),
+                elements: vec![],
+                is_global: true,
+            }],
+            vec![],
+            vec![],
+            vec![],
+        );
+        context.criteria_root = CriteriaRoot {
+            trees: vec![CriteriaTree::Criterion {
+                declaration: criterion(&["passwd_fil"], "passwd_fil"),
+                node_id: 1,
+            }],
+            ..CriteriaRoot::default()
+        };
+
+        let missing = find_missing_references(&context);
+        assert_eq!(missing.len(), 2);
+        assert_eq!(missing[0].kind, RelationshipType::StateReference);
+        assert!(missing[0].suggestions.is_empty());
+        assert_eq!(missing[1].kind, RelationshipType::ObjectReference);
+        assert_eq!(missing[1].suggestions, ["passwd_file"]);
+    }
+
+    #[test]
+    fn test_typo_variable_references_suggest_variables_and_run_targets() {
+        let mut tls_ok = state("tls_ok");
+        tls_ok.fields.push(StateField {
+            name: "min_version".to_string(),
+            data_type: DataType::String,
+            operation: Operation::Equals,
+            value: Value::Variable("tls_mn".to_string()),
+            entity_check: None,
+        });
+        let mut context = ResolutionContext::new(
+            vec![
+                VariableDeclaration::new(
+                    "tls_min".to_string(),
+                    DataType::String,
+                    Some(Value::String("1.2".to_string())),
+                ),
+                VariableDeclaration::new("port_count".to_string(), DataType::Int, None),
+                VariableDeclaration::new(
+                    "tls_copy".to_string(),
+                    DataType::String,
+                    Some(Value::Variable("tls_mni".to_string())),
+                ),
+            ],
+            vec![tls_ok],
+            vec![],
+            vec![RuntimeOperation::new(
+                "port_total".to_string(),
+                RuntimeOperationType::Count,
+                vec![RunParameter::Variable("port_cont".to_string())],
+            )],
+            vec![],
+            vec![],
+        );
+        context.states[0].fields.push(StateField {
+            name: "ports".to_string(),
+            data_type: DataType::Int,
+            operation: Operation::Equals,
+            value: Value::Variable("port_totl".to_string()),
+            entity_check: None,
+        });
+
+        let error = validate_references(&context).unwrap_err();
+        let ResolutionError::UnresolvedReferences { missing } = &error else {
+            panic!("expected aggregated reference errors, got {}", error);
+        };
+        let found: Vec<(&str, &str, Vec<String>)> = missing
+            .iter()
+            .map(|m| {
+                (
+                    m.source.as_str(),
+                    m.identifier.as_str(),
+                    m.suggestions.clone(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("STATE tls_ok", "port_totl", vec!["port_total".to_string()]),
+                ("STATE tls_ok", "tls_mn", vec!["tls_min".to_string()]),
+                ("VAR tls_copy", "tls_mni", vec!["tls_min".to_string()]),
+                (
+                    "RUN port_total",
+                    "port_cont",
+                    vec!["port_count".to_string()]
+                ),
+            ]
+        );
+        assert!(error
+            .to_string()
+            .contains("STATE tls_ok: VAR 'tls_mn' is not declared (did you mean tls_min?)"));
+    }
+
+    #[test]
+    fn test_typo_state_ref_fails_resolution_with_every_missing_reference() {
+        let mut context = ResolutionContext::new(
+            vec![],
+            vec![state("owner_ok"), state("mode_ok")],
+            vec![],
+            vec![],
+            vec![],
+            vec![],
+        );
+        context.criteria_root = CriteriaRoot {
+            trees: vec![
+                CriteriaTree::Criterion {
+                    declaration: criterion(&["ownr_ok"], "passwd"),
+                    node_id: 1,
+                },
+                CriteriaTree::Criterion {
+                    declaration: criterion(&["mode_ok", "mod_ok"], "passwd"),
+                    node_id: 2,
+                },
+            ],
+            ..CriteriaRoot::default()
+        };
+
+        let error = ResolutionEngine::new()
+            .resolve_context(&mut context)
+            .unwrap_err();
+        let ResolutionError::UnresolvedReferences { missing } = &error else {
+            panic!("expected aggregated reference errors, got {}", error);
+        };
+        assert_eq!(missing.len(), 4);
+        assert_eq!(missing[0].source, "CTN file_metadata (#1)");
+        assert_eq!(missing[0].identifier, "ownr_ok");
+        assert_eq!(missing[0].suggestions, ["owner_ok"]);
+        assert_eq!(missing[1].kind, RelationshipType::ObjectReference);
+        assert!(missing[1].suggestions.is_empty());
+
+        let message = error.to_string();
+        assert!(message.contains("4 undefined references"));
+        assert!(message.contains(
+            "CTN file_metadata (#2): STATE_REF 'mod_ok' is not declared (did you mean mode_ok"
+        ));
+        assert!(message.contains("CTN file_metadata (#2): OBJECT_REF 'passwd' is not declared"));
+    }
+}
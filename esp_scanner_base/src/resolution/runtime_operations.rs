@@ -27,6 +27,66 @@ pub fn execute_runtime_operation(
         RuntimeOperationType::Unique => execute_unique(operation, resolved_variables),
         RuntimeOperationType::Merge => execute_merge(operation, resolved_variables),
         RuntimeOperationType::End => execute_end(operation, resolved_variables),
+        RuntimeOperationType::Replace => execute_replace(operation, resolved_variables),
+        RuntimeOperationType::Length => execute_length(operation, resolved_variables),
+        RuntimeOperationType::Uppercase => execute_string_transform(
+            operation,
+            resolved_variables,
+            "UPPERCASE",
+            str::to_uppercase,
+        ),
+        RuntimeOperationType::Lowercase => execute_string_transform(
+            operation,
+            resolved_variables,
+            "LOWERCASE",
+            str::to_lowercase,
+        ),
+        RuntimeOperationType::Trim => {
+            execute_string_transform(operation, resolved_variables, "TRIM", |value| {
+                value.trim().to_string()
+            })
+        }
+    }
+}
+
+/// ARITHMETIC value. Integers stay integers until a float operand or an
+/// inexact division promotes the running value to float.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Number {
+    Integer(i64),
+    Float(f64),
+}
+
+impl Number {
+    fn parse(s: &str) -> Option<Self> {
+        s.parse::<i64>()
+            .map(Self::Integer)
+            .ok()
+            .or_else(|| s.parse::<f64>().ok().map(Self::Float))
+    }
+
+    fn as_f64(self) -> f64 {
+        match self {
+            Self::Integer(i) => i as f64,
+            Self::Float(f) => f,
+        }
+    }
+
+    /// A whole-number float is an integer again only when every input was
+    /// an integer, so a float variable never resolves to an integer.
+    fn into_resolved(self, integer_inputs: bool) -> ResolvedValue {
+        match self {
+            Self::Integer(i) => ResolvedValue::Integer(i),
+            Self::Float(f)
+                if integer_inputs
+                    && f.fract() == 0.0
+                    && f >= i64::MIN as f64
+                    && f < i64::MAX as f64 =>
+            {
+                ResolvedValue::Integer(f as i64)
+            }
+            Self::Float(f) => ResolvedValue::Float(f),
+        }
     }
 }
 
@@ -34,36 +94,50 @@ fn execute_arithmetic(
     operation: &RuntimeOperation,
     resolved_variables: &HashMap<String, ResolvedVariable>,
 ) -> Result<ResolvedValue, ResolutionError> {
-    let mut current_value: f64 = 0.0;
-    let mut has_initial_value = false;
+    let mut current_value: Option<Number> = None;
+    let mut integer_inputs = true;
 
     for (param_index, parameter) in operation.parameters.iter().enumerate() {
         match parameter {
             RunParameter::Literal(_) | RunParameter::Variable(_) => {
-                if !has_initial_value {
-                    let numeric_value = get_numeric_value_from_parameter(
+                if current_value.is_none() {
+                    let start = get_numeric_value_from_parameter(
                         parameter,
                         resolved_variables,
                         &operation.target_variable,
                         param_index,
                     )?;
-                    current_value = numeric_value;
-                    has_initial_value = true;
+                    integer_inputs = matches!(start, Number::Integer(_));
+                    current_value = Some(start);
                 }
             }
             RunParameter::ArithmeticOp(operator, operand) => {
-                let operand_value = extract_operand_value(
-                    operand,
-                    resolved_variables,
-                    &operation.target_variable,
-                    param_index,
-                )?;
-                current_value = apply_arithmetic_operation(
+                let current =
+                    current_value.ok_or_else(|| ResolutionError::RuntimeOperationFailed {
+                        operation: operation.target_variable.clone(),
+                        reason: format!(
+                            "ARITHMETIC operator '{}' at parameter {} has no value to apply to",
+                            operator, param_index
+                        ),
+                    })?;
+                let operand_value = operand
+                    .as_ref()
+                    .map(|operand| {
+                        extract_operand_value(
+                            operand,
+                            resolved_variables,
+                            &operation.target_variable,
+                            param_index,
+                        )
+                    })
+                    .transpose()?;
+                integer_inputs &= !matches!(operand_value, Some(Number::Float(_)));
+                current_value = Some(apply_arithmetic_operation(
                     *operator,
-                    current_value,
+                    current,
                     operand_value,
                     &operation.target_variable,
-                )?;
+                )?);
             }
             _ => {
                 return Err(ResolutionError::RuntimeOperationFailed {
@@ -77,52 +151,95 @@ fn execute_arithmetic(
         }
     }
 
-    if !has_initial_value {
-        return Err(ResolutionError::RuntimeOperationFailed {
+    current_value
+        .map(|value| value.into_resolved(integer_inputs))
+        .ok_or_else(|| ResolutionError::RuntimeOperationFailed {
             operation: operation.target_variable.clone(),
             reason: "ARITHMETIC operation requires at least one numeric value".to_string(),
-        });
-    }
-
-    let result = if current_value.fract() == 0.0 && current_value.is_finite() {
-        ResolvedValue::Integer(current_value as i64)
-    } else {
-        ResolvedValue::Float(current_value)
-    };
-
-    Ok(result)
+        })
 }
 
+/// Apply one operator to the running value.
+///
+/// `int op int` stays an integer (overflow is an error, not a wrap), except
+/// that a division with a remainder yields a float. Any float operand makes
+/// the result a float. `%` truncates like Rust's, so the result takes the
+/// sign of the dividend (`-7 % 2` is `-1`). Dividing by zero is an error.
 fn apply_arithmetic_operation(
     operator: crate::types::runtime_operation::ArithmeticOperator,
-    current: f64,
-    operand: f64,
+    current: Number,
+    operand: Option<Number>,
     operation_name: &str,
-) -> Result<f64, ResolutionError> {
+) -> Result<Number, ResolutionError> {
     use crate::types::runtime_operation::ArithmeticOperator;
 
-    match operator {
-        ArithmeticOperator::Add => Ok(current + operand),
-        ArithmeticOperator::Subtract => Ok(current - operand),
-        ArithmeticOperator::Multiply => Ok(current * operand),
-        ArithmeticOperator::Divide => {
-            if operand == 0.0 {
-                Err(ResolutionError::RuntimeOperationFailed {
-                    operation: operation_name.to_string(),
-                    reason: "Division by zero".to_string(),
-                })
-            } else {
-                Ok(current / operand)
+    let fail = |reason: String| ResolutionError::RuntimeOperationFailed {
+        operation: operation_name.to_string(),
+        reason,
+    };
+    let overflow = || fail(format!("Integer overflow applying '{}'", operator));
+    let no_operand = || {
+        fail(format!(
+            "Arithmetic operator '{}' does not take an operand",
+            operator
+        ))
+    };
+
+    let Some(operand) = operand else {
+        return match (operator, current) {
+            (ArithmeticOperator::Abs, Number::Integer(a)) => {
+                a.checked_abs().map(Number::Integer).ok_or_else(overflow)
             }
+            (ArithmeticOperator::Abs, Number::Float(a)) => Ok(Number::Float(a.abs())),
+            _ => Err(fail(format!(
+                "Arithmetic operator '{}' requires an operand",
+                operator
+            ))),
+        };
+    };
+
+    if operand.as_f64() == 0.0 {
+        match operator {
+            ArithmeticOperator::Divide => return Err(fail("Division by zero".to_string())),
+            ArithmeticOperator::Modulus => return Err(fail("Modulus by zero".to_string())),
+            _ => {}
         }
-        ArithmeticOperator::Modulus => {
-            if operand == 0.0 {
-                Err(ResolutionError::RuntimeOperationFailed {
-                    operation: operation_name.to_string(),
-                    reason: "Modulus by zero".to_string(),
-                })
+    }
+
+    match (current, operand) {
+        (Number::Integer(a), Number::Integer(b)) => {
+            let result = match operator {
+                ArithmeticOperator::Add => a.checked_add(b),
+                ArithmeticOperator::Subtract => a.checked_sub(b),
+                ArithmeticOperator::Multiply => a.checked_mul(b),
+                ArithmeticOperator::Divide => match a.checked_rem(b) {
+                    Some(0) => a.checked_div(b),
+                    Some(_) => return Ok(Number::Float(a as f64 / b as f64)),
+                    None => None,
+                },
+                ArithmeticOperator::Modulus => a.checked_rem(b),
+                ArithmeticOperator::Min => Some(a.min(b)),
+                ArithmeticOperator::Max => Some(a.max(b)),
+                ArithmeticOperator::Abs => return Err(no_operand()),
+            };
+            result.map(Number::Integer).ok_or_else(overflow)
+        }
+        (current, operand) => {
+            let (a, b) = (current.as_f64(), operand.as_f64());
+            let result = match operator {
+                ArithmeticOperator::Add => a + b,
+                ArithmeticOperator::Subtract => a - b,
+                ArithmeticOperator::Multiply => a * b,
+                ArithmeticOperator::Divide => a / b,
+                ArithmeticOperator::Modulus => a % b,
+                ArithmeticOperator::Min => a.min(b),
+                ArithmeticOperator::Max => a.max(b),
+                ArithmeticOperator::Abs => return Err(no_operand()),
+            };
+            if result.is_finite() {
+                Ok(Number::Float(result))
             } else {
-                Ok(current % operand)
+                Err(fail(format!("'{}' produced a non-finite result", operator)))
             }
         }
     }
@@ -133,16 +250,16 @@ fn extract_operand_value(
     resolved_variables: &HashMap<String, ResolvedVariable>,
     operation_name: &str,
     param_index: usize,
-) -> Result<f64, ResolutionError> {
+) -> Result<Number, ResolutionError> {
     match operand {
-        Value::Integer(i) => Ok(*i as f64),
-        Value::Float(f) => Ok(*f),
-        Value::String(s) => s
-            .parse::<f64>()
-            .map_err(|_| ResolutionError::RuntimeOperationFailed {
+        Value::Integer(i) => Ok(Number::Integer(*i)),
+        Value::Float(f) => Ok(Number::Float(*f)),
+        Value::String(s) => {
+            Number::parse(s).ok_or_else(|| ResolutionError::RuntimeOperationFailed {
                 operation: operation_name.to_string(),
                 reason: format!("Cannot parse '{}' as number", s),
-            }),
+            })
+        }
         Value::Variable(var_name) => {
             if let Some(resolved_var) = resolved_variables.get(var_name) {
                 get_numeric_value_from_resolved(
@@ -174,66 +291,13 @@ fn execute_concat(
 
     for (param_index, parameter) in operation.parameters.iter().enumerate() {
         let string_value = match parameter {
-            RunParameter::Literal(value) => {
-                // Convert literal value to string based on type
-                match value {
-                    crate::types::common::Value::String(s) => s.clone(),
-                    crate::types::common::Value::Integer(i) => i.to_string(),
-                    crate::types::common::Value::Float(f) => f.to_string(),
-                    crate::types::common::Value::Boolean(b) => b.to_string(),
-                    crate::types::common::Value::Variable(var_name) => {
-                        // Resolve variable reference
-                        if let Some(resolved_var) = resolved_variables.get(var_name) {
-                            match &resolved_var.value {
-                                ResolvedValue::String(s) => s.clone(),
-                                ResolvedValue::Integer(i) => i.to_string(),
-                                ResolvedValue::Float(f) => f.to_string(),
-                                ResolvedValue::Boolean(b) => b.to_string(),
-                                ResolvedValue::Version(v) => v.clone(),
-                                ResolvedValue::EvrString(e) => e.clone(),
-                                _ => {
-                                    return Err(ResolutionError::RuntimeOperationFailed {
-                                        operation: operation.target_variable.clone(),
-                                        reason: format!("Cannot convert variable '{}' type to string for concatenation", var_name),
-                                    });
-                                }
-                            }
-                        } else {
-                            return Err(ResolutionError::UndefinedVariable {
-                                name: var_name.clone(),
-                                context: format!("CONCAT operation parameter {}", param_index),
-                            });
-                        }
-                    }
-                }
-            }
-            RunParameter::Variable(var_name) => {
-                // Direct variable reference
-                if let Some(resolved_var) = resolved_variables.get(var_name) {
-                    match &resolved_var.value {
-                        ResolvedValue::String(s) => s.clone(),
-                        ResolvedValue::Integer(i) => i.to_string(),
-                        ResolvedValue::Float(f) => f.to_string(),
-                        ResolvedValue::Boolean(b) => b.to_string(),
-                        ResolvedValue::Version(v) => v.clone(),
-                        ResolvedValue::EvrString(e) => e.clone(),
-                        _ => {
-                            return Err(ResolutionError::RuntimeOperationFailed {
-                                operation: operation.target_variable.clone(),
-                                reason: format!(
-                                    "Cannot convert variable '{}' type to string for concatenation",
-                                    var_name
-                                ),
-                            });
-                        }
-                    }
-                } else {
-                    return Err(ResolutionError::UndefinedVariable {
-                        name: var_name.clone(),
-                        context: format!("CONCAT operation parameter {}", param_index),
-                    });
-                }
-            }
+            RunParameter::Literal(_) | RunParameter::Variable(_) => parameter_as_string(
+                parameter,
+                resolved_variables,
+                operation,
+                "CONCAT",
+                param_index,
+            )?,
             _ => {
                 return Err(ResolutionError::RuntimeOperationFailed {
                     operation: operation.target_variable.clone(),
@@ -251,26 +315,145 @@ fn execute_concat(
     Ok(ResolvedValue::String(result))
 }
 
+/// Rewrite every match of the pattern in the input; `$1` / `${name}` in the
+/// replacement expand to capture groups
+fn execute_replace(
+    operation: &RuntimeOperation,
+    resolved_variables: &HashMap<String, ResolvedVariable>,
+) -> Result<ResolvedValue, ResolutionError> {
+    let mut input: Option<String> = None;
+    let mut pattern: Option<&str> = None;
+    let mut replacement: Option<String> = None;
+
+    for (param_index, parameter) in operation.parameters.iter().enumerate() {
+        match parameter {
+            RunParameter::Pattern(pat) => pattern = Some(pat),
+            RunParameter::Literal(_) | RunParameter::Variable(_) => {
+                let value = parameter_as_string(
+                    parameter,
+                    resolved_variables,
+                    operation,
+                    "REPLACE",
+                    param_index,
+                )?;
+                // Input comes before the pattern, the replacement after it
+                if pattern.is_none() {
+                    input = Some(value);
+                } else {
+                    replacement = Some(value);
+                }
+            }
+            _ => {
+                return Err(ResolutionError::RuntimeOperationFailed {
+                    operation: operation.target_variable.clone(),
+                    reason: format!(
+                        "REPLACE operation does not support parameter type: {:?}",
+                        parameter.parameter_type_name()
+                    ),
+                });
+            }
+        }
+    }
+
+    let missing = |what: &str| ResolutionError::RuntimeOperationFailed {
+        operation: operation.target_variable.clone(),
+        reason: format!("REPLACE operation requires {}", what),
+    };
+    let input = input.ok_or_else(|| missing("an input before the pattern"))?;
+    let pattern = pattern.ok_or_else(|| missing("a pattern parameter"))?;
+    let replacement = replacement.ok_or_else(|| missing("a replacement after the pattern"))?;
+
+    let regex = Regex::new(pattern).map_err(|e| ResolutionError::RuntimeOperationFailed {
+        operation: operation.target_variable.clone(),
+        reason: format!("Invalid regex pattern '{}': {}", pattern, e),
+    })?;
+
+    Ok(ResolvedValue::String(
+        regex.replace_all(&input, replacement.as_str()).into_owned(),
+    ))
+}
+
+/// String form of a literal or VAR parameter for string-building operations
+fn parameter_as_string(
+    parameter: &RunParameter,
+    resolved_variables: &HashMap<String, ResolvedVariable>,
+    operation: &RuntimeOperation,
+    operation_name: &str,
+    param_index: usize,
+) -> Result<String, ResolutionError> {
+    let var_name = match parameter {
+        RunParameter::Literal(Value::Variable(var_name)) | RunParameter::Variable(var_name) => {
+            var_name
+        }
+        RunParameter::Literal(Value::String(s)) => return Ok(s.clone()),
+        RunParameter::Literal(Value::Integer(i)) => return Ok(i.to_string()),
+        RunParameter::Literal(Value::Float(f)) => return Ok(f.to_string()),
+        RunParameter::Literal(Value::Boolean(b)) => return Ok(b.to_string()),
+        RunParameter::Literal(Value::List(_)) => {
+            return Err(ResolutionError::RuntimeOperationFailed {
+                operation: operation.target_variable.clone(),
+                reason: format!(
+                    "Value list cannot be used as {} parameter {}",
+                    operation_name, param_index
+                ),
+            });
+        }
+        _ => {
+            return Err(ResolutionError::RuntimeOperationFailed {
+                operation: operation.target_variable.clone(),
+                reason: format!(
+                    "{} operation does not support parameter type: {:?}",
+                    operation_name,
+                    parameter.parameter_type_name()
+                ),
+            });
+        }
+    };
+
+    let resolved_var =
+        resolved_variables
+            .get(var_name)
+            .ok_or_else(|| ResolutionError::UndefinedVariable {
+                name: var_name.clone(),
+                context: format!("{} operation parameter {}", operation_name, param_index),
+            })?;
+
+    match &resolved_var.value {
+        ResolvedValue::String(s) => Ok(s.clone()),
+        ResolvedValue::Integer(i) => Ok(i.to_string()),
+        ResolvedValue::Float(f) => Ok(f.to_string()),
+        ResolvedValue::Boolean(b) => Ok(b.to_string()),
+        ResolvedValue::Version(v) => Ok(v.clone()),
+        ResolvedValue::EvrString(e) => Ok(e.clone()),
+        _ => Err(ResolutionError::RuntimeOperationFailed {
+            operation: operation.target_variable.clone(),
+            reason: format!(
+                "Cannot convert variable '{}' type to string for {}",
+                var_name, operation_name
+            ),
+        }),
+    }
+}
+
 // Helper function to extract numeric values from parameters
 fn get_numeric_value_from_parameter(
     parameter: &RunParameter,
     resolved_variables: &HashMap<String, ResolvedVariable>,
     operation_name: &str,
     param_index: usize,
-) -> Result<f64, ResolutionError> {
+) -> Result<Number, ResolutionError> {
     match parameter {
         RunParameter::Literal(value) => match value {
-            crate::types::common::Value::Integer(i) => Ok(*i as f64),
-            crate::types::common::Value::Float(f) => Ok(*f),
+            crate::types::common::Value::Integer(i) => Ok(Number::Integer(*i)),
+            crate::types::common::Value::Float(f) => Ok(Number::Float(*f)),
             crate::types::common::Value::String(s) => {
-                s.parse::<f64>()
-                    .map_err(|_| ResolutionError::RuntimeOperationFailed {
-                        operation: operation_name.to_string(),
-                        reason: format!(
-                            "Cannot parse string '{}' as number at parameter {}",
-                            s, param_index
-                        ),
-                    })
+                Number::parse(s).ok_or_else(|| ResolutionError::RuntimeOperationFailed {
+                    operation: operation_name.to_string(),
+                    reason: format!(
+                        "Cannot parse string '{}' as number at parameter {}",
+                        s, param_index
+                    ),
+                })
             }
             crate::types::common::Value::Variable(var_name) => {
                 if let Some(resolved_var) = resolved_variables.get(var_name) {
@@ -327,19 +510,18 @@ fn get_numeric_value_from_resolved(
     var_name: &str,
     operation_name: &str,
     param_index: usize,
-) -> Result<f64, ResolutionError> {
+) -> Result<Number, ResolutionError> {
     match resolved_value {
-        ResolvedValue::Integer(i) => Ok(*i as f64),
-        ResolvedValue::Float(f) => Ok(*f),
+        ResolvedValue::Integer(i) => Ok(Number::Integer(*i)),
+        ResolvedValue::Float(f) => Ok(Number::Float(*f)),
         ResolvedValue::String(s) => {
-            s.parse::<f64>()
-                .map_err(|_| ResolutionError::RuntimeOperationFailed {
-                    operation: operation_name.to_string(),
-                    reason: format!(
-                        "Cannot parse variable '{}' value '{}' as number at parameter {}",
-                        var_name, s, param_index
-                    ),
-                })
+            Number::parse(s).ok_or_else(|| ResolutionError::RuntimeOperationFailed {
+                operation: operation_name.to_string(),
+                reason: format!(
+                    "Cannot parse variable '{}' value '{}' as number at parameter {}",
+                    var_name, s, param_index
+                ),
+            })
         }
         _ => Err(ResolutionError::RuntimeOperationFailed {
             operation: operation_name.to_string(),
@@ -499,57 +681,77 @@ fn execute_count(
     operation: &RuntimeOperation,
     resolved_variables: &HashMap<String, ResolvedVariable>,
 ) -> Result<ResolvedValue, ResolutionError> {
-    // COUNT operation counts characters in a string (literal-only implementation)
+    // OBJ parameters defer COUNT to scan time; here it counts a variable
     for parameter in &operation.parameters {
-        match parameter {
-            RunParameter::Variable(var_name) => {
-                if let Some(resolved_var) = resolved_variables.get(var_name) {
-                    let count = match &resolved_var.value {
-                        ResolvedValue::Collection(items) => items.len() as i64,
-                        ResolvedValue::String(s) => s.len() as i64,
-                        ResolvedValue::Integer(i) => i.to_string().len() as i64,
-                        ResolvedValue::Float(f) => f.to_string().len() as i64,
-                        ResolvedValue::Boolean(b) => b.to_string().len() as i64,
-                        ResolvedValue::Version(v) => v.len() as i64,
-                        ResolvedValue::EvrString(e) => e.len() as i64,
-                        ResolvedValue::RecordData(_) => 1,
-                        ResolvedValue::Binary(b) => b.len() as i64,
-                    };
-
-                    return Ok(ResolvedValue::Integer(count));
-                } else {
-                    return Err(ResolutionError::UndefinedVariable {
-                        name: var_name.clone(),
-                        context: "COUNT operation".to_string(),
-                    });
+        if let RunParameter::Variable(var_name) = parameter {
+            let resolved_var = resolved_variables.get(var_name).ok_or_else(|| {
+                ResolutionError::UndefinedVariable {
+                    name: var_name.clone(),
+                    context: "COUNT operation".to_string(),
                 }
-            }
-            RunParameter::Literal(value) => {
-                let count = match value {
-                    crate::types::common::Value::String(s) => s.len() as i64,
-                    crate::types::common::Value::Integer(i) => i.to_string().len() as i64,
-                    crate::types::common::Value::Float(f) => f.to_string().len() as i64,
-                    crate::types::common::Value::Boolean(b) => b.to_string().len() as i64,
-                    _ => {
-                        return Err(ResolutionError::RuntimeOperationFailed {
-                            operation: operation.target_variable.clone(),
-                            reason: "Cannot count elements in this literal type".to_string(),
-                        })
-                    }
-                };
-
-                return Ok(ResolvedValue::Integer(count));
-            }
-            _ => {} // Continue to next parameter
+            })?;
+            return Ok(ResolvedValue::Integer(count_items(&resolved_var.value)));
         }
     }
 
     Err(ResolutionError::RuntimeOperationFailed {
         operation: operation.target_variable.clone(),
-        reason: "COUNT operation requires at least one countable parameter".to_string(),
+        reason: "COUNT operation requires a VAR or OBJ parameter".to_string(),
     })
 }
 
+/// Number of items in a value: a collection's length, 0 for an empty
+/// string and 1 for any other single value
+pub(crate) fn count_items(value: &ResolvedValue) -> i64 {
+    match value {
+        ResolvedValue::Collection(items) => items.len() as i64,
+        ResolvedValue::String(s) if s.is_empty() => 0,
+        _ => 1,
+    }
+}
+
+/// Length of a string in characters, like the `length_*` operations
+fn execute_length(
+    operation: &RuntimeOperation,
+    resolved_variables: &HashMap<String, ResolvedVariable>,
+) -> Result<ResolvedValue, ResolutionError> {
+    let value = single_string_parameter(operation, resolved_variables, "LENGTH")?;
+
+    Ok(ResolvedValue::Integer(value.chars().count() as i64))
+}
+
+/// UPPERCASE, LOWERCASE and TRIM: apply a Unicode-aware transform to one string
+fn execute_string_transform(
+    operation: &RuntimeOperation,
+    resolved_variables: &HashMap<String, ResolvedVariable>,
+    operation_name: &str,
+    transform: fn(&str) -> String,
+) -> Result<ResolvedValue, ResolutionError> {
+    let value = single_string_parameter(operation, resolved_variables, operation_name)?;
+
+    Ok(ResolvedValue::String(transform(&value)))
+}
+
+fn single_string_parameter(
+    operation: &RuntimeOperation,
+    resolved_variables: &HashMap<String, ResolvedVariable>,
+    operation_name: &str,
+) -> Result<String, ResolutionError> {
+    let parameter =
+        operation
+            .parameters
+            .first()
+            .ok_or_else(|| ResolutionError::RuntimeOperationFailed {
+                operation: operation.target_variable.clone(),
+                reason: format!(
+                    "{} operation requires a VAR or literal parameter",
+                    operation_name
+                ),
+            })?;
+
+    parameter_as_string(parameter, resolved_variables, operation, operation_name, 0)
+}
+
 fn execute_regex_capture(
     operation: &RuntimeOperation,
     resolved_variables: &HashMap<String, ResolvedVariable>,
@@ -659,37 +861,16 @@ fn execute_extract(
                         // TODO: WE dont use json anymore. Fix RUN oeprations
                         match record_data.get_field_by_path(&field_path.join(".")) {
                             Some(json_value) => {
-                                // Convert JSON value to ResolvedValue
-                                let result = match json_value {
-                                    serde_json::Value::String(s) => {
-                                        ResolvedValue::String(s.clone())
-                                    }
-                                    serde_json::Value::Number(n) => {
-                                        if let Some(i) = n.as_i64() {
-                                            ResolvedValue::Integer(i)
-                                        } else if let Some(f) = n.as_f64() {
-                                            ResolvedValue::Float(f)
-                                        } else {
-                                            return Err(ResolutionError::RuntimeOperationFailed {
-                                                operation: operation.target_variable.clone(),
-                                                reason: format!("Cannot convert number field '{}' to supported type", field),
-                                            });
-                                        }
-                                    }
-                                    serde_json::Value::Bool(b) => ResolvedValue::Boolean(*b),
-                                    serde_json::Value::Null => ResolvedValue::String(String::new()),
-                                    _ => {
-                                        return Err(ResolutionError::RuntimeOperationFailed {
-                                            operation: operation.target_variable.clone(),
-                                            reason: format!(
-                                                "Field '{}' contains unsupported JSON type",
-                                                field
-                                            ),
-                                        });
+                                // Arrays become collections so every item survives
+                                return extracted_json_value(json_value).ok_or_else(|| {
+                                    ResolutionError::RuntimeOperationFailed {
+                                        operation: operation.target_variable.clone(),
+                                        reason: format!(
+                                            "Field '{}' contains unsupported JSON type",
+                                            field
+                                        ),
                                     }
-                                };
-
-                                return Ok(result);
+                                });
                             }
                             None => {
                                 return Err(ResolutionError::RuntimeOperationFailed {
@@ -742,6 +923,26 @@ fn execute_extract(
     })
 }
 
+/// Convert an extracted JSON field to a resolved value, keeping every element
+/// of an array as a collection item. Nested objects are not extractable.
+fn extracted_json_value(json_value: &serde_json::Value) -> Option<ResolvedValue> {
+    match json_value {
+        serde_json::Value::String(s) => Some(ResolvedValue::String(s.clone())),
+        serde_json::Value::Number(n) => n
+            .as_i64()
+            .map(ResolvedValue::Integer)
+            .or_else(|| n.as_f64().map(ResolvedValue::Float)),
+        serde_json::Value::Bool(b) => Some(ResolvedValue::Boolean(*b)),
+        serde_json::Value::Null => Some(ResolvedValue::String(String::new())),
+        serde_json::Value::Array(items) => items
+            .iter()
+            .map(extracted_json_value)
+            .collect::<Option<Vec<_>>>()
+            .map(ResolvedValue::Collection),
+        serde_json::Value::Object(_) => None,
+    }
+}
+
 /// Execute UNIQUE operation - remove duplicates from a collection
 fn execute_unique(
     operation: &RuntimeOperation,
@@ -916,3 +1117,516 @@ fn get_collection_from_variable(
         }),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::common::DataType;
+    use crate::types::runtime_operation::ArithmeticOperator;
+
+    fn variables(pairs: &[(&str, &str)]) -> HashMap<String, ResolvedVariable> {
+        pairs
+            .iter()
+            .map(|(name, value)| {
+                let variable = ResolvedVariable::new(
+                    name.to_string(),
+                    DataType::String,
+                    ResolvedValue::String(value.to_string()),
+                );
+                (name.to_string(), variable)
+            })
+            .collect()
+    }
+
+    fn replace(input: RunParameter, pattern: &str, replacement: &str) -> RuntimeOperation {
+        RuntimeOperation::new(
+            "result".to_string(),
+            RuntimeOperationType::Replace,
+            vec![
+                input,
+                RunParameter::Pattern(pattern.to_string()),
+                RunParameter::Literal(Value::String(replacement.to_string())),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_replace_rewrites_every_match() {
+        let vars = variables(&[("ciphers", "aes256-gcm, chacha20,aes128-ctr")]);
+        let operation = replace(RunParameter::Variable("ciphers".to_string()), r",\s*", " ");
+        assert_eq!(
+            execute_runtime_operation(&operation, &vars).unwrap(),
+            ResolvedValue::String("aes256-gcm chacha20 aes128-ctr".to_string())
+        );
+
+        // Capture groups expand in the replacement
+        let operation = replace(
+            RunParameter::Literal(Value::String("key=value".to_string())),
+            r"(\w+)=(\w+)",
+            "$2=$1",
+        );
+        assert_eq!(
+            execute_runtime_operation(&operation, &vars).unwrap(),
+            ResolvedValue::String("value=key".to_string())
+        );
+
+        let operation = replace(RunParameter::Variable("ciphers".to_string()), "(", "");
+        assert!(matches!(
+            execute_runtime_operation(&operation, &vars),
+            Err(ResolutionError::RuntimeOperationFailed { .. })
+        ));
+    }
+
+    #[test]
+    fn test_concat_joins_literals_and_variables_in_order() {
+        let vars = variables(&[("unit", "sshd.service")]);
+        let operation = RuntimeOperation::new(
+            "path".to_string(),
+            RuntimeOperationType::Concat,
+            vec![
+                RunParameter::Literal(Value::String("/etc/systemd/system/".to_string())),
+                RunParameter::Variable("unit".to_string()),
+                RunParameter::Literal(Value::String(".d".to_string())),
+            ],
+        );
+        assert_eq!(
+            execute_runtime_operation(&operation, &vars).unwrap(),
+            ResolvedValue::String("/etc/systemd/system/sshd.service.d".to_string())
+        );
+
+        let operation = RuntimeOperation::new(
+            "path".to_string(),
+            RuntimeOperationType::Concat,
+            vec![RunParameter::Variable("missing".to_string())],
+        );
+        assert!(matches!(
+            execute_runtime_operation(&operation, &vars),
+            Err(ResolutionError::UndefinedVariable { .. })
+        ));
+    }
+
+    #[test]
+    fn test_count_items_and_length_in_characters() {
+        let vars = variables(&[("motd", "Grüße"), ("empty", "")]);
+        let single = |operation_type, parameter| {
+            RuntimeOperation::new("result".to_string(), operation_type, vec![parameter])
+        };
+
+        let length = single(
+            RuntimeOperationType::Length,
+            RunParameter::Variable("motd".to_string()),
+        );
+        assert_eq!(
+            execute_runtime_operation(&length, &vars).unwrap(),
+            ResolvedValue::Integer(5)
+        );
+
+        // COUNT is items, not characters: one string is one item
+        let count = single(
+            RuntimeOperationType::Count,
+            RunParameter::Variable("motd".to_string()),
+        );
+        assert_eq!(
+            execute_runtime_operation(&count, &vars).unwrap(),
+            ResolvedValue::Integer(1)
+        );
+        let count = single(
+            RuntimeOperationType::Count,
+            RunParameter::Variable("empty".to_string()),
+        );
+        assert_eq!(
+            execute_runtime_operation(&count, &vars).unwrap(),
+            ResolvedValue::Integer(0)
+        );
+    }
+
+    #[test]
+    fn test_case_and_trim_transforms_are_unicode_aware() {
+        let vars = variables(&[
+            ("street", "straße"),
+            ("street_sign", "ΟΔΟΣ"),
+            ("padded", "\u{3000}sshd\u{00A0}\t"),
+        ]);
+        let transform = |operation_type, variable: &str| {
+            let operation = RuntimeOperation::new(
+                "result".to_string(),
+                operation_type,
+                vec![RunParameter::Variable(variable.to_string())],
+            );
+            execute_runtime_operation(&operation, &vars).unwrap()
+        };
+
+        assert_eq!(
+            transform(RuntimeOperationType::Uppercase, "street"),
+            ResolvedValue::String("STRASSE".to_string())
+        );
+        // Final sigma lowercases to ς, not σ
+        assert_eq!(
+            transform(RuntimeOperationType::Lowercase, "street_sign"),
+            ResolvedValue::String("οδος".to_string())
+        );
+        assert_eq!(
+            transform(RuntimeOperationType::Trim, "padded"),
+            ResolvedValue::String("sshd".to_string())
+        );
+    }
+
+    #[test]
+    fn test_chained_transforms_resolve_in_dependency_order() {
+        use crate::resolution::ast_conversion::resolution_context_from_ast;
+        use crate::resolution::engine::ResolutionEngine;
+
+        let policy = "META
+    version `1.0.0`
+    esp_version `1.0`
+    author `test`
+    date `2026-01-01`
+    severity `medium`
+    platform `linux`
+    description `Hostname case`
+    control_framework `TEST`
+    control `HOST-1`
+    esp_scan_id `hostname`
+    criticality `medium`
+    tags `test`
+META_END
+
+DEF
+    VAR raw_hostname string `  Build-Unit.EXAMPLE  `
+
+    RUN hostname LOWERCASE
+        VAR trimmed_hostname
+    RUN_END
+
+    RUN trimmed_hostname TRIM
+        VAR raw_hostname
+    RUN_END
+
+    OBJECT hostname_file
+        path `/etc/hostname`
+    OBJECT_END
+
+    STATE expected_hostname
+        content string = VAR hostname
+    STATE_END
+
+    CRI AND
+        CTN file_content
+            TEST all all
+            STATE_REF expected_hostname
+            OBJECT_REF hostname_file
+        CTN_END
+    CRI_END
+DEF_END
+";
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("hostname.esp");
+        std::fs::write(&path, policy).unwrap();
+        let ast = esp_compiler::pipeline::process_file(&path.display().to_string())
+            .unwrap()
+            .ast;
+
+        let resolved = ResolutionEngine::new()
+            .resolve_context(&mut resolution_context_from_ast(&ast))
+            .unwrap();
+        assert_eq!(
+            resolved.global_variables["hostname"].value,
+            ResolvedValue::String("build-unit.example".to_string())
+        );
+    }
+
+    fn arithmetic(start: Value, steps: &[(ArithmeticOperator, Option<Value>)]) -> RuntimeOperation {
+        let mut parameters = vec![RunParameter::Literal(start)];
+        parameters.extend(
+            steps
+                .iter()
+                .map(|(operator, operand)| RunParameter::ArithmeticOp(*operator, operand.clone())),
+        );
+        RuntimeOperation::new(
+            "result".to_string(),
+            RuntimeOperationType::Arithmetic,
+            parameters,
+        )
+    }
+
+    #[test]
+    fn test_arithmetic_promotion_with_negative_operands() {
+        use ArithmeticOperator::*;
+        let vars = HashMap::new();
+        let eval = |start, steps: &[(ArithmeticOperator, Option<Value>)]| {
+            execute_runtime_operation(&arithmetic(start, steps), &vars).unwrap()
+        };
+
+        // Remainders take the sign of the dividend
+        assert_eq!(
+            eval(Value::Integer(-7), &[(Modulus, Some(Value::Integer(2)))]),
+            ResolvedValue::Integer(-1)
+        );
+        assert_eq!(
+            eval(Value::Integer(7), &[(Modulus, Some(Value::Integer(-2)))]),
+            ResolvedValue::Integer(1)
+        );
+        assert_eq!(
+            eval(Value::Integer(-7), &[(Modulus, Some(Value::Integer(-2)))]),
+            ResolvedValue::Integer(-1)
+        );
+        assert_eq!(
+            eval(Value::Float(-7.5), &[(Modulus, Some(Value::Integer(2)))]),
+            ResolvedValue::Float(-1.5)
+        );
+        assert_eq!(
+            eval(Value::Integer(-8), &[(Divide, Some(Value::Integer(2)))]),
+            ResolvedValue::Integer(-4)
+        );
+        // An inexact integer division promotes to float
+        assert_eq!(
+            eval(Value::Integer(-7), &[(Divide, Some(Value::Integer(2)))]),
+            ResolvedValue::Float(-3.5)
+        );
+        assert_eq!(
+            eval(Value::Integer(3), &[(Add, Some(Value::Float(0.5)))]),
+            ResolvedValue::Float(3.5)
+        );
+        // A whole-number result is an integer again if every input was one
+        assert_eq!(
+            eval(
+                Value::Integer(-7),
+                &[
+                    (Divide, Some(Value::Integer(2))),
+                    (Multiply, Some(Value::Integer(2)))
+                ]
+            ),
+            ResolvedValue::Integer(-7)
+        );
+        assert_eq!(
+            eval(Value::Float(-1.5), &[(Multiply, Some(Value::Integer(2)))]),
+            ResolvedValue::Float(-3.0)
+        );
+        assert_eq!(
+            eval(Value::Integer(6), &[(Divide, Some(Value::Float(2.0)))]),
+            ResolvedValue::Float(3.0)
+        );
+        assert_eq!(
+            eval(Value::Float(4.0), &[(Modulus, Some(Value::Integer(-3)))]),
+            ResolvedValue::Float(1.0)
+        );
+        // Clamp |-70000| into the port range
+        assert_eq!(
+            eval(
+                Value::Integer(-70000),
+                &[
+                    (Abs, None),
+                    (Min, Some(Value::Integer(65535))),
+                    (Max, Some(Value::Integer(1)))
+                ]
+            ),
+            ResolvedValue::Integer(65535)
+        );
+        assert_eq!(
+            eval(
+                Value::Float(-2.5),
+                &[(Abs, None), (Max, Some(Value::Integer(-1)))]
+            ),
+            ResolvedValue::Float(2.5)
+        );
+    }
+
+    #[test]
+    fn test_arithmetic_float_variable_stays_float() {
+        use ArithmeticOperator::*;
+        let mut vars = HashMap::new();
+        vars.insert(
+            "ratio".to_string(),
+            ResolvedVariable {
+                identifier: "ratio".to_string(),
+                data_type: DataType::Float,
+                value: ResolvedValue::Float(1.5),
+            },
+        );
+
+        let operation = RuntimeOperation::new(
+            "result".to_string(),
+            RuntimeOperationType::Arithmetic,
+            vec![
+                RunParameter::Variable("ratio".to_string()),
+                RunParameter::ArithmeticOp(Multiply, Some(Value::Integer(4))),
+            ],
+        );
+        assert_eq!(
+            execute_runtime_operation(&operation, &vars).unwrap(),
+            ResolvedValue::Float(6.0)
+        );
+    }
+
+    #[test]
+    fn test_arithmetic_failures_are_errors_not_panics() {
+        use ArithmeticOperator::*;
+        let vars = HashMap::new();
+
+        for (start, steps) in [
+            (Value::Integer(5), vec![(Divide, Some(Value::Integer(0)))]),
+            (Value::Integer(5), vec![(Modulus, Some(Value::Integer(0)))]),
+            (Value::Float(5.0), vec![(Modulus, Some(Value::Float(0.0)))]),
+            (Value::Integer(i64::MIN), vec![(Abs, None)]),
+            (
+                Value::Integer(i64::MIN),
+                vec![(Divide, Some(Value::Integer(-1)))],
+            ),
+            (
+                Value::Integer(i64::MIN),
+                vec![(Modulus, Some(Value::Integer(-1)))],
+            ),
+            (
+                Value::Integer(i64::MAX),
+                vec![(Add, Some(Value::Integer(1)))],
+            ),
+            (
+                Value::Float(f64::MAX),
+                vec![(Multiply, Some(Value::Float(2.0)))],
+            ),
+            (Value::Integer(5), vec![(Abs, Some(Value::Integer(1)))]),
+            (Value::Integer(5), vec![(Min, None)]),
+        ] {
+            let result = execute_runtime_operation(&arithmetic(start.clone(), &steps), &vars);
+            assert!(
+                matches!(result, Err(ResolutionError::RuntimeOperationFailed { .. })),
+                "{:?} {:?} gave {:?}",
+                start,
+                steps,
+                result
+            );
+        }
+    }
+
+    #[test]
+    fn test_extract_keeps_every_item_of_an_array_field() {
+        let record = crate::types::common::RecordData::from_json_value(serde_json::json!({
+            "nameservers": ["10.0.0.53", "10.0.1.53"],
+            "ports": [[53, 853]],
+            "options": {"ndots": 2}
+        }));
+        let mut vars = HashMap::new();
+        vars.insert(
+            "resolv".to_string(),
+            ResolvedVariable::new(
+                "resolv".to_string(),
+                DataType::RecordData,
+                ResolvedValue::RecordData(Box::new(record)),
+            ),
+        );
+        let extract = |field: &str| {
+            let operation = RuntimeOperation::new(
+                "result".to_string(),
+                RuntimeOperationType::Extract,
+                vec![RunParameter::ObjectExtraction {
+                    object_id: "resolv".to_string(),
+                    field: field.to_string(),
+                }],
+            );
+            execute_runtime_operation(&operation, &vars)
+        };
+
+        assert_eq!(
+            extract("nameservers").unwrap(),
+            ResolvedValue::Collection(vec![
+                ResolvedValue::String("10.0.0.53".to_string()),
+                ResolvedValue::String("10.0.1.53".to_string()),
+            ])
+        );
+        assert_eq!(
+            extract("ports").unwrap(),
+            ResolvedValue::Collection(vec![ResolvedValue::Collection(vec![
+                ResolvedValue::Integer(53),
+                ResolvedValue::Integer(853),
+            ])])
+        );
+        assert!(extract("options").is_err());
+    }
+
+    #[test]
+    fn test_list_variables_resolve_and_survive_serialization() {
+        use crate::resolution::ast_conversion::resolution_context_from_ast;
+        use crate::resolution::engine::ResolutionEngine;
+        use crate::types::resolution_context::ResolutionContext;
+
+        let policy = "META
+    version `1.0.0`
+    esp_version `1.0`
+    author `test`
+    date `2026-01-01`
+    severity `medium`
+    platform `linux`
+    description `DNS servers`
+    control_framework `TEST`
+    control `DNS-1`
+    esp_scan_id `dns`
+    criticality `medium`
+    tags `test`
+META_END
+
+DEF
+    VAR approved_servers string [`10.0.0.53`, `10.0.1.53`]
+    VAR server_csv string `10.0.0.53,10.0.1.53,10.0.0.53`
+
+    RUN configured_servers UNIQUE
+        VAR split_servers
+    RUN_END
+
+    RUN split_servers SPLIT
+        VAR server_csv
+        delimiter `,`
+    RUN_END
+
+    OBJECT resolv
+        path `/etc/resolv.conf`
+    OBJECT_END
+
+    STATE approved
+        content string = VAR approved_servers
+    STATE_END
+
+    CRI AND
+        CTN file_content
+            TEST all all
+            STATE_REF approved
+            OBJECT_REF resolv
+        CTN_END
+    CRI_END
+DEF_END
+";
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("dns.esp");
+        std::fs::write(&path, policy).unwrap();
+        let ast = esp_compiler::pipeline::process_file(&path.display().to_string())
+            .unwrap()
+            .ast;
+
+        let mut context = resolution_context_from_ast(&ast);
+        let resolved = ResolutionEngine::new()
+            .resolve_context(&mut context)
+            .unwrap();
+        let servers = ResolvedValue::Collection(vec![
+            ResolvedValue::String("10.0.0.53".to_string()),
+            ResolvedValue::String("10.0.1.53".to_string()),
+        ]);
+        assert_eq!(resolved.global_variables["approved_servers"].value, servers);
+        assert_eq!(
+            resolved.global_variables["configured_servers"].value,
+            servers
+        );
+
+        let json = serde_json::to_string(&context).unwrap();
+        let restored: ResolutionContext = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            restored.resolved_variables["configured_servers"].value,
+            servers
+        );
+        assert_eq!(
+            restored.variables[0].initial_value,
+            Some(Value::List(vec![
+                Value::String("10.0.0.53".to_string()),
+                Value::String("10.0.1.53".to_string()),
+            ]))
+        );
+    }
+}
@@ -3,6 +3,7 @@
 //! This module provides functions to expand SET_REF elements during the resolution phase,
 //! working directly on CriterionDeclaration structures before ExecutionContext creation.
 
+use crate::resolution::error::DependencyCycle;
 use crate::resolution::ResolutionError;
 use crate::types::criteria::CriteriaTree;
 use crate::types::criterion::CriterionDeclaration;
@@ -297,7 +298,14 @@ pub fn validate_set_expansions(context: &ResolutionContext) -> Result<(), Resolu
                 &format!("Circular SET_REF dependency detected: {}", cycle.join(" -> ")),
                 "set_id" => set_id
             );
-            return Err(ResolutionError::CircularDependency { cycle });
+            let contexts = cycle
+                .windows(2)
+                .map(|step| format!("SET {} uses SET_REF {}", step[0], step[1]))
+                .collect();
+            let path = cycle.iter().map(|id| format!("SET:{}", id)).collect();
+            return Err(ResolutionError::CircularDependency {
+                cycles: vec![DependencyCycle { path, contexts }],
+            });
         }
     }
 
@@ -265,6 +265,14 @@ fn resolve_inline_object(
                 esp_compiler::grammar::ast::nodes::Value::Boolean(b) => {
                     crate::types::common::ResolvedValue::Boolean(*b)
                 }
+                esp_compiler::grammar::ast::nodes::Value::List(_) => {
+                    return Err(ResolutionError::InvalidInput {
+                        message: format!(
+                            "Value list not allowed for field '{}' of inline object in SET",
+                            field.name
+                        ),
+                    });
+                }
                 esp_compiler::grammar::ast::nodes::Value::Variable(var_name) => {
                     // FIXED: Add & for HashMap::get
                     if let Some(resolved_var) = context.resolved_variables.get(var_name) {
@@ -404,11 +412,6 @@ pub fn resolve_set_operations(context: &mut ResolutionContext) -> Result<(), Res
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::common::ResolvedValue;
-    use crate::types::object::ObjectDeclaration;
-    use crate::types::set::{SetOperand, SetOperation, SetOperationType};
-    use crate::types::variable::ResolvedVariable;
-    use esp_compiler::grammar::ast::nodes::DataType;
 
     #[test]
     fn test_union_operation() {
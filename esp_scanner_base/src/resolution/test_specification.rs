@@ -0,0 +1,262 @@
+//! TEST specification validation against resolved state counts
+//!
+//! The parser accepts any `state_operator` on a TEST line because the number
+//! of states a CTN evaluates isn't known until references are resolved. This
+//! check runs after DAG resolution and SET expansion, once every STATE_REF has
+//! been confirmed and local states are attached to their CTN.
+//!
+//! - No states while the item check needs state evaluation: error
+//! - Several states without a state operator: error (join is ambiguous)
+//! - A state operator with a single state: warning (the operator is unused)
+//!
+//! SETs only contribute objects, so the state count is always STATE_REFs plus
+//! CTN-local STATE blocks.
+
+use crate::resolution::ResolutionError;
+use crate::types::criteria::CriteriaTree;
+use crate::types::criterion::{CriterionDeclaration, CtnNodeId};
+use crate::types::resolution_context::ResolutionContext;
+use crate::types::{ExistenceCheck, ItemCheck};
+use esp_compiler::log_warning;
+
+/// States a CTN evaluates, by origin
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct CtnStateCount {
+    /// Global states referenced with STATE_REF
+    pub state_refs: Vec<String>,
+    /// STATE blocks declared inside the CTN
+    pub local_states: Vec<String>,
+}
+
+impl CtnStateCount {
+    pub fn from_declaration(declaration: &CriterionDeclaration) -> Self {
+        Self {
+            state_refs: declaration
+                .state_refs
+                .iter()
+                .map(|r| r.state_id.clone())
+                .collect(),
+            local_states: declaration
+                .local_states
+                .iter()
+                .map(|s| s.identifier.clone())
+                .collect(),
+        }
+    }
+
+    pub fn total(&self) -> usize {
+        self.state_refs.len() + self.local_states.len()
+    }
+
+    /// Human-readable list naming each state and where it came from
+    pub fn describe(&self) -> String {
+        let refs = self.state_refs.iter().map(|s| format!("STATE_REF {}", s));
+        let locals = self
+            .local_states
+            .iter()
+            .map(|s| format!("local STATE {}", s));
+        refs.chain(locals).collect::<Vec<_>>().join(", ")
+    }
+}
+
+/// Validate every CTN's TEST line against its resolved state count
+///
+/// Returns warnings for CTNs whose state operator has no effect. Fails on the
+/// first CTN whose state configuration is an error.
+pub fn validate_test_specifications(
+    context: &ResolutionContext,
+) -> Result<Vec<String>, ResolutionError> {
+    let mut warnings = Vec::new();
+    for tree in &context.criteria_root.trees {
+        validate_tree(tree, &mut warnings)?;
+    }
+    Ok(warnings)
+}
+
+fn validate_tree(tree: &CriteriaTree, warnings: &mut Vec<String>) -> Result<(), ResolutionError> {
+    match tree {
+        CriteriaTree::Criterion {
+            declaration,
+            node_id,
+        } => {
+            if let Some(warning) = validate_criterion(declaration, *node_id)? {
+                log_warning!("TEST state operator has no effect",
+                    "ctn_type" => &declaration.criterion_type,
+                    "ctn_node_id" => node_id,
+                    "detail" => &warning
+                );
+                warnings.push(warning);
+            }
+            Ok(())
+        }
+        CriteriaTree::Block { children, .. } => {
+            for child in children {
+                validate_tree(child, warnings)?;
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Check one CTN, returning a warning message if the operator is redundant
+pub fn validate_criterion(
+    declaration: &CriterionDeclaration,
+    node_id: CtnNodeId,
+) -> Result<Option<String>, ResolutionError> {
+    let test = &declaration.test;
+    let states = CtnStateCount::from_declaration(declaration);
+    let invalid = |reason: String| ResolutionError::InvalidTestSpecification {
+        ctn_type: declaration.criterion_type.clone(),
+        ctn_id: node_id,
+        reason,
+    };
+
+    match states.total() {
+        0 if item_check_requires_states(test.existence_check, test.item_check) => {
+            Err(invalid(format!(
+                "no states to evaluate, but item check '{}' requires state evaluation; \
+                 add a STATE or STATE_REF, or use existence check 'none'",
+                test.item_check.as_str()
+            )))
+        }
+        n if n > 1 && test.state_operator.is_none() => Err(invalid(format!(
+            "{} states ({}) without a state operator; add AND or OR to the TEST line",
+            n,
+            states.describe()
+        ))),
+        1 => Ok(test.state_operator.map(|op| {
+            format!(
+                "CTN '{}' (#{}) has state operator {:?} but only one state ({})",
+                declaration.criterion_type,
+                node_id,
+                op,
+                states.describe()
+            )
+        })),
+        _ => Ok(None),
+    }
+}
+
+/// Whether a TEST line needs at least one state to be meaningful
+///
+/// Existence check `none` asserts no objects exist, so states are never consulted.
+fn item_check_requires_states(existence: ExistenceCheck, item: ItemCheck) -> bool {
+    existence != ExistenceCheck::None
+        && matches!(
+            item,
+            ItemCheck::All | ItemCheck::AtLeastOne | ItemCheck::OnlyOne
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::criteria::CriteriaRoot;
+    use crate::types::state::StateDeclaration;
+    use crate::types::{StateJoinOp, TestSpecification};
+    use esp_compiler::grammar::ast::nodes::StateRef;
+
+    fn declaration(
+        item_check: ItemCheck,
+        state_operator: Option<StateJoinOp>,
+        refs: &[&str],
+        locals: &[&str],
+    ) -> CriterionDeclaration {
+        CriterionDeclaration {
+            criterion_type: "file_metadata".to_string(),
+            test: TestSpecification {
+                existence_check: ExistenceCheck::All,
+                item_check,
+                state_operator,
+                entity_check: None,
+                span: None,
+            },
+            state_refs: refs
+                .iter()
+                .map(|id| StateRef {
+                    state_id: id.to_string(),
+                    span: None,
+                })
+                .collect(),
+            object_refs: vec![],
+            local_states: locals
+                .iter()
+                .map(|id| StateDeclaration {
+                    identifier: id.to_string(),
+                    fields: vec![],
+                    record_checks: vec![],
+                    is_global: false,
+                })
+                .collect(),
+            local_object: None,
+            controls: vec![],
+            severity: None,
+            ctn_node_id: Some(7),
+        }
+    }
+
+    fn context_with(declaration: CriterionDeclaration) -> ResolutionContext {
+        let mut context = ResolutionContext::new(vec![], vec![], vec![], vec![], vec![], vec![]);
+        context.criteria_root = CriteriaRoot {
+            trees: vec![CriteriaTree::Criterion {
+                declaration,
+                node_id: 7,
+            }],
+            ..CriteriaRoot::default()
+        };
+        context
+    }
+
+    #[test]
+    fn test_zero_states_with_state_item_check_is_error() {
+        let ctn = declaration(ItemCheck::All, None, &[], &[]);
+        let err = validate_test_specifications(&context_with(ctn)).unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("file_metadata"));
+        assert!(message.contains("#7"));
+        assert!(message.contains("no states"));
+    }
+
+    #[test]
+    fn test_zero_states_allowed_when_existence_is_none() {
+        let mut ctn = declaration(ItemCheck::All, None, &[], &[]);
+        ctn.test.existence_check = ExistenceCheck::None;
+        assert!(validate_test_specifications(&context_with(ctn))
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_multiple_states_without_operator_names_each_state() {
+        let ctn = declaration(ItemCheck::All, None, &["owner_ok", "mode_ok"], &["size_ok"]);
+        let message = validate_test_specifications(&context_with(ctn))
+            .unwrap_err()
+            .to_string();
+        assert!(message.contains("3 states"));
+        assert!(message.contains("STATE_REF owner_ok"));
+        assert!(message.contains("STATE_REF mode_ok"));
+        assert!(message.contains("local STATE size_ok"));
+        assert!(message.contains("AND or OR"));
+    }
+
+    #[test]
+    fn test_multiple_states_with_operator_is_valid() {
+        let ctn = declaration(
+            ItemCheck::All,
+            Some(StateJoinOp::Or),
+            &["owner_ok"],
+            &["size_ok"],
+        );
+        assert!(validate_test_specifications(&context_with(ctn))
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_operator_with_single_state_warns() {
+        let ctn = declaration(ItemCheck::All, Some(StateJoinOp::And), &["owner_ok"], &[]);
+        let warnings = validate_test_specifications(&context_with(ctn)).unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("only one state"));
+    }
+}
@@ -0,0 +1,37 @@
+//! # Advisories
+//!
+//! Informational notes an executor attaches to a criterion result, such as a
+//! service that is running but will not start at boot. Advisories never
+//! change a criterion's status or the scan counts; they are listed under their
+//! own key in the scan result.
+
+use serde::{Deserialize, Serialize};
+
+/// One informational, non-scoring note
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Advisory {
+    /// Criterion that emitted the advisory (`<type>#<node>`); set by the engine
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub criterion_id: String,
+    /// Object the note is about, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub object_id: Option<String>,
+    pub message: String,
+}
+
+impl Advisory {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            criterion_id: String::new(),
+            object_id: None,
+            message: message.into(),
+        }
+    }
+
+    pub fn for_object(object_id: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            object_id: Some(object_id.into()),
+            ..Self::new(message)
+        }
+    }
+}
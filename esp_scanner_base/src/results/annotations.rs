@@ -0,0 +1,523 @@
+//! # Result Annotations
+//!
+//! Reviewer dispositions attached to findings after a scan. Annotations live
+//! in a sidecar JSON file next to the scan result and are keyed by the stable
+//! criterion id carried on each [`ComplianceFinding`], so they survive rescans
+//! of the same policy.
+//!
+//! ```rust
+//! use esp_scanner_base::results::{Annotation, AnnotationSet, Disposition};
+//!
+//! let mut annotations = AnnotationSet::new();
+//! annotations.insert(
+//!     "file_metadata#3",
+//!     Annotation::new(Disposition::AcceptedRisk, "auditor")
+//!         .with_comment("Compensating control in place"),
+//! );
+//! assert_eq!(annotations.len(), 1);
+//! ```
+
+use super::controls::Control;
+use super::error::ResultGenerationError;
+use super::types::{ComplianceFinding, ScanResult};
+use crate::types::criterion::CtnNodeId;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Stable identifier for a criterion within a policy
+pub fn criterion_id(ctn_type: &str, ctn_node_id: CtnNodeId) -> String {
+    format!("{}#{}", ctn_type, ctn_node_id)
+}
+
+/// Sidecar location for annotations of a result file (`x.json` -> `x.annotations.json`)
+pub fn sidecar_path(result_path: &Path) -> PathBuf {
+    let stem = result_path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "scan_result".to_string());
+    result_path.with_file_name(format!("{}.annotations.json", stem))
+}
+
+/// Reviewer disposition of a finding
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Disposition {
+    /// The finding does not reflect a real compliance gap
+    FalsePositive,
+    /// The gap is real and accepted
+    AcceptedRisk,
+    /// The gap is real and a fix is scheduled
+    RemediationPlanned,
+}
+
+impl Disposition {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::FalsePositive => "false_positive",
+            Self::AcceptedRisk => "accepted_risk",
+            Self::RemediationPlanned => "remediation_planned",
+        }
+    }
+
+    /// Parse a disposition, accepting `-` or `_` separators
+    pub fn parse(s: &str) -> Result<Self, String> {
+        match s.trim().to_ascii_lowercase().replace('-', "_").as_str() {
+            "false_positive" => Ok(Self::FalsePositive),
+            "accepted_risk" => Ok(Self::AcceptedRisk),
+            "remediation_planned" => Ok(Self::RemediationPlanned),
+            _ => Err(format!(
+                "Unknown disposition '{}' (expected false_positive, accepted_risk or remediation_planned)",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Disposition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// A reviewer's disposition of one criterion's finding
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Annotation {
+    pub disposition: Disposition,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+
+    pub reviewer: String,
+
+    /// When the annotation was last edited
+    pub timestamp: DateTime<Utc>,
+}
+
+impl Annotation {
+    /// Create an annotation timestamped now
+    pub fn new(disposition: Disposition, reviewer: impl Into<String>) -> Self {
+        Self {
+            disposition,
+            comment: None,
+            reviewer: reviewer.into(),
+            timestamp: Utc::now(),
+        }
+    }
+
+    pub fn with_comment(mut self, comment: impl Into<String>) -> Self {
+        self.comment = Some(comment.into());
+        self
+    }
+
+    pub fn with_timestamp(mut self, timestamp: DateTime<Utc>) -> Self {
+        self.timestamp = timestamp;
+        self
+    }
+}
+
+/// Annotations for one scan result, keyed by criterion id
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AnnotationSet {
+    #[serde(default)]
+    pub annotations: BTreeMap<String, Annotation>,
+}
+
+impl AnnotationSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add or replace the annotation for a criterion, returning the previous one
+    pub fn insert(
+        &mut self,
+        criterion_id: impl Into<String>,
+        annotation: Annotation,
+    ) -> Option<Annotation> {
+        self.annotations.insert(criterion_id.into(), annotation)
+    }
+
+    pub fn remove(&mut self, criterion_id: &str) -> Option<Annotation> {
+        self.annotations.remove(criterion_id)
+    }
+
+    pub fn get(&self, criterion_id: &str) -> Option<&Annotation> {
+        self.annotations.get(criterion_id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.annotations.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.annotations.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &Annotation)> {
+        self.annotations.iter()
+    }
+
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Load a sidecar file
+    pub fn load(path: &Path) -> Result<Self, ResultGenerationError> {
+        let display = path.display().to_string();
+        let json = std::fs::read_to_string(path).map_err(|e| {
+            ResultGenerationError::annotation_file_failed(&display, "read", &e.to_string())
+        })?;
+        Self::from_json(&json).map_err(|e| {
+            ResultGenerationError::annotation_file_failed(&display, "parse", &e.to_string())
+        })
+    }
+
+    /// Load a sidecar file, treating a missing file as an empty set
+    pub fn load_or_default(path: &Path) -> Result<Self, ResultGenerationError> {
+        if path.exists() {
+            Self::load(path)
+        } else {
+            Ok(Self::new())
+        }
+    }
+
+    /// Write a sidecar file
+    pub fn save(&self, path: &Path) -> Result<(), ResultGenerationError> {
+        let display = path.display().to_string();
+        let json = self.to_json().map_err(|e| {
+            ResultGenerationError::annotation_file_failed(&display, "serialize", &e.to_string())
+        })?;
+        std::fs::write(path, json).map_err(|e| {
+            ResultGenerationError::annotation_file_failed(&display, "write", &e.to_string())
+        })
+    }
+}
+
+/// A finding paired with its reviewer annotation, if any
+#[derive(Debug, Serialize)]
+pub struct AnnotatedFinding<'a> {
+    #[serde(flatten)]
+    pub finding: &'a ComplianceFinding,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub annotation: Option<&'a Annotation>,
+}
+
+impl AnnotatedFinding<'_> {
+    pub fn disposition(&self) -> Option<Disposition> {
+        self.annotation.map(|a| a.disposition)
+    }
+
+    pub fn is_false_positive(&self) -> bool {
+        self.disposition() == Some(Disposition::FalsePositive)
+    }
+}
+
+/// Read-only view of a scan result with annotations applied
+#[derive(Debug, Serialize)]
+pub struct AnnotatedScanResult<'a> {
+    #[serde(skip)]
+    pub result: &'a ScanResult,
+
+    pub findings: Vec<AnnotatedFinding<'a>>,
+
+    /// Annotated criterion ids with no matching finding in the result
+    pub stale: Vec<String>,
+}
+
+impl<'a> AnnotatedScanResult<'a> {
+    /// Findings, optionally leaving out those marked false positive
+    pub fn findings(&self, exclude_false_positives: bool) -> Vec<&AnnotatedFinding<'a>> {
+        self.findings
+            .iter()
+            .filter(|f| !(exclude_false_positives && f.is_false_positive()))
+            .collect()
+    }
+
+    pub fn finding_count(&self, exclude_false_positives: bool) -> usize {
+        self.findings(exclude_false_positives).len()
+    }
+
+    pub fn false_positive_count(&self) -> usize {
+        self.findings
+            .iter()
+            .filter(|f| f.is_false_positive())
+            .count()
+    }
+
+    /// Render findings with dispositions and comments as a markdown report
+    pub fn to_markdown(&self, exclude_false_positives: bool) -> String {
+        let mut out = format!(
+            "# Scan {}\n\n**Control:** {} {}\n\n",
+            self.result.scan_id,
+            self.result.metadata.esp_metadata.control_framework,
+            self.result.metadata.esp_metadata.control
+        );
+
+        let vacuous = self.result.results.check.vacuous_pass_criteria;
+        if vacuous > 0 {
+            out.push_str(&format!(
+                "**Warning:** {} criteria passed without observing anything; \
+                 see the findings marked as passed vacuously.\n\n",
+                vacuous
+            ));
+        }
+
+        let findings = self.findings(exclude_false_positives);
+        if findings.is_empty() {
+            out.push_str("No findings.\n");
+        } else {
+            out.push_str("| Severity | Finding | Criterion | Controls | Disposition | Comment |\n");
+            out.push_str("|---|---|---|---|---|---|\n");
+            for annotated in findings {
+                let finding = annotated.finding;
+                out.push_str(&format!(
+                    "| {:?} | {} | {} | {} | {} | {} |\n",
+                    finding.severity,
+                    escape_cell(&finding.title),
+                    finding.criterion_id.as_deref().unwrap_or("-"),
+                    controls_cell(&finding.controls),
+                    annotated.disposition().map(|d| d.as_str()).unwrap_or("-"),
+                    annotated
+                        .annotation
+                        .and_then(|a| a.comment.as_deref())
+                        .map(escape_cell)
+                        .unwrap_or_default()
+                ));
+            }
+        }
+
+        if exclude_false_positives && self.false_positive_count() > 0 {
+            out.push_str(&format!(
+                "\n{} finding(s) excluded as false positive.\n",
+                self.false_positive_count()
+            ));
+        }
+
+        if !self.result.advisories.is_empty() {
+            out.push_str("\n## Advisories\n\n");
+            for advisory in &self.result.advisories {
+                out.push_str(&format!(
+                    "- {} {}: {}\n",
+                    advisory.criterion_id,
+                    advisory.object_id.as_deref().unwrap_or("-"),
+                    advisory.message
+                ));
+            }
+        }
+
+        if !self.stale.is_empty() {
+            out.push_str("\n## Stale annotations\n\n");
+            for id in &self.stale {
+                out.push_str(&format!("- {}\n", id));
+            }
+        }
+
+        out
+    }
+}
+
+/// Controls as markdown links, `-` when there are none
+fn controls_cell(controls: &[Control]) -> String {
+    if controls.is_empty() {
+        return "-".to_string();
+    }
+    controls
+        .iter()
+        .map(|c| escape_cell(&c.to_markdown()))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn escape_cell(s: &str) -> String {
+    s.replace('|', "\\|").replace('\n', " ")
+}
+
+impl ScanResult {
+    /// Pair findings with reviewer annotations
+    pub fn apply_annotations<'a>(
+        &'a self,
+        annotations: &'a AnnotationSet,
+    ) -> AnnotatedScanResult<'a> {
+        let findings: Vec<AnnotatedFinding<'a>> = self
+            .results
+            .findings
+            .iter()
+            .map(|finding| AnnotatedFinding {
+                finding,
+                annotation: finding
+                    .criterion_id
+                    .as_deref()
+                    .and_then(|id| annotations.get(id)),
+            })
+            .collect();
+
+        let present: HashSet<&str> = self
+            .results
+            .findings
+            .iter()
+            .filter_map(|f| f.criterion_id.as_deref())
+            .collect();
+        let stale = annotations
+            .annotations
+            .keys()
+            .filter(|id| !present.contains(id.as_str()))
+            .cloned()
+            .collect();
+
+        AnnotatedScanResult {
+            result: self,
+            findings,
+            stale,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::results::{Advisory, EspMetadata, FindingSeverity, HostContext, UserContext};
+
+    fn result_with_findings(ids: &[&str]) -> ScanResult {
+        let mut result = ScanResult::new(
+            "scan-1".to_string(),
+            EspMetadata::default_test(),
+            HostContext::new("host".to_string(), "linux".to_string()),
+            UserContext::new("tester".to_string(), "user".to_string()),
+        );
+        for id in ids {
+            result.results.findings.push(
+                ComplianceFinding::auto_id(
+                    FindingSeverity::High,
+                    format!("{} validation failed", id),
+                    "failed".to_string(),
+                    serde_json::json!({}),
+                    serde_json::json!({}),
+                )
+                .with_criterion_id(id.to_string()),
+            );
+        }
+        result
+    }
+
+    #[test]
+    fn test_sidecar_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = sidecar_path(&dir.path().join("scan_result.json"));
+        assert!(path.ends_with("scan_result.annotations.json"));
+
+        let mut annotations = AnnotationSet::new();
+        annotations.insert(
+            "file_metadata#1",
+            Annotation::new(Disposition::FalsePositive, "alice").with_comment("Mode set by IaC"),
+        );
+        annotations.save(&path).unwrap();
+
+        let loaded = AnnotationSet::load(&path).unwrap();
+        assert_eq!(loaded, annotations);
+        assert!(
+            AnnotationSet::load_or_default(&dir.path().join("missing.json"))
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_apply_annotations_reports_stale() {
+        let result = result_with_findings(&["file_metadata#1", "sysctl_parameter#2"]);
+        let mut annotations = AnnotationSet::new();
+        annotations.insert(
+            "file_metadata#1",
+            Annotation::new(Disposition::FalsePositive, "alice"),
+        );
+        annotations.insert(
+            "rpm_package#9",
+            Annotation::new(Disposition::AcceptedRisk, "bob"),
+        );
+
+        let annotated = result.apply_annotations(&annotations);
+
+        assert_eq!(annotated.stale, vec!["rpm_package#9".to_string()]);
+        assert!(annotated.findings[0].is_false_positive());
+        assert!(annotated.findings[1].annotation.is_none());
+        assert_eq!(annotated.finding_count(false), 2);
+        assert_eq!(annotated.finding_count(true), 1);
+    }
+
+    #[test]
+    fn test_markdown_includes_disposition_and_comment() {
+        let result = result_with_findings(&["file_metadata#1", "sysctl_parameter#2"]);
+        let mut annotations = AnnotationSet::new();
+        annotations.insert(
+            "sysctl_parameter#2",
+            Annotation::new(Disposition::RemediationPlanned, "carol")
+                .with_comment("Fix in next | maintenance window"),
+        );
+        annotations.insert(
+            "file_metadata#1",
+            Annotation::new(Disposition::FalsePositive, "carol"),
+        );
+
+        let annotated = result.apply_annotations(&annotations);
+        let markdown = annotated.to_markdown(false);
+        assert!(markdown.contains("remediation_planned"));
+        assert!(markdown.contains("| sysctl_parameter#2 | - |"));
+        assert!(markdown.contains("Fix in next \\| maintenance window"));
+
+        let filtered = annotated.to_markdown(true);
+        assert!(!filtered.contains("file_metadata#1"));
+        assert!(filtered.contains("1 finding(s) excluded as false positive"));
+    }
+
+    #[test]
+    fn test_markdown_links_controls() {
+        let mut result = result_with_findings(&["sshd#2"]);
+        result.results.findings[0].controls = vec![
+            Control::new("CIS", "5.2.10").with_url("https://example.test/cis"),
+            Control::new("STIG", "RHEL-09-255045"),
+        ];
+
+        let markdown = result
+            .apply_annotations(&AnnotationSet::new())
+            .to_markdown(false);
+        assert!(markdown.contains(
+            "| sshd#2 | [CIS 5.2.10](https://example.test/cis), STIG RHEL-09-255045 | - |"
+        ));
+    }
+
+    #[test]
+    fn test_markdown_lists_advisories_separately() {
+        let mut result = result_with_findings(&[]);
+        result.advisories.push(Advisory {
+            criterion_id: "systemd_service#3".to_string(),
+            ..Advisory::for_object("chronyd", "Service is enabled but not running")
+        });
+
+        let markdown = result
+            .apply_annotations(&AnnotationSet::new())
+            .to_markdown(false);
+        assert!(markdown.contains("No findings."));
+        assert!(markdown.contains(
+            "## Advisories\n\n- systemd_service#3 chronyd: Service is enabled but not running"
+        ));
+    }
+
+    #[test]
+    fn test_parse_disposition() {
+        assert_eq!(
+            Disposition::parse("false-positive").unwrap(),
+            Disposition::FalsePositive
+        );
+        assert_eq!(
+            Disposition::parse("ACCEPTED_RISK").unwrap(),
+            Disposition::AcceptedRisk
+        );
+        assert!(Disposition::parse("ignored").is_err());
+    }
+}
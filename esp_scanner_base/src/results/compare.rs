@@ -0,0 +1,295 @@
+//! # Semantic Comparison
+//!
+//! Compares scan results the way golden files checked into CI need: the
+//! order of findings, evidence and advisories does not matter, floats match
+//! within a tolerance, and fields that change on every run are ignored even
+//! under a fixed clock (scan id, timestamps, finding ids, host facts
+//! provenance, process id and strategy timings). Policy provenance is left out too:
+//! whether two results come from the same policy is checked separately with
+//! [`ScanResult::policy_mismatch`].
+//!
+//! ```rust
+//! use esp_scanner_base::results::{compare, EspMetadata, HostContext, ScanResult, UserContext};
+//!
+//! let scan = |id: &str| {
+//!     let mut result = ScanResult::new(
+//!         id.to_string(),
+//!         EspMetadata::default_test(),
+//!         HostContext::new("web-01".to_string(), "linux".to_string()),
+//!         UserContext::new("root".to_string(), "root".to_string()),
+//!     );
+//!     result.update_criteria_counts(3, 1, 2, 0);
+//!     result.finalize();
+//!     result
+//! };
+//! let golden = scan("scan-1");
+//! assert!(golden.semantically_equals(&scan("scan-2"), compare::DEFAULT_TOLERANCE));
+//! ```
+
+use super::types::ScanResult;
+use serde::Serializer;
+use serde_json::Value;
+
+/// Tolerance for floats when comparing results: half the last displayed
+/// decimal of a percentage
+pub const DEFAULT_TOLERANCE: f64 = 0.05;
+
+/// Top-level arrays whose element order carries no meaning
+const UNORDERED: [&str; 4] = ["findings", "evidence", "advisories", "consistency"];
+
+/// Round a percentage to the one decimal it is displayed and serialized with
+pub fn round_percentage(value: f32) -> f32 {
+    (value * 10.0).round() / 10.0
+}
+
+/// `serialize_with` for percentages, rounded by [`round_percentage`]
+pub(crate) fn serialize_percentage<S: Serializer>(
+    value: &Option<f32>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match value {
+        Some(value) => serializer.serialize_some(&round_percentage(*value)),
+        None => serializer.serialize_none(),
+    }
+}
+
+impl ScanResult {
+    /// Sort findings, evidence and advisories and round percentages so the
+    /// serialized result is stable across runs
+    pub fn canonicalize(&mut self) {
+        self.results.findings.sort_by(|a, b| {
+            (&a.criterion_id, &a.title, &a.description).cmp(&(
+                &b.criterion_id,
+                &b.title,
+                &b.description,
+            ))
+        });
+        self.evidence
+            .sort_by(|a, b| a.criterion_id.cmp(&b.criterion_id));
+        self.advisories.sort_by(|a, b| {
+            (&a.criterion_id, &a.object_id, &a.message).cmp(&(
+                &b.criterion_id,
+                &b.object_id,
+                &b.message,
+            ))
+        });
+        self.consistency
+            .sort_by(|a, b| (&a.path, &a.first_criterion).cmp(&(&b.path, &b.first_criterion)));
+        let check = &mut self.results.check;
+        check.pass_percentage = check.pass_percentage.map(round_percentage);
+    }
+
+    /// Whether two results report the same outcome, ignoring ordering,
+    /// volatile fields and float differences up to `tolerance`
+    pub fn semantically_equals(&self, other: &ScanResult, tolerance: f64) -> bool {
+        self.semantic_differences(other, tolerance).is_empty()
+    }
+
+    /// Where two results differ, one `path: ours != theirs` line per
+    /// difference; empty when they are semantically equal
+    pub fn semantic_differences(&self, other: &ScanResult, tolerance: f64) -> Vec<String> {
+        let mut differences = Vec::new();
+        diff(
+            "",
+            &comparable(self),
+            &comparable(other),
+            tolerance,
+            &mut differences,
+        );
+        differences
+    }
+}
+
+/// The result as JSON with volatile fields removed and unordered arrays
+/// sorted
+fn comparable(result: &ScanResult) -> Value {
+    let mut value = serde_json::to_value(result).unwrap_or(Value::Null);
+    let Some(root) = value.as_object_mut() else {
+        return value;
+    };
+    root.remove("scan_id");
+    root.remove("target");
+    if let Some(metadata) = root.get_mut("metadata").and_then(Value::as_object_mut) {
+        metadata.remove("timestamp");
+        metadata.remove("host_facts");
+        metadata.remove("provenance");
+        if let Some(user) = metadata
+            .get_mut("user_context")
+            .and_then(Value::as_object_mut)
+        {
+            user.remove("process_info");
+        }
+    }
+    if let Some(strategies) = root
+        .get_mut("performance")
+        .and_then(|p| p.get_mut("strategies"))
+        .and_then(Value::as_object_mut)
+    {
+        for usage in strategies.values_mut().filter_map(Value::as_object_mut) {
+            usage.retain(|field, _| !field.ends_with("_ms"));
+        }
+    }
+    if let Some(findings) = root
+        .get_mut("results")
+        .and_then(|r| r.get_mut("findings"))
+        .and_then(Value::as_array_mut)
+    {
+        for finding in findings.iter_mut().filter_map(Value::as_object_mut) {
+            finding.remove("finding_id");
+        }
+    }
+
+    for name in UNORDERED {
+        let array = match name {
+            "findings" => root.get_mut("results").and_then(|r| r.get_mut("findings")),
+            _ => root.get_mut(name),
+        };
+        if let Some(Value::Array(items)) = array {
+            items.sort_by_cached_key(|item| item.to_string());
+        }
+    }
+    value
+}
+
+fn diff(path: &str, ours: &Value, theirs: &Value, tolerance: f64, out: &mut Vec<String>) {
+    match (ours, theirs) {
+        (Value::Object(a), Value::Object(b)) => {
+            for (field, value) in a {
+                let child = format!("{}/{}", path, field);
+                diff(
+                    &child,
+                    value,
+                    b.get(field).unwrap_or(&Value::Null),
+                    tolerance,
+                    out,
+                );
+            }
+            for (field, value) in b.iter().filter(|(field, _)| !a.contains_key(*field)) {
+                diff(
+                    &format!("{}/{}", path, field),
+                    &Value::Null,
+                    value,
+                    tolerance,
+                    out,
+                );
+            }
+        }
+        (Value::Array(a), Value::Array(b)) if a.len() == b.len() => {
+            for (index, (x, y)) in a.iter().zip(b).enumerate() {
+                diff(&format!("{}/{}", path, index), x, y, tolerance, out);
+            }
+        }
+        (Value::Number(a), Value::Number(b)) => {
+            let (a, b) = (
+                a.as_f64().unwrap_or(f64::NAN),
+                b.as_f64().unwrap_or(f64::NAN),
+            );
+            if (a - b).abs() > tolerance || a.is_nan() != b.is_nan() {
+                out.push(format!("{}: {} != {}", path, ours, theirs));
+            }
+        }
+        _ if ours != theirs => out.push(format!("{}: {} != {}", path, ours, theirs)),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::results::{
+        Advisory, ComplianceFinding, EspMetadata, FindingSeverity, HostContext, UserContext,
+    };
+    use proptest::prelude::*;
+
+    fn scan(scan_id: &str, findings: &[(&str, &str)]) -> ScanResult {
+        let mut result = ScanResult::new(
+            scan_id.to_string(),
+            EspMetadata::default_test(),
+            HostContext::new("web-01".to_string(), "linux".to_string()),
+            UserContext::new("root".to_string(), "root".to_string()),
+        );
+        for (criterion_id, title) in findings {
+            result.results.findings.push(
+                ComplianceFinding::auto_id(
+                    FindingSeverity::High,
+                    title.to_string(),
+                    format!("{} failed", title),
+                    serde_json::json!({}),
+                    serde_json::json!({}),
+                )
+                .with_criterion_id(criterion_id.to_string()),
+            );
+        }
+        result.update_criteria_counts(3, 1, 2, 0);
+        result.finalize();
+        result
+    }
+
+    #[test]
+    fn test_pass_percentage_is_serialized_to_one_decimal() {
+        let result = scan("scan-1", &[]);
+        let raw = result.results.check.pass_percentage.unwrap();
+        assert!((raw - 33.333).abs() < 0.001);
+        let json: Value = serde_json::from_str(&result.to_json().unwrap()).unwrap();
+        assert_eq!(json["results"]["check"]["pass_percentage"], 33.3);
+    }
+
+    #[test]
+    fn test_differences_name_the_field_and_ignore_volatile_ones() {
+        let ours = scan("scan-1", &[("sshd#1", "sshd failed")]);
+        let mut theirs = scan("scan-2", &[("sshd#1", "sshd failed")]);
+        theirs.metadata.user_context.process_info = Some("pid:1".to_string());
+        assert!(ours.semantically_equals(&theirs, DEFAULT_TOLERANCE));
+
+        // 33.333... and 33.3 serialize alike; 33.4 is within a looser tolerance
+        theirs.results.check.pass_percentage = Some(33.3);
+        assert!(ours.semantically_equals(&theirs, 0.0));
+        theirs.results.check.pass_percentage = Some(33.4);
+        assert!(!ours.semantically_equals(&theirs, DEFAULT_TOLERANCE));
+        assert!(ours.semantically_equals(&theirs, 0.15));
+
+        theirs.results.check.pass_percentage = ours.results.check.pass_percentage;
+        theirs.results.check.failed_criteria = 1;
+        theirs.advisories.push(Advisory::new("note"));
+        let differences = ours.semantic_differences(&theirs, DEFAULT_TOLERANCE);
+        assert_eq!(differences.len(), 2, "{:?}", differences);
+        assert_eq!(differences[0], "/results/check/failed_criteria: 2 != 1");
+        assert!(differences[1].starts_with("/advisories: null != "));
+    }
+
+    #[test]
+    fn test_canonicalize_sorts_findings_and_rounds() {
+        let mut result = scan("scan-1", &[("sysctl#2", "b"), ("file#1", "a")]);
+        result.canonicalize();
+        let ids: Vec<_> = result
+            .results
+            .findings
+            .iter()
+            .map(|f| f.criterion_id.as_deref().unwrap())
+            .collect();
+        assert_eq!(ids, vec!["file#1", "sysctl#2"]);
+        assert_eq!(result.results.check.pass_percentage, Some(33.3));
+    }
+
+    fn refs(findings: &[(String, String)]) -> Vec<(&str, &str)> {
+        findings
+            .iter()
+            .map(|(id, title)| (id.as_str(), title.as_str()))
+            .collect()
+    }
+
+    proptest! {
+        #[test]
+        fn prop_shuffled_findings_stay_semantically_equal(
+            (findings, shuffled) in proptest::collection::vec(("ctn#[0-2]", "[a-z]{1,8}"), 1..12)
+                .prop_flat_map(|findings| (Just(findings.clone()), Just(findings).prop_shuffle())),
+        ) {
+            let ours = scan("scan-1", &refs(&findings));
+            let mut theirs = scan("scan-2", &refs(&shuffled));
+            prop_assert!(ours.semantically_equals(&theirs, DEFAULT_TOLERANCE));
+
+            theirs.canonicalize();
+            prop_assert!(ours.semantically_equals(&theirs, DEFAULT_TOLERANCE));
+        }
+    }
+}
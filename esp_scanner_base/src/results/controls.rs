@@ -0,0 +1,364 @@
+//! # Control Mapping
+//!
+//! Benchmark controls (CIS, STIG, internal ids) each criterion implements.
+//! Controls are declared with `CONTROL` lines in a CTN block or supplied by a
+//! control map file keyed by stable criterion id (`--control-map`). When both
+//! name a control for the same framework, the map wins; frameworks only the
+//! policy declares are kept.
+//!
+//! ```rust
+//! use esp_scanner_base::results::{Control, ControlMap};
+//!
+//! let map = ControlMap::from_json(
+//!     r#"{ "file_metadata#3": [{ "framework": "CIS", "control_id": "5.2.10" }] }"#,
+//! )
+//! .unwrap();
+//! let declared = vec![
+//!     Control::new("CIS", "5.2.9"),
+//!     Control::new("STIG", "RHEL-09-255045"),
+//! ];
+//! let controls = map.resolve("file_metadata#3", &declared);
+//! assert_eq!(controls[0].framework, "STIG");
+//! assert_eq!(controls[1].control_id, "5.2.10");
+//! ```
+
+use super::error::ResultGenerationError;
+use super::types::ScanResult;
+use crate::strategies::ComplianceStatus;
+use esp_compiler::grammar::ast::nodes::ControlRef;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+
+/// One benchmark control a criterion implements
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Control {
+    /// Framework the control belongs to, e.g. `CIS` or `STIG`
+    pub framework: String,
+    /// Control identifier within the framework
+    pub control_id: String,
+    /// Link to the control's documentation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+impl Control {
+    pub fn new(framework: impl Into<String>, control_id: impl Into<String>) -> Self {
+        Self {
+            framework: framework.into(),
+            control_id: control_id.into(),
+            url: None,
+        }
+    }
+
+    pub fn with_url(mut self, url: impl Into<String>) -> Self {
+        self.url = Some(url.into());
+        self
+    }
+
+    /// `[CIS 5.2.10](url)` when a link is known, otherwise `CIS 5.2.10`
+    pub fn to_markdown(&self) -> String {
+        match &self.url {
+            Some(url) => format!("[{}]({})", self, url),
+            None => self.to_string(),
+        }
+    }
+
+    fn key(&self) -> (&str, &str) {
+        (&self.framework, &self.control_id)
+    }
+}
+
+impl fmt::Display for Control {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.framework, self.control_id)
+    }
+}
+
+impl From<&ControlRef> for Control {
+    fn from(control: &ControlRef) -> Self {
+        Self {
+            framework: control.framework.clone(),
+            control_id: control.control_id.clone(),
+            url: control.url.clone(),
+        }
+    }
+}
+
+/// External controls keyed by criterion id (`<type>#<node>`), loaded from a
+/// JSON object of criterion id to control list
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ControlMap {
+    pub criteria: BTreeMap<String, Vec<Control>>,
+}
+
+impl ControlMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a control for a criterion
+    pub fn insert(&mut self, criterion_id: impl Into<String>, control: Control) {
+        self.criteria
+            .entry(criterion_id.into())
+            .or_default()
+            .push(control);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.criteria.is_empty()
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Load a control map file
+    pub fn load(path: &Path) -> Result<Self, ResultGenerationError> {
+        let display = path.display().to_string();
+        let json = std::fs::read_to_string(path).map_err(|e| {
+            ResultGenerationError::control_map_failed(&display, "read", &e.to_string())
+        })?;
+        Self::from_json(&json).map_err(|e| {
+            ResultGenerationError::control_map_failed(&display, "parse", &e.to_string())
+        })
+    }
+
+    /// Controls of a criterion: the policy's declarations, with every
+    /// framework the map lists for it replaced by the map's controls
+    pub fn resolve(&self, criterion_id: &str, declared: &[Control]) -> Vec<Control> {
+        let Some(mapped) = self.criteria.get(criterion_id) else {
+            return declared.to_vec();
+        };
+        let mut controls: Vec<Control> = declared
+            .iter()
+            .filter(|c| !mapped.iter().any(|m| m.framework == c.framework))
+            .cloned()
+            .collect();
+        controls.extend(mapped.iter().cloned());
+        controls
+    }
+}
+
+/// Criterion outcomes counted for one control
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ControlSummary {
+    #[serde(flatten)]
+    pub control: Control,
+    pub passed: u32,
+    pub failed: u32,
+    pub errors: u32,
+    #[serde(default)]
+    pub not_evaluated: u32,
+    /// Criteria that do not apply to the host; not part of the total
+    #[serde(default)]
+    pub not_applicable: u32,
+    /// Failed criteria covered by a waiver
+    #[serde(default)]
+    pub waived: u32,
+}
+
+impl ControlSummary {
+    fn new(control: Control) -> Self {
+        Self {
+            control,
+            passed: 0,
+            failed: 0,
+            errors: 0,
+            not_evaluated: 0,
+            not_applicable: 0,
+            waived: 0,
+        }
+    }
+
+    fn record(&mut self, status: ComplianceStatus) {
+        match status {
+            ComplianceStatus::Pass => self.passed += 1,
+            ComplianceStatus::Fail => self.failed += 1,
+            ComplianceStatus::Error => self.errors += 1,
+            ComplianceStatus::Unknown => self.not_evaluated += 1,
+            ComplianceStatus::NotApplicable => self.not_applicable += 1,
+            ComplianceStatus::Waived => self.waived += 1,
+        }
+    }
+
+    fn merge(&mut self, other: &ControlSummary) {
+        self.passed += other.passed;
+        self.failed += other.failed;
+        self.errors += other.errors;
+        self.not_evaluated += other.not_evaluated;
+        self.not_applicable += other.not_applicable;
+        self.waived += other.waived;
+    }
+
+    pub fn total(&self) -> u32 {
+        self.passed + self.failed + self.errors + self.not_evaluated + self.waived
+    }
+
+    /// Every criterion implementing the control passed or was waived
+    pub fn is_compliant(&self) -> bool {
+        self.passed + self.waived == self.total()
+    }
+
+    /// Percentage of criteria that passed (0-100)
+    pub fn pass_percentage(&self) -> f32 {
+        match self.total() {
+            0 => 0.0,
+            total => self.passed as f32 / total as f32 * 100.0,
+        }
+    }
+}
+
+/// Per-control summaries from criterion outcomes, ordered by framework and
+/// control id
+pub fn summarize<'a>(
+    outcomes: impl IntoIterator<Item = (&'a [Control], ComplianceStatus)>,
+) -> Vec<ControlSummary> {
+    let mut by_control: BTreeMap<(String, String), ControlSummary> = BTreeMap::new();
+    for (controls, status) in outcomes {
+        for control in controls {
+            let (framework, id) = control.key();
+            by_control
+                .entry((framework.to_string(), id.to_string()))
+                .or_insert_with(|| ControlSummary::new(control.clone()))
+                .record(status);
+        }
+    }
+    by_control.into_values().collect()
+}
+
+/// Per-control summaries of several scans added together, e.g. one policy
+/// scanned on many hosts
+pub fn roll_up<'a>(results: impl IntoIterator<Item = &'a ScanResult>) -> Vec<ControlSummary> {
+    let mut by_control: BTreeMap<(String, String), ControlSummary> = BTreeMap::new();
+    for summary in results.into_iter().flat_map(|r| &r.controls) {
+        let (framework, id) = summary.control.key();
+        by_control
+            .entry((framework.to_string(), id.to_string()))
+            .and_modify(|total| {
+                total.merge(summary);
+                if total.control.url.is_none() {
+                    total.control.url = summary.control.url.clone();
+                }
+            })
+            .or_insert_with(|| summary.clone());
+    }
+    by_control.into_values().collect()
+}
+
+/// `framework  control  passed/total  status` table of control summaries
+pub fn to_table(summaries: &[ControlSummary]) -> String {
+    let width = summaries
+        .iter()
+        .map(|s| s.control.to_string().len())
+        .max()
+        .unwrap_or(0);
+    summaries
+        .iter()
+        .map(|s| {
+            format!(
+                "{:<width$}  {}/{}  {}\n",
+                s.control.to_string(),
+                s.passed,
+                s.total(),
+                if s.is_compliant() {
+                    "compliant"
+                } else {
+                    "non-compliant"
+                },
+                width = width
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::results::{EspMetadata, HostContext, UserContext};
+
+    fn cis(id: &str) -> Control {
+        Control::new("CIS", id)
+    }
+
+    #[test]
+    fn test_map_overrides_declared_framework_and_keeps_others() {
+        let mut map = ControlMap::new();
+        map.insert(
+            "sshd#2",
+            cis("5.2.10").with_url("https://example.test/cis/5.2.10"),
+        );
+        map.insert("sshd#2", Control::new("INTERNAL", "SEC-12"));
+
+        let declared = vec![cis("5.2.9"), Control::new("STIG", "RHEL-09-255045")];
+        let controls = map.resolve("sshd#2", &declared);
+        assert_eq!(
+            controls,
+            vec![
+                Control::new("STIG", "RHEL-09-255045"),
+                cis("5.2.10").with_url("https://example.test/cis/5.2.10"),
+                Control::new("INTERNAL", "SEC-12"),
+            ]
+        );
+
+        // Criteria the map does not list keep their declarations
+        assert_eq!(map.resolve("sshd#3", &declared), declared);
+    }
+
+    #[test]
+    fn test_map_loads_json_and_reports_bad_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("controls.json");
+        std::fs::write(
+            &path,
+            r#"{ "file_metadata#1": [{ "framework": "CIS", "control_id": "6.1.1", "url": "https://example.test" }] }"#,
+        )
+        .unwrap();
+        let map = ControlMap::load(&path).unwrap();
+        assert_eq!(
+            map.criteria["file_metadata#1"][0].to_markdown(),
+            "[CIS 6.1.1](https://example.test)"
+        );
+
+        std::fs::write(&path, "[]").unwrap();
+        let error = ControlMap::load(&path).unwrap_err().to_string();
+        assert!(
+            error.starts_with("Failed to parse control map"),
+            "{}",
+            error
+        );
+    }
+
+    #[test]
+    fn test_summaries_count_each_control_and_roll_up_across_scans() {
+        let shared = [cis("5.2.10"), Control::new("STIG", "RHEL-09-255045")];
+        let only_cis = [cis("5.2.10")];
+        let summaries = summarize([
+            (&shared[..], ComplianceStatus::Pass),
+            (&only_cis[..], ComplianceStatus::Fail),
+        ]);
+        assert_eq!(summaries.len(), 2);
+        assert_eq!(summaries[0].control, cis("5.2.10"));
+        assert_eq!((summaries[0].passed, summaries[0].failed), (1, 1));
+        assert!(!summaries[0].is_compliant());
+        assert!(summaries[1].is_compliant());
+
+        let host = |name: &str| {
+            let mut result = ScanResult::new(
+                "scan-1".to_string(),
+                EspMetadata::default_test(),
+                HostContext::new(name.to_string(), "linux".to_string()),
+                UserContext::new("root".to_string(), "root".to_string()),
+            );
+            result.controls = summaries.clone();
+            result
+        };
+        let fleet = roll_up(&[host("web-01"), host("web-02")]);
+        assert_eq!(fleet[0].total(), 4);
+        assert_eq!(fleet[0].pass_percentage(), 50.0);
+        assert_eq!(fleet[1].passed, 2);
+        assert!(to_table(&fleet).contains("STIG RHEL-09-255045  2/2  compliant"));
+    }
+}
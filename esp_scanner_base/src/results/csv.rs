@@ -0,0 +1,521 @@
+//! # CSV Findings Export
+//!
+//! Flattens a [`ScanResult`] into one row per finding for spreadsheet-driven
+//! audits. Fields are quoted per RFC 4180: any value containing the
+//! delimiter, a double quote or a line break is wrapped in quotes with inner
+//! quotes doubled, and records end with CRLF.
+//!
+//! With field evidence (`--field-evidence`) a finding can be split into one
+//! row per item and field it failed on, and passing criteria can be listed;
+//! without it only findings are known.
+
+use super::evidence::{CriterionEvidence, FieldEvidence};
+use super::types::{ComplianceFinding, FindingSeverity, ScanResult};
+use crate::strategies::FieldOutcome;
+use std::collections::HashMap;
+use std::io::{self, Write};
+use std::str::FromStr;
+
+/// Longest evidence text kept in a row before truncation
+const MAX_EVIDENCE_CHARS: usize = 200;
+
+/// Columns of every row, in order
+pub const COLUMNS: [&str; 15] = [
+    "policy",
+    "host",
+    "criterion_id",
+    "criterion_type",
+    "severity",
+    "status",
+    "item",
+    "field",
+    "operation",
+    "expected",
+    "actual",
+    "evidence",
+    "tags",
+    "controls",
+    "timestamp",
+];
+
+/// Field separator
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Delimiter {
+    #[default]
+    Comma,
+    Semicolon,
+    Tab,
+}
+
+impl Delimiter {
+    pub fn as_char(&self) -> char {
+        match self {
+            Self::Comma => ',',
+            Self::Semicolon => ';',
+            Self::Tab => '\t',
+        }
+    }
+}
+
+impl FromStr for Delimiter {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "comma" | "," => Ok(Self::Comma),
+            "semicolon" | ";" => Ok(Self::Semicolon),
+            "tab" | "\t" => Ok(Self::Tab),
+            other => Err(format!(
+                "invalid delimiter '{}' (expected comma, semicolon or tab)",
+                other
+            )),
+        }
+    }
+}
+
+/// What to export and how
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CsvOptions {
+    pub delimiter: Delimiter,
+    /// Add a row per passing criterion (requires field evidence)
+    pub include_passing: bool,
+    /// Split findings into one row per failed item field (requires field
+    /// evidence)
+    pub per_item: bool,
+}
+
+impl CsvOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_delimiter(mut self, delimiter: Delimiter) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    pub fn with_passing(mut self, include: bool) -> Self {
+        self.include_passing = include;
+        self
+    }
+
+    pub fn with_per_item(mut self, per_item: bool) -> Self {
+        self.per_item = per_item;
+        self
+    }
+}
+
+/// Write a header and one row per finding of `result`
+pub fn write_findings(
+    result: &ScanResult,
+    out: &mut impl Write,
+    options: &CsvOptions,
+) -> io::Result<()> {
+    let writer = RecordWriter::new(options.delimiter);
+    writer.write(out, &COLUMNS)?;
+    for row in rows(result, options) {
+        writer.write(out, &row)?;
+    }
+    Ok(())
+}
+
+/// [`write_findings`] for a batch scan, with the policy file as first column
+pub fn write_batch_findings<'a>(
+    results: impl IntoIterator<Item = (&'a str, &'a ScanResult)>,
+    out: &mut impl Write,
+    options: &CsvOptions,
+) -> io::Result<()> {
+    let writer = RecordWriter::new(options.delimiter);
+    let mut header = vec!["file"];
+    header.extend(COLUMNS);
+    writer.write(out, &header)?;
+    for (file, result) in results {
+        for row in rows(result, options) {
+            let mut record = vec![file.to_string()];
+            record.extend(row);
+            writer.write(out, &record)?;
+        }
+    }
+    Ok(())
+}
+
+/// Quotes and joins records with one delimiter
+struct RecordWriter {
+    delimiter: char,
+}
+
+impl RecordWriter {
+    fn new(delimiter: Delimiter) -> Self {
+        Self {
+            delimiter: delimiter.as_char(),
+        }
+    }
+
+    fn write<S: AsRef<str>>(&self, out: &mut impl Write, fields: &[S]) -> io::Result<()> {
+        let mut line = String::new();
+        for (i, field) in fields.iter().enumerate() {
+            if i > 0 {
+                line.push(self.delimiter);
+            }
+            line.push_str(&self.escape(field.as_ref()));
+        }
+        line.push_str("\r\n");
+        out.write_all(line.as_bytes())
+    }
+
+    fn escape(&self, field: &str) -> String {
+        let needs_quotes = field
+            .chars()
+            .any(|c| c == self.delimiter || c == '"' || c == '\n' || c == '\r');
+        if needs_quotes {
+            format!("\"{}\"", field.replace('"', "\"\""))
+        } else {
+            field.to_string()
+        }
+    }
+}
+
+/// Values shared by every row of one scan
+struct ScanColumns<'a> {
+    policy: &'a str,
+    host: &'a str,
+    tags: &'a str,
+    timestamp: String,
+}
+
+/// Per-row values; unset columns are left empty
+#[derive(Clone, Default)]
+struct Row<'a> {
+    criterion_id: &'a str,
+    severity: &'a str,
+    status: &'a str,
+    item: &'a str,
+    field: &'a str,
+    operation: &'a str,
+    expected: String,
+    actual: String,
+    evidence: &'a str,
+    controls: String,
+}
+
+impl ScanColumns<'_> {
+    fn record(&self, row: Row<'_>) -> Vec<String> {
+        let criterion_type = row.criterion_id.split('#').next().unwrap_or_default();
+        vec![
+            self.policy.to_string(),
+            self.host.to_string(),
+            row.criterion_id.to_string(),
+            criterion_type.to_string(),
+            row.severity.to_string(),
+            row.status.to_string(),
+            row.item.to_string(),
+            row.field.to_string(),
+            row.operation.to_string(),
+            row.expected,
+            row.actual,
+            truncate(row.evidence),
+            self.tags.to_string(),
+            row.controls,
+            self.timestamp.clone(),
+        ]
+    }
+}
+
+fn rows(result: &ScanResult, options: &CsvOptions) -> Vec<Vec<String>> {
+    let metadata = &result.metadata;
+    let scan = ScanColumns {
+        policy: &metadata.esp_metadata.esp_scan_id,
+        host: &metadata.host.hostname,
+        tags: &metadata.esp_metadata.tags,
+        timestamp: metadata.timestamp.scan_start.to_rfc3339(),
+    };
+    let evidence: HashMap<&str, &CriterionEvidence> = result
+        .evidence
+        .iter()
+        .map(|e| (e.criterion_id.as_str(), e))
+        .collect();
+
+    let mut rows = Vec::new();
+    for finding in &result.results.findings {
+        let id = finding.criterion_id.as_deref().unwrap_or_default();
+        let criterion = evidence.get(id);
+        let status = criterion
+            .map(|e| e.status.as_str())
+            .unwrap_or_else(|| status_from_finding(finding));
+        let finding_columns = Row {
+            criterion_id: id,
+            severity: finding.severity.as_str(),
+            status: if finding.vacuous && status == "pass" {
+                "vacuous_pass"
+            } else {
+                status
+            },
+            evidence: &finding.description,
+            controls: finding
+                .controls
+                .iter()
+                .map(|c| c.to_string())
+                .collect::<Vec<_>>()
+                .join("; "),
+            ..Row::default()
+        };
+
+        let failed_fields: Vec<(&str, &FieldEvidence)> = match criterion {
+            Some(criterion) if options.per_item => criterion
+                .items
+                .iter()
+                .flat_map(|item| {
+                    item.fields
+                        .iter()
+                        .filter(|f| f.outcome != FieldOutcome::Pass)
+                        .map(move |f| (item.object_id.as_str(), f))
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+
+        if failed_fields.is_empty() {
+            // One row for the whole finding; expected and actual stay compact JSON
+            rows.push(scan.record(Row {
+                field: finding.field_path.as_deref().unwrap_or_default(),
+                expected: compact_json(&finding.expected),
+                actual: compact_json(&finding.actual),
+                ..finding_columns
+            }));
+            continue;
+        }
+        for (item, field) in failed_fields {
+            rows.push(scan.record(Row {
+                item,
+                field: &field.field,
+                operation: &field.operation,
+                expected: field.expected.clone(),
+                actual: field.actual.clone().unwrap_or_default(),
+                ..finding_columns.clone()
+            }));
+        }
+    }
+
+    if options.include_passing {
+        // Vacuous passes already have a row from their finding
+        let vacuous: Vec<&str> = result
+            .results
+            .findings
+            .iter()
+            .filter(|f| f.vacuous)
+            .filter_map(|f| f.criterion_id.as_deref())
+            .collect();
+        for criterion in result
+            .evidence
+            .iter()
+            .filter(|e| e.status == "pass" && !vacuous.contains(&e.criterion_id.as_str()))
+        {
+            rows.push(scan.record(Row {
+                criterion_id: &criterion.criterion_id,
+                status: &criterion.status,
+                ..Row::default()
+            }));
+        }
+    }
+    rows
+}
+
+fn compact_json(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::Object(map) if map.is_empty() => String::new(),
+        other => other.to_string(),
+    }
+}
+
+/// Criterion status implied by how the engine grades findings
+pub(super) fn status_from_finding(finding: &ComplianceFinding) -> &'static str {
+    match finding.severity {
+        _ if finding.waiver.is_some() => "waived",
+        FindingSeverity::Critical => "error",
+        FindingSeverity::Info => "not_evaluated",
+        FindingSeverity::Low if finding.vacuous => "pass",
+        _ => "fail",
+    }
+}
+
+fn truncate(text: &str) -> String {
+    if text.chars().count() <= MAX_EVIDENCE_CHARS {
+        return text.to_string();
+    }
+    let truncated: String = text.chars().take(MAX_EVIDENCE_CHARS).collect();
+    format!("{}...", truncated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::results::{
+        ComplianceFinding, Control, EspMetadata, HostContext, ItemEvidence, UserContext,
+    };
+
+    /// Minimal RFC 4180 reader, so the export is checked by an independent
+    /// parser rather than by string matching
+    fn parse(text: &str, delimiter: char) -> Vec<Vec<String>> {
+        let mut records = Vec::new();
+        let mut record = Vec::new();
+        let mut field = String::new();
+        let mut chars = text.chars().peekable();
+        let mut quoted = false;
+        while let Some(c) = chars.next() {
+            match (quoted, c) {
+                (true, '"') if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                (true, '"') => quoted = false,
+                (true, c) => field.push(c),
+                (false, '"') if field.is_empty() => quoted = true,
+                (false, '\r') if chars.peek() == Some(&'\n') => {
+                    chars.next();
+                    record.push(std::mem::take(&mut field));
+                    records.push(std::mem::take(&mut record));
+                }
+                (false, c) if c == delimiter => record.push(std::mem::take(&mut field)),
+                (false, c) => field.push(c),
+            }
+        }
+        assert!(
+            !quoted && field.is_empty() && record.is_empty(),
+            "unterminated record"
+        );
+        records
+    }
+
+    const ADVERSARIAL: &str = "line one, with comma\r\nline \"two\"; tab\there\nend";
+
+    fn scan_result() -> ScanResult {
+        let mut result = ScanResult::new(
+            "scan-1".to_string(),
+            EspMetadata::default_test(),
+            HostContext::new("web-01".to_string(), "linux".to_string()),
+            UserContext::new("root".to_string(), "root".to_string()),
+        );
+        result.add_finding(
+            ComplianceFinding::new(
+                "f-1".to_string(),
+                FindingSeverity::High,
+                "file_content validation failed".to_string(),
+                ADVERSARIAL.to_string(),
+                serde_json::json!({ "file_content": "PermitRootLogin no" }),
+                serde_json::json!({ "file_content": ADVERSARIAL }),
+            )
+            .with_criterion_id("file_content#2".to_string())
+            .with_controls(vec![
+                Control::new("CIS", "5.2.10"),
+                Control::new("STIG", "RHEL-09-255045"),
+            ]),
+        );
+        result.add_finding(
+            ComplianceFinding::new(
+                "f-2".to_string(),
+                FindingSeverity::Critical,
+                "sysctl_parameter validation failed".to_string(),
+                "collector failed".to_string(),
+                serde_json::json!({}),
+                serde_json::json!({}),
+            )
+            .with_criterion_id("sysctl_parameter#3".to_string()),
+        );
+        result
+    }
+
+    fn column(record: &[String], name: &str) -> String {
+        let index = COLUMNS.iter().position(|c| *c == name).unwrap();
+        record[index].clone()
+    }
+
+    #[test]
+    fn test_adversarial_evidence_round_trips_for_every_delimiter() {
+        let result = scan_result();
+        for delimiter in [Delimiter::Comma, Delimiter::Semicolon, Delimiter::Tab] {
+            let mut out = Vec::new();
+            write_findings(
+                &result,
+                &mut out,
+                &CsvOptions::new().with_delimiter(delimiter),
+            )
+            .unwrap();
+            let records = parse(&String::from_utf8(out).unwrap(), delimiter.as_char());
+
+            assert_eq!(records.len(), 3, "{:?}", delimiter);
+            assert_eq!(records[0], COLUMNS);
+            assert!(records.iter().all(|r| r.len() == COLUMNS.len()));
+
+            let fail = &records[1];
+            assert_eq!(column(fail, "evidence"), ADVERSARIAL);
+            assert_eq!(column(fail, "criterion_type"), "file_content");
+            assert_eq!(column(fail, "status"), "fail");
+            assert_eq!(column(fail, "host"), "web-01");
+            assert_eq!(column(fail, "controls"), "CIS 5.2.10; STIG RHEL-09-255045");
+            let actual: serde_json::Value = serde_json::from_str(&column(fail, "actual")).unwrap();
+            assert_eq!(actual["file_content"], ADVERSARIAL);
+
+            let error = &records[2];
+            assert_eq!(column(error, "status"), "error");
+            assert_eq!(column(error, "severity"), "critical");
+            assert_eq!(column(error, "expected"), "");
+        }
+    }
+
+    #[test]
+    fn test_per_item_rows_and_passing_criteria_from_evidence() {
+        let mut result = scan_result();
+        result.evidence = vec![
+            CriterionEvidence {
+                criterion_id: "file_content#2".to_string(),
+                status: "fail".to_string(),
+                items: vec![ItemEvidence {
+                    object_id: "sshd_config".to_string(),
+                    fields: vec![
+                        FieldEvidence {
+                            field: "file_content".to_string(),
+                            operation: "contains".to_string(),
+                            expected: "\"PermitRootLogin no\"".to_string(),
+                            actual: Some("x".repeat(500)),
+                            outcome: FieldOutcome::Fail,
+                        },
+                        FieldEvidence {
+                            field: "file_size".to_string(),
+                            operation: ">".to_string(),
+                            expected: "0".to_string(),
+                            actual: Some("12".to_string()),
+                            outcome: FieldOutcome::Pass,
+                        },
+                    ],
+                }],
+            },
+            CriterionEvidence {
+                criterion_id: "file_metadata#1".to_string(),
+                status: "pass".to_string(),
+                items: vec![],
+            },
+        ];
+
+        let options = CsvOptions::new().with_per_item(true).with_passing(true);
+        let mut out = Vec::new();
+        write_batch_findings([("policies/ssh.esp", &result)], &mut out, &options).unwrap();
+        let records = parse(&String::from_utf8(out).unwrap(), ',');
+
+        assert_eq!(records.len(), 4);
+        assert_eq!(records[0][0], "file");
+        let rows: Vec<&[String]> = records[1..].iter().map(|r| &r[1..]).collect();
+        assert!(records[1..].iter().all(|r| r[0] == "policies/ssh.esp"));
+
+        assert_eq!(column(rows[0], "item"), "sshd_config");
+        assert_eq!(column(rows[0], "field"), "file_content");
+        assert_eq!(column(rows[0], "actual").len(), 500);
+        assert_eq!(column(rows[0], "evidence"), ADVERSARIAL);
+        assert_eq!(column(rows[1], "criterion_id"), "sysctl_parameter#3");
+        assert_eq!(column(rows[2], "criterion_id"), "file_metadata#1");
+        assert_eq!(column(rows[2], "status"), "pass");
+
+        let long = truncate(&"y".repeat(300));
+        assert_eq!(long.chars().count(), MAX_EVIDENCE_CHARS + 3);
+        assert!(long.ends_with("..."));
+    }
+}
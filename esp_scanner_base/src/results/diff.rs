@@ -0,0 +1,479 @@
+//! # Scan Diff
+//!
+//! What changed between two scans of a host: criteria that started failing,
+//! passing or erroring, criteria that were added or removed, and observed
+//! values that differ in field evidence. Where [`ScanResult::semantic_differences`]
+//! answers "is this the golden result", a diff answers "what regressed since
+//! yesterday".
+//!
+//! Criteria are matched by their fingerprint first, a hash of the criterion
+//! type, test, objects and states that leaves out the CTN node id, so moving
+//! a CTN within the policy does not show up as a change. Criteria whose
+//! definition changed, and results saved before fingerprints were recorded,
+//! fall back to matching by criterion id (`<type>#<node id>`).
+//!
+//! ```rust
+//! use esp_scanner_base::results::{CriterionOutcome, EspMetadata, HostContext, ScanResult, UserContext};
+//!
+//! let scan = |status: &str| {
+//!     let mut result = ScanResult::new(
+//!         "scan".to_string(),
+//!         EspMetadata::default_test(),
+//!         HostContext::new("web-01".to_string(), "linux".to_string()),
+//!         UserContext::new("root".to_string(), "root".to_string()),
+//!     );
+//!     result.criteria.push(CriterionOutcome::new("file_metadata", 1, status, None));
+//!     result
+//! };
+//! let diff = scan("fail").diff(&scan("pass"));
+//! assert_eq!(diff.newly_failing[0].criterion_id, "file_metadata#1");
+//! assert!(diff.has_regressions());
+//! ```
+
+use super::annotations::criterion_id;
+use super::csv::status_from_finding;
+use super::evidence::CriterionEvidence;
+use super::provenance::sha256;
+use super::types::ScanResult;
+use crate::types::criterion::CtnNodeId;
+use crate::types::execution_context::ExecutableCriterion;
+use crate::types::execution_plan::PhaseKind;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Status and identity of one criterion in a scan
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CriterionOutcome {
+    pub criterion_id: String,
+    pub criterion_type: String,
+    pub ctn_node_id: CtnNodeId,
+    /// `pass`, `fail`, `error`, `unknown` or `not_applicable`
+    pub status: String,
+    /// Hash of the criterion's definition without its position; see
+    /// [`criterion_fingerprint`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fingerprint: Option<String>,
+}
+
+impl CriterionOutcome {
+    pub fn new(
+        criterion_type: &str,
+        ctn_node_id: CtnNodeId,
+        status: &str,
+        fingerprint: Option<String>,
+    ) -> Self {
+        Self {
+            criterion_id: criterion_id(criterion_type, ctn_node_id),
+            criterion_type: criterion_type.to_string(),
+            ctn_node_id,
+            status: status.to_string(),
+            fingerprint,
+        }
+    }
+}
+
+/// Hash of what a criterion checks: its type, test, objects and states
+///
+/// The CTN node id, source spans and SET filters are left out, so the
+/// fingerprint survives reordering the policy.
+pub fn criterion_fingerprint(criterion: &ExecutableCriterion) -> String {
+    let definition = serde_json::json!({
+        "type": criterion.criterion_type,
+        "test": criterion.test,
+        "objects": criterion.objects,
+        "states": criterion.states,
+    });
+    sha256(definition.to_string().as_bytes())
+}
+
+/// A criterion whose status differs between two scans
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatusChange {
+    /// Id in the current scan
+    pub criterion_id: String,
+    /// Id in the previous scan, when the criterion moved
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous_criterion_id: Option<String>,
+    pub previous_status: String,
+    pub status: String,
+}
+
+/// An observed field value that differs between two scans
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValueChange {
+    pub criterion_id: String,
+    pub object_id: String,
+    pub field: String,
+    pub previous: Option<String>,
+    pub current: Option<String>,
+}
+
+/// Changes from a previous scan to the current one
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScanDiff {
+    pub newly_failing: Vec<StatusChange>,
+    pub newly_erroring: Vec<StatusChange>,
+    pub newly_passing: Vec<StatusChange>,
+    /// Other status changes, e.g. to `unknown` or `not_applicable`
+    pub other_changes: Vec<StatusChange>,
+    pub added: Vec<CriterionOutcome>,
+    pub removed: Vec<CriterionOutcome>,
+    /// Only present when both scans recorded field evidence
+    pub value_changes: Vec<ValueChange>,
+}
+
+impl ScanDiff {
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Whether a criterion started failing or erroring
+    pub fn has_regressions(&self) -> bool {
+        !self.newly_failing.is_empty() || !self.newly_erroring.is_empty()
+    }
+}
+
+impl fmt::Display for ScanDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "No changes");
+        }
+        let sections = [
+            ("Newly failing", &self.newly_failing),
+            ("Newly erroring", &self.newly_erroring),
+            ("Newly passing", &self.newly_passing),
+            ("Other status changes", &self.other_changes),
+        ];
+        for (title, changes) in sections {
+            if changes.is_empty() {
+                continue;
+            }
+            writeln!(f, "{} ({}):", title, changes.len())?;
+            for change in changes {
+                write!(
+                    f,
+                    "  {} {} -> {}",
+                    change.criterion_id, change.previous_status, change.status
+                )?;
+                match &change.previous_criterion_id {
+                    Some(previous) => writeln!(f, " (was {})", previous)?,
+                    None => writeln!(f)?,
+                }
+            }
+        }
+        for (title, outcomes) in [("Added", &self.added), ("Removed", &self.removed)] {
+            if outcomes.is_empty() {
+                continue;
+            }
+            writeln!(f, "{} ({}):", title, outcomes.len())?;
+            for outcome in outcomes {
+                writeln!(f, "  {} {}", outcome.criterion_id, outcome.status)?;
+            }
+        }
+        if !self.value_changes.is_empty() {
+            writeln!(f, "Changed values ({}):", self.value_changes.len())?;
+            let shown =
+                |value: &Option<String>| value.as_deref().unwrap_or("<missing>").to_string();
+            for change in &self.value_changes {
+                writeln!(
+                    f,
+                    "  {} {}.{}: {} -> {}",
+                    change.criterion_id,
+                    change.object_id,
+                    change.field,
+                    shown(&change.previous),
+                    shown(&change.current)
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl ScanResult {
+    /// Status of every criterion: the recorded outcomes, or for results
+    /// saved before they were recorded, the criteria of the plan with their
+    /// evidence or finding status (no finding means the criterion passed)
+    pub fn criterion_outcomes(&self) -> Vec<CriterionOutcome> {
+        if !self.criteria.is_empty() {
+            return self.criteria.clone();
+        }
+        let mut statuses: Vec<(String, String)> = self
+            .plan
+            .phase(PhaseKind::Evaluate)
+            .iter()
+            .map(|id| (id.clone(), "pass".to_string()))
+            .collect();
+        let mut set = |id: &str, status: &str| match statuses.iter_mut().find(|(i, _)| i == id) {
+            Some(entry) => entry.1 = status.to_string(),
+            None => statuses.push((id.to_string(), status.to_string())),
+        };
+        for finding in &self.results.findings {
+            if let Some(id) = &finding.criterion_id {
+                set(id, status_from_finding(finding));
+            }
+        }
+        for evidence in &self.evidence {
+            set(&evidence.criterion_id, &evidence.status);
+        }
+        statuses
+            .into_iter()
+            .filter_map(|(id, status)| {
+                let (criterion_type, node) = id.rsplit_once('#')?;
+                Some(CriterionOutcome::new(
+                    criterion_type,
+                    node.parse().ok()?,
+                    &status,
+                    None,
+                ))
+            })
+            .collect()
+    }
+
+    /// What changed since `previous`, matching criteria by fingerprint and
+    /// then by criterion id
+    pub fn diff(&self, previous: &ScanResult) -> ScanDiff {
+        let current = self.criterion_outcomes();
+        let mut unmatched: Vec<Option<CriterionOutcome>> = previous
+            .criterion_outcomes()
+            .into_iter()
+            .map(Some)
+            .collect();
+        let mut pairs: Vec<Option<CriterionOutcome>> = vec![None; current.len()];
+
+        // Definitions first, so a CTN that moved keeps its history
+        for (outcome, pair) in current.iter().zip(pairs.iter_mut()) {
+            let Some(fingerprint) = &outcome.fingerprint else {
+                continue;
+            };
+            if let Some(slot) = unmatched.iter_mut().find(|p| {
+                p.as_ref()
+                    .is_some_and(|p| p.fingerprint.as_ref() == Some(fingerprint))
+            }) {
+                *pair = slot.take();
+            }
+        }
+        for (outcome, pair) in current.iter().zip(pairs.iter_mut()) {
+            if pair.is_some() {
+                continue;
+            }
+            if let Some(slot) = unmatched.iter_mut().find(|p| {
+                p.as_ref()
+                    .is_some_and(|p| p.criterion_id == outcome.criterion_id)
+            }) {
+                *pair = slot.take();
+            }
+        }
+
+        let mut diff = ScanDiff {
+            removed: unmatched.into_iter().flatten().collect(),
+            ..ScanDiff::default()
+        };
+        let current_evidence = evidence_by_id(&self.evidence);
+        let previous_evidence = evidence_by_id(&previous.evidence);
+        for (outcome, pair) in current.into_iter().zip(pairs) {
+            let Some(before) = pair else {
+                diff.added.push(outcome);
+                continue;
+            };
+            if let (Some(now), Some(then)) = (
+                current_evidence.get(outcome.criterion_id.as_str()),
+                previous_evidence.get(before.criterion_id.as_str()),
+            ) {
+                value_changes(&outcome.criterion_id, then, now, &mut diff.value_changes);
+            }
+            if before.status == outcome.status {
+                continue;
+            }
+            let change = StatusChange {
+                previous_criterion_id: (before.criterion_id != outcome.criterion_id)
+                    .then_some(before.criterion_id),
+                criterion_id: outcome.criterion_id,
+                previous_status: before.status,
+                status: outcome.status,
+            };
+            match change.status.as_str() {
+                "fail" => diff.newly_failing.push(change),
+                "error" => diff.newly_erroring.push(change),
+                "pass" => diff.newly_passing.push(change),
+                _ => diff.other_changes.push(change),
+            }
+        }
+        diff
+    }
+}
+
+fn evidence_by_id(evidence: &[CriterionEvidence]) -> HashMap<&str, &CriterionEvidence> {
+    evidence
+        .iter()
+        .map(|e| (e.criterion_id.as_str(), e))
+        .collect()
+}
+
+/// Observed values of fields present in both scans' evidence that differ
+fn value_changes(
+    criterion_id: &str,
+    previous: &CriterionEvidence,
+    current: &CriterionEvidence,
+    out: &mut Vec<ValueChange>,
+) {
+    for item in &current.items {
+        let Some(before) = previous
+            .items
+            .iter()
+            .find(|i| i.object_id == item.object_id)
+        else {
+            continue;
+        };
+        for field in &item.fields {
+            let Some(old) = before.fields.iter().find(|f| f.field == field.field) else {
+                continue;
+            };
+            if old.actual != field.actual {
+                out.push(ValueChange {
+                    criterion_id: criterion_id.to_string(),
+                    object_id: item.object_id.clone(),
+                    field: field.field.clone(),
+                    previous: old.actual.clone(),
+                    current: field.actual.clone(),
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::results::{
+        ComplianceFinding, EspMetadata, FieldEvidence, FindingSeverity, HostContext, ItemEvidence,
+        UserContext,
+    };
+    use crate::strategies::FieldOutcome;
+
+    fn scan(outcomes: &[(&str, CtnNodeId, &str, &str)]) -> ScanResult {
+        let mut result = ScanResult::new(
+            "scan".to_string(),
+            EspMetadata::default_test(),
+            HostContext::new("web-01".to_string(), "linux".to_string()),
+            UserContext::new("root".to_string(), "root".to_string()),
+        );
+        for (ctn_type, node, status, fingerprint) in outcomes {
+            result.criteria.push(CriterionOutcome::new(
+                ctn_type,
+                *node,
+                status,
+                Some(fingerprint.to_string()),
+            ));
+        }
+        result
+    }
+
+    fn evidence(criterion_id: &str, actual: &str) -> CriterionEvidence {
+        CriterionEvidence {
+            criterion_id: criterion_id.to_string(),
+            status: "fail".to_string(),
+            items: vec![ItemEvidence {
+                object_id: "sshd".to_string(),
+                fields: vec![FieldEvidence {
+                    field: "permissions".to_string(),
+                    operation: "equals".to_string(),
+                    expected: "0600".to_string(),
+                    actual: Some(actual.to_string()),
+                    outcome: FieldOutcome::Fail,
+                }],
+            }],
+        }
+    }
+
+    #[test]
+    fn test_reordered_criteria_match_by_fingerprint() {
+        let previous = scan(&[
+            ("file_metadata", 1, "pass", "a"),
+            ("file_metadata", 2, "fail", "b"),
+        ]);
+        let current = scan(&[
+            ("file_metadata", 1, "fail", "b"),
+            ("file_metadata", 2, "pass", "a"),
+        ]);
+        assert!(current.diff(&previous).is_empty());
+
+        // A changed definition falls back to the criterion id
+        let current = scan(&[
+            ("file_metadata", 1, "pass", "a"),
+            ("file_metadata", 2, "pass", "b2"),
+            ("rpm_package", 3, "error", "c"),
+        ]);
+        let diff = current.diff(&previous);
+        assert_eq!(diff.newly_passing[0].criterion_id, "file_metadata#2");
+        assert_eq!(diff.added[0].criterion_id, "rpm_package#3");
+        assert!(diff.removed.is_empty());
+        assert!(!diff.has_regressions());
+    }
+
+    #[test]
+    fn test_status_changes_are_classified() {
+        let previous = scan(&[
+            ("file_metadata", 1, "pass", "a"),
+            ("file_metadata", 2, "pass", "b"),
+            ("selinux_boolean", 3, "pass", "c"),
+            ("rpm_package", 4, "pass", "d"),
+        ]);
+        let current = scan(&[
+            ("file_metadata", 2, "fail", "a"),
+            ("file_metadata", 1, "error", "b"),
+            ("selinux_boolean", 3, "not_applicable", "c"),
+        ]);
+        let diff = current.diff(&previous);
+        let change = &diff.newly_failing[0];
+        assert_eq!(change.criterion_id, "file_metadata#2");
+        assert_eq!(
+            change.previous_criterion_id.as_deref(),
+            Some("file_metadata#1")
+        );
+        assert_eq!(diff.newly_erroring[0].criterion_id, "file_metadata#1");
+        assert_eq!(diff.other_changes[0].status, "not_applicable");
+        assert_eq!(diff.removed[0].criterion_id, "rpm_package#4");
+        assert!(diff.has_regressions());
+
+        let text = diff.to_string();
+        assert!(text.contains(
+            "Newly failing (1):\n  file_metadata#2 pass -> fail (was file_metadata#1)\n"
+        ));
+        assert!(text.contains("Removed (1):\n  rpm_package#4 pass\n"));
+    }
+
+    #[test]
+    fn test_observed_values_and_legacy_results() {
+        let mut previous = scan(&[]);
+        previous.results.findings.push(
+            ComplianceFinding::auto_id(
+                FindingSeverity::High,
+                "sshd".to_string(),
+                "sshd failed".to_string(),
+                serde_json::json!({}),
+                serde_json::json!({}),
+            )
+            .with_criterion_id("file_metadata#1".to_string()),
+        );
+        previous.evidence.push(evidence("file_metadata#1", "0644"));
+        let mut current = scan(&[("file_metadata", 1, "fail", "a")]);
+        current.evidence.push(evidence("file_metadata#1", "0666"));
+
+        // Results without recorded outcomes are read from findings
+        assert_eq!(previous.criterion_outcomes()[0].status, "fail");
+        let diff = current.diff(&previous);
+        assert!(diff.newly_failing.is_empty());
+        assert_eq!(
+            diff.value_changes,
+            vec![ValueChange {
+                criterion_id: "file_metadata#1".to_string(),
+                object_id: "sshd".to_string(),
+                field: "permissions".to_string(),
+                previous: Some("0644".to_string()),
+                current: Some("0666".to_string()),
+            }]
+        );
+    }
+}
@@ -150,6 +150,27 @@ pub enum ResultGenerationError {
         operation: String,
         cause: String,
     },
+
+    /// Reading or writing an annotation sidecar failed
+    AnnotationFileFailed {
+        path: String,
+        operation: String,
+        cause: String,
+    },
+
+    /// Reading a control map file failed
+    ControlMapFailed {
+        path: String,
+        operation: String,
+        cause: String,
+    },
+
+    /// Reading or validating a waivers file failed
+    WaiversFileFailed {
+        path: String,
+        operation: String,
+        cause: String,
+    },
 }
 
 impl ResultGenerationError {
@@ -309,6 +330,33 @@ impl ResultGenerationError {
             supported_formats,
         }
     }
+
+    /// Create annotation sidecar I/O error
+    pub fn annotation_file_failed(path: &str, operation: &str, cause: &str) -> Self {
+        Self::AnnotationFileFailed {
+            path: path.to_string(),
+            operation: operation.to_string(),
+            cause: cause.to_string(),
+        }
+    }
+
+    /// Create control map I/O error
+    pub fn control_map_failed(path: &str, operation: &str, cause: &str) -> Self {
+        Self::ControlMapFailed {
+            path: path.to_string(),
+            operation: operation.to_string(),
+            cause: cause.to_string(),
+        }
+    }
+
+    /// Create waivers file error
+    pub fn waivers_file_failed(path: &str, operation: &str, cause: &str) -> Self {
+        Self::WaiversFileFailed {
+            path: path.to_string(),
+            operation: operation.to_string(),
+            cause: cause.to_string(),
+        }
+    }
 }
 
 impl std::fmt::Display for ResultGenerationError {
@@ -564,6 +612,39 @@ impl std::fmt::Display for ResultGenerationError {
                     scan_id, operation, cause
                 )
             }
+            Self::AnnotationFileFailed {
+                path,
+                operation,
+                cause,
+            } => {
+                write!(
+                    f,
+                    "Failed to {} annotation file '{}': {}",
+                    operation, path, cause
+                )
+            }
+            Self::ControlMapFailed {
+                path,
+                operation,
+                cause,
+            } => {
+                write!(
+                    f,
+                    "Failed to {} control map '{}': {}",
+                    operation, path, cause
+                )
+            }
+            Self::WaiversFileFailed {
+                path,
+                operation,
+                cause,
+            } => {
+                write!(
+                    f,
+                    "Failed to {} waivers file '{}': {}",
+                    operation, path, cause
+                )
+            }
         }
     }
 }
@@ -0,0 +1,342 @@
+//! # Field Evidence
+//!
+//! Opt-in record of what each criterion actually compared: for every
+//! collected item, the state fields evaluated against it with the actual
+//! value, the operation and the outcome. Fields the item did not carry are
+//! listed with an `unknown` outcome, so a pass can be told apart from a check
+//! that never looked at the attribute.
+//!
+//! Independently of that, every failing finding carries the fields that
+//! failed as [`ObservedField`]s, so a report can say "expected
+//! PASS_MAX_DAYS <= 60, observed 99999" without anyone logging in to look.
+
+use crate::strategies::{FieldOutcome, FieldValidationResult, StateValidationResult};
+use crate::types::common::ResolvedValue;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Longest rendered value kept in evidence before truncation
+const MAX_VALUE_CHARS: usize = 200;
+
+/// Default byte limit for each expected or observed value on a finding
+pub const DEFAULT_OBSERVED_MAX_BYTES: usize = 1024;
+
+/// Fields compared for one criterion
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CriterionEvidence {
+    /// Stable criterion id (`<type>#<node>`), as used by findings
+    pub criterion_id: String,
+    /// Criterion status: pass, fail, error or unknown
+    pub status: String,
+    #[serde(default)]
+    pub items: Vec<ItemEvidence>,
+}
+
+/// Fields compared against one collected item
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ItemEvidence {
+    pub object_id: String,
+    pub fields: Vec<FieldEvidence>,
+}
+
+/// One state field evaluated against one item
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldEvidence {
+    pub field: String,
+    pub operation: String,
+    pub expected: String,
+    /// Value found on the item; absent when the field was not present
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub actual: Option<String>,
+    pub outcome: FieldOutcome,
+}
+
+/// A state field that failed on one item, with the value observed there
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ObservedField {
+    pub object_id: String,
+    pub field: String,
+    pub operation: String,
+    pub expected: String,
+    /// Value collected from the system; absent when the item lacked the
+    /// field
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub observed: Option<String>,
+    /// Set when `expected` or `observed` was cut to the byte limit
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub truncated: bool,
+}
+
+impl ObservedField {
+    /// Evidence for one field result, keeping at most `max_bytes` of each
+    /// value
+    pub fn new(object_id: &str, result: &FieldValidationResult, max_bytes: usize) -> Self {
+        let (expected, expected_cut) =
+            truncate_bytes(plain_value(&result.expected_value), max_bytes);
+        let (observed, observed_cut) = if result.was_compared() {
+            let (observed, cut) = truncate_bytes(plain_value(&result.actual_value), max_bytes);
+            (Some(observed), cut)
+        } else {
+            (None, false)
+        };
+        Self {
+            object_id: object_id.to_string(),
+            field: result.field_name.clone(),
+            operation: result.operation.as_str().to_string(),
+            expected,
+            observed,
+            truncated: expected_cut || observed_cut,
+        }
+    }
+
+    /// Key of this field in a finding's evidence map: `<object id>/<field>`
+    pub fn key(&self) -> String {
+        format!("{}/{}", self.object_id, self.field)
+    }
+}
+
+impl std::fmt::Display for ObservedField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "expected {} {} {}, observed {}",
+            self.field,
+            self.operation,
+            self.expected,
+            self.observed.as_deref().unwrap_or("(not present)")
+        )
+    }
+}
+
+/// Fields that failed in `state_results`, keyed by [`ObservedField::key`]
+pub fn observed_fields(
+    state_results: &[StateValidationResult],
+    max_bytes: usize,
+) -> BTreeMap<String, ObservedField> {
+    state_results
+        .iter()
+        .flat_map(|item| {
+            item.state_results
+                .iter()
+                .filter(|field| !field.passed)
+                .map(move |field| ObservedField::new(&item.object_id, field, max_bytes))
+        })
+        .map(|field| (field.key(), field))
+        .collect()
+}
+
+impl CriterionEvidence {
+    pub fn new(
+        criterion_id: impl Into<String>,
+        status: impl Into<String>,
+        state_results: &[StateValidationResult],
+    ) -> Self {
+        Self {
+            criterion_id: criterion_id.into(),
+            status: status.into(),
+            items: state_results.iter().map(ItemEvidence::from).collect(),
+        }
+    }
+
+    /// Fields that were not present on some item
+    pub fn unknown_fields(&self) -> impl Iterator<Item = (&str, &FieldEvidence)> {
+        self.items.iter().flat_map(|item| {
+            item.fields
+                .iter()
+                .filter(|f| f.outcome == FieldOutcome::Unknown)
+                .map(move |f| (item.object_id.as_str(), f))
+        })
+    }
+
+    /// Plain-text table of every compared field, for `scanner explain`
+    pub fn to_table(&self) -> String {
+        let mut out = format!("{} ({})\n", self.criterion_id, self.status);
+        if self.items.iter().all(|item| item.fields.is_empty()) {
+            out.push_str("  no fields evaluated\n");
+            return out;
+        }
+        out.push_str(&format!(
+            "  {:<24} {:<20} {:<18} {:<24} {:<24} {}\n",
+            "ITEM", "FIELD", "OPERATION", "EXPECTED", "ACTUAL", "OUTCOME"
+        ));
+        for item in &self.items {
+            for field in &item.fields {
+                out.push_str(&format!(
+                    "  {:<24} {:<20} {:<18} {:<24} {:<24} {}\n",
+                    item.object_id,
+                    field.field,
+                    field.operation,
+                    field.expected,
+                    field.actual.as_deref().unwrap_or("(not present)"),
+                    field.outcome.as_str()
+                ));
+            }
+        }
+        out
+    }
+}
+
+impl From<&StateValidationResult> for ItemEvidence {
+    fn from(result: &StateValidationResult) -> Self {
+        Self {
+            object_id: result.object_id.clone(),
+            fields: result
+                .state_results
+                .iter()
+                .map(FieldEvidence::from)
+                .collect(),
+        }
+    }
+}
+
+impl From<&FieldValidationResult> for FieldEvidence {
+    fn from(result: &FieldValidationResult) -> Self {
+        Self {
+            field: result.field_name.clone(),
+            operation: result.operation.as_str().to_string(),
+            expected: render_value(&result.expected_value),
+            actual: result
+                .was_compared()
+                .then(|| render_value(&result.actual_value)),
+            outcome: result.outcome,
+        }
+    }
+}
+
+/// A value as an operator would write it: strings without quotes
+fn plain_value(value: &ResolvedValue) -> String {
+    match value {
+        ResolvedValue::String(s) | ResolvedValue::Version(s) | ResolvedValue::EvrString(s) => {
+            s.clone()
+        }
+        other => other.to_string(),
+    }
+}
+
+/// `text` cut to at most `max_bytes` on a character boundary, and whether
+/// it was cut
+fn truncate_bytes(mut text: String, max_bytes: usize) -> (String, bool) {
+    if text.len() <= max_bytes {
+        return (text, false);
+    }
+    let mut end = max_bytes;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    text.truncate(end);
+    (text, true)
+}
+
+fn render_value(value: &ResolvedValue) -> String {
+    let rendered = value.to_string();
+    if rendered.chars().count() <= MAX_VALUE_CHARS {
+        return rendered;
+    }
+    let truncated: String = rendered.chars().take(MAX_VALUE_CHARS).collect();
+    format!("{}...", truncated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::common::Operation;
+
+    #[test]
+    fn test_missing_field_is_listed_as_unknown() {
+        let state = StateValidationResult {
+            object_id: "sshd_config".to_string(),
+            state_results: vec![
+                FieldValidationResult {
+                    field_name: "mode".to_string(),
+                    expected_value: ResolvedValue::String("0600".to_string()),
+                    actual_value: ResolvedValue::String("0600".to_string()),
+                    operation: Operation::Equals,
+                    passed: true,
+                    message: String::new(),
+                    outcome: FieldOutcome::Pass,
+                },
+                FieldValidationResult::not_present(
+                    "owner",
+                    ResolvedValue::String("root".to_string()),
+                    Operation::Equals,
+                    "Field 'owner' not collected",
+                ),
+            ],
+            combined_result: false,
+            state_operator: None,
+            message: String::new(),
+        };
+
+        let evidence = CriterionEvidence::new("file_metadata#1", "fail", &[state]);
+        let unknown: Vec<_> = evidence.unknown_fields().collect();
+        assert_eq!(unknown.len(), 1);
+        assert_eq!(unknown[0].0, "sshd_config");
+        assert_eq!(unknown[0].1.field, "owner");
+        assert_eq!(unknown[0].1.actual, None);
+
+        let table = evidence.to_table();
+        assert!(table.contains("(not present)"));
+        assert!(table.contains("unknown"));
+        assert_eq!(
+            evidence.items[0].fields[0].actual.as_deref(),
+            Some("\"0600\"")
+        );
+    }
+
+    #[test]
+    fn test_observed_fields_keep_failures_and_truncate_on_char_boundaries() {
+        let field = |name: &str, expected, actual, passed| FieldValidationResult {
+            field_name: name.to_string(),
+            expected_value: expected,
+            actual_value: actual,
+            operation: Operation::LessThanOrEqual,
+            passed,
+            message: String::new(),
+            outcome: if passed {
+                FieldOutcome::Pass
+            } else {
+                FieldOutcome::Fail
+            },
+        };
+        let state = StateValidationResult {
+            object_id: "login_defs".to_string(),
+            state_results: vec![
+                field(
+                    "PASS_MAX_DAYS",
+                    ResolvedValue::Integer(60),
+                    ResolvedValue::Integer(99999),
+                    false,
+                ),
+                field(
+                    "PASS_MIN_DAYS",
+                    ResolvedValue::Integer(7),
+                    ResolvedValue::Integer(1),
+                    true,
+                ),
+                field(
+                    "banner",
+                    ResolvedValue::String("ok".to_string()),
+                    ResolvedValue::String("é".repeat(8)),
+                    false,
+                ),
+            ],
+            combined_result: false,
+            state_operator: None,
+            message: String::new(),
+        };
+
+        let observed = observed_fields(&[state], 5);
+        assert_eq!(observed.len(), 2);
+        let max_days = &observed["login_defs/PASS_MAX_DAYS"];
+        assert_eq!(
+            max_days.to_string(),
+            "expected PASS_MAX_DAYS <= 60, observed 99999"
+        );
+        assert!(!max_days.truncated);
+        let banner = &observed["login_defs/banner"];
+        assert_eq!(banner.observed.as_deref(), Some("éé"));
+        assert!(banner.truncated);
+        let json = serde_json::to_value(max_days).unwrap();
+        assert!(json.get("truncated").is_none());
+    }
+}
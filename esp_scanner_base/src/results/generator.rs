@@ -7,6 +7,7 @@ use crate::results::{
     ComplianceStatus as ResultComplianceStatus, FindingSeverity, ResultGenerationError, ScanResult,
 };
 use crate::strategies::ComplianceStatus;
+use std::collections::BTreeMap;
 /// Result generator for converting execution results to SIEM-compatible format
 pub struct ResultGenerator;
 impl ResultGenerator {
@@ -44,7 +45,8 @@ impl ResultGenerator {
             description,
             expected,
             actual,
-        );
+        )
+        .with_policy_severity(ctn_result.severity);
 
         // Add field path if we can construct one from state results
         if let Some(path) = Self::construct_field_path(ctn_result) {
@@ -66,6 +68,8 @@ impl ResultGenerator {
             ComplianceStatus::Fail => FindingSeverity::High,
             ComplianceStatus::Error => FindingSeverity::Critical,
             ComplianceStatus::Unknown => FindingSeverity::Medium,
+            ComplianceStatus::NotApplicable => FindingSeverity::Info,
+            ComplianceStatus::Waived => FindingSeverity::High,
         }
     }
 
@@ -165,6 +169,8 @@ impl ResultGenerator {
                 ComplianceStatus::Fail => stats.failed += 1,
                 ComplianceStatus::Error => stats.errors += 1,
                 ComplianceStatus::Unknown => stats.unknown += 1,
+                ComplianceStatus::NotApplicable => stats.not_applicable += 1,
+                ComplianceStatus::Waived => stats.waived += 1,
             }
 
             stats.total_execution_time_ms += ctn_result.execution_time_ms;
@@ -175,11 +181,9 @@ impl ResultGenerator {
 
     /// Build compliance check structure from statistics
     pub fn build_compliance_check(stats: &ComplianceStatistics) -> ComplianceCheck {
-        let pass_percentage = if stats.total_criteria > 0 {
-            (stats.passed as f32 / stats.total_criteria as f32) * 100.0
-        } else {
-            0.0
-        };
+        let applicable = stats.total_criteria - stats.not_applicable;
+        let pass_percentage =
+            (applicable > 0).then(|| (stats.passed as f32 / applicable as f32) * 100.0);
 
         let status = if stats.errors > 0 {
             ResultComplianceStatus::Partial
@@ -194,6 +198,12 @@ impl ResultGenerator {
             passed_criteria: stats.passed,
             failed_criteria: stats.failed,
             error_criteria: stats.errors,
+            not_evaluated_criteria: stats.unknown,
+            not_applicable_criteria: stats.not_applicable,
+            waived_criteria: stats.waived,
+            path_denied_criteria: 0,
+            vacuous_pass_criteria: 0,
+            failed_by_severity: BTreeMap::new(),
             pass_percentage,
             status,
         }
@@ -205,7 +215,8 @@ impl ResultGenerator {
         findings: Vec<ComplianceFinding>,
     ) -> ComplianceResults {
         let stats = Self::generate_statistics(ctn_results);
-        let check = Self::build_compliance_check(&stats);
+        let mut check = Self::build_compliance_check(&stats);
+        check.count_failures_by_severity(&findings);
         let passed = stats.failed == 0 && stats.errors == 0;
 
         ComplianceResults {
@@ -223,6 +234,8 @@ pub struct ComplianceStatistics {
     pub failed: u32,
     pub errors: u32,
     pub unknown: u32,
+    pub not_applicable: u32,
+    pub waived: u32,
     pub total_execution_time_ms: u64,
 }
 impl ComplianceStatistics {
@@ -0,0 +1,391 @@
+//! # JUnit XML Report
+//!
+//! Scan results as JUnit XML for CI systems that only read test reports.
+//! Each policy file is a `<testsuite>` and each criterion it evaluated a
+//! `<testcase>` named by its criterion id (`<type>#<node>`), with the policy's
+//! `esp_scan_id` as class name.
+//!
+//! Failed criteria carry a `<failure>` with the finding message, criteria
+//! whose collection or evaluation errored an `<error>`, and criteria that were
+//! not evaluated, do not apply or whose outcome is unknown are `<skipped>`. Vacuous passes
+//! pass, with the reason in `<system-out>`.
+
+use super::csv::status_from_finding;
+use super::types::{ComplianceFinding, ScanResult};
+use crate::types::execution_plan::PhaseKind;
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// A report with one test suite for `result`, scanned from the policy file
+/// `policy`
+pub fn report(result: &ScanResult, policy: &str) -> String {
+    batch_report([(policy, result)])
+}
+
+/// [`report`] for a batch scan: one test suite per policy file
+pub fn batch_report<'a>(results: impl IntoIterator<Item = (&'a str, &'a ScanResult)>) -> String {
+    let suites: Vec<Suite> = results
+        .into_iter()
+        .map(|(policy, result)| Suite::new(policy, result))
+        .collect();
+    let total = |count: fn(&Suite) -> usize| suites.iter().map(count).sum::<usize>();
+    let time: f64 = suites.iter().map(|s| s.result.duration_seconds()).sum();
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        xml,
+        "<testsuites name=\"esp-scanner\" tests=\"{}\" failures=\"{}\" errors=\"{}\" skipped=\"{}\" time=\"{:.3}\">",
+        total(|s| s.cases.len()),
+        total(|s| s.count(Outcome::Failure)),
+        total(|s| s.count(Outcome::Error)),
+        total(|s| s.count(Outcome::Skipped)),
+        time
+    );
+    for suite in &suites {
+        suite.write(&mut xml);
+    }
+    xml.push_str("</testsuites>\n");
+    xml
+}
+
+/// How a test case ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Outcome {
+    Pass,
+    Failure,
+    Error,
+    Skipped,
+}
+
+impl Outcome {
+    fn from_status(status: &str) -> Self {
+        match status {
+            "pass" => Self::Pass,
+            "fail" => Self::Failure,
+            "error" => Self::Error,
+            _ => Self::Skipped,
+        }
+    }
+}
+
+/// One criterion and the findings it produced
+struct Case<'a> {
+    criterion_id: &'a str,
+    status: &'a str,
+    findings: Vec<&'a ComplianceFinding>,
+}
+
+impl Case<'_> {
+    fn outcome(&self) -> Outcome {
+        Outcome::from_status(self.status)
+    }
+}
+
+/// The test cases of one policy
+struct Suite<'a> {
+    policy: &'a str,
+    result: &'a ScanResult,
+    cases: Vec<Case<'a>>,
+}
+
+impl<'a> Suite<'a> {
+    /// Criteria in plan order, then any criterion only known from a finding
+    fn new(policy: &'a str, result: &'a ScanResult) -> Self {
+        let evidence_status: HashMap<&str, &str> = result
+            .evidence
+            .iter()
+            .map(|e| (e.criterion_id.as_str(), e.status.as_str()))
+            .collect();
+
+        let mut cases: Vec<Case<'a>> = result
+            .plan
+            .phase(PhaseKind::Evaluate)
+            .iter()
+            .map(|id| Case {
+                criterion_id: id.as_str(),
+                status: evidence_status.get(id.as_str()).copied().unwrap_or("pass"),
+                findings: Vec::new(),
+            })
+            .collect();
+        for finding in &result.results.findings {
+            let criterion_id = finding.criterion_id.as_deref().unwrap_or("unknown");
+            let status = evidence_status
+                .get(criterion_id)
+                .copied()
+                .unwrap_or_else(|| status_from_finding(finding));
+            match cases.iter_mut().find(|c| c.criterion_id == criterion_id) {
+                Some(case) => {
+                    if case.findings.is_empty() {
+                        case.status = status;
+                    }
+                    case.findings.push(finding);
+                }
+                None => cases.push(Case {
+                    criterion_id,
+                    status,
+                    findings: vec![finding],
+                }),
+            }
+        }
+        Self {
+            policy,
+            result,
+            cases,
+        }
+    }
+
+    fn count(&self, outcome: Outcome) -> usize {
+        self.cases.iter().filter(|c| c.outcome() == outcome).count()
+    }
+
+    fn write(&self, xml: &mut String) {
+        let metadata = &self.result.metadata;
+        let _ = writeln!(
+            xml,
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"{}\" skipped=\"{}\" time=\"{:.3}\" timestamp=\"{}\" hostname=\"{}\">",
+            escape(self.policy),
+            self.cases.len(),
+            self.count(Outcome::Failure),
+            self.count(Outcome::Error),
+            self.count(Outcome::Skipped),
+            self.result.duration_seconds(),
+            metadata.timestamp.scan_start.format("%Y-%m-%dT%H:%M:%S"),
+            escape(&metadata.host.hostname)
+        );
+        let classname = escape(&metadata.esp_metadata.esp_scan_id);
+        for case in &self.cases {
+            let open = format!(
+                "    <testcase name=\"{}\" classname=\"{}\"",
+                escape(case.criterion_id),
+                classname
+            );
+            let element = match case.outcome() {
+                Outcome::Failure => "failure",
+                Outcome::Error => "error",
+                Outcome::Skipped => "skipped",
+                Outcome::Pass => {
+                    match case.findings.iter().find(|f| f.vacuous) {
+                        Some(vacuous) => {
+                            let _ = writeln!(xml, "{}>", open);
+                            let _ = writeln!(
+                                xml,
+                                "      <system-out>Vacuous pass: {}</system-out>",
+                                escape(&vacuous.description)
+                            );
+                            xml.push_str("    </testcase>\n");
+                        }
+                        None => {
+                            let _ = writeln!(xml, "{}/>", open);
+                        }
+                    }
+                    continue;
+                }
+            };
+
+            let _ = writeln!(xml, "{}>", open);
+            let first = case.findings.first();
+            let message = first.map_or(case.status, |f| f.title.as_str());
+            let kind = first.map_or(case.status, |f| f.severity.as_str());
+            let details: Vec<String> = case
+                .findings
+                .iter()
+                .map(|f| details(f))
+                .filter(|d| !d.is_empty())
+                .collect();
+            if details.is_empty() {
+                let _ = writeln!(
+                    xml,
+                    "      <{} message=\"{}\" type=\"{}\"/>",
+                    element,
+                    escape(message),
+                    escape(kind)
+                );
+            } else {
+                let _ = writeln!(
+                    xml,
+                    "      <{} message=\"{}\" type=\"{}\">{}</{}>",
+                    element,
+                    escape(message),
+                    escape(kind),
+                    escape(&details.join("\n\n")),
+                    element
+                );
+            }
+            xml.push_str("    </testcase>\n");
+        }
+        xml.push_str("  </testsuite>\n");
+    }
+}
+
+/// Body of a failure or error: what was found, expected and how to fix it
+fn details(finding: &ComplianceFinding) -> String {
+    let mut text = finding.description.clone();
+    for (label, value) in [("expected", &finding.expected), ("actual", &finding.actual)] {
+        if value.as_object().is_some_and(|m| !m.is_empty()) {
+            let _ = write!(text, "\n{}: {}", label, value);
+        }
+    }
+    if let Some(remediation) = &finding.remediation {
+        let _ = write!(text, "\nremediation: {}", remediation);
+    }
+    if !finding.controls.is_empty() {
+        let controls: Vec<String> = finding.controls.iter().map(|c| c.to_string()).collect();
+        let _ = write!(text, "\ncontrols: {}", controls.join("; "));
+    }
+    text
+}
+
+/// Escape text for an XML attribute or element, dropping characters XML 1.0
+/// cannot represent
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' | '\n' | '\r' => escaped.push(c),
+            c if c < ' ' || c == '\u{FFFE}' || c == '\u{FFFF}' => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::results::{EspMetadata, FindingSeverity, HostContext, UserContext};
+    use crate::types::execution_plan::ExecutionPlan;
+
+    /// Element names in document order, checking every element is closed in
+    /// order, so the report is checked for well-formedness without a parser
+    fn elements(xml: &str) -> Vec<String> {
+        let mut names = Vec::new();
+        let mut open: Vec<String> = Vec::new();
+        for tag in xml.split('<').skip(1) {
+            let tag = tag.split('>').next().unwrap();
+            if tag.starts_with('?') {
+                continue;
+            }
+            if let Some(name) = tag.strip_prefix('/') {
+                assert_eq!(open.pop().as_deref(), Some(name), "unbalanced </{}>", name);
+                continue;
+            }
+            let name = tag.split_whitespace().next().unwrap().trim_end_matches('/');
+            names.push(name.to_string());
+            if !tag.ends_with('/') {
+                open.push(name.to_string());
+            }
+        }
+        assert!(open.is_empty(), "unclosed {:?}", open);
+        names
+    }
+
+    fn scan_result(esp_scan_id: &str) -> ScanResult {
+        let mut metadata = EspMetadata::default_test();
+        metadata.esp_scan_id = esp_scan_id.to_string();
+        let mut result = ScanResult::new(
+            "scan-1".to_string(),
+            metadata,
+            HostContext::new("web-01".to_string(), "linux".to_string()),
+            UserContext::new("root".to_string(), "root".to_string()),
+        );
+        result.plan = ExecutionPlan::new(
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+            [
+                "file_content#2",
+                "sysctl_parameter#3",
+                "file_metadata#4",
+                "file_metadata#5",
+            ]
+            .map(String::from)
+            .to_vec(),
+        );
+        let finding = |severity, criterion_id: &str, description: &str| {
+            ComplianceFinding::new(
+                format!("f-{}", criterion_id),
+                severity,
+                format!("{} validation failed", criterion_id),
+                description.to_string(),
+                serde_json::json!({"file_content": "PermitRootLogin no"}),
+                serde_json::json!({"file_content": "<PermitRootLogin \"yes\">"}),
+            )
+            .with_criterion_id(criterion_id.to_string())
+        };
+        result.add_finding(finding(
+            FindingSeverity::High,
+            "file_content#2",
+            "root login & password auth\u{1}",
+        ));
+        result.add_finding(finding(
+            FindingSeverity::Critical,
+            "sysctl_parameter#3",
+            "collector failed",
+        ));
+        result.add_finding(
+            finding(FindingSeverity::Low, "file_metadata#5", "parent missing").with_vacuous(true),
+        );
+        result
+    }
+
+    #[test]
+    fn test_criteria_become_test_cases_with_failures_and_errors() {
+        let result = scan_result("sshd");
+        let xml = result.to_junit_xml("policies/sshd.esp");
+        assert_eq!(
+            elements(&xml),
+            [
+                "testsuites",
+                "testsuite",
+                "testcase",
+                "failure",
+                "testcase",
+                "error",
+                "testcase",
+                "testcase",
+                "system-out"
+            ]
+        );
+        assert!(xml.contains(
+            r#"<testsuites name="esp-scanner" tests="4" failures="1" errors="1" skipped="0""#
+        ));
+        assert!(xml.contains(r#"<testsuite name="policies/sshd.esp" tests="4""#));
+        assert!(xml.contains(r#"<testcase name="file_content#2" classname="sshd">"#));
+        assert!(xml.contains(r#"<failure message="file_content#2 validation failed" type="high">root login &amp; password auth"#));
+        assert!(xml.contains(r#"actual: {&quot;file_content&quot;:&quot;&lt;PermitRootLogin \&quot;yes\&quot;&gt;&quot;}"#));
+        assert!(!xml.contains('\u{1}'));
+        assert!(xml.contains(r#"<error message="sysctl_parameter#3 validation failed" type="critical">collector failed"#));
+        assert!(xml.contains(r#"<testcase name="file_metadata#4" classname="sshd"/>"#));
+        assert!(xml.contains("<system-out>Vacuous pass: parent missing</system-out>"));
+    }
+
+    #[test]
+    fn test_batch_has_a_suite_per_policy() {
+        let mut skipped = scan_result("sysctl");
+        skipped.results.findings.clear();
+        skipped.add_finding(
+            ComplianceFinding::new(
+                "f-1".to_string(),
+                FindingSeverity::Info,
+                "file_content#2 not evaluated".to_string(),
+                String::new(),
+                serde_json::json!({}),
+                serde_json::json!({}),
+            )
+            .with_criterion_id("file_content#2".to_string()),
+        );
+        let first = scan_result("sshd");
+
+        let xml = batch_report([("a.esp", &first), ("b.esp", &skipped)]);
+        let names = elements(&xml);
+        assert_eq!(names.iter().filter(|n| *n == "testsuite").count(), 2);
+        assert!(xml.contains(r#"tests="8" failures="1" errors="1" skipped="1""#));
+        assert!(xml.contains(r#"<skipped message="file_content#2 not evaluated" type="info"/>"#));
+    }
+}
@@ -8,6 +8,19 @@
 //! - [`ScanResult`] - Complete scan result for one ESP definition
 //! - [`ComplianceFinding`] - Individual compliance violations
 //! - [`ScanMetadata`] - Metadata about scan execution and ESP definition
+//! - [`AnnotationSet`] - Reviewer dispositions attached to findings after a scan
+//! - [`ScanPerformance`] - Per-criterion-type strategy usage recorded during a scan
+//! - [`CriterionEvidence`] - Fields each criterion compared, when field evidence is enabled
+//! - [`Advisory`] - Informational notes from executors that never affect scoring
+//! - [`ControlMap`] - Benchmark controls per criterion from a control map file
+//! - [`WaiverSet`] - Approved exceptions for failing criteria, with expiry dates
+//! - [`ScanResult::semantically_equals`] - Order- and float-tolerant comparison for golden files
+//! - [`ScanResult::diff`] - Criteria that regressed, recovered, appeared or disappeared since a previous scan
+//! - [`TargetInfo`] - Host, OS release, kernel and scan times a result came from
+//! - [`ScanProvenance`] - Policy, compiled artifact and strategy set hashes a result was scanned with
+//! - [`csv::write_findings`] - Findings flattened to CSV rows for spreadsheets
+//! - [`sarif::log`] - Findings and passing criteria as a SARIF 2.1.0 log
+//! - [`junit::report`] - Criteria as JUnit XML test cases for CI systems
 //! - [`ResultGenerationError`] - Errors that occur during result processing
 //!
 //! ## Usage
@@ -23,14 +36,38 @@
 //! );
 //! ```
 
+pub mod advisory;
+pub mod annotations;
+pub mod compare;
+pub mod controls;
+pub mod csv;
+pub mod diff;
 pub mod error;
+pub mod evidence;
 pub mod generator;
+pub mod junit;
+pub mod performance;
+pub mod provenance;
+pub mod sarif;
+pub mod target;
 pub mod types;
+pub mod waivers;
 
 // Re-export all public types for convenient access
+pub use advisory::Advisory;
+pub use annotations::{
+    AnnotatedFinding, AnnotatedScanResult, Annotation, AnnotationSet, Disposition,
+};
+pub use controls::{Control, ControlMap, ControlSummary};
+pub use diff::{CriterionOutcome, ScanDiff, StatusChange, ValueChange};
 pub use error::*;
+pub use evidence::{CriterionEvidence, FieldEvidence, ItemEvidence, ObservedField};
 pub use generator::ResultGenerator;
+pub use performance::{CollectionCacheStats, ScanPerformance, StrategyUsage};
+pub use provenance::ScanProvenance;
+pub use target::{OsRelease, TargetInfo};
 pub use types::*;
+pub use waivers::{Waiver, WaiverMatch, WaiverSet, WaiverTarget};
 
 // Future module stubs for planned functionality
 // pub mod formatters;  // Output format conversion (XML, CSV, etc.)
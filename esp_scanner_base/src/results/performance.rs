@@ -0,0 +1,189 @@
+//! # Scan Performance
+//!
+//! Runtime strategy usage collected while a scan executes: which registered
+//! strategies were exercised, how often, and how long collection and
+//! evaluation took, plus the criterion types that had no strategy at all, and
+//! how often the collection cache served a collection.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Per-scan strategy usage, keyed by criterion type
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ScanPerformance {
+    /// Usage of each registered strategy that was invoked
+    #[serde(default)]
+    pub strategies: BTreeMap<String, StrategyUsage>,
+
+    /// Criterion types in the policy with no registered strategy
+    #[serde(default)]
+    pub unmatched_criterion_types: Vec<String>,
+
+    /// Collections served from, or added to, the per-scan collection cache
+    #[serde(default)]
+    pub collection_cache: CollectionCacheStats,
+}
+
+/// Use of the per-scan collection cache
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CollectionCacheStats {
+    /// Collections served from the cache
+    pub hits: u64,
+    /// Cacheable collections that had to run
+    pub misses: u64,
+    /// Entries dropped to stay within the cache bounds
+    pub evictions: u64,
+    /// Entries held when the scan ended
+    pub entries: u64,
+    /// Estimated bytes held when the scan ended
+    pub bytes: u64,
+}
+
+/// Usage of one criterion type's strategy during a scan
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StrategyUsage {
+    /// Collector that served this criterion type
+    pub strategy: String,
+    pub invocations: u32,
+    /// Invocations that ended in an error result
+    pub errors: u32,
+    pub total_collection_ms: f64,
+    pub total_evaluation_ms: f64,
+    pub avg_collection_ms: f64,
+    pub avg_evaluation_ms: f64,
+}
+
+impl StrategyUsage {
+    pub fn new(strategy: impl Into<String>) -> Self {
+        Self {
+            strategy: strategy.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Account for one criterion execution
+    pub fn record(&mut self, collection_ms: f64, evaluation_ms: f64, error: bool) {
+        self.invocations += 1;
+        if error {
+            self.errors += 1;
+        }
+        self.total_collection_ms += collection_ms;
+        self.total_evaluation_ms += evaluation_ms;
+        self.avg_collection_ms = self.total_collection_ms / f64::from(self.invocations);
+        self.avg_evaluation_ms = self.total_evaluation_ms / f64::from(self.invocations);
+    }
+}
+
+impl ScanPerformance {
+    pub fn is_empty(&self) -> bool {
+        self.strategies.is_empty() && self.unmatched_criterion_types.is_empty()
+    }
+
+    /// Record a criterion type that has no registered strategy (deduplicated)
+    pub fn record_unmatched(&mut self, criterion_type: &str) {
+        if let Err(pos) = self
+            .unmatched_criterion_types
+            .binary_search_by(|t| t.as_str().cmp(criterion_type))
+        {
+            self.unmatched_criterion_types
+                .insert(pos, criterion_type.to_string());
+        }
+    }
+
+    /// Fold another scan's usage into this one (batch scans)
+    pub fn merge(&mut self, other: &ScanPerformance) {
+        for (ctn_type, usage) in &other.strategies {
+            let entry = self
+                .strategies
+                .entry(ctn_type.clone())
+                .or_insert_with(|| StrategyUsage::new(usage.strategy.clone()));
+            entry.invocations += usage.invocations;
+            entry.errors += usage.errors;
+            entry.total_collection_ms += usage.total_collection_ms;
+            entry.total_evaluation_ms += usage.total_evaluation_ms;
+            if entry.invocations > 0 {
+                entry.avg_collection_ms = entry.total_collection_ms / f64::from(entry.invocations);
+                entry.avg_evaluation_ms = entry.total_evaluation_ms / f64::from(entry.invocations);
+            }
+        }
+        for ctn_type in &other.unmatched_criterion_types {
+            self.record_unmatched(ctn_type);
+        }
+        let cache = &mut self.collection_cache;
+        cache.hits += other.collection_cache.hits;
+        cache.misses += other.collection_cache.misses;
+        cache.evictions += other.collection_cache.evictions;
+        cache.entries += other.collection_cache.entries;
+        cache.bytes += other.collection_cache.bytes;
+    }
+
+    /// Plain-text table for `--strategy-report`
+    pub fn to_table(&self) -> String {
+        let mut out = format!(
+            "{:<24} {:<28} {:>6} {:>6} {:>12} {:>12}\n",
+            "CRITERION TYPE", "STRATEGY", "CALLS", "ERRORS", "AVG COLL ms", "AVG EVAL ms"
+        );
+        for (ctn_type, usage) in &self.strategies {
+            out.push_str(&format!(
+                "{:<24} {:<28} {:>6} {:>6} {:>12.2} {:>12.2}\n",
+                ctn_type,
+                usage.strategy,
+                usage.invocations,
+                usage.errors,
+                usage.avg_collection_ms,
+                usage.avg_evaluation_ms
+            ));
+        }
+        if !self.unmatched_criterion_types.is_empty() {
+            out.push_str(&format!(
+                "\nNo strategy registered: {}\n",
+                self.unmatched_criterion_types.join(", ")
+            ));
+        }
+        let cache = &self.collection_cache;
+        if cache.hits + cache.misses > 0 {
+            out.push_str(&format!(
+                "\nCollection cache: {} hits, {} misses, {} evictions\n",
+                cache.hits, cache.misses, cache.evictions
+            ));
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_updates_averages() {
+        let mut usage = StrategyUsage::new("filesystem_collector");
+        usage.record(2.0, 1.0, false);
+        usage.record(4.0, 3.0, true);
+
+        assert_eq!(usage.invocations, 2);
+        assert_eq!(usage.errors, 1);
+        assert_eq!(usage.avg_collection_ms, 3.0);
+        assert_eq!(usage.avg_evaluation_ms, 2.0);
+    }
+
+    #[test]
+    fn test_merge_and_unmatched_are_deduplicated() {
+        let mut a = ScanPerformance::default();
+        a.strategies
+            .entry("file_metadata".to_string())
+            .or_insert_with(|| StrategyUsage::new("filesystem_collector"))
+            .record(1.0, 1.0, false);
+        a.record_unmatched("ldap_bind");
+
+        let mut total = ScanPerformance::default();
+        total.merge(&a);
+        total.merge(&a);
+
+        assert_eq!(total.strategies["file_metadata"].invocations, 2);
+        assert_eq!(total.unmatched_criterion_types, vec!["ldap_bind"]);
+        assert!(total
+            .to_table()
+            .contains("No strategy registered: ldap_bind"));
+    }
+}
@@ -0,0 +1,291 @@
+//! # Scan Provenance
+//!
+//! Hashes tying a scan result to the policy and scanner that produced it:
+//! the policy file as read, the compiled AST it executed and the set of
+//! strategies registered. Together with the scan timestamp they let a saved
+//! result be checked against a policy before it is reported or compared, so
+//! an old result is not mistaken for a current one.
+//!
+//! ```rust
+//! use esp_scanner_base::results::provenance::{self, ScanProvenance};
+//!
+//! let scanned = ScanProvenance::new(provenance::sha256(b"policy v1"), "sha256:ast");
+//! let current = ScanProvenance::new(provenance::sha256(b"policy v2"), "sha256:ast");
+//! // Only comments or layout changed: the compiled policy is the same
+//! assert!(!scanned.same_source(&current));
+//! assert!(scanned.same_policy(&current));
+//! ```
+
+use super::types::ScanResult;
+use chrono::{DateTime, Duration, Utc};
+use esp_compiler::grammar::ast::{AstDocument, EspFile};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fmt;
+
+/// Age after which `verify-result` warns that a result is stale
+pub const DEFAULT_MAX_AGE: &str = "7d";
+
+/// Hex SHA-256 of `bytes`, prefixed with the algorithm
+pub fn sha256(bytes: &[u8]) -> String {
+    format!("sha256:{:x}", Sha256::digest(bytes))
+}
+
+/// Where a scan result came from
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScanProvenance {
+    /// Hash of the policy file as read (ESP source or AST document)
+    pub policy_hash: String,
+    /// Hash of the compiled AST document the scan executed; unchanged by
+    /// comments and layout
+    pub artifact_hash: String,
+    /// Hash of the criterion types, collectors and contracts registered
+    pub strategy_set_hash: String,
+}
+
+impl ScanProvenance {
+    pub fn new(policy_hash: impl Into<String>, artifact_hash: impl Into<String>) -> Self {
+        Self {
+            policy_hash: policy_hash.into(),
+            artifact_hash: artifact_hash.into(),
+            strategy_set_hash: String::new(),
+        }
+    }
+
+    /// Provenance of a policy file and the AST compiled from it
+    pub fn for_policy(source: &[u8], ast: &EspFile) -> Self {
+        let artifact = AstDocument::new(ast.clone()).to_json().unwrap_or_default();
+        Self::new(sha256(source), sha256(artifact.as_bytes()))
+    }
+
+    pub fn with_strategy_set_hash(mut self, strategy_set_hash: impl Into<String>) -> Self {
+        self.strategy_set_hash = strategy_set_hash.into();
+        self
+    }
+
+    /// Whether policy hashes were recorded; results saved before provenance
+    /// existed deserialize with empty hashes
+    pub fn is_recorded(&self) -> bool {
+        !self.policy_hash.is_empty() && !self.artifact_hash.is_empty()
+    }
+
+    /// The policy file is byte for byte the same
+    pub fn same_source(&self, other: &ScanProvenance) -> bool {
+        self.policy_hash == other.policy_hash
+    }
+
+    /// The policy compiles to the same AST, whatever its layout
+    pub fn same_policy(&self, other: &ScanProvenance) -> bool {
+        self.artifact_hash == other.artifact_hash
+    }
+}
+
+/// Something found checking a result against a policy
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProvenanceIssue {
+    /// The result records no policy hashes
+    Unrecorded,
+    /// The policy compiles differently from the one scanned
+    PolicyChanged { scanned: String, current: String },
+    /// The policy file changed but compiles to the same AST
+    SourceChanged,
+    /// The scan is older than allowed
+    Stale { age: Duration, max_age: Duration },
+    /// The scan ran under a fixed clock, so its age is unknown
+    FixedClock,
+}
+
+impl ProvenanceIssue {
+    /// Whether the result cannot be taken as a report on the policy
+    pub fn is_error(&self) -> bool {
+        matches!(self, Self::Unrecorded | Self::PolicyChanged { .. })
+    }
+}
+
+impl fmt::Display for ProvenanceIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unrecorded => write!(f, "result records no policy hash"),
+            Self::PolicyChanged { scanned, current } => write!(
+                f,
+                "policy changed since the scan (scanned {}, now {})",
+                scanned, current
+            ),
+            Self::SourceChanged => write!(
+                f,
+                "policy file changed since the scan but compiles to the same policy"
+            ),
+            Self::Stale { age, max_age } => write!(
+                f,
+                "scan is {} old, older than {}",
+                describe_age(*age),
+                describe_age(*max_age)
+            ),
+            Self::FixedClock => write!(f, "scan ran under a fixed clock; its age is unknown"),
+        }
+    }
+}
+
+/// Parse an age such as `7d`, `12h` or `30m`
+pub fn parse_age(value: &str) -> Result<Duration, String> {
+    let invalid = || format!("invalid age '{}': expected e.g. 7d, 12h or 30m", value);
+    let unit_start = value.char_indices().last().ok_or_else(invalid)?.0;
+    let (count, unit) = value.split_at(unit_start);
+    let count: i64 = count.parse().map_err(|_| invalid())?;
+    match unit {
+        "d" => Ok(Duration::days(count)),
+        "h" => Ok(Duration::hours(count)),
+        "m" => Ok(Duration::minutes(count)),
+        _ => Err(invalid()),
+    }
+}
+
+/// An age in the largest whole unit, e.g. `12 days`
+fn describe_age(age: Duration) -> String {
+    let (count, unit) = if age.num_days() > 0 {
+        (age.num_days(), "day")
+    } else if age.num_hours() > 0 {
+        (age.num_hours(), "hour")
+    } else {
+        (age.num_minutes(), "minute")
+    };
+    format!("{} {}{}", count, unit, if count == 1 { "" } else { "s" })
+}
+
+impl ScanResult {
+    /// Check that this result reports on `policy` and is no older than
+    /// `max_age` at `now`
+    pub fn verify_provenance(
+        &self,
+        policy: &ScanProvenance,
+        max_age: Duration,
+        now: DateTime<Utc>,
+    ) -> Vec<ProvenanceIssue> {
+        let mut issues = Vec::new();
+        let scanned = &self.metadata.provenance;
+        if !scanned.is_recorded() {
+            issues.push(ProvenanceIssue::Unrecorded);
+        } else if !scanned.same_policy(policy) {
+            issues.push(ProvenanceIssue::PolicyChanged {
+                scanned: scanned.artifact_hash.clone(),
+                current: policy.artifact_hash.clone(),
+            });
+        } else if !scanned.same_source(policy) {
+            issues.push(ProvenanceIssue::SourceChanged);
+        }
+
+        if self.metadata.timestamp.fixed_clock {
+            issues.push(ProvenanceIssue::FixedClock);
+        } else {
+            let age = now - self.metadata.timestamp.scan_start;
+            if age > max_age {
+                issues.push(ProvenanceIssue::Stale { age, max_age });
+            }
+        }
+        issues
+    }
+
+    /// Why two results should not be compared: they were scanned from
+    /// different policies. `None` when the policies match or either result
+    /// records none.
+    pub fn policy_mismatch(&self, other: &ScanResult) -> Option<String> {
+        let (ours, theirs) = (&self.metadata.provenance, &other.metadata.provenance);
+        if !ours.is_recorded() || !theirs.is_recorded() || ours.same_policy(theirs) {
+            return None;
+        }
+        Some(format!(
+            "results were scanned from different policies ({} and {})",
+            ours.artifact_hash, theirs.artifact_hash
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::execution::ScanClock;
+    use crate::results::{EspMetadata, HostContext, UserContext};
+
+    fn scanned(provenance: ScanProvenance, clock: &ScanClock) -> ScanResult {
+        let mut result = ScanResult::new_at(
+            "scan-1".to_string(),
+            EspMetadata::default_test(),
+            HostContext::new("web-01".to_string(), "linux".to_string()),
+            UserContext::new("root".to_string(), "root".to_string()),
+            clock,
+        );
+        result.metadata.provenance = provenance;
+        result
+    }
+
+    #[test]
+    fn test_changed_policy_is_an_error_and_reformatting_is_not() {
+        let policy = ScanProvenance::new(sha256(b"v1"), sha256(b"ast v1"));
+        let result = scanned(policy.clone(), &ScanClock::system());
+        let now = Utc::now();
+        let week = Duration::days(7);
+        assert!(result.verify_provenance(&policy, week, now).is_empty());
+
+        let reformatted = ScanProvenance::new(sha256(b"v1 "), sha256(b"ast v1"));
+        let issues = result.verify_provenance(&reformatted, week, now);
+        assert_eq!(issues, vec![ProvenanceIssue::SourceChanged]);
+        assert!(!issues[0].is_error());
+
+        let changed = ScanProvenance::new(sha256(b"v2"), sha256(b"ast v2"));
+        let issues = result.verify_provenance(&changed, week, now);
+        assert!(issues[0].is_error());
+        assert!(issues[0]
+            .to_string()
+            .starts_with("policy changed since the scan (scanned sha256:"));
+
+        let legacy = scanned(ScanProvenance::default(), &ScanClock::system());
+        assert_eq!(
+            legacy.verify_provenance(&policy, week, now),
+            vec![ProvenanceIssue::Unrecorded]
+        );
+    }
+
+    #[test]
+    fn test_old_scans_are_stale() {
+        let policy = ScanProvenance::new(sha256(b"v1"), sha256(b"ast v1"));
+        let result = scanned(policy.clone(), &ScanClock::system());
+        let later = Utc::now() + Duration::days(12);
+        let issues = result.verify_provenance(&policy, parse_age("7d").unwrap(), later);
+        assert_eq!(issues.len(), 1);
+        assert!(!issues[0].is_error());
+        assert_eq!(
+            issues[0].to_string(),
+            "scan is 12 days old, older than 7 days"
+        );
+
+        let fixed = scanned(
+            policy.clone(),
+            &ScanClock::parse_fixed("2026-01-01T00:00:00Z").unwrap(),
+        );
+        assert_eq!(
+            fixed.verify_provenance(&policy, Duration::days(7), later),
+            vec![ProvenanceIssue::FixedClock]
+        );
+    }
+
+    #[test]
+    fn test_parse_age_units() {
+        assert_eq!(parse_age("12h"), Ok(Duration::hours(12)));
+        assert_eq!(parse_age("30m"), Ok(Duration::minutes(30)));
+        assert!(parse_age("7").is_err());
+        assert!(parse_age("").is_err());
+    }
+
+    #[test]
+    fn test_mismatch_needs_both_policies_recorded() {
+        let clock = ScanClock::system();
+        let v1 = scanned(ScanProvenance::new("sha256:a", "sha256:ast1"), &clock);
+        let v2 = scanned(ScanProvenance::new("sha256:b", "sha256:ast2"), &clock);
+        let legacy = scanned(ScanProvenance::default(), &clock);
+        assert_eq!(
+            v1.policy_mismatch(&v2).as_deref(),
+            Some("results were scanned from different policies (sha256:ast1 and sha256:ast2)")
+        );
+        assert_eq!(v1.policy_mismatch(&legacy), None);
+    }
+}
@@ -0,0 +1,484 @@
+//! # SARIF Export
+//!
+//! Scan results as a SARIF 2.1.0 log for code scanning dashboards. Every
+//! criterion the scan evaluated becomes a rule, identified as
+//! `<esp_scan_id>/<type>#<node>` so criteria of different policies never share
+//! one, and described by the policy's META title, description and severity.
+//! Every finding becomes a result located at the policy file.
+//!
+//! Criteria without findings are reported as `pass` results, vacuous passes
+//! as `review`, criteria that do not apply to the host as `notApplicable`
+//! and errors or unevaluated criteria as `open`. SARIF only allows a level on
+//! failures, so every other result has level `none`.
+
+use super::csv::status_from_finding;
+use super::types::{ComplianceFinding, FindingSeverity, ScanResult};
+use crate::types::execution_plan::PhaseKind;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+/// SARIF version written
+pub const VERSION: &str = "2.1.0";
+
+/// JSON schema of [`VERSION`]
+pub const SCHEMA_URI: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// Name of the tool in the log
+const TOOL_NAME: &str = "esp-scanner";
+
+/// A SARIF log with one run holding the results of `result`, scanned from
+/// the policy file `policy`
+pub fn log(result: &ScanResult, policy: &str) -> Value {
+    batch_log([(policy, result)])
+}
+
+/// [`log`] for a batch scan: one run with the results of every policy
+pub fn batch_log<'a>(results: impl IntoIterator<Item = (&'a str, &'a ScanResult)>) -> Value {
+    let mut run = RunBuilder::default();
+    for (policy, result) in results {
+        run.add_scan(policy, result);
+    }
+    json!({
+        "$schema": SCHEMA_URI,
+        "version": VERSION,
+        "runs": [run.finish()],
+    })
+}
+
+/// Rules, artifacts and results of the run being built
+#[derive(Default)]
+struct RunBuilder {
+    rules: Vec<Value>,
+    rule_index: HashMap<String, usize>,
+    artifacts: Vec<Value>,
+    results: Vec<Value>,
+}
+
+impl RunBuilder {
+    fn add_scan(&mut self, policy: &str, result: &ScanResult) {
+        let meta = &result.metadata.esp_metadata;
+        let uri = artifact_uri(policy);
+        let artifact = self.artifacts.len();
+        self.artifacts.push(json!({"location": {"uri": uri}}));
+
+        let evidence_status: HashMap<&str, &str> = result
+            .evidence
+            .iter()
+            .map(|e| (e.criterion_id.as_str(), e.status.as_str()))
+            .collect();
+        let location = |criterion_id: &str| {
+            json!([{
+                "physicalLocation": {"artifactLocation": {"uri": uri, "index": artifact}},
+                "logicalLocations": [{
+                    "name": criterion_id,
+                    "fullyQualifiedName": format!("{}/{}", meta.esp_scan_id, criterion_id),
+                }],
+            }])
+        };
+
+        for finding in &result.results.findings {
+            let criterion_id = finding.criterion_id.as_deref().unwrap_or("unknown");
+            let rule_index = self.rule(result, criterion_id);
+            let status = evidence_status
+                .get(criterion_id)
+                .copied()
+                .unwrap_or_else(|| status_from_finding(finding));
+            let (kind, level) = finding_kind(finding, status);
+            let message = if finding.description.is_empty() {
+                &finding.title
+            } else {
+                &finding.description
+            };
+            let mut sarif_result = json!({
+                "ruleId": self.rules[rule_index]["id"],
+                "ruleIndex": rule_index,
+                "kind": kind,
+                "level": level,
+                "message": {"text": message},
+                "locations": location(criterion_id),
+                "partialFingerprints": {
+                    "criterionId/v1": format!("{}/{}", meta.esp_scan_id, criterion_id),
+                },
+                "properties": {
+                    "severity": finding.severity.as_str(),
+                    "status": status,
+                    "expected": finding.expected,
+                    "actual": finding.actual,
+                },
+            });
+            let properties = &mut sarif_result["properties"];
+            if let Some(field_path) = &finding.field_path {
+                properties["fieldPath"] = json!(field_path);
+            }
+            if let Some(remediation) = &finding.remediation {
+                properties["remediation"] = json!(remediation);
+            }
+            if !finding.controls.is_empty() {
+                let controls: Vec<String> =
+                    finding.controls.iter().map(|c| c.to_string()).collect();
+                properties["controls"] = json!(controls);
+            }
+            if let Some(waiver) = &finding.waiver {
+                sarif_result["suppressions"] = json!([{
+                    "kind": "external",
+                    "status": "accepted",
+                    "justification": waiver.justification,
+                }]);
+            }
+            self.results.push(sarif_result);
+        }
+
+        // Criteria evaluated without a finding passed
+        let with_findings: Vec<&str> = result
+            .results
+            .findings
+            .iter()
+            .filter_map(|f| f.criterion_id.as_deref())
+            .collect();
+        for criterion_id in result.plan.phase(PhaseKind::Evaluate) {
+            if with_findings.contains(&criterion_id.as_str()) {
+                continue;
+            }
+            let rule_index = self.rule(result, criterion_id);
+            let status = evidence_status
+                .get(criterion_id.as_str())
+                .copied()
+                .unwrap_or("pass");
+            self.results.push(json!({
+                "ruleId": self.rules[rule_index]["id"],
+                "ruleIndex": rule_index,
+                "kind": if status == "pass" { "pass" } else { "open" },
+                "level": "none",
+                "message": {"text": format!("{} {}", criterion_id, status)},
+                "locations": location(criterion_id),
+                "partialFingerprints": {
+                    "criterionId/v1": format!("{}/{}", meta.esp_scan_id, criterion_id),
+                },
+                "properties": {"status": status},
+            }));
+        }
+    }
+
+    /// Index of the rule for `criterion_id` of `result`, added on first use
+    fn rule(&mut self, result: &ScanResult, criterion_id: &str) -> usize {
+        let meta = &result.metadata.esp_metadata;
+        let id = format!("{}/{}", meta.esp_scan_id, criterion_id);
+        if let Some(&index) = self.rule_index.get(&id) {
+            return index;
+        }
+
+        let criterion_type = criterion_id.split('#').next().unwrap_or(criterion_id);
+        let title = meta
+            .field("title")
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("{} check", criterion_type));
+        let mut rule = json!({
+            "id": id,
+            "name": criterion_type,
+            "shortDescription": {"text": title},
+            "properties": {
+                "policy": meta.esp_scan_id,
+                "criterion": criterion_id,
+                "tags": meta
+                    .tags
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|t| !t.is_empty())
+                    .collect::<Vec<_>>(),
+            },
+        });
+        if let Some(description) = meta.field("description") {
+            rule["fullDescription"] = json!({"text": description});
+        }
+        if let Some(severity) = meta.field("severity") {
+            rule["defaultConfiguration"] = json!({"level": severity_level(severity)});
+            rule["properties"]["severity"] = json!(severity);
+            if let Some(score) = security_severity(severity) {
+                rule["properties"]["security-severity"] = json!(score);
+            }
+        }
+        if !meta.control.is_empty() {
+            rule["properties"]["controls"] =
+                json!([format!("{} {}", meta.control_framework, meta.control)]);
+        }
+
+        let index = self.rules.len();
+        self.rules.push(rule);
+        self.rule_index.insert(id, index);
+        index
+    }
+
+    fn finish(self) -> Value {
+        json!({
+            "tool": {
+                "driver": {
+                    "name": TOOL_NAME,
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": "https://github.com/CurtisSlone/Endpoint-State-Policy",
+                    "rules": self.rules,
+                },
+            },
+            "artifacts": self.artifacts,
+            "results": self.results,
+        })
+    }
+}
+
+/// SARIF kind and level of a finding whose criterion ended with `status`
+fn finding_kind(finding: &ComplianceFinding, status: &str) -> (&'static str, &'static str) {
+    match status {
+        "pass" if finding.vacuous => ("review", "none"),
+        "pass" => ("pass", "none"),
+        "not_applicable" => ("notApplicable", "none"),
+        "fail" | "waived" => (
+            "fail",
+            match finding.severity {
+                FindingSeverity::Critical | FindingSeverity::High => "error",
+                FindingSeverity::Medium => "warning",
+                FindingSeverity::Low | FindingSeverity::Info => "note",
+            },
+        ),
+        _ => ("open", "none"),
+    }
+}
+
+/// Default level for a META severity
+fn severity_level(severity: &str) -> &'static str {
+    match severity.to_ascii_lowercase().as_str() {
+        "critical" | "high" => "error",
+        "low" | "info" | "informational" => "note",
+        _ => "warning",
+    }
+}
+
+/// Numeric severity code scanning dashboards rank alerts by
+fn security_severity(severity: &str) -> Option<&'static str> {
+    match severity.to_ascii_lowercase().as_str() {
+        "critical" => Some("9.0"),
+        "high" => Some("7.0"),
+        "medium" => Some("5.0"),
+        "low" => Some("3.0"),
+        _ => None,
+    }
+}
+
+/// `path` as a relative or absolute URI reference: forward slashes, with
+/// characters outside the URI path set percent-encoded
+fn artifact_uri(path: &str) -> String {
+    let mut uri = String::new();
+    for byte in path.replace('\\', "/").bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~/:@!$&'()*+,;=".contains(&byte) {
+            uri.push(byte as char);
+        } else {
+            uri.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    uri
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::results::{
+        ComplianceFinding, CriterionEvidence, EspMetadata, HostContext, MetadataEntry, UserContext,
+        WaiverSet,
+    };
+    use crate::types::execution_plan::ExecutionPlan;
+
+    const KINDS: [&str; 6] = [
+        "pass",
+        "fail",
+        "review",
+        "open",
+        "informational",
+        "notApplicable",
+    ];
+    const LEVELS: [&str; 4] = ["none", "note", "warning", "error"];
+
+    /// The parts of the SARIF 2.1.0 schema consumers rely on: required
+    /// properties, enumerations and index references
+    fn assert_sarif_shape(log: &Value) {
+        assert_eq!(log["version"], "2.1.0");
+        assert!(log["$schema"]
+            .as_str()
+            .unwrap()
+            .ends_with("sarif-2.1.0.json"));
+        for run in log["runs"].as_array().unwrap() {
+            let driver = &run["tool"]["driver"];
+            assert!(driver["name"].is_string());
+            let rules = driver["rules"].as_array().unwrap();
+            for rule in rules {
+                assert!(rule["id"].is_string());
+                assert!(rule["shortDescription"]["text"].is_string());
+                if let Some(level) = rule.pointer("/defaultConfiguration/level") {
+                    assert!(LEVELS.contains(&level.as_str().unwrap()));
+                }
+            }
+            let artifacts = run["artifacts"].as_array().unwrap();
+            for result in run["results"].as_array().unwrap() {
+                assert!(result["message"]["text"].is_string());
+                let kind = result["kind"].as_str().unwrap();
+                let level = result["level"].as_str().unwrap();
+                assert!(KINDS.contains(&kind) && LEVELS.contains(&level));
+                assert!(kind == "fail" || level == "none", "{}", result);
+
+                let rule_index = result["ruleIndex"].as_u64().unwrap() as usize;
+                assert_eq!(rules[rule_index]["id"], result["ruleId"]);
+                for location in result["locations"].as_array().unwrap() {
+                    let artifact = &location["physicalLocation"]["artifactLocation"];
+                    let index = artifact["index"].as_u64().unwrap() as usize;
+                    assert_eq!(artifacts[index]["location"]["uri"], artifact["uri"]);
+                }
+            }
+        }
+    }
+
+    fn scan_result(esp_scan_id: &str) -> ScanResult {
+        let mut metadata = EspMetadata::default_test();
+        metadata.esp_scan_id = esp_scan_id.to_string();
+        metadata.tags = "ssh, hardening".to_string();
+        metadata.fields = vec![
+            MetadataEntry {
+                name: "title".to_string(),
+                value: "SSH hardening".to_string(),
+            },
+            MetadataEntry {
+                name: "severity".to_string(),
+                value: "high".to_string(),
+            },
+        ];
+        let mut result = ScanResult::new(
+            "scan-1".to_string(),
+            metadata,
+            HostContext::new("web-01".to_string(), "linux".to_string()),
+            UserContext::new("root".to_string(), "root".to_string()),
+        );
+        result.plan = ExecutionPlan::new(
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+            [
+                "file_content#2",
+                "sysctl_parameter#3",
+                "file_metadata#4",
+                "file_metadata#5",
+            ]
+            .map(String::from)
+            .to_vec(),
+        );
+        let finding = |severity, criterion_id: &str| {
+            ComplianceFinding::new(
+                format!("f-{}", criterion_id),
+                severity,
+                format!("{} validation failed", criterion_id),
+                String::new(),
+                json!({"file_content": "PermitRootLogin no"}),
+                json!({"file_content": "PermitRootLogin yes"}),
+            )
+            .with_criterion_id(criterion_id.to_string())
+        };
+        result.add_finding(
+            finding(FindingSeverity::Medium, "file_content#2")
+                .with_remediation("Set PermitRootLogin no".to_string()),
+        );
+        result.add_finding(finding(FindingSeverity::Critical, "sysctl_parameter#3"));
+        result.add_finding(finding(FindingSeverity::Low, "file_metadata#5").with_vacuous(true));
+        result
+    }
+
+    fn results_by_rule(log: &Value) -> HashMap<String, Value> {
+        log["runs"][0]["results"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|r| (r["ruleId"].as_str().unwrap().to_string(), r.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_statuses_map_to_kinds_and_levels() {
+        let result = scan_result("sshd");
+        let sarif = result.to_sarif("policies/ssh hardening.esp").unwrap();
+        let log: Value = serde_json::from_str(&sarif).unwrap();
+        assert_sarif_shape(&log);
+        assert_eq!(log, super::log(&result, "policies/ssh hardening.esp"));
+
+        let results = results_by_rule(&log);
+        assert_eq!(results.len(), 4);
+        let kind_level = |id: &str| {
+            let r = &results[id];
+            (r["kind"].as_str().unwrap(), r["level"].as_str().unwrap())
+        };
+        assert_eq!(kind_level("sshd/file_content#2"), ("fail", "warning"));
+        assert_eq!(kind_level("sshd/sysctl_parameter#3"), ("open", "none"));
+        assert_eq!(kind_level("sshd/file_metadata#4"), ("pass", "none"));
+        assert_eq!(kind_level("sshd/file_metadata#5"), ("review", "none"));
+
+        let fail = &results["sshd/file_content#2"];
+        assert_eq!(fail["properties"]["remediation"], "Set PermitRootLogin no");
+        assert_eq!(
+            fail["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+            "policies/ssh%20hardening.esp"
+        );
+
+        let rule = &log["runs"][0]["tool"]["driver"]["rules"][0];
+        assert_eq!(rule["name"], "file_content");
+        assert_eq!(rule["shortDescription"]["text"], "SSH hardening");
+        assert_eq!(rule["defaultConfiguration"]["level"], "error");
+        assert_eq!(rule["properties"]["tags"], json!(["ssh", "hardening"]));
+    }
+
+    #[test]
+    fn test_waived_failure_is_suppressed() {
+        let mut result = scan_result("sshd");
+        let waivers = WaiverSet::from_yaml(
+            "waivers:\n  - ctn_node_id: 4\n    expires: 2025-06-01\n    justification: Legacy mount\n",
+        )
+        .unwrap();
+        result.add_finding(
+            ComplianceFinding::new(
+                "f-4".to_string(),
+                FindingSeverity::High,
+                "file_metadata#4 validation failed".to_string(),
+                String::new(),
+                json!({}),
+                json!({}),
+            )
+            .with_criterion_id("file_metadata#4".to_string())
+            .with_waiver(waivers.waivers.first().cloned()),
+        );
+
+        let log: Value = serde_json::from_str(&result.to_sarif("ssh.esp").unwrap()).unwrap();
+        assert_sarif_shape(&log);
+        let waived = &results_by_rule(&log)["sshd/file_metadata#4"];
+        assert_eq!(
+            (&waived["kind"], &waived["level"]),
+            (&json!("fail"), &json!("error"))
+        );
+        assert_eq!(waived["properties"]["status"], "waived");
+        assert_eq!(waived["suppressions"][0]["justification"], "Legacy mount");
+    }
+
+    #[test]
+    fn test_evidence_status_wins_and_batches_keep_rules_apart() {
+        let mut first = scan_result("sshd");
+        first.evidence = vec![CriterionEvidence::new("file_metadata#4", "unknown", &[])];
+        let second = scan_result("sysctl");
+
+        let log = batch_log([("a.esp", &first), ("b.esp", &second)]);
+        assert_sarif_shape(&log);
+        let run = &log["runs"][0];
+        assert_eq!(run["tool"]["driver"]["rules"].as_array().unwrap().len(), 8);
+        assert_eq!(run["artifacts"].as_array().unwrap().len(), 2);
+
+        let results = results_by_rule(&log);
+        assert_eq!(results["sshd/file_metadata#4"]["kind"], "open");
+        assert_eq!(results["sysctl/file_metadata#4"]["kind"], "pass");
+        assert_eq!(
+            results["sysctl/file_content#2"]["locations"][0]["physicalLocation"]
+                ["artifactLocation"]["index"],
+            1
+        );
+    }
+}
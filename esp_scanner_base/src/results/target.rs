@@ -0,0 +1,185 @@
+//! # Scan Target Information
+//!
+//! Which machine a result came from, for aggregating results across a
+//! fleet: hostname, OS release, kernel and architecture, when the scan ran
+//! (RFC 3339 in the scanning host's offset), the scanner version and the
+//! hash of the policy source. Results saved before it was recorded have no
+//! `target` section.
+//!
+//! For a mounted root (`--root`) the OS release is read from the root and
+//! the kernel is left out, since the running kernel is the scanning host's.
+//! Pipelines that must not identify hosts can leave the hostname out.
+
+use super::types::TimestampInfo;
+use chrono::{DateTime, FixedOffset, Local, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Hostname reported when host identification is suppressed
+pub const REDACTED_HOSTNAME: &str = "redacted";
+
+/// Host and environment a scan ran on
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TargetInfo {
+    /// `None` when host identification is suppressed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hostname: Option<String>,
+    /// `ID` from os-release, e.g. `rhel`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub os_id: Option<String>,
+    /// `VERSION_ID` from os-release, e.g. `9.4`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub os_version_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kernel_version: Option<String>,
+    pub architecture: String,
+    pub scan_start: DateTime<FixedOffset>,
+    pub scan_end: DateTime<FixedOffset>,
+    pub scanner_version: String,
+    /// Hash of the policy file the scan compiled, as in the provenance
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub policy_sha256: Option<String>,
+}
+
+impl TargetInfo {
+    /// Target of a finished scan; `policy_hash` is empty when the caller
+    /// recorded no provenance
+    pub fn new(
+        hostname: &str,
+        os_release: &OsRelease,
+        kernel_version: Option<String>,
+        timestamp: &TimestampInfo,
+        policy_hash: &str,
+    ) -> Self {
+        Self {
+            hostname: Some(hostname.to_string()),
+            os_id: os_release.id.clone(),
+            os_version_id: os_release.version_id.clone(),
+            kernel_version,
+            architecture: std::env::consts::ARCH.to_string(),
+            scan_start: local_time(timestamp.scan_start),
+            scan_end: local_time(timestamp.scan_end),
+            scanner_version: env!("CARGO_PKG_VERSION").to_string(),
+            policy_sha256: (!policy_hash.is_empty()).then(|| policy_hash.to_string()),
+        }
+    }
+
+    /// Leave the hostname out
+    pub fn without_hostname(mut self) -> Self {
+        self.hostname = None;
+        self
+    }
+}
+
+/// `ID` and `VERSION_ID` of an os-release file
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OsRelease {
+    pub id: Option<String>,
+    pub version_id: Option<String>,
+}
+
+impl OsRelease {
+    /// Parse os-release `KEY=value` lines, unquoting values
+    pub fn parse(text: &str) -> Self {
+        let mut release = Self::default();
+        for line in text.lines() {
+            let Some((name, value)) = line.trim().split_once('=') else {
+                continue;
+            };
+            let value = value.trim().trim_matches(|c| c == '"' || c == '\'');
+            match name {
+                "ID" => release.id = Some(value.to_string()),
+                "VERSION_ID" => release.version_id = Some(value.to_string()),
+                _ => {}
+            }
+        }
+        release
+    }
+
+    /// Read `/etc/os-release`, or `/usr/lib/os-release` when it is missing,
+    /// under `root`; empty when neither can be read
+    pub fn read(root: &Path) -> Self {
+        ["etc/os-release", "usr/lib/os-release"]
+            .iter()
+            .find_map(|path| std::fs::read_to_string(root.join(path)).ok())
+            .map(|text| Self::parse(&text))
+            .unwrap_or_default()
+    }
+
+    /// OS release of the running host
+    #[cfg(feature = "host")]
+    pub fn probe() -> Self {
+        Self::read(Path::new("/"))
+    }
+
+    /// Without the `host` feature the OS release is not read
+    #[cfg(not(feature = "host"))]
+    pub fn probe() -> Self {
+        Self::default()
+    }
+}
+
+/// Release of the running kernel, e.g. `5.14.0-427.el9.x86_64`
+#[cfg(feature = "host")]
+pub fn kernel_version() -> Option<String> {
+    std::fs::read_to_string("/proc/sys/kernel/osrelease")
+        .ok()
+        .map(|release| release.trim().to_string())
+        .filter(|release| !release.is_empty())
+}
+
+/// Without the `host` feature the kernel is not looked up
+#[cfg(not(feature = "host"))]
+pub fn kernel_version() -> Option<String> {
+    None
+}
+
+fn local_time(time: DateTime<Utc>) -> DateTime<FixedOffset> {
+    time.with_timezone(&Local).fixed_offset()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_os_release_is_parsed_and_read_under_a_root() {
+        let release = OsRelease::parse(
+            "NAME=\"Red Hat Enterprise Linux\"\n# comment\nID=\"rhel\"\nVERSION_ID='9.4'\n",
+        );
+        assert_eq!(release.id.as_deref(), Some("rhel"));
+        assert_eq!(release.version_id.as_deref(), Some("9.4"));
+
+        let root = tempfile::tempdir().unwrap();
+        assert_eq!(OsRelease::read(root.path()), OsRelease::default());
+        std::fs::create_dir_all(root.path().join("usr/lib")).unwrap();
+        std::fs::write(root.path().join("usr/lib/os-release"), "ID=debian\n").unwrap();
+        assert_eq!(OsRelease::read(root.path()).id.as_deref(), Some("debian"));
+    }
+
+    #[test]
+    fn test_target_serializes_offsets_and_omits_suppressed_hostname() {
+        let start = "2025-05-01T12:00:00Z".parse().unwrap();
+        let timestamp = TimestampInfo {
+            scan_start: start,
+            scan_end: start,
+            duration_ms: 0,
+            fixed_clock: true,
+        };
+        let target = TargetInfo::new(
+            "web-01",
+            &OsRelease::default(),
+            None,
+            &timestamp,
+            "sha256:abc",
+        );
+        assert_eq!(target.scan_start, start);
+        assert_eq!(target.policy_sha256.as_deref(), Some("sha256:abc"));
+
+        let json = serde_json::to_value(target.without_hostname()).unwrap();
+        assert!(json.get("hostname").is_none());
+        assert!(json.get("os_id").is_none());
+        let start = json["scan_start"].as_str().unwrap();
+        assert!(DateTime::parse_from_rfc3339(start).is_ok(), "{}", start);
+    }
+}
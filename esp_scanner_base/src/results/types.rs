@@ -4,8 +4,24 @@
 //! These types are designed for serialization to JSON and integration
 //! with SIEM/SOAR tools and compliance reporting systems.
 
+use super::advisory::Advisory;
+use super::controls::{Control, ControlSummary};
+use super::diff::CriterionOutcome;
+use super::evidence::{CriterionEvidence, ObservedField};
+use super::performance::ScanPerformance;
+use super::provenance::ScanProvenance;
+use super::target::TargetInfo;
+use super::waivers::Waiver;
+use crate::execution::clock::ScanClock;
+use crate::execution::consistency::ConsistencyMismatch;
+use crate::types::execution_plan::ExecutionPlan;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Layout version of the serialized [`ScanResult`], reported in capability
+/// manifests
+pub const RESULT_SCHEMA_VERSION: &str = "1.0";
 
 /// Complete scan result for one ESP definition file
 #[derive(Debug, Serialize, Deserialize)]
@@ -18,6 +34,53 @@ pub struct ScanResult {
 
     /// Results of the configuration compliance check
     pub results: ComplianceResults,
+
+    /// Set when the scan degraded to stay within resource limits
+    #[serde(default)]
+    pub degraded: bool,
+
+    /// Set when the policy has no criteria and nothing was scanned
+    #[serde(default)]
+    pub empty_policy: bool,
+
+    /// Strategy usage recorded while executing
+    #[serde(default, skip_serializing_if = "ScanPerformance::is_empty")]
+    pub performance: ScanPerformance,
+
+    /// Execution phases the scan ran through
+    #[serde(default, skip_serializing_if = "ExecutionPlan::is_empty")]
+    pub plan: ExecutionPlan,
+
+    /// Fields compared per criterion and item, when field evidence is enabled
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub evidence: Vec<CriterionEvidence>,
+
+    /// Informational notes from executors; not counted and never scored
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub advisories: Vec<Advisory>,
+
+    /// Files that changed between two criteria reading them, with both
+    /// observations
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub consistency: Vec<ConsistencyMismatch>,
+
+    /// Criterion outcomes per benchmark control
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub controls: Vec<ControlSummary>,
+
+    /// Status and fingerprint of every criterion, in tree order, for
+    /// matching criteria across scans
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub criteria: Vec<CriterionOutcome>,
+
+    /// Waivers that matched a criterion of this scan but had expired, so
+    /// were ignored
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub expired_waivers: Vec<Waiver>,
+
+    /// Host and environment the scan ran on, set by the execution engine
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<TargetInfo>,
 }
 
 /// Metadata for the scan execution and ESP definition
@@ -35,6 +98,35 @@ pub struct ScanMetadata {
 
     /// Scan execution timestamps
     pub timestamp: TimestampInfo,
+
+    /// Hashes of the policy and strategies the scan ran; empty in results
+    /// saved before they were recorded
+    #[serde(default)]
+    pub provenance: ScanProvenance,
+
+    /// Whether `host` and `user_context` were probed for this scan or reused
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub host_facts: Option<HostFactsProvenance>,
+
+    /// Mounted root the scan read instead of the host's `/`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scan_root: Option<String>,
+}
+
+/// Where the host facts of a scan came from
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HostFactsProvenance {
+    pub source: FactsSource,
+    /// When the facts were probed
+    pub collected_at: DateTime<Utc>,
+}
+
+/// Whether host facts were probed for the scan or taken from a cache
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FactsSource {
+    Fresh,
+    Cached,
 }
 
 /// Required fields from ESP META block for SIEM/SOAR output
@@ -57,10 +149,32 @@ pub struct EspMetadata {
 
     /// Tags for categorization and filtering
     pub tags: String,
+
+    /// Every MET field, in the order it was authored
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<MetadataEntry>,
+}
+
+/// One MET field as written in the policy
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetadataEntry {
+    pub name: String,
+    pub value: String,
+}
+
+fn authored_fields(metadata: &crate::types::metadata::MetaDataBlock) -> Vec<MetadataEntry> {
+    metadata
+        .ordered_fields()
+        .into_iter()
+        .map(|(name, value)| MetadataEntry {
+            name: name.to_string(),
+            value: value.to_string(),
+        })
+        .collect()
 }
 
 /// Host execution context
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HostContext {
     /// Hostname where scan executed
     pub hostname: String,
@@ -76,7 +190,7 @@ pub struct HostContext {
 }
 
 /// User execution context
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UserContext {
     /// User account that executed the scan
     pub username: String,
@@ -99,6 +213,10 @@ pub struct TimestampInfo {
 
     /// Total execution duration in milliseconds
     pub duration_ms: u64,
+
+    /// Set when the scan ran against an injected fixed clock
+    #[serde(default)]
+    pub fixed_clock: bool,
 }
 
 /// Results of configuration compliance validation
@@ -129,13 +247,57 @@ pub struct ComplianceCheck {
     /// Number of criteria that had execution errors
     pub error_criteria: u32,
 
-    /// Percentage of criteria that passed (0-100)
-    pub pass_percentage: f32,
+    /// Number of criteria skipped without evaluation
+    #[serde(default)]
+    pub not_evaluated_criteria: u32,
+
+    /// Number of criteria that do not apply to the host
+    #[serde(default)]
+    pub not_applicable_criteria: u32,
+
+    /// Number of failed criteria covered by a waiver; not counted as failed
+    #[serde(default)]
+    pub waived_criteria: u32,
+
+    /// Number of error criteria blocked by the scanner path policy
+    #[serde(default)]
+    pub path_denied_criteria: u32,
+
+    /// Number of criteria that passed, or would have passed, without
+    /// observing anything because collection could not look
+    #[serde(default)]
+    pub vacuous_pass_criteria: u32,
+
+    /// Number of failed and error findings at each policy severity
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub failed_by_severity: BTreeMap<PolicySeverity, u32>,
+
+    /// Percentage of applicable criteria that passed (0-100), serialized
+    /// to one decimal; null when no criteria apply
+    #[serde(serialize_with = "super::compare::serialize_percentage")]
+    pub pass_percentage: Option<f32>,
 
     /// Overall compliance status
     pub status: ComplianceStatus,
 }
 
+impl ComplianceCheck {
+    /// Recount [`failed_by_severity`](Self::failed_by_severity) from the
+    /// findings
+    pub fn count_failures_by_severity(&mut self, findings: &[ComplianceFinding]) {
+        self.failed_by_severity.clear();
+        for finding in findings
+            .iter()
+            .filter(|f| f.severity.is_failure() && f.waiver.is_none())
+        {
+            *self
+                .failed_by_severity
+                .entry(finding.policy_severity)
+                .or_default() += 1;
+        }
+    }
+}
+
 /// Overall compliance status enumeration
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -162,6 +324,11 @@ pub struct ComplianceFinding {
     /// Severity level of the compliance violation
     pub severity: FindingSeverity,
 
+    /// Importance the policy assigns to the criterion: its CTN SEVERITY,
+    /// else the META severity
+    #[serde(default)]
+    pub policy_severity: PolicySeverity,
+
     /// Human-readable title of the finding
     pub title: String,
 
@@ -179,6 +346,28 @@ pub struct ComplianceFinding {
 
     /// Field path that failed validation
     pub field_path: Option<String>,
+
+    /// Stable identifier of the criterion that produced this finding
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub criterion_id: Option<String>,
+
+    /// Benchmark controls the criterion implements
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub controls: Vec<Control>,
+
+    /// The criterion passed without observing anything
+    #[serde(default)]
+    pub vacuous: bool,
+
+    /// Approved exception covering this failure; a waived finding does not
+    /// count as a failure
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub waiver: Option<Waiver>,
+
+    /// Failed fields with the values observed on the system, keyed by
+    /// `<object id>/<field>`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub evidence: BTreeMap<String, ObservedField>,
 }
 
 /// Severity levels for compliance findings
@@ -201,6 +390,76 @@ pub enum FindingSeverity {
     Info,
 }
 
+impl FindingSeverity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Critical => "critical",
+            Self::High => "high",
+            Self::Medium => "medium",
+            Self::Low => "low",
+            Self::Info => "info",
+        }
+    }
+
+    /// Whether the finding reports a failed or errored criterion
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Self::Critical | Self::High)
+    }
+}
+
+/// Severity a policy assigns to its criteria, ordered from least to most
+/// severe
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum PolicySeverity {
+    /// The policy declares no (or an unrecognized) severity
+    #[default]
+    Unknown,
+    Info,
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+impl PolicySeverity {
+    /// Every level, least severe first
+    pub const ALL: [Self; 6] = [
+        Self::Unknown,
+        Self::Info,
+        Self::Low,
+        Self::Medium,
+        Self::High,
+        Self::Critical,
+    ];
+
+    /// Parse a META or CTN severity, case-insensitively; anything
+    /// unrecognized is [`Unknown`](Self::Unknown)
+    pub fn from_label(label: &str) -> Self {
+        match label.trim().to_ascii_lowercase().as_str() {
+            "critical" => Self::Critical,
+            "high" => Self::High,
+            "medium" => Self::Medium,
+            "low" => Self::Low,
+            "info" | "informational" => Self::Info,
+            _ => Self::Unknown,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Unknown => "unknown",
+            Self::Info => "info",
+            Self::Low => "low",
+            Self::Medium => "medium",
+            Self::High => "high",
+            Self::Critical => "critical",
+        }
+    }
+}
+
 impl ScanResult {
     /// Create a new scan result with basic metadata
     pub fn new(
@@ -209,7 +468,24 @@ impl ScanResult {
         host: HostContext,
         user_context: UserContext,
     ) -> Self {
-        let now = Utc::now();
+        Self::new_at(
+            scan_id,
+            esp_metadata,
+            host,
+            user_context,
+            &ScanClock::system(),
+        )
+    }
+
+    /// Create a scan result whose start time is the scan clock's instant
+    pub fn new_at(
+        scan_id: String,
+        esp_metadata: EspMetadata,
+        host: HostContext,
+        user_context: UserContext,
+        clock: &ScanClock,
+    ) -> Self {
+        let now = clock.now();
 
         Self {
             scan_id,
@@ -221,7 +497,11 @@ impl ScanResult {
                     scan_start: now,
                     scan_end: now,
                     duration_ms: 0,
+                    fixed_clock: clock.is_fixed(),
                 },
+                provenance: ScanProvenance::default(),
+                host_facts: None,
+                scan_root: None,
             },
             results: ComplianceResults {
                 check: ComplianceCheck {
@@ -229,29 +509,52 @@ impl ScanResult {
                     passed_criteria: 0,
                     failed_criteria: 0,
                     error_criteria: 0,
-                    pass_percentage: 0.0,
+                    not_evaluated_criteria: 0,
+                    not_applicable_criteria: 0,
+                    waived_criteria: 0,
+                    path_denied_criteria: 0,
+                    vacuous_pass_criteria: 0,
+                    failed_by_severity: BTreeMap::new(),
+                    pass_percentage: None,
                     status: ComplianceStatus::Error,
                 },
                 findings: Vec::new(),
                 passed: false,
             },
+            degraded: false,
+            empty_policy: false,
+            performance: ScanPerformance::default(),
+            plan: ExecutionPlan::default(),
+            evidence: Vec::new(),
+            advisories: Vec::new(),
+            consistency: Vec::new(),
+            controls: Vec::new(),
+            criteria: Vec::new(),
+            expired_waivers: Vec::new(),
+            target: None,
         }
     }
 
     /// Mark scan as completed and calculate final metrics
     pub fn finalize(&mut self) {
-        self.metadata.timestamp.scan_end = Utc::now();
+        self.finalize_at(&ScanClock::system());
+    }
+
+    /// [`finalize`](Self::finalize) with the end time taken from the scan clock
+    pub fn finalize_at(&mut self, clock: &ScanClock) {
+        self.metadata.timestamp.scan_end = clock.wall_time();
         self.metadata.timestamp.duration_ms = (self.metadata.timestamp.scan_end
             - self.metadata.timestamp.scan_start)
             .num_milliseconds() as u64;
 
         let check = &mut self.results.check;
 
-        // Calculate pass percentage
-        if check.total_criteria > 0 {
-            check.pass_percentage =
-                (check.passed_criteria as f32 / check.total_criteria as f32) * 100.0;
-        }
+        // Calculate pass percentage over the criteria that apply
+        let applicable = check.total_criteria - check.not_applicable_criteria;
+        check.pass_percentage =
+            (applicable > 0).then(|| (check.passed_criteria as f32 / applicable as f32) * 100.0);
+
+        check.count_failures_by_severity(&self.results.findings);
 
         // Determine overall status
         check.status = if check.error_criteria > 0 {
@@ -290,6 +593,31 @@ impl ScanResult {
         check.error_criteria = errors;
     }
 
+    /// Record criteria that were skipped without evaluation
+    pub fn set_not_evaluated_count(&mut self, not_evaluated: u32) {
+        self.results.check.not_evaluated_criteria = not_evaluated;
+    }
+
+    /// Record criteria that do not apply to the host
+    pub fn set_not_applicable_count(&mut self, not_applicable: u32) {
+        self.results.check.not_applicable_criteria = not_applicable;
+    }
+
+    /// Record failed criteria covered by a waiver
+    pub fn set_waived_count(&mut self, waived: u32) {
+        self.results.check.waived_criteria = waived;
+    }
+
+    /// Record error criteria that were blocked by the path policy
+    pub fn set_path_denied_count(&mut self, path_denied: u32) {
+        self.results.check.path_denied_criteria = path_denied;
+    }
+
+    /// Record criteria that passed without observing anything
+    pub fn set_vacuous_pass_count(&mut self, vacuous: u32) {
+        self.results.check.vacuous_pass_criteria = vacuous;
+    }
+
     /// Serialize to JSON string
     pub fn to_json(&self) -> Result<String, serde_json::Error> {
         serde_json::to_string_pretty(self)
@@ -305,6 +633,28 @@ impl ScanResult {
         serde_json::from_str(json)
     }
 
+    /// Serialize to YAML string
+    pub fn to_yaml(&self) -> Result<String, serde_yaml::Error> {
+        serde_yaml::to_string(self)
+    }
+
+    /// Parse from YAML string
+    pub fn from_yaml(yaml: &str) -> Result<Self, serde_yaml::Error> {
+        serde_yaml::from_str(yaml)
+    }
+
+    /// Serialize to a SARIF 2.1.0 log, locating results at the policy file
+    /// `policy_path` (see [`super::sarif`])
+    pub fn to_sarif(&self, policy_path: &str) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(&super::sarif::log(self, policy_path))
+    }
+
+    /// Serialize to a JUnit XML report with one test suite named after the
+    /// policy file `policy_path` (see [`super::junit`])
+    pub fn to_junit_xml(&self, policy_path: &str) -> String {
+        super::junit::report(self, policy_path)
+    }
+
     /// Get scan duration in seconds
     pub fn duration_seconds(&self) -> f64 {
         self.metadata.timestamp.duration_ms as f64 / 1000.0
@@ -315,6 +665,16 @@ impl ScanResult {
         self.results.check.error_criteria == 0
     }
 
+    /// Whether a failed or error finding is at or above the policy severity
+    /// `level`
+    pub fn has_failures_at_or_above(&self, level: PolicySeverity) -> bool {
+        self.results
+            .check
+            .failed_by_severity
+            .range(level..)
+            .any(|(_, count)| *count > 0)
+    }
+
     /// Get findings by severity level
     pub fn findings_by_severity(&self, severity: FindingSeverity) -> Vec<&ComplianceFinding> {
         self.results
@@ -357,6 +717,7 @@ impl EspMetadata {
                 .ok_or("Missing criticality")?
                 .clone(),
             tags: metadata.fields.get("tags").ok_or("Missing tags")?.clone(),
+            fields: authored_fields(metadata),
         })
     }
 
@@ -369,18 +730,38 @@ impl EspMetadata {
             platform: "Test".to_string(),
             criticality: "medium".to_string(),
             tags: "test".to_string(),
+            fields: Vec::new(),
         }
     }
+
+    /// Value of the MET field `name`, if the policy set it
+    pub fn field(&self, name: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|f| f.name == name)
+            .map(|f| f.value.as_str())
+    }
+}
+
+#[cfg(feature = "host")]
+fn system_hostname() -> String {
+    hostname::get()
+        .unwrap_or_else(|_| std::ffi::OsString::from("unknown"))
+        .to_string_lossy()
+        .to_string()
+}
+
+/// Without the `host` feature the hostname is not looked up
+#[cfg(not(feature = "host"))]
+fn system_hostname() -> String {
+    "unknown".to_string()
 }
 
 impl HostContext {
     /// Create host context from system information
     pub fn from_system() -> Self {
         Self {
-            hostname: hostname::get()
-                .unwrap_or_else(|_| std::ffi::OsString::from("unknown"))
-                .to_string_lossy()
-                .to_string(),
+            hostname: system_hostname(),
             os_info: format!("{} {}", std::env::consts::OS, std::env::consts::ARCH),
             ip_address: None, // Would need additional dependency to get IP
             asset_id: None,
@@ -475,12 +856,18 @@ impl ComplianceFinding {
         Self {
             finding_id,
             severity,
+            policy_severity: PolicySeverity::Unknown,
             title,
             description,
             expected,
             actual,
             remediation: None,
             field_path: None,
+            criterion_id: None,
+            controls: Vec::new(),
+            vacuous: false,
+            waiver: None,
+            evidence: BTreeMap::new(),
         }
     }
 
@@ -496,6 +883,42 @@ impl ComplianceFinding {
         self
     }
 
+    /// Add the stable criterion identifier
+    pub fn with_criterion_id(mut self, criterion_id: String) -> Self {
+        self.criterion_id = Some(criterion_id);
+        self
+    }
+
+    /// Add the severity the policy assigns to the criterion
+    pub fn with_policy_severity(mut self, policy_severity: PolicySeverity) -> Self {
+        self.policy_severity = policy_severity;
+        self
+    }
+
+    /// Add the benchmark controls the criterion implements
+    pub fn with_controls(mut self, controls: Vec<Control>) -> Self {
+        self.controls = controls;
+        self
+    }
+
+    /// Mark the finding as reporting a vacuous pass
+    pub fn with_vacuous(mut self, vacuous: bool) -> Self {
+        self.vacuous = vacuous;
+        self
+    }
+
+    /// Attach the failed fields and the values observed for them
+    pub fn with_evidence(mut self, evidence: BTreeMap<String, ObservedField>) -> Self {
+        self.evidence = evidence;
+        self
+    }
+
+    /// Attach the waiver covering this failure
+    pub fn with_waiver(mut self, waiver: Option<Waiver>) -> Self {
+        self.waiver = waiver;
+        self
+    }
+
     /// Create a finding with auto-generated ID
     pub fn auto_id(
         severity: FindingSeverity,
@@ -554,6 +977,7 @@ impl From<&crate::types::metadata::MetaDataBlock> for EspMetadata {
                 .get("tags")
                 .expect("tags should be validated before conversion")
                 .clone(),
+            fields: authored_fields(metadata),
         }
     }
 }
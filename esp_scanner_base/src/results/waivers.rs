@@ -0,0 +1,236 @@
+//! # Waivers
+//!
+//! Approved exceptions for criteria known to fail ("auditd_rules on web-01
+//! waived until 2025-06-01 per SEC-123"), loaded from a YAML or JSON file
+//! with `--waivers`. A waiver selects criteria by criterion type, CTN node
+//! id, policy (`esp_scan_id` from META) and host; every selector it gives
+//! must match, and it must give at least one of the first three.
+//!
+//! A failing criterion matched by a waiver that has not expired is reported
+//! as `waived`: it counts as passing in its block and toward the overall
+//! result, is left out of the failed count, and keeps its finding with the
+//! waiver attached. A waiver is valid through its expiry date (UTC); after
+//! that it is ignored and listed in the result's `expired_waivers`, so stale
+//! exceptions surface instead of silently persisting.
+//!
+//! ```rust
+//! use chrono::NaiveDate;
+//! use esp_scanner_base::results::{WaiverSet, WaiverTarget};
+//!
+//! let waivers = WaiverSet::from_yaml(
+//!     r#"
+//! waivers:
+//!   - criterion_type: auditd_rules
+//!     host: web-01
+//!     expires: 2025-06-01
+//!     justification: Replaced by the SIEM agent
+//!     ticket: SEC-123
+//! "#,
+//! )
+//! .unwrap();
+//! let target = WaiverTarget {
+//!     criterion_type: "auditd_rules",
+//!     ctn_node_id: 4,
+//!     esp_scan_id: "rhel9-baseline",
+//!     host: "web-01",
+//! };
+//! let today = NaiveDate::from_ymd_opt(2025, 5, 1).unwrap();
+//! assert_eq!(waivers.find(&target, today).active.unwrap().ticket.as_deref(), Some("SEC-123"));
+//! ```
+
+use super::error::ResultGenerationError;
+use crate::types::criterion::CtnNodeId;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// One approved exception
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Waiver {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub criterion_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ctn_node_id: Option<CtnNodeId>,
+    /// `esp_scan_id` of the policy, from its META block
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub esp_scan_id: Option<String>,
+    /// Hostname the waiver is limited to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub host: Option<String>,
+    /// Last day the waiver applies
+    pub expires: NaiveDate,
+    pub justification: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ticket: Option<String>,
+}
+
+/// The criterion a waiver is checked against
+#[derive(Debug, Clone, Copy)]
+pub struct WaiverTarget<'a> {
+    pub criterion_type: &'a str,
+    pub ctn_node_id: CtnNodeId,
+    pub esp_scan_id: &'a str,
+    pub host: &'a str,
+}
+
+/// Waivers matching one criterion
+#[derive(Debug, Default)]
+pub struct WaiverMatch<'a> {
+    /// First matching waiver that has not expired
+    pub active: Option<&'a Waiver>,
+    pub expired: Vec<&'a Waiver>,
+}
+
+impl Waiver {
+    pub fn matches(&self, target: &WaiverTarget) -> bool {
+        self.criterion_type
+            .as_deref()
+            .map_or(true, |t| t == target.criterion_type)
+            && self.ctn_node_id.map_or(true, |id| id == target.ctn_node_id)
+            && self
+                .esp_scan_id
+                .as_deref()
+                .map_or(true, |id| id == target.esp_scan_id)
+            && self.host.as_deref().map_or(true, |h| h == target.host)
+    }
+
+    /// Whether `today` is past the expiry date
+    pub fn is_expired(&self, today: NaiveDate) -> bool {
+        today > self.expires
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.criterion_type.is_none() && self.ctn_node_id.is_none() && self.esp_scan_id.is_none()
+        {
+            return Err(
+                "a waiver needs a criterion_type, ctn_node_id or esp_scan_id selector".to_string(),
+            );
+        }
+        if self.justification.trim().is_empty() {
+            return Err("a waiver needs a justification".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Waivers from a waivers file: `{"waivers": [...]}` in YAML or JSON
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WaiverSet {
+    pub waivers: Vec<Waiver>,
+}
+
+impl WaiverSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.waivers.is_empty()
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let set: Self = serde_json::from_str(json).map_err(|e| e.to_string())?;
+        set.validate()
+    }
+
+    pub fn from_yaml(yaml: &str) -> Result<Self, String> {
+        let set: Self = serde_yaml::from_str(yaml).map_err(|e| e.to_string())?;
+        set.validate()
+    }
+
+    /// Load a waivers file, as JSON when its extension is `.json` and as
+    /// YAML otherwise
+    pub fn load(path: &Path) -> Result<Self, ResultGenerationError> {
+        let display = path.display().to_string();
+        let text = std::fs::read_to_string(path).map_err(|e| {
+            ResultGenerationError::waivers_file_failed(&display, "read", &e.to_string())
+        })?;
+        let parsed = match path.extension().and_then(|e| e.to_str()) {
+            Some("json") => Self::from_json(&text),
+            _ => Self::from_yaml(&text),
+        };
+        parsed.map_err(|e| ResultGenerationError::waivers_file_failed(&display, "parse", &e))
+    }
+
+    /// Waivers matching `target`, split by whether they expired before
+    /// `today`
+    pub fn find(&self, target: &WaiverTarget, today: NaiveDate) -> WaiverMatch<'_> {
+        let mut found = WaiverMatch::default();
+        for waiver in self.waivers.iter().filter(|w| w.matches(target)) {
+            if waiver.is_expired(today) {
+                found.expired.push(waiver);
+            } else if found.active.is_none() {
+                found.active = Some(waiver);
+            }
+        }
+        found
+    }
+
+    fn validate(self) -> Result<Self, String> {
+        for (index, waiver) in self.waivers.iter().enumerate() {
+            waiver
+                .validate()
+                .map_err(|e| format!("waiver {}: {}", index + 1, e))?;
+        }
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(day: &str) -> NaiveDate {
+        day.parse().unwrap()
+    }
+
+    #[test]
+    fn test_selectors_and_expiry() {
+        let waivers = WaiverSet::from_json(
+            r#"{"waivers": [
+                {"criterion_type": "auditd_rules", "expires": "2025-01-31", "justification": "old"},
+                {"criterion_type": "auditd_rules", "ctn_node_id": 4, "host": "web-01",
+                 "expires": "2025-06-01", "justification": "SIEM agent", "ticket": "SEC-123"}
+            ]}"#,
+        )
+        .unwrap();
+        let target = WaiverTarget {
+            criterion_type: "auditd_rules",
+            ctn_node_id: 4,
+            esp_scan_id: "baseline",
+            host: "web-01",
+        };
+
+        let found = waivers.find(&target, date("2025-06-01"));
+        assert_eq!(found.active.unwrap().ticket.as_deref(), Some("SEC-123"));
+        assert_eq!(found.expired.len(), 1);
+
+        let found = waivers.find(&target, date("2025-06-02"));
+        assert!(found.active.is_none());
+        assert_eq!(found.expired.len(), 2);
+
+        let other_host = WaiverTarget {
+            host: "web-02",
+            ..target
+        };
+        let found = waivers.find(&other_host, date("2025-01-01"));
+        assert_eq!(found.active.unwrap().justification, "old");
+    }
+
+    #[test]
+    fn test_waivers_need_a_selector_and_justification() {
+        let err = WaiverSet::from_yaml(
+            "waivers:\n  - host: web-01\n    expires: 2025-06-01\n    justification: x\n",
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            "waiver 1: a waiver needs a criterion_type, ctn_node_id or esp_scan_id selector"
+        );
+        let err = WaiverSet::from_yaml(
+            "waivers:\n  - ctn_node_id: 2\n    expires: 2025-06-01\n    justification: ' '\n",
+        )
+        .unwrap_err();
+        assert_eq!(err, "waiver 1: a waiver needs a justification");
+    }
+}
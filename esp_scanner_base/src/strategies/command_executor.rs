@@ -1,7 +1,15 @@
 //! Command execution with security controls for system state collection
+//!
+//! A command's timeout is cut short by the criterion deadline the engine
+//! runs it under (see [`crate::execution::deadline`]). On timeout the
+//! command's whole process group is killed and reaped, so neither it nor
+//! anything it started outlives the wait.
 
+use crate::execution::deadline;
 use std::collections::HashSet;
-use std::process::{Command, Stdio};
+use std::io::Read;
+use std::process::{Child, Command, Stdio};
+use std::thread::JoinHandle;
 use std::time::{Duration, Instant};
 
 /// Executes system commands with security controls and timeout enforcement
@@ -59,7 +67,10 @@ impl SystemCommandExecutor {
             });
         }
 
-        let timeout_duration = timeout.unwrap_or(self.default_timeout);
+        let timeout_duration = deadline::bounded(timeout.unwrap_or(self.default_timeout));
+        if timeout_duration.is_zero() {
+            return Err(CommandError::Timeout { timeout_ms: 0 });
+        }
         let start = Instant::now();
 
         // Build command with sanitized environment
@@ -70,6 +81,9 @@ impl SystemCommandExecutor {
             .stdin(Stdio::null())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped());
+        // Own process group, so a timeout can kill what the command started
+        #[cfg(unix)]
+        std::os::unix::process::CommandExt::process_group(&mut cmd, 0);
 
         // Spawn process
         let mut child = cmd.spawn().map_err(|e| {
@@ -89,36 +103,36 @@ impl SystemCommandExecutor {
             }
         })?;
 
+        // Drain the pipes while waiting so a chatty command cannot block
+        let stdout = child.stdout.take().map(drain);
+        let stderr = child.stderr.take().map(drain);
+
         // Wait with timeout
-        let result =
-            wait_timeout::ChildExt::wait_timeout(&mut child, timeout_duration).map_err(|e| {
-                CommandError::ExecutionFailed {
+        let result = match wait_timeout::ChildExt::wait_timeout(&mut child, timeout_duration) {
+            Ok(result) => result,
+            Err(e) => {
+                kill(&mut child);
+                return Err(CommandError::ExecutionFailed {
                     program: program.to_string(),
                     reason: e.to_string(),
-                }
-            })?;
+                });
+            }
+        };
 
         match result {
             Some(status) => {
                 // Process completed within timeout
-                let output =
-                    child
-                        .wait_with_output()
-                        .map_err(|e| CommandError::ExecutionFailed {
-                            program: program.to_string(),
-                            reason: e.to_string(),
-                        })?;
-
                 Ok(CommandOutput {
-                    stdout: String::from_utf8_lossy(&output.stdout).to_string(),
-                    stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+                    stdout: collect(stdout),
+                    stderr: collect(stderr),
                     exit_code: status.code().unwrap_or(-1),
                     duration: start.elapsed(),
                 })
             }
             None => {
-                // Timeout - kill process
-                let _ = child.kill();
+                // Timeout - kill the process group and reap the child; the
+                // drain threads finish on their own once the pipes close
+                kill(&mut child);
                 Err(CommandError::Timeout {
                     timeout_ms: timeout_duration.as_millis() as u64,
                 })
@@ -127,6 +141,37 @@ impl SystemCommandExecutor {
     }
 }
 
+/// Read a pipe to the end on its own thread
+fn drain(mut pipe: impl Read + Send + 'static) -> JoinHandle<Vec<u8>> {
+    std::thread::spawn(move || {
+        let mut buffer = Vec::new();
+        let _ = pipe.read_to_end(&mut buffer);
+        buffer
+    })
+}
+
+/// Output a drain thread read
+fn collect(reader: Option<JoinHandle<Vec<u8>>>) -> String {
+    let bytes = reader
+        .and_then(|reader| reader.join().ok())
+        .unwrap_or_default();
+    String::from_utf8_lossy(&bytes).to_string()
+}
+
+/// Kill the child and its process group, then reap it
+fn kill(child: &mut Child) {
+    #[cfg(unix)]
+    {
+        use nix::sys::signal::{killpg, Signal};
+        use nix::unistd::Pid;
+
+        // The child leads its own group, so its pid is the group id
+        let _ = killpg(Pid::from_raw(child.id() as i32), Signal::SIGKILL);
+    }
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
 /// Command execution output
 #[derive(Debug, Clone)]
 pub struct CommandOutput {
@@ -191,4 +236,33 @@ mod tests {
             _ => panic!("Expected SecurityViolation error"),
         }
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_timeout_kills_the_command() {
+        let mut executor = SystemCommandExecutor::new();
+        executor.allow_command("sh");
+
+        let start = Instant::now();
+        let result = executor.execute(
+            "sh",
+            &["-c", "sleep 30 & wait"],
+            Some(Duration::from_millis(200)),
+        );
+        assert!(matches!(result, Err(CommandError::Timeout { .. })));
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_expired_deadline_skips_the_command() {
+        let mut executor = SystemCommandExecutor::new();
+        executor.allow_command("true");
+
+        let _deadline = deadline::enter(Instant::now());
+        let result = executor.execute("true", &[], None);
+        assert!(matches!(
+            result,
+            Err(CommandError::Timeout { timeout_ms: 0 })
+        ));
+    }
 }
@@ -4,6 +4,9 @@
 //! Defines complete contracts for CTN types including object requirements,
 //! state requirements, field mappings, and collection strategies.
 
+use crate::execution::behavior::{parse_behavior_value, BehaviorValues};
+use crate::execution::object_parameters::{check_argv_value, extract_parameters, ParameterValues};
+use crate::resolution::references::nearest_names;
 use crate::strategies::errors::{
     CtnContractError, ValidationErrorType, ValidationReport, ValidationWarningType,
 };
@@ -21,6 +24,7 @@ pub struct CtnContract {
     pub collection_strategy: CollectionStrategy,
     pub metadata: CtnMetadata,
     pub supported_behaviors: Vec<SupportedBehavior>,
+    pub supported_parameters: Vec<SupportedParameter>,
 }
 
 /// Metadata about the CTN contract
@@ -43,6 +47,44 @@ pub struct SupportedBehavior {
     pub example: String,
 }
 
+impl SupportedBehavior {
+    /// One-line usage, e.g. `recursive_scan [max_depth int = 3]` or `timeout <int>`
+    pub fn usage(&self) -> String {
+        let parameters = self.parameters.iter().filter(|p| p.name != self.name);
+        let mut usage = self.name.clone();
+        if self.behavior_type == BehaviorType::Parameter {
+            if let Some(own) = self.parameters.iter().find(|p| p.name == self.name) {
+                usage.push_str(&format!(" <{}>", own.data_type.as_str()));
+            }
+        }
+        for parameter in parameters {
+            usage.push_str(&format!(
+                " [{} {}",
+                parameter.name,
+                parameter.data_type.as_str()
+            ));
+            if let Some(default) = &parameter.default_value {
+                usage.push_str(&format!(" = {}", default));
+            }
+            usage.push(']');
+        }
+        usage
+    }
+}
+
+/// Field a CTN type accepts in an object's PARAMETERS element
+///
+/// Values must be usable as a single command argument whether or not the
+/// collector runs a command; see
+/// [`check_argv_value`](crate::execution::object_parameters::check_argv_value).
+#[derive(Debug, Clone)]
+pub struct SupportedParameter {
+    pub name: String,
+    pub data_type: DataType,
+    pub description: String,
+    pub example: String,
+}
+
 /// Type of behavior
 #[derive(Debug, Clone, PartialEq)]
 pub enum BehaviorType {
@@ -155,6 +197,52 @@ pub struct CollectionStrategy {
     pub collection_mode: CollectionMode,
     pub required_capabilities: Vec<String>,
     pub performance_hints: PerformanceHints,
+    /// Minimum privilege the collector needs to produce meaningful data
+    pub required_privilege: PrivilegeRequirement,
+}
+
+/// Minimum privilege level a CTN type needs for collection
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub enum PrivilegeRequirement {
+    /// Runs as any user
+    #[default]
+    None,
+    /// Requires effective uid 0
+    Root,
+    /// Requires a specific Linux capability (e.g. `cap_dac_read_search`)
+    Capability(String),
+}
+
+impl PrivilegeRequirement {
+    /// Parse `none`, `root` or a `cap_*` capability name
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let lower = s.trim().to_ascii_lowercase();
+        match lower.as_str() {
+            "none" | "" => Ok(Self::None),
+            "root" => Ok(Self::Root),
+            cap if cap.starts_with("cap_") => Ok(Self::Capability(cap.to_string())),
+            _ => Err(format!("Unknown privilege requirement '{}'", s)),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::None => "none",
+            Self::Root => "root",
+            Self::Capability(cap) => cap,
+        }
+    }
+
+    /// Whether this requirement needs anything beyond an unprivileged user
+    pub fn is_elevated(&self) -> bool {
+        !matches!(self, Self::None)
+    }
+}
+
+impl std::fmt::Display for PrivilegeRequirement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -190,6 +278,7 @@ impl CtnContract {
             collection_strategy: CollectionStrategy::default(),
             metadata: CtnMetadata::default(),
             supported_behaviors: Vec::new(),
+            supported_parameters: Vec::new(),
         }
     }
 
@@ -481,51 +570,187 @@ impl CtnContract {
         &self,
         hints: &crate::execution::BehaviorHints,
     ) -> Result<(), CtnContractError> {
-        // Validate flags
+        self.resolve_behaviors(hints).map(|_| ())
+    }
+
+    /// Check behavior hints against the declared behaviors and type their values
+    ///
+    /// Unknown names, flags given a value and parameters given no value or a
+    /// value of the wrong type are all reported together.
+    pub fn resolve_behaviors(
+        &self,
+        hints: &crate::execution::BehaviorHints,
+    ) -> Result<BehaviorValues, CtnContractError> {
+        let mut values = BehaviorValues::new();
+        for parameter in self.behavior_parameters() {
+            if let Some(default) = &parameter.default_value {
+                if let Ok(value) = parse_behavior_value(parameter.data_type, default) {
+                    values = values.with_value(&parameter.name, value);
+                }
+            }
+        }
+
+        let mut problems = Vec::new();
         for flag in &hints.flags {
-            if !self.is_behavior_supported(flag) {
-                return Err(CtnContractError::UnsupportedBehavior {
-                    ctn_type: self.ctn_type.clone(),
-                    behavior: flag.clone(),
-                    supported_behaviors: self.get_supported_behavior_names(),
-                });
+            if self.flag_behavior(flag).is_some() {
+                values = values.with_flag(flag);
+            } else if let Some(parameter) = self.behavior_parameter(flag) {
+                problems.push(format!(
+                    "behavior '{}' needs a value of type {}",
+                    flag,
+                    parameter.data_type.as_str()
+                ));
+            } else {
+                problems.push(self.unknown_behavior(flag));
             }
         }
 
-        // Validate parameters - check if they belong to a declared behavior
-        for (param_name, _param_value) in &hints.parameters {
-            if !self.is_parameter_of_supported_behavior(param_name) {
-                return Err(CtnContractError::UnsupportedBehavior {
-                    ctn_type: self.ctn_type.clone(),
-                    behavior: param_name.clone(),
-                    supported_behaviors: self.get_supported_behavior_names(),
-                });
+        let mut parameters: Vec<_> = hints.parameters.iter().collect();
+        parameters.sort();
+        for (name, raw) in parameters {
+            if let Some(parameter) = self.behavior_parameter(name) {
+                match parse_behavior_value(parameter.data_type, raw) {
+                    Ok(value) => values = values.with_value(name, value),
+                    Err(reason) => problems.push(format!("behavior '{}': {}", name, reason)),
+                }
+            } else if self.flag_behavior(name).is_some() {
+                problems.push(format!(
+                    "behavior '{}' takes no value (got '{}')",
+                    name, raw
+                ));
+            } else {
+                problems.push(self.unknown_behavior(name));
             }
         }
 
-        Ok(())
+        if problems.is_empty() {
+            Ok(values)
+        } else {
+            Err(CtnContractError::InvalidBehaviors {
+                ctn_type: self.ctn_type.clone(),
+                problems,
+            })
+        }
     }
 
-    /// Check if parameter name belongs to any supported behavior
-    fn is_parameter_of_supported_behavior(&self, param_name: &str) -> bool {
-        self.supported_behaviors
+    /// Add a parameter an object's PARAMETERS element may set
+    pub fn add_supported_parameter(&mut self, parameter: SupportedParameter) {
+        self.supported_parameters.push(parameter);
+    }
+
+    /// Check an object's PARAMETERS elements against the declared parameters
+    ///
+    /// Unknown names, values of the wrong type and values that are not safe
+    /// as a single argv element are all reported together.
+    pub fn resolve_parameters(
+        &self,
+        object: &ExecutableObject,
+    ) -> Result<ParameterValues, CtnContractError> {
+        let (mut raw, mut problems) = extract_parameters(object);
+        raw.sort();
+
+        let mut values = ParameterValues::new();
+        for (name, raw) in raw {
+            let Some(parameter) = self.supported_parameters.iter().find(|p| p.name == name) else {
+                problems.push(self.unknown_parameter(&name));
+                continue;
+            };
+            let checked = check_argv_value(&raw)
+                .and_then(|()| parse_behavior_value(parameter.data_type, &raw));
+            match checked {
+                Ok(value) => values = values.with_value(&name, value),
+                Err(reason) => problems.push(format!("parameter '{}': {}", name, reason)),
+            }
+        }
+
+        if problems.is_empty() {
+            Ok(values)
+        } else {
+            Err(CtnContractError::InvalidParameters {
+                ctn_type: self.ctn_type.clone(),
+                problems,
+            })
+        }
+    }
+
+    fn unknown_parameter(&self, name: &str) -> String {
+        let names: Vec<&str> = self
+            .supported_parameters
             .iter()
-            .any(|behavior| behavior.parameters.iter().any(|p| p.name == param_name))
+            .map(|p| p.name.as_str())
+            .collect();
+        if names.is_empty() {
+            return format!(
+                "unknown parameter '{}': CTN type '{}' accepts no parameters",
+                name, self.ctn_type
+            );
+        }
+        match nearest_names(name, names.iter().copied()).first() {
+            Some(suggestion) => format!(
+                "unknown parameter '{}' (did you mean '{}'?)",
+                name, suggestion
+            ),
+            None => format!(
+                "unknown parameter '{}' (supported: {})",
+                name,
+                names.join(", ")
+            ),
+        }
     }
 
-    /// Check if a behavior is supported
-    fn is_behavior_supported(&self, behavior_name: &str) -> bool {
-        self.supported_behaviors
+    /// Every name a BEHAVIOR element may use with this contract
+    pub fn behavior_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self
+            .supported_behaviors
             .iter()
-            .any(|b| b.name == behavior_name)
+            .map(|b| b.name.as_str())
+            .chain(self.behavior_parameters().map(|p| p.name.as_str()))
+            .collect();
+        names.sort_unstable();
+        names.dedup();
+        names
     }
 
-    /// Get list of supported behavior names
-    fn get_supported_behavior_names(&self) -> Vec<String> {
+    fn behavior_parameters(&self) -> impl Iterator<Item = &BehaviorParameter> {
         self.supported_behaviors
             .iter()
-            .map(|b| b.name.clone())
-            .collect()
+            .flat_map(|behavior| behavior.parameters.iter())
+    }
+
+    /// Parameter declared under any behavior
+    fn behavior_parameter(&self, name: &str) -> Option<&BehaviorParameter> {
+        self.behavior_parameters().find(|p| p.name == name)
+    }
+
+    /// Flag behavior that is not also the name of a parameter
+    fn flag_behavior(&self, name: &str) -> Option<&SupportedBehavior> {
+        self.supported_behaviors.iter().find(|b| {
+            b.name == name
+                && b.behavior_type == BehaviorType::Flag
+                && self.behavior_parameter(name).is_none()
+        })
+    }
+
+    fn unknown_behavior(&self, name: &str) -> String {
+        let names = self.behavior_names();
+        if names.is_empty() {
+            return format!(
+                "unknown behavior '{}': CTN type '{}' supports no behaviors",
+                name, self.ctn_type
+            );
+        }
+        let suggestions = nearest_names(name, names.iter().copied());
+        match suggestions.first() {
+            Some(suggestion) => format!(
+                "unknown behavior '{}' (did you mean '{}'?)",
+                name, suggestion
+            ),
+            None => format!(
+                "unknown behavior '{}' (supported: {})",
+                name,
+                names.join(", ")
+            ),
+        }
     }
 }
 
@@ -697,6 +922,7 @@ impl Default for CollectionStrategy {
             collection_mode: CollectionMode::Metadata,
             required_capabilities: Vec::new(),
             performance_hints: PerformanceHints::default(),
+            required_privilege: PrivilegeRequirement::None,
         }
     }
 }
@@ -761,12 +987,19 @@ impl DataTypeCompatible for DataType {
             (DataType::Binary, DataType::Binary) => true,
             (DataType::Version, DataType::Version) => true,
             (DataType::EvrString, DataType::EvrString) => true,
+            (DataType::IpAddress, DataType::IpAddress) => true,
+            (DataType::Octal, DataType::Octal) => true,
+            (DataType::DateTime, DataType::DateTime) => true,
             (DataType::RecordData, DataType::RecordData) => true,
             // Allow some flexible conversions
             (DataType::Int, DataType::Float) => true,
             (DataType::Float, DataType::Int) => true,
             (DataType::String, DataType::Version) => true,
             (DataType::String, DataType::EvrString) => true,
+            (DataType::String, DataType::IpAddress) => true,
+            (DataType::String, DataType::Octal) => true,
+            (DataType::Int, DataType::DateTime) => true,
+            (DataType::DateTime, DataType::Int) => true,
             _ => false,
         }
     }
@@ -5,6 +5,12 @@
 
 use crate::types::common::{DataType, Operation};
 
+/// Reason recorded on criteria whose data source has nothing for them
+pub const DATA_UNAVAILABLE: &str = "data_unavailable";
+
+/// Reason recorded on criteria whose collector lacks a tool or service it needs
+pub const MISSING_CAPABILITY: &str = "missing_capability";
+
 /// CTN contract validation and compatibility errors
 #[derive(Debug, thiserror::Error)]
 pub enum CtnContractError {
@@ -86,6 +92,16 @@ pub enum CtnContractError {
         behavior: String,
         supported_behaviors: Vec<String>,
     },
+    #[error("Invalid behaviors for CTN type '{ctn_type}': {}", problems.join("; "))]
+    InvalidBehaviors {
+        ctn_type: String,
+        problems: Vec<String>,
+    },
+    #[error("Invalid parameters for CTN type '{ctn_type}': {}", problems.join("; "))]
+    InvalidParameters {
+        ctn_type: String,
+        problems: Vec<String>,
+    },
 }
 
 /// Strategy registry and management errors
@@ -178,6 +194,17 @@ pub enum CollectionError {
 
     #[error("Resource limit exceeded: {limit_type}")]
     ResourceLimitExceeded { limit_type: String },
+
+    #[error("Path denied by scanner path policy for object '{object_id}': {reason}")]
+    PathDenied {
+        object_id: String,
+        path: String,
+        reason: String,
+    },
+
+    /// The data source (e.g. an offline export) holds nothing for this object
+    #[error("Data unavailable for object '{object_id}': {reason}")]
+    DataUnavailable { object_id: String, reason: String },
 }
 
 /// CTN execution errors
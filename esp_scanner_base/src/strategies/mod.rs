@@ -24,10 +24,13 @@
 //!
 //! ```
 
+#[cfg(feature = "host")]
 pub mod command_executor;
 pub mod ctn_contract;
 pub mod errors;
+pub mod path_policy;
 pub mod registry;
+pub mod scan_target;
 pub mod traits;
 pub mod validation;
 
@@ -35,32 +38,37 @@ pub mod validation;
 pub use ctn_contract::{
     BehaviorParameter, BehaviorType, CollectionMappings, CollectionMode, CollectionStrategy,
     ComputedField, CtnContract, CtnFieldMappings, CtnMetadata, FieldComputation, ObjectFieldSpec,
-    ObjectRequirements, PerformanceHints, StateFieldSpec, StateRequirements, SupportedBehavior,
-    ValidationMappings,
+    ObjectRequirements, PerformanceHints, PrivilegeRequirement, StateFieldSpec, StateRequirements,
+    SupportedBehavior, SupportedParameter, ValidationMappings,
 };
 
 pub use errors::{
     BehaviorValidationError, CollectionError, CtnContractError, CtnExecutionError, StrategyError,
     ValidationError, ValidationErrorType, ValidationReport, ValidationWarning,
-    ValidationWarningType,
+    ValidationWarningType, DATA_UNAVAILABLE, MISSING_CAPABILITY,
 };
 
 pub use registry::{
-    ContractDetails, ContractValidationResult, CtnStrategyRegistry, RegistryBuilder,
-    RegistryHealth, RegistryQuery, RegistryStatistics,
+    ContractDetails, ContractValidationResult, CriterionProblem, CtnStrategyRegistry,
+    RegistryBuilder, RegistryHealth, RegistryQuery, RegistryStatistics,
 };
 
 pub use traits::{
     CollectedData, CollectionMetadata, CollectorPerformanceProfile, ComplianceStatus,
     CtnDataCollector, CtnExecutionResult, CtnExecutor, DefaultTestProcessor, ExecutionMetadata,
-    ExistenceResult, FieldValidationResult, ItemCheckResult, StateValidationResult, TestPhase,
+    ExistenceResult, FieldOutcome, FieldValidationResult, ItemCheckResult, StateValidationResult, TestPhase,
     TestProcessor,
 };
 
 pub use validation::{CtnCompatibilityChecker, CtnContractValidator};
 
+#[cfg(feature = "host")]
 pub use command_executor::{CommandError, CommandOutput, SystemCommandExecutor};
 
+pub use path_policy::{PathPolicy, PATH_DENIED};
+
+pub use scan_target::{ScanTarget, OFFLINE_TARGET_UNSUPPORTED};
+
 // ============================================================================
 // Module-level convenience functions
 // ============================================================================
@@ -0,0 +1,262 @@
+// src/strategies/path_policy.rs
+//! Global filesystem path policy
+//!
+//! Deny-list (and optional allow-list) of path prefixes that collectors must
+//! check before any open/stat, regardless of what a policy asks for. Paths are
+//! checked both as written (after lexical `..` normalization) and as the OS
+//! resolves them, one component at a time with each symlink followed before
+//! the next `..` applies, so neither `..` segments nor a symlink from an
+//! allowed area into a denied one can bypass the deny-list.
+
+use super::scan_target::{components, MAX_SYMLINK_HOPS};
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+
+/// Reason recorded on criteria whose objects touch a denied path
+pub const PATH_DENIED: &str = "path_denied";
+
+/// Deny/allow prefixes applied to every filesystem access
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PathPolicy {
+    deny: Vec<PathBuf>,
+    allow: Vec<PathBuf>,
+}
+
+impl PathPolicy {
+    /// Policy that permits every path
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Shared, unrestricted policy for collectors built without configuration
+    pub fn unrestricted() -> Arc<Self> {
+        Arc::new(Self::default())
+    }
+
+    /// Never read anything under `prefix`
+    pub fn with_deny(mut self, prefix: impl AsRef<Path>) -> Self {
+        self.deny.extend(prefix_forms(prefix.as_ref()));
+        self
+    }
+
+    /// Only read paths under an allowed prefix (once any allow prefix is set)
+    pub fn with_allow(mut self, prefix: impl AsRef<Path>) -> Self {
+        self.allow.extend(prefix_forms(prefix.as_ref()));
+        self
+    }
+
+    /// Whether any restriction is configured
+    pub fn is_restricted(&self) -> bool {
+        !self.deny.is_empty() || !self.allow.is_empty()
+    }
+
+    /// Check a path before touching it
+    ///
+    /// Returns the reason on violation. Both the lexical and the resolved form
+    /// must pass.
+    pub fn check(&self, path: &Path) -> Result<(), String> {
+        if !self.is_restricted() {
+            return Ok(());
+        }
+
+        let absolute = absolute(path);
+        let lexical = normalize_lexically(&absolute);
+        let resolved = resolve(&absolute)
+            .map_err(|reason| format!("'{}' cannot be resolved: {}", path.display(), reason))?;
+
+        for candidate in [&lexical, &resolved] {
+            if let Some(prefix) = self.deny.iter().find(|p| candidate.starts_with(p)) {
+                return Err(format!(
+                    "'{}' is under denied prefix '{}'",
+                    candidate.display(),
+                    prefix.display()
+                ));
+            }
+            if !self.allow.is_empty() && !self.allow.iter().any(|p| candidate.starts_with(p)) {
+                return Err(format!(
+                    "'{}' is outside the allowed paths",
+                    candidate.display()
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Whether a path passes [`check`](Self::check)
+    pub fn permits(&self, path: &Path) -> bool {
+        self.check(path).is_ok()
+    }
+}
+
+/// Lexical and resolved forms of a configured prefix
+///
+/// A prefix that is itself a symlink (e.g. `/var/lib/pgsql -> /data/pgsql`)
+/// must block both spellings.
+fn prefix_forms(prefix: &Path) -> Vec<PathBuf> {
+    let absolute = absolute(prefix);
+    let lexical = normalize_lexically(&absolute);
+    let resolved = resolve(&absolute).unwrap_or_else(|_| lexical.clone());
+    if resolved == lexical {
+        vec![lexical]
+    } else {
+        vec![lexical, resolved]
+    }
+}
+
+fn absolute(path: &Path) -> PathBuf {
+    if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        std::env::current_dir()
+            .map(|cwd| cwd.join(path))
+            .unwrap_or_else(|_| path.to_path_buf())
+    }
+}
+
+/// Collapse `.` and `..` without touching the filesystem
+fn normalize_lexically(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                out.pop();
+            }
+            other => out.push(other),
+        }
+    }
+    out
+}
+
+/// Resolve an absolute path the way the OS walks it: each existing symlink
+/// is followed before the next component, so `link/..` leaves the link's
+/// target rather than the link. Components that do not exist are appended as
+/// written
+fn resolve(path: &Path) -> Result<PathBuf, String> {
+    let root: PathBuf = path
+        .components()
+        .take_while(|c| matches!(c, Component::Prefix(_) | Component::RootDir))
+        .collect();
+
+    let mut pending = components(path);
+    let mut resolved = root.clone();
+    let mut hops = 0;
+    while let Some(name) = pending.pop_front() {
+        if name == ".." {
+            resolved.pop();
+            continue;
+        }
+
+        let candidate = resolved.join(&name);
+        let is_symlink = std::fs::symlink_metadata(&candidate)
+            .map(|metadata| metadata.file_type().is_symlink())
+            .unwrap_or(false);
+        if !is_symlink {
+            resolved = candidate;
+            continue;
+        }
+
+        hops += 1;
+        if hops > MAX_SYMLINK_HOPS {
+            return Err("too many levels of symbolic links".to_string());
+        }
+        let link = std::fs::read_link(&candidate).map_err(|e| e.to_string())?;
+        if link.is_absolute() {
+            resolved = root.clone();
+        }
+        for component in components(&link).into_iter().rev() {
+            pending.push_front(component);
+        }
+    }
+    Ok(resolved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unrestricted_permits_everything() {
+        let policy = PathPolicy::new();
+        assert!(!policy.is_restricted());
+        assert!(policy.permits(Path::new("/home/alice/.ssh/id_rsa")));
+    }
+
+    #[test]
+    fn test_deny_prefix_is_component_wise() {
+        let policy = PathPolicy::new().with_deny("/home");
+        assert!(!policy.permits(Path::new("/home/alice")));
+        assert!(!policy.permits(Path::new("/home")));
+        assert!(policy.permits(Path::new("/homework/notes")));
+    }
+
+    #[test]
+    fn test_dotdot_cannot_escape_into_denied_area() {
+        let policy = PathPolicy::new().with_deny("/home");
+        assert!(!policy.permits(Path::new("/etc/../home/alice/.bashrc")));
+        assert!(policy.permits(Path::new("/home/../etc/passwd")));
+    }
+
+    #[test]
+    fn test_allow_list_restricts_to_prefixes() {
+        let policy = PathPolicy::new().with_allow("/etc");
+        assert!(policy.permits(Path::new("/etc/ssh/sshd_config")));
+        assert!(!policy.permits(Path::new("/var/log/messages")));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlink_into_denied_area_is_denied() {
+        let root = tempfile::tempdir().unwrap();
+        let allowed = root.path().join("allowed");
+        let denied = root.path().join("denied");
+        std::fs::create_dir_all(&allowed).unwrap();
+        std::fs::create_dir_all(&denied).unwrap();
+        std::fs::write(denied.join("secret"), "s3cret").unwrap();
+        std::os::unix::fs::symlink(&denied, allowed.join("link")).unwrap();
+
+        let policy = PathPolicy::new().with_deny(&denied);
+
+        assert!(policy.permits(&allowed));
+        assert!(!policy.permits(&allowed.join("link/secret")));
+        // Nonexistent leaf below the symlink is still resolved through it
+        assert!(!policy.permits(&allowed.join("link/missing")));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlinked_prefix_blocks_both_spellings() {
+        let root = tempfile::tempdir().unwrap();
+        let data = root.path().join("data");
+        std::fs::create_dir_all(&data).unwrap();
+        let alias = root.path().join("alias");
+        std::os::unix::fs::symlink(&data, &alias).unwrap();
+
+        let policy = PathPolicy::new().with_deny(&alias);
+
+        assert!(!policy.permits(&alias.join("file")));
+        assert!(!policy.permits(&data.join("file")));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_dotdot_after_symlink_applies_to_link_target() {
+        let root = tempfile::tempdir().unwrap();
+        let allowed = root.path().join("allowed");
+        let home = root.path().join("home");
+        std::fs::create_dir_all(&allowed).unwrap();
+        std::fs::create_dir_all(home.join("x/sub")).unwrap();
+        std::fs::write(home.join("x/secret"), "s3cret").unwrap();
+        std::os::unix::fs::symlink(home.join("x/sub"), allowed.join("link")).unwrap();
+
+        let policy = PathPolicy::new().with_deny(&home);
+
+        // Lexically this is allowed/secret; the OS opens home/x/secret
+        let path = allowed.join("link/../secret");
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "s3cret");
+        assert!(!policy.permits(&path));
+        // Climbing back out of the target past the denied prefix is fine
+        assert!(policy.permits(&allowed.join("link/../../../allowed/file")));
+    }
+}
@@ -4,12 +4,17 @@
 //! Provides centralized registration and lookup of CTN strategies with comprehensive
 //! contract validation and compatibility checking.
 
+use crate::execution::behavior::extract_behavior_hints;
+use crate::resolution::references::nearest_names;
+use crate::results::RESULT_SCHEMA_VERSION;
 use crate::strategies::ctn_contract::CtnContract;
-use crate::strategies::errors::{StrategyError, ValidationReport};
+use crate::strategies::errors::{CtnContractError, StrategyError, ValidationReport};
 use crate::strategies::traits::{CtnDataCollector, CtnExecutor};
 use crate::strategies::validation::{CtnCompatibilityChecker, CtnContractValidator};
+use crate::types::criterion::CtnNodeId;
 use crate::types::execution_context::ExecutableCriterion;
-use std::collections::HashMap;
+use esp_compiler::compatibility::CapabilityManifest;
+use std::collections::{BTreeSet, HashMap};
 use std::sync::Arc;
 
 /// CTN strategy registry with contract-based validation
@@ -182,6 +187,79 @@ impl CtnStrategyRegistry {
         ))
     }
 
+    /// Everything that would stop `criterion` from running on this registry,
+    /// found without collecting
+    ///
+    /// Reports an unregistered CTN type on its own; otherwise contract errors
+    /// on object and state fields, then BEHAVIOR and PARAMETERS problems.
+    pub fn check_criterion(&self, criterion: &ExecutableCriterion) -> Vec<CriterionProblem> {
+        let problem = |message: String| CriterionProblem {
+            ctn_node_id: criterion.ctn_node_id,
+            criterion_type: criterion.criterion_type.clone(),
+            message,
+        };
+
+        let Ok(contract) = self.get_ctn_contract(&criterion.criterion_type) else {
+            let mut types = self.list_ctn_types();
+            types.sort();
+            let message =
+                match nearest_names(&criterion.criterion_type, types.iter().map(String::as_str))
+                    .first()
+                {
+                    Some(suggestion) => {
+                        format!("no registered strategy (did you mean '{}'?)", suggestion)
+                    }
+                    None => "no registered strategy".to_string(),
+                };
+            return vec![problem(message)];
+        };
+
+        let report =
+            CtnContractValidator::validate_criterion_against_contract(criterion, &contract);
+        let mut problems: Vec<CriterionProblem> = report
+            .errors
+            .into_iter()
+            .map(|error| match error.context {
+                Some(context) => problem(format!("{} ({})", error.message, context)),
+                None => problem(error.message),
+            })
+            .collect();
+        problems.extend(self.check_object_elements(criterion));
+        problems
+    }
+
+    /// BEHAVIOR and PARAMETERS problems in a criterion's objects
+    ///
+    /// Unregistered CTN types report nothing here; `check_criterion` covers them.
+    pub fn check_object_elements(&self, criterion: &ExecutableCriterion) -> Vec<CriterionProblem> {
+        let Ok(contract) = self.get_ctn_contract(&criterion.criterion_type) else {
+            return Vec::new();
+        };
+
+        let mut problems = Vec::new();
+        for object in &criterion.objects {
+            let behaviors = contract.resolve_behaviors(&extract_behavior_hints(object));
+            let parameters = contract.resolve_parameters(object);
+            for error in [behaviors.err(), parameters.err()].into_iter().flatten() {
+                let (CtnContractError::InvalidBehaviors {
+                    problems: found, ..
+                }
+                | CtnContractError::InvalidParameters {
+                    problems: found, ..
+                }) = error
+                else {
+                    continue;
+                };
+                problems.extend(found.into_iter().map(|message| CriterionProblem {
+                    ctn_node_id: criterion.ctn_node_id,
+                    criterion_type: criterion.criterion_type.clone(),
+                    message: format!("object '{}': {}", object.identifier, message),
+                }));
+            }
+        }
+        problems
+    }
+
     /// Check if CTN type is registered
     pub fn has_ctn_type(&self, ctn_type: &str) -> bool {
         self.contracts.contains_key(ctn_type)
@@ -192,10 +270,65 @@ impl CtnStrategyRegistry {
         self.contracts.keys().cloned().collect()
     }
 
+    /// What a scanner built on this registry can execute
+    ///
+    /// Criterion types come from the registered contracts; operations,
+    /// runtime operations and data types are everything the execution engine
+    /// implements.
+    pub fn capability_manifest(&self, scanner_version: &str) -> CapabilityManifest {
+        let mut manifest = CapabilityManifest::language();
+        manifest.scanner_version = scanner_version.to_string();
+        manifest.result_schema_version = RESULT_SCHEMA_VERSION.to_string();
+        manifest.criterion_types = self.contracts.keys().cloned().collect();
+        manifest
+    }
+
+    /// Hash of the registered strategies recorded in scan provenance
+    ///
+    /// Covers each criterion type's contract version, collector, state
+    /// fields and behaviors, so results from scanners that check the same
+    /// policy differently can be told apart.
+    pub fn strategy_set_hash(&self) -> String {
+        let mut ctn_types: Vec<_> = self.contracts.keys().collect();
+        ctn_types.sort();
+        let mut described = format!("result schema {}\n", RESULT_SCHEMA_VERSION);
+        for ctn_type in ctn_types {
+            let contract = &self.contracts[ctn_type];
+            let collector = self
+                .get_collector_for_ctn(ctn_type)
+                .map(|c| c.collector_id().to_string())
+                .unwrap_or_default();
+            let state = &contract.state_requirements;
+            let mut fields: Vec<_> = state
+                .required_fields
+                .iter()
+                .chain(&state.optional_fields)
+                .map(|f| format!("{}:{}", f.name, f.data_type.as_str()))
+                .collect();
+            fields.sort();
+            let mut behaviors: Vec<_> = contract
+                .supported_behaviors
+                .iter()
+                .map(|b| b.usage())
+                .collect();
+            behaviors.sort();
+            described.push_str(&format!(
+                "{} {} {} [{}] [{}]\n",
+                ctn_type,
+                contract.metadata.version,
+                collector,
+                fields.join(","),
+                behaviors.join(",")
+            ));
+        }
+        crate::results::provenance::sha256(described.as_bytes())
+    }
+
     /// Get registry statistics
     pub fn get_statistics(&self) -> RegistryStatistics {
         RegistryStatistics {
             total_ctn_types: self.contracts.len(),
+            registered_types: self.contracts.keys().cloned().collect(),
             total_collectors: self.collectors.len(),
             total_executors: self.executors.len(),
             contracts_with_computed_fields: self
@@ -380,6 +513,8 @@ impl Default for CtnStrategyRegistry {
 #[derive(Debug, Clone)]
 pub struct RegistryStatistics {
     pub total_ctn_types: usize,
+    /// Names of the registered CTN types
+    pub registered_types: BTreeSet<String>,
     pub total_collectors: usize,
     pub total_executors: usize,
     pub contracts_with_computed_fields: usize,
@@ -397,6 +532,24 @@ pub struct ContractDetails {
     pub performance_profile: crate::strategies::traits::CollectorPerformanceProfile,
 }
 
+/// A reason one criterion cannot run on a registry
+#[derive(Debug, Clone, PartialEq)]
+pub struct CriterionProblem {
+    pub ctn_node_id: CtnNodeId,
+    pub criterion_type: String,
+    pub message: String,
+}
+
+impl std::fmt::Display for CriterionProblem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "CTN {} (#{}): {}",
+            self.criterion_type, self.ctn_node_id, self.message
+        )
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ContractValidationResult {
     pub ctn_type: String,
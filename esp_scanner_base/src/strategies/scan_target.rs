@@ -0,0 +1,233 @@
+// src/strategies/scan_target.rs
+//! Scan target: the live host, or a filesystem mounted somewhere else
+//!
+//! An offline target is an image or container root mounted at a directory
+//! such as `/mnt/image`. Collectors that read files map every object path
+//! into the root with [`ScanTarget::resolve`]; collectors that run commands
+//! or read kernel state would observe the scanning host instead, so they
+//! declare themselves unsupported and their criteria are reported as not
+//! evaluated.
+//!
+//! Resolution follows symlinks one component at a time inside the root, the
+//! way the kernel would after a `chroot`: an absolute link target starts
+//! over at the root and `..` stops at it. A link in the image pointing at
+//! `/etc/shadow` therefore reads the image's shadow file, never the host's.
+
+use std::collections::VecDeque;
+use std::ffi::OsString;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+
+/// Reason recorded on criteria whose collector cannot read an offline target
+pub const OFFLINE_TARGET_UNSUPPORTED: &str = "offline_target_unsupported";
+
+/// Symlinks followed while resolving one path, as `MAXSYMLINKS` on Linux
+pub(crate) const MAX_SYMLINK_HOPS: usize = 40;
+
+/// Filesystem the scan evaluates
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ScanTarget {
+    root: Option<PathBuf>,
+}
+
+impl ScanTarget {
+    /// The running host, with paths used as written
+    pub fn host() -> Self {
+        Self::default()
+    }
+
+    /// Shared host target for collectors built without configuration
+    pub fn shared_host() -> Arc<Self> {
+        Arc::new(Self::default())
+    }
+
+    /// A filesystem mounted at `root`
+    ///
+    /// The root is canonicalized once here and must be a directory.
+    pub fn offline(root: impl AsRef<Path>) -> io::Result<Self> {
+        let root = std::fs::canonicalize(root.as_ref())?;
+        if !root.is_dir() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("scan root '{}' is not a directory", root.display()),
+            ));
+        }
+        Ok(Self { root: Some(root) })
+    }
+
+    /// Whether paths are mapped into a mounted root
+    pub fn is_offline(&self) -> bool {
+        self.root.is_some()
+    }
+
+    /// The mounted root, for an offline target
+    pub fn root(&self) -> Option<&Path> {
+        self.root.as_deref()
+    }
+
+    /// Host path of `path` as the target sees it
+    ///
+    /// On the host target the path is returned unchanged. On an offline
+    /// target, relative paths are taken from the target's `/`, and the
+    /// result is always under the root with every existing symlink
+    /// resolved. Components that do not exist are appended as written.
+    pub fn resolve(&self, path: &Path) -> io::Result<PathBuf> {
+        let Some(root) = &self.root else {
+            return Ok(path.to_path_buf());
+        };
+
+        let mut pending: VecDeque<OsString> = components(path);
+        let mut resolved = root.clone();
+        let mut hops = 0;
+        while let Some(name) = pending.pop_front() {
+            if name == ".." {
+                if resolved != *root {
+                    resolved.pop();
+                }
+                continue;
+            }
+
+            let candidate = resolved.join(&name);
+            match std::fs::symlink_metadata(&candidate) {
+                Ok(metadata) if metadata.file_type().is_symlink() => {
+                    hops += 1;
+                    if hops > MAX_SYMLINK_HOPS {
+                        return Err(io::Error::new(
+                            io::ErrorKind::Other,
+                            format!(
+                                "too many levels of symbolic links resolving '{}'",
+                                path.display()
+                            ),
+                        ));
+                    }
+                    let link = std::fs::read_link(&candidate)?;
+                    if link.is_absolute() {
+                        resolved = root.clone();
+                    }
+                    for component in components(&link).into_iter().rev() {
+                        pending.push_front(component);
+                    }
+                }
+                Err(e) if e.kind() == io::ErrorKind::PermissionDenied => return Err(e),
+                _ => resolved = candidate,
+            }
+        }
+        Ok(resolved)
+    }
+
+    /// Re-resolve a host path found under the root, such as a directory
+    /// entry, so a symlink it names cannot lead out of the root
+    pub fn contain(&self, host_path: &Path) -> io::Result<PathBuf> {
+        match &self.root {
+            Some(root) => match host_path.strip_prefix(root) {
+                Ok(inside) => self.resolve(inside),
+                Err(_) => self.resolve(host_path),
+            },
+            None => Ok(host_path.to_path_buf()),
+        }
+    }
+}
+
+/// Normal and `..` components of a path; `/` and `.` are dropped
+pub(crate) fn components(path: &Path) -> VecDeque<OsString> {
+    path.components()
+        .filter_map(|component| match component {
+            Component::Normal(name) => Some(name.to_os_string()),
+            Component::ParentDir => Some(OsString::from("..")),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn image() -> (tempfile::TempDir, ScanTarget) {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("etc/ssh")).unwrap();
+        std::fs::write(dir.path().join("etc/shadow"), "image").unwrap();
+        let target = ScanTarget::offline(dir.path()).unwrap();
+        (dir, target)
+    }
+
+    #[test]
+    fn test_host_target_leaves_paths_alone() {
+        let target = ScanTarget::host();
+        assert!(!target.is_offline());
+        assert_eq!(
+            target.resolve(Path::new("/etc/passwd")).unwrap(),
+            PathBuf::from("/etc/passwd")
+        );
+    }
+
+    #[test]
+    fn test_paths_map_under_root() {
+        let (_dir, target) = image();
+        let root = target.root().unwrap().to_path_buf();
+        assert_eq!(
+            target.resolve(Path::new("/etc/ssh/sshd_config")).unwrap(),
+            root.join("etc/ssh/sshd_config")
+        );
+        assert_eq!(
+            target.resolve(Path::new("etc/shadow")).unwrap(),
+            root.join("etc/shadow")
+        );
+        assert_eq!(
+            target
+                .resolve(Path::new("/../../etc/../etc/shadow"))
+                .unwrap(),
+            root.join("etc/shadow")
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_absolute_symlink_stays_inside_root() {
+        let (dir, target) = image();
+        let root = target.root().unwrap().to_path_buf();
+        std::os::unix::fs::symlink("/etc/shadow", dir.path().join("etc/link")).unwrap();
+        std::os::unix::fs::symlink("/etc", dir.path().join("etc/ssh/up")).unwrap();
+
+        let resolved = target.resolve(Path::new("/etc/link")).unwrap();
+        assert_eq!(resolved, root.join("etc/shadow"));
+        assert_eq!(std::fs::read_to_string(resolved).unwrap(), "image");
+        assert_eq!(
+            target.resolve(Path::new("/etc/ssh/up/shadow")).unwrap(),
+            root.join("etc/shadow")
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_relative_symlink_cannot_climb_out() {
+        let (dir, target) = image();
+        let root = target.root().unwrap().to_path_buf();
+        std::os::unix::fs::symlink("../../../../../../etc/passwd", dir.path().join("etc/ssh/x"))
+            .unwrap();
+
+        let resolved = target.resolve(Path::new("/etc/ssh/x")).unwrap();
+        assert_eq!(resolved, root.join("etc/passwd"));
+        assert!(target
+            .contain(&root.join("etc/ssh/x"))
+            .unwrap()
+            .starts_with(&root));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlink_loop_is_an_error() {
+        let (dir, target) = image();
+        std::os::unix::fs::symlink("b", dir.path().join("a")).unwrap();
+        std::os::unix::fs::symlink("a", dir.path().join("b")).unwrap();
+        assert!(target.resolve(Path::new("/a")).is_err());
+    }
+
+    #[test]
+    fn test_root_must_be_a_directory() {
+        let (dir, _) = image();
+        assert!(ScanTarget::offline(dir.path().join("etc/shadow")).is_err());
+        assert!(ScanTarget::offline(dir.path().join("missing")).is_err());
+    }
+}
@@ -6,6 +6,8 @@
 //! mappings, and validation rules.
 
 use crate::execution::behavior::BehaviorHints;
+use crate::execution::consistency::FileObservation;
+use crate::results::Advisory;
 use crate::strategies::ctn_contract::CtnContract;
 use crate::strategies::errors::{CollectionError, CtnExecutionError, ValidationReport};
 use crate::types::common::ResolvedValue;
@@ -113,6 +115,25 @@ pub trait CtnDataCollector: Send + Sync {
     fn supports_batch_collection(&self) -> bool {
         false
     }
+
+    /// Whether this CTN type can be collected from an offline
+    /// [`ScanTarget`](super::ScanTarget)
+    ///
+    /// Only collectors that map every path they read into the target's root
+    /// may return `true`; commands and kernel interfaces describe the
+    /// scanning host, not the mounted image.
+    fn supports_offline_target(&self, _ctn_type: &str) -> bool {
+        false
+    }
+
+    /// Whether data collected for this CTN type may be reused by later
+    /// criteria of the same scan
+    ///
+    /// Return `false` for volatile data, such as open sockets or running
+    /// processes, that each criterion should observe afresh.
+    fn supports_result_caching(&self, _ctn_type: &str) -> bool {
+        true
+    }
 }
 
 /// Performance profile for collectors
@@ -216,8 +237,13 @@ pub struct CollectionMetadata {
     /// Platform-specific metadata
     pub platform_specific: Option<serde_json::Value>,
 
-    /// Collection warnings or notes
+    /// Why collection observed nothing for this object (path not found,
+    /// permission denied, pattern matched nothing); an object carrying
+    /// warnings does not count as observed when checking for vacuous passes
     pub warnings: Vec<String>,
+
+    /// Files read or stat'ed, checked for changes during the scan
+    pub observed_files: Vec<FileObservation>,
 }
 
 impl CollectedData {
@@ -234,6 +260,7 @@ impl CollectedData {
                 collection_duration: Duration::from_millis(0),
                 platform_specific: None,
                 warnings: Vec::new(),
+                observed_files: Vec::new(),
             },
         }
     }
@@ -258,6 +285,11 @@ impl CollectedData {
         self.metadata.warnings.push(warning);
     }
 
+    /// Record what was seen of a file for the scan consistency check
+    pub fn observe_file(&mut self, observation: FileObservation) {
+        self.metadata.observed_files.push(observation);
+    }
+
     /// Set collection duration
     pub fn set_collection_duration(&mut self, duration: Duration) {
         self.metadata.collection_duration = duration;
@@ -307,15 +339,26 @@ pub struct ExecutionMetadata {
     pub states_evaluated: usize,
     pub warnings: Vec<String>,
     pub debug_info: Option<serde_json::Value>,
+    /// Informational notes; never affect the status
+    pub advisories: Vec<Advisory>,
 }
 
 /// Compliance status enumeration
+///
+/// Only `Pass` and `Fail` are outcomes of a check. `Error` means the check
+/// could not run (collection or execution failed), `Unknown` that it ran
+/// but could not decide, and `NotApplicable` that it does not apply to the
+/// host (e.g. SELinux checks on a host without SELinux). `Waived` is a
+/// `Fail` covered by an approved exception and counts as `Pass` in blocks.
+/// Negation swaps `Pass` and `Fail` and leaves the others alone.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ComplianceStatus {
     Pass,
     Fail,
     Error,
     Unknown,
+    NotApplicable,
+    Waived,
 }
 
 impl ComplianceStatus {
@@ -325,11 +368,74 @@ impl ComplianceStatus {
             Self::Fail => Self::Pass,
             Self::Error => Self::Error,
             Self::Unknown => Self::Unknown,
+            Self::NotApplicable => Self::NotApplicable,
+            Self::Waived => Self::Waived,
+        }
+    }
+
+    /// Status of an AND over `statuses`
+    ///
+    /// A `Fail` decides the result whatever else is there. Otherwise an
+    /// `Error`, then an `Unknown`, leaves it undecided. `NotApplicable`
+    /// children are ignored, so the result is `Pass` when the rest passed
+    /// and `NotApplicable` when nothing applied. Empty is `NotApplicable`.
+    pub fn all(statuses: impl IntoIterator<Item = Self>) -> Self {
+        Self::combine(statuses, Self::Fail, Self::Pass)
+    }
+
+    /// Status of an OR over `statuses`
+    ///
+    /// A `Pass` decides the result whatever else is there. Otherwise an
+    /// `Error`, then an `Unknown`, leaves it undecided. `NotApplicable`
+    /// children are ignored, so the result is `Fail` when the rest failed
+    /// and `NotApplicable` when nothing applied. Empty is `NotApplicable`.
+    pub fn any(statuses: impl IntoIterator<Item = Self>) -> Self {
+        Self::combine(statuses, Self::Pass, Self::Fail)
+    }
+
+    /// `decisive` if any status is, else the first undecided status by
+    /// precedence, else `otherwise` if anything applied
+    fn combine(statuses: impl IntoIterator<Item = Self>, decisive: Self, otherwise: Self) -> Self {
+        let (mut error, mut unknown, mut applied) = (false, false, false);
+        for status in statuses {
+            let status = if status == Self::Waived {
+                Self::Pass
+            } else {
+                status
+            };
+            match status {
+                status if status == decisive => return decisive,
+                Self::Error => error = true,
+                Self::Unknown => unknown = true,
+                Self::NotApplicable => continue,
+                _ => {}
+            }
+            applied = true;
+        }
+        if error {
+            Self::Error
+        } else if unknown {
+            Self::Unknown
+        } else if applied {
+            otherwise
+        } else {
+            Self::NotApplicable
         }
     }
 
     pub fn is_successful(self) -> bool {
-        matches!(self, Self::Pass | Self::Fail)
+        matches!(self, Self::Pass | Self::Fail | Self::Waived)
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Pass => "pass",
+            Self::Fail => "fail",
+            Self::Error => "error",
+            Self::Unknown => "unknown",
+            Self::NotApplicable => "not_applicable",
+            Self::Waived => "waived",
+        }
     }
 }
 
@@ -371,6 +477,63 @@ pub struct FieldValidationResult {
     pub operation: crate::types::common::Operation,
     pub passed: bool,
     pub message: String,
+    /// How the field was evaluated; `Unknown` when the item lacked the field
+    pub outcome: FieldOutcome,
+}
+
+impl FieldValidationResult {
+    /// Result for a state field the collected item does not carry
+    ///
+    /// Never passes, so a missing attribute cannot satisfy a check vacuously.
+    pub fn not_present(
+        field_name: impl Into<String>,
+        expected_value: ResolvedValue,
+        operation: crate::types::common::Operation,
+        message: impl Into<String>,
+    ) -> Self {
+        Self {
+            field_name: field_name.into(),
+            expected_value,
+            actual_value: ResolvedValue::String(String::new()),
+            operation,
+            passed: false,
+            message: message.into(),
+            outcome: FieldOutcome::Unknown,
+        }
+    }
+
+    /// Whether the field was present on the item and compared
+    pub fn was_compared(&self) -> bool {
+        self.outcome != FieldOutcome::Unknown
+    }
+}
+
+/// Outcome of comparing one state field against one collected item
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FieldOutcome {
+    Pass,
+    Fail,
+    /// The field was not present on the item, so nothing was compared
+    Unknown,
+}
+
+impl FieldOutcome {
+    pub fn from_passed(passed: bool) -> Self {
+        if passed {
+            Self::Pass
+        } else {
+            Self::Fail
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Pass => "pass",
+            Self::Fail => "fail",
+            Self::Unknown => "unknown",
+        }
+    }
 }
 
 /// Item check evaluation result
@@ -429,6 +592,88 @@ impl CtnExecutionResult {
         }
     }
 
+    /// Create a result for a criterion that was skipped without evaluation
+    pub fn not_evaluated(ctn_type: String, reason: &str) -> Self {
+        Self {
+            ctn_type,
+            status: ComplianceStatus::Unknown,
+            test_phase: TestPhase::ExistenceCheck,
+            existence_result: None,
+            state_results: Vec::new(),
+            item_check_result: None,
+            message: format!("Criterion not evaluated: {}", reason),
+            details: serde_json::json!({
+                "not_evaluated": true,
+                "reason": reason,
+            }),
+            execution_metadata: ExecutionMetadata::default(),
+        }
+    }
+
+    /// Create a result for a criterion that does not apply to the host
+    pub fn not_applicable(ctn_type: String, reason: &str) -> Self {
+        Self {
+            ctn_type,
+            status: ComplianceStatus::NotApplicable,
+            test_phase: TestPhase::ExistenceCheck,
+            existence_result: None,
+            state_results: Vec::new(),
+            item_check_result: None,
+            message: format!("Criterion not applicable: {}", reason),
+            details: serde_json::json!({ "reason": reason }),
+            execution_metadata: ExecutionMetadata::default(),
+        }
+    }
+
+    /// Whether this result was produced without evaluating the criterion
+    pub fn is_not_evaluated(&self) -> bool {
+        self.details
+            .get("not_evaluated")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false)
+    }
+
+    /// Flag a result that passed without observing anything, e.g. a
+    /// `none_exist` check whose path could not be looked at
+    pub fn mark_vacuous(&mut self, reason: &str) {
+        if !self.details.is_object() {
+            self.details = serde_json::json!({});
+        }
+        self.details["vacuous"] = serde_json::json!(true);
+        self.details["vacuous_reason"] = serde_json::json!(reason);
+    }
+
+    /// Whether this result passed without observing anything
+    pub fn is_vacuous(&self) -> bool {
+        self.details
+            .get("vacuous")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false)
+    }
+
+    /// Drop retained evidence down to what findings need
+    ///
+    /// Passing field results, structured details and debug info are discarded;
+    /// failing field results are kept so findings still report expected/actual.
+    pub fn summarize_evidence(&mut self) {
+        let objects = self.state_results.len();
+        for state_result in &mut self.state_results {
+            state_result.state_results.retain(|field| !field.passed);
+        }
+        if !self.is_not_evaluated() {
+            let vacuous_reason = self.details.get("vacuous_reason").cloned();
+            self.details = serde_json::json!({
+                "summarized": true,
+                "objects": objects,
+            });
+            if let Some(reason) = vacuous_reason {
+                self.details["vacuous"] = serde_json::json!(true);
+                self.details["vacuous_reason"] = reason;
+            }
+        }
+        self.execution_metadata.debug_info = None;
+    }
+
     /// Add execution details
     pub fn with_details(mut self, details: serde_json::Value) -> Self {
         self.details = details;
@@ -458,6 +703,19 @@ impl CtnExecutionResult {
         self.item_check_result = Some(result);
         self
     }
+
+    /// Attach an informational note about one object
+    ///
+    /// Advisories are reported separately and never change the status.
+    pub fn add_advisory(&mut self, object_id: impl Into<String>, message: impl Into<String>) {
+        self.execution_metadata
+            .advisories
+            .push(Advisory::for_object(object_id, message));
+    }
+
+    pub fn advisories(&self) -> &[Advisory] {
+        &self.execution_metadata.advisories
+    }
 }
 
 impl Default for ExecutionMetadata {
@@ -468,6 +726,7 @@ impl Default for ExecutionMetadata {
             states_evaluated: 0,
             warnings: Vec::new(),
             debug_info: None,
+            advisories: Vec::new(),
         }
     }
 }
@@ -417,7 +417,8 @@ impl CtnContractValidator {
                 );
             }
 
-            // Check validation mapping exists
+            // Check validation mapping exists; a `*` spec accepts any field
+            let accepts_any_field = contract.state_requirements.get_field_spec("*").is_some();
             let has_direct_mapping = contract
                 .field_mappings
                 .validation_mappings
@@ -429,7 +430,7 @@ impl CtnContractValidator {
                 .computed_mappings
                 .contains_key(&field.name);
 
-            if !has_direct_mapping && !has_computed_mapping {
+            if !accepts_any_field && !has_direct_mapping && !has_computed_mapping {
                 report.add_error(
                     ValidationErrorType::FieldMappingError,
                     format!("State field '{}' has no validation mapping", field.name),
@@ -0,0 +1,72 @@
+//! Item shapes produced by the stock collectors, for [`MockCollector`]
+//!
+//! Field names match what the SDK's filesystem and command collectors emit,
+//! so executors written against those shapes can be tested without a host.
+//!
+//! [`MockCollector`]: super::MockCollector
+
+use serde_json::{json, Value};
+
+/// An existing, readable file with `mode` (e.g. `"0644"`) owned by `uid:gid`
+pub fn file_metadata(mode: &str, uid: u32, gid: u32) -> Value {
+    json!({
+        "exists": true,
+        "readable": true,
+        "file_mode": mode,
+        "file_owner": uid.to_string(),
+        "file_group": gid.to_string(),
+        "file_size": 0,
+    })
+}
+
+/// A file that does not exist
+pub fn absent_file() -> Value {
+    json!({
+        "exists": false,
+        "readable": false,
+        "file_mode": "",
+        "file_owner": "",
+        "file_group": "",
+        "file_size": 0,
+        "file_mtime": 0,
+    })
+}
+
+/// A file holding `content`
+pub fn file_content(content: &str) -> Value {
+    json!({"file_content": content})
+}
+
+/// A JSON file whose top level is the object `data`
+pub fn json_record(data: Value) -> Value {
+    json!({"json_data": data})
+}
+
+/// A systemd unit; loaded when active or enabled
+pub fn service(name: &str, active: bool, enabled: bool) -> Value {
+    json!({
+        "service_name": name,
+        "active": active,
+        "enabled": enabled,
+        "loaded": active || enabled,
+    })
+}
+
+/// An installed package at `version`
+pub fn package(name: &str, version: &str) -> Value {
+    json!({"package_name": name, "installed": true, "version": version})
+}
+
+/// A package that is not installed
+pub fn missing_package(name: &str) -> Value {
+    json!({"package_name": name, "installed": false})
+}
+
+/// A kernel parameter; integer values are also exposed as `value_int`
+pub fn sysctl(name: &str, value: &str) -> Value {
+    let mut item = json!({"parameter_name": name, "value": value});
+    if let Ok(int_value) = value.parse::<i64>() {
+        item["value_int"] = json!(int_value);
+    }
+    item
+}
@@ -0,0 +1,161 @@
+//! Golden assertions for criterion results and scan findings
+//!
+//! Golden files are rewritten instead of compared when `UPDATE_SNAPSHOTS` is
+//! set, e.g. `UPDATE_SNAPSHOTS=1 cargo test`.
+
+use super::TestkitError;
+use crate::results::compare::DEFAULT_TOLERANCE;
+use crate::results::{HostContext, ScanResult, UserContext};
+use crate::strategies::CtnExecutionResult;
+use crate::types::common::ResolvedValue;
+use serde_json::{json, Value};
+use std::path::Path;
+
+/// The outcome of a criterion result as JSON: status, per-object field
+/// outcomes with expected and actual values, the vacuous-pass reason and
+/// advisories. Messages and timings are left out so goldens stay stable.
+pub fn summarize(result: &CtnExecutionResult) -> Value {
+    let mut objects = serde_json::Map::new();
+    for state_result in &result.state_results {
+        let fields: Vec<Value> = state_result
+            .state_results
+            .iter()
+            .map(|field| {
+                json!({
+                    "field": field.field_name,
+                    "outcome": field.outcome.as_str(),
+                    "expected": plain(&field.expected_value),
+                    "actual": plain(&field.actual_value),
+                })
+            })
+            .collect();
+        objects.insert(
+            state_result.object_id.clone(),
+            json!({"passed": state_result.combined_result, "fields": fields}),
+        );
+    }
+
+    let mut summary = json!({
+        "status": result.status.as_str(),
+        "objects": objects,
+    });
+    if result.is_vacuous() {
+        summary["vacuous"] = result.details["vacuous_reason"].clone();
+    }
+    if !result.advisories().is_empty() {
+        let advisories: Vec<Value> = result
+            .advisories()
+            .iter()
+            .map(|a| json!({"object_id": a.object_id, "message": a.message}))
+            .collect();
+        summary["advisories"] = Value::Array(advisories);
+    }
+    summary
+}
+
+/// Check that [`summarize`] of `result` equals `expected`
+pub fn check_golden(result: &CtnExecutionResult, expected: Value) -> Result<(), TestkitError> {
+    let actual = summarize(result);
+    if actual == expected {
+        return Ok(());
+    }
+    Err(TestkitError::GoldenMismatch {
+        expected: pretty(&expected),
+        actual: pretty(&actual),
+        message: result.message.clone(),
+    })
+}
+
+/// Check that [`summarize`] of `result` equals the JSON in `path`
+pub fn check_golden_file(
+    result: &CtnExecutionResult,
+    path: impl AsRef<Path>,
+) -> Result<(), TestkitError> {
+    let path = path.as_ref();
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        write_golden(path, &pretty(&summarize(result)))?;
+    }
+    check_golden(result, read_golden(path)?)
+}
+
+/// Check that a scan reports the same findings as the scan result in `path`,
+/// ignoring ordering, volatile fields and the host and user scanned from, so
+/// one golden serves every machine
+pub fn check_findings_golden(
+    result: &ScanResult,
+    path: impl AsRef<Path>,
+) -> Result<(), TestkitError> {
+    let path = path.as_ref();
+    let invalid = |reason: String| TestkitError::InvalidGolden {
+        path: path.display().to_string(),
+        reason,
+    };
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        let serialize = |result: &ScanResult| {
+            result
+                .to_json()
+                .map_err(|e| invalid(format!("cannot serialize scan result: {}", e)))
+        };
+        let mut canonical = ScanResult::from_json(&serialize(result)?)
+            .map_err(|e| invalid(format!("scan result does not round-trip: {}", e)))?;
+        canonical.canonicalize();
+        canonical.metadata.host = HostContext::new(String::new(), String::new());
+        canonical.metadata.user_context = UserContext::new(String::new(), String::new());
+        write_golden(path, &serialize(&canonical)?)?;
+    }
+    let mut golden = ScanResult::from_json(&read_golden(path)?.to_string())
+        .map_err(|e| invalid(format!("not a scan result: {}", e)))?;
+    golden.metadata.host = result.metadata.host.clone();
+    golden.metadata.user_context = result.metadata.user_context.clone();
+    let differences = golden.semantic_differences(result, DEFAULT_TOLERANCE);
+    if differences.is_empty() {
+        return Ok(());
+    }
+    Err(TestkitError::FindingsMismatch {
+        path: path.display().to_string(),
+        differences,
+    })
+}
+
+/// A value as plain JSON rather than its tagged serde form
+fn plain(value: &ResolvedValue) -> Value {
+    match value {
+        ResolvedValue::String(s) | ResolvedValue::Version(s) | ResolvedValue::EvrString(s) => {
+            json!(s)
+        }
+        ResolvedValue::Integer(i) => json!(i),
+        ResolvedValue::Float(f) => json!(f),
+        ResolvedValue::Boolean(b) => json!(b),
+        ResolvedValue::Collection(items) => Value::Array(items.iter().map(plain).collect()),
+        ResolvedValue::RecordData(record) => record.as_json_value().clone(),
+        ResolvedValue::Binary(bytes) => json!(format!("<{} bytes>", bytes.len())),
+    }
+}
+
+fn pretty(value: &Value) -> String {
+    serde_json::to_string_pretty(value).unwrap_or_else(|_| value.to_string())
+}
+
+fn read_golden(path: &Path) -> Result<Value, TestkitError> {
+    let invalid = |reason: String| TestkitError::InvalidGolden {
+        path: path.display().to_string(),
+        reason,
+    };
+    let text = std::fs::read_to_string(path).map_err(|e| {
+        invalid(format!(
+            "cannot read: {}; run with UPDATE_SNAPSHOTS=1 to create it",
+            e
+        ))
+    })?;
+    serde_json::from_str(&text).map_err(|e| invalid(format!("not JSON: {}", e)))
+}
+
+fn write_golden(path: &Path, contents: &str) -> Result<(), TestkitError> {
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    std::fs::write(path, format!("{}\n", contents)).map_err(|e| TestkitError::InvalidGolden {
+        path: path.display().to_string(),
+        reason: format!("cannot write: {}", e),
+    })
+}
@@ -0,0 +1,155 @@
+//! Collector returning canned items
+
+use super::json_value;
+use crate::execution::behavior::BehaviorHints;
+use crate::strategies::{CollectedData, CollectionError, CtnContract, CtnDataCollector};
+use crate::types::execution_context::ExecutableObject;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+type ErrorFn = Box<dyn Fn(&str) -> CollectionError + Send + Sync>;
+
+enum Canned {
+    Item {
+        fields: serde_json::Value,
+        warnings: Vec<String>,
+    },
+    Error(ErrorFn),
+}
+
+/// Collector for one CTN type that returns canned items by object id
+///
+/// Objects without a canned item are reported as not found. The behavior
+/// hints each object was collected with are kept for assertions.
+pub struct MockCollector {
+    ctn_type: String,
+    collector_id: String,
+    canned: HashMap<String, Canned>,
+    hints: Mutex<HashMap<String, BehaviorHints>>,
+}
+
+impl MockCollector {
+    pub fn new(ctn_type: impl Into<String>) -> Self {
+        Self {
+            ctn_type: ctn_type.into(),
+            collector_id: "mock".to_string(),
+            canned: HashMap::new(),
+            hints: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn with_collector_id(mut self, collector_id: impl Into<String>) -> Self {
+        self.collector_id = collector_id.into();
+        self
+    }
+
+    /// Return `fields`, a JSON object of item fields, for `object_id`
+    pub fn with_item(self, object_id: impl Into<String>, fields: serde_json::Value) -> Self {
+        self.with_canned(
+            object_id,
+            Canned::Item {
+                fields,
+                warnings: Vec::new(),
+            },
+        )
+    }
+
+    /// Return `fields` with a collection warning, as collectors do when they
+    /// could not look at the object
+    pub fn with_warned_item(
+        self,
+        object_id: impl Into<String>,
+        fields: serde_json::Value,
+        warning: impl Into<String>,
+    ) -> Self {
+        self.with_canned(
+            object_id,
+            Canned::Item {
+                fields,
+                warnings: vec![warning.into()],
+            },
+        )
+    }
+
+    /// Fail collection of `object_id` with the error `make` builds from it
+    pub fn with_error(
+        self,
+        object_id: impl Into<String>,
+        make: impl Fn(&str) -> CollectionError + Send + Sync + 'static,
+    ) -> Self {
+        self.with_canned(object_id, Canned::Error(Box::new(make)))
+    }
+
+    fn with_canned(mut self, object_id: impl Into<String>, canned: Canned) -> Self {
+        self.canned.insert(object_id.into(), canned);
+        self
+    }
+
+    /// Behavior hints `object_id` was last collected with
+    pub fn hints_for(&self, object_id: &str) -> Option<BehaviorHints> {
+        self.hints.lock().ok()?.get(object_id).cloned()
+    }
+}
+
+impl CtnDataCollector for MockCollector {
+    fn collect_for_ctn_with_hints(
+        &self,
+        object: &ExecutableObject,
+        _ctn_contract: &CtnContract,
+        hints: &BehaviorHints,
+    ) -> Result<CollectedData, CollectionError> {
+        let object_id = &object.identifier;
+        if let Ok(mut seen) = self.hints.lock() {
+            seen.insert(object_id.clone(), hints.clone());
+        }
+
+        let (fields, warnings) = match self.canned.get(object_id) {
+            Some(Canned::Item { fields, warnings }) => (fields, warnings),
+            Some(Canned::Error(make)) => return Err(make(object_id)),
+            None => {
+                return Err(CollectionError::ObjectNotFound {
+                    object_id: object_id.clone(),
+                })
+            }
+        };
+        let invalid = |reason: String| CollectionError::DataFormatError {
+            reason: format!("canned item for '{}': {}", object_id, reason),
+        };
+        let fields = fields
+            .as_object()
+            .ok_or_else(|| invalid("expected a JSON object of fields".to_string()))?;
+
+        let mut data = CollectedData::new(
+            object_id.clone(),
+            self.ctn_type.clone(),
+            self.collector_id.clone(),
+        );
+        for (name, value) in fields {
+            let value =
+                json_value(value).map_err(|e| invalid(format!("field '{}': {}", name, e)))?;
+            data.add_field(name.clone(), value);
+        }
+        for warning in warnings {
+            data.add_warning(warning.clone());
+        }
+        Ok(data)
+    }
+
+    fn supported_ctn_types(&self) -> Vec<String> {
+        vec![self.ctn_type.clone()]
+    }
+
+    fn validate_ctn_compatibility(&self, contract: &CtnContract) -> Result<(), CollectionError> {
+        if contract.ctn_type != self.ctn_type {
+            return Err(CollectionError::UnsupportedCtnType {
+                ctn_type: contract.ctn_type.clone(),
+                collector_id: self.collector_id.clone(),
+            });
+        }
+        Ok(())
+    }
+
+    fn collector_id(&self) -> &str {
+        &self.collector_id
+    }
+}
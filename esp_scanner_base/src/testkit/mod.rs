@@ -0,0 +1,572 @@
+//! # Strategy Test Kit
+//!
+//! Helpers for testing a collector/executor pair without a registry or a
+//! policy. A criterion is described in JSON, collected by the collector and
+//! evaluated by the executor the way the registry and execution engine would:
+//! the contract is validated as on registration, the criterion is checked
+//! against it, behaviors are checked, each object is collected with its
+//! behavior hints and vacuous passes are flagged.
+//!
+//! - [`run_single_criterion`] / [`CriterionFixture`] - run one criterion
+//! - [`MockCollector`] - canned items for executor tests
+//! - [`fixtures`] - item shapes produced by the stock collectors
+//! - [`check_golden`] / [`check_golden_file`] / [`check_findings_golden`] -
+//!   compare results against expected summaries
+//!
+//! Objects are `{"id": ..., "fields": {...}, "behavior": [...]}`. States are a
+//! list of `{"id": ..., "fields": [...], "records": [...]}`, each field being
+//! `{"name", "type", "op", "value"}` with an optional `"entity_check"`; record
+//! fields name a path into the item's record data.
+//!
+//! ```rust
+//! use esp_scanner_base::testkit::{fixtures, CriterionFixture, MockCollector};
+//!
+//! let collector = MockCollector::new("file_metadata")
+//!     .with_item("sshd_config", fixtures::file_metadata("0600", 0, 0));
+//! let fixture = CriterionFixture::from_json(
+//!     r#"{"id": "sshd_config", "fields": {"path": "/etc/ssh/sshd_config"}}"#,
+//!     r#"[{"id": "private", "fields": [
+//!         {"name": "file_mode", "type": "string", "op": "=", "value": "0600"}
+//!     ]}]"#,
+//! )
+//! .unwrap()
+//! .with_test("all all")
+//! .unwrap();
+//! assert_eq!(fixture.criterion("file_metadata").states[0].fields.len(), 1);
+//! # let _ = collector;
+//! ```
+
+pub mod fixtures;
+mod golden;
+mod mock;
+
+pub use golden::{check_findings_golden, check_golden, check_golden_file, summarize};
+pub use mock::MockCollector;
+
+use crate::execution::behavior::extract_behavior_hints;
+use crate::execution::engine::{collection_warnings, flag_vacuous_pass};
+use crate::strategies::{
+    CollectionError, CtnContractError, CtnContractValidator, CtnDataCollector, CtnExecutionError,
+    CtnExecutionResult, CtnExecutor,
+};
+use crate::types::common::{DataType, Operation, RecordData, ResolvedValue, Timestamp};
+use crate::types::execution_context::{
+    ExecutableCriterion, ExecutableObject, ExecutableObjectElement, ExecutableRecordCheck,
+    ExecutableRecordContent, ExecutableRecordField, ExecutableState, ExecutableStateField,
+};
+use crate::types::{
+    EntityCheck, ExistenceCheck, FieldPath, ItemCheck, StateJoinOp, TestSpecification,
+};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// Errors building or running a criterion fixture
+#[derive(Debug, thiserror::Error)]
+pub enum TestkitError {
+    #[error("Invalid fixture: {0}")]
+    InvalidFixture(String),
+
+    #[error("Criterion does not satisfy the '{ctn_type}' contract: {}", errors.join("; "))]
+    ContractViolation {
+        ctn_type: String,
+        errors: Vec<String>,
+    },
+
+    #[error(transparent)]
+    Contract(#[from] CtnContractError),
+
+    #[error(transparent)]
+    Collection(#[from] CollectionError),
+
+    #[error(transparent)]
+    Execution(#[from] CtnExecutionError),
+
+    #[error("Criterion result differs from golden\nexpected: {expected}\n  actual: {actual}\nmessage: {message}")]
+    GoldenMismatch {
+        expected: String,
+        actual: String,
+        message: String,
+    },
+
+    #[error("Scan result differs from {path}; rerun with UPDATE_SNAPSHOTS=1 to accept\n{}", differences.join("\n"))]
+    FindingsMismatch {
+        path: String,
+        differences: Vec<String>,
+    },
+
+    #[error("Golden {path}: {reason}")]
+    InvalidGolden { path: String, reason: String },
+}
+
+/// Collect `object_json` with `collector` and evaluate it against
+/// `states_json` with `executor`, under `TEST all all`
+pub fn run_single_criterion(
+    collector: &dyn CtnDataCollector,
+    executor: &dyn CtnExecutor,
+    object_json: &str,
+    states_json: &str,
+) -> Result<CtnExecutionResult, TestkitError> {
+    CriterionFixture::from_json(object_json, states_json)?.run(collector, executor)
+}
+
+/// One criterion described in JSON, run without a registry or policy
+#[derive(Debug, Clone)]
+pub struct CriterionFixture {
+    test: TestSpecification,
+    objects: Vec<ExecutableObject>,
+    states: Vec<ExecutableState>,
+}
+
+impl CriterionFixture {
+    pub fn from_json(object_json: &str, states_json: &str) -> Result<Self, TestkitError> {
+        let states: Vec<StateSpec> = parse_json("states", states_json)?;
+        let fixture = Self {
+            test: parse_test("all all")?,
+            objects: Vec::new(),
+            states: states
+                .into_iter()
+                .map(StateSpec::into_state)
+                .collect::<Result<_, _>>()?,
+        };
+        fixture.with_object(object_json)
+    }
+
+    /// Add another object to the criterion
+    pub fn with_object(mut self, object_json: &str) -> Result<Self, TestkitError> {
+        let object: ObjectSpec = parse_json("object", object_json)?;
+        self.objects.push(object.into_object()?);
+        Ok(self)
+    }
+
+    /// Replace the TEST specification, written as in ESP: `existence item
+    /// [state_operator]`, e.g. `at_least_one all AND`
+    pub fn with_test(mut self, test: &str) -> Result<Self, TestkitError> {
+        self.test = parse_test(test)?;
+        Ok(self)
+    }
+
+    /// The criterion as the execution engine would hand it to an executor
+    pub fn criterion(&self, ctn_type: &str) -> ExecutableCriterion {
+        ExecutableCriterion {
+            ctn_node_id: 1,
+            criterion_type: ctn_type.to_string(),
+            test: self.test.clone(),
+            objects: self.objects.clone(),
+            states: self.states.clone(),
+            set_filters: HashMap::new(),
+            active_object_ids: None,
+            controls: Vec::new(),
+            severity: None,
+        }
+    }
+
+    /// Collect every object and evaluate the criterion
+    pub fn run(
+        &self,
+        collector: &dyn CtnDataCollector,
+        executor: &dyn CtnExecutor,
+    ) -> Result<CtnExecutionResult, TestkitError> {
+        // The checks the registry makes when the pair is registered
+        let contract = executor.get_ctn_contract();
+        CtnContractValidator::validate_contract(&contract)?;
+        if !collector.supported_ctn_types().contains(&contract.ctn_type) {
+            return Err(CollectionError::UnsupportedCtnType {
+                ctn_type: contract.ctn_type.clone(),
+                collector_id: collector.collector_id().to_string(),
+            }
+            .into());
+        }
+        collector.validate_ctn_compatibility(&contract)?;
+
+        let criterion = self.criterion(&contract.ctn_type);
+
+        let report = executor.validate_criterion(&criterion)?;
+        if !report.is_valid() {
+            return Err(TestkitError::ContractViolation {
+                ctn_type: contract.ctn_type.clone(),
+                errors: report.errors.into_iter().map(|e| e.message).collect(),
+            });
+        }
+
+        let mut collected_data = HashMap::new();
+        for object in &criterion.objects {
+            let hints = extract_behavior_hints(object);
+            contract.resolve_behaviors(&hints)?;
+            contract.resolve_parameters(object)?;
+            let data = collector.collect_for_ctn_with_hints(object, &contract, &hints)?;
+            collected_data.insert(object.identifier.clone(), data);
+        }
+
+        let warnings = collection_warnings(&collected_data);
+        let mut result = executor.execute_with_contract(&criterion, &collected_data, &contract)?;
+        flag_vacuous_pass(&mut result, &collected_data, &warnings);
+        Ok(result)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ObjectSpec {
+    id: String,
+    #[serde(default)]
+    fields: serde_json::Map<String, serde_json::Value>,
+    #[serde(default)]
+    behavior: Vec<String>,
+}
+
+impl ObjectSpec {
+    fn into_object(self) -> Result<ExecutableObject, TestkitError> {
+        let mut elements = Vec::new();
+        for (name, value) in self.fields {
+            let value = json_value(&value)
+                .map_err(|e| invalid(format!("object field '{}': {}", name, e)))?;
+            elements.push(ExecutableObjectElement::Field { name, value });
+        }
+        if !self.behavior.is_empty() {
+            elements.push(ExecutableObjectElement::Behavior {
+                values: self.behavior,
+            });
+        }
+        Ok(ExecutableObject {
+            identifier: self.id,
+            elements,
+            is_global: false,
+        })
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct StateSpec {
+    id: String,
+    #[serde(default)]
+    fields: Vec<FieldSpec>,
+    #[serde(default)]
+    records: Vec<FieldSpec>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FieldSpec {
+    name: String,
+    #[serde(rename = "type")]
+    data_type: String,
+    op: String,
+    value: serde_json::Value,
+    #[serde(default)]
+    entity_check: Option<String>,
+}
+
+/// A state field's parts, parsed from its spellings in ESP
+type ParsedField = (DataType, Operation, ResolvedValue, Option<EntityCheck>);
+
+impl FieldSpec {
+    fn parse(&self) -> Result<ParsedField, TestkitError> {
+        let context = |e: String| invalid(format!("state field '{}': {}", self.name, e));
+        let data_type = DataType::parse(&self.data_type)
+            .ok_or_else(|| context(format!("unknown data type '{}'", self.data_type)))?;
+        let operation = Operation::parse_or_error(&self.op).map_err(context)?;
+        let value = match (data_type, &self.value) {
+            (DataType::Version, serde_json::Value::String(s)) => ResolvedValue::Version(s.clone()),
+            (DataType::EvrString, serde_json::Value::String(s)) => {
+                ResolvedValue::EvrString(s.clone())
+            }
+            // Written as in ESP (`"0640"`), held as the mode bits
+            (DataType::Octal, serde_json::Value::String(s)) => i64::from_str_radix(s, 8)
+                .map(ResolvedValue::Integer)
+                .map_err(|_| context(format!("'{}' is not an octal mode", s)))?,
+            // `now-30d` is read against the current time, as under a real clock
+            (DataType::DateTime, serde_json::Value::String(s)) => Timestamp::parse(s)
+                .map(|t| ResolvedValue::Integer(t.epoch_seconds(chrono::Utc::now().timestamp())))
+                .map_err(context)?,
+            (_, value) => json_value(value).map_err(context)?,
+        };
+        let entity_check = self
+            .entity_check
+            .as_deref()
+            .map(EntityCheck::parse_or_error)
+            .transpose()
+            .map_err(context)?;
+        Ok((data_type, operation, value, entity_check))
+    }
+}
+
+impl StateSpec {
+    fn into_state(self) -> Result<ExecutableState, TestkitError> {
+        let mut fields = Vec::new();
+        for spec in &self.fields {
+            let (data_type, operation, value, entity_check) = spec.parse()?;
+            fields.push(ExecutableStateField {
+                name: spec.name.clone(),
+                data_type,
+                operation,
+                value,
+                entity_check,
+            });
+        }
+
+        let mut record_fields = Vec::new();
+        for spec in &self.records {
+            let (data_type, operation, value, entity_check) = spec.parse()?;
+            record_fields.push(ExecutableRecordField {
+                path: FieldPath::parse(&spec.name),
+                data_type,
+                operation,
+                value,
+                entity_check,
+            });
+        }
+        let record_checks = if record_fields.is_empty() {
+            Vec::new()
+        } else {
+            vec![ExecutableRecordCheck {
+                data_type: Some(DataType::RecordData),
+                content: ExecutableRecordContent::Nested {
+                    fields: record_fields,
+                },
+            }]
+        };
+
+        Ok(ExecutableState {
+            identifier: self.id,
+            fields,
+            record_checks,
+            is_global: false,
+        })
+    }
+}
+
+fn parse_json<T: serde::de::DeserializeOwned>(what: &str, json: &str) -> Result<T, TestkitError> {
+    serde_json::from_str(json).map_err(|e| invalid(format!("{} JSON: {}", what, e)))
+}
+
+fn parse_test(test: &str) -> Result<TestSpecification, TestkitError> {
+    let parts: Vec<&str> = test.split_whitespace().collect();
+    let (existence, item, operator) = match parts[..] {
+        [existence, item] => (existence, item, None),
+        [existence, item, operator] => (existence, item, Some(operator)),
+        _ => {
+            return Err(invalid(format!(
+                "TEST '{}': expected 'existence item [state_operator]'",
+                test
+            )))
+        }
+    };
+    let state_operator = operator
+        .map(|op| {
+            StateJoinOp::parse(op)
+                .ok_or_else(|| invalid(format!("unknown state operator '{}'", op)))
+        })
+        .transpose()?;
+    Ok(TestSpecification {
+        existence_check: ExistenceCheck::parse_or_error(existence).map_err(invalid)?,
+        item_check: ItemCheck::parse_or_error(item).map_err(invalid)?,
+        state_operator,
+        entity_check: None,
+        span: None,
+    })
+}
+
+/// A value as collectors produce it: arrays become collections and objects
+/// become record data
+pub(crate) fn json_value(value: &serde_json::Value) -> Result<ResolvedValue, String> {
+    match value {
+        serde_json::Value::String(s) => Ok(ResolvedValue::String(s.clone())),
+        serde_json::Value::Number(n) => match n.as_i64() {
+            Some(i) => Ok(ResolvedValue::Integer(i)),
+            None => n
+                .as_f64()
+                .map(ResolvedValue::Float)
+                .ok_or_else(|| format!("unsupported number {}", n)),
+        },
+        serde_json::Value::Bool(b) => Ok(ResolvedValue::Boolean(*b)),
+        serde_json::Value::Array(items) => items
+            .iter()
+            .map(json_value)
+            .collect::<Result<_, _>>()
+            .map(ResolvedValue::Collection),
+        serde_json::Value::Object(_) => Ok(ResolvedValue::RecordData(Box::new(
+            RecordData::from_json_value(value.clone()),
+        ))),
+        serde_json::Value::Null => Err("null is not a value".to_string()),
+    }
+}
+
+fn invalid(message: impl Into<String>) -> TestkitError {
+    TestkitError::InvalidFixture(message.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategies::{
+        CollectedData, ComplianceStatus, CtnContract, FieldOutcome, FieldValidationResult,
+        ObjectFieldSpec, StateFieldSpec, StateValidationResult,
+    };
+
+    /// Compares every state field against the item field of the same name
+    struct EqualsExecutor;
+
+    impl CtnExecutor for EqualsExecutor {
+        fn execute_with_contract(
+            &self,
+            criterion: &ExecutableCriterion,
+            collected_data: &HashMap<String, CollectedData>,
+            _contract: &CtnContract,
+        ) -> Result<CtnExecutionResult, CtnExecutionError> {
+            let mut state_results = Vec::new();
+            for (object_id, data) in collected_data {
+                let fields: Vec<FieldValidationResult> = criterion.states[0]
+                    .fields
+                    .iter()
+                    .map(|field| {
+                        let actual = data.get_field(&field.name).cloned().unwrap();
+                        let passed = actual == field.value;
+                        FieldValidationResult {
+                            field_name: field.name.clone(),
+                            expected_value: field.value.clone(),
+                            actual_value: actual,
+                            operation: field.operation,
+                            passed,
+                            message: String::new(),
+                            outcome: FieldOutcome::from_passed(passed),
+                        }
+                    })
+                    .collect();
+                state_results.push(StateValidationResult {
+                    object_id: object_id.clone(),
+                    combined_result: fields.iter().all(|f| f.passed),
+                    state_results: fields,
+                    state_operator: None,
+                    message: String::new(),
+                });
+            }
+            let passed = state_results.iter().all(|r| r.combined_result);
+            let result = if passed {
+                CtnExecutionResult::pass("kv".to_string(), "ok".to_string())
+            } else {
+                CtnExecutionResult::fail("kv".to_string(), "mismatch".to_string())
+            };
+            Ok(result.with_state_results(state_results))
+        }
+
+        fn get_ctn_contract(&self) -> CtnContract {
+            let mut contract = CtnContract::new("kv".to_string());
+            contract
+                .object_requirements
+                .add_required_field(ObjectFieldSpec {
+                    name: "host".to_string(),
+                    data_type: DataType::String,
+                    description: "Host".to_string(),
+                    example_values: vec![],
+                    validation_notes: None,
+                });
+            contract
+                .state_requirements
+                .add_optional_field(StateFieldSpec {
+                    name: "port".to_string(),
+                    data_type: DataType::Int,
+                    allowed_operations: vec![Operation::Equals],
+                    description: "Port".to_string(),
+                    example_values: vec![],
+                    validation_notes: None,
+                });
+            let mappings = &mut contract.field_mappings.collection_mappings;
+            mappings
+                .object_to_collection
+                .insert("host".to_string(), "host".to_string());
+            mappings.required_data_fields = vec!["port".to_string()];
+            contract
+        }
+
+        fn validate_collected_data(
+            &self,
+            _collected_data: &HashMap<String, CollectedData>,
+            _contract: &CtnContract,
+        ) -> Result<(), CtnExecutionError> {
+            Ok(())
+        }
+
+        fn ctn_type(&self) -> &str {
+            "kv"
+        }
+    }
+
+    const WEB: &str = r#"{"id": "web", "fields": {"host": "web01"}}"#;
+
+    const STATES: &str =
+        r#"[{"id": "s", "fields": [{"name": "port", "type": "int", "op": "=", "value": 443}]}]"#;
+
+    #[test]
+    fn test_runs_canned_items_through_the_executor() {
+        let collector = MockCollector::new("kv").with_item("web", serde_json::json!({"port": 443}));
+        let result = run_single_criterion(&collector, &EqualsExecutor, WEB, STATES).unwrap();
+        check_golden(
+            &result,
+            serde_json::json!({
+                "status": "pass",
+                "objects": {"web": {"passed": true, "fields": [
+                    {"field": "port", "outcome": "pass", "expected": 443, "actual": 443}
+                ]}}
+            }),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_golden_mismatch_is_an_error() {
+        let collector = MockCollector::new("kv").with_item("web", serde_json::json!({"port": 80}));
+        let result = run_single_criterion(&collector, &EqualsExecutor, WEB, STATES).unwrap();
+        let err = check_golden(&result, serde_json::json!({"status": "pass"})).unwrap_err();
+        assert!(matches!(err, TestkitError::GoldenMismatch { .. }));
+        assert!(err.to_string().contains("\"fail\""));
+
+        let err = check_golden_file(&result, "tests/fixtures/missing.json").unwrap_err();
+        assert!(matches!(err, TestkitError::InvalidGolden { .. }));
+    }
+
+    #[test]
+    fn test_warned_objects_make_a_vacuous_pass() {
+        let collector = MockCollector::new("kv").with_warned_item(
+            "web",
+            serde_json::json!({"port": 443}),
+            "permission denied",
+        );
+        let result = run_single_criterion(&collector, &EqualsExecutor, WEB, STATES).unwrap();
+        assert_eq!(result.status, ComplianceStatus::Pass);
+        assert!(result.is_vacuous());
+        assert_eq!(summarize(&result)["vacuous"], "web: permission denied");
+    }
+
+    #[test]
+    fn test_bad_fixtures_name_the_problem() {
+        let err = CriterionFixture::from_json(
+            r#"{"id": "web"}"#,
+            r#"[{"id": "s", "fields": [{"name": "port", "type": "integer", "op": "=", "value": 1}]}]"#,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid fixture: state field 'port': unknown data type 'integer'"
+        );
+
+        let fixture = CriterionFixture::from_json(r#"{"id": "web"}"#, "[]").unwrap();
+        assert!(fixture.clone().with_test("all").is_err());
+        let criterion = fixture.with_test("any all OR").unwrap().criterion("kv");
+        assert_eq!(criterion.test.existence_check, ExistenceCheck::Any);
+        assert_eq!(criterion.test.state_operator, Some(StateJoinOp::Or));
+
+        let collector = MockCollector::new("kv");
+        let err = run_single_criterion(
+            &collector,
+            &EqualsExecutor,
+            r#"{"id": "db", "fields": {"host": "db01"}}"#,
+            STATES,
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            TestkitError::Collection(CollectionError::ObjectNotFound { .. })
+        ));
+    }
+}
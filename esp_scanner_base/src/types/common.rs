@@ -12,6 +12,7 @@ pub use esp_compiler::grammar::ast::nodes::{DataType, LogicalOp, Operation, Valu
 
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
 
 /// Resolved value after variable substitution and computation
 /// This is the scanner's runtime representation of values
@@ -270,6 +271,9 @@ impl DataTypeExt for DataType {
             (DataType::Boolean, ResolvedValue::Boolean(_)) => true,
             (DataType::Version, ResolvedValue::Version(_)) => true,
             (DataType::EvrString, ResolvedValue::EvrString(_)) => true,
+            (DataType::IpAddress, ResolvedValue::String(_)) => true,
+            (DataType::Octal, ResolvedValue::Integer(_)) => true,
+            (DataType::DateTime, ResolvedValue::Integer(_)) => true,
             (DataType::RecordData, ResolvedValue::RecordData(_)) => true,
             (DataType::Binary, ResolvedValue::Binary(_)) => true,
             _ => false,
@@ -288,6 +292,7 @@ impl DataTypeExt for DataType {
                 | DataType::String
                 | DataType::Version
                 | DataType::EvrString
+                | DataType::DateTime
         )
     }
 
@@ -299,6 +304,9 @@ impl DataTypeExt for DataType {
             DataType::Boolean => ResolvedValue::Boolean(false),
             DataType::Version => ResolvedValue::Version("0.0.0".to_string()),
             DataType::EvrString => ResolvedValue::EvrString("0:0-0".to_string()),
+            DataType::IpAddress => ResolvedValue::String("0.0.0.0".to_string()),
+            DataType::Octal => ResolvedValue::Integer(0),
+            DataType::DateTime => ResolvedValue::Integer(0),
             DataType::RecordData => ResolvedValue::RecordData(Box::new(
                 RecordData::from_json_value(serde_json::json!({})),
             )),
@@ -314,6 +322,9 @@ impl DataTypeExt for DataType {
             DataType::Boolean => "boolean",
             DataType::Version => "version",
             DataType::EvrString => "evr_string",
+            DataType::IpAddress => "ip_address",
+            DataType::Octal => "octal",
+            DataType::DateTime => "datetime",
             DataType::RecordData => "record_data",
             DataType::Binary => "binary",
         }
@@ -331,7 +342,7 @@ impl DataTypeExt for DataType {
                 EndsWith,
                 PatternMatch,
             ],
-            DataType::Int | DataType::Float => vec![
+            DataType::Int | DataType::Float | DataType::DateTime => vec![
                 Equals,
                 NotEqual,
                 GreaterThan,
@@ -349,6 +360,8 @@ impl DataTypeExt for DataType {
                 LessThanOrEqual,
             ],
             DataType::Binary => vec![Equals, NotEqual],
+            DataType::IpAddress => vec![Equals, NotEqual, InSubnet, NotInSubnet],
+            DataType::Octal => vec![Equals, NotEqual, PermissionsAtMost, PermissionsAtLeast],
             DataType::RecordData => vec![
                 Equals,
                 NotEqual, // RecordData might support additional operations like Contains
@@ -435,10 +448,232 @@ impl OperationExt for Operation {
             Operation::Matches => "matches",
             Operation::SubsetOf => "subset_of",
             Operation::SupersetOf => "superset_of",
+            Operation::EqualsAny => "equals_any",
+            Operation::NotEqualsAny => "not_equals_any",
+            Operation::LengthEquals => "length_equals",
+            Operation::LengthGreaterThan => "length_greater_than",
+            Operation::OccurrenceCount => "occurrence_count",
+            Operation::VersionEquals => "version_equals",
+            Operation::VersionNotEqual => "version_not_equal",
+            Operation::VersionGreaterThan => "version_greater_than",
+            Operation::VersionLessThan => "version_less_than",
+            Operation::VersionGreaterOrEqual => "version_greater_or_equal",
+            Operation::VersionLessOrEqual => "version_less_or_equal",
+            Operation::InSubnet => "in_subnet",
+            Operation::NotInSubnet => "not_in_subnet",
+            Operation::GlobMatch => "glob_match",
+            Operation::NotGlobMatch => "not_glob_match",
+            Operation::PermissionsAtMost => "permissions_at_most",
+            Operation::PermissionsAtLeast => "permissions_at_least",
         }
     }
 }
 
+// ============================================================================
+// NETWORK ADDRESSES - `ip_address` fields and CIDR operands
+// ============================================================================
+
+/// An IPv4 or IPv6 network in CIDR notation, e.g. `10.0.0.0/8`
+///
+/// A bare address is a single host (`/32` or `/128`). Host bits below the
+/// prefix are ignored, so `10.1.2.3/8` is the network `10.0.0.0/8`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cidr {
+    network: IpAddr,
+    prefix_len: u8,
+}
+
+impl Cidr {
+    pub fn parse(network: &str) -> Result<Self, String> {
+        let (address, prefix) = match network.trim().split_once('/') {
+            Some((address, prefix)) => (address, Some(prefix)),
+            None => (network.trim(), None),
+        };
+        let address: IpAddr = address
+            .parse()
+            .map_err(|_| format!("'{}' is not a CIDR network: bad address", network))?;
+        let max_prefix = if address.is_ipv4() { 32 } else { 128 };
+        let prefix_len = match prefix.map(str::parse::<u8>) {
+            None => max_prefix,
+            Some(Ok(prefix)) if prefix <= max_prefix => prefix,
+            Some(_) => {
+                return Err(format!(
+                    "'{}' is not a CIDR network: the prefix length must be 0 to {}",
+                    network, max_prefix
+                ))
+            }
+        };
+
+        let network = match address {
+            IpAddr::V4(v4) => IpAddr::V4(Ipv4Addr::from(u32::from(v4) & mask_u32(prefix_len))),
+            IpAddr::V6(v6) => IpAddr::V6(Ipv6Addr::from(u128::from(v6) & mask_u128(prefix_len))),
+        };
+        Ok(Self {
+            network,
+            prefix_len,
+        })
+    }
+
+    pub fn network(&self) -> IpAddr {
+        self.network
+    }
+
+    pub fn prefix_len(&self) -> u8 {
+        self.prefix_len
+    }
+
+    /// Whether `address` is in this network; an IPv4-mapped IPv6 address
+    /// (`::ffff:10.1.2.3`) is matched as the IPv4 address it maps
+    pub fn contains(&self, address: IpAddr) -> bool {
+        let address = match address {
+            IpAddr::V6(v6) => v6
+                .to_ipv4_mapped()
+                .filter(|_| self.network.is_ipv4())
+                .map_or(address, IpAddr::V4),
+            v4 => v4,
+        };
+        match (self.network, address) {
+            (IpAddr::V4(network), IpAddr::V4(address)) => {
+                u32::from(address) & mask_u32(self.prefix_len) == u32::from(network)
+            }
+            (IpAddr::V6(network), IpAddr::V6(address)) => {
+                u128::from(address) & mask_u128(self.prefix_len) == u128::from(network)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for Cidr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.network, self.prefix_len)
+    }
+}
+
+impl std::str::FromStr for Cidr {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+fn mask_u32(prefix_len: u8) -> u32 {
+    u32::MAX
+        .checked_shl(32 - u32::from(prefix_len))
+        .unwrap_or(0)
+}
+
+fn mask_u128(prefix_len: u8) -> u128 {
+    u128::MAX
+        .checked_shl(128 - u32::from(prefix_len))
+        .unwrap_or(0)
+}
+
+/// Parse a collected address as tools print it: `[fe80::1]` brackets and a
+/// `%eth0` zone are dropped, and `*` (every address) is `0.0.0.0`
+pub fn parse_ip_address(address: &str) -> Result<IpAddr, String> {
+    let trimmed = address.trim();
+    if trimmed == "*" {
+        return Ok(IpAddr::V4(Ipv4Addr::UNSPECIFIED));
+    }
+    let unbracketed = trimmed
+        .strip_prefix('[')
+        .and_then(|rest| rest.strip_suffix(']'))
+        .unwrap_or(trimmed);
+    let without_zone = unbracketed
+        .split_once('%')
+        .map_or(unbracketed, |(address, _)| address);
+    without_zone
+        .parse()
+        .map_err(|_| format!("'{}' is not an IP address", address))
+}
+
+// ============================================================================
+// TIMESTAMPS - `datetime` fields
+// ============================================================================
+
+/// A `datetime` operand: an instant, or an offset from the scan instant
+///
+/// Written as RFC 3339 (`2025-01-01T00:00:00Z`), epoch seconds, or `now`,
+/// `now-30d`, `now+12h` with units `s`, `m`, `h`, `d` and `w`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Timestamp {
+    /// Seconds since the Unix epoch
+    Absolute(i64),
+    /// Seconds from the scan instant; negative is in the past
+    Relative(i64),
+}
+
+impl Timestamp {
+    pub fn parse(timestamp: &str) -> Result<Self, String> {
+        let trimmed = timestamp.trim();
+        let Some(offset) = trimmed.strip_prefix("now") else {
+            return chrono::DateTime::parse_from_rfc3339(trimmed)
+                .map(|t| Self::Absolute(t.timestamp()))
+                .map_err(|e| format!("'{}' is not an RFC 3339 timestamp: {}", timestamp, e));
+        };
+        if offset.is_empty() {
+            return Ok(Self::Relative(0));
+        }
+
+        let malformed = || {
+            format!(
+                "'{}' is not a relative time: expected `now-<n><unit>` with unit s, m, h, d or w",
+                timestamp
+            )
+        };
+        let (sign, amount) = match (offset.strip_prefix('+'), offset.strip_prefix('-')) {
+            (Some(amount), _) => (1, amount),
+            (_, Some(amount)) => (-1, amount),
+            _ => return Err(malformed()),
+        };
+        let unit_seconds = match amount.chars().last() {
+            Some('s') => 1,
+            Some('m') => 60,
+            Some('h') => 3_600,
+            Some('d') => 86_400,
+            Some('w') => 604_800,
+            _ => return Err(malformed()),
+        };
+        let digits = &amount[..amount.len() - 1];
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(malformed());
+        }
+        digits
+            .parse::<i64>()
+            .ok()
+            .and_then(|n| n.checked_mul(unit_seconds))
+            .map(|seconds| Self::Relative(sign * seconds))
+            .ok_or_else(|| format!("'{}' is out of range", timestamp))
+    }
+
+    /// An integer is epoch seconds; a string is parsed
+    pub fn from_resolved(value: &ResolvedValue) -> Result<Self, String> {
+        match value {
+            ResolvedValue::Integer(epoch) => Ok(Self::Absolute(*epoch)),
+            ResolvedValue::String(timestamp) => Self::parse(timestamp),
+            other => Err(format!("expected a timestamp, found {}", other)),
+        }
+    }
+
+    /// Epoch seconds, with `now` as the scan instant
+    pub fn epoch_seconds(self, now: i64) -> i64 {
+        match self {
+            Self::Absolute(epoch) => epoch,
+            Self::Relative(offset) => now.saturating_add(offset),
+        }
+    }
+}
+
+impl std::str::FromStr for Timestamp {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
 // ============================================================================
 // ERRORS
 // ============================================================================
@@ -488,37 +723,45 @@ mod tests {
 
     #[test]
     fn test_path_component_conversions() {
+        use crate::types::field_path_extensions::PathComponent;
+
         let field = PathComponent::Field("name".to_string());
-        assert_eq!(field.to_string(), "name");
-        assert!(field.is_field());
+        assert_eq!(field.to_string_component(), "name");
+        assert!(matches!(field, PathComponent::Field(_)));
 
         let index = PathComponent::Index(5);
-        assert_eq!(index.to_string(), "5");
-        assert!(index.is_index());
+        assert_eq!(index.to_string_component(), "5");
+        assert!(matches!(index, PathComponent::Index(5)));
 
         let wildcard = PathComponent::Wildcard;
-        assert_eq!(wildcard.to_string(), "*");
-        assert!(wildcard.is_wildcard());
+        assert_eq!(wildcard.to_string_component(), "*");
+        assert!(matches!(wildcard, PathComponent::Wildcard));
     }
 
     #[test]
     fn test_path_component_from_string() {
-        let field = PathComponent::from_string("test");
-        assert!(matches!(field, PathComponent::Field(_)));
+        use crate::types::field_path_extensions::{FieldPathExt, PathComponent};
+        use esp_compiler::grammar::FieldPath;
 
-        let index = PathComponent::from_string("42");
-        assert!(matches!(index, PathComponent::Index(42)));
+        let field = FieldPath::parse("test").parse_components();
+        assert!(matches!(field[..], [PathComponent::Field(_)]));
 
-        let wildcard = PathComponent::from_string("*");
-        assert!(matches!(wildcard, PathComponent::Wildcard));
+        let index = FieldPath::parse("42").parse_components();
+        assert!(matches!(index[..], [PathComponent::Index(42)]));
+
+        let wildcard = FieldPath::parse("*");
+        assert!(matches!(
+            wildcard.parse_components()[..],
+            [PathComponent::Wildcard]
+        ));
+        assert!(wildcard.has_wildcards());
     }
 
     #[test]
     fn test_field_path_dot_notation() {
-        let path = FieldPath::from_string("Config.Database.Host");
+        let path = esp_compiler::grammar::FieldPath::parse("Config.Database.Host");
         assert_eq!(path.components.len(), 3);
         assert_eq!(path.to_dot_notation(), "Config.Database.Host");
-        assert_eq!(path.depth(), 3);
     }
 
     #[test]
@@ -576,4 +819,92 @@ mod tests {
         assert!(var_ref.has_variable_reference());
         assert_eq!(var_ref.get_variable_name(), Some("my_var"));
     }
+
+    #[test]
+    fn test_cidr_parsing_and_containment() {
+        let private = Cidr::parse("10.0.0.0/8").unwrap();
+        assert_eq!(private.prefix_len(), 8);
+        assert!(private.contains(parse_ip_address("10.255.0.1").unwrap()));
+        assert!(!private.contains(parse_ip_address("11.0.0.1").unwrap()));
+        assert!(private.contains(parse_ip_address("::ffff:10.1.2.3").unwrap()));
+        assert!(!private.contains(parse_ip_address("fd00::1").unwrap()));
+
+        // Host bits are dropped, a bare address is a single host
+        assert_eq!(Cidr::parse("10.1.2.3/8").unwrap(), private);
+        assert_eq!(
+            Cidr::parse("192.0.2.7").unwrap().to_string(),
+            "192.0.2.7/32"
+        );
+        assert!(Cidr::parse("0.0.0.0/0")
+            .unwrap()
+            .contains(parse_ip_address("203.0.113.9").unwrap()));
+
+        let ula: Cidr = "fd00::/8".parse().unwrap();
+        assert!(ula.contains(parse_ip_address("[fd12:3456::1]").unwrap()));
+        assert!(!ula.contains(parse_ip_address("fe80::1%eth0").unwrap()));
+        assert!(Cidr::parse("fe80::/10")
+            .unwrap()
+            .contains(parse_ip_address("fe80::1%eth0").unwrap()));
+
+        for malformed in [
+            "10.0.0.0/33",
+            "10.0.0/8",
+            "fd00::/129",
+            "10.0.0.0/",
+            "host/8",
+        ] {
+            assert!(Cidr::parse(malformed).is_err(), "{}", malformed);
+        }
+        assert_eq!(
+            parse_ip_address("*").unwrap(),
+            IpAddr::V4(Ipv4Addr::UNSPECIFIED)
+        );
+        assert!(parse_ip_address("localhost").is_err());
+    }
+
+    #[test]
+    fn test_timestamp_parsing() {
+        assert_eq!(
+            Timestamp::parse("2025-01-01T00:00:00Z").unwrap(),
+            Timestamp::Absolute(1_735_689_600)
+        );
+        assert_eq!(
+            Timestamp::parse("2025-01-01T02:00:00+02:00").unwrap(),
+            Timestamp::Absolute(1_735_689_600)
+        );
+        assert_eq!(Timestamp::parse("now").unwrap(), Timestamp::Relative(0));
+        assert_eq!(
+            Timestamp::parse("now-30d").unwrap(),
+            Timestamp::Relative(-30 * 86_400)
+        );
+        assert_eq!(
+            "now+2w".parse::<Timestamp>().unwrap(),
+            Timestamp::Relative(2 * 604_800)
+        );
+        assert_eq!(
+            Timestamp::from_resolved(&ResolvedValue::Integer(42)).unwrap(),
+            Timestamp::Absolute(42)
+        );
+
+        let now = 1_735_689_600;
+        assert_eq!(
+            Timestamp::parse("now-1h").unwrap().epoch_seconds(now),
+            now - 3_600
+        );
+        assert_eq!(Timestamp::Absolute(7).epoch_seconds(now), 7);
+
+        for malformed in [
+            "2025-01-01",
+            "now-30",
+            "now-30y",
+            "now30d",
+            "now--1d",
+            "now-+1d",
+            "now-é",
+            "now-99999999999999999999d",
+        ] {
+            assert!(Timestamp::parse(malformed).is_err(), "{}", malformed);
+        }
+        assert!(Timestamp::from_resolved(&ResolvedValue::Boolean(true)).is_err());
+    }
 }
@@ -28,6 +28,9 @@ pub struct CriteriaRoot {
     pub trees: Vec<CriteriaTree>,
     /// How to combine top-level trees (default: AND)
     pub root_logical_op: LogicalOp,
+    /// Whether to negate the combined result of the top-level trees
+    #[serde(default)]
+    pub root_negate: bool,
 }
 
 impl CriteriaTree {
@@ -42,6 +45,20 @@ impl CriteriaTree {
         }
     }
 
+    /// CTN declarations paired with their node ids
+    pub fn criteria_with_node_ids(&self) -> Vec<(CtnNodeId, &CriterionDeclaration)> {
+        match self {
+            CriteriaTree::Criterion {
+                declaration,
+                node_id,
+            } => vec![(*node_id, declaration)],
+            CriteriaTree::Block { children, .. } => children
+                .iter()
+                .flat_map(|child| child.criteria_with_node_ids())
+                .collect(),
+        }
+    }
+
     /// Count total CTNs in tree
     pub fn count_criteria(&self) -> usize {
         match self {
@@ -72,6 +89,15 @@ impl CriteriaRoot {
             .collect()
     }
 
+    /// All CTN declarations with their node ids, the keys of the CTN-local
+    /// state and object maps
+    pub fn criteria_with_node_ids(&self) -> Vec<(CtnNodeId, &CriterionDeclaration)> {
+        self.trees
+            .iter()
+            .flat_map(|tree| tree.criteria_with_node_ids())
+            .collect()
+    }
+
     /// Total count of CTNs
     pub fn total_criteria_count(&self) -> usize {
         self.trees.iter().map(|tree| tree.count_criteria()).sum()
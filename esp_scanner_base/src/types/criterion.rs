@@ -1,6 +1,6 @@
 use super::object::{ObjectDeclaration, ResolvedObject};
 use super::state::{ResolvedState, StateDeclaration};
-use esp_compiler::grammar::ast::nodes::{ObjectRef, StateRef, TestSpecification};
+use esp_compiler::grammar::ast::nodes::{ControlRef, ObjectRef, StateRef, TestSpecification};
 use serde::{Deserialize, Serialize};
 
 /// CTN node identifier for tracking local symbol scopes
@@ -21,6 +21,12 @@ pub struct CriterionDeclaration {
     pub local_states: Vec<StateDeclaration>,
     /// Local object (CTN-level, non-referenceable, max 1)
     pub local_object: Option<ObjectDeclaration>,
+    /// Benchmark controls declared with CONTROL
+    #[serde(default)]
+    pub controls: Vec<ControlRef>, // Using compiler's type
+    /// Severity declared with SEVERITY
+    #[serde(default)]
+    pub severity: Option<String>,
     /// CTN node ID for scope tracking
     pub ctn_node_id: Option<CtnNodeId>,
 }
@@ -95,6 +101,8 @@ impl CriterionDeclaration {
             object_refs,
             local_states,
             local_object,
+            controls: Vec::new(),
+            severity: None,
             ctn_node_id: None,
         }
     }
@@ -142,6 +150,8 @@ impl CriterionDeclaration {
             object_refs: node.object_refs.clone(), // Using compiler type directly
             local_states,
             local_object,
+            controls: node.controls.clone(),
+            severity: node.severity.clone(),
             ctn_node_id: Some(ctn_node_id),
         }
     }
@@ -409,6 +419,7 @@ fn format_entity_check(check: &esp_compiler::grammar::ast::nodes::EntityCheck) -
         EntityCheck::AtLeastOne => "entity:at_least_one".to_string(),
         EntityCheck::None => "entity:none".to_string(),
         EntityCheck::OnlyOne => "entity:only_one".to_string(),
+        EntityCheck::AtLeastN(_) | EntityCheck::AtLeastPercent(_) => format!("entity:{}", check),
     }
 }
 
@@ -420,9 +431,9 @@ fn format_entity_check(check: &esp_compiler::grammar::ast::nodes::EntityCheck) -
 mod tests {
     use super::*;
     use esp_compiler::grammar::ast::nodes::{
-        CriterionNode as AstCriterion, DataType as AstDataType, EntityCheck, ExistenceCheck,
-        ItemCheck, ObjectDefinition as AstObject, ObjectElement as AstElement,
-        ObjectField as AstField, ObjectRef as AstObjectRef, Operation, StateDefinition as AstState,
+        CriterionNode as AstCriterion, DataType as AstDataType, ExistenceCheck, ItemCheck,
+        ObjectDefinition as AstObject, ObjectElement as AstElement, ObjectField as AstField,
+        ObjectRef as AstObjectRef, Operation, StateDefinition as AstState,
         StateField as AstStateField, StateRef as AstStateRef, TestSpecification as AstTest,
         Value as AstValue,
     };
@@ -433,6 +444,7 @@ mod tests {
             item_check: ItemCheck::All,
             state_operator: None,
             entity_check: None,
+            span: None,
         }
     }
 
@@ -452,6 +464,8 @@ mod tests {
             }],
             local_states: vec![],
             local_object: None,
+            controls: vec![],
+            severity: None,
             span: None,
         };
 
@@ -477,12 +491,15 @@ mod tests {
             object_refs: vec![],
             local_states: vec![AstState {
                 id: "local_state".to_string(),
-                fields: vec![AstStateField::Simple {
-                    field_name: "test".to_string(),
+                fields: vec![AstStateField {
+                    name: "test".to_string(),
                     data_type: AstDataType::String,
                     operation: Operation::Equals,
                     value: AstValue::String("value".to_string()),
+                    entity_check: None,
+                    span: None,
                 }],
+                record_checks: vec![],
                 is_global: false,
                 span: None,
             }],
@@ -496,6 +513,8 @@ mod tests {
                 is_global: false,
                 span: None,
             }),
+            controls: vec![],
+            severity: None,
             span: None,
         };
 
@@ -531,6 +550,8 @@ mod tests {
             }],
             local_states: vec![],
             local_object: None,
+            controls: vec![],
+            severity: None,
             span: None,
         };
 
@@ -555,6 +576,8 @@ mod tests {
             object_refs: vec![],
             local_states: vec![],
             local_object: None,
+            controls: vec![],
+            severity: None,
             span: None,
         };
 
@@ -565,6 +588,8 @@ mod tests {
             object_refs: vec![],
             local_states: vec![],
             local_object: None,
+            controls: vec![],
+            severity: None,
             span: None,
         };
 
@@ -600,16 +625,21 @@ mod tests {
             }],
             local_states: vec![AstState {
                 id: "local_with_var".to_string(),
-                fields: vec![AstStateField::Simple {
-                    field_name: "field".to_string(),
+                fields: vec![AstStateField {
+                    name: "field".to_string(),
                     data_type: AstDataType::String,
                     operation: Operation::Equals,
                     value: AstValue::Variable("my_var".to_string()),
+                    entity_check: None,
+                    span: None,
                 }],
+                record_checks: vec![],
                 is_global: false,
                 span: None,
             }],
             local_object: None,
+            controls: vec![],
+            severity: None,
             span: None,
         };
 
@@ -639,10 +669,13 @@ mod tests {
             local_states: vec![AstState {
                 id: "empty_state".to_string(),
                 fields: vec![], // Empty!
+                record_checks: vec![],
                 is_global: false,
                 span: None,
             }],
             local_object: None,
+            controls: vec![],
+            severity: None,
             span: None,
         };
 
@@ -670,6 +703,8 @@ mod tests {
                 is_global: false,
                 span: None,
             }),
+            controls: vec![],
+            severity: None,
             span: None,
         };
 
@@ -710,6 +745,8 @@ mod tests {
             ],
             local_states: vec![],
             local_object: None,
+            controls: vec![],
+            severity: None,
             span: None,
         };
 
@@ -739,12 +776,15 @@ mod tests {
             object_refs: vec![],
             local_states: vec![AstState {
                 id: "state_with_var".to_string(),
-                fields: vec![AstStateField::Simple {
-                    field_name: "field".to_string(),
+                fields: vec![AstStateField {
+                    name: "field".to_string(),
                     data_type: AstDataType::String,
                     operation: Operation::Equals,
                     value: AstValue::Variable("var1".to_string()),
+                    entity_check: None,
+                    span: None,
                 }],
+                record_checks: vec![],
                 is_global: false,
                 span: None,
             }],
@@ -758,6 +798,8 @@ mod tests {
                 is_global: false,
                 span: None,
             }),
+            controls: vec![],
+            severity: None,
             span: None,
         };
 
@@ -783,6 +825,8 @@ mod tests {
             object_refs: vec![],
             local_states: vec![],
             local_object: None,
+            controls: vec![],
+            severity: None,
             span: None,
         };
 
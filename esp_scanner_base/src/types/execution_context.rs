@@ -1,17 +1,20 @@
 // src/types/execution_context.rs
 //! Execution context with resolved symbols ready for compliance validation
+use crate::execution::clock::ScanClock;
 use crate::types::common::{DataType, LogicalOp, Operation, RecordData, ResolvedValue};
 use crate::types::criteria::CriteriaTree;
 use crate::types::criterion::{CriterionDeclaration, CtnNodeId};
+use crate::types::execution_plan::ExecutionPlan;
 use crate::types::filter::ResolvedFilterSpec;
 use crate::types::metadata::MetaDataBlock;
-use crate::types::object::{ResolvedObject, ResolvedObjectElement};
+use crate::types::object::{ObjectDeclaration, ResolvedObject, ResolvedObjectElement};
 use crate::types::resolution_context::{DeferredOperation, ResolutionContext};
 use crate::types::variable::ResolvedVariable;
 use crate::types::FieldPath;
 use crate::types::ResolvedSetOperation;
 use crate::types::TestSpecification;
 use crate::types::{EntityCheck, ResolvedState};
+use esp_compiler::grammar::ast::nodes::ControlRef;
 use esp_compiler::grammar::ModuleField;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
@@ -137,7 +140,10 @@ impl ExecutableCriteriaTree {
 // Execution Context (Modified to use tree)
 // ============================================================================
 /// Execution context with resolved symbols and executable criteria tree
-#[derive(Debug, Clone)]
+///
+/// Serializable so a resolved policy can be cached between scans; the clock
+/// is per scan and is not saved.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExecutionContext {
     /// Metadata from ESP definition
     pub metadata: Option<MetaDataBlock>,
@@ -159,11 +165,21 @@ pub struct ExecutionContext {
     /// Deferred operations to execute at scan-time
     pub deferred_operations: Vec<DeferredOperation>,
 
+    /// Global objects to re-resolve once scan-time variables are computed
+    pub late_bound_objects: HashMap<String, ObjectDeclaration>,
+
+    /// Ordered execution phases
+    pub plan: ExecutionPlan,
+
     /// Local states by CTN node ID
     pub local_states: HashMap<CtnNodeId, Vec<ExecutableState>>,
 
     /// Local objects by CTN node ID (max 1 per CTN)
     pub local_objects: HashMap<CtnNodeId, ExecutableObject>,
+
+    /// Time source for every time comparison in this scan
+    #[serde(skip, default = "ScanClock::system")]
+    pub clock: ScanClock,
 }
 impl ExecutionContext {
     /// Create execution context from resolution context
@@ -210,19 +226,28 @@ impl ExecutionContext {
             global_objects,
             global_sets,
             deferred_operations,
+            late_bound_objects: resolution_context.late_bound_objects.clone(),
+            plan: resolution_context.execution_plan.clone(),
             local_states,
             local_objects,
+            clock: ScanClock::system(),
         })
     }
 
+    /// Use a specific clock (fixed for deterministic scans)
+    pub fn with_clock(mut self, clock: ScanClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
     /// Build executable tree from CriteriaRoot
     fn build_executable_tree(
         context: &ResolutionContext,
     ) -> Result<ExecutableCriteriaTree, String> {
         let root = &context.criteria_root;
 
-        // Handle single tree case
-        if root.trees.len() == 1 {
+        // Handle single tree case; a negated root still needs its own block
+        if root.trees.len() == 1 && !root.root_negate {
             return ExecutableCriteriaTree::from_criteria_tree(&root.trees[0], context);
         }
 
@@ -235,7 +260,7 @@ impl ExecutionContext {
 
         Ok(ExecutableCriteriaTree::Block {
             logical_op: root.root_logical_op,
-            negate: false,
+            negate: root.root_negate,
             children: executable_children,
         })
     }
@@ -252,15 +277,14 @@ impl ExecutionContext {
 
     /// Validate execution context
     pub fn validate(&self) -> Result<(), String> {
+        // A policy with no criteria at all is left to the engine to report
+        if self.count_criteria() == 0 {
+            return Ok(());
+        }
+
         // Validate tree structure
         self.criteria_tree.validate()?;
 
-        // Validate criteria count
-        let count = self.count_criteria();
-        if count == 0 {
-            return Err("Execution context has no criteria".to_string());
-        }
-
         // Validate all CTN node IDs are unique
         let all_criteria = self.get_all_criteria();
         let mut seen_ids = std::collections::HashSet::new();
@@ -287,6 +311,12 @@ pub struct ExecutableCriterion {
     #[serde(default)]
     pub set_filters: HashMap<String, (String, ResolvedFilterSpec)>,
     pub active_object_ids: Option<HashSet<String>>,
+    /// Benchmark controls declared on the CTN
+    #[serde(default)]
+    pub controls: Vec<ControlRef>,
+    /// Severity declared on the CTN
+    #[serde(default)]
+    pub severity: Option<String>,
 }
 impl ExecutableCriterion {
     /// Convert from CriterionDeclaration during execution context creation
@@ -376,6 +406,8 @@ impl ExecutableCriterion {
             states,
             set_filters,
             active_object_ids: None,
+            controls: declaration.controls.clone(),
+            severity: declaration.severity.clone(),
         })
     }
 
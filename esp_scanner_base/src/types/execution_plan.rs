@@ -0,0 +1,152 @@
+//! # Execution Plan
+//!
+//! The ordered phases a scan runs through. RUN EXTRACT reads fields from
+//! collected objects, so some collection has to happen before variables
+//! that depend on it can be computed and bound into other objects. The
+//! resolution engine derives these phases from the dependency graph; the
+//! execution engine runs them in order and records the plan in the result.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// What a phase does
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PhaseKind {
+    /// Collect the objects RUN EXTRACT operations read from
+    CollectForExtraction,
+    /// Run scan-time RUN operations in dependency order
+    ComputeVariables,
+    /// Bind computed variables into the objects that reference them
+    Substitute,
+    /// Collect each criterion's objects, reusing extraction collections
+    CollectForCriteria,
+    /// Evaluate criteria against their collected data
+    Evaluate,
+}
+
+impl PhaseKind {
+    /// Every phase, in execution order
+    pub const ALL: [PhaseKind; 5] = [
+        PhaseKind::CollectForExtraction,
+        PhaseKind::ComputeVariables,
+        PhaseKind::Substitute,
+        PhaseKind::CollectForCriteria,
+        PhaseKind::Evaluate,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PhaseKind::CollectForExtraction => "collect_for_extraction",
+            PhaseKind::ComputeVariables => "compute_variables",
+            PhaseKind::Substitute => "substitute",
+            PhaseKind::CollectForCriteria => "collect_for_criteria",
+            PhaseKind::Evaluate => "evaluate",
+        }
+    }
+}
+
+/// One phase and the symbols assigned to it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExecutionPhase {
+    pub kind: PhaseKind,
+    /// Object ids, variable names or criterion labels, in execution order
+    pub items: Vec<String>,
+}
+
+/// Ordered phases for one policy
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ExecutionPlan {
+    pub phases: Vec<ExecutionPhase>,
+}
+
+impl ExecutionPlan {
+    /// Plan with every phase present, in execution order
+    pub fn new(
+        extraction_objects: Vec<String>,
+        computed_variables: Vec<String>,
+        substituted_objects: Vec<String>,
+        criteria_collection: Vec<String>,
+        criteria: Vec<String>,
+    ) -> Self {
+        let items = [
+            extraction_objects,
+            computed_variables,
+            substituted_objects,
+            criteria_collection,
+            criteria,
+        ];
+        Self {
+            phases: PhaseKind::ALL
+                .into_iter()
+                .zip(items)
+                .map(|(kind, items)| ExecutionPhase { kind, items })
+                .collect(),
+        }
+    }
+
+    /// Items assigned to a phase (empty when the phase is absent)
+    pub fn phase(&self, kind: PhaseKind) -> &[String] {
+        self.phases
+            .iter()
+            .find(|p| p.kind == kind)
+            .map(|p| p.items.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// Whether anything must be collected or computed before criteria run
+    pub fn has_scan_time_work(&self) -> bool {
+        !self.phase(PhaseKind::CollectForExtraction).is_empty()
+            || !self.phase(PhaseKind::ComputeVariables).is_empty()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.phases.is_empty()
+    }
+}
+
+impl fmt::Display for ExecutionPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, phase) in self.phases.iter().enumerate() {
+            writeln!(f, "{}. {}", index + 1, phase.kind.as_str())?;
+            if phase.items.is_empty() {
+                writeln!(f, "   (none)")?;
+            }
+            for item in &phase.items {
+                writeln!(f, "   - {}", item)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_phases_in_order_and_display() {
+        let plan = ExecutionPlan::new(
+            vec!["pointer".to_string()],
+            vec!["target_path".to_string()],
+            vec!["target".to_string()],
+            vec!["file_content#1: target".to_string()],
+            vec!["file_content#1".to_string()],
+        );
+
+        let kinds: Vec<PhaseKind> = plan.phases.iter().map(|p| p.kind).collect();
+        assert_eq!(kinds, PhaseKind::ALL);
+        assert!(plan.has_scan_time_work());
+        assert_eq!(plan.phase(PhaseKind::Substitute), ["target"]);
+        assert!(plan
+            .to_string()
+            .starts_with("1. collect_for_extraction\n   - pointer\n2. compute_variables"));
+
+        let plain = ExecutionPlan::new(vec![], vec![], vec![], vec![], vec![]);
+        assert!(!plain.has_scan_time_work());
+        assert!(plain.to_string().contains("3. substitute\n   (none)"));
+        assert!(ExecutionPlan::default()
+            .phase(PhaseKind::Evaluate)
+            .is_empty());
+    }
+}
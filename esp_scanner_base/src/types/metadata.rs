@@ -4,4 +4,55 @@ use std::collections::HashMap;
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct MetaDataBlock {
     pub fields: HashMap<String, String>,
+    /// Field names in authored order
+    #[serde(default)]
+    pub order: Vec<String>,
+}
+
+impl MetaDataBlock {
+    /// Build from fields in authored order
+    pub fn from_ordered<I, K, V>(fields: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        let mut block = Self::default();
+        for (name, value) in fields {
+            block.insert(name, value);
+        }
+        block
+    }
+
+    /// Add a field; a repeated name keeps its first position
+    pub fn insert(&mut self, name: impl Into<String>, value: impl Into<String>) {
+        let name = name.into();
+        if !self.fields.contains_key(&name) {
+            self.order.push(name.clone());
+        }
+        self.fields.insert(name, value.into());
+    }
+
+    /// Fields in authored order; fields without a recorded position follow, sorted
+    pub fn ordered_fields(&self) -> Vec<(&str, &str)> {
+        let mut ordered: Vec<(&str, &str)> = self
+            .order
+            .iter()
+            .filter_map(|name| {
+                self.fields
+                    .get_key_value(name)
+                    .map(|(k, v)| (k.as_str(), v.as_str()))
+            })
+            .collect();
+
+        let mut rest: Vec<(&str, &str)> = self
+            .fields
+            .iter()
+            .filter(|(name, _)| !self.order.contains(name))
+            .map(|(k, v)| (k.as_str(), v.as_str()))
+            .collect();
+        rest.sort();
+        ordered.extend(rest);
+        ordered
+    }
 }
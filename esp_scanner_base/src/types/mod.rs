@@ -21,6 +21,7 @@ pub mod criterion;
 
 // Context types
 pub mod execution_context;
+pub mod execution_plan;
 pub mod resolution_context;
 
 // Record traits (may be redundant with state.rs extensions)
@@ -89,6 +90,7 @@ pub use criterion::*; // CriterionDeclaration, ResolvedCriterion
 
 // Context types
 pub use execution_context::*; // ExecutionContext, ExecutableCriteriaTree (the actual one we want)
+pub use execution_plan::*; // ExecutionPlan, PhaseKind
 pub use resolution_context::*; // ResolutionContext
 
 // Record traits
@@ -258,7 +258,7 @@ impl std::fmt::Display for ResolvedObject {
 mod tests {
     use super::*;
     use esp_compiler::grammar::ast::nodes::{
-        DataType as AstDataType, ObjectDefinition as AstObject, ObjectElement as AstElement,
+        ObjectDefinition as AstObject, ObjectElement as AstElement,
         ObjectField as AstField, Value as AstValue,
     };
 
@@ -329,12 +329,12 @@ impl JsonRecord {
 
 impl RecordAccess for JsonRecord {
     fn get_field(&self, path: &FieldPath) -> Result<Option<ResolvedValue>, RecordError> {
-        let current = &self.data;
+        let mut current = &self.data;
 
         // Navigate through path components
         let components = path.parse_components();
         for component in &components {
-            match component {
+            current = match component {
                 PathComponent::Field(field_name) => {
                     match current.get(field_name) {
                         Some(value) => value,
@@ -363,11 +363,11 @@ impl RecordAccess for JsonRecord {
     }
 
     fn has_field(&self, path: &FieldPath) -> bool {
-        let current = &self.data;
+        let mut current = &self.data;
 
         let components = path.parse_components();
         for component in &components {
-            match component {
+            current = match component {
                 PathComponent::Field(field_name) => match current.get(field_name) {
                     Some(value) => value,
                     None => return false,
@@ -720,7 +720,6 @@ impl<T: RecordAccess + ?Sized> RecordAccessExt for T {}
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::common::FieldPath;
 
     #[test]
     fn test_json_record_basic_operations() {
@@ -736,31 +735,49 @@ mod tests {
         let record = JsonRecord::from_json_value(json_data);
 
         // Test field access
-        let name_path = FieldPath::from_string("name");
+        let name_path = FieldPath::parse("name");
         assert!(record.has_field(&name_path));
 
         let name_value = record.get_field(&name_path).unwrap();
         assert!(matches!(name_value, Some(ResolvedValue::String(ref s)) if s == "test"));
 
         // Test nested field access
-        let nested_path = FieldPath::from_string("nested.value");
+        let nested_path = FieldPath::parse("nested.value");
         assert!(record.has_field(&nested_path));
 
         let nested_value = record.get_field(&nested_path).unwrap();
         assert!(matches!(nested_value, Some(ResolvedValue::String(ref s)) if s == "inner"));
 
         // Test non-existent field
-        let missing_path = FieldPath::from_string("missing");
+        let missing_path = FieldPath::parse("missing");
         assert!(!record.has_field(&missing_path));
         assert!(matches!(record.get_field(&missing_path).unwrap(), None));
     }
 
+    #[test]
+    fn test_json_record_reads_deeply_nested_fields() {
+        let record = JsonRecord::from_json_value(serde_json::json!({
+            "a": { "b": { "c": 1 } },
+            "c": "root"
+        }));
+
+        // Each component is looked up under the previous one, not the root
+        let deep_path = FieldPath::parse("a.b.c");
+        assert!(record.has_field(&deep_path));
+        let deep_value = record.get_field(&deep_path).unwrap();
+        assert!(matches!(deep_value, Some(ResolvedValue::Integer(1))));
+
+        let misplaced_path = FieldPath::parse("a.c");
+        assert!(!record.has_field(&misplaced_path));
+        assert!(record.get_field(&misplaced_path).unwrap().is_none());
+    }
+
     #[test]
     fn test_json_record_mutable_operations() {
         let mut record = JsonRecord::from_json_value(serde_json::json!({}));
 
         // Test setting fields
-        let name_path = FieldPath::from_string("name");
+        let name_path = FieldPath::parse("name");
         record
             .set_field(&name_path, ResolvedValue::String("test".to_string()))
             .unwrap();
@@ -785,7 +802,7 @@ mod tests {
         assert_eq!(record.format_hint(), Some("json"));
         assert_eq!(record.field_count(), 2);
 
-        let name_path = FieldPath::from_string("name");
+        let name_path = FieldPath::parse("name");
         assert!(record.has_field(&name_path));
     }
 }
@@ -1,6 +1,7 @@
 use crate::types::{
     criteria::CriteriaRoot,
     criterion::CriterionDeclaration,
+    execution_plan::ExecutionPlan,
     metadata::MetaDataBlock,
     object::{ObjectDeclaration, ResolvedObject},
     runtime_operation::RuntimeOperation,
@@ -81,6 +82,15 @@ pub struct ResolutionContext {
     #[serde(default)]
     pub scan_time_operations: Vec<DeferredOperation>,
 
+    /// Global objects referencing scan-time variables, re-resolved once
+    /// those variables are computed
+    #[serde(default)]
+    pub late_bound_objects: HashMap<String, ObjectDeclaration>,
+
+    /// Ordered execution phases derived from the dependency graph
+    #[serde(default)]
+    pub execution_plan: ExecutionPlan,
+
     /// Metadata from ESP definition
     #[serde(default)]
     pub metadata: MetaDataBlock,
@@ -129,7 +139,7 @@ pub struct SymbolRelationship {
     pub relationship_type: RelationshipType,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum RelationshipType {
     VariableReference,
     StateReference,
@@ -169,6 +179,8 @@ impl ResolutionContext {
             resolved_local_objects: HashMap::new(),
             criteria_root: CriteriaRoot::default(),
             scan_time_operations: Vec::new(),
+            late_bound_objects: HashMap::new(),
+            execution_plan: ExecutionPlan::default(),
             metadata: MetaDataBlock::default(),
 
             // Initialize working data
@@ -203,6 +215,18 @@ impl ResolutionContext {
             .cloned()
             .collect();
 
+        // CTN-local states and objects, keyed by the node id of their CTN
+        let mut ctn_local_states = HashMap::new();
+        let mut ctn_local_objects = HashMap::new();
+        for (node_id, declaration) in criteria_root.criteria_with_node_ids() {
+            if !declaration.local_states.is_empty() {
+                ctn_local_states.insert(node_id, declaration.local_states.clone());
+            }
+            if let Some(object) = &declaration.local_object {
+                ctn_local_objects.insert(node_id, object.clone());
+            }
+        }
+
         Self {
             variables,
             states,
@@ -223,14 +247,16 @@ impl ResolutionContext {
             criteria_root,
 
             scan_time_operations: Vec::new(),
+            late_bound_objects: HashMap::new(),
+            execution_plan: ExecutionPlan::default(),
             metadata,
 
             // Initialize working data
             global_states,
             global_objects,
             set_operations: sets,
-            ctn_local_objects: HashMap::new(),
-            ctn_local_states: HashMap::new(),
+            ctn_local_objects,
+            ctn_local_states,
             relationships: Vec::new(),
         }
     }
@@ -246,38 +272,35 @@ impl ResolutionContext {
         criteria: Vec<CriterionDeclaration>,
         metadata: MetaDataBlock,
     ) -> Self {
-        let mut context = Self::new(
-            variables,
-            states,
-            objects,
-            runtime_operations,
-            sets,
-            criteria.clone(),
-        );
-        context.metadata = metadata;
-
-        // Build a simple flat CriteriaRoot from the criteria list
+        // Build a simple flat CriteriaRoot from the criteria list, keeping the
+        // node id each declaration was built with
         // This preserves backward compatibility but loses hierarchical structure
         use crate::types::common::LogicalOp;
-        let mut node_id = 1;
-        let trees: Vec<_> = criteria
-            .iter()
-            .map(|c| {
-                let id = node_id;
-                node_id += 1;
-                crate::types::criteria::CriteriaTree::Criterion {
-                    declaration: c.clone(),
-                    node_id: id,
-                }
-            })
+        let trees = criteria
+            .into_iter()
+            .enumerate()
+            .map(
+                |(index, declaration)| crate::types::criteria::CriteriaTree::Criterion {
+                    node_id: declaration.ctn_node_id.unwrap_or(index + 1),
+                    declaration,
+                },
+            )
             .collect();
-
-        context.criteria_root = CriteriaRoot {
+        let criteria_root = CriteriaRoot {
             trees,
             root_logical_op: LogicalOp::And,
+            root_negate: false,
         };
 
-        context
+        Self::from_ast_with_criteria_root(
+            variables,
+            states,
+            objects,
+            runtime_operations,
+            sets,
+            criteria_root,
+            metadata,
+        )
     }
 }
 
@@ -286,6 +309,7 @@ impl Default for CriteriaRoot {
         Self {
             trees: Vec::new(),
             root_logical_op: crate::types::common::LogicalOp::And,
+            root_negate: false,
         }
     }
 }
@@ -294,6 +318,7 @@ impl Default for MetaDataBlock {
     fn default() -> Self {
         Self {
             fields: std::collections::HashMap::new(),
+            order: Vec::new(),
         }
     }
 }
@@ -120,7 +120,7 @@ impl RunParameterExt for RunParameter {
             RunParameter::Character(_) => false,
             RunParameter::StartPosition(_) => false,
             RunParameter::Length(_) => false,
-            RunParameter::ArithmeticOp(_, value) => matches!(value, Value::Variable(_)),
+            RunParameter::ArithmeticOp(_, value) => matches!(value, Some(Value::Variable(_))),
         }
     }
 
@@ -141,7 +141,7 @@ impl RunParameterExt for RunParameter {
             RunParameter::StartPosition(_) => Vec::new(),
             RunParameter::Length(_) => Vec::new(),
             RunParameter::ArithmeticOp(_, value) => {
-                if let Value::Variable(var_name) = value {
+                if let Some(Value::Variable(var_name)) = value {
                     vec![var_name.clone()]
                 } else {
                     Vec::new()
@@ -276,7 +276,7 @@ mod tests {
                 AstParam::Variable("x".to_string()),
                 AstParam::ArithmeticOp(
                     esp_compiler::grammar::ast::nodes::ArithmeticOperator::Add,
-                    AstValue::Integer(10),
+                    Some(AstValue::Integer(10)),
                 ),
             ],
             span: None,
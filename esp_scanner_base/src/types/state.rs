@@ -286,22 +286,18 @@ impl RecordFieldExt for RecordField {
 impl std::fmt::Display for StateField {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         // Helper to format the value
-        let value_str = match &self.value {
-            Value::String(s) => format!("`{}`", s),
-            Value::Variable(v) => format!("VAR {}", v),
-            Value::Integer(i) => i.to_string(),
-            Value::Float(fl) => fl.to_string(),
-            Value::Boolean(b) => b.to_string(),
-        };
+        let value_str = self.value.to_string();
 
         // Format with or without entity check
         if let Some(entity_check) = &self.entity_check {
             // Convert EntityCheck to string inline (can't impl Display due to orphan rules)
             let entity_str = match entity_check {
-                EntityCheck::All => "ALL",
-                EntityCheck::AtLeastOne => "AT_LEAST_ONE",
-                EntityCheck::None => "NONE",
-                EntityCheck::OnlyOne => "ONLY_ONE",
+                EntityCheck::All => "ALL".to_string(),
+                EntityCheck::AtLeastOne => "AT_LEAST_ONE".to_string(),
+                EntityCheck::None => "NONE".to_string(),
+                EntityCheck::OnlyOne => "ONLY_ONE".to_string(),
+                EntityCheck::AtLeastN(n) => format!("AT_LEAST({})", n),
+                EntityCheck::AtLeastPercent(p) => format!("AT_LEAST_PERCENT({})", p),
             };
 
             write!(
@@ -0,0 +1,350 @@
+//! Worked example: a custom `nginx_config_check` strategy written and tested
+//! against the public API only, as an extension crate would. Walked through in
+//! "Testing Your Implementation" in docs/Scanner_Development_Guide.md.
+
+use esp_scanner_base::execution::{
+    evaluate_existence_check, evaluate_item_check, evaluate_state_operator, BehaviorHints,
+    ComparisonExt, ExecutionEngine,
+};
+use esp_scanner_base::resolution::engine::ResolutionEngine;
+use esp_scanner_base::resolution::resolution_context_from_ast;
+use esp_scanner_base::strategies::{
+    CollectedData, CollectionError, CollectionMode, CollectionStrategy, ComplianceStatus,
+    CtnContract, CtnDataCollector, CtnExecutionError, CtnExecutionResult, CtnExecutor,
+    CtnStrategyRegistry, FieldOutcome, FieldValidationResult, ObjectFieldSpec, PerformanceHints,
+    PrivilegeRequirement, StateFieldSpec, StateValidationResult,
+};
+use esp_scanner_base::testkit::{
+    check_findings_golden, check_golden, run_single_criterion, MockCollector,
+};
+use esp_scanner_base::types::common::{DataType, Operation, ResolvedValue};
+use esp_scanner_base::types::execution_context::{ExecutableCriterion, ExecutableObject};
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+const CTN_TYPE: &str = "nginx_config_check";
+
+// ============================================================================
+// Contract
+// ============================================================================
+
+fn create_nginx_config_contract() -> CtnContract {
+    let mut contract = CtnContract::new(CTN_TYPE.to_string());
+
+    contract
+        .object_requirements
+        .add_required_field(ObjectFieldSpec {
+            name: "path".to_string(),
+            data_type: DataType::String,
+            description: "Path to nginx.conf".to_string(),
+            example_values: vec!["/etc/nginx/nginx.conf".to_string()],
+            validation_notes: None,
+        });
+
+    contract
+        .state_requirements
+        .add_optional_field(StateFieldSpec {
+            name: "exists".to_string(),
+            data_type: DataType::Boolean,
+            allowed_operations: vec![Operation::Equals],
+            description: "Whether the config file exists".to_string(),
+            example_values: vec!["true".to_string()],
+            validation_notes: None,
+        });
+    for directive in ["server_tokens", "ssl_protocols", "client_max_body_size"] {
+        contract
+            .state_requirements
+            .add_optional_field(StateFieldSpec {
+                name: directive.to_string(),
+                data_type: DataType::String,
+                allowed_operations: vec![
+                    Operation::Equals,
+                    Operation::NotEqual,
+                    Operation::Contains,
+                    Operation::NotContains,
+                ],
+                description: format!("Arguments of the last `{}` directive", directive),
+                example_values: vec![],
+                validation_notes: None,
+            });
+    }
+
+    contract
+        .field_mappings
+        .collection_mappings
+        .object_to_collection
+        .insert("path".to_string(), "path".to_string());
+    contract
+        .field_mappings
+        .collection_mappings
+        .required_data_fields = vec!["exists".to_string()];
+
+    contract.collection_strategy = CollectionStrategy {
+        collector_type: "nginx".to_string(),
+        collection_mode: CollectionMode::Content,
+        required_capabilities: vec!["file_access".to_string()],
+        performance_hints: PerformanceHints {
+            expected_collection_time_ms: Some(5),
+            memory_usage_mb: Some(1),
+            network_intensive: false,
+            cpu_intensive: false,
+            requires_elevated_privileges: false,
+        },
+        required_privilege: PrivilegeRequirement::None,
+    };
+
+    contract
+}
+
+// ============================================================================
+// Collector
+// ============================================================================
+
+/// Reads `directive args;` lines; later directives override earlier ones
+struct NginxConfigCollector;
+
+impl CtnDataCollector for NginxConfigCollector {
+    fn collect_for_ctn_with_hints(
+        &self,
+        object: &ExecutableObject,
+        _ctn_contract: &CtnContract,
+        _hints: &BehaviorHints,
+    ) -> Result<CollectedData, CollectionError> {
+        let Some(ResolvedValue::String(path)) = object.get_field("path") else {
+            return Err(CollectionError::MissingCollectionField {
+                field: "path".to_string(),
+            });
+        };
+        let mut data = CollectedData::new(
+            object.identifier.clone(),
+            CTN_TYPE.to_string(),
+            self.collector_id().to_string(),
+        );
+
+        let Ok(config) = std::fs::read_to_string(path) else {
+            data.add_field("exists".to_string(), ResolvedValue::Boolean(false));
+            return Ok(data);
+        };
+        data.add_field("exists".to_string(), ResolvedValue::Boolean(true));
+        for line in config.lines() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            let Some(statement) = line.strip_suffix(';') else {
+                continue;
+            };
+            if let Some((directive, args)) = statement.split_once(char::is_whitespace) {
+                data.add_field(
+                    directive.to_string(),
+                    ResolvedValue::String(args.trim().to_string()),
+                );
+            }
+        }
+        Ok(data)
+    }
+
+    fn supported_ctn_types(&self) -> Vec<String> {
+        vec![CTN_TYPE.to_string()]
+    }
+
+    fn validate_ctn_compatibility(&self, contract: &CtnContract) -> Result<(), CollectionError> {
+        if contract.ctn_type != CTN_TYPE {
+            return Err(CollectionError::UnsupportedCtnType {
+                ctn_type: contract.ctn_type.clone(),
+                collector_id: self.collector_id().to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    fn collector_id(&self) -> &str {
+        "nginx"
+    }
+}
+
+// ============================================================================
+// Executor
+// ============================================================================
+
+struct NginxConfigExecutor {
+    contract: CtnContract,
+}
+
+impl CtnExecutor for NginxConfigExecutor {
+    fn execute_with_contract(
+        &self,
+        criterion: &ExecutableCriterion,
+        collected_data: &HashMap<String, CollectedData>,
+        _contract: &CtnContract,
+    ) -> Result<CtnExecutionResult, CtnExecutionError> {
+        let test = &criterion.test;
+        let found = collected_data
+            .values()
+            .filter(|data| data.get_field("exists") == Some(&ResolvedValue::Boolean(true)))
+            .count();
+        if !evaluate_existence_check(
+            test.existence_check,
+            found,
+            criterion.expected_object_count(),
+        ) {
+            return Ok(CtnExecutionResult::fail(
+                criterion.criterion_type.clone(),
+                format!("Existence check failed: {} config files found", found),
+            ));
+        }
+
+        let mut state_results = Vec::new();
+        for (object_id, data) in collected_data {
+            let mut fields = Vec::new();
+            for field in criterion.states.iter().flat_map(|state| &state.fields) {
+                let Some(actual) = data.get_field(&field.name) else {
+                    fields.push(FieldValidationResult::not_present(
+                        field.name.clone(),
+                        field.value.clone(),
+                        field.operation,
+                        format!("Directive '{}' not set", field.name),
+                    ));
+                    continue;
+                };
+                let passed = actual
+                    .compare_with(&field.value, field.operation)
+                    .unwrap_or(false);
+                fields.push(FieldValidationResult {
+                    field_name: field.name.clone(),
+                    expected_value: field.value.clone(),
+                    actual_value: actual.clone(),
+                    operation: field.operation,
+                    passed,
+                    message: format!("Directive '{}' {}", field.name, field.operation),
+                    outcome: FieldOutcome::from_passed(passed),
+                });
+            }
+            let passed: Vec<bool> = fields.iter().map(|f| f.passed).collect();
+            let combined = evaluate_state_operator(test.state_operator, &passed);
+            state_results.push(StateValidationResult {
+                object_id: object_id.clone(),
+                state_results: fields,
+                combined_result: combined,
+                state_operator: test.state_operator,
+                message: format!(
+                    "{}: {}",
+                    object_id,
+                    if combined {
+                        "compliant"
+                    } else {
+                        "not compliant"
+                    }
+                ),
+            });
+        }
+
+        let passing = state_results.iter().filter(|r| r.combined_result).count();
+        let result = if evaluate_item_check(test.item_check, passing, state_results.len()) {
+            CtnExecutionResult::pass(
+                criterion.criterion_type.clone(),
+                format!(
+                    "{} of {} nginx configs compliant",
+                    passing,
+                    state_results.len()
+                ),
+            )
+        } else {
+            CtnExecutionResult::fail(
+                criterion.criterion_type.clone(),
+                format!(
+                    "{} of {} nginx configs compliant",
+                    passing,
+                    state_results.len()
+                ),
+            )
+        };
+        Ok(result.with_state_results(state_results))
+    }
+
+    fn get_ctn_contract(&self) -> CtnContract {
+        self.contract.clone()
+    }
+
+    fn validate_collected_data(
+        &self,
+        _collected_data: &HashMap<String, CollectedData>,
+        _contract: &CtnContract,
+    ) -> Result<(), CtnExecutionError> {
+        Ok(())
+    }
+
+    fn ctn_type(&self) -> &str {
+        CTN_TYPE
+    }
+}
+
+fn executor() -> NginxConfigExecutor {
+    NginxConfigExecutor {
+        contract: create_nginx_config_contract(),
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+const NGINX_CONF: &str =
+    r#"{"id": "nginx_conf", "fields": {"path": "tests/fixtures/nginx/nginx.conf"}}"#;
+
+const HARDENED: &str = r#"[{"id": "hardened", "fields": [
+    {"name": "server_tokens", "type": "string", "op": "=", "value": "off"},
+    {"name": "ssl_protocols", "type": "string", "op": "not_contains", "value": "TLSv1.1"}
+]}]"#;
+
+#[test]
+fn test_executor_against_canned_config() {
+    let collector = MockCollector::new(CTN_TYPE).with_item(
+        "nginx_conf",
+        json!({"exists": true, "server_tokens": "on", "ssl_protocols": "TLSv1.1 TLSv1.2"}),
+    );
+    let result = run_single_criterion(&collector, &executor(), NGINX_CONF, HARDENED).unwrap();
+    check_golden(
+        &result,
+        json!({
+            "status": "fail",
+            "objects": {"nginx_conf": {"passed": false, "fields": [
+                {"field": "server_tokens", "outcome": "fail", "expected": "off", "actual": "on"},
+                {"field": "ssl_protocols", "outcome": "fail", "expected": "TLSv1.1",
+                 "actual": "TLSv1.1 TLSv1.2"}
+            ]}}
+        }),
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_collector_reads_directives_from_disk() {
+    let result =
+        run_single_criterion(&NginxConfigCollector, &executor(), NGINX_CONF, HARDENED).unwrap();
+    assert_eq!(result.status, ComplianceStatus::Pass, "{}", result.message);
+
+    let missing = r#"{"id": "gone", "fields": {"path": "tests/fixtures/nginx/missing.conf"}}"#;
+    let result =
+        run_single_criterion(&NginxConfigCollector, &executor(), missing, HARDENED).unwrap();
+    assert_eq!(result.status, ComplianceStatus::Fail);
+}
+
+#[test]
+fn test_policy_scan_matches_golden_findings() {
+    let mut registry = CtnStrategyRegistry::new();
+    registry
+        .register_ctn_strategy(Box::new(NginxConfigCollector), Box::new(executor()))
+        .unwrap();
+
+    let compiled = esp_compiler::pipeline::process_file("tests/fixtures/nginx/hardening.esp")
+        .expect("policy compiles");
+    let mut resolution_context = resolution_context_from_ast(&compiled.ast);
+    let execution_context = ResolutionEngine::new()
+        .resolve_context(&mut resolution_context)
+        .unwrap();
+    let result = ExecutionEngine::new(execution_context, Arc::new(registry))
+        .execute()
+        .unwrap();
+
+    assert_eq!(result.results.check.total_criteria, 2);
+    check_findings_golden(&result, "tests/fixtures/nginx/scan_result.json").unwrap();
+}
@@ -0,0 +1,1264 @@
+//! # Scanner Command Line
+//!
+//! clap definitions for the `scanner` binary, plus shell completion and
+//! man page generation from those same definitions.
+
+use clap::builder::{PossibleValuesParser, TypedValueParser};
+use clap::{Args, CommandFactory, Parser, Subcommand, ValueEnum, ValueHint};
+use clap_complete::Shell;
+use esp_compiler::terminal::{ColorChoice, Palette};
+use esp_scanner_base::execution::deadline::parse_timeout;
+use esp_scanner_base::execution::{
+    EmptyPolicy, ErrorMode, ExecutionEngine, ExecutionEvent, HostFactsCache, MemoryBudget,
+    ScanClock, Timeouts,
+};
+use esp_scanner_base::resolution::PolicyCache;
+use esp_scanner_base::results::csv::{CsvOptions, Delimiter};
+use esp_scanner_base::results::evidence::DEFAULT_OBSERVED_MAX_BYTES;
+use esp_scanner_base::results::{
+    compare, provenance, ControlMap, Disposition, PolicySeverity, ScanResult, WaiverSet,
+};
+use esp_scanner_base::strategies::{CtnStrategyRegistry, ScanTarget};
+use esp_scanner_base::types::ExecutionContext;
+use esp_scanner_sdk::config::ScannerConfig;
+use std::io::{IsTerminal, Write};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Exit codes and examples appended to `--help` and the man page
+const AFTER_LONG_HELP: &str = "\
+EXIT CODES:
+    0    Scan completed and the policy is compliant
+    1    Scan completed and the policy is non-compliant (with
+         --fail-on-severity, only when a failure is at or above that level)
+    2    The scan failed (resolution, registry or execution error), a
+         directory scan had a policy that failed, --dry-run found
+         criterion types with no registered strategy, or --validate-only
+         found a problem
+    3    The policy file could not be read
+    4    The policy did not compile (lexical, syntax, symbol, reference,
+         semantic or structural error)
+
+EXAMPLES:
+    scanner policy.esp
+    scanner /etc/esp/policies/
+    scanner --no-recursive --max-files 50 /etc/esp/policies/
+    scanner policy.ast.json
+    scanner --max-memory 256M policy.esp
+    scanner --preflight policy.esp
+    scanner --fail-on-severity high /etc/esp/policies/
+    scanner --dump-plan policy.esp
+    scanner --dry-run policy.esp > plan.json && diff old-plan.json plan.json
+    scanner --validate-only /etc/esp/policies/
+    scanner --cache-dir /var/cache/esp /etc/esp/policies/
+    scanner annotate scan_result.json --criterion file_metadata#3 --disposition accepted_risk
+    scanner --field-evidence policy.esp && scanner explain scan_result.json
+    scanner --format csv --csv-delimiter semicolon /etc/esp/policies/
+    scanner --format yaml --output /var/lib/esp/results/scan.yaml policy.esp
+    scanner --output - policy.esp | jq .results.check
+    scanner --format sarif --output results.sarif /etc/esp/policies/
+    scanner --format junit --output reports/esp.xml /etc/esp/policies/
+    scanner --control-map cis-controls.json /etc/esp/policies/
+    scanner --waivers waivers.yaml /etc/esp/policies/
+    scanner --no-host-id --output - policy.esp | jq .target
+    scanner eval-osquery policy.esp --data snapshot.json
+    scanner capabilities --json > scanner-caps.json
+    scanner check policy.esp --target-capabilities scanner-caps.json --deny-incompatible
+    scanner --now 2026-01-01T00:00:00Z policy.esp && scanner diff expected.json scan_result.json
+    scanner diff --criteria yesterday.json scan_result.json
+    scanner verify-result scan_result.json --policy policy.esp --max-age 24h
+    scanner completions bash > /etc/bash_completion.d/scanner
+    scanner --man > scanner.1";
+
+/// Exit statuses, documented under EXIT CODES
+pub mod exit_code {
+    pub const NON_COMPLIANT: i32 = 1;
+    pub const SCAN_FAILED: i32 = 2;
+    pub const UNREADABLE_POLICY: i32 = 3;
+    pub const INVALID_POLICY: i32 = 4;
+}
+
+/// Compliance scanning for ESP (Endpoint State Policy) files
+#[derive(Debug, Parser)]
+#[command(
+    name = "scanner",
+    version,
+    after_long_help = AFTER_LONG_HELP,
+    args_conflicts_with_subcommands = true,
+    subcommand_negates_reqs = true
+)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// ESP file, AST document (.json from `esp_compiler --emit-ast`), or directory of ESP files
+    #[arg(value_name = "PATH", value_hint = ValueHint::AnyPath, required_unless_present = "man")]
+    pub input: Option<PathBuf>,
+
+    #[command(flatten)]
+    pub scan: ScanOptions,
+
+    /// Color statuses and severities: auto (terminal without NO_COLOR),
+    /// always or never
+    #[arg(
+        long,
+        global = true,
+        value_name = "WHEN",
+        default_value = "auto",
+        value_parser = PossibleValuesParser::new(ColorChoice::VALUES)
+            .map(|s| s.parse::<ColorChoice>().unwrap())
+    )]
+    pub color: ColorChoice,
+
+    /// Print the man page (roff) to stdout and exit
+    #[arg(long)]
+    pub man: bool,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Print a shell completion script to stdout
+    Completions {
+        /// Shell to generate completions for
+        #[arg(value_enum)]
+        shell: Shell,
+    },
+
+    /// Add, edit or review reviewer annotations on a saved scan result
+    Annotate(AnnotateArgs),
+
+    /// Evaluate a policy offline against an osquery results export
+    EvalOsquery(EvalOsqueryArgs),
+
+    /// Show which fields each criterion compared in a saved scan result
+    Explain(ExplainArgs),
+
+    /// Print the capability manifest of this scanner build
+    Capabilities {
+        /// Print the manifest as JSON (for `--target-capabilities`)
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Check that a policy only uses constructs a scanner can execute
+    Check(CheckArgs),
+
+    /// Compare a saved scan result against an expected (golden) one,
+    /// ignoring ordering, volatile fields and float formatting, or with
+    /// --criteria list what changed since a previous scan
+    Diff(DiffArgs),
+
+    /// Check that a saved scan result was produced from a policy and is
+    /// recent enough to report
+    VerifyResult(VerifyResultArgs),
+}
+
+/// Options for the `diff` subcommand
+#[derive(Debug, Clone, Args)]
+pub struct DiffArgs {
+    /// Expected scan result JSON, e.g. checked into git
+    #[arg(value_name = "EXPECTED", value_hint = ValueHint::FilePath)]
+    pub expected: PathBuf,
+
+    /// Scan result JSON to compare against it
+    #[arg(value_name = "ACTUAL", value_hint = ValueHint::FilePath)]
+    pub actual: PathBuf,
+
+    /// Largest difference between two numbers that still counts as equal
+    #[arg(long, value_name = "EPSILON", default_value_t = compare::DEFAULT_TOLERANCE)]
+    pub tolerance: f64,
+
+    /// Compare results scanned from different policies instead of refusing
+    #[arg(long)]
+    pub force: bool,
+
+    /// List criteria that started failing, erroring or passing, were added
+    /// or removed, or observed different values since EXPECTED; exits 1
+    /// when a criterion started failing or erroring. Results from different
+    /// policies are compared, matching criteria that moved
+    #[arg(long)]
+    pub criteria: bool,
+
+    /// Print the --criteria changes as JSON
+    #[arg(long, requires = "criteria")]
+    pub json: bool,
+}
+
+/// Options for the `verify-result` subcommand
+#[derive(Debug, Clone, Args)]
+pub struct VerifyResultArgs {
+    /// Scan result JSON to check
+    #[arg(value_name = "RESULT", value_hint = ValueHint::FilePath)]
+    pub result: PathBuf,
+
+    /// ESP file or AST document the result should report on
+    #[arg(long, value_name = "POLICY", value_hint = ValueHint::FilePath)]
+    pub policy: PathBuf,
+
+    /// Warn when the scan is older than this (e.g. 7d, 12h, 30m)
+    #[arg(
+        long,
+        value_name = "AGE",
+        default_value = provenance::DEFAULT_MAX_AGE,
+        value_parser = provenance::parse_age
+    )]
+    pub max_age: chrono::Duration,
+}
+
+/// Options for the `check` subcommand
+#[derive(Debug, Clone, Args)]
+pub struct CheckArgs {
+    /// ESP file or AST document to check
+    #[arg(value_name = "POLICY", value_hint = ValueHint::FilePath)]
+    pub policy: PathBuf,
+
+    /// Capability manifest of the target scanner (default: this scanner)
+    #[arg(long, value_name = "FILE", value_hint = ValueHint::FilePath)]
+    pub target_capabilities: Option<PathBuf>,
+
+    /// Exit with status 1 when any construct is unsupported
+    #[arg(long)]
+    pub deny_incompatible: bool,
+}
+
+/// Options for the `explain` subcommand
+#[derive(Debug, Clone, Args)]
+pub struct ExplainArgs {
+    /// Scan result JSON from a scan run with --field-evidence
+    #[arg(value_name = "RESULT", value_hint = ValueHint::FilePath)]
+    pub result: PathBuf,
+
+    /// Only show this criterion (as shown in the report, e.g. file_metadata#3)
+    #[arg(long, value_name = "ID")]
+    pub criterion: Option<String>,
+}
+
+/// Options for the `eval-osquery` subcommand
+#[derive(Debug, Clone, Args)]
+pub struct EvalOsqueryArgs {
+    /// ESP file or AST document to evaluate
+    #[arg(value_name = "POLICY", value_hint = ValueHint::FilePath)]
+    pub policy: PathBuf,
+
+    /// osquery export (table map or snapshot log records, JSON)
+    #[arg(long, value_name = "FILE", value_hint = ValueHint::FilePath)]
+    pub data: PathBuf,
+
+    /// Criterion type to osquery table mapping (JSON or TOML) replacing the
+    /// built-in one
+    #[arg(long, value_name = "FILE", value_hint = ValueHint::FilePath)]
+    pub mapping: Option<PathBuf>,
+
+    /// Evaluate all time comparisons against this fixed instant (RFC 3339)
+    #[arg(long, value_name = "TIMESTAMP", value_parser = ScanClock::parse_fixed)]
+    pub now: Option<ScanClock>,
+}
+
+/// Options for the `annotate` subcommand
+#[derive(Debug, Clone, Args)]
+pub struct AnnotateArgs {
+    /// Scan result JSON produced by a previous scan
+    #[arg(value_name = "RESULT", value_hint = ValueHint::FilePath)]
+    pub result: PathBuf,
+
+    /// Annotation sidecar file (default: <RESULT stem>.annotations.json)
+    #[arg(long, value_name = "FILE", value_hint = ValueHint::FilePath)]
+    pub annotations: Option<PathBuf>,
+
+    /// Criterion id to annotate (as shown in the report, e.g. file_metadata#3)
+    #[arg(long, value_name = "ID")]
+    pub criterion: Option<String>,
+
+    /// Disposition to record for --criterion
+    #[arg(
+        long,
+        value_name = "DISPOSITION",
+        value_parser = Disposition::parse,
+        requires = "criterion",
+        required_unless_present_any = ["remove", "report_only"]
+    )]
+    pub disposition: Option<Disposition>,
+
+    /// Free-text reviewer comment
+    #[arg(long, requires = "disposition")]
+    pub comment: Option<String>,
+
+    /// Reviewer name (default: $USER)
+    #[arg(long, requires = "disposition")]
+    pub reviewer: Option<String>,
+
+    /// Remove the annotation for --criterion
+    #[arg(long, requires = "criterion", conflicts_with = "disposition")]
+    pub remove: bool,
+
+    /// Print the annotated report without changing annotations
+    #[arg(long = "report", conflicts_with_all = ["criterion", "remove"])]
+    pub report_only: bool,
+
+    /// Leave findings marked false_positive out of the report
+    #[arg(long)]
+    pub exclude_false_positives: bool,
+}
+
+/// Options controlling how a scan executes
+#[derive(Debug, Clone, Default, Args)]
+pub struct ScanOptions {
+    /// Soft memory budget (e.g. 256M, 1G); degrades the scan and skips
+    /// remaining criteria when exceeded
+    #[arg(long, value_name = "SIZE", value_parser = MemoryBudget::parse)]
+    pub max_memory: Option<MemoryBudget>,
+
+    /// Deadline for the whole scan (e.g. 90s, 10m); criteria not finished
+    /// by then are reported as errors with reason scan_deadline_exceeded
+    #[arg(long, value_name = "DURATION", value_parser = parse_timeout)]
+    pub timeout: Option<Duration>,
+
+    /// Longest a single criterion may run (default 30s); commands still
+    /// running are killed and the criterion is reported as an error with
+    /// reason criterion_timeout
+    #[arg(long, value_name = "DURATION", value_parser = parse_timeout)]
+    pub criterion_timeout: Option<Duration>,
+
+    /// Report criteria needing privileges this process lacks as not
+    /// evaluated instead of errors
+    #[arg(long)]
+    pub skip_unprivileged: bool,
+
+    /// List criteria that need elevated privileges and exit without scanning
+    #[arg(long)]
+    pub preflight: bool,
+
+    /// Print the execution phases resolved for the policy and exit without
+    /// scanning
+    #[arg(long)]
+    pub dump_plan: bool,
+
+    /// Print the criteria, resolved objects and matched strategies as JSON
+    /// and exit without scanning; exits 2 if a criterion type has no
+    /// registered strategy
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Compile and resolve the policy, or each policy in a directory, and
+    /// check every criterion against the registered strategies and their
+    /// contracts without collecting; exits 2 if any problem is found
+    #[arg(long)]
+    pub validate_only: bool,
+
+    /// Keep resolved policies in this directory, keyed by policy source and
+    /// scanner version, and reuse them instead of compiling again; an
+    /// unusable entry is warned about and replaced
+    #[arg(long, value_name = "DIR", value_hint = ValueHint::DirPath)]
+    pub cache_dir: Option<PathBuf>,
+
+    /// Do not print a progress line per criterion (shown only when stdout
+    /// is a terminal)
+    #[arg(short, long)]
+    pub quiet: bool,
+
+    /// Never read files under this prefix (repeatable); criteria touching it
+    /// are reported as errors with reason path_denied
+    #[arg(long, value_name = "PATH", value_hint = ValueHint::DirPath)]
+    pub deny_path: Vec<PathBuf>,
+
+    /// Only read files under this prefix (repeatable)
+    #[arg(long, value_name = "PATH", value_hint = ValueHint::DirPath)]
+    pub allow_path: Vec<PathBuf>,
+
+    /// Scan the filesystem mounted at this directory (an image or container
+    /// root) instead of the host; symlinks resolve inside it, and criteria
+    /// that need commands or kernel state are reported as not evaluated
+    /// (reason offline_target_unsupported)
+    #[arg(
+        long,
+        value_name = "PATH",
+        value_hint = ValueHint::DirPath,
+        value_parser = load_scan_root
+    )]
+    pub root: Option<Arc<ScanTarget>>,
+
+    /// Evaluate all time comparisons against this fixed instant (RFC 3339)
+    /// instead of the system clock, for deterministic reruns
+    #[arg(long, value_name = "TIMESTAMP", value_parser = ScanClock::parse_fixed)]
+    pub now: Option<ScanClock>,
+
+    /// Print per-criterion-type strategy usage after the scan
+    #[arg(long)]
+    pub strategy_report: bool,
+
+    /// Record every compared field (item, actual value, operation, outcome)
+    /// in the scan result for `scanner explain`
+    #[arg(long)]
+    pub field_evidence: bool,
+
+    /// Longest expected or observed value kept in a finding's evidence
+    /// [default: 1024]; longer values are cut and marked truncated
+    #[arg(long, value_name = "BYTES")]
+    pub evidence_max_bytes: Option<usize>,
+
+    /// Leave executor advisories (informational, non-scoring notes) out of
+    /// the scan result
+    #[arg(long)]
+    pub no_advisories: bool,
+
+    /// Leave the hostname, IP address and asset id out of results, for
+    /// pipelines that must not identify hosts
+    #[arg(long)]
+    pub no_host_id: bool,
+
+    /// Report criteria that saw a file change during the scan as errors
+    /// (reason data_changed_during_scan) instead of only flagging them
+    #[arg(long)]
+    pub strict_consistency: bool,
+
+    /// Fail criteria that pass without observing anything (e.g. a missing
+    /// parent directory or an unreadable path) instead of only flagging them
+    /// as vacuous
+    #[arg(long)]
+    pub fail_on_vacuous: bool,
+
+    /// Leave criteria with errors or unknown outcomes out of the overall
+    /// pass/fail instead of failing the policy; they are still counted
+    #[arg(long)]
+    pub lenient: bool,
+
+    /// Exit non-compliant only when a failed or error criterion has at least
+    /// this policy severity (CTN SEVERITY, else META severity); criteria
+    /// without a severity never trigger it
+    #[arg(
+        long,
+        value_name = "LEVEL",
+        value_parser = PossibleValuesParser::new(["critical", "high", "medium", "low", "info"])
+            .map(|s| PolicySeverity::from_label(&s))
+    )]
+    pub fail_on_severity: Option<PolicySeverity>,
+
+    /// Run policies using criterion types this scanner has no strategy for,
+    /// reporting those criteria as errors instead of refusing to scan
+    #[arg(long)]
+    pub allow_unknown_criteria: bool,
+
+    /// How to report a policy with no criteria [default: error for a file,
+    /// warn for a directory]
+    #[arg(long, value_enum, value_name = "MODE")]
+    pub empty_policy: Option<EmptyPolicyMode>,
+
+    /// Only scan ESP files directly in a directory, not in its
+    /// subdirectories
+    #[arg(long)]
+    pub no_recursive: bool,
+
+    /// Scan at most N ESP files of a directory (the first N by relative
+    /// path)
+    #[arg(long, value_name = "N")]
+    pub max_files: Option<usize>,
+
+    /// Format of the saved results: json (scan_result.json), yaml
+    /// (scan_result.yaml), csv (scan_result.csv, one row per finding), sarif
+    /// (scan_result.sarif, SARIF 2.1.0 for code scanning) or junit
+    /// (scan_result.xml, one test case per criterion)
+    #[arg(long, value_enum, default_value_t = OutputFormat::Json)]
+    pub format: OutputFormat,
+
+    /// Save results to this file instead of scan_result.<format> (or
+    /// batch_results.<format>) in the working directory, creating missing
+    /// directories; `-` writes them to stdout and the report to stderr
+    #[arg(short, long, value_name = "PATH", value_hint = ValueHint::FilePath)]
+    pub output: Option<PathBuf>,
+
+    /// CSV field separator: comma, semicolon or tab
+    #[arg(
+        long,
+        value_name = "SEP",
+        default_value = "comma",
+        value_parser = PossibleValuesParser::new(["comma", "semicolon", "tab"])
+            .map(|s| s.parse::<Delimiter>().unwrap())
+    )]
+    pub csv_delimiter: Delimiter,
+
+    /// Add a CSV row per passing criterion (needs --field-evidence)
+    #[arg(long)]
+    pub csv_include_passing: bool,
+
+    /// Split CSV findings into one row per failed item field (needs
+    /// --field-evidence)
+    #[arg(long)]
+    pub csv_per_item: bool,
+
+    /// Benchmark controls per criterion id (JSON object of id to control
+    /// list); overrides the policy's CONTROL declarations for each framework
+    /// it names
+    #[arg(
+        long,
+        value_name = "FILE",
+        value_hint = ValueHint::FilePath,
+        value_parser = load_control_map
+    )]
+    pub control_map: Option<Arc<ControlMap>>,
+
+    /// Approved exceptions (YAML, or JSON with a .json extension): failing
+    /// criteria a waiver selects are reported as waived until it expires
+    #[arg(
+        long,
+        value_name = "FILE",
+        value_hint = ValueHint::FilePath,
+        value_parser = load_waivers
+    )]
+    pub waivers: Option<Arc<WaiverSet>>,
+
+    /// Scanner configuration file (TOML) with a [paths] deny/allow section
+    #[arg(long, value_name = "FILE", value_hint = ValueHint::FilePath)]
+    pub config: Option<PathBuf>,
+
+    /// Host facts shared by every scan run with these options
+    #[arg(skip)]
+    pub host_facts: Option<Arc<HostFactsCache>>,
+}
+
+/// Format results are saved in
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    #[default]
+    Json,
+    Yaml,
+    Csv,
+    Sarif,
+    Junit,
+}
+
+impl OutputFormat {
+    /// File extension for results saved in this format
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::Yaml => "yaml",
+            Self::Csv => "csv",
+            Self::Sarif => "sarif",
+            Self::Junit => "xml",
+        }
+    }
+}
+
+/// Outcome for a policy with no criteria
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum EmptyPolicyMode {
+    /// Report it as compliant
+    Pass,
+    /// Report it as non-compliant
+    Fail,
+    /// Fail the scan of that policy
+    Error,
+    /// Report it as compliant with a warning
+    Warn,
+}
+
+impl ScanOptions {
+    /// These options with the directory-scan defaults filled in, probing
+    /// host facts once for the whole batch
+    pub fn for_batch(&self) -> Self {
+        let mut options = self.clone();
+        options.empty_policy.get_or_insert(EmptyPolicyMode::Warn);
+        options
+            .host_facts
+            .get_or_insert_with(|| Arc::new(HostFactsCache::new()));
+        options
+    }
+
+    /// Cache of resolved policies under --cache-dir, for this scanner
+    /// version
+    pub fn policy_cache(&self) -> Option<PolicyCache> {
+        self.cache_dir
+            .as_ref()
+            .map(|dir| PolicyCache::new(dir, env!("CARGO_PKG_VERSION")))
+    }
+
+    /// Where results are saved: --output, else `<stem>.<format>` in the
+    /// working directory
+    pub fn output_path(&self, stem: &str) -> PathBuf {
+        self.output
+            .clone()
+            .unwrap_or_else(|| PathBuf::from(format!("{}.{}", stem, self.format.extension())))
+    }
+
+    /// Whether to print a progress line per criterion: stdout is a terminal
+    /// that results do not go to, and --quiet is not given
+    pub fn shows_progress(&self) -> bool {
+        !self.quiet && !self.writes_to_stdout() && std::io::stdout().is_terminal()
+    }
+
+    /// Whether results or the --dry-run plan go to stdout, so logs and the
+    /// report move to stderr
+    pub fn writes_to_stdout(&self) -> bool {
+        self.dry_run || self.output.as_deref() == Some(std::path::Path::new("-"))
+    }
+
+    /// Whether `result` exits non-compliant: any non-compliant result, or
+    /// under --fail-on-severity one with a failure at or above that level
+    pub fn exits_non_compliant(&self, result: &ScanResult) -> bool {
+        match self.fail_on_severity {
+            _ if result.results.passed => false,
+            Some(level) => result.empty_policy || result.has_failures_at_or_above(level),
+            None => true,
+        }
+    }
+
+    /// The --empty-policy mode, error when unset
+    pub fn empty_policy_mode(&self) -> EmptyPolicyMode {
+        self.empty_policy.unwrap_or(EmptyPolicyMode::Error)
+    }
+
+    /// CSV export options from the --csv-* flags
+    pub fn csv_options(&self) -> CsvOptions {
+        CsvOptions::new()
+            .with_delimiter(self.csv_delimiter)
+            .with_passing(self.csv_include_passing)
+            .with_per_item(self.csv_per_item)
+    }
+
+    /// Scanner configuration from --config, extended by path flags and
+    /// --root
+    pub fn scanner_config(&self) -> Result<ScannerConfig, String> {
+        let config = match &self.config {
+            Some(path) => ScannerConfig::load(path)?,
+            None => ScannerConfig::default(),
+        };
+        let config = match &self.root {
+            Some(target) => config.with_scan_target(Arc::clone(target)),
+            None => config,
+        };
+        let config = self
+            .deny_path
+            .iter()
+            .fold(config, |config, p| config.with_deny_path(p));
+        Ok(self
+            .allow_path
+            .iter()
+            .fold(config, |config, p| config.with_allow_path(p)))
+    }
+
+    /// Build an execution engine honoring these options
+    pub fn engine(
+        &self,
+        context: ExecutionContext,
+        registry: Arc<CtnStrategyRegistry>,
+    ) -> ExecutionEngine {
+        let context = match self.now {
+            Some(clock) => context.with_clock(clock),
+            None => context,
+        };
+        let mut timeouts = Timeouts::default();
+        if let Some(timeout) = self.criterion_timeout {
+            timeouts = timeouts.with_criterion(timeout);
+        }
+        if let Some(timeout) = self.timeout {
+            timeouts = timeouts.with_scan(timeout);
+        }
+        let engine = ExecutionEngine::new(context, registry)
+            .with_timeouts(timeouts)
+            .with_skip_unprivileged(self.skip_unprivileged)
+            .with_field_evidence(self.field_evidence)
+            .with_observed_max_bytes(
+                self.evidence_max_bytes
+                    .unwrap_or(DEFAULT_OBSERVED_MAX_BYTES),
+            )
+            .with_advisories(!self.no_advisories)
+            .with_host_identification(!self.no_host_id)
+            .with_strict_consistency(self.strict_consistency)
+            .with_fail_on_vacuous(self.fail_on_vacuous)
+            .with_error_mode(if self.lenient {
+                ErrorMode::Lenient
+            } else {
+                ErrorMode::Strict
+            })
+            .with_allow_unknown_criteria(self.allow_unknown_criteria)
+            .with_empty_policy(match self.empty_policy_mode() {
+                EmptyPolicyMode::Pass | EmptyPolicyMode::Warn => EmptyPolicy::Pass,
+                EmptyPolicyMode::Fail => EmptyPolicy::Fail,
+                EmptyPolicyMode::Error => EmptyPolicy::Error,
+            });
+        let engine = match &self.host_facts {
+            Some(cache) => engine.with_host_facts(Arc::clone(cache)),
+            None => engine,
+        };
+        let engine = match &self.control_map {
+            Some(map) => engine.with_control_map(Arc::clone(map)),
+            None => engine,
+        };
+        let engine = match &self.waivers {
+            Some(waivers) => engine.with_waivers(Arc::clone(waivers)),
+            None => engine,
+        };
+        let engine = match &self.root {
+            Some(target) => engine.with_scan_target(Arc::clone(target)),
+            None => engine,
+        };
+        let engine = if self.shows_progress() {
+            engine.with_progress_handler(Arc::new(|event: &ExecutionEvent| {
+                if let Some(line) = progress_line(event, Palette::current()) {
+                    println!("{}", line);
+                }
+            }))
+        } else {
+            engine
+        };
+        match self.max_memory {
+            Some(budget) => engine.with_memory_budget(budget),
+            None => engine,
+        }
+    }
+}
+
+/// `[12/87] pass file_metadata (4ms)` for a completed criterion; other
+/// events print nothing
+fn progress_line(event: &ExecutionEvent, palette: Palette) -> Option<String> {
+    match event {
+        ExecutionEvent::CriterionCompleted {
+            criterion_type,
+            status,
+            duration,
+            completed,
+            total_criteria,
+            ..
+        } => Some(format!(
+            "[{}/{}] {} {} ({}ms)",
+            completed,
+            total_criteria,
+            palette.status(status.as_str()),
+            criterion_type,
+            duration.as_millis()
+        )),
+        _ => None,
+    }
+}
+
+/// Load `--control-map` once at argument parsing
+fn load_control_map(path: &str) -> Result<Arc<ControlMap>, String> {
+    ControlMap::load(std::path::Path::new(path))
+        .map(Arc::new)
+        .map_err(|e| e.to_string())
+}
+
+/// Load `--waivers` once at argument parsing
+fn load_waivers(path: &str) -> Result<Arc<WaiverSet>, String> {
+    WaiverSet::load(std::path::Path::new(path))
+        .map(Arc::new)
+        .map_err(|e| e.to_string())
+}
+
+/// Check `--root` once at argument parsing
+fn load_scan_root(path: &str) -> Result<Arc<ScanTarget>, String> {
+    ScanTarget::offline(path)
+        .map(Arc::new)
+        .map_err(|e| format!("Cannot scan root '{}': {}", path, e))
+}
+
+/// Write a completion script for `shell`
+pub fn write_completions(shell: Shell, out: &mut dyn Write) {
+    let mut command = Cli::command();
+    let name = command.get_name().to_string();
+    clap_complete::generate(shell, &mut command, name, out);
+}
+
+/// Write the roff man page
+pub fn write_man_page(out: &mut dyn Write) -> std::io::Result<()> {
+    clap_mangen::Man::new(Cli::command()).render(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use esp_scanner_base::strategies::ComplianceStatus;
+    use std::path::Path;
+
+    const BASH_SNAPSHOT: &str = "tests/snapshots/scanner.bash";
+
+    #[test]
+    fn test_cli_definition_is_valid() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn test_parse_scan_with_max_memory() {
+        let cli = Cli::try_parse_from(["scanner", "--max-memory", "256M", "policy.esp"]).unwrap();
+        assert_eq!(cli.input, Some(PathBuf::from("policy.esp")));
+        assert_eq!(
+            cli.scan.max_memory.map(|b| b.limit_bytes),
+            Some(256 * 1024 * 1024)
+        );
+        assert_eq!(cli.scan.evidence_max_bytes, None);
+
+        let cli =
+            Cli::try_parse_from(["scanner", "--evidence-max-bytes", "64", "policy.esp"]).unwrap();
+        assert_eq!(cli.scan.evidence_max_bytes, Some(64));
+    }
+
+    #[test]
+    fn test_parse_privilege_flags() {
+        let cli = Cli::try_parse_from([
+            "scanner",
+            "--skip-unprivileged",
+            "--preflight",
+            "policy.esp",
+        ])
+        .unwrap();
+        assert!(cli.scan.skip_unprivileged);
+        assert!(cli.scan.preflight);
+    }
+
+    #[test]
+    fn test_parse_dump_plan() {
+        let cli = Cli::try_parse_from(["scanner", "--dump-plan", "policy.esp"]).unwrap();
+        assert!(cli.scan.dump_plan);
+        assert!(!cli.scan.preflight);
+    }
+
+    #[test]
+    fn test_parse_validate_only() {
+        let cli = Cli::try_parse_from(["scanner", "--validate-only", "policies/"]).unwrap();
+        assert!(cli.scan.validate_only);
+        assert!(!cli.scan.writes_to_stdout());
+    }
+
+    #[test]
+    fn test_batch_warns_on_empty_policy_by_default() {
+        assert_eq!(
+            ScanOptions::default().empty_policy_mode(),
+            EmptyPolicyMode::Error
+        );
+        let batch = ScanOptions::default().for_batch();
+        assert_eq!(batch.empty_policy_mode(), EmptyPolicyMode::Warn);
+        assert!(batch.host_facts.is_some());
+
+        let cli = Cli::try_parse_from(["scanner", "--empty-policy", "fail", "policies/"]).unwrap();
+        assert_eq!(
+            cli.scan.for_batch().empty_policy_mode(),
+            EmptyPolicyMode::Fail
+        );
+    }
+
+    #[test]
+    fn test_parse_cache_dir() {
+        let cli = Cli::try_parse_from(["scanner", "policy.esp"]).unwrap();
+        assert!(cli.scan.policy_cache().is_none());
+
+        let cli = Cli::try_parse_from(["scanner", "--cache-dir", "/var/cache/esp", "policy.esp"])
+            .unwrap();
+        let cache = cli.scan.policy_cache().unwrap();
+        assert_eq!(cache.dir(), Path::new("/var/cache/esp"));
+    }
+
+    #[test]
+    fn test_dry_run_writes_to_stdout() {
+        let cli = Cli::try_parse_from(["scanner", "--dry-run", "policy.esp"]).unwrap();
+        assert!(cli.scan.dry_run);
+        assert!(cli.scan.writes_to_stdout());
+        assert!(!ScanOptions::default().writes_to_stdout());
+    }
+
+    #[test]
+    fn test_parse_repeated_deny_path() {
+        let cli = Cli::try_parse_from([
+            "scanner",
+            "--deny-path",
+            "/home",
+            "--deny-path",
+            "/var/lib/pgsql",
+            "policy.esp",
+        ])
+        .unwrap();
+        let config = cli.scan.scanner_config().unwrap();
+        assert_eq!(
+            config.paths.deny,
+            vec![PathBuf::from("/home"), PathBuf::from("/var/lib/pgsql")]
+        );
+    }
+
+    #[test]
+    fn test_parse_scan_root() {
+        let root = tempfile::tempdir().unwrap();
+        let cli = Cli::try_parse_from([
+            "scanner",
+            "--root",
+            root.path().to_str().unwrap(),
+            "policy.esp",
+        ])
+        .unwrap();
+        let config = cli.scan.scanner_config().unwrap();
+        assert!(config.scan_target().is_offline());
+        assert!(
+            Cli::try_parse_from(["scanner", "--root", "/nonexistent/image", "policy.esp"]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_progress_line_per_completed_criterion() {
+        let completed = ExecutionEvent::CriterionCompleted {
+            ctn_node_id: 3,
+            criterion_type: "file_metadata".to_string(),
+            status: ComplianceStatus::Pass,
+            duration: Duration::from_micros(4200),
+            completed: 12,
+            total_criteria: 87,
+        };
+        assert_eq!(
+            progress_line(&completed, Palette::new(false)).as_deref(),
+            Some("[12/87] pass file_metadata (4ms)")
+        );
+        let started = ExecutionEvent::ScanStarted { total_criteria: 87 };
+        assert_eq!(progress_line(&started, Palette::new(false)), None);
+
+        let cli = Cli::try_parse_from(["scanner", "-q", "policy.esp"]).unwrap();
+        assert!(cli.scan.quiet);
+        assert!(!cli.scan.shows_progress());
+    }
+
+    #[test]
+    fn test_parse_timeouts() {
+        let cli = Cli::try_parse_from([
+            "scanner",
+            "--timeout",
+            "10m",
+            "--criterion-timeout",
+            "5s",
+            "policy.esp",
+        ])
+        .unwrap();
+        assert_eq!(cli.scan.timeout, Some(Duration::from_secs(600)));
+        assert_eq!(cli.scan.criterion_timeout, Some(Duration::from_secs(5)));
+        assert!(
+            Cli::try_parse_from(["scanner", "--criterion-timeout", "0s", "policy.esp"]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_parse_fixed_now() {
+        let cli = Cli::try_parse_from(["scanner", "--now", "2026-03-01T12:00:00Z", "policy.esp"])
+            .unwrap();
+        assert!(cli.scan.now.unwrap().is_fixed());
+        assert!(Cli::try_parse_from(["scanner", "--now", "tomorrow", "policy.esp"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_annotate() {
+        let cli = Cli::try_parse_from([
+            "scanner",
+            "annotate",
+            "scan_result.json",
+            "--criterion",
+            "file_metadata#3",
+            "--disposition",
+            "false-positive",
+            "--comment",
+            "Managed by IaC",
+        ])
+        .unwrap();
+        let Some(Command::Annotate(args)) = cli.command else {
+            panic!("expected annotate subcommand");
+        };
+        assert_eq!(args.disposition, Some(Disposition::FalsePositive));
+        assert_eq!(args.comment.as_deref(), Some("Managed by IaC"));
+
+        // A disposition is needed unless removing or only reporting
+        assert!(
+            Cli::try_parse_from(["scanner", "annotate", "r.json", "--criterion", "x#1"]).is_err()
+        );
+        assert!(Cli::try_parse_from(["scanner", "annotate", "r.json", "--report"]).is_ok());
+    }
+
+    #[test]
+    fn test_parse_eval_osquery() {
+        let cli = Cli::try_parse_from([
+            "scanner",
+            "eval-osquery",
+            "policy.esp",
+            "--data",
+            "snapshot.json",
+        ])
+        .unwrap();
+        let Some(Command::EvalOsquery(args)) = cli.command else {
+            panic!("expected eval-osquery subcommand");
+        };
+        assert_eq!(args.data, PathBuf::from("snapshot.json"));
+        assert!(args.mapping.is_none());
+
+        // The export is required
+        assert!(Cli::try_parse_from(["scanner", "eval-osquery", "policy.esp"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_explain() {
+        let cli = Cli::try_parse_from([
+            "scanner",
+            "explain",
+            "scan_result.json",
+            "--criterion",
+            "file_metadata#2",
+        ])
+        .unwrap();
+        let Some(Command::Explain(args)) = cli.command else {
+            panic!("expected explain subcommand");
+        };
+        assert_eq!(args.criterion.as_deref(), Some("file_metadata#2"));
+
+        let cli = Cli::try_parse_from(["scanner", "--field-evidence", "policy.esp"]).unwrap();
+        assert!(cli.scan.field_evidence);
+    }
+
+    #[test]
+    fn test_parse_diff() {
+        let cli = Cli::try_parse_from(["scanner", "diff", "expected.json", "actual.json"]).unwrap();
+        let Some(Command::Diff(args)) = cli.command else {
+            panic!("expected diff subcommand");
+        };
+        assert_eq!(args.actual, PathBuf::from("actual.json"));
+        assert_eq!(args.tolerance, compare::DEFAULT_TOLERANCE);
+
+        let cli = Cli::try_parse_from([
+            "scanner",
+            "diff",
+            "expected.json",
+            "actual.json",
+            "--tolerance",
+            "0.5",
+        ])
+        .unwrap();
+        let Some(Command::Diff(args)) = cli.command else {
+            panic!("expected diff subcommand");
+        };
+        assert_eq!(args.tolerance, 0.5);
+        assert!(!args.force);
+        assert!(!args.criteria);
+
+        let cli = Cli::try_parse_from([
+            "scanner",
+            "diff",
+            "--criteria",
+            "--json",
+            "old.json",
+            "new.json",
+        ])
+        .unwrap();
+        let Some(Command::Diff(args)) = cli.command else {
+            panic!("expected diff subcommand");
+        };
+        assert!(args.criteria && args.json);
+        assert!(
+            Cli::try_parse_from(["scanner", "diff", "--json", "old.json", "new.json"]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_parse_verify_result() {
+        let cli = Cli::try_parse_from([
+            "scanner",
+            "verify-result",
+            "scan_result.json",
+            "--policy",
+            "policy.esp",
+        ])
+        .unwrap();
+        let Some(Command::VerifyResult(args)) = cli.command else {
+            panic!("expected verify-result subcommand");
+        };
+        assert_eq!(args.policy, PathBuf::from("policy.esp"));
+        assert_eq!(args.max_age, chrono::Duration::days(7));
+
+        let err = Cli::try_parse_from([
+            "scanner",
+            "verify-result",
+            "scan_result.json",
+            "--policy",
+            "policy.esp",
+            "--max-age",
+            "7 days",
+        ])
+        .unwrap_err();
+        assert!(err.to_string().contains("invalid age '7 days'"));
+    }
+
+    #[test]
+    fn test_parse_capabilities_and_check() {
+        let cli = Cli::try_parse_from(["scanner", "capabilities", "--json"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Command::Capabilities { json: true })
+        ));
+
+        let cli = Cli::try_parse_from([
+            "scanner",
+            "check",
+            "policy.esp",
+            "--target-capabilities",
+            "caps.json",
+            "--deny-incompatible",
+        ])
+        .unwrap();
+        let Some(Command::Check(args)) = cli.command else {
+            panic!("expected check subcommand");
+        };
+        assert!(args.deny_incompatible);
+        assert_eq!(args.target_capabilities, Some(PathBuf::from("caps.json")));
+    }
+
+    #[test]
+    fn test_color_flag_applies_to_subcommands() {
+        let cli = Cli::try_parse_from(["scanner", "policy.esp"]).unwrap();
+        assert_eq!(cli.color, ColorChoice::Auto);
+        let cli =
+            Cli::try_parse_from(["scanner", "check", "policy.esp", "--color", "always"]).unwrap();
+        assert_eq!(cli.color, ColorChoice::Always);
+        assert!(Cli::try_parse_from(["scanner", "policy.esp", "--color", "maybe"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_csv_format() {
+        let cli = Cli::try_parse_from([
+            "scanner",
+            "policies/",
+            "--format",
+            "csv",
+            "--csv-delimiter",
+            "tab",
+            "--csv-per-item",
+        ])
+        .unwrap();
+        assert_eq!(cli.scan.format, OutputFormat::Csv);
+        let options = cli.scan.csv_options();
+        assert_eq!(options.delimiter, Delimiter::Tab);
+        assert!(options.per_item && !options.include_passing);
+        assert!(Cli::try_parse_from(["scanner", "p.esp", "--csv-delimiter", "pipe"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_output_and_yaml_format() {
+        let cli = Cli::try_parse_from(["scanner", "p.esp", "--format", "yaml"]).unwrap();
+        assert_eq!(
+            cli.scan.output_path("scan_result"),
+            PathBuf::from("scan_result.yaml")
+        );
+        assert!(!cli.scan.writes_to_stdout());
+
+        let cli =
+            Cli::try_parse_from(["scanner", "policies/", "-o", "/var/lib/esp/out.json"]).unwrap();
+        assert_eq!(
+            cli.scan.output_path("batch_results"),
+            PathBuf::from("/var/lib/esp/out.json")
+        );
+
+        let cli = Cli::try_parse_from(["scanner", "p.esp", "--output", "-"]).unwrap();
+        assert!(cli.scan.writes_to_stdout());
+        let cli = Cli::try_parse_from(["scanner", "p.esp", "--format", "sarif"]).unwrap();
+        assert_eq!(
+            cli.scan.output_path("scan_result"),
+            PathBuf::from("scan_result.sarif")
+        );
+        let cli = Cli::try_parse_from(["scanner", "policies/", "--format", "junit"]).unwrap();
+        assert_eq!(
+            cli.scan.output_path("batch_results"),
+            PathBuf::from("batch_results.xml")
+        );
+        assert!(Cli::try_parse_from(["scanner", "p.esp", "--format", "xml"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_fail_on_severity() {
+        let cli = Cli::try_parse_from(["scanner", "p.esp", "--fail-on-severity", "high"]).unwrap();
+        assert_eq!(cli.scan.fail_on_severity, Some(PolicySeverity::High));
+        assert!(
+            Cli::try_parse_from(["scanner", "p.esp", "--fail-on-severity", "unknown"]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_parse_waivers() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("waivers.yaml");
+        std::fs::write(
+            &path,
+            "waivers:\n  - criterion_type: auditd_rules\n    expires: 2025-06-01\n    justification: SIEM\n",
+        )
+        .unwrap();
+
+        let cli =
+            Cli::try_parse_from(["scanner", "--waivers", path.to_str().unwrap(), "p.esp"]).unwrap();
+        assert_eq!(cli.scan.waivers.unwrap().waivers.len(), 1);
+
+        std::fs::write(&path, "waivers:\n  - host: web-01\n").unwrap();
+        let err = Cli::try_parse_from(["scanner", "--waivers", path.to_str().unwrap(), "p.esp"])
+            .unwrap_err();
+        assert!(err.to_string().contains("Failed to parse waivers file"));
+    }
+
+    #[test]
+    fn test_parse_control_map() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("controls.json");
+        std::fs::write(
+            &path,
+            r#"{ "sshd#2": [{ "framework": "CIS", "control_id": "5.2.10" }] }"#,
+        )
+        .unwrap();
+
+        let cli =
+            Cli::try_parse_from(["scanner", "--control-map", path.to_str().unwrap(), "p.esp"])
+                .unwrap();
+        let map = cli.scan.control_map.unwrap();
+        assert_eq!(map.criteria["sshd#2"][0].control_id, "5.2.10");
+
+        let missing = dir.path().join("missing.json");
+        assert!(Cli::try_parse_from([
+            "scanner",
+            "--control-map",
+            missing.to_str().unwrap(),
+            "p.esp"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_input_required_unless_man() {
+        assert!(Cli::try_parse_from(["scanner"]).is_err());
+        assert!(Cli::try_parse_from(["scanner", "--man"]).unwrap().man);
+        assert!(matches!(
+            Cli::try_parse_from(["scanner", "completions", "zsh"])
+                .unwrap()
+                .command,
+            Some(Command::Completions { shell: Shell::Zsh })
+        ));
+    }
+
+    #[test]
+    fn test_bash_completion_snapshot() {
+        let mut out = Vec::new();
+        write_completions(Shell::Bash, &mut out);
+        let generated = String::from_utf8(out).unwrap();
+
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join(BASH_SNAPSHOT);
+        if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+            std::fs::write(&path, &generated).unwrap();
+        }
+        let expected = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            generated, expected,
+            "bash completions changed; rerun with UPDATE_SNAPSHOTS=1 to accept"
+        );
+    }
+
+    #[test]
+    fn test_man_page_lists_every_flag() {
+        let mut out = Vec::new();
+        write_man_page(&mut out).unwrap();
+        let man = String::from_utf8(out).unwrap();
+
+        for arg in Cli::command().get_arguments() {
+            if let Some(long) = arg.get_long() {
+                // roff escapes hyphens in option names
+                let escaped = format!("\\-\\-{}", long.replace('-', "\\-"));
+                assert!(man.contains(&escaped), "man page missing --{}", long);
+            }
+        }
+        assert!(man.contains("EXIT CODES"));
+        assert!(man.contains("EXAMPLES"));
+    }
+}
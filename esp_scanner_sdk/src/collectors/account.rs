@@ -0,0 +1,614 @@
+//! # User Account Collector
+//!
+//! Reads the local account databases (`/etc/passwd` and `/etc/group`) for the
+//! `user_account` criterion type.
+//!
+//! The object's `username` selects accounts by exact name or by a glob using
+//! `*` and `?`. Every matching passwd line becomes one record in the
+//! `accounts` collection, so a username that appears twice is evaluated twice
+//! instead of one entry hiding the other; such records carry `duplicate`.
+//!
+//! NIS/compat lines (those starting with `+` or `-`) do not describe local
+//! accounts and are skipped, as are lines that do not have seven fields or
+//! whose uid or gid is not a number. A missing group file leaves the group
+//! fields empty. Home directories are only stat'ed when the [`PathPolicy`]
+//! permits it; otherwise `home_exists` is left out of the record.
+//!
+//! For the `password_policy` criterion type, `/etc/login.defs` gives the
+//! aging defaults and `/etc/shadow` each account's aging. The shadow fields
+//! of the matching accounts are collected as parallel lists, one value per
+//! account, so a state can check every account with an entity check. With
+//! the `exclude_locked` behavior, accounts whose password field starts with
+//! `!` or `*` are left out. The shadow file is only readable by root; reading
+//! it without permission is an access error, not an empty list.
+//!
+//! With an offline [`ScanTarget`], every database and home directory is
+//! resolved inside the target's root.
+use esp_scanner_base::execution::comparisons::string::glob_matches;
+use esp_scanner_base::execution::{BehaviorHints, FileObservation};
+use esp_scanner_base::strategies::{
+    CollectedData, CollectionError, CtnContract, CtnDataCollector, PathPolicy, ScanTarget,
+};
+use esp_scanner_base::types::common::{RecordData, ResolvedValue};
+use esp_scanner_base::types::execution_context::{ExecutableObject, ExecutableObjectElement};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+const DEFAULT_PASSWD_FILE: &str = "/etc/passwd";
+const DEFAULT_GROUP_FILE: &str = "/etc/group";
+const DEFAULT_SHADOW_FILE: &str = "/etc/shadow";
+const DEFAULT_LOGIN_DEFS_FILE: &str = "/etc/login.defs";
+
+/// `max_days` of an account without a maximum password age, as shadow
+/// tools write "never"
+const NO_MAX_DAYS: i64 = 99999;
+
+/// Aging defaults read from login.defs, and the fields they are stored as
+const LOGIN_DEFS_FIELDS: &[(&str, &str)] = &[
+    ("PASS_MAX_DAYS", "pass_max_days"),
+    ("PASS_MIN_DAYS", "pass_min_days"),
+    ("PASS_WARN_AGE", "pass_warn_age"),
+];
+
+/// Collector for local user accounts
+pub struct AccountCollector {
+    id: String,
+    path_policy: Arc<PathPolicy>,
+    scan_target: Arc<ScanTarget>,
+}
+
+impl AccountCollector {
+    pub fn new() -> Self {
+        Self {
+            id: "account_collector".to_string(),
+            path_policy: PathPolicy::unrestricted(),
+            scan_target: ScanTarget::shared_host(),
+        }
+    }
+
+    /// Restrict collection to paths the policy permits
+    pub fn with_path_policy(mut self, path_policy: Arc<PathPolicy>) -> Self {
+        self.path_policy = path_policy;
+        self
+    }
+
+    /// Read account databases inside the target's root
+    pub fn with_scan_target(mut self, scan_target: Arc<ScanTarget>) -> Self {
+        self.scan_target = scan_target;
+        self
+    }
+
+    /// Where `path` is read on this host
+    fn locate(&self, path: &str, object_id: &str) -> Result<String, CollectionError> {
+        self.scan_target
+            .resolve(Path::new(path))
+            .map(|located| located.display().to_string())
+            .map_err(|e| CollectionError::CollectionFailed {
+                object_id: object_id.to_string(),
+                reason: format!("Cannot resolve '{}' in the scan root: {}", path, e),
+            })
+    }
+
+    /// Whether an optional database exists in the scan target
+    fn database_exists(&self, path: &str) -> bool {
+        self.scan_target
+            .resolve(Path::new(path))
+            .is_ok_and(|located| located.exists())
+    }
+
+    fn check_path(&self, path: &str, object_id: &str) -> Result<(), CollectionError> {
+        self.path_policy
+            .check(Path::new(path))
+            .map_err(|reason| CollectionError::PathDenied {
+                object_id: object_id.to_string(),
+                path: path.to_string(),
+                reason,
+            })
+    }
+
+    /// Read an account database, recording the observation
+    fn read_database(
+        &self,
+        path: &str,
+        object_id: &str,
+        data: &mut CollectedData,
+    ) -> Result<String, CollectionError> {
+        let path = &self.locate(path, object_id)?;
+        self.check_path(path, object_id)?;
+        let content = fs::read_to_string(path).map_err(|e| match e.kind() {
+            std::io::ErrorKind::PermissionDenied => CollectionError::AccessDenied {
+                object_id: object_id.to_string(),
+                reason: format!("Cannot read '{}': {}", path, e),
+            },
+            _ => CollectionError::CollectionFailed {
+                object_id: object_id.to_string(),
+                reason: format!("Failed to read '{}': {}", path, e),
+            },
+        })?;
+        data.observe_file(FileObservation::new(path).with_content(content.as_bytes()));
+        Ok(content)
+    }
+
+    fn home_exists(&self, home: &str) -> Option<bool> {
+        if home.is_empty() {
+            return Some(false);
+        }
+        let path = self.scan_target.resolve(Path::new(home)).ok()?;
+        self.path_policy.permits(&path).then(|| path.is_dir())
+    }
+}
+
+impl Default for AccountCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// One local account line from a passwd file
+#[derive(Debug, Clone, PartialEq)]
+struct PasswdEntry {
+    username: String,
+    uid: i64,
+    gid: i64,
+    gecos: String,
+    home: String,
+    shell: String,
+}
+
+/// One group line from a group file
+#[derive(Debug, Clone, PartialEq)]
+struct GroupEntry {
+    name: String,
+    gid: i64,
+    members: Vec<String>,
+}
+
+/// One account line from a shadow file; empty aging fields are `None`
+#[derive(Debug, Clone, PartialEq)]
+struct ShadowEntry {
+    username: String,
+    password_field: String,
+    last_change: Option<i64>,
+    min_days: Option<i64>,
+    max_days: Option<i64>,
+    warn_days: Option<i64>,
+}
+
+impl ShadowEntry {
+    /// A `!` or `*` password field: no password login is possible
+    fn locked(&self) -> bool {
+        self.password_field.starts_with('!') || self.password_field.starts_with('*')
+    }
+}
+
+/// Whether a database line is a NIS/compat inclusion rather than a local entry
+fn is_compat_line(line: &str) -> bool {
+    line.starts_with('+') || line.starts_with('-')
+}
+
+/// Parse local accounts, skipping comments, compat and malformed lines
+fn parse_passwd(content: &str) -> Vec<PasswdEntry> {
+    content
+        .lines()
+        .filter(|line| !line.trim().is_empty() && !line.starts_with('#'))
+        .filter(|line| !is_compat_line(line))
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split(':').collect();
+            let [username, _, uid, gid, gecos, home, shell] = fields[..] else {
+                return None;
+            };
+            if username.is_empty() {
+                return None;
+            }
+            Some(PasswdEntry {
+                username: username.to_string(),
+                uid: uid.parse().ok()?,
+                gid: gid.parse().ok()?,
+                gecos: gecos.to_string(),
+                home: home.to_string(),
+                shell: shell.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Names of the local accounts in a passwd file
+pub(super) fn local_usernames(content: &str) -> Vec<String> {
+    parse_passwd(content)
+        .into_iter()
+        .map(|entry| entry.username)
+        .collect()
+}
+
+/// Parse local groups, skipping comments, compat and malformed lines
+fn parse_group(content: &str) -> Vec<GroupEntry> {
+    content
+        .lines()
+        .filter(|line| !line.trim().is_empty() && !line.starts_with('#'))
+        .filter(|line| !is_compat_line(line))
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split(':').collect();
+            let [name, _, gid, members] = fields[..] else {
+                return None;
+            };
+            Some(GroupEntry {
+                name: name.to_string(),
+                gid: gid.parse().ok()?,
+                members: members
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|m| !m.is_empty())
+                    .map(str::to_string)
+                    .collect(),
+            })
+        })
+        .collect()
+}
+
+/// Parse shadow entries, skipping comments, compat and malformed lines
+fn parse_shadow(content: &str) -> Vec<ShadowEntry> {
+    let number = |field: &str| -> Result<Option<i64>, ()> {
+        match field.trim() {
+            "" => Ok(None),
+            value => value.parse().map(Some).map_err(|_| ()),
+        }
+    };
+    content
+        .lines()
+        .filter(|line| !line.trim().is_empty() && !line.starts_with('#'))
+        .filter(|line| !is_compat_line(line))
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split(':').collect();
+            let [username, password_field, last_change, min_days, max_days, warn_days, _, _, _] =
+                fields[..]
+            else {
+                return None;
+            };
+            if username.is_empty() {
+                return None;
+            }
+            Some(ShadowEntry {
+                username: username.to_string(),
+                password_field: password_field.to_string(),
+                last_change: number(last_change).ok()?,
+                min_days: number(min_days).ok()?,
+                max_days: number(max_days).ok()?,
+                warn_days: number(warn_days).ok()?,
+            })
+        })
+        .collect()
+}
+
+/// `KEY value` settings of login.defs; a later setting replaces an earlier one
+fn parse_login_defs(content: &str) -> HashMap<String, String> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let (name, value) = line.split_once(char::is_whitespace)?;
+            Some((name.to_string(), value.trim().trim_matches('"').to_string()))
+        })
+        .collect()
+}
+
+/// Build the record for one account
+fn account_record(
+    entry: &PasswdEntry,
+    groups: &[GroupEntry],
+    duplicate: bool,
+    home_exists: Option<bool>,
+) -> ResolvedValue {
+    let primary = groups.iter().find(|g| g.gid == entry.gid);
+    let member_of: Vec<&str> = groups
+        .iter()
+        .filter(|g| g.gid == entry.gid || g.members.contains(&entry.username))
+        .map(|g| g.name.as_str())
+        .collect();
+
+    let mut fields = vec![
+        ("username".to_string(), entry.username.clone().into()),
+        ("uid".to_string(), entry.uid.into()),
+        ("gid".to_string(), entry.gid.into()),
+        ("gecos".to_string(), entry.gecos.clone().into()),
+        ("home".to_string(), entry.home.clone().into()),
+        ("shell".to_string(), entry.shell.clone().into()),
+        (
+            "group".to_string(),
+            primary.map(|g| g.name.clone()).unwrap_or_default().into(),
+        ),
+        (
+            "group_members".to_string(),
+            primary
+                .map(|g| g.members.join(","))
+                .unwrap_or_default()
+                .into(),
+        ),
+        ("groups".to_string(), member_of.join(",").into()),
+        ("duplicate".to_string(), duplicate.into()),
+    ];
+    if let Some(exists) = home_exists {
+        fields.push(("home_exists".to_string(), exists.into()));
+    }
+    ResolvedValue::RecordData(Box::new(RecordData::from_field_pairs(fields)))
+}
+
+fn object_string(
+    object: &ExecutableObject,
+    field: &str,
+) -> Result<Option<String>, CollectionError> {
+    for element in &object.elements {
+        if let ExecutableObjectElement::Field { name, value, .. } = element {
+            if name == field {
+                return match value {
+                    ResolvedValue::String(s) => Ok(Some(s.clone())),
+                    _ => Err(CollectionError::InvalidObjectConfiguration {
+                        object_id: object.identifier.clone(),
+                        reason: format!("'{}' field must be a string, got {:?}", field, value),
+                    }),
+                };
+            }
+        }
+    }
+    Ok(None)
+}
+
+impl AccountCollector {
+    /// Collect login.defs aging defaults and the shadow aging of matching
+    /// accounts
+    fn collect_password_policy(
+        &self,
+        object: &ExecutableObject,
+        contract: &CtnContract,
+        exclude_locked: bool,
+    ) -> Result<CollectedData, CollectionError> {
+        let object_id = &object.identifier;
+        let username = object_string(object, "username")?.unwrap_or_else(|| "*".to_string());
+        let login_defs_file = object_string(object, "login_defs_file")?
+            .unwrap_or_else(|| DEFAULT_LOGIN_DEFS_FILE.to_string());
+        let shadow_file = object_string(object, "shadow_file")?
+            .unwrap_or_else(|| DEFAULT_SHADOW_FILE.to_string());
+
+        let mut data = CollectedData::new(
+            object_id.clone(),
+            contract.ctn_type.clone(),
+            self.id.clone(),
+        );
+
+        if self.database_exists(&login_defs_file) {
+            let settings =
+                parse_login_defs(&self.read_database(&login_defs_file, object_id, &mut data)?);
+            for (setting, field) in LOGIN_DEFS_FIELDS {
+                match settings.get(*setting).map(|value| value.parse::<i64>()) {
+                    Some(Ok(days)) => {
+                        data.add_field(field.to_string(), ResolvedValue::Integer(days))
+                    }
+                    Some(Err(_)) => data.add_warning(format!(
+                        "{} in '{}' is not a number",
+                        setting, login_defs_file
+                    )),
+                    None => {}
+                }
+            }
+        } else {
+            data.add_warning(format!("'{}' not found", login_defs_file));
+        }
+
+        let entries: Vec<ShadowEntry> =
+            parse_shadow(&self.read_database(&shadow_file, object_id, &mut data)?)
+                .into_iter()
+                .filter(|entry| glob_matches(&username, &entry.username).unwrap_or(false))
+                .filter(|entry| !(exclude_locked && entry.locked()))
+                .collect();
+
+        let list = |value: fn(&ShadowEntry) -> ResolvedValue| {
+            ResolvedValue::Collection(entries.iter().map(value).collect())
+        };
+        data.add_field(
+            "username".to_string(),
+            list(|entry| ResolvedValue::String(entry.username.clone())),
+        );
+        data.add_field(
+            "max_days".to_string(),
+            list(|entry| ResolvedValue::Integer(entry.max_days.unwrap_or(NO_MAX_DAYS))),
+        );
+        data.add_field(
+            "min_days".to_string(),
+            list(|entry| ResolvedValue::Integer(entry.min_days.unwrap_or(0))),
+        );
+        data.add_field(
+            "warn_days".to_string(),
+            list(|entry| ResolvedValue::Integer(entry.warn_days.unwrap_or(0))),
+        );
+        data.add_field(
+            "last_change_epoch".to_string(),
+            list(|entry| ResolvedValue::Integer(entry.last_change.unwrap_or(0) * 86_400)),
+        );
+        data.add_field(
+            "locked".to_string(),
+            list(|entry| ResolvedValue::Boolean(entry.locked())),
+        );
+        Ok(data)
+    }
+}
+
+impl CtnDataCollector for AccountCollector {
+    fn collect_for_ctn_with_hints(
+        &self,
+        object: &ExecutableObject,
+        contract: &CtnContract,
+        hints: &BehaviorHints,
+    ) -> Result<CollectedData, CollectionError> {
+        if contract.ctn_type == "password_policy" {
+            let behaviors = contract.resolve_behaviors(hints).map_err(|e| {
+                CollectionError::CtnContractValidation {
+                    reason: e.to_string(),
+                }
+            })?;
+            return self.collect_password_policy(
+                object,
+                contract,
+                behaviors.flag("exclude_locked"),
+            );
+        }
+
+        let object_id = &object.identifier;
+        let username = object_string(object, "username")?.ok_or_else(|| {
+            CollectionError::InvalidObjectConfiguration {
+                object_id: object_id.clone(),
+                reason: "Missing required 'username' field".to_string(),
+            }
+        })?;
+        let passwd_file = object_string(object, "passwd_file")?
+            .unwrap_or_else(|| DEFAULT_PASSWD_FILE.to_string());
+        let group_file =
+            object_string(object, "group_file")?.unwrap_or_else(|| DEFAULT_GROUP_FILE.to_string());
+
+        let mut data = CollectedData::new(
+            object_id.clone(),
+            contract.ctn_type.clone(),
+            self.id.clone(),
+        );
+
+        let accounts = parse_passwd(&self.read_database(&passwd_file, object_id, &mut data)?);
+        let groups = if self.database_exists(&group_file) {
+            parse_group(&self.read_database(&group_file, object_id, &mut data)?)
+        } else {
+            Vec::new()
+        };
+
+        let mut occurrences: HashMap<&str, usize> = HashMap::new();
+        for entry in &accounts {
+            *occurrences.entry(entry.username.as_str()).or_default() += 1;
+        }
+
+        let records = accounts
+            .iter()
+            .filter(|entry| glob_matches(&username, &entry.username).unwrap_or(false))
+            .map(|entry| {
+                let duplicate = occurrences[entry.username.as_str()] > 1;
+                account_record(entry, &groups, duplicate, self.home_exists(&entry.home))
+            })
+            .collect();
+
+        data.add_field("username".to_string(), ResolvedValue::String(username));
+        data.add_field("accounts".to_string(), ResolvedValue::Collection(records));
+        Ok(data)
+    }
+
+    fn supported_ctn_types(&self) -> Vec<String> {
+        vec!["user_account".to_string(), "password_policy".to_string()]
+    }
+
+    fn validate_ctn_compatibility(&self, contract: &CtnContract) -> Result<(), CollectionError> {
+        if !self.supported_ctn_types().contains(&contract.ctn_type) {
+            return Err(CollectionError::CtnContractValidation {
+                reason: format!("CTN type '{}' not supported", contract.ctn_type),
+            });
+        }
+        Ok(())
+    }
+
+    fn collector_id(&self) -> &str {
+        &self.id
+    }
+
+    fn supports_batch_collection(&self) -> bool {
+        false
+    }
+
+    fn supports_offline_target(&self, _ctn_type: &str) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PASSWD: &str = "\
+root:x:0:0:root:/root:/bin/bash
+# service accounts
+daemon:x:2:2:daemon:/sbin:/sbin/nologin
++nisuser::::::
++@admins
+-blocked
+broken:x:1000
+alice:x:1000:1000:Alice:/home/alice:/bin/bash
+alice:x:1001:1000:Alice again:/home/alice2:/bin/zsh
+bob:x:notanumber:1000::/home/bob:/bin/sh
+nohome:x:1002:100:::/sbin/nologin
+";
+
+    const GROUP: &str = "\
+root:x:0:
+users:x:100:nohome
+staff:x:1000:carol,dave
+wheel:x:10:alice,root
++:::
+";
+
+    #[test]
+    fn test_parse_passwd_skips_compat_and_malformed_lines() {
+        let accounts = parse_passwd(PASSWD);
+        let names: Vec<&str> = accounts.iter().map(|a| a.username.as_str()).collect();
+        assert_eq!(names, ["root", "daemon", "alice", "alice", "nohome"]);
+        assert_eq!(accounts[2].uid, 1000);
+        assert_eq!(accounts[3].shell, "/bin/zsh");
+        assert_eq!(accounts[4].home, "");
+    }
+
+    #[test]
+    fn test_parse_group_members() {
+        let groups = parse_group(GROUP);
+        assert_eq!(groups.len(), 4);
+        assert_eq!(groups[2].members, ["carol", "dave"]);
+        assert!(groups[0].members.is_empty());
+    }
+
+    #[test]
+    fn test_parse_shadow_and_login_defs() {
+        let entries = parse_shadow(
+            "root:$6$salt$hash:19700:0:99999:7:::\n\
+             bin:*:19000::::::\n\
+             alice:!$6$salt$hash:19750:1:60:14:::\n\
+             broken:x:notanumber:0:90:7:::\n\
+             short:x:19700\n",
+        );
+        let names: Vec<&str> = entries.iter().map(|e| e.username.as_str()).collect();
+        assert_eq!(names, ["root", "bin", "alice"]);
+        assert!(!entries[0].locked());
+        assert!(entries[1].locked() && entries[2].locked());
+        assert_eq!(entries[1].max_days, None);
+        assert_eq!(entries[2].max_days, Some(60));
+
+        let settings =
+            parse_login_defs("# aging\nPASS_MAX_DAYS\t99999\nPASS_MAX_DAYS 60\nUMASK 077\n");
+        assert_eq!(settings["PASS_MAX_DAYS"], "60");
+        assert_eq!(settings["UMASK"], "077");
+    }
+
+    #[test]
+    fn test_account_record_groups_and_missing_home() {
+        let accounts = parse_passwd(PASSWD);
+        let groups = parse_group(GROUP);
+
+        let ResolvedValue::RecordData(alice) = account_record(&accounts[2], &groups, true, None)
+        else {
+            panic!("expected a record");
+        };
+        let field = |record: &RecordData, name: &str| record.get_field_by_path(name).cloned();
+        assert_eq!(field(&alice, "group"), Some("staff".into()));
+        assert_eq!(field(&alice, "group_members"), Some("carol,dave".into()));
+        assert_eq!(field(&alice, "groups"), Some("staff,wheel".into()));
+        assert_eq!(field(&alice, "duplicate"), Some(true.into()));
+        assert_eq!(field(&alice, "home_exists"), None);
+
+        let collector = AccountCollector::new();
+        assert_eq!(collector.home_exists(""), Some(false));
+        assert_eq!(collector.home_exists("/nonexistent/home/dir"), Some(false));
+        assert_eq!(collector.home_exists("/"), Some(true));
+    }
+}
@@ -0,0 +1,554 @@
+//! # Archive Member Reader
+//!
+//! Reads one file out of a tar or cpio (newc) archive, optionally compressed
+//! with gzip, xz or zstd, without unpacking anything to disk. Used by the
+//! file system collector for objects with an `archive_member` behavior, e.g.
+//! a config inside a container layer or an initramfs.
+//!
+//! Decompression is bounded by [`ArchiveLimits`] so a small archive cannot
+//! expand into unbounded memory or CPU. A member that is itself an archive
+//! is refused rather than opened.
+use std::cell::Cell;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufReader, Cursor, Read, Write};
+use std::path::Path;
+use std::rc::Rc;
+
+/// Size limits for reading archives
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArchiveLimits {
+    /// Most bytes decompressed from the archive while looking for the member
+    pub max_decompressed: u64,
+    /// Largest member read into memory
+    pub max_member: u64,
+}
+
+impl Default for ArchiveLimits {
+    fn default() -> Self {
+        Self {
+            max_decompressed: 256 * 1024 * 1024,
+            max_member: 16 * 1024 * 1024,
+        }
+    }
+}
+
+/// A member found in an archive; `content` is empty unless requested
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchiveMember {
+    pub name: String,
+    /// Permission bits (without the file type)
+    pub mode: u32,
+    pub uid: u64,
+    pub gid: u64,
+    pub size: u64,
+    pub is_file: bool,
+    pub content: Vec<u8>,
+}
+
+/// Why a member could not be read
+#[derive(Debug)]
+pub enum ArchiveError {
+    /// The archive itself could not be opened
+    Io(io::Error),
+    UnknownFormat,
+    MemberNotFound,
+    NotRegularFile,
+    /// A size limit was hit; names which one
+    TooLarge(String),
+    /// The member is itself an archive of the named format
+    Nested(&'static str),
+    Corrupt(String),
+}
+
+impl fmt::Display for ArchiveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "cannot open archive: {}", e),
+            Self::UnknownFormat => write!(f, "not a tar or cpio archive"),
+            Self::MemberNotFound => write!(f, "member not found"),
+            Self::NotRegularFile => write!(f, "member is not a regular file"),
+            Self::TooLarge(limit) => write!(f, "{}", limit),
+            Self::Nested(format) => write!(
+                f,
+                "member is itself a {} archive; nested archives are not read",
+                format
+            ),
+            Self::Corrupt(reason) => write!(f, "corrupt archive: {}", reason),
+        }
+    }
+}
+
+/// Compression format named by a stream's first bytes
+fn compression(magic: &[u8]) -> Option<&'static str> {
+    if magic.starts_with(&[0x1f, 0x8b]) {
+        Some("gzip")
+    } else if magic.starts_with(&[0xfd, b'7', b'z', b'X', b'Z', 0x00]) {
+        Some("xz")
+    } else if magic.starts_with(&[0x28, 0xb5, 0x2f, 0xfd]) {
+        Some("zstd")
+    } else {
+        None
+    }
+}
+
+/// Archive format named by the first block of an uncompressed stream
+fn container(block: &[u8]) -> Option<&'static str> {
+    if block.starts_with(b"070701") || block.starts_with(b"070702") {
+        Some("cpio")
+    } else if block.len() >= 262 && &block[257..262] == b"ustar" {
+        Some("tar")
+    } else {
+        None
+    }
+}
+
+/// Archive or compression format of a member's content, if any
+fn nested_format(content: &[u8]) -> Option<&'static str> {
+    compression(content).or_else(|| container(content))
+}
+
+/// Strip `./` and `/` prefixes so `./etc/hosts`, `/etc/hosts` and
+/// `etc/hosts` name the same member
+fn normalize(name: &str) -> &str {
+    let mut name = name;
+    loop {
+        if let Some(rest) = name.strip_prefix("./") {
+            name = rest;
+        } else if let Some(rest) = name.strip_prefix('/') {
+            name = rest;
+        } else {
+            return name;
+        }
+    }
+}
+
+/// Reader that fails once more than `limit` bytes came through, recording
+/// that it did so the caller can tell a limit from a corrupt stream
+struct Bounded<R> {
+    inner: R,
+    remaining: u64,
+    exceeded: Rc<Cell<bool>>,
+}
+
+impl<R: Read> Read for Bounded<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        if n as u64 > self.remaining {
+            self.exceeded.set(true);
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                "decompressed size limit exceeded",
+            ));
+        }
+        self.remaining -= n as u64;
+        Ok(n)
+    }
+}
+
+/// Writer counterpart of [`Bounded`] for decoders that write their output
+struct BoundedWriter {
+    buffer: Vec<u8>,
+    limit: u64,
+    exceeded: Rc<Cell<bool>>,
+}
+
+impl Write for BoundedWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if (self.buffer.len() + buf.len()) as u64 > self.limit {
+            self.exceeded.set(true);
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                "decompressed size limit exceeded",
+            ));
+        }
+        self.buffer.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Read `member` from the archive at `path`, with its content when
+/// `with_content` is set
+pub fn read_member(
+    path: &Path,
+    member: &str,
+    with_content: bool,
+    limits: ArchiveLimits,
+) -> Result<ArchiveMember, ArchiveError> {
+    let mut file = BufReader::new(File::open(path).map_err(ArchiveError::Io)?);
+    let mut magic = [0u8; 6];
+    let read = read_up_to(&mut file, &mut magic).map_err(ArchiveError::Io)?;
+    let file = Cursor::new(magic[..read].to_vec()).chain(file);
+
+    let exceeded = Rc::new(Cell::new(false));
+    let too_large = || {
+        ArchiveError::TooLarge(format!(
+            "archive decompresses to more than {} bytes",
+            limits.max_decompressed
+        ))
+    };
+    let decompressed: Box<dyn Read> = match compression(&magic[..read]) {
+        Some("gzip") => Box::new(flate2::read::MultiGzDecoder::new(file)),
+        Some("xz") => {
+            let mut output = BoundedWriter {
+                buffer: Vec::new(),
+                limit: limits.max_decompressed,
+                exceeded: exceeded.clone(),
+            };
+            let mut input = BufReader::new(file);
+            if let Err(e) = lzma_rs::xz_decompress(&mut input, &mut output) {
+                return Err(if exceeded.get() {
+                    too_large()
+                } else {
+                    ArchiveError::Corrupt(e.to_string())
+                });
+            }
+            Box::new(Cursor::new(output.buffer))
+        }
+        Some(_) => Box::new(
+            ruzstd::decoding::StreamingDecoder::new(file)
+                .map_err(|e| ArchiveError::Corrupt(e.to_string()))?,
+        ),
+        None => Box::new(file),
+    };
+    let mut stream = Bounded {
+        inner: decompressed,
+        remaining: limits.max_decompressed,
+        exceeded: exceeded.clone(),
+    };
+
+    let mut block = [0u8; 512];
+    let read = read_up_to(&mut stream, &mut block).map_err(|e| {
+        if exceeded.get() {
+            too_large()
+        } else {
+            ArchiveError::Corrupt(e.to_string())
+        }
+    })?;
+    let format = container(&block[..read]).ok_or(ArchiveError::UnknownFormat)?;
+    let stream = Cursor::new(block[..read].to_vec()).chain(stream);
+
+    let wanted = normalize(member);
+    let found = match format {
+        "tar" => find_tar(stream, wanted, with_content, limits),
+        _ => find_cpio(stream, wanted, with_content, limits),
+    };
+    match found {
+        Err(ArchiveError::Corrupt(_)) if exceeded.get() => Err(too_large()),
+        other => other,
+    }
+}
+
+/// Fill as much of `buf` as the stream has
+fn read_up_to(reader: &mut impl Read, buf: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..])? {
+            0 => break,
+            n => filled += n,
+        }
+    }
+    Ok(filled)
+}
+
+/// Read a member's content, refusing oversized members and nested archives
+fn read_content(
+    reader: &mut impl Read,
+    size: u64,
+    limits: ArchiveLimits,
+) -> Result<Vec<u8>, ArchiveError> {
+    if size > limits.max_member {
+        return Err(ArchiveError::TooLarge(format!(
+            "member is {} bytes, more than the {} byte limit",
+            size, limits.max_member
+        )));
+    }
+    let mut content = Vec::with_capacity(size as usize);
+    reader
+        .take(size)
+        .read_to_end(&mut content)
+        .map_err(|e| ArchiveError::Corrupt(e.to_string()))?;
+    if let Some(format) = nested_format(&content) {
+        return Err(ArchiveError::Nested(format));
+    }
+    Ok(content)
+}
+
+fn find_tar(
+    stream: impl Read,
+    wanted: &str,
+    with_content: bool,
+    limits: ArchiveLimits,
+) -> Result<ArchiveMember, ArchiveError> {
+    let corrupt = |e: io::Error| ArchiveError::Corrupt(e.to_string());
+    let mut archive = tar::Archive::new(stream);
+    for entry in archive.entries().map_err(corrupt)? {
+        let mut entry = entry.map_err(corrupt)?;
+        let name = String::from_utf8_lossy(&entry.path_bytes()).into_owned();
+        if normalize(&name).trim_end_matches('/') != wanted {
+            continue;
+        }
+        let header = entry.header();
+        let mut found = ArchiveMember {
+            mode: header.mode().map_err(corrupt)? & 0o7777,
+            uid: header.uid().map_err(corrupt)?,
+            gid: header.gid().map_err(corrupt)?,
+            size: entry.size(),
+            is_file: header.entry_type().is_file(),
+            name,
+            content: Vec::new(),
+        };
+        if with_content {
+            if !found.is_file {
+                return Err(ArchiveError::NotRegularFile);
+            }
+            found.content = read_content(&mut entry, found.size, limits)?;
+        }
+        return Ok(found);
+    }
+    Err(ArchiveError::MemberNotFound)
+}
+
+/// Size of one newc header; every field is eight hex digits after the
+/// six byte magic
+const CPIO_HEADER: usize = 110;
+const CPIO_TRAILER: &str = "TRAILER!!!";
+const S_IFMT: u32 = 0o170000;
+const S_IFREG: u32 = 0o100000;
+
+fn find_cpio(
+    mut stream: impl Read,
+    wanted: &str,
+    with_content: bool,
+    limits: ArchiveLimits,
+) -> Result<ArchiveMember, ArchiveError> {
+    let corrupt = |e: io::Error| ArchiveError::Corrupt(e.to_string());
+    let mut header = [0u8; CPIO_HEADER];
+    loop {
+        stream.read_exact(&mut header).map_err(corrupt)?;
+        if container(&header) != Some("cpio") {
+            return Err(ArchiveError::Corrupt("bad cpio header magic".to_string()));
+        }
+        let field = |index: usize| -> Result<u32, ArchiveError> {
+            let start = 6 + index * 8;
+            std::str::from_utf8(&header[start..start + 8])
+                .ok()
+                .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                .ok_or_else(|| ArchiveError::Corrupt("bad cpio header field".to_string()))
+        };
+        let (mode, uid, gid) = (field(1)?, field(2)?, field(3)?);
+        let size = field(6)? as u64;
+        let name_size = field(11)? as usize;
+
+        // Header and name are padded to four bytes together, as is the data
+        let mut name = vec![0u8; name_size + (4 - (CPIO_HEADER + name_size) % 4) % 4];
+        stream.read_exact(&mut name).map_err(corrupt)?;
+        let name = String::from_utf8_lossy(&name[..name_size.saturating_sub(1)]).into_owned();
+        let padding = (4 - size % 4) % 4;
+
+        if name == CPIO_TRAILER {
+            return Err(ArchiveError::MemberNotFound);
+        }
+        if normalize(&name) != wanted {
+            io::copy(&mut (&mut stream).take(size + padding), &mut io::sink()).map_err(corrupt)?;
+            continue;
+        }
+
+        let mut found = ArchiveMember {
+            name,
+            mode: mode & 0o7777,
+            uid: uid as u64,
+            gid: gid as u64,
+            size,
+            is_file: mode & S_IFMT == S_IFREG,
+            content: Vec::new(),
+        };
+        if with_content {
+            if !found.is_file {
+                return Err(ArchiveError::NotRegularFile);
+            }
+            found.content = read_content(&mut stream, size, limits)?;
+        }
+        return Ok(found);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// newc archive of `(name, mode, content)` entries
+    fn cpio(entries: &[(&str, u32, &[u8])]) -> Vec<u8> {
+        let mut out = Vec::new();
+        let trailer = (CPIO_TRAILER, 0, &b""[..]);
+        for (index, (name, mode, content)) in entries.iter().chain([&trailer]).enumerate() {
+            let fields = [
+                index as u32 + 1,
+                *mode,
+                0,
+                0,
+                1,
+                0,
+                content.len() as u32,
+                0,
+                0,
+                0,
+                0,
+                name.len() as u32 + 1,
+                0,
+            ];
+            out.extend_from_slice(b"070701");
+            for value in fields {
+                out.extend_from_slice(format!("{:08x}", value).as_bytes());
+            }
+            out.extend_from_slice(name.as_bytes());
+            out.push(0);
+            while out.len() % 4 != 0 {
+                out.push(0);
+            }
+            out.extend_from_slice(content);
+            while out.len() % 4 != 0 {
+                out.push(0);
+            }
+        }
+        out
+    }
+
+    fn tar(entries: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut builder = tar::Builder::new(Vec::new());
+        for (name, content) in entries {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(content.len() as u64);
+            header.set_mode(0o640);
+            header.set_uid(0);
+            header.set_gid(4);
+            header.set_cksum();
+            builder.append_data(&mut header, name, *content).unwrap();
+        }
+        builder.into_inner().unwrap()
+    }
+
+    fn gzip(data: &[u8]) -> Vec<u8> {
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::fast());
+        encoder.write_all(data).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    fn write(dir: &Path, name: &str, data: &[u8]) -> std::path::PathBuf {
+        let path = dir.join(name);
+        std::fs::write(&path, data).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_reads_members_of_every_compression() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = tar(&[("./etc/hosts", b"127.0.0.1 localhost\n")]);
+        let mut xz = Vec::new();
+        lzma_rs::xz_compress(&mut &archive[..], &mut xz).unwrap();
+        let zstd = ruzstd::encoding::compress_to_vec(
+            &archive[..],
+            ruzstd::encoding::CompressionLevel::Fastest,
+        );
+
+        for (name, data) in [
+            ("layer.tar", archive.clone()),
+            ("layer.tar.gz", gzip(&archive)),
+            ("layer.tar.xz", xz),
+            ("layer.tar.zst", zstd),
+        ] {
+            let path = write(dir.path(), name, &data);
+            let member = read_member(&path, "/etc/hosts", true, ArchiveLimits::default())
+                .unwrap_or_else(|e| panic!("{}: {}", name, e));
+            assert_eq!(member.content, b"127.0.0.1 localhost\n", "{}", name);
+            assert_eq!((member.mode, member.gid), (0o640, 4));
+        }
+    }
+
+    #[test]
+    fn test_reads_cpio_and_reports_missing_members() {
+        let dir = tempfile::tempdir().unwrap();
+        let data = cpio(&[
+            ("etc", 0o040755, b""),
+            (
+                "etc/modprobe.d/blacklist.conf",
+                0o100600,
+                b"blacklist usb_storage\n",
+            ),
+        ]);
+        let path = write(dir.path(), "initramfs.img", &gzip(&data));
+
+        let member = read_member(
+            &path,
+            "etc/modprobe.d/blacklist.conf",
+            true,
+            ArchiveLimits::default(),
+        )
+        .unwrap();
+        assert_eq!(member.mode, 0o600);
+        assert_eq!(member.content, b"blacklist usb_storage\n");
+
+        let directory = read_member(&path, "etc", false, ArchiveLimits::default()).unwrap();
+        assert!(!directory.is_file);
+        assert!(matches!(
+            read_member(&path, "etc", true, ArchiveLimits::default()),
+            Err(ArchiveError::NotRegularFile)
+        ));
+        assert!(matches!(
+            read_member(&path, "etc/passwd", true, ArchiveLimits::default()),
+            Err(ArchiveError::MemberNotFound)
+        ));
+        assert!(matches!(
+            read_member(
+                &dir.path().join("missing.img"),
+                "etc",
+                true,
+                ArchiveLimits::default()
+            ),
+            Err(ArchiveError::Io(_))
+        ));
+    }
+
+    #[test]
+    fn test_refuses_nested_archives_and_oversized_data() {
+        let dir = tempfile::tempdir().unwrap();
+        let inner = gzip(&tar(&[("etc/hosts", b"x")]));
+        let outer = tar(&[("layer.tar.gz", &inner), ("big", &[b'a'; 4096])]);
+        let path = write(dir.path(), "image.tar.gz", &gzip(&outer));
+
+        let error = read_member(&path, "layer.tar.gz", true, ArchiveLimits::default())
+            .unwrap_err()
+            .to_string();
+        assert_eq!(
+            error,
+            "member is itself a gzip archive; nested archives are not read"
+        );
+
+        let small = ArchiveLimits {
+            max_decompressed: 256 * 1024,
+            max_member: 1024,
+        };
+        assert!(matches!(
+            read_member(&path, "big", true, small),
+            Err(ArchiveError::TooLarge(_))
+        ));
+        let tiny = ArchiveLimits {
+            max_decompressed: 1024,
+            max_member: 1024,
+        };
+        assert!(matches!(
+            read_member(&path, "big", false, tiny),
+            Err(ArchiveError::TooLarge(_))
+        ));
+
+        let plain = write(dir.path(), "notes.txt", b"just text");
+        assert!(matches!(
+            read_member(&plain, "notes.txt", true, ArchiveLimits::default()),
+            Err(ArchiveError::UnknownFormat)
+        ));
+    }
+}
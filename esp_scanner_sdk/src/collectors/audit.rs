@@ -0,0 +1,522 @@
+//! # Audit Rules Collector
+//!
+//! Gathers Linux audit rules for the `audit_rules` criterion type, from the
+//! kernel (`auditctl -l`) and from the rule files augenrules compiles at boot
+//! (`/etc/audit/rules.d/*.rules`).
+//!
+//! Rule files are read in lexical order and replayed the way auditctl loads
+//! them: `-D` discards every earlier rule, and `-W` or `-d` removes an earlier
+//! watch or syscall rule with the same options. Only rules that survive are
+//! collected, so a rule overridden by a later file does not satisfy a check.
+//!
+//! The object's `source` picks the rule set: `loaded`, `disk`, or `both`
+//! (the default), which keeps only rules that are both loaded and persisted.
+//! Rules are compared by their parsed options, so `-S a -S b -k x` on disk
+//! matches `-S a,b -F key=x` as auditctl prints it.
+//!
+//! When audit is not installed, `auditctl` or the rules directory is missing
+//! and collection fails with [`CollectionError::MissingCapability`], which the
+//! engine reports as an error finding for the criterion alone.
+use esp_scanner_base::execution::{BehaviorHints, FileObservation};
+use esp_scanner_base::strategies::{
+    CollectedData, CollectionError, CommandError, CtnContract, CtnDataCollector, PathPolicy,
+    SystemCommandExecutor,
+};
+use esp_scanner_base::types::common::{RecordData, ResolvedValue};
+use esp_scanner_base::types::execution_context::{ExecutableObject, ExecutableObjectElement};
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+const DEFAULT_RULES_DIR: &str = "/etc/audit/rules.d";
+
+/// Which rules an object is checked against
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RuleSource {
+    Loaded,
+    Disk,
+    Both,
+}
+
+impl RuleSource {
+    fn parse(s: &str) -> Option<Self> {
+        match s {
+            "loaded" => Some(Self::Loaded),
+            "disk" => Some(Self::Disk),
+            "both" => Some(Self::Both),
+            _ => None,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Loaded => "loaded",
+            Self::Disk => "disk",
+            Self::Both => "both",
+        }
+    }
+}
+
+/// One watch (`-w`) or syscall (`-a`) rule
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AuditRule {
+    /// `watch` or `syscall`
+    pub rule_type: String,
+    pub path: String,
+    /// Permission letters in `rwxa` order
+    pub permissions: String,
+    /// Keys joined by commas, as auditctl reports several
+    pub key: String,
+    pub syscalls: Vec<String>,
+    pub arch: String,
+    /// `always` or `never`; empty for watches
+    pub action: String,
+    /// `exit`, `task`, `user` or `exclude`; empty for watches
+    pub list: String,
+    /// The rule as written
+    pub rule: String,
+    /// Rule file it came from; empty for loaded rules
+    pub file: String,
+}
+
+impl AuditRule {
+    /// Whether two rules have the same options, however they were spelled
+    fn matches(&self, other: &AuditRule) -> bool {
+        let sorted = |syscalls: &[String]| {
+            let mut syscalls = syscalls.to_vec();
+            syscalls.sort_unstable();
+            syscalls
+        };
+        self.rule_type == other.rule_type
+            && self.path == other.path
+            && self.permissions == other.permissions
+            && self.key == other.key
+            && self.arch == other.arch
+            && self.action == other.action
+            && self.list == other.list
+            && sorted(&self.syscalls) == sorted(&other.syscalls)
+    }
+
+    fn to_record(&self, source: RuleSource) -> ResolvedValue {
+        ResolvedValue::RecordData(Box::new(RecordData::from_field_pairs(vec![
+            ("source".to_string(), source.as_str().into()),
+            ("type".to_string(), self.rule_type.clone().into()),
+            ("path".to_string(), self.path.clone().into()),
+            ("permissions".to_string(), self.permissions.clone().into()),
+            ("key".to_string(), self.key.clone().into()),
+            ("syscalls".to_string(), self.syscalls.join(",").into()),
+            ("arch".to_string(), self.arch.clone().into()),
+            ("action".to_string(), self.action.clone().into()),
+            ("list".to_string(), self.list.clone().into()),
+            ("rule".to_string(), self.rule.clone().into()),
+            ("file".to_string(), self.file.clone().into()),
+        ])))
+    }
+}
+
+/// What one line of rules asks auditctl to do
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum RuleLine {
+    Add(AuditRule),
+    Remove(AuditRule),
+    DeleteAll,
+}
+
+/// Parse one rule line; control lines such as `-b` or `-e` yield `None`
+fn parse_rule_line(line: &str) -> Option<RuleLine> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+    let tokens: Vec<&str> = line.split_whitespace().collect();
+    if tokens[0] == "-D" {
+        return Some(RuleLine::DeleteAll);
+    }
+
+    let mut rule = AuditRule {
+        rule: line.to_string(),
+        ..Default::default()
+    };
+    let mut remove = false;
+    let mut keys = Vec::new();
+    let mut args = tokens.iter();
+    while let Some(option) = args.next() {
+        match *option {
+            "-w" | "-W" => {
+                rule.rule_type = "watch".to_string();
+                rule.path = args.next()?.to_string();
+                remove = *option == "-W";
+            }
+            "-a" | "-A" | "-d" => {
+                rule.rule_type = "syscall".to_string();
+                let (action, list) = split_action_list(args.next()?);
+                rule.action = action;
+                rule.list = list;
+                remove = *option == "-d";
+            }
+            "-p" => rule.permissions = normalize_permissions(args.next()?),
+            "-k" => keys.push(args.next()?.to_string()),
+            "-S" => rule
+                .syscalls
+                .extend(args.next()?.split(',').map(str::to_string)),
+            "-F" => match args.next()?.split_once('=') {
+                Some(("arch", value)) => rule.arch = value.to_string(),
+                Some(("key", value)) => keys.push(value.to_string()),
+                Some(("path" | "dir", value)) => rule.path = value.to_string(),
+                Some(("perm", value)) => rule.permissions = normalize_permissions(value),
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+    if rule.rule_type.is_empty() {
+        return None;
+    }
+    rule.key = keys.join(",");
+    Some(if remove {
+        RuleLine::Remove(rule)
+    } else {
+        RuleLine::Add(rule)
+    })
+}
+
+/// Split `always,exit` (or `exit,always`) into action and list
+fn split_action_list(value: &str) -> (String, String) {
+    let (first, second) = value.split_once(',').unwrap_or((value, ""));
+    if matches!(first, "always" | "never") {
+        (first.to_string(), second.to_string())
+    } else {
+        (second.to_string(), first.to_string())
+    }
+}
+
+fn normalize_permissions(perms: &str) -> String {
+    "rwxa".chars().filter(|c| perms.contains(*c)).collect()
+}
+
+/// Replay rule lines in order, applying deletions
+fn apply_rules(rules: &mut Vec<AuditRule>, content: &str, file: &str) {
+    for line in content.lines() {
+        match parse_rule_line(line) {
+            Some(RuleLine::Add(mut rule)) => {
+                rule.file = file.to_string();
+                rules.push(rule);
+            }
+            Some(RuleLine::Remove(removed)) => rules.retain(|rule| !rule.matches(&removed)),
+            Some(RuleLine::DeleteAll) => rules.clear(),
+            None => {}
+        }
+    }
+}
+
+/// Rules from `auditctl -l` output, which prints `No rules` when empty
+fn parse_loaded_rules(stdout: &str) -> Vec<AuditRule> {
+    let mut rules = Vec::new();
+    apply_rules(&mut rules, stdout, "");
+    rules
+}
+
+/// Collector for loaded and persisted audit rules
+#[derive(Clone)]
+pub struct AuditRulesCollector {
+    id: String,
+    executor: SystemCommandExecutor,
+    path_policy: Arc<PathPolicy>,
+}
+
+impl AuditRulesCollector {
+    /// Create a collector running `auditctl` through the given executor
+    pub fn new(id: impl Into<String>, executor: SystemCommandExecutor) -> Self {
+        Self {
+            id: id.into(),
+            executor,
+            path_policy: PathPolicy::unrestricted(),
+        }
+    }
+
+    /// Restrict rule file reads to paths the policy permits
+    pub fn with_path_policy(mut self, path_policy: Arc<PathPolicy>) -> Self {
+        self.path_policy = path_policy;
+        self
+    }
+
+    fn loaded_rules(&self, object_id: &str) -> Result<Vec<AuditRule>, CollectionError> {
+        let output = self
+            .executor
+            .execute("auditctl", &["-l"], None)
+            .map_err(|e| match e {
+                CommandError::ProgramNotFound { .. } => CollectionError::MissingCapability {
+                    capability: "auditctl (audit is not installed)".to_string(),
+                },
+                _ => CollectionError::CollectionFailed {
+                    object_id: object_id.to_string(),
+                    reason: format!("auditctl command failed: {}", e),
+                },
+            })?;
+        if output.exit_code != 0 {
+            return Err(CollectionError::CollectionFailed {
+                object_id: object_id.to_string(),
+                reason: format!(
+                    "auditctl -l exited with {}: {}",
+                    output.exit_code,
+                    output.stderr.trim()
+                ),
+            });
+        }
+        Ok(parse_loaded_rules(&output.stdout))
+    }
+
+    fn disk_rules(
+        &self,
+        rules_dir: &str,
+        object_id: &str,
+        data: &mut CollectedData,
+    ) -> Result<Vec<AuditRule>, CollectionError> {
+        let dir = Path::new(rules_dir);
+        self.path_policy
+            .check(dir)
+            .map_err(|reason| CollectionError::PathDenied {
+                object_id: object_id.to_string(),
+                path: rules_dir.to_string(),
+                reason,
+            })?;
+        if !dir.is_dir() {
+            return Err(CollectionError::MissingCapability {
+                capability: format!("audit rules directory '{}'", rules_dir),
+            });
+        }
+
+        let read_failed = |path: &Path, e: std::io::Error| match e.kind() {
+            std::io::ErrorKind::PermissionDenied => CollectionError::AccessDenied {
+                object_id: object_id.to_string(),
+                reason: format!("Cannot read '{}': {}", path.display(), e),
+            },
+            _ => CollectionError::CollectionFailed {
+                object_id: object_id.to_string(),
+                reason: format!("Failed to read '{}': {}", path.display(), e),
+            },
+        };
+
+        let mut files: Vec<_> = fs::read_dir(dir)
+            .map_err(|e| read_failed(dir, e))?
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "rules"))
+            .collect();
+        files.sort();
+
+        let mut rules = Vec::new();
+        for path in files {
+            let content = fs::read_to_string(&path).map_err(|e| read_failed(&path, e))?;
+            let file = path.display().to_string();
+            data.observe_file(FileObservation::new(&file).with_content(content.as_bytes()));
+            apply_rules(&mut rules, &content, &file);
+        }
+        Ok(rules)
+    }
+}
+
+fn object_string(
+    object: &ExecutableObject,
+    field: &str,
+) -> Result<Option<String>, CollectionError> {
+    for element in &object.elements {
+        if let ExecutableObjectElement::Field { name, value, .. } = element {
+            if name == field {
+                return match value {
+                    ResolvedValue::String(s) => Ok(Some(s.clone())),
+                    _ => Err(CollectionError::InvalidObjectConfiguration {
+                        object_id: object.identifier.clone(),
+                        reason: format!("'{}' field must be a string, got {:?}", field, value),
+                    }),
+                };
+            }
+        }
+    }
+    Ok(None)
+}
+
+impl CtnDataCollector for AuditRulesCollector {
+    fn collect_for_ctn_with_hints(
+        &self,
+        object: &ExecutableObject,
+        contract: &CtnContract,
+        _hints: &BehaviorHints,
+    ) -> Result<CollectedData, CollectionError> {
+        let object_id = &object.identifier;
+        let source = match object_string(object, "source")? {
+            Some(value) => RuleSource::parse(&value).ok_or_else(|| {
+                CollectionError::InvalidObjectConfiguration {
+                    object_id: object_id.clone(),
+                    reason: format!("'source' must be loaded, disk or both, got '{}'", value),
+                }
+            })?,
+            None => RuleSource::Both,
+        };
+        let rules_dir =
+            object_string(object, "rules_dir")?.unwrap_or_else(|| DEFAULT_RULES_DIR.to_string());
+        let path_filter = object_string(object, "path")?;
+        let key_filter = object_string(object, "key")?;
+        let syscall_filter = object_string(object, "syscall")?;
+
+        let mut data = CollectedData::new(
+            object_id.clone(),
+            contract.ctn_type.clone(),
+            self.id.clone(),
+        );
+
+        let rules = match source {
+            RuleSource::Loaded => self.loaded_rules(object_id)?,
+            RuleSource::Disk => self.disk_rules(&rules_dir, object_id, &mut data)?,
+            RuleSource::Both => {
+                let loaded = self.loaded_rules(object_id)?;
+                let disk = self.disk_rules(&rules_dir, object_id, &mut data)?;
+                disk.into_iter()
+                    .filter(|rule| loaded.iter().any(|l| l.matches(rule)))
+                    .collect()
+            }
+        };
+
+        let records = rules
+            .iter()
+            .filter(|rule| path_filter.as_ref().map_or(true, |p| &rule.path == p))
+            .filter(|rule| {
+                key_filter
+                    .as_ref()
+                    .map_or(true, |k| rule.key.split(',').any(|r| r == k))
+            })
+            .filter(|rule| {
+                syscall_filter
+                    .as_ref()
+                    .map_or(true, |s| rule.syscalls.contains(s))
+            })
+            .map(|rule| rule.to_record(source))
+            .collect();
+
+        data.add_field(
+            "source".to_string(),
+            ResolvedValue::String(source.as_str().to_string()),
+        );
+        data.add_field("rules".to_string(), ResolvedValue::Collection(records));
+        Ok(data)
+    }
+
+    fn supported_ctn_types(&self) -> Vec<String> {
+        vec!["audit_rules".to_string()]
+    }
+
+    fn validate_ctn_compatibility(&self, contract: &CtnContract) -> Result<(), CollectionError> {
+        if !self.supported_ctn_types().contains(&contract.ctn_type) {
+            return Err(CollectionError::CtnContractValidation {
+                reason: format!("CTN type '{}' not supported", contract.ctn_type),
+            });
+        }
+        Ok(())
+    }
+
+    fn collector_id(&self) -> &str {
+        &self.id
+    }
+
+    fn supports_batch_collection(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(line: &str) -> AuditRule {
+        match parse_rule_line(line) {
+            Some(RuleLine::Add(rule)) => rule,
+            other => panic!("expected a rule, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_watch_and_syscall_rules() {
+        let watch = rule("-w /etc/passwd -p aw -k identity");
+        assert_eq!(watch.rule_type, "watch");
+        assert_eq!(watch.path, "/etc/passwd");
+        assert_eq!(watch.permissions, "wa");
+        assert_eq!(watch.key, "identity");
+
+        let syscall = rule("-a always,exit -F arch=b64 -S adjtimex -S settimeofday -k time-change");
+        assert_eq!(syscall.rule_type, "syscall");
+        assert_eq!(syscall.action, "always");
+        assert_eq!(syscall.list, "exit");
+        assert_eq!(syscall.arch, "b64");
+        assert_eq!(syscall.syscalls, ["adjtimex", "settimeofday"]);
+
+        let printed =
+            rule("-a always,exit -F arch=b64 -S settimeofday,adjtimex -F key=time-change");
+        assert!(printed.matches(&syscall));
+
+        assert_eq!(parse_rule_line("-b 8192"), None);
+        assert_eq!(parse_rule_line("# comment"), None);
+    }
+
+    #[test]
+    fn test_later_lines_remove_earlier_rules() {
+        let mut rules = Vec::new();
+        apply_rules(
+            &mut rules,
+            "-w /etc/shadow -p wa -k identity\n-w /etc/passwd -p wa -k identity\n",
+            "30-identity.rules",
+        );
+        apply_rules(
+            &mut rules,
+            "-W /etc/passwd -p wa -k identity\n-W /etc/group -p wa -k identity\n",
+            "99-local.rules",
+        );
+        assert_eq!(rules.len(), 1);
+        assert_eq!(rules[0].path, "/etc/shadow");
+        assert_eq!(rules[0].file, "30-identity.rules");
+
+        apply_rules(
+            &mut rules,
+            "-D\n-a always,exit -S mount -k mounts\n",
+            "z.rules",
+        );
+        assert_eq!(rules.len(), 1);
+        assert_eq!(rules[0].key, "mounts");
+    }
+
+    #[test]
+    fn test_parse_loaded_rules() {
+        assert!(parse_loaded_rules("No rules\n").is_empty());
+        let rules = parse_loaded_rules(
+            "-w /etc/sudoers -p wa -k scope\n\
+             -a always,exit -F arch=b32 -S mount -F auid>=1000 -F key=mounts\n",
+        );
+        assert_eq!(rules.len(), 2);
+        assert_eq!(rules[1].arch, "b32");
+        assert_eq!(rules[1].key, "mounts");
+    }
+
+    #[test]
+    fn test_disk_rules_read_in_order_and_missing_dir_is_a_capability_error() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("30-identity.rules"),
+            "-w /etc/passwd -p wa -k identity\n",
+        )
+        .unwrap();
+        fs::write(dir.path().join("10-base.rules"), "-D\n-b 8192\n").unwrap();
+        fs::write(dir.path().join("README"), "-w /etc/group -p wa\n").unwrap();
+
+        let collector = AuditRulesCollector::new("audit", SystemCommandExecutor::new());
+        let mut data = CollectedData::new("o".into(), "audit_rules".into(), "audit".into());
+        let rules_dir = dir.path().display().to_string();
+        let rules = collector.disk_rules(&rules_dir, "o", &mut data).unwrap();
+        assert_eq!(rules.len(), 1);
+        assert!(rules[0].file.ends_with("30-identity.rules"));
+
+        let missing = dir.path().join("missing").display().to_string();
+        assert!(matches!(
+            collector.disk_rules(&missing, "o", &mut data),
+            Err(CollectionError::MissingCapability { .. })
+        ));
+    }
+}
@@ -2,22 +2,57 @@
 //!
 //! Executes whitelisted system commands to gather compliance data for:
 //! - RPM package information
-//! - Systemd service status
+//! - dpkg package information
+//! - Systemd service status and queried unit properties
+//! - Every property of a systemd unit, from `systemctl show`
 //! - Sysctl kernel parameters
-//! - SELinux enforcement mode
-use esp_scanner_base::execution::BehaviorHints;
+//! - SELinux enforcement mode, booleans and file contexts
+//! - firewalld zones, services and ports
+//! - Listening TCP and UDP sockets
+//! - Environment variables of files and running processes
+//! - Cron jobs and systemd timers
+//!
+//! SELinux booleans and file contexts are only read when `getenforce`
+//! reports SELinux enabled; otherwise the data carries
+//! `selinux_enabled = false` and nothing else, so the executors can report
+//! the criterion as not applicable. File contexts are read with `stat -c %C`
+//! for paths the [`PathPolicy`] permits.
+//!
+//! Environment variables are read from the files [`environment`] parses or,
+//! for processes found with `pgrep -x`, from `/proc/<pid>/environ`. A process
+//! that exits before its environment is read is skipped.
+//!
+//! Scheduled jobs are parsed by [`scheduled_jobs`] from the cron tables and
+//! from `systemctl show` for each timer `systemctl list-timers` names.
+//!
+//! The engine reuses collected output across criteria of a scan, keyed by
+//! the object's elements, so only objects that would run the same command
+//! with the same arguments share it. Listening sockets and process
+//! environments change too quickly to reuse and are always collected.
+use super::environment::{self, DEFAULT_ENVIRONMENT_PATHS};
+use super::scheduled_jobs::{self, CronJob};
+use super::unit_properties;
+use esp_scanner_base::execution::comparisons::string::glob_matches;
+use esp_scanner_base::execution::{
+    ArgvSlot, BehaviorHints, BehaviorValues, CommandTemplate, ParameterValues,
+};
 use esp_scanner_base::strategies::{
-    CollectedData, CollectionError, CtnContract, CtnDataCollector, SystemCommandExecutor,
+    CollectedData, CollectionError, CommandError, CommandOutput, CtnContract, CtnDataCollector,
+    PathPolicy, SystemCommandExecutor,
 };
-use esp_scanner_base::types::common::ResolvedValue;
+use esp_scanner_base::types::common::{RecordData, ResolvedValue};
 use esp_scanner_base::types::execution_context::{ExecutableObject, ExecutableObjectElement};
 use std::collections::HashMap;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 /// Collector that executes system commands to gather compliance data
 #[derive(Clone)]
 pub struct CommandCollector {
     id: String,
     executor: SystemCommandExecutor,
+    path_policy: Arc<PathPolicy>,
 }
 
 impl CommandCollector {
@@ -26,9 +61,16 @@ impl CommandCollector {
         Self {
             id: id.into(),
             executor,
+            path_policy: PathPolicy::unrestricted(),
         }
     }
 
+    /// Restrict the paths commands are run against to those the policy permits
+    pub fn with_path_policy(mut self, path_policy: Arc<PathPolicy>) -> Self {
+        self.path_policy = path_policy;
+        self
+    }
+
     /// Parse RPM package info from rpm -q output
     /// Format: "package-version-release.arch"
     fn parse_rpm_output(&self, stdout: &str) -> Option<(String, String)> {
@@ -53,18 +95,18 @@ impl CommandCollector {
     }
 
     /// Collect RPM package data for a single package
-    /// Now supports BEHAVIOR hints for timeout configuration
+    /// Honors the `timeout` behavior
     fn collect_rpm_package(
         &self,
         object: &ExecutableObject,
-        hints: &BehaviorHints,
+        behaviors: &BehaviorValues,
     ) -> Result<CollectedData, CollectionError> {
         // Extract package name from object
         let package_name = self.extract_field(object, "package_name")?;
 
         // Check for timeout behavior
-        let timeout = hints
-            .get_parameter_as_int("timeout")
+        let timeout = behaviors
+            .int("timeout")
             .map(|t| std::time::Duration::from_secs(t as u64));
 
         // Execute rpm query with optional timeout
@@ -101,20 +143,113 @@ impl CommandCollector {
         Ok(data)
     }
 
+    /// Run `dpkg-query -W` for `names`, or for every package when empty
+    ///
+    /// dpkg-query exits with 1 when a name matches nothing, which only means
+    /// fewer packages are returned.
+    fn dpkg_query(
+        &self,
+        names: &[&str],
+        timeout: Option<std::time::Duration>,
+        object_id: &str,
+    ) -> Result<Vec<DpkgPackage>, CollectionError> {
+        let mut args = vec!["-W", "-f", DPKG_QUERY_FORMAT];
+        args.extend_from_slice(names);
+        let output = self
+            .executor
+            .execute("dpkg-query", &args, timeout)
+            .map_err(|e| match e {
+                CommandError::ProgramNotFound { .. } => CollectionError::MissingCapability {
+                    capability: "dpkg-query (not a Debian-family host)".to_string(),
+                },
+                _ => CollectionError::CollectionFailed {
+                    object_id: object_id.to_string(),
+                    reason: format!("dpkg-query failed: {}", e),
+                },
+            })?;
+        if output.exit_code > 1 {
+            return Err(CollectionError::CollectionFailed {
+                object_id: object_id.to_string(),
+                reason: format!(
+                    "dpkg-query exited with {}: {}",
+                    output.exit_code,
+                    output.stderr.trim()
+                ),
+            });
+        }
+        Ok(parse_dpkg_query(&output.stdout))
+    }
+
+    /// Collect dpkg package data for a single package
+    /// Honors the `timeout` behavior
+    fn collect_dpkg_package(
+        &self,
+        object: &ExecutableObject,
+        behaviors: &BehaviorValues,
+    ) -> Result<CollectedData, CollectionError> {
+        let package_name = self.extract_field(object, "package_name")?;
+        let timeout = behaviors
+            .int("timeout")
+            .map(|t| std::time::Duration::from_secs(t as u64));
+
+        let packages = self.dpkg_query(&[&package_name], timeout, &object.identifier)?;
+        let installed = packages
+            .iter()
+            .find(|package| package.name == package_name && package.installed);
+        Ok(self.dpkg_package_data(&object.identifier, package_name.clone(), installed))
+    }
+
+    fn dpkg_package_data(
+        &self,
+        object_id: &str,
+        package_name: String,
+        installed: Option<&DpkgPackage>,
+    ) -> CollectedData {
+        let mut data = CollectedData::new(
+            object_id.to_string(),
+            "dpkg_package".to_string(),
+            self.id.clone(),
+        );
+        data.add_field(
+            "package_name".to_string(),
+            ResolvedValue::String(package_name),
+        );
+        data.add_field(
+            "installed".to_string(),
+            ResolvedValue::Boolean(installed.is_some()),
+        );
+        if let Some(package) = installed {
+            data.add_field(
+                "version".to_string(),
+                ResolvedValue::String(package.version.clone()),
+            );
+            data.add_field(
+                "architecture".to_string(),
+                ResolvedValue::String(package.architecture.clone()),
+            );
+        }
+        data
+    }
+
     /// Collect systemd service status
-    /// Now supports BEHAVIOR hints for timeout configuration
+    /// Honors the `timeout` behavior and the `properties` parameter
     fn collect_systemd_service(
         &self,
         object: &ExecutableObject,
-        hints: &BehaviorHints,
+        behaviors: &BehaviorValues,
+        parameters: &ParameterValues,
     ) -> Result<CollectedData, CollectionError> {
         let service_name = self.extract_field(object, "service_name")?;
 
         // Check for timeout behavior
-        let timeout = hints
-            .get_parameter_as_int("timeout")
+        let timeout = behaviors
+            .int("timeout")
             .map(|t| std::time::Duration::from_secs(t as u64));
 
+        if is_template_pattern(&service_name) {
+            return self.collect_systemd_instances(object, &service_name, timeout);
+        }
+
         let mut data = CollectedData::new(
             object.identifier.clone(),
             "systemd_service".to_string(),
@@ -125,6 +260,10 @@ impl CommandCollector {
             "service_name".to_string(),
             ResolvedValue::String(service_name.clone()),
         );
+        data.add_field(
+            "instance".to_string(),
+            ResolvedValue::String(unit_instance(&service_name).to_string()),
+        );
 
         // Check if active
         let active_output = self
@@ -156,21 +295,157 @@ impl CommandCollector {
             ResolvedValue::Boolean(active || enabled),
         );
 
+        if parameters.get("properties").is_some() {
+            let args = systemctl_show_template()
+                .argv(&service_name, parameters)
+                .map_err(|reason| CollectionError::CtnContractValidation { reason })?;
+            let args: Vec<&str> = args.iter().map(String::as_str).collect();
+            let show_output = self
+                .executor
+                .execute("systemctl", &args, timeout)
+                .map_err(|e| CollectionError::CollectionFailed {
+                    object_id: object.identifier.clone(),
+                    reason: format!("systemctl show failed: {}", e),
+                })?;
+            data.add_field(
+                "properties".to_string(),
+                ResolvedValue::RecordData(Box::new(parse_show_properties(&show_output.stdout))),
+            );
+        }
+
+        Ok(data)
+    }
+
+    /// Collect every instance of a template unit glob such as `getty@*.service`
+    ///
+    /// Instances are enumerated in one `systemctl list-units` call and stored
+    /// as records in an `instances` field; the executor evaluates each record
+    /// as its own item. No matching instances yields an empty list, not an
+    /// error.
+    fn collect_systemd_instances(
+        &self,
+        object: &ExecutableObject,
+        pattern: &str,
+        timeout: Option<std::time::Duration>,
+    ) -> Result<CollectedData, CollectionError> {
+        let listing = self
+            .executor
+            .execute(
+                "systemctl",
+                &[
+                    "list-units",
+                    "--all",
+                    "--plain",
+                    "--no-legend",
+                    "--no-pager",
+                    "--type=service",
+                    pattern,
+                ],
+                timeout,
+            )
+            .map_err(|e| CollectionError::CollectionFailed {
+                object_id: object.identifier.clone(),
+                reason: format!("systemctl list-units failed: {}", e),
+            })?;
+
+        let mut instances = Vec::new();
+        for unit in parse_list_units(&listing.stdout) {
+            let enabled_output = self
+                .executor
+                .execute("systemctl", &["is-enabled", &unit.unit], timeout)
+                .map_err(|e| CollectionError::CollectionFailed {
+                    object_id: object.identifier.clone(),
+                    reason: format!("systemctl is-enabled failed: {}", e),
+                })?;
+            let enabled =
+                enabled_output.exit_code == 0 && enabled_output.stdout.trim() == "enabled";
+            instances.push(unit.into_record(enabled));
+        }
+
+        let mut data = CollectedData::new(
+            object.identifier.clone(),
+            "systemd_service".to_string(),
+            self.id.clone(),
+        );
+        data.add_field(
+            "service_name".to_string(),
+            ResolvedValue::String(pattern.to_string()),
+        );
+        data.add_field(
+            "instances".to_string(),
+            ResolvedValue::Collection(instances),
+        );
+        Ok(data)
+    }
+
+    /// Collect every property of a unit from `systemctl show`
+    /// Honors the `timeout` behavior
+    ///
+    /// A unit systemd has no definition for is reported with
+    /// `found = false` rather than as an error, so `TEST none ...` can assert
+    /// that it does not exist.
+    fn collect_systemd_unit_property(
+        &self,
+        object: &ExecutableObject,
+        behaviors: &BehaviorValues,
+    ) -> Result<CollectedData, CollectionError> {
+        let unit_name = self.extract_field(object, "unit_name")?;
+        let timeout = behaviors
+            .int("timeout")
+            .map(|t| std::time::Duration::from_secs(t as u64));
+
+        let output = self
+            .executor
+            .execute("systemctl", &["show", &unit_name, "--no-pager"], timeout)
+            .map_err(|e| CollectionError::CollectionFailed {
+                object_id: object.identifier.clone(),
+                reason: format!("systemctl show failed: {}", e),
+            })?;
+        if output.exit_code != 0 {
+            return Err(CollectionError::CollectionFailed {
+                object_id: object.identifier.clone(),
+                reason: format!(
+                    "systemctl show exited with {}: {}",
+                    output.exit_code,
+                    output.stderr.trim()
+                ),
+            });
+        }
+
+        let properties = unit_properties::parse(&output.stdout);
+        let found = properties
+            .get("LoadState")
+            .and_then(|state| state.as_str())
+            .is_some_and(|state| state != unit_properties::NOT_FOUND_LOAD_STATE);
+
+        let mut data = CollectedData::new(
+            object.identifier.clone(),
+            "systemd_unit_property".to_string(),
+            self.id.clone(),
+        );
+        data.add_field("unit_name".to_string(), ResolvedValue::String(unit_name));
+        data.add_field("found".to_string(), ResolvedValue::Boolean(found));
+        if found {
+            data.add_field(
+                "properties".to_string(),
+                ResolvedValue::RecordData(Box::new(RecordData::from_json_value(properties))),
+            );
+        }
         Ok(data)
     }
 
     /// Collect sysctl kernel parameter value
-    /// Now supports BEHAVIOR hints for timeout configuration
+    /// Honors the `timeout` behavior
     fn collect_sysctl_parameter(
         &self,
         object: &ExecutableObject,
-        hints: &BehaviorHints,
+        behaviors: &BehaviorValues,
     ) -> Result<CollectedData, CollectionError> {
         let parameter_name = self.extract_field(object, "parameter_name")?;
 
         // Check for timeout behavior
-        let timeout = hints
-            .get_parameter_as_int("timeout")
+        let timeout = behaviors
+            .int("timeout")
             .map(|t| std::time::Duration::from_secs(t as u64));
 
         let mut data = CollectedData::new(
@@ -207,15 +482,15 @@ impl CommandCollector {
     }
 
     /// Collect SELinux enforcement status
-    /// Now supports BEHAVIOR hints for timeout configuration
+    /// Honors the `timeout` behavior
     fn collect_selinux_status(
         &self,
         object: &ExecutableObject,
-        hints: &BehaviorHints,
+        behaviors: &BehaviorValues,
     ) -> Result<CollectedData, CollectionError> {
         // Check for timeout behavior
-        let timeout = hints
-            .get_parameter_as_int("timeout")
+        let timeout = behaviors
+            .int("timeout")
             .map(|t| std::time::Duration::from_secs(t as u64));
 
         let mut data = CollectedData::new(
@@ -244,6 +519,834 @@ impl CommandCollector {
         Ok(data)
     }
 
+    /// Whether SELinux is enabled, according to `getenforce`
+    ///
+    /// A host without `getenforce` has no SELinux userland and counts as
+    /// disabled, as does one where it fails or prints `Disabled`.
+    fn selinux_enabled(
+        &self,
+        object_id: &str,
+        timeout: Option<std::time::Duration>,
+    ) -> Result<bool, CollectionError> {
+        match self.executor.execute("getenforce", &[], timeout) {
+            Ok(output) => Ok(output.exit_code == 0 && output.stdout.trim() != "Disabled"),
+            Err(CommandError::ProgramNotFound { .. }) => Ok(false),
+            Err(e) => Err(CollectionError::CollectionFailed {
+                object_id: object_id.to_string(),
+                reason: format!("getenforce failed: {}", e),
+            }),
+        }
+    }
+
+    /// Every SELinux boolean from `getsebool -a`, or `None` when SELinux is
+    /// disabled
+    fn selinux_booleans(
+        &self,
+        object_id: &str,
+        timeout: Option<std::time::Duration>,
+    ) -> Result<Option<HashMap<String, bool>>, CollectionError> {
+        if !self.selinux_enabled(object_id, timeout)? {
+            return Ok(None);
+        }
+        let output = self
+            .executor
+            .execute("getsebool", &["-a"], timeout)
+            .map_err(|e| match e {
+                CommandError::ProgramNotFound { .. } => CollectionError::MissingCapability {
+                    capability: "getsebool (libselinux-utils is not installed)".to_string(),
+                },
+                _ => CollectionError::CollectionFailed {
+                    object_id: object_id.to_string(),
+                    reason: format!("getsebool failed: {}", e),
+                },
+            })?;
+        if output.exit_code != 0 {
+            return Err(CollectionError::CollectionFailed {
+                object_id: object_id.to_string(),
+                reason: format!(
+                    "getsebool exited with {}: {}",
+                    output.exit_code,
+                    output.stderr.trim()
+                ),
+            });
+        }
+        Ok(Some(parse_getsebool(&output.stdout)))
+    }
+
+    fn selinux_boolean_data(
+        &self,
+        object_id: &str,
+        boolean_name: String,
+        booleans: Option<&HashMap<String, bool>>,
+    ) -> CollectedData {
+        let mut data = CollectedData::new(
+            object_id.to_string(),
+            "selinux_boolean".to_string(),
+            self.id.clone(),
+        );
+        let value = booleans.and_then(|booleans| booleans.get(&boolean_name).copied());
+        data.add_field(
+            "boolean_name".to_string(),
+            ResolvedValue::String(boolean_name),
+        );
+        data.add_field(
+            "selinux_enabled".to_string(),
+            ResolvedValue::Boolean(booleans.is_some()),
+        );
+        if booleans.is_some() {
+            data.add_field("found".to_string(), ResolvedValue::Boolean(value.is_some()));
+        }
+        if let Some(value) = value {
+            data.add_field("value".to_string(), ResolvedValue::Boolean(value));
+        }
+        data
+    }
+
+    /// Collect the current value of the SELinux boolean named `boolean_name`
+    /// Honors the `timeout` behavior
+    fn collect_selinux_boolean(
+        &self,
+        object: &ExecutableObject,
+        behaviors: &BehaviorValues,
+    ) -> Result<CollectedData, CollectionError> {
+        let boolean_name = self.extract_field(object, "boolean_name")?;
+        let timeout = behaviors
+            .int("timeout")
+            .map(|t| std::time::Duration::from_secs(t as u64));
+
+        let booleans = self.selinux_booleans(&object.identifier, timeout)?;
+        Ok(self.selinux_boolean_data(&object.identifier, boolean_name, booleans.as_ref()))
+    }
+
+    /// Collect the SELinux security context of the object's `path`
+    /// Honors the `timeout` behavior
+    ///
+    /// Symlinks are not followed, matching `ls -Zd`. A file without a label
+    /// has `exists` set but no context fields.
+    fn collect_selinux_file_context(
+        &self,
+        object: &ExecutableObject,
+        behaviors: &BehaviorValues,
+    ) -> Result<CollectedData, CollectionError> {
+        let path = self.extract_field(object, "path")?;
+        self.path_policy
+            .check(Path::new(&path))
+            .map_err(|reason| CollectionError::PathDenied {
+                object_id: object.identifier.clone(),
+                path: path.clone(),
+                reason,
+            })?;
+        let timeout = behaviors
+            .int("timeout")
+            .map(|t| std::time::Duration::from_secs(t as u64));
+
+        let mut data = CollectedData::new(
+            object.identifier.clone(),
+            "selinux_file_context".to_string(),
+            self.id.clone(),
+        );
+        data.add_field("path".to_string(), ResolvedValue::String(path.clone()));
+
+        let enabled = self.selinux_enabled(&object.identifier, timeout)?;
+        data.add_field(
+            "selinux_enabled".to_string(),
+            ResolvedValue::Boolean(enabled),
+        );
+        if !enabled {
+            return Ok(data);
+        }
+
+        let exists = Path::new(&path).symlink_metadata().is_ok();
+        data.add_field("exists".to_string(), ResolvedValue::Boolean(exists));
+        if !exists {
+            return Ok(data);
+        }
+
+        let output = self
+            .executor
+            .execute("stat", &["-c", "%C", "--", &path], timeout)
+            .map_err(|e| CollectionError::CollectionFailed {
+                object_id: object.identifier.clone(),
+                reason: format!("stat failed: {}", e),
+            })?;
+        if output.exit_code != 0 {
+            return Err(CollectionError::CollectionFailed {
+                object_id: object.identifier.clone(),
+                reason: format!(
+                    "stat exited with {}: {}",
+                    output.exit_code,
+                    output.stderr.trim()
+                ),
+            });
+        }
+
+        let context = output.stdout.trim();
+        match parse_selinux_context(context) {
+            Some([user, role, context_type, level]) => {
+                data.add_field(
+                    "context".to_string(),
+                    ResolvedValue::String(context.to_string()),
+                );
+                data.add_field("user".to_string(), ResolvedValue::String(user));
+                data.add_field("role".to_string(), ResolvedValue::String(role));
+                data.add_field("type".to_string(), ResolvedValue::String(context_type));
+                data.add_field("level".to_string(), ResolvedValue::String(level));
+            }
+            None => data.add_warning(format!("'{}' has no SELinux label", path)),
+        }
+        Ok(data)
+    }
+
+    /// Collect firewalld zones whose name matches the object's `zone`
+    /// Honors the `timeout` behavior
+    ///
+    /// When firewalld is not running the `zones` list is empty and `running`
+    /// is false, so policies can assert that no zone exists. A missing
+    /// `firewall-cmd` means firewalld is not installed and fails collection
+    /// with a missing capability.
+    fn collect_firewalld_zone(
+        &self,
+        object: &ExecutableObject,
+        behaviors: &BehaviorValues,
+    ) -> Result<CollectedData, CollectionError> {
+        let zone_pattern = self.extract_field(object, "zone")?;
+
+        // Check for timeout behavior
+        let timeout = behaviors
+            .int("timeout")
+            .map(|t| std::time::Duration::from_secs(t as u64));
+
+        let mut data = CollectedData::new(
+            object.identifier.clone(),
+            "firewalld_zone".to_string(),
+            self.id.clone(),
+        );
+        data.add_field(
+            "zone".to_string(),
+            ResolvedValue::String(zone_pattern.clone()),
+        );
+
+        // firewall-cmd --state exits non-zero with "not running"
+        let state = self.firewall_cmd(object, &["--state"], timeout)?;
+        let running = state.exit_code == 0 && state.stdout.trim() == "running";
+        data.add_field("running".to_string(), ResolvedValue::Boolean(running));
+
+        let mut zones = Vec::new();
+        if running {
+            let default_zone = self
+                .firewall_query(object, &["--get-default-zone"], timeout)?
+                .stdout
+                .trim()
+                .to_string();
+            let listing = self.firewall_query(object, &["--list-all-zones"], timeout)?;
+            zones = parse_firewalld_zones(&listing.stdout)
+                .into_iter()
+                .filter(|zone| glob_matches(&zone_pattern, &zone.name).unwrap_or(false))
+                .map(|zone| zone.into_record(&default_zone))
+                .collect();
+        }
+        data.add_field("zones".to_string(), ResolvedValue::Collection(zones));
+
+        Ok(data)
+    }
+
+    /// Run `firewall-cmd`, treating a missing binary as firewalld not installed
+    fn firewall_cmd(
+        &self,
+        object: &ExecutableObject,
+        args: &[&str],
+        timeout: Option<std::time::Duration>,
+    ) -> Result<CommandOutput, CollectionError> {
+        self.executor
+            .execute("firewall-cmd", args, timeout)
+            .map_err(|e| match e {
+                CommandError::ProgramNotFound { .. } => CollectionError::MissingCapability {
+                    capability: "firewall-cmd (firewalld is not installed)".to_string(),
+                },
+                _ => CollectionError::CollectionFailed {
+                    object_id: object.identifier.clone(),
+                    reason: format!("firewall-cmd {} failed: {}", args.join(" "), e),
+                },
+            })
+    }
+
+    /// Run a `firewall-cmd` query that must succeed
+    fn firewall_query(
+        &self,
+        object: &ExecutableObject,
+        args: &[&str],
+        timeout: Option<std::time::Duration>,
+    ) -> Result<CommandOutput, CollectionError> {
+        let output = self.firewall_cmd(object, args, timeout)?;
+        if output.exit_code != 0 {
+            return Err(CollectionError::CollectionFailed {
+                object_id: object.identifier.clone(),
+                reason: format!(
+                    "firewall-cmd {} exited with {}: {}",
+                    args.join(" "),
+                    output.exit_code,
+                    output.stderr.trim()
+                ),
+            });
+        }
+        Ok(output)
+    }
+
+    /// Collect listening sockets from `ss -tulpn` matching the object's filters
+    /// Honors the `timeout` behavior
+    ///
+    /// Every filter is optional: `port`, `protocol`, and `local_address` and
+    /// `process_name` globs. Processes owned by other users are only named
+    /// when running as root; their listeners are still collected, with an
+    /// empty `process_name` and a warning.
+    fn collect_listening_port(
+        &self,
+        object: &ExecutableObject,
+        behaviors: &BehaviorValues,
+    ) -> Result<CollectedData, CollectionError> {
+        let filter = ListenerFilter::from_object(object)?;
+        let timeout = behaviors
+            .int("timeout")
+            .map(|t| std::time::Duration::from_secs(t as u64));
+
+        let output = self
+            .executor
+            .execute("ss", &["-tulpn"], timeout)
+            .map_err(|e| match e {
+                CommandError::ProgramNotFound { .. } => CollectionError::MissingCapability {
+                    capability: "ss (iproute2 is not installed)".to_string(),
+                },
+                _ => CollectionError::CollectionFailed {
+                    object_id: object.identifier.clone(),
+                    reason: format!("ss failed: {}", e),
+                },
+            })?;
+        if output.exit_code != 0 {
+            return Err(CollectionError::CollectionFailed {
+                object_id: object.identifier.clone(),
+                reason: format!(
+                    "ss exited with {}: {}",
+                    output.exit_code,
+                    output.stderr.trim()
+                ),
+            });
+        }
+
+        let mut data = CollectedData::new(
+            object.identifier.clone(),
+            "listening_port".to_string(),
+            self.id.clone(),
+        );
+
+        let listeners = parse_ss_listeners(&output.stdout);
+        let hidden = listeners
+            .iter()
+            .filter(|listener| listener.process_name.is_empty())
+            .count();
+        if hidden > 0 {
+            data.add_warning(format!(
+                "process not visible for {} of {} listeners; run as root to see every process",
+                hidden,
+                listeners.len()
+            ));
+        }
+
+        let listeners = listeners
+            .into_iter()
+            .filter(|listener| filter.matches(listener))
+            .map(Listener::into_record)
+            .collect();
+        data.add_field(
+            "listeners".to_string(),
+            ResolvedValue::Collection(listeners),
+        );
+        Ok(data)
+    }
+
+    /// Collect where an environment variable is set
+    /// Honors the `timeout` behavior
+    ///
+    /// With `source file` (the default), the object's comma-separated `paths`
+    /// or `/etc/environment` and `/etc/profile.d` are read; with
+    /// `source process`, every process named `process_name`. Each file or
+    /// process that sets the variable becomes one record.
+    fn collect_environment_variable(
+        &self,
+        object: &ExecutableObject,
+        behaviors: &BehaviorValues,
+    ) -> Result<CollectedData, CollectionError> {
+        let variable_name = self.extract_field(object, "variable_name")?;
+        let source = self
+            .extract_optional_field(object, "source")?
+            .unwrap_or_else(|| "file".to_string());
+
+        let mut data = CollectedData::new(
+            object.identifier.clone(),
+            "environment_variable".to_string(),
+            self.id.clone(),
+        );
+
+        let variables = match source.as_str() {
+            "file" => {
+                let paths = match self.extract_optional_field(object, "paths")? {
+                    Some(paths) => paths
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|path| !path.is_empty())
+                        .map(str::to_string)
+                        .collect(),
+                    None => DEFAULT_ENVIRONMENT_PATHS
+                        .iter()
+                        .map(|path| path.to_string())
+                        .collect::<Vec<_>>(),
+                };
+                self.file_environment(&paths, &variable_name, &mut data)
+            }
+            "process" => {
+                let process_name = self.extract_field(object, "process_name")?;
+                let timeout = behaviors
+                    .int("timeout")
+                    .map(|t| std::time::Duration::from_secs(t as u64));
+                let pids = self.process_ids(&process_name, &object.identifier, timeout)?;
+                self.process_environment(&pids, &process_name, &variable_name, &mut data)
+            }
+            other => {
+                return Err(CollectionError::InvalidObjectConfiguration {
+                    object_id: object.identifier.clone(),
+                    reason: format!("unknown source '{}'; expected file or process", other),
+                })
+            }
+        };
+
+        data.add_field(
+            "variables".to_string(),
+            ResolvedValue::Collection(variables),
+        );
+        Ok(data)
+    }
+
+    /// Records for the files under `paths` that assign `variable_name`
+    fn file_environment(
+        &self,
+        paths: &[String],
+        variable_name: &str,
+        data: &mut CollectedData,
+    ) -> Vec<ResolvedValue> {
+        let mut records = Vec::new();
+        for path in paths {
+            let path = Path::new(path);
+            if !path.exists() || !self.path_policy.permits(path) {
+                continue;
+            }
+            for file in environment::environment_files(path) {
+                if !self.path_policy.permits(&file) {
+                    continue;
+                }
+                let content = match std::fs::read_to_string(&file) {
+                    Ok(content) => content,
+                    Err(e) => {
+                        data.add_warning(format!("cannot read '{}': {}", file.display(), e));
+                        continue;
+                    }
+                };
+                if let Some(value) = environment::last_assignment(&content, variable_name) {
+                    records.push(environment_record(
+                        variable_name,
+                        value,
+                        &file.display().to_string(),
+                        "",
+                    ));
+                }
+            }
+        }
+        records
+    }
+
+    /// Process IDs of processes named exactly `process_name`
+    fn process_ids(
+        &self,
+        process_name: &str,
+        object_id: &str,
+        timeout: Option<std::time::Duration>,
+    ) -> Result<Vec<u32>, CollectionError> {
+        let output = self
+            .executor
+            .execute("pgrep", &["-x", process_name], timeout)
+            .map_err(|e| match e {
+                CommandError::ProgramNotFound { .. } => CollectionError::MissingCapability {
+                    capability: "pgrep (procps is not installed)".to_string(),
+                },
+                _ => CollectionError::CollectionFailed {
+                    object_id: object_id.to_string(),
+                    reason: format!("pgrep failed: {}", e),
+                },
+            })?;
+        // pgrep exits with 1 when nothing matched
+        match output.exit_code {
+            0 | 1 => Ok(output
+                .stdout
+                .lines()
+                .filter_map(|line| line.trim().parse().ok())
+                .collect()),
+            code => Err(CollectionError::CollectionFailed {
+                object_id: object_id.to_string(),
+                reason: format!("pgrep exited with {}: {}", code, output.stderr.trim()),
+            }),
+        }
+    }
+
+    /// Records for the processes among `pids` with `variable_name` set
+    fn process_environment(
+        &self,
+        pids: &[u32],
+        process_name: &str,
+        variable_name: &str,
+        data: &mut CollectedData,
+    ) -> Vec<ResolvedValue> {
+        let mut records = Vec::new();
+        let mut hidden = 0;
+        for pid in pids {
+            let environ = match std::fs::read(format!("/proc/{}/environ", pid)) {
+                Ok(environ) => environ,
+                Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => {
+                    hidden += 1;
+                    continue;
+                }
+                Err(e) => {
+                    // The process exited between pgrep and the read
+                    esp_compiler::log_debug!(
+                        "Skipping process that exited mid-scan",
+                        "pid" => pid,
+                        "process_name" => process_name,
+                        "error" => e.to_string().as_str()
+                    );
+                    continue;
+                }
+            };
+            let value = environment::parse_environ(&environ)
+                .into_iter()
+                .rev()
+                .find(|(name, _)| name == variable_name)
+                .map(|(_, value)| value);
+            if let Some(value) = value {
+                records.push(environment_record(
+                    variable_name,
+                    value,
+                    &format!("pid {}", pid),
+                    process_name,
+                ));
+            }
+        }
+        if hidden > 0 {
+            data.add_warning(format!(
+                "environment not readable for {} of {} '{}' processes; run as root to see every process",
+                hidden,
+                pids.len(),
+                process_name
+            ));
+        }
+        records
+    }
+
+    /// Collect cron jobs and systemd timers matching the object's filters
+    /// Honors the `timeout` behavior
+    ///
+    /// Jobs come from `/etc/crontab`, `/etc/cron.d`, the `run-parts`
+    /// directories, every local user's crontab (`crontab -l -u`) and
+    /// `systemctl list-timers --all`. Every filter is optional: `source`,
+    /// `user` and `command` globs. A user without a crontab has no jobs, and
+    /// a host without cron or systemd has none from that source, with a
+    /// warning.
+    fn collect_scheduled_job(
+        &self,
+        object: &ExecutableObject,
+        behaviors: &BehaviorValues,
+    ) -> Result<CollectedData, CollectionError> {
+        let filters = [
+            ("source", self.extract_optional_field(object, "source")?),
+            ("user", self.extract_optional_field(object, "user")?),
+            ("command", self.extract_optional_field(object, "command")?),
+        ];
+        let timeout = behaviors
+            .int("timeout")
+            .map(|t| std::time::Duration::from_secs(t as u64));
+
+        let mut data = CollectedData::new(
+            object.identifier.clone(),
+            "scheduled_job".to_string(),
+            self.id.clone(),
+        );
+
+        let users = self
+            .read_permitted(Path::new("/etc/passwd"), &mut data)
+            .map(|passwd| super::account::local_usernames(&passwd))
+            .unwrap_or_default();
+
+        let mut jobs = self.system_cron_jobs(&mut data);
+        jobs.extend(self.user_cron_jobs(&users, &object.identifier, timeout, &mut data)?);
+        jobs.extend(self.timer_jobs(&object.identifier, timeout, &mut data)?);
+
+        let jobs = jobs
+            .into_iter()
+            .filter(|job| {
+                filters.iter().all(|(name, pattern)| {
+                    pattern.as_ref().map_or(true, |pattern| {
+                        glob_matches(pattern, job.field(name)).unwrap_or(false)
+                    })
+                })
+            })
+            .map(|job| {
+                let user_exists = users.contains(&job.user);
+                job.into_record(user_exists)
+            })
+            .collect();
+        data.add_field("jobs".to_string(), ResolvedValue::Collection(jobs));
+        Ok(data)
+    }
+
+    /// Content of a file the path policy permits; unreadable files warn
+    fn read_permitted(&self, path: &Path, data: &mut CollectedData) -> Option<String> {
+        if !path.exists() || !self.path_policy.permits(path) {
+            return None;
+        }
+        match std::fs::read_to_string(path) {
+            Ok(content) => Some(content),
+            Err(e) => {
+                data.add_warning(format!("cannot read '{}': {}", path.display(), e));
+                None
+            }
+        }
+    }
+
+    /// Jobs of `/etc/crontab`, `/etc/cron.d` and the `run-parts` directories
+    fn system_cron_jobs(&self, data: &mut CollectedData) -> Vec<ScheduledJob> {
+        let mut tables = vec![PathBuf::from(scheduled_jobs::SYSTEM_CRONTAB)];
+        tables.extend(directory_entries(Path::new(scheduled_jobs::CRON_D_DIR)));
+
+        let mut jobs = Vec::new();
+        for table in tables {
+            let Some(content) = self.read_permitted(&table, data) else {
+                continue;
+            };
+            let source = table.display().to_string();
+            for job in scheduled_jobs::parse_crontab(&content, true) {
+                jobs.push(ScheduledJob::from_cron(&source, job, ""));
+            }
+        }
+
+        // Each executable script runs as root on the directory's schedule
+        for (dir, alias) in scheduled_jobs::CRON_PERIOD_DIRS {
+            for script in directory_entries(Path::new(dir)) {
+                if !self.path_policy.permits(&script) {
+                    continue;
+                }
+                let Ok(metadata) = std::fs::metadata(&script) else {
+                    continue;
+                };
+                let schedule = scheduled_jobs::normalize_alias(alias).unwrap_or_default();
+                jobs.push(ScheduledJob {
+                    source: dir.to_string(),
+                    line: 0,
+                    max_interval_hours: scheduled_jobs::cron_max_interval_hours(&schedule),
+                    schedule,
+                    command: script.display().to_string(),
+                    user: "root".to_string(),
+                    enabled: metadata.permissions().mode() & 0o111 != 0,
+                });
+            }
+        }
+        jobs
+    }
+
+    /// Jobs of each user's crontab
+    ///
+    /// With a readable spool directory only the users with a spool file are
+    /// asked; spool files of users no longer in `/etc/passwd` are read
+    /// directly, since `crontab -u` refuses unknown users.
+    fn user_cron_jobs(
+        &self,
+        users: &[String],
+        object_id: &str,
+        timeout: Option<std::time::Duration>,
+        data: &mut CollectedData,
+    ) -> Result<Vec<ScheduledJob>, CollectionError> {
+        let spooled: Vec<PathBuf> = scheduled_jobs::CRON_SPOOL_DIRS
+            .iter()
+            .filter(|dir| self.path_policy.permits(Path::new(dir)))
+            .flat_map(|dir| directory_entries(Path::new(dir)))
+            .filter(|path| path.is_file())
+            .collect();
+        let spool_readable = scheduled_jobs::CRON_SPOOL_DIRS
+            .iter()
+            .any(|dir| std::fs::read_dir(dir).is_ok());
+        let spool_user = |path: &PathBuf| {
+            path.file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default()
+        };
+
+        let mut jobs = Vec::new();
+        for path in &spooled {
+            let user = spool_user(path);
+            if users.contains(&user) {
+                continue;
+            }
+            if let Some(content) = self.read_permitted(path, data) {
+                let source = path.display().to_string();
+                for job in scheduled_jobs::parse_crontab(&content, false) {
+                    jobs.push(ScheduledJob::from_cron(&source, job, &user));
+                }
+            }
+        }
+
+        for user in users {
+            if spool_readable && !spooled.iter().any(|path| spool_user(path) == *user) {
+                continue;
+            }
+            let output = match self
+                .executor
+                .execute("crontab", &["-l", "-u", user], timeout)
+            {
+                Ok(output) => output,
+                Err(CommandError::ProgramNotFound { .. }) => {
+                    data.add_warning(
+                        "crontab is not installed; per-user crontabs were not read".to_string(),
+                    );
+                    break;
+                }
+                Err(e) => {
+                    return Err(CollectionError::CollectionFailed {
+                        object_id: object_id.to_string(),
+                        reason: format!("crontab -l failed: {}", e),
+                    })
+                }
+            };
+            match output.exit_code {
+                0 => {
+                    let source = format!("crontab -u {}", user);
+                    for job in scheduled_jobs::parse_crontab(&output.stdout, false) {
+                        jobs.push(ScheduledJob::from_cron(&source, job, user));
+                    }
+                }
+                // `no crontab for <user>`, or refused for lack of privilege
+                1 if output.stderr.contains("no crontab") => {}
+                1 => data.add_warning(format!(
+                    "crontab of '{}' not readable: {}; run as root to see every crontab",
+                    user,
+                    output.stderr.trim()
+                )),
+                code => {
+                    return Err(CollectionError::CollectionFailed {
+                        object_id: object_id.to_string(),
+                        reason: format!(
+                            "crontab -l -u {} exited with {}: {}",
+                            user,
+                            code,
+                            output.stderr.trim()
+                        ),
+                    })
+                }
+            }
+        }
+        Ok(jobs)
+    }
+
+    /// A job for every systemd timer, running the unit it activates
+    fn timer_jobs(
+        &self,
+        object_id: &str,
+        timeout: Option<std::time::Duration>,
+        data: &mut CollectedData,
+    ) -> Result<Vec<ScheduledJob>, CollectionError> {
+        let output = match self.executor.execute(
+            "systemctl",
+            &["list-timers", "--all", "--no-pager", "--no-legend"],
+            timeout,
+        ) {
+            Ok(output) if output.exit_code == 0 => output,
+            Ok(output) => {
+                data.add_warning(format!(
+                    "systemd timers not listed: {}",
+                    output.stderr.trim()
+                ));
+                return Ok(Vec::new());
+            }
+            Err(CommandError::ProgramNotFound { .. }) => {
+                data.add_warning("systemctl is not installed; no timers were read".to_string());
+                return Ok(Vec::new());
+            }
+            Err(e) => {
+                return Err(CollectionError::CollectionFailed {
+                    object_id: object_id.to_string(),
+                    reason: format!("systemctl list-timers failed: {}", e),
+                })
+            }
+        };
+
+        let mut jobs = Vec::new();
+        for (timer, activates) in scheduled_jobs::parse_list_timers(&output.stdout) {
+            let show = self
+                .executor
+                .execute(
+                    "systemctl",
+                    &[
+                        "show",
+                        &timer,
+                        &activates,
+                        "--property=TimersCalendar,TimersMonotonic,UnitFileState,ExecStart,User",
+                        "--no-pager",
+                    ],
+                    timeout,
+                )
+                .map_err(|e| CollectionError::CollectionFailed {
+                    object_id: object_id.to_string(),
+                    reason: format!("systemctl show failed: {}", e),
+                })?;
+            // One block per unit, timer first
+            let (timer_block, unit_block) = show
+                .stdout
+                .split_once("\n\n")
+                .unwrap_or((show.stdout.as_str(), ""));
+
+            let property = |block: &str, name: &str| {
+                block
+                    .lines()
+                    .find_map(|line| line.strip_prefix(name)?.strip_prefix('='))
+                    .unwrap_or_default()
+                    .to_string()
+            };
+            let schedules = scheduled_jobs::timer_schedules(timer_block);
+            let command = match &unit_properties::parse(unit_block)["ExecStart"] {
+                serde_json::Value::Array(commands) => commands
+                    .iter()
+                    .filter_map(|command| command.as_str())
+                    .collect::<Vec<_>>()
+                    .join("; "),
+                _ => String::new(),
+            };
+            let user = match property(unit_block, "User") {
+                user if user.is_empty() => "root".to_string(),
+                user => user,
+            };
+            jobs.push(ScheduledJob {
+                source: timer,
+                line: 0,
+                max_interval_hours: schedules
+                    .iter()
+                    .filter_map(|schedule| scheduled_jobs::timer_max_interval_hours(schedule))
+                    .min(),
+                schedule: schedules.join("; "),
+                command,
+                user,
+                enabled: matches!(
+                    property(timer_block, "UnitFileState").as_str(),
+                    "enabled" | "enabled-runtime"
+                ),
+            });
+        }
+        Ok(jobs)
+    }
+
     /// Extract a required string field from object
     fn extract_field(
         &self,
@@ -274,6 +1377,466 @@ impl CommandCollector {
             reason: format!("Missing required field '{}'", field_name),
         })
     }
+
+    /// Extract an optional string field from object
+    fn extract_optional_field(
+        &self,
+        object: &ExecutableObject,
+        field_name: &str,
+    ) -> Result<Option<String>, CollectionError> {
+        let present = object.elements.iter().any(|element| {
+            matches!(element, ExecutableObjectElement::Field { name, .. } if name == field_name)
+        });
+        if present {
+            self.extract_field(object, field_name).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+/// SELinux boolean values from `getsebool -a` lines such as
+/// `selinuxuser_execheap --> off`
+///
+/// A pending value, shown as `off pending: on`, is ignored.
+fn parse_getsebool(stdout: &str) -> HashMap<String, bool> {
+    stdout
+        .lines()
+        .filter_map(|line| {
+            let (name, value) = line.split_once("-->")?;
+            let value = value.split_whitespace().next()?;
+            Some((name.trim().to_string(), value == "on"))
+        })
+        .collect()
+}
+
+/// Split a security context into user, role, type and level
+///
+/// The level keeps any category colons (`s0:c0.c1023`) and is empty under a
+/// policy without MLS. `?` means the file is unlabeled.
+fn parse_selinux_context(context: &str) -> Option<[String; 4]> {
+    let mut parts = context.splitn(4, ':');
+    let user = parts
+        .next()
+        .filter(|user| !user.is_empty() && *user != "?")?;
+    let role = parts.next()?;
+    let context_type = parts.next()?;
+    let level = parts.next().unwrap_or("");
+    Some([
+        user.to_string(),
+        role.to_string(),
+        context_type.to_string(),
+        level.to_string(),
+    ])
+}
+
+/// One listening socket from `ss -tulpn`
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Listener {
+    protocol: String,
+    state: String,
+    local_address: String,
+    port: i64,
+    /// Empty when the owning process is not visible to the scanner
+    process_name: String,
+}
+
+impl Listener {
+    fn into_record(self) -> ResolvedValue {
+        let record = RecordData::from_field_pairs(vec![
+            ("protocol".to_string(), self.protocol.into()),
+            ("state".to_string(), self.state.into()),
+            ("local_address".to_string(), self.local_address.into()),
+            ("port".to_string(), self.port.into()),
+            ("process_name".to_string(), self.process_name.into()),
+        ]);
+        ResolvedValue::RecordData(Box::new(record))
+    }
+}
+
+/// Where a variable is set: a file path, or `pid N` for a process
+fn environment_record(
+    variable_name: &str,
+    value: String,
+    source: &str,
+    process_name: &str,
+) -> ResolvedValue {
+    let record = RecordData::from_field_pairs(vec![
+        ("variable_name".to_string(), variable_name.into()),
+        ("value".to_string(), value.into()),
+        ("source".to_string(), source.into()),
+        ("process_name".to_string(), process_name.into()),
+    ]);
+    ResolvedValue::RecordData(Box::new(record))
+}
+
+/// One cron job or systemd timer
+#[derive(Debug, Clone, PartialEq)]
+struct ScheduledJob {
+    /// Crontab path, `crontab -u <user>`, `run-parts` directory or timer unit
+    source: String,
+    /// Line of the job in its crontab; 0 for scripts and timers
+    line: usize,
+    schedule: String,
+    command: String,
+    user: String,
+    enabled: bool,
+    max_interval_hours: Option<i64>,
+}
+
+impl ScheduledJob {
+    /// A crontab line; `user` owns a table without a user column
+    fn from_cron(source: &str, job: CronJob, user: &str) -> Self {
+        Self {
+            source: source.to_string(),
+            line: job.line,
+            max_interval_hours: scheduled_jobs::cron_max_interval_hours(&job.schedule),
+            schedule: job.schedule,
+            command: job.command,
+            user: job.user.unwrap_or_else(|| user.to_string()),
+            enabled: true,
+        }
+    }
+
+    /// The value of a string filter field
+    fn field(&self, name: &str) -> &str {
+        match name {
+            "source" => &self.source,
+            "user" => &self.user,
+            "command" => &self.command,
+            _ => "",
+        }
+    }
+
+    fn into_record(self, user_exists: bool) -> ResolvedValue {
+        let mut fields = vec![
+            ("source".to_string(), self.source.into()),
+            ("line".to_string(), (self.line as i64).into()),
+            ("schedule".to_string(), self.schedule.into()),
+            ("command".to_string(), self.command.into()),
+            ("user".to_string(), self.user.into()),
+            ("enabled".to_string(), self.enabled.into()),
+            ("user_exists".to_string(), user_exists.into()),
+        ];
+        // `@reboot` and unrecognized calendars have no interval to compare
+        if let Some(hours) = self.max_interval_hours {
+            fields.push(("max_interval_hours".to_string(), hours.into()));
+        }
+        ResolvedValue::RecordData(Box::new(RecordData::from_field_pairs(fields)))
+    }
+}
+
+/// Files of a directory in name order, skipping hidden and backup files
+///
+/// A missing or unreadable directory has none.
+fn directory_entries(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .map(|name| name.to_string_lossy())
+                .is_some_and(|name| !name.starts_with('.') && !name.ends_with('~'))
+        })
+        .collect();
+    paths.sort();
+    paths
+}
+
+/// Split `ss` `address:port`, dropping IPv6 brackets and `%interface` scopes
+fn split_socket_address(socket: &str) -> Option<(String, i64)> {
+    let (address, port) = socket.rsplit_once(':')?;
+    let port = port.parse().ok()?;
+    let address = address.split('%').next().unwrap_or_default();
+    let address = address.trim_start_matches('[').trim_end_matches(']');
+    Some((address.to_string(), port))
+}
+
+/// The first process name in an `ss` process column, `users:(("sshd",pid=1,fd=3))`
+fn ss_process_name(column: &str) -> String {
+    column
+        .split_once("((\"")
+        .and_then(|(_, rest)| rest.split_once('"'))
+        .map(|(name, _)| name.to_string())
+        .unwrap_or_default()
+}
+
+/// Parse `ss -tulpn` output, skipping the header and unparsable lines
+///
+/// Columns are `Netid State Recv-Q Send-Q Local Peer [Process]`; the process
+/// column is missing for sockets of processes the scanner cannot inspect.
+fn parse_ss_listeners(stdout: &str) -> Vec<Listener> {
+    stdout
+        .lines()
+        .filter_map(|line| {
+            let columns: Vec<&str> = line.split_whitespace().collect();
+            if columns.len() < 6 || columns[0] == "Netid" {
+                return None;
+            }
+            let (local_address, port) = split_socket_address(columns[4])?;
+            Some(Listener {
+                protocol: columns[0].to_string(),
+                state: columns[1].to_string(),
+                local_address,
+                port,
+                process_name: ss_process_name(&columns[6..].join(" ")),
+            })
+        })
+        .collect()
+}
+
+/// Object fields that narrow which listeners are collected
+#[derive(Debug, Default)]
+struct ListenerFilter {
+    port: Option<i64>,
+    protocol: Option<String>,
+    local_address: Option<String>,
+    process_name: Option<String>,
+}
+
+impl ListenerFilter {
+    fn from_object(object: &ExecutableObject) -> Result<Self, CollectionError> {
+        let mut filter = Self::default();
+        for element in &object.elements {
+            let ExecutableObjectElement::Field { name, value, .. } = element else {
+                continue;
+            };
+            let invalid = |expected: &str| CollectionError::InvalidObjectConfiguration {
+                object_id: object.identifier.clone(),
+                reason: format!("Field '{}' must be {}, got {:?}", name, expected, value),
+            };
+            match (name.as_str(), value) {
+                ("port", ResolvedValue::Integer(port)) => filter.port = Some(*port),
+                ("port", ResolvedValue::String(port)) => {
+                    filter.port = Some(port.parse().map_err(|_| invalid("an integer"))?)
+                }
+                ("port", _) => return Err(invalid("an integer")),
+                ("protocol", ResolvedValue::String(s)) => filter.protocol = Some(s.clone()),
+                ("local_address", ResolvedValue::String(s)) => {
+                    filter.local_address = Some(s.clone())
+                }
+                ("process_name", ResolvedValue::String(s)) => filter.process_name = Some(s.clone()),
+                ("protocol" | "local_address" | "process_name", _) => {
+                    return Err(invalid("a string"))
+                }
+                _ => {}
+            }
+        }
+        Ok(filter)
+    }
+
+    fn matches(&self, listener: &Listener) -> bool {
+        self.port.map_or(true, |port| listener.port == port)
+            && self.protocol.as_ref().map_or(true, |protocol| {
+                protocol.eq_ignore_ascii_case(&listener.protocol)
+            })
+            && self.local_address.as_ref().map_or(true, |pattern| {
+                glob_matches(pattern, &listener.local_address).unwrap_or(false)
+            })
+            && self.process_name.as_ref().map_or(true, |pattern| {
+                glob_matches(pattern, &listener.process_name).unwrap_or(false)
+            })
+    }
+}
+
+/// Whether a service name is a template glob such as `getty@*.service`
+///
+/// A template unit named without a glob (`getty@.service`) is queried like
+/// any other unit.
+fn is_template_pattern(service_name: &str) -> bool {
+    service_name.contains('@') && service_name.contains('*')
+}
+
+/// Instance part of a unit name: `getty@tty1.service` -> `tty1`
+///
+/// Empty for units that are not template instances.
+fn unit_instance(unit: &str) -> &str {
+    match unit.split_once('@') {
+        Some((_, rest)) => rest.rsplit_once('.').map_or(rest, |(instance, _)| instance),
+        None => "",
+    }
+}
+
+/// One row of `systemctl list-units --plain --no-legend` output
+#[derive(Debug, Clone, PartialEq)]
+struct ListedUnit {
+    unit: String,
+    load: String,
+    active: String,
+}
+
+impl ListedUnit {
+    fn into_record(self, enabled: bool) -> ResolvedValue {
+        let record = RecordData::from_field_pairs(vec![
+            ("instance".to_string(), unit_instance(&self.unit).into()),
+            ("active".to_string(), (self.active == "active").into()),
+            ("enabled".to_string(), enabled.into()),
+            ("loaded".to_string(), (self.load == "loaded").into()),
+            ("service_name".to_string(), self.unit.into()),
+        ]);
+        ResolvedValue::RecordData(Box::new(record))
+    }
+}
+
+/// `systemctl show` invocation for the `properties` parameter
+///
+/// The property list is the only argv position a policy may fill for the
+/// `execute_systemctl` capability; it is passed as one `--property=` element.
+fn systemctl_show_template() -> CommandTemplate {
+    CommandTemplate::new(
+        "systemctl",
+        vec![
+            ArgvSlot::Fixed("show"),
+            ArgvSlot::Subject,
+            ArgvSlot::Parameter {
+                name: "properties",
+                prefix: "--property=",
+            },
+            ArgvSlot::Fixed("--no-pager"),
+        ],
+    )
+}
+
+/// Parse `Key=Value` lines from `systemctl show` into a record of strings
+fn parse_show_properties(stdout: &str) -> RecordData {
+    RecordData::from_field_pairs(
+        stdout
+            .lines()
+            .filter_map(|line| line.split_once('='))
+            .map(|(name, value)| (name.trim().to_string(), value.trim().into()))
+            .collect(),
+    )
+}
+
+/// Parse `UNIT LOAD ACTIVE SUB DESCRIPTION` rows, skipping anything shorter
+fn parse_list_units(stdout: &str) -> Vec<ListedUnit> {
+    stdout
+        .lines()
+        .filter_map(|line| {
+            // Failed units keep a status marker on some systemd versions
+            let mut columns = line
+                .trim_start_matches(|c: char| c == '●' || c == '*' || c.is_whitespace())
+                .split_whitespace();
+            Some(ListedUnit {
+                unit: columns.next()?.to_string(),
+                load: columns.next()?.to_string(),
+                active: columns.next()?.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// One zone block of `firewall-cmd --list-all-zones` output
+#[derive(Debug, Clone, Default, PartialEq)]
+struct FirewalldZone {
+    name: String,
+    active: bool,
+    /// `key: value` lines, e.g. `services` -> `ssh dhcpv6-client`
+    properties: HashMap<String, String>,
+    rich_rules: Vec<String>,
+}
+
+impl FirewalldZone {
+    fn into_record(self, default_zone: &str) -> ResolvedValue {
+        let property = |name: &str| self.properties.get(name).cloned().unwrap_or_default();
+        let list = |name: &str| -> serde_json::Value {
+            property(name)
+                .split_whitespace()
+                .map(|item| serde_json::Value::String(item.to_string()))
+                .collect()
+        };
+        let record = RecordData::from_field_pairs(vec![
+            ("zone".to_string(), self.name.clone().into()),
+            ("default_zone".to_string(), default_zone.into()),
+            ("default".to_string(), (self.name == default_zone).into()),
+            ("active".to_string(), self.active.into()),
+            ("target".to_string(), property("target").into()),
+            ("interfaces".to_string(), list("interfaces")),
+            ("sources".to_string(), list("sources")),
+            ("services".to_string(), list("services")),
+            ("ports".to_string(), list("ports")),
+            ("protocols".to_string(), list("protocols")),
+            ("forward_ports".to_string(), list("forward-ports")),
+            (
+                "masquerade".to_string(),
+                (property("masquerade") == "yes").into(),
+            ),
+            (
+                "rich_rules".to_string(),
+                self.rich_rules.iter().cloned().collect(),
+            ),
+        ]);
+        ResolvedValue::RecordData(Box::new(record))
+    }
+}
+
+/// Parse `firewall-cmd --list-all-zones` output
+///
+/// Each zone starts with an unindented `name` line, marked `(active)` or
+/// `(default, active)` when in use, followed by indented `key: value`
+/// lines. Rich rules follow `rich rules:`, one per tab-indented line.
+fn parse_firewalld_zones(stdout: &str) -> Vec<FirewalldZone> {
+    let mut zones: Vec<FirewalldZone> = Vec::new();
+    let mut in_rich_rules = false;
+    for line in stdout.lines() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        if !line.starts_with(char::is_whitespace) {
+            let (name, marker) = line.split_once(' ').unwrap_or((line, ""));
+            zones.push(FirewalldZone {
+                name: name.to_string(),
+                active: marker.contains("active"),
+                ..Default::default()
+            });
+            in_rich_rules = false;
+            continue;
+        }
+        let Some(zone) = zones.last_mut() else {
+            continue;
+        };
+        if in_rich_rules && line.starts_with('\t') {
+            zone.rich_rules.push(line.trim().to_string());
+        } else if let Some((name, value)) = line.trim().split_once(':') {
+            in_rich_rules = name == "rich rules";
+            zone.properties
+                .insert(name.to_string(), value.trim().to_string());
+        }
+    }
+    zones
+}
+
+/// `dpkg-query -f` format: one tab-separated line per package
+const DPKG_QUERY_FORMAT: &str = "${Package}\t${Version}\t${Architecture}\t${db:Status-Status}\n";
+
+/// One package line from `dpkg-query -W`
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct DpkgPackage {
+    name: String,
+    version: String,
+    architecture: String,
+    /// Unpacked and configured, not merely known or left with config files
+    installed: bool,
+}
+
+/// Parse `dpkg-query -W` output in [`DPKG_QUERY_FORMAT`]
+fn parse_dpkg_query(stdout: &str) -> Vec<DpkgPackage> {
+    stdout
+        .lines()
+        .filter_map(|line| {
+            let mut columns = line.split('\t');
+            let name = columns.next().filter(|name| !name.is_empty())?;
+            Some(DpkgPackage {
+                name: name.to_string(),
+                version: columns.next().unwrap_or_default().to_string(),
+                architecture: columns.next().unwrap_or_default().to_string(),
+                installed: columns.next() == Some("installed"),
+            })
+        })
+        .collect()
 }
 
 impl CtnDataCollector for CommandCollector {
@@ -283,18 +1846,30 @@ impl CtnDataCollector for CommandCollector {
         contract: &CtnContract,
         hints: &BehaviorHints,
     ) -> Result<CollectedData, CollectionError> {
-        contract.validate_behavior_hints(hints).map_err(|e| {
+        let behaviors = contract.resolve_behaviors(hints).map_err(|e| {
+            CollectionError::CtnContractValidation {
+                reason: e.to_string(),
+            }
+        })?;
+        let parameters = contract.resolve_parameters(object).map_err(|e| {
             CollectionError::CtnContractValidation {
                 reason: e.to_string(),
             }
         })?;
 
-        // Then existing code...
         match contract.ctn_type.as_str() {
-            "rpm_package" => self.collect_rpm_package(object, hints),
-            "systemd_service" => self.collect_systemd_service(object, hints),
-            "sysctl_parameter" => self.collect_sysctl_parameter(object, hints),
-            "selinux_status" => self.collect_selinux_status(object, hints),
+            "rpm_package" => self.collect_rpm_package(object, &behaviors),
+            "dpkg_package" => self.collect_dpkg_package(object, &behaviors),
+            "systemd_service" => self.collect_systemd_service(object, &behaviors, &parameters),
+            "systemd_unit_property" => self.collect_systemd_unit_property(object, &behaviors),
+            "sysctl_parameter" => self.collect_sysctl_parameter(object, &behaviors),
+            "selinux_status" => self.collect_selinux_status(object, &behaviors),
+            "firewalld_zone" => self.collect_firewalld_zone(object, &behaviors),
+            "listening_port" => self.collect_listening_port(object, &behaviors),
+            "environment_variable" => self.collect_environment_variable(object, &behaviors),
+            "scheduled_job" => self.collect_scheduled_job(object, &behaviors),
+            "selinux_boolean" => self.collect_selinux_boolean(object, &behaviors),
+            "selinux_file_context" => self.collect_selinux_file_context(object, &behaviors),
             _ => Err(CollectionError::UnsupportedCtnType {
                 ctn_type: contract.ctn_type.clone(),
                 collector_id: self.id.clone(),
@@ -311,16 +1886,20 @@ impl CtnDataCollector for CommandCollector {
 
         match contract.ctn_type.as_str() {
             "rpm_package" => {
-                // Extract hints from first object (batch operations use same hints)
+                // Batch operations use the first object's behaviors
                 let hints = if !objects.is_empty() {
                     extract_behavior_hints(objects[0])
                 } else {
                     BehaviorHints::empty()
                 };
+                let behaviors = contract.resolve_behaviors(&hints).map_err(|e| {
+                    CollectionError::CtnContractValidation {
+                        reason: e.to_string(),
+                    }
+                })?;
 
-                // Check for timeout behavior
-                let timeout = hints
-                    .get_parameter_as_int("timeout")
+                let timeout = behaviors
+                    .int("timeout")
                     .map(|t| std::time::Duration::from_secs(t as u64));
 
                 // Execute rpm -qa ONCE for all packages
@@ -371,6 +1950,74 @@ impl CtnDataCollector for CommandCollector {
 
                 Ok(results)
             }
+            "dpkg_package" => {
+                // Batch operations use the first object's behaviors
+                let hints = objects
+                    .first()
+                    .map(|object| extract_behavior_hints(object))
+                    .unwrap_or_else(BehaviorHints::empty);
+                let behaviors = contract.resolve_behaviors(&hints).map_err(|e| {
+                    CollectionError::CtnContractValidation {
+                        reason: e.to_string(),
+                    }
+                })?;
+                let timeout = behaviors
+                    .int("timeout")
+                    .map(|t| std::time::Duration::from_secs(t as u64));
+
+                // List every package ONCE, keeping the first installed entry per name
+                let mut installed_packages: HashMap<String, DpkgPackage> = HashMap::new();
+                for package in self.dpkg_query(&[], timeout, "batch")? {
+                    if package.installed {
+                        installed_packages
+                            .entry(package.name.clone())
+                            .or_insert(package);
+                    }
+                }
+
+                let mut results = HashMap::new();
+                for object in objects {
+                    let package_name = self.extract_field(object, "package_name")?;
+                    let installed = installed_packages.get(&package_name);
+                    results.insert(
+                        object.identifier.clone(),
+                        self.dpkg_package_data(&object.identifier, package_name, installed),
+                    );
+                }
+                Ok(results)
+            }
+            "selinux_boolean" => {
+                // Batch operations use the first object's behaviors
+                let hints = objects
+                    .first()
+                    .map(|object| extract_behavior_hints(object))
+                    .unwrap_or_else(BehaviorHints::empty);
+                let behaviors = contract.resolve_behaviors(&hints).map_err(|e| {
+                    CollectionError::CtnContractValidation {
+                        reason: e.to_string(),
+                    }
+                })?;
+                let timeout = behaviors
+                    .int("timeout")
+                    .map(|t| std::time::Duration::from_secs(t as u64));
+
+                // Read every boolean ONCE
+                let booleans = self.selinux_booleans("batch", timeout)?;
+
+                let mut results = HashMap::new();
+                for object in objects {
+                    let boolean_name = self.extract_field(object, "boolean_name")?;
+                    results.insert(
+                        object.identifier.clone(),
+                        self.selinux_boolean_data(
+                            &object.identifier,
+                            boolean_name,
+                            booleans.as_ref(),
+                        ),
+                    );
+                }
+                Ok(results)
+            }
             _ => Err(CollectionError::UnsupportedCtnType {
                 ctn_type: contract.ctn_type.clone(),
                 collector_id: self.id.clone(),
@@ -385,9 +2032,17 @@ impl CtnDataCollector for CommandCollector {
     fn supported_ctn_types(&self) -> Vec<String> {
         vec![
             "rpm_package".to_string(),
+            "dpkg_package".to_string(),
             "systemd_service".to_string(),
+            "systemd_unit_property".to_string(),
             "sysctl_parameter".to_string(),
             "selinux_status".to_string(),
+            "firewalld_zone".to_string(),
+            "listening_port".to_string(),
+            "environment_variable".to_string(),
+            "scheduled_job".to_string(),
+            "selinux_boolean".to_string(),
+            "selinux_file_context".to_string(),
         ]
     }
 
@@ -403,4 +2058,214 @@ impl CtnDataCollector for CommandCollector {
     fn collector_id(&self) -> &str {
         &self.id
     }
+
+    fn supports_result_caching(&self, ctn_type: &str) -> bool {
+        !matches!(ctn_type, "listening_port" | "environment_variable")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIST_UNITS: &str = "\
+getty@tty1.service  loaded active   running Getty on tty1
+getty@tty2.service  loaded inactive dead    Getty on tty2
+\u{25cf} getty@tty3.service loaded failed failed Getty on tty3
+";
+
+    #[test]
+    fn test_parse_getsebool_and_context() {
+        let booleans = parse_getsebool(
+            "selinuxuser_execheap --> off\n\
+             ssh_sysadm_login --> on\n\
+             virt_use_nfs --> off pending: on\n",
+        );
+        assert_eq!(booleans.len(), 3);
+        assert_eq!(booleans.get("selinuxuser_execheap"), Some(&false));
+        assert_eq!(booleans.get("ssh_sysadm_login"), Some(&true));
+        assert_eq!(booleans.get("virt_use_nfs"), Some(&false));
+
+        let [user, role, context_type, level] =
+            parse_selinux_context("system_u:object_r:shadow_t:s0:c0.c1023").unwrap();
+        assert_eq!(
+            (user.as_str(), role.as_str(), context_type.as_str()),
+            ("system_u", "object_r", "shadow_t")
+        );
+        assert_eq!(level, "s0:c0.c1023");
+        assert_eq!(
+            parse_selinux_context("user_u:object_r:tmp_t").unwrap()[3],
+            ""
+        );
+        assert_eq!(parse_selinux_context("?"), None);
+    }
+
+    #[test]
+    fn test_parse_ss_listeners() {
+        let listeners = parse_ss_listeners(
+            "Netid State  Recv-Q Send-Q Local Address:Port  Peer Address:Port Process\n\
+             udp   UNCONN 0      0      127.0.0.53%lo:53    0.0.0.0:*  users:((\"systemd-resolve\",pid=611,fd=13))\n\
+             tcp   LISTEN 0      128    0.0.0.0:22          0.0.0.0:*  users:((\"sshd\",pid=812,fd=3),(\"sshd\",pid=901,fd=3))\n\
+             tcp   LISTEN 0      128    [::]:22             [::]:*\n\
+             tcp   LISTEN 0      4096   [fe80::1]%eth0:8080 [::]:*\n",
+        );
+        assert_eq!(listeners.len(), 4);
+        assert_eq!(listeners[0].protocol, "udp");
+        assert_eq!(listeners[0].local_address, "127.0.0.53");
+        assert_eq!(listeners[0].process_name, "systemd-resolve");
+        assert_eq!(listeners[1].state, "LISTEN");
+        assert_eq!(listeners[1].port, 22);
+        assert_eq!(listeners[1].process_name, "sshd");
+        assert_eq!(listeners[2].local_address, "::");
+        assert_eq!(listeners[2].process_name, "");
+        assert_eq!(listeners[3].local_address, "fe80::1");
+        assert_eq!(listeners[3].port, 8080);
+
+        let ssh = ListenerFilter {
+            port: Some(22),
+            protocol: Some("TCP".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(listeners.iter().filter(|l| ssh.matches(l)).count(), 2);
+    }
+
+    #[test]
+    fn test_volatile_types_are_not_cached() {
+        let collector = CommandCollector::new("command_collector", SystemCommandExecutor::new());
+        assert!(collector.supports_result_caching("rpm_package"));
+        assert!(!collector.supports_result_caching("listening_port"));
+        assert!(!collector.supports_result_caching("environment_variable"));
+    }
+
+    #[test]
+    fn test_parse_dpkg_query() {
+        let packages = parse_dpkg_query(
+            "openssh-server\t1:8.9p1-3ubuntu0.6\tamd64\tinstalled\n\
+             libc6\t2.35-0ubuntu3.6\ti386\tinstalled\n\
+             telnetd\t0.17-44\tamd64\tconfig-files\n",
+        );
+        assert_eq!(packages.len(), 3);
+        assert_eq!(packages[0].name, "openssh-server");
+        assert_eq!(packages[0].version, "1:8.9p1-3ubuntu0.6");
+        assert_eq!(packages[1].architecture, "i386");
+        assert!(packages[1].installed);
+        assert!(!packages[2].installed);
+    }
+
+    #[test]
+    fn test_template_pattern_and_instance_names() {
+        assert!(is_template_pattern("getty@*.service"));
+        assert!(!is_template_pattern("getty@.service"));
+        assert!(!is_template_pattern("sshd.service"));
+        assert_eq!(unit_instance("getty@tty1.service"), "tty1");
+        assert_eq!(unit_instance("openvpn@site.a.service"), "site.a");
+        assert_eq!(unit_instance("getty@.service"), "");
+        assert_eq!(unit_instance("sshd.service"), "");
+    }
+
+    #[test]
+    fn test_parse_list_units_with_several_instances() {
+        let units = parse_list_units(LIST_UNITS);
+        assert_eq!(units.len(), 3);
+        assert_eq!(units[0].unit, "getty@tty1.service");
+        assert_eq!(units[1].active, "inactive");
+        assert_eq!(units[2].unit, "getty@tty3.service");
+        assert_eq!(units[2].active, "failed");
+
+        let ResolvedValue::RecordData(record) = units[0].clone().into_record(true) else {
+            panic!("expected a record");
+        };
+        assert_eq!(record.get_field_by_path("instance"), Some(&"tty1".into()));
+        assert_eq!(record.get_field_by_path("active"), Some(&true.into()));
+        assert_eq!(record.get_field_by_path("enabled"), Some(&true.into()));
+    }
+
+    #[test]
+    fn test_systemctl_property_query_from_parameters() {
+        let contract = crate::contracts::create_systemd_service_contract();
+        let object = ExecutableObject {
+            identifier: "sshd".to_string(),
+            elements: vec![ExecutableObjectElement::Parameter {
+                data_type: esp_scanner_base::types::common::DataType::String,
+                data: RecordData::from_string_pairs(vec![(
+                    "properties".to_string(),
+                    "ActiveState,NoNewPrivileges".to_string(),
+                )]),
+            }],
+            is_global: false,
+        };
+        let parameters = contract.resolve_parameters(&object).unwrap();
+        assert_eq!(
+            systemctl_show_template()
+                .argv("sshd.service", &parameters)
+                .unwrap(),
+            [
+                "show",
+                "sshd.service",
+                "--property=ActiveState,NoNewPrivileges",
+                "--no-pager"
+            ]
+        );
+
+        let record = parse_show_properties("ActiveState=active\nNoNewPrivileges=yes\n");
+        assert_eq!(
+            record.get_field_by_path("NoNewPrivileges"),
+            Some(&"yes".into())
+        );
+    }
+
+    const ALL_ZONES: &str = "\
+drop
+  target: DROP
+  icmp-block-inversion: no
+  interfaces: 
+  services: 
+  masquerade: no
+  rich rules: 
+
+public (default, active)
+  target: default
+  interfaces: eth0 eth1
+  sources: 
+  services: cockpit dhcpv6-client ssh
+  ports: 8080/tcp
+  masquerade: yes
+  forward-ports: 
+  rich rules: 
+\trule family=\"ipv4\" source address=\"10.0.0.0/8\" service name=\"ssh\" accept
+\trule family=\"ipv6\" source address=\"fd00::/8\" drop
+";
+
+    #[test]
+    fn test_parse_firewalld_zones() {
+        let zones = parse_firewalld_zones(ALL_ZONES);
+        assert_eq!(zones.len(), 2);
+        assert_eq!(zones[0].name, "drop");
+        assert!(!zones[0].active);
+        assert!(zones[0].rich_rules.is_empty());
+        assert_eq!(zones[1].name, "public");
+        assert!(zones[1].active);
+        assert_eq!(zones[1].rich_rules.len(), 2);
+        assert!(zones[1].rich_rules[1].contains("fd00::/8"));
+
+        let ResolvedValue::RecordData(record) = zones[1].clone().into_record("public") else {
+            panic!("expected a record");
+        };
+        assert_eq!(
+            record.get_field_by_path("services"),
+            Some(&serde_json::json!(["cockpit", "dhcpv6-client", "ssh"]))
+        );
+        assert_eq!(record.get_field_by_path("masquerade"), Some(&true.into()));
+        assert_eq!(record.get_field_by_path("default"), Some(&true.into()));
+        assert_eq!(
+            record.get_field_by_path("sources"),
+            Some(&serde_json::json!([]))
+        );
+    }
+
+    #[test]
+    fn test_parse_list_units_with_no_instances() {
+        assert!(parse_list_units("").is_empty());
+        assert!(parse_list_units("\n").is_empty());
+    }
 }
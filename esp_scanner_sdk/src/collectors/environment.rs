@@ -0,0 +1,192 @@
+//! # Environment Variables
+//!
+//! Parses the places an environment variable can be set for the
+//! `environment_variable` criterion type: `/etc/environment` and the shell
+//! fragments in `/etc/profile.d`, and the `/proc/<pid>/environ` of running
+//! processes.
+//!
+//! Files are read as assignments, `NAME=value`, optionally after `export`,
+//! several to a line. Single-quoted values are literal; double-quoted values
+//! honour `\"`, `\\`, `\$` and `` \` ``; an unquoted `#` starting a word
+//! begins a comment. References such as `$PATH` are not expanded. Lines that
+//! are not assignments, such as `if` blocks, are skipped. When a file assigns
+//! a variable more than once, the last value is the one a login shell sees.
+
+use std::path::{Path, PathBuf};
+
+/// Files read when an object names none
+pub const DEFAULT_ENVIRONMENT_PATHS: &[&str] = &["/etc/environment", "/etc/profile.d"];
+
+/// Variables of a `/proc/<pid>/environ` image, in order
+pub fn parse_environ(environ: &[u8]) -> Vec<(String, String)> {
+    environ
+        .split(|&b| b == 0)
+        .filter(|entry| !entry.is_empty())
+        .filter_map(|entry| {
+            let entry = String::from_utf8_lossy(entry);
+            let (name, value) = entry.split_once('=')?;
+            Some((name.to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+/// Assignments of an environment file or shell fragment, in file order
+pub fn parse_assignments(content: &str) -> Vec<(String, String)> {
+    let mut assignments = Vec::new();
+    for line in content.lines() {
+        let Some(words) = shell_words(line) else {
+            continue;
+        };
+        let words = match words.split_first() {
+            Some((first, rest)) if first == "export" => rest,
+            _ => &words[..],
+        };
+        for word in words {
+            match word.split_once('=') {
+                Some((name, value)) if is_variable_name(name) => {
+                    assignments.push((name.to_string(), value.to_string()));
+                }
+                // `export NAME` marks a variable without assigning it
+                None if is_variable_name(word) => {}
+                _ => break,
+            }
+        }
+    }
+    assignments
+}
+
+/// The value a file leaves `name` with, if it assigns it
+pub fn last_assignment(content: &str, name: &str) -> Option<String> {
+    parse_assignments(content)
+        .into_iter()
+        .rev()
+        .find(|(assigned, _)| assigned == name)
+        .map(|(_, value)| value)
+}
+
+/// Files to read for `path`: the file itself, or a directory's `*.sh`
+/// fragments in name order, as `/etc/profile` sources them
+pub fn environment_files(path: &Path) -> Vec<PathBuf> {
+    if !path.is_dir() {
+        return vec![path.to_path_buf()];
+    }
+    let mut files: Vec<PathBuf> = std::fs::read_dir(path)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.path())
+                .filter(|file| file.extension().is_some_and(|ext| ext == "sh") && file.is_file())
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort();
+    files
+}
+
+fn is_variable_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Split a line into words the way the shell quotes them, dropping a
+/// trailing comment; `None` for an unterminated quote
+pub(super) fn shell_words(line: &str) -> Option<Vec<String>> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            '#' if !in_word => break,
+            '\'' => {
+                in_word = true;
+                loop {
+                    match chars.next()? {
+                        '\'' => break,
+                        c => word.push(c),
+                    }
+                }
+            }
+            '"' => {
+                in_word = true;
+                loop {
+                    match chars.next()? {
+                        '"' => break,
+                        '\\' => match chars.next()? {
+                            c @ ('"' | '\\' | '$' | '`') => word.push(c),
+                            c => {
+                                word.push('\\');
+                                word.push(c);
+                            }
+                        },
+                        c => word.push(c),
+                    }
+                }
+            }
+            '\\' => {
+                in_word = true;
+                if let Some(c) = chars.next() {
+                    word.push(c);
+                }
+            }
+            c => {
+                in_word = true;
+                word.push(c);
+            }
+        }
+    }
+    if in_word {
+        words.push(word);
+    }
+    Some(words)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_environ_splits_on_nul() {
+        let environ = b"PATH=/usr/bin\0LD_PRELOAD=/tmp/x.so\0EMPTY=\0A=b=c\0";
+        assert_eq!(
+            parse_environ(environ),
+            [
+                ("PATH".to_string(), "/usr/bin".to_string()),
+                ("LD_PRELOAD".to_string(), "/tmp/x.so".to_string()),
+                ("EMPTY".to_string(), String::new()),
+                ("A".to_string(), "b=c".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_assignments_exports_and_quotes() {
+        let content = r#"
+# comment
+PATH="/usr/local/sbin:/usr/bin"
+export TMOUT=900 HISTSIZE='5000'
+export EDITOR
+if [ -n "$BASH" ]; then GREETING=hi; fi
+PROMPT="a \"quoted\" \$value" # trailing comment
+TMOUT=600
+BROKEN="unterminated
+"#;
+        let assignments = parse_assignments(content);
+        let names: Vec<&str> = assignments.iter().map(|(n, _)| n.as_str()).collect();
+        assert_eq!(names, ["PATH", "TMOUT", "HISTSIZE", "PROMPT", "TMOUT"]);
+        assert_eq!(assignments[0].1, "/usr/local/sbin:/usr/bin");
+        assert_eq!(assignments[2].1, "5000");
+        assert_eq!(assignments[3].1, r#"a "quoted" $value"#);
+        assert_eq!(last_assignment(content, "TMOUT").as_deref(), Some("600"));
+        assert_eq!(last_assignment(content, "EDITOR"), None);
+    }
+}
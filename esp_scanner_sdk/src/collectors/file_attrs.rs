@@ -0,0 +1,307 @@
+//! # Extended File Attribute Reader
+//!
+//! Reads a file's POSIX ACLs and file capabilities from their extended
+//! attributes, for the `extended_file_attr` criterion type, without running
+//! `getfacl` or `getcap`.
+//!
+//! ACL entries are written as `getfacl -n` prints them: `user::rwx`,
+//! `user:1000:r-x`, `mask::r-x`, with `default:` in front of a directory's
+//! default entries. Named users and groups stay numeric ids. Capabilities
+//! are written one per capability as `getcap` did before libcap 2.40, e.g.
+//! `cap_net_raw+ep`.
+//!
+//! A filesystem without extended attribute support has no ACLs and no
+//! capabilities rather than an error.
+
+use std::io;
+use std::path::Path;
+
+const ACCESS_ACL_ATTR: &str = "system.posix_acl_access";
+const DEFAULT_ACL_ATTR: &str = "system.posix_acl_default";
+const CAPABILITY_ATTR: &str = "security.capability";
+
+/// `EOPNOTSUPP` on Linux: the filesystem has no extended attributes
+const EOPNOTSUPP: i32 = 95;
+
+const ACL_VERSION: u32 = 2;
+const ACL_USER_OBJ: u16 = 0x01;
+const ACL_USER: u16 = 0x02;
+const ACL_GROUP_OBJ: u16 = 0x04;
+const ACL_GROUP: u16 = 0x08;
+const ACL_MASK: u16 = 0x10;
+const ACL_OTHER: u16 = 0x20;
+
+const VFS_CAP_REVISION_MASK: u32 = 0xFF00_0000;
+const VFS_CAP_REVISION_1: u32 = 0x0100_0000;
+const VFS_CAP_REVISION_2: u32 = 0x0200_0000;
+const VFS_CAP_REVISION_3: u32 = 0x0300_0000;
+const VFS_CAP_FLAGS_EFFECTIVE: u32 = 0x0000_0001;
+
+/// Capability names by bit number, as in `linux/capability.h`
+const CAPABILITY_NAMES: &[&str] = &[
+    "cap_chown",
+    "cap_dac_override",
+    "cap_dac_read_search",
+    "cap_fowner",
+    "cap_fsetid",
+    "cap_kill",
+    "cap_setgid",
+    "cap_setuid",
+    "cap_setpcap",
+    "cap_linux_immutable",
+    "cap_net_bind_service",
+    "cap_net_broadcast",
+    "cap_net_admin",
+    "cap_net_raw",
+    "cap_ipc_lock",
+    "cap_ipc_owner",
+    "cap_sys_module",
+    "cap_sys_rawio",
+    "cap_sys_chroot",
+    "cap_sys_ptrace",
+    "cap_sys_pacct",
+    "cap_sys_admin",
+    "cap_sys_boot",
+    "cap_sys_nice",
+    "cap_sys_resource",
+    "cap_sys_time",
+    "cap_sys_tty_config",
+    "cap_mknod",
+    "cap_lease",
+    "cap_audit_write",
+    "cap_audit_control",
+    "cap_setfcap",
+    "cap_mac_override",
+    "cap_mac_admin",
+    "cap_syslog",
+    "cap_wake_alarm",
+    "cap_block_suspend",
+    "cap_audit_read",
+    "cap_perfmon",
+    "cap_bpf",
+    "cap_checkpoint_restore",
+];
+
+/// The ACLs and capabilities of one file
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ExtendedAttrs {
+    /// Named user or group entries, a mask, or a default ACL
+    pub has_extended_acl: bool,
+    /// Access entries, then `default:` entries
+    pub acl_entries: Vec<String>,
+    pub capabilities: Vec<String>,
+}
+
+/// Read the ACLs and capabilities of `path`, following symlinks
+pub fn read_extended_attrs(path: &Path) -> io::Result<ExtendedAttrs> {
+    let access = read_attr(path, ACCESS_ACL_ATTR)?;
+    let default = read_attr(path, DEFAULT_ACL_ATTR)?;
+    let capability = read_attr(path, CAPABILITY_ATTR)?;
+
+    let access = access.map(|value| parse_acl(&value)).transpose()?;
+    let default = default.map(|value| parse_acl(&value)).transpose()?;
+    let capabilities = match capability {
+        Some(value) => parse_capabilities(&value)?,
+        None => Vec::new(),
+    };
+
+    let mut attrs = ExtendedAttrs {
+        capabilities,
+        ..Default::default()
+    };
+    if let Some(entries) = access {
+        attrs.has_extended_acl = entries
+            .iter()
+            .any(|(tag, _, _)| matches!(*tag, ACL_USER | ACL_GROUP | ACL_MASK));
+        attrs
+            .acl_entries
+            .extend(entries.iter().map(|entry| format_acl_entry(entry, "")));
+    }
+    if let Some(entries) = default.filter(|entries| !entries.is_empty()) {
+        attrs.has_extended_acl = true;
+        attrs.acl_entries.extend(
+            entries
+                .iter()
+                .map(|entry| format_acl_entry(entry, "default:")),
+        );
+    }
+    Ok(attrs)
+}
+
+/// An attribute's value; absent, or unsupported by the filesystem, is `None`
+fn read_attr(path: &Path, name: &str) -> io::Result<Option<Vec<u8>>> {
+    match xattr::get_deref(path, name) {
+        Err(e) if e.raw_os_error() == Some(EOPNOTSUPP) => Ok(None),
+        result => result,
+    }
+}
+
+fn invalid(what: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, what.to_string())
+}
+
+fn le_u16(bytes: &[u8]) -> u16 {
+    u16::from_le_bytes([bytes[0], bytes[1]])
+}
+
+fn le_u32(bytes: &[u8]) -> u32 {
+    u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+}
+
+/// Decode an ACL attribute into `(tag, permissions, id)` entries
+fn parse_acl(value: &[u8]) -> io::Result<Vec<(u16, u16, u32)>> {
+    if value.len() < 4 || (value.len() - 4) % 8 != 0 || le_u32(value) != ACL_VERSION {
+        return Err(invalid("malformed POSIX ACL"));
+    }
+    Ok(value[4..]
+        .chunks_exact(8)
+        .map(|entry| (le_u16(entry), le_u16(&entry[2..]), le_u32(&entry[4..])))
+        .collect())
+}
+
+/// One entry in `getfacl -n` form
+fn format_acl_entry(&(tag, permissions, id): &(u16, u16, u32), prefix: &str) -> String {
+    let qualifier = match tag {
+        ACL_USER | ACL_GROUP => id.to_string(),
+        _ => String::new(),
+    };
+    let tag = match tag {
+        ACL_USER_OBJ | ACL_USER => "user",
+        ACL_GROUP_OBJ | ACL_GROUP => "group",
+        ACL_MASK => "mask",
+        ACL_OTHER => "other",
+        _ => "unknown",
+    };
+    let bit = |mask: u16, c: char| if permissions & mask != 0 { c } else { '-' };
+    format!(
+        "{}{}:{}:{}{}{}",
+        prefix,
+        tag,
+        qualifier,
+        bit(4, 'r'),
+        bit(2, 'w'),
+        bit(1, 'x')
+    )
+}
+
+/// Decode a `security.capability` attribute into `cap_name+flags` strings
+fn parse_capabilities(value: &[u8]) -> io::Result<Vec<String>> {
+    if value.len() < 4 {
+        return Err(invalid("malformed file capabilities"));
+    }
+    let magic = le_u32(value);
+    let words = match magic & VFS_CAP_REVISION_MASK {
+        VFS_CAP_REVISION_1 => 1,
+        VFS_CAP_REVISION_2 | VFS_CAP_REVISION_3 => 2,
+        _ => return Err(invalid("unknown file capability revision")),
+    };
+    if value.len() < 4 + words * 8 {
+        return Err(invalid("malformed file capabilities"));
+    }
+    let (mut permitted, mut inheritable) = (0u64, 0u64);
+    for word in 0..words {
+        let offset = 4 + word * 8;
+        permitted |= u64::from(le_u32(&value[offset..])) << (32 * word);
+        inheritable |= u64::from(le_u32(&value[offset + 4..])) << (32 * word);
+    }
+    let effective = magic & VFS_CAP_FLAGS_EFFECTIVE != 0;
+
+    Ok((0..64)
+        .filter(|bit| (permitted | inheritable) & (1 << bit) != 0)
+        .map(|bit| {
+            let name = CAPABILITY_NAMES
+                .get(bit)
+                .map_or_else(|| format!("cap_{}", bit), |name| name.to_string());
+            let mut flags = String::new();
+            if effective {
+                flags.push('e');
+            }
+            if inheritable & (1 << bit) != 0 {
+                flags.push('i');
+            }
+            if permitted & (1 << bit) != 0 {
+                flags.push('p');
+            }
+            format!("{}+{}", name, flags)
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn acl(entries: &[(u16, u16, u32)]) -> Vec<u8> {
+        let mut value = ACL_VERSION.to_le_bytes().to_vec();
+        for (tag, permissions, id) in entries {
+            value.extend(tag.to_le_bytes());
+            value.extend(permissions.to_le_bytes());
+            value.extend(id.to_le_bytes());
+        }
+        value
+    }
+
+    #[test]
+    fn test_acl_entries_in_getfacl_form() {
+        let entries = parse_acl(&acl(&[
+            (ACL_USER_OBJ, 7, u32::MAX),
+            (ACL_USER, 5, 1000),
+            (ACL_GROUP_OBJ, 5, u32::MAX),
+            (ACL_MASK, 5, u32::MAX),
+            (ACL_OTHER, 4, u32::MAX),
+        ]))
+        .unwrap();
+        let formatted: Vec<String> = entries
+            .iter()
+            .map(|entry| format_acl_entry(entry, ""))
+            .collect();
+        assert_eq!(
+            formatted,
+            vec![
+                "user::rwx",
+                "user:1000:r-x",
+                "group::r-x",
+                "mask::r-x",
+                "other::r--"
+            ]
+        );
+        assert_eq!(
+            format_acl_entry(&(ACL_GROUP, 6, 10), "default:"),
+            "default:group:10:rw-"
+        );
+        assert!(parse_acl(&[2, 0, 0, 0, 1]).is_err());
+    }
+
+    #[test]
+    fn test_capabilities_from_vfs_cap_data() {
+        // setcap cap_net_raw,cap_net_admin+ep: revision 2, effective
+        let mut value = (VFS_CAP_REVISION_2 | VFS_CAP_FLAGS_EFFECTIVE)
+            .to_le_bytes()
+            .to_vec();
+        for word in [(1u32 << 12) | (1 << 13), 0, 0, 0] {
+            value.extend(word.to_le_bytes());
+        }
+        assert_eq!(
+            parse_capabilities(&value).unwrap(),
+            vec!["cap_net_admin+ep", "cap_net_raw+ep"]
+        );
+
+        // cap_bpf (bit 39) inheritable only, no effective flag
+        let mut value = VFS_CAP_REVISION_2.to_le_bytes().to_vec();
+        for word in [0u32, 0, 0, 1 << 7] {
+            value.extend(word.to_le_bytes());
+        }
+        assert_eq!(parse_capabilities(&value).unwrap(), vec!["cap_bpf+i"]);
+        assert!(parse_capabilities(&[0, 0, 0, 9]).is_err());
+    }
+
+    #[test]
+    fn test_plain_file_has_no_extended_attrs() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("plain");
+        std::fs::write(&file, "x").unwrap();
+        let attrs = read_extended_attrs(&file).unwrap();
+        assert!(!attrs.has_extended_acl);
+        assert!(attrs.capabilities.is_empty());
+    }
+}
@@ -0,0 +1,91 @@
+//! # File Digests
+//!
+//! Streams file content through SHA-256 and SHA-512, and BLAKE3 when built
+//! with the `blake3` feature, for the `file_hash` criterion type. Content is
+//! read in fixed-size chunks, so hashing a multi-gigabyte file needs no
+//! more memory than a small one.
+use sha2::{Digest, Sha256, Sha512};
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+
+/// Bytes read per chunk
+const CHUNK_BYTES: usize = 64 * 1024;
+
+/// Digests of one file, as lowercase hex
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileDigests {
+    pub size: u64,
+    pub sha256: String,
+    pub sha512: String,
+    /// Only computed with the `blake3` feature
+    pub blake3: Option<String>,
+}
+
+/// Digest everything `reader` yields
+pub fn digest_reader(mut reader: impl Read) -> io::Result<FileDigests> {
+    let mut sha256 = Sha256::new();
+    let mut sha512 = Sha512::new();
+    #[cfg(feature = "blake3")]
+    let mut blake3 = blake3::Hasher::new();
+    let mut size = 0u64;
+    let mut chunk = vec![0u8; CHUNK_BYTES];
+    loop {
+        let read = match reader.read(&mut chunk) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        sha256.update(&chunk[..read]);
+        sha512.update(&chunk[..read]);
+        #[cfg(feature = "blake3")]
+        blake3.update(&chunk[..read]);
+        size += read as u64;
+    }
+
+    #[cfg(feature = "blake3")]
+    let blake3 = Some(blake3.finalize().to_hex().to_string());
+    #[cfg(not(feature = "blake3"))]
+    let blake3 = None;
+
+    Ok(FileDigests {
+        size,
+        sha256: format!("{:x}", sha256.finalize()),
+        sha512: format!("{:x}", sha512.finalize()),
+        blake3,
+    })
+}
+
+/// Digest the file at `path`
+pub fn digest_file(path: &Path) -> io::Result<FileDigests> {
+    digest_reader(File::open(path)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_digests_span_chunks() {
+        let empty = digest_reader(io::empty()).unwrap();
+        assert_eq!(empty.size, 0);
+        assert_eq!(
+            empty.sha256,
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+
+        let abc = digest_reader(&b"abc"[..]).unwrap();
+        assert_eq!(
+            abc.sha256,
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert!(abc.sha512.starts_with("ddaf35a193617aba"));
+
+        // Content larger than one chunk hashes the same as in one piece
+        let content = vec![0x5a; CHUNK_BYTES * 2 + 7];
+        let streamed = digest_reader(&content[..]).unwrap();
+        assert_eq!(streamed.size, content.len() as u64);
+        assert_eq!(streamed.sha256, format!("{:x}", Sha256::digest(&content)));
+    }
+}
@@ -1,49 +1,291 @@
 //! # File System Data Collector
 //!
 //! Collects file metadata (permissions, owner, group) and content for validation.
-use esp_scanner_base::execution::BehaviorHints;
+//!
+//! Every path is checked against the scanner's [`PathPolicy`] before it is
+//! opened or stat'ed. Recursive scans silently skip denied entries. Single
+//! file reads and stats record a [`FileObservation`] so the engine can spot
+//! files that change while the scan runs.
+//!
+//! A missing path is only reported as absent when its parent directory could
+//! be looked at; otherwise the data carries a warning so a check that passes
+//! on it is flagged as vacuous.
+//!
+//! With an `archive_member` behavior the object is a file inside the archive
+//! at `path` (see [`archive`](super::archive)). A missing member is absent; a
+//! missing archive is a warning in metadata mode and not found otherwise.
+//!
+//! `json_record`, `yaml_record`, `toml_record`, `ini_record` and `xml_record`
+//! objects are parsed with [`record_formats`](super::record_formats) into the
+//! same record value. `ini_record` objects may set `comment_chars` and
+//! `duplicate_keys`; `xml_record` objects may set `namespaces`.
+//!
+//! `sshd_config` objects are resolved with [`sshd_config`](super::sshd_config);
+//! every file the config includes goes through the path policy too.
+//!
+//! `mount_point` objects are looked up in the active mount table and in
+//! `/etc/fstab` with [`mounts`](super::mounts). The active table is
+//! `/proc/self/mountinfo`, or `/etc/mtab` when that cannot be found.
+//!
+//! `kernel_cmdline` objects read `/proc/cmdline`, `/etc/default/grub` and the
+//! first grubenv found with [`kernel_cmdline`](super::kernel_cmdline). A
+//! missing GRUB config or grubenv is not an error; nothing is configured there.
+//!
+//! `sudoers` objects are parsed with [`sudoers`](super::sudoers), every
+//! included file going through the path policy. The object's `section`
+//! selects whether user specifications or `Defaults` are collected.
+//!
+//! `x509_certificate` objects name a file, a directory, or a glob in the
+//! last path component; every matching regular file is decoded with
+//! [`x509`](super::x509). A file that cannot be read or decoded becomes a
+//! record carrying an `error` instead of failing the whole object.
+//!
+//! `file_hash` objects select files the same way; every file is streamed
+//! through [`file_hash`](super::file_hash) into a record with its size and
+//! digests, or an `error` when it cannot be read.
+//!
+//! `extended_file_attr` objects select files the same way; every file's
+//! POSIX ACLs and capabilities are read from its extended attributes with
+//! [`file_attrs`](super::file_attrs) into a record, or an `error` when they
+//! cannot be read.
+//!
+//! `file_permission_scan` objects walk the tree below `path` with
+//! [`permission_scan`](super::permission_scan), within the object's depth and
+//! entry limits. Directories the walk cannot read, or does not enter because
+//! of a limit, become warnings.
+//!
+//! With an offline [`ScanTarget`], every object path is resolved inside the
+//! target's root before the path policy sees it, as are included files and
+//! the files a glob or a symlink-following recursive scan reaches; deny and
+//! allow prefixes therefore name host paths under the root. `mount_point`
+//! and `kernel_cmdline` describe the running kernel and are not supported
+//! for an offline target.
+use super::archive::{self, ArchiveError, ArchiveLimits};
+use super::file_attrs;
+use super::ini::{DuplicateKeys, IniOptions};
+use super::kernel_cmdline;
+use super::mounts::{self, MountEntry};
+use super::permission_scan::{self, PermissionFilter, ScanLimits};
+use super::record_formats::{RecordFormat, RecordOptions};
+use super::sshd_config::{self, MatchBlock};
+use super::sudoers;
+use super::xml::XmlOptions;
+use super::{file_hash, x509};
+use esp_scanner_base::execution::comparisons::string::glob_matches;
+use esp_scanner_base::execution::{BehaviorHints, FileObservation};
 use esp_scanner_base::strategies::{
-    CollectedData, CollectionError, CollectionMode, CtnContract, CtnDataCollector,
+    CollectedData, CollectionError, CollectionMode, CtnContract, CtnDataCollector, PathPolicy,
+    ScanTarget,
 };
 use esp_scanner_base::types::common::{RecordData, ResolvedValue};
 use esp_scanner_base::types::execution_context::{ExecutableObject, ExecutableObjectElement};
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Where mount tables are read from
+struct MountTables<'a> {
+    mountinfo: &'a str,
+    mtab: &'a str,
+    fstab: &'a str,
+}
+
+const SYSTEM_MOUNT_TABLES: MountTables<'static> = MountTables {
+    mountinfo: "/proc/self/mountinfo",
+    mtab: "/etc/mtab",
+    fstab: "/etc/fstab",
+};
+
+/// Where the running and configured kernel command lines are read from
+struct KernelCmdlineSources<'a> {
+    cmdline: &'a str,
+    default_grub: &'a str,
+    /// Candidate grubenv files; the first that exists is read
+    grubenv: &'a [&'a str],
+}
+
+const SYSTEM_KERNEL_CMDLINE: KernelCmdlineSources<'static> = KernelCmdlineSources {
+    cmdline: "/proc/cmdline",
+    default_grub: "/etc/default/grub",
+    grubenv: &["/boot/grub2/grubenv", "/boot/grub/grubenv"],
+};
+
+/// Largest file decoded as a certificate; CA bundles are well under this
+const MAX_CERTIFICATE_FILE_BYTES: u64 = 4 * 1024 * 1024;
 
 /// Collector for file system data
 pub struct FileSystemCollector {
     id: String,
+    path_policy: Arc<PathPolicy>,
+    scan_target: Arc<ScanTarget>,
+    archive_limits: ArchiveLimits,
 }
 
 impl FileSystemCollector {
     pub fn new() -> Self {
         Self {
             id: "filesystem_collector".to_string(),
+            path_policy: PathPolicy::unrestricted(),
+            scan_target: ScanTarget::shared_host(),
+            archive_limits: ArchiveLimits::default(),
         }
     }
 
+    /// Restrict collection to paths the policy permits
+    pub fn with_path_policy(mut self, path_policy: Arc<PathPolicy>) -> Self {
+        self.path_policy = path_policy;
+        self
+    }
+
+    /// Read object paths inside the target's root instead of the host's `/`
+    pub fn with_scan_target(mut self, scan_target: Arc<ScanTarget>) -> Self {
+        self.scan_target = scan_target;
+        self
+    }
+
+    /// Size limits for reading archive members
+    pub fn with_archive_limits(mut self, archive_limits: ArchiveLimits) -> Self {
+        self.archive_limits = archive_limits;
+        self
+    }
+
+    /// Refuse paths denied by the scanner path policy
+    fn check_path(&self, path: &str, object_id: &str) -> Result<(), CollectionError> {
+        self.path_policy
+            .check(Path::new(path))
+            .map_err(|reason| CollectionError::PathDenied {
+                object_id: object_id.to_string(),
+                path: path.to_string(),
+                reason,
+            })
+    }
+
+    /// Where `path`, as the policy names it, is read on this host
+    fn locate(&self, path: &str, object_id: &str) -> Result<String, CollectionError> {
+        self.scan_target
+            .resolve(Path::new(path))
+            .map(|located| located.display().to_string())
+            .map_err(|e| CollectionError::CollectionFailed {
+                object_id: object_id.to_string(),
+                reason: format!("Cannot resolve '{}' in the scan root: {}", path, e),
+            })
+    }
+
     /// Extract path from object, handling VAR resolution
     fn extract_path(&self, object: &ExecutableObject) -> Result<String, CollectionError> {
+        self.extract_string(object, "path")
+    }
+
+    /// Extract a required string field from object
+    fn extract_string(
+        &self,
+        object: &ExecutableObject,
+        field: &str,
+    ) -> Result<String, CollectionError> {
+        self.extract_optional_string(object, field)?.ok_or_else(|| {
+            CollectionError::InvalidObjectConfiguration {
+                object_id: object.identifier.clone(),
+                reason: format!("Missing required '{}' field", field),
+            }
+        })
+    }
+
+    /// Extract an optional string field from object
+    fn extract_optional_string(
+        &self,
+        object: &ExecutableObject,
+        field: &str,
+    ) -> Result<Option<String>, CollectionError> {
         for element in &object.elements {
             if let ExecutableObjectElement::Field { name, value, .. } = element {
-                if name == "path" {
+                if name == field {
                     match value {
-                        ResolvedValue::String(s) => return Ok(s.clone()),
+                        ResolvedValue::String(s) => return Ok(Some(s.clone())),
                         _ => {
                             return Err(CollectionError::InvalidObjectConfiguration {
                                 object_id: object.identifier.clone(),
-                                reason: format!("'path' field must be a string, got {:?}", value),
+                                reason: format!(
+                                    "'{}' field must be a string, got {:?}",
+                                    field, value
+                                ),
                             })
                         }
                     }
                 }
             }
         }
+        Ok(None)
+    }
+
+    /// Extract an optional non-negative integer field from object
+    fn extract_optional_count(
+        &self,
+        object: &ExecutableObject,
+        field: &str,
+    ) -> Result<Option<usize>, CollectionError> {
+        for element in &object.elements {
+            if let ExecutableObjectElement::Field { name, value, .. } = element {
+                if name == field {
+                    let count = match value {
+                        ResolvedValue::Integer(n) => usize::try_from(*n).ok(),
+                        ResolvedValue::String(s) => s.trim().parse().ok(),
+                        _ => None,
+                    };
+                    return count.map(Some).ok_or_else(|| {
+                        CollectionError::InvalidObjectConfiguration {
+                            object_id: object.identifier.clone(),
+                            reason: format!(
+                                "'{}' field must be a non-negative integer, got {:?}",
+                                field, value
+                            ),
+                        }
+                    });
+                }
+            }
+        }
+        Ok(None)
+    }
 
-        Err(CollectionError::InvalidObjectConfiguration {
+    /// INI reading options from the object's `comment_chars` and
+    /// `duplicate_keys` fields
+    fn extract_ini_options(
+        &self,
+        object: &ExecutableObject,
+    ) -> Result<IniOptions, CollectionError> {
+        let invalid = |reason: String| CollectionError::InvalidObjectConfiguration {
             object_id: object.identifier.clone(),
-            reason: "Missing required 'path' field".to_string(),
-        })
+            reason,
+        };
+        let mut options = IniOptions::default();
+        if let Some(chars) = self.extract_optional_string(object, "comment_chars")? {
+            options.comment_chars = chars.chars().filter(|c| !c.is_whitespace()).collect();
+            if options.comment_chars.is_empty() {
+                return Err(invalid("'comment_chars' names no characters".to_string()));
+            }
+        }
+        if let Some(mode) = self.extract_optional_string(object, "duplicate_keys")? {
+            options.duplicate_keys = DuplicateKeys::parse(&mode).map_err(invalid)?;
+        }
+        Ok(options)
+    }
+
+    /// XML reading options from the object's comma-separated `namespaces`
+    fn extract_xml_options(
+        &self,
+        object: &ExecutableObject,
+    ) -> Result<XmlOptions, CollectionError> {
+        let namespaces = self
+            .extract_optional_string(object, "namespaces")?
+            .map(|list| {
+                list.split(',')
+                    .map(str::trim)
+                    .filter(|uri| !uri.is_empty())
+                    .map(str::to_string)
+                    .collect()
+            });
+        Ok(XmlOptions { namespaces })
     }
 
     /// Collect metadata via stat() - fast operation
@@ -65,21 +307,11 @@ impl FileSystemCollector {
         data.add_field("exists".to_string(), ResolvedValue::Boolean(exists));
 
         if !exists {
-            // Early return - file doesn't exist
-            data.add_field(
-                "file_mode".to_string(),
-                ResolvedValue::String("".to_string()),
-            );
-            data.add_field(
-                "file_owner".to_string(),
-                ResolvedValue::String("".to_string()),
-            );
-            data.add_field(
-                "file_group".to_string(),
-                ResolvedValue::String("".to_string()),
-            );
-            data.add_field("readable".to_string(), ResolvedValue::Boolean(false));
-            data.add_field("file_size".to_string(), ResolvedValue::Integer(0));
+            // Early return - file doesn't exist, or we couldn't look
+            if let Some(warning) = absence_warning(path_obj) {
+                data.add_warning(warning);
+            }
+            add_absent_metadata(&mut data);
             return Ok(data);
         }
 
@@ -106,6 +338,18 @@ impl FileSystemCollector {
         let readable = fs::File::open(path).is_ok();
         data.add_field("readable".to_string(), ResolvedValue::Boolean(readable));
 
+        // Modification time as epoch seconds, where the platform records it
+        if let Ok(modified) = metadata.modified() {
+            let mtime = chrono::DateTime::<chrono::Utc>::from(modified).timestamp();
+            data.add_field("file_mtime".to_string(), ResolvedValue::Integer(mtime));
+        }
+
+        let observation = FileObservation::new(path).with_size(metadata.len());
+        data.observe_file(match metadata.modified() {
+            Ok(modified) => observation.with_modified(modified),
+            Err(_) => observation,
+        });
+
         // Platform-specific metadata
         #[cfg(unix)]
         {
@@ -151,10 +395,128 @@ impl FileSystemCollector {
         Ok(data)
     }
 
+    /// Collect metadata of an archive member from its archive header
+    fn collect_archive_metadata(
+        &self,
+        path: &str,
+        member: &str,
+        object_id: &str,
+    ) -> Result<CollectedData, CollectionError> {
+        let mut data = CollectedData::new(
+            object_id.to_string(),
+            "file_metadata".to_string(),
+            self.id.clone(),
+        );
+
+        let found = match archive::read_member(Path::new(path), member, false, self.archive_limits)
+        {
+            Ok(found) => Some(found),
+            // Absent from an archive that was read through: confirmed
+            Err(ArchiveError::MemberNotFound) => None,
+            Err(ArchiveError::Io(e)) if e.kind() == std::io::ErrorKind::NotFound => {
+                data.add_warning(format!(
+                    "archive not found: cannot look for '{}' in '{}'",
+                    member, path
+                ));
+                None
+            }
+            Err(e) => return Err(self.archive_error(e, path, member, object_id)),
+        };
+        observe_archive(&mut data, path);
+
+        let Some(found) = found else {
+            data.add_field("exists".to_string(), ResolvedValue::Boolean(false));
+            add_absent_metadata(&mut data);
+            return Ok(data);
+        };
+        data.add_field("exists".to_string(), ResolvedValue::Boolean(true));
+        data.add_field(
+            "file_size".to_string(),
+            ResolvedValue::Integer(found.size as i64),
+        );
+        data.add_field(
+            "readable".to_string(),
+            ResolvedValue::Boolean(found.is_file),
+        );
+        data.add_field(
+            "file_mode".to_string(),
+            ResolvedValue::String(format!("{:04o}", found.mode)),
+        );
+        data.add_field(
+            "file_owner".to_string(),
+            ResolvedValue::String(found.uid.to_string()),
+        );
+        data.add_field(
+            "file_group".to_string(),
+            ResolvedValue::String(found.gid.to_string()),
+        );
+
+        Ok(data)
+    }
+
+    /// Read an archive member as UTF-8 text
+    fn read_archive_text(
+        &self,
+        path: &str,
+        member: &str,
+        object_id: &str,
+        data: &mut CollectedData,
+    ) -> Result<String, CollectionError> {
+        let found = archive::read_member(Path::new(path), member, true, self.archive_limits)
+            .map_err(|e| self.archive_error(e, path, member, object_id))?;
+        observe_archive(data, path);
+        String::from_utf8(found.content).map_err(|_| CollectionError::CollectionFailed {
+            object_id: object_id.to_string(),
+            reason: format!(
+                "Member '{}' of archive '{}' is not valid UTF-8 (binary file)",
+                member, path
+            ),
+        })
+    }
+
+    /// Collection error for an archive member that could not be read; a
+    /// missing archive and a missing member stay distinguishable
+    fn archive_error(
+        &self,
+        error: ArchiveError,
+        path: &str,
+        member: &str,
+        object_id: &str,
+    ) -> CollectionError {
+        match error {
+            ArchiveError::Io(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                CollectionError::ObjectNotFound {
+                    object_id: object_id.to_string(),
+                }
+            }
+            ArchiveError::Io(e) if e.kind() == std::io::ErrorKind::PermissionDenied => {
+                CollectionError::AccessDenied {
+                    object_id: object_id.to_string(),
+                    reason: format!("Cannot read archive '{}': {}", path, e),
+                }
+            }
+            ArchiveError::MemberNotFound => CollectionError::CollectionFailed {
+                object_id: object_id.to_string(),
+                reason: format!("member '{}' not found in archive '{}'", member, path),
+            },
+            ArchiveError::TooLarge(limit) => CollectionError::ResourceLimitExceeded {
+                limit_type: format!("member '{}' of archive '{}': {}", member, path, limit),
+            },
+            e => CollectionError::CollectionFailed {
+                object_id: object_id.to_string(),
+                reason: format!(
+                    "Cannot read member '{}' of archive '{}': {}",
+                    member, path, e
+                ),
+            },
+        }
+    }
+
     /// Collect file content - expensive operation
     fn collect_content(
         &self,
         path: &str,
+        member: Option<&str>,
         object_id: &str,
     ) -> Result<CollectedData, CollectionError> {
         let mut data = CollectedData::new(
@@ -163,6 +525,12 @@ impl FileSystemCollector {
             self.id.clone(),
         );
 
+        if let Some(member) = member {
+            let content = self.read_archive_text(path, member, object_id, &mut data)?;
+            data.add_field("file_content".to_string(), ResolvedValue::String(content));
+            return Ok(data);
+        }
+
         let path_obj = Path::new(path);
 
         // Check existence
@@ -196,56 +564,701 @@ impl FileSystemCollector {
             }
         };
 
+        data.observe_file(FileObservation::new(path).with_content(content.as_bytes()));
         data.add_field("file_content".to_string(), ResolvedValue::String(content));
 
         Ok(data)
     }
 
-    /// Collect JSON file as RecordData/// Collect JSON file as RecordData
-    /// Collect JSON file as RecordData
-    fn collect_json_record(
+    /// Collect the resolved directives of an OpenSSH server config
+    ///
+    /// Unless `global_only` is set, each `Match` block is collected as a
+    /// record of its criteria and effective directives.
+    fn collect_sshd_config(
         &self,
         path: &str,
         object_id: &str,
+        global_only: bool,
     ) -> Result<CollectedData, CollectionError> {
         let mut data = CollectedData::new(
             object_id.to_string(),
-            "json_record".to_string(),
+            "sshd_config".to_string(),
             self.id.clone(),
         );
 
-        let path_obj = Path::new(path);
+        let located = self.locate(path, object_id)?;
+        self.check_path(&located, object_id)?;
+        if !Path::new(&located).exists() {
+            return Err(CollectionError::ObjectNotFound {
+                object_id: object_id.to_string(),
+            });
+        }
 
-        // Check existence
-        if !path_obj.exists() {
+        let mut observations = Vec::new();
+        let locate = |file: &Path| {
+            self.locate(&file.display().to_string(), object_id)
+                .map(PathBuf::from)
+        };
+        let config =
+            sshd_config::parse_sshd_config(Path::new(path), &locate, &mut |file: &Path| {
+                let file = file.display().to_string();
+                self.check_path(&file, object_id)?;
+                let content = fs::read_to_string(&file).map_err(|e| {
+                    if e.kind() == std::io::ErrorKind::PermissionDenied {
+                        CollectionError::AccessDenied {
+                            object_id: object_id.to_string(),
+                            reason: format!("Cannot read '{}': {}", file, e),
+                        }
+                    } else {
+                        CollectionError::CollectionFailed {
+                            object_id: object_id.to_string(),
+                            reason: format!("Failed to read '{}': {}", file, e),
+                        }
+                    }
+                })?;
+                observations.push(FileObservation::new(file).with_content(content.as_bytes()));
+                Ok(content)
+            })?;
+        for observation in observations {
+            data.observe_file(observation);
+        }
+        for warning in &config.warnings {
+            data.add_warning(warning.clone());
+        }
+
+        data.add_field(
+            "directives".to_string(),
+            directives_record(config.global.clone()),
+        );
+        if !global_only {
+            let blocks = config
+                .match_blocks
+                .iter()
+                .map(|block| match_block_record(block, config.effective(block)))
+                .collect();
+            data.add_field(
+                "match_blocks".to_string(),
+                ResolvedValue::Collection(blocks),
+            );
+        }
+        Ok(data)
+    }
+
+    /// Collect the user specifications or `Defaults` of a sudoers policy
+    ///
+    /// Each is a record in `entries`; `section` is `user_spec` or `defaults`.
+    fn collect_sudoers(
+        &self,
+        path: &str,
+        object_id: &str,
+        section: &str,
+    ) -> Result<CollectedData, CollectionError> {
+        let mut data = CollectedData::new(
+            object_id.to_string(),
+            "sudoers".to_string(),
+            self.id.clone(),
+        );
+
+        let located = self.locate(path, object_id)?;
+        self.check_path(&located, object_id)?;
+        if !Path::new(&located).exists() {
             return Err(CollectionError::ObjectNotFound {
                 object_id: object_id.to_string(),
             });
         }
 
-        // Read file content
-        let content = match fs::read_to_string(path) {
-            Ok(c) => c,
-            Err(e) => {
+        let mut observations = Vec::new();
+        let locate = |file: &Path| {
+            self.locate(&file.display().to_string(), object_id)
+                .map(PathBuf::from)
+        };
+        let policy = sudoers::parse_sudoers(Path::new(path), &locate, &mut |file: &Path| {
+            let file = file.display().to_string();
+            self.check_path(&file, object_id)?;
+            let content = fs::read_to_string(&file).map_err(|e| {
+                if e.kind() == std::io::ErrorKind::PermissionDenied {
+                    CollectionError::AccessDenied {
+                        object_id: object_id.to_string(),
+                        reason: format!("Cannot read '{}': {}", file, e),
+                    }
+                } else {
+                    CollectionError::CollectionFailed {
+                        object_id: object_id.to_string(),
+                        reason: format!("Failed to read '{}': {}", file, e),
+                    }
+                }
+            })?;
+            observations.push(FileObservation::new(file).with_content(content.as_bytes()));
+            Ok(content)
+        })?;
+        for observation in observations {
+            data.observe_file(observation);
+        }
+        for warning in &policy.warnings {
+            data.add_warning(warning.clone());
+        }
+
+        let entries = match section {
+            "user_spec" => policy
+                .user_specs
+                .iter()
+                .map(|spec| {
+                    record(vec![
+                        ("user_spec", spec.user.clone().into()),
+                        ("host", spec.host.clone().into()),
+                        ("runas", spec.runas.clone().into()),
+                        ("command", spec.command.clone().into()),
+                        ("nopasswd", spec.nopasswd.into()),
+                    ])
+                })
+                .collect(),
+            "defaults" => policy
+                .defaults
+                .iter()
+                .map(|entry| {
+                    record(vec![
+                        ("binding", entry.binding.clone().into()),
+                        ("name", entry.name.clone().into()),
+                        ("value", entry.value.clone().into()),
+                        ("enabled", entry.enabled.into()),
+                    ])
+                })
+                .collect(),
+            other => {
+                return Err(CollectionError::InvalidObjectConfiguration {
+                    object_id: object_id.to_string(),
+                    reason: format!(
+                        "'section' must be 'user_spec' or 'defaults', got '{}'",
+                        other
+                    ),
+                })
+            }
+        };
+        data.add_field(
+            "section".to_string(),
+            ResolvedValue::String(section.to_string()),
+        );
+        data.add_field("entries".to_string(), ResolvedValue::Collection(entries));
+        Ok(data)
+    }
+
+    /// Read a mount table or other system file; `None` when it does not exist
+    fn read_system_file(
+        &self,
+        path: &str,
+        object_id: &str,
+    ) -> Result<Option<String>, CollectionError> {
+        self.check_path(path, object_id)?;
+        match fs::read_to_string(path) {
+            Ok(content) => Ok(Some(content)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => {
+                Err(CollectionError::AccessDenied {
+                    object_id: object_id.to_string(),
+                    reason: format!("Cannot read '{}': {}", path, e),
+                })
+            }
+            Err(e) => Err(CollectionError::CollectionFailed {
+                object_id: object_id.to_string(),
+                reason: format!("Failed to read '{}': {}", path, e),
+            }),
+        }
+    }
+
+    /// Collect how a mount point is mounted now and how fstab configures it
+    ///
+    /// A mount point that is neither mounted nor configured is still
+    /// collected, with `mounted` and `configured` false.
+    fn collect_mount_point(
+        &self,
+        mount_point: &str,
+        object_id: &str,
+        tables: &MountTables,
+    ) -> Result<CollectedData, CollectionError> {
+        let mut data = CollectedData::new(
+            object_id.to_string(),
+            "mount_point".to_string(),
+            self.id.clone(),
+        );
+
+        let active = match self.read_system_file(tables.mountinfo, object_id)? {
+            Some(content) => mounts::parse_mountinfo(&content),
+            None => match self.read_system_file(tables.mtab, object_id)? {
+                Some(content) => {
+                    data.add_warning(format!(
+                        "'{}' not found, mounts read from '{}'",
+                        tables.mountinfo, tables.mtab
+                    ));
+                    mounts::parse_fstab(&content)
+                }
+                None => {
+                    return Err(CollectionError::CollectionFailed {
+                        object_id: object_id.to_string(),
+                        reason: format!(
+                            "No mount table: neither '{}' nor '{}' exists",
+                            tables.mountinfo, tables.mtab
+                        ),
+                    })
+                }
+            },
+        };
+
+        let configured = match self.read_system_file(tables.fstab, object_id)? {
+            Some(content) => {
+                data.observe_file(
+                    FileObservation::new(tables.fstab).with_content(content.as_bytes()),
+                );
+                mounts::parse_fstab(&content)
+            }
+            None => {
+                data.add_warning(format!("'{}' not found", tables.fstab));
+                Vec::new()
+            }
+        };
+
+        data.add_field(
+            "mount_point".to_string(),
+            ResolvedValue::String(mounts::normalize_mount_point(mount_point)),
+        );
+        add_mount_fields(
+            &mut data,
+            "mounted",
+            "",
+            mounts::find_mount(&active, mount_point),
+        );
+        add_mount_fields(
+            &mut data,
+            "configured",
+            "fstab_",
+            mounts::find_mount(&configured, mount_point),
+        );
+        Ok(data)
+    }
+
+    /// Collect the running kernel's arguments and those GRUB boots with
+    ///
+    /// With an `argument`, `runtime_has` and `configured_has` say whether
+    /// each command line sets it; see [`kernel_cmdline::has_arg`].
+    fn collect_kernel_cmdline(
+        &self,
+        argument: Option<&str>,
+        object_id: &str,
+        sources: &KernelCmdlineSources,
+    ) -> Result<CollectedData, CollectionError> {
+        let mut data = CollectedData::new(
+            object_id.to_string(),
+            "kernel_cmdline".to_string(),
+            self.id.clone(),
+        );
+
+        let runtime_args = match self.read_system_file(sources.cmdline, object_id)? {
+            Some(content) => kernel_cmdline::split_args(&content),
+            None => {
                 return Err(CollectionError::CollectionFailed {
                     object_id: object_id.to_string(),
-                    reason: format!("Failed to read file: {}", e),
-                });
+                    reason: format!("'{}' not found", sources.cmdline),
+                })
+            }
+        };
+
+        let mut configured_args = Vec::new();
+        match self.read_system_file(sources.default_grub, object_id)? {
+            Some(content) => {
+                data.observe_file(
+                    FileObservation::new(sources.default_grub).with_content(content.as_bytes()),
+                );
+                configured_args.extend(kernel_cmdline::parse_default_grub(&content));
+            }
+            None => data.add_warning(format!("'{}' not found", sources.default_grub)),
+        }
+        for grubenv in sources.grubenv {
+            if let Some(content) = self.read_system_file(grubenv, object_id)? {
+                data.observe_file(FileObservation::new(*grubenv).with_content(content.as_bytes()));
+                configured_args.extend(kernel_cmdline::parse_grubenv(&content));
+                break;
+            }
+        }
+
+        if let Some(argument) = argument {
+            data.add_field(
+                "argument".to_string(),
+                ResolvedValue::String(argument.to_string()),
+            );
+            data.add_field(
+                "runtime_has".to_string(),
+                ResolvedValue::Boolean(kernel_cmdline::has_arg(&runtime_args, argument)),
+            );
+            data.add_field(
+                "configured_has".to_string(),
+                ResolvedValue::Boolean(kernel_cmdline::has_arg(&configured_args, argument)),
+            );
+        }
+        for (name, args) in [
+            ("runtime_args", runtime_args),
+            ("configured_args", configured_args),
+        ] {
+            data.add_field(
+                name.to_string(),
+                ResolvedValue::Collection(args.into_iter().map(ResolvedValue::String).collect()),
+            );
+        }
+        Ok(data)
+    }
+
+    /// Files an `x509_certificate`, `file_hash` or `extended_file_attr` path
+    /// selects, sorted
+    ///
+    /// A directory selects its regular files and a glob the regular files
+    /// matching it; hidden files and paths the policy denies are skipped.
+    fn selected_files(&self, path: &str) -> Vec<PathBuf> {
+        let target = Path::new(path);
+        let file_name = target
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or_default();
+        let (dir, pattern) = if file_name.contains(['*', '?']) {
+            match target.parent() {
+                Some(parent) => (parent, Some(file_name)),
+                None => return Vec::new(),
+            }
+        } else if target.is_dir() {
+            (target, None)
+        } else if target.is_file() {
+            return vec![target.to_path_buf()];
+        } else {
+            return Vec::new();
+        };
+
+        let Ok(entries) = fs::read_dir(dir) else {
+            return Vec::new();
+        };
+        let mut files: Vec<PathBuf> = entries
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|file| {
+                let name = file
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .unwrap_or_default();
+                !name.starts_with('.')
+                    && pattern.map_or(true, |pattern| glob_matches(pattern, name).unwrap_or(false))
+            })
+            .filter_map(|file| self.scan_target.contain(&file).ok())
+            .filter(|file| self.path_policy.permits(file) && file.is_file())
+            .collect();
+        files.sort();
+        files
+    }
+
+    /// Collect every certificate in the files `path` selects
+    ///
+    /// Each certificate is a record in `certificates`, named by `source`:
+    /// the file, with `#n` appended for the n-th certificate of a bundle.
+    /// `days_until_expiry` counts whole days from `now`, in epoch seconds.
+    fn collect_certificates(
+        &self,
+        path: &str,
+        object_id: &str,
+        now: i64,
+    ) -> Result<CollectedData, CollectionError> {
+        let mut data = CollectedData::new(
+            object_id.to_string(),
+            "x509_certificate".to_string(),
+            self.id.clone(),
+        );
+
+        let files = self.selected_files(path);
+        if files.is_empty() {
+            if let Some(warning) = absence_warning(Path::new(path)) {
+                data.add_warning(warning);
+            }
+        }
+
+        let mut records = Vec::new();
+        for file in files {
+            let file = file.display().to_string();
+            let content = match fs::metadata(&file) {
+                Ok(metadata) if metadata.len() > MAX_CERTIFICATE_FILE_BYTES => Err(format!(
+                    "file is larger than {} bytes",
+                    MAX_CERTIFICATE_FILE_BYTES
+                )),
+                _ => fs::read(&file).map_err(|e| format!("cannot read file: {}", e)),
+            };
+            let certificates = content.and_then(|content| {
+                data.observe_file(FileObservation::new(&file).with_content(&content));
+                x509::parse_certificates(&content)
+            });
+
+            match certificates {
+                Err(error) => records.push(certificate_error_record(&file, &file, &error)),
+                Ok(certificates) => {
+                    let bundle = certificates.len() > 1;
+                    for (index, certificate) in certificates.into_iter().enumerate() {
+                        let source = if bundle {
+                            format!("{}#{}", file, index + 1)
+                        } else {
+                            file.clone()
+                        };
+                        records.push(match certificate {
+                            Ok(certificate) => {
+                                certificate_record(&file, &source, &certificate, now)
+                            }
+                            Err(error) => certificate_error_record(&file, &source, &error),
+                        });
+                    }
+                }
+            }
+        }
+
+        data.add_field(
+            "certificates".to_string(),
+            ResolvedValue::Collection(records),
+        );
+        Ok(data)
+    }
+
+    /// Collect the size and digests of every file `path` selects
+    ///
+    /// Each file is a record in `files`; one that cannot be read carries an
+    /// `error` instead of digests.
+    fn collect_file_hashes(
+        &self,
+        path: &str,
+        object_id: &str,
+    ) -> Result<CollectedData, CollectionError> {
+        let mut data = CollectedData::new(
+            object_id.to_string(),
+            "file_hash".to_string(),
+            self.id.clone(),
+        );
+
+        let files = self.selected_files(path);
+        if files.is_empty() {
+            if let Some(warning) = absence_warning(Path::new(path)) {
+                data.add_warning(warning);
+            }
+        }
+
+        let mut records = Vec::new();
+        for file in files {
+            let name = file.display().to_string();
+            let mut observation = FileObservation::new(&name);
+            if let Ok(metadata) = fs::metadata(&file) {
+                observation = observation.with_size(metadata.len());
+                if let Ok(modified) = metadata.modified() {
+                    observation = observation.with_modified(modified);
+                }
+            }
+            data.observe_file(observation);
+
+            records.push(match file_hash::digest_file(&file) {
+                Ok(digests) => {
+                    let mut fields = vec![
+                        ("path", name.into()),
+                        ("error", "".into()),
+                        ("size", digests.size.into()),
+                        ("sha256", digests.sha256.into()),
+                        ("sha512", digests.sha512.into()),
+                    ];
+                    if let Some(blake3) = digests.blake3 {
+                        fields.push(("blake3", blake3.into()));
+                    }
+                    record(fields)
+                }
+                Err(e) => record(vec![
+                    ("path", name.into()),
+                    ("error", format!("cannot read file: {}", e).into()),
+                ]),
+            });
+        }
+
+        data.add_field("files".to_string(), ResolvedValue::Collection(records));
+        Ok(data)
+    }
+
+    /// Collect the ACLs and capabilities of every file `path` selects
+    ///
+    /// Each file is a record in `files`; one whose attributes cannot be read
+    /// carries an `error` instead.
+    fn collect_extended_attrs(
+        &self,
+        path: &str,
+        object_id: &str,
+    ) -> Result<CollectedData, CollectionError> {
+        let mut data = CollectedData::new(
+            object_id.to_string(),
+            "extended_file_attr".to_string(),
+            self.id.clone(),
+        );
+
+        let files = self.selected_files(path);
+        if files.is_empty() {
+            if let Some(warning) = absence_warning(Path::new(path)) {
+                data.add_warning(warning);
+            }
+        }
+
+        let records = files
+            .iter()
+            .map(|file| {
+                let name = file.display().to_string();
+                match file_attrs::read_extended_attrs(file) {
+                    Ok(attrs) => record(vec![
+                        ("path", name.into()),
+                        ("error", "".into()),
+                        ("has_extended_acl", attrs.has_extended_acl.into()),
+                        ("acl_entries", attrs.acl_entries.into()),
+                        ("has_capabilities", (!attrs.capabilities.is_empty()).into()),
+                        ("capabilities", attrs.capabilities.into()),
+                    ]),
+                    Err(e) => record(vec![
+                        ("path", name.into()),
+                        (
+                            "error",
+                            format!("cannot read extended attributes: {}", e).into(),
+                        ),
+                    ]),
+                }
+            })
+            .collect();
+
+        data.add_field("files".to_string(), ResolvedValue::Collection(records));
+        Ok(data)
+    }
+
+    /// Collect files below `root` with the permission bits `filter` selects
+    ///
+    /// Besides the given `excludes`, the walk skips the default pseudo
+    /// filesystem paths and the network and pseudo filesystem mounts listed
+    /// in `mountinfo`, when that can be read.
+    fn collect_permission_scan(
+        &self,
+        root: &str,
+        object_id: &str,
+        filter: PermissionFilter,
+        limits: ScanLimits,
+        excludes: &[String],
+        mountinfo: &str,
+    ) -> Result<CollectedData, CollectionError> {
+        let mut data = CollectedData::new(
+            object_id.to_string(),
+            "file_permission_scan".to_string(),
+            self.id.clone(),
+        );
+
+        let root_path = Path::new(root);
+        if fs::symlink_metadata(root_path).is_err() {
+            match absence_warning(root_path) {
+                Some(warning) => data.add_warning(warning),
+                None => data.add_warning(format!("'{}' does not exist", root)),
+            }
+        }
+
+        let mut excluded: Vec<PathBuf> = permission_scan::DEFAULT_EXCLUDES
+            .iter()
+            .map(PathBuf::from)
+            .chain(excludes.iter().map(PathBuf::from))
+            .collect();
+        if self.path_policy.permits(Path::new(mountinfo)) {
+            if let Ok(content) = fs::read_to_string(mountinfo) {
+                excluded.extend(permission_scan::excluded_mounts(&mounts::parse_mountinfo(
+                    &content,
+                )));
+            }
+        }
+        // An excluded path named as the root is still scanned
+        excluded.retain(|path| path != root_path);
+
+        let permits = |path: &Path| self.path_policy.permits(path);
+        let outcome = permission_scan::scan(root_path, filter, limits, &excluded, &permits);
+        for warning in outcome.warnings {
+            data.add_warning(warning);
+        }
+
+        let records = outcome
+            .matches
+            .iter()
+            .map(|found| {
+                record(vec![
+                    ("path", found.path.clone().into()),
+                    ("type", found.file_type.into()),
+                    ("mode", format!("{:04o}", found.mode).into()),
+                    ("suid", found.suid().into()),
+                    ("sgid", found.sgid().into()),
+                    ("sticky", found.sticky().into()),
+                    ("world_writable", found.world_writable().into()),
+                    ("uid", found.uid.into()),
+                    ("gid", found.gid.into()),
+                ])
+            })
+            .collect();
+        data.add_field("files".to_string(), ResolvedValue::Collection(records));
+        data.add_field(
+            "entries_scanned".to_string(),
+            ResolvedValue::Integer(outcome.entries_scanned as i64),
+        );
+        data.add_field(
+            "truncated".to_string(),
+            ResolvedValue::Boolean(outcome.truncated),
+        );
+        Ok(data)
+    }
+
+    /// Collect a JSON, YAML, TOML, INI or XML file as RecordData
+    fn collect_record(
+        &self,
+        path: &str,
+        member: Option<&str>,
+        object_id: &str,
+        format: RecordFormat,
+        options: &RecordOptions,
+    ) -> Result<CollectedData, CollectionError> {
+        let mut data = CollectedData::new(
+            object_id.to_string(),
+            format.ctn_type().to_string(),
+            self.id.clone(),
+        );
+
+        let content = match member {
+            Some(member) => self.read_archive_text(path, member, object_id, &mut data)?,
+            None => {
+                let path_obj = Path::new(path);
+
+                // Check existence
+                if !path_obj.exists() {
+                    return Err(CollectionError::ObjectNotFound {
+                        object_id: object_id.to_string(),
+                    });
+                }
+
+                // Read file content
+                let content = match fs::read_to_string(path) {
+                    Ok(c) => c,
+                    Err(e) => {
+                        return Err(CollectionError::CollectionFailed {
+                            object_id: object_id.to_string(),
+                            reason: format!("Failed to read file: {}", e),
+                        });
+                    }
+                };
+
+                data.observe_file(FileObservation::new(path).with_content(content.as_bytes()));
+                content
             }
         };
 
-        // Parse JSON - FIXED: Use from_json_value instead of from_json_str
-        let json_value: serde_json::Value =
-            serde_json::from_str(&content).map_err(|e| CollectionError::CollectionFailed {
+        let value = format.parse_with(&content, options).map_err(|e| {
+            CollectionError::CollectionFailed {
                 object_id: object_id.to_string(),
-                reason: format!("Failed to parse JSON: {}", e),
-            })?;
+                reason: format!("Failed to parse {}: {}", format.name(), e),
+            }
+        })?;
 
-        let record_data = RecordData::from_json_value(json_value);
+        let record_data = RecordData::from_json_value(value);
 
         // Store as RecordData
         data.add_field(
-            "json_data".to_string(),
+            format.data_field().to_string(),
             ResolvedValue::RecordData(Box::new(record_data)),
         );
 
@@ -312,6 +1325,12 @@ impl FileSystemCollector {
             ResolvedValue::String(all_content),
         );
         data.add_field("file_count".to_string(), ResolvedValue::Integer(file_count));
+        if file_count == 0 {
+            data.add_warning(format!(
+                "pattern matched nothing: no readable files under '{}'",
+                base_path
+            ));
+        }
 
         Ok(data)
     }
@@ -346,7 +1365,20 @@ impl FileSystemCollector {
                 Err(_) => continue, // Skip bad entries
             };
 
-            let path = entry.path();
+            // A followed symlink must not lead out of an offline root
+            let path = if follow_symlinks {
+                match self.scan_target.contain(&entry.path()) {
+                    Ok(path) => path,
+                    Err(_) => continue,
+                }
+            } else {
+                entry.path()
+            };
+
+            // Never descend into or read denied areas
+            if !self.path_policy.permits(&path) {
+                continue;
+            }
 
             // Get filename for hidden check
             let file_name = match path.file_name().and_then(|n| n.to_str()) {
@@ -398,27 +1430,129 @@ impl CtnDataCollector for FileSystemCollector {
         contract: &CtnContract,
         hints: &BehaviorHints,
     ) -> Result<CollectedData, CollectionError> {
-        contract.validate_behavior_hints(hints).map_err(|e| {
+        let behaviors = contract.resolve_behaviors(hints).map_err(|e| {
             CollectionError::CtnContractValidation {
                 reason: e.to_string(),
             }
         })?;
 
-        // Then existing code...
-        let path = self.extract_path(object)?;
+        if contract.ctn_type == "mount_point" {
+            let mount_point = self.extract_string(object, "mount_point")?;
+            return self.collect_mount_point(
+                &mount_point,
+                &object.identifier,
+                &SYSTEM_MOUNT_TABLES,
+            );
+        }
+
+        if contract.ctn_type == "kernel_cmdline" {
+            let argument = self.extract_optional_string(object, "argument")?;
+            return self.collect_kernel_cmdline(
+                argument.as_deref(),
+                &object.identifier,
+                &SYSTEM_KERNEL_CMDLINE,
+            );
+        }
+
+        let named = self.extract_path(object)?;
+        if contract.ctn_type == "sudoers" {
+            let section = self
+                .extract_optional_string(object, "section")?
+                .unwrap_or_else(|| "user_spec".to_string());
+            return self.collect_sudoers(&named, &object.identifier, &section);
+        }
+        if contract.ctn_type == "sshd_config" {
+            return self.collect_sshd_config(
+                &named,
+                &object.identifier,
+                behaviors.flag("global_only"),
+            );
+        }
+        let path = self.locate(&named, &object.identifier)?;
+        self.check_path(&path, &object.identifier)?;
+        if contract.ctn_type == "x509_certificate" {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |elapsed| elapsed.as_secs() as i64);
+            return self.collect_certificates(&path, &object.identifier, now);
+        }
+        if contract.ctn_type == "file_hash" {
+            return self.collect_file_hashes(&path, &object.identifier);
+        }
+        if contract.ctn_type == "extended_file_attr" {
+            return self.collect_extended_attrs(&path, &object.identifier);
+        }
+        if contract.ctn_type == "file_permission_scan" {
+            let invalid = |reason: String| CollectionError::InvalidObjectConfiguration {
+                object_id: object.identifier.clone(),
+                reason,
+            };
+            let filter = match self.extract_optional_string(object, "permissions")? {
+                Some(list) => PermissionFilter::parse(&list).map_err(invalid)?,
+                None => PermissionFilter::default(),
+            };
+            let defaults = ScanLimits::default();
+            let limits = ScanLimits {
+                max_depth: self
+                    .extract_optional_count(object, "max_depth")?
+                    .unwrap_or(defaults.max_depth),
+                max_entries: self
+                    .extract_optional_count(object, "max_entries")?
+                    .unwrap_or(defaults.max_entries),
+            };
+            let excludes: Vec<String> = self
+                .extract_optional_string(object, "exclude")?
+                .map(|list| {
+                    list.split(',')
+                        .map(|path| path.trim().trim_end_matches('/').to_string())
+                        .filter(|path| !path.is_empty())
+                        .collect()
+                })
+                .unwrap_or_default();
+            return self.collect_permission_scan(
+                &path,
+                &object.identifier,
+                filter,
+                limits,
+                &excludes,
+                SYSTEM_MOUNT_TABLES.mountinfo,
+            );
+        }
+        let member = behaviors.string("archive_member");
 
         match contract.collection_strategy.collection_mode {
-            CollectionMode::Metadata => self.collect_metadata(&path, &object.identifier),
+            CollectionMode::Metadata => match member {
+                Some(member) => self.collect_archive_metadata(&path, member, &object.identifier),
+                None => self.collect_metadata(&path, &object.identifier),
+            },
             CollectionMode::Content => {
-                // Check if this is a JSON record request
-                if contract.ctn_type == "json_record" {
-                    return self.collect_json_record(&path, &object.identifier);
+                // JSON, YAML, TOML, INI and XML record requests
+                if let Some(format) = RecordFormat::for_ctn_type(&contract.ctn_type) {
+                    let mut options = RecordOptions::default();
+                    match format {
+                        RecordFormat::Ini => options.ini = self.extract_ini_options(object)?,
+                        RecordFormat::Xml => options.xml = self.extract_xml_options(object)?,
+                        _ => {}
+                    }
+                    return self.collect_record(
+                        &path,
+                        member,
+                        &object.identifier,
+                        format,
+                        &options,
+                    );
+                }
+                if behaviors.flag("recursive_scan") && member.is_some() {
+                    return Err(CollectionError::InvalidObjectConfiguration {
+                        object_id: object.identifier.clone(),
+                        reason: "archive_member cannot be combined with recursive_scan".to_string(),
+                    });
                 }
 
-                if hints.has_flag("recursive_scan") {
-                    let max_depth = hints.get_parameter_as_int("max_depth").unwrap_or(3);
-                    let include_hidden = hints.has_flag("include_hidden");
-                    let follow_symlinks = hints.has_flag("follow_symlinks");
+                if behaviors.flag("recursive_scan") {
+                    let max_depth = behaviors.int("max_depth").unwrap_or(3);
+                    let include_hidden = behaviors.flag("include_hidden");
+                    let follow_symlinks = behaviors.flag("follow_symlinks");
 
                     return self.collect_recursive(
                         &path,
@@ -430,7 +1564,7 @@ impl CtnDataCollector for FileSystemCollector {
                 }
 
                 // ADD THIS LINE - default content collection:
-                self.collect_content(&path, &object.identifier)
+                self.collect_content(&path, member, &object.identifier)
             }
             _ => Err(CollectionError::UnsupportedCollectionMode {
                 collector_id: self.id.clone(),
@@ -444,6 +1578,18 @@ impl CtnDataCollector for FileSystemCollector {
             "file_metadata".to_string(),
             "file_content".to_string(),
             "json_record".to_string(),
+            "yaml_record".to_string(),
+            "toml_record".to_string(),
+            "ini_record".to_string(),
+            "xml_record".to_string(),
+            "sshd_config".to_string(),
+            "mount_point".to_string(),
+            "kernel_cmdline".to_string(),
+            "x509_certificate".to_string(),
+            "sudoers".to_string(),
+            "file_hash".to_string(),
+            "extended_file_attr".to_string(),
+            "file_permission_scan".to_string(),
         ]
     }
 
@@ -463,6 +1609,165 @@ impl CtnDataCollector for FileSystemCollector {
     fn supports_batch_collection(&self) -> bool {
         false
     }
+
+    fn supports_offline_target(&self, ctn_type: &str) -> bool {
+        !matches!(ctn_type, "mount_point" | "kernel_cmdline")
+    }
+}
+
+/// Directives keyed by lowercased keyword, as a record
+fn directives_record(directives: BTreeMap<String, String>) -> ResolvedValue {
+    ResolvedValue::RecordData(Box::new(RecordData::from_field_pairs(
+        directives
+            .into_iter()
+            .map(|(keyword, value)| (keyword, value.into()))
+            .collect(),
+    )))
+}
+
+/// A `Match` block's criteria and effective directives, as a record
+fn match_block_record(block: &MatchBlock, effective: BTreeMap<String, String>) -> ResolvedValue {
+    let directives = effective
+        .into_iter()
+        .map(|(keyword, value)| (keyword, value.into()))
+        .collect();
+    ResolvedValue::RecordData(Box::new(RecordData::from_field_pairs(vec![
+        ("criteria".to_string(), block.criteria.clone().into()),
+        (
+            "directives".to_string(),
+            serde_json::Value::Object(directives),
+        ),
+    ])))
+}
+
+/// A mount's fields under `prefix`, plus the `flag` saying whether it exists
+fn add_mount_fields(
+    data: &mut CollectedData,
+    flag: &str,
+    prefix: &str,
+    entry: Option<&MountEntry>,
+) {
+    data.add_field(flag.to_string(), ResolvedValue::Boolean(entry.is_some()));
+    let entry = entry.cloned().unwrap_or_default();
+    data.add_field(
+        format!("{}device", prefix),
+        ResolvedValue::String(entry.device),
+    );
+    data.add_field(
+        format!("{}fstype", prefix),
+        ResolvedValue::String(entry.fstype),
+    );
+    data.add_field(
+        format!("{}options", prefix),
+        ResolvedValue::Collection(
+            entry
+                .options
+                .into_iter()
+                .map(ResolvedValue::String)
+                .collect(),
+        ),
+    );
+}
+
+/// Named values as a record
+fn record(fields: Vec<(&str, serde_json::Value)>) -> ResolvedValue {
+    ResolvedValue::RecordData(Box::new(RecordData::from_field_pairs(
+        fields
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value))
+            .collect(),
+    )))
+}
+
+/// A decoded certificate, as a record
+fn certificate_record(
+    path: &str,
+    source: &str,
+    certificate: &x509::Certificate,
+    now: i64,
+) -> ResolvedValue {
+    ResolvedValue::RecordData(Box::new(RecordData::from_field_pairs(vec![
+        ("path".to_string(), path.into()),
+        ("source".to_string(), source.into()),
+        ("error".to_string(), "".into()),
+        ("subject".to_string(), certificate.subject.clone().into()),
+        ("issuer".to_string(), certificate.issuer.clone().into()),
+        (
+            "not_before_epoch".to_string(),
+            certificate.not_before.into(),
+        ),
+        ("not_after_epoch".to_string(), certificate.not_after.into()),
+        (
+            "days_until_expiry".to_string(),
+            (certificate.not_after - now).div_euclid(86_400).into(),
+        ),
+        (
+            "key_algorithm".to_string(),
+            certificate.key_algorithm.clone().into(),
+        ),
+        ("key_bits".to_string(), certificate.key_bits.into()),
+        ("self_signed".to_string(), certificate.self_signed.into()),
+    ])))
+}
+
+/// A file or bundle entry that is not a readable certificate, as a record
+fn certificate_error_record(path: &str, source: &str, error: &str) -> ResolvedValue {
+    ResolvedValue::RecordData(Box::new(RecordData::from_field_pairs(vec![
+        ("path".to_string(), path.into()),
+        ("source".to_string(), source.into()),
+        ("error".to_string(), error.into()),
+    ])))
+}
+
+/// Why a path that does not exist might still be there: `None` when its
+/// absence is confirmed (the parent directory exists and was searched)
+fn absence_warning(path: &Path) -> Option<String> {
+    if let Err(e) = fs::symlink_metadata(path) {
+        if e.kind() == std::io::ErrorKind::PermissionDenied {
+            return Some(format!(
+                "permission denied: cannot look for '{}'",
+                path.display()
+            ));
+        }
+    }
+    match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() && !parent.is_dir() => Some(format!(
+            "path not found: parent directory '{}' does not exist",
+            parent.display()
+        )),
+        _ => None,
+    }
+}
+
+/// Fields of a file that is not there
+fn add_absent_metadata(data: &mut CollectedData) {
+    data.add_field(
+        "file_mode".to_string(),
+        ResolvedValue::String("".to_string()),
+    );
+    data.add_field(
+        "file_owner".to_string(),
+        ResolvedValue::String("".to_string()),
+    );
+    data.add_field(
+        "file_group".to_string(),
+        ResolvedValue::String("".to_string()),
+    );
+    data.add_field("readable".to_string(), ResolvedValue::Boolean(false));
+    data.add_field("file_size".to_string(), ResolvedValue::Integer(0));
+    data.add_field("file_mtime".to_string(), ResolvedValue::Integer(0));
+}
+
+/// Record the archive a member was read from, so a change to it mid-scan
+/// is noticed like any other file
+fn observe_archive(data: &mut CollectedData, path: &str) {
+    if let Ok(metadata) = fs::metadata(path) {
+        let observation = FileObservation::new(path).with_size(metadata.len());
+        data.observe_file(match metadata.modified() {
+            Ok(modified) => observation.with_modified(modified),
+            Err(_) => observation,
+        });
+    }
 }
 
 impl Default for FileSystemCollector {
@@ -470,3 +1775,184 @@ impl Default for FileSystemCollector {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mount_point_falls_back_to_mtab() {
+        let dir = tempfile::tempdir().unwrap();
+        let mtab = dir.path().join("mtab");
+        let fstab = dir.path().join("fstab");
+        fs::write(&mtab, "tmpfs /tmp tmpfs rw,nosuid,nodev 0 0\n").unwrap();
+        fs::write(&fstab, "tmpfs /tmp tmpfs defaults,noexec 0 0\n").unwrap();
+        let mountinfo = dir.path().join("mountinfo").display().to_string();
+        let mtab = mtab.display().to_string();
+        let fstab = fstab.display().to_string();
+        let tables = MountTables {
+            mountinfo: &mountinfo,
+            mtab: &mtab,
+            fstab: &fstab,
+        };
+
+        let data = FileSystemCollector::new()
+            .collect_mount_point("/tmp/", "tmp", &tables)
+            .unwrap();
+        assert_eq!(
+            data.get_field("mount_point"),
+            Some(&ResolvedValue::String("/tmp".to_string()))
+        );
+        assert_eq!(
+            data.get_field("mounted"),
+            Some(&ResolvedValue::Boolean(true))
+        );
+        let options = |field: &str| match data.get_field(field) {
+            Some(ResolvedValue::Collection(options)) => options.len(),
+            _ => 0,
+        };
+        assert_eq!(options("options"), 3);
+        assert_eq!(options("fstab_options"), 2);
+        assert!(data.metadata.warnings.iter().any(|w| w.contains("mtab")));
+    }
+
+    #[test]
+    fn test_kernel_cmdline_reads_first_grubenv() {
+        let dir = tempfile::tempdir().unwrap();
+        let cmdline = dir.path().join("cmdline");
+        let default_grub = dir.path().join("grub");
+        let grubenv = dir.path().join("grubenv");
+        fs::write(&cmdline, "BOOT_IMAGE=/vmlinuz ro audit=0\n").unwrap();
+        fs::write(&default_grub, "GRUB_CMDLINE_LINUX=\"rhgb audit=1\"\n").unwrap();
+        fs::write(&grubenv, "kernelopts=root=/dev/vda1 ro page_poison=1\n").unwrap();
+        let cmdline = cmdline.display().to_string();
+        let default_grub = default_grub.display().to_string();
+        let missing = dir.path().join("grub2env").display().to_string();
+        let grubenv = grubenv.display().to_string();
+        let sources = KernelCmdlineSources {
+            cmdline: &cmdline,
+            default_grub: &default_grub,
+            grubenv: &[&missing, &grubenv],
+        };
+
+        let data = FileSystemCollector::new()
+            .collect_kernel_cmdline(Some("audit=1"), "audit", &sources)
+            .unwrap();
+        assert_eq!(
+            data.get_field("runtime_has"),
+            Some(&ResolvedValue::Boolean(false))
+        );
+        assert_eq!(
+            data.get_field("configured_has"),
+            Some(&ResolvedValue::Boolean(true))
+        );
+        let args = |field: &str| match data.get_field(field) {
+            Some(ResolvedValue::Collection(args)) => args.len(),
+            _ => 0,
+        };
+        assert_eq!(args("runtime_args"), 3);
+        assert_eq!(args("configured_args"), 5);
+    }
+
+    #[test]
+    fn test_file_hash_glob_yields_one_record_per_file() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("a.cnf"), "abc").unwrap();
+        fs::write(dir.path().join("b.cnf"), "").unwrap();
+        fs::write(dir.path().join(".hidden.cnf"), "skipped").unwrap();
+        let pattern = dir.path().join("*.cnf").display().to_string();
+
+        let data = FileSystemCollector::new()
+            .collect_file_hashes(&pattern, "ssl")
+            .unwrap();
+        let Some(ResolvedValue::Collection(records)) = data.get_field("files") else {
+            panic!("files not collected");
+        };
+        assert_eq!(records.len(), 2);
+        let ResolvedValue::RecordData(record) = &records[0] else {
+            panic!("file is not a record");
+        };
+        assert_eq!(
+            record.get_field_by_path("sha256").and_then(|v| v.as_str()),
+            Some("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad")
+        );
+        assert_eq!(
+            record.get_field_by_path("size").and_then(|v| v.as_u64()),
+            Some(3)
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_offline_target_reads_includes_and_globs_inside_root() {
+        let image = tempfile::tempdir().unwrap();
+        let conf_d = image.path().join("etc/ssh/sshd_config.d");
+        fs::create_dir_all(&conf_d).unwrap();
+        fs::write(
+            image.path().join("etc/ssh/sshd_config"),
+            "Include /etc/ssh/sshd_config.d/*.conf\nPermitRootLogin yes\n",
+        )
+        .unwrap();
+        fs::write(conf_d.join("10-hardening.conf"), "PermitRootLogin no\n").unwrap();
+        fs::create_dir(image.path().join("certs")).unwrap();
+        fs::write(image.path().join("image-only.cnf"), "abc").unwrap();
+        std::os::unix::fs::symlink("/image-only.cnf", image.path().join("certs/link.cnf")).unwrap();
+
+        let target = Arc::new(ScanTarget::offline(image.path()).unwrap());
+        let collector = FileSystemCollector::new().with_scan_target(target);
+
+        let data = collector
+            .collect_sshd_config("/etc/ssh/sshd_config", "sshd", true)
+            .unwrap();
+        let Some(ResolvedValue::RecordData(directives)) = data.get_field("directives") else {
+            panic!("directives not collected");
+        };
+        assert_eq!(
+            directives
+                .get_field_by_path("permitrootlogin")
+                .and_then(|v| v.as_str()),
+            Some("no")
+        );
+
+        let pattern = collector.locate("/certs/*.cnf", "certs").unwrap();
+        let data = collector.collect_file_hashes(&pattern, "certs").unwrap();
+        let Some(ResolvedValue::Collection(records)) = data.get_field("files") else {
+            panic!("files not collected");
+        };
+        let ResolvedValue::RecordData(record) = &records[0] else {
+            panic!("file is not a record");
+        };
+        assert!(record
+            .get_field_by_path("path")
+            .and_then(|v| v.as_str())
+            .is_some_and(|path| path.ends_with("image-only.cnf")));
+        assert!(!collector.supports_offline_target("kernel_cmdline"));
+    }
+
+    #[test]
+    fn test_certificate_glob_reports_corrupt_files() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("junk.pem"), "not a certificate\n").unwrap();
+        fs::write(dir.path().join("notes.txt"), "not matched\n").unwrap();
+        let pattern = dir.path().join("*.pem").display().to_string();
+
+        let data = FileSystemCollector::new()
+            .collect_certificates(&pattern, "pki", 0)
+            .unwrap();
+        let Some(ResolvedValue::Collection(records)) = data.get_field("certificates") else {
+            panic!("certificates not collected");
+        };
+        assert_eq!(records.len(), 1);
+        let ResolvedValue::RecordData(record) = &records[0] else {
+            panic!("certificate is not a record");
+        };
+        assert!(record
+            .get_field_by_path("source")
+            .and_then(|v| v.as_str())
+            .is_some_and(|source| source.ends_with("junk.pem")));
+        assert!(record
+            .get_field_by_path("error")
+            .and_then(|v| v.as_str())
+            .is_some_and(|error| error.contains("no certificate")));
+    }
+}
@@ -0,0 +1,192 @@
+//! # INI Records
+//!
+//! Parses INI-style files, such as `pwquality.conf`, systemd units and yum
+//! repositories, into the record `ini_record` checks are evaluated against.
+//! Each `[section]` becomes a nested record, so `main.gpgcheck` names the
+//! `gpgcheck` key of `[main]`. Keys before the first section header belong
+//! to the default section and sit at the top of the record, so a file with
+//! no sections is addressed by bare key names. A section that appears twice
+//! is merged into one.
+//!
+//! Lines whose first non-blank character is a comment character are
+//! skipped; comments after a value are kept as part of it, as `pwquality`
+//! and systemd read them. Keys and values are separated by the first `=` or,
+//! failing that, `:`. A value written as a decimal integer, or as `true` or
+//! `false`, is held as that type; anything else, including `0644`, is a
+//! string.
+//!
+//! A key that repeats within a section either keeps its last value or, with
+//! [`DuplicateKeys::List`], every key becomes a list of its values in file
+//! order, for entity checks over `section.key.*`.
+
+use super::record_formats::typed_value;
+
+/// Comment characters used when an object names none
+pub const DEFAULT_COMMENT_CHARS: &str = "#;";
+
+/// What a key repeated within a section holds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DuplicateKeys {
+    /// The last value wins
+    #[default]
+    Last,
+    /// Every key is a list of all its values
+    List,
+}
+
+impl DuplicateKeys {
+    pub fn parse(s: &str) -> Result<Self, String> {
+        match s {
+            "last" => Ok(Self::Last),
+            "list" => Ok(Self::List),
+            other => Err(format!(
+                "unknown duplicate_keys '{}'; expected last or list",
+                other
+            )),
+        }
+    }
+}
+
+/// How an INI file is read
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IniOptions {
+    pub comment_chars: Vec<char>,
+    pub duplicate_keys: DuplicateKeys,
+}
+
+impl Default for IniOptions {
+    fn default() -> Self {
+        Self {
+            comment_chars: DEFAULT_COMMENT_CHARS.chars().collect(),
+            duplicate_keys: DuplicateKeys::default(),
+        }
+    }
+}
+
+/// Parse INI content into a record value
+pub fn parse(content: &str, options: &IniOptions) -> Result<serde_json::Value, String> {
+    let mut root = serde_json::Map::new();
+    let mut section: Option<String> = None;
+
+    for (index, raw) in content.lines().enumerate() {
+        let line = raw.trim();
+        if line.is_empty() || line.starts_with(options.comment_chars.as_slice()) {
+            continue;
+        }
+
+        if let Some(header) = line.strip_prefix('[') {
+            let name = header
+                .strip_suffix(']')
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+                .ok_or_else(|| format!("line {}: malformed section header", index + 1))?;
+            match root.get(name) {
+                Some(serde_json::Value::Object(_)) | None => {}
+                Some(_) => {
+                    return Err(format!(
+                        "line {}: section [{}] has the name of a default-section key",
+                        index + 1,
+                        name
+                    ))
+                }
+            }
+            root.entry(name.to_string())
+                .or_insert_with(|| serde_json::Value::Object(serde_json::Map::new()));
+            section = Some(name.to_string());
+            continue;
+        }
+
+        let (name, value) = line
+            .split_once('=')
+            .or_else(|| line.split_once(':'))
+            .ok_or_else(|| format!("line {}: expected key = value", index + 1))?;
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(format!("line {}: empty key", index + 1));
+        }
+
+        let fields = match &section {
+            Some(section) => match root.get_mut(section) {
+                Some(serde_json::Value::Object(fields)) => fields,
+                _ => unreachable!("section records are created with their header"),
+            },
+            None => &mut root,
+        };
+        let value = typed_value(value.trim());
+        match options.duplicate_keys {
+            DuplicateKeys::Last => {
+                fields.insert(name.to_string(), value);
+            }
+            DuplicateKeys::List => {
+                let entry = fields
+                    .entry(name.to_string())
+                    .or_insert_with(|| serde_json::Value::Array(Vec::new()));
+                match entry {
+                    serde_json::Value::Array(values) => values.push(value),
+                    _ => {
+                        return Err(format!(
+                            "line {}: key '{}' has the name of a section",
+                            index + 1,
+                            name
+                        ))
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(serde_json::Value::Object(root))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_sections_default_section_and_duplicates() {
+        let content = "\
+# pwquality-style keys before any section
+minlen = 14
+umask = 0077
+; a yum repository
+[epel]
+enabled=1
+gpgcheck = true
+baseurl = http://a.example/$basearch
+[Service]
+ExecStartPre=/bin/true
+ExecStartPre=/bin/echo ready # not a comment
+[epel]
+enabled = 0
+";
+        let value = parse(content, &IniOptions::default()).unwrap();
+        assert_eq!(value["minlen"], 14);
+        assert_eq!(value["umask"], "0077");
+        assert_eq!(value["epel"]["enabled"], 0);
+        assert_eq!(value["epel"]["gpgcheck"], true);
+        assert_eq!(
+            value["Service"]["ExecStartPre"],
+            "/bin/echo ready # not a comment"
+        );
+
+        let listed = IniOptions {
+            comment_chars: vec!['#'],
+            duplicate_keys: DuplicateKeys::List,
+        };
+        assert!(
+            parse(content, &listed).is_err(),
+            "';' is no longer a comment"
+        );
+        let value = parse(&content.replace("; a yum", "# a yum"), &listed).unwrap();
+        assert_eq!(value["epel"]["enabled"], json!([1, 0]));
+        assert_eq!(value["minlen"], json!([14]));
+        assert_eq!(
+            value["Service"]["ExecStartPre"],
+            json!(["/bin/true", "/bin/echo ready # not a comment"])
+        );
+
+        assert!(parse("[unterminated\n", &IniOptions::default()).is_err());
+        assert!(DuplicateKeys::parse("first").is_err());
+    }
+}
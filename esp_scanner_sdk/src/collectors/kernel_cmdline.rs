@@ -0,0 +1,148 @@
+//! # Kernel Command Line Reader
+//!
+//! Parses the running kernel's `/proc/cmdline` and the command line GRUB is
+//! configured to boot with, for the `kernel_cmdline` criterion type.
+//!
+//! The configured command line is `GRUB_CMDLINE_LINUX` followed by
+//! `GRUB_CMDLINE_LINUX_DEFAULT` from `/etc/default/grub`, read as shell
+//! assignments so quoting is unwrapped and `$GRUB_CMDLINE_LINUX` in a later
+//! assignment expands to the earlier value. When a grubenv exists, its
+//! `kernelopts` follow.
+//!
+//! Arguments are split on whitespace outside double quotes, as the kernel
+//! does; the quotes themselves are dropped, so `dyndbg="file x +p"` is one
+//! argument with an unquoted value.
+
+use super::environment::parse_assignments;
+
+/// GRUB variables that make up the configured command line, in boot order
+const GRUB_CMDLINE_VARIABLES: &[&str] = &["GRUB_CMDLINE_LINUX", "GRUB_CMDLINE_LINUX_DEFAULT"];
+
+/// Split a kernel command line into arguments
+pub fn split_args(cmdline: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut arg = String::new();
+    let mut quoted = false;
+    for c in cmdline.chars() {
+        match c {
+            '"' => quoted = !quoted,
+            c if c.is_whitespace() && !quoted => {
+                if !arg.is_empty() {
+                    args.push(std::mem::take(&mut arg));
+                }
+            }
+            c => arg.push(c),
+        }
+    }
+    if !arg.is_empty() {
+        args.push(arg);
+    }
+    args
+}
+
+/// Arguments `/etc/default/grub` configures, in boot order
+pub fn parse_default_grub(content: &str) -> Vec<String> {
+    let mut values: Vec<(&str, String)> = Vec::new();
+    for (name, value) in parse_assignments(content) {
+        let Some(variable) = GRUB_CMDLINE_VARIABLES.iter().find(|v| **v == name) else {
+            continue;
+        };
+        let value = expand(&value, &values);
+        values.retain(|(assigned, _)| assigned != variable);
+        values.push((variable, value));
+    }
+    GRUB_CMDLINE_VARIABLES
+        .iter()
+        .filter_map(|variable| values.iter().find(|(assigned, _)| assigned == variable))
+        .flat_map(|(_, value)| split_args(value))
+        .collect()
+}
+
+/// Replace `$NAME` and `${NAME}` with the values assigned so far
+fn expand(value: &str, values: &[(&str, String)]) -> String {
+    let mut expanded = value.to_string();
+    for (name, assigned) in values {
+        expanded = expanded
+            .replace(&format!("${{{}}}", name), assigned)
+            .replace(&format!("${}", name), assigned);
+    }
+    expanded
+}
+
+/// Arguments of a grubenv's `kernelopts` line
+pub fn parse_grubenv(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .rev()
+        .find_map(|line| line.strip_prefix("kernelopts="))
+        .map(split_args)
+        .unwrap_or_default()
+}
+
+/// The key of an argument: `audit=1` -> `audit`, `quiet` -> `quiet`
+fn arg_key(arg: &str) -> &str {
+    arg.split_once('=').map_or(arg, |(name, _)| name)
+}
+
+/// Whether `args` set `requested`
+///
+/// A bare name such as `audit` is set by the flag or by any value of it.
+/// With a value, as in `audit=1`, the last argument with that key must have
+/// exactly that value, since the kernel takes the last one; `audit=1` is not
+/// set by `audit=0` or by `audit=1 ... audit=0`.
+pub fn has_arg(args: &[String], requested: &str) -> bool {
+    match requested.split_once('=') {
+        Some((name, _)) => args
+            .iter()
+            .rev()
+            .find(|arg| arg_key(arg) == name)
+            .is_some_and(|arg| arg == requested),
+        None => args.iter().any(|arg| arg_key(arg) == requested),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grub_quoting_and_expansion() {
+        let grub = "\
+GRUB_TIMEOUT=5
+GRUB_CMDLINE_LINUX=\"crashkernel=auto rhgb quiet\"
+GRUB_CMDLINE_LINUX=\"$GRUB_CMDLINE_LINUX audit=1 audit_backlog_limit=8192\"
+GRUB_CMDLINE_LINUX_DEFAULT='page_poison=1'
+";
+        assert_eq!(
+            parse_default_grub(grub),
+            vec![
+                "crashkernel=auto",
+                "rhgb",
+                "quiet",
+                "audit=1",
+                "audit_backlog_limit=8192",
+                "page_poison=1"
+            ]
+        );
+        assert_eq!(
+            split_args("ro dyndbg=\"file x +p\"  quiet\n"),
+            vec!["ro", "dyndbg=file x +p", "quiet"]
+        );
+        assert_eq!(
+            parse_grubenv("# GRUB Environment Block\nkernelopts=root=/dev/vda1 ro audit=1\n"),
+            vec!["root=/dev/vda1", "ro", "audit=1"]
+        );
+    }
+
+    #[test]
+    fn test_key_value_matching() {
+        let args = split_args("ro audit=1 quiet slub_debug=P audit=0");
+        assert!(!has_arg(&args, "audit=1"));
+        assert!(has_arg(&args, "audit=0"));
+        assert!(has_arg(&args, "audit"));
+        assert!(has_arg(&args, "quiet"));
+        assert!(!has_arg(&args, "quiet=1"));
+        assert!(!has_arg(&args, "slub"));
+        assert!(!has_arg(&args, "page_poison=1"));
+    }
+}
@@ -0,0 +1,390 @@
+//! # Kernel Module Collector
+//!
+//! Reports whether a kernel module is loaded (`lsmod`) and how modprobe is
+//! configured to treat it (`modprobe.d` files), for the `kernel_module`
+//! criterion type.
+//!
+//! Configuration is read the way kmod reads it: `*.conf` files from
+//! `/etc/modprobe.d`, `/run/modprobe.d`, `/usr/local/lib/modprobe.d`,
+//! `/usr/lib/modprobe.d` and `/lib/modprobe.d`, where a file shadows any
+//! same-named file in a later directory, processed in file name order. Lines
+//! starting with `#` are comments and a trailing `\` continues a line. When
+//! several files set an `install` command for a module, the first one wins,
+//! as with modprobe; every file mentioning the module is listed. Directories
+//! the [`PathPolicy`] denies are skipped.
+//!
+//! Module names treat `-` and `_` as the same character and are reported
+//! with underscores, as `lsmod` prints them.
+use esp_scanner_base::execution::{BehaviorHints, FileObservation};
+use esp_scanner_base::strategies::{
+    CollectedData, CollectionError, CommandError, CtnContract, CtnDataCollector, PathPolicy,
+    SystemCommandExecutor,
+};
+use esp_scanner_base::types::common::ResolvedValue;
+use esp_scanner_base::types::execution_context::{ExecutableObject, ExecutableObjectElement};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// modprobe.d directories in kmod's precedence order
+const MODPROBE_DIRS: &[&str] = &[
+    "/etc/modprobe.d",
+    "/run/modprobe.d",
+    "/usr/local/lib/modprobe.d",
+    "/usr/lib/modprobe.d",
+    "/lib/modprobe.d",
+];
+
+/// Install commands that make loading a module fail or do nothing
+const DISABLING_COMMANDS: &[&str] = &["/bin/false", "/usr/bin/false", "/bin/true", "/usr/bin/true"];
+
+/// Normalize a module name the way kmod compares them
+fn module_name(name: &str) -> String {
+    name.replace('-', "_")
+}
+
+/// Names of loaded modules from `lsmod` output
+fn parse_lsmod(stdout: &str) -> Vec<String> {
+    stdout
+        .lines()
+        .skip_while(|line| line.starts_with("Module"))
+        .filter_map(|line| line.split_whitespace().next())
+        .map(module_name)
+        .collect()
+}
+
+/// What the modprobe configuration says about one module
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct ModuleConfig {
+    blacklisted: bool,
+    /// The first `install` command for the module
+    install: Option<String>,
+    /// Files with a directive naming the module, in processing order
+    files: Vec<String>,
+}
+
+impl ModuleConfig {
+    fn install_disabled(&self) -> bool {
+        self.install
+            .as_deref()
+            .and_then(|command| command.split_whitespace().next())
+            .is_some_and(|program| DISABLING_COMMANDS.contains(&program))
+    }
+}
+
+/// Join continued lines and drop comments and blank lines
+fn logical_lines(content: &str) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut pending = String::new();
+    for line in content.lines() {
+        if pending.is_empty() && line.trim_start().starts_with('#') {
+            continue;
+        }
+        match line.strip_suffix('\\') {
+            Some(continued) => {
+                pending.push_str(continued);
+                pending.push(' ');
+            }
+            None => {
+                pending.push_str(line);
+                if !pending.trim().is_empty() {
+                    lines.push(pending.trim().to_string());
+                }
+                pending.clear();
+            }
+        }
+    }
+    if !pending.trim().is_empty() {
+        lines.push(pending.trim().to_string());
+    }
+    lines
+}
+
+/// Apply one configuration file's directives for `module`
+fn apply_config(config: &mut ModuleConfig, content: &str, file: &str, module: &str) {
+    for line in logical_lines(content) {
+        let mut words = line.split_whitespace();
+        let (Some(directive), Some(name)) = (words.next(), words.next()) else {
+            continue;
+        };
+        if module_name(name) != module {
+            continue;
+        }
+        match directive {
+            "blacklist" => config.blacklisted = true,
+            "install" => {
+                if config.install.is_none() {
+                    config.install = Some(words.collect::<Vec<_>>().join(" "));
+                }
+            }
+            "options" | "remove" | "softdep" | "alias" => {}
+            _ => continue,
+        }
+        if !config.files.iter().any(|f| f == file) {
+            config.files.push(file.to_string());
+        }
+    }
+}
+
+/// Collector for kernel module state and configuration
+#[derive(Clone)]
+pub struct KernelModuleCollector {
+    id: String,
+    executor: SystemCommandExecutor,
+    path_policy: Arc<PathPolicy>,
+}
+
+impl KernelModuleCollector {
+    /// Create a collector running `lsmod` through the given executor
+    pub fn new(id: impl Into<String>, executor: SystemCommandExecutor) -> Self {
+        Self {
+            id: id.into(),
+            executor,
+            path_policy: PathPolicy::unrestricted(),
+        }
+    }
+
+    /// Restrict configuration reads to paths the policy permits
+    pub fn with_path_policy(mut self, path_policy: Arc<PathPolicy>) -> Self {
+        self.path_policy = path_policy;
+        self
+    }
+
+    fn loaded_modules(&self, object_id: &str) -> Result<Vec<String>, CollectionError> {
+        let output = self
+            .executor
+            .execute("lsmod", &[], None)
+            .map_err(|e| match e {
+                CommandError::ProgramNotFound { .. } => CollectionError::MissingCapability {
+                    capability: "lsmod (kmod is not installed)".to_string(),
+                },
+                _ => CollectionError::CollectionFailed {
+                    object_id: object_id.to_string(),
+                    reason: format!("lsmod failed: {}", e),
+                },
+            })?;
+        if output.exit_code != 0 {
+            return Err(CollectionError::CollectionFailed {
+                object_id: object_id.to_string(),
+                reason: format!(
+                    "lsmod exited with {}: {}",
+                    output.exit_code,
+                    output.stderr.trim()
+                ),
+            });
+        }
+        Ok(parse_lsmod(&output.stdout))
+    }
+
+    /// The `*.conf` files kmod would read from `dirs`, in processing order
+    fn config_files(
+        &self,
+        dirs: &[&str],
+        object_id: &str,
+    ) -> Result<Vec<PathBuf>, CollectionError> {
+        let mut by_name: BTreeMap<String, PathBuf> = BTreeMap::new();
+        for dir in dirs {
+            let dir = Path::new(dir);
+            if !dir.is_dir() || !self.path_policy.permits(dir) {
+                continue;
+            }
+            let entries = fs::read_dir(dir).map_err(|e| CollectionError::CollectionFailed {
+                object_id: object_id.to_string(),
+                reason: format!("Failed to list '{}': {}", dir.display(), e),
+            })?;
+            for path in entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.path())
+            {
+                let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+                    continue;
+                };
+                if name.ends_with(".conf") && path.is_file() {
+                    // Earlier directories shadow same-named files in later ones
+                    by_name.entry(name.to_string()).or_insert(path);
+                }
+            }
+        }
+        Ok(by_name.into_values().collect())
+    }
+
+    fn module_config(
+        &self,
+        dirs: &[&str],
+        module: &str,
+        object_id: &str,
+        data: &mut CollectedData,
+    ) -> Result<ModuleConfig, CollectionError> {
+        let mut config = ModuleConfig::default();
+        for path in self.config_files(dirs, object_id)? {
+            let file = path.display().to_string();
+            let content = fs::read_to_string(&path).map_err(|e| match e.kind() {
+                std::io::ErrorKind::PermissionDenied => CollectionError::AccessDenied {
+                    object_id: object_id.to_string(),
+                    reason: format!("Cannot read '{}': {}", file, e),
+                },
+                _ => CollectionError::CollectionFailed {
+                    object_id: object_id.to_string(),
+                    reason: format!("Failed to read '{}': {}", file, e),
+                },
+            })?;
+            data.observe_file(FileObservation::new(&file).with_content(content.as_bytes()));
+            apply_config(&mut config, &content, &file, module);
+        }
+        Ok(config)
+    }
+}
+
+fn object_string(
+    object: &ExecutableObject,
+    field: &str,
+) -> Result<Option<String>, CollectionError> {
+    for element in &object.elements {
+        if let ExecutableObjectElement::Field { name, value, .. } = element {
+            if name == field {
+                return match value {
+                    ResolvedValue::String(s) => Ok(Some(s.clone())),
+                    _ => Err(CollectionError::InvalidObjectConfiguration {
+                        object_id: object.identifier.clone(),
+                        reason: format!("'{}' field must be a string, got {:?}", field, value),
+                    }),
+                };
+            }
+        }
+    }
+    Ok(None)
+}
+
+impl CtnDataCollector for KernelModuleCollector {
+    fn collect_for_ctn_with_hints(
+        &self,
+        object: &ExecutableObject,
+        contract: &CtnContract,
+        _hints: &BehaviorHints,
+    ) -> Result<CollectedData, CollectionError> {
+        let object_id = &object.identifier;
+        let module = object_string(object, "module_name")?
+            .map(|name| module_name(&name))
+            .ok_or_else(|| CollectionError::InvalidObjectConfiguration {
+                object_id: object_id.clone(),
+                reason: "Missing required 'module_name' field".to_string(),
+            })?;
+        let config_dir = object_string(object, "config_dir")?;
+        let dirs: Vec<&str> = match &config_dir {
+            Some(dir) => vec![dir.as_str()],
+            None => MODPROBE_DIRS.to_vec(),
+        };
+
+        let mut data = CollectedData::new(
+            object_id.clone(),
+            contract.ctn_type.clone(),
+            self.id.clone(),
+        );
+
+        let loaded = self.loaded_modules(object_id)?.contains(&module);
+        let config = self.module_config(&dirs, &module, object_id, &mut data)?;
+
+        data.add_field("module_name".to_string(), ResolvedValue::String(module));
+        data.add_field("loaded".to_string(), ResolvedValue::Boolean(loaded));
+        data.add_field(
+            "blacklisted".to_string(),
+            ResolvedValue::Boolean(config.blacklisted),
+        );
+        data.add_field(
+            "install_disabled".to_string(),
+            ResolvedValue::Boolean(config.install_disabled()),
+        );
+        data.add_field(
+            "install_directive".to_string(),
+            ResolvedValue::String(config.install.unwrap_or_default()),
+        );
+        data.add_field(
+            "config_files".to_string(),
+            ResolvedValue::String(config.files.join(",")),
+        );
+        Ok(data)
+    }
+
+    fn supported_ctn_types(&self) -> Vec<String> {
+        vec!["kernel_module".to_string()]
+    }
+
+    fn validate_ctn_compatibility(&self, contract: &CtnContract) -> Result<(), CollectionError> {
+        if !self.supported_ctn_types().contains(&contract.ctn_type) {
+            return Err(CollectionError::CtnContractValidation {
+                reason: format!("CTN type '{}' not supported", contract.ctn_type),
+            });
+        }
+        Ok(())
+    }
+
+    fn collector_id(&self) -> &str {
+        &self.id
+    }
+
+    fn supports_batch_collection(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_lsmod() {
+        let modules = parse_lsmod(
+            "Module                  Size  Used by\n\
+             usb_storage            81920  0\n\
+             nf_tables             344064  1 nft_chain_nat\n",
+        );
+        assert_eq!(modules, ["usb_storage", "nf_tables"]);
+    }
+
+    #[test]
+    fn test_continuations_comments_and_names() {
+        let mut config = ModuleConfig::default();
+        apply_config(
+            &mut config,
+            "# install usb-storage /bin/sh\n\
+             install usb-storage \\\n    /bin/false\n\
+             blacklist  usb_storage\n\
+             blacklist cramfs\n",
+            "cis.conf",
+            "usb_storage",
+        );
+        assert!(config.blacklisted);
+        assert_eq!(config.install.as_deref(), Some("/bin/false"));
+        assert!(config.install_disabled());
+        assert_eq!(config.files, ["cis.conf"]);
+    }
+
+    #[test]
+    fn test_first_install_wins_across_files() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("50-vendor.conf"),
+            "install usb-storage /sbin/modprobe --ignore-install usb-storage\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("10-cis.conf"),
+            "install usb-storage /bin/true\n",
+        )
+        .unwrap();
+        fs::write(dir.path().join("notes.txt"), "blacklist usb-storage\n").unwrap();
+
+        let collector = KernelModuleCollector::new("kmod", SystemCommandExecutor::new());
+        let mut data = CollectedData::new("o".into(), "kernel_module".into(), "kmod".into());
+        let dir_name = dir.path().display().to_string();
+        let config = collector
+            .module_config(&[&dir_name], "usb_storage", "o", &mut data)
+            .unwrap();
+        assert_eq!(config.install.as_deref(), Some("/bin/true"));
+        assert!(config.install_disabled());
+        assert!(!config.blacklisted);
+        assert_eq!(config.files.len(), 2);
+        assert!(config.files[0].ends_with("10-cis.conf"));
+    }
+}
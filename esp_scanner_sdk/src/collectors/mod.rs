@@ -1,9 +1,32 @@
 //! # Data Collectors Module
 
+pub mod account;
+pub mod archive;
+pub mod audit;
 pub mod command;
 pub mod computed_values;
+pub mod environment;
+pub mod file_attrs;
+pub mod file_hash;
 pub mod filesystem;
+pub mod ini;
+pub mod kernel_cmdline;
+pub mod kernel_module;
+pub mod mounts;
+pub mod osquery;
+pub mod permission_scan;
+pub mod record_formats;
+pub mod scheduled_jobs;
+pub mod sshd_config;
+pub mod sudoers;
+pub mod unit_properties;
+pub mod x509;
+pub mod xml;
 
+pub use account::AccountCollector;
+pub use audit::AuditRulesCollector;
 pub use command::CommandCollector;
 pub use computed_values::ComputedValuesCollector;
 pub use filesystem::FileSystemCollector;
+pub use kernel_module::KernelModuleCollector;
+pub use osquery::{OsqueryCollector, OsqueryExport, OsqueryMapping};
@@ -0,0 +1,159 @@
+//! # Mount Table Reader
+//!
+//! Parses the kernel's mount table and `/etc/fstab` for the `mount_point`
+//! criterion type.
+//!
+//! `/proc/self/mountinfo` is preferred: its options are the per-mount flags
+//! (`nodev`, `nosuid`, `noexec`, ...) followed by the filesystem's own, with
+//! duplicates dropped. `/etc/mtab` and `/etc/fstab` share one format, six
+//! whitespace-separated columns of which the first four are used. In every
+//! table, spaces and other special characters in paths are octal escapes
+//! such as `\040`.
+//!
+//! When a mount point appears more than once, the last entry wins: it is the
+//! mount that is visible, and the one `mount -a` would leave on top.
+
+/// One mount, either active or configured
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MountEntry {
+    pub mount_point: String,
+    pub device: String,
+    pub fstype: String,
+    pub options: Vec<String>,
+}
+
+/// Decode the octal escapes used for special characters in mount tables
+fn unescape(field: &str) -> String {
+    let bytes = field.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'\\' {
+            let digits = bytes.get(i + 1..i + 4).unwrap_or_default();
+            if digits.len() == 3 && digits.iter().all(|d| (b'0'..=b'7').contains(d)) {
+                let value = digits.iter().fold(0u32, |n, d| n * 8 + u32::from(d - b'0'));
+                out.push(value as u8);
+                i += 4;
+                continue;
+            }
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// Drop a trailing `/` so `/tmp/` and `/tmp` name the same mount point
+pub fn normalize_mount_point(path: &str) -> String {
+    match path.trim_end_matches('/') {
+        "" => "/".to_string(),
+        trimmed => trimmed.to_string(),
+    }
+}
+
+/// Comma-separated options, with duplicates removed
+fn push_options(options: &mut Vec<String>, list: &str) {
+    for option in list.split(',').filter(|o| !o.is_empty()) {
+        if !options.iter().any(|o| o == option) {
+            options.push(option.to_string());
+        }
+    }
+}
+
+/// Entries of `/proc/self/mountinfo`
+///
+/// Each line is `id parent major:minor root mount_point options
+/// [optional fields...] - fstype source super_options`.
+pub fn parse_mountinfo(content: &str) -> Vec<MountEntry> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let (mount, filesystem) = line.split_once(" - ")?;
+            let mount: Vec<&str> = mount.split_whitespace().collect();
+            let filesystem: Vec<&str> = filesystem.split_whitespace().collect();
+            if mount.len() < 6 || filesystem.len() < 2 {
+                return None;
+            }
+            let mut options = Vec::new();
+            push_options(&mut options, mount[5]);
+            if let Some(super_options) = filesystem.get(2) {
+                push_options(&mut options, super_options);
+            }
+            Some(MountEntry {
+                mount_point: normalize_mount_point(&unescape(mount[4])),
+                device: unescape(filesystem[1]),
+                fstype: filesystem[0].to_string(),
+                options,
+            })
+        })
+        .collect()
+}
+
+/// Entries of `/etc/fstab` or `/etc/mtab`, skipping comments and blank lines
+pub fn parse_fstab(content: &str) -> Vec<MountEntry> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 3 {
+                return None;
+            }
+            let mut options = Vec::new();
+            push_options(&mut options, fields.get(3).copied().unwrap_or("defaults"));
+            Some(MountEntry {
+                mount_point: normalize_mount_point(&unescape(fields[1])),
+                device: unescape(fields[0]),
+                fstype: fields[2].to_string(),
+                options,
+            })
+        })
+        .collect()
+}
+
+/// The entry in effect for `mount_point`: the last one listed
+pub fn find_mount<'a>(entries: &'a [MountEntry], mount_point: &str) -> Option<&'a MountEntry> {
+    let mount_point = normalize_mount_point(mount_point);
+    entries
+        .iter()
+        .rev()
+        .find(|entry| entry.mount_point == mount_point)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_mountinfo() {
+        let entries = parse_mountinfo(
+            "22 1 253:0 / / rw,relatime shared:1 - xfs /dev/mapper/rhel-root rw,seclabel\n\
+             41 22 0:36 / /tmp rw,nosuid,nodev,noexec shared:20 - tmpfs tmpfs rw,seclabel\n\
+             42 22 0:37 / /mnt/my\\040share rw - cifs //srv/share rw\n",
+        );
+        assert_eq!(entries.len(), 3);
+
+        let tmp = find_mount(&entries, "/tmp/").unwrap();
+        assert_eq!(tmp.device, "tmpfs");
+        assert_eq!(tmp.fstype, "tmpfs");
+        assert_eq!(tmp.options, ["rw", "nosuid", "nodev", "noexec", "seclabel"]);
+
+        assert!(find_mount(&entries, "/mnt/my share").is_some());
+        assert_eq!(find_mount(&entries, "/").unwrap().fstype, "xfs");
+    }
+
+    #[test]
+    fn test_parse_fstab_last_entry_wins() {
+        let entries = parse_fstab(
+            "# /etc/fstab\n\
+             \n\
+             UUID=1234 /     xfs   defaults        0 0\n\
+             tmpfs     /tmp  tmpfs defaults        0 0\n\
+             tmpfs     /tmp/ tmpfs nodev,nosuid,noexec 0 0\n",
+        );
+        let tmp = find_mount(&entries, "/tmp").unwrap();
+        assert_eq!(tmp.options, ["nodev", "nosuid", "noexec"]);
+        assert!(find_mount(&entries, "/var").is_none());
+    }
+}
@@ -0,0 +1,541 @@
+//! # osquery Export Collector
+//!
+//! Evaluates policies offline against osquery results collected elsewhere,
+//! so fleets that already ship osquery snapshots don't touch endpoints twice.
+//!
+//! An [`OsqueryMapping`] says, per criterion type, which osquery table holds
+//! the data, which column matches the object's key field, and how columns
+//! become the collected fields the normal executors expect. The built-in
+//! mapping covers `file_metadata` (`file`), `rpm_package` (`rpm_packages`),
+//! `systemd_service` (`systemd_units`) and `sysctl_parameter`
+//! (`system_controls`); a JSON or TOML file can replace it.
+//!
+//! Criterion types without a mapped table, or whose table is missing from the
+//! export, fail collection with [`CollectionError::DataUnavailable`], which the
+//! engine reports as not evaluated with reason `data_unavailable`.
+use esp_scanner_base::execution::BehaviorHints;
+use esp_scanner_base::strategies::{CollectedData, CollectionError, CtnContract, CtnDataCollector};
+use esp_scanner_base::types::common::ResolvedValue;
+use esp_scanner_base::types::execution_context::{ExecutableObject, ExecutableObjectElement};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use std::sync::Arc;
+
+/// Built-in criterion type to osquery table mapping
+const DEFAULT_MAPPING: &str = include_str!("osquery_mapping.json");
+
+/// Criterion types whose data an osquery export may hold
+const EXPORT_BACKED_CTN_TYPES: &[&str] = &[
+    "file_metadata",
+    "file_content",
+    "json_record",
+    "yaml_record",
+    "toml_record",
+    "ini_record",
+    "xml_record",
+    "rpm_package",
+    "dpkg_package",
+    "systemd_service",
+    "systemd_unit_property",
+    "sysctl_parameter",
+    "selinux_status",
+    "sshd_config",
+    "user_account",
+    "audit_rules",
+    "firewalld_zone",
+    "kernel_module",
+    "mount_point",
+    "kernel_cmdline",
+    "listening_port",
+    "x509_certificate",
+    "sudoers",
+    "password_policy",
+    "selinux_boolean",
+    "selinux_file_context",
+    "file_hash",
+    "extended_file_attr",
+    "file_permission_scan",
+    "environment_variable",
+    "scheduled_job",
+];
+
+/// One osquery result row; osquery reports every column as a string
+pub type OsqueryRow = HashMap<String, String>;
+
+/// Rows from an osquery export, grouped by table
+#[derive(Debug, Clone, Default)]
+pub struct OsqueryExport {
+    tables: HashMap<String, Vec<OsqueryRow>>,
+}
+
+impl OsqueryExport {
+    /// Parse an export in any of the accepted layouts:
+    ///
+    /// - an object of table name to rows (`{"rpm_packages": [{...}]}`)
+    /// - snapshot log records (`{"name": "...", "snapshot": [{...}]}`), as a
+    ///   single object, an array, or one record per line
+    ///
+    /// A snapshot record's table is its query `name` after the last `/`, so
+    /// scheduled queries should be named after the table they select from.
+    pub fn from_json(content: &str) -> Result<Self, String> {
+        let mut export = Self::default();
+        match serde_json::from_str::<serde_json::Value>(content) {
+            Ok(serde_json::Value::Array(records)) => {
+                for record in &records {
+                    export.add_snapshot_record(record)?;
+                }
+            }
+            Ok(record) if record.get("snapshot").is_some() => {
+                export.add_snapshot_record(&record)?;
+            }
+            Ok(serde_json::Value::Object(tables)) => {
+                for (table, rows) in &tables {
+                    export.add_rows(table, rows)?;
+                }
+            }
+            Ok(_) => return Err("osquery export must be a JSON object or array".to_string()),
+            Err(_) => {
+                for (index, line) in content.lines().enumerate() {
+                    if line.trim().is_empty() {
+                        continue;
+                    }
+                    let record: serde_json::Value = serde_json::from_str(line).map_err(|e| {
+                        format!("Invalid osquery export at line {}: {}", index + 1, e)
+                    })?;
+                    export.add_snapshot_record(&record)?;
+                }
+            }
+        }
+        Ok(export)
+    }
+
+    /// Load an export file
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Cannot read osquery export '{}': {}", path.display(), e))?;
+        Self::from_json(&content)
+    }
+
+    /// Rows for a table, if the export contains it
+    pub fn rows(&self, table: &str) -> Option<&[OsqueryRow]> {
+        self.tables.get(table).map(Vec::as_slice)
+    }
+
+    fn add_snapshot_record(&mut self, record: &serde_json::Value) -> Result<(), String> {
+        let name = record
+            .get("name")
+            .and_then(|n| n.as_str())
+            .ok_or("osquery snapshot record has no 'name'")?;
+        let table = name.rsplit('/').next().unwrap_or(name);
+        let rows = record
+            .get("snapshot")
+            .ok_or_else(|| format!("osquery record '{}' has no 'snapshot' rows", name))?;
+        self.add_rows(table, rows)
+    }
+
+    fn add_rows(&mut self, table: &str, rows: &serde_json::Value) -> Result<(), String> {
+        let rows = rows
+            .as_array()
+            .ok_or_else(|| format!("osquery table '{}' rows must be an array", table))?;
+        let entry = self.tables.entry(table.to_string()).or_default();
+        for row in rows {
+            let columns = row
+                .as_object()
+                .ok_or_else(|| format!("osquery table '{}' has a non-object row", table))?;
+            entry.push(
+                columns
+                    .iter()
+                    .filter_map(|(column, value)| match value {
+                        serde_json::Value::Null => None,
+                        serde_json::Value::String(s) => Some((column.clone(), s.clone())),
+                        other => Some((column.clone(), other.to_string())),
+                    })
+                    .collect(),
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Criterion type to osquery table mapping
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct OsqueryMapping {
+    /// Mapping per criterion type
+    pub criteria: BTreeMap<String, TableMapping>,
+}
+
+/// How one criterion type's collected fields come from an osquery table
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TableMapping {
+    /// osquery table name
+    pub table: String,
+    /// Which row belongs to an object
+    pub key: KeyMapping,
+    /// Collected field to column conversion
+    #[serde(default)]
+    pub columns: BTreeMap<String, ColumnMapping>,
+    /// Fields set whenever a row matched
+    #[serde(default)]
+    pub constants: BTreeMap<String, serde_json::Value>,
+    /// Fields set when the table has no row for the object
+    #[serde(default)]
+    pub absent: BTreeMap<String, serde_json::Value>,
+}
+
+/// Object field matched against a table column
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct KeyMapping {
+    /// Object field holding the key (e.g. `package_name`)
+    pub object_field: String,
+    /// Column compared with it (e.g. `name`)
+    pub column: String,
+    /// Suffix the column may carry beyond the object value (e.g. `.service`)
+    #[serde(default)]
+    pub suffix: Option<String>,
+    /// Collected field that echoes the object key, if the executor expects one
+    #[serde(default)]
+    pub field: Option<String>,
+}
+
+/// Source and conversion for one collected field
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ColumnMapping {
+    /// Column copied verbatim
+    #[serde(default)]
+    pub column: Option<String>,
+    /// Several columns joined, e.g. `{version}-{release}`
+    #[serde(default)]
+    pub template: Option<String>,
+    /// Collect a boolean: whether the source equals this string
+    #[serde(default)]
+    pub equals: Option<String>,
+    /// Type of the collected value
+    #[serde(default, rename = "type")]
+    pub value_type: ColumnType,
+}
+
+/// Collected value type for a mapped column
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ColumnType {
+    #[default]
+    String,
+    Int,
+    Boolean,
+}
+
+impl Default for OsqueryMapping {
+    fn default() -> Self {
+        Self::from_json(DEFAULT_MAPPING).expect("built-in osquery mapping is valid")
+    }
+}
+
+impl OsqueryMapping {
+    /// Parse a JSON mapping document
+    pub fn from_json(content: &str) -> Result<Self, String> {
+        serde_json::from_str(content).map_err(|e| format!("Invalid osquery mapping: {}", e))
+    }
+
+    /// Parse a TOML mapping document
+    pub fn from_toml(content: &str) -> Result<Self, String> {
+        toml::from_str(content).map_err(|e| format!("Invalid osquery mapping: {}", e))
+    }
+
+    /// Load a mapping file; `.toml` files are TOML, anything else JSON
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Cannot read osquery mapping '{}': {}", path.display(), e))?;
+        if path.extension().is_some_and(|ext| ext == "toml") {
+            Self::from_toml(&content)
+        } else {
+            Self::from_json(&content)
+        }
+    }
+}
+
+impl TableMapping {
+    /// The row for `lookup`, matching the column exactly or with the suffix
+    fn find_row<'a>(&self, rows: &'a [OsqueryRow], lookup: &str) -> Option<&'a OsqueryRow> {
+        rows.iter().find(|row| {
+            row.get(&self.key.column).is_some_and(|value| {
+                value == lookup
+                    || self
+                        .key
+                        .suffix
+                        .as_deref()
+                        .is_some_and(|suffix| value.strip_suffix(suffix) == Some(lookup))
+            })
+        })
+    }
+}
+
+impl ColumnMapping {
+    /// Collected value for a row; `None` if a column is missing or won't convert
+    fn value(&self, row: &OsqueryRow) -> Option<ResolvedValue> {
+        let source = match (&self.column, &self.template) {
+            (Some(column), _) => row.get(column)?.clone(),
+            (None, Some(template)) => render_template(template, row)?,
+            (None, None) => return None,
+        };
+        if let Some(expected) = &self.equals {
+            return Some(ResolvedValue::Boolean(&source == expected));
+        }
+        match self.value_type {
+            ColumnType::String => Some(ResolvedValue::String(source)),
+            ColumnType::Int => source.trim().parse().ok().map(ResolvedValue::Integer),
+            ColumnType::Boolean => match source.trim() {
+                "1" | "true" => Some(ResolvedValue::Boolean(true)),
+                "0" | "false" => Some(ResolvedValue::Boolean(false)),
+                _ => None,
+            },
+        }
+    }
+}
+
+/// Replace `{column}` placeholders with row values
+fn render_template(template: &str, row: &OsqueryRow) -> Option<String> {
+    let mut rendered = String::new();
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        let close = open + rest[open..].find('}')?;
+        rendered.push_str(&rest[..open]);
+        rendered.push_str(row.get(&rest[open + 1..close])?);
+        rest = &rest[close + 1..];
+    }
+    rendered.push_str(rest);
+    Some(rendered)
+}
+
+/// Mapping constant to collected value
+fn constant_value(value: &serde_json::Value) -> Option<ResolvedValue> {
+    match value {
+        serde_json::Value::String(s) => Some(ResolvedValue::String(s.clone())),
+        serde_json::Value::Bool(b) => Some(ResolvedValue::Boolean(*b)),
+        serde_json::Value::Number(n) => n
+            .as_i64()
+            .map(ResolvedValue::Integer)
+            .or_else(|| n.as_f64().map(ResolvedValue::Float)),
+        _ => None,
+    }
+}
+
+/// Collector that serves pre-collected osquery rows instead of reading the host
+#[derive(Clone)]
+pub struct OsqueryCollector {
+    id: String,
+    export: Arc<OsqueryExport>,
+    mapping: Arc<OsqueryMapping>,
+}
+
+impl OsqueryCollector {
+    pub fn new(export: Arc<OsqueryExport>, mapping: Arc<OsqueryMapping>) -> Self {
+        Self {
+            id: "osquery_export".to_string(),
+            export,
+            mapping,
+        }
+    }
+
+    /// String value of an object field
+    fn object_field(
+        &self,
+        object: &ExecutableObject,
+        field_name: &str,
+    ) -> Result<String, CollectionError> {
+        object
+            .elements
+            .iter()
+            .find_map(|element| match element {
+                ExecutableObjectElement::Field {
+                    name,
+                    value: ResolvedValue::String(s),
+                    ..
+                } if name == field_name => Some(s.clone()),
+                _ => None,
+            })
+            .ok_or_else(|| CollectionError::InvalidObjectConfiguration {
+                object_id: object.identifier.clone(),
+                reason: format!("Missing required string field '{}'", field_name),
+            })
+    }
+}
+
+impl CtnDataCollector for OsqueryCollector {
+    fn collect_for_ctn_with_hints(
+        &self,
+        object: &ExecutableObject,
+        contract: &CtnContract,
+        _hints: &BehaviorHints,
+    ) -> Result<CollectedData, CollectionError> {
+        let unavailable = |reason: String| CollectionError::DataUnavailable {
+            object_id: object.identifier.clone(),
+            reason,
+        };
+
+        let table_mapping = self
+            .mapping
+            .criteria
+            .get(&contract.ctn_type)
+            .ok_or_else(|| {
+                unavailable(format!(
+                    "no osquery table mapped for criterion type '{}'",
+                    contract.ctn_type
+                ))
+            })?;
+        let rows = self.export.rows(&table_mapping.table).ok_or_else(|| {
+            unavailable(format!(
+                "osquery export has no '{}' table",
+                table_mapping.table
+            ))
+        })?;
+
+        let lookup = self.object_field(object, &table_mapping.key.object_field)?;
+        let mut data = CollectedData::new(
+            object.identifier.clone(),
+            contract.ctn_type.clone(),
+            self.id.clone(),
+        );
+        if let Some(field) = &table_mapping.key.field {
+            data.add_field(field.clone(), ResolvedValue::String(lookup.clone()));
+        }
+
+        let (fields, row) = match table_mapping.find_row(rows, &lookup) {
+            Some(row) => (&table_mapping.constants, Some(row)),
+            None => (&table_mapping.absent, None),
+        };
+        for (name, value) in fields {
+            if let Some(value) = constant_value(value) {
+                data.add_field(name.clone(), value);
+            }
+        }
+        if let Some(row) = row {
+            for (name, column) in &table_mapping.columns {
+                if let Some(value) = column.value(row) {
+                    data.add_field(name.clone(), value);
+                }
+            }
+        }
+
+        data.set_platform_metadata(serde_json::json!({
+            "source": "osquery",
+            "table": table_mapping.table,
+            "matched": row.is_some(),
+        }));
+        Ok(data)
+    }
+
+    fn supported_ctn_types(&self) -> Vec<String> {
+        EXPORT_BACKED_CTN_TYPES
+            .iter()
+            .map(|t| t.to_string())
+            .collect()
+    }
+
+    fn validate_ctn_compatibility(&self, contract: &CtnContract) -> Result<(), CollectionError> {
+        if !EXPORT_BACKED_CTN_TYPES.contains(&contract.ctn_type.as_str()) {
+            return Err(CollectionError::UnsupportedCtnType {
+                ctn_type: contract.ctn_type.clone(),
+                collector_id: self.id.clone(),
+            });
+        }
+        Ok(())
+    }
+
+    fn collector_id(&self) -> &str {
+        &self.id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(columns: &[(&str, &str)]) -> OsqueryRow {
+        columns
+            .iter()
+            .map(|(c, v)| (c.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_export_layouts_agree() {
+        let by_table = r#"{"rpm_packages": [{"name": "openssl", "version": "3.0.7"}]}"#;
+        let snapshot = r#"{"name": "pack/esp/rpm_packages", "snapshot": [{"name": "openssl", "version": "3.0.7"}]}"#;
+        let array = format!("[{}]", snapshot);
+        let lines = format!("{}\n\n{}\n", snapshot, snapshot);
+
+        for content in [by_table, snapshot, array.as_str()] {
+            let export = OsqueryExport::from_json(content).unwrap();
+            assert_eq!(export.rows("rpm_packages").unwrap().len(), 1, "{}", content);
+        }
+        let export = OsqueryExport::from_json(&lines).unwrap();
+        assert_eq!(export.rows("rpm_packages").unwrap().len(), 2);
+        assert!(export.rows("file").is_none());
+    }
+
+    #[test]
+    fn test_column_conversions() {
+        let row = row(&[
+            ("version", "3.0.7"),
+            ("release", "27.el9"),
+            ("size", "42"),
+            ("active_state", "active"),
+        ]);
+        let template = ColumnMapping {
+            column: None,
+            template: Some("{version}-{release}".to_string()),
+            equals: None,
+            value_type: ColumnType::String,
+        };
+        assert_eq!(
+            template.value(&row),
+            Some(ResolvedValue::String("3.0.7-27.el9".to_string()))
+        );
+
+        let size = ColumnMapping {
+            column: Some("size".to_string()),
+            template: None,
+            equals: None,
+            value_type: ColumnType::Int,
+        };
+        assert_eq!(size.value(&row), Some(ResolvedValue::Integer(42)));
+
+        let active = ColumnMapping {
+            column: Some("active_state".to_string()),
+            template: None,
+            equals: Some("active".to_string()),
+            value_type: ColumnType::String,
+        };
+        assert_eq!(active.value(&row), Some(ResolvedValue::Boolean(true)));
+
+        let missing = ColumnMapping {
+            column: Some("arch".to_string()),
+            ..size
+        };
+        assert_eq!(missing.value(&row), None);
+    }
+
+    #[test]
+    fn test_default_mapping_and_toml_mapping() {
+        let mapping = OsqueryMapping::default();
+        assert_eq!(mapping.criteria["rpm_package"].table, "rpm_packages");
+        assert!(!mapping.criteria.contains_key("file_content"));
+
+        let toml_mapping = OsqueryMapping::from_toml(
+            r#"
+            [criteria.sysctl_parameter]
+            table = "system_controls"
+            key = { object_field = "parameter_name", column = "name" }
+            columns = { value = { column = "current_value" } }
+            "#,
+        )
+        .unwrap();
+        assert_eq!(toml_mapping.criteria["sysctl_parameter"].key.column, "name");
+        assert!(OsqueryMapping::from_json(r#"{"criteria": {"x": {"tabel": "y"}}}"#).is_err());
+    }
+}
@@ -0,0 +1,356 @@
+//! # Permission Scan
+//!
+//! Walks a directory tree for files with special permission bits, for the
+//! `file_permission_scan` criterion type: set-user-ID and set-group-ID
+//! regular files, and world-writable files and directories.
+//!
+//! The walk never follows symlinks and is bounded twice: by depth below the
+//! root and by the number of entries looked at. Reaching either limit stops
+//! that part of the walk with a warning rather than an error, as does a
+//! directory that cannot be read. Excluded paths are not entered: the
+//! defaults are the kernel's pseudo filesystems, and every mount of a
+//! network or pseudo filesystem type found in the mount table.
+use super::mounts::MountEntry;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Paths never walked into
+pub const DEFAULT_EXCLUDES: &[&str] = &["/proc", "/sys", "/dev", "/run"];
+
+/// Filesystem types whose mounts are never walked into
+const EXCLUDED_FSTYPES: &[&str] = &[
+    "nfs",
+    "nfs4",
+    "cifs",
+    "smb3",
+    "smbfs",
+    "ncpfs",
+    "afs",
+    "ceph",
+    "glusterfs",
+    "9p",
+    "fuse.sshfs",
+    "fuse.s3fs",
+    "davfs",
+    "proc",
+    "sysfs",
+    "devtmpfs",
+    "devpts",
+    "cgroup",
+    "cgroup2",
+    "debugfs",
+    "tracefs",
+    "securityfs",
+    "autofs",
+    "binfmt_misc",
+];
+
+/// Depth below the root walked by default
+pub const DEFAULT_MAX_DEPTH: usize = 32;
+
+/// Entries looked at by default before the walk stops
+pub const DEFAULT_MAX_ENTRIES: usize = 500_000;
+
+/// Unreadable directories named one by one before the rest are counted
+const MAX_LISTED_DENIED: usize = 20;
+
+const S_ISUID: u32 = 0o4000;
+const S_ISGID: u32 = 0o2000;
+const S_ISVTX: u32 = 0o1000;
+const S_IWOTH: u32 = 0o0002;
+
+/// Which permission bits select a file; a file matching any is reported
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PermissionFilter {
+    pub suid: bool,
+    pub sgid: bool,
+    pub world_writable: bool,
+}
+
+impl PermissionFilter {
+    /// Parse a comma-separated list of `suid`, `sgid` and `world_writable`
+    pub fn parse(list: &str) -> Result<Self, String> {
+        let mut filter = Self {
+            suid: false,
+            sgid: false,
+            world_writable: false,
+        };
+        for name in list.split(',').map(str::trim).filter(|n| !n.is_empty()) {
+            match name {
+                "suid" => filter.suid = true,
+                "sgid" => filter.sgid = true,
+                "world_writable" => filter.world_writable = true,
+                other => {
+                    return Err(format!(
+                        "unknown permission '{}'; expected suid, sgid or world_writable",
+                        other
+                    ))
+                }
+            }
+        }
+        if !(filter.suid || filter.sgid || filter.world_writable) {
+            return Err("no permission selected".to_string());
+        }
+        Ok(filter)
+    }
+
+    /// Whether an entry of this kind and mode is selected
+    ///
+    /// Set-ID bits only count on regular files; on directories they are
+    /// routine and mean something else. Symlinks, whose mode is always
+    /// 0777, and special files are never selected.
+    fn selects(&self, is_file: bool, is_dir: bool, mode: u32) -> bool {
+        (self.suid && is_file && mode & S_ISUID != 0)
+            || (self.sgid && is_file && mode & S_ISGID != 0)
+            || (self.world_writable && (is_file || is_dir) && mode & S_IWOTH != 0)
+    }
+}
+
+impl Default for PermissionFilter {
+    fn default() -> Self {
+        Self {
+            suid: true,
+            sgid: true,
+            world_writable: true,
+        }
+    }
+}
+
+/// How far a walk may go
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScanLimits {
+    pub max_depth: usize,
+    pub max_entries: usize,
+}
+
+impl Default for ScanLimits {
+    fn default() -> Self {
+        Self {
+            max_depth: DEFAULT_MAX_DEPTH,
+            max_entries: DEFAULT_MAX_ENTRIES,
+        }
+    }
+}
+
+/// A file the filter selected
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PermissionMatch {
+    pub path: String,
+    /// `file` or `directory`
+    pub file_type: &'static str,
+    pub mode: u32,
+    pub uid: u32,
+    pub gid: u32,
+}
+
+impl PermissionMatch {
+    pub fn suid(&self) -> bool {
+        self.mode & S_ISUID != 0
+    }
+
+    pub fn sgid(&self) -> bool {
+        self.mode & S_ISGID != 0
+    }
+
+    pub fn sticky(&self) -> bool {
+        self.mode & S_ISVTX != 0
+    }
+
+    pub fn world_writable(&self) -> bool {
+        self.mode & S_IWOTH != 0
+    }
+}
+
+/// What a walk found
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ScanOutcome {
+    /// Selected files, in path order
+    pub matches: Vec<PermissionMatch>,
+    pub entries_scanned: usize,
+    /// Whether a limit stopped part of the walk
+    pub truncated: bool,
+    pub warnings: Vec<String>,
+}
+
+/// Mount points of network and pseudo filesystems, from a mount table
+pub fn excluded_mounts(entries: &[MountEntry]) -> Vec<PathBuf> {
+    entries
+        .iter()
+        .filter(|entry| EXCLUDED_FSTYPES.contains(&entry.fstype.as_str()))
+        .map(|entry| PathBuf::from(&entry.mount_point))
+        .collect()
+}
+
+/// Walk `root` for files `filter` selects
+///
+/// Paths in `excludes`, and paths `permits` rejects, are skipped along with
+/// everything below them. The root itself is always looked at.
+pub fn scan(
+    root: &Path,
+    filter: PermissionFilter,
+    limits: ScanLimits,
+    excludes: &[PathBuf],
+    permits: &dyn Fn(&Path) -> bool,
+) -> ScanOutcome {
+    let mut outcome = ScanOutcome::default();
+    let mut denied = Vec::new();
+    let mut too_deep = 0usize;
+    let mut stack = vec![(root.to_path_buf(), 0usize)];
+
+    while let Some((path, depth)) = stack.pop() {
+        if outcome.entries_scanned >= limits.max_entries {
+            outcome.truncated = true;
+            outcome.warnings.push(format!(
+                "walk stopped after {} entries; raise max_entries to scan further",
+                limits.max_entries
+            ));
+            break;
+        }
+        outcome.entries_scanned += 1;
+
+        let Ok(metadata) = fs::symlink_metadata(&path) else {
+            continue;
+        };
+        let mode = mode_of(&metadata);
+        let is_dir = metadata.is_dir();
+        if filter.selects(metadata.is_file(), is_dir, mode) {
+            let (uid, gid) = owner_of(&metadata);
+            outcome.matches.push(PermissionMatch {
+                path: path.display().to_string(),
+                file_type: if is_dir { "directory" } else { "file" },
+                mode: mode & 0o7777,
+                uid,
+                gid,
+            });
+        }
+        if !is_dir {
+            continue;
+        }
+        if depth >= limits.max_depth {
+            too_deep += 1;
+            continue;
+        }
+
+        let entries = match fs::read_dir(&path) {
+            Ok(entries) => entries,
+            Err(e) => {
+                denied.push(format!("{}: {}", path.display(), e));
+                continue;
+            }
+        };
+        let mut children: Vec<PathBuf> = entries
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|child| !excludes.iter().any(|excluded| child == excluded) && permits(child))
+            .collect();
+        // Popped in reverse, so the walk visits children in name order
+        children.sort_by(|a, b| b.cmp(a));
+        stack.extend(children.into_iter().map(|child| (child, depth + 1)));
+    }
+
+    if too_deep > 0 {
+        outcome.truncated = true;
+        outcome.warnings.push(format!(
+            "{} directories below max_depth {} were not entered",
+            too_deep, limits.max_depth
+        ));
+    }
+    for reason in denied.iter().take(MAX_LISTED_DENIED) {
+        outcome
+            .warnings
+            .push(format!("directory not scanned: {}", reason));
+    }
+    if denied.len() > MAX_LISTED_DENIED {
+        outcome.warnings.push(format!(
+            "{} more directories not scanned",
+            denied.len() - MAX_LISTED_DENIED
+        ));
+    }
+    outcome.matches.sort_by(|a, b| a.path.cmp(&b.path));
+    outcome
+}
+
+#[cfg(unix)]
+fn mode_of(metadata: &fs::Metadata) -> u32 {
+    use std::os::unix::fs::PermissionsExt;
+    metadata.permissions().mode()
+}
+
+#[cfg(not(unix))]
+fn mode_of(_metadata: &fs::Metadata) -> u32 {
+    0
+}
+
+#[cfg(unix)]
+fn owner_of(metadata: &fs::Metadata) -> (u32, u32) {
+    use std::os::unix::fs::MetadataExt;
+    (metadata.uid(), metadata.gid())
+}
+
+#[cfg(not(unix))]
+fn owner_of(_metadata: &fs::Metadata) -> (u32, u32) {
+    (0, 0)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt;
+
+    fn chmod(path: &Path, mode: u32) {
+        fs::set_permissions(path, fs::Permissions::from_mode(mode)).unwrap();
+    }
+
+    #[test]
+    fn test_scan_finds_special_bits_within_limits() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("bin")).unwrap();
+        fs::create_dir_all(root.join("skip/deep")).unwrap();
+        fs::create_dir_all(root.join("a/b/c")).unwrap();
+        for (file, mode) in [
+            ("bin/passwd", 0o4755),
+            ("bin/wall", 0o2755),
+            ("bin/ls", 0o755),
+            ("skip/deep/su", 0o4755),
+            ("a/b/c/hidden", 0o4755),
+        ] {
+            fs::write(root.join(file), "").unwrap();
+            chmod(&root.join(file), mode);
+        }
+        chmod(&root.join("a"), 0o1777);
+
+        let excludes = vec![root.join("skip")];
+        let limits = ScanLimits {
+            max_depth: 2,
+            max_entries: 1000,
+        };
+        let outcome = scan(
+            root,
+            PermissionFilter::default(),
+            limits,
+            &excludes,
+            &|_| true,
+        );
+        let found: Vec<&str> = outcome
+            .matches
+            .iter()
+            .map(|m| m.path.strip_prefix(&root.display().to_string()).unwrap())
+            .collect();
+        assert_eq!(found, ["/a", "/bin/passwd", "/bin/wall"]);
+        assert!(outcome.matches[0].sticky());
+        assert_eq!(outcome.matches[0].file_type, "directory");
+        assert!(outcome.truncated);
+        assert!(outcome.warnings[0].contains("max_depth 2"));
+
+        let suid_only = PermissionFilter::parse("suid").unwrap();
+        let limits = ScanLimits {
+            max_depth: 10,
+            max_entries: 3,
+        };
+        let outcome = scan(root, suid_only, limits, &[], &|_| true);
+        assert_eq!(outcome.entries_scanned, 3);
+        assert!(outcome.warnings[0].contains("after 3 entries"));
+        assert!(PermissionFilter::parse("suid,setuid").is_err());
+    }
+}
@@ -0,0 +1,263 @@
+//! # Record Formats
+//!
+//! Parses the structured documents behind `json_record`, `yaml_record`,
+//! `toml_record`, `ini_record` and `xml_record` into one value
+//! representation, the JSON value a
+//! [`RecordData`](esp_scanner_base::types::common::RecordData) holds, so the
+//! same record checks and field paths apply to all of them. INI files are
+//! read by [`ini`](super::ini) and XML files by [`xml`](super::xml).
+//!
+//! YAML aliases are resolved to the node their anchor names, and `<<` merge
+//! keys are applied. Mapping keys that are numbers or booleans become their
+//! string form; tags are dropped in favour of the tagged value. TOML dotted
+//! keys and tables produce the same nested records, and dates and times
+//! become strings in their TOML spelling.
+
+use super::ini::{self, IniOptions};
+use super::xml::{self, XmlOptions};
+
+/// A structured document format record checks can be evaluated against
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordFormat {
+    Json,
+    Yaml,
+    Toml,
+    Ini,
+    Xml,
+}
+
+/// Per-format reading options an object can set
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RecordOptions {
+    pub ini: IniOptions,
+    pub xml: XmlOptions,
+}
+
+impl RecordFormat {
+    /// Format collected for a criterion type
+    pub fn for_ctn_type(ctn_type: &str) -> Option<Self> {
+        match ctn_type {
+            "json_record" => Some(Self::Json),
+            "yaml_record" => Some(Self::Yaml),
+            "toml_record" => Some(Self::Toml),
+            "ini_record" => Some(Self::Ini),
+            "xml_record" => Some(Self::Xml),
+            _ => None,
+        }
+    }
+
+    pub fn ctn_type(self) -> &'static str {
+        match self {
+            Self::Json => "json_record",
+            Self::Yaml => "yaml_record",
+            Self::Toml => "toml_record",
+            Self::Ini => "ini_record",
+            Self::Xml => "xml_record",
+        }
+    }
+
+    /// Collected field holding the parsed record
+    pub fn data_field(self) -> &'static str {
+        match self {
+            Self::Json => "json_data",
+            Self::Yaml => "yaml_data",
+            Self::Toml => "toml_data",
+            Self::Ini => "ini_data",
+            Self::Xml => "xml_data",
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Json => "JSON",
+            Self::Yaml => "YAML",
+            Self::Toml => "TOML",
+            Self::Ini => "INI",
+            Self::Xml => "XML",
+        }
+    }
+
+    /// Parse a document into its record value
+    pub fn parse(self, content: &str) -> Result<serde_json::Value, String> {
+        self.parse_with(content, &RecordOptions::default())
+    }
+
+    /// Parse a document with the reading options for its format
+    pub fn parse_with(
+        self,
+        content: &str,
+        options: &RecordOptions,
+    ) -> Result<serde_json::Value, String> {
+        match self {
+            Self::Json => serde_json::from_str(content).map_err(|e| e.to_string()),
+            Self::Yaml => {
+                let mut value: serde_yaml::Value =
+                    serde_yaml::from_str(content).map_err(|e| e.to_string())?;
+                value.apply_merge().map_err(|e| e.to_string())?;
+                yaml_to_json(value)
+            }
+            Self::Toml => {
+                let table: toml::Table = toml::from_str(content).map_err(|e| e.to_string())?;
+                toml_to_json(toml::Value::Table(table))
+            }
+            Self::Ini => ini::parse(content, &options.ini),
+            Self::Xml => xml::parse(content, &options.xml),
+        }
+    }
+}
+
+fn yaml_to_json(value: serde_yaml::Value) -> Result<serde_json::Value, String> {
+    use serde_yaml::Value;
+    Ok(match value {
+        Value::Null => serde_json::Value::Null,
+        Value::Bool(b) => serde_json::Value::Bool(b),
+        Value::Number(n) => {
+            if let Some(i) = n.as_i64() {
+                i.into()
+            } else if let Some(u) = n.as_u64() {
+                u.into()
+            } else {
+                float_to_json(n.as_f64().unwrap_or(f64::NAN))?
+            }
+        }
+        Value::String(s) => serde_json::Value::String(s),
+        Value::Sequence(items) => serde_json::Value::Array(
+            items
+                .into_iter()
+                .map(yaml_to_json)
+                .collect::<Result<_, _>>()?,
+        ),
+        Value::Mapping(mapping) => {
+            let mut object = serde_json::Map::new();
+            for (name, item) in mapping {
+                object.insert(yaml_key(name)?, yaml_to_json(item)?);
+            }
+            serde_json::Value::Object(object)
+        }
+        Value::Tagged(tagged) => yaml_to_json(tagged.value)?,
+    })
+}
+
+/// Mapping key as a record field name
+fn yaml_key(name: serde_yaml::Value) -> Result<String, String> {
+    use serde_yaml::Value;
+    match name {
+        Value::String(s) => Ok(s),
+        Value::Number(n) => Ok(n.to_string()),
+        Value::Bool(b) => Ok(b.to_string()),
+        Value::Null => Ok("null".to_string()),
+        Value::Tagged(tagged) => yaml_key(tagged.value),
+        Value::Sequence(_) | Value::Mapping(_) => {
+            Err("mapping keys must be strings, numbers or booleans".to_string())
+        }
+    }
+}
+
+fn toml_to_json(value: toml::Value) -> Result<serde_json::Value, String> {
+    use toml::Value;
+    Ok(match value {
+        Value::String(s) => serde_json::Value::String(s),
+        Value::Integer(i) => i.into(),
+        Value::Float(f) => float_to_json(f)?,
+        Value::Boolean(b) => serde_json::Value::Bool(b),
+        Value::Datetime(datetime) => serde_json::Value::String(datetime.to_string()),
+        Value::Array(items) => serde_json::Value::Array(
+            items
+                .into_iter()
+                .map(toml_to_json)
+                .collect::<Result<_, _>>()?,
+        ),
+        Value::Table(table) => {
+            let mut object = serde_json::Map::new();
+            for (name, item) in table {
+                object.insert(name, toml_to_json(item)?);
+            }
+            serde_json::Value::Object(object)
+        }
+    })
+}
+
+/// A value as its type: canonical decimal integers and booleans, else text
+pub(super) fn typed_value(value: &str) -> serde_json::Value {
+    let digits = value.strip_prefix('-').unwrap_or(value);
+    let canonical = !digits.is_empty()
+        && digits.bytes().all(|b| b.is_ascii_digit())
+        && (digits == "0" || !digits.starts_with('0'));
+    if canonical {
+        if let Ok(i) = value.parse::<i64>() {
+            return i.into();
+        }
+    }
+    match value {
+        "true" => serde_json::Value::Bool(true),
+        "false" => serde_json::Value::Bool(false),
+        _ => serde_json::Value::String(value.to_string()),
+    }
+}
+
+/// JSON has no infinities or NaN, so records cannot hold them either
+fn float_to_json(f: f64) -> Result<serde_json::Value, String> {
+    serde_json::Number::from_f64(f)
+        .map(serde_json::Value::Number)
+        .ok_or_else(|| format!("{} cannot be held in a record", f))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_yaml_aliases_and_merge_keys_resolve() {
+        let document = "\
+defaults: &defaults
+  dhcp4: false
+  mtu: 1500
+ethernets:
+  eth0:
+    <<: *defaults
+    mtu: 9000
+  eth1: *defaults
+ports: [22, 443]
+1: numeric key
+";
+        let value = RecordFormat::Yaml.parse(document).unwrap();
+        assert_eq!(
+            value["ethernets"]["eth0"],
+            json!({"dhcp4": false, "mtu": 9000})
+        );
+        assert_eq!(
+            value["ethernets"]["eth1"],
+            json!({"dhcp4": false, "mtu": 1500})
+        );
+        assert_eq!(value["ports"], json!([22, 443]));
+        assert_eq!(value["1"], "numeric key");
+        assert!(RecordFormat::Yaml.parse("a: [unclosed").is_err());
+    }
+
+    #[test]
+    fn test_toml_dotted_keys_nest_like_tables() {
+        let document = r#"
+engine.cgroup_manager = "systemd"
+engine.runtimes.crun = ["/usr/bin/crun"]
+started = 2024-01-02T03:04:05Z
+
+[network]
+default_subnet = "10.88.0.0/16"
+mtu = 1500
+"#;
+        let value = RecordFormat::Toml.parse(document).unwrap();
+        assert_eq!(value["engine"]["cgroup_manager"], "systemd");
+        assert_eq!(
+            value["engine"]["runtimes"]["crun"],
+            json!(["/usr/bin/crun"])
+        );
+        assert_eq!(value["network"]["mtu"], 1500);
+        assert_eq!(value["started"], "2024-01-02T03:04:05Z");
+        assert!(RecordFormat::Toml.parse("nan = nan").is_err());
+        assert_eq!(
+            RecordFormat::for_ctn_type("toml_record").map(RecordFormat::data_field),
+            Some("toml_data")
+        );
+    }
+}
@@ -0,0 +1,497 @@
+//! # Scheduled Job Parsing
+//!
+//! Parses cron tables and systemd timers into the jobs the `scheduled_job`
+//! criterion type checks.
+//!
+//! Cron schedules are normalized to five single-space-separated fields:
+//! `@daily` and the other aliases are expanded, and month and weekday names
+//! become numbers, so `30 4 * * sun` and `30  4 * * 0` compare equal. The
+//! longest gap between two runs is worked out by walking eight years of
+//! calendar, which is enough for a job that only runs on 29 February.
+//! `@reboot` jobs have no interval.
+//!
+//! Timer schedules are the `OnCalendar=` and `OnUnitActiveSec=` values
+//! `systemctl show` reports. Intervals are derived for the calendar
+//! shorthands (`daily`, `weekly`, ...), for `[weekdays] *-*-* hh:mm[:ss]`
+//! and `*-*-dd hh:mm[:ss]` expressions and for repeating time spans; other
+//! calendar expressions have none.
+
+/// System crontab, with a user column
+pub const SYSTEM_CRONTAB: &str = "/etc/crontab";
+
+/// Drop-in crontabs, with a user column
+pub const CRON_D_DIR: &str = "/etc/cron.d";
+
+/// Directories of scripts run by `run-parts`, and how often each runs
+pub const CRON_PERIOD_DIRS: &[(&str, &str)] = &[
+    ("/etc/cron.hourly", "@hourly"),
+    ("/etc/cron.daily", "@daily"),
+    ("/etc/cron.weekly", "@weekly"),
+    ("/etc/cron.monthly", "@monthly"),
+];
+
+/// Per-user crontab spools: RHEL keeps them in the first, Debian the second
+pub const CRON_SPOOL_DIRS: &[&str] = &["/var/spool/cron", "/var/spool/cron/crontabs"];
+
+const MONTHS: &[&str] = &[
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+const WEEKDAYS: &[&str] = &["sun", "mon", "tue", "wed", "thu", "fri", "sat"];
+
+/// Days of calendar walked to find the longest gap between runs
+const CALENDAR_DAYS: i64 = 8 * 365 + 2;
+
+/// Days from 1970-01-01 to the first day walked, 2024-01-01
+const CALENDAR_START: i64 = 19723;
+
+/// One job line of a crontab
+#[derive(Debug, Clone, PartialEq)]
+pub struct CronJob {
+    /// Normalized schedule, or `@reboot`
+    pub schedule: String,
+    /// User column of a system crontab; `None` in a user crontab
+    pub user: Option<String>,
+    pub command: String,
+    /// 1-based line number
+    pub line: usize,
+}
+
+/// Job lines of a crontab; `system` tables carry a user column
+///
+/// Comments, blank lines, variable assignments and lines with an invalid
+/// schedule are skipped.
+pub fn parse_crontab(content: &str, system: bool) -> Vec<CronJob> {
+    let mut jobs = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || is_assignment(line) {
+            continue;
+        }
+        let mut rest = line;
+        let mut next_word = || {
+            let trimmed = rest.trim_start();
+            let end = trimmed.find(char::is_whitespace).unwrap_or(trimmed.len());
+            let (word, remainder) = trimmed.split_at(end);
+            rest = remainder;
+            word
+        };
+        let first = next_word();
+        let schedule = if first.starts_with('@') {
+            normalize_alias(first)
+        } else {
+            let fields = [first, next_word(), next_word(), next_word(), next_word()];
+            normalize_fields(&fields)
+        };
+        let Some(schedule) = schedule else {
+            continue;
+        };
+        let user = if system {
+            match next_word() {
+                "" => continue,
+                user => Some(user.to_string()),
+            }
+        } else {
+            None
+        };
+        let command = rest.trim();
+        if command.is_empty() {
+            continue;
+        }
+        jobs.push(CronJob {
+            schedule,
+            user,
+            command: command.to_string(),
+            line: index + 1,
+        });
+    }
+    jobs
+}
+
+/// `NAME=value` lines such as `MAILTO=root`
+fn is_assignment(line: &str) -> bool {
+    match line.split_once('=') {
+        Some((name, _)) => {
+            let name = name.trim();
+            !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        }
+        None => false,
+    }
+}
+
+/// Five fields an `@` alias stands for; `@reboot` stays as it is
+pub fn normalize_alias(alias: &str) -> Option<String> {
+    let fields = match alias.to_ascii_lowercase().as_str() {
+        "@reboot" => return Some("@reboot".to_string()),
+        "@yearly" | "@annually" => "0 0 1 1 *",
+        "@monthly" => "0 0 1 * *",
+        "@weekly" => "0 0 * * 0",
+        "@daily" | "@midnight" => "0 0 * * *",
+        "@hourly" => "0 * * * *",
+        _ => return None,
+    };
+    Some(fields.to_string())
+}
+
+/// Five cron fields joined by single spaces, with names as numbers
+fn normalize_fields(fields: &[&str; 5]) -> Option<String> {
+    let mut normalized = Vec::with_capacity(5);
+    for (position, field) in fields.iter().enumerate() {
+        let field = field.to_ascii_lowercase();
+        let field = match position {
+            3 => replace_names(&field, MONTHS, 1),
+            4 => replace_names(&field, WEEKDAYS, 0),
+            _ => field,
+        };
+        // Reject what cron would reject
+        let (min, max) = FIELD_BOUNDS[position];
+        expand_field(&field, min, max)?;
+        normalized.push(field);
+    }
+    Some(normalized.join(" "))
+}
+
+fn replace_names(field: &str, names: &[&str], first: usize) -> String {
+    let mut field = field.to_string();
+    for (index, name) in names.iter().enumerate() {
+        field = field.replace(name, &(index + first).to_string());
+    }
+    field
+}
+
+/// Inclusive bounds of minute, hour, day of month, month and weekday
+const FIELD_BOUNDS: [(u32, u32); 5] = [(0, 59), (0, 23), (1, 31), (1, 12), (0, 7)];
+
+/// Values a cron field selects: lists of `*`, `n`, `a-b`, each with `/step`
+fn expand_field(field: &str, min: u32, max: u32) -> Option<Vec<u32>> {
+    let mut values = Vec::new();
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (range, step.parse::<u32>().ok().filter(|s| *s > 0)?),
+            None => (part, 1),
+        };
+        let (start, end) = match range {
+            "*" => (min, max),
+            _ => match range.split_once('-') {
+                Some((start, end)) => (start.parse().ok()?, end.parse().ok()?),
+                // `n/step` runs from n to the end of the range
+                None if part.contains('/') => (range.parse().ok()?, max),
+                None => {
+                    let value = range.parse().ok()?;
+                    (value, value)
+                }
+            },
+        };
+        if start < min || end > max || start > end {
+            return None;
+        }
+        values.extend((start..=end).step_by(step as usize));
+    }
+    values.sort_unstable();
+    values.dedup();
+    Some(values)
+}
+
+/// Longest time between two runs of a normalized schedule, rounded up to
+/// whole hours; `None` for `@reboot` or a schedule that never runs twice
+pub fn cron_max_interval_hours(schedule: &str) -> Option<i64> {
+    let fields: Vec<&str> = schedule.split(' ').collect();
+    let [minute, hour, day, month, weekday] = fields[..] else {
+        return None;
+    };
+    let minutes = expand_field(minute, 0, 59)?;
+    let hours = expand_field(hour, 0, 23)?;
+    let days = expand_field(day, 1, 31)?;
+    let months = expand_field(month, 1, 12)?;
+    let weekdays: Vec<u32> = expand_field(weekday, 0, 7)?
+        .into_iter()
+        .map(|d| d % 7)
+        .collect();
+
+    // Minutes past midnight of each run on a day the job runs
+    let times: Vec<i64> = hours
+        .iter()
+        .flat_map(|h| minutes.iter().map(move |m| i64::from(h * 60 + m)))
+        .collect();
+    let (first, last) = (*times.first()?, *times.last()?);
+    let mut longest = times.windows(2).map(|w| w[1] - w[0]).max().unwrap_or(0);
+
+    // Both day fields restricted: either may match, as cron does
+    let either_day = !day.starts_with('*') && !weekday.starts_with('*');
+    let mut previous_day = None;
+    let mut runs_on_days = 0;
+    for offset in 0..CALENDAR_DAYS {
+        let epoch_day = CALENDAR_START + offset;
+        let (_, m, d) = civil_from_days(epoch_day);
+        let w = ((epoch_day + 4) % 7) as u32;
+        if !months.contains(&m) {
+            continue;
+        }
+        let day_matches = days.contains(&d);
+        let weekday_matches = weekdays.contains(&w);
+        let runs = if either_day {
+            day_matches || weekday_matches
+        } else {
+            day_matches && weekday_matches
+        };
+        if !runs {
+            continue;
+        }
+        if let Some(previous) = previous_day {
+            longest = longest.max((offset - previous) * 1440 + first - last);
+        }
+        previous_day = Some(offset);
+        runs_on_days += 1;
+    }
+    if runs_on_days == 0 || (runs_on_days == 1 && times.len() == 1) {
+        return None;
+    }
+    Some((longest + 59) / 60)
+}
+
+/// Year, month and day of a count of days since 1970-01-01
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Timer units named in `systemctl list-timers --all --no-legend` rows,
+/// with the unit each activates
+pub fn parse_list_timers(stdout: &str) -> Vec<(String, String)> {
+    stdout
+        .lines()
+        .filter_map(|line| {
+            let mut words = line.split_whitespace();
+            let timer = words.find(|word| word.ends_with(".timer"))?;
+            let activates = words.next().unwrap_or_default();
+            Some((timer.to_string(), activates.to_string()))
+        })
+        .collect()
+}
+
+/// Schedules of a timer from `systemctl show` `TimersCalendar=` and
+/// `TimersMonotonic=` lines, such as `OnCalendar=*-*-* 00:00:00`
+pub fn timer_schedules(show: &str) -> Vec<String> {
+    show.lines()
+        .filter_map(|line| {
+            line.strip_prefix("TimersCalendar=")
+                .or_else(|| line.strip_prefix("TimersMonotonic="))
+        })
+        .filter_map(|value| {
+            value
+                .trim()
+                .trim_start_matches('{')
+                .split(" ; ")
+                .next()
+                .map(|schedule| schedule.trim().to_string())
+        })
+        .filter(|schedule| !schedule.is_empty())
+        .collect()
+}
+
+/// Longest time between two runs of a timer schedule, in whole hours
+pub fn timer_max_interval_hours(schedule: &str) -> Option<i64> {
+    let (kind, value) = schedule.split_once('=')?;
+    match kind {
+        "OnCalendar" => calendar_interval_hours(value.trim()),
+        "OnUnitActiveSec" | "OnUnitInactiveSec" => {
+            let seconds = timespan_seconds(value.trim())?;
+            (seconds > 0).then(|| (seconds + 3599) / 3600)
+        }
+        _ => None,
+    }
+}
+
+fn calendar_interval_hours(expression: &str) -> Option<i64> {
+    let shorthand = match expression.to_ascii_lowercase().as_str() {
+        "minutely" | "hourly" => Some(1),
+        "daily" => Some(24),
+        "weekly" => Some(7 * 24),
+        "monthly" => Some(31 * 24),
+        "quarterly" => Some(92 * 24),
+        "semiannually" => Some(184 * 24),
+        "yearly" | "annually" => Some(366 * 24),
+        _ => None,
+    };
+    if shorthand.is_some() {
+        return shorthand;
+    }
+
+    let parts: Vec<&str> = expression.split_whitespace().collect();
+    let (weekdays, date, time) = match parts[..] {
+        [date, time] if date.contains('-') => ("*", date, time),
+        [weekdays, date, time] => (weekdays, date, time),
+        _ => return None,
+    };
+    let hour = time.split(':').next()?;
+    let day = match date.split('-').collect::<Vec<_>>()[..] {
+        ["*", "*", day] => day,
+        _ => return None,
+    };
+    let weekdays = calendar_weekdays(weekdays)?;
+    let cron = format!(
+        "0 {} {} * {}",
+        calendar_field(hour),
+        calendar_field(day),
+        weekdays
+    );
+    cron_max_interval_hours(&cron)
+}
+
+/// A calendar hour or day field in cron syntax: `a..b` ranges, `a/step`
+fn calendar_field(field: &str) -> String {
+    match field.trim_start_matches('0') {
+        "" if !field.is_empty() => "0".to_string(),
+        _ => field
+            .split(',')
+            .map(|part| part.replace("..", "-").trim_start_matches('0').to_string())
+            .map(|part| {
+                if part.is_empty() {
+                    "0".to_string()
+                } else {
+                    part
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(","),
+    }
+}
+
+/// Calendar weekdays such as `Mon..Fri` or `Sat,Sun` as a cron field
+fn calendar_weekdays(weekdays: &str) -> Option<String> {
+    if weekdays == "*" {
+        return Some("*".to_string());
+    }
+    let number = |name: &str| {
+        let name = name.get(..3)?.to_ascii_lowercase();
+        WEEKDAYS.iter().position(|day| *day == name)
+    };
+    let mut days = Vec::new();
+    for part in weekdays.split(',') {
+        match part.split_once("..") {
+            Some((start, end)) => days.push(format!("{}-{}", number(start)?, number(end)?)),
+            None => days.push(number(part)?.to_string()),
+        }
+    }
+    Some(days.join(","))
+}
+
+/// Seconds in a systemd time span such as `1d`, `12h` or `1h 30min`
+fn timespan_seconds(span: &str) -> Option<i64> {
+    let mut total = 0;
+    for word in span.split_whitespace() {
+        let split = word
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(word.len());
+        let (number, unit) = word.split_at(split);
+        let number: i64 = number.parse().ok()?;
+        let seconds = match unit {
+            "" | "s" | "sec" | "second" | "seconds" => 1,
+            "min" | "m" | "minute" | "minutes" => 60,
+            "h" | "hr" | "hour" | "hours" => 3600,
+            "d" | "day" | "days" => 86_400,
+            "w" | "week" | "weeks" => 7 * 86_400,
+            "month" | "months" | "M" => 2_630_016,
+            "y" | "year" | "years" => 31_557_600,
+            _ => return None,
+        };
+        total += number * seconds;
+    }
+    Some(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_crontab_normalizes_schedules() {
+        let crontab = "\
+SHELL=/bin/bash
+MAILTO=root
+# run-parts
+17 *  * * *  root  cd / && run-parts --report /etc/cron.hourly
+30 4 * * SUN root /usr/sbin/aide --check
+@daily root /usr/local/bin/backup
+61 * * * * root /bin/broken
+";
+        let jobs = parse_crontab(crontab, true);
+        assert_eq!(jobs.len(), 3);
+        assert_eq!(jobs[0].schedule, "17 * * * *");
+        assert_eq!(
+            jobs[0].command,
+            "cd / && run-parts --report /etc/cron.hourly"
+        );
+        assert_eq!(jobs[0].line, 4);
+        assert_eq!(jobs[1].schedule, "30 4 * * 0");
+        assert_eq!(jobs[1].user.as_deref(), Some("root"));
+        assert_eq!(jobs[2].schedule, "0 0 * * *");
+
+        let user_jobs = parse_crontab("*/5 * * * * /home/alice/poll.sh\n", false);
+        assert_eq!(user_jobs[0].user, None);
+        assert_eq!(user_jobs[0].command, "/home/alice/poll.sh");
+    }
+
+    #[test]
+    fn test_cron_max_interval_hours() {
+        assert_eq!(cron_max_interval_hours("*/5 * * * *"), Some(1));
+        assert_eq!(cron_max_interval_hours("0 0 * * *"), Some(24));
+        assert_eq!(cron_max_interval_hours("0 3,15 * * *"), Some(12));
+        assert_eq!(cron_max_interval_hours("30 4 * * 0"), Some(168));
+        assert_eq!(cron_max_interval_hours("0 0 * * 1-5"), Some(72));
+        assert_eq!(cron_max_interval_hours("0 0 1 * *"), Some(31 * 24));
+        // Day of month or weekday, not both
+        assert_eq!(cron_max_interval_hours("0 0 1 * 1"), Some(7 * 24));
+        assert_eq!(cron_max_interval_hours("0 0 29 2 *"), Some(1461 * 24));
+        assert_eq!(cron_max_interval_hours("0 0 31 2 *"), None);
+        assert_eq!(cron_max_interval_hours("@reboot"), None);
+    }
+
+    #[test]
+    fn test_timer_schedules_and_intervals() {
+        let show = "\
+TimersCalendar={ OnCalendar=*-*-* 04:00:00 ; next_elapse=Wed 2024-05-01 04:00:00 UTC }
+TimersMonotonic={ OnUnitActiveSec=12h ; next_elapse=n/a }
+UnitFileState=enabled
+";
+        let schedules = timer_schedules(show);
+        assert_eq!(
+            schedules,
+            vec!["OnCalendar=*-*-* 04:00:00", "OnUnitActiveSec=12h"]
+        );
+        assert_eq!(timer_max_interval_hours(&schedules[0]), Some(24));
+        assert_eq!(timer_max_interval_hours(&schedules[1]), Some(12));
+        assert_eq!(timer_max_interval_hours("OnCalendar=weekly"), Some(168));
+        assert_eq!(
+            timer_max_interval_hours("OnCalendar=Mon..Fri *-*-* 09:00:00"),
+            Some(72)
+        );
+        assert_eq!(
+            timer_max_interval_hours("OnCalendar=*-*-01 00:00"),
+            Some(744)
+        );
+        assert_eq!(timer_max_interval_hours("OnBootSec=15min"), None);
+
+        let timers = parse_list_timers(
+            "Wed 2024-05-01 04:00:00 UTC 5h left Tue 2024-04-30 04:00:00 UTC 18h ago aide-check.timer aide-check.service\n\
+             -                           -       -                             -       fstrim.timer fstrim.service\n",
+        );
+        assert_eq!(
+            timers,
+            vec![
+                (
+                    "aide-check.timer".to_string(),
+                    "aide-check.service".to_string()
+                ),
+                ("fstrim.timer".to_string(), "fstrim.service".to_string()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,297 @@
+//! # sshd_config Reader
+//!
+//! Resolves an OpenSSH server configuration into the values sshd would use,
+//! for the `sshd_config` criterion type. Keywords are case-insensitive and
+//! are stored lowercased; values keep their case except `yes`/`no`.
+//!
+//! Like sshd, the first value obtained for a keyword wins, so a later line
+//! (or a later included file) cannot override an earlier one. Keywords that
+//! sshd accumulates instead, such as `AllowUsers` or `Port`, collect every
+//! line's values separated by spaces.
+//!
+//! `Include` is resolved relative to the directory of the main file, with `*`
+//! and `?` globs in the file name expanded in lexical order; a pattern that
+//! matches nothing is ignored, as sshd does. Includes nest at most
+//! [`MAX_INCLUDE_DEPTH`] levels deep; deeper ones are skipped with a warning.
+//! Paths are taken as the config names them and mapped by a `locate`
+//! function before the filesystem is touched, so an absolute `Include` in an
+//! offline scan root stays inside that root.
+//!
+//! Each `Match` block runs to the next `Match` or the end of the config. Its
+//! directives are kept apart from the global section, and its effective
+//! values are the block's own with the global section filling the gaps.
+use esp_scanner_base::execution::comparisons::string::glob_matches;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// How deeply `Include` directives may nest, matching sshd
+pub const MAX_INCLUDE_DEPTH: usize = 16;
+
+/// Keywords sshd accumulates across lines rather than taking the first
+const ACCUMULATING_KEYWORDS: &[&str] = &[
+    "acceptenv",
+    "allowgroups",
+    "allowusers",
+    "denygroups",
+    "denyusers",
+    "hostcertificate",
+    "hostkey",
+    "listenaddress",
+    "port",
+];
+
+/// A `Match` block and the directives set inside it
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MatchBlock {
+    /// The criteria after `Match`, e.g. `User backup`
+    pub criteria: String,
+    pub directives: BTreeMap<String, String>,
+}
+
+/// A resolved server configuration
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SshdConfig {
+    /// Directives outside any `Match` block
+    pub global: BTreeMap<String, String>,
+    pub match_blocks: Vec<MatchBlock>,
+    /// Problems that did not stop parsing but may hide directives
+    pub warnings: Vec<String>,
+}
+
+impl SshdConfig {
+    /// The values sshd would use for connections matching `block`
+    pub fn effective(&self, block: &MatchBlock) -> BTreeMap<String, String> {
+        let mut directives = self.global.clone();
+        directives.extend(block.directives.clone());
+        directives
+    }
+
+    fn set(&mut self, keyword: String, value: String) {
+        let directives = match self.match_blocks.last_mut() {
+            Some(block) => &mut block.directives,
+            None => &mut self.global,
+        };
+        if ACCUMULATING_KEYWORDS.contains(&keyword.as_str()) {
+            directives
+                .entry(keyword)
+                .and_modify(|existing| {
+                    existing.push(' ');
+                    existing.push_str(&value);
+                })
+                .or_insert(value);
+        } else {
+            directives.entry(keyword).or_insert(value);
+        }
+    }
+}
+
+/// Read the config at `path`, loading it and any included files with `read`
+///
+/// `locate` maps a path as the config names it to the file to list or read.
+pub fn parse_sshd_config<E>(
+    path: &Path,
+    locate: &dyn Fn(&Path) -> Result<PathBuf, E>,
+    read: &mut dyn FnMut(&Path) -> Result<String, E>,
+) -> Result<SshdConfig, E> {
+    let base_dir = path.parent().unwrap_or(Path::new("/")).to_path_buf();
+    let mut config = SshdConfig::default();
+    let content = read(&locate(path)?)?;
+    parse_into(&mut config, &content, &base_dir, 0, locate, read)?;
+    Ok(config)
+}
+
+fn parse_into<E>(
+    config: &mut SshdConfig,
+    content: &str,
+    base_dir: &Path,
+    depth: usize,
+    locate: &dyn Fn(&Path) -> Result<PathBuf, E>,
+    read: &mut dyn FnMut(&Path) -> Result<String, E>,
+) -> Result<(), E> {
+    for line in content.lines() {
+        let Some((keyword, value)) = split_directive(line) else {
+            continue;
+        };
+        match keyword.as_str() {
+            "match" => config.match_blocks.push(MatchBlock {
+                criteria: value,
+                directives: BTreeMap::new(),
+            }),
+            "include" => {
+                if depth + 1 > MAX_INCLUDE_DEPTH {
+                    config.warnings.push(format!(
+                        "Include '{}' skipped: nested more than {} levels deep",
+                        value, MAX_INCLUDE_DEPTH
+                    ));
+                    continue;
+                }
+                for pattern in value.split_whitespace() {
+                    for included in expand_include(base_dir, pattern, locate)? {
+                        let content = read(&included)?;
+                        parse_into(config, &content, base_dir, depth + 1, locate, read)?;
+                    }
+                }
+            }
+            _ => config.set(keyword, value),
+        }
+    }
+    Ok(())
+}
+
+/// Split a config line into a lowercased keyword and its value
+fn split_directive(line: &str) -> Option<(String, String)> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+    let end = line
+        .find(|c: char| c.is_whitespace() || c == '=')
+        .unwrap_or(line.len());
+    let (keyword, rest) = line.split_at(end);
+    let rest = rest.trim_start();
+    let rest = rest.strip_prefix('=').unwrap_or(rest);
+    let value = rest
+        .split_whitespace()
+        .map(|arg| arg.trim_matches('"'))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let value = if value.eq_ignore_ascii_case("yes") || value.eq_ignore_ascii_case("no") {
+        value.to_ascii_lowercase()
+    } else {
+        value
+    };
+    Some((keyword.to_ascii_lowercase(), value))
+}
+
+/// The located files an `Include` pattern names, in the order sshd reads
+/// them
+fn expand_include<E>(
+    base_dir: &Path,
+    pattern: &str,
+    locate: &dyn Fn(&Path) -> Result<PathBuf, E>,
+) -> Result<Vec<PathBuf>, E> {
+    let path = base_dir.join(pattern);
+    let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+        return Ok(Vec::new());
+    };
+    if !name.contains(['*', '?']) {
+        let located = locate(&path)?;
+        return Ok(if located.is_file() {
+            vec![located]
+        } else {
+            Vec::new()
+        });
+    }
+    let Some(dir) = path.parent() else {
+        return Ok(Vec::new());
+    };
+    let Ok(entries) = fs::read_dir(locate(dir)?) else {
+        return Ok(Vec::new());
+    };
+    let mut names: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| entry.file_name().to_str().map(str::to_string))
+        .filter(|file| glob_matches(name, file).unwrap_or(false))
+        .collect();
+    names.sort();
+    let mut files = Vec::new();
+    for file in names {
+        let located = locate(&dir.join(file))?;
+        if located.is_file() {
+            files.push(located);
+        }
+    }
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(dir: &Path, main: &str) -> SshdConfig {
+        let path = dir.join("sshd_config");
+        fs::write(&path, main).unwrap();
+        parse_sshd_config(&path, &|p: &Path| Ok(p.to_path_buf()), &mut |p: &Path| {
+            fs::read_to_string(p)
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_first_value_wins_and_keywords_ignore_case() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = parse(
+            dir.path(),
+            "# comment\n\
+             permitrootlogin No\n\
+             PermitRootLogin yes\n\
+             Ciphers=aes256-gcm@openssh.com,aes128-ctr\n\
+             AllowUsers alice bob\n\
+             AllowUsers carol\n\
+             Banner \"/etc/issue.net\"\n",
+        );
+        assert_eq!(config.global["permitrootlogin"], "no");
+        assert_eq!(
+            config.global["ciphers"],
+            "aes256-gcm@openssh.com,aes128-ctr"
+        );
+        assert_eq!(config.global["allowusers"], "alice bob carol");
+        assert_eq!(config.global["banner"], "/etc/issue.net");
+    }
+
+    #[test]
+    fn test_includes_are_read_in_place_and_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let conf_d = dir.path().join("sshd_config.d");
+        fs::create_dir(&conf_d).unwrap();
+        fs::write(
+            conf_d.join("50-distro.conf"),
+            "MaxAuthTries 6\nX11Forwarding yes\n",
+        )
+        .unwrap();
+        fs::write(conf_d.join("10-hardening.conf"), "MaxAuthTries 3\n").unwrap();
+        fs::write(conf_d.join("README"), "MaxAuthTries 9\n").unwrap();
+
+        let config = parse(
+            dir.path(),
+            "X11Forwarding no\nInclude sshd_config.d/*.conf missing.conf\nMaxAuthTries 10\n",
+        );
+        assert_eq!(config.global["maxauthtries"], "3");
+        assert_eq!(config.global["x11forwarding"], "no");
+        assert!(config.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_match_blocks_are_kept_apart() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = parse(
+            dir.path(),
+            "PasswordAuthentication no\n\
+             X11Forwarding no\n\
+             Match User backup\n\
+             \tPasswordAuthentication yes\n\
+             Match Address 10.0.0.0/8\n\
+             \tAllowTcpForwarding no\n",
+        );
+        assert_eq!(config.global["passwordauthentication"], "no");
+        assert!(!config.global.contains_key("allowtcpforwarding"));
+        assert_eq!(config.match_blocks.len(), 2);
+        assert_eq!(config.match_blocks[0].criteria, "User backup");
+
+        let backup = config.effective(&config.match_blocks[0]);
+        assert_eq!(backup["passwordauthentication"], "yes");
+        assert_eq!(backup["x11forwarding"], "no");
+    }
+
+    #[test]
+    fn test_recursive_include_stops_with_a_warning() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = parse(dir.path(), "Include sshd_config\nPort 22\n");
+        assert_eq!(config.warnings.len(), 1);
+        assert_eq!(
+            config.global["port"].split(' ').count(),
+            MAX_INCLUDE_DEPTH + 1
+        );
+    }
+}
@@ -0,0 +1,615 @@
+//! # sudoers Reader
+//!
+//! Parses a sudoers policy into the user specifications and `Defaults` it
+//! grants, for the `sudoers` criterion type.
+//!
+//! Lines ending in `\` continue on the next line, and `#` starts a comment
+//! unless it is followed by a digit (`#1000` is a uid). `#include` and
+//! `@include` read a file in place, relative to the including file's
+//! directory; `#includedir` and `@includedir` read every file of a directory
+//! in lexical order, skipping names that end in `~` or contain a `.`, as sudo
+//! does. Includes nest at most [`MAX_INCLUDE_DEPTH`] levels deep. Included
+//! paths go through a `locate` function before the filesystem is touched,
+//! as in [`sshd_config`](super::sshd_config).
+//!
+//! `User_Alias`, `Runas_Alias`, `Host_Alias` and `Cmnd_Alias` definitions are
+//! gathered first, so a reference is expanded wherever the alias is defined.
+//! A user specification becomes one [`UserSpec`] per user and command, with
+//! the host and runas lists kept whole. Runas and tags carry over to later
+//! commands of the same list, as in sudo; without a runas list the target
+//! is `root`.
+//!
+//! For each `Defaults` binding, the last setting of a parameter wins.
+//! `+=` appends to a list parameter and `-=` removes from it.
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// How deeply includes may nest, matching sudo
+pub const MAX_INCLUDE_DEPTH: usize = 128;
+
+/// How deeply aliases may refer to other aliases before expansion stops
+const MAX_ALIAS_DEPTH: usize = 32;
+
+/// Tags a command may carry; `NOPASSWD` and `PASSWD` are the ones recorded
+const TAGS: &[&str] = &[
+    "NOPASSWD",
+    "PASSWD",
+    "NOEXEC",
+    "EXEC",
+    "SETENV",
+    "NOSETENV",
+    "LOG_INPUT",
+    "NOLOG_INPUT",
+    "LOG_OUTPUT",
+    "NOLOG_OUTPUT",
+    "MAIL",
+    "NOMAIL",
+    "FOLLOW",
+    "NOFOLLOW",
+    "INTERCEPT",
+    "NOINTERCEPT",
+];
+
+/// Options that may precede a command, e.g. `CWD=/tmp`
+const COMMAND_OPTIONS: &[&str] = &[
+    "ROLE",
+    "TYPE",
+    "CWD",
+    "CHROOT",
+    "NOTBEFORE",
+    "NOTAFTER",
+    "TIMEOUT",
+    "APPARMOR_PROFILE",
+    "PRIVS",
+    "LIMITPRIVS",
+];
+
+/// One command a user may run
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UserSpec {
+    /// User, `%group` or `ALL`
+    pub user: String,
+    /// Hosts, joined with `, `
+    pub host: String,
+    /// Target users, with `:groups` when given, e.g. `root` or `ALL:ALL`
+    pub runas: String,
+    pub command: String,
+    pub nopasswd: bool,
+}
+
+/// An effective `Defaults` parameter
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DefaultsEntry {
+    /// Empty for global defaults, else as written: `:alice`, `@host`, ...
+    pub binding: String,
+    pub name: String,
+    /// Empty for flags
+    pub value: String,
+    /// False when negated with `!`
+    pub enabled: bool,
+}
+
+/// A parsed sudoers policy
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Sudoers {
+    pub user_specs: Vec<UserSpec>,
+    pub defaults: Vec<DefaultsEntry>,
+    /// Problems that did not stop parsing but may hide rules
+    pub warnings: Vec<String>,
+}
+
+/// Aliases by kind, each name mapping to its members
+#[derive(Default)]
+struct Aliases {
+    user: HashMap<String, Vec<String>>,
+    runas: HashMap<String, Vec<String>>,
+    host: HashMap<String, Vec<String>>,
+    command: HashMap<String, Vec<String>>,
+}
+
+/// Read the policy at `path`, loading it and any included files with `read`
+///
+/// `locate` maps a path as the policy names it to the file to list or read.
+pub fn parse_sudoers<E>(
+    path: &Path,
+    locate: &dyn Fn(&Path) -> Result<PathBuf, E>,
+    read: &mut dyn FnMut(&Path) -> Result<String, E>,
+) -> Result<Sudoers, E> {
+    let mut sudoers = Sudoers::default();
+    let mut lines = Vec::new();
+    let content = read(&locate(path)?)?;
+    collect_lines(&mut sudoers, &mut lines, &content, path, 0, locate, read)?;
+
+    let mut aliases = Aliases::default();
+    let mut rules = Vec::new();
+    for line in lines {
+        let (keyword, rest) = line.split_once(char::is_whitespace).unwrap_or((&line, ""));
+        let table = match keyword {
+            "User_Alias" => &mut aliases.user,
+            "Runas_Alias" => &mut aliases.runas,
+            "Host_Alias" => &mut aliases.host,
+            "Cmnd_Alias" | "Cmd_Alias" => &mut aliases.command,
+            _ => {
+                rules.push(line);
+                continue;
+            }
+        };
+        for definition in split_unescaped(rest, ':') {
+            if let Some((name, members)) = definition.split_once('=') {
+                table.insert(name.trim().to_string(), split_list(members));
+            }
+        }
+    }
+
+    for rule in rules {
+        let is_defaults = rule.strip_prefix("Defaults").is_some_and(|rest| {
+            rest.is_empty() || rest.starts_with([' ', '\t', '@', ':', '!', '>'])
+        });
+        if is_defaults {
+            parse_defaults(&mut sudoers.defaults, &rule);
+        } else if let Some(specs) = parse_user_spec(&rule, &aliases) {
+            sudoers.user_specs.extend(specs);
+        } else {
+            sudoers
+                .warnings
+                .push(format!("Unrecognized sudoers line skipped: '{}'", rule));
+        }
+    }
+    Ok(sudoers)
+}
+
+/// Append the logical lines of `content`, reading includes in place
+fn collect_lines<E>(
+    sudoers: &mut Sudoers,
+    lines: &mut Vec<String>,
+    content: &str,
+    path: &Path,
+    depth: usize,
+    locate: &dyn Fn(&Path) -> Result<PathBuf, E>,
+    read: &mut dyn FnMut(&Path) -> Result<String, E>,
+) -> Result<(), E> {
+    let base_dir = path.parent().unwrap_or(Path::new("/"));
+    for line in logical_lines(content) {
+        let include = ["#includedir", "@includedir", "#include", "@include"]
+            .iter()
+            .find_map(|directive| {
+                line.strip_prefix(directive)
+                    .filter(|rest| rest.starts_with(char::is_whitespace))
+                    .map(|rest| (directive.ends_with("dir"), rest.trim().trim_matches('"')))
+            });
+        let Some((is_dir, target)) = include else {
+            let line = strip_comment(&line);
+            if !line.is_empty() {
+                lines.push(line);
+            }
+            continue;
+        };
+
+        if depth + 1 > MAX_INCLUDE_DEPTH {
+            sudoers.warnings.push(format!(
+                "Include '{}' skipped: nested more than {} levels deep",
+                target, MAX_INCLUDE_DEPTH
+            ));
+            continue;
+        }
+        let target = base_dir.join(target);
+        let files = if is_dir {
+            include_dir_files(&target, locate)?
+        } else if locate(&target)?.is_file() {
+            vec![target]
+        } else {
+            sudoers
+                .warnings
+                .push(format!("Included file '{}' not found", target.display()));
+            Vec::new()
+        };
+        for file in files {
+            let content = read(&locate(&file)?)?;
+            collect_lines(sudoers, lines, &content, &file, depth + 1, locate, read)?;
+        }
+    }
+    Ok(())
+}
+
+/// The files `#includedir` reads, in order, as the policy names them
+fn include_dir_files<E>(
+    dir: &Path,
+    locate: &dyn Fn(&Path) -> Result<PathBuf, E>,
+) -> Result<Vec<PathBuf>, E> {
+    let Ok(entries) = fs::read_dir(locate(dir)?) else {
+        return Ok(Vec::new());
+    };
+    let mut names: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| entry.file_name().to_str().map(str::to_string))
+        .filter(|name| !name.ends_with('~') && !name.contains('.'))
+        .collect();
+    names.sort();
+    let mut files = Vec::new();
+    for name in names {
+        let file = dir.join(name);
+        if locate(&file)?.is_file() {
+            files.push(file);
+        }
+    }
+    Ok(files)
+}
+
+/// Lines with `\` continuations joined, trimmed, blank ones dropped
+fn logical_lines(content: &str) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    for line in content.lines() {
+        match line.strip_suffix('\\') {
+            Some(continued) => {
+                current.push_str(continued);
+                current.push(' ');
+            }
+            None => {
+                current.push_str(line);
+                let joined = std::mem::take(&mut current);
+                if !joined.trim().is_empty() {
+                    lines.push(joined.trim().to_string());
+                }
+            }
+        }
+    }
+    if !current.trim().is_empty() {
+        lines.push(current.trim().to_string());
+    }
+    lines
+}
+
+/// Drop a `#` comment; `#` followed by a digit is a uid, not a comment
+fn strip_comment(line: &str) -> String {
+    let bytes = line.as_bytes();
+    let mut quoted = false;
+    for (i, &byte) in bytes.iter().enumerate() {
+        match byte {
+            b'"' if i == 0 || bytes[i - 1] != b'\\' => quoted = !quoted,
+            b'#' if !quoted
+                && (i == 0 || bytes[i - 1] != b'\\')
+                && !bytes.get(i + 1).is_some_and(u8::is_ascii_digit) =>
+            {
+                return line[..i].trim().to_string();
+            }
+            _ => {}
+        }
+    }
+    line.trim().to_string()
+}
+
+/// Split on `separator` outside quotes and parentheses, unless escaped
+fn split_unescaped(text: &str, separator: char) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0usize;
+    let mut quoted = false;
+    let mut escaped = false;
+    for c in text.chars() {
+        if escaped {
+            current.push(c);
+            escaped = false;
+            continue;
+        }
+        match c {
+            '\\' => {
+                escaped = true;
+                current.push(c);
+                continue;
+            }
+            '"' => quoted = !quoted,
+            '(' if !quoted => depth += 1,
+            ')' if !quoted => depth = depth.saturating_sub(1),
+            _ if c == separator && !quoted && depth == 0 => {
+                parts.push(std::mem::take(&mut current));
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    parts.push(current);
+    parts
+}
+
+/// A comma-separated list, trimmed, with escaped commas kept
+fn split_list(text: &str) -> Vec<String> {
+    split_unescaped(text, ',')
+        .iter()
+        .map(|item| item.trim().replace("\\,", ","))
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+/// Expand alias references in `items`, keeping `!` negations
+fn expand(items: &[String], table: &HashMap<String, Vec<String>>, depth: usize) -> Vec<String> {
+    let mut expanded = Vec::new();
+    for item in items {
+        let (negated, name) = match item.strip_prefix('!') {
+            Some(name) => (true, name.trim()),
+            None => (false, item.as_str()),
+        };
+        match table.get(name) {
+            Some(members) if depth < MAX_ALIAS_DEPTH => {
+                for member in expand(members, table, depth + 1) {
+                    expanded.push(if negated {
+                        format!("!{}", member)
+                    } else {
+                        member
+                    });
+                }
+            }
+            _ => expanded.push(item.clone()),
+        }
+    }
+    expanded
+}
+
+/// Record the parameters of a `Defaults` line
+fn parse_defaults(defaults: &mut Vec<DefaultsEntry>, line: &str) {
+    let rest = &line["Defaults".len()..];
+    let (binding, params) = if rest.starts_with(['@', ':', '!', '>']) {
+        rest.split_once(char::is_whitespace).unwrap_or((rest, ""))
+    } else {
+        ("", rest)
+    };
+    for param in split_list(params) {
+        let (negated, param) = match param.strip_prefix('!') {
+            Some(param) => (true, param.trim()),
+            None => (false, param.as_str()),
+        };
+        let (name, operator, value) = match param.split_once('=') {
+            Some((name, value)) => match name.strip_suffix(['+', '-']) {
+                Some(base) => (base, &name[base.len()..], value),
+                None => (name, "", value),
+            },
+            None => (param, "", ""),
+        };
+        let name = name.trim().to_string();
+        let value = value.trim().trim_matches('"').to_string();
+
+        let existing = defaults
+            .iter()
+            .position(|entry| entry.binding == binding && entry.name == name);
+        let previous = existing.map(|i| defaults.remove(i));
+        let value = match (operator, previous) {
+            ("+", Some(previous)) if !previous.value.is_empty() => {
+                format!("{} {}", previous.value, value)
+            }
+            ("-", Some(previous)) => previous
+                .value
+                .split_whitespace()
+                .filter(|word| !value.split_whitespace().any(|removed| removed == *word))
+                .collect::<Vec<_>>()
+                .join(" "),
+            _ => value,
+        };
+        defaults.push(DefaultsEntry {
+            binding: binding.to_string(),
+            name,
+            value,
+            enabled: !negated,
+        });
+    }
+}
+
+/// Whether `text` could be a host list followed by `=`: `ALL =`, `web1, web2=`
+fn starts_host_spec(text: &str) -> bool {
+    let Some((hosts, _)) = text.split_once('=') else {
+        return false;
+    };
+    let hosts = hosts.trim();
+    !hosts.is_empty()
+        && !COMMAND_OPTIONS.contains(&hosts)
+        && hosts
+            .split(',')
+            .all(|host| !host.trim().is_empty() && !host.trim().contains([' ', '/', '(']))
+}
+
+/// Split the text after `users` into `(hosts, commands)` pairs
+///
+/// A `:` separates host specifications only when it does not end a tag
+/// (`NOPASSWD:`) and a host list follows it.
+fn split_host_specs(text: &str) -> Vec<(String, String)> {
+    let mut segments: Vec<String> = Vec::new();
+    for piece in split_unescaped(text, ':') {
+        let joins_previous = segments.last().is_some_and(|previous| {
+            let ends_with_tag = previous
+                .trim_end()
+                .rsplit(|c: char| c.is_whitespace() || c == ',' || c == ')')
+                .next()
+                .is_some_and(|word| TAGS.contains(&word));
+            ends_with_tag || !starts_host_spec(&piece)
+        });
+        match segments.last_mut() {
+            Some(previous) if joins_previous => {
+                previous.push(':');
+                previous.push_str(&piece);
+            }
+            _ => segments.push(piece),
+        }
+    }
+    segments
+        .iter()
+        .filter_map(|segment| {
+            let (hosts, commands) = segment.split_once('=')?;
+            Some((hosts.to_string(), commands.to_string()))
+        })
+        .collect()
+}
+
+/// Expand a user specification line into one entry per user and command
+fn parse_user_spec(line: &str, aliases: &Aliases) -> Option<Vec<UserSpec>> {
+    // Users are a comma-separated list ending at the first unjoined space
+    let mut end = 0;
+    let mut after_comma = true;
+    for (i, c) in line.char_indices() {
+        if c.is_whitespace() && !after_comma {
+            end = i;
+            break;
+        }
+        if c == ',' {
+            after_comma = true;
+        } else if !c.is_whitespace() {
+            after_comma = false;
+        }
+    }
+    if end == 0 {
+        return None;
+    }
+    let users = expand(&split_list(&line[..end]), &aliases.user, 0);
+    let host_specs = split_host_specs(&line[end..]);
+    if host_specs.is_empty() {
+        return None;
+    }
+
+    let mut specs = Vec::new();
+    for (hosts, commands) in host_specs {
+        let hosts = expand(&split_list(&hosts), &aliases.host, 0).join(", ");
+        let mut runas = "root".to_string();
+        let mut nopasswd = false;
+        for command_spec in split_list(&commands) {
+            let mut rest = command_spec.as_str();
+            if let Some(inner) = rest.strip_prefix('(') {
+                let (list, after) = inner.split_once(')').unwrap_or((inner, ""));
+                let (users, groups) = list.split_once(':').unwrap_or((list, ""));
+                let users = expand(&split_list(users), &aliases.runas, 0).join(", ");
+                let groups = expand(&split_list(groups), &aliases.runas, 0).join(", ");
+                runas = match (users.is_empty(), groups.is_empty()) {
+                    (_, true) => users,
+                    (true, false) => format!(":{}", groups),
+                    (false, false) => format!("{}:{}", users, groups),
+                };
+                rest = after.trim_start();
+            }
+            loop {
+                let word_end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+                let word = &rest[..word_end];
+                if let Some(tag) = word.strip_suffix(':').filter(|tag| TAGS.contains(tag)) {
+                    match tag {
+                        "NOPASSWD" => nopasswd = true,
+                        "PASSWD" => nopasswd = false,
+                        _ => {}
+                    }
+                } else if !word
+                    .split_once('=')
+                    .is_some_and(|(option, _)| COMMAND_OPTIONS.contains(&option))
+                {
+                    break;
+                }
+                rest = rest[word_end..].trim_start();
+            }
+            if rest.is_empty() {
+                continue;
+            }
+            for command in expand(&[rest.to_string()], &aliases.command, 0) {
+                for user in &users {
+                    specs.push(UserSpec {
+                        user: user.clone(),
+                        host: hosts.clone(),
+                        runas: runas.clone(),
+                        command: command.clone(),
+                        nopasswd,
+                    });
+                }
+            }
+        }
+    }
+    Some(specs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(dir: &Path, main: &str) -> Sudoers {
+        let path = dir.join("sudoers");
+        fs::write(&path, main).unwrap();
+        parse_sudoers(&path, &|p: &Path| Ok(p.to_path_buf()), &mut |p: &Path| {
+            fs::read_to_string(p)
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_user_specs_with_aliases_and_continuations() {
+        let dir = tempfile::tempdir().unwrap();
+        let sudoers = parse(
+            dir.path(),
+            "# Admins\n\
+             User_Alias ADMINS = alice, \\\n    bob\n\
+             Cmnd_Alias SERVICES = /usr/bin/systemctl restart *, /usr/bin/journalctl\n\
+             ADMINS ALL = (root) NOPASSWD: SERVICES, PASSWD: /usr/bin/vi\n\
+             %wheel ALL=(ALL:ALL) ALL # full access\n\
+             #1000 web1, web2 = (www) CWD=/srv /usr/bin/make : db1 = /usr/bin/psql\n",
+        );
+        assert!(sudoers.warnings.is_empty());
+        assert_eq!(sudoers.user_specs.len(), 6 + 1 + 2);
+
+        let vi = sudoers
+            .user_specs
+            .iter()
+            .find(|spec| spec.user == "bob" && spec.command == "/usr/bin/vi")
+            .unwrap();
+        assert_eq!(vi.runas, "root");
+        assert!(!vi.nopasswd);
+        assert!(sudoers
+            .user_specs
+            .iter()
+            .any(|spec| spec.command == "/usr/bin/systemctl restart *" && spec.nopasswd));
+
+        let wheel = &sudoers.user_specs[6];
+        assert_eq!(
+            (
+                wheel.user.as_str(),
+                wheel.runas.as_str(),
+                wheel.command.as_str()
+            ),
+            ("%wheel", "ALL:ALL", "ALL")
+        );
+
+        let psql = sudoers.user_specs.last().unwrap();
+        assert_eq!(psql.user, "#1000");
+        assert_eq!(psql.host, "db1");
+        assert_eq!(psql.runas, "root");
+        assert_eq!(sudoers.user_specs[7].host, "web1, web2");
+        assert_eq!(sudoers.user_specs[7].command, "/usr/bin/make");
+    }
+
+    #[test]
+    fn test_includedir_and_defaults() {
+        let dir = tempfile::tempdir().unwrap();
+        let sudoers_d = dir.path().join("sudoers.d");
+        fs::create_dir(&sudoers_d).unwrap();
+        fs::write(sudoers_d.join("10-pty"), "Defaults use_pty\n").unwrap();
+        fs::write(sudoers_d.join("20-ops"), "ops ALL=(ALL) NOPASSWD: ALL\n").unwrap();
+        fs::write(sudoers_d.join("README.txt"), "evil ALL=(ALL) ALL\n").unwrap();
+
+        let sudoers = parse(
+            dir.path(),
+            "Defaults !use_pty, logfile=\"/var/log/sudo.log\"\n\
+             Defaults env_keep = \"LANG\"\n\
+             Defaults env_keep += \"TZ HOME\"\n\
+             Defaults env_keep -= HOME\n\
+             Defaults:ops !requiretty\n\
+             #includedir sudoers.d\n",
+        );
+        let find = |binding: &str, name: &str| {
+            sudoers
+                .defaults
+                .iter()
+                .find(|entry| entry.binding == binding && entry.name == name)
+                .unwrap()
+        };
+        assert!(find("", "use_pty").enabled);
+        assert_eq!(find("", "logfile").value, "/var/log/sudo.log");
+        assert_eq!(find("", "env_keep").value, "LANG TZ");
+        assert!(!find(":ops", "requiretty").enabled);
+
+        assert_eq!(sudoers.user_specs.len(), 1);
+        assert_eq!(sudoers.user_specs[0].user, "ops");
+        assert!(sudoers.user_specs[0].nopasswd);
+    }
+}
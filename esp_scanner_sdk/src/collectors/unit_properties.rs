@@ -0,0 +1,164 @@
+//! # systemd Unit Properties
+//!
+//! Parses `systemctl show <unit>` output into the record the
+//! `systemd_unit_property` criterion type checks, one field per property.
+//!
+//! Values that are canonical decimal integers, such as `LimitNOFILE=524288`,
+//! become integers; `UMask=0022`, `infinity` and `yes`/`no` stay strings.
+//! Properties holding several values become lists, so each entry can be
+//! checked with an entity check:
+//!
+//! - a property printed on several lines, such as a socket's `Listen`
+//! - the dependency and security properties in [`LIST_PROPERTIES`], split
+//!   on whitespace with shell quoting, so `Environment="A=two words"` is one
+//!   entry
+//! - `Exec*` commands, as the command line of each, from its `argv[]`
+
+use super::environment::shell_words;
+use super::record_formats::typed_value;
+
+/// `LoadState` of a unit systemd has no unit file or definition for
+pub const NOT_FOUND_LOAD_STATE: &str = "not-found";
+
+/// Properties printed as one space-separated list
+pub const LIST_PROPERTIES: &[&str] = &[
+    "Names",
+    "Requires",
+    "Requisite",
+    "Wants",
+    "BindsTo",
+    "PartOf",
+    "Upholds",
+    "RequiredBy",
+    "RequisiteOf",
+    "WantedBy",
+    "BoundBy",
+    "UpheldBy",
+    "ConsistsOf",
+    "Conflicts",
+    "ConflictedBy",
+    "Before",
+    "After",
+    "OnFailure",
+    "OnSuccess",
+    "Triggers",
+    "TriggeredBy",
+    "PropagatesReloadTo",
+    "ReloadPropagatedFrom",
+    "JoinsNamespaceOf",
+    "CapabilityBoundingSet",
+    "AmbientCapabilities",
+    "ReadWritePaths",
+    "ReadOnlyPaths",
+    "InaccessiblePaths",
+    "ExecPaths",
+    "NoExecPaths",
+    "SupplementaryGroups",
+    "Environment",
+];
+
+/// Parse `Key=Value` lines into a record of typed properties
+pub fn parse(stdout: &str) -> serde_json::Value {
+    let mut properties = serde_json::Map::new();
+    for (name, value) in stdout.lines().filter_map(|line| line.split_once('=')) {
+        let name = name.trim();
+        let value = value.trim();
+        if name.starts_with("Exec") {
+            push_entry(&mut properties, name, exec_command_line(value).into());
+            continue;
+        }
+        if LIST_PROPERTIES.contains(&name) {
+            let entries = shell_words(value)
+                .unwrap_or_else(|| value.split_whitespace().map(str::to_string).collect());
+            properties.insert(
+                name.to_string(),
+                serde_json::Value::Array(entries.into_iter().map(Into::into).collect()),
+            );
+            continue;
+        }
+        match properties.get_mut(name) {
+            // A repeated property holds every value
+            Some(serde_json::Value::Array(values)) => values.push(typed_value(value)),
+            Some(previous) => {
+                let first = previous.take();
+                *previous = serde_json::Value::Array(vec![first, typed_value(value)]);
+            }
+            None => {
+                properties.insert(name.to_string(), typed_value(value));
+            }
+        }
+    }
+    serde_json::Value::Object(properties)
+}
+
+fn push_entry(
+    properties: &mut serde_json::Map<String, serde_json::Value>,
+    name: &str,
+    value: serde_json::Value,
+) {
+    let entry = properties
+        .entry(name.to_string())
+        .or_insert_with(|| serde_json::Value::Array(Vec::new()));
+    if let serde_json::Value::Array(values) = entry {
+        values.push(value);
+    }
+}
+
+/// The command line of an `Exec*` value such as
+/// `{ path=/usr/sbin/sshd ; argv[]=/usr/sbin/sshd -D $OPTIONS ; ... }`
+fn exec_command_line(value: &str) -> String {
+    value
+        .split(" ; ")
+        .find_map(|part| part.trim_start_matches("{ ").strip_prefix("argv[]="))
+        .unwrap_or(value)
+        .trim()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_typed_lists_and_exec_commands() {
+        let show = "\
+Id=sshd.service
+LoadState=loaded
+User=
+ProtectSystem=strict
+LimitNOFILE=524288
+UMask=0022
+CapabilityBoundingSet=cap_chown cap_net_bind_service
+TriggeredBy=sshd.socket
+Environment=LANG=C \"MOTD=hello world\"
+ExecStartPre={ path=/usr/sbin/sshd ; argv[]=/usr/sbin/sshd -t ; ignore_errors=no ; start_time=[n/a] ; stop_time=[n/a] ; pid=0 ; code=(null) ; status=0/0 }
+ExecStart={ path=/usr/sbin/sshd ; argv[]=/usr/sbin/sshd -D $OPTIONS ; ignore_errors=no ; start_time=[n/a] ; stop_time=[n/a] ; pid=0 ; code=(null) ; status=0/0 }
+Listen=[::]:22 (Stream)
+Listen=0.0.0.0:2222 (Stream)
+";
+        let properties = parse(show);
+        assert_eq!(properties["ProtectSystem"], "strict");
+        assert_eq!(properties["User"], "");
+        assert_eq!(properties["LimitNOFILE"], 524288);
+        assert_eq!(properties["UMask"], "0022");
+        assert_eq!(
+            properties["CapabilityBoundingSet"],
+            json!(["cap_chown", "cap_net_bind_service"])
+        );
+        assert_eq!(properties["TriggeredBy"], json!(["sshd.socket"]));
+        assert_eq!(
+            properties["Environment"],
+            json!(["LANG=C", "MOTD=hello world"])
+        );
+        assert_eq!(properties["ExecStartPre"], json!(["/usr/sbin/sshd -t"]));
+        assert_eq!(
+            properties["ExecStart"],
+            json!(["/usr/sbin/sshd -D $OPTIONS"])
+        );
+        assert_eq!(
+            properties["Listen"],
+            json!(["[::]:22 (Stream)", "0.0.0.0:2222 (Stream)"])
+        );
+    }
+}
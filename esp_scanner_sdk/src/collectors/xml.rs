@@ -0,0 +1,204 @@
+//! # XML Records
+//!
+//! Parses XML files, such as Tomcat's `server.xml` or JBoss's
+//! `standalone.xml`, into the record `xml_record` checks are evaluated
+//! against. An element is a record holding its attributes as `@name`, its
+//! text as `#text`, and its child elements as lists by name, in document
+//! order; the top of the record holds the root element the same way.
+//!
+//! Field paths name elements from the root down, as in
+//! `Server.Service.Connector.@port`. [`record_path`] turns such a path into
+//! one over the record: every element step matches all elements of that
+//! name, so a path reaching several elements yields one value per element
+//! for entity checks, and a path ending at an element compares its text.
+//! An index picks one element instead, as in `Service.0.Connector`.
+//!
+//! Names are matched on their local part, ignoring namespace prefixes.
+//! Hyphens and dots in names are written as underscores, since field path
+//! components are identifiers: `socket-binding-group` is
+//! `socket_binding_group`. With [`XmlOptions::namespaces`] set, matching is
+//! namespace-strict: elements and attributes in any other namespace are left
+//! out of the record, while those in no namespace stay.
+//!
+//! Attribute and text values are typed as INI values are: decimal integers
+//! and `true`/`false` become those types, anything else is a string.
+
+use super::record_formats::typed_value;
+
+/// Key of an element's text in its record
+pub const TEXT_FIELD: &str = "#text";
+
+/// How an XML file is read
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct XmlOptions {
+    /// Namespace URIs elements and attributes may be in; any namespace when
+    /// unset
+    pub namespaces: Option<Vec<String>>,
+}
+
+impl XmlOptions {
+    fn admits(&self, namespace: Option<&str>) -> bool {
+        match (namespace, &self.namespaces) {
+            (Some(uri), Some(allowed)) => allowed.iter().any(|a| a == uri),
+            _ => true,
+        }
+    }
+}
+
+/// Parse XML content into a record value
+///
+/// A parse error names its byte offset into `content`.
+pub fn parse(content: &str, options: &XmlOptions) -> Result<serde_json::Value, String> {
+    let parsing = roxmltree::ParsingOptions {
+        allow_dtd: true,
+        ..Default::default()
+    };
+    let document = roxmltree::Document::parse_with_options(content, parsing)
+        .map_err(|e| format!("{} (byte offset {})", e, byte_offset(content, e.pos())))?;
+
+    let mut top = serde_json::Map::new();
+    let root = document.root_element();
+    if options.admits(root.tag_name().namespace()) {
+        top.insert(
+            field_name(root.tag_name().name()),
+            serde_json::Value::Array(vec![element_record(root, options)]),
+        );
+    }
+    Ok(serde_json::Value::Object(top))
+}
+
+/// A record field path for an element path
+///
+/// Each element step is followed by a wildcard over its list of matches,
+/// unless the next step is an index, and a path ending at an element goes on
+/// to its text.
+pub fn record_path(components: &[String]) -> Vec<String> {
+    let is_index = |c: &str| c.parse::<usize>().is_ok();
+    let is_element = |c: &str| !c.starts_with('@') && c != "*" && !is_index(c);
+
+    let mut path = Vec::with_capacity(components.len() * 2 + 1);
+    for (i, component) in components.iter().enumerate() {
+        path.push(component.clone());
+        let next_is_index = components.get(i + 1).is_some_and(|c| is_index(c));
+        if is_element(component) && !next_is_index {
+            path.push("*".to_string());
+        }
+    }
+    if components.last().is_some_and(|c| !c.starts_with('@')) {
+        path.push(TEXT_FIELD.to_string());
+    }
+    path
+}
+
+fn element_record(node: roxmltree::Node, options: &XmlOptions) -> serde_json::Value {
+    let mut record = serde_json::Map::new();
+
+    for attribute in node.attributes() {
+        if options.admits(attribute.namespace()) {
+            record.insert(
+                format!("@{}", field_name(attribute.name())),
+                typed_value(attribute.value()),
+            );
+        }
+    }
+
+    let text: String = node
+        .children()
+        .filter(|child| child.is_text())
+        .filter_map(|child| child.text())
+        .collect();
+    let text = text.trim();
+    if !text.is_empty() {
+        record.insert(TEXT_FIELD.to_string(), typed_value(text));
+    }
+
+    for child in node.children().filter(|child| child.is_element()) {
+        if !options.admits(child.tag_name().namespace()) {
+            continue;
+        }
+        let entry = record
+            .entry(field_name(child.tag_name().name()))
+            .or_insert_with(|| serde_json::Value::Array(Vec::new()));
+        if let serde_json::Value::Array(elements) = entry {
+            elements.push(element_record(child, options));
+        }
+    }
+
+    serde_json::Value::Object(record)
+}
+
+/// A local name as a field path component
+fn field_name(name: &str) -> String {
+    name.replace(['-', '.'], "_")
+}
+
+/// Byte offset of a 1-based row and column, the column counted in characters
+fn byte_offset(content: &str, pos: roxmltree::TextPos) -> usize {
+    let mut offset = 0;
+    for (row, line) in content.split_inclusive('\n').enumerate() {
+        if row + 1 == pos.row as usize {
+            return offset
+                + line
+                    .char_indices()
+                    .nth(pos.col.saturating_sub(1) as usize)
+                    .map_or(line.len(), |(i, _)| i);
+        }
+        offset += line.len();
+    }
+    content.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const SERVER_XML: &str = r#"<?xml version="1.0"?>
+<Server port="8005" xmlns:x="urn:extra">
+  <Service name="Catalina">
+    <Connector port="8443" SSLEnabled="true" x:debug="1"/>
+    <Connector port="8080" protocol="HTTP/1.1"/>
+    <x:Valve className="ignored"/>
+    <Engine-Name>  Catalina  </Engine-Name>
+  </Service>
+</Server>
+"#;
+
+    #[test]
+    fn test_elements_attributes_and_namespaces() {
+        let value = parse(SERVER_XML, &XmlOptions::default()).unwrap();
+        let service = &value["Server"][0]["Service"][0];
+        assert_eq!(value["Server"][0]["@port"], 8005);
+        assert_eq!(
+            service["Connector"][0],
+            json!({"@port": 8443, "@SSLEnabled": true, "@debug": 1})
+        );
+        assert_eq!(service["Connector"][1]["@protocol"], "HTTP/1.1");
+        assert_eq!(service["Engine_Name"][0]["#text"], "Catalina");
+        assert_eq!(service["Valve"][0]["@className"], "ignored");
+
+        let strict = XmlOptions {
+            namespaces: Some(vec![]),
+        };
+        let value = parse(SERVER_XML, &strict).unwrap();
+        let service = &value["Server"][0]["Service"][0];
+        assert!(service.get("Valve").is_none());
+        assert!(service["Connector"][0].get("@debug").is_none());
+    }
+
+    #[test]
+    fn test_record_path_and_parse_errors() {
+        let path = |p: &str| record_path(&p.split('.').map(str::to_string).collect::<Vec<_>>());
+        assert_eq!(
+            path("Server.Service.Connector.@port"),
+            ["Server", "*", "Service", "*", "Connector", "*", "@port"]
+        );
+        assert_eq!(
+            path("Server.Service.0.Engine_Name"),
+            ["Server", "*", "Service", "0", "Engine_Name", "*", "#text"]
+        );
+
+        let err = parse("<Server>\n  <Service></Server>", &XmlOptions::default()).unwrap_err();
+        assert!(err.contains("byte offset 20"), "{}", err);
+    }
+}
@@ -0,0 +1,71 @@
+//! Debian command executor configuration
+//!
+//! Provides a whitelisted command executor for Debian and Ubuntu scanning.
+
+use esp_scanner_base::strategies::SystemCommandExecutor;
+use std::time::Duration;
+
+/// Create command executor configured for Debian-family hosts
+///
+/// Whitelist includes:
+/// - dpkg-query: Package database queries
+/// - systemctl: Service status checks
+/// - sysctl: Kernel parameter queries
+/// - getenforce, getsebool: SELinux state, when selinux-utils is installed
+/// - auditctl: Audit rule inspection
+/// - id: User identity information
+/// - stat: File metadata queries
+/// - getent: User/group database queries
+/// - firewall-cmd: firewalld zone queries
+/// - lsmod, modprobe: Kernel module queries
+/// - ss: Listening socket queries
+/// - pgrep: Process lookup by name
+/// - crontab: Per-user cron tables (`crontab -l -u`)
+pub fn create_debian_command_executor() -> SystemCommandExecutor {
+    let mut executor = SystemCommandExecutor::with_timeout(Duration::from_secs(5));
+
+    executor.allow_commands(&[
+        "dpkg-query",   // Package database
+        "systemctl",    // Service status
+        "auditctl",     // Audit rules
+        "sysctl",       // Kernel parameters
+        "getenforce",   // SELinux status
+        "getsebool",    // SELinux booleans
+        "id",           // User info
+        "stat",         // File metadata
+        "getent",       // User/group database
+        "firewall-cmd", // firewalld zones
+        "lsmod",        // Loaded kernel modules
+        "modprobe",     // Kernel module configuration
+        "ss",           // Listening sockets
+        "pgrep",        // Process lookup by name
+        "crontab",      // Per-user cron tables
+    ]);
+
+    executor
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_debian_executor_whitelist() {
+        let executor = create_debian_command_executor();
+
+        assert!(executor.is_allowed("dpkg-query"));
+        assert!(executor.is_allowed("systemctl"));
+        assert!(executor.is_allowed("sysctl"));
+        assert!(executor.is_allowed("getsebool"));
+        assert!(executor.is_allowed("lsmod"));
+        assert!(executor.is_allowed("ss"));
+        assert!(executor.is_allowed("pgrep"));
+        assert!(executor.is_allowed("crontab"));
+
+        // No RPM tooling, and no package changes
+        assert!(!executor.is_allowed("rpm"));
+        assert!(!executor.is_allowed("dpkg"));
+        assert!(!executor.is_allowed("apt-get"));
+        assert!(!executor.is_allowed("rm"));
+    }
+}
@@ -2,6 +2,40 @@
 //!
 //! Provides whitelisted command executors for secure system scanning.
 
+pub mod debian;
 pub mod rhel9;
 
+pub use debian::create_debian_command_executor;
 pub use rhel9::create_rhel9_command_executor;
+
+use esp_scanner_base::strategies::SystemCommandExecutor;
+use serde::Deserialize;
+
+/// Target platform family, selecting the command whitelist and package manager
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Platform {
+    /// RHEL 9 and derivatives: `rpm` packages
+    #[default]
+    Rhel9,
+    /// Debian, Ubuntu and derivatives: `dpkg` packages
+    Debian,
+}
+
+impl Platform {
+    /// Short name used in collector ids
+    pub fn name(self) -> &'static str {
+        match self {
+            Platform::Rhel9 => "rhel9",
+            Platform::Debian => "debian",
+        }
+    }
+
+    /// The whitelisted command executor for this platform
+    pub fn command_executor(self) -> SystemCommandExecutor {
+        match self {
+            Platform::Rhel9 => create_rhel9_command_executor(),
+            Platform::Debian => create_debian_command_executor(),
+        }
+    }
+}
@@ -11,23 +11,36 @@ use std::time::Duration;
 /// - rpm: Package management queries
 /// - systemctl: Service status checks
 /// - getenforce: SELinux enforcement mode
+/// - getsebool: SELinux boolean values
 /// - sysctl: Kernel parameter queries
 /// - auditctl: Audit rule inspection
 /// - id: User identity information
 /// - stat: File metadata queries
 /// - getent: User/group database queries
+/// - firewall-cmd: firewalld zone queries
+/// - lsmod, modprobe: Kernel module queries
+/// - ss: Listening socket queries
+/// - pgrep: Process lookup by name
+/// - crontab: Per-user cron tables (`crontab -l -u`)
 pub fn create_rhel9_command_executor() -> SystemCommandExecutor {
     let mut executor = SystemCommandExecutor::with_timeout(Duration::from_secs(5));
 
     executor.allow_commands(&[
-        "rpm",        // Package management
-        "systemctl",  // Service status
-        "getenforce", // SELinux status
-        "auditctl",   // Audit rules
-        "sysctl",     // Kernel parameters
-        "id",         // User info
-        "stat",       // File metadata
-        "getent",     // User/group database
+        "rpm",          // Package management
+        "systemctl",    // Service status
+        "getenforce",   // SELinux status
+        "getsebool",    // SELinux booleans
+        "auditctl",     // Audit rules
+        "sysctl",       // Kernel parameters
+        "id",           // User info
+        "stat",         // File metadata
+        "getent",       // User/group database
+        "firewall-cmd", // firewalld zones
+        "lsmod",        // Loaded kernel modules
+        "modprobe",     // Kernel module configuration
+        "ss",           // Listening sockets
+        "pgrep",        // Process lookup by name
+        "crontab",      // Per-user cron tables
     ]);
 
     executor
@@ -55,7 +68,14 @@ mod tests {
         assert!(executor.is_allowed("rpm"));
         assert!(executor.is_allowed("systemctl"));
         assert!(executor.is_allowed("getenforce"));
+        assert!(executor.is_allowed("getsebool"));
         assert!(executor.is_allowed("sysctl"));
+        assert!(executor.is_allowed("firewall-cmd"));
+        assert!(executor.is_allowed("lsmod"));
+        assert!(executor.is_allowed("modprobe"));
+        assert!(executor.is_allowed("ss"));
+        assert!(executor.is_allowed("pgrep"));
+        assert!(executor.is_allowed("crontab"));
 
         // Test that random commands are NOT whitelisted
         assert!(!executor.is_allowed("rm"));
@@ -0,0 +1,151 @@
+//! # Scanner Configuration
+//!
+//! Host-level settings that apply to every scan regardless of policy content,
+//! loaded from a TOML file and/or command-line flags.
+//!
+//! ```toml
+//! platform = "debian"
+//!
+//! [paths]
+//! deny = ["/home", "/var/lib/pgsql"]
+//! allow = []
+//! ```
+
+use crate::commands::Platform;
+use esp_scanner_base::strategies::{PathPolicy, ScanTarget};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Scanner-wide configuration
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScannerConfig {
+    /// Platform family of the scanned host; `rhel9` when unset
+    #[serde(default)]
+    pub platform: Platform,
+    #[serde(default)]
+    pub paths: PathsConfig,
+    /// Filesystem to scan; the host unless `--root` names a mounted image
+    #[serde(skip)]
+    pub target: Arc<ScanTarget>,
+}
+
+/// `[paths]` section: filesystem areas the scanner may never read
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PathsConfig {
+    /// Prefixes that are never opened or stat'ed
+    #[serde(default)]
+    pub deny: Vec<PathBuf>,
+    /// If non-empty, only these prefixes may be read
+    #[serde(default)]
+    pub allow: Vec<PathBuf>,
+}
+
+impl ScannerConfig {
+    /// Parse a TOML configuration document
+    pub fn from_toml(content: &str) -> Result<Self, String> {
+        toml::from_str(content).map_err(|e| format!("Invalid scanner config: {}", e))
+    }
+
+    /// Load a TOML configuration file
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Cannot read scanner config '{}': {}", path.display(), e))?;
+        Self::from_toml(&content)
+    }
+
+    /// Scan a host of the given platform family
+    pub fn with_platform(mut self, platform: Platform) -> Self {
+        self.platform = platform;
+        self
+    }
+
+    /// Scan a mounted root instead of the host (e.g. from `--root`)
+    pub fn with_scan_target(mut self, target: Arc<ScanTarget>) -> Self {
+        self.target = target;
+        self
+    }
+
+    /// Add a denied prefix (e.g. from `--deny-path`)
+    pub fn with_deny_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.paths.deny.push(path.into());
+        self
+    }
+
+    /// Add an allowed prefix (e.g. from `--allow-path`)
+    pub fn with_allow_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.paths.allow.push(path.into());
+        self
+    }
+
+    /// Build the path policy shared by filesystem collectors
+    pub fn path_policy(&self) -> Arc<PathPolicy> {
+        let policy = self
+            .paths
+            .deny
+            .iter()
+            .fold(PathPolicy::new(), |policy, p| policy.with_deny(p));
+        let policy = self
+            .paths
+            .allow
+            .iter()
+            .fold(policy, |policy, p| policy.with_allow(p));
+        Arc::new(policy)
+    }
+
+    /// The scan target shared by collectors and the engine
+    pub fn scan_target(&self) -> Arc<ScanTarget> {
+        self.target.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_paths_section() {
+        let config = ScannerConfig::from_toml(
+            r#"
+            [paths]
+            deny = ["/home", "/var/lib/pgsql"]
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.paths.deny.len(), 2);
+        assert!(config.paths.allow.is_empty());
+
+        let policy = config.path_policy();
+        assert!(!policy.permits(Path::new("/home/alice")));
+        assert!(policy.permits(Path::new("/etc/passwd")));
+    }
+
+    #[test]
+    fn test_flags_extend_file_config() {
+        let config = ScannerConfig::from_toml("[paths]\ndeny = [\"/home\"]\n")
+            .unwrap()
+            .with_deny_path("/root")
+            .with_allow_path("/etc");
+
+        let policy = config.path_policy();
+        assert!(!policy.permits(Path::new("/root/.bashrc")));
+        assert!(!policy.permits(Path::new("/var/log/messages")));
+        assert!(policy.permits(Path::new("/etc/hosts")));
+    }
+
+    #[test]
+    fn test_platform_defaults_to_rhel9() {
+        assert_eq!(ScannerConfig::default().platform, Platform::Rhel9);
+        let config = ScannerConfig::from_toml("platform = \"debian\"\n").unwrap();
+        assert_eq!(config.platform, Platform::Debian);
+        assert!(ScannerConfig::from_toml("platform = \"solaris\"\n").is_err());
+    }
+
+    #[test]
+    fn test_unknown_keys_are_rejected() {
+        assert!(ScannerConfig::from_toml("[paths]\ndenied = [\"/home\"]\n").is_err());
+    }
+}
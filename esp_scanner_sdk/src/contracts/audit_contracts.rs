@@ -0,0 +1,156 @@
+//! Audit rules CTN contract
+//!
+//! Validates Linux audit watches and syscall rules, loaded in the kernel and
+//! persisted under `/etc/audit/rules.d`. Each state field is checked across
+//! the object's rules and combined with the field's entity check, so
+//! `key string = \`identity\` at_least_one` passes when any rule has the key.
+
+use esp_scanner_base::strategies::{
+    CollectionMode, CollectionStrategy, CtnContract, ObjectFieldSpec, PerformanceHints,
+    PrivilegeRequirement, StateFieldSpec,
+};
+use esp_scanner_base::types::common::{DataType, Operation};
+
+pub fn create_audit_rules_contract() -> CtnContract {
+    let mut contract = CtnContract::new("audit_rules".to_string());
+
+    // Object requirements
+    let object_fields = [
+        (
+            "source",
+            "Rule set to check: loaded, disk or both",
+            "both",
+            "Defaults to both, which keeps rules that are loaded and persisted",
+        ),
+        (
+            "rules_dir",
+            "Directory of persisted rule files",
+            "/etc/audit/rules.d",
+            "Defaults to /etc/audit/rules.d; only *.rules files are read",
+        ),
+        (
+            "path",
+            "Only rules watching this path",
+            "/etc/passwd",
+            "Matches watches and path/dir syscall filters exactly",
+        ),
+        (
+            "key",
+            "Only rules tagged with this key",
+            "identity",
+            "Matches any of a rule's keys",
+        ),
+        (
+            "syscall",
+            "Only rules auditing this syscall",
+            "settimeofday",
+            "Matches any of a rule's syscalls",
+        ),
+    ];
+    for (name, description, example, notes) in object_fields {
+        contract
+            .object_requirements
+            .add_optional_field(ObjectFieldSpec {
+                name: name.to_string(),
+                data_type: DataType::String,
+                description: description.to_string(),
+                example_values: vec![example.to_string()],
+                validation_notes: Some(notes.to_string()),
+            });
+        contract
+            .field_mappings
+            .collection_mappings
+            .object_to_collection
+            .insert(name.to_string(), name.to_string());
+    }
+
+    // State requirements
+    let string_operations = vec![
+        Operation::Equals,
+        Operation::NotEqual,
+        Operation::Contains,
+        Operation::NotContains,
+        Operation::StartsWith,
+        Operation::EndsWith,
+        Operation::PatternMatch,
+        Operation::EqualsAny,
+        Operation::NotEqualsAny,
+    ];
+
+    let state_fields = [
+        ("type", "Rule kind: watch or syscall", "watch"),
+        (
+            "path",
+            "Watched path, or the path/dir filter",
+            "/etc/passwd",
+        ),
+        ("permissions", "Watched access types, in rwxa order", "wa"),
+        ("key", "Comma-separated rule keys", "identity"),
+        (
+            "syscalls",
+            "Comma-separated syscalls of a syscall rule",
+            "adjtimex,settimeofday",
+        ),
+        ("arch", "Architecture filter of a syscall rule", "b64"),
+        ("action", "Syscall rule action: always or never", "always"),
+        ("list", "Syscall rule list, e.g. exit", "exit"),
+        (
+            "rule",
+            "The rule text as written",
+            "-w /etc/passwd -p wa -k identity",
+        ),
+        (
+            "file",
+            "Rule file the rule came from; empty when loaded",
+            "/etc/audit/rules.d/30-identity.rules",
+        ),
+        ("source", "Rule set the rule was checked in", "both"),
+    ];
+    for (name, description, example) in state_fields {
+        contract
+            .state_requirements
+            .add_optional_field(StateFieldSpec {
+                name: name.to_string(),
+                data_type: DataType::String,
+                allowed_operations: string_operations.clone(),
+                description: description.to_string(),
+                example_values: vec![example.to_string()],
+                validation_notes: Some(
+                    "Checked on every rule; the field's entity check combines them (default all)"
+                        .to_string(),
+                ),
+            });
+        contract
+            .field_mappings
+            .validation_mappings
+            .state_to_data
+            .insert(name.to_string(), name.to_string());
+        contract
+            .field_mappings
+            .collection_mappings
+            .optional_data_fields
+            .push(name.to_string());
+    }
+
+    contract
+        .field_mappings
+        .collection_mappings
+        .required_data_fields = vec!["rules".to_string()];
+
+    // Collection strategy
+    contract.collection_strategy = CollectionStrategy {
+        collector_type: "command".to_string(),
+        collection_mode: CollectionMode::Command,
+        required_capabilities: vec!["execute_auditctl".to_string(), "file_access".to_string()],
+        performance_hints: PerformanceHints {
+            expected_collection_time_ms: Some(100),
+            memory_usage_mb: Some(1),
+            network_intensive: false,
+            cpu_intensive: false,
+            requires_elevated_privileges: true,
+        },
+        required_privilege: PrivilegeRequirement::Root,
+    };
+
+    contract
+}
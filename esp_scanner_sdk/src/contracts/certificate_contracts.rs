@@ -0,0 +1,202 @@
+//! X.509 certificate CTN contract
+//!
+//! Validates certificates in PEM or DER files. The object's `path` may be a
+//! file, a directory, or a glob such as `/etc/pki/tls/certs/*.pem`, and every
+//! certificate found is an item, so one criterion covers a whole directory.
+//! A file that is not a certificate is an item that fails with its error.
+
+use esp_scanner_base::strategies::{
+    CollectionMode, CollectionStrategy, CtnContract, ObjectFieldSpec, PerformanceHints,
+    PrivilegeRequirement, StateFieldSpec,
+};
+use esp_scanner_base::types::common::{DataType, Operation};
+
+pub fn create_certificate_contract() -> CtnContract {
+    let mut contract = CtnContract::new("x509_certificate".to_string());
+
+    // Object requirements
+    contract
+        .object_requirements
+        .add_required_field(ObjectFieldSpec {
+            name: "path".to_string(),
+            data_type: DataType::String,
+            description: "Certificate file, directory of certificates, or glob".to_string(),
+            example_values: vec![
+                "/etc/pki/tls/certs/server.crt".to_string(),
+                "/etc/pki/tls/certs/*.pem".to_string(),
+            ],
+            validation_notes: Some(
+                "Globs apply to the last path component; hidden files are skipped".to_string(),
+            ),
+        });
+
+    // State requirements
+    let string_operations = vec![
+        Operation::Equals,
+        Operation::NotEqual,
+        Operation::Contains,
+        Operation::NotContains,
+        Operation::StartsWith,
+        Operation::EndsWith,
+        Operation::PatternMatch,
+        Operation::EqualsAny,
+        Operation::NotEqualsAny,
+    ];
+    let int_operations = vec![
+        Operation::Equals,
+        Operation::NotEqual,
+        Operation::GreaterThan,
+        Operation::LessThan,
+        Operation::GreaterThanOrEqual,
+        Operation::LessThanOrEqual,
+        Operation::EqualsAny,
+        Operation::NotEqualsAny,
+    ];
+    let bool_operations = vec![Operation::Equals, Operation::NotEqual];
+
+    let state_fields = [
+        (
+            "subject",
+            DataType::String,
+            "Subject name",
+            "C=US, O=Example, CN=www.example.com",
+            Some("Attributes in certificate order, joined with `, `"),
+        ),
+        (
+            "issuer",
+            DataType::String,
+            "Issuer name",
+            "C=US, O=Example, CN=Example CA",
+            None,
+        ),
+        (
+            "not_after_epoch",
+            DataType::Int,
+            "End of validity, in seconds since the Unix epoch",
+            "1893456000",
+            None,
+        ),
+        (
+            "days_until_expiry",
+            DataType::Int,
+            "Whole days of validity left",
+            "30",
+            Some("Negative once the certificate has expired"),
+        ),
+        (
+            "key_algorithm",
+            DataType::String,
+            "Public key algorithm",
+            "RSA",
+            Some("`RSA`, `EC`, `DSA`, `Ed25519`, `Ed448`, or the algorithm OID"),
+        ),
+        (
+            "key_bits",
+            DataType::Int,
+            "Public key size in bits",
+            "2048",
+            Some("RSA modulus or EC curve size; 0 when unknown"),
+        ),
+        (
+            "self_signed",
+            DataType::Boolean,
+            "Whether the issuer is the subject",
+            "false",
+            None,
+        ),
+    ];
+
+    for (name, data_type, description, example, notes) in state_fields {
+        let allowed_operations = match data_type {
+            DataType::Int => int_operations.clone(),
+            DataType::Boolean => bool_operations.clone(),
+            _ => string_operations.clone(),
+        };
+        contract
+            .state_requirements
+            .add_optional_field(StateFieldSpec {
+                name: name.to_string(),
+                data_type,
+                allowed_operations,
+                description: description.to_string(),
+                example_values: vec![example.to_string()],
+                validation_notes: notes.map(str::to_string),
+            });
+        contract
+            .field_mappings
+            .validation_mappings
+            .state_to_data
+            .insert(name.to_string(), name.to_string());
+        contract
+            .field_mappings
+            .collection_mappings
+            .optional_data_fields
+            .push(name.to_string());
+    }
+
+    // Validity bounds as datetimes, read from the epoch fields
+    let datetime_operations = vec![
+        Operation::Equals,
+        Operation::NotEqual,
+        Operation::GreaterThan,
+        Operation::LessThan,
+        Operation::GreaterThanOrEqual,
+        Operation::LessThanOrEqual,
+    ];
+    for (name, data_field, description) in [
+        ("not_before", "not_before_epoch", "Start of validity"),
+        ("not_after", "not_after_epoch", "End of validity"),
+    ] {
+        contract
+            .state_requirements
+            .add_optional_field(StateFieldSpec {
+                name: name.to_string(),
+                data_type: DataType::DateTime,
+                allowed_operations: datetime_operations.clone(),
+                description: description.to_string(),
+                example_values: vec!["now+30d".to_string()],
+                validation_notes: Some("`not_after > now+30d` checks expiry".to_string()),
+            });
+        contract
+            .field_mappings
+            .validation_mappings
+            .state_to_data
+            .insert(name.to_string(), data_field.to_string());
+        let optional_fields = &mut contract
+            .field_mappings
+            .collection_mappings
+            .optional_data_fields;
+        if !optional_fields.iter().any(|field| field == data_field) {
+            optional_fields.push(data_field.to_string());
+        }
+    }
+
+    // Field mappings
+    contract
+        .field_mappings
+        .collection_mappings
+        .object_to_collection
+        .insert("path".to_string(), "file_path".to_string());
+
+    contract
+        .field_mappings
+        .collection_mappings
+        .required_data_fields = vec!["certificates".to_string()];
+
+    // Collection strategy
+    contract.collection_strategy = CollectionStrategy {
+        collector_type: "filesystem".to_string(),
+        collection_mode: CollectionMode::Content,
+        required_capabilities: vec!["file_access".to_string()],
+        performance_hints: PerformanceHints {
+            expected_collection_time_ms: Some(20),
+            memory_usage_mb: Some(1),
+            network_intensive: false,
+            cpu_intensive: false,
+            requires_elevated_privileges: false,
+        },
+        required_privilege: PrivilegeRequirement::None,
+    };
+
+    contract
+}
@@ -7,7 +7,7 @@
 
 use esp_scanner_base::strategies::{
     CollectionMode, CollectionStrategy, CtnContract, ObjectFieldSpec, PerformanceHints,
-    StateFieldSpec,
+    PrivilegeRequirement, StateFieldSpec,
 };
 use esp_scanner_base::types::common::{DataType, Operation};
 
@@ -111,6 +111,7 @@ pub fn create_computed_values_contract() -> CtnContract {
             cpu_intensive: false,
             requires_elevated_privileges: false,
         },
+        required_privilege: PrivilegeRequirement::None,
     };
 
     contract
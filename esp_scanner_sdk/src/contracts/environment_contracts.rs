@@ -0,0 +1,147 @@
+//! Environment variable CTN contract
+//!
+//! Validates where an environment variable is set: in `/etc/environment` and
+//! `/etc/profile.d`, or in the environment of running processes. Every file
+//! or process that sets the variable is an item, so `TEST none ...` on an
+//! object naming `LD_PRELOAD` requires that nothing sets it.
+
+use esp_scanner_base::strategies::{
+    CollectionMode, CollectionStrategy, CtnContract, ObjectFieldSpec, PerformanceHints,
+    PrivilegeRequirement, StateFieldSpec,
+};
+use esp_scanner_base::types::common::{DataType, Operation};
+
+pub fn create_environment_variable_contract() -> CtnContract {
+    let mut contract = CtnContract::new("environment_variable".to_string());
+
+    // Object requirements
+    contract
+        .object_requirements
+        .add_required_field(ObjectFieldSpec {
+            name: "variable_name".to_string(),
+            data_type: DataType::String,
+            description: "Name of the environment variable".to_string(),
+            example_values: vec!["LD_PRELOAD".to_string(), "TMOUT".to_string()],
+            validation_notes: None,
+        });
+    contract
+        .object_requirements
+        .add_optional_field(ObjectFieldSpec {
+            name: "source".to_string(),
+            data_type: DataType::String,
+            description: "Where to look for the variable".to_string(),
+            example_values: vec!["file".to_string(), "process".to_string()],
+            validation_notes: Some(
+                "`file` (default) reads environment files; `process` reads \
+                 /proc/<pid>/environ"
+                    .to_string(),
+            ),
+        });
+    contract
+        .object_requirements
+        .add_optional_field(ObjectFieldSpec {
+            name: "paths".to_string(),
+            data_type: DataType::String,
+            description: "Comma-separated files or directories to read".to_string(),
+            example_values: vec!["/etc/environment,/etc/profile.d".to_string()],
+            validation_notes: Some(
+                "File source only; directories contribute their *.sh files. Defaults to \
+                 /etc/environment and /etc/profile.d"
+                    .to_string(),
+            ),
+        });
+    contract
+        .object_requirements
+        .add_optional_field(ObjectFieldSpec {
+            name: "process_name".to_string(),
+            data_type: DataType::String,
+            description: "Exact name of the processes to read".to_string(),
+            example_values: vec!["sshd".to_string()],
+            validation_notes: Some(
+                "Required with the process source; other users' processes are only \
+                 readable by root"
+                    .to_string(),
+            ),
+        });
+
+    // State requirements
+    let string_operations = vec![
+        Operation::Equals,
+        Operation::NotEqual,
+        Operation::Contains,
+        Operation::NotContains,
+        Operation::StartsWith,
+        Operation::EndsWith,
+        Operation::PatternMatch,
+        Operation::EqualsAny,
+        Operation::NotEqualsAny,
+    ];
+
+    let state_fields = [
+        ("variable_name", "Name of the variable", "LD_PRELOAD", None),
+        (
+            "value",
+            "Value the file or process sets",
+            "900",
+            Some("Unexpanded: `$PATH` in a file is compared as written"),
+        ),
+        (
+            "process_name",
+            "Name of the process",
+            "sshd",
+            Some("Empty for files"),
+        ),
+    ];
+
+    for (name, description, example, notes) in state_fields {
+        contract
+            .state_requirements
+            .add_optional_field(StateFieldSpec {
+                name: name.to_string(),
+                data_type: DataType::String,
+                allowed_operations: string_operations.clone(),
+                description: description.to_string(),
+                example_values: vec![example.to_string()],
+                validation_notes: notes.map(str::to_string),
+            });
+        contract
+            .field_mappings
+            .validation_mappings
+            .state_to_data
+            .insert(name.to_string(), name.to_string());
+        contract
+            .field_mappings
+            .collection_mappings
+            .optional_data_fields
+            .push(name.to_string());
+    }
+
+    // Field mappings
+    contract
+        .field_mappings
+        .collection_mappings
+        .object_to_collection
+        .insert("variable_name".to_string(), "variable_name".to_string());
+
+    contract
+        .field_mappings
+        .collection_mappings
+        .required_data_fields = vec!["variables".to_string()];
+
+    // Collection strategy
+    contract.collection_strategy = CollectionStrategy {
+        collector_type: "command".to_string(),
+        collection_mode: CollectionMode::Command,
+        required_capabilities: vec!["execute_pgrep".to_string(), "file_access".to_string()],
+        performance_hints: PerformanceHints {
+            expected_collection_time_ms: Some(100),
+            memory_usage_mb: Some(1),
+            network_intensive: false,
+            cpu_intensive: false,
+            requires_elevated_privileges: false,
+        },
+        required_privilege: PrivilegeRequirement::None,
+    };
+
+    contract
+}
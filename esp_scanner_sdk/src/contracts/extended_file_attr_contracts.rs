@@ -0,0 +1,145 @@
+//! Extended file attribute CTN contract
+//!
+//! Validates what mode bits do not show: POSIX ACL entries and file
+//! capabilities. The object's `path` may be a file, a directory, or a glob
+//! such as `/usr/bin/*`, and every file selected is an item, so one
+//! criterion can sweep a directory for extended ACLs or unapproved
+//! capabilities.
+
+use esp_scanner_base::strategies::{
+    CollectionMode, CollectionStrategy, CtnContract, ObjectFieldSpec, PerformanceHints,
+    PrivilegeRequirement, StateFieldSpec,
+};
+use esp_scanner_base::types::common::{DataType, Operation};
+
+pub fn create_extended_file_attr_contract() -> CtnContract {
+    let mut contract = CtnContract::new("extended_file_attr".to_string());
+
+    // Object requirements
+    contract
+        .object_requirements
+        .add_required_field(ObjectFieldSpec {
+            name: "path".to_string(),
+            data_type: DataType::String,
+            description: "File, directory of files, or glob".to_string(),
+            example_values: vec!["/usr/bin/*".to_string(), "/etc/shadow".to_string()],
+            validation_notes: Some(
+                "Globs apply to the last path component; hidden files are skipped".to_string(),
+            ),
+        });
+
+    // State requirements
+    let string_operations = vec![
+        Operation::Equals,
+        Operation::NotEqual,
+        Operation::Contains,
+        Operation::NotContains,
+        Operation::StartsWith,
+        Operation::EndsWith,
+        Operation::PatternMatch,
+        Operation::EqualsAny,
+        Operation::NotEqualsAny,
+    ];
+    let boolean_operations = vec![Operation::Equals, Operation::NotEqual];
+
+    let state_fields = [
+        (
+            "has_extended_acl",
+            DataType::Boolean,
+            boolean_operations.clone(),
+            "Whether the file has ACL entries beyond its mode bits",
+            "false",
+            Some(
+                "Named user or group entries, a mask, or a default ACL; false on \
+                 filesystems without extended attributes",
+            ),
+        ),
+        (
+            "acl_entries",
+            DataType::String,
+            string_operations.clone(),
+            "ACL entries in `getfacl -n` form",
+            "user:1000:r-x",
+            Some(
+                "A list compared entry by entry under the field's entity check; named \
+                 users and groups are numeric ids, default entries start with `default:`",
+            ),
+        ),
+        (
+            "has_capabilities",
+            DataType::Boolean,
+            boolean_operations,
+            "Whether the file carries capabilities",
+            "false",
+            None,
+        ),
+        (
+            "capabilities",
+            DataType::String,
+            string_operations.clone(),
+            "File capabilities, one per entry",
+            "cap_net_raw+ep",
+            Some("A list compared entry by entry under the field's entity check"),
+        ),
+        (
+            "path",
+            DataType::String,
+            string_operations,
+            "Path of the file",
+            "/usr/bin/ping",
+            None,
+        ),
+    ];
+
+    for (name, data_type, allowed_operations, description, example, notes) in state_fields {
+        contract
+            .state_requirements
+            .add_optional_field(StateFieldSpec {
+                name: name.to_string(),
+                data_type,
+                allowed_operations,
+                description: description.to_string(),
+                example_values: vec![example.to_string()],
+                validation_notes: notes.map(str::to_string),
+            });
+        contract
+            .field_mappings
+            .validation_mappings
+            .state_to_data
+            .insert(name.to_string(), name.to_string());
+        contract
+            .field_mappings
+            .collection_mappings
+            .optional_data_fields
+            .push(name.to_string());
+    }
+
+    // Field mappings
+    contract
+        .field_mappings
+        .collection_mappings
+        .object_to_collection
+        .insert("path".to_string(), "file_path".to_string());
+
+    contract
+        .field_mappings
+        .collection_mappings
+        .required_data_fields = vec!["files".to_string()];
+
+    // Collection strategy
+    contract.collection_strategy = CollectionStrategy {
+        collector_type: "filesystem".to_string(),
+        collection_mode: CollectionMode::Metadata,
+        required_capabilities: vec!["file_access".to_string()],
+        performance_hints: PerformanceHints {
+            expected_collection_time_ms: Some(50),
+            memory_usage_mb: Some(1),
+            network_intensive: false,
+            cpu_intensive: false,
+            requires_elevated_privileges: false,
+        },
+        required_privilege: PrivilegeRequirement::None,
+    };
+
+    contract
+}
@@ -4,7 +4,7 @@
 
 use esp_scanner_base::strategies::{
     BehaviorParameter, BehaviorType, CollectionMode, CollectionStrategy, CtnContract,
-    ObjectFieldSpec, PerformanceHints, StateFieldSpec, SupportedBehavior,
+    ObjectFieldSpec, PerformanceHints, PrivilegeRequirement, StateFieldSpec, SupportedBehavior,
 };
 use esp_scanner_base::types::common::{DataType, Operation};
 
@@ -40,11 +40,20 @@ pub fn create_file_metadata_contract() -> CtnContract {
         .state_requirements
         .add_optional_field(StateFieldSpec {
             name: "permissions".to_string(),
-            data_type: DataType::String,
-            allowed_operations: vec![Operation::Equals, Operation::NotEqual],
+            data_type: DataType::Octal,
+            allowed_operations: vec![
+                Operation::Equals,
+                Operation::NotEqual,
+                Operation::EqualsAny,
+                Operation::NotEqualsAny,
+                Operation::PermissionsAtMost,
+                Operation::PermissionsAtLeast,
+            ],
             description: "File permissions in octal format".to_string(),
             example_values: vec!["0440".to_string(), "0644".to_string()],
-            validation_notes: Some("4-digit octal format (e.g., 0440)".to_string()),
+            validation_notes: Some(
+                "octal mode such as 0640; string fields compare the 4-digit text".to_string(),
+            ),
         });
 
     contract
@@ -52,7 +61,12 @@ pub fn create_file_metadata_contract() -> CtnContract {
         .add_optional_field(StateFieldSpec {
             name: "owner".to_string(),
             data_type: DataType::String,
-            allowed_operations: vec![Operation::Equals, Operation::NotEqual],
+            allowed_operations: vec![
+                Operation::Equals,
+                Operation::NotEqual,
+                Operation::EqualsAny,
+                Operation::NotEqualsAny,
+            ],
             description: "File owner (username or UID)".to_string(),
             example_values: vec!["root".to_string(), "0".to_string()],
             validation_notes: Some("Returns UID as string on Unix".to_string()),
@@ -63,7 +77,12 @@ pub fn create_file_metadata_contract() -> CtnContract {
         .add_optional_field(StateFieldSpec {
             name: "group".to_string(),
             data_type: DataType::String,
-            allowed_operations: vec![Operation::Equals, Operation::NotEqual],
+            allowed_operations: vec![
+                Operation::Equals,
+                Operation::NotEqual,
+                Operation::EqualsAny,
+                Operation::NotEqualsAny,
+            ],
             description: "File group (group name or GID)".to_string(),
             example_values: vec!["root".to_string(), "0".to_string()],
             validation_notes: Some("Returns GID as string on Unix".to_string()),
@@ -109,6 +128,24 @@ pub fn create_file_metadata_contract() -> CtnContract {
             validation_notes: Some("Integer bytes".to_string()),
         });
 
+    contract
+        .state_requirements
+        .add_optional_field(StateFieldSpec {
+            name: "modified".to_string(),
+            data_type: DataType::DateTime,
+            allowed_operations: vec![
+                Operation::Equals,
+                Operation::NotEqual,
+                Operation::GreaterThan,
+                Operation::LessThan,
+                Operation::GreaterThanOrEqual,
+                Operation::LessThanOrEqual,
+            ],
+            description: "Last modification time (mtime)".to_string(),
+            example_values: vec!["now-30d".to_string(), "2025-01-01T00:00:00Z".to_string()],
+            validation_notes: Some("datetime; not collected for archive members".to_string()),
+        });
+
     // Field mappings
     contract
         .field_mappings
@@ -127,6 +164,10 @@ pub fn create_file_metadata_contract() -> CtnContract {
         "readable".to_string(),
         "file_size".to_string(),
     ];
+    contract
+        .field_mappings
+        .collection_mappings
+        .optional_data_fields = vec!["file_mtime".to_string()];
 
     contract
         .field_mappings
@@ -158,6 +199,11 @@ pub fn create_file_metadata_contract() -> CtnContract {
         .validation_mappings
         .state_to_data
         .insert("size".to_string(), "file_size".to_string());
+    contract
+        .field_mappings
+        .validation_mappings
+        .state_to_data
+        .insert("modified".to_string(), "file_mtime".to_string());
 
     // Collection strategy
     contract.collection_strategy = CollectionStrategy {
@@ -171,11 +217,33 @@ pub fn create_file_metadata_contract() -> CtnContract {
             cpu_intensive: false,
             requires_elevated_privileges: false,
         },
+        required_privilege: PrivilegeRequirement::None,
     };
 
+    contract.add_supported_behavior(archive_member_behavior());
+
     contract
 }
 
+/// Behavior reading the object from a member of the archive at `path`
+/// instead of from `path` itself; shared by every filesystem contract
+pub fn archive_member_behavior() -> SupportedBehavior {
+    SupportedBehavior {
+        name: "archive_member".to_string(),
+        behavior_type: BehaviorType::Parameter,
+        parameters: vec![BehaviorParameter {
+            name: "archive_member".to_string(),
+            data_type: DataType::String,
+            required: true,
+            default_value: None,
+            description: "Path of the member inside the archive".to_string(),
+        }],
+        description: "Read a file inside a tar or cpio archive (plain, gzip, xz or zstd)"
+            .to_string(),
+        example: "BEHAVIOR archive_member `etc/ssh/sshd_config`".to_string(),
+    }
+}
+
 /// Create contract for file_content CTN type
 ///
 /// Full file content reading for string validation
@@ -217,6 +285,11 @@ pub fn create_file_content_contract() -> CtnContract {
                 Operation::StartsWith,
                 Operation::EndsWith,
                 Operation::PatternMatch,
+                Operation::EqualsAny,
+                Operation::NotEqualsAny,
+                Operation::LengthEquals,
+                Operation::LengthGreaterThan,
+                Operation::OccurrenceCount,
             ],
             description: "File content as UTF-8 string".to_string(),
             example_values: vec!["logfile=".to_string(), "NOPASSWD".to_string()],
@@ -253,6 +326,7 @@ pub fn create_file_content_contract() -> CtnContract {
             cpu_intensive: false,
             requires_elevated_privileges: false,
         },
+        required_privilege: PrivilegeRequirement::None,
     };
 
     contract.add_supported_behavior(SupportedBehavior {
@@ -293,5 +367,7 @@ pub fn create_file_content_contract() -> CtnContract {
         example: "BEHAVIOR follow_symlinks".to_string(),
     });
 
+    contract.add_supported_behavior(archive_member_behavior());
+
     contract
 }
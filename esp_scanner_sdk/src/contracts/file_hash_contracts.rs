@@ -0,0 +1,154 @@
+//! File hash CTN contract
+//!
+//! Validates file integrity by digest. The object's `path` may be a file, a
+//! directory, or a glob such as `/etc/ssl/*.cnf`, and every file selected is
+//! an item, so one criterion can pin a whole directory of approved files.
+//! Files are streamed, so large and binary files are handled alike.
+
+use esp_scanner_base::strategies::{
+    CollectionMode, CollectionStrategy, CtnContract, ObjectFieldSpec, PerformanceHints,
+    PrivilegeRequirement, StateFieldSpec,
+};
+use esp_scanner_base::types::common::{DataType, Operation};
+
+pub fn create_file_hash_contract() -> CtnContract {
+    let mut contract = CtnContract::new("file_hash".to_string());
+
+    // Object requirements
+    contract
+        .object_requirements
+        .add_required_field(ObjectFieldSpec {
+            name: "path".to_string(),
+            data_type: DataType::String,
+            description: "File, directory of files, or glob".to_string(),
+            example_values: vec![
+                "/etc/ssl/openssl.cnf".to_string(),
+                "/usr/sbin/*".to_string(),
+            ],
+            validation_notes: Some(
+                "Globs apply to the last path component; hidden files are skipped".to_string(),
+            ),
+        });
+
+    // State requirements
+    let hash_operations = vec![
+        Operation::Equals,
+        Operation::NotEqual,
+        Operation::CaseInsensitiveEquals,
+        Operation::CaseInsensitiveNotEqual,
+        Operation::EqualsAny,
+        Operation::NotEqualsAny,
+    ];
+    let string_operations = vec![
+        Operation::Equals,
+        Operation::NotEqual,
+        Operation::Contains,
+        Operation::NotContains,
+        Operation::StartsWith,
+        Operation::EndsWith,
+        Operation::PatternMatch,
+    ];
+    let int_operations = vec![
+        Operation::Equals,
+        Operation::NotEqual,
+        Operation::GreaterThan,
+        Operation::LessThan,
+        Operation::GreaterThanOrEqual,
+        Operation::LessThanOrEqual,
+    ];
+
+    let state_fields = [
+        (
+            "sha256",
+            DataType::String,
+            hash_operations.clone(),
+            "SHA-256 digest of the content",
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+            Some("Lowercase hex; use `ieq` for digests copied in uppercase"),
+        ),
+        (
+            "sha512",
+            DataType::String,
+            hash_operations.clone(),
+            "SHA-512 digest of the content",
+            "cf83e1357eefb8bdf1542850d66d8007d620e4050b5715dc83f4a921d36ce9ce...",
+            Some("Lowercase hex"),
+        ),
+        (
+            "blake3",
+            DataType::String,
+            hash_operations,
+            "BLAKE3 digest of the content",
+            "af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262",
+            Some("Only collected when the scanner is built with the `blake3` feature"),
+        ),
+        (
+            "size",
+            DataType::Int,
+            int_operations,
+            "Content size in bytes",
+            "10909",
+            None,
+        ),
+        (
+            "path",
+            DataType::String,
+            string_operations,
+            "Path of the file",
+            "/etc/ssl/openssl.cnf",
+            None,
+        ),
+    ];
+
+    for (name, data_type, allowed_operations, description, example, notes) in state_fields {
+        contract
+            .state_requirements
+            .add_optional_field(StateFieldSpec {
+                name: name.to_string(),
+                data_type,
+                allowed_operations,
+                description: description.to_string(),
+                example_values: vec![example.to_string()],
+                validation_notes: notes.map(str::to_string),
+            });
+        contract
+            .field_mappings
+            .validation_mappings
+            .state_to_data
+            .insert(name.to_string(), name.to_string());
+        contract
+            .field_mappings
+            .collection_mappings
+            .optional_data_fields
+            .push(name.to_string());
+    }
+
+    // Field mappings
+    contract
+        .field_mappings
+        .collection_mappings
+        .object_to_collection
+        .insert("path".to_string(), "file_path".to_string());
+
+    contract
+        .field_mappings
+        .collection_mappings
+        .required_data_fields = vec!["files".to_string()];
+
+    // Collection strategy
+    contract.collection_strategy = CollectionStrategy {
+        collector_type: "filesystem".to_string(),
+        collection_mode: CollectionMode::Content,
+        required_capabilities: vec!["file_access".to_string()],
+        performance_hints: PerformanceHints {
+            expected_collection_time_ms: Some(100),
+            memory_usage_mb: Some(1),
+            network_intensive: false,
+            cpu_intensive: true,
+            requires_elevated_privileges: false,
+        },
+        required_privilege: PrivilegeRequirement::None,
+    };
+
+    contract
+}
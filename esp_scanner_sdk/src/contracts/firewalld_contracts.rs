@@ -0,0 +1,188 @@
+//! firewalld zone CTN contract
+//!
+//! Validates firewalld zones from `firewall-cmd --list-all-zones`. List
+//! fields such as `services` hold one value per entry and are combined with
+//! the field's entity check, so `services string = \`telnet\` none` passes
+//! when no service is telnet.
+
+use esp_scanner_base::strategies::{
+    CollectionMode, CollectionStrategy, CtnContract, ObjectFieldSpec, PerformanceHints,
+    PrivilegeRequirement, StateFieldSpec,
+};
+use esp_scanner_base::types::common::{DataType, Operation};
+
+pub fn create_firewalld_zone_contract() -> CtnContract {
+    let mut contract = CtnContract::new("firewalld_zone".to_string());
+
+    // Object requirements
+    contract
+        .object_requirements
+        .add_required_field(ObjectFieldSpec {
+            name: "zone".to_string(),
+            data_type: DataType::String,
+            description: "Zone name, or a glob using `*` and `?`".to_string(),
+            example_values: vec!["public".to_string(), "*".to_string()],
+            validation_notes: Some(
+                "Each matching zone is an item; none exist while firewalld is not running"
+                    .to_string(),
+            ),
+        });
+
+    // State requirements
+    let string_operations = vec![
+        Operation::Equals,
+        Operation::NotEqual,
+        Operation::Contains,
+        Operation::NotContains,
+        Operation::StartsWith,
+        Operation::EndsWith,
+        Operation::PatternMatch,
+        Operation::EqualsAny,
+        Operation::NotEqualsAny,
+    ];
+    let bool_operations = vec![Operation::Equals, Operation::NotEqual];
+
+    let state_fields = [
+        ("zone", DataType::String, "Zone name", "public", None),
+        (
+            "default_zone",
+            DataType::String,
+            "Name of the default zone",
+            "drop",
+            Some("From firewall-cmd --get-default-zone"),
+        ),
+        (
+            "default",
+            DataType::Boolean,
+            "Whether this zone is the default zone",
+            "true",
+            None,
+        ),
+        (
+            "active",
+            DataType::Boolean,
+            "Whether the zone has interfaces or sources bound",
+            "true",
+            None,
+        ),
+        ("target", DataType::String, "Zone target", "DROP", None),
+        (
+            "services",
+            DataType::String,
+            "Each allowed service",
+            "ssh",
+            Some("List field; combine entries with an entity check"),
+        ),
+        (
+            "ports",
+            DataType::String,
+            "Each open port, as port/protocol",
+            "443/tcp",
+            Some("List field; combine entries with an entity check"),
+        ),
+        (
+            "protocols",
+            DataType::String,
+            "Each allowed protocol",
+            "icmp",
+            Some("List field; combine entries with an entity check"),
+        ),
+        (
+            "interfaces",
+            DataType::String,
+            "Each bound interface",
+            "eth0",
+            Some("List field; combine entries with an entity check"),
+        ),
+        (
+            "sources",
+            DataType::String,
+            "Each bound source address",
+            "10.0.0.0/8",
+            Some("List field; combine entries with an entity check"),
+        ),
+        (
+            "forward_ports",
+            DataType::String,
+            "Each port forward",
+            "port=80:proto=tcp:toport=8080:toaddr=",
+            Some("List field; combine entries with an entity check"),
+        ),
+        (
+            "rich_rules",
+            DataType::String,
+            "Each rich rule",
+            "rule family=\"ipv4\" service name=\"ssh\" accept",
+            Some("List field; combine entries with an entity check"),
+        ),
+        (
+            "masquerade",
+            DataType::Boolean,
+            "Whether masquerading is enabled",
+            "false",
+            None,
+        ),
+    ];
+
+    for (name, data_type, description, example, notes) in state_fields {
+        let allowed_operations = match data_type {
+            DataType::Boolean => bool_operations.clone(),
+            _ => string_operations.clone(),
+        };
+        contract
+            .state_requirements
+            .add_optional_field(StateFieldSpec {
+                name: name.to_string(),
+                data_type,
+                allowed_operations,
+                description: description.to_string(),
+                example_values: vec![example.to_string()],
+                validation_notes: notes.map(str::to_string),
+            });
+        contract
+            .field_mappings
+            .validation_mappings
+            .state_to_data
+            .insert(name.to_string(), name.to_string());
+        if name != "zone" {
+            contract
+                .field_mappings
+                .collection_mappings
+                .optional_data_fields
+                .push(name.to_string());
+        }
+    }
+
+    // Field mappings
+    contract
+        .field_mappings
+        .collection_mappings
+        .object_to_collection
+        .insert("zone".to_string(), "zone".to_string());
+
+    contract
+        .field_mappings
+        .collection_mappings
+        .required_data_fields = vec![
+        "zone".to_string(),
+        "running".to_string(),
+        "zones".to_string(),
+    ];
+
+    // Collection strategy
+    contract.collection_strategy = CollectionStrategy {
+        collector_type: "command".to_string(),
+        collection_mode: CollectionMode::Command,
+        required_capabilities: vec!["execute_firewall_cmd".to_string()],
+        performance_hints: PerformanceHints {
+            expected_collection_time_ms: Some(500),
+            memory_usage_mb: Some(1),
+            network_intensive: false,
+            cpu_intensive: false,
+            requires_elevated_privileges: true,
+        },
+        required_privilege: PrivilegeRequirement::Root,
+    };
+
+    contract
+}
@@ -1,15 +1,110 @@
-//! JSON record CTN contract
+//! JSON, YAML, TOML, INI and XML record CTN contracts
 //!
-//! Validates structured JSON data with field path queries.
+//! Validates structured documents with field path queries. Every format is
+//! parsed into the same record representation, so the contracts differ only
+//! in their names, collected field and examples, and the INI and XML reading
+//! options.
 
 use esp_scanner_base::strategies::{
-    CollectionMode, CollectionStrategy, CtnContract, ObjectFieldSpec, PerformanceHints,
-    StateFieldSpec,
+    BehaviorParameter, BehaviorType, CollectionMode, CollectionStrategy, CtnContract,
+    ObjectFieldSpec, PerformanceHints, PrivilegeRequirement, StateFieldSpec, SupportedBehavior,
 };
 use esp_scanner_base::types::common::{DataType, Operation};
 
 pub fn create_json_record_contract() -> CtnContract {
-    let mut contract = CtnContract::new("json_record".to_string());
+    create_record_contract(
+        "json_record",
+        "JSON",
+        "json_data",
+        "scanfiles/test_data.json",
+    )
+}
+
+pub fn create_yaml_record_contract() -> CtnContract {
+    create_record_contract(
+        "yaml_record",
+        "YAML",
+        "yaml_data",
+        "/etc/netplan/01-netcfg.yaml",
+    )
+}
+
+pub fn create_toml_record_contract() -> CtnContract {
+    create_record_contract(
+        "toml_record",
+        "TOML",
+        "toml_data",
+        "/etc/containers/containers.conf",
+    )
+}
+
+pub fn create_ini_record_contract() -> CtnContract {
+    let mut contract = create_record_contract(
+        "ini_record",
+        "INI",
+        "ini_data",
+        "/etc/security/pwquality.conf",
+    );
+
+    contract
+        .object_requirements
+        .add_optional_field(ObjectFieldSpec {
+            name: "comment_chars".to_string(),
+            data_type: DataType::String,
+            description: "Characters that start a comment line".to_string(),
+            example_values: vec!["#;".to_string(), "#".to_string()],
+            validation_notes: Some("Defaults to `#;`".to_string()),
+        });
+    contract
+        .object_requirements
+        .add_optional_field(ObjectFieldSpec {
+            name: "duplicate_keys".to_string(),
+            data_type: DataType::String,
+            description: "What a key repeated within a section holds".to_string(),
+            example_values: vec!["last".to_string(), "list".to_string()],
+            validation_notes: Some(
+                "`last` (default) keeps the last value; `list` makes every key a list for \
+                 `section.key.*` entity checks"
+                    .to_string(),
+            ),
+        });
+
+    contract
+}
+
+pub fn create_xml_record_contract() -> CtnContract {
+    let mut contract = create_record_contract(
+        "xml_record",
+        "XML",
+        "xml_data",
+        "/opt/tomcat/conf/server.xml",
+    );
+
+    contract
+        .object_requirements
+        .add_optional_field(ObjectFieldSpec {
+            name: "namespaces".to_string(),
+            data_type: DataType::String,
+            description: "Comma-separated namespace URIs elements and attributes may be in"
+                .to_string(),
+            example_values: vec!["urn:jboss:domain:20.0".to_string()],
+            validation_notes: Some(
+                "Unset matches on local names in any namespace; set, anything in another \
+                 namespace is left out"
+                    .to_string(),
+            ),
+        });
+
+    contract
+}
+
+fn create_record_contract(
+    ctn_type: &str,
+    format: &str,
+    data_field: &str,
+    example_path: &str,
+) -> CtnContract {
+    let mut contract = CtnContract::new(ctn_type.to_string());
 
     // Object requirements
     contract
@@ -17,9 +112,9 @@ pub fn create_json_record_contract() -> CtnContract {
         .add_required_field(ObjectFieldSpec {
             name: "path".to_string(),
             data_type: DataType::String,
-            description: "Path to JSON file".to_string(),
-            example_values: vec!["scanfiles/test_data.json".to_string()],
-            validation_notes: Some("Must be valid JSON file".to_string()),
+            description: format!("Path to {} file", format),
+            example_values: vec![example_path.to_string()],
+            validation_notes: Some(format!("Must be valid {} file", format)),
         });
 
     // State requirements - allow record checks
@@ -31,7 +126,7 @@ pub fn create_json_record_contract() -> CtnContract {
             allowed_operations: vec![Operation::Equals],
             description: "Record validation with field paths".to_string(),
             example_values: vec!["See record_checks".to_string()],
-            validation_notes: Some("Use record checks for JSON validation".to_string()),
+            validation_notes: Some(format!("Use record checks for {} validation", format)),
         });
 
     // Field mappings
@@ -44,19 +139,22 @@ pub fn create_json_record_contract() -> CtnContract {
     contract
         .field_mappings
         .collection_mappings
-        .required_data_fields = vec!["json_data".to_string()];
+        .required_data_fields = vec![data_field.to_string()];
 
     contract
         .field_mappings
         .validation_mappings
         .state_to_data
-        .insert("record".to_string(), "json_data".to_string());
+        .insert("record".to_string(), data_field.to_string());
 
     // Collection strategy
     contract.collection_strategy = CollectionStrategy {
         collector_type: "filesystem".to_string(),
         collection_mode: CollectionMode::Content,
-        required_capabilities: vec!["file_access".to_string(), "json_parsing".to_string()],
+        required_capabilities: vec![
+            "file_access".to_string(),
+            format!("{}_parsing", format.to_lowercase()),
+        ],
         performance_hints: PerformanceHints {
             expected_collection_time_ms: Some(100),
             memory_usage_mb: Some(10),
@@ -64,7 +162,25 @@ pub fn create_json_record_contract() -> CtnContract {
             cpu_intensive: false,
             requires_elevated_privileges: false,
         },
+        required_privilege: PrivilegeRequirement::None,
     };
 
+    contract.add_supported_behavior(SupportedBehavior {
+        name: "float_epsilon".to_string(),
+        behavior_type: BehaviorType::Parameter,
+        parameters: vec![BehaviorParameter {
+            name: "float_epsilon".to_string(),
+            data_type: DataType::Float,
+            required: true,
+            default_value: None,
+            description: "Largest difference at which two floats compare equal".to_string(),
+        }],
+        description: "Compare float record fields within a tolerance instead of exactly"
+            .to_string(),
+        example: "BEHAVIOR float_epsilon 0.000001".to_string(),
+    });
+
+    contract.add_supported_behavior(super::file_contracts::archive_member_behavior());
+
     contract
 }
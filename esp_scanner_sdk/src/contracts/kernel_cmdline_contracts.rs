@@ -0,0 +1,144 @@
+//! Kernel command line CTN contract
+//!
+//! Validates the arguments the running kernel was booted with and those GRUB
+//! is configured to boot with. `runtime_args` and `configured_args` hold one
+//! value per argument and are combined with the field's entity check; with
+//! an `argument` object field, `runtime_has` and `configured_has` say
+//! whether each command line sets it.
+
+use esp_scanner_base::strategies::{
+    CollectionMode, CollectionStrategy, CtnContract, ObjectFieldSpec, PerformanceHints,
+    PrivilegeRequirement, StateFieldSpec,
+};
+use esp_scanner_base::types::common::{DataType, Operation};
+
+pub fn create_kernel_cmdline_contract() -> CtnContract {
+    let mut contract = CtnContract::new("kernel_cmdline".to_string());
+
+    // Object requirements
+    contract
+        .object_requirements
+        .add_optional_field(ObjectFieldSpec {
+            name: "argument".to_string(),
+            data_type: DataType::String,
+            description: "Argument the `*_has` fields look for".to_string(),
+            example_values: vec!["audit=1".to_string(), "nousb".to_string()],
+            validation_notes: Some(
+                "`key=value` must be the last value given for the key; a bare key matches \
+                 the flag or any value"
+                    .to_string(),
+            ),
+        });
+
+    // State requirements
+    let string_operations = vec![
+        Operation::Equals,
+        Operation::NotEqual,
+        Operation::Contains,
+        Operation::NotContains,
+        Operation::StartsWith,
+        Operation::EndsWith,
+        Operation::PatternMatch,
+        Operation::EqualsAny,
+        Operation::NotEqualsAny,
+    ];
+    let bool_operations = vec![Operation::Equals, Operation::NotEqual];
+
+    let state_fields = [
+        (
+            "argument",
+            DataType::String,
+            "The object's argument",
+            "audit=1",
+            None,
+        ),
+        (
+            "runtime_has",
+            DataType::Boolean,
+            "Whether the running kernel was booted with the argument",
+            "true",
+            Some("From /proc/cmdline; needs the `argument` object field"),
+        ),
+        (
+            "configured_has",
+            DataType::Boolean,
+            "Whether GRUB is configured to boot with the argument",
+            "true",
+            Some("Needs the `argument` object field"),
+        ),
+        (
+            "runtime_args",
+            DataType::String,
+            "Each argument of /proc/cmdline",
+            "audit=1",
+            Some("List field; combine entries with an entity check"),
+        ),
+        (
+            "configured_args",
+            DataType::String,
+            "Each argument of GRUB_CMDLINE_LINUX, GRUB_CMDLINE_LINUX_DEFAULT and grubenv",
+            "page_poison=1",
+            Some("List field, with GRUB's quoting removed"),
+        ),
+    ];
+
+    for (name, data_type, description, example, notes) in state_fields {
+        let allowed_operations = match data_type {
+            DataType::Boolean => bool_operations.clone(),
+            _ => string_operations.clone(),
+        };
+        contract
+            .state_requirements
+            .add_optional_field(StateFieldSpec {
+                name: name.to_string(),
+                data_type,
+                allowed_operations,
+                description: description.to_string(),
+                example_values: vec![example.to_string()],
+                validation_notes: notes.map(str::to_string),
+            });
+        contract
+            .field_mappings
+            .validation_mappings
+            .state_to_data
+            .insert(name.to_string(), name.to_string());
+    }
+
+    // Field mappings
+    contract
+        .field_mappings
+        .collection_mappings
+        .object_to_collection
+        .insert("argument".to_string(), "argument".to_string());
+
+    contract
+        .field_mappings
+        .collection_mappings
+        .required_data_fields = vec!["runtime_args".to_string(), "configured_args".to_string()];
+
+    contract
+        .field_mappings
+        .collection_mappings
+        .optional_data_fields = vec![
+        "argument".to_string(),
+        "runtime_has".to_string(),
+        "configured_has".to_string(),
+    ];
+
+    // Collection strategy
+    contract.collection_strategy = CollectionStrategy {
+        collector_type: "filesystem".to_string(),
+        collection_mode: CollectionMode::Content,
+        required_capabilities: vec!["file_access".to_string()],
+        performance_hints: PerformanceHints {
+            expected_collection_time_ms: Some(10),
+            memory_usage_mb: Some(1),
+            network_intensive: false,
+            cpu_intensive: false,
+            requires_elevated_privileges: false,
+        },
+        required_privilege: PrivilegeRequirement::None,
+    };
+
+    contract
+}
@@ -0,0 +1,150 @@
+//! Kernel module CTN contract
+//!
+//! Validates whether a kernel module is loaded and how modprobe.d configures
+//! it, so one criterion can require a module to be unloaded and blacklisted.
+
+use esp_scanner_base::strategies::{
+    CollectionMode, CollectionStrategy, CtnContract, ObjectFieldSpec, PerformanceHints,
+    PrivilegeRequirement, StateFieldSpec,
+};
+use esp_scanner_base::types::common::{DataType, Operation};
+
+pub fn create_kernel_module_contract() -> CtnContract {
+    let mut contract = CtnContract::new("kernel_module".to_string());
+
+    // Object requirements
+    contract
+        .object_requirements
+        .add_required_field(ObjectFieldSpec {
+            name: "module_name".to_string(),
+            data_type: DataType::String,
+            description: "Kernel module name".to_string(),
+            example_values: vec!["usb-storage".to_string(), "cramfs".to_string()],
+            validation_notes: Some("`-` and `_` are interchangeable".to_string()),
+        });
+
+    contract
+        .object_requirements
+        .add_optional_field(ObjectFieldSpec {
+            name: "config_dir".to_string(),
+            data_type: DataType::String,
+            description: "Read modprobe configuration from this directory only".to_string(),
+            example_values: vec!["/etc/modprobe.d".to_string()],
+            validation_notes: Some(
+                "Defaults to the standard modprobe.d directories, /etc first".to_string(),
+            ),
+        });
+
+    // State requirements
+    let bool_operations = vec![Operation::Equals, Operation::NotEqual];
+    let string_operations = vec![
+        Operation::Equals,
+        Operation::NotEqual,
+        Operation::Contains,
+        Operation::NotContains,
+        Operation::StartsWith,
+        Operation::EndsWith,
+        Operation::PatternMatch,
+    ];
+
+    let state_fields = [
+        (
+            "module_name",
+            DataType::String,
+            "Module name, with underscores",
+            "usb_storage",
+            None,
+        ),
+        (
+            "loaded",
+            DataType::Boolean,
+            "Whether the module is loaded",
+            "false",
+            Some("From lsmod"),
+        ),
+        (
+            "blacklisted",
+            DataType::Boolean,
+            "Whether a blacklist directive names the module",
+            "true",
+            None,
+        ),
+        (
+            "install_directive",
+            DataType::String,
+            "Command of the first install directive for the module",
+            "/bin/false",
+            Some("Empty when no install directive names the module"),
+        ),
+        (
+            "install_disabled",
+            DataType::Boolean,
+            "Whether the install command is /bin/false or /bin/true",
+            "true",
+            None,
+        ),
+        (
+            "config_files",
+            DataType::String,
+            "Comma-separated modprobe.d files naming the module",
+            "/etc/modprobe.d/cis.conf",
+            None,
+        ),
+    ];
+
+    let data_fields: Vec<String> = state_fields
+        .iter()
+        .map(|(name, ..)| name.to_string())
+        .collect();
+
+    for (name, data_type, description, example, notes) in state_fields {
+        let allowed_operations = match data_type {
+            DataType::Boolean => bool_operations.clone(),
+            _ => string_operations.clone(),
+        };
+        contract
+            .state_requirements
+            .add_optional_field(StateFieldSpec {
+                name: name.to_string(),
+                data_type,
+                allowed_operations,
+                description: description.to_string(),
+                example_values: vec![example.to_string()],
+                validation_notes: notes.map(str::to_string),
+            });
+        contract
+            .field_mappings
+            .validation_mappings
+            .state_to_data
+            .insert(name.to_string(), name.to_string());
+    }
+
+    // Field mappings
+    contract
+        .field_mappings
+        .collection_mappings
+        .object_to_collection
+        .insert("module_name".to_string(), "module_name".to_string());
+
+    contract
+        .field_mappings
+        .collection_mappings
+        .required_data_fields = data_fields;
+
+    // Collection strategy
+    contract.collection_strategy = CollectionStrategy {
+        collector_type: "command".to_string(),
+        collection_mode: CollectionMode::Command,
+        required_capabilities: vec!["execute_lsmod".to_string(), "file_access".to_string()],
+        performance_hints: PerformanceHints {
+            expected_collection_time_ms: Some(50),
+            memory_usage_mb: Some(1),
+            network_intensive: false,
+            cpu_intensive: false,
+            requires_elevated_privileges: false,
+        },
+        required_privilege: PrivilegeRequirement::None,
+    };
+
+    contract
+}
@@ -6,18 +6,60 @@
 //! - Field mappings: How to map between ESP field names and collected data
 //! - Collection strategy: Performance hints and capabilities
 
+pub mod audit_contracts;
+pub mod certificate_contracts;
 pub mod computed_values;
+pub mod dpkg_contracts;
+pub mod environment_contracts;
+pub mod extended_file_attr_contracts;
 pub mod file_contracts;
+pub mod file_hash_contracts;
+pub mod firewalld_contracts;
 pub mod json_contracts;
+pub mod kernel_cmdline_contracts;
+pub mod kernel_module_contracts;
+pub mod mount_contracts;
+pub mod network_contracts;
+pub mod password_contracts;
+pub mod permission_scan_contracts;
 pub mod rpm_contracts;
+pub mod scheduled_job_contracts;
 pub mod selinux_contracts;
+pub mod ssh_contracts;
+pub mod sudoers_contracts;
 pub mod sysctl_contracts;
 pub mod systemd_contracts;
+pub mod user_contracts;
 
+pub use audit_contracts::create_audit_rules_contract;
+pub use certificate_contracts::create_certificate_contract;
 pub use computed_values::create_computed_values_contract;
+pub use dpkg_contracts::create_dpkg_package_contract;
+pub use environment_contracts::create_environment_variable_contract;
+pub use extended_file_attr_contracts::create_extended_file_attr_contract;
 pub use file_contracts::{create_file_content_contract, create_file_metadata_contract};
-pub use json_contracts::create_json_record_contract;
+pub use file_hash_contracts::create_file_hash_contract;
+pub use firewalld_contracts::create_firewalld_zone_contract;
+pub use json_contracts::{
+    create_ini_record_contract, create_json_record_contract, create_toml_record_contract,
+    create_xml_record_contract, create_yaml_record_contract,
+};
+pub use kernel_cmdline_contracts::create_kernel_cmdline_contract;
+pub use kernel_module_contracts::create_kernel_module_contract;
+pub use mount_contracts::create_mount_point_contract;
+pub use network_contracts::create_listening_port_contract;
+pub use password_contracts::create_password_policy_contract;
+pub use permission_scan_contracts::create_file_permission_scan_contract;
 pub use rpm_contracts::create_rpm_package_contract;
-pub use selinux_contracts::create_selinux_status_contract;
+pub use scheduled_job_contracts::create_scheduled_job_contract;
+pub use selinux_contracts::{
+    create_selinux_boolean_contract, create_selinux_file_context_contract,
+    create_selinux_status_contract,
+};
+pub use ssh_contracts::create_sshd_config_contract;
+pub use sudoers_contracts::create_sudoers_contract;
 pub use sysctl_contracts::create_sysctl_parameter_contract;
-pub use systemd_contracts::create_systemd_service_contract;
+pub use systemd_contracts::{
+    create_systemd_service_contract, create_systemd_unit_property_contract,
+};
+pub use user_contracts::create_user_account_contract;
@@ -0,0 +1,158 @@
+//! Mount point CTN contract
+//!
+//! Validates how a mount point is mounted now and how `/etc/fstab` configures
+//! it. `options` and `fstab_options` hold one value per option and are
+//! combined with the field's entity check, so `options string = \`nodev\`
+//! at_least_one` requires the option and `options string = \`exec\` none`
+//! forbids one.
+
+use esp_scanner_base::strategies::{
+    CollectionMode, CollectionStrategy, CtnContract, ObjectFieldSpec, PerformanceHints,
+    PrivilegeRequirement, StateFieldSpec,
+};
+use esp_scanner_base::types::common::{DataType, Operation};
+
+pub fn create_mount_point_contract() -> CtnContract {
+    let mut contract = CtnContract::new("mount_point".to_string());
+
+    // Object requirements
+    contract
+        .object_requirements
+        .add_required_field(ObjectFieldSpec {
+            name: "mount_point".to_string(),
+            data_type: DataType::String,
+            description: "Directory a filesystem is mounted on".to_string(),
+            example_values: vec!["/tmp".to_string(), "/dev/shm".to_string()],
+            validation_notes: Some("A trailing `/` is ignored".to_string()),
+        });
+
+    // State requirements
+    let string_operations = vec![
+        Operation::Equals,
+        Operation::NotEqual,
+        Operation::Contains,
+        Operation::NotContains,
+        Operation::StartsWith,
+        Operation::EndsWith,
+        Operation::PatternMatch,
+        Operation::EqualsAny,
+        Operation::NotEqualsAny,
+    ];
+    let bool_operations = vec![Operation::Equals, Operation::NotEqual];
+
+    let state_fields = [
+        ("mount_point", DataType::String, "Mount point", "/tmp", None),
+        (
+            "mounted",
+            DataType::Boolean,
+            "Whether a filesystem is mounted there now",
+            "true",
+            Some("From /proc/self/mountinfo, or /etc/mtab without it"),
+        ),
+        (
+            "device",
+            DataType::String,
+            "Mounted device or source",
+            "tmpfs",
+            None,
+        ),
+        (
+            "fstype",
+            DataType::String,
+            "Mounted filesystem type",
+            "tmpfs",
+            None,
+        ),
+        (
+            "options",
+            DataType::String,
+            "Each active mount option",
+            "nodev",
+            Some("List field; combine entries with an entity check"),
+        ),
+        (
+            "configured",
+            DataType::Boolean,
+            "Whether /etc/fstab has an entry for the mount point",
+            "true",
+            None,
+        ),
+        (
+            "fstab_device",
+            DataType::String,
+            "Device of the fstab entry",
+            "tmpfs",
+            None,
+        ),
+        (
+            "fstab_fstype",
+            DataType::String,
+            "Filesystem type of the fstab entry",
+            "tmpfs",
+            None,
+        ),
+        (
+            "fstab_options",
+            DataType::String,
+            "Each option of the fstab entry",
+            "noexec",
+            Some("List field, as written in fstab; `defaults` is not expanded"),
+        ),
+    ];
+
+    let data_fields: Vec<String> = state_fields
+        .iter()
+        .map(|(name, ..)| name.to_string())
+        .collect();
+
+    for (name, data_type, description, example, notes) in state_fields {
+        let allowed_operations = match data_type {
+            DataType::Boolean => bool_operations.clone(),
+            _ => string_operations.clone(),
+        };
+        contract
+            .state_requirements
+            .add_optional_field(StateFieldSpec {
+                name: name.to_string(),
+                data_type,
+                allowed_operations,
+                description: description.to_string(),
+                example_values: vec![example.to_string()],
+                validation_notes: notes.map(str::to_string),
+            });
+        contract
+            .field_mappings
+            .validation_mappings
+            .state_to_data
+            .insert(name.to_string(), name.to_string());
+    }
+
+    // Field mappings
+    contract
+        .field_mappings
+        .collection_mappings
+        .object_to_collection
+        .insert("mount_point".to_string(), "mount_point".to_string());
+
+    contract
+        .field_mappings
+        .collection_mappings
+        .required_data_fields = data_fields;
+
+    // Collection strategy
+    contract.collection_strategy = CollectionStrategy {
+        collector_type: "filesystem".to_string(),
+        collection_mode: CollectionMode::Content,
+        required_capabilities: vec!["file_access".to_string()],
+        performance_hints: PerformanceHints {
+            expected_collection_time_ms: Some(10),
+            memory_usage_mb: Some(1),
+            network_intensive: false,
+            cpu_intensive: false,
+            requires_elevated_privileges: false,
+        },
+        required_privilege: PrivilegeRequirement::None,
+    };
+
+    contract
+}
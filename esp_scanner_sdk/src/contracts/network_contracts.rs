@@ -0,0 +1,158 @@
+//! Listening port CTN contract
+//!
+//! Validates listening TCP and UDP sockets from `ss -tulpn`. Each object
+//! selects listeners with optional filters, and every listener it selects is
+//! an item, so `TEST none ...` on an object with `port 23` requires that
+//! nothing listens on port 23.
+
+use esp_scanner_base::strategies::{
+    CollectionMode, CollectionStrategy, CtnContract, ObjectFieldSpec, PerformanceHints,
+    PrivilegeRequirement, StateFieldSpec,
+};
+use esp_scanner_base::types::common::{DataType, Operation};
+
+pub fn create_listening_port_contract() -> CtnContract {
+    let mut contract = CtnContract::new("listening_port".to_string());
+
+    // Object requirements
+    contract
+        .object_requirements
+        .add_optional_field(ObjectFieldSpec {
+            name: "port".to_string(),
+            data_type: DataType::Int,
+            description: "Select listeners on this port".to_string(),
+            example_values: vec!["23".to_string(), "22".to_string()],
+            validation_notes: None,
+        });
+    contract
+        .object_requirements
+        .add_optional_field(ObjectFieldSpec {
+            name: "protocol".to_string(),
+            data_type: DataType::String,
+            description: "Select listeners of this protocol".to_string(),
+            example_values: vec!["tcp".to_string(), "udp".to_string()],
+            validation_notes: None,
+        });
+    contract
+        .object_requirements
+        .add_optional_field(ObjectFieldSpec {
+            name: "local_address".to_string(),
+            data_type: DataType::String,
+            description: "Select listeners on addresses matching a glob".to_string(),
+            example_values: vec!["0.0.0.0".to_string(), "127.*".to_string()],
+            validation_notes: Some("IPv6 addresses are written without brackets".to_string()),
+        });
+    contract
+        .object_requirements
+        .add_optional_field(ObjectFieldSpec {
+            name: "process_name".to_string(),
+            data_type: DataType::String,
+            description: "Select listeners of processes matching a glob".to_string(),
+            example_values: vec!["sshd".to_string()],
+            validation_notes: Some("Processes of other users are only visible to root".to_string()),
+        });
+
+    // State requirements
+    let string_operations = vec![
+        Operation::Equals,
+        Operation::NotEqual,
+        Operation::Contains,
+        Operation::NotContains,
+        Operation::StartsWith,
+        Operation::EndsWith,
+        Operation::PatternMatch,
+        Operation::EqualsAny,
+        Operation::NotEqualsAny,
+    ];
+    let int_operations = vec![
+        Operation::Equals,
+        Operation::NotEqual,
+        Operation::GreaterThan,
+        Operation::LessThan,
+        Operation::GreaterThanOrEqual,
+        Operation::LessThanOrEqual,
+        Operation::EqualsAny,
+        Operation::NotEqualsAny,
+    ];
+
+    let state_fields = [
+        ("protocol", DataType::String, "Socket protocol", "tcp", None),
+        (
+            "state",
+            DataType::String,
+            "Socket state",
+            "LISTEN",
+            Some("`LISTEN` for TCP, `UNCONN` for UDP"),
+        ),
+        (
+            "local_address",
+            DataType::IpAddress,
+            "Address the socket is bound to",
+            "127.0.0.1",
+            Some("`0.0.0.0`, `::` or `*` for every address; `in_subnet` takes a CIDR or a list of CIDRs"),
+        ),
+        ("port", DataType::Int, "Local port", "22", None),
+        (
+            "process_name",
+            DataType::String,
+            "Name of the process owning the socket",
+            "sshd",
+            Some("Empty when the process is not visible to the scanner"),
+        ),
+    ];
+
+    for (name, data_type, description, example, notes) in state_fields {
+        let allowed_operations = match data_type {
+            DataType::Int => int_operations.clone(),
+            DataType::IpAddress => string_operations
+                .iter()
+                .copied()
+                .chain([Operation::InSubnet, Operation::NotInSubnet])
+                .collect(),
+            _ => string_operations.clone(),
+        };
+        contract
+            .state_requirements
+            .add_optional_field(StateFieldSpec {
+                name: name.to_string(),
+                data_type,
+                allowed_operations,
+                description: description.to_string(),
+                example_values: vec![example.to_string()],
+                validation_notes: notes.map(str::to_string),
+            });
+        contract
+            .field_mappings
+            .validation_mappings
+            .state_to_data
+            .insert(name.to_string(), name.to_string());
+        contract
+            .field_mappings
+            .collection_mappings
+            .optional_data_fields
+            .push(name.to_string());
+    }
+
+    // Field mappings
+    contract
+        .field_mappings
+        .collection_mappings
+        .required_data_fields = vec!["listeners".to_string()];
+
+    // Collection strategy
+    contract.collection_strategy = CollectionStrategy {
+        collector_type: "command".to_string(),
+        collection_mode: CollectionMode::Command,
+        required_capabilities: vec!["execute_ss".to_string()],
+        performance_hints: PerformanceHints {
+            expected_collection_time_ms: Some(100),
+            memory_usage_mb: Some(1),
+            network_intensive: false,
+            cpu_intensive: false,
+            requires_elevated_privileges: false,
+        },
+        required_privilege: PrivilegeRequirement::None,
+    };
+
+    contract
+}
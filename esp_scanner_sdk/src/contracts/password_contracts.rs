@@ -0,0 +1,204 @@
+//! Password policy CTN contract
+//!
+//! Validates password aging: the defaults `/etc/login.defs` gives new
+//! accounts and the aging `/etc/shadow` records for existing ones. Each
+//! object is one check; the per-account fields hold one value per matching
+//! account and are combined with the field's entity check, so
+//! `max_days int <= 60 all` requires every account to comply.
+
+use esp_scanner_base::strategies::{
+    BehaviorType, CollectionMode, CollectionStrategy, CtnContract, ObjectFieldSpec,
+    PerformanceHints, PrivilegeRequirement, StateFieldSpec, SupportedBehavior,
+};
+use esp_scanner_base::types::common::{DataType, Operation};
+
+pub fn create_password_policy_contract() -> CtnContract {
+    let mut contract = CtnContract::new("password_policy".to_string());
+
+    // Object requirements
+    contract
+        .object_requirements
+        .add_optional_field(ObjectFieldSpec {
+            name: "username".to_string(),
+            data_type: DataType::String,
+            description: "Accounts to check, by name or glob".to_string(),
+            example_values: vec!["*".to_string(), "svc_*".to_string()],
+            validation_notes: Some("Defaults to every account in the shadow file".to_string()),
+        });
+
+    contract
+        .object_requirements
+        .add_optional_field(ObjectFieldSpec {
+            name: "login_defs_file".to_string(),
+            data_type: DataType::String,
+            description: "login.defs file to read".to_string(),
+            example_values: vec!["/etc/login.defs".to_string()],
+            validation_notes: Some("Defaults to /etc/login.defs".to_string()),
+        });
+
+    contract
+        .object_requirements
+        .add_optional_field(ObjectFieldSpec {
+            name: "shadow_file".to_string(),
+            data_type: DataType::String,
+            description: "Shadow database to read".to_string(),
+            example_values: vec!["/etc/shadow".to_string()],
+            validation_notes: Some("Defaults to /etc/shadow, readable only by root".to_string()),
+        });
+
+    // State requirements
+    let string_operations = vec![
+        Operation::Equals,
+        Operation::NotEqual,
+        Operation::StartsWith,
+        Operation::EndsWith,
+        Operation::PatternMatch,
+        Operation::EqualsAny,
+        Operation::NotEqualsAny,
+    ];
+    let int_operations = vec![
+        Operation::Equals,
+        Operation::NotEqual,
+        Operation::GreaterThan,
+        Operation::LessThan,
+        Operation::GreaterThanOrEqual,
+        Operation::LessThanOrEqual,
+        Operation::EqualsAny,
+        Operation::NotEqualsAny,
+    ];
+    let bool_operations = vec![Operation::Equals, Operation::NotEqual];
+
+    let state_fields = [
+        (
+            "pass_max_days",
+            DataType::Int,
+            "PASS_MAX_DAYS in login.defs",
+            "60",
+            Some("Not collected when login.defs does not set it"),
+        ),
+        (
+            "pass_min_days",
+            DataType::Int,
+            "PASS_MIN_DAYS in login.defs",
+            "1",
+            None,
+        ),
+        (
+            "pass_warn_age",
+            DataType::Int,
+            "PASS_WARN_AGE in login.defs",
+            "7",
+            None,
+        ),
+        (
+            "username",
+            DataType::String,
+            "Name of each account",
+            "root",
+            Some("List field; combine entries with an entity check"),
+        ),
+        (
+            "max_days",
+            DataType::Int,
+            "Maximum password age of each account",
+            "60",
+            Some("List field; 99999 when the account has no maximum"),
+        ),
+        (
+            "min_days",
+            DataType::Int,
+            "Minimum password age of each account",
+            "1",
+            Some("List field; 0 when unset"),
+        ),
+        (
+            "warn_days",
+            DataType::Int,
+            "Expiry warning period of each account",
+            "7",
+            Some("List field; 0 when unset"),
+        ),
+        (
+            "last_change_epoch",
+            DataType::Int,
+            "Last password change of each account, in seconds since the epoch",
+            "1704067200",
+            Some("List field; 0 when unset or when a change is forced at next login"),
+        ),
+        (
+            "locked",
+            DataType::Boolean,
+            "Whether each account's password field starts with `!` or `*`",
+            "false",
+            Some("List field"),
+        ),
+    ];
+
+    for (name, data_type, description, example, notes) in state_fields {
+        let allowed_operations = match data_type {
+            DataType::Int => int_operations.clone(),
+            DataType::Boolean => bool_operations.clone(),
+            _ => string_operations.clone(),
+        };
+        contract
+            .state_requirements
+            .add_optional_field(StateFieldSpec {
+                name: name.to_string(),
+                data_type,
+                allowed_operations,
+                description: description.to_string(),
+                example_values: vec![example.to_string()],
+                validation_notes: notes.map(str::to_string),
+            });
+        contract
+            .field_mappings
+            .validation_mappings
+            .state_to_data
+            .insert(name.to_string(), name.to_string());
+        if name.starts_with("pass_") {
+            contract
+                .field_mappings
+                .collection_mappings
+                .optional_data_fields
+                .push(name.to_string());
+        }
+    }
+
+    // Field mappings
+    contract
+        .field_mappings
+        .collection_mappings
+        .required_data_fields = vec![
+        "username".to_string(),
+        "max_days".to_string(),
+        "min_days".to_string(),
+        "warn_days".to_string(),
+        "last_change_epoch".to_string(),
+        "locked".to_string(),
+    ];
+
+    // Collection strategy
+    contract.collection_strategy = CollectionStrategy {
+        collector_type: "filesystem".to_string(),
+        collection_mode: CollectionMode::Content,
+        required_capabilities: vec!["file_access".to_string()],
+        performance_hints: PerformanceHints {
+            expected_collection_time_ms: Some(10),
+            memory_usage_mb: Some(1),
+            network_intensive: false,
+            cpu_intensive: false,
+            requires_elevated_privileges: true,
+        },
+        required_privilege: PrivilegeRequirement::Root,
+    };
+
+    contract.add_supported_behavior(SupportedBehavior {
+        name: "exclude_locked".to_string(),
+        behavior_type: BehaviorType::Flag,
+        parameters: vec![],
+        description: "Skip accounts whose password field starts with `!` or `*`".to_string(),
+        example: "BEHAVIOR exclude_locked".to_string(),
+    });
+
+    contract
+}
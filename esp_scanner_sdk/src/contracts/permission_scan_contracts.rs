@@ -0,0 +1,197 @@
+//! File permission scan CTN contract
+//!
+//! Finds files with special permission bits by walking a directory tree:
+//! set-user-ID and set-group-ID regular files, and world-writable files and
+//! directories. Every file found is an item, so a criterion can require that
+//! none exist, or that each one found is on an approved list.
+
+use esp_scanner_base::strategies::{
+    CollectionMode, CollectionStrategy, CtnContract, ObjectFieldSpec, PerformanceHints,
+    PrivilegeRequirement, StateFieldSpec,
+};
+use esp_scanner_base::types::common::{DataType, Operation};
+
+pub fn create_file_permission_scan_contract() -> CtnContract {
+    let mut contract = CtnContract::new("file_permission_scan".to_string());
+
+    // Object requirements
+    contract
+        .object_requirements
+        .add_required_field(ObjectFieldSpec {
+            name: "path".to_string(),
+            data_type: DataType::String,
+            description: "Root of the walk".to_string(),
+            example_values: vec!["/".to_string(), "/usr".to_string()],
+            validation_notes: Some("Symlinks are never followed".to_string()),
+        });
+
+    let optional_object_fields = [
+        (
+            "permissions",
+            DataType::String,
+            "Comma-separated permissions that select a file",
+            "suid,sgid",
+            "`suid`, `sgid` and `world_writable`; all three by default",
+        ),
+        (
+            "max_depth",
+            DataType::Int,
+            "Directory levels below the root to walk",
+            "32",
+            "Defaults to 32; deeper directories become a warning",
+        ),
+        (
+            "max_entries",
+            DataType::Int,
+            "Entries to look at before the walk stops",
+            "500000",
+            "Defaults to 500000; stopping early becomes a warning",
+        ),
+        (
+            "exclude",
+            DataType::String,
+            "Comma-separated paths not to walk into",
+            "/var/lib/containers,/home",
+            "Added to /proc, /sys, /dev, /run and network filesystem mounts",
+        ),
+    ];
+    for (name, data_type, description, example, notes) in optional_object_fields {
+        contract
+            .object_requirements
+            .add_optional_field(ObjectFieldSpec {
+                name: name.to_string(),
+                data_type,
+                description: description.to_string(),
+                example_values: vec![example.to_string()],
+                validation_notes: Some(notes.to_string()),
+            });
+    }
+
+    // State requirements
+    let string_operations = vec![
+        Operation::Equals,
+        Operation::NotEqual,
+        Operation::Contains,
+        Operation::NotContains,
+        Operation::StartsWith,
+        Operation::EndsWith,
+        Operation::NotStartsWith,
+        Operation::PatternMatch,
+        Operation::EqualsAny,
+        Operation::NotEqualsAny,
+    ];
+    let int_operations = vec![
+        Operation::Equals,
+        Operation::NotEqual,
+        Operation::GreaterThan,
+        Operation::LessThan,
+        Operation::GreaterThanOrEqual,
+        Operation::LessThanOrEqual,
+        Operation::EqualsAny,
+        Operation::NotEqualsAny,
+    ];
+    let bool_operations = vec![Operation::Equals, Operation::NotEqual];
+
+    let state_fields = [
+        (
+            "path",
+            DataType::String,
+            "Path of the file found",
+            "/usr/bin/passwd",
+            None,
+        ),
+        (
+            "type",
+            DataType::String,
+            "`file` or `directory`",
+            "file",
+            Some("Only world-writable matches can be directories"),
+        ),
+        (
+            "mode",
+            DataType::String,
+            "Permission bits in octal",
+            "4755",
+            None,
+        ),
+        ("suid", DataType::Boolean, "Set-user-ID bit", "true", None),
+        ("sgid", DataType::Boolean, "Set-group-ID bit", "false", None),
+        (
+            "sticky",
+            DataType::Boolean,
+            "Sticky bit",
+            "true",
+            Some("World-writable directories should have it"),
+        ),
+        (
+            "world_writable",
+            DataType::Boolean,
+            "Writable by others",
+            "false",
+            None,
+        ),
+        ("uid", DataType::Int, "Owner user ID", "0", None),
+        ("gid", DataType::Int, "Owner group ID", "0", None),
+    ];
+
+    for (name, data_type, description, example, notes) in state_fields {
+        let allowed_operations = match data_type {
+            DataType::Int => int_operations.clone(),
+            DataType::Boolean => bool_operations.clone(),
+            _ => string_operations.clone(),
+        };
+        contract
+            .state_requirements
+            .add_optional_field(StateFieldSpec {
+                name: name.to_string(),
+                data_type,
+                allowed_operations,
+                description: description.to_string(),
+                example_values: vec![example.to_string()],
+                validation_notes: notes.map(str::to_string),
+            });
+        contract
+            .field_mappings
+            .validation_mappings
+            .state_to_data
+            .insert(name.to_string(), name.to_string());
+        contract
+            .field_mappings
+            .collection_mappings
+            .optional_data_fields
+            .push(name.to_string());
+    }
+
+    // Field mappings
+    contract
+        .field_mappings
+        .collection_mappings
+        .object_to_collection
+        .insert("path".to_string(), "file_path".to_string());
+
+    contract
+        .field_mappings
+        .collection_mappings
+        .required_data_fields = vec![
+        "files".to_string(),
+        "entries_scanned".to_string(),
+        "truncated".to_string(),
+    ];
+
+    // Collection strategy
+    contract.collection_strategy = CollectionStrategy {
+        collector_type: "filesystem".to_string(),
+        collection_mode: CollectionMode::Metadata,
+        required_capabilities: vec!["file_access".to_string()],
+        performance_hints: PerformanceHints {
+            expected_collection_time_ms: Some(5000),
+            memory_usage_mb: Some(16),
+            network_intensive: false,
+            cpu_intensive: false,
+            requires_elevated_privileges: true,
+        },
+        required_privilege: PrivilegeRequirement::Root,
+    };
+
+    contract
+}
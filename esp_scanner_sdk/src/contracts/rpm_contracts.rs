@@ -4,7 +4,7 @@
 
 use esp_scanner_base::strategies::{
     BehaviorParameter, BehaviorType, CollectionMode, CollectionStrategy, CtnContract,
-    ObjectFieldSpec, PerformanceHints, StateFieldSpec, SupportedBehavior,
+    ObjectFieldSpec, PerformanceHints, PrivilegeRequirement, StateFieldSpec, SupportedBehavior,
 };
 use esp_scanner_base::types::common::{DataType, Operation};
 
@@ -47,10 +47,18 @@ pub fn create_rpm_package_contract() -> CtnContract {
                 Operation::LessThan,
                 Operation::GreaterThanOrEqual,
                 Operation::LessThanOrEqual,
+                Operation::VersionEquals,
+                Operation::VersionNotEqual,
+                Operation::VersionGreaterThan,
+                Operation::VersionLessThan,
+                Operation::VersionGreaterOrEqual,
+                Operation::VersionLessOrEqual,
             ],
             description: "Package version".to_string(),
             example_values: vec!["3.0.7".to_string(), "1.2.3-4.el9".to_string()],
-            validation_notes: Some("Version comparison as strings".to_string()),
+            validation_notes: Some(
+                "Ordered in RPM version order; `=` and `!=` compare the exact string".to_string(),
+            ),
         });
 
     // Field mappings
@@ -93,6 +101,7 @@ pub fn create_rpm_package_contract() -> CtnContract {
             cpu_intensive: false,
             requires_elevated_privileges: false,
         },
+        required_privilege: PrivilegeRequirement::None,
     };
 
     contract.add_supported_behavior(SupportedBehavior {
@@ -102,8 +111,9 @@ pub fn create_rpm_package_contract() -> CtnContract {
             name: "timeout".to_string(),
             data_type: DataType::Int,
             required: true,
-            default_value: Some("5".to_string()),
-            description: "Command timeout in seconds".to_string(),
+            default_value: None,
+            description: "Command timeout in seconds; the executor's timeout when unset"
+                .to_string(),
         }],
         description: "Set command execution timeout".to_string(),
         example: "BEHAVIOR timeout 30".to_string(),
@@ -0,0 +1,184 @@
+//! Scheduled job CTN contract
+//!
+//! Validates cron jobs and systemd timers. Every job in `/etc/crontab`,
+//! `/etc/cron.d`, the `run-parts` directories, a user's crontab or a timer
+//! is an item, so `TEST none ...` on an object filtering `command` for
+//! `*wget*` requires that no job runs it.
+
+use esp_scanner_base::strategies::{
+    CollectionMode, CollectionStrategy, CtnContract, ObjectFieldSpec, PerformanceHints,
+    PrivilegeRequirement, StateFieldSpec,
+};
+use esp_scanner_base::types::common::{DataType, Operation};
+
+pub fn create_scheduled_job_contract() -> CtnContract {
+    let mut contract = CtnContract::new("scheduled_job".to_string());
+
+    // Object requirements
+    let filters = [
+        (
+            "source",
+            "Glob over where the job is defined",
+            "/etc/cron.d/*",
+            "A crontab path, `crontab -u <user>`, a run-parts directory or a timer unit",
+        ),
+        (
+            "user",
+            "Glob over the user the job runs as",
+            "root",
+            "Timers without `User=` run as root",
+        ),
+        (
+            "command",
+            "Glob over the job's command",
+            "*/usr/sbin/aide*",
+            "A timer's command is the ExecStart of the unit it activates",
+        ),
+    ];
+    for (name, description, example, notes) in filters {
+        contract
+            .object_requirements
+            .add_optional_field(ObjectFieldSpec {
+                name: name.to_string(),
+                data_type: DataType::String,
+                description: description.to_string(),
+                example_values: vec![example.to_string()],
+                validation_notes: Some(notes.to_string()),
+            });
+    }
+
+    // State requirements
+    let string_operations = vec![
+        Operation::Equals,
+        Operation::NotEqual,
+        Operation::Contains,
+        Operation::NotContains,
+        Operation::StartsWith,
+        Operation::EndsWith,
+        Operation::PatternMatch,
+        Operation::EqualsAny,
+        Operation::NotEqualsAny,
+    ];
+    let integer_operations = vec![
+        Operation::Equals,
+        Operation::NotEqual,
+        Operation::LessThan,
+        Operation::LessThanOrEqual,
+        Operation::GreaterThan,
+        Operation::GreaterThanOrEqual,
+    ];
+    let boolean_operations = vec![Operation::Equals, Operation::NotEqual];
+
+    let state_fields = [
+        (
+            "source",
+            DataType::String,
+            &string_operations,
+            "Where the job is defined",
+            "/etc/crontab",
+            None,
+        ),
+        (
+            "schedule",
+            DataType::String,
+            &string_operations,
+            "When the job runs",
+            "0 5 * * *",
+            Some(
+                "Cron schedules are five single-spaced fields with @aliases expanded and \
+                 names as numbers (`30 4 * * 0`); timers give their OnCalendar= or \
+                 OnUnitActiveSec= settings",
+            ),
+        ),
+        (
+            "command",
+            DataType::String,
+            &string_operations,
+            "Command the job runs",
+            "/usr/sbin/aide --check",
+            None,
+        ),
+        (
+            "user",
+            DataType::String,
+            &string_operations,
+            "User the job runs as",
+            "root",
+            None,
+        ),
+        (
+            "enabled",
+            DataType::Boolean,
+            &boolean_operations,
+            "Whether the job will run",
+            "true",
+            Some("Cron lines are enabled; run-parts scripts when executable; timers when enabled"),
+        ),
+        (
+            "user_exists",
+            DataType::Boolean,
+            &boolean_operations,
+            "Whether the job's user is in /etc/passwd",
+            "true",
+            None,
+        ),
+        (
+            "max_interval_hours",
+            DataType::Int,
+            &integer_operations,
+            "Longest time between two runs, rounded up to hours",
+            "24",
+            Some("Not collected for @reboot jobs and calendar expressions it cannot derive"),
+        ),
+    ];
+
+    for (name, data_type, operations, description, example, notes) in state_fields {
+        contract
+            .state_requirements
+            .add_optional_field(StateFieldSpec {
+                name: name.to_string(),
+                data_type,
+                allowed_operations: operations.clone(),
+                description: description.to_string(),
+                example_values: vec![example.to_string()],
+                validation_notes: notes.map(str::to_string),
+            });
+        contract
+            .field_mappings
+            .validation_mappings
+            .state_to_data
+            .insert(name.to_string(), name.to_string());
+        contract
+            .field_mappings
+            .collection_mappings
+            .optional_data_fields
+            .push(name.to_string());
+    }
+
+    // Field mappings
+    contract
+        .field_mappings
+        .collection_mappings
+        .required_data_fields = vec!["jobs".to_string()];
+
+    // Collection strategy
+    contract.collection_strategy = CollectionStrategy {
+        collector_type: "command".to_string(),
+        collection_mode: CollectionMode::Command,
+        required_capabilities: vec![
+            "execute_crontab".to_string(),
+            "execute_systemctl".to_string(),
+            "file_access".to_string(),
+        ],
+        performance_hints: PerformanceHints {
+            expected_collection_time_ms: Some(500),
+            memory_usage_mb: Some(1),
+            network_intensive: false,
+            cpu_intensive: false,
+            requires_elevated_privileges: true,
+        },
+        required_privilege: PrivilegeRequirement::Root,
+    };
+
+    contract
+}
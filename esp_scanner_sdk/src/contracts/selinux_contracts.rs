@@ -1,10 +1,12 @@
-//! SELinux status CTN contract
+//! SELinux CTN contracts
 //!
-//! Validates SELinux enforcement mode.
+//! Validates SELinux enforcement mode, boolean values and file security
+//! contexts. Boolean and file context criteria are not evaluated on hosts
+//! with SELinux disabled.
 
 use esp_scanner_base::strategies::{
     CollectionMode, CollectionStrategy, CtnContract, ObjectFieldSpec, PerformanceHints,
-    StateFieldSpec,
+    PrivilegeRequirement, StateFieldSpec,
 };
 use esp_scanner_base::types::common::{DataType, Operation};
 
@@ -78,6 +80,179 @@ pub fn create_selinux_status_contract() -> CtnContract {
             cpu_intensive: false,
             requires_elevated_privileges: false,
         },
+        required_privilege: PrivilegeRequirement::None,
+    };
+
+    contract
+}
+
+pub fn create_selinux_boolean_contract() -> CtnContract {
+    let mut contract = CtnContract::new("selinux_boolean".to_string());
+
+    // Object requirements
+    contract
+        .object_requirements
+        .add_required_field(ObjectFieldSpec {
+            name: "boolean_name".to_string(),
+            data_type: DataType::String,
+            description: "SELinux boolean name".to_string(),
+            example_values: vec![
+                "selinuxuser_execheap".to_string(),
+                "ssh_sysadm_login".to_string(),
+            ],
+            validation_notes: Some("As listed by getsebool -a".to_string()),
+        });
+
+    // State requirements
+    contract
+        .state_requirements
+        .add_optional_field(StateFieldSpec {
+            name: "value".to_string(),
+            data_type: DataType::Boolean,
+            allowed_operations: vec![Operation::Equals, Operation::NotEqual],
+            description: "Current value of the boolean".to_string(),
+            example_values: vec!["false".to_string()],
+            validation_notes: Some("true when getsebool reports on".to_string()),
+        });
+
+    // Field mappings
+    contract
+        .field_mappings
+        .collection_mappings
+        .object_to_collection
+        .insert("boolean_name".to_string(), "boolean_name".to_string());
+
+    contract
+        .field_mappings
+        .collection_mappings
+        .required_data_fields = vec!["boolean_name".to_string(), "selinux_enabled".to_string()];
+
+    contract
+        .field_mappings
+        .collection_mappings
+        .optional_data_fields = vec!["found".to_string(), "value".to_string()];
+
+    contract
+        .field_mappings
+        .validation_mappings
+        .state_to_data
+        .insert("value".to_string(), "value".to_string());
+
+    // Collection strategy
+    contract.collection_strategy = CollectionStrategy {
+        collector_type: "command".to_string(),
+        collection_mode: CollectionMode::Command,
+        required_capabilities: vec![
+            "execute_getenforce".to_string(),
+            "execute_getsebool".to_string(),
+        ],
+        performance_hints: PerformanceHints {
+            expected_collection_time_ms: Some(50),
+            memory_usage_mb: Some(1),
+            network_intensive: false,
+            cpu_intensive: false,
+            requires_elevated_privileges: false,
+        },
+        required_privilege: PrivilegeRequirement::None,
+    };
+
+    contract
+}
+
+pub fn create_selinux_file_context_contract() -> CtnContract {
+    let mut contract = CtnContract::new("selinux_file_context".to_string());
+
+    // Object requirements
+    contract
+        .object_requirements
+        .add_required_field(ObjectFieldSpec {
+            name: "path".to_string(),
+            data_type: DataType::String,
+            description: "File or directory whose context is checked".to_string(),
+            example_values: vec!["/etc/shadow".to_string()],
+            validation_notes: Some("Symlinks are not followed".to_string()),
+        });
+
+    // State requirements
+    let string_operations = vec![
+        Operation::Equals,
+        Operation::NotEqual,
+        Operation::Contains,
+        Operation::NotContains,
+        Operation::StartsWith,
+        Operation::EndsWith,
+        Operation::PatternMatch,
+    ];
+
+    let state_fields = [
+        ("user", "SELinux user", "system_u", None),
+        ("role", "SELinux role", "object_r", None),
+        ("type", "SELinux type", "shadow_t", None),
+        (
+            "level",
+            "MLS/MCS sensitivity level and categories",
+            "s0",
+            Some("Empty under a policy without MLS"),
+        ),
+        (
+            "context",
+            "Full security context",
+            "system_u:object_r:shadow_t:s0",
+            None,
+        ),
+    ];
+
+    for (name, description, example, notes) in state_fields {
+        contract
+            .state_requirements
+            .add_optional_field(StateFieldSpec {
+                name: name.to_string(),
+                data_type: DataType::String,
+                allowed_operations: string_operations.clone(),
+                description: description.to_string(),
+                example_values: vec![example.to_string()],
+                validation_notes: notes.map(str::to_string),
+            });
+        contract
+            .field_mappings
+            .validation_mappings
+            .state_to_data
+            .insert(name.to_string(), name.to_string());
+    }
+
+    // Field mappings
+    contract
+        .field_mappings
+        .collection_mappings
+        .object_to_collection
+        .insert("path".to_string(), "path".to_string());
+
+    contract
+        .field_mappings
+        .collection_mappings
+        .required_data_fields = vec!["path".to_string(), "selinux_enabled".to_string()];
+
+    contract
+        .field_mappings
+        .collection_mappings
+        .optional_data_fields = ["exists", "context", "user", "role", "type", "level"]
+        .iter()
+        .map(|name| name.to_string())
+        .collect();
+
+    // Collection strategy
+    contract.collection_strategy = CollectionStrategy {
+        collector_type: "command".to_string(),
+        collection_mode: CollectionMode::Command,
+        required_capabilities: vec!["execute_getenforce".to_string(), "execute_stat".to_string()],
+        performance_hints: PerformanceHints {
+            expected_collection_time_ms: Some(30),
+            memory_usage_mb: Some(1),
+            network_intensive: false,
+            cpu_intensive: false,
+            requires_elevated_privileges: false,
+        },
+        required_privilege: PrivilegeRequirement::None,
     };
 
     contract
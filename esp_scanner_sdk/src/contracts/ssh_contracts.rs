@@ -0,0 +1,170 @@
+//! sshd_config CTN contract
+//!
+//! Validates OpenSSH server directives by name. State field names are the
+//! directive keywords in any case (`PermitRootLogin`, `permitrootlogin`);
+//! directives not listed here can still be checked, with a validation warning.
+
+use esp_scanner_base::strategies::{
+    BehaviorType, CollectionMode, CollectionStrategy, CtnContract, ObjectFieldSpec,
+    PerformanceHints, PrivilegeRequirement, StateFieldSpec, SupportedBehavior,
+};
+use esp_scanner_base::types::common::{DataType, Operation};
+
+/// Commonly audited directives, with an example value
+const STRING_DIRECTIVES: &[(&str, &str)] = &[
+    ("PermitRootLogin", "no"),
+    ("PasswordAuthentication", "no"),
+    ("PermitEmptyPasswords", "no"),
+    ("PubkeyAuthentication", "yes"),
+    ("KbdInteractiveAuthentication", "no"),
+    ("HostbasedAuthentication", "no"),
+    ("IgnoreRhosts", "yes"),
+    ("PermitUserEnvironment", "no"),
+    ("UsePAM", "yes"),
+    ("X11Forwarding", "no"),
+    ("AllowTcpForwarding", "no"),
+    ("AllowAgentForwarding", "no"),
+    ("GatewayPorts", "no"),
+    ("PermitTunnel", "no"),
+    ("Ciphers", "aes256-gcm@openssh.com,aes128-ctr"),
+    ("MACs", "hmac-sha2-512-etm@openssh.com"),
+    ("KexAlgorithms", "curve25519-sha256"),
+    ("HostKeyAlgorithms", "ssh-ed25519"),
+    ("LogLevel", "VERBOSE"),
+    ("SyslogFacility", "AUTH"),
+    ("LoginGraceTime", "60"),
+    ("Banner", "/etc/issue.net"),
+    ("AllowUsers", "alice bob"),
+    ("AllowGroups", "sshusers"),
+    ("DenyUsers", "guest"),
+    ("DenyGroups", "nossh"),
+    ("Port", "22"),
+    ("ListenAddress", "0.0.0.0"),
+    ("Subsystem", "sftp internal-sftp"),
+];
+
+/// Directives with numeric values, checked as integers
+const INT_DIRECTIVES: &[(&str, &str)] = &[
+    ("MaxAuthTries", "4"),
+    ("MaxSessions", "10"),
+    ("ClientAliveInterval", "300"),
+    ("ClientAliveCountMax", "3"),
+];
+
+pub fn create_sshd_config_contract() -> CtnContract {
+    let mut contract = CtnContract::new("sshd_config".to_string());
+
+    // Object requirements
+    contract
+        .object_requirements
+        .add_required_field(ObjectFieldSpec {
+            name: "path".to_string(),
+            data_type: DataType::String,
+            description: "Path to the sshd server config".to_string(),
+            example_values: vec!["/etc/ssh/sshd_config".to_string()],
+            validation_notes: Some(
+                "Include directives are resolved from its directory".to_string(),
+            ),
+        });
+
+    // State requirements
+    let string_operations = vec![
+        Operation::Equals,
+        Operation::NotEqual,
+        Operation::CaseInsensitiveEquals,
+        Operation::CaseInsensitiveNotEqual,
+        Operation::Contains,
+        Operation::NotContains,
+        Operation::StartsWith,
+        Operation::EndsWith,
+        Operation::PatternMatch,
+        Operation::GlobMatch,
+        Operation::NotGlobMatch,
+        Operation::EqualsAny,
+        Operation::NotEqualsAny,
+    ];
+    let int_operations = vec![
+        Operation::Equals,
+        Operation::NotEqual,
+        Operation::GreaterThan,
+        Operation::LessThan,
+        Operation::GreaterThanOrEqual,
+        Operation::LessThanOrEqual,
+    ];
+
+    let directives = STRING_DIRECTIVES
+        .iter()
+        .map(|(name, example)| (name, example, DataType::String, &string_operations))
+        .chain(
+            INT_DIRECTIVES
+                .iter()
+                .map(|(name, example)| (name, example, DataType::Int, &int_operations)),
+        );
+    for (name, example, data_type, operations) in directives {
+        let keyword = name.to_ascii_lowercase();
+        contract
+            .state_requirements
+            .add_optional_field(StateFieldSpec {
+                name: name.to_string(),
+                data_type,
+                allowed_operations: operations.clone(),
+                description: format!("Effective value of the {} directive", name),
+                example_values: vec![example.to_string()],
+                validation_notes: Some(
+                    "First value wins; unset directives are not collected".to_string(),
+                ),
+            });
+        contract
+            .field_mappings
+            .validation_mappings
+            .state_to_data
+            .insert(name.to_string(), keyword.clone());
+        contract
+            .field_mappings
+            .collection_mappings
+            .optional_data_fields
+            .push(keyword);
+    }
+
+    // Field mappings
+    contract
+        .field_mappings
+        .collection_mappings
+        .object_to_collection
+        .insert("path".to_string(), "file_path".to_string());
+
+    contract
+        .field_mappings
+        .collection_mappings
+        .required_data_fields = vec!["directives".to_string()];
+    contract
+        .field_mappings
+        .collection_mappings
+        .optional_data_fields
+        .push("match_blocks".to_string());
+
+    // Collection strategy
+    contract.collection_strategy = CollectionStrategy {
+        collector_type: "filesystem".to_string(),
+        collection_mode: CollectionMode::Content,
+        required_capabilities: vec!["file_access".to_string()],
+        performance_hints: PerformanceHints {
+            expected_collection_time_ms: Some(10),
+            memory_usage_mb: Some(1),
+            network_intensive: false,
+            cpu_intensive: false,
+            requires_elevated_privileges: false,
+        },
+        required_privilege: PrivilegeRequirement::None,
+    };
+
+    contract.add_supported_behavior(SupportedBehavior {
+        name: "global_only".to_string(),
+        behavior_type: BehaviorType::Flag,
+        parameters: vec![],
+        description: "Evaluate only directives outside Match blocks".to_string(),
+        example: "BEHAVIOR global_only".to_string(),
+    });
+
+    contract
+}
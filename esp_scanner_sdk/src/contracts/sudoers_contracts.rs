@@ -0,0 +1,175 @@
+//! sudoers CTN contract
+//!
+//! Validates a parsed sudoers policy rather than its raw text, with includes,
+//! line continuations and aliases resolved. The object's `section` selects
+//! the items checked: `user_spec` (the default) makes every user and command
+//! a policy grants an item, and `defaults` every effective `Defaults`
+//! parameter.
+
+use esp_scanner_base::strategies::{
+    CollectionMode, CollectionStrategy, CtnContract, ObjectFieldSpec, PerformanceHints,
+    PrivilegeRequirement, StateFieldSpec,
+};
+use esp_scanner_base::types::common::{DataType, Operation};
+
+pub fn create_sudoers_contract() -> CtnContract {
+    let mut contract = CtnContract::new("sudoers".to_string());
+
+    // Object requirements
+    contract
+        .object_requirements
+        .add_required_field(ObjectFieldSpec {
+            name: "path".to_string(),
+            data_type: DataType::String,
+            description: "Path to the sudoers policy".to_string(),
+            example_values: vec!["/etc/sudoers".to_string()],
+            validation_notes: Some(
+                "Relative includes are resolved from the including file's directory".to_string(),
+            ),
+        });
+    contract
+        .object_requirements
+        .add_optional_field(ObjectFieldSpec {
+            name: "section".to_string(),
+            data_type: DataType::String,
+            description: "Which entries are items".to_string(),
+            example_values: vec!["user_spec".to_string(), "defaults".to_string()],
+            validation_notes: Some("Defaults to `user_spec`".to_string()),
+        });
+
+    // State requirements
+    let string_operations = vec![
+        Operation::Equals,
+        Operation::NotEqual,
+        Operation::Contains,
+        Operation::NotContains,
+        Operation::StartsWith,
+        Operation::EndsWith,
+        Operation::PatternMatch,
+        Operation::EqualsAny,
+        Operation::NotEqualsAny,
+    ];
+    let bool_operations = vec![Operation::Equals, Operation::NotEqual];
+
+    let state_fields = [
+        (
+            "user_spec",
+            DataType::String,
+            "User, `%group` or `ALL` granted the command",
+            "%wheel",
+            Some("User_Alias references are expanded; section `user_spec`"),
+        ),
+        (
+            "host",
+            DataType::String,
+            "Hosts the grant applies on",
+            "ALL",
+            Some("Joined with `, `; section `user_spec`"),
+        ),
+        (
+            "runas",
+            DataType::String,
+            "Users, and `:groups`, the command runs as",
+            "ALL:ALL",
+            Some("`root` when the rule names none; section `user_spec`"),
+        ),
+        (
+            "command",
+            DataType::String,
+            "Command granted",
+            "ALL",
+            Some("Cmnd_Alias references are expanded; section `user_spec`"),
+        ),
+        (
+            "nopasswd",
+            DataType::Boolean,
+            "Whether the command runs without a password",
+            "false",
+            Some("Section `user_spec`"),
+        ),
+        (
+            "name",
+            DataType::String,
+            "Defaults parameter name",
+            "use_pty",
+            Some("Section `defaults`"),
+        ),
+        (
+            "value",
+            DataType::String,
+            "Defaults parameter value",
+            "/var/log/sudo.log",
+            Some("Empty for flags; section `defaults`"),
+        ),
+        (
+            "enabled",
+            DataType::Boolean,
+            "False when the parameter is negated with `!`",
+            "true",
+            Some("Section `defaults`"),
+        ),
+        (
+            "binding",
+            DataType::String,
+            "Who the parameter applies to",
+            ":alice",
+            Some("Empty for global Defaults; section `defaults`"),
+        ),
+    ];
+
+    for (name, data_type, description, example, notes) in state_fields {
+        let allowed_operations = match data_type {
+            DataType::Boolean => bool_operations.clone(),
+            _ => string_operations.clone(),
+        };
+        contract
+            .state_requirements
+            .add_optional_field(StateFieldSpec {
+                name: name.to_string(),
+                data_type,
+                allowed_operations,
+                description: description.to_string(),
+                example_values: vec![example.to_string()],
+                validation_notes: notes.map(str::to_string),
+            });
+        contract
+            .field_mappings
+            .validation_mappings
+            .state_to_data
+            .insert(name.to_string(), name.to_string());
+        contract
+            .field_mappings
+            .collection_mappings
+            .optional_data_fields
+            .push(name.to_string());
+    }
+
+    // Field mappings
+    contract
+        .field_mappings
+        .collection_mappings
+        .object_to_collection
+        .insert("path".to_string(), "file_path".to_string());
+
+    contract
+        .field_mappings
+        .collection_mappings
+        .required_data_fields = vec!["entries".to_string()];
+
+    // Collection strategy
+    contract.collection_strategy = CollectionStrategy {
+        collector_type: "filesystem".to_string(),
+        collection_mode: CollectionMode::Content,
+        required_capabilities: vec!["file_access".to_string()],
+        performance_hints: PerformanceHints {
+            expected_collection_time_ms: Some(10),
+            memory_usage_mb: Some(1),
+            network_intensive: false,
+            cpu_intensive: false,
+            requires_elevated_privileges: true,
+        },
+        required_privilege: PrivilegeRequirement::Root,
+    };
+
+    contract
+}
@@ -4,7 +4,7 @@
 
 use esp_scanner_base::strategies::{
     CollectionMode, CollectionStrategy, CtnContract, ObjectFieldSpec, PerformanceHints,
-    StateFieldSpec,
+    PrivilegeRequirement, StateFieldSpec,
 };
 use esp_scanner_base::types::common::{DataType, Operation};
 
@@ -95,6 +95,7 @@ pub fn create_sysctl_parameter_contract() -> CtnContract {
             cpu_intensive: false,
             requires_elevated_privileges: false,
         },
+        required_privilege: PrivilegeRequirement::None,
     };
 
     contract
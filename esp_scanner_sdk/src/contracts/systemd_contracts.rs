@@ -1,10 +1,12 @@
-//! Systemd service CTN contract
+//! Systemd service and unit property CTN contracts
 //!
-//! Validates systemd service status (active, enabled, loaded).
+//! Validates systemd service status (active, enabled, loaded), and any unit
+//! properties a policy queries through the `properties` parameter; for
+//! `systemd_unit_property`, every property of a unit.
 
 use esp_scanner_base::strategies::{
     CollectionMode, CollectionStrategy, CtnContract, ObjectFieldSpec, PerformanceHints,
-    StateFieldSpec,
+    PrivilegeRequirement, StateFieldSpec, SupportedParameter,
 };
 use esp_scanner_base::types::common::{DataType, Operation};
 
@@ -15,12 +17,19 @@ pub fn create_systemd_service_contract() -> CtnContract {
     contract
         .object_requirements
         .add_required_field(ObjectFieldSpec {
-            name: "service_name".to_string(),
-            data_type: DataType::String,
-            description: "Systemd service unit name".to_string(),
-            example_values: vec!["sshd.service".to_string(), "firewalld.service".to_string()],
-            validation_notes: Some("Include .service suffix".to_string()),
-        });
+        name: "service_name".to_string(),
+        data_type: DataType::String,
+        description: "Systemd service unit name, or a template glob".to_string(),
+        example_values: vec![
+            "sshd.service".to_string(),
+            "firewalld.service".to_string(),
+            "getty@*.service".to_string(),
+        ],
+        validation_notes: Some(
+            "Include .service suffix; `name@*.service` evaluates every instance of the template"
+                .to_string(),
+        ),
+    });
 
     // State requirements
     contract
@@ -56,6 +65,37 @@ pub fn create_systemd_service_contract() -> CtnContract {
             validation_notes: Some("From 'systemctl status'".to_string()),
         });
 
+    contract
+        .state_requirements
+        .add_optional_field(StateFieldSpec {
+            name: "instance".to_string(),
+            data_type: DataType::String,
+            allowed_operations: vec![
+                Operation::Equals,
+                Operation::NotEqual,
+                Operation::StartsWith,
+                Operation::PatternMatch,
+                Operation::EqualsAny,
+                Operation::NotEqualsAny,
+            ],
+            description: "Template instance name (the part after '@')".to_string(),
+            example_values: vec!["tty1".to_string()],
+            validation_notes: Some("Empty for units that are not template instances".to_string()),
+        });
+
+    contract
+        .state_requirements
+        .add_optional_field(StateFieldSpec {
+            name: "properties".to_string(),
+            data_type: DataType::RecordData,
+            allowed_operations: vec![Operation::Equals],
+            description: "Unit properties queried with the `properties` parameter".to_string(),
+            example_values: vec!["See record_checks".to_string()],
+            validation_notes: Some(
+                "From 'systemctl show --property=...'; check with record checks".to_string(),
+            ),
+        });
+
     // Field mappings
     contract
         .field_mappings
@@ -75,6 +115,9 @@ pub fn create_systemd_service_contract() -> CtnContract {
         "active".to_string(),
         "enabled".to_string(),
         "loaded".to_string(),
+        "instance".to_string(),
+        "instances".to_string(),
+        "properties".to_string(),
     ];
 
     contract
@@ -92,6 +135,107 @@ pub fn create_systemd_service_contract() -> CtnContract {
         .validation_mappings
         .state_to_data
         .insert("loaded".to_string(), "loaded".to_string());
+    contract
+        .field_mappings
+        .validation_mappings
+        .state_to_data
+        .insert("instance".to_string(), "instance".to_string());
+    contract
+        .field_mappings
+        .validation_mappings
+        .state_to_data
+        .insert("properties".to_string(), "properties".to_string());
+
+    // Collection strategy
+    contract.collection_strategy = CollectionStrategy {
+        collector_type: "command".to_string(),
+        collection_mode: CollectionMode::Command,
+        required_capabilities: vec!["execute_systemctl".to_string()],
+        performance_hints: PerformanceHints {
+            expected_collection_time_ms: Some(50),
+            memory_usage_mb: Some(2),
+            network_intensive: false,
+            cpu_intensive: false,
+            requires_elevated_privileges: false,
+        },
+        required_privilege: PrivilegeRequirement::None,
+    };
+
+    contract.add_supported_parameter(SupportedParameter {
+        name: "properties".to_string(),
+        data_type: DataType::String,
+        description: "Comma-separated unit properties to query with 'systemctl show'".to_string(),
+        example: "parameters string / properties `ActiveState,NoNewPrivileges` / parameters_end"
+            .to_string(),
+    });
+
+    contract
+}
+
+/// Every property `systemctl show` reports for a unit, checked as a record
+///
+/// Property names are record field paths, so `ProtectSystem` and
+/// `LimitNOFILE` are checked with record checks; list properties such as
+/// `ExecStart` and `CapabilityBoundingSet` take `Name.*` with an entity check.
+pub fn create_systemd_unit_property_contract() -> CtnContract {
+    let mut contract = CtnContract::new("systemd_unit_property".to_string());
+
+    // Object requirements
+    contract
+        .object_requirements
+        .add_required_field(ObjectFieldSpec {
+            name: "unit_name".to_string(),
+            data_type: DataType::String,
+            description: "Full systemd unit name, of any unit type".to_string(),
+            example_values: vec![
+                "sshd.service".to_string(),
+                "sshd.socket".to_string(),
+                "tmp.mount".to_string(),
+            ],
+            validation_notes: Some(
+                "A unit systemd has no definition for is not found rather than an error"
+                    .to_string(),
+            ),
+        });
+
+    // State requirements - allow record checks
+    contract
+        .state_requirements
+        .add_optional_field(StateFieldSpec {
+            name: "record".to_string(),
+            data_type: DataType::RecordData,
+            allowed_operations: vec![Operation::Equals],
+            description: "Unit properties by name, e.g. `ProtectSystem`".to_string(),
+            example_values: vec!["See record_checks".to_string()],
+            validation_notes: Some(
+                "From 'systemctl show'; canonical decimal integers are typed, and list \
+                 properties take `Name.*` with an entity check"
+                    .to_string(),
+            ),
+        });
+
+    // Field mappings
+    contract
+        .field_mappings
+        .collection_mappings
+        .object_to_collection
+        .insert("unit_name".to_string(), "unit_name".to_string());
+
+    contract
+        .field_mappings
+        .collection_mappings
+        .required_data_fields = vec!["unit_name".to_string(), "found".to_string()];
+
+    contract
+        .field_mappings
+        .collection_mappings
+        .optional_data_fields = vec!["properties".to_string()];
+
+    contract
+        .field_mappings
+        .validation_mappings
+        .state_to_data
+        .insert("record".to_string(), "properties".to_string());
 
     // Collection strategy
     contract.collection_strategy = CollectionStrategy {
@@ -105,6 +249,7 @@ pub fn create_systemd_service_contract() -> CtnContract {
             cpu_intensive: false,
             requires_elevated_privileges: false,
         },
+        required_privilege: PrivilegeRequirement::None,
     };
 
     contract
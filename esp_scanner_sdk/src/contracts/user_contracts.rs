@@ -0,0 +1,188 @@
+//! User account CTN contract
+//!
+//! Validates local accounts from `/etc/passwd`, with group membership from
+//! `/etc/group`. Each matching passwd line is checked as its own item.
+
+use esp_scanner_base::strategies::{
+    CollectionMode, CollectionStrategy, CtnContract, ObjectFieldSpec, PerformanceHints,
+    PrivilegeRequirement, StateFieldSpec,
+};
+use esp_scanner_base::types::common::{DataType, Operation};
+
+pub fn create_user_account_contract() -> CtnContract {
+    let mut contract = CtnContract::new("user_account".to_string());
+
+    // Object requirements
+    contract
+        .object_requirements
+        .add_required_field(ObjectFieldSpec {
+            name: "username".to_string(),
+            data_type: DataType::String,
+            description: "Account name, or a glob using `*` and `?`".to_string(),
+            example_values: vec!["root".to_string(), "svc_*".to_string(), "*".to_string()],
+            validation_notes: Some(
+                "A glob evaluates every matching account; duplicates are evaluated separately"
+                    .to_string(),
+            ),
+        });
+
+    contract
+        .object_requirements
+        .add_optional_field(ObjectFieldSpec {
+            name: "passwd_file".to_string(),
+            data_type: DataType::String,
+            description: "Passwd database to read".to_string(),
+            example_values: vec!["/etc/passwd".to_string()],
+            validation_notes: Some("Defaults to /etc/passwd".to_string()),
+        });
+
+    contract
+        .object_requirements
+        .add_optional_field(ObjectFieldSpec {
+            name: "group_file".to_string(),
+            data_type: DataType::String,
+            description: "Group database to read".to_string(),
+            example_values: vec!["/etc/group".to_string()],
+            validation_notes: Some(
+                "Defaults to /etc/group; when missing, group fields are empty".to_string(),
+            ),
+        });
+
+    // State requirements
+    let string_operations = vec![
+        Operation::Equals,
+        Operation::NotEqual,
+        Operation::Contains,
+        Operation::StartsWith,
+        Operation::EndsWith,
+        Operation::PatternMatch,
+        Operation::EqualsAny,
+        Operation::NotEqualsAny,
+    ];
+    let int_operations = vec![
+        Operation::Equals,
+        Operation::NotEqual,
+        Operation::GreaterThan,
+        Operation::LessThan,
+        Operation::GreaterThanOrEqual,
+        Operation::LessThanOrEqual,
+        Operation::EqualsAny,
+        Operation::NotEqualsAny,
+    ];
+    let bool_operations = vec![Operation::Equals, Operation::NotEqual];
+
+    let state_fields = [
+        ("username", DataType::String, "Account name", "root", None),
+        ("uid", DataType::Int, "Numeric user ID", "0", None),
+        ("gid", DataType::Int, "Numeric primary group ID", "0", None),
+        (
+            "gecos",
+            DataType::String,
+            "Comment (GECOS) field",
+            "root",
+            None,
+        ),
+        ("home", DataType::String, "Home directory", "/root", None),
+        (
+            "shell",
+            DataType::String,
+            "Login shell",
+            "/sbin/nologin",
+            None,
+        ),
+        (
+            "home_exists",
+            DataType::Boolean,
+            "Whether the home directory exists",
+            "true",
+            Some("Not collected when the path policy denies the home directory"),
+        ),
+        (
+            "group",
+            DataType::String,
+            "Name of the primary group",
+            "root",
+            Some("Empty when the primary GID has no group entry"),
+        ),
+        (
+            "group_members",
+            DataType::String,
+            "Comma-separated members listed for the primary group",
+            "alice,bob",
+            Some("From the member list in /etc/group"),
+        ),
+        (
+            "groups",
+            DataType::String,
+            "Comma-separated names of every group the account belongs to",
+            "wheel",
+            Some("The primary group plus groups listing the account as a member"),
+        ),
+        (
+            "duplicate",
+            DataType::Boolean,
+            "Whether the username appears on more than one passwd line",
+            "false",
+            None,
+        ),
+    ];
+
+    for (name, data_type, description, example, notes) in state_fields {
+        let allowed_operations = match data_type {
+            DataType::Int => int_operations.clone(),
+            DataType::Boolean => bool_operations.clone(),
+            _ => string_operations.clone(),
+        };
+        contract
+            .state_requirements
+            .add_optional_field(StateFieldSpec {
+                name: name.to_string(),
+                data_type,
+                allowed_operations,
+                description: description.to_string(),
+                example_values: vec![example.to_string()],
+                validation_notes: notes.map(str::to_string),
+            });
+        contract
+            .field_mappings
+            .validation_mappings
+            .state_to_data
+            .insert(name.to_string(), name.to_string());
+        if name != "username" {
+            contract
+                .field_mappings
+                .collection_mappings
+                .optional_data_fields
+                .push(name.to_string());
+        }
+    }
+
+    // Field mappings
+    contract
+        .field_mappings
+        .collection_mappings
+        .object_to_collection
+        .insert("username".to_string(), "username".to_string());
+
+    contract
+        .field_mappings
+        .collection_mappings
+        .required_data_fields = vec!["username".to_string(), "accounts".to_string()];
+
+    // Collection strategy
+    contract.collection_strategy = CollectionStrategy {
+        collector_type: "filesystem".to_string(),
+        collection_mode: CollectionMode::Content,
+        required_capabilities: vec!["file_access".to_string()],
+        performance_hints: PerformanceHints {
+            expected_collection_time_ms: Some(10),
+            memory_usage_mb: Some(1),
+            network_intensive: false,
+            cpu_intensive: false,
+            requires_elevated_privileges: false,
+        },
+        required_privilege: PrivilegeRequirement::None,
+    };
+
+    contract
+}
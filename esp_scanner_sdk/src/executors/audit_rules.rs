@@ -0,0 +1,224 @@
+//! Audit rules executor
+//!
+//! Validates Linux audit rules gathered by the audit rules collector.
+//!
+//! Each object is one item, and it exists when at least one rule survived its
+//! source and filters, so a missing watch fails `all` and passes `none`. A
+//! state field is compared against every rule of the object and the results
+//! are combined with the field's entity check, `all` by default: use
+//! `at_least_one` to require that some rule carries a key or path. Fields are
+//! combined independently, so to tie several fields to one rule, narrow the
+//! object with its `path`, `key` or `syscall` filters instead.
+
+use super::{compare_value, existence_failure, validate_items, ItemNames};
+use esp_scanner_base::execution::evaluate_entity_check;
+use esp_scanner_base::strategies::{
+    CollectedData, CtnContract, CtnExecutionError, CtnExecutionResult, CtnExecutor, FieldOutcome,
+    FieldValidationResult,
+};
+use esp_scanner_base::types::common::{RecordData, ResolvedValue};
+use esp_scanner_base::types::execution_context::ExecutableCriterion;
+use std::collections::HashMap;
+
+pub struct AuditRulesExecutor {
+    contract: CtnContract,
+}
+
+const NAMES: ItemNames = ItemNames {
+    item: "Rules",
+    items: "rule sets",
+    subject: "Audit rules validation",
+};
+
+impl AuditRulesExecutor {
+    pub fn new(contract: CtnContract) -> Self {
+        Self { contract }
+    }
+}
+
+/// The rules collected for each object, in object order
+fn object_rules(
+    collected_data: &HashMap<String, CollectedData>,
+) -> Vec<(String, Vec<&RecordData>)> {
+    let mut objects: Vec<(String, Vec<&RecordData>)> = collected_data
+        .iter()
+        .map(|(object_id, data)| {
+            let rules = match data.get_field("rules") {
+                Some(ResolvedValue::Collection(rules)) => rules
+                    .iter()
+                    .filter_map(|rule| match rule {
+                        ResolvedValue::RecordData(record) => Some(record.as_ref()),
+                        _ => None,
+                    })
+                    .collect(),
+                _ => Vec::new(),
+            };
+            (object_id.clone(), rules)
+        })
+        .collect();
+    objects.sort_by(|a, b| a.0.cmp(&b.0));
+    objects
+}
+
+impl CtnExecutor for AuditRulesExecutor {
+    fn execute_with_contract(
+        &self,
+        criterion: &ExecutableCriterion,
+        collected_data: &HashMap<String, CollectedData>,
+        _contract: &CtnContract,
+    ) -> Result<CtnExecutionResult, CtnExecutionError> {
+        let objects: Vec<_> = object_rules(collected_data)
+            .into_iter()
+            .filter(|(_, rules)| !rules.is_empty())
+            .collect();
+        let objects_expected = criterion.expected_object_count();
+        if let Some(result) =
+            existence_failure(criterion, NAMES.items, objects.len(), objects_expected)
+        {
+            return Ok(result);
+        }
+
+        let results = validate_items(
+            criterion,
+            &self.contract,
+            &NAMES,
+            objects.iter().map(|(object_id, rules)| (object_id, rules)),
+            |rules: &Vec<&RecordData>, field, name| {
+                let values: Vec<ResolvedValue> = rules
+                    .iter()
+                    .filter_map(|rule| rule.get_field_by_path(name))
+                    .filter_map(|v| v.as_str())
+                    .map(|s| ResolvedValue::String(s.to_string()))
+                    .collect();
+
+                if values.is_empty() {
+                    return FieldValidationResult::not_present(
+                        field.name.clone(),
+                        field.value.clone(),
+                        field.operation,
+                        format!("Field '{}' not collected", field.name),
+                    );
+                }
+
+                let rule_results: Vec<bool> = values
+                    .iter()
+                    .map(|actual| compare_value(&field.value, actual, field.operation))
+                    .collect();
+                let passed = evaluate_entity_check(field.entity_check, &rule_results);
+
+                let message = if passed {
+                    format!(
+                        "Field '{}' passed on {} of {} rules",
+                        field.name,
+                        rule_results.iter().filter(|&&r| r).count(),
+                        rule_results.len()
+                    )
+                } else {
+                    format!(
+                        "Field '{}' failed: expected {:?} ({} rules), got {:?}",
+                        field.name,
+                        field.value,
+                        field
+                            .entity_check
+                            .map(|check| format!("{:?}", check))
+                            .unwrap_or_else(|| "All".to_string()),
+                        values
+                    )
+                };
+
+                FieldValidationResult {
+                    field_name: field.name.clone(),
+                    expected_value: field.value.clone(),
+                    actual_value: ResolvedValue::Collection(values),
+                    operation: field.operation,
+                    passed,
+                    message,
+                    outcome: FieldOutcome::from_passed(passed),
+                }
+            },
+        );
+        Ok(results.into_result(criterion, &NAMES))
+    }
+
+    fn get_ctn_contract(&self) -> CtnContract {
+        self.contract.clone()
+    }
+
+    fn ctn_type(&self) -> &str {
+        "audit_rules"
+    }
+
+    fn validate_collected_data(
+        &self,
+        _collected_data: &HashMap<String, CollectedData>,
+        _contract: &CtnContract,
+    ) -> Result<(), CtnExecutionError> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contracts::create_audit_rules_contract;
+    use esp_scanner_base::strategies::ComplianceStatus;
+    use esp_scanner_base::testkit::{CriterionFixture, MockCollector};
+    use serde_json::json;
+
+    const IDENTITY: &str = r#"{"id": "identity_watches", "fields": {"key": "identity"}}"#;
+
+    fn rules() -> MockCollector {
+        MockCollector::new("audit_rules").with_item(
+            "identity_watches",
+            json!({
+                "source": "both",
+                "rules": [
+                    {"type": "watch", "path": "/etc/passwd", "permissions": "wa",
+                     "key": "identity", "syscalls": "", "arch": ""},
+                    {"type": "watch", "path": "/etc/group", "permissions": "w",
+                     "key": "identity", "syscalls": "", "arch": ""}
+                ]
+            }),
+        )
+    }
+
+    fn run(collector: &MockCollector, states: &str) -> CtnExecutionResult {
+        let executor = AuditRulesExecutor::new(create_audit_rules_contract());
+        CriterionFixture::from_json(IDENTITY, states)
+            .and_then(|fixture| fixture.run(collector, &executor))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_at_least_one_rule_satisfies_the_field() {
+        let states = r#"[{"id": "passwd", "fields": [
+            {"name": "path", "type": "string", "op": "=", "value": "/etc/passwd",
+             "entity_check": "at_least_one"}
+        ]}]"#;
+        assert_eq!(run(&rules(), states).status, ComplianceStatus::Pass);
+    }
+
+    #[test]
+    fn test_fields_default_to_every_rule() {
+        let states = r#"[{"id": "perms", "fields": [
+            {"name": "permissions", "type": "string", "op": "=", "value": "wa"}
+        ]}]"#;
+        let result = run(&rules(), states);
+        assert_eq!(result.status, ComplianceStatus::Fail);
+        let field = &result.state_results[0].state_results[0];
+        assert!(!field.passed);
+        assert!(field.message.contains("\"w\""));
+    }
+
+    #[test]
+    fn test_object_without_rules_fails_existence() {
+        let collector = MockCollector::new("audit_rules")
+            .with_item("identity_watches", json!({"source": "both", "rules": []}));
+        let states = r#"[{"id": "key", "fields": [
+            {"name": "key", "type": "string", "op": "=", "value": "identity"}
+        ]}]"#;
+        let result = run(&collector, states);
+        assert_eq!(result.status, ComplianceStatus::Fail);
+        assert!(result.message.starts_with("Existence check failed"));
+    }
+}
@@ -0,0 +1,192 @@
+//! X.509 certificate executor
+//!
+//! Validates certificates collected from PEM and DER files.
+//!
+//! Every object expands into the certificates its path selected, each a
+//! separate item named `object_id[path]`, or `object_id[path#n]` for the
+//! n-th certificate of a bundle. With `TEST all all`, a single certificate
+//! close to expiry fails the criterion and is named in the message.
+//!
+//! A file that is not a readable certificate is still an item: every field
+//! checked on it fails with the decoding error, so a corrupt file in a
+//! certificate directory is reported rather than silently skipped.
+
+use super::{
+    existence_failure, field_matches, field_result, field_unavailable, validate_items, ItemNames,
+};
+use esp_scanner_base::execution::{collection_items, record_field, record_text};
+use esp_scanner_base::strategies::{
+    CollectedData, CtnContract, CtnExecutionError, CtnExecutionResult, CtnExecutor,
+};
+use esp_scanner_base::types::common::RecordData;
+use esp_scanner_base::types::execution_context::ExecutableCriterion;
+use std::collections::HashMap;
+
+pub struct CertificateExecutor {
+    contract: CtnContract,
+}
+
+const NAMES: ItemNames = ItemNames {
+    item: "Certificate",
+    items: "certificates",
+    subject: "Certificate validation",
+};
+
+impl CertificateExecutor {
+    pub fn new(contract: CtnContract) -> Self {
+        Self { contract }
+    }
+}
+
+impl CtnExecutor for CertificateExecutor {
+    fn execute_with_contract(
+        &self,
+        criterion: &ExecutableCriterion,
+        collected_data: &HashMap<String, CollectedData>,
+        _contract: &CtnContract,
+    ) -> Result<CtnExecutionResult, CtnExecutionError> {
+        let items = collection_items(collected_data, "certificates", |_, record| {
+            record_text(record, "source")
+        });
+        let objects_expected = criterion
+            .expected_object_count()
+            .saturating_sub(collected_data.len())
+            + items.len();
+        if let Some(mut result) =
+            existence_failure(criterion, "certificates", items.len(), objects_expected)
+        {
+            let certificates: Vec<&str> = items.iter().map(|(item, _)| item.as_str()).collect();
+            result.details = serde_json::json!({ "certificates": certificates });
+            return Ok(result);
+        }
+
+        let results = validate_items(
+            criterion,
+            &self.contract,
+            &NAMES,
+            items.iter().map(|(item, record)| (item, *record)),
+            |record: &RecordData, field, name| {
+                let error = record_text(record, "error");
+                if !error.is_empty() {
+                    return field_unavailable(field, &error);
+                }
+                field_result(field, record_field(record, name), field_matches)
+            },
+        );
+        Ok(results.into_result(criterion, &NAMES))
+    }
+
+    fn get_ctn_contract(&self) -> CtnContract {
+        self.contract.clone()
+    }
+
+    fn ctn_type(&self) -> &str {
+        "x509_certificate"
+    }
+
+    fn validate_collected_data(
+        &self,
+        _collected_data: &HashMap<String, CollectedData>,
+        _contract: &CtnContract,
+    ) -> Result<(), CtnExecutionError> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contracts::create_certificate_contract;
+    use esp_scanner_base::strategies::ComplianceStatus;
+    use esp_scanner_base::testkit::{CriterionFixture, MockCollector};
+    use serde_json::json;
+
+    fn certificates(certificates: serde_json::Value) -> MockCollector {
+        MockCollector::new("x509_certificate")
+            .with_item("pki", json!({ "certificates": certificates }))
+    }
+
+    fn run(collector: &MockCollector, states: &str, test: &str) -> CtnExecutionResult {
+        let executor = CertificateExecutor::new(create_certificate_contract());
+        CriterionFixture::from_json(
+            r#"{"id": "pki", "fields": {"path": "/etc/pki/tls/certs"}}"#,
+            states,
+        )
+        .and_then(|fixture| fixture.with_test(test))
+        .and_then(|fixture| fixture.run(collector, &executor))
+        .unwrap()
+    }
+
+    const VALIDITY: &str = r#"[{"id": "valid", "fields": [
+        {"name": "days_until_expiry", "type": "int", "op": ">", "value": 30}
+    ]}]"#;
+
+    fn certificate(source: &str, days: i64) -> serde_json::Value {
+        json!({
+            "path": source, "source": source, "error": "",
+            "subject": "CN=host", "issuer": "CN=Example CA",
+            "not_before_epoch": 1_700_000_000, "not_after_epoch": 1_900_000_000,
+            "days_until_expiry": days,
+            "key_algorithm": "RSA", "key_bits": 2048, "self_signed": false
+        })
+    }
+
+    #[test]
+    fn test_expiring_certificate_fails() {
+        let result = run(
+            &certificates(json!([certificate("/etc/pki/a.pem", 400)])),
+            VALIDITY,
+            "all all",
+        );
+        assert_eq!(result.status, ComplianceStatus::Pass);
+
+        let result = run(
+            &certificates(json!([
+                certificate("/etc/pki/a.pem", 400),
+                certificate("/etc/pki/b.pem", 12)
+            ])),
+            VALIDITY,
+            "all all",
+        );
+        assert_eq!(result.status, ComplianceStatus::Fail);
+        assert!(result.message.contains("pki[/etc/pki/b.pem]"));
+    }
+
+    #[test]
+    fn test_corrupt_file_is_a_failing_item() {
+        let collector = certificates(json!([
+            certificate("/etc/pki/a.pem", 400),
+            {"path": "/etc/pki/junk.pem", "source": "/etc/pki/junk.pem",
+             "error": "no certificate found (expected DER or PEM)"}
+        ]));
+
+        let result = run(&collector, VALIDITY, "all all");
+        assert_eq!(result.status, ComplianceStatus::Fail);
+        assert!(result.message.contains("pki[/etc/pki/junk.pem]"));
+        assert!(result.message.contains("no certificate found"));
+
+        let result = run(&collector, VALIDITY, "all at_least_one");
+        assert_eq!(result.status, ComplianceStatus::Pass);
+    }
+
+    #[test]
+    fn test_validity_bounds_compare_as_datetimes() {
+        let collector = certificates(json!([certificate("/etc/pki/a.pem", 400)]));
+        let bounds = |not_after: &str| {
+            format!(
+                r#"[{{"id": "valid", "fields": [
+                    {{"name": "not_before", "type": "datetime", "op": "<", "value": "now"}},
+                    {{"name": "not_after", "type": "datetime", "op": ">", "value": "{}"}}
+                ]}}]"#,
+                not_after
+            )
+        };
+
+        let result = run(&collector, &bounds("2030-01-01T00:00:00Z"), "all all");
+        assert_eq!(result.status, ComplianceStatus::Pass);
+        // 1_900_000_000 is 2030-03-17
+        let result = run(&collector, &bounds("2030-06-01T00:00:00Z"), "all all");
+        assert_eq!(result.status, ComplianceStatus::Fail);
+        assert!(result.message.contains("not_after"), "{}", result.message);
+    }
+}
@@ -10,7 +10,7 @@ use esp_scanner_base::execution::{
 };
 use esp_scanner_base::strategies::{
     CollectedData, ComplianceStatus, CtnContract, CtnExecutionError, CtnExecutionResult,
-    CtnExecutor, FieldValidationResult, StateValidationResult, TestPhase,
+    CtnExecutor, FieldOutcome, FieldValidationResult, StateValidationResult, TestPhase,
 };
 use esp_scanner_base::types::common::ResolvedValue;
 use esp_scanner_base::types::execution_context::ExecutableCriterion;
@@ -71,6 +71,7 @@ impl CtnExecutor for ComputedValuesExecutor {
                         operation: field.operation,
                         passed: true,
                         message: format!("STUB: Variable '{}' validation", field.name),
+                        outcome: FieldOutcome::Pass,
                     });
                 }
             }
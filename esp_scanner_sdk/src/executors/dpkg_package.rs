@@ -0,0 +1,296 @@
+//! dpkg package executor
+//!
+//! Validates Debian package installation status, versions and architectures.
+//!
+//! Versions are ordered as dpkg orders them: the epoch first, then the
+//! upstream version, then the Debian revision, where runs of digits compare
+//! numerically and `~` sorts before anything, even the end of the version,
+//! so `1.0~rc1` is older than `1.0`.
+
+use super::{
+    compare_field, existence_failure, field_result, match_value_list, validate_items, ItemNames,
+};
+use esp_scanner_base::execution::evr;
+use esp_scanner_base::strategies::{
+    CollectedData, CtnContract, CtnExecutionError, CtnExecutionResult, CtnExecutor,
+};
+use esp_scanner_base::types::common::{Operation, ResolvedValue};
+use esp_scanner_base::types::execution_context::ExecutableCriterion;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+/// Weight of a character in the non-digit part of a version, as in dpkg:
+/// `~` before the end, the end before letters, letters before other symbols
+fn char_order(c: Option<u8>) -> i32 {
+    match c {
+        None => 0,
+        Some(b'~') => -1,
+        Some(c) if c.is_ascii_digit() => 0,
+        Some(c) if c.is_ascii_alphabetic() => i32::from(c),
+        Some(c) => i32::from(c) + 256,
+    }
+}
+
+/// Compare two upstream versions or two revisions
+fn compare_version_part(a: &str, b: &str) -> Ordering {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() || j < b.len() {
+        // Non-digit run, character by character
+        while (i < a.len() && !a[i].is_ascii_digit()) || (j < b.len() && !b[j].is_ascii_digit()) {
+            let (ac, bc) = (char_order(a.get(i).copied()), char_order(b.get(j).copied()));
+            if ac != bc {
+                return ac.cmp(&bc);
+            }
+            i += 1;
+            j += 1;
+        }
+
+        // Digit run, numerically
+        while a.get(i) == Some(&b'0') {
+            i += 1;
+        }
+        while b.get(j) == Some(&b'0') {
+            j += 1;
+        }
+        let mut first_diff = Ordering::Equal;
+        while i < a.len() && a[i].is_ascii_digit() && j < b.len() && b[j].is_ascii_digit() {
+            if first_diff == Ordering::Equal {
+                first_diff = a[i].cmp(&b[j]);
+            }
+            i += 1;
+            j += 1;
+        }
+        if i < a.len() && a[i].is_ascii_digit() {
+            return Ordering::Greater;
+        }
+        if j < b.len() && b[j].is_ascii_digit() {
+            return Ordering::Less;
+        }
+        if first_diff != Ordering::Equal {
+            return first_diff;
+        }
+    }
+    Ordering::Equal
+}
+
+/// Split `[epoch:]upstream[-revision]` into its parts
+fn split_version(version: &str) -> (u64, &str, &str) {
+    let version = version.trim();
+    let (epoch, rest) = match version.split_once(':') {
+        Some((epoch, rest)) if epoch.bytes().all(|c| c.is_ascii_digit()) => {
+            (epoch.parse().unwrap_or(0), rest)
+        }
+        _ => (0, version),
+    };
+    match rest.rsplit_once('-') {
+        Some((upstream, revision)) => (epoch, upstream, revision),
+        None => (epoch, rest, ""),
+    }
+}
+
+/// Order two Debian package versions the way `dpkg --compare-versions` does
+pub fn compare_debian_versions(a: &str, b: &str) -> Ordering {
+    let (a_epoch, a_upstream, a_revision) = split_version(a);
+    let (b_epoch, b_upstream, b_revision) = split_version(b);
+    a_epoch
+        .cmp(&b_epoch)
+        .then_with(|| compare_version_part(a_upstream, b_upstream))
+        .then_with(|| compare_version_part(a_revision, b_revision))
+}
+
+/// Executor for dpkg_package validation
+pub struct DpkgPackageExecutor {
+    contract: CtnContract,
+}
+
+const NAMES: ItemNames = ItemNames {
+    item: "Package",
+    items: "packages",
+    subject: "dpkg package validation",
+};
+
+impl DpkgPackageExecutor {
+    pub fn new(contract: CtnContract) -> Self {
+        Self { contract }
+    }
+
+    /// Compare values, ordering `version` fields as dpkg does
+    fn compare_values(
+        &self,
+        expected: &ResolvedValue,
+        actual: &ResolvedValue,
+        operation: Operation,
+        field_is_version: bool,
+    ) -> bool {
+        if let Some(matched) = match_value_list(expected, actual, operation) {
+            return matched;
+        }
+
+        match (expected, actual, operation) {
+            // Boolean comparisons
+            (ResolvedValue::Boolean(exp), ResolvedValue::Boolean(act), Operation::Equals) => {
+                exp == act
+            }
+            (ResolvedValue::Boolean(exp), ResolvedValue::Boolean(act), Operation::NotEqual) => {
+                exp != act
+            }
+
+            // Versions compare in Debian order
+            (ResolvedValue::String(exp), ResolvedValue::String(act), _)
+                if field_is_version || operation.is_version_comparison() =>
+            {
+                evr::ordering_satisfies(compare_debian_versions(act, exp), operation)
+                    .unwrap_or(false)
+            }
+
+            (ResolvedValue::String(exp), ResolvedValue::String(act), Operation::Equals) => {
+                exp == act
+            }
+            (ResolvedValue::String(exp), ResolvedValue::String(act), Operation::NotEqual) => {
+                exp != act
+            }
+
+            _ => false,
+        }
+    }
+}
+
+impl CtnExecutor for DpkgPackageExecutor {
+    fn execute_with_contract(
+        &self,
+        criterion: &ExecutableCriterion,
+        collected_data: &HashMap<String, CollectedData>,
+        _contract: &CtnContract,
+    ) -> Result<CtnExecutionResult, CtnExecutionError> {
+        // Phase 1: Existence check
+        let objects_expected = criterion.expected_object_count();
+        if let Some(result) = existence_failure(
+            criterion,
+            NAMES.items,
+            collected_data.len(),
+            objects_expected,
+        ) {
+            return Ok(result);
+        }
+
+        // Phase 2: State validation
+        let results = validate_items(
+            criterion,
+            &self.contract,
+            &NAMES,
+            collected_data,
+            |data: &CollectedData, field, name| {
+                field_result(field, data.get_field(name).cloned(), |field, actual| {
+                    compare_field(field, actual, |expected| {
+                        self.compare_values(expected, actual, field.operation, name == "version")
+                    })
+                })
+            },
+        );
+
+        // Phase 3: Item check
+        Ok(results.into_result(criterion, &NAMES))
+    }
+
+    fn get_ctn_contract(&self) -> CtnContract {
+        self.contract.clone()
+    }
+
+    fn ctn_type(&self) -> &str {
+        "dpkg_package"
+    }
+
+    fn validate_collected_data(
+        &self,
+        collected_data: &HashMap<String, CollectedData>,
+        _contract: &CtnContract,
+    ) -> Result<(), CtnExecutionError> {
+        for data in collected_data.values() {
+            if !data.has_field("installed") {
+                return Err(CtnExecutionError::MissingDataField {
+                    field: "installed".to_string(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contracts::create_dpkg_package_contract;
+    use esp_scanner_base::strategies::ComplianceStatus;
+    use esp_scanner_base::testkit::{CriterionFixture, MockCollector};
+    use serde_json::json;
+
+    #[test]
+    fn test_debian_version_ordering() {
+        let older = [
+            ("1.0~rc1", "1.0"),
+            ("1.0~~", "1.0~"),
+            ("1.0~rc1-1", "1.0-1"),
+            ("1.0", "1.0a"),
+            ("1.0a", "1.0+"),
+            ("1.2", "1.10"),
+            ("9.9", "1:0.1"),
+            ("3.0.2-0ubuntu1", "3.0.2-0ubuntu1.10"),
+            ("3.0.2-0ubuntu1~22.04", "3.0.2-0ubuntu1"),
+        ];
+        for (a, b) in older {
+            assert_eq!(
+                compare_debian_versions(a, b),
+                Ordering::Less,
+                "{} < {}",
+                a,
+                b
+            );
+            assert_eq!(
+                compare_debian_versions(b, a),
+                Ordering::Greater,
+                "{} > {}",
+                b,
+                a
+            );
+        }
+        assert_eq!(compare_debian_versions("1.01", "1.1"), Ordering::Equal);
+        assert_eq!(compare_debian_versions("0:1.0", "1.0"), Ordering::Equal);
+        assert_eq!(compare_debian_versions("1.0", "1.0-0"), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_version_states_use_debian_order() {
+        let collector = MockCollector::new("dpkg_package").with_item(
+            "openssl",
+            json!({
+                "package_name": "openssl",
+                "installed": true,
+                "version": "3.0.2-0ubuntu1.10",
+                "architecture": "amd64"
+            }),
+        );
+        let executor = DpkgPackageExecutor::new(create_dpkg_package_contract());
+        let run = |states: &str| {
+            CriterionFixture::from_json(
+                r#"{"id": "openssl", "fields": {"package_name": "openssl"}}"#,
+                states,
+            )
+            .and_then(|fixture| fixture.run(&collector, &executor))
+            .unwrap()
+            .status
+        };
+
+        let patched = r#"[{"id": "patched", "fields": [
+            {"name": "installed", "type": "boolean", "op": "=", "value": true},
+            {"name": "version", "type": "string", "op": ">=", "value": "3.0.2-0ubuntu1.9"},
+            {"name": "architecture", "type": "string", "op": "=", "value": "amd64"}
+        ]}]"#;
+        assert_eq!(run(patched), ComplianceStatus::Pass);
+
+        let newer = r#"[{"id": "newer", "fields": [
+            {"name": "version", "type": "string", "op": ">", "value": "3.0.2-0ubuntu1.10~"}
+        ]}]"#;
+        assert_eq!(run(newer), ComplianceStatus::Pass);
+    }
+}
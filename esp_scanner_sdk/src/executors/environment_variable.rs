@@ -0,0 +1,161 @@
+//! Environment variable executor
+//!
+//! Validates where an environment variable is set, in environment files or
+//! in the environment of running processes.
+//!
+//! Every object expands into the files or processes that set its variable,
+//! each a separate item named `object_id[source]`, where the source is a
+//! file path or `pid N`. An object naming `LD_PRELOAD` with `TEST none ...`
+//! therefore passes only when nothing sets it, while
+//! `TEST at_least_one ...` requires the variable to be set somewhere.
+//!
+//! Processes whose environment the scanner cannot read are not items; the
+//! collected data carries a warning for them instead.
+
+use super::{existence_failure, field_matches, field_result, validate_items, ItemNames};
+use esp_scanner_base::execution::{collection_items, record_field, record_text};
+use esp_scanner_base::strategies::{
+    CollectedData, CtnContract, CtnExecutionError, CtnExecutionResult, CtnExecutor,
+};
+use esp_scanner_base::types::common::RecordData;
+use esp_scanner_base::types::execution_context::ExecutableCriterion;
+use std::collections::HashMap;
+
+pub struct EnvironmentExecutor {
+    contract: CtnContract,
+}
+
+const NAMES: ItemNames = ItemNames {
+    item: "Source",
+    items: "sources",
+    subject: "Environment variable validation",
+};
+
+impl EnvironmentExecutor {
+    pub fn new(contract: CtnContract) -> Self {
+        Self { contract }
+    }
+}
+
+impl CtnExecutor for EnvironmentExecutor {
+    fn execute_with_contract(
+        &self,
+        criterion: &ExecutableCriterion,
+        collected_data: &HashMap<String, CollectedData>,
+        _contract: &CtnContract,
+    ) -> Result<CtnExecutionResult, CtnExecutionError> {
+        let items = collection_items(collected_data, "variables", |_, record| {
+            record_text(record, "source")
+        });
+        let objects_expected = criterion
+            .expected_object_count()
+            .saturating_sub(collected_data.len())
+            + items.len();
+        if let Some(mut result) =
+            existence_failure(criterion, "sources", items.len(), objects_expected)
+        {
+            let sources: Vec<&str> = items.iter().map(|(item, _)| item.as_str()).collect();
+            result.details = serde_json::json!({ "sources": sources });
+            return Ok(result);
+        }
+
+        let results = validate_items(
+            criterion,
+            &self.contract,
+            &NAMES,
+            items.iter().map(|(item, record)| (item, *record)),
+            |record: &RecordData, field, name| {
+                field_result(field, record_field(record, name), field_matches)
+            },
+        );
+        Ok(results.into_result(criterion, &NAMES))
+    }
+
+    fn get_ctn_contract(&self) -> CtnContract {
+        self.contract.clone()
+    }
+
+    fn ctn_type(&self) -> &str {
+        "environment_variable"
+    }
+
+    fn validate_collected_data(
+        &self,
+        _collected_data: &HashMap<String, CollectedData>,
+        _contract: &CtnContract,
+    ) -> Result<(), CtnExecutionError> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collectors::CommandCollector;
+    use crate::contracts::create_environment_variable_contract;
+    use esp_scanner_base::strategies::{ComplianceStatus, SystemCommandExecutor};
+    use esp_scanner_base::testkit::{CriterionFixture, MockCollector};
+    use serde_json::json;
+
+    fn run(
+        collector: &dyn esp_scanner_base::strategies::CtnDataCollector,
+        object: &str,
+        states: &str,
+        test: &str,
+    ) -> CtnExecutionResult {
+        let executor = EnvironmentExecutor::new(create_environment_variable_contract());
+        CriterionFixture::from_json(object, states)
+            .and_then(|fixture| fixture.with_test(test))
+            .and_then(|fixture| fixture.run(collector, &executor))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_files_setting_a_variable_are_items() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("environment"), "PATH=\"/usr/bin\"\n").unwrap();
+        let profile = dir.path().join("profile.d");
+        std::fs::create_dir(&profile).unwrap();
+        std::fs::write(
+            profile.join("tmout.sh"),
+            "export TMOUT=900\nreadonly TMOUT\n",
+        )
+        .unwrap();
+        std::fs::write(profile.join("notes.txt"), "TMOUT=0\n").unwrap();
+        let object = format!(
+            r#"{{"id": "tmout", "fields": {{"variable_name": "TMOUT", "paths": "{},{}"}}}}"#,
+            dir.path().join("environment").display(),
+            profile.display()
+        );
+        let collector = CommandCollector::new("cmd", SystemCommandExecutor::new());
+        let states = r#"[{"id": "idle", "fields": [
+            {"name": "value", "type": "string", "op": "=", "value": "900"}
+        ]}]"#;
+
+        let result = run(&collector, &object, states, "all all");
+        assert_eq!(result.status, ComplianceStatus::Pass, "{}", result.message);
+        assert_eq!(result.state_results.len(), 1);
+        assert!(result.state_results[0].object_id.ends_with("tmout.sh]"));
+
+        let preload = object.replace("TMOUT", "LD_PRELOAD");
+        let result = run(&collector, &preload, "[]", "none all");
+        assert_eq!(result.status, ComplianceStatus::Pass);
+    }
+
+    #[test]
+    fn test_process_with_variable_set_fails_none() {
+        let sshd = MockCollector::new("environment_variable").with_item(
+            "preload",
+            json!({ "variables": [{
+                "variable_name": "LD_PRELOAD", "value": "/tmp/hook.so",
+                "source": "pid 812", "process_name": "sshd"
+            }]}),
+        );
+        let object = r#"{"id": "preload", "fields": {"variable_name": "LD_PRELOAD",
+            "source": "process", "process_name": "sshd"}}"#;
+
+        let result = run(&sshd, object, "[]", "none all");
+        assert_eq!(result.status, ComplianceStatus::Fail);
+        assert_eq!(result.details["sources"][0], "preload[pid 812]");
+    }
+}
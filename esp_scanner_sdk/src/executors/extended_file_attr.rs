@@ -0,0 +1,187 @@
+//! Extended file attribute executor
+//!
+//! Validates the POSIX ACLs and file capabilities that mode bits do not
+//! show, read from each file's extended attributes.
+//!
+//! Every object expands into the files its path selected, each a separate
+//! item named `object_id[path]`, so `has_extended_acl boolean = false` with
+//! `TEST all all` against `/usr/bin/*` requires that no file there carries
+//! an extended ACL. `acl_entries` and `capabilities` are lists, compared
+//! entry by entry under the field's entity check.
+//!
+//! A file whose attributes cannot be read is still an item: every field
+//! checked on it fails with the read error.
+
+use super::{
+    entries_match, existence_failure, field_result, field_unavailable, validate_items, ItemNames,
+};
+use esp_scanner_base::execution::{collection_items, record_field, record_text};
+use esp_scanner_base::strategies::{
+    CollectedData, CtnContract, CtnExecutionError, CtnExecutionResult, CtnExecutor,
+};
+use esp_scanner_base::types::common::RecordData;
+use esp_scanner_base::types::execution_context::ExecutableCriterion;
+use std::collections::HashMap;
+
+pub struct ExtendedFileAttrExecutor {
+    contract: CtnContract,
+}
+
+const NAMES: ItemNames = ItemNames {
+    item: "File",
+    items: "files",
+    subject: "Extended file attribute validation",
+};
+
+impl ExtendedFileAttrExecutor {
+    pub fn new(contract: CtnContract) -> Self {
+        Self { contract }
+    }
+}
+
+impl CtnExecutor for ExtendedFileAttrExecutor {
+    fn execute_with_contract(
+        &self,
+        criterion: &ExecutableCriterion,
+        collected_data: &HashMap<String, CollectedData>,
+        _contract: &CtnContract,
+    ) -> Result<CtnExecutionResult, CtnExecutionError> {
+        let items = collection_items(collected_data, "files", |_, record| {
+            record_text(record, "path")
+        });
+        let objects_expected = criterion
+            .expected_object_count()
+            .saturating_sub(collected_data.len())
+            + items.len();
+        if let Some(mut result) =
+            existence_failure(criterion, "files", items.len(), objects_expected)
+        {
+            let files: Vec<&str> = items.iter().map(|(item, _)| item.as_str()).collect();
+            result.details = serde_json::json!({ "files": files });
+            return Ok(result);
+        }
+
+        let results = validate_items(
+            criterion,
+            &self.contract,
+            &NAMES,
+            items.iter().map(|(item, record)| (item, *record)),
+            |record: &RecordData, field, name| {
+                let error = record_text(record, "error");
+                if !error.is_empty() {
+                    return field_unavailable(field, &error);
+                }
+                field_result(field, record_field(record, name), entries_match)
+            },
+        );
+        Ok(results.into_result(criterion, &NAMES))
+    }
+
+    fn get_ctn_contract(&self) -> CtnContract {
+        self.contract.clone()
+    }
+
+    fn ctn_type(&self) -> &str {
+        "extended_file_attr"
+    }
+
+    fn validate_collected_data(
+        &self,
+        _collected_data: &HashMap<String, CollectedData>,
+        _contract: &CtnContract,
+    ) -> Result<(), CtnExecutionError> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contracts::create_extended_file_attr_contract;
+    use esp_scanner_base::strategies::ComplianceStatus;
+    use esp_scanner_base::testkit::{CriterionFixture, MockCollector};
+    use serde_json::json;
+
+    fn files(files: serde_json::Value) -> MockCollector {
+        MockCollector::new("extended_file_attr").with_item("bin", json!({ "files": files }))
+    }
+
+    fn file(
+        path: &str,
+        acl_entries: serde_json::Value,
+        capabilities: &[&str],
+    ) -> serde_json::Value {
+        let extended = acl_entries
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|entry| entry.as_str().unwrap().starts_with("mask"));
+        json!({
+            "path": path, "error": "", "has_extended_acl": extended,
+            "acl_entries": acl_entries, "has_capabilities": !capabilities.is_empty(),
+            "capabilities": capabilities,
+        })
+    }
+
+    fn run(collector: &MockCollector, states: &str) -> CtnExecutionResult {
+        let executor = ExtendedFileAttrExecutor::new(create_extended_file_attr_contract());
+        CriterionFixture::from_json(r#"{"id": "bin", "fields": {"path": "/usr/bin/*"}}"#, states)
+            .and_then(|fixture| fixture.run(collector, &executor))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_extended_acl_fails_sweep() {
+        let states = r#"[{"id": "no_acls", "fields": [
+            {"name": "has_extended_acl", "type": "boolean", "op": "=", "value": false}
+        ]}]"#;
+        let plain = file("/usr/bin/ls", json!([]), &[]);
+        assert_eq!(
+            run(&files(json!([plain.clone()])), states).status,
+            ComplianceStatus::Pass
+        );
+
+        let shared = file(
+            "/usr/bin/tool",
+            json!([
+                "user::rwx",
+                "user:1000:rwx",
+                "group::r-x",
+                "mask::rwx",
+                "other::r-x"
+            ]),
+            &[],
+        );
+        let result = run(&files(json!([plain, shared])), states);
+        assert_eq!(result.status, ComplianceStatus::Fail);
+        assert!(
+            result.message.contains("bin[/usr/bin/tool]"),
+            "{}",
+            result.message
+        );
+    }
+
+    #[test]
+    fn test_capabilities_compared_per_entry() {
+        let ping = file("/usr/bin/ping", json!([]), &["cap_net_raw+ep"]);
+        let states = r#"[{"id": "approved", "fields": [
+            {"name": "capabilities", "type": "string", "op": "=", "value": "cap_net_raw+ep",
+             "entity_check": "all"}
+        ]}]"#;
+        assert_eq!(
+            run(&files(json!([ping.clone()])), states).status,
+            ComplianceStatus::Pass
+        );
+
+        let escalate = file("/usr/bin/python3", json!([]), &["cap_setuid+ep"]);
+        assert_eq!(
+            run(&files(json!([ping, escalate])), states).status,
+            ComplianceStatus::Fail
+        );
+
+        let unreadable = json!([{"path": "/usr/bin/ping", "error": "cannot read extended attributes: Permission denied"}]);
+        let result = run(&files(unreadable), states);
+        assert_eq!(result.status, ComplianceStatus::Fail);
+        assert!(result.message.contains("Permission denied"));
+    }
+}
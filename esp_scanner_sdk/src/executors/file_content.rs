@@ -1,13 +1,15 @@
 //! # File Content Executor
 //!
-//! Validates file content with string operations (contains, starts, ends, pattern_match).
+//! Validates file content with string operations (contains, starts, ends, pattern_match)
+//! and string measures (length_equals, length_greater_than, occurrence_count).
 
 use esp_scanner_base::execution::{
-    comparisons::string, evaluate_existence_check, evaluate_item_check, evaluate_state_operator,
+    comparisons::{collection, string, ComparisonExt},
+    evaluate_existence_check, evaluate_item_check, evaluate_state_operator,
 };
 use esp_scanner_base::strategies::{
     CollectedData, ComplianceStatus, CtnContract, CtnExecutionError, CtnExecutionResult,
-    CtnExecutor, FieldValidationResult, StateValidationResult, TestPhase,
+    CtnExecutor, FieldOutcome, FieldValidationResult, StateValidationResult, TestPhase,
 };
 use esp_scanner_base::types::common::{Operation, ResolvedValue};
 use esp_scanner_base::types::execution_context::ExecutableCriterion;
@@ -23,6 +25,15 @@ impl FileContentExecutor {
         Self { contract }
     }
 
+    /// Policy operand of a length or occurrence check, as written
+    fn format_measure_operand(value: &ResolvedValue) -> String {
+        match value {
+            ResolvedValue::Integer(n) => n.to_string(),
+            ResolvedValue::Collection(items) => collection::format_list(items),
+            other => format!("{:?}", other),
+        }
+    }
+
     /// Compare string operations using base comparison logic
     fn compare_string_operation(&self, expected: &str, actual: &str, operation: Operation) -> bool {
         // FIXED: Use the base string comparison module
@@ -97,8 +108,112 @@ impl CtnExecutor for FileContentExecutor {
             // Validate each state
             for state in &criterion.states {
                 for field in &state.fields {
-                    // For content validation, field.name should be "content"
+                    // Only "content" is collected; anything else was never
+                    // compared and must not pass by default
                     if field.name != "content" {
+                        let msg = format!("Field '{}' not present on file content", field.name);
+                        all_field_results.push(FieldValidationResult::not_present(
+                            field.name.clone(),
+                            field.value.clone(),
+                            field.operation,
+                            msg.clone(),
+                        ));
+                        failure_messages.push(format!("Object '{}': {}", object_id, msg));
+                        continue;
+                    }
+
+                    // Value list: the whole content must (not) be one of the allowed values
+                    if let (
+                        ResolvedValue::Collection(allowed),
+                        Operation::EqualsAny | Operation::NotEqualsAny,
+                    ) = (&field.value, field.operation)
+                    {
+                        let actual = ResolvedValue::String(content.clone());
+                        let passed = collection::matches_any(&actual, allowed, field.operation)
+                            .unwrap_or_else(|e| {
+                                eprintln!("Value list comparison error: {}", e);
+                                false
+                            });
+                        let allowed_list = collection::format_list(allowed);
+
+                        let msg = match (passed, field.operation) {
+                            (true, _) => format!(
+                                "Content check passed: {} {}",
+                                field.operation.as_str(),
+                                allowed_list
+                            ),
+                            (false, Operation::EqualsAny) => format!(
+                                "Content check failed: actual '{}' is not one of {}",
+                                self.preview_content(&content, 100),
+                                allowed_list
+                            ),
+                            (false, _) => format!(
+                                "Content check failed: actual '{}' is one of the disallowed values {}",
+                                self.preview_content(&content, 100),
+                                allowed_list
+                            ),
+                        };
+
+                        if !passed {
+                            failure_messages.push(format!("Object '{}': {}", object_id, msg));
+                        }
+
+                        all_field_results.push(FieldValidationResult {
+                            field_name: field.name.clone(),
+                            expected_value: field.value.clone(),
+                            actual_value: actual,
+                            operation: field.operation,
+                            passed,
+                            message: msg,
+                            outcome: FieldOutcome::from_passed(passed),
+                        });
+                        continue;
+                    }
+
+                    // Length and occurrence counts: integer or [pattern, count] operand
+                    if matches!(
+                        field.operation,
+                        Operation::LengthEquals
+                            | Operation::LengthGreaterThan
+                            | Operation::OccurrenceCount
+                    ) {
+                        let actual = ResolvedValue::String(content.clone());
+                        let (passed, msg) = match actual.compare_with(&field.value, field.operation)
+                        {
+                            Ok(true) => (
+                                true,
+                                format!(
+                                    "Content check passed: {} {}",
+                                    field.operation.as_str(),
+                                    Self::format_measure_operand(&field.value)
+                                ),
+                            ),
+                            Ok(false) => (
+                                false,
+                                format!(
+                                    "Content check failed: {} {} (length {}, content preview: {})",
+                                    field.operation.as_str(),
+                                    Self::format_measure_operand(&field.value),
+                                    content.chars().count(),
+                                    self.preview_content(&content, 100)
+                                ),
+                            ),
+                            Err(e) => (false, format!("Content check error: {}", e)),
+                        };
+
+                        if !passed {
+                            failure_messages.push(format!("Object '{}': {}", object_id, msg));
+                        }
+
+                        all_field_results.push(FieldValidationResult {
+                            field_name: field.name.clone(),
+                            expected_value: field.value.clone(),
+                            actual_value: actual,
+                            operation: field.operation,
+                            passed,
+                            message: msg,
+                            outcome: FieldOutcome::from_passed(passed),
+                        });
                         continue;
                     }
 
@@ -117,6 +232,7 @@ impl CtnExecutor for FileContentExecutor {
                                 operation: field.operation,
                                 passed: false,
                                 message: msg.clone(),
+                                outcome: FieldOutcome::Fail,
                             });
                             failure_messages.push(format!("Object '{}': {}", object_id, msg));
                             continue;
@@ -180,6 +296,7 @@ impl CtnExecutor for FileContentExecutor {
                         operation: field.operation,
                         passed,
                         message: msg,
+                        outcome: FieldOutcome::from_passed(passed),
                     });
                 }
             }
@@ -0,0 +1,178 @@
+//! File hash executor
+//!
+//! Validates file digests and sizes collected by streaming file content.
+//!
+//! Every object expands into the files its path selected, each a separate
+//! item named `object_id[path]`, so `TEST all all` against a glob requires
+//! every matching file to carry an approved digest.
+//!
+//! A file that cannot be read is still an item: every field checked on it
+//! fails with the read error. When an item fails, its actual SHA-256 digest
+//! is named in the failure message so the finding shows what is on disk.
+
+use super::{
+    existence_failure, field_matches, field_result, field_unavailable, validate_items, ItemNames,
+};
+use esp_scanner_base::execution::{collection_items, record_field, record_text};
+use esp_scanner_base::strategies::{
+    CollectedData, CtnContract, CtnExecutionError, CtnExecutionResult, CtnExecutor,
+};
+use esp_scanner_base::types::common::RecordData;
+use esp_scanner_base::types::execution_context::ExecutableCriterion;
+use std::collections::HashMap;
+
+pub struct FileHashExecutor {
+    contract: CtnContract,
+}
+
+const NAMES: ItemNames = ItemNames {
+    item: "File",
+    items: "files",
+    subject: "File hash validation",
+};
+
+impl FileHashExecutor {
+    pub fn new(contract: CtnContract) -> Self {
+        Self { contract }
+    }
+}
+
+impl CtnExecutor for FileHashExecutor {
+    fn execute_with_contract(
+        &self,
+        criterion: &ExecutableCriterion,
+        collected_data: &HashMap<String, CollectedData>,
+        _contract: &CtnContract,
+    ) -> Result<CtnExecutionResult, CtnExecutionError> {
+        let items = collection_items(collected_data, "files", |_, record| {
+            record_text(record, "path")
+        });
+        let objects_expected = criterion
+            .expected_object_count()
+            .saturating_sub(collected_data.len())
+            + items.len();
+        if let Some(mut result) =
+            existence_failure(criterion, "files", items.len(), objects_expected)
+        {
+            let files: Vec<&str> = items.iter().map(|(item, _)| item.as_str()).collect();
+            result.details = serde_json::json!({ "files": files });
+            return Ok(result);
+        }
+
+        let mut results = validate_items(
+            criterion,
+            &self.contract,
+            &NAMES,
+            items.iter().map(|(item, record)| (item, *record)),
+            |record: &RecordData, field, name| {
+                let error = record_text(record, "error");
+                if !error.is_empty() {
+                    return field_unavailable(field, &error);
+                }
+                field_result(field, record_field(record, name), field_matches)
+            },
+        );
+
+        // Name what is on disk for every file that failed
+        for (state, (object_id, record)) in results.state_results.iter().zip(&items) {
+            let sha256 = record_text(record, "sha256");
+            if !state.combined_result && !sha256.is_empty() {
+                results
+                    .failures
+                    .push(format!("File '{}': actual sha256 {}", object_id, sha256));
+            }
+        }
+        Ok(results.into_result(criterion, &NAMES))
+    }
+
+    fn get_ctn_contract(&self) -> CtnContract {
+        self.contract.clone()
+    }
+
+    fn ctn_type(&self) -> &str {
+        "file_hash"
+    }
+
+    fn validate_collected_data(
+        &self,
+        _collected_data: &HashMap<String, CollectedData>,
+        _contract: &CtnContract,
+    ) -> Result<(), CtnExecutionError> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contracts::create_file_hash_contract;
+    use esp_scanner_base::strategies::ComplianceStatus;
+    use esp_scanner_base::testkit::{CriterionFixture, MockCollector};
+    use serde_json::json;
+
+    const APPROVED: &str = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+    const TAMPERED: &str = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+
+    fn files(files: serde_json::Value) -> MockCollector {
+        MockCollector::new("file_hash").with_item("ssl", json!({ "files": files }))
+    }
+
+    fn file(path: &str, sha256: &str) -> serde_json::Value {
+        json!({"path": path, "error": "", "size": 3, "sha256": sha256, "sha512": "ddaf35a1"})
+    }
+
+    fn run(collector: &MockCollector, states: &str) -> CtnExecutionResult {
+        let executor = FileHashExecutor::new(create_file_hash_contract());
+        CriterionFixture::from_json(
+            r#"{"id": "ssl", "fields": {"path": "/etc/ssl/*.cnf"}}"#,
+            states,
+        )
+        .and_then(|fixture| fixture.run(collector, &executor))
+        .unwrap()
+    }
+
+    fn approved(op: &str, value: &str) -> String {
+        format!(
+            r#"[{{"id": "approved", "fields": [
+                {{"name": "sha256", "type": "string", "op": "{}", "value": "{}"}}
+            ]}}]"#,
+            op, value
+        )
+    }
+
+    #[test]
+    fn test_tampered_file_reports_actual_digest() {
+        let result = run(
+            &files(json!([file("/etc/ssl/openssl.cnf", APPROVED)])),
+            &approved("=", APPROVED),
+        );
+        assert_eq!(result.status, ComplianceStatus::Pass);
+
+        let result = run(
+            &files(json!([
+                file("/etc/ssl/openssl.cnf", APPROVED),
+                file("/etc/ssl/extra.cnf", TAMPERED)
+            ])),
+            &approved("=", APPROVED),
+        );
+        assert_eq!(result.status, ComplianceStatus::Fail);
+        assert!(result.message.contains(&format!(
+            "ssl[/etc/ssl/extra.cnf]': actual sha256 {}",
+            TAMPERED
+        )));
+    }
+
+    #[test]
+    fn test_uppercase_digest_and_unreadable_file() {
+        let result = run(
+            &files(json!([file("/etc/ssl/openssl.cnf", APPROVED)])),
+            &approved("ieq", &APPROVED.to_uppercase()),
+        );
+        assert_eq!(result.status, ComplianceStatus::Pass);
+
+        let unreadable = json!([{"path": "/etc/ssl/openssl.cnf", "error": "cannot read file: Permission denied"}]);
+        let result = run(&files(unreadable), &approved("=", APPROVED));
+        assert_eq!(result.status, ComplianceStatus::Fail);
+        assert!(result.message.contains("Permission denied"));
+    }
+}
@@ -2,14 +2,15 @@
 //!
 //! Validates file metadata (permissions, owner, group, size, existence).
 
+use super::{compare_field, match_value_list};
 use esp_scanner_base::execution::{
-    evaluate_existence_check, evaluate_item_check, evaluate_state_operator,
+    evaluate_existence_check, evaluate_item_check, evaluate_state_operator, mode,
 };
 use esp_scanner_base::strategies::{
     CollectedData, ComplianceStatus, CtnContract, CtnExecutionError, CtnExecutionResult,
-    CtnExecutor, FieldValidationResult, StateValidationResult, TestPhase,
+    CtnExecutor, FieldOutcome, FieldValidationResult, StateValidationResult, TestPhase,
 };
-use esp_scanner_base::types::common::{Operation, ResolvedValue};
+use esp_scanner_base::types::common::{DataType, Operation, ResolvedValue};
 use esp_scanner_base::types::execution_context::ExecutableCriterion;
 use std::collections::HashMap;
 
@@ -30,6 +31,10 @@ impl FileMetadataExecutor {
         actual: &ResolvedValue,
         operation: Operation,
     ) -> bool {
+        if let Some(matched) = match_value_list(expected, actual, operation) {
+            return matched;
+        }
+
         match (expected, actual, operation) {
             // String comparisons
             (ResolvedValue::String(exp), ResolvedValue::String(act), Operation::Equals) => {
@@ -121,8 +126,12 @@ impl CtnExecutor for FileMetadataExecutor {
         // Phase 2: State Validation
         let mut state_results = Vec::new();
         let mut failure_messages = Vec::new();
+        let mut advisories = Vec::new();
 
         for (object_id, data) in collected_data {
+            if let Some(note) = world_writable_note(data) {
+                advisories.push((object_id.clone(), note));
+            }
             let mut all_field_results = Vec::new();
 
             // Validate each state
@@ -137,28 +146,42 @@ impl CtnExecutor for FileMetadataExecutor {
                         .cloned()
                         .unwrap_or_else(|| field.name.clone());
 
+                    // Octal fields compare mode bits and report both sides in octal
+                    let octal = field.data_type == DataType::Octal;
+                    let expected_value = if octal {
+                        mode::render(&field.value)
+                    } else {
+                        field.value.clone()
+                    };
+
                     let actual_value = match data.get_field(&data_field_name) {
+                        Some(v) if octal => mode::render(v),
                         Some(v) => v.clone(),
                         None => {
                             let msg = format!(
                                 "Field '{}' (mapped to '{}') not collected",
                                 field.name, data_field_name
                             );
-                            all_field_results.push(FieldValidationResult {
-                                field_name: field.name.clone(),
-                                expected_value: field.value.clone(),
-                                actual_value: ResolvedValue::String("".to_string()),
-                                operation: field.operation,
-                                passed: false,
-                                message: msg.clone(),
-                            });
+                            all_field_results.push(FieldValidationResult::not_present(
+                                field.name.clone(),
+                                expected_value,
+                                field.operation,
+                                msg.clone(),
+                            ));
                             failure_messages.push(format!("Object '{}': {}", object_id, msg));
                             continue;
                         }
                     };
 
                     // Perform comparison
-                    let passed = self.compare_values(&field.value, &actual_value, field.operation);
+                    let passed = if octal {
+                        mode::compare_values(&actual_value, &field.value, field.operation)
+                            .unwrap_or(false)
+                    } else {
+                        compare_field(field, &actual_value, |expected| {
+                            self.compare_values(expected, &actual_value, field.operation)
+                        })
+                    };
 
                     let msg = if passed {
                         format!(
@@ -166,15 +189,15 @@ impl CtnExecutor for FileMetadataExecutor {
                             field.name,
                             self.format_value(&actual_value),
                             field.operation,
-                            self.format_value(&field.value)
+                            self.format_value(&expected_value)
                         )
                     } else {
                         format!(
                             "Field '{}' failed: expected {} {:?} {}, got {}",
                             field.name,
-                            self.format_value(&field.value),
+                            self.format_value(&expected_value),
                             field.operation,
-                            self.format_value(&field.value),
+                            self.format_value(&expected_value),
                             self.format_value(&actual_value)
                         )
                     };
@@ -185,11 +208,12 @@ impl CtnExecutor for FileMetadataExecutor {
 
                     all_field_results.push(FieldValidationResult {
                         field_name: field.name.clone(),
-                        expected_value: field.value.clone(),
+                        expected_value,
                         actual_value,
                         operation: field.operation,
                         passed,
                         message: msg,
+                        outcome: FieldOutcome::from_passed(passed),
                     });
                 }
             }
@@ -245,7 +269,7 @@ impl CtnExecutor for FileMetadataExecutor {
             )
         };
 
-        Ok(CtnExecutionResult {
+        let mut result = CtnExecutionResult {
             ctn_type: criterion.criterion_type.clone(),
             status: final_status,
             test_phase: TestPhase::Complete,
@@ -265,7 +289,11 @@ impl CtnExecutor for FileMetadataExecutor {
                 }
             }),
             execution_metadata: Default::default(),
-        })
+        };
+        for (object_id, note) in advisories {
+            result.add_advisory(object_id, note);
+        }
+        Ok(result)
     }
 
     fn get_ctn_contract(&self) -> CtnContract {
@@ -299,3 +327,93 @@ impl CtnExecutor for FileMetadataExecutor {
         Ok(())
     }
 }
+
+/// Advisory for a world-writable file, whatever the states check
+fn world_writable_note(data: &CollectedData) -> Option<String> {
+    let Some(ResolvedValue::String(mode)) = data.get_field("file_mode") else {
+        return None;
+    };
+    let bits = u32::from_str_radix(mode, 8).ok()?;
+    (bits & 0o002 != 0).then(|| format!("File is world-writable (mode {})", mode))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contracts::create_file_metadata_contract;
+    use esp_scanner_base::testkit::{CriterionFixture, MockCollector};
+    use serde_json::json;
+
+    const SHADOW: &str = r#"{"id": "shadow", "fields": {"path": "/etc/shadow"}}"#;
+
+    fn run(file_mode: &str, states: &str) -> CtnExecutionResult {
+        let collector = MockCollector::new("file_metadata")
+            .with_item("shadow", json!({"file_mode": file_mode, "exists": true}));
+        let executor = FileMetadataExecutor::new(create_file_metadata_contract());
+        CriterionFixture::from_json(SHADOW, states)
+            .and_then(|fixture| fixture.run(&collector, &executor))
+            .unwrap()
+    }
+
+    fn at_most(mode: &str) -> String {
+        format!(
+            r#"[{{"id": "mode", "fields": [
+                {{"name": "permissions", "type": "octal", "op": "permissions_at_most", "value": "{}"}}
+            ]}}]"#,
+            mode
+        )
+    }
+
+    #[test]
+    fn test_permission_masks_and_octal_evidence() {
+        assert_eq!(run("0600", &at_most("0640")).status, ComplianceStatus::Pass);
+        assert_eq!(run("0640", &at_most("0640")).status, ComplianceStatus::Pass);
+
+        // setuid is a bit outside 0640, whatever the rest of the mode
+        let result = run("4640", &at_most("0640"));
+        assert_eq!(result.status, ComplianceStatus::Fail);
+        let field = &result.state_results[0].state_results[0];
+        assert_eq!(
+            field.expected_value,
+            ResolvedValue::String("0640".to_string())
+        );
+        assert_eq!(
+            field.actual_value,
+            ResolvedValue::String("4640".to_string())
+        );
+        assert!(field.message.contains("'0640'"), "{}", field.message);
+
+        let sticky = r#"[{"id": "tmp", "fields": [
+            {"name": "permissions", "type": "octal", "op": "permissions_at_least", "value": "1000"}
+        ]}]"#;
+        assert_eq!(run("1777", sticky).status, ComplianceStatus::Pass);
+        assert_eq!(run("0777", sticky).status, ComplianceStatus::Fail);
+
+        // Legacy string states still compare the collected text
+        let legacy = r#"[{"id": "mode", "fields": [
+            {"name": "permissions", "type": "string", "op": "=", "value": "0640"}
+        ]}]"#;
+        assert_eq!(run("0640", legacy).status, ComplianceStatus::Pass);
+    }
+
+    #[test]
+    fn test_modification_time_compares_as_datetime() {
+        let executor = FileMetadataExecutor::new(create_file_metadata_contract());
+        let run = |mtime: i64| {
+            let collector = MockCollector::new("file_metadata")
+                .with_item("shadow", json!({"file_mtime": mtime, "exists": true}));
+            CriterionFixture::from_json(
+                SHADOW,
+                r#"[{"id": "stale", "fields": [
+                    {"name": "modified", "type": "datetime", "op": "<", "value": "now-30d"}
+                ]}]"#,
+            )
+            .and_then(|fixture| fixture.run(&collector, &executor))
+            .unwrap()
+        };
+
+        let now = chrono::Utc::now().timestamp();
+        assert_eq!(run(now - 90 * 86_400).status, ComplianceStatus::Pass);
+        assert_eq!(run(now - 86_400).status, ComplianceStatus::Fail);
+    }
+}
@@ -0,0 +1,172 @@
+//! File permission scan executor
+//!
+//! Validates the files a permission scan found below an object's root:
+//! set-user-ID and set-group-ID files, and world-writable files and
+//! directories.
+//!
+//! Every object expands into the files its walk selected, each a separate
+//! item named `object_id[path]`. `TEST none ...` therefore requires that no
+//! such file exists, and `TEST any all` with an approved `path` list requires
+//! every one found to be approved.
+//!
+//! Directories the walk could not read, or left out because of a depth or
+//! entry limit, are listed under `warnings` in the result details and in the
+//! message, so a partial scan is visible in the finding.
+
+use super::{existence_failure, field_matches, field_result, validate_items, ItemNames};
+use esp_scanner_base::execution::{collection_items, engine, record_field, record_text};
+use esp_scanner_base::strategies::{
+    CollectedData, CtnContract, CtnExecutionError, CtnExecutionResult, CtnExecutor,
+};
+use esp_scanner_base::types::common::RecordData;
+use esp_scanner_base::types::execution_context::ExecutableCriterion;
+use std::collections::HashMap;
+
+pub struct FilePermissionScanExecutor {
+    contract: CtnContract,
+}
+
+const NAMES: ItemNames = ItemNames {
+    item: "File",
+    items: "files",
+    subject: "File permission scan",
+};
+
+impl FilePermissionScanExecutor {
+    pub fn new(contract: CtnContract) -> Self {
+        Self { contract }
+    }
+}
+
+impl CtnExecutor for FilePermissionScanExecutor {
+    fn execute_with_contract(
+        &self,
+        criterion: &ExecutableCriterion,
+        collected_data: &HashMap<String, CollectedData>,
+        _contract: &CtnContract,
+    ) -> Result<CtnExecutionResult, CtnExecutionError> {
+        let items = collection_items(collected_data, "files", |_, record| {
+            record_text(record, "path")
+        });
+        let objects_expected = criterion
+            .expected_object_count()
+            .saturating_sub(collected_data.len())
+            + items.len();
+        if let Some(mut result) =
+            existence_failure(criterion, "files", items.len(), objects_expected)
+        {
+            let files: Vec<&str> = items.iter().map(|(item, _)| item.as_str()).collect();
+            result.details = serde_json::json!({ "files": files });
+            return Ok(result);
+        }
+
+        let results = validate_items(
+            criterion,
+            &self.contract,
+            &NAMES,
+            items.iter().map(|(item, record)| (item, *record)),
+            |record: &RecordData, field, name| {
+                field_result(field, record_field(record, name), field_matches)
+            },
+        );
+        let warnings = engine::collection_warnings(collected_data);
+
+        let mut result = results.into_result(criterion, &NAMES);
+        if !warnings.is_empty() {
+            result.message.push_str(&format!(
+                "\n  Scan incomplete:\n  - {}",
+                warnings.join("\n  - ")
+            ));
+        }
+        result.details["warnings"] = serde_json::json!(warnings);
+        Ok(result)
+    }
+
+    fn get_ctn_contract(&self) -> CtnContract {
+        self.contract.clone()
+    }
+
+    fn ctn_type(&self) -> &str {
+        "file_permission_scan"
+    }
+
+    fn validate_collected_data(
+        &self,
+        _collected_data: &HashMap<String, CollectedData>,
+        _contract: &CtnContract,
+    ) -> Result<(), CtnExecutionError> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contracts::create_file_permission_scan_contract;
+    use esp_scanner_base::strategies::ComplianceStatus;
+    use esp_scanner_base::testkit::{CriterionFixture, MockCollector};
+    use serde_json::json;
+
+    fn suid(path: &str) -> serde_json::Value {
+        json!({
+            "path": path, "type": "file", "mode": "4755", "suid": true, "sgid": false,
+            "sticky": false, "world_writable": false, "uid": 0, "gid": 0
+        })
+    }
+
+    fn scan(files: serde_json::Value) -> MockCollector {
+        MockCollector::new("file_permission_scan").with_item(
+            "usr",
+            json!({ "files": files, "entries_scanned": 1200, "truncated": false }),
+        )
+    }
+
+    fn run(collector: &MockCollector, states: &str, test: &str) -> CtnExecutionResult {
+        let executor = FilePermissionScanExecutor::new(create_file_permission_scan_contract());
+        CriterionFixture::from_json(r#"{"id": "usr", "fields": {"path": "/usr"}}"#, states)
+            .and_then(|fixture| fixture.with_test(test))
+            .and_then(|fixture| fixture.run(collector, &executor))
+            .unwrap()
+    }
+
+    const LOCAL: &str = r#"[{"id": "local", "fields": [
+        {"name": "path", "type": "string", "op": "starts", "value": "/usr/local/"}
+    ]}]"#;
+
+    #[test]
+    fn test_no_suid_file_under_usr_local() {
+        let files = scan(json!([suid("/usr/bin/passwd"), suid("/usr/bin/su")]));
+        assert_eq!(
+            run(&files, LOCAL, "any none_satisfy").status,
+            ComplianceStatus::Pass
+        );
+
+        let files = scan(json!([
+            suid("/usr/bin/passwd"),
+            suid("/usr/local/bin/helper")
+        ]));
+        let result = run(&files, LOCAL, "any none_satisfy");
+        assert_eq!(result.status, ComplianceStatus::Fail);
+
+        assert_eq!(
+            run(&scan(json!([])), LOCAL, "none none_satisfy").status,
+            ComplianceStatus::Pass
+        );
+    }
+
+    #[test]
+    fn test_unreadable_directories_are_reported() {
+        let collector = MockCollector::new("file_permission_scan").with_warned_item(
+            "usr",
+            json!({ "files": [suid("/usr/bin/passwd")], "entries_scanned": 40, "truncated": false }),
+            "directory not scanned: /usr/private: denied",
+        );
+        let approved = r#"[{"id": "approved", "fields": [
+            {"name": "path", "type": "string", "op": "=", "value": "/usr/bin/passwd"}
+        ]}]"#;
+        let result = run(&collector, approved, "any all");
+        assert_eq!(result.status, ComplianceStatus::Pass);
+        assert!(result.message.contains("/usr/private: denied"));
+        assert_eq!(result.details["warnings"].as_array().unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,181 @@
+//! firewalld zone executor
+//!
+//! Validates firewalld zones collected with `firewall-cmd`.
+//!
+//! Every object expands into the zones its `zone` matched, each a separate
+//! item named `object_id[zone]`. While firewalld is not running no zones
+//! exist, so a policy can require that state with `TEST none ...`; other
+//! existence checks fail with a message saying firewalld is not running.
+//!
+//! List fields (`services`, `ports`, `interfaces`, ...) compare each entry and
+//! combine the results with the field's entity check, `all` by default. An
+//! empty list satisfies `all` and `none` but not `at_least_one` or
+//! `only_one`, so `services string = \`telnet\` none` passes for a zone
+//! without services.
+
+use super::{entries_match, existence_failure, field_result, validate_items, ItemNames};
+use esp_scanner_base::execution::{collection_items, record_field, record_text};
+use esp_scanner_base::strategies::{
+    CollectedData, ComplianceStatus, CtnContract, CtnExecutionError, CtnExecutionResult,
+    CtnExecutor,
+};
+use esp_scanner_base::types::common::{RecordData, ResolvedValue};
+use esp_scanner_base::types::execution_context::ExecutableCriterion;
+use std::collections::HashMap;
+
+pub struct FirewalldZoneExecutor {
+    contract: CtnContract,
+}
+
+const NAMES: ItemNames = ItemNames {
+    item: "Zone",
+    items: "zones",
+    subject: "firewalld validation",
+};
+
+impl FirewalldZoneExecutor {
+    pub fn new(contract: CtnContract) -> Self {
+        Self { contract }
+    }
+}
+
+impl CtnExecutor for FirewalldZoneExecutor {
+    fn execute_with_contract(
+        &self,
+        criterion: &ExecutableCriterion,
+        collected_data: &HashMap<String, CollectedData>,
+        _contract: &CtnContract,
+    ) -> Result<CtnExecutionResult, CtnExecutionError> {
+        let running = !collected_data.values().any(|data| {
+            matches!(
+                data.get_field("running"),
+                Some(ResolvedValue::Boolean(false))
+            )
+        });
+
+        let items = collection_items(collected_data, "zones", |_, record| {
+            record_text(record, "zone")
+        });
+        let objects_expected = criterion
+            .expected_object_count()
+            .saturating_sub(collected_data.len())
+            + items.len();
+        if let Some(mut result) =
+            existence_failure(criterion, "zones", items.len(), objects_expected)
+        {
+            if !running {
+                result.message = "Existence check failed: firewalld is not running".to_string();
+            }
+            result.details = serde_json::json!({ "firewalld_running": running });
+            return Ok(result);
+        }
+
+        let results = validate_items(
+            criterion,
+            &self.contract,
+            &NAMES,
+            items.iter().map(|(item, record)| (item, *record)),
+            |record: &RecordData, field, name| {
+                field_result(field, record_field(record, name), entries_match)
+            },
+        );
+        let mut result = results.into_result(criterion, &NAMES);
+        if result.status == ComplianceStatus::Pass && !running {
+            result.message = "firewalld validation passed: firewalld is not running".to_string();
+        }
+        result.details["firewalld_running"] = serde_json::json!(running);
+        Ok(result)
+    }
+
+    fn get_ctn_contract(&self) -> CtnContract {
+        self.contract.clone()
+    }
+
+    fn ctn_type(&self) -> &str {
+        "firewalld_zone"
+    }
+
+    fn validate_collected_data(
+        &self,
+        _collected_data: &HashMap<String, CollectedData>,
+        _contract: &CtnContract,
+    ) -> Result<(), CtnExecutionError> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contracts::create_firewalld_zone_contract;
+    use esp_scanner_base::testkit::{CriterionFixture, MockCollector};
+    use serde_json::json;
+
+    const PUBLIC: &str = r#"{"id": "public", "fields": {"zone": "public"}}"#;
+
+    const NO_TELNET: &str = r#"[{"id": "no_telnet", "fields": [
+        {"name": "services", "type": "string", "op": "=", "value": "telnet",
+         "entity_check": "none"}
+    ]}]"#;
+
+    fn zone(services: serde_json::Value) -> MockCollector {
+        MockCollector::new("firewalld_zone").with_item(
+            "public",
+            json!({
+                "zone": "public",
+                "running": true,
+                "zones": [{
+                    "zone": "public", "default_zone": "drop", "default": false,
+                    "active": true, "target": "default", "services": services,
+                    "ports": [], "masquerade": false
+                }]
+            }),
+        )
+    }
+
+    fn run(collector: &MockCollector, states: &str, test: &str) -> CtnExecutionResult {
+        let executor = FirewalldZoneExecutor::new(create_firewalld_zone_contract());
+        CriterionFixture::from_json(PUBLIC, states)
+            .and_then(|fixture| fixture.with_test(test))
+            .and_then(|fixture| fixture.run(collector, &executor))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_services_entity_check_none() {
+        let result = run(&zone(json!(["ssh", "cockpit"])), NO_TELNET, "all all");
+        assert_eq!(result.status, ComplianceStatus::Pass);
+
+        let result = run(&zone(json!(["ssh", "telnet"])), NO_TELNET, "all all");
+        assert_eq!(result.status, ComplianceStatus::Fail);
+
+        let result = run(&zone(json!([])), NO_TELNET, "all all");
+        assert_eq!(result.status, ComplianceStatus::Pass);
+    }
+
+    #[test]
+    fn test_default_zone_and_masquerade() {
+        let states = r#"[{"id": "locked_down", "fields": [
+            {"name": "default_zone", "type": "string", "op": "=", "value": "drop"},
+            {"name": "masquerade", "type": "boolean", "op": "=", "value": false}
+        ]}]"#;
+        let result = run(&zone(json!(["ssh"])), states, "all all");
+        assert_eq!(result.status, ComplianceStatus::Pass);
+    }
+
+    #[test]
+    fn test_not_running_has_no_zones() {
+        let stopped = MockCollector::new("firewalld_zone").with_item(
+            "public",
+            json!({"zone": "public", "running": false, "zones": []}),
+        );
+
+        let result = run(&stopped, NO_TELNET, "none all");
+        assert_eq!(result.status, ComplianceStatus::Pass);
+        assert_eq!(result.details["firewalld_running"], false);
+
+        let result = run(&stopped, NO_TELNET, "all all");
+        assert_eq!(result.status, ComplianceStatus::Fail);
+        assert!(result.message.contains("firewalld is not running"));
+    }
+}
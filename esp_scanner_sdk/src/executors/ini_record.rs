@@ -0,0 +1,103 @@
+//! INI record executor
+//!
+//! Validates INI-style files such as `pwquality.conf`, systemd units and yum
+//! repositories using record checks. A key is addressed as `section.key`, or
+//! by its bare name before the first section; state syntax is the same as for
+//! `json_record`.
+
+use super::json_record::{execute_record_checks, validate_record_data};
+use crate::collectors::record_formats::RecordFormat;
+use esp_scanner_base::strategies::{
+    CollectedData, CtnContract, CtnExecutionError, CtnExecutionResult, CtnExecutor,
+};
+use esp_scanner_base::types::execution_context::ExecutableCriterion;
+use std::collections::HashMap;
+
+pub struct IniRecordExecutor {
+    contract: CtnContract,
+}
+
+impl IniRecordExecutor {
+    pub fn new(contract: CtnContract) -> Self {
+        Self { contract }
+    }
+}
+
+impl CtnExecutor for IniRecordExecutor {
+    fn execute_with_contract(
+        &self,
+        criterion: &ExecutableCriterion,
+        collected_data: &HashMap<String, CollectedData>,
+        _contract: &CtnContract,
+    ) -> Result<CtnExecutionResult, CtnExecutionError> {
+        execute_record_checks(&self.contract, RecordFormat::Ini, criterion, collected_data)
+    }
+
+    fn get_ctn_contract(&self) -> CtnContract {
+        self.contract.clone()
+    }
+
+    fn ctn_type(&self) -> &str {
+        "ini_record"
+    }
+
+    fn validate_collected_data(
+        &self,
+        collected_data: &HashMap<String, CollectedData>,
+        _contract: &CtnContract,
+    ) -> Result<(), CtnExecutionError> {
+        validate_record_data(RecordFormat::Ini, collected_data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collectors::FileSystemCollector;
+    use crate::contracts::create_ini_record_contract;
+    use esp_scanner_base::strategies::ComplianceStatus;
+    use esp_scanner_base::testkit::CriterionFixture;
+
+    fn run(object: &str, states: &str) -> CtnExecutionResult {
+        let executor = IniRecordExecutor::new(create_ini_record_contract());
+        CriterionFixture::from_json(object, states)
+            .and_then(|fixture| fixture.with_test("all all"))
+            .and_then(|fixture| fixture.run(&FileSystemCollector::new(), &executor))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_default_section_and_repeated_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("pwquality.conf");
+        std::fs::write(
+            &path,
+            "minlen = 14\n\n[repo]\ngpgcheck = 1\ngpgcheck = 0\n; gpgcheck = 0\n",
+        )
+        .unwrap();
+        let path = path.display().to_string();
+
+        let last_wins = format!(r#"{{"id": "pw", "fields": {{"path": "{}"}}}}"#, path);
+        let states = r#"[{"id": "strong", "records": [
+            {"name": "minlen", "type": "int", "op": ">=", "value": 14},
+            {"name": "repo.gpgcheck", "type": "int", "op": "=", "value": 0}
+        ]}]"#;
+        assert_eq!(run(&last_wins, states).status, ComplianceStatus::Pass);
+
+        let listed = format!(
+            r##"{{"id": "pw", "fields": {{"path": "{}", "comment_chars": ";", "duplicate_keys": "list"}}}}"##,
+            path
+        );
+        let every_gpgcheck = r#"[{"id": "signed", "records": [
+            {"name": "repo.gpgcheck.*", "type": "int", "op": "=", "value": 1,
+             "entity_check": "all"}
+        ]}]"#;
+        let result = run(&listed, every_gpgcheck);
+        assert_eq!(result.status, ComplianceStatus::Fail);
+        assert!(
+            result.message.contains("1 of 2 items matched"),
+            "{}",
+            result.message
+        );
+    }
+}
@@ -1,13 +1,17 @@
 //! JSON record executor
 //!
-//! Validates structured JSON data using record checks.
+//! Validates structured JSON data using record checks. The other record
+//! executors and `systemd_unit_property` share this evaluation; only the
+//! collected field and the wording of the result differ.
 
+use crate::collectors::record_formats::RecordFormat;
 use esp_scanner_base::execution::{
-    evaluate_existence_check, evaluate_item_check, record_validation::validate_record_checks,
+    evaluate_existence_check, evaluate_item_check, extract_behavior_hints,
+    record_validation::{validate_record_checks_with, RecordValidationOptions},
 };
 use esp_scanner_base::strategies::{
     CollectedData, ComplianceStatus, CtnContract, CtnExecutionError, CtnExecutionResult,
-    CtnExecutor, FieldValidationResult, StateValidationResult, TestPhase,
+    CtnExecutor, FieldOutcome, FieldValidationResult, StateValidationResult, TestPhase,
 };
 use esp_scanner_base::types::common::{Operation, ResolvedValue};
 use esp_scanner_base::types::execution_context::ExecutableCriterion;
@@ -30,142 +34,12 @@ impl CtnExecutor for JsonRecordExecutor {
         collected_data: &HashMap<String, CollectedData>,
         _contract: &CtnContract,
     ) -> Result<CtnExecutionResult, CtnExecutionError> {
-        let test_spec = &criterion.test;
-
-        // Phase 1: Existence check
-        let objects_expected = criterion.expected_object_count();
-        let objects_found = collected_data.len();
-
-        let existence_passed =
-            evaluate_existence_check(test_spec.existence_check, objects_found, objects_expected);
-
-        if !existence_passed {
-            return Ok(CtnExecutionResult::fail(
-                criterion.criterion_type.clone(),
-                format!(
-                    "Existence check failed: expected {} objects, found {}",
-                    objects_expected, objects_found
-                ),
-            ));
-        }
-
-        // Phase 2: State validation with record checks
-        let mut state_results = Vec::new();
-        let mut failure_messages = Vec::new();
-
-        for (object_id, data) in collected_data {
-            // Extract RecordData from collected data
-            let record_data = match data.get_field("json_data") {
-                Some(ResolvedValue::RecordData(rd)) => rd,
-                Some(_) => {
-                    return Err(CtnExecutionError::DataValidationFailed {
-                        reason: "json_data field is not RecordData".to_string(),
-                    });
-                }
-                None => {
-                    return Err(CtnExecutionError::MissingDataField {
-                        field: "json_data".to_string(),
-                    });
-                }
-            };
-
-            // Validate all states for this object
-            for state in &criterion.states {
-                // Validate record checks if present
-                if !state.record_checks.is_empty() {
-                    // FIXED: Use validate_record_checks from base library
-                    let validation_results =
-                        validate_record_checks(record_data, &state.record_checks).map_err(|e| {
-                            CtnExecutionError::ExecutionFailed {
-                                ctn_type: criterion.criterion_type.clone(),
-                                reason: format!("Record validation failed: {}", e),
-                            }
-                        })?;
-
-                    // Convert to FieldValidationResult format
-                    let field_results: Vec<FieldValidationResult> = validation_results
-                        .iter()
-                        .map(|r| FieldValidationResult {
-                            field_name: r.field_path.clone(),
-                            expected_value: ResolvedValue::String(
-                                r.expected.clone().unwrap_or_default(),
-                            ),
-                            actual_value: ResolvedValue::String(
-                                r.actual.clone().unwrap_or_default(),
-                            ),
-                            operation: Operation::Equals,
-                            passed: r.passed,
-                            message: r.message.clone(),
-                        })
-                        .collect();
-
-                    // Check if all validations passed
-                    let all_passed = validation_results.iter().all(|r| r.passed);
-
-                    if !all_passed {
-                        for result in &validation_results {
-                            if !result.passed {
-                                failure_messages
-                                    .push(format!("Object '{}': {}", object_id, result.message));
-                            }
-                        }
-                    }
-
-                    state_results.push(StateValidationResult {
-                        object_id: object_id.clone(),
-                        state_results: field_results,
-                        combined_result: all_passed,
-                        state_operator: test_spec.state_operator,
-                        message: format!(
-                            "Object '{}': {} ({} of {} checks passed)",
-                            object_id,
-                            if all_passed { "passed" } else { "failed" },
-                            validation_results.iter().filter(|r| r.passed).count(),
-                            validation_results.len()
-                        ),
-                    });
-                }
-            }
-        }
-
-        // Phase 3: Item check
-        let objects_passing = state_results.iter().filter(|r| r.combined_result).count();
-        let item_passed =
-            evaluate_item_check(test_spec.item_check, objects_passing, state_results.len());
-
-        let final_status = if existence_passed && item_passed {
-            ComplianceStatus::Pass
-        } else {
-            ComplianceStatus::Fail
-        };
-
-        let message = if final_status == ComplianceStatus::Pass {
-            format!(
-                "JSON record validation passed: {} of {} objects compliant",
-                objects_passing,
-                state_results.len()
-            )
-        } else {
-            format!(
-                "JSON record validation failed:\n  - {}",
-                failure_messages.join("\n  - ")
-            )
-        };
-
-        Ok(CtnExecutionResult {
-            ctn_type: criterion.criterion_type.clone(),
-            status: final_status,
-            test_phase: TestPhase::Complete,
-            existence_result: None,
-            state_results,
-            item_check_result: None,
-            message,
-            details: serde_json::json!({
-                "failures": failure_messages,
-                "objects_passing": objects_passing,
-            }),
-            execution_metadata: Default::default(),
-        })
+        execute_record_checks(
+            &self.contract,
+            RecordFormat::Json,
+            criterion,
+            collected_data,
+        )
     }
 
     fn get_ctn_contract(&self) -> CtnContract {
@@ -181,13 +55,222 @@ impl CtnExecutor for JsonRecordExecutor {
         collected_data: &HashMap<String, CollectedData>,
         _contract: &CtnContract,
     ) -> Result<(), CtnExecutionError> {
-        for data in collected_data.values() {
-            if !data.has_field("json_data") {
+        validate_record_data(RecordFormat::Json, collected_data)
+    }
+}
+
+/// Record comparison options from the object's behaviors
+fn record_options(
+    contract: &CtnContract,
+    criterion: &ExecutableCriterion,
+    object_id: &str,
+) -> Result<RecordValidationOptions, CtnExecutionError> {
+    let Some(object) = criterion.objects.iter().find(|o| o.identifier == object_id) else {
+        return Ok(RecordValidationOptions::new());
+    };
+    let behaviors = contract
+        .resolve_behaviors(&extract_behavior_hints(object))
+        .map_err(|e| CtnExecutionError::ExecutionFailed {
+            ctn_type: criterion.criterion_type.clone(),
+            reason: e.to_string(),
+        })?;
+    Ok(RecordValidationOptions::new().with_float_epsilon(behaviors.float("float_epsilon")))
+}
+
+/// Evaluate a criterion's record checks against records parsed from `format`
+pub(crate) fn execute_record_checks(
+    contract: &CtnContract,
+    format: RecordFormat,
+    criterion: &ExecutableCriterion,
+    collected_data: &HashMap<String, CollectedData>,
+) -> Result<CtnExecutionResult, CtnExecutionError> {
+    execute_record_field_checks(
+        contract,
+        format.data_field(),
+        format.name(),
+        criterion,
+        collected_data,
+    )
+}
+
+/// Evaluate a criterion's record checks against the record each object
+/// carries in `data_field`; `label` names the records in the result message
+pub(crate) fn execute_record_field_checks(
+    contract: &CtnContract,
+    data_field: &str,
+    label: &str,
+    criterion: &ExecutableCriterion,
+    collected_data: &HashMap<String, CollectedData>,
+) -> Result<CtnExecutionResult, CtnExecutionError> {
+    let test_spec = &criterion.test;
+
+    // Phase 1: Existence check
+    let objects_expected = criterion.expected_object_count();
+    let objects_found = collected_data.len();
+
+    let existence_passed =
+        evaluate_existence_check(test_spec.existence_check, objects_found, objects_expected);
+
+    if !existence_passed {
+        return Ok(CtnExecutionResult::fail(
+            criterion.criterion_type.clone(),
+            format!(
+                "Existence check failed: expected {} objects, found {}",
+                objects_expected, objects_found
+            ),
+        ));
+    }
+
+    // Phase 2: State validation with record checks
+    let mut state_results = Vec::new();
+    let mut failure_messages = Vec::new();
+    let mut undetermined_messages = Vec::new();
+
+    for (object_id, data) in collected_data {
+        let options = record_options(contract, criterion, object_id)?;
+
+        // Extract RecordData from collected data
+        let record_data = match data.get_field(data_field) {
+            Some(ResolvedValue::RecordData(rd)) => rd,
+            Some(_) => {
+                return Err(CtnExecutionError::DataValidationFailed {
+                    reason: format!("{} field is not RecordData", data_field),
+                });
+            }
+            None => {
                 return Err(CtnExecutionError::MissingDataField {
-                    field: "json_data".to_string(),
+                    field: data_field.to_string(),
+                });
+            }
+        };
+
+        // Validate all states for this object
+        for state in &criterion.states {
+            // Validate record checks if present
+            if !state.record_checks.is_empty() {
+                let validation_results =
+                    validate_record_checks_with(record_data, &state.record_checks, &options)
+                        .map_err(|e| CtnExecutionError::ExecutionFailed {
+                            ctn_type: criterion.criterion_type.clone(),
+                            reason: format!("Record validation failed: {}", e),
+                        })?;
+
+                // Convert to FieldValidationResult format
+                let field_results: Vec<FieldValidationResult> = validation_results
+                    .iter()
+                    .map(|r| FieldValidationResult {
+                        field_name: r.field_path.clone(),
+                        expected_value: ResolvedValue::String(
+                            r.expected.clone().unwrap_or_default(),
+                        ),
+                        actual_value: ResolvedValue::String(r.actual.clone().unwrap_or_default()),
+                        operation: Operation::Equals,
+                        passed: r.passed,
+                        message: r.message.clone(),
+                        // A path absent from the record, or a number that cannot
+                        // settle the comparison, was never decided
+                        outcome: match (&r.actual, r.passed) {
+                            _ if r.undetermined.is_some() => FieldOutcome::Unknown,
+                            (None, false) => FieldOutcome::Unknown,
+                            (_, passed) => FieldOutcome::from_passed(passed),
+                        },
+                    })
+                    .collect();
+
+                // Check if all validations passed
+                let all_passed = validation_results.iter().all(|r| r.passed);
+
+                if !all_passed {
+                    for result in &validation_results {
+                        let message = format!("Object '{}': {}", object_id, result.message);
+                        if result.undetermined.is_some() {
+                            undetermined_messages.push(message);
+                        } else if !result.passed {
+                            failure_messages.push(message);
+                        }
+                    }
+                }
+
+                state_results.push(StateValidationResult {
+                    object_id: object_id.clone(),
+                    state_results: field_results,
+                    combined_result: all_passed,
+                    state_operator: test_spec.state_operator,
+                    message: format!(
+                        "Object '{}': {} ({} of {} checks passed)",
+                        object_id,
+                        if all_passed { "passed" } else { "failed" },
+                        validation_results.iter().filter(|r| r.passed).count(),
+                        validation_results.len()
+                    ),
                 });
             }
         }
-        Ok(())
     }
+
+    // Phase 3: Item check
+    let objects_passing = state_results.iter().filter(|r| r.combined_result).count();
+    let item_passed =
+        evaluate_item_check(test_spec.item_check, objects_passing, state_results.len());
+
+    // A decided failure outweighs comparisons that could not be decided
+    let final_status = if existence_passed && item_passed {
+        ComplianceStatus::Pass
+    } else if failure_messages.is_empty() && !undetermined_messages.is_empty() {
+        ComplianceStatus::Unknown
+    } else {
+        ComplianceStatus::Fail
+    };
+
+    let message = if final_status == ComplianceStatus::Pass {
+        format!(
+            "{} record validation passed: {} of {} objects compliant",
+            label,
+            objects_passing,
+            state_results.len()
+        )
+    } else if final_status == ComplianceStatus::Unknown {
+        format!(
+            "{} record validation undetermined:\n  - {}",
+            label,
+            undetermined_messages.join("\n  - ")
+        )
+    } else {
+        format!(
+            "{} record validation failed:\n  - {}",
+            label,
+            failure_messages.join("\n  - ")
+        )
+    };
+
+    Ok(CtnExecutionResult {
+        ctn_type: criterion.criterion_type.clone(),
+        status: final_status,
+        test_phase: TestPhase::Complete,
+        existence_result: None,
+        state_results,
+        item_check_result: None,
+        message,
+        details: serde_json::json!({
+            "failures": failure_messages,
+            "undetermined": undetermined_messages,
+            "objects_passing": objects_passing,
+        }),
+        execution_metadata: Default::default(),
+    })
+}
+
+/// Every object must carry the record parsed from `format`
+pub(crate) fn validate_record_data(
+    format: RecordFormat,
+    collected_data: &HashMap<String, CollectedData>,
+) -> Result<(), CtnExecutionError> {
+    for data in collected_data.values() {
+        if !data.has_field(format.data_field()) {
+            return Err(CtnExecutionError::MissingDataField {
+                field: format.data_field().to_string(),
+            });
+        }
+    }
+    Ok(())
 }
@@ -0,0 +1,142 @@
+//! Kernel command line executor
+//!
+//! Validates the arguments the running kernel was booted with and those GRUB
+//! is configured to boot with. Each object is one view of both command
+//! lines; with an `argument` object field, `runtime_has` and
+//! `configured_has` say whether each sets it.
+//!
+//! `runtime_args` and `configured_args` compare each argument and combine
+//! the results with the field's entity check, `all` by default:
+//! `runtime_args string = \`nousb\` at_least_one` requires an argument and
+//! `runtime_args string != \`audit=0\`` rules one out.
+
+use super::{entries_match, existence_failure, field_result, validate_items, ItemNames};
+use esp_scanner_base::strategies::{
+    CollectedData, CtnContract, CtnExecutionError, CtnExecutionResult, CtnExecutor,
+};
+use esp_scanner_base::types::execution_context::ExecutableCriterion;
+use std::collections::HashMap;
+
+pub struct KernelCmdlineExecutor {
+    contract: CtnContract,
+}
+
+const NAMES: ItemNames = ItemNames {
+    item: "Object",
+    items: "objects",
+    subject: "Kernel command line validation",
+};
+
+impl KernelCmdlineExecutor {
+    pub fn new(contract: CtnContract) -> Self {
+        Self { contract }
+    }
+}
+
+impl CtnExecutor for KernelCmdlineExecutor {
+    fn execute_with_contract(
+        &self,
+        criterion: &ExecutableCriterion,
+        collected_data: &HashMap<String, CollectedData>,
+        _contract: &CtnContract,
+    ) -> Result<CtnExecutionResult, CtnExecutionError> {
+        let objects_expected = criterion.expected_object_count();
+        if let Some(result) = existence_failure(
+            criterion,
+            NAMES.items,
+            collected_data.len(),
+            objects_expected,
+        ) {
+            return Ok(result);
+        }
+
+        let results = validate_items(
+            criterion,
+            &self.contract,
+            &NAMES,
+            collected_data,
+            |data: &CollectedData, field, name| {
+                field_result(field, data.get_field(name).cloned(), entries_match)
+            },
+        );
+        Ok(results.into_result(criterion, &NAMES))
+    }
+
+    fn get_ctn_contract(&self) -> CtnContract {
+        self.contract.clone()
+    }
+
+    fn ctn_type(&self) -> &str {
+        "kernel_cmdline"
+    }
+
+    fn validate_collected_data(
+        &self,
+        _collected_data: &HashMap<String, CollectedData>,
+        _contract: &CtnContract,
+    ) -> Result<(), CtnExecutionError> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contracts::create_kernel_cmdline_contract;
+    use esp_scanner_base::strategies::ComplianceStatus;
+    use esp_scanner_base::testkit::{CriterionFixture, MockCollector};
+    use serde_json::json;
+
+    const AUDIT: &str = r#"{"id": "audit", "fields": {"argument": "audit=1"}}"#;
+
+    fn cmdline(runtime: serde_json::Value, configured: serde_json::Value) -> MockCollector {
+        let has = |args: &serde_json::Value| args.as_array().unwrap().contains(&json!("audit=1"));
+        MockCollector::new("kernel_cmdline").with_item(
+            "audit",
+            json!({
+                "argument": "audit=1",
+                "runtime_has": has(&runtime),
+                "configured_has": has(&configured),
+                "runtime_args": runtime,
+                "configured_args": configured
+            }),
+        )
+    }
+
+    fn run(collector: &MockCollector, states: &str) -> CtnExecutionResult {
+        let executor = KernelCmdlineExecutor::new(create_kernel_cmdline_contract());
+        CriterionFixture::from_json(AUDIT, states)
+            .and_then(|fixture| fixture.run(collector, &executor))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_runtime_and_configured_are_separate() {
+        let states = r#"[{"id": "audited", "fields": [
+            {"name": "runtime_has", "type": "boolean", "op": "=", "value": true},
+            {"name": "configured_has", "type": "boolean", "op": "=", "value": true}
+        ]}]"#;
+        let both = cmdline(json!(["ro", "audit=1"]), json!(["rhgb", "audit=1"]));
+        assert_eq!(run(&both, states).status, ComplianceStatus::Pass);
+
+        let runtime_only = cmdline(json!(["ro", "audit=1"]), json!(["rhgb"]));
+        let result = run(&runtime_only, states);
+        assert_eq!(result.status, ComplianceStatus::Fail);
+        assert!(result.message.contains("configured_has"));
+    }
+
+    #[test]
+    fn test_argument_entity_checks() {
+        let states = r#"[{"id": "no_usb", "fields": [
+            {"name": "runtime_args", "type": "string", "op": "=", "value": "nousb",
+             "entity_check": "at_least_one"},
+            {"name": "configured_args", "type": "string", "op": "starts", "value": "audit=0",
+             "entity_check": "none"}
+        ]}]"#;
+        let hardened = cmdline(json!(["ro", "nousb"]), json!(["nousb", "audit=1"]));
+        assert_eq!(run(&hardened, states).status, ComplianceStatus::Pass);
+
+        let audit_off = cmdline(json!(["ro", "nousb"]), json!(["nousb", "audit=0"]));
+        assert_eq!(run(&audit_off, states).status, ComplianceStatus::Fail);
+    }
+}
@@ -0,0 +1,138 @@
+//! Kernel module executor
+//!
+//! Validates whether kernel modules are loaded and how modprobe.d configures
+//! them. Each object is one module, so `loaded` and `blacklisted` can be
+//! required together in a single state.
+
+use super::{existence_failure, field_matches, field_result, validate_items, ItemNames};
+use esp_scanner_base::strategies::{
+    CollectedData, CtnContract, CtnExecutionError, CtnExecutionResult, CtnExecutor,
+};
+use esp_scanner_base::types::execution_context::ExecutableCriterion;
+use std::collections::HashMap;
+
+pub struct KernelModuleExecutor {
+    contract: CtnContract,
+}
+
+const NAMES: ItemNames = ItemNames {
+    item: "Module",
+    items: "modules",
+    subject: "Kernel module validation",
+};
+
+impl KernelModuleExecutor {
+    pub fn new(contract: CtnContract) -> Self {
+        Self { contract }
+    }
+}
+
+impl CtnExecutor for KernelModuleExecutor {
+    fn execute_with_contract(
+        &self,
+        criterion: &ExecutableCriterion,
+        collected_data: &HashMap<String, CollectedData>,
+        _contract: &CtnContract,
+    ) -> Result<CtnExecutionResult, CtnExecutionError> {
+        let objects_expected = criterion.expected_object_count();
+        if let Some(result) = existence_failure(
+            criterion,
+            NAMES.items,
+            collected_data.len(),
+            objects_expected,
+        ) {
+            return Ok(result);
+        }
+
+        let results = validate_items(
+            criterion,
+            &self.contract,
+            &NAMES,
+            collected_data,
+            |data: &CollectedData, field, name| {
+                field_result(field, data.get_field(name).cloned(), field_matches)
+            },
+        );
+        Ok(results.into_result(criterion, &NAMES))
+    }
+
+    fn get_ctn_contract(&self) -> CtnContract {
+        self.contract.clone()
+    }
+
+    fn ctn_type(&self) -> &str {
+        "kernel_module"
+    }
+
+    fn validate_collected_data(
+        &self,
+        _collected_data: &HashMap<String, CollectedData>,
+        _contract: &CtnContract,
+    ) -> Result<(), CtnExecutionError> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contracts::create_kernel_module_contract;
+    use esp_scanner_base::strategies::ComplianceStatus;
+    use esp_scanner_base::testkit::{CriterionFixture, MockCollector};
+    use serde_json::json;
+
+    const USB_STORAGE: &str = r#"{"id": "usb_storage", "fields": {"module_name": "usb-storage"}}"#;
+
+    const DISABLED: &str = r#"[{"id": "disabled", "fields": [
+        {"name": "loaded", "type": "boolean", "op": "=", "value": false},
+        {"name": "blacklisted", "type": "boolean", "op": "=", "value": true}
+    ]}]"#;
+
+    fn module(loaded: bool, blacklisted: bool) -> MockCollector {
+        MockCollector::new("kernel_module").with_item(
+            "usb_storage",
+            json!({
+                "module_name": "usb_storage",
+                "loaded": loaded,
+                "blacklisted": blacklisted,
+                "install_directive": "/bin/false",
+                "install_disabled": true,
+                "config_files": "/etc/modprobe.d/cis.conf"
+            }),
+        )
+    }
+
+    fn run(collector: &MockCollector, states: &str) -> CtnExecutionResult {
+        let executor = KernelModuleExecutor::new(create_kernel_module_contract());
+        CriterionFixture::from_json(USB_STORAGE, states)
+            .and_then(|fixture| fixture.run(collector, &executor))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_unloaded_and_blacklisted_in_one_state() {
+        assert_eq!(
+            run(&module(false, true), DISABLED).status,
+            ComplianceStatus::Pass
+        );
+        assert_eq!(
+            run(&module(true, true), DISABLED).status,
+            ComplianceStatus::Fail
+        );
+        assert_eq!(
+            run(&module(false, false), DISABLED).status,
+            ComplianceStatus::Fail
+        );
+    }
+
+    #[test]
+    fn test_install_directive_string_operations() {
+        let states = r#"[{"id": "install", "fields": [
+            {"name": "install_directive", "type": "string", "op": "ends", "value": "false"}
+        ]}]"#;
+        assert_eq!(
+            run(&module(false, true), states).status,
+            ComplianceStatus::Pass
+        );
+    }
+}
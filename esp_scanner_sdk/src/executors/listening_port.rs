@@ -0,0 +1,188 @@
+//! Listening port executor
+//!
+//! Validates listening sockets collected with `ss -tulpn`.
+//!
+//! Every object expands into the listeners its filters selected, each a
+//! separate item named `object_id[protocol address:port]`. An object with
+//! `port 23` and `TEST none ...` therefore passes only when nothing listens
+//! on port 23, while `TEST at_least_one ...` requires a listener.
+//!
+//! Listeners whose process the scanner cannot see are still items, with an
+//! empty `process_name`, so a `process_name` check fails for them rather
+//! than the whole criterion erroring.
+
+use super::{existence_failure, field_matches, field_result, validate_items, ItemNames};
+use esp_scanner_base::execution::{collection_items, record_field, record_text};
+use esp_scanner_base::strategies::{
+    CollectedData, CtnContract, CtnExecutionError, CtnExecutionResult, CtnExecutor,
+};
+use esp_scanner_base::types::common::RecordData;
+use esp_scanner_base::types::execution_context::ExecutableCriterion;
+use std::collections::HashMap;
+
+pub struct ListeningPortExecutor {
+    contract: CtnContract,
+}
+
+const NAMES: ItemNames = ItemNames {
+    item: "Listener",
+    items: "listeners",
+    subject: "Listening port validation",
+};
+
+impl ListeningPortExecutor {
+    pub fn new(contract: CtnContract) -> Self {
+        Self { contract }
+    }
+}
+
+impl CtnExecutor for ListeningPortExecutor {
+    fn execute_with_contract(
+        &self,
+        criterion: &ExecutableCriterion,
+        collected_data: &HashMap<String, CollectedData>,
+        _contract: &CtnContract,
+    ) -> Result<CtnExecutionResult, CtnExecutionError> {
+        let items = collection_items(collected_data, "listeners", |_, record| {
+            format!(
+                "{} {}:{}",
+                record_text(record, "protocol"),
+                record_text(record, "local_address"),
+                record_text(record, "port")
+            )
+        });
+        let objects_expected = criterion
+            .expected_object_count()
+            .saturating_sub(collected_data.len())
+            + items.len();
+        if let Some(mut result) =
+            existence_failure(criterion, "listeners", items.len(), objects_expected)
+        {
+            let listeners: Vec<&str> = items.iter().map(|(item, _)| item.as_str()).collect();
+            result.details = serde_json::json!({ "listeners": listeners });
+            return Ok(result);
+        }
+
+        let results = validate_items(
+            criterion,
+            &self.contract,
+            &NAMES,
+            items.iter().map(|(item, record)| (item, *record)),
+            |record: &RecordData, field, name| {
+                field_result(field, record_field(record, name), field_matches)
+            },
+        );
+        Ok(results.into_result(criterion, &NAMES))
+    }
+
+    fn get_ctn_contract(&self) -> CtnContract {
+        self.contract.clone()
+    }
+
+    fn ctn_type(&self) -> &str {
+        "listening_port"
+    }
+
+    fn validate_collected_data(
+        &self,
+        _collected_data: &HashMap<String, CollectedData>,
+        _contract: &CtnContract,
+    ) -> Result<(), CtnExecutionError> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contracts::create_listening_port_contract;
+    use esp_scanner_base::strategies::ComplianceStatus;
+    use esp_scanner_base::testkit::{CriterionFixture, MockCollector};
+    use serde_json::json;
+
+    fn listeners(listeners: serde_json::Value) -> MockCollector {
+        MockCollector::new("listening_port").with_item("telnet", json!({ "listeners": listeners }))
+    }
+
+    fn run(collector: &MockCollector, states: &str, test: &str) -> CtnExecutionResult {
+        let executor = ListeningPortExecutor::new(create_listening_port_contract());
+        CriterionFixture::from_json(r#"{"id": "telnet", "fields": {"port": 23}}"#, states)
+            .and_then(|fixture| fixture.with_test(test))
+            .and_then(|fixture| fixture.run(collector, &executor))
+            .unwrap()
+    }
+
+    const TCP: &str = r#"[{"id": "tcp", "fields": [
+        {"name": "protocol", "type": "string", "op": "=", "value": "tcp"}
+    ]}]"#;
+
+    #[test]
+    fn test_no_listener_on_port() {
+        let result = run(&listeners(json!([])), TCP, "none all");
+        assert_eq!(result.status, ComplianceStatus::Pass);
+
+        let telnetd = listeners(json!([{
+            "protocol": "tcp", "state": "LISTEN", "local_address": "0.0.0.0",
+            "port": 23, "process_name": "in.telnetd"
+        }]));
+        let result = run(&telnetd, TCP, "none all");
+        assert_eq!(result.status, ComplianceStatus::Fail);
+        assert_eq!(result.details["listeners"][0], "telnet[tcp 0.0.0.0:23]");
+    }
+
+    #[test]
+    fn test_port_and_hidden_process() {
+        let sshd = listeners(json!([
+            {"protocol": "tcp", "state": "LISTEN", "local_address": "0.0.0.0",
+             "port": 22, "process_name": "sshd"},
+            {"protocol": "tcp", "state": "LISTEN", "local_address": "::",
+             "port": 22, "process_name": ""}
+        ]));
+        let states = r#"[{"id": "ssh", "fields": [
+            {"name": "port", "type": "int", "op": "=", "value": 22},
+            {"name": "process_name", "type": "string", "op": "=", "value": "sshd"}
+        ]}]"#;
+
+        let result = run(&sshd, states, "at_least_one at_least_one");
+        assert_eq!(result.status, ComplianceStatus::Pass);
+
+        let result = run(&sshd, states, "all all");
+        assert_eq!(result.status, ComplianceStatus::Fail);
+        assert!(result.message.contains("telnet[tcp :::22]"));
+    }
+
+    #[test]
+    fn test_local_address_in_subnet() {
+        let listeners = listeners(json!([
+            {"protocol": "tcp", "state": "LISTEN", "local_address": "10.1.2.3",
+             "port": 23, "process_name": "in.telnetd"},
+            {"protocol": "tcp", "state": "LISTEN", "local_address": "fe80::1%eth0",
+             "port": 23, "process_name": "in.telnetd"}
+        ]));
+        let private = r#"[{"id": "private", "fields": [
+            {"name": "local_address", "type": "ip_address", "op": "in_subnet",
+             "value": ["10.0.0.0/8", "fe80::/10"]}
+        ]}]"#;
+        assert_eq!(
+            run(&listeners, private, "all all").status,
+            ComplianceStatus::Pass
+        );
+
+        let not_loopback = r#"[{"id": "not_loopback", "fields": [
+            {"name": "local_address", "type": "ip_address", "op": "not_in_subnet",
+             "value": "127.0.0.0/8"}
+        ]}]"#;
+        assert_eq!(
+            run(&listeners, not_loopback, "all all").status,
+            ComplianceStatus::Pass
+        );
+
+        let ten_only = r#"[{"id": "ten", "fields": [
+            {"name": "local_address", "type": "ip_address", "op": "in_subnet",
+             "value": "10.0.0.0/8"}
+        ]}]"#;
+        let result = run(&listeners, ten_only, "all all");
+        assert_eq!(result.status, ComplianceStatus::Fail);
+        assert!(result.message.contains("fe80::1%eth0"));
+    }
+}
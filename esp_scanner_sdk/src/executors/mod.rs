@@ -1,28 +1,388 @@
 //! # Executors Module
 //!
 //! Executors validate collected data against state requirements:
+//! - AuditRulesExecutor: Audit watch and syscall rule validation
+//! - CertificateExecutor: X.509 certificate expiry and key strength validation
+//! - DpkgPackageExecutor: Debian package installation and version checks
+//! - ExtendedFileAttrExecutor: POSIX ACL and file capability validation
 //! - FileMetadataExecutor: File permissions, ownership, size validation
 //! - FileContentExecutor: Content string operations (contains, starts, ends, pattern)
+//! - FileHashExecutor: SHA-256/SHA-512 file digest and size validation
+//! - FilePermissionScanExecutor: SUID/SGID and world-writable file discovery
+//! - FirewalldZoneExecutor: firewalld zone, service and port validation
+//! - IniRecordExecutor: INI-style section and key validation
 //! - JsonRecordExecutor: Structured JSON field validation
+//! - ListeningPortExecutor: Listening TCP/UDP socket validation
+//! - KernelCmdlineExecutor: Running and GRUB-configured kernel argument validation
+//! - KernelModuleExecutor: Loaded and blacklisted kernel module validation
+//! - MountPointExecutor: Mounted and fstab-configured mount option validation
+//! - PasswordPolicyExecutor: login.defs and shadow password aging validation
 //! - RpmPackageExecutor: Package installation and version checks
+//! - ScheduledJobExecutor: Cron job and systemd timer validation
+//! - SelinuxBooleanExecutor: SELinux boolean value validation
+//! - SelinuxFileContextExecutor: SELinux file security context validation
 //! - SelinuxStatusExecutor: SELinux enforcement mode validation
+//! - SshdConfigExecutor: OpenSSH server directive validation
+//! - SudoersExecutor: sudoers user specification and Defaults validation
 //! - SysctlParameterExecutor: Kernel parameter validation
 //! - SystemdServiceExecutor: Service status validation
+//! - SystemdUnitPropertyExecutor: systemd unit property validation
+//! - TomlRecordExecutor: Structured TOML field validation
+//! - UserAccountExecutor: Local account (passwd/group) validation
+//! - YamlRecordExecutor: Structured YAML field validation
 
+pub mod audit_rules;
+pub mod certificate;
 pub mod computed_values;
+pub mod dpkg_package;
+pub mod environment_variable;
+pub mod extended_file_attr;
 pub mod file_content;
+pub mod file_hash;
 pub mod file_metadata;
+pub mod file_permission_scan;
+pub mod firewalld_zone;
+pub mod ini_record;
 pub mod json_record;
+pub mod kernel_cmdline;
+pub mod kernel_module;
+pub mod listening_port;
+pub mod mount_point;
+pub mod password_policy;
 pub mod rpm_package;
+pub mod scheduled_job;
+pub mod selinux_boolean;
+pub mod selinux_file_context;
 pub mod selinux_status;
+pub mod sshd_config;
+pub mod sudoers;
 pub mod sysctl_parameter;
 pub mod systemd_service;
+pub mod systemd_unit_property;
+pub mod toml_record;
+pub mod user_account;
+pub mod xml_record;
+pub mod yaml_record;
 
+pub use audit_rules::AuditRulesExecutor;
+pub use certificate::CertificateExecutor;
 pub use computed_values::ComputedValuesExecutor;
+pub use dpkg_package::DpkgPackageExecutor;
+pub use environment_variable::EnvironmentExecutor;
+pub use extended_file_attr::ExtendedFileAttrExecutor;
 pub use file_content::FileContentExecutor;
+pub use file_hash::FileHashExecutor;
 pub use file_metadata::FileMetadataExecutor;
+pub use file_permission_scan::FilePermissionScanExecutor;
+pub use firewalld_zone::FirewalldZoneExecutor;
+pub use ini_record::IniRecordExecutor;
 pub use json_record::JsonRecordExecutor;
+pub use kernel_cmdline::KernelCmdlineExecutor;
+pub use kernel_module::KernelModuleExecutor;
+pub use listening_port::ListeningPortExecutor;
+pub use mount_point::MountPointExecutor;
+pub use password_policy::PasswordPolicyExecutor;
 pub use rpm_package::RpmPackageExecutor;
+pub use scheduled_job::ScheduledJobExecutor;
+pub use selinux_boolean::SelinuxBooleanExecutor;
+pub use selinux_file_context::SelinuxFileContextExecutor;
 pub use selinux_status::SelinuxStatusExecutor;
+pub use sshd_config::SshdConfigExecutor;
+pub use sudoers::SudoersExecutor;
 pub use sysctl_parameter::SysctlParameterExecutor;
 pub use systemd_service::SystemdServiceExecutor;
+pub use systemd_unit_property::SystemdUnitPropertyExecutor;
+pub use toml_record::TomlRecordExecutor;
+pub use user_account::UserAccountExecutor;
+pub use xml_record::XmlRecordExecutor;
+pub use yaml_record::YamlRecordExecutor;
+
+use esp_scanner_base::execution::{
+    collection, evaluate_existence_check, evaluate_state_operator, evaluate_test_item_check,
+    ComparisonExt,
+};
+use esp_scanner_base::strategies::{
+    ComplianceStatus, CtnContract, CtnExecutionResult, FieldOutcome, FieldValidationResult,
+    StateValidationResult, TestPhase,
+};
+use esp_scanner_base::types::common::{Operation, ResolvedValue};
+use esp_scanner_base::types::execution_context::{ExecutableCriterion, ExecutableStateField};
+
+/// Entity check over list entries; an empty list holds for `all` and `none`
+pub(crate) use esp_scanner_base::execution::evaluate_list_entity_check as list_entity_check;
+
+/// Compare one collected value against a state field
+///
+/// `compare` receives the expected value. When the field holds a list-valued
+/// variable and its operation does not take a value list, every list item is
+/// compared and the results are combined by the field's entity check.
+pub(crate) fn compare_field(
+    field: &ExecutableStateField,
+    actual: &ResolvedValue,
+    compare: impl Fn(&ResolvedValue) -> bool,
+) -> bool {
+    match &field.value {
+        ResolvedValue::Collection(items)
+            if !field.operation.takes_value_list()
+                && !matches!(actual, ResolvedValue::Collection(_)) =>
+        {
+            let results: Vec<bool> = items.iter().map(compare).collect();
+            list_entity_check(field.entity_check, &results)
+        }
+        expected => compare(expected),
+    }
+}
+
+/// Match a value-list operation (`equals_any` / `not_equals_any`)
+///
+/// Returns `None` when the operation does not take a value list or the
+/// expected value is not a list, so the caller compares as usual.
+pub(crate) fn match_value_list(
+    expected: &ResolvedValue,
+    actual: &ResolvedValue,
+    operation: Operation,
+) -> Option<bool> {
+    match (expected, operation) {
+        (ResolvedValue::Collection(allowed), Operation::EqualsAny | Operation::NotEqualsAny) => {
+            Some(collection::matches_any(actual, allowed, operation).unwrap_or(false))
+        }
+        _ => None,
+    }
+}
+
+/// Compare one collected value against one expected value
+///
+/// A type the operation does not support compares as a failure.
+pub(crate) fn compare_value(
+    expected: &ResolvedValue,
+    actual: &ResolvedValue,
+    operation: Operation,
+) -> bool {
+    match_value_list(expected, actual, operation)
+        .unwrap_or_else(|| actual.compare_with(expected, operation).unwrap_or(false))
+}
+
+/// Whether a collected value satisfies a state field
+pub(crate) fn field_matches(field: &ExecutableStateField, actual: &ResolvedValue) -> bool {
+    compare_field(field, actual, |expected| {
+        compare_value(expected, actual, field.operation)
+    })
+}
+
+/// Like [`field_matches`], but a list-valued actual is compared entry by
+/// entry and the results are combined by the field's entity check
+pub(crate) fn entries_match(field: &ExecutableStateField, actual: &ResolvedValue) -> bool {
+    match actual {
+        ResolvedValue::Collection(entries) => {
+            let results: Vec<bool> = entries
+                .iter()
+                .map(|entry| compare_value(&field.value, entry, field.operation))
+                .collect();
+            list_entity_check(field.entity_check, &results)
+        }
+        actual => field_matches(field, actual),
+    }
+}
+
+/// Compare a state field against its collected value, `None` when the item
+/// does not carry the field
+pub(crate) fn field_result(
+    field: &ExecutableStateField,
+    actual: Option<ResolvedValue>,
+    matches: impl Fn(&ExecutableStateField, &ResolvedValue) -> bool,
+) -> FieldValidationResult {
+    let Some(actual_value) = actual else {
+        return FieldValidationResult::not_present(
+            field.name.clone(),
+            field.value.clone(),
+            field.operation,
+            format!("Field '{}' not collected", field.name),
+        );
+    };
+
+    let passed = matches(field, &actual_value);
+    let message = if passed {
+        format!("Field '{}' passed", field.name)
+    } else {
+        format!(
+            "Field '{}' failed: expected {:?}, got {:?}",
+            field.name, field.value, actual_value
+        )
+    };
+
+    FieldValidationResult {
+        field_name: field.name.clone(),
+        expected_value: field.value.clone(),
+        actual_value,
+        operation: field.operation,
+        passed,
+        message,
+        outcome: FieldOutcome::from_passed(passed),
+    }
+}
+
+/// A state field on an item whose collection failed
+pub(crate) fn field_unavailable(
+    field: &ExecutableStateField,
+    error: &str,
+) -> FieldValidationResult {
+    FieldValidationResult::not_present(
+        field.name.clone(),
+        field.value.clone(),
+        field.operation,
+        format!("Field '{}' unavailable: {}", field.name, error),
+    )
+}
+
+/// How an executor names its items in result messages
+pub(crate) struct ItemNames {
+    /// Prefix of item messages, as in `Module 'usb_storage': passed`
+    pub item: &'static str,
+    /// Plural in counts, as in `2 of 3 modules compliant`
+    pub items: &'static str,
+    /// Subject of the summary, as in `Kernel module validation passed`
+    pub subject: &'static str,
+}
+
+/// The failed result of an existence check, `None` when it holds
+pub(crate) fn existence_failure(
+    criterion: &ExecutableCriterion,
+    items: &str,
+    objects_found: usize,
+    objects_expected: usize,
+) -> Option<CtnExecutionResult> {
+    if evaluate_existence_check(
+        criterion.test.existence_check,
+        objects_found,
+        objects_expected,
+    ) {
+        return None;
+    }
+    Some(CtnExecutionResult::fail(
+        criterion.criterion_type.clone(),
+        format!(
+            "Existence check failed: expected {} {}, found {}",
+            objects_expected, items, objects_found
+        ),
+    ))
+}
+
+/// State results of every item, with one failure message per failed field
+pub(crate) struct ItemResults {
+    pub state_results: Vec<StateValidationResult>,
+    pub failures: Vec<String>,
+}
+
+/// Validate every item against the criterion's states
+///
+/// Each state field is mapped to its data field through the contract and
+/// handed to `evaluate` along with the item. Field results are combined by
+/// the state operator.
+pub(crate) fn validate_items<'a, T: ?Sized + 'a>(
+    criterion: &ExecutableCriterion,
+    contract: &CtnContract,
+    names: &ItemNames,
+    items: impl IntoIterator<Item = (&'a String, &'a T)>,
+    evaluate: impl Fn(&T, &ExecutableStateField, &str) -> FieldValidationResult,
+) -> ItemResults {
+    let state_to_data = &contract.field_mappings.validation_mappings.state_to_data;
+    let state_operator = criterion.test.state_operator;
+    let mut state_results = Vec::new();
+    let mut failures = Vec::new();
+
+    for (object_id, item) in items {
+        let mut field_results = Vec::new();
+
+        for field in criterion.states.iter().flat_map(|state| &state.fields) {
+            let data_field_name = state_to_data.get(&field.name).unwrap_or(&field.name);
+            let result = evaluate(item, field, data_field_name);
+            if !result.passed {
+                failures.push(format!(
+                    "{} '{}': {}",
+                    names.item, object_id, result.message
+                ));
+            }
+            field_results.push(result);
+        }
+
+        let field_bools: Vec<bool> = field_results.iter().map(|r| r.passed).collect();
+        let combined = evaluate_state_operator(state_operator, &field_bools);
+
+        state_results.push(StateValidationResult {
+            object_id: object_id.clone(),
+            state_results: field_results,
+            combined_result: combined,
+            state_operator,
+            message: format!(
+                "{} '{}': {}",
+                names.item,
+                object_id,
+                if combined { "passed" } else { "failed" }
+            ),
+        });
+    }
+
+    ItemResults {
+        state_results,
+        failures,
+    }
+}
+
+impl ItemResults {
+    /// Apply the item check and summarize the items as the criterion result
+    ///
+    /// Call after the existence check has passed.
+    pub(crate) fn into_result(
+        self,
+        criterion: &ExecutableCriterion,
+        names: &ItemNames,
+    ) -> CtnExecutionResult {
+        let test_spec = &criterion.test;
+        let objects_total = self.state_results.len();
+        let objects_passing = self
+            .state_results
+            .iter()
+            .filter(|r| r.combined_result)
+            .count();
+        let item_passed = evaluate_test_item_check(
+            test_spec.existence_check,
+            test_spec.item_check,
+            objects_passing,
+            objects_total,
+        );
+
+        let (status, message) = if item_passed {
+            (
+                ComplianceStatus::Pass,
+                format!(
+                    "{} passed: {} of {} {} compliant",
+                    names.subject, objects_passing, objects_total, names.items
+                ),
+            )
+        } else {
+            (
+                ComplianceStatus::Fail,
+                format!(
+                    "{} failed:\n  - {}",
+                    names.subject,
+                    self.failures.join("\n  - ")
+                ),
+            )
+        };
+
+        CtnExecutionResult {
+            ctn_type: criterion.criterion_type.clone(),
+            status,
+            test_phase: TestPhase::Complete,
+            existence_result: None,
+            state_results: self.state_results,
+            item_check_result: None,
+            message,
+            details: serde_json::json!({
+                "failures": self.failures,
+                "objects_passing": objects_passing,
+            }),
+            execution_metadata: Default::default(),
+        }
+    }
+}
@@ -0,0 +1,151 @@
+//! Mount point executor
+//!
+//! Validates how mount points are mounted now and how `/etc/fstab` configures
+//! them. Each object is one mount point; `mounted` and `configured` say
+//! whether it is in the active mount table and in fstab.
+//!
+//! `options` and `fstab_options` compare each option and combine the results
+//! with the field's entity check, `all` by default: `options string =
+//! \`nosuid\` at_least_one` requires an option and `options string !=
+//! \`exec\`` rules one out. An empty list satisfies `all` and `none`, so
+//! pair option checks with `mounted boolean = true` or
+//! `configured boolean = true`.
+
+use super::{entries_match, existence_failure, field_result, validate_items, ItemNames};
+use esp_scanner_base::strategies::{
+    CollectedData, CtnContract, CtnExecutionError, CtnExecutionResult, CtnExecutor,
+};
+use esp_scanner_base::types::execution_context::ExecutableCriterion;
+use std::collections::HashMap;
+
+pub struct MountPointExecutor {
+    contract: CtnContract,
+}
+
+const NAMES: ItemNames = ItemNames {
+    item: "Mount",
+    items: "mount points",
+    subject: "Mount point validation",
+};
+
+impl MountPointExecutor {
+    pub fn new(contract: CtnContract) -> Self {
+        Self { contract }
+    }
+}
+
+impl CtnExecutor for MountPointExecutor {
+    fn execute_with_contract(
+        &self,
+        criterion: &ExecutableCriterion,
+        collected_data: &HashMap<String, CollectedData>,
+        _contract: &CtnContract,
+    ) -> Result<CtnExecutionResult, CtnExecutionError> {
+        let objects_expected = criterion.expected_object_count();
+        if let Some(result) = existence_failure(
+            criterion,
+            NAMES.items,
+            collected_data.len(),
+            objects_expected,
+        ) {
+            return Ok(result);
+        }
+
+        let results = validate_items(
+            criterion,
+            &self.contract,
+            &NAMES,
+            collected_data,
+            |data: &CollectedData, field, name| {
+                field_result(field, data.get_field(name).cloned(), entries_match)
+            },
+        );
+        Ok(results.into_result(criterion, &NAMES))
+    }
+
+    fn get_ctn_contract(&self) -> CtnContract {
+        self.contract.clone()
+    }
+
+    fn ctn_type(&self) -> &str {
+        "mount_point"
+    }
+
+    fn validate_collected_data(
+        &self,
+        _collected_data: &HashMap<String, CollectedData>,
+        _contract: &CtnContract,
+    ) -> Result<(), CtnExecutionError> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contracts::create_mount_point_contract;
+    use esp_scanner_base::strategies::ComplianceStatus;
+    use esp_scanner_base::testkit::{CriterionFixture, MockCollector};
+    use serde_json::json;
+
+    const TMP: &str = r#"{"id": "tmp", "fields": {"mount_point": "/tmp"}}"#;
+
+    const HARDENED: &str = r#"[{"id": "hardened", "fields": [
+        {"name": "mounted", "type": "boolean", "op": "=", "value": true},
+        {"name": "options", "type": "string", "op": "=", "value": "nosuid",
+         "entity_check": "at_least_one"},
+        {"name": "options", "type": "string", "op": "!=", "value": "exec"}
+    ]}]"#;
+
+    fn tmp(options: serde_json::Value, fstab_options: serde_json::Value) -> MockCollector {
+        MockCollector::new("mount_point").with_item(
+            "tmp",
+            json!({
+                "mount_point": "/tmp",
+                "mounted": true,
+                "device": "tmpfs",
+                "fstype": "tmpfs",
+                "options": options,
+                "configured": true,
+                "fstab_device": "tmpfs",
+                "fstab_fstype": "tmpfs",
+                "fstab_options": fstab_options
+            }),
+        )
+    }
+
+    fn run(collector: &MockCollector, states: &str) -> CtnExecutionResult {
+        let executor = MountPointExecutor::new(create_mount_point_contract());
+        CriterionFixture::from_json(TMP, states)
+            .and_then(|fixture| fixture.run(collector, &executor))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_option_entity_checks() {
+        let hardened = tmp(json!(["rw", "nosuid", "nodev", "noexec"]), json!([]));
+        assert_eq!(run(&hardened, HARDENED).status, ComplianceStatus::Pass);
+
+        let exec = tmp(json!(["rw", "nosuid", "exec"]), json!([]));
+        assert_eq!(run(&exec, HARDENED).status, ComplianceStatus::Fail);
+
+        let no_nosuid = tmp(json!(["rw", "nodev"]), json!([]));
+        assert_eq!(run(&no_nosuid, HARDENED).status, ComplianceStatus::Fail);
+    }
+
+    #[test]
+    fn test_mounted_and_configured_are_separate() {
+        let states = r#"[{"id": "persistent", "fields": [
+            {"name": "configured", "type": "boolean", "op": "=", "value": true},
+            {"name": "fstab_options", "type": "string", "op": "=", "value": "noexec",
+             "entity_check": "at_least_one"}
+        ]}]"#;
+        let runtime_only = tmp(json!(["rw", "noexec"]), json!(["defaults"]));
+        let result = run(&runtime_only, states);
+        assert_eq!(result.status, ComplianceStatus::Fail);
+        assert!(result.message.contains("fstab_options"));
+
+        let persistent = tmp(json!(["rw"]), json!(["defaults", "noexec"]));
+        assert_eq!(run(&persistent, states).status, ComplianceStatus::Pass);
+    }
+}
@@ -0,0 +1,189 @@
+//! Password policy executor
+//!
+//! Validates password aging from login.defs and the shadow database. Each
+//! object is one item; `pass_max_days`, `pass_min_days` and `pass_warn_age`
+//! are single values, while `username`, `max_days`, `min_days`, `warn_days`,
+//! `last_change_epoch` and `locked` hold one value per account.
+//!
+//! Per-account fields compare each account and combine the results with the
+//! field's entity check, `all` by default, so `max_days int <= 60` is one
+//! check over every account. Failures name the accounts that did not comply.
+//! No accounts at all satisfies `all` and `none`.
+
+use super::{
+    compare_value, existence_failure, field_matches, field_result, list_entity_check,
+    validate_items, ItemNames,
+};
+use esp_scanner_base::strategies::{
+    CollectedData, CtnContract, CtnExecutionError, CtnExecutionResult, CtnExecutor,
+};
+use esp_scanner_base::types::common::ResolvedValue;
+use esp_scanner_base::types::execution_context::ExecutableCriterion;
+use std::collections::HashMap;
+
+pub struct PasswordPolicyExecutor {
+    contract: CtnContract,
+}
+
+const NAMES: ItemNames = ItemNames {
+    item: "Policy",
+    items: "policies",
+    subject: "Password policy validation",
+};
+
+impl PasswordPolicyExecutor {
+    pub fn new(contract: CtnContract) -> Self {
+        Self { contract }
+    }
+}
+
+impl CtnExecutor for PasswordPolicyExecutor {
+    fn execute_with_contract(
+        &self,
+        criterion: &ExecutableCriterion,
+        collected_data: &HashMap<String, CollectedData>,
+        _contract: &CtnContract,
+    ) -> Result<CtnExecutionResult, CtnExecutionError> {
+        let objects_expected = criterion.expected_object_count();
+        if let Some(result) = existence_failure(
+            criterion,
+            NAMES.items,
+            collected_data.len(),
+            objects_expected,
+        ) {
+            return Ok(result);
+        }
+
+        let results = validate_items(
+            criterion,
+            &self.contract,
+            &NAMES,
+            collected_data,
+            |data: &CollectedData, field, name| {
+                let Some(ResolvedValue::Collection(values)) = data.get_field(name) else {
+                    return field_result(field, data.get_field(name).cloned(), field_matches);
+                };
+
+                // Per-account values name the accounts that fail
+                let usernames = match data.get_field("username") {
+                    Some(ResolvedValue::Collection(names)) => names.as_slice(),
+                    _ => &[],
+                };
+                let results: Vec<bool> = values
+                    .iter()
+                    .map(|value| compare_value(&field.value, value, field.operation))
+                    .collect();
+                let offenders: Vec<&str> = results
+                    .iter()
+                    .zip(usernames)
+                    .filter(|(passed, _)| !**passed)
+                    .filter_map(|(_, name)| match name {
+                        ResolvedValue::String(name) => Some(name.as_str()),
+                        _ => None,
+                    })
+                    .collect();
+                let passed = list_entity_check(field.entity_check, &results);
+
+                let mut result = field_result(
+                    field,
+                    Some(ResolvedValue::Collection(values.clone())),
+                    |_, _| passed,
+                );
+                if !passed {
+                    result.message = format!(
+                        "Field '{}' failed: expected {:?}; not satisfied by: {}",
+                        field.name,
+                        field.value,
+                        offenders.join(", ")
+                    );
+                }
+                result
+            },
+        );
+        Ok(results.into_result(criterion, &NAMES))
+    }
+
+    fn get_ctn_contract(&self) -> CtnContract {
+        self.contract.clone()
+    }
+
+    fn ctn_type(&self) -> &str {
+        "password_policy"
+    }
+
+    fn validate_collected_data(
+        &self,
+        _collected_data: &HashMap<String, CollectedData>,
+        _contract: &CtnContract,
+    ) -> Result<(), CtnExecutionError> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contracts::create_password_policy_contract;
+    use esp_scanner_base::strategies::ComplianceStatus;
+    use esp_scanner_base::testkit::{CriterionFixture, MockCollector};
+    use serde_json::json;
+
+    const AGING: &str = r#"[{"id": "aging", "fields": [
+        {"name": "pass_max_days", "type": "int", "op": "<=", "value": 60},
+        {"name": "max_days", "type": "int", "op": "<=", "value": 60}
+    ]}]"#;
+
+    fn policy(pass_max_days: i64, max_days: serde_json::Value) -> MockCollector {
+        MockCollector::new("password_policy").with_item(
+            "shadow",
+            json!({
+                "pass_max_days": pass_max_days,
+                "pass_min_days": 1,
+                "pass_warn_age": 7,
+                "username": ["root", "alice", "bob"],
+                "max_days": max_days,
+                "min_days": [1, 1, 1],
+                "warn_days": [7, 7, 7],
+                "last_change_epoch": [1_704_067_200, 1_704_067_200, 1_704_067_200],
+                "locked": [false, false, false]
+            }),
+        )
+    }
+
+    fn run(collector: &MockCollector, states: &str) -> CtnExecutionResult {
+        let executor = PasswordPolicyExecutor::new(create_password_policy_contract());
+        CriterionFixture::from_json(r#"{"id": "shadow", "fields": {}}"#, states)
+            .and_then(|fixture| fixture.run(collector, &executor))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_every_account_must_comply() {
+        let result = run(&policy(60, json!([60, 45, 30])), AGING);
+        assert_eq!(result.status, ComplianceStatus::Pass);
+
+        let result = run(&policy(60, json!([60, 99999, 30])), AGING);
+        assert_eq!(result.status, ComplianceStatus::Fail);
+        assert!(result.message.contains("not satisfied by: alice"));
+
+        let result = run(&policy(99999, json!([60, 45, 30])), AGING);
+        assert_eq!(result.status, ComplianceStatus::Fail);
+        assert!(result.message.contains("pass_max_days"));
+    }
+
+    #[test]
+    fn test_entity_check_over_accounts() {
+        let states = r#"[{"id": "some_short", "fields": [
+            {"name": "max_days", "type": "int", "op": "<", "value": 40,
+             "entity_check": "at_least_one"}
+        ]}]"#;
+        assert_eq!(
+            run(&policy(60, json!([60, 45, 30])), states).status,
+            ComplianceStatus::Pass
+        );
+        assert_eq!(
+            run(&policy(60, json!([60, 45, 90])), states).status,
+            ComplianceStatus::Fail
+        );
+    }
+}
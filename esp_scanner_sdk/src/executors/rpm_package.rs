@@ -1,13 +1,17 @@
 //! RPM package executor
 //!
 //! Validates RPM package installation status and versions.
+//!
+//! Ordering comparisons and the `version_*` operations on `version` use
+//! RPM's ordering (`1.0~rc1 < 1.0 < 1.0.1`); `=` and `!=` stay exact.
 
+use super::{compare_field, match_value_list};
 use esp_scanner_base::execution::{
-    evaluate_existence_check, evaluate_item_check, evaluate_state_operator,
+    evaluate_existence_check, evaluate_item_check, evaluate_state_operator, evr,
 };
 use esp_scanner_base::strategies::{
     CollectedData, ComplianceStatus, CtnContract, CtnExecutionError, CtnExecutionResult,
-    CtnExecutor, FieldValidationResult, StateValidationResult, TestPhase,
+    CtnExecutor, FieldOutcome, FieldValidationResult, StateValidationResult, TestPhase,
 };
 use esp_scanner_base::types::common::{Operation, ResolvedValue};
 use esp_scanner_base::types::execution_context::ExecutableCriterion;
@@ -23,13 +27,18 @@ impl RpmPackageExecutor {
         Self { contract }
     }
 
-    /// Compare values with version-aware string comparison
+    /// Compare values, ordering `version` fields as RPM does
     fn compare_values(
         &self,
         expected: &ResolvedValue,
         actual: &ResolvedValue,
         operation: Operation,
+        field_is_version: bool,
     ) -> bool {
+        if let Some(matched) = match_value_list(expected, actual, operation) {
+            return matched;
+        }
+
         match (expected, actual, operation) {
             // Boolean comparisons
             (ResolvedValue::Boolean(exp), ResolvedValue::Boolean(act), Operation::Equals) => {
@@ -39,29 +48,21 @@ impl RpmPackageExecutor {
                 exp != act
             }
 
-            // String comparisons (including versions)
+            // Versions order as RPM orders them
+            (ResolvedValue::String(exp), ResolvedValue::String(act), _)
+                if operation.is_version_comparison()
+                    || (field_is_version
+                        && !matches!(operation, Operation::Equals | Operation::NotEqual)) =>
+            {
+                evr::compare(exp, act, operation).unwrap_or(false)
+            }
+
             (ResolvedValue::String(exp), ResolvedValue::String(act), Operation::Equals) => {
                 exp == act
             }
             (ResolvedValue::String(exp), ResolvedValue::String(act), Operation::NotEqual) => {
                 exp != act
             }
-            (ResolvedValue::String(exp), ResolvedValue::String(act), Operation::GreaterThan) => {
-                act > exp // Lexicographic comparison for now
-            }
-            (ResolvedValue::String(exp), ResolvedValue::String(act), Operation::LessThan) => {
-                act < exp
-            }
-            (
-                ResolvedValue::String(exp),
-                ResolvedValue::String(act),
-                Operation::GreaterThanOrEqual,
-            ) => act >= exp,
-            (
-                ResolvedValue::String(exp),
-                ResolvedValue::String(act),
-                Operation::LessThanOrEqual,
-            ) => act <= exp,
 
             _ => false,
         }
@@ -116,20 +117,25 @@ impl CtnExecutor for RpmPackageExecutor {
                         Some(v) => v.clone(),
                         None => {
                             let msg = format!("Field '{}' not collected", field.name);
-                            all_field_results.push(FieldValidationResult {
-                                field_name: field.name.clone(),
-                                expected_value: field.value.clone(),
-                                actual_value: ResolvedValue::String("".to_string()),
-                                operation: field.operation,
-                                passed: false,
-                                message: msg.clone(),
-                            });
+                            all_field_results.push(FieldValidationResult::not_present(
+                                field.name.clone(),
+                                field.value.clone(),
+                                field.operation,
+                                msg.clone(),
+                            ));
                             failure_messages.push(format!("Package '{}': {}", object_id, msg));
                             continue;
                         }
                     };
 
-                    let passed = self.compare_values(&field.value, &actual_value, field.operation);
+                    let passed = compare_field(field, &actual_value, |expected| {
+                        self.compare_values(
+                            expected,
+                            &actual_value,
+                            field.operation,
+                            data_field_name == "version",
+                        )
+                    });
 
                     let msg = if passed {
                         format!("Field '{}' passed", field.name)
@@ -151,6 +157,7 @@ impl CtnExecutor for RpmPackageExecutor {
                         operation: field.operation,
                         passed,
                         message: msg,
+                        outcome: FieldOutcome::from_passed(passed),
                     });
                 }
             }
@@ -234,3 +241,46 @@ impl CtnExecutor for RpmPackageExecutor {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contracts::create_rpm_package_contract;
+    use esp_scanner_base::testkit::{CriterionFixture, MockCollector};
+    use serde_json::json;
+
+    #[test]
+    fn test_version_states_use_rpm_order() {
+        let collector = MockCollector::new("rpm_package").with_item(
+            "openssl",
+            json!({
+                "package_name": "openssl",
+                "installed": true,
+                "version": "3.0.10-1.el9"
+            }),
+        );
+        let executor = RpmPackageExecutor::new(create_rpm_package_contract());
+        let run = |op: &str, value: &str| {
+            let states = json!([{"id": "version", "fields": [
+                {"name": "version", "type": "string", "op": op, "value": value}
+            ]}]);
+            CriterionFixture::from_json(
+                r#"{"id": "openssl", "fields": {"package_name": "openssl"}}"#,
+                &states.to_string(),
+            )
+            .and_then(|fixture| fixture.run(&collector, &executor))
+            .unwrap()
+            .status
+        };
+
+        // 3.0.10 is newer than 3.0.9, although it sorts first as a string
+        assert_eq!(run(">=", "3.0.9-1.el9"), ComplianceStatus::Pass);
+        assert_eq!(
+            run("version_greater_than", "3.0.10~rc1"),
+            ComplianceStatus::Pass
+        );
+        assert_eq!(run("version_equals", "0:3.0.10"), ComplianceStatus::Pass);
+        assert_eq!(run("=", "3.0.10"), ComplianceStatus::Fail);
+        assert_eq!(run("version_less_than", "1:1.0"), ComplianceStatus::Pass);
+    }
+}
@@ -0,0 +1,168 @@
+//! Scheduled job executor
+//!
+//! Validates cron jobs and systemd timers: what they run, as whom, and how
+//! often.
+//!
+//! Every object expands into the jobs its filters match, each a separate
+//! item named `object_id[source:line]` for a crontab line and
+//! `object_id[source]` otherwise. An object filtering `command` for
+//! `*curl*` with `TEST none ...` therefore passes only when no job runs it,
+//! while `TEST at_least_one ...` requires such a job.
+//!
+//! Jobs without a derivable interval have no `max_interval_hours`, so a
+//! state comparing it fails for them rather than passing.
+
+use super::{existence_failure, field_matches, field_result, validate_items, ItemNames};
+use esp_scanner_base::execution::{collection_items, record_field, record_text};
+use esp_scanner_base::strategies::{
+    CollectedData, CtnContract, CtnExecutionError, CtnExecutionResult, CtnExecutor,
+};
+use esp_scanner_base::types::common::RecordData;
+use esp_scanner_base::types::execution_context::ExecutableCriterion;
+use std::collections::HashMap;
+
+pub struct ScheduledJobExecutor {
+    contract: CtnContract,
+}
+
+const NAMES: ItemNames = ItemNames {
+    item: "Job",
+    items: "jobs",
+    subject: "Scheduled job validation",
+};
+
+impl ScheduledJobExecutor {
+    pub fn new(contract: CtnContract) -> Self {
+        Self { contract }
+    }
+}
+
+impl CtnExecutor for ScheduledJobExecutor {
+    fn execute_with_contract(
+        &self,
+        criterion: &ExecutableCriterion,
+        collected_data: &HashMap<String, CollectedData>,
+        _contract: &CtnContract,
+    ) -> Result<CtnExecutionResult, CtnExecutionError> {
+        let items = collection_items(collected_data, "jobs", |_, record| {
+            let source = record_text(record, "source");
+            match record.get_field_by_path("line").and_then(|l| l.as_i64()) {
+                Some(line) if line > 0 => format!("{}:{}", source, line),
+                _ => source,
+            }
+        });
+        let objects_expected = criterion
+            .expected_object_count()
+            .saturating_sub(collected_data.len())
+            + items.len();
+        if let Some(mut result) =
+            existence_failure(criterion, "jobs", items.len(), objects_expected)
+        {
+            let jobs: Vec<&str> = items.iter().map(|(item, _)| item.as_str()).collect();
+            result.details = serde_json::json!({ "jobs": jobs });
+            return Ok(result);
+        }
+
+        let results = validate_items(
+            criterion,
+            &self.contract,
+            &NAMES,
+            items.iter().map(|(item, record)| (item, *record)),
+            |record: &RecordData, field, name| {
+                field_result(field, record_field(record, name), field_matches)
+            },
+        );
+        Ok(results.into_result(criterion, &NAMES))
+    }
+
+    fn get_ctn_contract(&self) -> CtnContract {
+        self.contract.clone()
+    }
+
+    fn ctn_type(&self) -> &str {
+        "scheduled_job"
+    }
+
+    fn validate_collected_data(
+        &self,
+        _collected_data: &HashMap<String, CollectedData>,
+        _contract: &CtnContract,
+    ) -> Result<(), CtnExecutionError> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contracts::create_scheduled_job_contract;
+    use esp_scanner_base::strategies::ComplianceStatus;
+    use esp_scanner_base::testkit::{CriterionFixture, MockCollector};
+    use serde_json::json;
+
+    fn run(collector: &MockCollector, states: &str, test: &str) -> CtnExecutionResult {
+        let executor = ScheduledJobExecutor::new(create_scheduled_job_contract());
+        CriterionFixture::from_json(r#"{"id": "aide", "fields": {"command": "*aide*"}}"#, states)
+            .and_then(|fixture| fixture.with_test(test))
+            .and_then(|fixture| fixture.run(collector, &executor))
+            .unwrap()
+    }
+
+    fn aide_jobs(jobs: serde_json::Value) -> MockCollector {
+        MockCollector::new("scheduled_job").with_item("aide", json!({ "jobs": jobs }))
+    }
+
+    #[test]
+    fn test_interval_checked_per_job() {
+        let daily = json!({
+            "source": "/etc/cron.d/aide", "line": 3, "schedule": "0 5 * * *",
+            "command": "/usr/sbin/aide --check", "user": "root", "enabled": true,
+            "user_exists": true, "max_interval_hours": 24
+        });
+        let states = r#"[{"id": "daily", "fields": [
+            {"name": "max_interval_hours", "type": "int", "op": "<=", "value": 24},
+            {"name": "enabled", "type": "boolean", "op": "=", "value": true}
+        ]}]"#;
+        let result = run(
+            &aide_jobs(json!([daily.clone()])),
+            states,
+            "at_least_one all",
+        );
+        assert_eq!(result.status, ComplianceStatus::Pass, "{}", result.message);
+        assert_eq!(
+            result.state_results[0].object_id,
+            "aide[/etc/cron.d/aide:3]"
+        );
+
+        // An @reboot job has no interval, so it cannot satisfy the bound
+        let reboot = json!({
+            "source": "crontab -u root", "line": 1, "schedule": "@reboot",
+            "command": "/usr/sbin/aide --init", "user": "root", "enabled": true,
+            "user_exists": true
+        });
+        let result = run(&aide_jobs(json!([daily, reboot])), states, "all all");
+        assert_eq!(result.status, ComplianceStatus::Fail);
+        assert!(
+            result.message.contains("not collected"),
+            "{}",
+            result.message
+        );
+    }
+
+    #[test]
+    fn test_matching_job_fails_none() {
+        assert_eq!(
+            run(&aide_jobs(json!([])), "[]", "none all").status,
+            ComplianceStatus::Pass
+        );
+
+        let timer = json!({
+            "source": "aide-check.timer", "line": 0,
+            "schedule": "OnCalendar=*-*-* 04:00:00", "command": "/usr/sbin/aide --check",
+            "user": "root", "enabled": false, "user_exists": true, "max_interval_hours": 24
+        });
+        let result = run(&aide_jobs(json!([timer])), "[]", "none all");
+        assert_eq!(result.status, ComplianceStatus::Fail);
+        assert_eq!(result.details["jobs"][0], "aide[aide-check.timer]");
+    }
+}
@@ -0,0 +1,151 @@
+//! SELinux boolean executor
+//!
+//! Validates SELinux boolean values read with `getsebool -a`. A boolean the
+//! policy does not define does not exist, so `TEST all ...` fails for it.
+//!
+//! When SELinux is disabled there are no booleans to check, and the
+//! criterion is reported as not applicable rather than passed or failed.
+
+use super::{existence_failure, field_matches, field_result, validate_items, ItemNames};
+use esp_scanner_base::strategies::{
+    CollectedData, CtnContract, CtnExecutionError, CtnExecutionResult, CtnExecutor,
+};
+use esp_scanner_base::types::common::ResolvedValue;
+use esp_scanner_base::types::execution_context::ExecutableCriterion;
+use std::collections::HashMap;
+
+pub struct SelinuxBooleanExecutor {
+    contract: CtnContract,
+}
+
+const NAMES: ItemNames = ItemNames {
+    item: "Boolean",
+    items: "booleans",
+    subject: "SELinux boolean validation",
+};
+
+impl SelinuxBooleanExecutor {
+    pub fn new(contract: CtnContract) -> Self {
+        Self { contract }
+    }
+}
+
+/// Whether the collector found SELinux enabled on the host
+fn selinux_enabled(data: &CollectedData) -> bool {
+    !matches!(
+        data.get_field("selinux_enabled"),
+        Some(ResolvedValue::Boolean(false))
+    )
+}
+
+impl CtnExecutor for SelinuxBooleanExecutor {
+    fn execute_with_contract(
+        &self,
+        criterion: &ExecutableCriterion,
+        collected_data: &HashMap<String, CollectedData>,
+        _contract: &CtnContract,
+    ) -> Result<CtnExecutionResult, CtnExecutionError> {
+        if collected_data.values().any(|data| !selinux_enabled(data)) {
+            return Ok(CtnExecutionResult::not_applicable(
+                criterion.criterion_type.clone(),
+                "SELinux is disabled",
+            ));
+        }
+
+        let present: Vec<(&String, &CollectedData)> = collected_data
+            .iter()
+            .filter(|(_, data)| {
+                matches!(data.get_field("found"), Some(ResolvedValue::Boolean(true)))
+            })
+            .collect();
+
+        let objects_expected = criterion.expected_object_count();
+        if let Some(result) =
+            existence_failure(criterion, NAMES.items, present.len(), objects_expected)
+        {
+            return Ok(result);
+        }
+
+        let results = validate_items(
+            criterion,
+            &self.contract,
+            &NAMES,
+            present,
+            |data: &CollectedData, field, name| {
+                field_result(field, data.get_field(name).cloned(), field_matches)
+            },
+        );
+        Ok(results.into_result(criterion, &NAMES))
+    }
+
+    fn get_ctn_contract(&self) -> CtnContract {
+        self.contract.clone()
+    }
+
+    fn ctn_type(&self) -> &str {
+        "selinux_boolean"
+    }
+
+    fn validate_collected_data(
+        &self,
+        _collected_data: &HashMap<String, CollectedData>,
+        _contract: &CtnContract,
+    ) -> Result<(), CtnExecutionError> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contracts::create_selinux_boolean_contract;
+    use esp_scanner_base::strategies::ComplianceStatus;
+    use esp_scanner_base::testkit::{CriterionFixture, MockCollector};
+    use serde_json::json;
+
+    const EXECHEAP: &str =
+        r#"{"id": "execheap", "fields": {"boolean_name": "selinuxuser_execheap"}}"#;
+
+    const OFF: &str = r#"[{"id": "off", "fields": [
+        {"name": "value", "type": "boolean", "op": "=", "value": false}
+    ]}]"#;
+
+    fn run(data: serde_json::Value) -> CtnExecutionResult {
+        let collector = MockCollector::new("selinux_boolean").with_item("execheap", data);
+        let executor = SelinuxBooleanExecutor::new(create_selinux_boolean_contract());
+        CriterionFixture::from_json(EXECHEAP, OFF)
+            .and_then(|fixture| fixture.run(&collector, &executor))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_boolean_value() {
+        let boolean = |value: bool| {
+            json!({
+                "boolean_name": "selinuxuser_execheap",
+                "selinux_enabled": true,
+                "found": true,
+                "value": value
+            })
+        };
+        assert_eq!(run(boolean(false)).status, ComplianceStatus::Pass);
+        assert_eq!(run(boolean(true)).status, ComplianceStatus::Fail);
+
+        let undefined = run(json!({
+            "boolean_name": "selinuxuser_execheap",
+            "selinux_enabled": true,
+            "found": false
+        }));
+        assert_eq!(undefined.status, ComplianceStatus::Fail);
+    }
+
+    #[test]
+    fn test_selinux_disabled_is_not_applicable() {
+        let result = run(json!({
+            "boolean_name": "selinuxuser_execheap",
+            "selinux_enabled": false
+        }));
+        assert_eq!(result.status, ComplianceStatus::NotApplicable);
+        assert!(!result.is_not_evaluated());
+    }
+}
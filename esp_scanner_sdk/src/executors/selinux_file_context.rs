@@ -0,0 +1,163 @@
+//! SELinux file context executor
+//!
+//! Validates the security context of files: `user`, `role`, `type` and
+//! `level`, or the whole `context`. A missing path does not exist, so
+//! `TEST all ...` fails for it; an unlabeled file exists without context
+//! fields, so any field check fails for it.
+//!
+//! When SELinux is disabled files carry no contexts, and the criterion is
+//! reported as not applicable rather than passed or failed.
+
+use super::{existence_failure, field_matches, field_result, validate_items, ItemNames};
+use esp_scanner_base::strategies::{
+    CollectedData, CtnContract, CtnExecutionError, CtnExecutionResult, CtnExecutor,
+};
+use esp_scanner_base::types::common::ResolvedValue;
+use esp_scanner_base::types::execution_context::ExecutableCriterion;
+use std::collections::HashMap;
+
+pub struct SelinuxFileContextExecutor {
+    contract: CtnContract,
+}
+
+const NAMES: ItemNames = ItemNames {
+    item: "Path",
+    items: "paths",
+    subject: "SELinux file context validation",
+};
+
+impl SelinuxFileContextExecutor {
+    pub fn new(contract: CtnContract) -> Self {
+        Self { contract }
+    }
+}
+
+/// Whether the collector found SELinux enabled on the host
+fn selinux_enabled(data: &CollectedData) -> bool {
+    !matches!(
+        data.get_field("selinux_enabled"),
+        Some(ResolvedValue::Boolean(false))
+    )
+}
+
+impl CtnExecutor for SelinuxFileContextExecutor {
+    fn execute_with_contract(
+        &self,
+        criterion: &ExecutableCriterion,
+        collected_data: &HashMap<String, CollectedData>,
+        _contract: &CtnContract,
+    ) -> Result<CtnExecutionResult, CtnExecutionError> {
+        if collected_data.values().any(|data| !selinux_enabled(data)) {
+            return Ok(CtnExecutionResult::not_applicable(
+                criterion.criterion_type.clone(),
+                "SELinux is disabled",
+            ));
+        }
+
+        let present: Vec<(&String, &CollectedData)> = collected_data
+            .iter()
+            .filter(|(_, data)| {
+                matches!(data.get_field("exists"), Some(ResolvedValue::Boolean(true)))
+            })
+            .collect();
+
+        let objects_expected = criterion.expected_object_count();
+        if let Some(result) =
+            existence_failure(criterion, NAMES.items, present.len(), objects_expected)
+        {
+            return Ok(result);
+        }
+
+        let results = validate_items(
+            criterion,
+            &self.contract,
+            &NAMES,
+            present,
+            |data: &CollectedData, field, name| {
+                field_result(field, data.get_field(name).cloned(), field_matches)
+            },
+        );
+        Ok(results.into_result(criterion, &NAMES))
+    }
+
+    fn get_ctn_contract(&self) -> CtnContract {
+        self.contract.clone()
+    }
+
+    fn ctn_type(&self) -> &str {
+        "selinux_file_context"
+    }
+
+    fn validate_collected_data(
+        &self,
+        _collected_data: &HashMap<String, CollectedData>,
+        _contract: &CtnContract,
+    ) -> Result<(), CtnExecutionError> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contracts::create_selinux_file_context_contract;
+    use esp_scanner_base::strategies::ComplianceStatus;
+    use esp_scanner_base::testkit::{CriterionFixture, MockCollector};
+    use serde_json::json;
+
+    const SHADOW: &str = r#"{"id": "shadow", "fields": {"path": "/etc/shadow"}}"#;
+
+    fn run(data: serde_json::Value, states: &str) -> CtnExecutionResult {
+        let collector = MockCollector::new("selinux_file_context").with_item("shadow", data);
+        let executor = SelinuxFileContextExecutor::new(create_selinux_file_context_contract());
+        CriterionFixture::from_json(SHADOW, states)
+            .and_then(|fixture| fixture.run(&collector, &executor))
+            .unwrap()
+    }
+
+    fn labeled(context_type: &str) -> serde_json::Value {
+        json!({
+            "path": "/etc/shadow",
+            "selinux_enabled": true,
+            "exists": true,
+            "context": format!("system_u:object_r:{}:s0", context_type),
+            "user": "system_u",
+            "role": "object_r",
+            "type": context_type,
+            "level": "s0"
+        })
+    }
+
+    const SHADOW_T: &str = r#"[{"id": "shadow_t", "fields": [
+        {"name": "user", "type": "string", "op": "=", "value": "system_u"},
+        {"name": "type", "type": "string", "op": "=", "value": "shadow_t"},
+        {"name": "level", "type": "string", "op": "starts", "value": "s0"}
+    ]}]"#;
+
+    #[test]
+    fn test_context_fields() {
+        assert_eq!(
+            run(labeled("shadow_t"), SHADOW_T).status,
+            ComplianceStatus::Pass
+        );
+        let result = run(labeled("etc_t"), SHADOW_T);
+        assert_eq!(result.status, ComplianceStatus::Fail);
+        assert!(result.message.contains("'type'"));
+
+        let unlabeled = json!({"path": "/etc/shadow", "selinux_enabled": true, "exists": true});
+        assert_eq!(run(unlabeled, SHADOW_T).status, ComplianceStatus::Fail);
+    }
+
+    #[test]
+    fn test_missing_path_and_disabled_selinux() {
+        let missing = json!({"path": "/etc/shadow", "selinux_enabled": true, "exists": false});
+        assert_eq!(run(missing, SHADOW_T).status, ComplianceStatus::Fail);
+
+        let disabled = run(
+            json!({"path": "/etc/shadow", "selinux_enabled": false}),
+            SHADOW_T,
+        );
+        assert_eq!(disabled.status, ComplianceStatus::NotApplicable);
+        assert!(!disabled.is_not_evaluated());
+    }
+}
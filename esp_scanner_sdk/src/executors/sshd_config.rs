@@ -0,0 +1,258 @@
+//! sshd_config executor
+//!
+//! Validates OpenSSH server directives resolved by the file system collector.
+//! State field names are directive keywords and are matched without regard
+//! to case.
+//!
+//! The global section is checked as the object itself. Unless the object has
+//! the `global_only` behavior, every `Match` block is an additional item named
+//! `object_id[Match <criteria>]`, checked against the block's directives with
+//! the global section filling the gaps, so `TEST all all` requires every
+//! connection context to comply. Match blocks do not count towards the
+//! existence check.
+//!
+//! A directive the config never sets is reported as not collected; sshd's
+//! built-in defaults are not assumed.
+
+use super::{
+    compare_field, compare_value, existence_failure, field_result, validate_items, ItemNames,
+};
+use esp_scanner_base::execution::{collection_items, record_text};
+use esp_scanner_base::strategies::{
+    CollectedData, CtnContract, CtnExecutionError, CtnExecutionResult, CtnExecutor,
+    FieldValidationResult,
+};
+use esp_scanner_base::types::common::{RecordData, ResolvedValue};
+use esp_scanner_base::types::execution_context::{ExecutableCriterion, ExecutableStateField};
+use std::collections::HashMap;
+
+pub struct SshdConfigExecutor {
+    contract: CtnContract,
+}
+
+const NAMES: ItemNames = ItemNames {
+    item: "Config",
+    items: "sections",
+    subject: "sshd_config validation",
+};
+
+impl SshdConfigExecutor {
+    pub fn new(contract: CtnContract) -> Self {
+        Self { contract }
+    }
+}
+
+/// Directive values are text; an integer field compares the parsed number
+fn directive_matches(field: &ExecutableStateField, actual: &ResolvedValue) -> bool {
+    compare_field(field, actual, |expected| match (expected, actual) {
+        (ResolvedValue::Integer(_), ResolvedValue::String(s)) => s
+            .trim()
+            .parse::<i64>()
+            .is_ok_and(|n| compare_value(expected, &ResolvedValue::Integer(n), field.operation)),
+        _ => compare_value(expected, actual, field.operation),
+    })
+}
+
+/// The global section, or one `Match` block
+enum ConfigItem<'a> {
+    Global(&'a RecordData),
+    Match(&'a RecordData),
+}
+
+impl ConfigItem<'_> {
+    fn directive(&self, keyword: &str) -> Option<ResolvedValue> {
+        let keyword = keyword.to_ascii_lowercase();
+        let value = match self {
+            Self::Global(directives) => directives.get_field_by_path(&keyword)?,
+            Self::Match(block) => block.get_field_by_path(&format!("directives.{}", keyword))?,
+        };
+        value.as_str().map(|s| ResolvedValue::String(s.to_string()))
+    }
+}
+
+/// Expand collected configs into the global section and their Match blocks
+fn config_items(collected_data: &HashMap<String, CollectedData>) -> Vec<(String, ConfigItem<'_>)> {
+    let mut items: Vec<(String, ConfigItem<'_>)> =
+        collection_items(collected_data, "match_blocks", |_, record| {
+            format!("Match {}", record_text(record, "criteria"))
+        })
+        .into_iter()
+        .map(|(item_id, record)| (item_id, ConfigItem::Match(record)))
+        .collect();
+    for (object_id, data) in collected_data {
+        if let Some(ResolvedValue::RecordData(directives)) = data.get_field("directives") {
+            items.push((object_id.clone(), ConfigItem::Global(directives)));
+        }
+    }
+    items.sort_by(|a, b| a.0.cmp(&b.0));
+    items
+}
+
+impl CtnExecutor for SshdConfigExecutor {
+    fn execute_with_contract(
+        &self,
+        criterion: &ExecutableCriterion,
+        collected_data: &HashMap<String, CollectedData>,
+        _contract: &CtnContract,
+    ) -> Result<CtnExecutionResult, CtnExecutionError> {
+        let objects_expected = criterion.expected_object_count();
+        if let Some(result) =
+            existence_failure(criterion, "configs", collected_data.len(), objects_expected)
+        {
+            return Ok(result);
+        }
+
+        let items = config_items(collected_data);
+        let results = validate_items(
+            criterion,
+            &self.contract,
+            &NAMES,
+            items.iter().map(|(item_id, item)| (item_id, item)),
+            |item: &ConfigItem<'_>, field, name| match item.directive(name) {
+                Some(actual) => field_result(field, Some(actual), directive_matches),
+                None => FieldValidationResult::not_present(
+                    field.name.clone(),
+                    field.value.clone(),
+                    field.operation,
+                    format!("Directive '{}' not set", field.name),
+                ),
+            },
+        );
+        Ok(results.into_result(criterion, &NAMES))
+    }
+
+    fn get_ctn_contract(&self) -> CtnContract {
+        self.contract.clone()
+    }
+
+    fn ctn_type(&self) -> &str {
+        "sshd_config"
+    }
+
+    fn validate_collected_data(
+        &self,
+        _collected_data: &HashMap<String, CollectedData>,
+        _contract: &CtnContract,
+    ) -> Result<(), CtnExecutionError> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contracts::create_sshd_config_contract;
+    use esp_scanner_base::strategies::{ComplianceStatus, FieldOutcome};
+    use esp_scanner_base::testkit::{check_golden, CriterionFixture, MockCollector};
+    use serde_json::json;
+
+    const SSHD: &str = r#"{"id": "sshd", "fields": {"path": "/etc/ssh/sshd_config"}}"#;
+
+    const NO_PASSWORDS: &str = r#"[{"id": "no_passwords", "fields": [
+        {"name": "PasswordAuthentication", "type": "string", "op": "=", "value": "no"}
+    ]}]"#;
+
+    fn config() -> MockCollector {
+        MockCollector::new("sshd_config").with_item(
+            "sshd",
+            json!({
+                "directives": {"passwordauthentication": "no", "maxauthtries": "4"},
+                "match_blocks": [{
+                    "criteria": "User backup",
+                    "directives": {"passwordauthentication": "yes", "maxauthtries": "4"}
+                }]
+            }),
+        )
+    }
+
+    fn run(collector: &MockCollector, states: &str) -> CtnExecutionResult {
+        let executor = SshdConfigExecutor::new(create_sshd_config_contract());
+        CriterionFixture::from_json(SSHD, states)
+            .and_then(|fixture| fixture.run(collector, &executor))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_match_blocks_are_checked_as_items() {
+        check_golden(
+            &run(&config(), NO_PASSWORDS),
+            json!({
+                "status": "fail",
+                "objects": {
+                    "sshd": {"passed": true, "fields": [
+                        {"field": "PasswordAuthentication", "outcome": "pass",
+                         "expected": "no", "actual": "no"}
+                    ]},
+                    "sshd[Match User backup]": {"passed": false, "fields": [
+                        {"field": "PasswordAuthentication", "outcome": "fail",
+                         "expected": "no", "actual": "yes"}
+                    ]}
+                }
+            }),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_directive_names_ignore_case_and_compare_as_integers() {
+        let states = r#"[{"id": "limits", "fields": [
+            {"name": "maxAuthTries", "type": "int", "op": "<=", "value": 4}
+        ]}]"#;
+        let result = run(&config(), states);
+        assert_eq!(result.status, ComplianceStatus::Pass);
+        assert_eq!(result.state_results.len(), 2);
+    }
+
+    #[test]
+    fn test_glob_and_ignore_case_operations() {
+        let states = r#"[{"id": "passwords", "fields": [
+            {"name": "PasswordAuthentication", "type": "string", "op": "equals_ignore_case", "value": "NO"},
+            {"name": "PasswordAuthentication", "type": "string", "op": "glob_match", "value": "[nN]?"}
+        ]}]"#;
+        let result = run(&config(), states);
+        // The global section passes; the Match block allows passwords
+        assert_eq!(result.status, ComplianceStatus::Fail);
+        assert!(result.state_results[0].combined_result);
+        assert!(!result.state_results[1].combined_result);
+
+        let states = r#"[{"id": "passwords", "fields": [
+            {"name": "PasswordAuthentication", "type": "string", "op": "not_glob_match", "value": "y*"}
+        ]}]"#;
+        assert_eq!(run(&config(), states).status, ComplianceStatus::Fail);
+    }
+
+    #[test]
+    fn test_unset_directive_is_not_a_pass() {
+        let states = r#"[{"id": "root", "fields": [
+            {"name": "PermitRootLogin", "type": "string", "op": "=", "value": "no"}
+        ]}]"#;
+        let result = run(&config(), states);
+        assert_eq!(result.status, ComplianceStatus::Fail);
+        assert_eq!(
+            result.state_results[0].state_results[0].outcome,
+            FieldOutcome::Unknown
+        );
+    }
+
+    #[test]
+    fn test_list_valued_expectations_apply_the_entity_check() {
+        // A list-valued VAR resolves to a collection; every item must hold
+        let states = r#"[{"id": "limits", "fields": [
+            {"name": "PasswordAuthentication", "type": "string", "op": "!=",
+             "value": ["yes", "without-password"]},
+            {"name": "MaxAuthTries", "type": "int", "op": "<=", "value": [4, 6]}
+        ]}]"#;
+        let result = run(&config(), states);
+        assert_eq!(result.status, ComplianceStatus::Fail);
+        assert!(result.state_results[0].combined_result);
+        assert!(!result.state_results[1].combined_result);
+
+        let either = r#"[{"id": "either", "fields": [
+            {"name": "PasswordAuthentication", "type": "string", "op": "=",
+             "value": ["no", "yes"], "entity_check": "at_least_one"}
+        ]}]"#;
+        assert_eq!(run(&config(), either).status, ComplianceStatus::Pass);
+        let both = either.replace(r#", "entity_check": "at_least_one""#, "");
+        assert_eq!(run(&config(), &both).status, ComplianceStatus::Fail);
+    }
+}
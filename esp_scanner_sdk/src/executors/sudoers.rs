@@ -0,0 +1,182 @@
+//! sudoers executor
+//!
+//! Validates a sudoers policy parsed by the file system collector.
+//!
+//! Every object expands into the entries of its `section`, each a separate
+//! item. User specifications are named `object_id[user host=(runas)
+//! command]`, so "no entry grants `NOPASSWD: ALL`" is `TEST any all OR`
+//! with `nopasswd = false` and `command != ALL`. Defaults are named
+//! `object_id[Defaults<binding> name]`; "`use_pty` is set" is `TEST
+//! at_least_one at_least_one` with `name = use_pty` and `enabled = true`.
+//!
+//! A field from the other section is reported as not collected.
+
+use super::{existence_failure, field_matches, field_result, validate_items, ItemNames};
+use esp_scanner_base::execution::{collection_items, record_field, record_text};
+use esp_scanner_base::strategies::{
+    CollectedData, CtnContract, CtnExecutionError, CtnExecutionResult, CtnExecutor,
+};
+use esp_scanner_base::types::common::{RecordData, ResolvedValue};
+use esp_scanner_base::types::execution_context::ExecutableCriterion;
+use std::collections::HashMap;
+
+pub struct SudoersExecutor {
+    contract: CtnContract,
+}
+
+const NAMES: ItemNames = ItemNames {
+    item: "Entry",
+    items: "entries",
+    subject: "sudoers validation",
+};
+
+impl SudoersExecutor {
+    pub fn new(contract: CtnContract) -> Self {
+        Self { contract }
+    }
+}
+
+impl CtnExecutor for SudoersExecutor {
+    fn execute_with_contract(
+        &self,
+        criterion: &ExecutableCriterion,
+        collected_data: &HashMap<String, CollectedData>,
+        _contract: &CtnContract,
+    ) -> Result<CtnExecutionResult, CtnExecutionError> {
+        let items = collection_items(collected_data, "entries", |data, record| {
+            let text = |name: &str| record_text(record, name);
+            let defaults = matches!(
+                data.get_field("section"),
+                Some(ResolvedValue::String(section)) if section == "defaults"
+            );
+            if defaults {
+                format!("Defaults{} {}", text("binding"), text("name"))
+            } else {
+                format!(
+                    "{} {}=({}) {}",
+                    text("user_spec"),
+                    text("host"),
+                    text("runas"),
+                    text("command")
+                )
+            }
+        });
+        let objects_expected = criterion
+            .expected_object_count()
+            .saturating_sub(collected_data.len())
+            + items.len();
+        if let Some(mut result) =
+            existence_failure(criterion, "sudoers entries", items.len(), objects_expected)
+        {
+            let entries: Vec<&str> = items.iter().map(|(item, _)| item.as_str()).collect();
+            result.details = serde_json::json!({ "entries": entries });
+            return Ok(result);
+        }
+
+        let results = validate_items(
+            criterion,
+            &self.contract,
+            &NAMES,
+            items.iter().map(|(item, record)| (item, *record)),
+            |record: &RecordData, field, name| {
+                field_result(field, record_field(record, name), field_matches)
+            },
+        );
+        Ok(results.into_result(criterion, &NAMES))
+    }
+
+    fn get_ctn_contract(&self) -> CtnContract {
+        self.contract.clone()
+    }
+
+    fn ctn_type(&self) -> &str {
+        "sudoers"
+    }
+
+    fn validate_collected_data(
+        &self,
+        _collected_data: &HashMap<String, CollectedData>,
+        _contract: &CtnContract,
+    ) -> Result<(), CtnExecutionError> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contracts::create_sudoers_contract;
+    use esp_scanner_base::strategies::ComplianceStatus;
+    use esp_scanner_base::testkit::{CriterionFixture, MockCollector};
+    use serde_json::json;
+
+    fn sudoers(section: &str, entries: serde_json::Value) -> MockCollector {
+        MockCollector::new("sudoers")
+            .with_item("policy", json!({ "section": section, "entries": entries }))
+    }
+
+    fn run(collector: &MockCollector, states: &str, test: &str) -> CtnExecutionResult {
+        let executor = SudoersExecutor::new(create_sudoers_contract());
+        CriterionFixture::from_json(
+            r#"{"id": "policy", "fields": {"path": "/etc/sudoers"}}"#,
+            states,
+        )
+        .and_then(|fixture| fixture.with_test(test))
+        .and_then(|fixture| fixture.run(collector, &executor))
+        .unwrap()
+    }
+
+    fn grant(user: &str, command: &str, nopasswd: bool) -> serde_json::Value {
+        json!({
+            "user_spec": user, "host": "ALL", "runas": "ALL:ALL",
+            "command": command, "nopasswd": nopasswd
+        })
+    }
+
+    #[test]
+    fn test_no_nopasswd_all() {
+        let states = r#"[{"id": "no_nopasswd_all", "fields": [
+            {"name": "nopasswd", "type": "boolean", "op": "=", "value": false},
+            {"name": "command", "type": "string", "op": "!=", "value": "ALL"}
+        ]}]"#;
+
+        let compliant = sudoers(
+            "user_spec",
+            json!([
+                grant("%wheel", "ALL", false),
+                grant("backup", "/usr/bin/rsync", true)
+            ]),
+        );
+        let result = run(&compliant, states, "any all OR");
+        assert_eq!(result.status, ComplianceStatus::Pass);
+
+        let open = sudoers("user_spec", json!([grant("ops", "ALL", true)]));
+        let result = run(&open, states, "any all OR");
+        assert_eq!(result.status, ComplianceStatus::Fail);
+        assert!(result.message.contains("policy[ops ALL=(ALL:ALL) ALL]"));
+    }
+
+    #[test]
+    fn test_defaults_use_pty() {
+        let states = r#"[{"id": "use_pty", "fields": [
+            {"name": "name", "type": "string", "op": "=", "value": "use_pty"},
+            {"name": "enabled", "type": "boolean", "op": "=", "value": true}
+        ]}]"#;
+        let defaults = |enabled: bool| {
+            sudoers(
+                "defaults",
+                json!([
+                    {"binding": "", "name": "logfile", "value": "/var/log/sudo.log", "enabled": true},
+                    {"binding": "", "name": "use_pty", "value": "", "enabled": enabled}
+                ]),
+            )
+        };
+
+        let result = run(&defaults(true), states, "at_least_one at_least_one");
+        assert_eq!(result.status, ComplianceStatus::Pass);
+
+        let result = run(&defaults(false), states, "at_least_one at_least_one");
+        assert_eq!(result.status, ComplianceStatus::Fail);
+        assert!(result.message.contains("policy[Defaults use_pty]"));
+    }
+}
@@ -2,12 +2,13 @@
 //!
 //! Validates kernel parameter values.
 
+use super::{compare_field, match_value_list};
 use esp_scanner_base::execution::{
     evaluate_existence_check, evaluate_item_check, evaluate_state_operator,
 };
 use esp_scanner_base::strategies::{
     CollectedData, ComplianceStatus, CtnContract, CtnExecutionError, CtnExecutionResult,
-    CtnExecutor, FieldValidationResult, StateValidationResult, TestPhase,
+    CtnExecutor, FieldOutcome, FieldValidationResult, StateValidationResult, TestPhase,
 };
 use esp_scanner_base::types::common::{Operation, ResolvedValue};
 use esp_scanner_base::types::execution_context::ExecutableCriterion;
@@ -28,6 +29,10 @@ impl SysctlParameterExecutor {
         actual: &ResolvedValue,
         operation: Operation,
     ) -> bool {
+        if let Some(matched) = match_value_list(expected, actual, operation) {
+            return matched;
+        }
+
         match (expected, actual, operation) {
             (ResolvedValue::String(exp), ResolvedValue::String(act), Operation::Equals) => {
                 exp == act
@@ -108,20 +113,20 @@ impl CtnExecutor for SysctlParameterExecutor {
                         Some(v) => v.clone(),
                         None => {
                             let msg = format!("Field '{}' not collected", field.name);
-                            all_field_results.push(FieldValidationResult {
-                                field_name: field.name.clone(),
-                                expected_value: field.value.clone(),
-                                actual_value: ResolvedValue::String("".to_string()),
-                                operation: field.operation,
-                                passed: false,
-                                message: msg.clone(),
-                            });
+                            all_field_results.push(FieldValidationResult::not_present(
+                                field.name.clone(),
+                                field.value.clone(),
+                                field.operation,
+                                msg.clone(),
+                            ));
                             failure_messages.push(format!("Parameter '{}': {}", object_id, msg));
                             continue;
                         }
                     };
 
-                    let passed = self.compare_values(&field.value, &actual_value, field.operation);
+                    let passed = compare_field(field, &actual_value, |expected| {
+                        self.compare_values(expected, &actual_value, field.operation)
+                    });
 
                     let msg = if passed {
                         format!("Field '{}' passed", field.name)
@@ -143,6 +148,7 @@ impl CtnExecutor for SysctlParameterExecutor {
                         operation: field.operation,
                         passed,
                         message: msg,
+                        outcome: FieldOutcome::from_passed(passed),
                     });
                 }
             }
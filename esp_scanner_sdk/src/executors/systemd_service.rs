@@ -1,15 +1,32 @@
 //! Systemd service executor
 //!
 //! Validates systemd service status (active, enabled, loaded).
+//!
+//! A template glob such as `getty@*.service` is evaluated as one item per
+//! discovered instance. Each instance counts as both an expected and a found
+//! object, so the existence check sees the instances rather than the glob:
+//!
+//! | instances | all / any / at_least_one | none | only_one |
+//! |-----------|--------------------------|------|----------|
+//! | 0         | fail                     | pass | fail     |
+//! | 1         | pass                     | fail | pass     |
+//! | 2+        | pass                     | fail | fail     |
+//!
+//! Named units in the same criterion are counted as usual alongside them.
+//!
+//! Record checks in a state are evaluated against the unit properties queried
+//! with the `properties` parameter. Template instances carry no properties.
 
+use super::{compare_field, match_value_list};
 use esp_scanner_base::execution::{
-    evaluate_existence_check, evaluate_item_check, evaluate_state_operator,
+    evaluate_existence_check, evaluate_state_operator, evaluate_test_item_check, record_field,
+    record_text, validate_record_checks, ComparisonExt,
 };
 use esp_scanner_base::strategies::{
     CollectedData, ComplianceStatus, CtnContract, CtnExecutionError, CtnExecutionResult,
-    CtnExecutor, FieldValidationResult, StateValidationResult, TestPhase,
+    CtnExecutor, FieldOutcome, FieldValidationResult, StateValidationResult, TestPhase,
 };
-use esp_scanner_base::types::common::{Operation, ResolvedValue};
+use esp_scanner_base::types::common::{Operation, RecordData, ResolvedValue};
 use esp_scanner_base::types::execution_context::ExecutableCriterion;
 use std::collections::HashMap;
 
@@ -28,6 +45,10 @@ impl SystemdServiceExecutor {
         actual: &ResolvedValue,
         operation: Operation,
     ) -> bool {
+        if let Some(matched) = match_value_list(expected, actual, operation) {
+            return matched;
+        }
+
         match (expected, actual, operation) {
             (ResolvedValue::Boolean(exp), ResolvedValue::Boolean(act), Operation::Equals) => {
                 exp == act
@@ -35,11 +56,81 @@ impl SystemdServiceExecutor {
             (ResolvedValue::Boolean(exp), ResolvedValue::Boolean(act), Operation::NotEqual) => {
                 exp != act
             }
+            (ResolvedValue::String(_), ResolvedValue::String(_), _) => {
+                actual.compare_with(expected, operation).unwrap_or(false)
+            }
             _ => false,
         }
     }
 }
 
+/// A named unit, or one instance of a template glob
+enum ServiceItem<'a> {
+    Unit(&'a CollectedData),
+    Instance(&'a RecordData),
+}
+
+impl ServiceItem<'_> {
+    fn field(&self, name: &str) -> Option<ResolvedValue> {
+        match self {
+            Self::Unit(data) => data.get_field(name).cloned(),
+            Self::Instance(record) => record_field(record, name),
+        }
+    }
+}
+
+impl ServiceItem<'_> {
+    /// Unit properties queried through the `properties` parameter
+    fn properties(&self) -> Option<&RecordData> {
+        match self {
+            Self::Unit(data) => match data.get_field("properties")? {
+                ResolvedValue::RecordData(record) => Some(record),
+                _ => None,
+            },
+            Self::Instance(_) => None,
+        }
+    }
+}
+
+/// Advisory when a unit's runtime state and boot state disagree
+fn boot_state_note(item: &ServiceItem<'_>) -> Option<String> {
+    match (item.field("active")?, item.field("enabled")?) {
+        (ResolvedValue::Boolean(true), ResolvedValue::Boolean(false)) => {
+            Some("Service is running but not enabled; it will not start at boot".to_string())
+        }
+        (ResolvedValue::Boolean(false), ResolvedValue::Boolean(true)) => {
+            Some("Service is enabled but not running".to_string())
+        }
+        _ => None,
+    }
+}
+
+/// Expand collected objects into items, returning the number of template
+/// objects that were replaced by their instances
+fn service_items(
+    collected_data: &HashMap<String, CollectedData>,
+) -> (Vec<(String, ServiceItem<'_>)>, usize) {
+    let mut items = Vec::new();
+    let mut templates = 0;
+    for (object_id, data) in collected_data {
+        match data.get_field("instances") {
+            Some(ResolvedValue::Collection(instances)) => {
+                templates += 1;
+                for instance in instances {
+                    if let ResolvedValue::RecordData(record) = instance {
+                        items.push((
+                            format!("{}[{}]", object_id, record_text(record, "service_name")),
+                            ServiceItem::Instance(record),
+                        ));
+                    }
+                }
+            }
+            _ => items.push((object_id.clone(), ServiceItem::Unit(data))),
+        }
+    }
+    (items, templates)
+}
+
 impl CtnExecutor for SystemdServiceExecutor {
     fn execute_with_contract(
         &self,
@@ -49,8 +140,14 @@ impl CtnExecutor for SystemdServiceExecutor {
     ) -> Result<CtnExecutionResult, CtnExecutionError> {
         let test_spec = &criterion.test;
 
-        let objects_expected = criterion.expected_object_count();
-        let objects_found = collected_data.len();
+        let (items, templates) = service_items(collected_data);
+        let instances = items
+            .iter()
+            .filter(|(_, item)| matches!(item, ServiceItem::Instance(_)))
+            .count();
+        let objects_expected =
+            criterion.expected_object_count().saturating_sub(templates) + instances;
+        let objects_found = collected_data.len() - templates + instances;
 
         let existence_passed =
             evaluate_existence_check(test_spec.existence_check, objects_found, objects_expected);
@@ -67,8 +164,12 @@ impl CtnExecutor for SystemdServiceExecutor {
 
         let mut state_results = Vec::new();
         let mut failure_messages = Vec::new();
+        let mut advisories = Vec::new();
 
-        for (object_id, data) in collected_data {
+        for (object_id, item) in &items {
+            if let Some(note) = boot_state_note(item) {
+                advisories.push((object_id.clone(), note));
+            }
             let mut all_field_results = Vec::new();
 
             for state in &criterion.states {
@@ -82,24 +183,24 @@ impl CtnExecutor for SystemdServiceExecutor {
                         .cloned()
                         .unwrap_or_else(|| field.name.clone());
 
-                    let actual_value = match data.get_field(&data_field_name) {
-                        Some(v) => v.clone(),
+                    let actual_value = match item.field(&data_field_name) {
+                        Some(v) => v,
                         None => {
                             let msg = format!("Field '{}' not collected", field.name);
-                            all_field_results.push(FieldValidationResult {
-                                field_name: field.name.clone(),
-                                expected_value: field.value.clone(),
-                                actual_value: ResolvedValue::Boolean(false),
-                                operation: field.operation,
-                                passed: false,
-                                message: msg.clone(),
-                            });
+                            all_field_results.push(FieldValidationResult::not_present(
+                                field.name.clone(),
+                                field.value.clone(),
+                                field.operation,
+                                msg.clone(),
+                            ));
                             failure_messages.push(format!("Service '{}': {}", object_id, msg));
                             continue;
                         }
                     };
 
-                    let passed = self.compare_values(&field.value, &actual_value, field.operation);
+                    let passed = compare_field(field, &actual_value, |expected| {
+                        self.compare_values(expected, &actual_value, field.operation)
+                    });
 
                     let msg = if passed {
                         format!("Field '{}' passed", field.name)
@@ -121,6 +222,46 @@ impl CtnExecutor for SystemdServiceExecutor {
                         operation: field.operation,
                         passed,
                         message: msg,
+                        outcome: FieldOutcome::from_passed(passed),
+                    });
+                }
+
+                if state.record_checks.is_empty() {
+                    continue;
+                }
+                let Some(properties) = item.properties() else {
+                    let msg = "Field 'properties' not collected; set the `properties` parameter"
+                        .to_string();
+                    all_field_results.push(FieldValidationResult::not_present(
+                        "properties".to_string(),
+                        ResolvedValue::String(String::new()),
+                        Operation::Equals,
+                        msg.clone(),
+                    ));
+                    failure_messages.push(format!("Service '{}': {}", object_id, msg));
+                    continue;
+                };
+                let record_results = validate_record_checks(properties, &state.record_checks)
+                    .map_err(|e| CtnExecutionError::ExecutionFailed {
+                        ctn_type: criterion.criterion_type.clone(),
+                        reason: format!("Record validation failed: {}", e),
+                    })?;
+                for r in record_results {
+                    if !r.passed {
+                        failure_messages.push(format!("Service '{}': {}", object_id, r.message));
+                    }
+                    all_field_results.push(FieldValidationResult {
+                        field_name: r.field_path,
+                        expected_value: ResolvedValue::String(r.expected.unwrap_or_default()),
+                        outcome: match (&r.actual, r.passed) {
+                            _ if r.undetermined.is_some() => FieldOutcome::Unknown,
+                            (None, false) => FieldOutcome::Unknown,
+                            (_, passed) => FieldOutcome::from_passed(passed),
+                        },
+                        actual_value: ResolvedValue::String(r.actual.unwrap_or_default()),
+                        operation: Operation::Equals,
+                        passed: r.passed,
+                        message: r.message,
                     });
                 }
             }
@@ -142,8 +283,12 @@ impl CtnExecutor for SystemdServiceExecutor {
         }
 
         let objects_passing = state_results.iter().filter(|r| r.combined_result).count();
-        let item_passed =
-            evaluate_item_check(test_spec.item_check, objects_passing, state_results.len());
+        let item_passed = evaluate_test_item_check(
+            test_spec.existence_check,
+            test_spec.item_check,
+            objects_passing,
+            state_results.len(),
+        );
 
         let final_status = if existence_passed && item_passed {
             ComplianceStatus::Pass
@@ -164,7 +309,7 @@ impl CtnExecutor for SystemdServiceExecutor {
             )
         };
 
-        Ok(CtnExecutionResult {
+        let mut result = CtnExecutionResult {
             ctn_type: criterion.criterion_type.clone(),
             status: final_status,
             test_phase: TestPhase::Complete,
@@ -177,7 +322,11 @@ impl CtnExecutor for SystemdServiceExecutor {
                 "objects_passing": objects_passing,
             }),
             execution_metadata: Default::default(),
-        })
+        };
+        for (object_id, note) in advisories {
+            result.add_advisory(object_id, note);
+        }
+        Ok(result)
     }
 
     fn get_ctn_contract(&self) -> CtnContract {
@@ -196,3 +345,144 @@ impl CtnExecutor for SystemdServiceExecutor {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contracts::create_systemd_service_contract;
+    use esp_scanner_base::testkit::{check_golden, CriterionFixture, MockCollector};
+    use serde_json::{json, Value};
+
+    const GETTYS: &str = r#"{"id": "gettys", "fields": {"service_name": "getty@*.service"}}"#;
+
+    const ACTIVE: &str = r#"[{"id": "running", "fields": [
+        {"name": "active", "type": "boolean", "op": "=", "value": true}
+    ]}]"#;
+
+    fn instance(unit: &str, active: bool) -> Value {
+        let name = unit.split_once('@').unwrap().1.trim_end_matches(".service");
+        json!({
+            "service_name": unit,
+            "instance": name,
+            "active": active,
+            "enabled": true,
+            "loaded": true,
+        })
+    }
+
+    fn gettys(instances: Vec<Value>) -> MockCollector {
+        MockCollector::new("systemd_service").with_item(
+            "gettys",
+            json!({"service_name": "getty@*.service", "instances": instances}),
+        )
+    }
+
+    fn run(
+        collector: &MockCollector,
+        object: &str,
+        test: &str,
+        states: &str,
+    ) -> CtnExecutionResult {
+        let executor = SystemdServiceExecutor::new(create_systemd_service_contract());
+        CriterionFixture::from_json(object, states)
+            .and_then(|fixture| fixture.with_test(test))
+            .and_then(|fixture| fixture.run(collector, &executor))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_template_instances_are_separate_items() {
+        let collector = gettys(vec![
+            instance("getty@tty1.service", true),
+            instance("getty@tty2.service", false),
+        ]);
+        check_golden(
+            &run(&collector, GETTYS, "all all", ACTIVE),
+            json!({
+                "status": "fail",
+                "objects": {
+                    "gettys[getty@tty1.service]": {"passed": true, "fields": [
+                        {"field": "active", "outcome": "pass", "expected": true, "actual": true}
+                    ]},
+                    "gettys[getty@tty2.service]": {"passed": false, "fields": [
+                        {"field": "active", "outcome": "fail", "expected": true, "actual": false}
+                    ]}
+                },
+                "advisories": [{
+                    "object_id": "gettys[getty@tty2.service]",
+                    "message": "Service is enabled but not running"
+                }]
+            }),
+        )
+        .unwrap();
+
+        // Instance names are exposed as a state field
+        let tty1 = r#"[{"id": "running", "fields": [
+            {"name": "active", "type": "boolean", "op": "=", "value": true},
+            {"name": "instance", "type": "string", "op": "=", "value": "tty1"}
+        ]}]"#;
+        let collector = gettys(vec![instance("getty@tty1.service", true)]);
+        let result = run(&collector, GETTYS, "only_one all", tty1);
+        assert_eq!(result.status, ComplianceStatus::Pass);
+    }
+
+    #[test]
+    fn test_enabled_but_stopped_instance_gets_advisory() {
+        let collector = gettys(vec![
+            instance("getty@tty1.service", true),
+            instance("getty@tty2.service", false),
+        ]);
+        let loaded = r#"[{"id": "loaded", "fields": [
+            {"name": "loaded", "type": "boolean", "op": "=", "value": true}
+        ]}]"#;
+        let result = run(&collector, GETTYS, "all all", loaded);
+
+        // The advisory does not turn the passing criterion into a failure
+        assert_eq!(result.status, ComplianceStatus::Pass);
+        assert_eq!(result.advisories().len(), 1);
+        let advisory = &result.advisories()[0];
+        assert_eq!(
+            advisory.object_id.as_deref(),
+            Some("gettys[getty@tty2.service]")
+        );
+        assert_eq!(advisory.message, "Service is enabled but not running");
+    }
+
+    #[test]
+    fn test_record_checks_use_queried_properties() {
+        let collector = MockCollector::new("systemd_service").with_item(
+            "sshd",
+            json!({"properties": {"ActiveState": "active", "NoNewPrivileges": "no"}}),
+        );
+        let sshd = r#"{"id": "sshd", "fields": {"service_name": "sshd.service"}}"#;
+        let hardened = r#"[{"id": "hardened", "records": [
+            {"name": "NoNewPrivileges", "type": "string", "op": "=", "value": "yes"}
+        ]}]"#;
+        let result = run(&collector, sshd, "all all", hardened);
+        assert_eq!(result.status, ComplianceStatus::Fail);
+        assert_eq!(
+            result.state_results[0].state_results[0].field_name,
+            "NoNewPrivileges"
+        );
+    }
+
+    #[test]
+    fn test_zero_instances_follow_existence_matrix() {
+        let collector = gettys(vec![]);
+        for (check, expected) in [
+            ("all", ComplianceStatus::Fail),
+            ("any", ComplianceStatus::Fail),
+            ("at_least_one", ComplianceStatus::Fail),
+            ("only_one", ComplianceStatus::Fail),
+            ("none", ComplianceStatus::Pass),
+        ] {
+            let test = format!("{} all", check);
+            let result = run(&collector, GETTYS, &test, ACTIVE);
+            assert_eq!(result.status, expected, "{}", check);
+        }
+
+        let collector = gettys(vec![instance("getty@tty1.service", true)]);
+        let result = run(&collector, GETTYS, "none all", ACTIVE);
+        assert_eq!(result.status, ComplianceStatus::Fail);
+    }
+}
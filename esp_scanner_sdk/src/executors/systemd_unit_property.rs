@@ -0,0 +1,195 @@
+//! Systemd unit property executor
+//!
+//! Validates the properties `systemctl show` reports for a unit with record
+//! checks, as for `json_record`: `ProtectSystem string = `strict``, or
+//! `ExecStart.* string contains `-D` all` for a list property.
+//!
+//! A unit systemd has no definition for (`LoadState=not-found`) is not found
+//! rather than an error, so `TEST none ...` asserts that a unit does not
+//! exist, and other existence checks fail on it.
+
+use super::json_record::execute_record_field_checks;
+use esp_scanner_base::execution::evaluate_existence_check;
+use esp_scanner_base::strategies::{
+    CollectedData, ComplianceStatus, CtnContract, CtnExecutionError, CtnExecutionResult,
+    CtnExecutor, TestPhase,
+};
+use esp_scanner_base::types::common::ResolvedValue;
+use esp_scanner_base::types::execution_context::ExecutableCriterion;
+use esp_scanner_base::types::ExistenceCheck;
+use std::collections::HashMap;
+
+pub struct SystemdUnitPropertyExecutor {
+    contract: CtnContract,
+}
+
+impl SystemdUnitPropertyExecutor {
+    pub fn new(contract: CtnContract) -> Self {
+        Self { contract }
+    }
+}
+
+fn is_found(data: &CollectedData) -> bool {
+    matches!(data.get_field("found"), Some(ResolvedValue::Boolean(true)))
+}
+
+impl CtnExecutor for SystemdUnitPropertyExecutor {
+    fn execute_with_contract(
+        &self,
+        criterion: &ExecutableCriterion,
+        collected_data: &HashMap<String, CollectedData>,
+        _contract: &CtnContract,
+    ) -> Result<CtnExecutionResult, CtnExecutionError> {
+        let found: HashMap<String, CollectedData> = collected_data
+            .iter()
+            .filter(|(_, data)| is_found(data))
+            .map(|(object_id, data)| (object_id.clone(), data.clone()))
+            .collect();
+
+        // With `none` there are no properties to check, only units to miss
+        if matches!(criterion.test.existence_check, ExistenceCheck::None) {
+            let mut units: Vec<&str> = found.keys().map(String::as_str).collect();
+            units.sort();
+            let passed = evaluate_existence_check(
+                ExistenceCheck::None,
+                units.len(),
+                criterion.expected_object_count(),
+            );
+            let (status, message) = if passed {
+                (
+                    ComplianceStatus::Pass,
+                    "Systemd unit property validation passed: no units found".to_string(),
+                )
+            } else {
+                (
+                    ComplianceStatus::Fail,
+                    format!(
+                        "Existence check failed: expected no units, found {}",
+                        units.join(", ")
+                    ),
+                )
+            };
+            return Ok(CtnExecutionResult {
+                ctn_type: criterion.criterion_type.clone(),
+                status,
+                test_phase: TestPhase::Complete,
+                existence_result: None,
+                state_results: Vec::new(),
+                item_check_result: None,
+                message,
+                details: serde_json::json!({ "units": units }),
+                execution_metadata: Default::default(),
+            });
+        }
+
+        execute_record_field_checks(
+            &self.contract,
+            "properties",
+            "Systemd unit property",
+            criterion,
+            &found,
+        )
+    }
+
+    fn get_ctn_contract(&self) -> CtnContract {
+        self.contract.clone()
+    }
+
+    fn ctn_type(&self) -> &str {
+        "systemd_unit_property"
+    }
+
+    fn validate_collected_data(
+        &self,
+        collected_data: &HashMap<String, CollectedData>,
+        _contract: &CtnContract,
+    ) -> Result<(), CtnExecutionError> {
+        for data in collected_data.values() {
+            if is_found(data) && !data.has_field("properties") {
+                return Err(CtnExecutionError::MissingDataField {
+                    field: "properties".to_string(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contracts::create_systemd_unit_property_contract;
+    use esp_scanner_base::testkit::{CriterionFixture, MockCollector};
+    use serde_json::json;
+
+    fn run(collector: &MockCollector, states: &str, test: &str) -> CtnExecutionResult {
+        let executor = SystemdUnitPropertyExecutor::new(create_systemd_unit_property_contract());
+        CriterionFixture::from_json(
+            r#"{"id": "sshd", "fields": {"unit_name": "sshd.service"}}"#,
+            states,
+        )
+        .and_then(|fixture| fixture.with_test(test))
+        .and_then(|fixture| fixture.run(collector, &executor))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_scalar_and_list_properties() {
+        let collector = MockCollector::new("systemd_unit_property").with_item(
+            "sshd",
+            json!({
+                "unit_name": "sshd.service",
+                "found": true,
+                "properties": crate::collectors::unit_properties::parse(
+                    "LoadState=loaded\nProtectSystem=strict\nLimitNOFILE=524288\n\
+                     CapabilityBoundingSet=cap_chown cap_net_bind_service\n"
+                ),
+            }),
+        );
+        let states = r#"[{"id": "hardened", "records": [
+            {"name": "ProtectSystem", "type": "string", "op": "=", "value": "strict"},
+            {"name": "LimitNOFILE", "type": "int", "op": ">=", "value": 65536},
+            {"name": "CapabilityBoundingSet.*", "type": "string", "op": "!=",
+             "value": "cap_sys_admin", "entity_check": "all"}
+        ]}]"#;
+        let result = run(&collector, states, "all all");
+        assert_eq!(result.status, ComplianceStatus::Pass, "{}", result.message);
+
+        let states = r#"[{"id": "no_binding", "records": [
+            {"name": "CapabilityBoundingSet.*", "type": "string", "op": "=",
+             "value": "cap_net_bind_service", "entity_check": "none"}
+        ]}]"#;
+        assert_eq!(
+            run(&collector, states, "all all").status,
+            ComplianceStatus::Fail
+        );
+    }
+
+    #[test]
+    fn test_unit_not_found_satisfies_none() {
+        let missing = MockCollector::new("systemd_unit_property").with_item(
+            "sshd",
+            json!({ "unit_name": "sshd.service", "found": false }),
+        );
+        assert_eq!(
+            run(&missing, "[]", "none all").status,
+            ComplianceStatus::Pass
+        );
+        assert_eq!(
+            run(&missing, "[]", "all all").status,
+            ComplianceStatus::Fail
+        );
+
+        let present = MockCollector::new("systemd_unit_property").with_item(
+            "sshd",
+            json!({
+                "unit_name": "sshd.service",
+                "found": true,
+                "properties": { "LoadState": "loaded" },
+            }),
+        );
+        let result = run(&present, "[]", "none all");
+        assert_eq!(result.status, ComplianceStatus::Fail);
+        assert_eq!(result.details["units"][0], "sshd");
+    }
+}
@@ -0,0 +1,95 @@
+//! TOML record executor
+//!
+//! Validates TOML documents such as containers.conf or crio.conf using record
+//! checks. Dotted keys and tables both become nested records, so
+//! `engine.cgroup_manager` names the same field however the file spells it.
+
+use super::json_record::{execute_record_checks, validate_record_data};
+use crate::collectors::record_formats::RecordFormat;
+use esp_scanner_base::strategies::{
+    CollectedData, CtnContract, CtnExecutionError, CtnExecutionResult, CtnExecutor,
+};
+use esp_scanner_base::types::execution_context::ExecutableCriterion;
+use std::collections::HashMap;
+
+pub struct TomlRecordExecutor {
+    contract: CtnContract,
+}
+
+impl TomlRecordExecutor {
+    pub fn new(contract: CtnContract) -> Self {
+        Self { contract }
+    }
+}
+
+impl CtnExecutor for TomlRecordExecutor {
+    fn execute_with_contract(
+        &self,
+        criterion: &ExecutableCriterion,
+        collected_data: &HashMap<String, CollectedData>,
+        _contract: &CtnContract,
+    ) -> Result<CtnExecutionResult, CtnExecutionError> {
+        execute_record_checks(
+            &self.contract,
+            RecordFormat::Toml,
+            criterion,
+            collected_data,
+        )
+    }
+
+    fn get_ctn_contract(&self) -> CtnContract {
+        self.contract.clone()
+    }
+
+    fn ctn_type(&self) -> &str {
+        "toml_record"
+    }
+
+    fn validate_collected_data(
+        &self,
+        collected_data: &HashMap<String, CollectedData>,
+        _contract: &CtnContract,
+    ) -> Result<(), CtnExecutionError> {
+        validate_record_data(RecordFormat::Toml, collected_data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collectors::FileSystemCollector;
+    use crate::contracts::create_toml_record_contract;
+    use esp_scanner_base::strategies::ComplianceStatus;
+    use esp_scanner_base::testkit::CriterionFixture;
+
+    #[test]
+    fn test_dotted_keys_and_tables_are_one_record() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("containers.conf");
+        std::fs::write(
+            &path,
+            r#"
+engine.cgroup_manager = "systemd"
+
+[containers]
+log_size_max = 10485760
+"#,
+        )
+        .unwrap();
+        let object = format!(
+            r#"{{"id": "containers_conf", "fields": {{"path": "{}"}}}}"#,
+            path.display()
+        );
+        let states = r#"[{"id": "hardened", "records": [
+            {"name": "engine.cgroup_manager", "type": "string", "op": "=", "value": "systemd"},
+            {"name": "containers.log_size_max", "type": "int", "op": "<=", "value": 10485760}
+        ]}]"#;
+        let executor = TomlRecordExecutor::new(create_toml_record_contract());
+        let result = CriterionFixture::from_json(&object, states)
+            .and_then(|fixture| fixture.with_test("all all"))
+            .and_then(|fixture| fixture.run(&FileSystemCollector::new(), &executor))
+            .unwrap();
+        assert_eq!(result.status, ComplianceStatus::Pass, "{}", result.message);
+        assert!(result.message.starts_with("TOML record validation passed"));
+    }
+}
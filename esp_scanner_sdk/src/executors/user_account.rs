@@ -0,0 +1,210 @@
+//! User account executor
+//!
+//! Validates local accounts read from the passwd and group databases.
+//!
+//! Every object expands into the accounts its `username` matched, and each
+//! account is a separate item named `object_id[username]`. Accounts are what
+//! the existence check counts, so a missing user fails `all` and passes
+//! `none`, and a glob matching several accounts fails `only_one`. When a
+//! username appears on more than one passwd line, later lines are suffixed
+//! `#2`, `#3`, ... and every one of them is checked.
+
+use super::{existence_failure, field_matches, field_result, validate_items, ItemNames};
+use esp_scanner_base::execution::{collection_items, record_field, record_text};
+use esp_scanner_base::strategies::{
+    CollectedData, CtnContract, CtnExecutionError, CtnExecutionResult, CtnExecutor,
+};
+use esp_scanner_base::types::common::{RecordData, ResolvedValue};
+use esp_scanner_base::types::execution_context::ExecutableCriterion;
+use std::collections::HashMap;
+
+pub struct UserAccountExecutor {
+    contract: CtnContract,
+}
+
+const NAMES: ItemNames = ItemNames {
+    item: "Account",
+    items: "accounts",
+    subject: "User account validation",
+};
+
+impl UserAccountExecutor {
+    pub fn new(contract: CtnContract) -> Self {
+        Self { contract }
+    }
+}
+
+impl CtnExecutor for UserAccountExecutor {
+    fn execute_with_contract(
+        &self,
+        criterion: &ExecutableCriterion,
+        collected_data: &HashMap<String, CollectedData>,
+        _contract: &CtnContract,
+    ) -> Result<CtnExecutionResult, CtnExecutionError> {
+        let items = collection_items(collected_data, "accounts", |_, record| {
+            record_text(record, "username")
+        });
+        let objects_expected = criterion
+            .expected_object_count()
+            .saturating_sub(collected_data.len())
+            + items.len();
+        if let Some(result) =
+            existence_failure(criterion, "accounts", items.len(), objects_expected)
+        {
+            return Ok(result);
+        }
+
+        let results = validate_items(
+            criterion,
+            &self.contract,
+            &NAMES,
+            items.iter().map(|(item, record)| (item, *record)),
+            |record: &RecordData, field, name| {
+                field_result(field, record_field(record, name), field_matches)
+            },
+        );
+        let mut result = results.into_result(criterion, &NAMES);
+        for (object_id, record) in &items {
+            if record_field(record, "duplicate") == Some(ResolvedValue::Boolean(true)) {
+                result.add_advisory(
+                    object_id.clone(),
+                    "Username appears on more than one passwd line".to_string(),
+                );
+            }
+        }
+        Ok(result)
+    }
+
+    fn get_ctn_contract(&self) -> CtnContract {
+        self.contract.clone()
+    }
+
+    fn ctn_type(&self) -> &str {
+        "user_account"
+    }
+
+    fn validate_collected_data(
+        &self,
+        _collected_data: &HashMap<String, CollectedData>,
+        _contract: &CtnContract,
+    ) -> Result<(), CtnExecutionError> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contracts::create_user_account_contract;
+    use esp_scanner_base::strategies::{ComplianceStatus, FieldOutcome};
+    use esp_scanner_base::testkit::{check_golden, CriterionFixture, MockCollector};
+    use serde_json::{json, Value};
+
+    const ALL_USERS: &str = r#"{"id": "users", "fields": {"username": "*"}}"#;
+
+    const NOLOGIN: &str = r#"[{"id": "nologin", "fields": [
+        {"name": "shell", "type": "string", "op": "=", "value": "/sbin/nologin"}
+    ]}]"#;
+
+    fn account(username: &str, uid: i64, shell: &str, duplicate: bool) -> Value {
+        json!({
+            "username": username,
+            "uid": uid,
+            "gid": uid,
+            "home": format!("/home/{}", username),
+            "shell": shell,
+            "duplicate": duplicate,
+        })
+    }
+
+    fn users(accounts: Vec<Value>) -> MockCollector {
+        MockCollector::new("user_account")
+            .with_item("users", json!({"username": "*", "accounts": accounts}))
+    }
+
+    fn run(collector: &MockCollector, test: &str, states: &str) -> CtnExecutionResult {
+        let executor = UserAccountExecutor::new(create_user_account_contract());
+        CriterionFixture::from_json(ALL_USERS, states)
+            .and_then(|fixture| fixture.with_test(test))
+            .and_then(|fixture| fixture.run(collector, &executor))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_duplicate_usernames_are_separate_items() {
+        let collector = users(vec![
+            account("toor", 0, "/bin/bash", true),
+            account("toor", 1001, "/sbin/nologin", true),
+        ]);
+        check_golden(
+            &run(&collector, "all all", NOLOGIN),
+            json!({
+                "status": "fail",
+                "objects": {
+                    "users[toor]": {"passed": false, "fields": [
+                        {"field": "shell", "outcome": "fail",
+                         "expected": "/sbin/nologin", "actual": "/bin/bash"}
+                    ]},
+                    "users[toor#2]": {"passed": true, "fields": [
+                        {"field": "shell", "outcome": "pass",
+                         "expected": "/sbin/nologin", "actual": "/sbin/nologin"}
+                    ]}
+                },
+                "advisories": [
+                    {"object_id": "users[toor#2]",
+                     "message": "Username appears on more than one passwd line"},
+                    {"object_id": "users[toor]",
+                     "message": "Username appears on more than one passwd line"}
+                ]
+            }),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_integer_fields_compare_numerically() {
+        let collector = users(vec![
+            account("root", 0, "/bin/bash", false),
+            account("alice", 1000, "/bin/bash", false),
+        ]);
+        let root_uid = r#"[{"id": "root_uid", "fields": [
+            {"name": "uid", "type": "int", "op": "=", "value": 0}
+        ]}]"#;
+        let result = run(&collector, "all only_one", root_uid);
+        assert_eq!(result.status, ComplianceStatus::Pass);
+
+        let system = r#"[{"id": "system", "fields": [
+            {"name": "uid", "type": "int", "op": "<", "value": 1000}
+        ]}]"#;
+        let result = run(&collector, "all all", system);
+        assert_eq!(result.status, ComplianceStatus::Fail);
+    }
+
+    #[test]
+    fn test_missing_account_follows_existence_check() {
+        let collector = users(vec![]);
+        for (check, expected) in [
+            ("all", ComplianceStatus::Fail),
+            ("at_least_one", ComplianceStatus::Fail),
+            ("none", ComplianceStatus::Pass),
+        ] {
+            let test = format!("{} all", check);
+            let result = run(&collector, &test, NOLOGIN);
+            assert_eq!(result.status, expected, "{}", check);
+        }
+    }
+
+    #[test]
+    fn test_uncollected_home_exists_is_not_a_pass() {
+        let collector = users(vec![account("alice", 1000, "/bin/bash", false)]);
+        let home = r#"[{"id": "home", "fields": [
+            {"name": "home_exists", "type": "boolean", "op": "=", "value": true}
+        ]}]"#;
+        let result = run(&collector, "all all", home);
+        assert_eq!(result.status, ComplianceStatus::Fail);
+        assert_eq!(
+            result.state_results[0].state_results[0].outcome,
+            FieldOutcome::Unknown
+        );
+    }
+}
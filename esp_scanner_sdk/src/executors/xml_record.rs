@@ -0,0 +1,126 @@
+//! XML record executor
+//!
+//! Validates XML files such as Tomcat's `server.xml` or JBoss's
+//! `standalone.xml` using record checks. Field paths name elements from the
+//! root down and end at an element's text or an `@attribute`, as in
+//! `Server.Service.Connector.@port`; every element a path reaches is one
+//! value for its entity check. Paths are rewritten onto the collected record
+//! with [`record_path`] before the checks run.
+
+use super::json_record::{execute_record_checks, validate_record_data};
+use crate::collectors::record_formats::RecordFormat;
+use crate::collectors::xml::record_path;
+use esp_scanner_base::strategies::{
+    CollectedData, CtnContract, CtnExecutionError, CtnExecutionResult, CtnExecutor,
+};
+use esp_scanner_base::types::execution_context::{ExecutableCriterion, ExecutableRecordContent};
+use esp_scanner_base::types::FieldPath;
+use std::collections::HashMap;
+
+pub struct XmlRecordExecutor {
+    contract: CtnContract,
+}
+
+impl XmlRecordExecutor {
+    pub fn new(contract: CtnContract) -> Self {
+        Self { contract }
+    }
+}
+
+impl CtnExecutor for XmlRecordExecutor {
+    fn execute_with_contract(
+        &self,
+        criterion: &ExecutableCriterion,
+        collected_data: &HashMap<String, CollectedData>,
+        _contract: &CtnContract,
+    ) -> Result<CtnExecutionResult, CtnExecutionError> {
+        let mut criterion = criterion.clone();
+        for state in &mut criterion.states {
+            for check in &mut state.record_checks {
+                if let ExecutableRecordContent::Nested { fields } = &mut check.content {
+                    for field in fields {
+                        field.path = FieldPath::new(record_path(&field.path.components));
+                    }
+                }
+            }
+        }
+        execute_record_checks(
+            &self.contract,
+            RecordFormat::Xml,
+            &criterion,
+            collected_data,
+        )
+    }
+
+    fn get_ctn_contract(&self) -> CtnContract {
+        self.contract.clone()
+    }
+
+    fn ctn_type(&self) -> &str {
+        "xml_record"
+    }
+
+    fn validate_collected_data(
+        &self,
+        collected_data: &HashMap<String, CollectedData>,
+        _contract: &CtnContract,
+    ) -> Result<(), CtnExecutionError> {
+        validate_record_data(RecordFormat::Xml, collected_data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collectors::FileSystemCollector;
+    use crate::contracts::create_xml_record_contract;
+    use esp_scanner_base::strategies::ComplianceStatus;
+    use esp_scanner_base::testkit::CriterionFixture;
+
+    #[test]
+    fn test_attribute_paths_check_every_element() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("server.xml");
+        std::fs::write(
+            &path,
+            r#"<Server port="-1">
+  <Service name="Catalina">
+    <Connector port="8443" secure="true"/>
+    <Connector port="8080" secure="false"/>
+    <Engine name="Catalina" defaultHost="localhost"/>
+  </Service>
+</Server>"#,
+        )
+        .unwrap();
+        let object = format!(
+            r#"{{"id": "tomcat", "fields": {{"path": "{}"}}}}"#,
+            path.display()
+        );
+        let run = |states: &str| {
+            let executor = XmlRecordExecutor::new(create_xml_record_contract());
+            CriterionFixture::from_json(&object, states)
+                .and_then(|fixture| fixture.with_test("all all"))
+                .and_then(|fixture| fixture.run(&FileSystemCollector::new(), &executor))
+                .unwrap()
+        };
+
+        let hardened = r#"[{"id": "hardened", "records": [
+            {"name": "Server.@port", "type": "int", "op": "=", "value": -1},
+            {"name": "Server.Service.Engine.@defaultHost", "type": "string", "op": "=",
+             "value": "localhost"}
+        ]}]"#;
+        assert_eq!(run(hardened).status, ComplianceStatus::Pass);
+
+        let all_secure = r#"[{"id": "tls_only", "records": [
+            {"name": "Server.Service.Connector.@secure", "type": "boolean", "op": "=",
+             "value": true, "entity_check": "all"}
+        ]}]"#;
+        let result = run(all_secure);
+        assert_eq!(result.status, ComplianceStatus::Fail);
+        assert!(
+            result.message.contains("1 of 2 items matched"),
+            "{}",
+            result.message
+        );
+    }
+}
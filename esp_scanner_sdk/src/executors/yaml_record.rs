@@ -0,0 +1,113 @@
+//! YAML record executor
+//!
+//! Validates YAML documents such as netplan, cloud-init or kubelet
+//! configuration using record checks. Aliases and merge keys are resolved
+//! when the document is collected, so field paths see the values they refer
+//! to.
+
+use super::json_record::{execute_record_checks, validate_record_data};
+use crate::collectors::record_formats::RecordFormat;
+use esp_scanner_base::strategies::{
+    CollectedData, CtnContract, CtnExecutionError, CtnExecutionResult, CtnExecutor,
+};
+use esp_scanner_base::types::execution_context::ExecutableCriterion;
+use std::collections::HashMap;
+
+pub struct YamlRecordExecutor {
+    contract: CtnContract,
+}
+
+impl YamlRecordExecutor {
+    pub fn new(contract: CtnContract) -> Self {
+        Self { contract }
+    }
+}
+
+impl CtnExecutor for YamlRecordExecutor {
+    fn execute_with_contract(
+        &self,
+        criterion: &ExecutableCriterion,
+        collected_data: &HashMap<String, CollectedData>,
+        _contract: &CtnContract,
+    ) -> Result<CtnExecutionResult, CtnExecutionError> {
+        execute_record_checks(
+            &self.contract,
+            RecordFormat::Yaml,
+            criterion,
+            collected_data,
+        )
+    }
+
+    fn get_ctn_contract(&self) -> CtnContract {
+        self.contract.clone()
+    }
+
+    fn ctn_type(&self) -> &str {
+        "yaml_record"
+    }
+
+    fn validate_collected_data(
+        &self,
+        collected_data: &HashMap<String, CollectedData>,
+        _contract: &CtnContract,
+    ) -> Result<(), CtnExecutionError> {
+        validate_record_data(RecordFormat::Yaml, collected_data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collectors::FileSystemCollector;
+    use crate::contracts::create_yaml_record_contract;
+    use esp_scanner_base::strategies::ComplianceStatus;
+    use esp_scanner_base::testkit::CriterionFixture;
+
+    fn run(path: &str, states: &str) -> CtnExecutionResult {
+        let object = format!(r#"{{"id": "netplan", "fields": {{"path": "{}"}}}}"#, path);
+        let executor = YamlRecordExecutor::new(create_yaml_record_contract());
+        CriterionFixture::from_json(&object, states)
+            .and_then(|fixture| fixture.with_test("all all"))
+            .and_then(|fixture| fixture.run(&FileSystemCollector::new(), &executor))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_aliased_values_are_checked_and_mismatches_name_the_type() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("01-netcfg.yaml");
+        std::fs::write(
+            &path,
+            "\
+common: &common
+  dhcp4: false
+  mtu: \"9000\"
+network:
+  ethernets:
+    eth0:
+      <<: *common
+      addresses: [10.0.0.5/24]
+    eth1: *common
+",
+        )
+        .unwrap();
+        let path = path.display().to_string();
+
+        let dhcp_off = r#"[{"id": "static", "records": [
+            {"name": "network.ethernets.*.dhcp4", "type": "boolean", "op": "=",
+             "value": false, "entity_check": "all"}
+        ]}]"#;
+        assert_eq!(run(&path, dhcp_off).status, ComplianceStatus::Pass);
+
+        let jumbo = r#"[{"id": "jumbo", "records": [
+            {"name": "network.ethernets.eth1.mtu", "type": "int", "op": "=", "value": 9000}
+        ]}]"#;
+        let result = run(&path, jumbo);
+        assert_eq!(result.status, ComplianceStatus::Fail);
+        assert!(
+            result.message.contains("found string \"9000\""),
+            "{}",
+            result.message
+        );
+    }
+}
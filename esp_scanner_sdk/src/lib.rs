@@ -1,29 +1,164 @@
 //! # ESP Scanner SDK
 //!
 //! Extended scanner strategies for ESP compliance validation.
-//! Provides file system, RPM, systemd, sysctl, SELinux, and JSON validation.
+//! Provides file system, RPM, dpkg, systemd, sysctl, SELinux, sshd_config,
+//! user account, audit rule, firewalld, listening port, kernel module, kernel
+//! command line, mount point, and JSON, YAML, TOML and INI validation.
 
 // Module declarations
 pub mod collectors;
 pub mod commands;
+pub mod config;
 pub mod contracts;
 pub mod executors;
 
 // REMOVED: pub use create_scanner_registry; (this was the duplicate)
 
-use esp_scanner_base::strategies::{CtnStrategyRegistry, StrategyError};
+pub use commands::Platform;
+use config::ScannerConfig;
+use esp_scanner_base::strategies::{CtnDataCollector, CtnStrategyRegistry, StrategyError};
+use std::sync::Arc;
 
 /// Create a registry with all available strategies
 ///
 /// Includes:
 /// - File metadata validation (fast stat-based checks)
 /// - File content validation (string operations)
-/// - JSON record validation (structured data)
+/// - File hash validation (streamed SHA-256/SHA-512 digests)
+/// - Extended file attribute validation (POSIX ACLs and file capabilities)
+/// - File permission scans (SUID/SGID and world-writable files under a root)
+/// - JSON, YAML, TOML, INI and XML record validation (structured data)
+/// - sshd_config validation (resolved OpenSSH server directives)
 /// - RPM package validation (installation and version checks)
+/// - dpkg package validation, in place of RPM on Debian-family hosts
 /// - Systemd service validation (active, enabled, loaded status)
+/// - Systemd unit property validation (every `systemctl show` property)
 /// - Sysctl parameter validation (kernel parameters)
 /// - SELinux status validation (enforcement mode)
+/// - SELinux boolean and file context validation
+/// - firewalld zone validation (default zone, services, ports)
+/// - Listening port validation (sockets reported by `ss`)
+/// - Environment variable validation (environment files and process environments)
+/// - Scheduled job validation (cron jobs and systemd timers)
+/// - Kernel module validation (loaded and blacklisted modules)
+/// - Mount point validation (active and fstab mount options)
+/// - Kernel command line validation (running and GRUB-configured arguments)
+/// - User account validation (passwd and group entries)
+/// - Audit rule validation (loaded and persisted auditd rules)
+///
+/// Commands are whitelisted for RHEL 9; see [`create_scanner_registry_for`].
 pub fn create_scanner_registry() -> Result<CtnStrategyRegistry, StrategyError> {
+    create_scanner_registry_with_config(&ScannerConfig::default())
+}
+
+/// Create a registry with all available strategies for a platform family
+///
+/// The platform picks the command whitelist and the package executor:
+/// `rpm_package` on RHEL, `dpkg_package` on Debian.
+pub fn create_scanner_registry_for(
+    platform: Platform,
+) -> Result<CtnStrategyRegistry, StrategyError> {
+    create_scanner_registry_with_config(&ScannerConfig::default().with_platform(platform))
+}
+
+/// Create a registry with all available strategies, applying scanner config
+///
+/// The configured path policy is shared by every filesystem collector, and
+/// the configured platform picks the command whitelist and package executor.
+/// With an offline scan target, the filesystem and account collectors read
+/// inside its root; every other collector declines the target.
+pub fn create_scanner_registry_with_config(
+    config: &ScannerConfig,
+) -> Result<CtnStrategyRegistry, StrategyError> {
+    let path_policy = config.path_policy();
+    let scan_target = config.scan_target();
+    let filesystem_collector = || -> Box<dyn CtnDataCollector> {
+        Box::new(
+            collectors::FileSystemCollector::new()
+                .with_path_policy(path_policy.clone())
+                .with_scan_target(scan_target.clone()),
+        )
+    };
+
+    // Create ONE command executor with the platform's full whitelist
+    let platform = config.platform;
+    let command_executor = platform.command_executor();
+    let command_collector = collectors::CommandCollector::new(
+        format!("{}-command-collector", platform.name()),
+        command_executor.clone(),
+    )
+    .with_path_policy(path_policy.clone());
+    let command_collector = || -> Box<dyn CtnDataCollector> { Box::new(command_collector.clone()) };
+
+    let audit_collector = collectors::AuditRulesCollector::new(
+        format!("{}-audit-collector", platform.name()),
+        command_executor.clone(),
+    )
+    .with_path_policy(path_policy.clone());
+    let audit_collector = || -> Box<dyn CtnDataCollector> { Box::new(audit_collector.clone()) };
+
+    let kernel_module_collector = collectors::KernelModuleCollector::new(
+        format!("{}-kernel-module-collector", platform.name()),
+        command_executor,
+    )
+    .with_path_policy(path_policy.clone());
+    let kernel_module_collector =
+        || -> Box<dyn CtnDataCollector> { Box::new(kernel_module_collector.clone()) };
+
+    let account_collector = || -> Box<dyn CtnDataCollector> {
+        Box::new(
+            collectors::AccountCollector::new()
+                .with_path_policy(path_policy.clone())
+                .with_scan_target(scan_target.clone()),
+        )
+    };
+
+    register_strategies(
+        &filesystem_collector,
+        &command_collector,
+        &account_collector,
+        &audit_collector,
+        &kernel_module_collector,
+        &[platform],
+    )
+}
+
+/// Create a registry that evaluates against an osquery export instead of the host
+///
+/// Every host-backed criterion type is served from the export through the
+/// mapping; types the mapping or export lacks are reported as not evaluated.
+/// Both package types are registered, since the export may come from any host.
+pub fn create_osquery_registry(
+    export: Arc<collectors::OsqueryExport>,
+    mapping: Arc<collectors::OsqueryMapping>,
+) -> Result<CtnStrategyRegistry, StrategyError> {
+    let osquery_collector = || -> Box<dyn CtnDataCollector> {
+        Box::new(collectors::OsqueryCollector::new(
+            export.clone(),
+            mapping.clone(),
+        ))
+    };
+    register_strategies(
+        &osquery_collector,
+        &osquery_collector,
+        &osquery_collector,
+        &osquery_collector,
+        &osquery_collector,
+        &[Platform::Rhel9, Platform::Debian],
+    )
+}
+
+/// Register every strategy, taking host data from the given collectors
+///
+/// A package executor is registered for each of `package_platforms`.
+fn register_strategies(
+    filesystem_collector: &dyn Fn() -> Box<dyn CtnDataCollector>,
+    command_collector: &dyn Fn() -> Box<dyn CtnDataCollector>,
+    account_collector: &dyn Fn() -> Box<dyn CtnDataCollector>,
+    audit_collector: &dyn Fn() -> Box<dyn CtnDataCollector>,
+    kernel_module_collector: &dyn Fn() -> Box<dyn CtnDataCollector>,
+    package_platforms: &[Platform],
+) -> Result<CtnStrategyRegistry, StrategyError> {
     let mut registry = CtnStrategyRegistry::new();
 
     // Register file system strategies
@@ -33,12 +168,12 @@ pub fn create_scanner_registry() -> Result<CtnStrategyRegistry, StrategyError> {
     let computed_values_contract = contracts::create_computed_values_contract();
 
     registry.register_ctn_strategy(
-        Box::new(collectors::FileSystemCollector::new()),
+        filesystem_collector(),
         Box::new(executors::FileMetadataExecutor::new(metadata_contract)),
     )?;
 
     registry.register_ctn_strategy(
-        Box::new(collectors::FileSystemCollector::new()),
+        filesystem_collector(),
         Box::new(executors::FileContentExecutor::new(content_contract)),
     )?;
 
@@ -50,39 +185,202 @@ pub fn create_scanner_registry() -> Result<CtnStrategyRegistry, StrategyError> {
     )?;
 
     registry.register_ctn_strategy(
-        Box::new(collectors::FileSystemCollector::new()),
+        filesystem_collector(),
         Box::new(executors::JsonRecordExecutor::new(json_contract)),
     )?;
 
-    // Create ONE command executor with full RHEL 9 whitelist
-    let command_executor = commands::create_rhel9_command_executor();
-    let command_collector =
-        collectors::CommandCollector::new("rhel9-command-collector", command_executor);
+    let yaml_contract = contracts::create_yaml_record_contract();
+    registry.register_ctn_strategy(
+        filesystem_collector(),
+        Box::new(executors::YamlRecordExecutor::new(yaml_contract)),
+    )?;
 
-    // Register command-based strategies
-    let rpm_contract = contracts::create_rpm_package_contract();
+    let toml_contract = contracts::create_toml_record_contract();
+    registry.register_ctn_strategy(
+        filesystem_collector(),
+        Box::new(executors::TomlRecordExecutor::new(toml_contract)),
+    )?;
+
+    let ini_contract = contracts::create_ini_record_contract();
+    registry.register_ctn_strategy(
+        filesystem_collector(),
+        Box::new(executors::IniRecordExecutor::new(ini_contract)),
+    )?;
+
+    let xml_contract = contracts::create_xml_record_contract();
+    registry.register_ctn_strategy(
+        filesystem_collector(),
+        Box::new(executors::XmlRecordExecutor::new(xml_contract)),
+    )?;
+
+    let sshd_contract = contracts::create_sshd_config_contract();
+    registry.register_ctn_strategy(
+        filesystem_collector(),
+        Box::new(executors::SshdConfigExecutor::new(sshd_contract)),
+    )?;
+
+    let mount_contract = contracts::create_mount_point_contract();
+    registry.register_ctn_strategy(
+        filesystem_collector(),
+        Box::new(executors::MountPointExecutor::new(mount_contract)),
+    )?;
+
+    let kernel_cmdline_contract = contracts::create_kernel_cmdline_contract();
+    registry.register_ctn_strategy(
+        filesystem_collector(),
+        Box::new(executors::KernelCmdlineExecutor::new(
+            kernel_cmdline_contract,
+        )),
+    )?;
+
+    let certificate_contract = contracts::create_certificate_contract();
+    registry.register_ctn_strategy(
+        filesystem_collector(),
+        Box::new(executors::CertificateExecutor::new(certificate_contract)),
+    )?;
+
+    let file_hash_contract = contracts::create_file_hash_contract();
+    registry.register_ctn_strategy(
+        filesystem_collector(),
+        Box::new(executors::FileHashExecutor::new(file_hash_contract)),
+    )?;
+
+    let extended_file_attr_contract = contracts::create_extended_file_attr_contract();
+    registry.register_ctn_strategy(
+        filesystem_collector(),
+        Box::new(executors::ExtendedFileAttrExecutor::new(
+            extended_file_attr_contract,
+        )),
+    )?;
+
+    let permission_scan_contract = contracts::create_file_permission_scan_contract();
+    registry.register_ctn_strategy(
+        filesystem_collector(),
+        Box::new(executors::FilePermissionScanExecutor::new(
+            permission_scan_contract,
+        )),
+    )?;
+
+    let sudoers_contract = contracts::create_sudoers_contract();
     registry.register_ctn_strategy(
-        Box::new(command_collector.clone()),
-        Box::new(executors::RpmPackageExecutor::new(rpm_contract)),
+        filesystem_collector(),
+        Box::new(executors::SudoersExecutor::new(sudoers_contract)),
     )?;
 
+    // Register command-based strategies
+    for platform in package_platforms {
+        match platform {
+            Platform::Rhel9 => registry.register_ctn_strategy(
+                command_collector(),
+                Box::new(executors::RpmPackageExecutor::new(
+                    contracts::create_rpm_package_contract(),
+                )),
+            )?,
+            Platform::Debian => registry.register_ctn_strategy(
+                command_collector(),
+                Box::new(executors::DpkgPackageExecutor::new(
+                    contracts::create_dpkg_package_contract(),
+                )),
+            )?,
+        }
+    }
+
     let systemd_contract = contracts::create_systemd_service_contract();
     registry.register_ctn_strategy(
-        Box::new(command_collector.clone()),
+        command_collector(),
         Box::new(executors::SystemdServiceExecutor::new(systemd_contract)),
     )?;
 
+    let unit_property_contract = contracts::create_systemd_unit_property_contract();
+    registry.register_ctn_strategy(
+        command_collector(),
+        Box::new(executors::SystemdUnitPropertyExecutor::new(
+            unit_property_contract,
+        )),
+    )?;
+
     let sysctl_contract = contracts::create_sysctl_parameter_contract();
     registry.register_ctn_strategy(
-        Box::new(command_collector.clone()),
+        command_collector(),
         Box::new(executors::SysctlParameterExecutor::new(sysctl_contract)),
     )?;
 
     let selinux_contract = contracts::create_selinux_status_contract();
     registry.register_ctn_strategy(
-        Box::new(command_collector),
+        command_collector(),
         Box::new(executors::SelinuxStatusExecutor::new(selinux_contract)),
     )?;
 
+    let selinux_boolean_contract = contracts::create_selinux_boolean_contract();
+    registry.register_ctn_strategy(
+        command_collector(),
+        Box::new(executors::SelinuxBooleanExecutor::new(
+            selinux_boolean_contract,
+        )),
+    )?;
+
+    let selinux_file_context_contract = contracts::create_selinux_file_context_contract();
+    registry.register_ctn_strategy(
+        command_collector(),
+        Box::new(executors::SelinuxFileContextExecutor::new(
+            selinux_file_context_contract,
+        )),
+    )?;
+
+    let firewalld_contract = contracts::create_firewalld_zone_contract();
+    registry.register_ctn_strategy(
+        command_collector(),
+        Box::new(executors::FirewalldZoneExecutor::new(firewalld_contract)),
+    )?;
+
+    let listening_port_contract = contracts::create_listening_port_contract();
+    registry.register_ctn_strategy(
+        command_collector(),
+        Box::new(executors::ListeningPortExecutor::new(
+            listening_port_contract,
+        )),
+    )?;
+
+    let environment_contract = contracts::create_environment_variable_contract();
+    registry.register_ctn_strategy(
+        command_collector(),
+        Box::new(executors::EnvironmentExecutor::new(environment_contract)),
+    )?;
+
+    let scheduled_job_contract = contracts::create_scheduled_job_contract();
+    registry.register_ctn_strategy(
+        command_collector(),
+        Box::new(executors::ScheduledJobExecutor::new(scheduled_job_contract)),
+    )?;
+
+    // Register account database strategies
+    let user_account_contract = contracts::create_user_account_contract();
+    registry.register_ctn_strategy(
+        account_collector(),
+        Box::new(executors::UserAccountExecutor::new(user_account_contract)),
+    )?;
+
+    let password_policy_contract = contracts::create_password_policy_contract();
+    registry.register_ctn_strategy(
+        account_collector(),
+        Box::new(executors::PasswordPolicyExecutor::new(
+            password_policy_contract,
+        )),
+    )?;
+
+    // Register audit rule strategies
+    let audit_rules_contract = contracts::create_audit_rules_contract();
+    registry.register_ctn_strategy(
+        audit_collector(),
+        Box::new(executors::AuditRulesExecutor::new(audit_rules_contract)),
+    )?;
+
+    // Register kernel module strategies
+    let kernel_module_contract = contracts::create_kernel_module_contract();
+    registry.register_ctn_strategy(
+        kernel_module_collector(),
+        Box::new(executors::KernelModuleExecutor::new(kernel_module_contract)),
+    )?;
+
     Ok(registry)
 }
@@ -1,310 +1,549 @@
 //! # ESP Scanner CLI
 //!
 
-use esp_compiler::{log_error, log_info, log_success, logging, pipeline};
-use esp_scanner_base::execution::ExecutionEngine;
+mod cli;
+
+use clap::{CommandFactory, Parser};
+use cli::{
+    exit_code, AnnotateArgs, CheckArgs, Cli, Command, DiffArgs, EmptyPolicyMode, EvalOsqueryArgs,
+    ExplainArgs, OutputFormat, ScanOptions, VerifyResultArgs,
+};
+use esp_compiler::compatibility::{self, CapabilityManifest};
+use esp_compiler::terminal::{self, Palette, Style};
+use esp_compiler::{log_error, log_info, log_success, log_warning, logging, pipeline};
+use esp_scanner_base::execution::ExecutionError;
 use esp_scanner_base::resolution::engine::ResolutionEngine;
-use esp_scanner_base::types::*;
+use esp_scanner_base::resolution::{resolution_context_from_ast, CachedPolicy, PolicyCache};
+use esp_scanner_base::results::{controls, csv, junit, sarif, PolicySeverity, ScanProvenance};
+use esp_scanner_base::strategies::{CriterionProblem, CtnStrategyRegistry};
+use esp_scanner_base::types::ExecutionContext;
+use std::collections::{BTreeMap, HashSet};
+use std::io::Write;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::Instant;
 
+/// Set under `--output -`: results own stdout, so the scan report goes to
+/// stderr
+static REPORT_TO_STDERR: AtomicBool = AtomicBool::new(false);
+
+/// `print!` for the scan report
+macro_rules! report {
+    ($($arg:tt)*) => {
+        if REPORT_TO_STDERR.load(Ordering::Relaxed) {
+            eprint!($($arg)*)
+        } else {
+            print!($($arg)*)
+        }
+    };
+}
+
+/// `println!` for the scan report
+macro_rules! reportln {
+    ($($arg:tt)*) => {
+        if REPORT_TO_STDERR.load(Ordering::Relaxed) {
+            eprintln!($($arg)*)
+        } else {
+            println!($($arg)*)
+        }
+    };
+}
+
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    logging::init_global_logging()?;
-    log_info!("ESP Scanner starting");
+    let cli = Cli::parse();
+    terminal::set_color_choice(cli.color);
 
-    let args: Vec<String> = std::env::args().collect();
-    if args.len() < 2 {
-        print_usage(&args[0]);
-        std::process::exit(1);
+    match cli.command {
+        Some(Command::Completions { shell }) => {
+            cli::write_completions(shell, &mut std::io::stdout());
+            return Ok(());
+        }
+        Some(Command::Annotate(args)) => return annotate(&args),
+        Some(Command::Explain(args)) => return explain(&args),
+        Some(Command::Capabilities { json }) => return capabilities(json),
+        Some(Command::Diff(args)) => return diff(&args),
+        Some(Command::VerifyResult(args)) => return verify_result(&args),
+        Some(Command::Check(args)) => {
+            logging::init_global_logging()?;
+            return check(&args);
+        }
+        Some(Command::EvalOsquery(args)) => {
+            logging::init_global_logging()?;
+            eval_osquery(&args)?;
+            logging::print_cargo_style_summary();
+            return Ok(());
+        }
+        None => {}
     }
 
-    if args[1] == "--help" || args[1] == "-h" {
-        print_help(&args[0]);
+    if cli.man {
+        cli::write_man_page(&mut std::io::stdout())?;
         return Ok(());
     }
 
-    let input_path = Path::new(&args[1]);
-
-    if input_path.is_file() {
-        scan_single_file(input_path)?;
-    } else if input_path.is_dir() {
-        scan_directory(input_path)?;
+    let options = cli.scan;
+    if options.writes_to_stdout() {
+        REPORT_TO_STDERR.store(true, Ordering::Relaxed);
+        logging::init_global_logging_on_stderr()?;
     } else {
+        logging::init_global_logging()?;
+    }
+    log_info!("ESP Scanner starting");
+
+    let input_path = match cli.input.as_deref() {
+        Some(path) => path,
+        None => {
+            Cli::command().print_help()?;
+            std::process::exit(1);
+        }
+    };
+
+    let scanned = if !input_path.is_file() && !input_path.is_dir() {
         eprintln!("Error: Input must be an ESP file or directory");
         eprintln!("  Path: {}", input_path.display());
+        std::process::exit(exit_code::UNREADABLE_POLICY);
+    } else if options.validate_only {
+        validate_only(input_path, &options)
+    } else if input_path.is_file() {
+        scan_single_file(input_path, &options)
+    } else {
+        scan_directory(input_path, &options)
+    };
+    if let Err(e) = scanned {
+        eprintln!("Error: {}", e);
+        std::process::exit(exit_code::SCAN_FAILED);
+    }
+
+    if let Some(collector) = logging::try_get_global_error_collector() {
+        reportln!(
+            "{}",
+            logging::collector::format_cargo_style_errors(collector)
+        );
+    }
+    Ok(())
+}
+
+/// Record or review reviewer annotations for a saved scan result
+fn annotate(args: &AnnotateArgs) -> Result<(), Box<dyn std::error::Error>> {
+    use esp_scanner_base::results::{annotations, Annotation, AnnotationSet, ScanResult};
+
+    let json = std::fs::read_to_string(&args.result)
+        .map_err(|e| format!("Failed to read {}: {}", args.result.display(), e))?;
+    let scan_result = ScanResult::from_json(&json)?;
+
+    let sidecar = args
+        .annotations
+        .clone()
+        .unwrap_or_else(|| annotations::sidecar_path(&args.result));
+    let mut annotation_set = AnnotationSet::load_or_default(&sidecar)?;
+
+    if let Some(criterion) = &args.criterion {
+        if args.remove {
+            if annotation_set.remove(criterion).is_none() {
+                eprintln!("No annotation recorded for {}", criterion);
+            }
+        } else if let Some(disposition) = args.disposition {
+            let reviewer = args
+                .reviewer
+                .clone()
+                .unwrap_or_else(|| std::env::var("USER").unwrap_or_else(|_| "unknown".to_string()));
+            let mut annotation = Annotation::new(disposition, reviewer);
+            annotation.comment = args.comment.clone();
+            annotation_set.insert(criterion.clone(), annotation);
+        }
+        annotation_set.save(&sidecar)?;
+        println!("[OK] Annotations saved to: {}", sidecar.display());
+    }
+
+    let annotated = scan_result.apply_annotations(&annotation_set);
+    print!("{}", annotated.to_markdown(args.exclude_false_positives));
+    for id in &annotated.stale {
+        eprintln!(
+            "warning: annotation for {} matches no finding in this result",
+            id
+        );
+    }
+
+    Ok(())
+}
+
+/// Print the per-field evidence recorded in a saved scan result
+fn explain(args: &ExplainArgs) -> Result<(), Box<dyn std::error::Error>> {
+    use esp_scanner_base::results::ScanResult;
+
+    let json = std::fs::read_to_string(&args.result)
+        .map_err(|e| format!("Failed to read {}: {}", args.result.display(), e))?;
+    let scan_result = ScanResult::from_json(&json)?;
+    if scan_result.evidence.is_empty() {
+        return Err(format!(
+            "{} has no field evidence; rerun the scan with --field-evidence",
+            args.result.display()
+        )
+        .into());
+    }
+
+    let selected: Vec<_> = scan_result
+        .evidence
+        .iter()
+        .filter(|e| match &args.criterion {
+            Some(id) => &e.criterion_id == id,
+            None => true,
+        })
+        .collect();
+    if selected.is_empty() {
+        return Err(format!(
+            "no criterion {} in {}",
+            args.criterion.as_deref().unwrap_or_default(),
+            args.result.display()
+        )
+        .into());
+    }
+
+    for evidence in selected {
+        println!("{}", evidence.to_table());
+    }
+    Ok(())
+}
+
+/// Capability manifest of this scanner build
+fn capability_manifest() -> Result<CapabilityManifest, Box<dyn std::error::Error>> {
+    Ok(scanner_registry()?.capability_manifest(env!("CARGO_PKG_VERSION")))
+}
+
+fn scanner_registry() -> Result<CtnStrategyRegistry, Box<dyn std::error::Error>> {
+    Ok(esp_scanner_sdk::create_scanner_registry()
+        .map_err(|e| format!("Registry creation failed: {}", e))?)
+}
+
+fn load_scan_result(
+    path: &Path,
+) -> Result<esp_scanner_base::results::ScanResult, Box<dyn std::error::Error>> {
+    let json = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    Ok(esp_scanner_base::results::ScanResult::from_json(&json)?)
+}
+
+/// Compare a scan result against an expected one, exiting with status 1 when
+/// they differ semantically
+fn diff(args: &DiffArgs) -> Result<(), Box<dyn std::error::Error>> {
+    if args.criteria {
+        return diff_criteria(args);
+    }
+    let differences = diff_results(args)?;
+    if differences.is_empty() {
+        println!(
+            "{} and {} are equivalent",
+            args.expected.display(),
+            args.actual.display()
+        );
+        return Ok(());
+    }
+    for difference in &differences {
+        println!("{}", difference);
+    }
+    std::process::exit(1);
+}
+
+/// Criterion changes from the first result of `diff` to the second, exiting
+/// with status 1 when a criterion regressed
+fn diff_criteria(args: &DiffArgs) -> Result<(), Box<dyn std::error::Error>> {
+    let previous = load_scan_result(&args.expected)?;
+    let current = load_scan_result(&args.actual)?;
+    let changes = current.diff(&previous);
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&changes)?);
+    } else {
+        print!("{}", changes);
+    }
+    if changes.has_regressions() {
         std::process::exit(1);
     }
+    Ok(())
+}
+
+/// Semantic differences between the two results of `diff`, refusing results
+/// scanned from different policies unless forced
+fn diff_results(args: &DiffArgs) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let expected = load_scan_result(&args.expected)?;
+    let actual = load_scan_result(&args.actual)?;
+    if let Some(mismatch) = expected.policy_mismatch(&actual) {
+        if !args.force {
+            return Err(format!("{}; pass --force to compare anyway", mismatch).into());
+        }
+        eprintln!("warning: {}", mismatch);
+    }
+    Ok(expected.semantic_differences(&actual, args.tolerance))
+}
 
-    logging::print_cargo_style_summary();
+/// Check a saved result against a policy, exiting with status 1 when it
+/// does not report on that policy
+fn verify_result(args: &VerifyResultArgs) -> Result<(), Box<dyn std::error::Error>> {
+    let issues = provenance_issues(args, chrono::Utc::now())?;
+    let palette = Palette::current();
+    for issue in &issues {
+        let level = if issue.is_error() { "error" } else { "warning" };
+        eprintln!(
+            "{}: {}: {}",
+            palette.status(level),
+            args.result.display(),
+            issue
+        );
+    }
+    if issues.iter().any(|issue| issue.is_error()) {
+        std::process::exit(1);
+    }
+    println!(
+        "{}: scanned from {}",
+        args.result.display(),
+        args.policy.display()
+    );
     Ok(())
 }
 
-fn print_usage(program_name: &str) {
-    eprintln!("Usage: {} <file.esp|directory>", program_name);
-    eprintln!("       {} --help", program_name);
+/// What `verify-result` finds wrong with the result at `now`
+fn provenance_issues(
+    args: &VerifyResultArgs,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Result<Vec<esp_scanner_base::results::provenance::ProvenanceIssue>, Box<dyn std::error::Error>>
+{
+    let scan_result = load_scan_result(&args.result)?;
+    let ast = load_policy_ast(&args.policy)?;
+    let policy = policy_provenance(&args.policy, &ast)?;
+    Ok(scan_result.verify_provenance(&policy, args.max_age, now))
 }
 
-fn print_help(program_name: &str) {
-    println!("ESP Scanner v{}", env!("CARGO_PKG_VERSION"));
-    println!("Compliance scanning for ESP (Endpoint State Policy) files\n");
-    println!("USAGE:");
-    println!("    {} <file.esp>       Scan single ESP file", program_name);
+fn capabilities(json: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let registry = scanner_registry()?;
+    let manifest = registry.capability_manifest(env!("CARGO_PKG_VERSION"));
+    if json {
+        println!("{}", manifest.to_json()?);
+        return Ok(());
+    }
+    println!("scanner {}", manifest.scanner_version);
+    println!("  AST version:           {}", manifest.ast_version);
     println!(
-        "    {} <directory>      Scan all ESP files in directory",
-        program_name
+        "  Result schema version: {}",
+        manifest.result_schema_version
     );
+    let list = |items: &std::collections::BTreeSet<String>| {
+        items.iter().cloned().collect::<Vec<_>>().join(", ")
+    };
+    println!("  Criterion types:    {}", list(&manifest.criterion_types));
+    println!("  Operations:         {}", list(&manifest.operations));
     println!(
-        "    {} --help           Show this help message\n",
-        program_name
+        "  Runtime operations: {}",
+        list(&manifest.runtime_operations)
     );
+    println!("  Data types:         {}", list(&manifest.data_types));
+    print!("{}", format_behaviors(&registry));
+    Ok(())
+}
 
-    println!("EXAMPLES:");
-    println!("    {} policy.esp", program_name);
-    println!("    {} /etc/esp/policies/", program_name);
-}
-
-/// Convert PipelineResult AST to scanner types
-fn convert_ast_to_scanner_types(
-    pipeline_result: &pipeline::PipelineResult,
-) -> Result<
-    (
-        Vec<VariableDeclaration>,
-        Vec<StateDeclaration>,
-        Vec<ObjectDeclaration>,
-        Vec<RuntimeOperation>,
-        Vec<SetOperation>,
-        CriteriaRoot,
-        MetaDataBlock,
-    ),
-    Box<dyn std::error::Error>,
-> {
-    let ast = &pipeline_result.ast;
-
-    // Metadata: field.name not field.key
-    let metadata = if let Some(meta) = &ast.metadata {
-        let mut fields = std::collections::HashMap::new();
-        for field in &meta.fields {
-            fields.insert(field.name.clone(), field.value.clone());
+/// BEHAVIOR usage for every criterion type that declares behaviors
+fn format_behaviors(registry: &CtnStrategyRegistry) -> String {
+    let mut out = String::new();
+    let mut ctn_types = registry.list_ctn_types();
+    ctn_types.sort();
+    for ctn_type in ctn_types {
+        let Ok(contract) = registry.get_ctn_contract(&ctn_type) else {
+            continue;
+        };
+        if contract.supported_behaviors.is_empty() {
+            continue;
         }
-        MetaDataBlock { fields }
-    } else {
-        MetaDataBlock::default()
+        if out.is_empty() {
+            out.push_str("  Behaviors:\n");
+        }
+        out.push_str(&format!("    {}:\n", ctn_type));
+        for behavior in &contract.supported_behaviors {
+            out.push_str(&format!(
+                "      {:<40} {}\n",
+                behavior.usage(),
+                behavior.description
+            ));
+        }
+    }
+    out
+}
+
+/// Report policy constructs the target scanner cannot execute
+fn check(args: &CheckArgs) -> Result<(), Box<dyn std::error::Error>> {
+    let target = match &args.target_capabilities {
+        Some(path) => CapabilityManifest::load(path)?,
+        None => capability_manifest()?,
     };
+    let ast = load_policy_ast(&args.policy)?;
+    let found = compatibility::check(&ast, &target);
+    let level = Palette::current().status(if args.deny_incompatible {
+        "error"
+    } else {
+        "warning"
+    });
+    for incompatibility in &found {
+        eprintln!("{}: {}: {}", level, args.policy.display(), incompatibility);
+    }
+    if found.is_empty() {
+        println!(
+            "{}: compatible with scanner {}",
+            args.policy.display(),
+            target.scanner_version
+        );
+    } else if args.deny_incompatible {
+        std::process::exit(1);
+    }
+    Ok(())
+}
 
-    // Variables
-    let variables: Vec<VariableDeclaration> = ast
-        .definition
-        .variables
-        .iter()
-        .map(|v| VariableDeclaration {
-            name: v.name.clone(),
-            data_type: v.data_type,
-            initial_value: v.initial_value.clone(),
-        })
-        .collect();
+/// Compile an ESP file, or load a versioned AST document (`.json`) written by
+/// `esp_compiler --emit-ast`
+/// Whether `file_path` is a compiled AST document rather than ESP source
+fn is_ast_document(file_path: &Path) -> bool {
+    file_path.extension().is_some_and(|ext| ext == "json")
+}
 
-    // States
-    let states: Vec<StateDeclaration> = ast
-        .definition
-        .states
-        .iter()
-        .map(|s| {
-            let fields: Vec<StateField> = s
-                .fields
-                .iter()
-                .map(|f| StateField {
-                    name: f.name.clone(),
-                    data_type: f.data_type,
-                    operation: f.operation,
-                    value: f.value.clone(),
-                    entity_check: f.entity_check.clone(),
-                })
-                .collect();
-
-            StateDeclaration {
-                identifier: s.id.clone(),
-                fields,
-                record_checks: s.record_checks.clone(),
-                is_global: s.is_global,
-            }
-        })
-        .collect();
+/// Exit status for a policy that did not compile
+fn compile_exit_code(error: &pipeline::PipelineError) -> i32 {
+    match error {
+        pipeline::PipelineError::FileProcessing(_) => exit_code::UNREADABLE_POLICY,
+        pipeline::PipelineError::Pipeline { .. } => exit_code::SCAN_FAILED,
+        _ => exit_code::INVALID_POLICY,
+    }
+}
 
-    // Objects
-    let objects: Vec<ObjectDeclaration> = ast
-        .definition
-        .objects
-        .iter()
-        .map(|o| ObjectDeclaration {
-            identifier: o.id.clone(),
-            elements: o.elements.clone(),
-            is_global: o.is_global,
-        })
-        .collect();
+fn load_policy_ast(
+    file_path: &Path,
+) -> Result<esp_compiler::grammar::ast::EspFile, Box<dyn std::error::Error>> {
+    load_policy(file_path).map(|(ast, _)| ast)
+}
 
-    // Runtime operations
-    let runtime_operations: Vec<RuntimeOperation> = ast
-        .definition
-        .runtime_operations
-        .iter()
-        .map(|r| RuntimeOperation {
-            target_variable: r.target_variable.clone(),
-            operation_type: r.operation_type,
-            parameters: r.parameters.clone(),
-        })
-        .collect();
+/// A policy's AST and the files it IMPORTs (none for an AST document)
+fn load_policy(
+    file_path: &Path,
+) -> Result<(esp_compiler::grammar::ast::EspFile, Vec<PathBuf>), Box<dyn std::error::Error>> {
+    if is_ast_document(file_path) {
+        let json = std::fs::read_to_string(file_path)?;
+        let ast = esp_scanner_base::resolution::parse_ast_document(&json)
+            .map_err(|e| format!("Cannot load AST document '{}': {}", file_path.display(), e))?;
+        return Ok((ast, Vec::new()));
+    }
+
+    let pipeline_result = pipeline::process_file(&file_path.display().to_string())
+        .map_err(|e| format!("Compilation failed: {}", e))?;
+    let imports = imported_paths(&pipeline_result);
+    Ok((pipeline_result.ast, imports))
+}
 
-    // Sets: field is set_operations not sets!
-    let sets: Vec<SetOperation> = ast
-        .definition
-        .set_operations
+fn imported_paths(result: &pipeline::PipelineResult) -> Vec<PathBuf> {
+    result
+        .imported_files
         .iter()
-        .map(|s| SetOperation {
-            set_id: s.set_id.clone(),
-            operation: s.operation,
-            operands: s.operands.clone(),
-            filter: s.filter.clone(),
-        })
-        .collect();
+        .map(|file| file.path.clone())
+        .collect()
+}
 
-    // Build CriteriaRoot tree structure instead of flattening
-    let mut node_id_counter = 1;
-    let criteria_root =
-        build_criteria_root_from_ast(&ast.definition.criteria, &mut node_id_counter)?;
-
-    Ok((
-        variables,
-        states,
-        objects,
-        runtime_operations,
-        sets,
-        criteria_root,
-        metadata,
-    ))
-}
-
-/// Build CriteriaRoot tree structure from compiler AST
-/// This preserves the hierarchical CRI/CTN structure
-fn build_criteria_root_from_ast(
-    criteria_nodes: &[esp_compiler::grammar::ast::nodes::CriteriaNode],
-    node_id_counter: &mut usize,
-) -> Result<CriteriaRoot, Box<dyn std::error::Error>> {
-    let mut trees = Vec::new();
-
-    // Convert each CRI block to a CriteriaTree
-    for cri_node in criteria_nodes {
-        let tree = convert_criteria_node_to_tree(cri_node, node_id_counter)?;
-        trees.push(tree);
-    }
-
-    Ok(CriteriaRoot {
-        trees,
-        root_logical_op: LogicalOp::And, // Default: combine top-level CRIs with AND
-    })
-}
-
-/// Convert a compiler CriteriaNode to scanner CriteriaTree
-fn convert_criteria_node_to_tree(
-    cri_node: &esp_compiler::grammar::ast::nodes::CriteriaNode,
-    node_id_counter: &mut usize,
-) -> Result<CriteriaTree, Box<dyn std::error::Error>> {
-    use esp_compiler::grammar::ast::nodes::CriteriaContent;
-
-    let mut children = Vec::new();
-
-    // Process all content items
-    for content in &cri_node.content {
-        match content {
-            CriteriaContent::Criterion(ctn_node) => {
-                // Leaf node: CTN
-                let node_id = *node_id_counter;
-                *node_id_counter += 1;
-
-                let mut declaration = convert_ctn_to_declaration(ctn_node)?;
-                // Assign the node_id to the declaration
-                declaration.ctn_node_id = Some(node_id);
-
-                children.push(CriteriaTree::Criterion {
-                    declaration,
-                    node_id,
-                });
-            }
-            CriteriaContent::Criteria(nested_cri) => {
-                // Recursive: nested CRI block
-                let nested_tree = convert_criteria_node_to_tree(nested_cri, node_id_counter)?;
-                children.push(nested_tree);
-            }
+/// Hashes of a policy file and the AST compiled from it, for the result
+fn policy_provenance(
+    file_path: &Path,
+    ast: &esp_compiler::grammar::ast::EspFile,
+) -> Result<ScanProvenance, Box<dyn std::error::Error>> {
+    let source = std::fs::read(file_path)
+        .map_err(|e| format!("Failed to read {}: {}", file_path.display(), e))?;
+    Ok(ScanProvenance::for_policy(&source, ast))
+}
+
+/// The resolved policy cached for `source` under --cache-dir; an unusable
+/// entry is warned about and treated as a miss, so the policy is compiled
+/// again and the entry replaced
+fn load_cached_policy(cache: &PolicyCache, source: &[u8]) -> Option<CachedPolicy> {
+    match cache.load(source) {
+        Ok(cached) => cached,
+        Err(e) => {
+            log_warning!(
+                "Ignoring unusable policy cache entry",
+                "error" => e.to_string()
+            );
+            None
         }
     }
+}
 
-    // If this CRI has only one child and it's already a Block or Criterion, unwrap it
-    if children.len() == 1 {
-        return Ok(children.into_iter().next().unwrap());
+/// Save a freshly resolved policy under --cache-dir; failing to is only
+/// worth a warning, since the scan itself is unaffected
+fn store_cached_policy(
+    cache: &PolicyCache,
+    source: &[u8],
+    imports: &[PathBuf],
+    provenance: &ScanProvenance,
+    context: &ExecutionContext,
+) {
+    if let Err(e) = cache.store(source, imports, provenance, context) {
+        log_warning!(
+            "Failed to cache execution context",
+            "directory" => cache.dir().display().to_string(),
+            "error" => e.to_string()
+        );
     }
+}
 
-    // Create Block node with proper logical operator
-    let logical_op = match cri_node.logical_op {
-        esp_compiler::grammar::ast::nodes::LogicalOp::And => LogicalOp::And,
-        esp_compiler::grammar::ast::nodes::LogicalOp::Or => LogicalOp::Or,
+/// Check a policy, or every policy in a directory, without scanning
+/// (`--validate-only`)
+fn validate_only(input: &Path, options: &ScanOptions) -> Result<(), Box<dyn std::error::Error>> {
+    let files = if input.is_dir() {
+        discover_esp_files(input, options)?
+    } else {
+        vec![input.to_path_buf()]
     };
+    let registry = esp_scanner_sdk::create_scanner_registry_with_config(&options.scanner_config()?)
+        .map_err(|e| format!("Registry creation failed: {}", e))?;
+
+    let level = Palette::current().status("error");
+    let mut invalid = 0;
+    for file in &files {
+        match validate_policy(file, &registry) {
+            Ok(problems) if problems.is_empty() => println!("{}: valid", file.display()),
+            Ok(problems) => {
+                invalid += 1;
+                for problem in problems {
+                    eprintln!("{}: {}: {}", level, file.display(), problem);
+                }
+            }
+            Err(e) => {
+                invalid += 1;
+                eprintln!("{}: {}: {}", level, file.display(), e);
+            }
+        }
+    }
 
-    Ok(CriteriaTree::Block {
-        logical_op,
-        negate: cri_node.negate,
-        children,
-    })
+    if invalid > 0 {
+        std::process::exit(exit_code::SCAN_FAILED);
+    }
+    Ok(())
 }
 
-/// Convert compiler CriterionNode to scanner CriterionDeclaration
-fn convert_ctn_to_declaration(
-    ctn_node: &esp_compiler::grammar::ast::nodes::CriterionNode,
-) -> Result<CriterionDeclaration, Box<dyn std::error::Error>> {
-    // Convert local states
-    let local_states: Vec<StateDeclaration> = ctn_node
-        .local_states
-        .iter()
-        .map(|ls| {
-            let fields: Vec<StateField> = ls
-                .fields
-                .iter()
-                .map(|f| StateField {
-                    name: f.name.clone(),
-                    data_type: f.data_type,
-                    operation: f.operation,
-                    value: f.value.clone(),
-                    entity_check: f.entity_check.clone(),
-                })
-                .collect();
-
-            StateDeclaration {
-                identifier: ls.id.clone(),
-                fields,
-                record_checks: ls.record_checks.clone(),
-                is_global: false,
-            }
-        })
-        .collect();
-
-    // Convert local object
-    let local_object = ctn_node.local_object.as_ref().map(|lo| ObjectDeclaration {
-        identifier: lo.id.clone(),
-        elements: lo.elements.clone(),
-        is_global: false,
-    });
+/// Compile and resolve a policy, then check each criterion against the
+/// registry; every problem found, or the error that stopped resolution
+fn validate_policy(
+    file_path: &Path,
+    registry: &CtnStrategyRegistry,
+) -> Result<Vec<CriterionProblem>, Box<dyn std::error::Error>> {
+    let ast = load_policy_ast(file_path)?;
+    let mut resolution_context = resolution_context_from_ast(&ast);
+    let execution_context = ResolutionEngine::new()
+        .resolve_context(&mut resolution_context)
+        .map_err(|e| format!("Resolution failed: {}", e))?;
 
-    Ok(CriterionDeclaration {
-        criterion_type: ctn_node.criterion_type.clone(),
-        test: ctn_node.test.clone(),
-        state_refs: ctn_node.state_refs.clone(),
-        object_refs: ctn_node.object_refs.clone(),
-        local_states,
-        local_object,
-        ctn_node_id: None, // Will be assigned during tree construction
-    })
+    Ok(execution_context
+        .get_all_criteria()
+        .into_iter()
+        .flat_map(|criterion| registry.check_criterion(criterion))
+        .collect())
 }
 
-fn scan_single_file(file_path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+fn scan_single_file(
+    file_path: &Path,
+    options: &ScanOptions,
+) -> Result<(), Box<dyn std::error::Error>> {
     let start = Instant::now();
 
     let file_path_str = file_path.display().to_string();
@@ -312,72 +551,105 @@ fn scan_single_file(file_path: &Path) -> Result<(), Box<dyn std::error::Error>>
 
     log_info!("Scanning ESP file", "path" => &file_path_str);
 
-    // Phase 1: Compile
-    log_info!("Phase 1: Compiling ESP file");
-    let pipeline_result = pipeline::process_file(&file_path_str).map_err(|e| {
-        log_error!(
-            esp_compiler::logging::codes::file_processing::FILE_NOT_FOUND,
-            "ESP compilation failed",
-            "error" => e.to_string()
-        );
-        logging::clear_file_context();
-        format!("Compilation failed: {}", e)
-    })?;
+    let cache = options.policy_cache();
+    let source = std::fs::read(file_path)
+        .map_err(|e| format!("Failed to read {}: {}", file_path.display(), e))?;
+    let (execution_context, provenance) = match cache
+        .as_ref()
+        .and_then(|cache| load_cached_policy(cache, &source))
+    {
+        Some(cached) => {
+            log_info!(
+                "Using cached execution context",
+                "criteria_count" => cached.context.count_criteria()
+            );
+            (cached.context, cached.provenance)
+        }
+        None => {
+            // Phase 1: Compile
+            log_info!("Phase 1: Compiling ESP file");
+            let (ast, imports) = if is_ast_document(file_path) {
+                load_policy(file_path).map_err(|e| {
+                    log_error!(
+                        esp_compiler::logging::codes::file_processing::INVALID_ENCODING,
+                        "AST document could not be loaded",
+                        "error" => e.to_string()
+                    );
+                    logging::clear_file_context();
+                    e
+                })?
+            } else {
+                match pipeline::process_file(&file_path_str) {
+                    Ok(result) => {
+                        let imports = imported_paths(&result);
+                        (result.ast, imports)
+                    }
+                    Err(error) => {
+                        // Same report, byte for byte, as `esp_compiler` prints
+                        pipeline::report_failure(&error);
+                        logging::clear_file_context();
+                        std::process::exit(compile_exit_code(&error));
+                    }
+                }
+            };
 
-    log_success!(
-        esp_compiler::logging::codes::success::FILE_PROCESSING_SUCCESS,
-        "ESP compilation successful"
-    );
+            log_success!(
+                esp_compiler::logging::codes::success::FILE_PROCESSING_SUCCESS,
+                "ESP compilation successful"
+            );
 
-    // Phase 2: Create execution context
-    log_info!("Phase 2: Creating execution context");
-
-    // FIXED: Now receives CriteriaRoot instead of Vec<CriterionDeclaration>
-    let (variables, states, objects, runtime_operations, sets, criteria_root, metadata) =
-        convert_ast_to_scanner_types(&pipeline_result)?;
-
-    // FIXED: Use new constructor that takes CriteriaRoot
-    let mut resolution_context = ResolutionContext::from_ast_with_criteria_root(
-        variables,
-        states,
-        objects,
-        runtime_operations,
-        sets,
-        criteria_root,
-        metadata,
-    );
+            // Phase 2: Create execution context
+            log_info!("Phase 2: Creating execution context");
 
-    let mut resolution_engine = ResolutionEngine::new();
-    let execution_context = resolution_engine
-        .resolve_context(&mut resolution_context)
+            let mut resolution_context = resolution_context_from_ast(&ast);
+
+            let mut resolution_engine = ResolutionEngine::new();
+            let execution_context = resolution_engine
+                .resolve_context(&mut resolution_context)
+                .map_err(|e| {
+                    log_error!(
+                        esp_compiler::logging::codes::system::INTERNAL_ERROR,
+                        "Failed to create execution context",
+                        "error" => e.to_string()
+                    );
+                    logging::clear_file_context();
+                    format!("Resolution failed: {}", e)
+                })?;
+
+            log_success!(
+                esp_compiler::logging::codes::success::SEMANTIC_ANALYSIS_COMPLETE,
+                "Execution context created",
+                "criteria_count" => execution_context.count_criteria()
+            );
+
+            let provenance = ScanProvenance::for_policy(&source, &ast);
+            if let Some(cache) = &cache {
+                store_cached_policy(cache, &source, &imports, &provenance, &execution_context);
+            }
+            (execution_context, provenance)
+        }
+    };
+
+    if options.dump_plan {
+        reportln!("\n=== Execution Plan ===");
+        report!("{}", execution_context.plan);
+        logging::clear_file_context();
+        return Ok(());
+    }
+
+    // Phase 3: Create scanner registry
+    log_info!("Phase 3: Initializing scanner registry");
+    let registry = esp_scanner_sdk::create_scanner_registry_with_config(&options.scanner_config()?)
         .map_err(|e| {
             log_error!(
                 esp_compiler::logging::codes::system::INTERNAL_ERROR,
-                "Failed to create execution context",
+                "Failed to create scanner registry",
                 "error" => e.to_string()
             );
             logging::clear_file_context();
-            format!("Resolution failed: {}", e)
+            format!("Registry creation failed: {}", e)
         })?;
 
-    log_success!(
-        esp_compiler::logging::codes::success::SEMANTIC_ANALYSIS_COMPLETE,
-        "Execution context created",
-        "criteria_count" => execution_context.count_criteria()
-    );
-
-    // Phase 3: Create scanner registry
-    log_info!("Phase 3: Initializing scanner registry");
-    let registry = esp_scanner_sdk::create_scanner_registry().map_err(|e| {
-        log_error!(
-            esp_compiler::logging::codes::system::INTERNAL_ERROR,
-            "Failed to create scanner registry",
-            "error" => e.to_string()
-        );
-        logging::clear_file_context();
-        format!("Registry creation failed: {}", e)
-    })?;
-
     let stats = registry.get_statistics();
     log_info!(
         "Registry initialized",
@@ -385,9 +657,29 @@ fn scan_single_file(file_path: &Path) -> Result<(), Box<dyn std::error::Error>>
         "healthy" => stats.registry_health.is_healthy()
     );
 
+    let mut engine = options
+        .engine(execution_context, Arc::new(registry))
+        .with_provenance(provenance);
+    if options.preflight {
+        print_preflight(&mut engine);
+        logging::clear_file_context();
+        return Ok(());
+    }
+    if options.dry_run {
+        let plan = engine.plan();
+        println!("{}", plan.to_json()?);
+        logging::clear_file_context();
+        if !plan.is_fully_matched() {
+            for entry in &plan.unmatched_criterion_types {
+                eprintln!("No strategy registered for {}", entry);
+            }
+            std::process::exit(exit_code::SCAN_FAILED);
+        }
+        return Ok(());
+    }
+
     // Phase 4: Execute scan
     log_info!("Phase 4: Executing compliance scan");
-    let mut engine = ExecutionEngine::new(execution_context, Arc::new(registry));
     let scan_result = engine.execute().map_err(|e| {
         log_error!(
             esp_compiler::logging::codes::system::INTERNAL_ERROR,
@@ -395,35 +687,13 @@ fn scan_single_file(file_path: &Path) -> Result<(), Box<dyn std::error::Error>>
             "error" => e.to_string()
         );
         logging::clear_file_context();
-        format!("Execution failed: {}", e)
+        execution_failure(&e)
     })?;
 
     let duration = start.elapsed();
 
     // Phase 5: Report
-    let status = if scan_result.results.passed {
-        "COMPLIANT"
-    } else {
-        "NON-COMPLIANT"
-    };
-    println!("\n=== Scan Results ===");
-    println!("Status: {}", status);
-    println!(
-        "Total Criteria: {}",
-        scan_result.results.check.total_criteria
-    );
-    println!("Passed: {}", scan_result.results.check.passed_criteria);
-    println!("Failed: {}", scan_result.results.check.failed_criteria);
-    println!(
-        "Pass Rate: {:.1}%",
-        scan_result.results.check.pass_percentage
-    );
-    println!("Findings: {}", scan_result.results.findings.len());
-    println!("Duration: {:.2}s", duration.as_secs_f64());
-
-    let json = scan_result.to_json()?;
-    std::fs::write("scan_result.json", &json)?;
-    println!("\n[OK] Results saved to: scan_result.json");
+    print_scan_report(&scan_result, file_path, duration, options)?;
 
     if scan_result.results.passed {
         log_success!(
@@ -443,27 +713,332 @@ fn scan_single_file(file_path: &Path) -> Result<(), Box<dyn std::error::Error>>
 
     logging::clear_file_context();
 
-    if !scan_result.results.passed {
-        std::process::exit(1);
+    if options.exits_non_compliant(&scan_result) {
+        std::process::exit(exit_code::NON_COMPLIANT);
     }
 
     Ok(())
 }
 
-fn scan_directory(dir_path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+/// Print the scan summary and save the full result to scan_result.json
+/// (`--format` and `--output` pick another format or destination)
+fn print_scan_report(
+    scan_result: &esp_scanner_base::results::ScanResult,
+    policy: &Path,
+    duration: std::time::Duration,
+    options: &ScanOptions,
+) -> Result<(), Box<dyn std::error::Error>> {
+    report!(
+        "{}",
+        format_scan_summary(
+            scan_result,
+            duration,
+            options.strategy_report,
+            Palette::current()
+        )
+    );
+
+    let policy = policy.display().to_string();
+    let saved = save_results(options, "scan_result", |mut out| {
+        match options.format {
+            OutputFormat::Json => writeln!(out, "{}", scan_result.to_json()?)?,
+            OutputFormat::Yaml => out.write_all(scan_result.to_yaml()?.as_bytes())?,
+            OutputFormat::Csv => {
+                csv::write_findings(scan_result, &mut out, &options.csv_options())?
+            }
+            OutputFormat::Sarif => writeln!(out, "{}", scan_result.to_sarif(&policy)?)?,
+            OutputFormat::Junit => out.write_all(scan_result.to_junit_xml(&policy).as_bytes())?,
+        }
+        Ok(())
+    })?;
+    reportln!("\n[OK] Results saved to: {}", saved);
+    Ok(())
+}
+
+/// Write results to the `--output` destination (`<stem>.<format>` by
+/// default), creating missing directories; returns where they went
+fn save_results(
+    options: &ScanOptions,
+    stem: &str,
+    write: impl FnOnce(&mut dyn Write) -> Result<(), Box<dyn std::error::Error>>,
+) -> Result<String, Box<dyn std::error::Error>> {
+    if options.writes_to_stdout() {
+        let mut out = std::io::stdout().lock();
+        write(&mut out)?;
+        out.flush()?;
+        return Ok("stdout".to_string());
+    }
+
+    let path = options.output_path(stem);
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("cannot create {}: {}", parent.display(), e))?;
+    }
+    let file = std::fs::File::create(&path)
+        .map_err(|e| format!("cannot write {}: {}", path.display(), e))?;
+    let mut out = std::io::BufWriter::new(file);
+    write(&mut out)?;
+    out.flush()?;
+    Ok(path.display().to_string())
+}
+
+/// The `=== Scan Results ===` block, statuses colored by `palette`
+fn format_scan_summary(
+    scan_result: &esp_scanner_base::results::ScanResult,
+    duration: std::time::Duration,
+    strategy_report: bool,
+    palette: Palette,
+) -> String {
+    use std::fmt::Write;
+
+    let check = &scan_result.results.check;
+    let status = if scan_result.results.passed {
+        "COMPLIANT"
+    } else {
+        "NON-COMPLIANT"
+    };
+    let mut out = String::new();
+    let _ = writeln!(out, "\n=== Scan Results ===");
+    let _ = writeln!(out, "Status: {}", palette.status(status));
+    let _ = writeln!(out, "Total Criteria: {}", check.total_criteria);
+    let _ = writeln!(out, "Passed: {}", check.passed_criteria);
+    let _ = writeln!(out, "Failed: {}", check.failed_criteria);
+    if !check.failed_by_severity.is_empty() {
+        let _ = writeln!(
+            out,
+            "Failed by Severity: {}",
+            format_severity_counts(&check.failed_by_severity)
+        );
+    }
+    match check.pass_percentage {
+        Some(percentage) => {
+            let _ = writeln!(out, "Pass Rate: {:.1}%", percentage);
+        }
+        None => {
+            let _ = writeln!(out, "Pass Rate: n/a");
+        }
+    }
+    let _ = writeln!(out, "Findings: {}", scan_result.results.findings.len());
+    if scan_result.empty_policy {
+        let _ = writeln!(
+            out,
+            "{}: policy has no criteria",
+            palette.paint(Style::Warning, "Empty Policy")
+        );
+    }
+    if check.vacuous_pass_criteria > 0 {
+        let _ = writeln!(
+            out,
+            "{}: {} criteria passed without observing anything",
+            palette.paint(Style::Warning, "Vacuous Passes"),
+            check.vacuous_pass_criteria
+        );
+        for finding in scan_result.results.findings.iter().filter(|f| f.vacuous) {
+            let _ = writeln!(
+                out,
+                "  {}: {}",
+                finding.criterion_id.as_deref().unwrap_or("-"),
+                finding.description
+            );
+        }
+    }
+    if check.path_denied_criteria > 0 {
+        let _ = writeln!(out, "Path Denied: {} criteria", check.path_denied_criteria);
+    }
+    if check.not_evaluated_criteria > 0 && !scan_result.degraded {
+        let _ = writeln!(
+            out,
+            "Not Evaluated: {} criteria",
+            check.not_evaluated_criteria
+        );
+    }
+    if check.not_applicable_criteria > 0 {
+        let _ = writeln!(
+            out,
+            "Not Applicable: {} criteria",
+            check.not_applicable_criteria
+        );
+    }
+    if check.waived_criteria > 0 {
+        let _ = writeln!(out, "Waived: {} criteria", check.waived_criteria);
+        for finding in scan_result.results.findings.iter() {
+            if let Some(waiver) = &finding.waiver {
+                let _ = writeln!(
+                    out,
+                    "  {}: {} (until {})",
+                    finding.criterion_id.as_deref().unwrap_or("-"),
+                    waiver.justification,
+                    waiver.expires
+                );
+            }
+        }
+    }
+    for waiver in &scan_result.expired_waivers {
+        let _ = writeln!(
+            out,
+            "{}: {} (expired {})",
+            palette.paint(Style::Warning, "Expired Waiver"),
+            waiver.justification,
+            waiver.expires
+        );
+    }
+    if scan_result.degraded {
+        let _ = writeln!(
+            out,
+            "{}: yes ({} criteria not evaluated)",
+            palette.paint(Style::Warning, "Degraded"),
+            check.not_evaluated_criteria
+        );
+    }
+    for mismatch in &scan_result.consistency {
+        let _ = writeln!(
+            out,
+            "{}: {} (between {} and {})",
+            palette.paint(Style::Warning, "Changed During Scan"),
+            mismatch.path,
+            mismatch.first_criterion,
+            mismatch.second_criterion
+        );
+    }
+    let _ = writeln!(out, "Duration: {:.2}s", duration.as_secs_f64());
+    if !scan_result.controls.is_empty() {
+        let _ = writeln!(out, "\n=== Controls ===");
+        out.push_str(&controls::to_table(&scan_result.controls));
+    }
+    if strategy_report {
+        let _ = writeln!(out, "\n=== Strategy Usage ===");
+        out.push_str(&scan_result.performance.to_table());
+    }
+    out
+}
+
+/// `critical 1, high 2, ...`, most severe first
+fn format_severity_counts(counts: &BTreeMap<PolicySeverity, u32>) -> String {
+    counts
+        .iter()
+        .rev()
+        .map(|(severity, count)| format!("{} {}", severity.as_str(), count))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Evaluate a policy against an osquery export instead of the local host
+fn eval_osquery(args: &EvalOsqueryArgs) -> Result<(), Box<dyn std::error::Error>> {
+    use esp_scanner_sdk::collectors::{OsqueryExport, OsqueryMapping};
+
+    let start = Instant::now();
+    let export = OsqueryExport::load(&args.data)?;
+    let mapping = match &args.mapping {
+        Some(path) => OsqueryMapping::load(path)?,
+        None => OsqueryMapping::default(),
+    };
+    let registry = esp_scanner_sdk::create_osquery_registry(Arc::new(export), Arc::new(mapping))
+        .map_err(|e| format!("Registry creation failed: {}", e))?;
+
+    logging::set_file_context(args.policy.clone(), 1);
+    log_info!("Evaluating policy against osquery export",
+        "policy" => args.policy.display(),
+        "data" => args.data.display()
+    );
+    let options = ScanOptions {
+        now: args.now,
+        ..ScanOptions::default()
+    };
+    let scan_result = scan_file_for_batch(&args.policy, Arc::new(registry), &options);
+    logging::clear_file_context();
+    let scan_result = scan_result?;
+
+    print_scan_report(&scan_result, &args.policy, start.elapsed(), &options)?;
+    if options.exits_non_compliant(&scan_result) {
+        std::process::exit(exit_code::NON_COMPLIANT);
+    }
+    Ok(())
+}
+
+/// Message for a scan that failed to execute, with a hint where one applies
+fn execution_failure(error: &ExecutionError) -> String {
+    match error {
+        ExecutionError::UnknownCriterionTypes { .. } => format!(
+            "Execution failed: {} (pass --allow-unknown-criteria to report them per criterion)",
+            error
+        ),
+        ExecutionError::EmptyPolicy => format!(
+            "Execution failed: {} (pass --empty-policy pass or fail to report it instead)",
+            error
+        ),
+        _ => format!("Execution failed: {}", error),
+    }
+}
+
+/// Print criterion types without a strategy, then which criteria need
+/// elevated privileges and whether they are met
+fn print_preflight(engine: &mut esp_scanner_base::execution::ExecutionEngine) {
+    let unknown = engine.unknown_criteria();
+    if !unknown.is_empty() {
+        reportln!("\n=== Unknown Criterion Types ===");
+        let palette = Palette::current();
+        for entry in &unknown {
+            reportln!("  {} {}", palette.status("✗"), entry);
+        }
+        reportln!("The scan refuses to start unless --allow-unknown-criteria is given");
+    }
+
+    let privileges = engine.privileges();
+    let checks = engine.privilege_checks();
+
+    reportln!("\n=== Privilege Preflight ===");
+    reportln!(
+        "Running as: {}",
+        if privileges.is_root() {
+            "root"
+        } else {
+            "unprivileged"
+        }
+    );
+
+    if checks.is_empty() {
+        reportln!("No criteria require elevated privileges");
+        return;
+    }
+
+    let palette = Palette::current();
+    for check in &checks {
+        reportln!(
+            "  {} CTN #{} {} requires {}",
+            palette.status(if check.satisfied { "✓" } else { "✗" }),
+            check.ctn_node_id,
+            check.criterion_type,
+            check.required
+        );
+    }
+
+    let unmet = checks.iter().filter(|c| !c.satisfied).count();
+    if unmet > 0 {
+        reportln!("{} criteria need elevation", unmet);
+    }
+}
+
+fn scan_directory(
+    dir_path: &Path,
+    options: &ScanOptions,
+) -> Result<(), Box<dyn std::error::Error>> {
     let start = Instant::now();
     log_info!("Starting batch directory scan", "path" => dir_path.display().to_string());
 
-    let esp_files = discover_esp_files(dir_path)?;
+    let esp_files = discover_esp_files(dir_path, options)?;
     if esp_files.is_empty() {
-        println!("No ESP files found in directory: {}", dir_path.display());
+        reportln!("No ESP files found in directory: {}", dir_path.display());
         return Ok(());
     }
 
+    let options = &options.for_batch();
     log_info!("Discovered ESP files", "count" => esp_files.len(), "directory" => dir_path.display().to_string());
-    println!("Scanning {} ESP files...", esp_files.len());
+    reportln!("Scanning {} ESP files...", esp_files.len());
 
-    let registry = esp_scanner_sdk::create_scanner_registry().map_err(|e| {
+    let registry = esp_scanner_sdk::create_scanner_registry_with_config(
+        &options.scanner_config()?,
+    )
+    .map_err(|e| {
         log_error!(esp_compiler::logging::codes::system::INTERNAL_ERROR, "Failed to create scanner registry", "error" => e.to_string());
         format!("Registry creation failed: {}", e)
     })?;
@@ -473,11 +1048,15 @@ fn scan_directory(dir_path: &Path) -> Result<(), Box<dyn std::error::Error>> {
     let mut failed_scans = 0;
     let mut compliant_scans = 0;
     let mut non_compliant_scans = 0;
+    let mut failing_scans = 0;
+    let mut empty_scans = 0;
     let mut all_results = Vec::new();
+    let mut scanned_files = Vec::new();
+    let palette = Palette::current();
 
     for (file_id, esp_file) in esp_files.iter().enumerate() {
         let file_id = file_id + 1;
-        println!(
+        reportln!(
             "\n[{}/{}] Scanning: {}",
             file_id,
             esp_files.len(),
@@ -485,27 +1064,48 @@ fn scan_directory(dir_path: &Path) -> Result<(), Box<dyn std::error::Error>> {
         );
         logging::set_file_context(esp_file.clone(), file_id);
 
-        match scan_file_for_batch(esp_file, registry.clone()) {
+        match scan_file_for_batch(esp_file, registry.clone(), options) {
             Ok(scan_result) => {
                 successful_scans += 1;
-                if scan_result.results.passed {
+                if scan_result.empty_policy && options.empty_policy_mode() == EmptyPolicyMode::Warn
+                {
+                    empty_scans += 1;
+                    reportln!(
+                        "  {} {}: policy has no criteria",
+                        palette.paint(Style::Warning, "!"),
+                        palette.paint(Style::Warning, "EMPTY")
+                    );
+                } else if scan_result.results.passed {
                     compliant_scans += 1;
-                    println!(
-                        "  ✓ COMPLIANT ({} criteria)",
+                    reportln!(
+                        "  {} {} ({} criteria)",
+                        palette.status("✓"),
+                        palette.status("COMPLIANT"),
                         scan_result.results.check.total_criteria
                     );
                 } else {
                     non_compliant_scans += 1;
-                    println!(
-                        "  ✗ NON-COMPLIANT ({} findings)",
+                    if options.exits_non_compliant(&scan_result) {
+                        failing_scans += 1;
+                    }
+                    reportln!(
+                        "  {} {} ({} findings)",
+                        palette.status("✗"),
+                        palette.status("NON-COMPLIANT"),
                         scan_result.results.findings.len()
                     );
                 }
                 all_results.push(scan_result);
+                scanned_files.push(esp_file.clone());
             }
             Err(e) => {
                 failed_scans += 1;
-                println!("  ✗ FAILED: {}", e);
+                reportln!(
+                    "  {} {}: {}",
+                    palette.status("✗"),
+                    palette.status("FAILED"),
+                    e
+                );
                 log_error!(esp_compiler::logging::codes::system::INTERNAL_ERROR, "File scan failed", "file" => esp_file.display().to_string(), "error" => e.to_string());
             }
         }
@@ -515,23 +1115,91 @@ fn scan_directory(dir_path: &Path) -> Result<(), Box<dyn std::error::Error>> {
 
     let duration = start.elapsed();
 
-    println!("\n=== Batch Scan Summary ===");
-    println!("Directory: {}", dir_path.display());
-    println!("Files Scanned: {}", esp_files.len());
-    println!("Successful: {}", successful_scans);
-    println!("Failed: {}", failed_scans);
-    println!("Compliant: {}", compliant_scans);
-    println!("Non-Compliant: {}", non_compliant_scans);
-    println!("Duration: {:.2}s", duration.as_secs_f64());
+    reportln!("\n=== Batch Scan Summary ===");
+    reportln!("Directory: {}", dir_path.display());
+    reportln!("Files Scanned: {}", esp_files.len());
+    reportln!("Successful: {}", successful_scans);
+    reportln!("Failed: {}", failed_scans);
+    reportln!("{}: {}", palette.status("Compliant"), compliant_scans);
+    reportln!(
+        "{}: {}",
+        palette.status("Non-Compliant"),
+        non_compliant_scans
+    );
+    if empty_scans > 0 {
+        reportln!(
+            "{}: {}",
+            palette.paint(Style::Warning, "Empty"),
+            empty_scans
+        );
+    }
+    let mut failed_by_severity = BTreeMap::new();
+    for scan_result in &all_results {
+        for (severity, count) in &scan_result.results.check.failed_by_severity {
+            *failed_by_severity.entry(*severity).or_default() += count;
+        }
+    }
+    if !failed_by_severity.is_empty() {
+        reportln!(
+            "Failed by Severity: {}",
+            format_severity_counts(&failed_by_severity)
+        );
+    }
+    let vacuous: u32 = all_results
+        .iter()
+        .map(|r| r.results.check.vacuous_pass_criteria)
+        .sum();
+    if vacuous > 0 {
+        reportln!(
+            "{}: {} criteria",
+            palette.paint(Style::Warning, "Vacuous Passes"),
+            vacuous
+        );
+    }
+    reportln!("Duration: {:.2}s", duration.as_secs_f64());
+    let control_totals = controls::roll_up(&all_results);
+    if !control_totals.is_empty() {
+        reportln!("\n=== Controls ===");
+        report!("{}", controls::to_table(&control_totals));
+    }
+    if options.strategy_report {
+        let mut usage = esp_scanner_base::results::ScanPerformance::default();
+        for scan_result in &all_results {
+            usage.merge(&scan_result.performance);
+        }
+        reportln!("\n=== Strategy Usage ===");
+        report!("{}", usage.to_table());
+    }
 
-    let json = serde_json::to_string_pretty(&all_results)?;
-    std::fs::write("batch_results.json", &json)?;
-    println!("\n[OK] Results saved to: batch_results.json");
+    let files: Vec<String> = scanned_files
+        .iter()
+        .map(|p| p.display().to_string())
+        .collect();
+    let scans = || files.iter().map(String::as_str).zip(&all_results);
+    let saved = save_results(options, "batch_results", |mut out| {
+        match options.format {
+            OutputFormat::Json => writeln!(out, "{}", serde_json::to_string_pretty(&all_results)?)?,
+            OutputFormat::Yaml => out.write_all(serde_yaml::to_string(&all_results)?.as_bytes())?,
+            OutputFormat::Csv => {
+                csv::write_batch_findings(scans(), &mut out, &options.csv_options())?;
+            }
+            OutputFormat::Sarif => {
+                let log = sarif::batch_log(scans());
+                writeln!(out, "{}", serde_json::to_string_pretty(&log)?)?
+            }
+            OutputFormat::Junit => out.write_all(junit::batch_report(scans()).as_bytes())?,
+        }
+        Ok(())
+    })?;
+    reportln!("\n[OK] Results saved to: {}", saved);
 
     log_success!(esp_compiler::logging::codes::success::FILE_PROCESSING_SUCCESS, "Batch scan completed", "total_files" => esp_files.len(), "successful" => successful_scans, "compliant" => compliant_scans, "duration_ms" => duration.as_millis());
 
-    if failed_scans > 0 || non_compliant_scans > 0 {
-        std::process::exit(1);
+    if failed_scans > 0 {
+        std::process::exit(exit_code::SCAN_FAILED);
+    }
+    if failing_scans > 0 {
+        std::process::exit(exit_code::NON_COMPLIANT);
     }
 
     Ok(())
@@ -540,52 +1208,101 @@ fn scan_directory(dir_path: &Path) -> Result<(), Box<dyn std::error::Error>> {
 fn scan_file_for_batch(
     file_path: &Path,
     registry: Arc<esp_scanner_base::strategies::CtnStrategyRegistry>,
+    options: &ScanOptions,
 ) -> Result<esp_scanner_base::results::ScanResult, Box<dyn std::error::Error>> {
-    let file_path_str = file_path.display().to_string();
-    let pipeline_result =
-        pipeline::process_file(&file_path_str).map_err(|e| format!("Compilation failed: {}", e))?;
-
-    // FIXED: Now receives CriteriaRoot
-    let (variables, states, objects, runtime_operations, sets, criteria_root, metadata) =
-        convert_ast_to_scanner_types(&pipeline_result)?;
-
-    // FIXED: Use new constructor
-    let mut resolution_context = ResolutionContext::from_ast_with_criteria_root(
-        variables,
-        states,
-        objects,
-        runtime_operations,
-        sets,
-        criteria_root,
-        metadata,
-    );
-
-    let mut resolution_engine = ResolutionEngine::new();
-    let execution_context = resolution_engine
-        .resolve_context(&mut resolution_context)
-        .map_err(|e| format!("Resolution failed: {}", e))?;
+    let cache = options.policy_cache();
+    let source = std::fs::read(file_path)
+        .map_err(|e| format!("Failed to read {}: {}", file_path.display(), e))?;
+    let (execution_context, provenance) = match cache
+        .as_ref()
+        .and_then(|cache| load_cached_policy(cache, &source))
+    {
+        Some(cached) => (cached.context, cached.provenance),
+        None => {
+            let (ast, imports) = load_policy(file_path)?;
+
+            let mut resolution_context = resolution_context_from_ast(&ast);
+
+            let mut resolution_engine = ResolutionEngine::new();
+            let execution_context = resolution_engine
+                .resolve_context(&mut resolution_context)
+                .map_err(|e| format!("Resolution failed: {}", e))?;
+
+            let provenance = ScanProvenance::for_policy(&source, &ast);
+            if let Some(cache) = &cache {
+                store_cached_policy(cache, &source, &imports, &provenance, &execution_context);
+            }
+            (execution_context, provenance)
+        }
+    };
 
-    let mut engine = ExecutionEngine::new(execution_context, registry);
-    let scan_result = engine
-        .execute()
-        .map_err(|e| format!("Execution failed: {}", e))?;
+    let mut engine = options
+        .engine(execution_context, registry)
+        .with_provenance(provenance);
+    let scan_result = engine.execute().map_err(|e| execution_failure(&e))?;
 
     Ok(scan_result)
 }
 
-fn discover_esp_files(dir_path: &Path) -> Result<Vec<PathBuf>, Box<dyn std::error::Error>> {
+/// ESP files under `dir_path` in relative-path order, descending into
+/// subdirectories unless --no-recursive and keeping the first --max-files
+fn discover_esp_files(
+    dir_path: &Path,
+    options: &ScanOptions,
+) -> Result<Vec<PathBuf>, Box<dyn std::error::Error>> {
     let mut esp_files = Vec::new();
+    let mut visited = HashSet::new();
+    visit_esp_directory(
+        dir_path,
+        !options.no_recursive,
+        &mut visited,
+        &mut esp_files,
+    )?;
+
+    // Every path shares the `dir_path` prefix, so this orders by relative path
+    esp_files.sort();
+    if let Some(max_files) = options.max_files {
+        if esp_files.len() > max_files {
+            log_warning!(
+                "Reached maximum file limit",
+                "files_found" => esp_files.len(),
+                "limit" => max_files
+            );
+            esp_files.truncate(max_files);
+        }
+    }
+    Ok(esp_files)
+}
+
+/// Collect the ESP files of one directory, skipping hidden subdirectories
+/// and directories already reached through a symlink
+fn visit_esp_directory(
+    dir_path: &Path,
+    recursive: bool,
+    visited: &mut HashSet<PathBuf>,
+    esp_files: &mut Vec<PathBuf>,
+) -> std::io::Result<()> {
+    if !visited.insert(dir_path.canonicalize()?) {
+        log_warning!(
+            "Skipping directory already visited (symlink loop)",
+            "directory" => dir_path.display().to_string()
+        );
+        return Ok(());
+    }
+
     for entry in std::fs::read_dir(dir_path)? {
-        let entry = entry?;
-        let path = entry.path();
-        if path.is_file() {
-            if let Some(extension) = path.extension() {
-                if extension == "esp" {
-                    esp_files.push(path);
-                }
+        let path = entry?.path();
+        if path.is_dir() {
+            let hidden = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with('.'));
+            if recursive && !hidden {
+                visit_esp_directory(&path, recursive, visited, esp_files)?;
             }
+        } else if path.is_file() && path.extension().is_some_and(|ext| ext == "esp") {
+            esp_files.push(path);
         }
     }
-    esp_files.sort();
-    Ok(esp_files)
+    Ok(())
 }
@@ -0,0 +1,827 @@
+//! The built `scanner` binary's exit codes, reports, saved results and
+//! subcommands
+
+mod common;
+
+use common::{run, scan, scan_error, scanner, status, stderr, stdout, write_policy};
+use esp_scanner_base::results::ScanResult;
+use std::path::Path;
+
+const WORLD_WRITABLE_DEF: &str = r#"DEF
+    OBJECT shared
+        path `FIXTURE_DIR/shared.conf`
+    OBJECT_END
+
+    STATE present
+        exists boolean = true
+    STATE_END
+
+    CRI AND
+        CTN file_metadata
+            TEST all all
+            STATE_REF present
+            OBJECT_REF shared
+        CTN_END
+    CRI_END
+DEF_END
+"#;
+
+const MISSING_FILE_DEF: &str = r#"DEF
+    OBJECT missing
+        path `FIXTURE_DIR/missing.conf`
+    OBJECT_END
+
+    STATE present
+        exists boolean = true
+    STATE_END
+
+    CRI AND
+        CTN file_metadata
+            TEST all all
+            STATE_REF present
+            OBJECT_REF missing
+        CTN_END
+    CRI_END
+DEF_END
+"#;
+
+/// Load a result the scanner saved under `dir`
+fn saved_result(dir: &Path, name: &str) -> ScanResult {
+    ScanResult::from_json(&std::fs::read_to_string(dir.join(name)).unwrap()).unwrap()
+}
+
+#[test]
+fn test_empty_policy_modes() {
+    use esp_compiler::grammar::ast::AstDocument;
+
+    let dir = tempfile::tempdir().unwrap();
+    let source = write_policy(dir.path(), WORLD_WRITABLE_DEF);
+    let mut ast = esp_compiler::pipeline::process_file(&source.display().to_string())
+        .unwrap()
+        .ast;
+    ast.definition.criteria.clear();
+    let policy = dir.path().join("policy.ast.json");
+    std::fs::write(&policy, AstDocument::new(ast).to_json().unwrap()).unwrap();
+
+    // A single file refuses by default
+    let err = scan_error(&policy, &[]);
+    assert!(err.contains("--empty-policy"), "{}", err);
+
+    let warned = scan(&policy, &["--empty-policy", "warn"]);
+    assert_eq!(warned.status, 0);
+    assert!(warned.result.empty_policy && warned.result.results.passed);
+    assert!(warned
+        .report
+        .contains("Empty Policy: policy has no criteria\n"));
+
+    let output = run(scanner(dir.path())
+        .args(["--empty-policy", "pass", "--output", "-"])
+        .arg(&policy));
+    assert_eq!(status(&output), 0);
+    let json: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap();
+    assert_eq!(json["empty_policy"], true);
+    assert_eq!(json["results"]["passed"], true);
+    assert!(json["results"]["check"]["pass_percentage"].is_null());
+    assert!(stderr(&output).contains("Pass Rate: n/a"));
+
+    let output = run(scanner(dir.path())
+        .args(["--empty-policy", "fail", "--output", "-"])
+        .arg(&policy));
+    assert_eq!(status(&output), 1);
+    let json: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap();
+    assert_eq!(json["results"]["passed"], false);
+    assert_eq!(json["results"]["check"]["status"], "noncompliant");
+
+    scan_error(&policy, &["--empty-policy", "error"]);
+}
+
+#[test]
+fn test_batch_probes_host_facts_once() {
+    use esp_scanner_base::results::FactsSource;
+
+    let dir = tempfile::tempdir().unwrap();
+    let policies = dir.path().join("policies");
+    std::fs::create_dir(&policies).unwrap();
+    std::fs::write(dir.path().join("shared.conf"), "x\n").unwrap();
+    let policy = write_policy(dir.path(), WORLD_WRITABLE_DEF);
+    for n in 0..3 {
+        std::fs::copy(&policy, policies.join(format!("{}.esp", n))).unwrap();
+    }
+
+    let output = run(scanner(dir.path()).arg(&policies));
+    assert_eq!(status(&output), 0, "{}", stdout(&output));
+    let saved = std::fs::read_to_string(dir.path().join("batch_results.json")).unwrap();
+    let results: Vec<ScanResult> = serde_json::from_str(&saved).unwrap();
+    let sources: Vec<FactsSource> = results
+        .into_iter()
+        .map(|result| {
+            assert!(result.results.passed);
+            result.metadata.host_facts.unwrap().source
+        })
+        .collect();
+    assert_eq!(
+        sources,
+        [FactsSource::Fresh, FactsSource::Cached, FactsSource::Cached]
+    );
+}
+
+#[test]
+fn test_compile_failures_map_to_exit_codes() {
+    let dir = tempfile::tempdir().unwrap();
+    let broken = write_policy(dir.path(), "DEF\n    CRI AND\n    CRI_END\nDEF_END\n");
+    let output = run(scanner(dir.path()).arg(&broken));
+    assert_eq!(status(&output), 4);
+    let report = stderr(&output);
+    assert!(
+        report.contains("Syntax analysis stage failed [E043]:"),
+        "{}",
+        report
+    );
+
+    let output = run(scanner(dir.path()).arg("missing.esp"));
+    assert_eq!(status(&output), 3);
+}
+
+const UNKNOWN_TYPE_DEF: &str = r#"DEF
+    OBJECT sshd_config
+        path `FIXTURE_DIR/sshd_config`
+    OBJECT_END
+
+    OBJECT directory
+        base_dn `dc=example,dc=com`
+    OBJECT_END
+
+    STATE present
+        exists boolean = true
+    STATE_END
+
+    STATE bound
+        bound boolean = true
+    STATE_END
+
+    CRI AND
+        CTN file_metadata
+            TEST all all
+            STATE_REF present
+            OBJECT_REF sshd_config
+        CTN_END
+        CTN ldap_bind
+            TEST all all
+            STATE_REF bound
+            OBJECT_REF directory
+        CTN_END
+    CRI_END
+DEF_END
+"#;
+
+const CONTRACT_PROBLEMS_DEF: &str = r#"DEF
+    OBJECT shared
+        path `FIXTURE_DIR/shared.conf`
+        behavior archive_membr
+    OBJECT_END
+
+    STATE owned
+        file_owner string = `root`
+    STATE_END
+
+    STATE present
+        exists boolean = true
+    STATE_END
+
+    CRI AND
+        CTN file_metadata
+            TEST all all
+            STATE_REF owned
+            OBJECT_REF shared
+        CTN_END
+        CTN file_metdata
+            TEST all all
+            STATE_REF present
+            OBJECT_REF shared
+        CTN_END
+    CRI_END
+DEF_END
+"#;
+
+#[test]
+fn test_validate_only_reports_every_problem_without_collecting() {
+    let dir = tempfile::tempdir().unwrap();
+    let validate = |definition: &str| {
+        let policy = write_policy(dir.path(), definition);
+        let output = run(scanner(dir.path()).arg("--validate-only").arg(&policy));
+        let prefix = format!("error: {}: ", policy.display());
+        let problems: Vec<String> = stderr(&output)
+            .lines()
+            .filter_map(|line| line.strip_prefix(&prefix))
+            .map(str::to_string)
+            .collect();
+        (status(&output), problems)
+    };
+
+    // shared.conf is never created: nothing is collected
+    assert_eq!(validate(WORLD_WRITABLE_DEF), (0, vec![]));
+
+    assert_eq!(
+        validate(CONTRACT_PROBLEMS_DEF),
+        (
+            2,
+            vec![
+                "CTN file_metadata (#1): State field 'file_owner' has no validation mapping \
+                 (Validation mapping required to compare against collected data)"
+                    .to_string(),
+                "CTN file_metadata (#1): object 'shared': unknown behavior 'archive_membr' \
+                 (did you mean 'archive_member'?)"
+                    .to_string(),
+                "CTN file_metdata (#2): no registered strategy (did you mean 'file_metadata'?)"
+                    .to_string(),
+            ]
+        )
+    );
+
+    let (code, problems) = validate(UNKNOWN_TYPE_DEF);
+    assert_eq!(code, 2);
+    assert_eq!(problems.len(), 1);
+    assert!(
+        problems[0].ends_with("ldap_bind (#2): no registered strategy"),
+        "{}",
+        problems[0]
+    );
+}
+
+#[test]
+fn test_cache_dir_reuses_and_repairs_resolved_policies() {
+    use esp_scanner_base::resolution::PolicyCache;
+
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("shared.conf"), "x\n").unwrap();
+    let policy = write_policy(dir.path(), WORLD_WRITABLE_DEF);
+    let source = std::fs::read(&policy).unwrap();
+    let cache_dir = dir.path().join("cache");
+    let args = ["--cache-dir", cache_dir.to_str().unwrap()];
+    let cache = PolicyCache::new(&cache_dir, env!("CARGO_PKG_VERSION"));
+
+    let fresh = scan(&policy, &args).result;
+    let cached = cache
+        .load(&source)
+        .unwrap()
+        .expect("resolved policy cached");
+    assert_eq!(
+        cached.provenance.artifact_hash,
+        fresh.metadata.provenance.artifact_hash
+    );
+
+    let reused = scan(&policy, &args);
+    assert!(reused.report.contains("Using cached execution context"));
+    assert_eq!(reused.result.metadata.provenance, fresh.metadata.provenance);
+    assert_eq!(reused.result.results.passed, fresh.results.passed);
+
+    // A corrupted entry is replaced by compiling the policy again
+    std::fs::write(cache.path_for(&source), "{ truncated").unwrap();
+    let repaired = scan(&policy, &args).result;
+    assert_eq!(repaired.results.passed, fresh.results.passed);
+    assert!(cache.load(&source).unwrap().is_some());
+}
+
+#[test]
+fn test_capabilities_list_behaviors() {
+    let dir = tempfile::tempdir().unwrap();
+    let output = run(scanner(dir.path()).arg("capabilities"));
+    assert_eq!(status(&output), 0);
+    let listing = stdout(&output);
+    assert!(listing.contains("\n  Behaviors:\n"));
+    assert!(listing.contains("    file_content:\n      recursive_scan [max_depth int = 3]"));
+    assert!(listing.contains("      timeout <int>"));
+}
+
+#[test]
+fn test_capability_manifest_round_trips_and_covers_sdk_policies() {
+    use esp_compiler::compatibility::CapabilityManifest;
+
+    let dir = tempfile::tempdir().unwrap();
+    let output = run(scanner(dir.path()).args(["capabilities", "--json"]));
+    assert_eq!(status(&output), 0);
+    let manifest = CapabilityManifest::from_json(&stdout(&output)).unwrap();
+    assert!(manifest.criterion_types.contains("systemd_service"));
+    assert!(manifest.operations.contains("occurrence_count"));
+    assert_eq!(
+        manifest.result_schema_version,
+        esp_scanner_base::results::RESULT_SCHEMA_VERSION
+    );
+    let reloaded = CapabilityManifest::from_json(&manifest.to_json().unwrap()).unwrap();
+    assert_eq!(reloaded, manifest);
+
+    let caps = dir.path().join("caps.json");
+    std::fs::write(&caps, manifest.to_json().unwrap()).unwrap();
+    let policy = write_policy(dir.path(), WORLD_WRITABLE_DEF);
+    let output = run(scanner(dir.path())
+        .arg("check")
+        .arg(&policy)
+        .arg("--target-capabilities")
+        .arg(&caps)
+        .arg("--deny-incompatible"));
+    assert_eq!(status(&output), 0, "{}", stderr(&output));
+    assert!(stdout(&output).contains(": compatible with scanner "));
+}
+
+#[test]
+fn test_scan_summary_plain_and_colored() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("shared.conf"), "x\n").unwrap();
+    let policy = write_policy(dir.path(), WORLD_WRITABLE_DEF);
+    let summary = "\n=== Scan Results ===\n\
+                   Status: COMPLIANT\n\
+                   Total Criteria: 1\n\
+                   Passed: 1\n\
+                   Failed: 0\n\
+                   Pass Rate: 100.0%\n\
+                   Findings: 0\n\
+                   Duration: ";
+
+    let plain = scan(&policy, &[]).report;
+    assert!(plain.contains(summary), "{}", plain);
+
+    let colored = scan(&policy, &["--color", "always"]).report;
+    assert!(colored.contains("Status: \x1b[32mCOMPLIANT\x1b[0m\n"));
+    assert!(esp_compiler::terminal::strip_styles(&colored).contains(summary));
+}
+
+#[test]
+fn test_fail_on_severity_gates_exit_by_policy_severity() {
+    let dir = tempfile::tempdir().unwrap();
+    let high_only = ["--fail-on-severity", "high"];
+
+    // shared.conf is missing; the META severity is high
+    let policy = write_policy(dir.path(), WORLD_WRITABLE_DEF);
+    assert_eq!(scan(&policy, &high_only).status, 1);
+
+    let low = WORLD_WRITABLE_DEF.replace(
+        "OBJECT_REF shared\n",
+        "OBJECT_REF shared\n            SEVERITY low\n",
+    );
+    let policy = write_policy(dir.path(), &low);
+    let gated = scan(&policy, &high_only);
+    assert_eq!(
+        gated.result.results.findings[0].policy_severity,
+        esp_scanner_base::results::PolicySeverity::Low
+    );
+    assert_eq!(gated.status, 0);
+    assert_eq!(scan(&policy, &[]).status, 1);
+
+    assert!(gated.report.contains("Status: NON-COMPLIANT\n"));
+    assert!(gated.report.contains("Failed by Severity: low 1\n"));
+}
+
+#[test]
+fn test_policy_controls_and_control_map_reach_findings() {
+    let dir = tempfile::tempdir().unwrap();
+    let policy = write_policy(
+        dir.path(),
+        &MISSING_FILE_DEF.replace(
+            "OBJECT_REF missing\n",
+            "OBJECT_REF missing
+            CONTROL CIS `6.1.1` `https://example.test/cis/6.1.1`
+            CONTROL STIG `RHEL-09-232010`\n",
+        ),
+    );
+
+    let declared = scan(&policy, &[]).result;
+    let finding = &declared.results.findings[0];
+    assert_eq!(finding.controls.len(), 2);
+    assert_eq!(
+        finding.controls[0].url.as_deref(),
+        Some("https://example.test/cis/6.1.1")
+    );
+    assert_eq!(declared.controls.len(), 2);
+    assert!(declared.controls.iter().all(|c| !c.is_compliant()));
+
+    let control_map = dir.path().join("controls.json");
+    std::fs::write(
+        &control_map,
+        r#"{ "file_metadata#1": [{ "framework": "STIG", "control_id": "RHEL-09-232015" }] }"#,
+    )
+    .unwrap();
+    let mapped = scan(&policy, &["--control-map", control_map.to_str().unwrap()]);
+    let controls: Vec<String> = mapped.result.results.findings[0]
+        .controls
+        .iter()
+        .map(|c| c.to_string())
+        .collect();
+    assert_eq!(controls, vec!["CIS 6.1.1", "STIG RHEL-09-232015"]);
+    assert!(mapped
+        .report
+        .contains("\n=== Controls ===\nCIS 6.1.1            0/1  non-compliant\n"));
+}
+
+#[test]
+fn test_waived_failure_is_compliant_and_expired_waiver_is_warned() {
+    let dir = tempfile::tempdir().unwrap();
+    let policy = write_policy(dir.path(), MISSING_FILE_DEF);
+    let waivers = dir.path().join("waivers.yaml");
+    std::fs::write(
+        &waivers,
+        r#"
+waivers:
+  - criterion_type: file_metadata
+    expires: 2026-01-31
+    justification: Config moved to drop-in directory
+  - criterion_type: file_metadata
+    expires: 2025-12-31
+    justification: Legacy host
+"#,
+    )
+    .unwrap();
+
+    let waived = scan(
+        &policy,
+        &[
+            "--waivers",
+            waivers.to_str().unwrap(),
+            "--now",
+            "2026-01-15T00:00:00Z",
+        ],
+    );
+    assert_eq!(waived.status, 0);
+    let result = &waived.result;
+    assert!(result.results.passed);
+    assert_eq!(result.results.check.failed_criteria, 0);
+    assert_eq!(result.results.check.waived_criteria, 1);
+    assert!(result.results.findings[0].waiver.is_some());
+
+    assert!(waived.report.contains(
+        "Waived: 1 criteria\n  file_metadata#1: Config moved to drop-in directory (until 2026-01-31)\n"
+    ));
+    assert!(waived
+        .report
+        .contains("Expired Waiver: Legacy host (expired 2025-12-31)\n"));
+}
+
+#[test]
+fn test_rerun_matches_golden_result_semantically() {
+    let dir = tempfile::tempdir().unwrap();
+    let policy = write_policy(
+        dir.path(),
+        &MISSING_FILE_DEF.replace(
+            "    CRI_END",
+            "        CTN file_metadata
+            TEST all all
+            STATE_REF present
+            OBJECT_REF missing
+        CTN_END
+    CRI_END",
+        ),
+    );
+    let save = |name: &str| {
+        let output = run(scanner(dir.path())
+            .args(["--now", "2026-01-01T00:00:00Z", "--output", name])
+            .arg(&policy));
+        assert_eq!(status(&output), 1);
+        saved_result(dir.path(), name)
+    };
+    let diff =
+        |expected: &str, actual: &str| run(scanner(dir.path()).args(["diff", expected, actual]));
+
+    let golden = save("golden.json");
+    let mut rerun = save("rerun.json");
+    rerun.results.findings.reverse();
+    std::fs::write(dir.path().join("rerun.json"), rerun.to_json().unwrap()).unwrap();
+
+    // Finding ids are fresh on every run, so the raw JSON never matches
+    assert_ne!(golden.to_json().unwrap(), rerun.to_json().unwrap());
+    let output = diff("golden.json", "rerun.json");
+    assert_eq!(status(&output), 0);
+    assert!(stdout(&output).contains("golden.json and rerun.json are equivalent"));
+
+    rerun.results.findings.pop();
+    std::fs::write(dir.path().join("rerun.json"), rerun.to_json().unwrap()).unwrap();
+    let output = diff("golden.json", "rerun.json");
+    assert_eq!(status(&output), 1);
+    let differences: Vec<String> = stdout(&output).lines().map(str::to_string).collect();
+    assert_eq!(differences.len(), 1);
+    assert!(differences[0].starts_with("/results/findings: "));
+}
+
+#[test]
+fn test_results_saved_as_yaml_under_missing_directories() {
+    let dir = tempfile::tempdir().unwrap();
+    let policy = write_policy(dir.path(), MISSING_FILE_DEF);
+    let output_path = dir.path().join("results/nightly/scan.yaml");
+
+    let output = run(scanner(dir.path())
+        .args(["--format", "yaml", "--output"])
+        .arg(&output_path)
+        .arg(&policy));
+    assert_eq!(status(&output), 1);
+    assert!(stdout(&output).contains(&format!("[OK] Results saved to: {}", output_path.display())));
+
+    let saved = ScanResult::from_yaml(&std::fs::read_to_string(&output_path).unwrap()).unwrap();
+    assert_eq!(saved.results.check.total_criteria, 1);
+    assert_eq!(saved.results.check.failed_criteria, 1);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_discovery_recurses_in_relative_path_order() {
+    let dir = tempfile::tempdir().unwrap();
+    let policies = dir.path().join("policies");
+    let policy = write_policy(dir.path(), WORLD_WRITABLE_DEF);
+    for file in [
+        "b.esp",
+        "a/z.esp",
+        "a/deeper/c.esp",
+        "a.esp",
+        ".hidden/skipped.esp",
+    ] {
+        let path = policies.join(file);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::copy(&policy, path).unwrap();
+    }
+    std::fs::write(policies.join("notes.txt"), "").unwrap();
+    std::os::unix::fs::symlink(&policies, policies.join("a/loop")).unwrap();
+
+    let discover = |args: &[&str]| -> Vec<String> {
+        let output = run(scanner(dir.path())
+            .arg("--validate-only")
+            .args(args)
+            .arg(&policies));
+        assert_eq!(status(&output), 0, "{}", stderr(&output));
+        stdout(&output)
+            .lines()
+            .filter_map(|line| line.strip_suffix(": valid"))
+            .map(|path| {
+                Path::new(path)
+                    .strip_prefix(&policies)
+                    .unwrap()
+                    .display()
+                    .to_string()
+            })
+            .collect()
+    };
+    assert_eq!(
+        discover(&[]),
+        ["a/deeper/c.esp", "a/z.esp", "a.esp", "b.esp"]
+    );
+    assert_eq!(discover(&["--no-recursive"]), ["a.esp", "b.esp"]);
+    assert_eq!(
+        discover(&["--max-files", "2"]),
+        ["a/deeper/c.esp", "a/z.esp"]
+    );
+}
+
+#[test]
+fn test_verify_result_and_diff_check_the_scanned_policy() {
+    let dir = tempfile::tempdir().unwrap();
+    let policy = write_policy(dir.path(), MISSING_FILE_DEF);
+    let save = |name: &str| {
+        run(scanner(dir.path()).args(["--output", name]).arg(&policy));
+        name.to_string()
+    };
+    let verify = |result: &str| {
+        let output = run(scanner(dir.path())
+            .args(["verify-result", result, "--policy"])
+            .arg(&policy));
+        (status(&output), stderr(&output))
+    };
+    let scanned = save("v1.json");
+    assert_eq!(verify(&scanned), (0, String::new()));
+
+    // A result from 12 days ago is past the default 7 day limit
+    let mut stale = saved_result(dir.path(), &scanned);
+    stale.metadata.timestamp.scan_start -= chrono::Duration::days(12);
+    std::fs::write(dir.path().join("stale.json"), stale.to_json().unwrap()).unwrap();
+    let (code, issues) = verify("stale.json");
+    assert_eq!(code, 0);
+    assert_eq!(
+        issues,
+        "warning: stale.json: scan is 12 days old, older than 7 days\n"
+    );
+
+    // A comment changes the file but not the compiled policy
+    let source = std::fs::read_to_string(&policy).unwrap();
+    std::fs::write(&policy, format!("# reviewed\n{}", source)).unwrap();
+    let (code, issues) = verify(&scanned);
+    assert_eq!(code, 0);
+    assert!(issues.starts_with("warning: v1.json: "), "{}", issues);
+
+    std::fs::write(&policy, source.replace("= true", "= false")).unwrap();
+    let (code, issues) = verify(&scanned);
+    assert_eq!(code, 1);
+    assert!(issues.starts_with("error: v1.json: "), "{}", issues);
+
+    let rescanned = save("v2.json");
+    let output =
+        run(scanner(dir.path()).args(["diff", &scanned, &rescanned, "--tolerance", "0.5"]));
+    assert_ne!(status(&output), 0);
+    let err = stderr(&output);
+    assert!(err.contains("results were scanned from different policies (sha256:"));
+    assert!(err.contains("; pass --force to compare anyway"));
+
+    let output = run(scanner(dir.path()).args([
+        "diff",
+        &scanned,
+        &rescanned,
+        "--tolerance",
+        "0.5",
+        "--force",
+    ]));
+    assert_eq!(status(&output), 1);
+    assert!(!stdout(&output).is_empty());
+}
+
+const FILE_METADATA_DEF: &str = r#"DEF
+    OBJECT present
+        path `FIXTURE_DIR/sshd_config`
+    OBJECT_END
+
+    OBJECT missing
+        path `FIXTURE_DIR/absent`
+    OBJECT_END
+
+    STATE private
+        permissions string = `0600`
+        size int = 11
+    STATE_END
+
+    STATE world_readable
+        permissions string = `0644`
+    STATE_END
+
+    STATE gone
+        exists boolean = false
+    STATE_END
+
+    CRI AND
+        CTN file_metadata
+            TEST all all
+            STATE_REF private
+            OBJECT_REF present
+        CTN_END
+        CTN file_metadata
+            TEST all all
+            STATE_REF world_readable
+            OBJECT_REF present
+        CTN_END
+        CTN file_metadata
+            TEST all all
+            STATE_REF gone
+            OBJECT_REF missing
+        CTN_END
+    CRI_END
+DEF_END
+"#;
+
+const PACKAGE_SERVICE_DEF: &str = r#"DEF
+    OBJECT openssl
+        package_name `openssl`
+    OBJECT_END
+
+    OBJECT telnet_server
+        package_name `telnet-server`
+    OBJECT_END
+
+    OBJECT sshd
+        service_name `sshd`
+    OBJECT_END
+
+    OBJECT ip_forward
+        parameter_name `net.ipv4.ip_forward`
+    OBJECT_END
+
+    OBJECT crypto_policy
+        path `/etc/crypto-policies/config`
+    OBJECT_END
+
+    STATE patched
+        installed boolean = true
+        version string = `3.0.7-27.el9`
+    STATE_END
+
+    STATE removed
+        installed boolean = false
+    STATE_END
+
+    STATE running
+        active boolean = true
+        enabled boolean = true
+    STATE_END
+
+    STATE forwarding_off
+        value string = `0`
+    STATE_END
+
+    STATE fips
+        content string contains `FIPS`
+    STATE_END
+
+    CRI AND
+        CTN rpm_package
+            TEST all all
+            STATE_REF patched
+            OBJECT_REF openssl
+        CTN_END
+        CTN rpm_package
+            TEST all all
+            STATE_REF removed
+            OBJECT_REF telnet_server
+        CTN_END
+        CTN systemd_service
+            TEST all all
+            STATE_REF running
+            OBJECT_REF sshd
+        CTN_END
+        CTN sysctl_parameter
+            TEST all all
+            STATE_REF forwarding_off
+            OBJECT_REF ip_forward
+        CTN_END
+        CTN file_content
+            TEST all all
+            STATE_REF fips
+            OBJECT_REF crypto_policy
+        CTN_END
+    CRI_END
+DEF_END
+"#;
+
+/// Evaluate `policy` with `eval-osquery` against the checked-in osquery
+/// export, its file paths pointed at `dir`
+fn eval_osquery(dir: &Path, policy: &Path) -> ScanResult {
+    let fixture =
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/osquery/snapshot.jsonl");
+    let content = std::fs::read_to_string(fixture)
+        .unwrap()
+        .replace("@FIXTURE_DIR@", &dir.display().to_string());
+    let data = dir.join("snapshot.jsonl");
+    std::fs::write(&data, content).unwrap();
+
+    let output = run(scanner(dir)
+        .arg("eval-osquery")
+        .arg(policy)
+        .arg("--data")
+        .arg(&data));
+    assert!(status(&output) <= 1, "{}", stderr(&output));
+    saved_result(dir, "scan_result.json")
+}
+
+#[cfg(unix)]
+#[test]
+fn test_osquery_export_matches_live_file_metadata_scan() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = tempfile::tempdir().unwrap();
+    let sshd_config = dir.path().join("sshd_config");
+    std::fs::write(&sshd_config, "PermitRoot\n").unwrap();
+    std::fs::set_permissions(&sshd_config, std::fs::Permissions::from_mode(0o600)).unwrap();
+    let policy = write_policy(dir.path(), FILE_METADATA_DEF);
+
+    let live = scan(&policy, &[]).result;
+    let offline = eval_osquery(dir.path(), &policy);
+
+    assert_eq!(live.results.check.passed_criteria, 2);
+    assert_eq!(live.results.check.failed_criteria, 1);
+    for (live_count, offline_count) in [
+        (
+            live.results.check.total_criteria,
+            offline.results.check.total_criteria,
+        ),
+        (
+            live.results.check.passed_criteria,
+            offline.results.check.passed_criteria,
+        ),
+        (
+            live.results.check.failed_criteria,
+            offline.results.check.failed_criteria,
+        ),
+        (
+            live.results.check.error_criteria,
+            offline.results.check.error_criteria,
+        ),
+    ] {
+        assert_eq!(live_count, offline_count);
+    }
+    let finding_ids = |result: &ScanResult| {
+        result
+            .results
+            .findings
+            .iter()
+            .map(|f| f.criterion_id.clone())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(finding_ids(&live), finding_ids(&offline));
+}
+
+#[test]
+fn test_osquery_export_serves_mapped_types_and_skips_unmapped() {
+    let dir = tempfile::tempdir().unwrap();
+    let policy = write_policy(dir.path(), PACKAGE_SERVICE_DEF);
+
+    let result = eval_osquery(dir.path(), &policy);
+
+    let check = &result.results.check;
+    assert_eq!(check.total_criteria, 5);
+    assert_eq!(check.passed_criteria, 4);
+    assert_eq!(check.failed_criteria, 0);
+    assert_eq!(check.not_evaluated_criteria, 1);
+
+    let saved = std::fs::read_to_string(dir.path().join("scan_result.json")).unwrap();
+    assert!(saved.contains("data_unavailable"));
+}
@@ -0,0 +1,90 @@
+//! Helpers for running the built `scanner` binary against policies written
+//! to a temporary directory
+
+use esp_scanner_base::results::ScanResult;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+const TEST_META: &str = "META
+    version `1.0.0`
+    esp_version `1.0`
+    author `test`
+    date `2026-01-01`
+    severity `high`
+    platform `linux`
+    description `scanner end-to-end`
+    control_framework `TEST`
+    control `E2E-1`
+    esp_scan_id `end-to-end`
+    criticality `high`
+    tags `test`
+META_END
+";
+
+/// Write `definition` under the test META as `dir/policy.esp`, pointing
+/// FIXTURE_DIR at `dir`
+pub fn write_policy(dir: &Path, definition: &str) -> PathBuf {
+    let policy = dir.join("policy.esp");
+    let definition = definition.replace("FIXTURE_DIR", &dir.display().to_string());
+    std::fs::write(&policy, format!("{}\n{}", TEST_META, definition)).unwrap();
+    policy
+}
+
+/// The `scanner` binary, run in `dir` so default result files land there
+#[allow(clippy::disallowed_methods)]
+pub fn scanner(dir: &Path) -> Command {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_scanner"));
+    command.current_dir(dir);
+    command
+}
+
+/// Run `command` to completion
+pub fn run(command: &mut Command) -> Output {
+    command.output().expect("scanner binary runs")
+}
+
+/// Exit status of a finished run
+pub fn status(output: &Output) -> i32 {
+    output.status.code().expect("scanner exited normally")
+}
+
+/// What a finished run printed to stdout
+pub fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+/// What a finished run printed to stderr
+pub fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
+
+/// A completed scan: its exit status, the result written to stdout and the
+/// report printed to stderr
+pub struct Scan {
+    pub status: i32,
+    pub result: ScanResult,
+    pub report: String,
+}
+
+/// Scan `policy` with `args`, taking the JSON result from `--output -`
+pub fn scan(policy: &Path, args: &[&str]) -> Scan {
+    let output = run(scanner(policy.parent().unwrap())
+        .args(args)
+        .args(["--output", "-"])
+        .arg(policy));
+    let report = stderr(&output);
+    let result = ScanResult::from_json(&stdout(&output))
+        .unwrap_or_else(|e| panic!("no scan result ({}): {}", e, report));
+    Scan {
+        status: status(&output),
+        result,
+        report,
+    }
+}
+
+/// Scan `policy` with `args` expecting the scan to fail; the error printed
+pub fn scan_error(policy: &Path, args: &[&str]) -> String {
+    let output = run(scanner(policy.parent().unwrap()).args(args).arg(policy));
+    assert_eq!(status(&output), 2, "{}", stdout(&output));
+    stderr(&output)
+}
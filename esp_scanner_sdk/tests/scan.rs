@@ -0,0 +1,946 @@
+//! Policies scanned end to end by the built `scanner` binary, one criterion
+//! type or language feature at a time
+
+mod common;
+
+use common::{scan, scan_error, scanner, write_policy};
+use std::path::Path;
+
+const VALUE_LIST_DEF: &str = r#"DEF
+    OBJECT cipher_file
+        path `FIXTURE_DIR/cipher`
+    OBJECT_END
+
+    STATE allowed_cipher
+        content string equals_any [`aes256-gcm`, `chacha20-poly1305`]
+    STATE_END
+
+    STATE strict_cipher
+        content string equals_any [`aes256-gcm`, `aes128-gcm`]
+    STATE_END
+
+    STATE no_weak_cipher
+        content string not_equals_any [`3des-cbc`, `arcfour`]
+    STATE_END
+
+    CRI AND
+        CTN file_content
+            TEST all all
+            STATE_REF allowed_cipher
+            OBJECT_REF cipher_file
+        CTN_END
+        CTN file_content
+            TEST all all
+            STATE_REF strict_cipher
+            OBJECT_REF cipher_file
+        CTN_END
+        CTN file_content
+            TEST all all
+            STATE_REF no_weak_cipher
+            OBJECT_REF cipher_file
+        CTN_END
+    CRI_END
+DEF_END
+"#;
+
+#[test]
+fn test_equals_any_file_content_end_to_end() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("cipher"), "chacha20-poly1305").unwrap();
+    let policy = write_policy(dir.path(), VALUE_LIST_DEF);
+
+    let scan = scan(&policy, &[]);
+    assert_eq!(scan.status, 1);
+    let check = &scan.result.results.check;
+    assert_eq!(check.total_criteria, 3);
+    assert_eq!(check.passed_criteria, 2);
+    assert_eq!(check.failed_criteria, 1);
+
+    let finding = &scan.result.results.findings[0];
+    assert!(finding
+        .description
+        .contains("actual 'chacha20-poly1305' is not one of [aes256-gcm, aes128-gcm]"));
+}
+
+const STRING_MEASURES_DEF: &str = r#"DEF
+    OBJECT sshd_config
+        path `FIXTURE_DIR/sshd_config`
+    OBJECT_END
+
+    STATE not_empty
+        content string length_greater_than 0
+    STATE_END
+
+    STATE one_root_login_line
+        content string occurrence_count [`PermitRootLogin`, 1]
+    STATE_END
+
+    STATE two_root_login_lines
+        content string occurrence_count [`PermitRootLogin`, 2]
+    STATE_END
+
+    CRI AND
+        CTN file_content
+            TEST all all
+            STATE_REF not_empty
+            OBJECT_REF sshd_config
+        CTN_END
+        CTN file_content
+            TEST all all
+            STATE_REF one_root_login_line
+            OBJECT_REF sshd_config
+        CTN_END
+        CTN file_content
+            TEST all all
+            STATE_REF two_root_login_lines
+            OBJECT_REF sshd_config
+        CTN_END
+    CRI_END
+DEF_END
+"#;
+
+#[test]
+fn test_string_measures_file_content_end_to_end() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("sshd_config"),
+        "PermitRootLogin no\n#PermitRootLogin yes\n",
+    )
+    .unwrap();
+    let policy = write_policy(dir.path(), STRING_MEASURES_DEF);
+
+    let scan = scan(&policy, &[]);
+    let check = &scan.result.results.check;
+    assert_eq!(check.total_criteria, 3);
+    assert_eq!(check.passed_criteria, 2);
+    assert_eq!(check.failed_criteria, 1);
+
+    let finding = &scan.result.results.findings[0];
+    assert!(finding
+        .description
+        .contains("occurrence_count [PermitRootLogin, 1] (length 40"));
+}
+
+const UNCOLLECTED_FIELD_DEF: &str = r#"DEF
+    OBJECT sshd_config
+        path `FIXTURE_DIR/sshd_config`
+    OBJECT_END
+
+    STATE owned_by_root
+        owner string = `root`
+    STATE_END
+
+    CRI AND
+        CTN file_content
+            TEST all all
+            STATE_REF owned_by_root
+            OBJECT_REF sshd_config
+        CTN_END
+    CRI_END
+DEF_END
+"#;
+
+#[test]
+fn test_uncollected_field_is_unknown_not_vacuous_pass() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("sshd_config"), "PermitRootLogin no\n").unwrap();
+    let policy = write_policy(dir.path(), UNCOLLECTED_FIELD_DEF);
+
+    let output = common::run(scanner(dir.path()).args(["--field-evidence"]).arg(&policy));
+    assert_eq!(common::status(&output), 1);
+    let saved = std::fs::read_to_string(dir.path().join("scan_result.json")).unwrap();
+    let result = esp_scanner_base::results::ScanResult::from_json(&saved).unwrap();
+    assert_eq!(result.results.check.failed_criteria, 1);
+    let evidence = &result.evidence[0];
+    assert_eq!(evidence.criterion_id, "file_content#1");
+    let unknown: Vec<_> = evidence.unknown_fields().collect();
+    assert_eq!(unknown.len(), 1);
+    assert_eq!(unknown[0].1.field, "owner");
+
+    let output = common::run(scanner(dir.path()).args([
+        "explain",
+        "scan_result.json",
+        "--criterion",
+        "file_content#1",
+    ]));
+    assert_eq!(common::status(&output), 0);
+    assert!(common::stdout(&output).contains("(not present)"));
+}
+
+const BEHAVIOR_DEF: &str = r#"DEF
+    OBJECT config_dir
+        path `FIXTURE_DIR/conf.d`
+        behavior BEHAVIORS
+    OBJECT_END
+
+    STATE enabled
+        content string contains `enabled`
+    STATE_END
+
+    CRI AND
+        CTN file_content
+            TEST all all
+            STATE_REF enabled
+            OBJECT_REF config_dir
+        CTN_END
+    CRI_END
+DEF_END
+"#;
+
+#[test]
+fn test_behaviors_are_checked_against_the_contract() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(dir.path().join("conf.d/nested")).unwrap();
+    std::fs::write(dir.path().join("conf.d/nested/app.conf"), "enabled\n").unwrap();
+
+    let policy = write_policy(
+        dir.path(),
+        &BEHAVIOR_DEF.replace("BEHAVIORS", "recursive_scan max_depth 2"),
+    );
+    let scan = scan(&policy, &[]);
+    assert_eq!(scan.status, 0);
+    assert_eq!(scan.result.results.check.passed_criteria, 1);
+
+    let policy = write_policy(
+        dir.path(),
+        &BEHAVIOR_DEF.replace("BEHAVIORS", "recursive_scan max_dpeth 2"),
+    );
+    let err = scan_error(&policy, &[]);
+    assert!(
+        err.contains("unknown behavior 'max_dpeth' (did you mean 'max_depth'?)"),
+        "{}",
+        err
+    );
+}
+
+const NUMBER_DEF: &str = r#"DEF
+    OBJECT metrics
+        path `FIXTURE_DIR/metrics.json`
+        BEHAVIORS
+    OBJECT_END
+
+    STATE ratio
+        record record_data
+            field ratio float = 0.3
+        record_end
+    STATE_END
+
+    CRI AND
+        CTN json_record
+            TEST all all
+            STATE_REF ratio
+            OBJECT_REF metrics
+        CTN_END
+    CRI_END
+DEF_END
+"#;
+
+#[test]
+fn test_json_float_epsilon_behavior() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("metrics.json"),
+        r#"{"ratio": 0.30000000000000004}"#,
+    )
+    .unwrap();
+
+    let exact = write_policy(dir.path(), &NUMBER_DEF.replace("BEHAVIORS", ""));
+    assert_eq!(scan(&exact, &[]).result.results.check.failed_criteria, 1);
+
+    let tolerant = write_policy(
+        dir.path(),
+        &NUMBER_DEF.replace("BEHAVIORS", "behavior float_epsilon 0.000001"),
+    );
+    assert_eq!(scan(&tolerant, &[]).result.results.check.passed_criteria, 1);
+}
+
+const LOCAL_RECORD_DEF: &str = r#"DEF
+    VAR min_tls string `MIN_TLS`
+
+    OBJECT app
+        path `FIXTURE_DIR/app.json`
+    OBJECT_END
+
+    STATE tls_policy
+        record record_data
+            field server.tls.min_version string = VAR min_tls
+            field server.ports.* int > 1024 at_least_one
+        record_end
+    STATE_END
+
+    CRI AND
+        CTN json_record
+            TEST all all
+            STATE_REF tls_policy
+            OBJECT_REF app
+        CTN_END
+        CTN json_record
+            TEST all all
+            STATE local_tls_policy
+                record record_data
+                    field server.tls.min_version string = VAR min_tls
+                    field server.ports.* int > 1024 at_least_one
+                record_end
+            STATE_END
+            OBJECT local_app
+                path `FIXTURE_DIR/app.json`
+            OBJECT_END
+        CTN_END
+    CRI_END
+DEF_END
+"#;
+
+#[test]
+fn test_local_state_record_checks_match_global_state() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("app.json"),
+        r#"{"server": {"tls": {"min_version": "1.2"}, "ports": [443, 8443]}}"#,
+    )
+    .unwrap();
+
+    // The CTN-local state carries the same record checks as the global one
+    let matching = write_policy(dir.path(), &LOCAL_RECORD_DEF.replace("MIN_TLS", "1.2"));
+    assert_eq!(scan(&matching, &[]).result.results.check.passed_criteria, 2);
+
+    let differing = write_policy(dir.path(), &LOCAL_RECORD_DEF.replace("MIN_TLS", "1.3"));
+    assert_eq!(
+        scan(&differing, &[]).result.results.check.failed_criteria,
+        2
+    );
+}
+
+#[test]
+fn test_xml_record_attribute_paths_compile_and_scan() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("server.xml"),
+        r#"<Server><Service><Connector port="8443"/><Connector port="8080"/></Service></Server>"#,
+    )
+    .unwrap();
+    let policy = |check: &str| {
+        let definition = format!(
+            r#"DEF
+    OBJECT tomcat
+        path `FIXTURE_DIR/server.xml`
+    OBJECT_END
+
+    STATE connectors
+        record record_data
+            {}
+        record_end
+    STATE_END
+
+    CRI AND
+        CTN xml_record
+            TEST all all
+            STATE_REF connectors
+            OBJECT_REF tomcat
+        CTN_END
+    CRI_END
+DEF_END
+"#,
+            check
+        );
+        write_policy(dir.path(), &definition)
+    };
+
+    let every = policy("field Server.Service.Connector.@port int = 8443 all");
+    assert_eq!(scan(&every, &[]).result.results.check.failed_criteria, 1);
+
+    let first = policy("field Server.Service.Connector.0.@port int = 8443");
+    assert_eq!(scan(&first, &[]).result.results.check.passed_criteria, 1);
+}
+
+const UNKNOWN_TYPE_DEF: &str = r#"DEF
+    OBJECT sshd_config
+        path `FIXTURE_DIR/sshd_config`
+    OBJECT_END
+
+    OBJECT directory
+        base_dn `dc=example,dc=com`
+    OBJECT_END
+
+    STATE present
+        exists boolean = true
+    STATE_END
+
+    STATE bound
+        bound boolean = true
+    STATE_END
+
+    CRI AND
+        CTN file_metadata
+            TEST all all
+            STATE_REF present
+            OBJECT_REF sshd_config
+        CTN_END
+        CTN ldap_bind
+            TEST all all
+            STATE_REF bound
+            OBJECT_REF directory
+        CTN_END
+    CRI_END
+DEF_END
+"#;
+
+#[test]
+fn test_unknown_criterion_type_fails_early_unless_allowed() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("sshd_config"), "PermitRootLogin no\n").unwrap();
+    let policy = write_policy(dir.path(), UNKNOWN_TYPE_DEF);
+
+    let err = scan_error(&policy, &[]);
+    assert!(
+        err.contains("No strategy registered for criterion types: 'ldap_bind' (CTN #"),
+        "{}",
+        err
+    );
+    assert!(err.contains("--allow-unknown-criteria"));
+
+    let scan = scan(&policy, &["--allow-unknown-criteria"]);
+    let check = &scan.result.results.check;
+    assert_eq!(check.passed_criteria, 1);
+    assert_eq!(check.error_criteria, 1);
+    assert_eq!(
+        scan.result.performance.unmatched_criterion_types,
+        vec!["ldap_bind"]
+    );
+}
+
+const CRITERIA_ROOT_DEF: &str = r#"DEF
+    OBJECT sshd_config
+        path `FIXTURE_DIR/SSHD_CONFIG`
+    OBJECT_END
+
+    OBJECT directory
+        base_dn `dc=example,dc=com`
+    OBJECT_END
+
+    STATE present
+        exists boolean = true
+    STATE_END
+
+    STATE bound
+        bound boolean = true
+    STATE_END
+
+    ROOT
+    CRI AND
+        CTN ldap_bind
+            TEST all all
+            STATE_REF bound
+            OBJECT_REF directory
+        CTN_END
+    CRI_END
+    CRI AND
+        CTN file_metadata
+            TEST all all
+            STATE_REF present
+            OBJECT_REF sshd_config
+        CTN_END
+    CRI_END
+DEF_END
+"#;
+
+#[test]
+fn test_criteria_root_combines_top_level_blocks() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("sshd_config"), "PermitRootLogin no\n").unwrap();
+    let passed = |root: &str, sshd_config: &str| {
+        let definition = CRITERIA_ROOT_DEF
+            .replace("ROOT", root)
+            .replace("SSHD_CONFIG", sshd_config);
+        let policy = write_policy(dir.path(), &definition);
+        // The unregistered ldap_bind criterion is an error in every scan
+        let scan = scan(&policy, &["--allow-unknown-criteria"]);
+        assert_eq!(scan.result.results.check.error_criteria, 1);
+        scan.result.results.passed
+    };
+
+    // Top-level blocks are ANDed by default, so the error decides
+    assert!(!passed("", "sshd_config"));
+    // Under OR the passing block is enough, whatever the other's error
+    assert!(passed("CRI_ROOT OR", "sshd_config"));
+    assert!(!passed("CRI_ROOT OR true", "sshd_config"));
+    // With nothing passing, the error leaves OR undecided, negated or not
+    assert!(!passed("CRI_ROOT OR", "missing"));
+    assert!(!passed("CRI_ROOT OR true", "missing"));
+}
+
+const USER_ACCOUNT_DEF: &str = r#"DEF
+    OBJECT accounts
+        username `*`
+        passwd_file `FIXTURE_DIR/passwd`
+        group_file `FIXTURE_DIR/group`
+    OBJECT_END
+
+    STATE unique_non_root
+        uid int > 0
+        duplicate boolean = false
+    STATE_END
+
+    CRI AND
+        CTN user_account
+            TEST all all
+            STATE_REF unique_non_root
+            OBJECT_REF accounts
+        CTN_END
+    CRI_END
+DEF_END
+"#;
+
+#[test]
+fn test_user_account_scan_reads_passwd_and_group_files() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("passwd"),
+        "+::::::
+alice:x:1000:1000::/nonexistent/alice:/bin/bash
+bob:x:1001:1000::/home/bob:/bin/sh
+",
+    )
+    .unwrap();
+    let policy = write_policy(dir.path(), USER_ACCOUNT_DEF);
+
+    // No group file, a compat line and a missing home are all tolerated
+    let scan_result = scan(&policy, &[]).result;
+    assert!(scan_result.results.passed);
+    assert_eq!(scan_result.results.check.passed_criteria, 1);
+
+    std::fs::write(
+        dir.path().join("passwd"),
+        "alice:x:1000:1000::/home/alice:/bin/bash
+alice:x:0:0::/root:/bin/bash
+",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.path().join("group"),
+        "staff:x:1000:alice
+",
+    )
+    .unwrap();
+    let scan_result = scan(&policy, &[]).result;
+    assert!(!scan_result.results.passed);
+    assert_eq!(scan_result.results.check.failed_criteria, 1);
+}
+
+const SSHD_CONFIG_DEF: &str = r#"DEF
+    OBJECT sshd
+        path `FIXTURE_DIR/sshd_config`
+        BEHAVIORS
+    OBJECT_END
+
+    STATE hardened
+        PermitRootLogin string = `no`
+        PasswordAuthentication string = `no`
+        MaxAuthTries int <= 4
+    STATE_END
+
+    CRI AND
+        CTN sshd_config
+            TEST all all
+            STATE_REF hardened
+            OBJECT_REF sshd
+        CTN_END
+    CRI_END
+DEF_END
+"#;
+
+#[test]
+fn test_sshd_config_scan_resolves_includes_and_match_blocks() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir(dir.path().join("sshd_config.d")).unwrap();
+    std::fs::write(
+        dir.path().join("sshd_config.d/10-hardening.conf"),
+        "permitrootlogin No
+MaxAuthTries 3
+",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.path().join("sshd_config"),
+        "Include sshd_config.d/*.conf
+             PermitRootLogin yes
+             PasswordAuthentication no
+             Match User backup
+             	PasswordAuthentication yes
+",
+    )
+    .unwrap();
+
+    let policy = write_policy(
+        dir.path(),
+        &SSHD_CONFIG_DEF.replace(
+            "BEHAVIORS
+",
+            "",
+        ),
+    );
+    let scan_result = scan(&policy, &[]).result;
+    assert!(!scan_result.results.passed);
+    assert_eq!(scan_result.results.check.failed_criteria, 1);
+
+    let global_only = SSHD_CONFIG_DEF.replace("BEHAVIORS", "behavior global_only");
+    let policy = write_policy(dir.path(), &global_only);
+    let scan_result = scan(&policy, &[]).result;
+    assert!(scan_result.results.passed);
+    assert_eq!(scan_result.results.check.passed_criteria, 1);
+}
+
+const WORLD_WRITABLE_DEF: &str = r#"DEF
+    OBJECT shared
+        path `FIXTURE_DIR/shared.conf`
+    OBJECT_END
+
+    STATE present
+        exists boolean = true
+    STATE_END
+
+    CRI AND
+        CTN file_metadata
+            TEST all all
+            STATE_REF present
+            OBJECT_REF shared
+        CTN_END
+    CRI_END
+DEF_END
+"#;
+
+#[cfg(unix)]
+#[test]
+fn test_advisories_do_not_score_and_can_be_filtered() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = tempfile::tempdir().unwrap();
+    let shared = dir.path().join("shared.conf");
+    std::fs::write(&shared, "x\n").unwrap();
+    std::fs::set_permissions(&shared, std::fs::Permissions::from_mode(0o666)).unwrap();
+    let policy = write_policy(dir.path(), WORLD_WRITABLE_DEF);
+
+    let scan_result = scan(&policy, &[]).result;
+    assert!(scan_result.results.passed);
+    assert_eq!(scan_result.results.check.passed_criteria, 1);
+    assert!(scan_result.results.findings.is_empty());
+    assert_eq!(scan_result.advisories.len(), 1);
+    assert_eq!(scan_result.advisories[0].criterion_id, "file_metadata#1");
+    assert!(scan_result.advisories[0].message.contains("0666"));
+
+    let filtered = scan(&policy, &["--no-advisories"]).result;
+    assert!(filtered.results.passed);
+    assert!(filtered.advisories.is_empty());
+}
+
+#[test]
+fn test_absent_file_passes_but_missing_parent_is_vacuous() {
+    let dir = tempfile::tempdir().unwrap();
+    let policy = write_policy(
+        dir.path(),
+        r#"DEF
+    OBJECT absent
+        path `FIXTURE_DIR/absent.conf`
+    OBJECT_END
+
+    OBJECT typo
+        path `FIXTURE_DIR/no-such-dir/absent.conf`
+    OBJECT_END
+
+    STATE gone
+        exists boolean = false
+    STATE_END
+
+    CRI AND
+        CTN file_metadata
+            TEST all all
+            STATE_REF gone
+            OBJECT_REF absent
+        CTN_END
+        CTN file_metadata
+            TEST all all
+            STATE_REF gone
+            OBJECT_REF typo
+        CTN_END
+    CRI_END
+DEF_END
+"#,
+    );
+
+    // The parent exists, so absent.conf is confirmed absent; the typo'd
+    // directory could not be looked in, so that pass is vacuous
+    let lenient = scan(&policy, &[]);
+    let result = &lenient.result;
+    assert_eq!(lenient.status, 0);
+    assert!(result.results.passed);
+    assert_eq!(result.results.check.passed_criteria, 2);
+    assert_eq!(result.results.check.vacuous_pass_criteria, 1);
+    let vacuous: Vec<_> = result
+        .results
+        .findings
+        .iter()
+        .filter(|f| f.vacuous)
+        .collect();
+    assert_eq!(vacuous.len(), 1);
+    assert_eq!(vacuous[0].criterion_id.as_deref(), Some("file_metadata#2"));
+    assert!(vacuous[0]
+        .description
+        .contains("path not found: parent directory"));
+    assert!(lenient.report.contains(
+        "Vacuous Passes: 1 criteria passed without observing anything\n  file_metadata#2: "
+    ));
+
+    let strict = scan(&policy, &["--fail-on-vacuous"]);
+    assert_eq!(strict.status, 1);
+    let check = &strict.result.results.check;
+    assert_eq!(check.passed_criteria, 1);
+    assert_eq!(check.failed_criteria, 1);
+    assert_eq!(check.vacuous_pass_criteria, 1);
+}
+
+const ARCHIVE_DEF: &str = r#"DEF
+    OBJECT layer_sshd
+        path `ARCHIVE_DIR/layer.tar.gz`
+        behavior archive_member `etc/ssh/sshd_config`
+    OBJECT_END
+
+    OBJECT layer_app
+        path `ARCHIVE_DIR/layer.tar.gz`
+        behavior archive_member `./etc/app/config.json`
+    OBJECT_END
+
+    OBJECT initramfs_modprobe
+        path `ARCHIVE_DIR/initramfs.cpio.gz`
+        behavior archive_member `etc/modprobe.d/usb-storage.conf`
+    OBJECT_END
+
+    STATE no_root_login
+        content string contains `PermitRootLogin no`
+    STATE_END
+
+    STATE private
+        permissions string = `0600`
+        owner string = `0`
+    STATE_END
+
+    STATE tls_on
+        record record_data
+            field tls.enabled boolean = true
+        record_end
+    STATE_END
+
+    STATE usb_disabled
+        content string contains `install usb-storage /bin/true`
+    STATE_END
+
+    CRI AND
+        CTN file_content
+            TEST all all
+            STATE_REF no_root_login
+            OBJECT_REF layer_sshd
+        CTN_END
+        CTN file_metadata
+            TEST all all
+            STATE_REF private
+            OBJECT_REF layer_sshd
+        CTN_END
+        CTN json_record
+            TEST all all
+            STATE_REF tls_on
+            OBJECT_REF layer_app
+        CTN_END
+        CTN file_content
+            TEST all all
+            STATE_REF usb_disabled
+            OBJECT_REF initramfs_modprobe
+        CTN_END
+    CRI_END
+DEF_END
+"#;
+
+const ARCHIVE_ABSENT_DEF: &str = r#"DEF
+    OBJECT missing_member
+        path `ARCHIVE_DIR/layer.tar.gz`
+        behavior archive_member `etc/shadow`
+    OBJECT_END
+
+    OBJECT missing_archive
+        path `ARCHIVE_DIR/image.tar.gz`
+        behavior archive_member `etc/shadow`
+    OBJECT_END
+
+    STATE gone
+        exists boolean = false
+    STATE_END
+
+    CRI AND
+        CTN file_metadata
+            TEST all all
+            STATE_REF gone
+            OBJECT_REF missing_member
+        CTN_END
+        CTN file_metadata
+            TEST all all
+            STATE_REF gone
+            OBJECT_REF missing_archive
+        CTN_END
+    CRI_END
+DEF_END
+"#;
+
+#[test]
+fn test_archive_member_content_metadata_and_json_checks() {
+    let dir = tempfile::tempdir().unwrap();
+    let archives = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/archive");
+    let archive_policy = |definition: &str| {
+        write_policy(
+            dir.path(),
+            &definition.replace("ARCHIVE_DIR", &archives.display().to_string()),
+        )
+    };
+
+    let result = scan(&archive_policy(ARCHIVE_DEF), &[]).result;
+    assert!(result.results.passed, "{:?}", result.results.findings);
+    assert_eq!(result.results.check.passed_criteria, 4);
+
+    // A member missing from a readable archive is confirmed absent; a
+    // missing archive leaves nothing observed
+    let result = scan(&archive_policy(ARCHIVE_ABSENT_DEF), &[]).result;
+    assert_eq!(result.results.check.passed_criteria, 2);
+    assert_eq!(result.results.check.vacuous_pass_criteria, 1);
+    let vacuous: Vec<_> = result
+        .results
+        .findings
+        .iter()
+        .filter(|f| f.vacuous)
+        .collect();
+    assert_eq!(vacuous[0].criterion_id.as_deref(), Some("file_metadata#2"));
+    assert!(vacuous[0].description.contains("archive not found"));
+
+    // Reading content, the two are distinct errors
+    let content_of = |path: &str| {
+        archive_policy(&ARCHIVE_DEF.replace(
+            "layer.tar.gz`\n        behavior archive_member `etc/ssh",
+            path,
+        ))
+    };
+    let err = scan_error(
+        &content_of("layer.tar.gz`\n        behavior archive_member `etc/sshd"),
+        &[],
+    );
+    assert!(
+        err.contains("member 'etc/sshd/sshd_config' not found in archive"),
+        "{}",
+        err
+    );
+    let err = scan_error(
+        &content_of("image.tar.gz`\n        behavior archive_member `etc/ssh"),
+        &[],
+    );
+    assert!(err.contains("Object not found: layer_sshd"), "{}", err);
+}
+
+const EXTRACT_DEF: &str = r#"DEF
+    RUN target_path EXTRACT
+        OBJ pointer content
+    RUN_END
+
+    OBJECT pointer
+        path `FIXTURE_DIR/pointer`
+    OBJECT_END
+
+    OBJECT target
+        path VAR target_path
+    OBJECT_END
+
+    STATE greeting
+        content string = `hello`
+    STATE_END
+
+    STATE names_target
+        content string ends `target`
+    STATE_END
+
+    CRI AND
+        CTN file_content
+            TEST all all
+            STATE_REF greeting
+            OBJECT_REF target
+        CTN_END
+        CTN file_content
+            TEST all all
+            STATE_REF names_target
+            OBJECT_REF pointer
+        CTN_END
+    CRI_END
+DEF_END
+"#;
+
+#[test]
+fn test_extract_collects_upstream_object_once_before_dependents() {
+    use esp_scanner_base::types::PhaseKind;
+
+    let dir = tempfile::tempdir().unwrap();
+    let target = dir.path().join("target");
+    std::fs::write(&target, "hello").unwrap();
+    std::fs::write(dir.path().join("pointer"), target.display().to_string()).unwrap();
+    let policy = write_policy(dir.path(), EXTRACT_DEF);
+
+    let result = scan(&policy, &[]).result;
+
+    assert!(result.results.passed);
+    assert_eq!(result.results.check.passed_criteria, 2);
+    assert_eq!(
+        result.plan.phase(PhaseKind::CollectForExtraction),
+        ["pointer"]
+    );
+    assert_eq!(
+        result.plan.phase(PhaseKind::ComputeVariables),
+        ["target_path"]
+    );
+    assert_eq!(result.plan.phase(PhaseKind::Substitute), ["target"]);
+    // pointer is collected once for the EXTRACT and reused by its criterion
+    assert_eq!(
+        result.plan.phase(PhaseKind::CollectForCriteria),
+        ["file_content#1: target", "file_content#2: pointer (shared)"]
+    );
+}
+
+const PACKAGE_DEF: &str = r#"DEF
+    OBJECT openssl
+        package_name `openssl`
+    OBJECT_END
+
+    STATE installed
+        installed boolean = true
+    STATE_END
+
+    CRI AND
+        CTN PACKAGE_TYPE
+            TEST all all
+            STATE_REF installed
+            OBJECT_REF openssl
+        CTN_END
+    CRI_END
+DEF_END
+"#;
+
+#[test]
+fn test_platform_config_picks_package_executor() {
+    let dir = tempfile::tempdir().unwrap();
+    let debian = dir.path().join("debian.toml");
+    std::fs::write(&debian, "platform = \"debian\"\n").unwrap();
+    let matched = |package_type: &str, args: &[&str]| {
+        let policy = write_policy(
+            dir.path(),
+            &PACKAGE_DEF.replace("PACKAGE_TYPE", package_type),
+        );
+        let output = common::run(scanner(dir.path()).arg("--dry-run").args(args).arg(&policy));
+        common::status(&output) == 0
+    };
+
+    let config = ["--config", debian.to_str().unwrap()];
+    assert!(matched("rpm_package", &[]));
+    assert!(!matched("dpkg_package", &[]));
+    assert!(matched("dpkg_package", &config));
+    assert!(!matched("rpm_package", &config));
+}